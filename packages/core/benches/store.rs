@@ -0,0 +1,173 @@
+//! SurrealStore benchmarks at workspace scale
+//!
+//! Run with: `cargo bench -p nodespace-core --bench store`
+//!
+//! Each workspace size (1k, 10k and 100k nodes in the full profile) is
+//! generated with [`SyntheticWorkspace`] from a fixed seed and written once into
+//! an embedded RocksDB store, then measured for:
+//! - `store_create`: creating a node
+//! - `store_get`: fetching a node by ID
+//! - `store_children_tree`: loading a document tree, from the largest to the smallest
+//! - `store_query`: a type-filtered query with a limit
+//! - `store_search`: a content search for a common word
+//!
+//! `BENCH_PROFILE=smoke` seeds only 1k nodes and takes few, short samples,
+//! so CI can run the suite to catch a broken or grossly regressed store in a
+//! couple of minutes. Compare full runs with `--save-baseline` / `--baseline`.
+
+use criterion::measurement::WallTime;
+use criterion::{
+    black_box, criterion_group, criterion_main, BenchmarkGroup, BenchmarkId, Criterion,
+};
+use nodespace_core::db::{SurrealStore, DEFAULT_BULK_CHUNK_SIZE};
+use nodespace_core::fixtures::{SyntheticWorkspace, WorkspaceShape, VOCABULARY};
+use nodespace_core::services::NodeService;
+use nodespace_core::{Node, NodeQuery};
+use serde_json::json;
+use std::sync::Arc;
+use std::time::Duration;
+use tempfile::TempDir;
+use tokio::runtime::Runtime;
+
+/// Seed of the generated workspaces, fixed so runs compare
+const SEED: u64 = 42;
+
+struct Profile {
+    sizes: &'static [usize],
+    sample_size: usize,
+    measurement_time: Duration,
+    warm_up_time: Duration,
+}
+
+fn profile() -> Profile {
+    match std::env::var("BENCH_PROFILE").as_deref() {
+        Ok("smoke") => Profile {
+            sizes: &[1_000],
+            sample_size: 10,
+            measurement_time: Duration::from_secs(1),
+            warm_up_time: Duration::from_millis(200),
+        },
+        _ => Profile {
+            sizes: &[1_000, 10_000, 100_000],
+            sample_size: 30,
+            measurement_time: Duration::from_secs(5),
+            warm_up_time: Duration::from_secs(1),
+        },
+    }
+}
+
+/// A seeded [`SyntheticWorkspace`]
+struct Workspace {
+    node_service: NodeService,
+    store: Arc<SurrealStore>,
+    documents: Vec<String>,
+    nodes: Vec<String>,
+    _temp_dir: TempDir,
+}
+
+async fn seed_workspace(node_count: usize) -> Workspace {
+    let temp_dir = TempDir::new().unwrap();
+    let mut store = Arc::new(
+        SurrealStore::new(temp_dir.path().join("bench.db"))
+            .await
+            .unwrap(),
+    );
+    let node_service = NodeService::new(&mut store).await.unwrap();
+
+    let workspace = SyntheticWorkspace::generate(&WorkspaceShape::with_nodes(node_count), SEED);
+    let documents = workspace.documents.clone();
+    let nodes = workspace.nodes.iter().map(|node| node.id.clone()).collect();
+    let result = workspace
+        .write_to(&store, DEFAULT_BULK_CHUNK_SIZE)
+        .await
+        .unwrap();
+    assert_eq!(result.failed(), 0, "seeding the benchmark workspace failed");
+
+    Workspace {
+        node_service,
+        store,
+        documents,
+        nodes,
+        _temp_dir: temp_dir,
+    }
+}
+
+fn group<'a>(c: &'a mut Criterion, name: &str, profile: &Profile) -> BenchmarkGroup<'a, WallTime> {
+    let mut group = c.benchmark_group(name);
+    group
+        .sample_size(profile.sample_size)
+        .measurement_time(profile.measurement_time)
+        .warm_up_time(profile.warm_up_time);
+    group
+}
+
+fn bench_store(c: &mut Criterion) {
+    let rt = Runtime::new().unwrap();
+    let profile = profile();
+
+    for &size in profile.sizes {
+        let workspace = rt.block_on(seed_workspace(size));
+        let store = &workspace.store;
+        let id = BenchmarkId::from_parameter(size);
+
+        let mut create = group(c, "store_create", &profile);
+        create.bench_function(id.clone(), |b| {
+            let mut i = 0;
+            b.iter(|| {
+                i += 1;
+                let node = Node::new("text".to_string(), format!("Bench {}", i), json!({}));
+                black_box(rt.block_on(store.create_node(node, None)).unwrap())
+            })
+        });
+        create.finish();
+
+        let mut get = group(c, "store_get", &profile);
+        get.bench_function(id.clone(), |b| {
+            let mut ids = workspace.nodes.iter().cycle();
+            b.iter(|| black_box(rt.block_on(store.get_node(ids.next().unwrap())).unwrap()))
+        });
+        get.finish();
+
+        let mut tree = group(c, "store_children_tree", &profile);
+        tree.bench_function(id.clone(), |b| {
+            let mut documents = workspace.documents.iter().cycle();
+            b.iter(|| {
+                let document = documents.next().unwrap();
+                black_box(
+                    rt.block_on(workspace.node_service.get_children_tree(document))
+                        .unwrap(),
+                )
+            })
+        });
+        tree.finish();
+
+        let mut query = group(c, "store_query", &profile);
+        query.bench_function(id.clone(), |b| {
+            b.iter(|| {
+                let tasks = NodeQuery {
+                    node_type: Some("task".to_string()),
+                    limit: Some(50),
+                    ..Default::default()
+                };
+                black_box(rt.block_on(store.query_nodes(tasks)).unwrap())
+            })
+        });
+        query.finish();
+
+        let mut search = group(c, "store_search", &profile);
+        search.bench_function(id, |b| {
+            let mut words = VOCABULARY.iter().cycle();
+            b.iter(|| {
+                let word = words.next().unwrap();
+                black_box(
+                    rt.block_on(store.search_nodes_by_content(word, Some(20)))
+                        .unwrap(),
+                )
+            })
+        });
+        search.finish();
+    }
+}
+
+criterion_group!(benches, bench_store);
+criterion_main!(benches);
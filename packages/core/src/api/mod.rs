@@ -0,0 +1,308 @@
+//! API DTOs - Request and Response Shapes Shared by Every Surface
+//!
+//! The Tauri commands, the dev-proxy HTTP server and the MCP handlers all
+//! accept and return nodes. They use the types here instead of defining their
+//! own, so a field added to one of them reaches every surface at once.
+//!
+//! Field names are camelCase; requests also accept the snake_case spelling
+//! MCP clients send.
+
+use crate::models::{excerpt_text, node_to_typed_value, Node, NodeFilter, NodeQuery, OrderBy};
+use crate::services::{
+    CollectionService, CreateNodeParams, NodeService, NodeServiceError, DEFAULT_QUERY_LIMIT,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashSet;
+
+/// A node as returned to clients
+///
+/// Namespaced properties are flattened and tasks and schemas carry their
+/// typed fields (see [`node_to_typed_value`]).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct NodeDto(pub Value);
+
+impl NodeDto {
+    pub fn from_node(node: Node) -> Result<Self, String> {
+        node_to_typed_value(node).map(Self)
+    }
+
+    pub fn from_nodes(nodes: Vec<Node>) -> Result<Vec<Self>, String> {
+        nodes.into_iter().map(Self::from_node).collect()
+    }
+
+    pub fn into_value(self) -> Value {
+        self.0
+    }
+}
+
+/// Request to create a node under an optional parent
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateNodeRequest {
+    /// Client-chosen ID (e.g. to track the node before it is saved, or
+    /// `YYYY-MM-DD` for date nodes); generated when absent
+    #[serde(default)]
+    pub id: Option<String>,
+    #[serde(alias = "node_type")]
+    pub node_type: String,
+    /// May be empty (blank nodes are allowed)
+    pub content: String,
+    #[serde(default, alias = "parent_id")]
+    pub parent_id: Option<String>,
+    /// Sibling to insert after; first among the siblings when absent
+    #[serde(default, alias = "insert_after_node_id")]
+    pub insert_after_node_id: Option<String>,
+    #[serde(default)]
+    pub properties: Value,
+}
+
+impl From<CreateNodeRequest> for CreateNodeParams {
+    fn from(request: CreateNodeRequest) -> Self {
+        CreateNodeParams {
+            id: request.id,
+            node_type: request.node_type,
+            content: request.content,
+            parent_id: request.parent_id,
+            insert_after_node_id: request.insert_after_node_id,
+            properties: request.properties,
+        }
+    }
+}
+
+/// Request for nodes matching simple criteria
+///
+/// `id` returns that node alone; otherwise all given criteria must match.
+/// Results are newest first, except for `mentionedBy` queries, which don't
+/// support `offset`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryRequest {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// Nodes that mention this node
+    #[serde(
+        default,
+        alias = "mentioned_by",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub mentioned_by: Option<String>,
+    /// Case-insensitive content substring
+    #[serde(
+        default,
+        alias = "content_contains",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub content_contains: Option<String>,
+    #[serde(default, alias = "node_type", skip_serializing_if = "Option::is_none")]
+    pub node_type: Option<String>,
+    /// Members of this collection only
+    #[serde(
+        default,
+        alias = "collection_id",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub collection_id: Option<String>,
+    /// Members of the collection at this path (e.g. "hr:policy"); takes
+    /// precedence over `collectionId`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub collection: Option<String>,
+    /// Defaults to [`DEFAULT_QUERY_LIMIT`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub offset: Option<usize>,
+}
+
+/// Nodes found by a [`QueryRequest`]
+#[derive(Debug, Clone)]
+pub struct QueryResult {
+    pub nodes: Vec<Node>,
+    /// Collection the results were restricted to
+    pub collection_id: Option<String>,
+}
+
+impl QueryRequest {
+    /// Run the query
+    ///
+    /// A collection path that doesn't resolve yields no nodes rather than an
+    /// error.
+    pub async fn execute<C>(
+        &self,
+        node_service: &NodeService<C>,
+    ) -> Result<QueryResult, NodeServiceError>
+    where
+        C: surrealdb::Connection,
+    {
+        node_service.ensure_unlocked()?;
+        let collection_service = CollectionService::new(&node_service.store, node_service);
+        let collection_id = match &self.collection {
+            Some(path) => match collection_service.resolve_path(path).await {
+                Ok(resolved) => Some(resolved.leaf_id().to_string()),
+                Err(NodeServiceError::CollectionNotFound(_)) => {
+                    return Ok(QueryResult {
+                        nodes: Vec::new(),
+                        collection_id: None,
+                    })
+                }
+                Err(e) => return Err(e),
+            },
+            None => self.collection_id.clone(),
+        };
+
+        let member_ids: Option<HashSet<String>> = match &collection_id {
+            Some(id) => Some(
+                collection_service
+                    .get_collection_members(id)
+                    .await?
+                    .into_iter()
+                    .map(|node| node.id)
+                    .collect(),
+            ),
+            None => None,
+        };
+
+        // Membership lives in edges, so over-fetch and filter here; lookups by
+        // id or mention can't skip rows in the store, so both skip to the
+        // offset after the query
+        let limit = self.limit.unwrap_or(DEFAULT_QUERY_LIMIT);
+        let offset = self.offset.unwrap_or_default();
+        let by_reference = self.id.is_some() || self.mentioned_by.is_some();
+        let skip_here = by_reference || member_ids.is_some();
+        let fetch_limit = if member_ids.is_some() {
+            (offset + limit) * 3
+        } else if by_reference {
+            offset + limit
+        } else {
+            limit
+        };
+
+        let nodes = if by_reference {
+            node_service
+                .query_nodes_simple(NodeQuery {
+                    id: self.id.clone(),
+                    mentioned_by: self.mentioned_by.clone(),
+                    content_contains: self.content_contains.clone(),
+                    node_type: self.node_type.clone(),
+                    limit: Some(fetch_limit),
+                })
+                .await?
+        } else {
+            let mut filter = NodeFilter::new()
+                .with_order_by(OrderBy::CreatedDesc)
+                .with_limit(fetch_limit);
+            if let Some(node_type) = &self.node_type {
+                filter = filter.with_node_type(node_type.clone());
+            }
+            if let Some(content) = &self.content_contains {
+                filter = filter.with_content_contains(content.clone());
+            }
+            if !skip_here && offset > 0 {
+                filter = filter.with_offset(offset);
+            }
+            node_service.query_nodes(filter).await?
+        };
+
+        let nodes = if skip_here {
+            nodes
+                .into_iter()
+                .filter(|node| member_ids.as_ref().is_none_or(|ids| ids.contains(&node.id)))
+                .skip(offset)
+                .take(limit)
+                .collect()
+        } else {
+            nodes
+        };
+        Ok(QueryResult {
+            nodes,
+            collection_id,
+        })
+    }
+}
+
+/// A semantic search hit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchResultDto {
+    #[serde(flatten)]
+    pub node: NodeDto,
+    /// Cosine similarity to the query (0.0-1.0)
+    pub similarity: f64,
+    /// Short summary of the node, when one has been generated
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub excerpt: Option<String>,
+    /// The node's document as markdown, for the top results when requested
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub markdown: Option<String>,
+}
+
+impl SearchResultDto {
+    pub fn new(node: Node, similarity: f64) -> Result<Self, String> {
+        let excerpt = excerpt_text(&node.properties).map(str::to_string);
+        Ok(Self {
+            node: NodeDto::from_node(node)?,
+            similarity,
+            excerpt,
+            markdown: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_requests_accept_both_spellings() {
+        let desktop: CreateNodeRequest = serde_json::from_value(json!({
+            "id": "n1",
+            "nodeType": "text",
+            "content": "Hello",
+            "parentId": "p",
+            "insertAfterNodeId": "s",
+            "properties": {}
+        }))
+        .unwrap();
+        let mcp: CreateNodeRequest = serde_json::from_value(json!({
+            "node_type": "text",
+            "content": "Hello",
+            "parent_id": "p"
+        }))
+        .unwrap();
+        assert_eq!(desktop.node_type, mcp.node_type);
+        assert_eq!(desktop.parent_id, mcp.parent_id);
+        assert_eq!(mcp.id, None);
+        assert_eq!(desktop.insert_after_node_id.as_deref(), Some("s"));
+
+        let query: QueryRequest = serde_json::from_value(json!({
+            "mentioned_by": "n1",
+            "nodeType": "task",
+            "limit": 5
+        }))
+        .unwrap();
+        assert_eq!(query.mentioned_by.as_deref(), Some("n1"));
+        assert_eq!(query.node_type.as_deref(), Some("task"));
+        assert_eq!(
+            serde_json::to_value(&query).unwrap(),
+            json!({ "mentionedBy": "n1", "nodeType": "task", "limit": 5 })
+        );
+    }
+
+    #[test]
+    fn test_search_result_flattens_the_node() {
+        let node = Node::new(
+            "text".to_string(),
+            "Q4 planning".to_string(),
+            json!({ "excerpt": { "text": "Budget and goals" } }),
+        );
+        let id = node.id.clone();
+        let value = serde_json::to_value(SearchResultDto::new(node, 0.82).unwrap()).unwrap();
+        assert_eq!(value["id"], json!(id));
+        assert_eq!(value["nodeType"], json!("text"));
+        assert_eq!(value["similarity"], json!(0.82));
+        assert_eq!(value["excerpt"], json!("Budget and goals"));
+        assert!(value.get("markdown").is_none());
+    }
+}
@@ -9,8 +9,12 @@
 //! The behavior system enables extensibility while maintaining type safety
 //! and consistent validation across all node operations.
 
+use crate::i18n::UserMessage;
 use crate::models::schema::SchemaField;
-use crate::models::{Node, SchemaNode, TaskNode, ValidationError as NodeValidationError};
+use crate::models::{
+    ChildView, CollectionNode, Node, NodeAppearance, Period, Rollup, SchemaNode, TaskNode,
+    ValidationError as NodeValidationError,
+};
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::sync::{Arc, OnceLock};
@@ -27,6 +31,23 @@ fn get_date_pattern() -> &'static regex::Regex {
     })
 }
 
+/// Lazy-initialized regex for action-item markers in meeting notes
+static ACTION_ITEM_PATTERN: OnceLock<regex::Regex> = OnceLock::new();
+
+/// Returns the compiled action-item regex, initializing it on first use
+///
+/// Matches `- [ ] text`, `[ ] text`, `TODO: text`, `Action: text`,
+/// `Action item: text` and `AI: text` (markers case-insensitive, list
+/// bullets optional) and captures the text.
+fn get_action_item_pattern() -> &'static regex::Regex {
+    ACTION_ITEM_PATTERN.get_or_init(|| {
+        regex::Regex::new(
+            r"^\s*(?:[-*+]\s+)?(?:\[ \]|(?i:todo|action(?: item)?|ai)\s*:)\s*(?P<text>\S.*)$",
+        )
+        .expect("Invalid action item regex pattern (this is a bug)")
+    })
+}
+
 /// Errors that can occur during content processing
 ///
 /// These errors are returned by the `NodeBehavior::process_content()` method
@@ -553,8 +574,8 @@ impl NodeBehavior for TaskNodeBehavior {
                     // Schema system validates the actual value against allowed enum values
                     if let Some(status) = props.get("status") {
                         if !status.is_string() && !status.is_null() {
-                            return Err(NodeValidationError::InvalidProperties(
-                                "Status must be a string".to_string(),
+                            return Err(NodeValidationError::property_type_mismatch(
+                                "status", "string",
                             ));
                         }
                     }
@@ -562,8 +583,8 @@ impl NodeBehavior for TaskNodeBehavior {
                     // Validate priority type (must be integer if present)
                     if let Some(priority) = props.get("priority") {
                         if !priority.is_i64() && !priority.is_null() {
-                            return Err(NodeValidationError::InvalidProperties(
-                                "Priority must be an integer".to_string(),
+                            return Err(NodeValidationError::property_type_mismatch(
+                                "priority", "integer",
                             ));
                         }
                     }
@@ -818,16 +839,16 @@ impl NodeBehavior for DateNodeBehavior {
     fn validate(&self, node: &Node) -> Result<(), NodeValidationError> {
         // Validate date ID format (YYYY-MM-DD) using lazy-compiled regex
         if !get_date_pattern().is_match(&node.id) {
-            return Err(NodeValidationError::InvalidId(
-                "Date nodes must have ID format 'YYYY-MM-DD'".to_string(),
+            return Err(NodeValidationError::invalid_id(
+                node.id.as_str(),
+                "YYYY-MM-DD",
             ));
         }
 
         // Validate that it's an actual valid date using chrono
         use chrono::NaiveDate;
-        NaiveDate::parse_from_str(&node.id, "%Y-%m-%d").map_err(|_| {
-            NodeValidationError::InvalidId(format!("Invalid date format: {}", node.id))
-        })?;
+        NaiveDate::parse_from_str(&node.id, "%Y-%m-%d")
+            .map_err(|_| NodeValidationError::invalid_id(node.id.as_str(), "YYYY-MM-DD"))?;
 
         // NOTE: Per Issue #670, date nodes can have custom content (not required to match ID).
         // The ID is always in YYYY-MM-DD format, but content can be anything (e.g., "Custom Date Content").
@@ -867,6 +888,122 @@ impl NodeBehavior for DateNodeBehavior {
     }
 }
 
+/// Reject a period node whose ID isn't a canonical period of its type
+fn validate_period_id(node: &Node, format: &str) -> Result<(), NodeValidationError> {
+    match Period::parse(&node.id) {
+        Some(period) if period.node_type() == node.node_type => Ok(()),
+        _ => Err(NodeValidationError::invalid_id(node.id.as_str(), format)),
+    }
+}
+
+/// Built-in behavior for week nodes
+///
+/// Week nodes are weekly-review containers with deterministic ISO week IDs
+/// (`2025-W03`); see [`Period`] for the formats shared with month and
+/// quarter nodes. Like dates, their content is free-form.
+///
+/// # Examples
+///
+/// ```rust
+/// use nodespace_core::behaviors::{NodeBehavior, WeekNodeBehavior};
+/// use nodespace_core::models::Node;
+/// use serde_json::json;
+///
+/// let behavior = WeekNodeBehavior;
+/// let node = Node::new_with_id(
+///     "2025-W03".to_string(),
+///     "week".to_string(),
+///     "Week 3, 2025".to_string(),
+///     json!({}),
+/// );
+/// assert!(behavior.validate(&node).is_ok());
+/// ```
+pub struct WeekNodeBehavior;
+
+impl NodeBehavior for WeekNodeBehavior {
+    fn type_name(&self) -> &'static str {
+        "week"
+    }
+
+    fn validate(&self, node: &Node) -> Result<(), NodeValidationError> {
+        validate_period_id(node, "YYYY-Www")
+    }
+
+    fn can_have_children(&self) -> bool {
+        true // Review notes live under the week
+    }
+
+    fn supports_markdown(&self) -> bool {
+        false
+    }
+
+    fn get_embeddable_content(&self, _node: &Node) -> Option<String> {
+        None
+    }
+
+    fn get_parent_contribution(&self, _node: &Node) -> Option<String> {
+        None
+    }
+}
+
+/// Built-in behavior for month nodes (`2025-05`)
+pub struct MonthNodeBehavior;
+
+impl NodeBehavior for MonthNodeBehavior {
+    fn type_name(&self) -> &'static str {
+        "month"
+    }
+
+    fn validate(&self, node: &Node) -> Result<(), NodeValidationError> {
+        validate_period_id(node, "YYYY-MM")
+    }
+
+    fn can_have_children(&self) -> bool {
+        true
+    }
+
+    fn supports_markdown(&self) -> bool {
+        false
+    }
+
+    fn get_embeddable_content(&self, _node: &Node) -> Option<String> {
+        None
+    }
+
+    fn get_parent_contribution(&self, _node: &Node) -> Option<String> {
+        None
+    }
+}
+
+/// Built-in behavior for quarter nodes (`2025-Q2`)
+pub struct QuarterNodeBehavior;
+
+impl NodeBehavior for QuarterNodeBehavior {
+    fn type_name(&self) -> &'static str {
+        "quarter"
+    }
+
+    fn validate(&self, node: &Node) -> Result<(), NodeValidationError> {
+        validate_period_id(node, "YYYY-Qn")
+    }
+
+    fn can_have_children(&self) -> bool {
+        true
+    }
+
+    fn supports_markdown(&self) -> bool {
+        false
+    }
+
+    fn get_embeddable_content(&self, _node: &Node) -> Option<String> {
+        None
+    }
+
+    fn get_parent_contribution(&self, _node: &Node) -> Option<String> {
+        None
+    }
+}
+
 /// Schema node behavior
 ///
 /// Schema nodes store entity type definitions using the Pure JSON schema-as-node pattern.
@@ -889,10 +1026,9 @@ pub struct SchemaNodeBehavior;
 fn validate_schema_field(field: &SchemaField) -> Result<(), NodeValidationError> {
     // Validate field name characters (alphanumeric and underscores only)
     if !field.name.chars().all(|c| c.is_alphanumeric() || c == '_') {
-        return Err(NodeValidationError::InvalidProperties(format!(
-            "Invalid field name '{}': must contain only alphanumeric characters and underscores",
-            field.name
-        )));
+        return Err(NodeValidationError::InvalidProperties(
+            UserMessage::new("INVALID_SCHEMA_FIELD_NAME").with_param("field", field.name.as_str()),
+        ));
     }
 
     // Enum fields must have at least one value defined
@@ -901,10 +1037,9 @@ fn validate_schema_field(field: &SchemaField) -> Result<(), NodeValidationError>
             || field.user_values.as_ref().is_some_and(|v| !v.is_empty());
 
         if !has_values {
-            return Err(NodeValidationError::InvalidProperties(format!(
-                "Enum field '{}' must have at least one value defined (in core_values or user_values)",
-                field.name
-            )));
+            return Err(NodeValidationError::InvalidProperties(
+                UserMessage::new("ENUM_WITHOUT_VALUES").with_param("field", field.name.as_str()),
+            ));
         }
     }
 
@@ -946,24 +1081,22 @@ impl SchemaNodeBehavior {
     pub fn validate_schema_node(&self, schema: &SchemaNode) -> Result<(), NodeValidationError> {
         // Validate non-empty content (schema name)
         if is_empty_or_whitespace(&schema.content) {
-            return Err(NodeValidationError::MissingField(
-                "Schema nodes must have content (schema name)".to_string(),
-            ));
+            return Err(NodeValidationError::MissingField("name".to_string()));
         }
 
         // Validate schema version is positive
         if schema.schema_version == 0 {
-            return Err(NodeValidationError::InvalidProperties(
-                "Schema version must be positive".to_string(),
-            ));
+            return Err(NodeValidationError::InvalidProperties(UserMessage::new(
+                "SCHEMA_VERSION_NOT_POSITIVE",
+            )));
         }
 
         // Validate field name uniqueness
         let field_names: HashSet<_> = schema.fields.iter().map(|f| &f.name).collect();
         if field_names.len() != schema.fields.len() {
-            return Err(NodeValidationError::InvalidProperties(
-                "Schema contains duplicate field names".to_string(),
-            ));
+            return Err(NodeValidationError::InvalidProperties(UserMessage::new(
+                "DUPLICATE_SCHEMA_FIELDS",
+            )));
         }
 
         // Validate each field
@@ -994,15 +1127,14 @@ impl NodeBehavior for SchemaNodeBehavior {
 
                 // Basic validation - non-empty content
                 if is_empty_or_whitespace(&node.content) {
-                    return Err(NodeValidationError::MissingField(
-                        "Schema nodes must have content (schema name)".to_string(),
-                    ));
+                    return Err(NodeValidationError::MissingField("name".to_string()));
                 }
 
                 // Properties should be valid JSON object
                 if !node.properties.is_object() {
-                    return Err(NodeValidationError::InvalidProperties(
-                        "Schema properties must be a JSON object".to_string(),
+                    return Err(NodeValidationError::property_type_mismatch(
+                        "properties",
+                        "object",
                     ));
                 }
 
@@ -1151,20 +1283,19 @@ impl NodeBehavior for CollectionNodeBehavior {
         // Collection names must be non-empty
         // The content field stores the collection name
         if node.content.trim().is_empty() {
-            return Err(NodeValidationError::MissingField(
-                "Collection name (content) cannot be empty".to_string(),
-            ));
+            return Err(NodeValidationError::MissingField("name".to_string()));
         }
 
         // Collection names cannot contain the path delimiter ':'
         // This ensures clean path parsing
         if node.content.contains(':') {
-            return Err(NodeValidationError::InvalidProperties(
-                "Collection name cannot contain ':' (path delimiter)".to_string(),
-            ));
+            return Err(NodeValidationError::InvalidProperties(UserMessage::new(
+                "COLLECTION_NAME_HAS_DELIMITER",
+            )));
         }
 
-        Ok(())
+        // Smart collections must store a query that can run
+        CollectionNode::validate_smart_query(node)
     }
 
     fn can_have_children(&self) -> bool {
@@ -1196,6 +1327,229 @@ impl NodeBehavior for CollectionNodeBehavior {
     }
 }
 
+/// Built-in behavior for project nodes
+///
+/// A project's content is its name; tasks live anywhere beneath it and feed
+/// its progress rollup (see `services::project_rollup`). Project properties
+/// (`status`, `due_date`) are validated against the project schema.
+///
+/// # Examples
+///
+/// ```rust
+/// use nodespace_core::behaviors::{NodeBehavior, ProjectNodeBehavior};
+/// use nodespace_core::models::Node;
+/// use serde_json::json;
+///
+/// let behavior = ProjectNodeBehavior;
+/// let node = Node::new(
+///     "project".to_string(),
+///     "Website relaunch".to_string(),
+///     json!({}),
+/// );
+/// assert!(behavior.validate(&node).is_ok());
+/// ```
+pub struct ProjectNodeBehavior;
+
+impl NodeBehavior for ProjectNodeBehavior {
+    fn type_name(&self) -> &'static str {
+        "project"
+    }
+
+    fn validate(&self, node: &Node) -> Result<(), NodeValidationError> {
+        if node.content.trim().is_empty() {
+            return Err(NodeValidationError::MissingField("name".to_string()));
+        }
+        Ok(())
+    }
+
+    fn can_have_children(&self) -> bool {
+        true // Tasks and notes are organized under projects
+    }
+
+    fn supports_markdown(&self) -> bool {
+        false // Project names are plain text
+    }
+
+    fn default_metadata(&self) -> serde_json::Value {
+        serde_json::json!({})
+    }
+}
+
+/// Built-in behavior for meeting nodes
+///
+/// A meeting's content is its title; attendees, date and location are
+/// properties checked against the meeting schema. Notes taken during the
+/// meeting are its children, and lines among them marked as action items
+/// (see [`MeetingNodeBehavior::action_item`]) can be turned into tasks with
+/// `NodeService::extract_action_items`.
+///
+/// # Examples
+///
+/// ```rust
+/// use nodespace_core::behaviors::{NodeBehavior, MeetingNodeBehavior};
+/// use nodespace_core::models::Node;
+/// use serde_json::json;
+///
+/// let behavior = MeetingNodeBehavior;
+/// let node = Node::new(
+///     "meeting".to_string(),
+///     "Weekly sync".to_string(),
+///     json!({ "meeting": { "date": "2025-01-15", "attendees": [] } }),
+/// );
+/// assert!(behavior.validate(&node).is_ok());
+/// assert_eq!(
+///     MeetingNodeBehavior::action_item("TODO: send the recap").as_deref(),
+///     Some("send the recap")
+/// );
+/// ```
+pub struct MeetingNodeBehavior;
+
+impl MeetingNodeBehavior {
+    /// Task text of a note that is an action item, `None` for other notes
+    ///
+    /// Only single-line notes count, so a paragraph quoting a TODO stays a
+    /// note.
+    pub fn action_item(content: &str) -> Option<String> {
+        let content = content.trim();
+        if content.contains('\n') {
+            return None;
+        }
+        get_action_item_pattern()
+            .captures(content)
+            .map(|caps| caps["text"].trim().to_string())
+    }
+}
+
+impl NodeBehavior for MeetingNodeBehavior {
+    fn type_name(&self) -> &'static str {
+        "meeting"
+    }
+
+    fn validate(&self, node: &Node) -> Result<(), NodeValidationError> {
+        if node.content.trim().is_empty() {
+            return Err(NodeValidationError::MissingField("title".to_string()));
+        }
+
+        // Properties may still be flat when a client creates the node
+        let props = node.properties.get("meeting").unwrap_or(&node.properties);
+        if let Some(attendees) = props.get("attendees") {
+            let valid = attendees
+                .as_array()
+                .is_some_and(|ids| ids.iter().all(|id| id.is_string()));
+            if !valid {
+                return Err(NodeValidationError::property_type_mismatch(
+                    "attendees",
+                    "array",
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn can_have_children(&self) -> bool {
+        true // Notes and action items live under the meeting
+    }
+
+    fn supports_markdown(&self) -> bool {
+        false // Meeting titles are plain text
+    }
+
+    fn default_metadata(&self) -> serde_json::Value {
+        serde_json::json!({ "meeting": { "attendees": [] } })
+    }
+}
+
+/// Built-in behavior for highlight nodes
+///
+/// A highlight's content is the excerpt it marks. Where the excerpt sits in
+/// its source is described by the optional `page`, `start_offset` and
+/// `end_offset` properties, and the source itself (the text node embedding
+/// an attachment or bookmarked link) is the target of the highlight's
+/// `highlights` edge.
+/// Comments on a highlight are its children.
+///
+/// # Examples
+///
+/// ```rust
+/// use nodespace_core::behaviors::{NodeBehavior, HighlightNodeBehavior};
+/// use nodespace_core::models::Node;
+/// use serde_json::json;
+///
+/// let behavior = HighlightNodeBehavior;
+/// let node = Node::new(
+///     "highlight".to_string(),
+///     "the quick brown fox".to_string(),
+///     json!({ "highlight": { "page": 3, "start_offset": 120, "end_offset": 139 } }),
+/// );
+/// assert!(behavior.validate(&node).is_ok());
+/// assert_eq!(HighlightNodeBehavior::position(&node), (3, 120));
+/// ```
+pub struct HighlightNodeBehavior;
+
+impl HighlightNodeBehavior {
+    /// Sort key of a highlight within its source: `(page, start_offset)`
+    ///
+    /// Missing values count as 0, so unpositioned highlights come first.
+    pub fn position(node: &Node) -> (u64, u64) {
+        let props = node.properties.get("highlight").unwrap_or(&node.properties);
+        let get = |key: &str| props.get(key).and_then(|v| v.as_u64()).unwrap_or(0);
+        (get("page"), get("start_offset"))
+    }
+}
+
+impl NodeBehavior for HighlightNodeBehavior {
+    fn type_name(&self) -> &'static str {
+        "highlight"
+    }
+
+    fn validate(&self, node: &Node) -> Result<(), NodeValidationError> {
+        if node.content.trim().is_empty() {
+            return Err(NodeValidationError::MissingField("excerpt".to_string()));
+        }
+
+        // Properties may still be flat when a client creates the node
+        let props = node.properties.get("highlight").unwrap_or(&node.properties);
+        let mut offsets = [None, None];
+        for (key, slot) in ["start_offset", "end_offset"].iter().zip(&mut offsets) {
+            if let Some(value) = props.get(*key) {
+                *slot = Some(value.as_u64().ok_or_else(|| {
+                    NodeValidationError::InvalidProperties(
+                        UserMessage::new("NON_NEGATIVE_INTEGER_REQUIRED").with_param("field", *key),
+                    )
+                })?);
+            }
+        }
+        if let [Some(start), Some(end)] = offsets {
+            if start > end {
+                return Err(NodeValidationError::InvalidProperties(UserMessage::new(
+                    "HIGHLIGHT_RANGE_REVERSED",
+                )));
+            }
+        }
+        if props
+            .get("page")
+            .is_some_and(|page| page.as_u64().is_none())
+        {
+            return Err(NodeValidationError::InvalidProperties(
+                UserMessage::new("NON_NEGATIVE_INTEGER_REQUIRED").with_param("field", "page"),
+            ));
+        }
+        Ok(())
+    }
+
+    fn can_have_children(&self) -> bool {
+        true // Comments on the highlight
+    }
+
+    fn supports_markdown(&self) -> bool {
+        false // Excerpts are kept verbatim
+    }
+
+    fn default_metadata(&self) -> serde_json::Value {
+        serde_json::json!({})
+    }
+}
+
 /// Fallback behavior for schema-defined custom types
 ///
 /// This behavior is used for node types that have a schema definition but no
@@ -1258,8 +1612,9 @@ impl NodeBehavior for CustomNodeBehavior {
         // - Content can be empty (some entity types may use properties only)
         // - Properties must be a valid JSON object
         if !node.properties.is_object() {
-            return Err(NodeValidationError::InvalidProperties(
-                "Properties must be a JSON object".to_string(),
+            return Err(NodeValidationError::property_type_mismatch(
+                "properties",
+                "object",
             ));
         }
         Ok(())
@@ -1369,9 +1724,15 @@ impl NodeBehaviorRegistry {
         registry.register(Arc::new(QuoteBlockNodeBehavior));
         registry.register(Arc::new(OrderedListNodeBehavior));
         registry.register(Arc::new(DateNodeBehavior));
+        registry.register(Arc::new(WeekNodeBehavior));
+        registry.register(Arc::new(MonthNodeBehavior));
+        registry.register(Arc::new(QuarterNodeBehavior));
         registry.register(Arc::new(SchemaNodeBehavior));
         registry.register(Arc::new(QueryNodeBehavior));
         registry.register(Arc::new(CollectionNodeBehavior));
+        registry.register(Arc::new(ProjectNodeBehavior));
+        registry.register(Arc::new(MeetingNodeBehavior));
+        registry.register(Arc::new(HighlightNodeBehavior));
 
         registry
     }
@@ -1479,6 +1840,11 @@ impl NodeBehaviorRegistry {
             .get(&node.node_type)
             .unwrap_or_else(|| Arc::new(CustomNodeBehavior::new(&node.node_type)));
 
+        // Appearance, child views and rollups are shared by every node type,
+        // so they are checked here rather than in each behavior
+        NodeAppearance::validate_properties(&node.properties)?;
+        ChildView::validate_properties(&node.properties)?;
+        Rollup::validate_properties(&node.properties)?;
         behavior.validate(node)
     }
 }
@@ -1954,6 +2320,35 @@ mod tests {
         assert_eq!(metadata["timezone"], "UTC");
     }
 
+    #[test]
+    fn test_period_node_behaviors_validate_id_for_their_type() {
+        let registry = NodeBehaviorRegistry::new();
+        for (id, node_type) in [
+            ("2025-W03", "week"),
+            ("2025-05", "month"),
+            ("2025-Q2", "quarter"),
+        ] {
+            let node = Node::new_with_id(
+                id.to_string(),
+                node_type.to_string(),
+                "Review".to_string(),
+                json!({}),
+            );
+            assert!(registry.validate_node(&node).is_ok(), "{} is valid", id);
+        }
+
+        // A month ID on a week node, and a week that doesn't exist
+        for (id, node_type) in [("2025-05", "week"), ("2025-W53", "week")] {
+            let node = Node::new_with_id(
+                id.to_string(),
+                node_type.to_string(),
+                "Review".to_string(),
+                json!({}),
+            );
+            assert!(registry.validate_node(&node).is_err(), "{} is invalid", id);
+        }
+    }
+
     #[test]
     fn test_registry_new() {
         let registry = NodeBehaviorRegistry::new();
@@ -1995,7 +2390,13 @@ mod tests {
         assert!(types.contains(&"schema".to_string()));
         assert!(types.contains(&"query".to_string()));
         assert!(types.contains(&"collection".to_string()));
-        assert_eq!(types.len(), 10);
+        assert!(types.contains(&"project".to_string()));
+        assert!(types.contains(&"meeting".to_string()));
+        assert!(types.contains(&"highlight".to_string()));
+        assert!(types.contains(&"week".to_string()));
+        assert!(types.contains(&"month".to_string()));
+        assert!(types.contains(&"quarter".to_string()));
+        assert_eq!(types.len(), 16);
     }
 
     #[test]
@@ -2034,6 +2435,30 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_registry_validates_appearance_for_every_type() {
+        let registry = NodeBehaviorRegistry::new();
+
+        for node_type in ["text", "custom-type"] {
+            let styled = Node::new(
+                node_type.to_string(),
+                "Content".to_string(),
+                json!({ "appearance": { "icon": "📚", "color": "#3366ff" } }),
+            );
+            assert!(registry.validate_node(&styled).is_ok());
+
+            let bad = Node::new(
+                node_type.to_string(),
+                "Content".to_string(),
+                json!({ "appearance": { "color": "blue" } }),
+            );
+            assert!(matches!(
+                registry.validate_node(&bad),
+                Err(NodeValidationError::InvalidProperties(_))
+            ));
+        }
+    }
+
     #[test]
     fn test_registry_thread_safety() {
         use std::sync::Arc;
@@ -2429,7 +2854,7 @@ mod tests {
         assert!(matches!(
             result,
             Err(NodeValidationError::InvalidProperties(ref msg))
-                if msg.contains("duplicate field names")
+                if msg.code == "DUPLICATE_SCHEMA_FIELDS"
         ));
     }
 
@@ -2460,7 +2885,7 @@ mod tests {
         assert!(matches!(
             result,
             Err(NodeValidationError::InvalidProperties(ref msg))
-                if msg.contains("Enum field") && msg.contains("must have at least one value")
+                if msg.code == "ENUM_WITHOUT_VALUES"
         ));
 
         // Enum with core_values should pass
@@ -2699,7 +3124,7 @@ mod tests {
         assert!(matches!(
             result,
             Err(NodeValidationError::InvalidProperties(ref msg))
-                if msg.contains("Enum field") && msg.contains("must have at least one value")
+                if msg.code == "ENUM_WITHOUT_VALUES"
         ));
     }
 
@@ -2929,4 +3354,112 @@ mod tests {
         assert!(behavior.get_embeddable_content(&node).is_none());
         assert!(behavior.get_parent_contribution(&node).is_none());
     }
+
+    // =========================================================================
+    // ProjectNodeBehavior Tests
+    // =========================================================================
+
+    #[test]
+    fn test_project_node_behavior() {
+        let behavior = ProjectNodeBehavior;
+
+        assert_eq!(behavior.type_name(), "project");
+        assert!(behavior.can_have_children());
+        assert!(!behavior.supports_markdown());
+
+        let project = Node::new("project".to_string(), "Launch".to_string(), json!({}));
+        assert!(behavior.validate(&project).is_ok());
+        assert_eq!(
+            behavior.get_embeddable_content(&project).as_deref(),
+            Some("Launch")
+        );
+
+        let unnamed = Node::new("project".to_string(), "  ".to_string(), json!({}));
+        assert!(matches!(
+            behavior.validate(&unnamed),
+            Err(NodeValidationError::MissingField(_))
+        ));
+    }
+
+    // =========================================================================
+    // MeetingNodeBehavior Tests
+    // =========================================================================
+
+    #[test]
+    fn test_meeting_node_behavior_validation() {
+        let behavior = MeetingNodeBehavior;
+
+        let meeting = Node::new(
+            "meeting".to_string(),
+            "Kickoff".to_string(),
+            json!({ "meeting": { "attendees": ["person-1", "person-2"] } }),
+        );
+        assert!(behavior.validate(&meeting).is_ok());
+
+        let bad_attendees = Node::new(
+            "meeting".to_string(),
+            "Kickoff".to_string(),
+            json!({ "meeting": { "attendees": "person-1" } }),
+        );
+        assert!(matches!(
+            behavior.validate(&bad_attendees),
+            Err(NodeValidationError::InvalidProperties(_))
+        ));
+
+        let untitled = Node::new("meeting".to_string(), " ".to_string(), json!({}));
+        assert!(behavior.validate(&untitled).is_err());
+    }
+
+    #[test]
+    fn test_meeting_action_item_detection() {
+        let item = |content: &str| MeetingNodeBehavior::action_item(content);
+
+        assert_eq!(item("- [ ] Book venue").as_deref(), Some("Book venue"));
+        assert_eq!(item("[ ] Book venue").as_deref(), Some("Book venue"));
+        assert_eq!(item("TODO: email Sam").as_deref(), Some("email Sam"));
+        assert_eq!(item("todo:email Sam").as_deref(), Some("email Sam"));
+        assert_eq!(item("* Action: draft plan").as_deref(), Some("draft plan"));
+        assert_eq!(
+            item("Action item: draft plan").as_deref(),
+            Some("draft plan")
+        );
+        assert_eq!(item("AI: follow up").as_deref(), Some("follow up"));
+
+        assert_eq!(item("- [x] Already done"), None);
+        assert_eq!(item("Discussed the TODO: list"), None);
+        assert_eq!(item("TODO:"), None);
+        assert_eq!(item("TODO: one\nand more"), None);
+        assert_eq!(item("Aim: ship by Friday"), None);
+    }
+
+    // =========================================================================
+    // HighlightNodeBehavior Tests
+    // =========================================================================
+
+    #[test]
+    fn test_highlight_node_behavior_validation() {
+        let behavior = HighlightNodeBehavior;
+        let highlight = |props| Node::new("highlight".to_string(), "excerpt".to_string(), props);
+
+        assert!(behavior.validate(&highlight(json!({}))).is_ok());
+        assert!(behavior
+            .validate(&highlight(json!({ "start_offset": 4, "end_offset": 10 })))
+            .is_ok());
+        assert!(matches!(
+            behavior.validate(&highlight(
+                json!({ "highlight": { "start_offset": 10, "end_offset": 4 } })
+            )),
+            Err(NodeValidationError::InvalidProperties(_))
+        ));
+        assert!(behavior
+            .validate(&highlight(json!({ "highlight": { "page": -1 } })))
+            .is_err());
+
+        let empty = Node::new("highlight".to_string(), "".to_string(), json!({}));
+        assert!(behavior.validate(&empty).is_err());
+        assert_eq!(
+            HighlightNodeBehavior::position(&highlight(json!({ "highlight": { "page": 2 } }))),
+            (2, 0)
+        );
+    }
 }
@@ -0,0 +1,204 @@
+//! Standalone MCP Server
+//!
+//! Opens a NodeSpace workspace directly (no desktop app) and serves MCP over
+//! stdio or HTTP, so agents such as Claude Desktop can use NodeSpace without
+//! the app running.
+//!
+//! Only one process can have a workspace open. The server takes the
+//! workspace's process lock (see `db::process_lock`) and exits with an
+//! explanation when the app has the workspace; agents can then use the app's
+//! own MCP server instead.
+//!
+//! # Usage
+//!
+//! ```bash
+//! # stdio (for clients that spawn the server)
+//! nodespace-mcp ~/.nodespace/database/nodespace
+//!
+//! # HTTP on MCP_PORT or 3100
+//! nodespace-mcp ~/.nodespace/database/nodespace --http
+//! ```
+//!
+//! Claude Desktop (`claude_desktop_config.json`):
+//!
+//! ```json
+//! {
+//!   "mcpServers": {
+//!     "nodespace": {
+//!       "command": "nodespace-mcp",
+//!       "args": ["/Users/me/.nodespace/database/nodespace"]
+//!     }
+//!   }
+//! }
+//! ```
+//!
+//! Logs go to stderr, since stdout carries the protocol.
+
+use nodespace_core::db::{LockOwner, ProcessLock, ProcessLockError, StoreHealth, SurrealStore};
+use nodespace_core::mcp::StdioFraming;
+use nodespace_core::services::{
+    default_mcp_port, EmbeddingProcessor, McpServerService, NodeEmbeddingService, NodeService,
+};
+use nodespace_nlp_engine::EmbeddingService;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tracing_subscriber::EnvFilter;
+
+const PROGRAM: &str = "nodespace-mcp";
+
+const USAGE: &str = "\
+Usage: nodespace-mcp [WORKSPACE] [--http [--port PORT]] [--framing MODE]
+
+Serve a NodeSpace workspace over MCP.
+
+  WORKSPACE        Database directory (default: $NODESPACE_DB_PATH)
+  --http           Serve HTTP on 127.0.0.1 instead of stdio
+  --port PORT      HTTP port (default: $MCP_PORT or 3100)
+  --framing MODE   stdio framing: auto, newline or content-length (default: auto)
+  -h, --help       Show this help";
+
+#[derive(Debug)]
+struct Options {
+    workspace: PathBuf,
+    http_port: Option<u16>,
+    framing: StdioFraming,
+}
+
+fn parse_options(mut args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let mut workspace = None;
+    let mut http = false;
+    let mut port = None;
+    let mut framing = StdioFraming::Auto;
+
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| args.next().ok_or_else(|| format!("{} needs a value", name));
+        match arg.as_str() {
+            "--http" => http = true,
+            "--port" => {
+                port = Some(
+                    value("--port")?
+                        .parse()
+                        .map_err(|_| "--port needs a port number".to_string())?,
+                )
+            }
+            "--framing" => framing = value("--framing")?.parse()?,
+            flag if flag.starts_with('-') => return Err(format!("Unknown option {}", flag)),
+            _ if workspace.is_some() => return Err(format!("Unexpected argument {}", arg)),
+            _ => workspace = Some(PathBuf::from(&arg)),
+        }
+    }
+
+    let workspace = workspace
+        .or_else(|| std::env::var_os("NODESPACE_DB_PATH").map(PathBuf::from))
+        .ok_or_else(|| "No workspace given".to_string())?;
+    Ok(Options {
+        workspace,
+        http_port: http.then(|| port.unwrap_or_else(default_mcp_port)),
+        framing,
+    })
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.iter().any(|arg| arg == "-h" || arg == "--help") {
+        println!("{}", USAGE);
+        return Ok(());
+    }
+    let options = match parse_options(args.into_iter()) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, USAGE);
+            std::process::exit(2);
+        }
+    };
+
+    tracing_subscriber::fmt()
+        .with_writer(std::io::stderr)
+        .with_env_filter(
+            EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| EnvFilter::new("nodespace_core=info")),
+        )
+        .init();
+
+    // Held until exit
+    let _process_lock = match ProcessLock::acquire(&options.workspace, &LockOwner::current(PROGRAM))
+    {
+        Ok(lock) => lock,
+        Err(e @ ProcessLockError::Held(_)) => {
+            eprintln!("{}", e);
+            eprintln!(
+                "Quit it first, or connect to the app's MCP server \
+                 (http://127.0.0.1:{}/mcp by default).",
+                default_mcp_port()
+            );
+            std::process::exit(1);
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    let mut store = match SurrealStore::new(options.workspace.clone()).await {
+        Ok(store) => Arc::new(store),
+        Err(e) => {
+            let health = StoreHealth::from_open_error(&options.workspace, &e);
+            eprintln!("{}", health.message());
+            std::process::exit(1);
+        }
+    };
+
+    let mut node_service = NodeService::new(&mut store).await?;
+    if node_service.workspace_lock().is_locked() {
+        tracing::warn!("Workspace is locked with a passphrase; unlock it in the app to use it");
+    }
+
+    let mut nlp_engine = EmbeddingService::new(Default::default())
+        .map_err(|e| anyhow::anyhow!("Failed to create NLP engine: {}", e))?;
+    nlp_engine
+        .initialize()
+        .map_err(|e| anyhow::anyhow!("Failed to initialize NLP engine: {}", e))?;
+    let embedding_service = Arc::new(NodeEmbeddingService::new(
+        Arc::new(nlp_engine),
+        store.clone(),
+        node_service.workspace_lock().clone(),
+    ));
+
+    // Keep embeddings of what agents write current, as the app would
+    let processor = EmbeddingProcessor::new(embedding_service.clone())?;
+    node_service.set_embedding_waker(processor.waker());
+    processor.wake();
+
+    let port = options.http_port.unwrap_or_else(default_mcp_port);
+    let mcp_service = McpServerService::new(Arc::new(node_service), embedding_service, port);
+    let result = match options.http_port {
+        Some(_) => mcp_service.start().await,
+        None => mcp_service.start_stdio(options.framing).await,
+    };
+
+    processor.shutdown().await;
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Options, String> {
+        parse_options(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn test_parse_options() {
+        let options = parse(&["/tmp/ws", "--framing", "content-length"]).unwrap();
+        assert_eq!(options.workspace, PathBuf::from("/tmp/ws"));
+        assert_eq!(options.http_port, None);
+        assert_eq!(options.framing, StdioFraming::ContentLength);
+
+        let options = parse(&["--http", "--port", "4100", "/tmp/ws"]).unwrap();
+        assert_eq!(options.http_port, Some(4100));
+
+        assert!(parse(&["/tmp/ws", "--port"]).is_err());
+        assert!(parse(&["/tmp/ws", "--framing", "lsp"]).is_err());
+        assert!(parse(&["/tmp/ws", "/tmp/other"]).is_err());
+        assert!(parse(&["/tmp/ws", "--verbose"]).is_err());
+    }
+}
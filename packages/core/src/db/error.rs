@@ -4,6 +4,7 @@
 //! clear error handling for connection, initialization, and query failures.
 
 use std::path::PathBuf;
+use std::time::Duration;
 use thiserror::Error;
 
 /// Database operation errors
@@ -43,6 +44,10 @@ pub enum DatabaseError {
     /// SQL execution error with context
     #[error("SQL execution failed: {context}")]
     SqlExecutionError { context: String },
+
+    /// Query ran longer than the store's query timeout and was cancelled
+    #[error("Query timed out after {after:?}; narrow its filters or raise the query timeout")]
+    Timeout { after: Duration },
 }
 
 impl DatabaseError {
@@ -75,4 +80,16 @@ impl DatabaseError {
             context: context.into(),
         }
     }
+
+    /// Create a query timeout error
+    pub fn timeout(after: Duration) -> Self {
+        Self::Timeout { after }
+    }
+
+    /// Whether `error` (or anything in its chain) is a query timeout
+    pub fn is_timeout(error: &anyhow::Error) -> bool {
+        error
+            .chain()
+            .any(|cause| matches!(cause.downcast_ref(), Some(Self::Timeout { .. })))
+    }
 }
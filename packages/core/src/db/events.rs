@@ -41,7 +41,7 @@ use serde::{Deserialize, Serialize};
 /// # Properties
 ///
 /// Type-specific data stored in `properties`:
-/// - `has_child`: `{"order": 1.5}`
+/// - `has_child`: `{"order": "a0"}` (fractional index key)
 /// - `mentions`: `{"context": "optional context"}`
 /// - `member_of`: `{}` (no additional properties)
 /// - Custom: User-defined JSON properties
@@ -67,6 +67,8 @@ pub struct RelationshipEvent {
 ///
 /// Each event includes an optional `source_client_id` to identify the originating client,
 /// allowing event subscribers to filter out their own events (prevent feedback loops).
+/// `correlation_id` names the request that caused the change (see
+/// [`crate::utils::with_correlation_id`]), when it ran inside one.
 ///
 /// Node events send only the `node_id` (not full payload) for efficiency.
 /// Subscribers fetch the full node data via `get_node()` if needed (Issue #724).
@@ -80,18 +82,21 @@ pub enum DomainEvent {
         /// that need to know the type without fetching the full node
         node_type: String,
         source_client_id: Option<String>,
+        correlation_id: Option<String>,
     },
 
     /// An existing node was updated
     NodeUpdated {
         node_id: String,
         source_client_id: Option<String>,
+        correlation_id: Option<String>,
     },
 
     /// A node was deleted
     NodeDeleted {
         id: String,
         source_client_id: Option<String>,
+        correlation_id: Option<String>,
     },
 
     // ============================================================================
@@ -105,6 +110,7 @@ pub enum DomainEvent {
     RelationshipCreated {
         relationship: RelationshipEvent,
         source_client_id: Option<String>,
+        correlation_id: Option<String>,
     },
 
     /// An existing relationship was updated (unified format for all relationship types)
@@ -113,6 +119,7 @@ pub enum DomainEvent {
     RelationshipUpdated {
         relationship: RelationshipEvent,
         source_client_id: Option<String>,
+        correlation_id: Option<String>,
     },
 
     /// A relationship was deleted (unified format for all relationship types)
@@ -129,9 +136,24 @@ pub enum DomainEvent {
         /// Relationship type hint for handlers that need it
         relationship_type: String,
         source_client_id: Option<String>,
+        correlation_id: Option<String>,
     },
 }
 
+impl DomainEvent {
+    /// Correlation ID of the request that caused this change
+    pub fn correlation_id(&self) -> Option<&str> {
+        match self {
+            Self::NodeCreated { correlation_id, .. }
+            | Self::NodeUpdated { correlation_id, .. }
+            | Self::NodeDeleted { correlation_id, .. }
+            | Self::RelationshipCreated { correlation_id, .. }
+            | Self::RelationshipUpdated { correlation_id, .. }
+            | Self::RelationshipDeleted { correlation_id, .. } => correlation_id.as_deref(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -148,7 +170,7 @@ mod tests {
             from_id: "node:parent-123".to_string(),
             to_id: "node:child-456".to_string(),
             relationship_type: "has_child".to_string(),
-            properties: serde_json::json!({"order": 1.5}),
+            properties: serde_json::json!({"order": "a0"}),
         };
 
         let json = serde_json::to_string(&has_child).unwrap();
@@ -159,7 +181,10 @@ mod tests {
         assert_eq!(parsed.get("fromId").unwrap(), "node:parent-123");
         assert_eq!(parsed.get("toId").unwrap(), "node:child-456");
         assert_eq!(parsed.get("relationshipType").unwrap(), "has_child");
-        assert_eq!(parsed.get("properties").unwrap().get("order").unwrap(), 1.5);
+        assert_eq!(
+            parsed.get("properties").unwrap().get("order").unwrap(),
+            "a0"
+        );
 
         // Test member_of relationship (collection membership)
         let member_of = RelationshipEvent {
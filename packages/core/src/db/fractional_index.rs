@@ -0,0 +1,377 @@
+//! Fractional index keys for sibling order
+//!
+//! `has_child` edges order siblings, and `member_of` edges the members of a
+//! collection, by a string key instead of a float. Keys compare bytewise and
+//! there is always room for another key between any two, so repeated inserts
+//! at the same spot never run out of precision. The scheme is the base-62 one
+//! popularised by Figma: an integer part whose first character encodes its
+//! length (`a0`, `a1`, ..., `az`, `b00`, ...) followed by an optional fraction
+//! that never ends in `0`.
+//!
+//! Keys for single inserts also carry a short suffix derived from the node ID
+//! (see [`FractionalIndex::key_for_node`]). Two clients inserting different
+//! nodes between the same siblings compute distinct keys without coordinating,
+//! and replaying an insert for the same node yields the same key.
+
+/// Base-62 digits in ascending byte order
+const DIGITS: &[u8; 62] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+/// The lowest integer part; it has no predecessor, so it is not a valid key
+const SMALLEST_INTEGER: &str = "A00000000000000000000000000";
+
+/// Characters appended by [`FractionalIndex::key_for_node`]
+const NODE_SUFFIX_LEN: usize = 5;
+
+/// Keys longer than this are compacted by renumbering their siblings
+pub const MAX_KEY_LEN: usize = 48;
+
+/// Generates order keys for `has_child` and `member_of` edges
+pub struct FractionalIndex;
+
+impl FractionalIndex {
+    /// Shortest key strictly between `prev` and `next` (`None` = open end)
+    ///
+    /// The result is never a prefix of `next`, so it stays below `next` when
+    /// extended.
+    ///
+    /// # Examples
+    /// ```text
+    /// key_between(None, None)               => "a0"
+    /// key_between(Some("a0"), None)         => "a1"
+    /// key_between(Some("a0"), Some("a1"))   => "a0V"
+    /// key_between(None, Some("a0"))         => "Zz"
+    /// ```
+    pub fn key_between(prev: Option<&str>, next: Option<&str>) -> Result<String, String> {
+        if let (Some(a), Some(b)) = (prev, next) {
+            if a >= b {
+                return Err(format!("Order key '{}' is not below '{}'", a, b));
+            }
+        }
+
+        let key = match (prev, next) {
+            (None, None) => b"a0".to_vec(),
+            (None, Some(b)) => {
+                let (ib, fb) = split_key(b)?;
+                if fb.is_empty() {
+                    decrement_integer(ib).ok_or_else(|| format!("No order key below '{}'", b))?
+                } else {
+                    concat(ib, &midpoint(&[], Some(fb)))
+                }
+            }
+            (Some(a), None) => {
+                let (ia, fa) = split_key(a)?;
+                match increment_integer(ia) {
+                    Some(next_int) => next_int,
+                    None => concat(ia, &midpoint(fa, None)),
+                }
+            }
+            (Some(a), Some(b)) => {
+                let (ia, fa) = split_key(a)?;
+                let (ib, fb) = split_key(b)?;
+                if ia == ib {
+                    concat(ia, &midpoint(fa, Some(fb)))
+                } else {
+                    match increment_integer(ia) {
+                        Some(next_int)
+                            if next_int.as_slice() < b.as_bytes()
+                                && !b.as_bytes().starts_with(&next_int) =>
+                        {
+                            next_int
+                        }
+                        _ => concat(ia, &midpoint(fa, None)),
+                    }
+                }
+            }
+        };
+
+        Ok(String::from_utf8(key).expect("order keys are ASCII"))
+    }
+
+    /// Key between `prev` and `next` for inserting `node_id`
+    ///
+    /// Deterministic: the same neighbours and node always give the same key,
+    /// and different nodes get different keys.
+    pub fn key_for_node(
+        prev: Option<&str>,
+        next: Option<&str>,
+        node_id: &str,
+    ) -> Result<String, String> {
+        let mut key = Self::key_between(prev, next)?;
+        key.push_str(&node_suffix(node_id));
+        Ok(key)
+    }
+
+    /// The `n`th key of an evenly spaced sequence (`a0`, `a1`, ..., `az`, `b00`, ...)
+    ///
+    /// Used for bulk inserts, rebalancing and migrating float orders.
+    pub fn nth(n: usize) -> String {
+        let mut n = n;
+        let mut len = 1u32;
+        loop {
+            match 62usize.checked_pow(len) {
+                Some(block) if n >= block => {
+                    n -= block;
+                    len += 1;
+                }
+                _ => break,
+            }
+        }
+
+        let mut digits = vec![b'0'; len as usize];
+        for digit in digits.iter_mut().rev() {
+            *digit = DIGITS[n % 62];
+            n /= 62;
+        }
+        let mut key = vec![b'a' + (len - 1) as u8];
+        key.extend(digits);
+        String::from_utf8(key).expect("order keys are ASCII")
+    }
+
+    /// Whether `key` is a well-formed order key
+    pub fn is_valid(key: &str) -> bool {
+        split_key(key).is_ok()
+    }
+
+    /// Whether sibling keys (in stored order) should be renumbered
+    ///
+    /// True when a key is malformed (such as a legacy float), overly long, or
+    /// not strictly above its predecessor.
+    pub fn needs_rebalancing(keys: &[&str]) -> bool {
+        keys.iter()
+            .any(|key| key.len() > MAX_KEY_LEN || !Self::is_valid(key))
+            || keys.windows(2).any(|pair| pair[0] >= pair[1])
+    }
+}
+
+fn digit_value(c: u8) -> usize {
+    match c {
+        b'0'..=b'9' => (c - b'0') as usize,
+        b'A'..=b'Z' => (c - b'A') as usize + 10,
+        _ => (c - b'a') as usize + 36,
+    }
+}
+
+fn concat(a: &[u8], b: &[u8]) -> Vec<u8> {
+    let mut out = a.to_vec();
+    out.extend_from_slice(b);
+    out
+}
+
+/// Split a key into its integer part and fraction, validating both
+fn split_key(key: &str) -> Result<(&[u8], &[u8]), String> {
+    let bytes = key.as_bytes();
+    let invalid = || format!("Invalid order key '{}'", key);
+
+    let integer_len = match bytes.first() {
+        Some(&head @ b'a'..=b'z') => (head - b'a') as usize + 2,
+        Some(&head @ b'A'..=b'Z') => (b'Z' - head) as usize + 2,
+        _ => return Err(invalid()),
+    };
+    if integer_len > bytes.len()
+        || key == SMALLEST_INTEGER
+        || !bytes[1..].iter().all(u8::is_ascii_alphanumeric)
+    {
+        return Err(invalid());
+    }
+
+    let (integer, fraction) = bytes.split_at(integer_len);
+    if fraction.last() == Some(&b'0') {
+        return Err(invalid());
+    }
+    Ok((integer, fraction))
+}
+
+/// Fraction strictly between `a` and `b` (`None` = 1), never a prefix of `b`
+fn midpoint(a: &[u8], b: Option<&[u8]>) -> Vec<u8> {
+    if let Some(b) = b {
+        // Shared leading digits (treating a missing digit of `a` as 0)
+        let n = b
+            .iter()
+            .enumerate()
+            .take_while(|&(i, &digit)| a.get(i).copied().unwrap_or(b'0') == digit)
+            .count();
+        if n > 0 {
+            return concat(&b[..n], &midpoint(a.get(n..).unwrap_or(&[]), Some(&b[n..])));
+        }
+    }
+
+    let digit_a = a.first().map_or(0, |&c| digit_value(c));
+    let digit_b = b.map_or(62, |b| digit_value(b[0]));
+    if digit_b - digit_a > 1 {
+        return vec![DIGITS[(digit_a + digit_b).div_ceil(2)]];
+    }
+    match b {
+        // Adjacent first digits and `b` continues: go below the rest of `b`
+        Some(b) if b.len() > 1 => concat(&b[..1], &midpoint(&[], Some(&b[1..]))),
+        _ => concat(
+            &[DIGITS[digit_a]],
+            &midpoint(a.get(1..).unwrap_or(&[]), None),
+        ),
+    }
+}
+
+fn increment_integer(integer: &[u8]) -> Option<Vec<u8>> {
+    let head = integer[0];
+    let mut digits = integer[1..].to_vec();
+    let mut carry = true;
+    for digit in digits.iter_mut().rev() {
+        let value = digit_value(*digit) + 1;
+        if value == 62 {
+            *digit = b'0';
+        } else {
+            *digit = DIGITS[value];
+            carry = false;
+            break;
+        }
+    }
+    if !carry {
+        return Some(concat(&[head], &digits));
+    }
+
+    match head {
+        b'Z' => Some(b"a0".to_vec()),
+        b'z' => None,
+        _ => {
+            let head = head + 1;
+            if head > b'a' {
+                digits.push(b'0');
+            } else {
+                digits.pop();
+            }
+            Some(concat(&[head], &digits))
+        }
+    }
+}
+
+fn decrement_integer(integer: &[u8]) -> Option<Vec<u8>> {
+    let head = integer[0];
+    let mut digits = integer[1..].to_vec();
+    let mut borrow = true;
+    for digit in digits.iter_mut().rev() {
+        let value = digit_value(*digit);
+        if value == 0 {
+            *digit = b'z';
+        } else {
+            *digit = DIGITS[value - 1];
+            borrow = false;
+            break;
+        }
+    }
+    if !borrow {
+        return Some(concat(&[head], &digits));
+    }
+
+    match head {
+        b'a' => Some(b"Zz".to_vec()),
+        b'A' => None,
+        _ => {
+            let head = head - 1;
+            if head < b'Z' {
+                digits.push(b'z');
+            } else {
+                digits.pop();
+            }
+            Some(concat(&[head], &digits))
+        }
+    }
+}
+
+/// Stable base-62 digest of a node ID (FNV-1a), never ending in `0`
+fn node_suffix(node_id: &str) -> String {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in node_id.bytes() {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+
+    let mut suffix = String::with_capacity(NODE_SUFFIX_LEN);
+    for _ in 1..NODE_SUFFIX_LEN {
+        suffix.push(DIGITS[(hash % 62) as usize] as char);
+        hash /= 62;
+    }
+    suffix.push(DIGITS[1 + (hash % 61) as usize] as char);
+    suffix
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn between(prev: Option<&str>, next: Option<&str>) -> String {
+        FractionalIndex::key_between(prev, next).unwrap()
+    }
+
+    #[test]
+    fn test_key_between() {
+        assert_eq!(between(None, None), "a0");
+        assert_eq!(between(Some("a0"), None), "a1");
+        assert_eq!(between(Some("az"), None), "b00");
+        assert_eq!(between(None, Some("a0")), "Zz");
+        assert_eq!(between(Some("a0"), Some("a1")), "a0V");
+        assert_eq!(between(Some("a0"), Some("a0V")), "a0G");
+        assert_eq!(between(Some("a1"), Some("a3")), "a2");
+
+        // Never a prefix of `next`, so suffixed keys stay below it
+        let key = between(None, Some("a0V"));
+        assert!(key.as_str() < "a0V" && !"a0V".starts_with(&key));
+        let key = between(Some("Zz"), Some("a0V"));
+        assert!(key.as_str() > "Zz" && !"a0V".starts_with(&key));
+
+        assert!(FractionalIndex::key_between(Some("a1"), Some("a0")).is_err());
+        assert!(FractionalIndex::key_between(Some("1.5"), None).is_err());
+    }
+
+    #[test]
+    fn test_key_for_node_is_deterministic_and_distinct() {
+        let a = FractionalIndex::key_for_node(Some("a0"), Some("a1"), "node-1").unwrap();
+        let b = FractionalIndex::key_for_node(Some("a0"), Some("a1"), "node-2").unwrap();
+        assert_eq!(
+            a,
+            FractionalIndex::key_for_node(Some("a0"), Some("a1"), "node-1").unwrap()
+        );
+        assert_ne!(a, b);
+        for key in [&a, &b] {
+            assert!(FractionalIndex::is_valid(key));
+            assert!("a0" < key.as_str() && key.as_str() < "a1");
+        }
+    }
+
+    #[test]
+    fn test_random_inserts_stay_sorted() {
+        let mut keys: Vec<String> = Vec::new();
+        let mut seed: u64 = 42;
+        for i in 0..500 {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            let at = (seed >> 33) as usize % (keys.len() + 1);
+            let prev = at.checked_sub(1).map(|p| keys[p].as_str());
+            let next = keys.get(at).map(String::as_str);
+            let key = FractionalIndex::key_for_node(prev, next, &format!("node-{}", i)).unwrap();
+            keys.insert(at, key);
+        }
+        let refs: Vec<&str> = keys.iter().map(String::as_str).collect();
+        assert!(refs.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(refs.iter().all(|key| FractionalIndex::is_valid(key)));
+    }
+
+    #[test]
+    fn test_nth_is_increasing() {
+        assert_eq!(FractionalIndex::nth(0), "a0");
+        assert_eq!(FractionalIndex::nth(61), "az");
+        assert_eq!(FractionalIndex::nth(62), "b00");
+        let keys: Vec<String> = (0..5000).map(FractionalIndex::nth).collect();
+        assert!(keys.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(keys.iter().all(|key| FractionalIndex::is_valid(key)));
+    }
+
+    #[test]
+    fn test_needs_rebalancing() {
+        assert!(!FractionalIndex::needs_rebalancing(&["a0", "a0V", "a1"]));
+        assert!(FractionalIndex::needs_rebalancing(&["a0", "a0"]));
+        assert!(FractionalIndex::needs_rebalancing(&["a1", "a0"]));
+        assert!(FractionalIndex::needs_rebalancing(&["1", "2"]));
+        let long = format!("a0{}", "V".repeat(MAX_KEY_LEN));
+        assert!(FractionalIndex::needs_rebalancing(&["a0", &long]));
+    }
+}
@@ -0,0 +1,249 @@
+//! Store Health - Startup Diagnosis and Recovery
+//!
+//! Opening the database can fail in ways the user can do something about:
+//! the RocksDB lock is held (another process, or a lock file a crashed run
+//! left on a synced or network folder), the data is corrupt, or nodes use
+//! types whose schema is gone. [`StoreHealth`] names the problem and the
+//! [`RecoveryAction`]s that apply, so the app can offer them instead of a
+//! generic init failure.
+//!
+//! Lock and corruption problems are read from the error of
+//! [`SurrealStore::new`](super::SurrealStore::new) via
+//! [`StoreHealth::from_open_error`]. Schema problems need an open store and
+//! come from [`SurrealStore::health_check`](super::SurrealStore::health_check).
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// RocksDB's lock file inside the database directory
+pub const LOCK_FILE: &str = "LOCK";
+
+/// File every RocksDB database directory contains
+const CURRENT_FILE: &str = "CURRENT";
+
+/// Result of checking the database at startup
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(
+    tag = "status",
+    rename_all = "snake_case",
+    rename_all_fields = "camelCase"
+)]
+pub enum StoreHealth {
+    Healthy,
+    /// The RocksDB lock could not be taken
+    Locked {
+        lock_file: PathBuf,
+    },
+    /// RocksDB found corrupt data
+    Corrupt {
+        detail: String,
+    },
+    /// The database could not be opened for another reason
+    Unreadable {
+        detail: String,
+    },
+    /// Nodes use types that have no schema node
+    SchemaMismatch {
+        unknown_types: Vec<String>,
+    },
+}
+
+/// Ways out of an unhealthy state, offered by the startup recovery flow
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RecoveryAction {
+    /// Try opening again (e.g. after quitting the process holding the lock)
+    Retry,
+    /// Fix the problem in place: remove a stale lock file, or register
+    /// placeholder schemas for unknown types
+    Repair,
+    /// Open the database as it is, with every change refused, to read or
+    /// export what it holds
+    OpenReadOnly,
+    /// Replace the database with a copy the user picks
+    RestoreFromBackup,
+}
+
+impl StoreHealth {
+    /// Diagnose a failed [`SurrealStore::new`](super::SurrealStore::new)
+    pub fn from_open_error(db_path: &Path, error: &anyhow::Error) -> Self {
+        let detail = format!("{:#}", error);
+        let lowered = detail.to_lowercase();
+        if lowered.contains("lock file") || lowered.contains("resource temporarily unavailable") {
+            StoreHealth::Locked {
+                lock_file: db_path.join(LOCK_FILE),
+            }
+        } else if lowered.contains("corruption") {
+            StoreHealth::Corrupt { detail }
+        } else {
+            StoreHealth::Unreadable { detail }
+        }
+    }
+
+    pub fn is_healthy(&self) -> bool {
+        matches!(self, StoreHealth::Healthy)
+    }
+
+    pub fn actions(&self) -> Vec<RecoveryAction> {
+        use RecoveryAction::*;
+        match self {
+            StoreHealth::Healthy => vec![],
+            StoreHealth::Locked { .. } => vec![Retry, Repair],
+            StoreHealth::Corrupt { .. } => vec![RestoreFromBackup],
+            StoreHealth::Unreadable { .. } => vec![Retry, RestoreFromBackup],
+            StoreHealth::SchemaMismatch { .. } => vec![Repair, OpenReadOnly, RestoreFromBackup],
+        }
+    }
+
+    /// What went wrong, for the recovery screen
+    pub fn message(&self) -> String {
+        match self {
+            StoreHealth::Healthy => "The database is healthy".to_string(),
+            StoreHealth::Locked { lock_file } => format!(
+                "The database is in use by another process, or a lock file was left behind \
+                 by one that did not shut down ({})",
+                lock_file.display()
+            ),
+            StoreHealth::Corrupt { detail } => format!("The database is corrupt: {}", detail),
+            StoreHealth::Unreadable { detail } => {
+                format!("The database could not be opened: {}", detail)
+            }
+            StoreHealth::SchemaMismatch { unknown_types } => format!(
+                "Some nodes use types with no schema: {}",
+                unknown_types.join(", ")
+            ),
+        }
+    }
+}
+
+/// Remove the RocksDB lock file so the next open can take the lock
+///
+/// Only safe when no other process has the database open. Returns whether
+/// there was a lock file.
+pub fn remove_stale_lock(db_path: &Path) -> io::Result<bool> {
+    match fs::remove_file(db_path.join(LOCK_FILE)) {
+        Ok(()) => Ok(true),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
+/// Replace the database with a copy of `backup_path`
+///
+/// The current database is moved aside rather than deleted; its new location
+/// is returned (`None` when there was nothing at `db_path`).
+pub fn restore_from_backup(db_path: &Path, backup_path: &Path) -> io::Result<Option<PathBuf>> {
+    if !backup_path.join(CURRENT_FILE).is_file() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("{} is not a database folder", backup_path.display()),
+        ));
+    }
+
+    let moved_to = if db_path.exists() {
+        let mut aside = db_path.as_os_str().to_owned();
+        aside.push(format!(
+            ".broken-{}",
+            chrono::Local::now().format("%Y%m%d-%H%M%S")
+        ));
+        let aside = PathBuf::from(aside);
+        fs::rename(db_path, &aside)?;
+        Some(aside)
+    } else {
+        None
+    };
+
+    copy_dir(backup_path, db_path)?;
+    Ok(moved_to)
+}
+
+fn copy_dir(from: &Path, to: &Path) -> io::Result<()> {
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let target = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir(&entry.path(), &target)?;
+        } else if entry.file_name() != LOCK_FILE {
+            fs::copy(entry.path(), target)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_open_errors_are_classified() {
+        let db_path = Path::new("/data/nodespace.db");
+
+        let locked = anyhow::anyhow!(
+            "IO error: While lock file: /data/nodespace.db/LOCK: Resource temporarily unavailable"
+        )
+        .context("Failed to initialize SurrealDB with RocksDB backend");
+        assert_eq!(
+            StoreHealth::from_open_error(db_path, &locked),
+            StoreHealth::Locked {
+                lock_file: db_path.join(LOCK_FILE)
+            }
+        );
+
+        let corrupt = anyhow::anyhow!("Corruption: block checksum mismatch");
+        assert!(matches!(
+            StoreHealth::from_open_error(db_path, &corrupt),
+            StoreHealth::Corrupt { .. }
+        ));
+        assert_eq!(
+            StoreHealth::from_open_error(db_path, &corrupt).actions(),
+            vec![RecoveryAction::RestoreFromBackup]
+        );
+
+        let other = anyhow::anyhow!("Permission denied");
+        assert!(matches!(
+            StoreHealth::from_open_error(db_path, &other),
+            StoreHealth::Unreadable { .. }
+        ));
+    }
+
+    #[test]
+    fn test_restore_moves_current_database_aside() {
+        let temp = TempDir::new().unwrap();
+        let db_path = temp.path().join("nodespace.db");
+        let backup = temp.path().join("backup.db");
+        fs::create_dir_all(&db_path).unwrap();
+        fs::write(db_path.join(CURRENT_FILE), "broken").unwrap();
+        fs::create_dir_all(backup.join("archive")).unwrap();
+        fs::write(backup.join(CURRENT_FILE), "good").unwrap();
+        fs::write(backup.join(LOCK_FILE), "").unwrap();
+        fs::write(backup.join("archive").join("000001.log"), "x").unwrap();
+
+        let moved_to = restore_from_backup(&db_path, &backup).unwrap().unwrap();
+        assert_eq!(
+            fs::read_to_string(moved_to.join(CURRENT_FILE)).unwrap(),
+            "broken"
+        );
+        assert_eq!(
+            fs::read_to_string(db_path.join(CURRENT_FILE)).unwrap(),
+            "good"
+        );
+        assert!(db_path.join("archive").join("000001.log").exists());
+        assert!(!db_path.join(LOCK_FILE).exists());
+
+        // Anything without a CURRENT file is not a database
+        let err = restore_from_backup(&db_path, temp.path()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn test_remove_stale_lock() {
+        let temp = TempDir::new().unwrap();
+        fs::write(temp.path().join(LOCK_FILE), "").unwrap();
+        assert!(remove_stale_lock(temp.path()).unwrap());
+        assert!(!remove_stale_lock(temp.path()).unwrap());
+    }
+}
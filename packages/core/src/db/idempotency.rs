@@ -0,0 +1,197 @@
+//! Idempotency Keys - Remembered Outcomes of Mutating Requests
+//!
+//! A client that times out waiting for a create or move can't tell whether it
+//! happened, so it retries. When the request carries an idempotency key, the
+//! first attempt claims the key in the `idempotency_key` table and stores its
+//! response once it succeeds; a retry with the same key gets that response
+//! back instead of running again. Reached through [`SurrealStore::idempotency`].
+//!
+//! Claims are atomic (`CREATE` fails on an existing record), so of two
+//! concurrent attempts only one runs. A claim is a short lease, so a request
+//! that dies without completing or releasing its key doesn't block retries
+//! for long; completing it keeps the response for the longer response TTL.
+//! Expired records are replaced on the next claim of their key and purged by
+//! [`IdempotencyStore::purge_expired`].
+
+use super::surreal_store::SurrealStore;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::time::Duration;
+
+/// A key's record, when another request already claimed it
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct IdempotencyRecord {
+    pub operation: String,
+    /// Hash of the request that claimed the key
+    pub request_hash: String,
+    /// Serialized response, `None` while the first request is still running
+    pub response: Option<String>,
+}
+
+/// Idempotency key records of a store
+pub struct IdempotencyStore<'a, C>
+where
+    C: surrealdb::Connection,
+{
+    store: &'a SurrealStore<C>,
+}
+
+impl<C> SurrealStore<C>
+where
+    C: surrealdb::Connection,
+{
+    pub fn idempotency(&self) -> IdempotencyStore<'_, C> {
+        IdempotencyStore { store: self }
+    }
+}
+
+impl<C> IdempotencyStore<'_, C>
+where
+    C: surrealdb::Connection,
+{
+    /// Claim `key` for a request, unless it is already claimed
+    ///
+    /// The claim lapses after `lease` unless [`Self::complete`] stores a
+    /// response first. Returns `None` when the caller now holds the key and
+    /// should run the request, or the record of the request that holds it.
+    pub async fn claim(
+        &self,
+        key: &str,
+        operation: &str,
+        request_hash: &str,
+        lease: Duration,
+    ) -> Result<Option<IdempotencyRecord>> {
+        let expires_at = expires_at(lease)?;
+        let mut response = self
+            .store
+            .query(
+                "DELETE type::thing('idempotency_key', $key) WHERE expires_at < time::now();
+                CREATE type::thing('idempotency_key', $key) CONTENT {
+                    operation: $operation,
+                    request_hash: $request_hash,
+                    response: NONE,
+                    expires_at: <datetime> $expires_at
+                };",
+            )
+            .bind(("key", key.to_string()))
+            .bind(("operation", operation.to_string()))
+            .bind(("request_hash", request_hash.to_string()))
+            .bind(("expires_at", expires_at.to_rfc3339()))
+            .await
+            .context("Failed to claim idempotency key")?;
+
+        // The CREATE fails when the key is held
+        if !response.take_errors().contains_key(&1) {
+            return Ok(None);
+        }
+        let mut response = self
+            .store
+            .query(
+                "SELECT operation, request_hash, response
+                FROM type::thing('idempotency_key', $key);",
+            )
+            .bind(("key", key.to_string()))
+            .await
+            .context("Failed to read idempotency key")?;
+        let record: Option<IdempotencyRecord> =
+            response.take(0).context("Failed to read idempotency key")?;
+        // Gone again if its request failed in the meantime
+        record
+            .map(Some)
+            .context("Idempotency key was released while claiming it")
+    }
+
+    /// Store the response of the request holding `key`, kept for `ttl`
+    pub async fn complete(&self, key: &str, response: &str, ttl: Duration) -> Result<()> {
+        let expires_at = expires_at(ttl)?;
+        self.store
+            .query(
+                "UPDATE type::thing('idempotency_key', $key)
+                SET response = $response, expires_at = <datetime> $expires_at;",
+            )
+            .bind(("key", key.to_string()))
+            .bind(("response", response.to_string()))
+            .bind(("expires_at", expires_at.to_rfc3339()))
+            .await
+            .context("Failed to store idempotent response")?
+            .check()
+            .context("Failed to store idempotent response")?;
+        Ok(())
+    }
+
+    /// Give up `key` after its request failed, so a retry runs it again
+    pub async fn release(&self, key: &str) -> Result<()> {
+        self.store
+            .query("DELETE type::thing('idempotency_key', $key);")
+            .bind(("key", key.to_string()))
+            .await
+            .context("Failed to release idempotency key")?;
+        Ok(())
+    }
+
+    /// Delete expired records; returns how many
+    pub async fn purge_expired(&self) -> Result<u64> {
+        let mut response = self
+            .store
+            .query(
+                "LET $expired = (SELECT VALUE id FROM idempotency_key WHERE expires_at < time::now());
+                DELETE idempotency_key WHERE id INSIDE $expired;
+                RETURN array::len($expired);",
+            )
+            .await
+            .context("Failed to purge idempotency keys")?;
+        let purged: Option<u64> = response
+            .take(2)
+            .context("Failed to read purged idempotency key count")?;
+        Ok(purged.unwrap_or(0))
+    }
+}
+
+fn expires_at(ttl: Duration) -> Result<DateTime<Utc>> {
+    Ok(Utc::now() + chrono::Duration::from_std(ttl).context("Idempotency TTL out of range")?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_claim_complete_and_expire() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let store = SurrealStore::new(temp_dir.path().join("test.db")).await?;
+        let keys = store.idempotency();
+        let ttl = Duration::from_secs(60 * 60);
+
+        assert_eq!(keys.claim("k1", "create_node", "h1", ttl).await?, None);
+        let pending = keys.claim("k1", "create_node", "h1", ttl).await?.unwrap();
+        assert_eq!(pending.response, None);
+
+        keys.complete("k1", "\"node-1\"", ttl).await?;
+        let done = keys.claim("k1", "create_node", "h1", ttl).await?.unwrap();
+        assert_eq!(done.request_hash, "h1");
+        assert_eq!(done.response.as_deref(), Some("\"node-1\""));
+
+        // A released key can be claimed again
+        keys.release("k1").await?;
+        assert_eq!(keys.claim("k1", "create_node", "h2", ttl).await?, None);
+
+        // Expired records give way to a new claim and are purged
+        keys.claim("k2", "delete_node", "h3", Duration::ZERO)
+            .await?;
+        assert_eq!(keys.purge_expired().await?, 1);
+        assert_eq!(keys.claim("k2", "delete_node", "h4", ttl).await?, None);
+
+        // An abandoned claim lapses with its lease; a completed one outlives it
+        keys.claim("k3", "create_node", "h5", Duration::ZERO)
+            .await?;
+        assert_eq!(keys.claim("k3", "create_node", "h6", ttl).await?, None);
+        keys.claim("k4", "create_node", "h7", Duration::ZERO)
+            .await?;
+        keys.complete("k4", "\"node-4\"", ttl).await?;
+        let done = keys.claim("k4", "create_node", "h7", ttl).await?.unwrap();
+        assert_eq!(done.response.as_deref(), Some("\"node-4\""));
+        Ok(())
+    }
+}
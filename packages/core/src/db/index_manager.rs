@@ -1,42 +1,210 @@
-//! Dynamic Index Management for SurrealDB
+//! Property Index Management for SurrealDB
 //!
-//! **STATUS: Deferred to future performance optimization**
+//! Schema fields marked `indexed: true` get a SurrealDB index on their
+//! namespaced location in the universal `node` table, e.g. the task `status`
+//! field lives at `properties.task.status` and is indexed as
+//! `idx_prop_task__status`. Without these, property filters scan every node.
 //!
-//! Dynamic indexing deferred to future performance optimization work.
-//! Will be re-implemented with SurrealDB-native index management when needed.
+//! Like `SchemaTableManager`, this is a pure DDL generator: callers run the
+//! statements, usually in the same transaction as the schema node write.
 //!
-//! **Future Work (separate performance optimization issue):**
-//! - Use SurrealDB's DEFINE INDEX syntax
-//! - Implement query-driven index creation
-//! - Adapt to SurrealDB's indexing capabilities
+//! ## Naming
+//!
+//! Property indexes are `idx_prop_<type>__<field>` (hyphens in type names
+//! become underscores). The prefix marks indexes this module owns, so
+//! [`IndexManager::sync_ddl`] can drop stale ones without touching the static
+//! indexes from `schema.surql`.
+
+use crate::models::SchemaField;
 
-use crate::db::error::DatabaseError;
+/// Prefix of every index managed here
+pub const PROPERTY_INDEX_PREFIX: &str = "idx_prop_";
 
-/// Index manager for SurrealDB (temporarily disabled)
-pub struct IndexManager {}
+/// Generates DDL for schema-driven property indexes on the `node` table
+#[derive(Default)]
+pub struct IndexManager;
 
 impl IndexManager {
-    /// Create a new index manager (returns stub)
     pub fn new() -> Self {
-        tracing::debug!(
-            "IndexManager stubbed - dynamic indexing deferred to performance optimization"
-        );
-        Self {}
+        Self
+    }
+
+    /// Index name for a field of a node type
+    pub fn property_index_name(node_type: &str, field: &str) -> String {
+        format!(
+            "{}{}__{}",
+            PROPERTY_INDEX_PREFIX,
+            node_type.replace('-', "_"),
+            field
+        )
+    }
+
+    /// `DEFINE INDEX` statements for every indexed field of `node_type`
+    ///
+    /// Names are spliced into DDL, so fields (or types) outside the safe
+    /// character set are skipped with a warning rather than failing the
+    /// schema write; they simply stay unindexed.
+    pub fn index_ddl(&self, node_type: &str, fields: &[SchemaField]) -> Vec<String> {
+        if !is_safe_identifier(node_type, true) {
+            tracing::warn!(
+                "Not indexing fields of type '{}': unsupported name",
+                node_type
+            );
+            return Vec::new();
+        }
+        fields
+            .iter()
+            .filter(|field| field.indexed)
+            .filter(|field| {
+                let safe = is_safe_identifier(&field.name, false);
+                if !safe {
+                    tracing::warn!(
+                        "Not indexing field '{}.{}': unsupported name",
+                        node_type,
+                        field.name
+                    );
+                }
+                safe
+            })
+            .map(|field| {
+                format!(
+                    "DEFINE INDEX IF NOT EXISTS {} ON TABLE node COLUMNS properties.`{}`.{};",
+                    Self::property_index_name(node_type, &field.name),
+                    node_type,
+                    field.name
+                )
+            })
+            .collect()
     }
 
-    /// Stub: Create JSON path index
-    pub fn create_json_path_index(
+    /// Bring the indexes of `node_type` in line with its fields
+    ///
+    /// Defines indexes for indexed fields and removes those in
+    /// `existing_indexes` (names, e.g. from `SurrealStore::list_indexes`) that
+    /// belong to this type but no longer match an indexed field.
+    pub fn sync_ddl(
         &self,
-        _node_type: &str,
-        _property_name: &str,
-    ) -> Result<(), DatabaseError> {
-        // No-op: SurrealDB handles indexing differently
-        Ok(())
+        node_type: &str,
+        fields: &[SchemaField],
+        existing_indexes: &[String],
+    ) -> Vec<String> {
+        let mut statements = self.index_ddl(node_type, fields);
+
+        let type_prefix = Self::property_index_name(node_type, "");
+        let wanted: Vec<String> = fields
+            .iter()
+            .filter(|field| field.indexed)
+            .map(|field| Self::property_index_name(node_type, &field.name))
+            .collect();
+        for name in existing_indexes {
+            if name.starts_with(&type_prefix) && !wanted.contains(name) {
+                statements.push(format!("REMOVE INDEX IF EXISTS {} ON TABLE node;", name));
+            }
+        }
+        statements
+    }
+
+    /// `REMOVE INDEX` statements for property indexes of types not in `node_types`
+    ///
+    /// Cleans up after deleted schemas.
+    pub fn orphan_ddl(&self, node_types: &[&str], existing_indexes: &[String]) -> Vec<String> {
+        let prefixes: Vec<String> = node_types
+            .iter()
+            .map(|node_type| Self::property_index_name(node_type, ""))
+            .collect();
+        existing_indexes
+            .iter()
+            .filter(|name| name.starts_with(PROPERTY_INDEX_PREFIX))
+            .filter(|name| !prefixes.iter().any(|prefix| name.starts_with(prefix)))
+            .map(|name| format!("REMOVE INDEX IF EXISTS {} ON TABLE node;", name))
+            .collect()
     }
 }
 
-impl Default for IndexManager {
-    fn default() -> Self {
-        Self::new()
+/// ASCII word characters (plus `-` for type names), without the `__` separator
+fn is_safe_identifier(name: &str, allow_hyphen: bool) -> bool {
+    !name.is_empty()
+        && !name.contains("__")
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || (allow_hyphen && c == '-'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::schema::SchemaProtectionLevel;
+
+    fn field(name: &str, indexed: bool) -> SchemaField {
+        SchemaField {
+            name: name.to_string(),
+            field_type: "string".to_string(),
+            protection: SchemaProtectionLevel::User,
+            core_values: None,
+            user_values: None,
+            indexed,
+            required: None,
+            recommended: None,
+            extensible: None,
+            default: None,
+            description: None,
+            item_type: None,
+            fields: None,
+            item_fields: None,
+        }
+    }
+
+    #[test]
+    fn test_index_ddl_covers_indexed_fields() {
+        let ddl = IndexManager::new().index_ddl(
+            "code-block",
+            &[field("language", true), field("notes", false)],
+        );
+        assert_eq!(
+            ddl,
+            vec![
+                "DEFINE INDEX IF NOT EXISTS idx_prop_code_block__language ON TABLE node COLUMNS properties.`code-block`.language;"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sync_ddl_drops_stale_indexes_of_same_type() {
+        let existing = vec![
+            "idx_prop_task__status".to_string(),
+            "idx_prop_task__priority".to_string(),
+            "idx_prop_task_list__status".to_string(),
+            "idx_node_type".to_string(),
+        ];
+        let ddl = IndexManager::new().sync_ddl(
+            "task",
+            &[field("status", true), field("priority", false)],
+            &existing,
+        );
+        assert_eq!(ddl.len(), 2);
+        assert!(ddl[0].starts_with("DEFINE INDEX IF NOT EXISTS idx_prop_task__status "));
+        assert_eq!(
+            ddl[1],
+            "REMOVE INDEX IF EXISTS idx_prop_task__priority ON TABLE node;"
+        );
+
+        assert_eq!(
+            IndexManager::new().orphan_ddl(&["task"], &existing),
+            vec!["REMOVE INDEX IF EXISTS idx_prop_task_list__status ON TABLE node;"]
+        );
+    }
+
+    #[test]
+    fn test_skips_unsafe_identifiers() {
+        let manager = IndexManager::new();
+        assert!(manager.index_ddl("task;", &[field("a", true)]).is_empty());
+        assert!(manager.index_ddl("task", &[field("a b", true)]).is_empty());
+        assert!(manager.index_ddl("task", &[field("a-b", true)]).is_empty());
+        assert_eq!(
+            manager
+                .index_ddl("task", &[field("a__b", true), field("ok", true)])
+                .len(),
+            1
+        );
     }
 }
@@ -0,0 +1,283 @@
+//! Store Maintenance - Size Reporting and Cleanup
+//!
+//! Housekeeping that keeps the embedded database from growing without bound,
+//! reached through [`SurrealStore::maintenance`]:
+//!
+//! - [`StoreMaintenance::table_sizes`] counts each table's rows and estimates
+//!   the bytes they take
+//! - [`StoreMaintenance::purge_deleted_nodes`] removes nodes that have been in
+//!   the trash (`lifecycle_status = "deleted"`) longer than a retention period
+//! - [`StoreMaintenance::vacuum_orphaned_embeddings`] removes embedding vectors
+//!   whose root node no longer exists
+//! - expired idempotency keys are purged (see [`super::idempotency`])
+//!
+//! SurrealDB does not expose RocksDB's manual compaction, so space freed here
+//! is returned to the file system by RocksDB's background compaction.
+
+use super::surreal_store::{SurrealStore, RECORD_BROKEN_LINKS};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Tables covered by [`StoreMaintenance::table_sizes`]
+pub const MAINTAINED_TABLES: &[&str] = &[
+    "node",
+    "relationship",
+    "embedding",
+    "node_importance",
+    "broken_link",
+    "share",
+    "workspace_lock",
+    "idempotency_key",
+];
+
+/// How long a deleted node stays in the trash before it is purged
+pub const DEFAULT_TRASH_RETENTION: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+
+/// Row count and estimated size of one table
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TableSize {
+    pub table: String,
+    pub rows: u64,
+    /// Length of the rows' serialized form; on-disk size is lower after
+    /// RocksDB compression, so this is only good for comparing tables
+    pub approx_bytes: u64,
+}
+
+/// Outcome of a full [`StoreMaintenance::run`]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MaintenanceReport {
+    pub purged_nodes: u64,
+    pub vacuumed_embeddings: u64,
+    pub expired_idempotency_keys: u64,
+    /// Table sizes after cleanup
+    pub tables: Vec<TableSize>,
+}
+
+/// Maintenance operations on a store
+pub struct StoreMaintenance<'a, C>
+where
+    C: surrealdb::Connection,
+{
+    store: &'a SurrealStore<C>,
+}
+
+impl<C> SurrealStore<C>
+where
+    C: surrealdb::Connection,
+{
+    pub fn maintenance(&self) -> StoreMaintenance<'_, C> {
+        StoreMaintenance { store: self }
+    }
+}
+
+impl<C> StoreMaintenance<'_, C>
+where
+    C: surrealdb::Connection,
+{
+    /// Purge expired trash and idempotency keys, vacuum embeddings, then
+    /// report table sizes
+    pub async fn run(&self, trash_retention: Duration) -> Result<MaintenanceReport> {
+        let purged_nodes = self.purge_deleted_nodes(trash_retention).await?;
+        let vacuumed_embeddings = self.vacuum_orphaned_embeddings().await?;
+        let expired_idempotency_keys = self.store.idempotency().purge_expired().await?;
+        let tables = self.table_sizes().await?;
+        tracing::info!(
+            "Database maintenance: purged {} nodes, vacuumed {} embeddings, expired {} idempotency keys",
+            purged_nodes,
+            vacuumed_embeddings,
+            expired_idempotency_keys
+        );
+        Ok(MaintenanceReport {
+            purged_nodes,
+            vacuumed_embeddings,
+            expired_idempotency_keys,
+            tables,
+        })
+    }
+
+    pub async fn table_sizes(&self) -> Result<Vec<TableSize>> {
+        #[derive(Debug, Default, Deserialize)]
+        struct SizeRow {
+            #[serde(default)]
+            rows: u64,
+            #[serde(default)]
+            bytes: u64,
+        }
+
+        // Table names are constants, not user input
+        let sql: String = MAINTAINED_TABLES
+            .iter()
+            .map(|table| {
+                format!(
+                    "SELECT count() AS rows, math::sum(string::len(<string> $this)) AS bytes \
+                     FROM {} GROUP ALL;",
+                    table
+                )
+            })
+            .collect();
+        let mut response = self
+            .store
+            .query(sql)
+            .await
+            .context("Failed to measure table sizes")?;
+
+        let mut sizes = Vec::with_capacity(MAINTAINED_TABLES.len());
+        for (index, table) in MAINTAINED_TABLES.iter().enumerate() {
+            let row: Option<SizeRow> = response
+                .take(index)
+                .with_context(|| format!("Failed to read size of table {}", table))?;
+            let row = row.unwrap_or_default();
+            sizes.push(TableSize {
+                table: table.to_string(),
+                rows: row.rows,
+                approx_bytes: row.bytes,
+            });
+        }
+        Ok(sizes)
+    }
+
+    /// Permanently delete nodes in the trash for longer than `retention`
+    ///
+    /// Uses `modified_at` as the time a node was deleted. A node with children
+    /// that are not deleted themselves is kept, so purging never turns live
+    /// children into roots. Relationships, embeddings and importance scores
+    /// of purged nodes go with them; links to them from live content are
+    /// recorded as broken links. No domain events are emitted: deleted nodes
+    /// are already hidden everywhere. Returns the number of nodes removed.
+    pub async fn purge_deleted_nodes(&self, retention: Duration) -> Result<u64> {
+        let mut response = self
+            .store
+            .query(format!(
+                "
+                LET $targets = (
+                    SELECT VALUE id FROM node
+                    WHERE lifecycle_status = 'deleted'
+                        AND modified_at < time::now() - type::duration($retention)
+                        AND count(->relationship[WHERE relationship_type = 'has_child'
+                            AND out.lifecycle_status != 'deleted']) = 0
+                );
+                {RECORD_BROKEN_LINKS}
+                DELETE relationship WHERE in INSIDE $targets OR out INSIDE $targets;
+                DELETE embedding WHERE node INSIDE $targets;
+                DELETE node_importance WHERE node INSIDE $targets;
+                DELETE node WHERE id INSIDE $targets;
+                RETURN array::len($targets);
+                ",
+            ))
+            .bind(("retention", format!("{}s", retention.as_secs())))
+            .await
+            .context("Failed to purge deleted nodes")?;
+
+        // Statements: 0 LET, 1 FOR, 2 DELETE broken_link, 3-6 DELETE, 7 RETURN
+        let purged: Option<u64> = response
+            .take(7)
+            .context("Failed to read purged node count")?;
+        Ok(purged.unwrap_or(0))
+    }
+
+    /// Delete embeddings whose root node is gone; returns how many
+    pub async fn vacuum_orphaned_embeddings(&self) -> Result<u64> {
+        let mut response = self
+            .store
+            .query(
+                "
+                LET $orphans = (SELECT VALUE id FROM embedding WHERE node.id IS NONE);
+                DELETE embedding WHERE id INSIDE $orphans;
+                RETURN array::len($orphans);
+                ",
+            )
+            .await
+            .context("Failed to vacuum orphaned embeddings")?;
+
+        let vacuumed: Option<u64> = response
+            .take(2)
+            .context("Failed to read vacuumed embedding count")?;
+        Ok(vacuumed.unwrap_or(0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Node;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    async fn create_test_store() -> Result<(SurrealStore, TempDir)> {
+        let temp_dir = TempDir::new()?;
+        let store = SurrealStore::new(temp_dir.path().join("test.db")).await?;
+        Ok((store, temp_dir))
+    }
+
+    #[tokio::test]
+    async fn test_purge_keeps_recent_trash_and_live_children() -> Result<()> {
+        let (store, _temp_dir) = create_test_store().await?;
+
+        let old = store
+            .create_node(Node::new("text".into(), "Old".into(), json!({})), None)
+            .await?;
+        let recent = store
+            .create_node(Node::new("text".into(), "Recent".into(), json!({})), None)
+            .await?;
+        let parent = store
+            .create_node(Node::new("text".into(), "Parent".into(), json!({})), None)
+            .await?;
+        store
+            .create_child_node_atomic(&parent.id, "text", "Child", json!({}), None)
+            .await?;
+
+        for node in [&old, &recent, &parent] {
+            store.update_lifecycle_status(&node.id, "deleted").await?;
+        }
+        store
+            .db()
+            .query("UPDATE node SET modified_at = time::now() - 60d WHERE id INSIDE $ids")
+            .bind((
+                "ids",
+                vec![
+                    surrealdb::sql::Thing::from(("node".to_string(), old.id.clone())),
+                    surrealdb::sql::Thing::from(("node".to_string(), parent.id.clone())),
+                ],
+            ))
+            .await?;
+
+        let purged = store
+            .maintenance()
+            .purge_deleted_nodes(DEFAULT_TRASH_RETENTION)
+            .await?;
+        assert_eq!(purged, 1);
+        assert!(store.get_node(&old.id).await?.is_none());
+        assert!(store.get_node(&recent.id).await?.is_some());
+        assert!(store.get_node(&parent.id).await?.is_some());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_vacuum_removes_embeddings_of_missing_nodes() -> Result<()> {
+        let (store, _temp_dir) = create_test_store().await?;
+        let node = store
+            .create_node(Node::new("text".into(), "Kept".into(), json!({})), None)
+            .await?;
+
+        for node_id in [node.id.as_str(), "gone"] {
+            store
+                .db()
+                .query(
+                    "CREATE embedding SET node = type::thing('node', $node_id), vector = $vector",
+                )
+                .bind(("node_id", node_id.to_string()))
+                .bind(("vector", vec![0.1_f32; 768]))
+                .await?;
+        }
+
+        assert_eq!(store.maintenance().vacuum_orphaned_embeddings().await?, 1);
+        let sizes = store.maintenance().table_sizes().await?;
+        let embedding = sizes.iter().find(|s| s.table == "embedding").unwrap();
+        assert_eq!(embedding.rows, 1);
+        assert!(embedding.approx_bytes > 0);
+        Ok(())
+    }
+}
@@ -21,14 +21,28 @@
 
 mod error;
 pub mod events;
-pub mod fractional_ordering;
+pub mod fractional_index;
+pub mod health;
+pub mod idempotency;
 mod index_manager;
+pub mod maintenance;
+pub mod process_lock;
+pub mod retry;
+pub mod slow_query;
 mod surreal_store;
 
 pub use error::DatabaseError;
 pub use events::{DomainEvent, RelationshipEvent};
-pub use fractional_ordering::FractionalOrderCalculator;
+pub use fractional_index::FractionalIndex;
+pub use health::{RecoveryAction, StoreHealth};
+pub use idempotency::{IdempotencyRecord, IdempotencyStore};
 pub use index_manager::IndexManager;
+pub use maintenance::{MaintenanceReport, StoreMaintenance, TableSize};
+pub use process_lock::{LockOwner, ProcessLock, ProcessLockError};
+pub use retry::RetryPolicy;
+pub use slow_query::{SlowQuery, SlowQueryAlert, SlowQueryLog, TimedQuery};
 pub use surreal_store::{
-    EmbeddedStore, HttpStore, RelationshipRecord, StoreChange, StoreOperation, SurrealStore,
+    BulkItemResult, BulkUpsertResult, CollectionMove, CollectionStats, ContentWrite, EdgeUpsert,
+    EmbeddedStore, HttpStore, IndexInfo, IndexStats, RelationshipRecord, StoreChange,
+    StoreOperation, SubCollection, SurrealStore, DEFAULT_BULK_CHUNK_SIZE, DEFAULT_QUERY_TIMEOUT,
 };
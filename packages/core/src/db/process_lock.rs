@@ -0,0 +1,145 @@
+//! Process Lock - One Process per Workspace
+//!
+//! Only one process can open a database (RocksDB holds its own `LOCK` file).
+//! The desktop app and the standalone `nodespace-mcp` server take this
+//! advisory lock before opening the store and record themselves in it, so
+//! whichever comes second can name the process that has the workspace
+//! instead of failing with a RocksDB lock error.
+//!
+//! The operating system releases the lock when the [`ProcessLock`] is dropped
+//! or its process exits, so unlike RocksDB's lock file it is never left
+//! behind by a crash. The lock file itself stays.
+
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File, OpenOptions, TryLockError};
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Which process holds a workspace
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LockOwner {
+    /// Program name, e.g. "nodespace-app"
+    pub program: String,
+    pub pid: u32,
+}
+
+impl LockOwner {
+    /// This process, as `program`
+    pub fn current(program: &str) -> Self {
+        Self {
+            program: program.to_string(),
+            pid: std::process::id(),
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum ProcessLockError {
+    /// Another process has the workspace; its owner record may be unreadable
+    /// (e.g. mid-write, or on platforms where locked files can't be read)
+    #[error("{}", held_message(.0))]
+    Held(Option<LockOwner>),
+
+    #[error("Failed to lock {path}: {source}")]
+    Io { path: PathBuf, source: io::Error },
+}
+
+fn held_message(owner: &Option<LockOwner>) -> String {
+    match owner {
+        Some(owner) => format!(
+            "The workspace is open in {} (pid {})",
+            owner.program, owner.pid
+        ),
+        None => "The workspace is open in another NodeSpace process".to_string(),
+    }
+}
+
+/// Exclusive hold on a workspace, released on drop
+#[derive(Debug)]
+pub struct ProcessLock {
+    _file: File,
+}
+
+impl ProcessLock {
+    /// Lock the workspace whose database is at `db_path`
+    ///
+    /// Fails with [`ProcessLockError::Held`] without waiting if another
+    /// process holds it.
+    pub fn acquire(db_path: &Path, owner: &LockOwner) -> Result<Self, ProcessLockError> {
+        let path = lock_path(db_path);
+        let io_error = |source| ProcessLockError::Io {
+            path: path.clone(),
+            source,
+        };
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(io_error)?;
+        }
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&path)
+            .map_err(io_error)?;
+        match file.try_lock() {
+            Ok(()) => {}
+            Err(TryLockError::WouldBlock) => {
+                let mut record = String::new();
+                let owner = file
+                    .read_to_string(&mut record)
+                    .ok()
+                    .and_then(|_| serde_json::from_str(&record).ok());
+                return Err(ProcessLockError::Held(owner));
+            }
+            Err(TryLockError::Error(e)) => return Err(io_error(e)),
+        }
+
+        let record = serde_json::to_vec(owner).map_err(|e| io_error(e.into()))?;
+        file.set_len(0).map_err(io_error)?;
+        file.write_all(&record).map_err(io_error)?;
+        file.sync_all().map_err(io_error)?;
+        Ok(Self { _file: file })
+    }
+}
+
+/// Lock file of the database at `db_path`, next to its directory
+pub fn lock_path(db_path: &Path) -> PathBuf {
+    let mut name = db_path.file_name().unwrap_or_default().to_os_string();
+    name.push(".process.lock");
+    db_path.with_file_name(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_second_process_sees_the_owner() {
+        let dir = TempDir::new().unwrap();
+        let db_path = dir.path().join("database").join("nodespace");
+        assert_eq!(
+            lock_path(&db_path),
+            dir.path().join("database").join("nodespace.process.lock")
+        );
+
+        let app = LockOwner::current("nodespace-app");
+        let lock = ProcessLock::acquire(&db_path, &app).unwrap();
+
+        // A separate open of the file conflicts just as another process would
+        match ProcessLock::acquire(&db_path, &LockOwner::current("nodespace-mcp")) {
+            Err(ProcessLockError::Held(owner)) => {
+                if let Some(owner) = owner {
+                    assert_eq!(owner, app);
+                }
+            }
+            other => panic!("expected the workspace to be held, got {:?}", other),
+        }
+
+        drop(lock);
+        assert!(ProcessLock::acquire(&db_path, &LockOwner::current("nodespace-mcp")).is_ok());
+    }
+}
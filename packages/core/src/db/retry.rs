@@ -0,0 +1,117 @@
+//! Retry Policy for Transient Store Errors
+//!
+//! Concurrent writers (the editor, MCP agents, the embedding processor) can
+//! race on the same records. SurrealDB then aborts one transaction with a
+//! read/write conflict that asks to be retried, and RocksDB reports lock
+//! contention the same way. Rather than surfacing those as raw "failed
+//! transaction" errors, [`SurrealStore`](super::SurrealStore) re-runs the
+//! affected writes under a [`RetryPolicy`]: exponential backoff with jitter,
+//! up to a maximum number of attempts.
+
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+
+/// Messages of SurrealDB and RocksDB errors that succeed when retried
+const TRANSIENT_ERROR_MARKERS: &[&str] = &[
+    "can be retried",
+    "read or write conflict",
+    "Transaction conflict",
+    "Resource busy",
+    "TryAgain",
+];
+
+/// How often and how fast transient failures are retried
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Total attempts including the first; 1 disables retrying
+    pub max_attempts: u32,
+    /// Delay before the first retry, doubled for each one after it
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 4,
+            initial_backoff: Duration::from_millis(20),
+            max_backoff: Duration::from_millis(500),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Policy that runs every operation exactly once
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Self::default()
+        }
+    }
+
+    /// Delay before retry number `retry` (1-based)
+    ///
+    /// "Equal jitter": half the exponential delay plus a random share of the
+    /// other half, so racing writers don't retry in lockstep.
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let exponential = self
+            .initial_backoff
+            .saturating_mul(2u32.saturating_pow(retry.saturating_sub(1)))
+            .min(self.max_backoff);
+        let half = exponential / 2;
+        let jitter = (half.as_nanos() as u64).checked_mul(random_per_mille() as u64);
+        half + Duration::from_nanos(jitter.map_or(0, |j| j / 1000))
+    }
+}
+
+/// Whether `error` is a conflict or contention failure worth retrying
+pub fn is_transient(error: &anyhow::Error) -> bool {
+    error.chain().any(|cause| {
+        let message = cause.to_string();
+        TRANSIENT_ERROR_MARKERS
+            .iter()
+            .any(|marker| message.contains(marker))
+    })
+}
+
+/// Random value in `0..=1000`; jitter doesn't warrant a `rand` dependency
+fn random_per_mille() -> u32 {
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u64(
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_nanos() as u64),
+    );
+    (hasher.finish() % 1001) as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_grows_within_jitter_bounds() {
+        let policy = RetryPolicy {
+            max_attempts: 5,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_millis(300),
+        };
+        for (retry, full) in [(1, 100), (2, 200), (3, 300), (4, 300)] {
+            let delay = policy.backoff(retry);
+            assert!(delay >= Duration::from_millis(full / 2), "retry {}", retry);
+            assert!(delay <= Duration::from_millis(full), "retry {}", retry);
+        }
+    }
+
+    #[test]
+    fn test_only_conflicts_are_transient() {
+        let conflict = anyhow::anyhow!(
+            "Failed to commit transaction due to a read or write conflict. \
+             This transaction can be retried"
+        )
+        .context("Failed to move node");
+        assert!(is_transient(&conflict));
+        assert!(!is_transient(&anyhow::anyhow!("Parent node not found: p1")));
+    }
+}
@@ -0,0 +1,416 @@
+//! Slow Query Log - Store Queries Over Their Time Budget
+//!
+//! Queries run through [`SurrealStore::query`] are timed. One that takes
+//! longer than the threshold ([`DEFAULT_SLOW_QUERY_THRESHOLD`], or
+//! `NODESPACE_SLOW_QUERY_MS` milliseconds with `0` turning the log off) is
+//! logged and kept in a rolling log of the last [`SLOW_QUERY_LOG_CAPACITY`],
+//! which the diagnostics read. Entries keep the SQL with its string literals
+//! masked and only the names of its parameters, never their values, so note
+//! content doesn't end up in them.
+//!
+//! One slow query is noise. [`SLOW_QUERY_ALERT_COUNT`] of them within
+//! [`SLOW_QUERY_ALERT_WINDOW`] are reported to [`SlowQueryLog::subscribe`]rs
+//! as a [`SlowQueryAlert`], at most once per window.
+
+use super::surreal_store::SurrealStore;
+use crate::utils::current_correlation_id;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::future::{Future, IntoFuture};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use surrealdb::method::Query;
+use surrealdb::Response;
+use tokio::sync::broadcast;
+
+/// Queries taking longer than this are slow, unless configured otherwise
+pub const DEFAULT_SLOW_QUERY_THRESHOLD: Duration = Duration::from_millis(250);
+
+/// Environment variable overriding the threshold, in milliseconds
+pub const SLOW_QUERY_THRESHOLD_ENV: &str = "NODESPACE_SLOW_QUERY_MS";
+
+/// Most recent slow queries kept
+pub const SLOW_QUERY_LOG_CAPACITY: usize = 100;
+
+/// Slow queries within [`SLOW_QUERY_ALERT_WINDOW`] that raise an alert
+pub const SLOW_QUERY_ALERT_COUNT: usize = 5;
+
+pub const SLOW_QUERY_ALERT_WINDOW: Duration = Duration::from_secs(60);
+
+/// Longest SQL text kept per entry; bulk transactions run to megabytes
+const MAX_SQL_CHARS: usize = 2000;
+
+const ALERT_CHANNEL_CAPACITY: usize = 16;
+
+/// A query that took longer than the threshold
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SlowQuery {
+    /// Statement text with string literals replaced by `'?'`
+    pub sql: String,
+    /// Parameters the statement uses
+    pub binds: Vec<String>,
+    pub duration_ms: u64,
+    pub at: DateTime<Utc>,
+    /// Request that ran the query, when it ran inside one
+    pub correlation_id: Option<String>,
+}
+
+/// Repeated slow queries: the store is over its time budget
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SlowQueryAlert {
+    /// Slow queries within the window
+    pub slow_queries: usize,
+    pub window_secs: u64,
+    pub threshold_ms: u64,
+    /// The query that raised the alert
+    pub latest: SlowQuery,
+}
+
+#[derive(Default)]
+struct LogState {
+    entries: VecDeque<SlowQuery>,
+    /// When the slow queries of the current window finished
+    recent: VecDeque<Instant>,
+    last_alert: Option<Instant>,
+}
+
+/// Rolling log of a store's slow queries
+pub struct SlowQueryLog {
+    /// Threshold in milliseconds, 0 when disabled
+    threshold_ms: AtomicU64,
+    state: Mutex<LogState>,
+    alert_tx: broadcast::Sender<SlowQueryAlert>,
+}
+
+impl SlowQueryLog {
+    pub fn new(threshold: Option<Duration>) -> Self {
+        let (alert_tx, _) = broadcast::channel(ALERT_CHANNEL_CAPACITY);
+        let log = Self {
+            threshold_ms: AtomicU64::new(0),
+            state: Mutex::new(LogState::default()),
+            alert_tx,
+        };
+        log.set_threshold(threshold);
+        log
+    }
+
+    /// A log with the threshold from `NODESPACE_SLOW_QUERY_MS`, or the default
+    pub fn from_env() -> Self {
+        let threshold = match std::env::var(SLOW_QUERY_THRESHOLD_ENV)
+            .ok()
+            .and_then(|ms| ms.parse::<u64>().ok())
+        {
+            Some(0) => None,
+            Some(ms) => Some(Duration::from_millis(ms)),
+            None => Some(DEFAULT_SLOW_QUERY_THRESHOLD),
+        };
+        Self::new(threshold)
+    }
+
+    /// Duration above which a query is slow, `None` when the log is off
+    pub fn threshold(&self) -> Option<Duration> {
+        match self.threshold_ms.load(Ordering::Relaxed) {
+            0 => None,
+            ms => Some(Duration::from_millis(ms)),
+        }
+    }
+
+    pub fn set_threshold(&self, threshold: Option<Duration>) {
+        let ms = threshold.map_or(0, |t| (t.as_millis() as u64).max(1));
+        self.threshold_ms.store(ms, Ordering::Relaxed);
+    }
+
+    /// The logged slow queries, oldest first
+    pub fn entries(&self) -> Vec<SlowQuery> {
+        self.lock().entries.iter().cloned().collect()
+    }
+
+    pub fn clear(&self) {
+        *self.lock() = LogState::default();
+    }
+
+    /// Receive an alert whenever slow queries exceed the budget
+    pub fn subscribe(&self) -> broadcast::Receiver<SlowQueryAlert> {
+        self.alert_tx.subscribe()
+    }
+
+    /// Log `sql` if it took longer than the threshold
+    pub fn record(&self, sql: &str, duration: Duration) {
+        let Some(threshold) = self.threshold() else {
+            return;
+        };
+        if duration < threshold {
+            return;
+        }
+
+        let entry = SlowQuery {
+            sql: redact_sql(sql),
+            binds: parameter_names(sql),
+            duration_ms: duration.as_millis() as u64,
+            at: Utc::now(),
+            correlation_id: current_correlation_id(),
+        };
+        tracing::warn!(
+            duration_ms = entry.duration_ms,
+            correlation_id = entry.correlation_id.as_deref().unwrap_or("-"),
+            "Slow query: {}",
+            entry.sql
+        );
+
+        let now = Instant::now();
+        let alert = {
+            let mut state = self.lock();
+            if state.entries.len() == SLOW_QUERY_LOG_CAPACITY {
+                state.entries.pop_front();
+            }
+            state.entries.push_back(entry.clone());
+
+            state.recent.push_back(now);
+            while state
+                .recent
+                .front()
+                .is_some_and(|at| now.duration_since(*at) > SLOW_QUERY_ALERT_WINDOW)
+            {
+                state.recent.pop_front();
+            }
+            let quiet = state
+                .last_alert
+                .is_none_or(|at| now.duration_since(at) >= SLOW_QUERY_ALERT_WINDOW);
+            (state.recent.len() >= SLOW_QUERY_ALERT_COUNT && quiet).then(|| {
+                state.last_alert = Some(now);
+                SlowQueryAlert {
+                    slow_queries: state.recent.len(),
+                    window_secs: SLOW_QUERY_ALERT_WINDOW.as_secs(),
+                    threshold_ms: threshold.as_millis() as u64,
+                    latest: entry,
+                }
+            })
+        };
+
+        if let Some(alert) = alert {
+            tracing::warn!(
+                "{} slow queries within {}s, over the {}ms budget",
+                alert.slow_queries,
+                alert.window_secs,
+                alert.threshold_ms
+            );
+            // No subscribers is fine
+            let _ = self.alert_tx.send(alert);
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, LogState> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// A query being built through [`SurrealStore::query`], timed when awaited
+pub struct TimedQuery<'r, C>
+where
+    C: surrealdb::Connection,
+{
+    query: Query<'r, C>,
+    sql: String,
+    log: &'r SlowQueryLog,
+}
+
+impl<'r, C> TimedQuery<'r, C>
+where
+    C: surrealdb::Connection,
+{
+    pub(crate) fn new(store: &'r SurrealStore<C>, sql: String) -> Self {
+        Self {
+            query: store.db().query(sql.as_str()),
+            sql,
+            log: store.slow_queries(),
+        }
+    }
+
+    /// Bind parameters, as [`Query::bind`]
+    pub fn bind(self, bindings: impl Serialize + 'static) -> Self {
+        Self {
+            query: self.query.bind(bindings),
+            ..self
+        }
+    }
+
+    /// Append statements, as [`Query::query`]
+    pub fn query(mut self, sql: impl Into<String>) -> Self {
+        let sql = sql.into();
+        self.query = self.query.query(sql.as_str());
+        self.sql.push('\n');
+        self.sql.push_str(&sql);
+        self
+    }
+}
+
+impl<'r, C> IntoFuture for TimedQuery<'r, C>
+where
+    C: surrealdb::Connection,
+{
+    type Output = surrealdb::Result<Response>;
+    type IntoFuture = Pin<Box<dyn Future<Output = Self::Output> + Send + 'r>>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        let Self { query, sql, log } = self;
+        let response = query.into_future();
+        Box::pin(async move {
+            let _timer = QueryTimer {
+                sql,
+                log,
+                started: Instant::now(),
+            };
+            response.await
+        })
+    }
+}
+
+/// Records its query when dropped, so one cancelled by the query timeout
+/// is logged too
+struct QueryTimer<'r> {
+    sql: String,
+    log: &'r SlowQueryLog,
+    started: Instant,
+}
+
+impl Drop for QueryTimer<'_> {
+    fn drop(&mut self) {
+        self.log.record(&self.sql, self.started.elapsed());
+    }
+}
+
+/// `sql` with its string literals masked, shortened to [`MAX_SQL_CHARS`]
+fn redact_sql(sql: &str) -> String {
+    let mut redacted = String::with_capacity(sql.len().min(MAX_SQL_CHARS));
+    let mut chars = sql.trim().chars();
+    while let Some(c) = chars.next() {
+        if redacted.len() >= MAX_SQL_CHARS {
+            redacted.push('…');
+            break;
+        }
+        redacted.push(c);
+        if c == '\'' || c == '"' {
+            // Skip to the closing quote, honouring backslash escapes
+            let mut escaped = false;
+            for inner in chars.by_ref() {
+                match inner {
+                    '\\' if !escaped => escaped = true,
+                    quote if quote == c && !escaped => break,
+                    _ => escaped = false,
+                }
+            }
+            redacted.push('?');
+            redacted.push(c);
+        }
+    }
+    redacted
+}
+
+/// Names of the `$parameters` in `sql`, without the ones it defines itself
+fn parameter_names(sql: &str) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    let mut defined: Vec<String> = Vec::new();
+    for (i, _) in sql.match_indices('$') {
+        let name: String = sql[i + 1..]
+            .chars()
+            .take_while(|c| c.is_ascii_alphanumeric() || *c == '_')
+            .collect();
+        if name.is_empty() {
+            continue;
+        }
+        let keyword = sql[..i]
+            .trim_end()
+            .rsplit(|c: char| !c.is_ascii_alphabetic())
+            .next()
+            .unwrap_or_default();
+        if keyword.eq_ignore_ascii_case("LET") || keyword.eq_ignore_ascii_case("FOR") {
+            defined.push(name);
+        } else if !defined.contains(&name) && !names.contains(&name) {
+            names.push(name);
+        }
+    }
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sql_literals_are_redacted() {
+        assert_eq!(
+            redact_sql("SELECT * FROM node WHERE content CONTAINS 'my \\'secret\\' plan' LIMIT 5"),
+            "SELECT * FROM node WHERE content CONTAINS '?' LIMIT 5"
+        );
+        assert_eq!(
+            redact_sql(r#"UPDATE node SET title = "diary" WHERE id = $id"#),
+            r#"UPDATE node SET title = "?" WHERE id = $id"#
+        );
+        assert!(redact_sql(&"x".repeat(5000)).chars().count() <= MAX_SQL_CHARS + 1);
+    }
+
+    #[test]
+    fn test_parameter_names_skip_local_variables() {
+        let sql = "LET $expired = (SELECT VALUE id FROM t WHERE at < $cutoff);
+            FOR $row IN $expired { DELETE $row; };
+            UPDATE $target SET n = $n, m = $n;";
+        assert_eq!(parameter_names(sql), vec!["cutoff", "target", "n"]);
+    }
+
+    #[test]
+    fn test_only_slow_queries_are_logged() {
+        let log = SlowQueryLog::new(Some(Duration::from_millis(100)));
+        log.record("SELECT 1", Duration::from_millis(99));
+        log.record(
+            "SELECT * FROM node WHERE id = $id",
+            Duration::from_millis(150),
+        );
+
+        let entries = log.entries();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].duration_ms, 150);
+        assert_eq!(entries[0].binds, vec!["id"]);
+
+        log.set_threshold(None);
+        log.record("SELECT 2", Duration::from_secs(10));
+        assert_eq!(log.entries().len(), 1);
+    }
+
+    #[test]
+    fn test_log_keeps_the_most_recent_entries() {
+        let log = SlowQueryLog::new(Some(Duration::from_millis(1)));
+        for i in 0..SLOW_QUERY_LOG_CAPACITY + 3 {
+            log.record(&format!("SELECT {}", i), Duration::from_millis(5));
+        }
+        let entries = log.entries();
+        assert_eq!(entries.len(), SLOW_QUERY_LOG_CAPACITY);
+        assert_eq!(entries[0].sql, "SELECT 3");
+    }
+
+    #[test]
+    fn test_repeated_slow_queries_raise_one_alert_per_window() {
+        let log = SlowQueryLog::new(Some(Duration::from_millis(10)));
+        let mut alerts = log.subscribe();
+
+        for _ in 0..SLOW_QUERY_ALERT_COUNT - 1 {
+            log.record("SELECT 1", Duration::from_millis(20));
+        }
+        assert!(alerts.try_recv().is_err());
+
+        log.record("SELECT 2", Duration::from_millis(30));
+        let alert = alerts.try_recv().unwrap();
+        assert_eq!(alert.slow_queries, SLOW_QUERY_ALERT_COUNT);
+        assert_eq!(alert.threshold_ms, 10);
+        assert_eq!(alert.latest.sql, "SELECT 2");
+
+        log.record("SELECT 3", Duration::from_millis(30));
+        assert!(alerts.try_recv().is_err());
+    }
+}
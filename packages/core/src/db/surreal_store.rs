@@ -45,16 +45,23 @@
 //! }
 //! ```
 
+use crate::db::error::DatabaseError;
 use crate::db::events::DomainEvent;
-use crate::db::fractional_ordering::FractionalOrderCalculator;
+use crate::db::fractional_index::FractionalIndex;
+use crate::db::health::StoreHealth;
+use crate::db::retry::{self, RetryPolicy};
+use crate::db::slow_query::{SlowQueryLog, TimedQuery};
 use crate::models::{DeleteResult, Node, NodeQuery, NodeUpdate};
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::future::Future;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 use surrealdb::engine::local::{Db, RocksDb};
 use surrealdb::engine::remote::http::{Client, Http};
 use surrealdb::opt::auth::Root;
@@ -70,6 +77,108 @@ use tracing::warn;
 /// the current state, not historical events.
 const DOMAIN_EVENT_CHANNEL_CAPACITY: usize = 128;
 
+/// Default limit for queries run through [`SurrealStore::with_query_timeout`]
+pub const DEFAULT_QUERY_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Environment variable overriding [`DEFAULT_QUERY_TIMEOUT`], in milliseconds
+/// (`0` disables the timeout)
+pub const QUERY_TIMEOUT_ENV: &str = "NODESPACE_QUERY_TIMEOUT_MS";
+
+/// SurrealDB's message for a statement stopped by its `TIMEOUT` clause
+const ENGINE_TIMEOUT_MESSAGE: &str = "exceeded the timeout";
+
+/// Default number of nodes (or edges) per transaction in [`SurrealStore::bulk_upsert`]
+pub const DEFAULT_BULK_CHUNK_SIZE: usize = 500;
+
+/// Query timeout from `NODESPACE_QUERY_TIMEOUT_MS`, or the default
+fn query_timeout_from_env() -> Option<Duration> {
+    match std::env::var(QUERY_TIMEOUT_ENV)
+        .ok()
+        .and_then(|ms| ms.parse::<u64>().ok())
+    {
+        Some(0) => None,
+        Some(ms) => Some(Duration::from_millis(ms)),
+        None => Some(DEFAULT_QUERY_TIMEOUT),
+    }
+}
+
+/// Stored form of a query timeout, `0` meaning disabled
+fn timeout_millis(timeout: Option<Duration>) -> u64 {
+    timeout.map_or(0, |t| (t.as_millis() as u64).max(1))
+}
+
+/// Statements recording the links to the `$targets` node records about to be
+/// deleted as `broken_link`s, and dropping those held by the targets
+///
+/// Must run before the targets' relationships are deleted.
+pub(crate) const RECORD_BROKEN_LINKS: &str = r"
+    FOR $link IN (
+        SELECT in, out, relationship_type FROM relationship
+        WHERE out INSIDE $targets AND in NOT INSIDE $targets
+            AND relationship_type INSIDE ['mentions', 'embeds']
+            AND out.node_type != 'date'
+    ) {
+        CREATE broken_link CONTENT {
+            source: $link.in,
+            target_id: record::id($link.out),
+            target_title: $link.out.title ?? string::split($link.out.content, '\n')[0] ?? '',
+            link_type: $link.relationship_type
+        };
+    };
+    DELETE broken_link WHERE source INSIDE $targets;
+";
+
+/// Relationship written by [`SurrealStore::bulk_upsert`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EdgeUpsert {
+    pub from_id: String,
+    pub to_id: String,
+    pub relationship_type: String,
+    #[serde(default)]
+    pub properties: Value,
+}
+
+/// New content for one node, written by [`SurrealStore::replace_contents`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContentWrite {
+    pub node_id: String,
+    /// Content the node must still have for the write to happen
+    pub expected: String,
+    pub content: String,
+    /// New title, for nodes that have one
+    pub title: Option<String>,
+}
+
+/// Outcome of one node or edge in a [`SurrealStore::bulk_upsert`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkItemResult {
+    /// Node ID, or `from->to` for an edge
+    pub id: String,
+    /// Why the item was not written, `None` if it was
+    pub error: Option<String>,
+}
+
+/// Per-item results of a [`SurrealStore::bulk_upsert`], in input order
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkUpsertResult {
+    pub nodes: Vec<BulkItemResult>,
+    pub edges: Vec<BulkItemResult>,
+}
+
+impl BulkUpsertResult {
+    /// Number of nodes and edges that were not written
+    pub fn failed(&self) -> usize {
+        self.nodes
+            .iter()
+            .chain(&self.edges)
+            .filter(|item| item.error.is_some())
+            .count()
+    }
+}
+
 /// Represents an relationship from the universal relationship table
 ///
 /// Used for bulk loading relationships (e.g., tree structure on startup).
@@ -91,13 +200,78 @@ pub struct RelationshipRecord {
     pub properties: Value,
 }
 
+/// Aggregate figures for one collection, see [`SurrealStore::get_collection_stats`]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CollectionStats {
+    /// Members of the collection itself, sub-collections excluded
+    pub direct_members: usize,
+    /// Distinct members of the collection and all its sub-collections
+    pub recursive_members: usize,
+    /// Collections nested below this one, at any depth
+    pub subcollections: usize,
+    /// Latest edit of a member or membership change anywhere in the subtree
+    pub last_activity: Option<DateTime<Utc>>,
+    /// Recursive members by node type
+    pub types: BTreeMap<String, usize>,
+}
+
+/// A collection below another, see [`SurrealStore::get_subcollections`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubCollection {
+    pub id: String,
+    pub name: String,
+    /// Parent collections within the subtree
+    pub parent_ids: Vec<String>,
+}
+
+/// Outcome of [`SurrealStore::move_collection`]
+#[derive(Debug, Clone)]
+pub struct CollectionMove {
+    /// The collection after the rename
+    pub collection: Node,
+    /// member_of edge to the previous parent, if it was removed
+    pub removed_edge: Option<String>,
+    /// member_of edge to the new parent and its order key, if one was created
+    pub created_edge: Option<(String, String)>,
+}
+
+/// A table index as reported by `INFO FOR TABLE`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IndexInfo {
+    pub name: String,
+    /// Full `DEFINE INDEX` statement
+    pub definition: String,
+}
+
+/// How much of the `node` table a property index covers
+///
+/// SurrealDB keeps no per-index hit counters, so coverage is the practical
+/// signal: an index whose field is set on few nodes is cheap, one covering
+/// most of a large table is what turns property filters from scans into seeks.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IndexStats {
+    pub name: String,
+    /// Indexed column, e.g. `properties.task.status`
+    pub column: String,
+    /// Nodes with the indexed field set
+    pub entries: u64,
+    /// All nodes in the table
+    pub table_rows: u64,
+}
+
 impl RelationshipRecord {
-    /// Get the order property for has_child relationships
-    pub fn order(&self) -> f64 {
+    /// Get the order key for has_child relationships
+    pub fn order(&self) -> &str {
         self.properties
             .get("order")
-            .and_then(|v| v.as_f64())
-            .unwrap_or(0.0)
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+    }
+
+    /// Whether this has_child edge transcludes a node owned by another parent
+    pub fn is_reference(&self) -> bool {
+        self.properties.get("reference") == Some(&Value::Bool(true))
     }
 }
 
@@ -231,6 +405,7 @@ impl From<SurrealNode> for Node {
             member_of: Vec::new(),
             title: sn.title,
             lifecycle_status: sn.lifecycle_status,
+            rollups: BTreeMap::new(),
         }
     }
 }
@@ -269,6 +444,16 @@ where
     ///
     /// Set via `set_notifier()` after construction.
     notifier: Option<StoreNotifier>,
+    /// Query timeout in milliseconds, 0 when disabled
+    ///
+    /// Atomic so it can be changed on the shared `Arc<SurrealStore>`.
+    query_timeout_ms: AtomicU64,
+    /// Retries for writes that fail on transaction conflicts or lock contention
+    retry_policy: RetryPolicy,
+    /// Queries that took longer than their budget
+    slow_queries: SlowQueryLog,
+    /// Opened without schema setup or migrations; see [`SurrealStore::open`]
+    read_only: bool,
 }
 
 /// Type alias for embedded RocksDB store
@@ -307,6 +492,17 @@ impl SurrealStore<Db> {
     /// # }
     /// ```
     pub async fn new(db_path: PathBuf) -> Result<Self> {
+        Self::open(db_path, false).await
+    }
+
+    /// Open an embedded store, optionally without writing to it
+    ///
+    /// With `read_only`, opening skips the schema DDL and the order key
+    /// migration, so a backup or a synced copy is left byte for byte as it
+    /// was; `NodeService::new` then skips schema seeding and index sync and
+    /// starts in read-only mode. Legacy float orders still sort correctly
+    /// unmigrated.
+    pub async fn open(db_path: PathBuf, read_only: bool) -> Result<Self> {
         // Initialize embedded RocksDb
         let db = Surreal::new::<RocksDb>(db_path)
             .await
@@ -324,7 +520,9 @@ impl SurrealStore<Db> {
 
         // Initialize schema (create tables from schema.surql)
         // Note: Schema nodes are seeded by NodeService, not here (Issue #704)
-        Self::initialize_schema(&db).await?;
+        if !read_only {
+            Self::initialize_schema(&db).await?;
+        }
 
         // Build valid node types cache from schema definitions (Issue #691)
         let valid_node_types = Self::build_schema_caches(&db).await?;
@@ -332,12 +530,20 @@ impl SurrealStore<Db> {
         // Initialize broadcast channel for domain events
         let (event_tx, _) = broadcast::channel(DOMAIN_EVENT_CHANNEL_CAPACITY);
 
-        Ok(Self {
+        let store = Self {
             db,
             event_tx,
             valid_node_types,
             notifier: None,
-        })
+            query_timeout_ms: AtomicU64::new(timeout_millis(query_timeout_from_env())),
+            retry_policy: RetryPolicy::default(),
+            slow_queries: SlowQueryLog::from_env(),
+            read_only,
+        };
+        if !read_only {
+            store.migrate_order_keys().await?;
+        }
+        Ok(store)
     }
 }
 
@@ -416,12 +622,18 @@ impl SurrealStore<Client> {
         // Initialize broadcast channel for domain events
         let (event_tx, _) = broadcast::channel(DOMAIN_EVENT_CHANNEL_CAPACITY);
 
-        Ok(Self {
+        let store = Self {
             db,
             event_tx,
             valid_node_types,
             notifier: None,
-        })
+            query_timeout_ms: AtomicU64::new(timeout_millis(query_timeout_from_env())),
+            retry_policy: RetryPolicy::default(),
+            slow_queries: SlowQueryLog::from_env(),
+            read_only: false,
+        };
+        store.migrate_order_keys().await?;
+        Ok(store)
     }
 }
 
@@ -429,6 +641,12 @@ impl<C> SurrealStore<C>
 where
     C: surrealdb::Connection,
 {
+    /// Whether the store was opened read-only, without schema setup or
+    /// migrations
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
     /// Set the store change notifier callback (Issue #718)
     ///
     /// Registers a callback that will be invoked synchronously after every store
@@ -462,6 +680,105 @@ where
         &self.db
     }
 
+    /// Start a query on the database connection, timed for the slow query log
+    ///
+    /// Binds and further statements are added as on [`Surreal::query`].
+    pub fn query(&self, sql: impl Into<String>) -> TimedQuery<'_, C> {
+        TimedQuery::new(self, sql.into())
+    }
+
+    pub fn slow_queries(&self) -> &SlowQueryLog {
+        &self.slow_queries
+    }
+
+    /// Limit applied by [`Self::with_query_timeout`], `None` when disabled
+    pub fn query_timeout(&self) -> Option<Duration> {
+        match self.query_timeout_ms.load(Ordering::Relaxed) {
+            0 => None,
+            ms => Some(Duration::from_millis(ms)),
+        }
+    }
+
+    pub fn set_query_timeout(&self, timeout: Option<Duration>) {
+        self.query_timeout_ms
+            .store(timeout_millis(timeout), Ordering::Relaxed);
+    }
+
+    /// SurrealQL `TIMEOUT` clause for the current query timeout, or empty
+    ///
+    /// Appended to expensive `SELECT`s so the engine itself abandons them,
+    /// rather than only the caller giving up on the result.
+    pub fn timeout_clause(&self) -> String {
+        match self.query_timeout() {
+            Some(timeout) => format!(" TIMEOUT {}ms", timeout.as_millis()),
+            None => String::new(),
+        }
+    }
+
+    pub fn retry_policy(&self) -> RetryPolicy {
+        self.retry_policy
+    }
+
+    /// Replace the retry policy for transient write failures
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = policy;
+        self
+    }
+
+    /// Run `attempt` again while it fails with a transient error
+    ///
+    /// Only for operations that leave nothing behind when they fail, i.e. a
+    /// single transaction or a write preceded by reads.
+    async fn with_retry<T, F, Fut>(&self, operation: &str, mut attempt: F) -> Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let mut attempts = 1;
+        loop {
+            match attempt().await {
+                Err(e) if attempts < self.retry_policy.max_attempts && retry::is_transient(&e) => {
+                    let delay = self.retry_policy.backoff(attempts);
+                    tracing::debug!(
+                        "{} hit a transient error (attempt {}), retrying in {:?}: {:#}",
+                        operation,
+                        attempts,
+                        delay,
+                        e
+                    );
+                    tokio::time::sleep(delay).await;
+                    attempts += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Run `operation` under the query timeout
+    ///
+    /// When the timeout elapses the operation is dropped, which cancels any
+    /// query it is awaiting, and [`DatabaseError::Timeout`] is returned. A
+    /// statement stopped by its own `TIMEOUT` clause reports the same error.
+    /// Child listings, subtree fetches and searches always run under it.
+    pub async fn with_query_timeout<T>(
+        &self,
+        operation: impl Future<Output = Result<T>>,
+    ) -> Result<T> {
+        let Some(timeout) = self.query_timeout() else {
+            return operation.await;
+        };
+        match tokio::time::timeout(timeout, operation).await {
+            Ok(Err(e))
+                if e.chain()
+                    .any(|cause| cause.to_string().contains(ENGINE_TIMEOUT_MESSAGE)) =>
+            {
+                Err(DatabaseError::timeout(timeout).into())
+            }
+            Ok(result) => result,
+            Err(_) => Err(DatabaseError::timeout(timeout).into()),
+        }
+    }
+
     /// Subscribe to domain events emitted by this store
     ///
     /// Returns a receiver that will get notified when nodes or relationships change.
@@ -604,6 +921,52 @@ where
         Ok(())
     }
 
+    /// Convert legacy float `has_child` and `member_of` orders into
+    /// fractional index keys
+    ///
+    /// Databases created before sibling and membership order became string
+    /// keys store `properties.order` as a number. Each affected parent (or
+    /// collection) has its edges renumbered in their current order, which
+    /// numeric ORDER BY still reports correctly. Runs on every open and is a
+    /// no-op once converted.
+    async fn migrate_order_keys(&self) -> Result<()> {
+        let mut response = self
+            .query("SELECT VALUE in FROM relationship WHERE relationship_type = 'has_child' AND !type::is::string(properties.order);")
+            .await
+            .context("Failed to find legacy child orders")?;
+        let parents: Vec<Thing> = response
+            .take(0)
+            .context("Failed to extract legacy child orders")?;
+
+        let parents: HashSet<String> = parents.into_iter().map(|p| p.id.to_raw()).collect();
+        if !parents.is_empty() {
+            tracing::info!("Migrating child order keys for {} parent(s)", parents.len());
+        }
+        for parent_id in parents {
+            self.rebalance_children_for_parent(&parent_id).await?;
+        }
+
+        let mut response = self
+            .query("SELECT VALUE out FROM relationship WHERE relationship_type = 'member_of' AND !type::is::string(properties.order);")
+            .await
+            .context("Failed to find legacy member orders")?;
+        let collections: Vec<Thing> = response
+            .take(0)
+            .context("Failed to extract legacy member orders")?;
+
+        let collections: HashSet<String> = collections.into_iter().map(|c| c.id.to_raw()).collect();
+        if !collections.is_empty() {
+            tracing::info!(
+                "Migrating member order keys for {} collection(s)",
+                collections.len()
+            );
+        }
+        for collection_id in collections {
+            self.rebalance_collection_members(&collection_id).await?;
+        }
+        Ok(())
+    }
+
     /// Add a node type to valid types cache (called during schema seeding)
     ///
     /// When NodeService seeds schema records on first launch, it populates the cache
@@ -630,6 +993,92 @@ where
     pub(crate) fn add_to_schema_cache(&mut self, type_name: String) {
         self.valid_node_types.insert(type_name);
     }
+
+    /// Create an empty schema for each node type that has none
+    ///
+    /// The repair for [`StoreHealth::SchemaMismatch`]: nodes of those types
+    /// validate again, and their fields can be defined later by editing the
+    /// placeholder. Types that already have a schema are skipped. Returns the
+    /// types a placeholder was created for.
+    pub async fn create_placeholder_schemas(
+        &mut self,
+        node_types: &[String],
+    ) -> Result<Vec<String>> {
+        let mut created = Vec::new();
+        for node_type in node_types {
+            if self.get_node(node_type).await?.is_some() {
+                continue;
+            }
+            let schema = Node::new_with_id(
+                node_type.clone(),
+                "schema".to_string(),
+                node_type.clone(),
+                serde_json::json!({
+                    "isCore": false,
+                    "schemaVersion": 1,
+                    "description": "Placeholder created by database repair",
+                    "fields": [],
+                    "relationships": [],
+                }),
+            );
+            self.create_schema_node_atomic(schema, Vec::new(), None)
+                .await?;
+            self.add_to_schema_cache(node_type.clone());
+            created.push(node_type.clone());
+        }
+        Ok(created)
+    }
+
+    /// Whether the schema cache knows `node_type`
+    pub(crate) fn has_schema_type(&self, node_type: &str) -> bool {
+        self.valid_node_types.contains(node_type)
+    }
+
+    /// Check an opened database for problems to surface at startup
+    ///
+    /// Reports node types in use that have no schema node, so validation and
+    /// type-filtered queries for them fail. Core types are left out: NodeService
+    /// seeds any missing core schema during initialization.
+    ///
+    /// Lock and corruption problems surface earlier, as errors from
+    /// [`SurrealStore::new`]; see [`StoreHealth::from_open_error`].
+    pub async fn health_check(&self) -> Result<StoreHealth> {
+        #[derive(Deserialize)]
+        struct TypeRow {
+            node_type: String,
+        }
+
+        let mut response = self
+            .query("SELECT node_type FROM node GROUP BY node_type")
+            .query("SELECT VALUE record::id(id) FROM node WHERE node_type = 'schema'")
+            .await
+            .context("Failed to query node types for health check")?;
+        let in_use: Vec<TypeRow> = response
+            .take(0)
+            .context("Failed to read node types for health check")?;
+        let schema_ids: Vec<String> = response
+            .take(1)
+            .context("Failed to read schema IDs for health check")?;
+
+        let known: HashSet<String> = crate::models::core_schemas::get_core_schemas()
+            .into_iter()
+            .map(|schema| schema.id)
+            .chain(schema_ids)
+            .chain(std::iter::once("schema".to_string()))
+            .collect();
+        let mut unknown_types: Vec<String> = in_use
+            .into_iter()
+            .map(|row| row.node_type)
+            .filter(|node_type| !known.contains(node_type))
+            .collect();
+
+        if unknown_types.is_empty() {
+            Ok(StoreHealth::Healthy)
+        } else {
+            unknown_types.sort();
+            Ok(StoreHealth::SchemaMismatch { unknown_types })
+        }
+    }
 }
 
 impl<C> SurrealStore<C>
@@ -637,6 +1086,13 @@ where
     C: surrealdb::Connection,
 {
     pub async fn create_node(&self, node: Node, source: Option<String>) -> Result<Node> {
+        self.with_retry("create_node", || {
+            self.create_node_once(node.clone(), source.clone())
+        })
+        .await
+    }
+
+    async fn create_node_once(&self, node: Node, source: Option<String>) -> Result<Node> {
         // Universal Graph Architecture (Issue #783): All properties stored in node.properties
         // Embeddings are managed separately in dedicated embedding table
 
@@ -671,7 +1127,6 @@ where
         );
 
         let mut response = self
-            .db
             .query(&create_query)
             .bind(("node_type", node.node_type.clone()))
             .bind(("content", node.content.clone()))
@@ -688,7 +1143,6 @@ where
         // This ensures the CREATE statement fully persisted before proceeding
         let verify_query = format!("SELECT * FROM node:`{}` LIMIT 1;", node.id);
         let mut verify_response = self
-            .db
             .query(&verify_query)
             .await
             .context("Failed to verify node creation")?;
@@ -757,6 +1211,26 @@ where
         content: &str,
         properties: Value,
         source: Option<String>,
+    ) -> Result<Node> {
+        self.with_retry("create_child_node_atomic", || {
+            self.create_child_node_once(
+                parent_id,
+                node_type,
+                content,
+                properties.clone(),
+                source.clone(),
+            )
+        })
+        .await
+    }
+
+    async fn create_child_node_once(
+        &self,
+        parent_id: &str,
+        node_type: &str,
+        content: &str,
+        properties: Value,
+        source: Option<String>,
     ) -> Result<Node> {
         use uuid::Uuid;
 
@@ -778,33 +1252,9 @@ where
         // Validate no cycle (prevent child from being ancestor of parent)
         self.validate_no_cycle(&parent_id, &node_id).await?;
 
-        // Calculate fractional order for the new node
-        // Get the last child's order value
-        #[derive(Deserialize)]
-        struct EdgeOrder {
-            order: f64,
-        }
-
+        // Order key after the parent's current last child
+        let new_order = self.get_next_child_order(&parent_id, &node_id).await?;
         let parent_thing = surrealdb::sql::Thing::from(("node".to_string(), parent_id.clone()));
-        // Universal Relationship Architecture (Issue #788): Query from relationship table with relationship_type filter
-        let mut order_response = self
-            .db
-            .query(
-                "SELECT properties.order AS order FROM relationship WHERE in = $parent_thing AND relationship_type = 'has_child' ORDER BY properties.order DESC LIMIT 1;",
-            )
-            .bind(("parent_thing", parent_thing.clone()))
-            .await
-            .context("Failed to get last child order")?;
-
-        let last_order: Option<EdgeOrder> = order_response
-            .take(0)
-            .context("Failed to extract last child order")?;
-
-        let new_order = if let Some(rel) = last_order {
-            FractionalOrderCalculator::calculate_order(Some(rel.order), None)
-        } else {
-            FractionalOrderCalculator::calculate_order(None, None)
-        };
 
         // Universal Graph Architecture (Issue #783, #788): All properties embedded, relationships in universal table
         let transaction_query = r#"
@@ -838,7 +1288,6 @@ where
 
         // Execute transaction
         let response = self
-            .db
             .query(transaction_query)
             .bind(("node_id", node_thing))
             .bind(("parent_id", parent_thing))
@@ -936,6 +1385,7 @@ where
                 .as_str()
                 .unwrap_or("active")
                 .to_string(),
+            rollups: BTreeMap::new(),
         }
     }
 
@@ -946,7 +1396,6 @@ where
 
         let node_query = format!("SELECT * OMIT id FROM node:`{id}` LIMIT 1;", id = id);
         let mut response = self
-            .db
             .query(&node_query)
             .await
             .context("Failed to query node")?;
@@ -975,7 +1424,6 @@ where
     pub async fn node_exists(&self, id: &str) -> Result<bool> {
         let query = format!("SELECT VALUE true FROM node:`{id}` LIMIT 1;", id = id);
         let mut response = self
-            .db
             .query(&query)
             .await
             .context("Failed to check node existence")?;
@@ -1006,7 +1454,6 @@ where
             id_clause
         );
         let mut response = self
-            .db
             .query(&node_query)
             .await
             .context("Failed to batch query nodes")?;
@@ -1042,6 +1489,18 @@ where
         id: &str,
         update: NodeUpdate,
         source: Option<String>,
+    ) -> Result<Node> {
+        self.with_retry("update_node", || {
+            self.update_node_once(id, update.clone(), source.clone())
+        })
+        .await
+    }
+
+    async fn update_node_once(
+        &self,
+        id: &str,
+        update: NodeUpdate,
+        source: Option<String>,
     ) -> Result<Node> {
         // Universal Graph Architecture (Issue #783): All properties in node.properties
 
@@ -1056,17 +1515,11 @@ where
 
         // Merge properties if they're being updated
         // NOTE: _schema_version is managed by NodeService, not SurrealStore.
-        let properties_update = if let Some(ref updated_props) = update.properties {
-            let mut merged_props = current.properties.as_object().cloned().unwrap_or_default();
-            if let Some(new_props) = updated_props.as_object() {
-                for (key, value) in new_props {
-                    merged_props.insert(key.clone(), value.clone());
-                }
-            }
-            Some(serde_json::Value::Object(merged_props))
-        } else {
-            None
-        };
+        let properties_update = update.properties.as_ref().map(|patch| {
+            let mut merged = current.properties.clone();
+            crate::utils::merge_patch(&mut merged, patch);
+            merged
+        });
 
         // Title update: Some(Some(title)) = set title, Some(None) = clear title
         let title_update = update.title;
@@ -1092,7 +1545,6 @@ where
         );
 
         let mut query_builder = self
-            .db
             .query(&query)
             .bind(("id", id.to_string()))
             .bind(("content", updated_content))
@@ -1206,7 +1658,6 @@ where
 
         // Execute atomic transaction
         let response = self
-            .db
             .query(&transaction_query)
             .bind(("id", node.id.clone()))
             .bind(("node_type", node.node_type.clone()))
@@ -1255,17 +1706,10 @@ where
         let updated_node_type = update.node_type.unwrap_or(current.node_type.clone());
 
         // Merge properties if they're being updated
-        let properties_update = if let Some(ref updated_props) = update.properties {
-            let mut merged_props = current.properties.as_object().cloned().unwrap_or_default();
-            if let Some(new_props) = updated_props.as_object() {
-                for (key, value) in new_props {
-                    merged_props.insert(key.clone(), value.clone());
-                }
-            }
-            serde_json::Value::Object(merged_props)
-        } else {
-            current.properties.clone()
-        };
+        let mut properties_update = current.properties.clone();
+        if let Some(patch) = &update.properties {
+            crate::utils::merge_patch(&mut properties_update, patch);
+        }
 
         // Build the atomic transaction query
         // Universal Graph Architecture (Issue #783): All data in node.properties
@@ -1291,8 +1735,7 @@ where
         let transaction_query = transaction_parts.join("\n");
 
         // Execute the atomic transaction
-        self.db
-            .query(&transaction_query)
+        self.query(&transaction_query)
             .bind(("id", id.to_string()))
             .bind(("content", updated_content))
             .bind(("node_type", updated_node_type))
@@ -1415,7 +1858,6 @@ where
 
         // Execute transaction
         let response = self
-            .db
             .query(transaction_query)
             .bind(("node_id", node_thing))
             .bind(("new_type", new_type.clone()))
@@ -1517,10 +1959,14 @@ where
 
         let updated_content = update.content.unwrap_or(current.content);
         let updated_node_type = update.node_type.unwrap_or(current.node_type.clone());
-        let updated_properties = update.properties.clone();
+        // The version check below guarantees `current` is the state being patched
+        let updated_properties = update.properties.as_ref().map(|patch| {
+            let mut merged = current.properties.clone();
+            crate::utils::merge_patch(&mut merged, patch);
+            merged
+        });
 
         let mut response = self
-            .db
             .query(query)
             .bind(("id", id.to_string()))
             .bind(("expected_version", expected_version))
@@ -1543,8 +1989,7 @@ where
         // Universal Graph Architecture (Issue #783): Properties stored in node.properties
         // Update properties directly if provided
         if let Some(props) = updated_properties {
-            self.db
-                .query("UPDATE type::thing('node', $id) SET properties = $properties;")
+            self.query("UPDATE type::thing('node', $id) SET properties = $properties;")
                 .bind(("id", id.to_string()))
                 .bind(("properties", props))
                 .await
@@ -1553,8 +1998,7 @@ where
 
         // Issue #828, #770: Update lifecycle_status if provided
         if let Some(status) = update.lifecycle_status {
-            self.db
-                .query("UPDATE type::thing('node', $id) SET lifecycle_status = $lifecycle_status;")
+            self.query("UPDATE type::thing('node', $id) SET lifecycle_status = $lifecycle_status;")
                 .bind(("id", id.to_string()))
                 .bind(("lifecycle_status", status))
                 .await
@@ -1577,40 +2021,189 @@ where
         Ok(Some(node))
     }
 
-    /// Update lifecycle_status directly (for bulk import operations)
+    /// Replace a node's properties if its version still matches
     ///
-    /// This is a lightweight method that skips validation and event emission,
-    /// useful for bulk operations like docs import where we need to mark
-    /// many documents as archived efficiently.
-    pub async fn update_lifecycle_status(&self, id: &str, status: &str) -> Result<()> {
-        self.db
-            .query("UPDATE type::thing('node', $id) SET lifecycle_status = $status;")
+    /// Unlike `update_node_with_version_check`, properties are written in the
+    /// same statement as the version bump, so a concurrent writer can never
+    /// slip in between the check and the write.
+    ///
+    /// Returns `Ok(None)` on version mismatch (or when the node is gone).
+    pub async fn update_properties_with_version_check(
+        &self,
+        id: &str,
+        expected_version: i64,
+        properties: Value,
+        source: Option<String>,
+    ) -> Result<Option<Node>> {
+        let query = "
+            UPDATE type::thing('node', $id) SET
+                properties = $properties,
+                modified_at = time::now(),
+                version = $new_version
+            WHERE version = $expected_version
+            RETURN AFTER;
+        ";
+
+        let mut response = self
+            .query(query)
             .bind(("id", id.to_string()))
-            .bind(("status", status.to_string()))
+            .bind(("expected_version", expected_version))
+            .bind(("new_version", expected_version + 1))
+            .bind(("properties", properties))
             .await
-            .context("Failed to update lifecycle_status")?;
-        Ok(())
-    }
+            .context("Failed to update properties with version check")?;
 
-    pub async fn delete_node(&self, id: &str, source: Option<String>) -> Result<DeleteResult> {
-        // Universal Graph Architecture (Issue #783, #788): All relationships in universal table
-
-        // Get node before deletion for notification
-        let node = match self.get_node(id).await? {
-            Some(n) => n,
-            None => return Ok(DeleteResult { existed: false }),
-        };
+        let updated_nodes: Vec<SurrealNode> = response
+            .take(0)
+            .context("Failed to extract update results")?;
+        if updated_nodes.is_empty() {
+            return Ok(None);
+        }
 
-        // Delete node and its relationships atomically (Issue #788: use universal relationship table)
-        let transaction_query = "
-            BEGIN TRANSACTION;
-            DELETE type::thing('node', $id);
-            DELETE relationship WHERE in = type::thing('node', $id) OR out = type::thing('node', $id);
-            COMMIT TRANSACTION;
-        ";
+        let node = self
+            .get_node(id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Node not found after update"))?;
 
-        self.db
-            .query(transaction_query)
+        self.notify(StoreChange {
+            operation: StoreOperation::Updated,
+            node: node.clone(),
+            source,
+        });
+
+        Ok(Some(node))
+    }
+
+    /// Indexes defined on `table`, sorted by name
+    pub async fn list_indexes(&self, table: &str) -> Result<Vec<IndexInfo>> {
+        if table.is_empty() || !table.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            return Err(anyhow::anyhow!("Invalid table name: {}", table));
+        }
+        let mut response = self
+            .query(format!("INFO FOR TABLE {};", table))
+            .await
+            .context("Failed to read table info")?;
+        let info: Option<Value> = response.take(0).context("Failed to extract table info")?;
+
+        let mut indexes: Vec<IndexInfo> = info
+            .as_ref()
+            .and_then(|info| info.get("indexes"))
+            .and_then(Value::as_object)
+            .map(|indexes| {
+                indexes
+                    .iter()
+                    .map(|(name, definition)| IndexInfo {
+                        name: name.clone(),
+                        definition: definition
+                            .as_str()
+                            .map(str::to_string)
+                            .unwrap_or_else(|| definition.to_string()),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        indexes.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(indexes)
+    }
+
+    /// Coverage of the schema-driven property indexes on `node`
+    pub async fn property_index_stats(&self) -> Result<Vec<IndexStats>> {
+        let table_rows = self.count_nodes_where("true").await?;
+
+        let mut stats = Vec::new();
+        for index in self.list_indexes("node").await? {
+            if !index
+                .name
+                .starts_with(super::index_manager::PROPERTY_INDEX_PREFIX)
+            {
+                continue;
+            }
+            let Some(column) = index_column(&index.definition) else {
+                continue;
+            };
+            let entries = self
+                .count_nodes_where(&format!("{} != NONE", column))
+                .await?;
+            stats.push(IndexStats {
+                name: index.name,
+                column: column.to_string(),
+                entries,
+                table_rows,
+            });
+        }
+        Ok(stats)
+    }
+
+    async fn count_nodes_where(&self, condition: &str) -> Result<u64> {
+        let mut response = self
+            .query(format!(
+                "SELECT count() AS count FROM node WHERE {} GROUP ALL;",
+                condition
+            ))
+            .await
+            .context("Failed to count nodes")?;
+        let rows: Vec<Value> = response.take(0).context("Failed to extract count")?;
+        Ok(rows
+            .first()
+            .and_then(|row| row.get("count"))
+            .and_then(Value::as_u64)
+            .unwrap_or(0))
+    }
+
+    /// Run DDL statements (e.g. from `IndexManager`) in one transaction
+    pub async fn execute_ddl(&self, statements: Vec<String>) -> Result<()> {
+        if statements.is_empty() {
+            return Ok(());
+        }
+        let query = format!(
+            "BEGIN TRANSACTION;\n{}\nCOMMIT TRANSACTION;",
+            statements.join("\n")
+        );
+        self.query(&query)
+            .await
+            .context("Failed to execute DDL")?
+            .check()
+            .context(format!("DDL transaction failed. Query: {}", query))?;
+        Ok(())
+    }
+
+    /// Update lifecycle_status directly (for bulk import operations)
+    ///
+    /// This is a lightweight method that skips validation and event emission,
+    /// useful for bulk operations like docs import where we need to mark
+    /// many documents as archived efficiently.
+    pub async fn update_lifecycle_status(&self, id: &str, status: &str) -> Result<()> {
+        self.query("UPDATE type::thing('node', $id) SET lifecycle_status = $status;")
+            .bind(("id", id.to_string()))
+            .bind(("status", status.to_string()))
+            .await
+            .context("Failed to update lifecycle_status")?;
+        Ok(())
+    }
+
+    pub async fn delete_node(&self, id: &str, source: Option<String>) -> Result<DeleteResult> {
+        // Universal Graph Architecture (Issue #783, #788): All relationships in universal table
+
+        // Get node before deletion for notification
+        let node = match self.get_node(id).await? {
+            Some(n) => n,
+            None => return Ok(DeleteResult { existed: false }),
+        };
+
+        // Delete node and its relationships atomically (Issue #788: use universal relationship table),
+        // recording mentions of it elsewhere as broken links first
+        let transaction_query = format!(
+            "
+            BEGIN TRANSACTION;
+            LET $targets = [type::thing('node', $id)];
+            {RECORD_BROKEN_LINKS}
+            DELETE type::thing('node', $id);
+            DELETE relationship WHERE in = type::thing('node', $id) OR out = type::thing('node', $id);
+            COMMIT TRANSACTION;
+        "
+        );
+
+        self.query(transaction_query)
             .bind(("id", node.id.clone()))
             .await
             .context("Failed to delete node and relations")?;
@@ -1638,6 +2231,9 @@ where
     /// - Node record from universal `node` table
     /// - All relationships (incoming and outgoing) from universal `relationship` table
     ///
+    /// Mentions and embeds of the node in other nodes are recorded as broken
+    /// links (see [`BrokenLink`](crate::models::BrokenLink)).
+    ///
     /// Universal Relationship Architecture (Issue #788): All relationship types in single table.
     ///
     /// # Arguments
@@ -1675,9 +2271,14 @@ where
         let node_type = node.node_type.clone();
         let node_id_str = node.id.clone();
 
-        let transaction_query = r#"
+        let transaction_query = format!(
+            r#"
             BEGIN TRANSACTION;
 
+            -- Record links left behind in other nodes' content
+            LET $targets = [$node_id];
+            {RECORD_BROKEN_LINKS}
+
             -- Delete type-specific record (if exists - legacy support)
             DELETE $type_id;
 
@@ -1689,15 +2290,14 @@ where
 
             COMMIT TRANSACTION;
         "#
-        .to_string();
+        );
 
         // Construct Thing objects for Record IDs
         let node_thing = surrealdb::sql::Thing::from(("node".to_string(), node_id_str.clone()));
         let type_thing = surrealdb::sql::Thing::from((node_type.clone(), node_id_str.clone()));
 
         // Execute transaction (we don't care about the return value, just the side effects)
-        self.db
-            .query(&transaction_query)
+        self.query(&transaction_query)
             .bind(("node_id", node_thing))
             .bind(("type_id", type_thing))
             .await
@@ -1759,7 +2359,6 @@ where
             };
 
             let mut query_builder = self
-                .db
                 .query(sql)
                 .bind(("node_id", mentioned_node_id.to_string()));
 
@@ -1829,7 +2428,7 @@ where
             }
         };
 
-        let mut query_builder = self.db.query(sql);
+        let mut query_builder = self.query(sql);
 
         if let Some(node_type) = &query.node_type {
             query_builder = query_builder.bind(("node_type", node_type.clone()));
@@ -1852,6 +2451,11 @@ where
     }
 
     pub async fn get_children(&self, parent_id: Option<&str>) -> Result<Vec<Node>> {
+        self.with_query_timeout(self.get_children_unbounded(parent_id))
+            .await
+    }
+
+    async fn get_children_unbounded(&self, parent_id: Option<&str>) -> Result<Vec<Node>> {
         // Universal Graph Architecture (Issue #783, #788): Properties embedded in node.properties
         // Use universal relationship table for hierarchy traversal with fractional ordering
         let surreal_nodes = if let Some(parent_id) = parent_id {
@@ -1863,12 +2467,11 @@ where
             // Uses LET to store ordered IDs, then fetches nodes preserving order
             // Note: ORDER BY field must be included in SELECT, so we select out and properties.order
             let mut response = self
-                .db
                 .query(
                     r#"
                     LET $child_ids = (
                         SELECT out, properties.order FROM relationship
-                        WHERE in = $parent_thing AND relationship_type = 'has_child'
+                        WHERE in = $parent_thing AND relationship_type = 'has_child' AND properties.reference != true
                         ORDER BY properties.order ASC
                     ).out;
                     SELECT * FROM $child_ids;
@@ -1886,9 +2489,9 @@ where
             nodes
         } else {
             // Root nodes: nodes that have NO incoming has_child relationships (Issue #788: universal relationship table)
+            // Transcluded roots are still roots: reference edges don't give a node a parent
             let mut response = self
-                .db
-                .query("SELECT * FROM node WHERE count(<-relationship[WHERE relationship_type = 'has_child']) = 0;")
+                .query("SELECT * FROM node WHERE count(<-relationship[WHERE relationship_type = 'has_child' AND properties.reference != true]) = 0;")
                 .await
                 .context("Failed to get root nodes")?;
 
@@ -1906,10 +2509,69 @@ where
         Ok(nodes)
     }
 
+    /// Up to `limit` children of a node from position `start`, in order, and
+    /// how many children it has in all
+    ///
+    /// The window is applied to the ordered edges, so only the page's nodes
+    /// are loaded.
+    pub async fn get_children_page(
+        &self,
+        parent_id: &str,
+        start: usize,
+        limit: usize,
+    ) -> Result<(Vec<Node>, usize)> {
+        self.with_query_timeout(self.get_children_page_unbounded(parent_id, start, limit))
+            .await
+    }
+
+    async fn get_children_page_unbounded(
+        &self,
+        parent_id: &str,
+        start: usize,
+        limit: usize,
+    ) -> Result<(Vec<Node>, usize)> {
+        use surrealdb::sql::Thing;
+        let parent_thing = Thing::from(("node".to_string(), parent_id.to_string()));
+
+        let mut response = self
+            .query(
+                r#"
+                SELECT count() AS count FROM relationship
+                WHERE in = $parent_thing AND relationship_type = 'has_child' AND properties.reference != true
+                GROUP ALL;
+                LET $child_ids = (
+                    SELECT out, properties.order FROM relationship
+                    WHERE in = $parent_thing AND relationship_type = 'has_child' AND properties.reference != true
+                    ORDER BY properties.order ASC
+                    LIMIT $limit START $start
+                ).out;
+                SELECT * FROM $child_ids;
+                "#,
+            )
+            .bind(("parent_thing", parent_thing))
+            .bind(("limit", limit))
+            .bind(("start", start))
+            .await
+            .context("Failed to get children")?;
+
+        let counts: Vec<Value> = response.take(0).context("Failed to extract child count")?;
+        let child_count = counts
+            .first()
+            .and_then(|row| row.get("count"))
+            .and_then(Value::as_u64)
+            .unwrap_or(0) as usize;
+        let nodes: Vec<SurrealNode> = response
+            .take(2)
+            .context("Failed to extract children from response")?;
+
+        Ok((nodes.into_iter().map(Into::into).collect(), child_count))
+    }
+
     /// Get the parent of a node (via incoming has_child relationship)
     ///
     /// Returns the node's parent if it has one, or None if it's a root node.
     /// Universal Graph Architecture (Issue #783, #788): Properties embedded, relationships in universal table.
+    /// Reference edges are ignored: a transcluded node's parent is the one it lives under.
     ///
     /// # Arguments
     ///
@@ -1924,8 +2586,7 @@ where
 
         // Query for parent via incoming has_child relationship (Issue #788: universal relationship table)
         let mut response = self
-            .db
-            .query("SELECT * FROM node WHERE id IN (SELECT VALUE in FROM relationship WHERE out = $child_thing AND relationship_type = 'has_child') LIMIT 1;")
+            .query("SELECT * FROM node WHERE id IN (SELECT VALUE in FROM relationship WHERE out = $child_thing AND relationship_type = 'has_child' AND properties.reference != true) LIMIT 1;")
             .bind(("child_thing", child_thing))
             .await
             .context("Failed to get parent")?;
@@ -1963,8 +2624,7 @@ where
 
         // Query just the relationship to get parent ID (no node fetch)
         let mut response = self
-            .db
-            .query("SELECT VALUE in FROM relationship WHERE out = $child_thing AND relationship_type = 'has_child' LIMIT 1;")
+            .query("SELECT VALUE in FROM relationship WHERE out = $child_thing AND relationship_type = 'has_child' AND properties.reference != true LIMIT 1;")
             .bind(("child_thing", child_thing))
             .await
             .context("Failed to get parent ID")?;
@@ -2001,7 +2661,6 @@ where
         let node_thing = Thing::from(("node".to_string(), node_id.to_string()));
 
         let mut response = self
-            .db
             .query("SELECT VALUE node_type FROM node WHERE id = $node_id LIMIT 1;")
             .bind(("node_id", node_thing))
             .await
@@ -2219,36 +2878,70 @@ where
     /// Tuple of (all_nodes, relationships) where:
     /// - all_nodes: Vec<Node> - root node + all descendants with properties embedded
     /// - relationships: Vec<RelationshipRecord> - all parent-child relationships in the subtree
+    ///
+    /// Only nodes the root owns are included; reference (transclusion) edges are
+    /// not followed. Use [`Self::get_subtree_with_references`] for the displayed tree.
     pub async fn get_subtree_with_relationships(
         &self,
         root_id: &str,
+    ) -> Result<(Vec<Node>, Vec<RelationshipRecord>)> {
+        self.with_query_timeout(self.query_subtree(root_id, false))
+            .await
+    }
+
+    /// Get a subtree as displayed, following reference edges into transcluded nodes
+    ///
+    /// Like [`Self::get_subtree_with_relationships`], but also returns the
+    /// reference `has_child` edges and the subtrees of the nodes they point
+    /// to. References can form cycles, so callers walking the edges must track
+    /// the nodes on their current path.
+    pub async fn get_subtree_with_references(
+        &self,
+        root_id: &str,
+    ) -> Result<(Vec<Node>, Vec<RelationshipRecord>)> {
+        self.with_query_timeout(self.query_subtree(root_id, true))
+            .await
+    }
+
+    async fn query_subtree(
+        &self,
+        root_id: &str,
+        include_references: bool,
     ) -> Result<(Vec<Node>, Vec<RelationshipRecord>)> {
         use surrealdb::sql::Thing;
         let start = std::time::Instant::now();
 
         let root_thing = Thing::from(("node".to_string(), root_id.to_string()));
 
+        let edge_filter = if include_references {
+            "relationship_type = 'has_child'"
+        } else {
+            "relationship_type = 'has_child' AND properties.reference != true"
+        };
+
         // Universal Graph Architecture (Issue #783, #788): Single query batch
         // Uses recursive collect to get all descendants, then fetches nodes and relationships
         // Optimized: SELECT * FROM $ids is faster than SELECT * FROM node WHERE id IN $ids
-        let query = "
-            LET $descendants = $root_thing.{..+collect}->relationship[WHERE relationship_type = 'has_child']->node;
+        let query = format!(
+            "
+            LET $descendants = $root_thing.{{..+collect}}->relationship[WHERE {edge_filter}]->node;
             LET $all_node_ids = array::concat([$root_thing], $descendants);
             SELECT * FROM $all_node_ids;
-            SELECT id, in, out, relationship_type, properties, properties.order FROM relationship WHERE in IN $all_node_ids AND relationship_type = 'has_child' ORDER BY properties.order ASC;
-        ";
+            SELECT id, in, out, relationship_type, properties, properties.order FROM relationship WHERE in IN $all_node_ids AND {edge_filter} ORDER BY properties.order ASC;
+        "
+        );
 
         let mut response = self
-            .db
             .query(query)
             .bind(("root_thing", root_thing))
             .await
             .context("Failed to query subtree")?;
 
         tracing::debug!(
-            "get_subtree_with_relationships: query took {:?} for root_id={}",
+            "query_subtree: query took {:?} for root_id={} (references: {})",
             start.elapsed(),
-            root_id
+            root_id,
+            include_references
         );
 
         // Query has 4 statements:
@@ -2343,6 +3036,15 @@ where
         &self,
         search_query: &str,
         limit: Option<i64>,
+    ) -> Result<Vec<Node>> {
+        self.with_query_timeout(self.search_nodes_by_content_unbounded(search_query, limit))
+            .await
+    }
+
+    async fn search_nodes_by_content_unbounded(
+        &self,
+        search_query: &str,
+        limit: Option<i64>,
     ) -> Result<Vec<Node>> {
         // Use string::lowercase() for case-insensitive search
         // SurrealDB CONTAINS is case-sensitive by default
@@ -2353,7 +3055,6 @@ where
         };
 
         let mut query_builder = self
-            .db
             .query(sql)
             .bind(("search_query", search_query.to_string()));
 
@@ -2398,6 +3099,15 @@ where
         &self,
         search_query: &str,
         limit: Option<i64>,
+    ) -> Result<Vec<Node>> {
+        self.with_query_timeout(self.mention_autocomplete_unbounded(search_query, limit))
+            .await
+    }
+
+    async fn mention_autocomplete_unbounded(
+        &self,
+        search_query: &str,
+        limit: Option<i64>,
     ) -> Result<Vec<Node>> {
         // Issue #821: Use indexed title field for efficient @mention search
         // The title field is only populated for:
@@ -2419,7 +3129,6 @@ where
         let effective_limit = limit.unwrap_or(10);
 
         let mut response = self
-            .db
             .query(sql)
             .bind(("search_query", search_query.to_string()))
             .bind(("limit", effective_limit))
@@ -2482,7 +3191,6 @@ where
         ";
 
         let mut response = self
-            .db
             .query(query)
             .bind(("parent_id", parent_id.to_string()))
             .bind(("child_thing", child_thing))
@@ -2508,10 +3216,12 @@ where
         Ok(())
     }
 
-    /// Rebalance child ordering for a parent when precision degrades
+    /// Rebalance child ordering for a parent when its keys degrade
     ///
-    /// When fractional ordering gets too granular (gaps < 0.0001), this rebalances
-    /// all children of a parent to have even spacing (1.0, 2.0, 3.0, etc.).
+    /// When order keys grow too long, collide, or are not keys at all (legacy
+    /// floats), this renumbers all children of a parent with short, evenly
+    /// spaced keys (`a0`, `a1`, `a2`, etc.) in their current order.
+    /// [`Self::move_node`] runs it automatically before positioning a child.
     ///
     /// This operation is atomic - either all children are rebalanced or none are.
     ///
@@ -2534,7 +3244,6 @@ where
         }
 
         let mut rels_response = self
-            .db
             .query("SELECT out, properties.order FROM relationship WHERE in = $parent_thing AND relationship_type = 'has_child' ORDER BY properties.order ASC;")
             .bind(("parent_thing", parent_thing.clone()))
             .await
@@ -2548,37 +3257,41 @@ where
             return Ok(()); // Nothing to rebalance
         }
 
-        // Step 2: Calculate new orders [1.0, 2.0, 3.0, ...]
-        let new_orders = FractionalOrderCalculator::rebalance(relationships.len());
+        // Step 2: Calculate new keys [a0, a1, a2, ...]
+        let new_orders = (0..relationships.len()).map(FractionalIndex::nth);
 
         // Step 3: Build atomic transaction to update all relationships (Issue #788: universal relationship table)
         // We need to update each relationship's properties.order field
         let mut transaction = String::from("BEGIN TRANSACTION;\n");
 
-        for (i, _rel) in relationships.iter().enumerate() {
-            let new_order = new_orders[i];
+        for i in 0..relationships.len() {
             transaction.push_str(&format!(
-                "UPDATE relationship SET properties.order = {} WHERE in = $parent_thing AND out = $out{} AND relationship_type = 'has_child';\n",
-                new_order, i
+                "UPDATE relationship SET properties.order = $order{i} WHERE in = $parent_thing AND out = $out{i} AND relationship_type = 'has_child';\n"
             ));
         }
 
         transaction.push_str("COMMIT TRANSACTION;");
 
-        // Step 4: Execute transaction with all relationships bound
+        // Step 4: Execute transaction with all relationships and orders bound
         let mut query_builder = self
-            .db
             .query(&transaction)
             .bind(("parent_thing", parent_thing));
 
-        for (i, rel) in relationships.iter().enumerate() {
-            query_builder = query_builder.bind((format!("out{}", i), rel.out.clone()));
+        for (i, (rel, order)) in relationships.iter().zip(new_orders).enumerate() {
+            query_builder = query_builder
+                .bind((format!("out{}", i), rel.out.clone()))
+                .bind((format!("order{}", i), order));
         }
 
         query_builder
             .await
             .context("Failed to rebalance children")?;
 
+        tracing::debug!(
+            "Rebalanced {} children of {}",
+            relationships.len(),
+            parent_id
+        );
         Ok(())
     }
 
@@ -2616,6 +3329,18 @@ where
         node_id: &str,
         new_parent_id: Option<&str>,
         insert_after_sibling_id: Option<&str>,
+    ) -> Result<()> {
+        self.with_retry("move_node", || {
+            self.move_node_once(node_id, new_parent_id, insert_after_sibling_id)
+        })
+        .await
+    }
+
+    async fn move_node_once(
+        &self,
+        node_id: &str,
+        new_parent_id: Option<&str>,
+        insert_after_sibling_id: Option<&str>,
     ) -> Result<()> {
         // Convert parameters to owned strings for 'static lifetime
         let node_id = node_id.to_string();
@@ -2647,11 +3372,11 @@ where
             self.validate_no_cycle(parent_id, &node_id).await?;
         }
 
-        // Calculate fractional order for the new position (Issue #788: universal relationship table)
+        // Calculate the order key for the new position (Issue #788: universal relationship table)
         #[derive(Deserialize)]
         struct RelWithOrder {
             out: surrealdb::sql::Thing,
-            order: f64,
+            order: String,
         }
 
         let new_order = if let Some(ref parent_id) = new_parent_id {
@@ -2663,7 +3388,6 @@ where
             // when doing same-parent reorders. Otherwise, if the node being moved is at
             // position after_index+1, we'd use its current order as the "next" boundary.
             let mut rels_response = self
-                .db
                 .query(
                     "SELECT out, properties.order AS order FROM relationship WHERE in = $parent_thing AND relationship_type = 'has_child' AND out != $node_thing ORDER BY properties.order ASC;",
                 )
@@ -2672,86 +3396,63 @@ where
                 .await
                 .context("Failed to get child relationships")?;
 
-            let relationships: Vec<RelWithOrder> = rels_response
+            let mut relationships: Vec<RelWithOrder> = rels_response
                 .take(0)
                 .context("Failed to extract child relationships")?;
 
-            if let Some(after_id) = insert_after_sibling_id {
-                // Find the sibling we're inserting after
-                let after_thing =
-                    surrealdb::sql::Thing::from(("node".to_string(), after_id.clone()));
-
-                // If sibling not found, fall back to append at end (best-effort hint)
-                // This prevents data loss from race conditions during rapid operations
-                if let Some(after_index) = relationships.iter().position(|e| e.out == after_thing) {
-                    // Get orders before and after insertion point
-                    let prev_order = relationships[after_index].order;
-                    let next_order = relationships.get(after_index + 1).map(|e| e.order);
-
-                    // Calculate new order between them
-                    let calculated =
-                        FractionalOrderCalculator::calculate_order(Some(prev_order), next_order);
-
-                    // Check if rebalancing is needed
-                    if let Some(next) = next_order {
-                        if (next - prev_order) < 0.0001 {
-                            // Gap too small, need to rebalance before inserting
-                            self.rebalance_children_for_parent(parent_id).await?;
-
-                            // Re-query relationships after rebalancing
-                            let mut rels_response = self
-                                .db
-                                .query("SELECT out, properties.order AS order FROM relationship WHERE in = $parent_thing AND relationship_type = 'has_child' AND out != $node_thing ORDER BY properties.order ASC;")
-                                .bind(("parent_thing", parent_thing.clone()))
-                                .bind(("node_thing", node_thing.clone()))
-                                .await
-                                .context("Failed to get child relationships after rebalancing")?;
-
-                            let relationships: Vec<RelWithOrder> = rels_response.take(0).context(
-                                "Failed to extract child relationships after rebalancing",
-                            )?;
-
-                            // If sibling disappeared after rebalancing, fall back to append
-                            if let Some(after_index) =
-                                relationships.iter().position(|e| e.out == after_thing)
-                            {
-                                let prev_order = relationships[after_index].order;
-                                let next_order =
-                                    relationships.get(after_index + 1).map(|e| e.order);
-                                FractionalOrderCalculator::calculate_order(
-                                    Some(prev_order),
-                                    next_order,
-                                )
-                            } else {
-                                tracing::warn!(
-                                    sibling_id = %after_id,
-                                    "sibling disappeared after rebalancing, falling back to append"
-                                );
-                                let last_order = relationships.last().map(|e| e.order);
-                                FractionalOrderCalculator::calculate_order(last_order, None)
-                            }
-                        } else {
-                            calculated
+            // Renumber the siblings first if repeated inserts at one spot have grown
+            // the keys too long (or legacy floats / duplicate keys are present), so
+            // the key computed below always lands between its neighbours
+            let orders: Vec<&str> = relationships.iter().map(|e| e.order.as_str()).collect();
+            if FractionalIndex::needs_rebalancing(&orders) {
+                self.rebalance_children_for_parent(parent_id).await?;
+
+                let mut rels_response = self
+                    .query("SELECT out, properties.order AS order FROM relationship WHERE in = $parent_thing AND relationship_type = 'has_child' AND out != $node_thing ORDER BY properties.order ASC;")
+                    .bind(("parent_thing", parent_thing.clone()))
+                    .bind(("node_thing", node_thing.clone()))
+                    .await
+                    .context("Failed to get child relationships after rebalancing")?;
+                relationships = rels_response
+                    .take(0)
+                    .context("Failed to extract child relationships after rebalancing")?;
+            }
+
+            let (prev, next) = match insert_after_sibling_id {
+                Some(after_id) => {
+                    // Find the sibling we're inserting after
+                    let after_thing =
+                        surrealdb::sql::Thing::from(("node".to_string(), after_id.clone()));
+
+                    // If sibling not found, fall back to append at end (best-effort hint)
+                    // This prevents data loss from race conditions during rapid operations
+                    match relationships.iter().position(|e| e.out == after_thing) {
+                        Some(after_index) => (
+                            relationships.get(after_index),
+                            relationships.get(after_index + 1),
+                        ),
+                        None => {
+                            tracing::warn!(
+                                sibling_id = %after_id,
+                                parent_id = %parent_id,
+                                "insert_after_sibling_id not found in parent's children, falling back to append"
+                            );
+                            (relationships.last(), None)
                         }
-                    } else {
-                        calculated
                     }
-                } else {
-                    tracing::warn!(
-                        sibling_id = %after_id,
-                        parent_id = %parent_id,
-                        "insert_after_sibling_id not found in parent's children, falling back to append"
-                    );
-                    let last_order = relationships.last().map(|e| e.order);
-                    FractionalOrderCalculator::calculate_order(last_order, None)
                 }
-            } else {
                 // No insert_after_sibling specified, insert at beginning
-                let first_order = relationships.first().map(|e| e.order);
-                FractionalOrderCalculator::calculate_order(None, first_order)
-            }
+                None => (None, relationships.first()),
+            };
+
+            FractionalIndex::key_for_node(
+                prev.map(|e| e.order.as_str()),
+                next.map(|e| e.order.as_str()),
+                &node_id,
+            )
+            .map_err(|e| anyhow::anyhow!(e))?
         } else {
-            0.0 // Root nodes don't use order
+            String::new() // Root nodes don't use order
         };
 
         // Build atomic transaction query using Thing parameters (Issue #788: universal relationship table)
@@ -2768,7 +3469,7 @@ where
                     SET properties.order = $order,
                         modified_at = time::now(),
                         version = version + 1
-                    WHERE in = $parent_id AND out = $node_id AND relationship_type = 'has_child';
+                    WHERE in = $parent_id AND out = $node_id AND relationship_type = 'has_child' AND properties.reference != true;
 
                     COMMIT TRANSACTION;
                 "#
@@ -2778,8 +3479,10 @@ where
                 r#"
                     BEGIN TRANSACTION;
 
-                    -- Delete old parent relationship from universal relationship table
-                    DELETE relationship WHERE out = $node_id AND relationship_type = 'has_child';
+                    -- Delete old parent relationship from universal relationship table, keeping
+                    -- references elsewhere (a reference under the new parent becomes the move)
+                    DELETE relationship WHERE out = $node_id AND relationship_type = 'has_child'
+                        AND (properties.reference != true OR in = $parent_id);
 
                     -- Create new parent relationship with fractional order in universal relationship table
                     RELATE $parent_id->relationship->$node_id CONTENT {
@@ -2800,7 +3503,7 @@ where
                 BEGIN TRANSACTION;
 
                 -- Delete old parent relationship from universal relationship table
-                DELETE relationship WHERE out = $node_id AND relationship_type = 'has_child';
+                DELETE relationship WHERE out = $node_id AND relationship_type = 'has_child' AND properties.reference != true;
 
                 COMMIT TRANSACTION;
             "#
@@ -2814,10 +3517,7 @@ where
             .map(|pid| surrealdb::sql::Thing::from(("node".to_string(), pid.clone())));
 
         // Execute transaction
-        let mut query_builder = self
-            .db
-            .query(&transaction_query)
-            .bind(("node_id", node_thing));
+        let mut query_builder = self.query(&transaction_query).bind(("node_id", node_thing));
 
         if let Some(parent_thing) = parent_thing {
             query_builder = query_builder.bind(("parent_id", parent_thing));
@@ -2835,6 +3535,58 @@ where
         Ok(())
     }
 
+    /// Transclude a node as the last child of another parent
+    ///
+    /// Creates a `has_child` edge flagged `reference: true`. The node keeps its
+    /// own parent; the reference edge only makes it appear under `parent_id` as
+    /// well, ordered among that parent's children like any other child.
+    /// Callers validate that the edge won't create a cycle.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(id))` - Relationship ID if newly created
+    /// * `Ok(None)` - If the node already appears under `parent_id` (idempotent)
+    pub async fn add_reference_child(
+        &self,
+        parent_id: &str,
+        node_id: &str,
+    ) -> Result<Option<String>> {
+        let order = self.get_next_child_order(parent_id, node_id).await?;
+        let query = r#"
+            LET $existing = (SELECT id FROM relationship WHERE in = $parent_thing AND out = $node_thing AND relationship_type = 'has_child' LIMIT 1);
+            IF array::len($existing) = 0 THEN
+                (RELATE $parent_thing->relationship->$node_thing CONTENT {
+                    relationship_type: 'has_child',
+                    properties: { order: $order, reference: true },
+                    created_at: time::now(),
+                    modified_at: time::now(),
+                    version: 1
+                } RETURN id)
+            END;
+        "#;
+
+        let mut response = self
+            .query(query)
+            .bind((
+                "parent_thing",
+                Thing::from(("node".to_string(), parent_id.to_string())),
+            ))
+            .bind((
+                "node_thing",
+                Thing::from(("node".to_string(), node_id.to_string())),
+            ))
+            .bind(("order", order))
+            .await
+            .context("Failed to add reference child")?;
+
+        #[derive(Deserialize)]
+        struct RelateResult {
+            id: Thing,
+        }
+        let created: Vec<RelateResult> = response.take(1).unwrap_or_default();
+        Ok(created.first().map(|r| r.id.to_string()))
+    }
+
     /// Create a mention relationship between two nodes
     ///
     /// Issue #788: Universal Relationship Architecture - mentions stored in relationship table.
@@ -2852,29 +3604,49 @@ where
     /// * `Ok(None)` - If mention already existed (idempotent)
     /// * `Err` - Database error
     pub async fn create_mention(&self, source_id: &str, target_id: &str) -> Result<Option<String>> {
+        self.create_content_edge(source_id, target_id, "mentions")
+            .await
+    }
+
+    /// Create an embed relationship for a `((node-id))` block embed
+    ///
+    /// Same shape and idempotency as [`Self::create_mention`], under the
+    /// `embeds` relationship type.
+    pub async fn create_embed(&self, source_id: &str, target_id: &str) -> Result<Option<String>> {
+        self.create_content_edge(source_id, target_id, "embeds")
+            .await
+    }
+
+    /// Idempotently RELATE `source -> target` with an empty-properties edge
+    async fn create_content_edge(
+        &self,
+        source_id: &str,
+        target_id: &str,
+        relationship_type: &'static str,
+    ) -> Result<Option<String>> {
         let source_thing = surrealdb::sql::Thing::from(("node".to_string(), source_id.to_string()));
         let target_thing = surrealdb::sql::Thing::from(("node".to_string(), target_id.to_string()));
 
-        // Check if mention already exists (for idempotency)
-        let check_query = "SELECT VALUE id FROM relationship WHERE in = $source AND out = $target AND relationship_type = 'mentions';";
+        // Check if the edge already exists (for idempotency)
+        let check_query = "SELECT VALUE id FROM relationship WHERE in = $source AND out = $target AND relationship_type = $relationship_type;";
         let mut check_response = self
-            .db
             .query(check_query)
             .bind(("source", source_thing.clone()))
             .bind(("target", target_thing.clone()))
+            .bind(("relationship_type", relationship_type))
             .await
-            .context("Failed to check for existing mention")?;
+            .context("Failed to check for existing relationship")?;
 
-        let existing_mention_ids: Vec<Thing> = check_response
+        let existing_ids: Vec<Thing> = check_response
             .take(0)
-            .context("Failed to extract mention check results")?;
+            .context("Failed to extract relationship check results")?;
 
-        // Only create mention if it doesn't exist
-        if existing_mention_ids.is_empty() {
+        // Only create the edge if it doesn't exist
+        if existing_ids.is_empty() {
             // Issue #834: Simplified mention relationship - no properties.root_id
             // Root/container is computed dynamically via graph traversal in get_mentioning_containers
             let query = r#"RELATE $source->relationship->$target CONTENT {
-                    relationship_type: 'mentions',
+                    relationship_type: $relationship_type,
                     properties: {},
                     created_at: time::now(),
                     modified_at: time::now(),
@@ -2882,12 +3654,12 @@ where
                 } RETURN id;"#;
 
             let mut response = self
-                .db
                 .query(query)
                 .bind(("source", source_thing))
                 .bind(("target", target_thing))
+                .bind(("relationship_type", relationship_type))
                 .await
-                .context("Failed to create mention")?;
+                .with_context(|| format!("Failed to create {} relationship", relationship_type))?;
 
             // Extract relationship ID for caller (Issue #813)
             #[derive(Debug, Deserialize)]
@@ -2922,30 +3694,47 @@ where
     /// * `Ok(None)` - If mention didn't exist
     /// * `Err` - Database error
     pub async fn delete_mention(&self, source_id: &str, target_id: &str) -> Result<Option<String>> {
+        self.delete_content_edge(source_id, target_id, "mentions")
+            .await
+    }
+
+    /// Delete the embed relationship for a removed `((node-id))` block embed
+    pub async fn delete_embed(&self, source_id: &str, target_id: &str) -> Result<Option<String>> {
+        self.delete_content_edge(source_id, target_id, "embeds")
+            .await
+    }
+
+    async fn delete_content_edge(
+        &self,
+        source_id: &str,
+        target_id: &str,
+        relationship_type: &'static str,
+    ) -> Result<Option<String>> {
         let source_thing = surrealdb::sql::Thing::from(("node".to_string(), source_id.to_string()));
         let target_thing = surrealdb::sql::Thing::from(("node".to_string(), target_id.to_string()));
 
         // First get the relationship ID before deleting (Issue #813)
-        let check_query = "SELECT VALUE id FROM relationship WHERE in = $source AND out = $target AND relationship_type = 'mentions';";
+        let check_query = "SELECT VALUE id FROM relationship WHERE in = $source AND out = $target AND relationship_type = $relationship_type;";
         let mut check_response = self
-            .db
             .query(check_query)
             .bind(("source", source_thing.clone()))
             .bind(("target", target_thing.clone()))
+            .bind(("relationship_type", relationship_type))
             .await
-            .context("Failed to get mention ID")?;
+            .context("Failed to get relationship ID")?;
 
         let existing_ids: Vec<Thing> = check_response
             .take(0)
-            .context("Failed to extract mention IDs")?;
+            .context("Failed to extract relationship IDs")?;
 
         // Delete the relationship
-        self.db
-            .query("DELETE FROM relationship WHERE in = $source AND out = $target AND relationship_type = 'mentions';")
+        self
+            .query("DELETE FROM relationship WHERE in = $source AND out = $target AND relationship_type = $relationship_type;")
             .bind(("source", source_thing))
             .bind(("target", target_thing))
+            .bind(("relationship_type", relationship_type))
             .await
-            .context("Failed to delete mention")?;
+            .with_context(|| format!("Failed to delete {} relationship", relationship_type))?;
 
         // Return relationship ID for caller to emit event (Issue #813)
         if let Some(rel_id) = existing_ids.first() {
@@ -2962,7 +3751,6 @@ where
             "SELECT ->relationship[WHERE relationship_type = 'mentions']->node.id AS mentioned_ids FROM type::thing('node', $node_id);";
 
         let mut response = self
-            .db
             .query(query)
             .bind(("node_id", node_id.to_string()))
             .await
@@ -3002,7 +3790,6 @@ where
             "SELECT <-relationship[WHERE relationship_type = 'mentions']<-node.id AS mentioned_by_ids FROM type::thing('node', $node_id);";
 
         let mut response = self
-            .db
             .query(query)
             .bind(("node_id", node_id.to_string()))
             .await
@@ -3035,33 +3822,137 @@ where
         Ok(mentioned_by_ids)
     }
 
-    /// Get incoming mentions to a node with their container nodes (root or task)
-    ///
-    /// This method finds all nodes that mention the target and resolves each
-    /// to its "container" - either the root node of its hierarchy, or itself
-    /// if it's a task node.
-    ///
-    /// # Performance
-    ///
-    /// Optimized batch approach:
-    /// 1. Single query to get all mentioning sources with their types
-    /// 2. Single recursive query to get ancestor chains for non-task sources
-    /// 3. Single batch query to fetch container nodes with id, title, nodeType
-    ///
-    /// # Arguments
-    ///
-    /// * `node_id` - The target node ID to find incoming mentions for
-    ///
-    /// # Returns
-    ///
-    /// Vector of `NodeReference` containing {id, title, nodeType} for each container
-    pub async fn get_incoming_mention_containers(
-        &self,
-        node_id: &str,
-    ) -> Result<Vec<crate::models::NodeReference>> {
-        use surrealdb::sql::Thing;
-        let start = std::time::Instant::now();
-        let target_thing = Thing::from(("node".to_string(), node_id.to_string()));
+    /// Titles of the live nodes that something mentions, by node ID
+    pub async fn get_mentioned_titles(&self) -> Result<HashMap<String, String>> {
+        #[derive(Debug, Deserialize)]
+        struct TitleRow {
+            id: String,
+            title: String,
+        }
+
+        let mut response = self
+            .query(
+                "SELECT record::id(id) AS id, title FROM node
+                WHERE title != NONE AND lifecycle_status != 'deleted'
+                    AND count(<-relationship[WHERE relationship_type = 'mentions']) > 0;",
+            )
+            .await
+            .context("Failed to get mentioned titles")?;
+        let rows: Vec<TitleRow> = response
+            .take(0)
+            .context("Failed to extract mentioned titles")?;
+        Ok(rows.into_iter().map(|row| (row.id, row.title)).collect())
+    }
+
+    /// IDs of the nodes whose content embeds `node_id` as a `((node-id))` block
+    pub async fn get_embedding_hosts(&self, node_id: &str) -> Result<Vec<String>> {
+        let target_thing = surrealdb::sql::Thing::from(("node".to_string(), node_id.to_string()));
+        let query =
+            "SELECT VALUE in FROM relationship WHERE out = $target AND relationship_type = 'embeds';";
+        let mut response = self
+            .query(query)
+            .bind(("target", target_thing))
+            .await
+            .context("Failed to get embedding hosts")?;
+
+        let hosts: Vec<Thing> = response
+            .take(0)
+            .context("Failed to extract embedding hosts")?;
+
+        Ok(hosts
+            .into_iter()
+            .filter_map(|thing| match thing.id {
+                Id::String(id) => Some(id),
+                _ => None,
+            })
+            .collect())
+    }
+
+    /// Node types of the `limit` most recently modified live roots, most
+    /// recent first and without repeats
+    pub async fn get_recent_root_types(&self, limit: usize) -> Result<Vec<String>> {
+        #[derive(Deserialize)]
+        struct RootType {
+            node_type: String,
+        }
+
+        let mut response = self
+            .query(
+                "SELECT node_type, modified_at FROM node
+                WHERE lifecycle_status != 'deleted'
+                    AND node_type != 'schema'
+                    AND count(<-relationship[WHERE relationship_type = 'has_child' AND properties.reference != true]) = 0
+                ORDER BY modified_at DESC
+                LIMIT $limit;",
+            )
+            .bind(("limit", limit as i64))
+            .await
+            .context("Failed to query recent roots")?;
+        let rows: Vec<RootType> = response.take(0).context("Failed to extract recent roots")?;
+
+        let mut types: Vec<String> = Vec::new();
+        for row in rows {
+            if !types.contains(&row.node_type) {
+                types.push(row.node_type);
+            }
+        }
+        Ok(types)
+    }
+
+    /// Live roots of `node_types` that nothing links to
+    ///
+    /// A root counts as linked when another node mentions or embeds it, or
+    /// when it belongs to a collection. Links to nodes inside the root do not
+    /// count. Oldest modification first.
+    pub async fn get_unlinked_roots(&self, node_types: &[&str]) -> Result<Vec<Node>> {
+        let types: Vec<String> = node_types.iter().map(|t| t.to_string()).collect();
+        let mut response = self
+            .query(
+                "SELECT * FROM node
+                WHERE node_type INSIDE $types
+                    AND lifecycle_status != 'deleted'
+                    AND count(<-relationship[WHERE relationship_type = 'has_child' AND properties.reference != true]) = 0
+                    AND count(<-relationship[WHERE relationship_type INSIDE ['mentions', 'embeds']]) = 0
+                    AND count(->relationship[WHERE relationship_type = 'member_of']) = 0
+                ORDER BY modified_at ASC;",
+            )
+            .bind(("types", types))
+            .await
+            .context("Failed to query unlinked roots")?;
+
+        let surreal_nodes: Vec<SurrealNode> = response
+            .take(0)
+            .context("Failed to extract unlinked roots")?;
+        Ok(surreal_nodes.into_iter().map(Into::into).collect())
+    }
+
+    /// Get incoming mentions to a node with their container nodes (root or task)
+    ///
+    /// This method finds all nodes that mention the target and resolves each
+    /// to its "container" - either the root node of its hierarchy, or itself
+    /// if it's a task node.
+    ///
+    /// # Performance
+    ///
+    /// Optimized batch approach:
+    /// 1. Single query to get all mentioning sources with their types
+    /// 2. Single recursive query to get ancestor chains for non-task sources
+    /// 3. Single batch query to fetch container nodes with id, title, nodeType
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The target node ID to find incoming mentions for
+    ///
+    /// # Returns
+    ///
+    /// Vector of `NodeReference` containing {id, title, nodeType} for each container
+    pub async fn get_incoming_mention_containers(
+        &self,
+        node_id: &str,
+    ) -> Result<Vec<crate::models::NodeReference>> {
+        use surrealdb::sql::Thing;
+        let start = std::time::Instant::now();
+        let target_thing = Thing::from(("node".to_string(), node_id.to_string()));
 
         // Query: Get mentioning sources with type, plus ancestor chain for each
         // Uses SurrealDB's recursive traversal to get all ancestors in one query
@@ -3070,13 +3961,12 @@ where
             SELECT
                 in.id AS source_id,
                 in.node_type AS source_type,
-                in.{..+collect}<-relationship[WHERE relationship_type = 'has_child']<-node AS ancestors
+                in.{..+collect}<-relationship[WHERE relationship_type = 'has_child' AND properties.reference != true]<-node AS ancestors
             FROM relationship
             WHERE out = $target AND relationship_type = 'mentions';
         "#;
 
         let mut response = self
-            .db
             .query(query)
             .bind(("target", target_thing.clone()))
             .await
@@ -3130,15 +4020,15 @@ where
             return Ok(Vec::new());
         }
 
-        // Batch fetch container nodes with just id, title, node_type
+        // Batch fetch container nodes with just id, title, node_type and appearance
         let container_things: Vec<Thing> = container_ids
             .iter()
             .map(|id| Thing::from(("node".to_string(), id.clone())))
             .collect();
 
-        let batch_query = "SELECT id, title, node_type FROM $containers;";
+        let batch_query =
+            "SELECT id, title, node_type, properties.appearance AS appearance FROM $containers;";
         let mut response = self
-            .db
             .query(batch_query)
             .bind(("containers", container_things))
             .await
@@ -3149,6 +4039,8 @@ where
             id: Thing,
             title: Option<String>,
             node_type: String,
+            #[serde(default)]
+            appearance: Option<crate::models::NodeAppearance>,
         }
 
         let containers: Vec<ContainerRow> = response
@@ -3167,6 +4059,7 @@ where
                     id: id_str,
                     title: c.title,
                     node_type: c.node_type,
+                    appearance: c.appearance,
                 }
             })
             .collect();
@@ -3181,6 +4074,393 @@ where
         Ok(result)
     }
 
+    /// Relationships of `relationship_types` lifted to their containers
+    ///
+    /// Each endpoint is replaced by the root of its tree, or kept when it is
+    /// a task (the grouping used by [`Self::get_incoming_mention_containers`]).
+    /// One unmerged edge of weight 1 per live relationship; see
+    /// [`crate::models::GraphData`].
+    pub async fn get_container_edges(
+        &self,
+        relationship_types: &[String],
+    ) -> Result<Vec<crate::models::GraphEdge>> {
+        let query = r#"
+            SELECT
+                in.id AS source_id,
+                in.node_type AS source_type,
+                in.{..+collect}<-relationship[WHERE relationship_type = 'has_child' AND properties.reference != true]<-node AS source_ancestors,
+                out.id AS target_id,
+                out.node_type AS target_type,
+                out.{..+collect}<-relationship[WHERE relationship_type = 'has_child' AND properties.reference != true]<-node AS target_ancestors,
+                relationship_type
+            FROM relationship
+            WHERE relationship_type INSIDE $types
+                AND in.lifecycle_status != 'deleted'
+                AND out.lifecycle_status != 'deleted';
+        "#;
+        let mut response = self
+            .query(query)
+            .bind(("types", relationship_types.to_vec()))
+            .await
+            .context("Failed to get container edges")?;
+
+        #[derive(Debug, Deserialize)]
+        struct EdgeRow {
+            source_id: Thing,
+            source_type: String,
+            #[serde(default)]
+            source_ancestors: Vec<Thing>,
+            target_id: Thing,
+            target_type: String,
+            #[serde(default)]
+            target_ancestors: Vec<Thing>,
+            relationship_type: String,
+        }
+
+        // The recursive collect lists ancestors closest first, so the root is last
+        let container = |id: Thing, node_type: &str, ancestors: Vec<Thing>| {
+            let container = match ancestors.into_iter().last() {
+                Some(root) if node_type != "task" => root,
+                _ => id,
+            };
+            match container.id {
+                Id::String(id) => Some(id),
+                _ => None,
+            }
+        };
+
+        let rows: Vec<EdgeRow> = response
+            .take(0)
+            .context("Failed to extract container edges")?;
+        Ok(rows
+            .into_iter()
+            .filter_map(|row| {
+                Some(crate::models::GraphEdge {
+                    source: container(row.source_id, &row.source_type, row.source_ancestors)?,
+                    target: container(row.target_id, &row.target_type, row.target_ancestors)?,
+                    edge_type: row.relationship_type,
+                    weight: 1,
+                })
+            })
+            .collect())
+    }
+
+    /// Replace all stored importance scores with `scores`
+    pub async fn replace_node_importance(
+        &self,
+        scores: &[crate::models::NodeImportance],
+    ) -> Result<()> {
+        // Scores serialize in camelCase, as they are sent to the frontend
+        self.query(
+            "BEGIN TRANSACTION;
+                DELETE node_importance;
+                FOR $score IN $scores {
+                    CREATE type::thing('node_importance', $score.nodeId) CONTENT {
+                        node: type::thing('node', $score.nodeId),
+                        score: <float> $score.score,
+                        rank: $score.rank,
+                        inbound: $score.inbound,
+                        outbound: $score.outbound
+                    };
+                };
+                COMMIT TRANSACTION;",
+        )
+        .bind(("scores", scores.to_vec()))
+        .await
+        .context("Failed to store node importance")?
+        .check()
+        .context("Failed to store node importance")?;
+        Ok(())
+    }
+
+    /// Stored importance of the given nodes; nodes without a score are left out
+    pub async fn get_node_importance(
+        &self,
+        node_ids: &[String],
+    ) -> Result<HashMap<String, crate::models::NodeImportance>> {
+        if node_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+        let things: Vec<Thing> = node_ids
+            .iter()
+            .map(|id| Thing::from(("node_importance".to_string(), id.clone())))
+            .collect();
+        let scores = self
+            .query_node_importance(
+                "SELECT record::id(node) AS nodeId, score, rank, inbound, outbound FROM $things;",
+                ("things", things),
+            )
+            .await?;
+        Ok(scores
+            .into_iter()
+            .map(|score| (score.node_id.clone(), score))
+            .collect())
+    }
+
+    /// The `limit` most important nodes, most central first
+    pub async fn get_top_node_importance(
+        &self,
+        limit: usize,
+    ) -> Result<Vec<crate::models::NodeImportance>> {
+        self.query_node_importance(
+            "SELECT record::id(node) AS nodeId, score, rank, inbound, outbound FROM node_importance ORDER BY rank ASC LIMIT $limit;",
+            ("limit", limit as i64),
+        )
+        .await
+    }
+
+    /// Run an importance SELECT; columns are aliased to the camelCase field names
+    async fn query_node_importance<T>(
+        &self,
+        sql: &str,
+        binding: (&'static str, T),
+    ) -> Result<Vec<crate::models::NodeImportance>>
+    where
+        T: Serialize + 'static,
+    {
+        let mut response = self
+            .query(sql)
+            .bind(binding)
+            .await
+            .context("Failed to query node importance")?;
+        response
+            .take(0)
+            .context("Failed to extract node importance")
+    }
+
+    // ========================================================================
+    // Broken Links (mentions and embeds of deleted nodes)
+    // ========================================================================
+
+    /// Broken links held by live nodes, newest first
+    pub async fn get_broken_links(&self) -> Result<Vec<crate::models::BrokenLink>> {
+        self.query_broken_links(
+            "SELECT *, record::id(id) AS link_id, record::id(source) AS source_id FROM broken_link WHERE source.lifecycle_status != 'deleted' ORDER BY detected_at DESC;",
+            None,
+        )
+        .await
+    }
+
+    pub async fn get_broken_link(
+        &self,
+        link_id: &str,
+    ) -> Result<Option<crate::models::BrokenLink>> {
+        let mut links = self
+            .query_broken_links(
+                "SELECT *, record::id(id) AS link_id, record::id(source) AS source_id FROM type::thing('broken_link', $link_id);",
+                Some(link_id),
+            )
+            .await?;
+        Ok(links.pop())
+    }
+
+    async fn query_broken_links(
+        &self,
+        query: &str,
+        link_id: Option<&str>,
+    ) -> Result<Vec<crate::models::BrokenLink>> {
+        #[derive(Debug, Deserialize)]
+        struct BrokenLinkRow {
+            link_id: String,
+            source_id: String,
+            target_id: String,
+            target_title: String,
+            link_type: String,
+            detected_at: String,
+        }
+
+        let mut request = self.query(query);
+        if let Some(link_id) = link_id {
+            request = request.bind(("link_id", link_id.to_string()));
+        }
+        let mut response = request.await.context("Failed to query broken links")?;
+        let rows: Vec<BrokenLinkRow> =
+            response.take(0).context("Failed to extract broken links")?;
+
+        rows.into_iter()
+            .map(|row| {
+                Ok(crate::models::BrokenLink {
+                    id: row.link_id,
+                    source_id: row.source_id,
+                    target_id: row.target_id,
+                    target_title: row.target_title,
+                    link_type: row.link_type,
+                    detected_at: DateTime::parse_from_rfc3339(&row.detected_at)
+                        .map(|dt| dt.with_timezone(&Utc))
+                        .context("Invalid broken link timestamp")?,
+                })
+            })
+            .collect()
+    }
+
+    pub async fn delete_broken_link(&self, link_id: &str) -> Result<()> {
+        self.query("DELETE type::thing('broken_link', $link_id);")
+            .bind(("link_id", link_id.to_string()))
+            .await
+            .context("Failed to delete broken link")?;
+        Ok(())
+    }
+
+    /// Drop the broken links of `link_type` from `source_id` to `target_id`,
+    /// once the source's content no longer holds them
+    pub async fn clear_broken_links(
+        &self,
+        source_id: &str,
+        target_id: &str,
+        link_type: &str,
+    ) -> Result<()> {
+        self.query(
+            "DELETE broken_link WHERE source = type::thing('node', $source_id)
+                    AND target_id = $target_id AND link_type = $link_type;",
+        )
+        .bind(("source_id", source_id.to_string()))
+        .bind(("target_id", target_id.to_string()))
+        .bind(("link_type", link_type.to_string()))
+        .await
+        .context("Failed to clear broken links")?;
+        Ok(())
+    }
+
+    /// Live nodes a find-and-replace may touch
+    ///
+    /// Nodes of `excluded_types` are left out. With `contains`, only nodes
+    /// whose content holds that text (ignoring case) are returned, so callers
+    /// still have to match exactly.
+    pub async fn get_replace_candidates(
+        &self,
+        contains: Option<&str>,
+        excluded_types: &[&str],
+    ) -> Result<Vec<Node>> {
+        let excluded: Vec<String> = excluded_types.iter().map(|t| t.to_string()).collect();
+        let sql = if contains.is_some() {
+            "SELECT * FROM node WHERE lifecycle_status != 'deleted' AND node_type NOT INSIDE $excluded
+                AND string::lowercase(content) CONTAINS string::lowercase($contains);"
+        } else {
+            "SELECT * FROM node WHERE lifecycle_status != 'deleted' AND node_type NOT INSIDE $excluded;"
+        };
+        let mut response = self
+            .query(sql)
+            .bind(("excluded", excluded))
+            .bind(("contains", contains.unwrap_or_default().to_string()))
+            .await
+            .context("Failed to get replace candidates")?;
+        let nodes: Vec<SurrealNode> = response
+            .take(0)
+            .context("Failed to extract replace candidates")?;
+        Ok(nodes.into_iter().map(Into::into).collect())
+    }
+
+    /// Write new contents in one transaction, skipping nodes edited meanwhile
+    ///
+    /// A node is only written while its content is still
+    /// [`ContentWrite::expected`]. Returns the IDs of the nodes written.
+    pub async fn replace_contents(
+        &self,
+        writes: &[ContentWrite],
+        source: Option<String>,
+    ) -> Result<Vec<String>> {
+        if writes.is_empty() {
+            return Ok(Vec::new());
+        }
+        self.query(
+            "BEGIN TRANSACTION;
+                FOR $write IN $writes {
+                    UPDATE type::thing('node', $write.node_id) SET
+                        content = $write.content,
+                        title = $write.title ?? title,
+                        modified_at = time::now(),
+                        version = version + 1
+                    WHERE content = $write.expected;
+                };
+                COMMIT TRANSACTION;",
+        )
+        .bind(("writes", writes.to_vec()))
+        .await
+        .context("Failed to replace contents")?
+        .check()
+        .context("Failed to replace contents")?;
+
+        let ids: Vec<String> = writes.iter().map(|w| w.node_id.clone()).collect();
+        let nodes = self.get_nodes_by_ids(&ids).await?;
+        let mut written = Vec::new();
+        for write in writes {
+            let Some(node) = nodes.get(&write.node_id) else {
+                continue;
+            };
+            if node.content != write.content {
+                continue;
+            }
+            written.push(write.node_id.clone());
+            self.notify(StoreChange {
+                operation: StoreOperation::Updated,
+                node: node.clone(),
+                source: source.clone(),
+            });
+        }
+        Ok(written)
+    }
+
+    /// The last applied find-and-replace, if it has not been undone
+    pub async fn get_replace_undo(&self) -> Result<Option<crate::models::ReplaceUndo>> {
+        #[derive(Deserialize)]
+        struct UndoRow {
+            pattern: crate::models::ReplacePattern,
+            replacement: String,
+            replaced_at: String,
+            changes: Vec<crate::models::ContentChange>,
+        }
+
+        let mut response = self
+            .query("SELECT pattern, replacement, replaced_at, changes FROM replace_undo:last;")
+            .await
+            .context("Failed to query replace undo")?;
+        let mut rows: Vec<UndoRow> = response.take(0).context("Failed to extract replace undo")?;
+        rows.pop()
+            .map(|row| {
+                Ok(crate::models::ReplaceUndo {
+                    pattern: row.pattern,
+                    replacement: row.replacement,
+                    replaced_at: DateTime::parse_from_rfc3339(&row.replaced_at)
+                        .context("Invalid replace timestamp")?
+                        .with_timezone(&Utc),
+                    changes: row.changes,
+                })
+            })
+            .transpose()
+    }
+
+    /// Keep `changes` as the one undoable replace, replacing any earlier one
+    pub async fn set_replace_undo(
+        &self,
+        pattern: &crate::models::ReplacePattern,
+        replacement: &str,
+        changes: &[crate::models::ContentChange],
+    ) -> Result<()> {
+        self.query(
+            "UPSERT replace_undo:last CONTENT {
+                    pattern: $pattern,
+                    replacement: $replacement,
+                    replaced_at: time::now(),
+                    changes: $changes
+                };",
+        )
+        .bind(("pattern", serde_json::to_value(pattern)?))
+        .bind(("replacement", replacement.to_string()))
+        .bind(("changes", serde_json::to_value(changes)?))
+        .await
+        .context("Failed to store replace undo")?
+        .check()
+        .context("Failed to store replace undo")?;
+        Ok(())
+    }
+
+    pub async fn delete_replace_undo(&self) -> Result<()> {
+        self.query("DELETE replace_undo:last;")
+            .await
+            .context("Failed to delete replace undo")?;
+        Ok(())
+    }
+
     pub async fn get_schema(&self, node_type: &str) -> Result<Option<Value>> {
         // Schema nodes use simple IDs (just the node type name, e.g., "date")
         // They're differentiated by node_type = "schema"
@@ -3304,6 +4584,7 @@ where
                 "UPDATE type::thing('node', $id_{idx}) SET
                     content = $content_{idx},
                     node_type = $node_type_{idx},
+                    properties = $properties_{idx},
                     modified_at = time::now(),
                     version = version + 1;",
                 idx = idx
@@ -3315,7 +4596,7 @@ where
         let transaction_query = transaction_parts.join("\n");
 
         // Build query with all bindings
-        let mut query_builder = self.db.query(transaction_query);
+        let mut query_builder = self.query(transaction_query);
 
         for (idx, (id, update)) in updates.iter().enumerate() {
             // Fetch current node again for building merged values
@@ -3326,11 +4607,16 @@ where
 
             let updated_content = update.content.clone().unwrap_or(current.content);
             let updated_node_type = update.node_type.clone().unwrap_or(current.node_type);
+            let mut updated_properties = current.properties;
+            if let Some(patch) = &update.properties {
+                crate::utils::merge_patch(&mut updated_properties, patch);
+            }
 
             query_builder = query_builder
                 .bind((format!("id_{}", idx), id.clone()))
                 .bind((format!("content_{}", idx), updated_content))
-                .bind((format!("node_type_{}", idx), updated_node_type));
+                .bind((format!("node_type_{}", idx), updated_node_type))
+                .bind((format!("properties_{}", idx), updated_properties));
         }
 
         query_builder
@@ -3340,6 +4626,163 @@ where
         Ok(())
     }
 
+    /// Fold `source_ids` into `target_id` in one transaction
+    ///
+    /// Appends the sources' children to the target's (in source order), moves
+    /// mentions and embeds of the sources onto the target, writes `contents`
+    /// (node ID, rewritten content and new title for nodes that have one) and
+    /// the target's merged `properties`, then moves the sources to the trash.
+    /// Links from the target or from another source are dropped rather than
+    /// moved. Returns the IDs of the moved children in their new order.
+    ///
+    /// Validation is left to `NodeService::merge_nodes`.
+    pub async fn merge_nodes_atomic(
+        &self,
+        target_id: &str,
+        source_ids: &[String],
+        properties: Value,
+        contents: Vec<(String, String, Option<String>)>,
+        source: Option<String>,
+    ) -> Result<Vec<String>> {
+        #[derive(Deserialize)]
+        struct ChildEdge {
+            parent_id: String,
+            child_id: String,
+            order: String,
+            reference: Option<bool>,
+        }
+
+        let target = Thing::from(("node".to_string(), target_id.to_string()));
+        let sources: Vec<Thing> = source_ids
+            .iter()
+            .map(|id| Thing::from(("node".to_string(), id.clone())))
+            .collect();
+
+        let mut response = self
+            .query(
+                "SELECT record::id(in) AS parent_id, record::id(out) AS child_id,
+                    properties.order AS order, properties.reference AS reference
+                FROM relationship
+                WHERE in INSIDE $sources AND relationship_type = 'has_child'
+                ORDER BY order ASC;",
+            )
+            .bind(("sources", sources.clone()))
+            .await
+            .context("Failed to get children of merged nodes")?;
+        let mut edges: Vec<ChildEdge> = response
+            .take(0)
+            .context("Failed to extract children of merged nodes")?;
+        edges.sort_by_key(|edge| source_ids.iter().position(|id| *id == edge.parent_id));
+
+        let mut children = Vec::with_capacity(edges.len());
+        let mut order: Option<String> = None;
+        for edge in &edges {
+            let key = match &order {
+                Some(prev) => {
+                    FractionalIndex::key_for_node(Some(prev.as_str()), None, &edge.child_id)
+                        .map_err(|e| anyhow::anyhow!(e))?
+                }
+                None => self.get_next_child_order(target_id, &edge.child_id).await?,
+            };
+            let mut edge_properties = serde_json::json!({ "order": key });
+            if edge.reference == Some(true) {
+                edge_properties["reference"] = Value::Bool(true);
+            }
+            children.push(serde_json::json!({
+                "parent": edge.parent_id,
+                "id": edge.child_id,
+                "properties": edge_properties,
+            }));
+            order = Some(key);
+        }
+        let updates: Vec<Value> = contents
+            .iter()
+            .map(|(id, content, title)| {
+                serde_json::json!({ "id": id, "content": content, "title": title })
+            })
+            .collect();
+
+        let transaction_query = r"
+            BEGIN TRANSACTION;
+
+            FOR $child IN $children {
+                LET $parent = type::thing('node', $child.parent);
+                LET $node = type::thing('node', $child.id);
+                DELETE relationship WHERE in = $parent AND out = $node AND relationship_type = 'has_child';
+                RELATE $target->relationship->$node CONTENT {
+                    relationship_type: 'has_child',
+                    properties: $child.properties,
+                    created_at: time::now(),
+                    modified_at: time::now(),
+                    version: 1
+                };
+            };
+
+            FOR $edge IN (
+                SELECT id, in, relationship_type FROM relationship
+                WHERE out INSIDE $sources AND relationship_type INSIDE ['mentions', 'embeds']
+            ) {
+                LET $from = $edge.in;
+                IF $from != $target AND $from NOT INSIDE $sources AND count(
+                    SELECT id FROM relationship
+                    WHERE in = $from AND out = $target AND relationship_type = $edge.relationship_type
+                ) = 0 {
+                    RELATE $from->relationship->$target CONTENT {
+                        relationship_type: $edge.relationship_type,
+                        properties: {},
+                        created_at: time::now(),
+                        modified_at: time::now(),
+                        version: 1
+                    };
+                };
+                DELETE $edge.id;
+            };
+
+            FOR $update IN $updates {
+                UPDATE type::thing('node', $update.id) SET
+                    content = $update.content,
+                    title = $update.title ?? title,
+                    modified_at = time::now(),
+                    version = version + 1;
+            };
+
+            UPDATE $target SET
+                properties = $properties,
+                modified_at = time::now(),
+                version = version + 1;
+
+            UPDATE $sources SET
+                lifecycle_status = 'deleted',
+                modified_at = time::now(),
+                version = version + 1;
+
+            COMMIT TRANSACTION;
+        ";
+
+        self.query(transaction_query)
+            .bind(("target", target))
+            .bind(("sources", sources))
+            .bind(("children", children))
+            .bind(("updates", updates))
+            .bind(("properties", properties))
+            .await
+            .context("Failed to execute merge transaction")?;
+
+        let mut changed: Vec<String> = vec![target_id.to_string()];
+        changed.extend(contents.into_iter().map(|(id, _, _)| id));
+        changed.extend(source_ids.iter().cloned());
+        let nodes = self.get_nodes_by_ids(&changed).await?;
+        for node in nodes.into_values() {
+            self.notify(StoreChange {
+                operation: StoreOperation::Updated,
+                node,
+                source: source.clone(),
+            });
+        }
+
+        Ok(edges.into_iter().map(|edge| edge.child_id).collect())
+    }
+
     pub async fn batch_create_nodes(&self, nodes: Vec<Node>) -> Result<Vec<Node>> {
         let mut created_nodes = Vec::new();
 
@@ -3377,7 +4820,7 @@ where
             String,
             String,
             Option<String>,
-            f64,
+            String,
             serde_json::Value,
         )>,
     ) -> Result<Vec<String>> {
@@ -3426,7 +4869,7 @@ where
                 query.push_str(&format!(
                     r#"RELATE node:`{parent}`->relationship->node:`{id}` CONTENT {{
                         relationship_type: 'has_child',
-                        properties: {{ order: {order} }},
+                        properties: {{ order: "{order}" }},
                         created_at: time::now(),
                         modified_at: time::now(),
                         version: 1
@@ -3434,7 +4877,7 @@ where
 "#,
                     parent = parent,
                     id = id,
-                    order = order
+                    order = Self::escape_surql_string(order)
                 ));
             }
         }
@@ -3443,7 +4886,6 @@ where
 
         // Execute the single transaction
         let response = self
-            .db
             .query(&query)
             .await
             .context("Failed to execute bulk hierarchy creation transaction")?;
@@ -3468,6 +4910,7 @@ where
                 member_of: vec![],
                 title: None, // Child nodes don't have titles
                 lifecycle_status: "active".to_string(),
+                rollups: BTreeMap::new(),
             };
             self.notify(StoreChange {
                 operation: StoreOperation::Created,
@@ -3490,7 +4933,7 @@ where
             String,
             String,
             Option<String>,
-            f64,
+            String,
             serde_json::Value,
         )>,
     ) -> Result<Vec<String>> {
@@ -3539,7 +4982,7 @@ where
                 query.push_str(&format!(
                     r#"RELATE node:`{parent}`->relationship->node:`{id}` CONTENT {{
                         relationship_type: 'has_child',
-                        properties: {{ order: {order} }},
+                        properties: {{ order: "{order}" }},
                         created_at: time::now(),
                         modified_at: time::now(),
                         version: 1
@@ -3547,7 +4990,7 @@ where
 "#,
                     parent = parent,
                     id = id,
-                    order = order
+                    order = Self::escape_surql_string(order)
                 ));
             }
         }
@@ -3556,7 +4999,6 @@ where
 
         // Execute the single transaction
         let response = self
-            .db
             .query(&query)
             .await
             .context("Failed to execute bulk hierarchy creation transaction")?;
@@ -3582,6 +5024,7 @@ where
                     member_of: vec![],
                     title: None,
                     lifecycle_status: "active".to_string(),
+                    rollups: BTreeMap::new(),
                 };
                 self.notify(StoreChange {
                     operation: StoreOperation::Created,
@@ -3594,6 +5037,242 @@ where
         Ok(nodes.into_iter().map(|(id, _, _, _, _, _)| id).collect())
     }
 
+    /// Create or replace many nodes and relationships for importers and sync
+    ///
+    /// Nodes are written first, `chunk_size` per transaction, then the edges
+    /// the same way, so a failing chunk only rolls back its own items. Each
+    /// node is created or has its content, type and properties replaced (its
+    /// version bumped, `created_at` kept); each edge replaces an existing one
+    /// of the same type between the same nodes. Edges touching a node that
+    /// failed are skipped.
+    ///
+    /// A `has_child` edge that isn't a reference moves its child like
+    /// [`Self::move_node`]: the child's other owning edge is removed, and an
+    /// edge that would make a cycle fails instead.
+    ///
+    /// Instead of one notification per row, only the batch's top-level nodes
+    /// (those no `has_child` edge in the batch points to) are notified, once
+    /// the whole batch is written, so clients refresh those subtrees.
+    pub async fn bulk_upsert(
+        &self,
+        nodes: Vec<Node>,
+        edges: Vec<EdgeUpsert>,
+        chunk_size: usize,
+    ) -> Result<BulkUpsertResult> {
+        let chunk_size = chunk_size.max(1);
+        let parented: HashSet<&str> = edges
+            .iter()
+            .filter(|edge| edge.relationship_type == "has_child")
+            .map(|edge| edge.to_id.as_str())
+            .collect();
+
+        // Top-level nodes are notified as created or updated, so look them up first
+        let mut existing_roots = HashSet::new();
+        for node in nodes.iter().filter(|n| !parented.contains(n.id.as_str())) {
+            if self.node_exists(&node.id).await? {
+                existing_roots.insert(node.id.clone());
+            }
+        }
+
+        let mut result = BulkUpsertResult::default();
+        let mut failed_nodes = HashSet::new();
+        for chunk in nodes.chunks(chunk_size) {
+            let mut valid = Vec::with_capacity(chunk.len());
+            let mut outcomes = Vec::with_capacity(chunk.len());
+            for node in chunk {
+                let error = self.validate_node_type(&node.node_type).err();
+                if error.is_none() {
+                    valid.push(node);
+                }
+                outcomes.push((node.id.clone(), error.map(|e| e.to_string())));
+            }
+
+            let written = self
+                .with_retry("bulk_upsert", || self.upsert_node_chunk(&valid, &parented))
+                .await;
+            for (id, error) in outcomes {
+                let error = error.or_else(|| written.as_ref().err().map(|e| format!("{:#}", e)));
+                if error.is_some() {
+                    failed_nodes.insert(id.clone());
+                }
+                result.nodes.push(BulkItemResult { id, error });
+            }
+        }
+
+        let writable = |edge: &EdgeUpsert| {
+            !failed_nodes.contains(&edge.from_id) && !failed_nodes.contains(&edge.to_id)
+        };
+        for chunk in edges.chunks(chunk_size) {
+            let mut valid = Vec::with_capacity(chunk.len());
+            let mut outcomes = Vec::with_capacity(chunk.len());
+            for edge in chunk {
+                let error = if !writable(edge) {
+                    Some("Node of this edge was not written".to_string())
+                } else if Self::owns_child(edge) {
+                    self.validate_child_edge(edge)
+                        .await
+                        .err()
+                        .map(|e| e.to_string())
+                } else {
+                    None
+                };
+                if error.is_none() {
+                    valid.push(edge);
+                }
+                outcomes.push((edge, error));
+            }
+
+            let written = self
+                .with_retry("bulk_upsert", || self.upsert_edge_chunk(&valid))
+                .await;
+            for (edge, error) in outcomes {
+                let error = error.or_else(|| written.as_ref().err().map(|e| format!("{:#}", e)));
+                result.edges.push(BulkItemResult {
+                    id: format!("{}->{}", edge.from_id, edge.to_id),
+                    error,
+                });
+            }
+        }
+
+        for node in nodes {
+            if parented.contains(node.id.as_str()) || failed_nodes.contains(&node.id) {
+                continue;
+            }
+            let operation = if existing_roots.contains(&node.id) {
+                StoreOperation::Updated
+            } else {
+                StoreOperation::Created
+            };
+            self.notify(StoreChange {
+                operation,
+                node,
+                source: Some("bulk_upsert".to_string()),
+            });
+        }
+
+        Ok(result)
+    }
+
+    /// Upsert `nodes` in one transaction
+    async fn upsert_node_chunk(&self, nodes: &[&Node], parented: &HashSet<&str>) -> Result<()> {
+        if nodes.is_empty() {
+            return Ok(());
+        }
+        let mut query = String::from("BEGIN TRANSACTION;\n");
+        for idx in 0..nodes.len() {
+            query.push_str(&format!(
+                "UPSERT type::thing('node', $id_{idx}) SET
+                    node_type = $node_type_{idx},
+                    content = $content_{idx},
+                    properties = $properties_{idx},
+                    title = $title_{idx},
+                    version = (version ?? 0) + 1,
+                    created_at = created_at ?? time::now(),
+                    modified_at = time::now(),
+                    lifecycle_status = lifecycle_status ?? 'active';\n",
+                idx = idx
+            ));
+        }
+        query.push_str("COMMIT TRANSACTION;\n");
+
+        let mut query_builder = self.query(query);
+        for (idx, node) in nodes.iter().enumerate() {
+            let title = Self::bulk_title(
+                &node.node_type,
+                parented.contains(node.id.as_str()),
+                &node.content,
+            );
+            query_builder = query_builder
+                .bind((format!("id_{}", idx), node.id.clone()))
+                .bind((format!("node_type_{}", idx), node.node_type.clone()))
+                .bind((format!("content_{}", idx), node.content.clone()))
+                .bind((format!("properties_{}", idx), node.properties.clone()))
+                .bind((format!("title_{}", idx), title));
+        }
+        query_builder
+            .await
+            .context("Failed to execute bulk node upsert transaction")?
+            .check()
+            .context("Bulk node upsert transaction failed")?;
+        Ok(())
+    }
+
+    /// Whether `edge` is a `has_child` edge that owns its child, not a
+    /// reference (transclusion)
+    fn owns_child(edge: &EdgeUpsert) -> bool {
+        edge.relationship_type == "has_child"
+            && edge.properties.get("reference") != Some(&Value::Bool(true))
+    }
+
+    /// Refuse an owning `has_child` edge that would make a cycle
+    async fn validate_child_edge(&self, edge: &EdgeUpsert) -> Result<()> {
+        if edge.from_id == edge.to_id {
+            return Err(anyhow::anyhow!(
+                "Node '{}' cannot be its own parent",
+                edge.from_id
+            ));
+        }
+        self.validate_no_cycle(&edge.from_id, &edge.to_id).await
+    }
+
+    /// Replace `edges` in one transaction
+    ///
+    /// An owning `has_child` edge replaces every other owning edge into its
+    /// child, keeping the child's references elsewhere, like `move_node`.
+    async fn upsert_edge_chunk(&self, edges: &[&EdgeUpsert]) -> Result<()> {
+        if edges.is_empty() {
+            return Ok(());
+        }
+        let mut query = String::from("BEGIN TRANSACTION;\n");
+        for (idx, edge) in edges.iter().enumerate() {
+            if Self::owns_child(edge) {
+                query.push_str(&format!(
+                    "DELETE relationship WHERE out = $to_{idx} AND relationship_type = 'has_child'
+                        AND (properties.reference != true OR in = $from_{idx});\n",
+                    idx = idx
+                ));
+            } else {
+                query.push_str(&format!(
+                    "DELETE relationship WHERE in = $from_{idx} AND out = $to_{idx}
+                        AND relationship_type = $type_{idx};\n",
+                    idx = idx
+                ));
+            }
+            query.push_str(&format!(
+                "RELATE $from_{idx}->relationship->$to_{idx} CONTENT {{
+                    relationship_type: $type_{idx},
+                    properties: $properties_{idx},
+                    created_at: time::now(),
+                    modified_at: time::now(),
+                    version: 1
+                }};\n",
+                idx = idx
+            ));
+        }
+        query.push_str("COMMIT TRANSACTION;\n");
+
+        let mut query_builder = self.query(query);
+        for (idx, edge) in edges.iter().enumerate() {
+            query_builder = query_builder
+                .bind((
+                    format!("from_{}", idx),
+                    Thing::from(("node".to_string(), edge.from_id.clone())),
+                ))
+                .bind((
+                    format!("to_{}", idx),
+                    Thing::from(("node".to_string(), edge.to_id.clone())),
+                ))
+                .bind((format!("type_{}", idx), edge.relationship_type.clone()))
+                .bind((format!("properties_{}", idx), edge.properties.clone()));
+        }
+        query_builder
+            .await
+            .context("Failed to execute bulk edge upsert transaction")?
+            .check()
+            .context("Bulk edge upsert transaction failed")?;
+        Ok(())
+    }
+
     /// Create a single node with parent relationship for streaming imports
     ///
     /// Universal Graph Architecture (Issue #783): All properties embedded in node.properties.
@@ -3610,7 +5289,7 @@ where
         node_type: String,
         content: String,
         parent_id: Option<String>,
-        order: f64,
+        order: String,
         properties: serde_json::Value,
     ) -> Result<String> {
         self.validate_node_type(&node_type)?;
@@ -3645,7 +5324,7 @@ where
             query.push_str(&format!(
                 r#"RELATE node:`{parent}`->relationship->node:`{id}` CONTENT {{
                     relationship_type: 'has_child',
-                    properties: {{ order: {order} }},
+                    properties: {{ order: "{order}" }},
                     created_at: time::now(),
                     modified_at: time::now(),
                     version: 1
@@ -3653,13 +5332,12 @@ where
 "#,
                 parent = parent,
                 id = id,
-                order = order
+                order = Self::escape_surql_string(&order)
             ));
         }
 
         // Execute query
         let response = self
-            .db
             .query(&query)
             .await
             .context("Failed to create node (streaming)")?;
@@ -3680,6 +5358,7 @@ where
             member_of: vec![],
             title: None, // Streaming nodes don't have titles (typically child nodes)
             lifecycle_status: "active".to_string(),
+            rollups: BTreeMap::new(),
         };
         self.notify(StoreChange {
             operation: StoreOperation::Created,
@@ -3709,14 +5388,23 @@ where
         parent_id: Option<&str>,
         content: &str,
     ) -> String {
-        if node_type == "date" || node_type == "schema" || node_type == "checkbox" {
-            "NONE".to_string()
-        } else if parent_id.is_none() || node_type == "task" || node_type == "collection" {
-            let stripped = crate::utils::strip_markdown(content);
-            let escaped_title = Self::escape_surql_string(&stripped);
-            format!("\"{}\"", escaped_title)
+        match Self::bulk_title(node_type, parent_id.is_some(), content) {
+            Some(title) => format!("\"{}\"", Self::escape_surql_string(&title)),
+            None => "NONE".to_string(),
+        }
+    }
+
+    /// Title of a node written in bulk, `None` for nodes without one
+    fn bulk_title(node_type: &str, has_parent: bool, content: &str) -> Option<String> {
+        if matches!(
+            node_type,
+            "date" | "week" | "month" | "quarter" | "schema" | "checkbox"
+        ) {
+            None
+        } else if !has_parent || node_type == "task" || node_type == "collection" {
+            Some(crate::utils::strip_markdown(content))
         } else {
-            "NONE".to_string()
+            None
         }
     }
 
@@ -3794,6 +5482,7 @@ where
                 properties.task.priority AS priority,
                 properties.task.due_date AS dueDate,
                 properties.task.assignee AS assignee,
+                properties.task.time_sessions ?? [] AS timeSessions,
                 content AS content,
                 version AS version,
                 created_at AS createdAt,
@@ -3804,7 +5493,6 @@ where
         );
 
         let mut response = self
-            .db
             .query(&query)
             .await
             .context(format!("Failed to query task node '{}'", id))?;
@@ -3815,6 +5503,49 @@ where
         Ok(tasks.into_iter().next())
     }
 
+    /// Get a node as a [`TypedNode`](crate::models::TypedNode) of a spoke type
+    ///
+    /// One query reads the node's metadata and `properties[T::NODE_TYPE]`
+    /// (falling back to flat properties on older nodes), so new spoke types
+    /// need no getter of their own. Returns `None` when the node is missing
+    /// or of another type.
+    pub async fn get_typed_node<T: crate::models::SpokeModel>(
+        &self,
+        id: &str,
+    ) -> Result<Option<crate::models::TypedNode<T>>> {
+        let mut response = self
+            .query(
+                "SELECT
+                    record::id(id) AS id,
+                    node_type AS nodeType,
+                    content,
+                    version,
+                    <string> created_at AS createdAt,
+                    <string> modified_at AS modifiedAt,
+                    properties[$node_type] ?? properties AS fields
+                FROM type::thing('node', $id)
+                WHERE node_type = $node_type;",
+            )
+            .bind(("id", id.to_string()))
+            .bind(("node_type", T::NODE_TYPE))
+            .await
+            .with_context(|| format!("Failed to query {} node '{}'", T::NODE_TYPE, id))?;
+
+        let rows: Vec<Value> = response
+            .take(0)
+            .with_context(|| format!("Failed to take {} node result", T::NODE_TYPE))?;
+        rows.into_iter()
+            .next()
+            .map(|row| {
+                serde_json::from_value::<crate::models::TypedNodeRow>(row)
+                    .and_then(|row| row.into_typed())
+                    .with_context(|| {
+                        format!("Failed to deserialize {} node '{}'", T::NODE_TYPE, id)
+                    })
+            })
+            .transpose()
+    }
+
     /// Update a task node with type-safe property updates
     ///
     /// Universal Graph Architecture (Issue #783): Updates task properties in
@@ -3968,7 +5699,6 @@ where
 
         // Execute transaction and check for errors (including IF/THROW version mismatch)
         let response = self
-            .db
             .query(&transaction_query)
             .await
             .context(format!("Failed to update task node '{}'", id))?;
@@ -3978,10 +5708,62 @@ where
             .check()
             .context(format!("Failed to update task node '{}'", id))?;
 
-        // Fetch and return updated task node
-        self.get_task_node(id)
-            .await?
-            .ok_or_else(|| anyhow::anyhow!("Task node '{}' not found after update", id))
+        // Fetch and return updated task node
+        self.get_task_node(id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Task node '{}' not found after update", id))
+    }
+
+    /// Open tasks whose `due_date` falls in `[from, until)`, earliest first
+    ///
+    /// Due dates are ISO strings (`YYYY-MM-DD` or RFC 3339), so a range over
+    /// the indexed `properties.task.due_date` column compares them
+    /// lexicographically: a timestamp on day `until` sorts after `until`
+    /// itself and is excluded. Without `from` the lower bound is the empty
+    /// string, which still keeps the scan on the index and skips tasks
+    /// without a due date (`NONE` sorts before every string).
+    pub async fn get_open_tasks_due(
+        &self,
+        from: Option<chrono::NaiveDate>,
+        until: chrono::NaiveDate,
+        filter: &crate::models::TaskDueFilter,
+    ) -> Result<Vec<Node>> {
+        let mut conditions = vec![
+            "node_type = 'task'",
+            "properties.task.due_date >= $from",
+            "properties.task.due_date < $until",
+            "properties.task.status NOTINSIDE ['done', 'cancelled']",
+        ];
+        if filter.assignee.is_some() {
+            conditions.push("properties.task.assignee = $assignee");
+        }
+        if filter.priority.is_some() {
+            conditions.push("properties.task.priority = $priority");
+        }
+        let sql = format!(
+            "SELECT * FROM node WHERE {} ORDER BY properties.task.due_date ASC;",
+            conditions.join(" AND ")
+        );
+
+        let from = from
+            .map(|date| date.format("%Y-%m-%d").to_string())
+            .unwrap_or_default();
+        let mut query = self
+            .query(sql)
+            .bind(("from", from))
+            .bind(("until", until.format("%Y-%m-%d").to_string()));
+        if let Some(assignee) = &filter.assignee {
+            query = query.bind(("assignee", assignee.clone()));
+        }
+        if let Some(priority) = &filter.priority {
+            query = query.bind(("priority", priority.as_str().to_string()));
+        }
+
+        let mut response = query.await.context("Failed to query tasks by due date")?;
+        let surreal_nodes: Vec<SurrealNode> = response
+            .take(0)
+            .context("Failed to extract tasks from due date query")?;
+        Ok(surreal_nodes.into_iter().map(Into::into).collect())
     }
 
     /// Get a schema node with strong typing
@@ -4025,7 +5807,6 @@ where
         );
 
         let mut response = self
-            .db
             .query(&query)
             .await
             .context(format!("Failed to query schema node '{}'", id))?;
@@ -4066,7 +5847,6 @@ where
         let query = "SELECT *, record::id(id) AS node_id OMIT id FROM node WHERE node_type = 'schema' ORDER BY node_id;";
 
         let mut response = self
-            .db
             .query(query)
             .await
             .context("Failed to query all schema nodes")?;
@@ -4126,8 +5906,7 @@ where
         }
 
         // Delete existing embeddings for this node
-        self.db
-            .query("DELETE embedding WHERE node = type::thing('node', $node_id);")
+        self.query("DELETE embedding WHERE node = type::thing('node', $node_id);")
             .bind(("node_id", node_id.to_string()))
             .await
             .context("Failed to delete existing embeddings")?;
@@ -4155,8 +5934,7 @@ where
             "#;
 
             let dimension = emb.vector.len() as i32;
-            self.db
-                .query(query)
+            self.query(query)
                 .bind(("node_id", emb.node_id.clone()))
                 .bind(("vector", emb.vector))
                 .bind(("dimension", dimension))
@@ -4182,7 +5960,7 @@ where
     ///
     /// Called when node content changes to trigger re-embedding.
     pub async fn mark_root_embedding_stale(&self, node_id: &str) -> Result<()> {
-        self.db
+        self
             .query(
                 "UPDATE embedding SET stale = true, modified_at = time::now() WHERE node = type::thing('node', $node_id);",
             )
@@ -4220,7 +5998,7 @@ where
         // Safety: debounce_secs is a u64 from config, not user input - validated at config layer
         let debounce_str = format!("{}s", debounce_secs);
 
-        let mut query_builder = self.db.query(sql).bind(("debounce", debounce_str));
+        let mut query_builder = self.query(sql).bind(("debounce", debounce_str));
 
         if let Some(lim) = limit {
             query_builder = query_builder.bind(("limit", lim));
@@ -4257,7 +6035,6 @@ where
         let debounce_str = format!("{}s", debounce_secs);
 
         let mut response = self
-            .db
             .query("SELECT count() AS count FROM embedding WHERE stale = true AND modified_at >= time::now() - type::duration($debounce) GROUP ALL;")
             .bind(("debounce", debounce_str))
             .await
@@ -4278,7 +6055,6 @@ where
         }
 
         let mut response = self
-            .db
             .query("SELECT count() AS count FROM embedding WHERE node = type::thing('node', $node_id) GROUP ALL;")
             .bind(("node_id", node_id.to_string()))
             .await
@@ -4293,8 +6069,7 @@ where
     ///
     /// Called when a node is deleted.
     pub async fn delete_embeddings(&self, node_id: &str) -> Result<()> {
-        self.db
-            .query("DELETE embedding WHERE node = type::thing('node', $node_id);")
+        self.query("DELETE embedding WHERE node = type::thing('node', $node_id);")
             .bind(("node_id", node_id.to_string()))
             .await
             .context("Failed to delete embeddings")?;
@@ -4306,20 +6081,19 @@ where
     ///
     /// Increments error count and stores the error message.
     pub async fn record_embedding_error(&self, node_id: &str, error: &str) -> Result<()> {
-        self.db
-            .query(
-                r#"
+        self.query(
+            r#"
                 UPDATE embedding SET
                     error_count = error_count + 1,
                     last_error = $error,
                     modified_at = time::now()
                 WHERE node = type::thing('node', $node_id);
                 "#,
-            )
-            .bind(("node_id", node_id.to_string()))
-            .bind(("error", error.to_string()))
-            .await
-            .context("Failed to record embedding error")?;
+        )
+        .bind(("node_id", node_id.to_string()))
+        .bind(("error", error.to_string()))
+        .await
+        .context("Failed to record embedding error")?;
 
         Ok(())
     }
@@ -4359,6 +6133,16 @@ where
         query_vector: &[f32],
         limit: i64,
         threshold: Option<f64>,
+    ) -> Result<Vec<crate::models::EmbeddingSearchResult>> {
+        self.with_query_timeout(self.search_embeddings_unbounded(query_vector, limit, threshold))
+            .await
+    }
+
+    async fn search_embeddings_unbounded(
+        &self,
+        query_vector: &[f32],
+        limit: i64,
+        threshold: Option<f64>,
     ) -> Result<Vec<crate::models::EmbeddingSearchResult>> {
         let min_score = threshold.unwrap_or(0.5);
 
@@ -4434,7 +6218,6 @@ where
         );
 
         let mut response = self
-            .db
             .query(&query)
             .bind(("query_vector", query_vector.to_vec()))
             .bind(("threshold", min_score))
@@ -4468,105 +6251,238 @@ where
     }
 
     // ========================================================================
-    // ========================================================================
-    // Collection Membership Operations (member_of relationships)
+    // Share Records (read-only public sharing bundles)
     // ========================================================================
 
-    /// Get the next order value for an ordered relationship.
-    ///
-    /// Issue #839: Common helper for fractional ordering of relationships.
-    /// Queries for the highest order value in the specified relationship type
-    /// and calculates the next value using FractionalOrderCalculator.
-    ///
-    /// # Arguments
-    ///
-    /// * `node_id` - The ID of the anchor node (collection for member_of, parent for has_child)
-    /// * `relationship_type` - The type of relationship ("member_of" or "has_child")
-    /// * `use_out_as_anchor` - If true, query by `out` field; if false, query by `in` field
-    ///
-    /// # Returns
-    ///
-    /// The next order value for appending to this relationship set
-    async fn get_next_order_for_relationship(
+    /// Record a newly created share bundle
+    pub async fn create_share(&self, share: &crate::models::ShareRecord) -> Result<()> {
+        self.query(
+            "CREATE type::thing('share', $share_id) CONTENT {
+                    root: type::thing('node', $root_id),
+                    secret: $secret,
+                    created_at: <datetime> $created_at,
+                    revoked_at: NONE
+                };",
+        )
+        .bind(("share_id", share.share_id.clone()))
+        .bind(("root_id", share.root_id.clone()))
+        .bind(("secret", share.secret.clone()))
+        .bind(("created_at", share.created_at.to_rfc3339()))
+        .await
+        .context("Failed to create share record")?
+        .check()
+        .context("Failed to create share record")?;
+        Ok(())
+    }
+
+    pub async fn get_share(&self, share_id: &str) -> Result<Option<crate::models::ShareRecord>> {
+        let mut shares = self
+            .query_shares(
+                "SELECT *, record::id(id) AS share_id, record::id(root) AS root_id FROM type::thing('share', $value);",
+                share_id,
+            )
+            .await?;
+        Ok(shares.pop())
+    }
+
+    /// Shares of a root, newest first
+    pub async fn get_shares_for_root(
         &self,
-        node_id: &str,
-        relationship_type: &str,
-        use_out_as_anchor: bool,
-    ) -> Result<f64> {
-        #[derive(Deserialize)]
-        struct EdgeOrder {
-            order: f64,
+        root_id: &str,
+    ) -> Result<Vec<crate::models::ShareRecord>> {
+        self.query_shares(
+            "SELECT *, record::id(id) AS share_id, record::id(root) AS root_id FROM share WHERE root = type::thing('node', $value) ORDER BY created_at DESC;",
+            root_id,
+        )
+        .await
+    }
+
+    async fn query_shares(
+        &self,
+        query: &str,
+        value: &str,
+    ) -> Result<Vec<crate::models::ShareRecord>> {
+        #[derive(Debug, Deserialize)]
+        struct ShareRow {
+            share_id: String,
+            root_id: String,
+            secret: String,
+            created_at: String,
+            revoked_at: Option<String>,
         }
 
-        let node_thing = Thing::from(("node".to_string(), node_id.to_string()));
+        let parse = |s: &str| {
+            DateTime::parse_from_rfc3339(s)
+                .map(|dt| dt.with_timezone(&Utc))
+                .context("Invalid share timestamp")
+        };
 
-        // Build query based on anchor direction
-        // member_of: collection is the OUT target (node -> relationship -> collection)
-        // has_child: parent is the IN source (parent -> relationship -> child)
-        let anchor_field = if use_out_as_anchor { "out" } else { "in" };
-        let query = format!(
-            "SELECT properties.order AS order FROM relationship WHERE {} = $node_thing AND relationship_type = $rel_type ORDER BY properties.order DESC LIMIT 1;",
-            anchor_field
-        );
+        let mut response = self
+            .query(query)
+            .bind(("value", value.to_string()))
+            .await
+            .context("Failed to query shares")?;
+        let rows: Vec<ShareRow> = response.take(0).context("Failed to extract shares")?;
+
+        rows.into_iter()
+            .map(|row| {
+                Ok(crate::models::ShareRecord {
+                    created_at: parse(&row.created_at)?,
+                    revoked_at: row.revoked_at.as_deref().map(parse).transpose()?,
+                    share_id: row.share_id,
+                    root_id: row.root_id,
+                    secret: row.secret,
+                })
+            })
+            .collect()
+    }
 
+    /// Mark a share revoked
+    ///
+    /// Returns `false` if no such share exists. Revoking twice keeps the
+    /// original revocation time.
+    pub async fn revoke_share(&self, share_id: &str) -> Result<bool> {
         let mut response = self
-            .db
-            .query(&query)
-            .bind(("node_thing", node_thing))
-            .bind(("rel_type", relationship_type.to_string()))
+            .query(
+                "UPDATE type::thing('share', $share_id) SET revoked_at = revoked_at ?? time::now() RETURN VALUE record::id(id);",
+            )
+            .bind(("share_id", share_id.to_string()))
             .await
-            .context(format!("Failed to get last {} order", relationship_type))?;
+            .context("Failed to revoke share")?;
+        let updated: Vec<String> = response
+            .take(0)
+            .context("Failed to extract revoked share")?;
+        Ok(!updated.is_empty())
+    }
 
-        let last_order: Option<EdgeOrder> = response.take(0).context(format!(
-            "Failed to extract last {} order",
-            relationship_type
-        ))?;
+    // ========================================================================
+    // Workspace Lock (passphrase verifier)
+    // ========================================================================
 
-        let new_order = if let Some(rel) = last_order {
-            FractionalOrderCalculator::calculate_order(Some(rel.order), None)
-        } else {
-            FractionalOrderCalculator::calculate_order(None, None)
-        };
+    pub async fn get_passphrase_verifier(
+        &self,
+    ) -> Result<Option<crate::models::PassphraseVerifier>> {
+        let mut response = self
+            .query("SELECT salt, hash, iterations FROM workspace_lock:passphrase;")
+            .await
+            .context("Failed to query passphrase verifier")?;
+        let mut verifiers: Vec<crate::models::PassphraseVerifier> = response
+            .take(0)
+            .context("Failed to extract passphrase verifier")?;
+        Ok(verifiers.pop())
+    }
 
-        Ok(new_order)
+    /// Store the verifier, replacing any previous passphrase
+    pub async fn set_passphrase_verifier(
+        &self,
+        verifier: &crate::models::PassphraseVerifier,
+    ) -> Result<()> {
+        self.query("UPSERT workspace_lock:passphrase CONTENT $verifier;")
+            .bind(("verifier", verifier.clone()))
+            .await
+            .context("Failed to store passphrase verifier")?
+            .check()
+            .context("Failed to store passphrase verifier")?;
+        Ok(())
+    }
+
+    pub async fn delete_passphrase_verifier(&self) -> Result<()> {
+        self.query("DELETE workspace_lock:passphrase;")
+            .await
+            .context("Failed to delete passphrase verifier")?
+            .check()
+            .context("Failed to delete passphrase verifier")?;
+        Ok(())
     }
 
-    /// Get the next order value for appending a member to a collection.
+    // ========================================================================
+    // ========================================================================
+    // Collection Membership Operations (member_of relationships)
+    // ========================================================================
+
+    /// Get the order key for appending `member_id` to a collection.
     ///
     /// Issue #839: Fractional ordering for member_of relationships.
-    /// Queries for the highest order value in the collection's member_of relationships
-    /// and calculates the next value using FractionalOrderCalculator.
+    /// Reads the collection's highest member_of key and derives the next one
+    /// with [`FractionalIndex::key_for_node`], as for has_child edges.
     ///
     /// # Arguments
     ///
     /// * `collection_id` - The ID of the collection node
+    /// * `member_id` - The ID of the node being appended
     ///
     /// # Returns
     ///
-    /// The next order value for appending to this collection
-    pub async fn get_next_member_order(&self, collection_id: &str) -> Result<f64> {
+    /// The order key for appending this member to the collection
+    pub async fn get_next_member_order(
+        &self,
+        collection_id: &str,
+        member_id: &str,
+    ) -> Result<String> {
+        let last_order = self.last_member_order(collection_id).await?;
+        FractionalIndex::key_for_node(last_order.as_deref(), None, member_id)
+            .map_err(|e| anyhow::anyhow!(e))
+    }
+
+    /// Highest member_of order key of a collection, `None` when it is empty
+    async fn last_member_order(&self, collection_id: &str) -> Result<Option<String>> {
+        #[derive(Deserialize)]
+        struct EdgeOrder {
+            order: String,
+        }
+
         // member_of: collection is the OUT target (node -> relationship -> collection)
-        self.get_next_order_for_relationship(collection_id, "member_of", true)
+        let collection_thing = Thing::from(("node".to_string(), collection_id.to_string()));
+        let mut response = self
+            .query("SELECT properties.order AS order FROM relationship WHERE out = $collection AND relationship_type = 'member_of' ORDER BY properties.order DESC LIMIT 1;")
+            .bind(("collection", collection_thing))
             .await
+            .context("Failed to get last member_of order")?;
+
+        let last_order: Option<EdgeOrder> = response
+            .take(0)
+            .context("Failed to extract last member_of order")?;
+        Ok(last_order.map(|rel| rel.order))
     }
 
-    /// Get the next order value for appending a child to a parent.
+    /// Get the order key for appending `child_id` to a parent.
     ///
     /// Issue #839: Factored out from add_child for reuse in NodeService.
-    /// Queries for the highest order value in the parent's has_child relationships
-    /// and calculates the next value using FractionalOrderCalculator.
+    /// Reads the parent's highest has_child key and derives the next one with
+    /// [`FractionalIndex::key_for_node`].
     ///
     /// # Arguments
     ///
     /// * `parent_id` - The ID of the parent node
+    /// * `child_id` - The ID of the child being appended
     ///
     /// # Returns
     ///
-    /// The next order value for appending to this parent
-    pub async fn get_next_child_order(&self, parent_id: &str) -> Result<f64> {
+    /// The order key for appending this child to the parent
+    pub async fn get_next_child_order(&self, parent_id: &str, child_id: &str) -> Result<String> {
+        #[derive(Deserialize)]
+        struct EdgeOrder {
+            order: String,
+        }
+
         // has_child: parent is the IN source (parent -> relationship -> child)
-        self.get_next_order_for_relationship(parent_id, "has_child", false)
+        let parent_thing = Thing::from(("node".to_string(), parent_id.to_string()));
+        let mut response = self
+            .query("SELECT properties.order AS order FROM relationship WHERE in = $parent_thing AND relationship_type = 'has_child' ORDER BY properties.order DESC LIMIT 1;")
+            .bind(("parent_thing", parent_thing))
             .await
+            .context("Failed to get last has_child order")?;
+
+        let last_order: Option<EdgeOrder> = response
+            .take(0)
+            .context("Failed to extract last has_child order")?;
+
+        FractionalIndex::key_for_node(
+            last_order.as_ref().map(|rel| rel.order.as_str()),
+            None,
+            child_id,
+        )
+        .map_err(|e| anyhow::anyhow!(e))
     }
 
     /// Add a node to a collection (create member_of relationship)
@@ -4601,26 +6517,18 @@ where
         // is done in CollectionService.add_to_collection (service layer).
         // Store layer focuses on data persistence only.
 
-        // Issue #865: Atomic add_to_collection operation
-        // Combines check, order calculation, and creation in a single query to prevent
-        // race conditions where concurrent adds see stale max order values.
-        //
-        // Jitter calculation:
-        // - counter_val increments atomically per-call
-        // - time_nanos provides sub-millisecond entropy
-        // - Combined into range 0.0 to 0.001 for uniqueness without affecting ordering
-        let jitter = FractionalOrderCalculator::generate_jitter();
+        // Issue #865: The existence check and creation run as a single query.
+        // The order key is derived from the member's ID, so concurrent adds
+        // that read the same last key still get distinct keys.
+        let order = self.get_next_member_order(collection_id, member_id).await?;
 
         // All LET statements must be at the top level (not inside IF blocks in SurrealDB)
-        // We compute everything upfront, then conditionally create the relationship.
         let query = r#"
             LET $existing = (SELECT id FROM relationship WHERE in = $member AND out = $collection AND relationship_type = 'member_of' LIMIT 1);
-            LET $max_order_result = (SELECT properties.order AS order FROM relationship WHERE out = $collection AND relationship_type = 'member_of' ORDER BY properties.order DESC LIMIT 1);
-            LET $new_order = IF array::len($max_order_result) > 0 THEN $max_order_result[0].order + 1.0 + $jitter ELSE 1.0 + $jitter END;
             IF array::len($existing) = 0 THEN
                 (RELATE $member->relationship->$collection CONTENT {
                     relationship_type: 'member_of',
-                    properties: { order: $new_order },
+                    properties: { order: $order },
                     created_at: time::now(),
                     modified_at: time::now(),
                     version: 1
@@ -4629,89 +6537,371 @@ where
         "#;
 
         let mut response = self
-            .db
             .query(query)
             .bind(("member", member_thing))
             .bind(("collection", collection_thing))
-            .bind(("jitter", jitter))
+            .bind(("order", order))
             .await
             .context("Failed to add to collection")?;
 
         // SurrealDB returns results for each statement.
-        // Statements: 0=LET $existing, 1=LET $max_order_result, 2=LET $new_order, 3=IF block
-        // The RELATE result is inside the IF block, so it's returned from statement index 3
+        // Statements: 0=LET $existing, 1=IF block
+        // The RELATE result is inside the IF block, so it's returned from statement index 1
         #[derive(Debug, Deserialize)]
         struct RelateResult {
             id: Thing,
         }
 
-        // Try indices 0-4 to find the result. We check beyond index 3 as a safety buffer
-        // in case SurrealDB query structure changes or adds intermediate results.
-        // Expected: index 3 contains the RELATE result from the IF block.
-        const MAX_RESULT_INDEX: usize = 5;
-        for idx in 0..MAX_RESULT_INDEX {
-            if let Ok(results) = response.take::<Vec<RelateResult>>(idx) {
-                if let Some(result) = results.first() {
-                    return Ok(Some(result.id.to_string()));
-                }
-            }
+        // Try indices 0-2 to find the result. We check beyond index 1 as a safety buffer
+        // in case SurrealDB query structure changes or adds intermediate results.
+        // Expected: index 1 contains the RELATE result from the IF block.
+        const MAX_RESULT_INDEX: usize = 3;
+        for idx in 0..MAX_RESULT_INDEX {
+            if let Ok(results) = response.take::<Vec<RelateResult>>(idx) {
+                if let Some(result) = results.first() {
+                    return Ok(Some(result.id.to_string()));
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Remove a node from a collection (delete member_of relationship)
+    ///
+    /// Deletes the member_of relationship from the member node to the collection node.
+    /// Issue #788: Universal Relationship Architecture - deletes from relationship table.
+    /// Issue #813: Pure data layer - no event emission, returns relationship ID for service layer.
+    ///
+    /// # Arguments
+    ///
+    /// * `member_id` - The ID of the node to remove from the collection
+    /// * `collection_id` - The ID of the collection node
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(id))` - Relationship ID if deleted
+    /// * `Ok(None)` - If membership didn't exist
+    /// * `Err` - Database error
+    pub async fn remove_from_collection(
+        &self,
+        member_id: &str,
+        collection_id: &str,
+    ) -> Result<Option<String>> {
+        let member_thing = Thing::from(("node".to_string(), member_id.to_string()));
+        let collection_thing = Thing::from(("node".to_string(), collection_id.to_string()));
+
+        // First get the relationship ID before deleting (Issue #813)
+        let check_query = "SELECT VALUE id FROM relationship WHERE in = $member AND out = $collection AND relationship_type = 'member_of';";
+        let mut check_response = self
+            .query(check_query)
+            .bind(("member", member_thing.clone()))
+            .bind(("collection", collection_thing.clone()))
+            .await
+            .context("Failed to get membership ID")?;
+
+        let existing_ids: Vec<Thing> = check_response
+            .take(0)
+            .context("Failed to extract membership IDs")?;
+
+        // Delete the relationship
+        self
+            .query("DELETE FROM relationship WHERE in = $member AND out = $collection AND relationship_type = 'member_of';")
+            .bind(("member", member_thing))
+            .bind(("collection", collection_thing))
+            .await
+            .context("Failed to delete membership")?;
+
+        // Return relationship ID for caller to emit event (Issue #813)
+        if let Some(rel_id) = existing_ids.first() {
+            return Ok(Some(rel_id.to_string()));
+        }
+
+        Ok(None)
+    }
+
+    /// Move a member to another position within its collection
+    ///
+    /// The member is placed right after `insert_after_member_id`, or first
+    /// when `None`. An `insert_after_member_id` that is not a member of the
+    /// collection falls back to appending, as in [`Self::move_node`]. The
+    /// collection's keys are renumbered first when they have degraded.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some((relationship_id, order)))` - The moved edge and its new key
+    /// * `Ok(None)` - If `member_id` is not a member of the collection
+    /// * `Err` - Database error
+    pub async fn move_collection_member(
+        &self,
+        collection_id: &str,
+        member_id: &str,
+        insert_after_member_id: Option<&str>,
+    ) -> Result<Option<(String, String)>> {
+        self.with_retry("move_collection_member", || {
+            self.move_collection_member_once(collection_id, member_id, insert_after_member_id)
+        })
+        .await
+    }
+
+    async fn move_collection_member_once(
+        &self,
+        collection_id: &str,
+        member_id: &str,
+        insert_after_member_id: Option<&str>,
+    ) -> Result<Option<(String, String)>> {
+        #[derive(Deserialize)]
+        struct MemberOrder {
+            id: Thing,
+            #[serde(rename = "in")]
+            member: Thing,
+            order: String,
+        }
+
+        let collection_thing = Thing::from(("node".to_string(), collection_id.to_string()));
+        let member_thing = Thing::from(("node".to_string(), member_id.to_string()));
+        let members_query = "SELECT id, in, properties.order AS order FROM relationship WHERE out = $collection AND relationship_type = 'member_of' ORDER BY properties.order ASC;";
+
+        let mut response = self
+            .query(members_query)
+            .bind(("collection", collection_thing.clone()))
+            .await
+            .context("Failed to get collection memberships")?;
+        let mut members: Vec<MemberOrder> = response
+            .take(0)
+            .context("Failed to extract collection memberships")?;
+
+        let orders: Vec<&str> = members.iter().map(|m| m.order.as_str()).collect();
+        if FractionalIndex::needs_rebalancing(&orders) {
+            self.rebalance_collection_members(collection_id).await?;
+            let mut response = self
+                .query(members_query)
+                .bind(("collection", collection_thing.clone()))
+                .await
+                .context("Failed to get collection memberships after rebalancing")?;
+            members = response
+                .take(0)
+                .context("Failed to extract collection memberships after rebalancing")?;
+        }
+
+        let Some(position) = members.iter().position(|m| m.member == member_thing) else {
+            return Ok(None);
+        };
+        let moved = members.remove(position);
+
+        let (prev, next) = match insert_after_member_id {
+            Some(after_id) => {
+                let after_thing = Thing::from(("node".to_string(), after_id.to_string()));
+                match members.iter().position(|m| m.member == after_thing) {
+                    Some(after_index) => (members.get(after_index), members.get(after_index + 1)),
+                    None => {
+                        tracing::warn!(
+                            member_id = %after_id,
+                            collection_id = %collection_id,
+                            "insert_after_member_id is not a member of the collection, falling back to append"
+                        );
+                        (members.last(), None)
+                    }
+                }
+            }
+            None => (None, members.first()),
+        };
+
+        let order = FractionalIndex::key_for_node(
+            prev.map(|m| m.order.as_str()),
+            next.map(|m| m.order.as_str()),
+            member_id,
+        )
+        .map_err(|e| anyhow::anyhow!(e))?;
+
+        self.query(
+            "UPDATE $edge SET properties.order = $order, modified_at = time::now(), version += 1;",
+        )
+        .bind(("edge", moved.id.clone()))
+        .bind(("order", order.clone()))
+        .await
+        .context("Failed to move collection member")?;
+
+        Ok(Some((moved.id.to_string(), order)))
+    }
+
+    /// Renumber a collection's member_of keys in their current order
+    ///
+    /// The member_of counterpart of [`Self::rebalance_children_for_parent`],
+    /// run when keys have grown too long, collide, or are legacy floats.
+    async fn rebalance_collection_members(&self, collection_id: &str) -> Result<()> {
+        #[derive(Deserialize)]
+        struct Edge {
+            id: Thing,
+        }
+
+        let collection_thing = Thing::from(("node".to_string(), collection_id.to_string()));
+        let mut response = self
+            .query("SELECT id, properties.order FROM relationship WHERE out = $collection AND relationship_type = 'member_of' ORDER BY properties.order ASC;")
+            .bind(("collection", collection_thing))
+            .await
+            .context("Failed to get memberships for rebalancing")?;
+        let edges: Vec<Edge> = response
+            .take(0)
+            .context("Failed to extract memberships for rebalancing")?;
+
+        if edges.is_empty() {
+            return Ok(());
+        }
+
+        let mut transaction = String::from("BEGIN TRANSACTION;\n");
+        for i in 0..edges.len() {
+            transaction.push_str(&format!(
+                "UPDATE $edge{i} SET properties.order = $order{i};\n"
+            ));
+        }
+        transaction.push_str("COMMIT TRANSACTION;");
+
+        let mut query_builder = self.query(&transaction);
+        for (i, edge) in edges.iter().enumerate() {
+            query_builder = query_builder
+                .bind((format!("edge{}", i), edge.id.clone()))
+                .bind((format!("order{}", i), FractionalIndex::nth(i)));
         }
+        query_builder
+            .await
+            .context("Failed to rebalance collection members")?;
 
-        Ok(None)
+        tracing::debug!(
+            "Rebalanced {} members of collection {}",
+            edges.len(),
+            collection_id
+        );
+        Ok(())
     }
 
-    /// Remove a node from a collection (delete member_of relationship)
-    ///
-    /// Deletes the member_of relationship from the member node to the collection node.
-    /// Issue #788: Universal Relationship Architecture - deletes from relationship table.
-    /// Issue #813: Pure data layer - no event emission, returns relationship ID for service layer.
-    ///
-    /// # Arguments
+    /// Rename a collection and optionally move it to another parent
     ///
-    /// * `member_id` - The ID of the node to remove from the collection
-    /// * `collection_id` - The ID of the collection node
+    /// Sets the collection's name to `name` and, when `to_parent_id` differs
+    /// from `from_parent_id`, replaces its member_of edge to the old parent
+    /// with one appended to the new parent's members, all in one transaction.
+    /// Members and sub-collections keep their edges to the collection, so
+    /// they move along with it. A collection already in the new parent just
+    /// loses the edge to the old one.
     ///
     /// # Returns
     ///
-    /// * `Ok(Some(id))` - Relationship ID if deleted
-    /// * `Ok(None)` - If membership didn't exist
-    /// * `Err` - Database error
-    pub async fn remove_from_collection(
+    /// The renamed collection and the edges replaced, for event emission
+    pub async fn move_collection(
         &self,
-        member_id: &str,
         collection_id: &str,
-    ) -> Result<Option<String>> {
-        let member_thing = Thing::from(("node".to_string(), member_id.to_string()));
-        let collection_thing = Thing::from(("node".to_string(), collection_id.to_string()));
+        name: &str,
+        from_parent_id: Option<&str>,
+        to_parent_id: Option<&str>,
+        source: Option<String>,
+    ) -> Result<CollectionMove> {
+        let reparent = from_parent_id != to_parent_id;
+        let from_parent_id = from_parent_id.filter(|_| reparent);
+        let to_parent_id = to_parent_id.filter(|_| reparent);
+
+        let removed_edge = match from_parent_id {
+            Some(parent_id) => self.membership_edge(collection_id, parent_id).await?,
+            None => None,
+        };
+        let new_order = match to_parent_id {
+            Some(parent_id) => match self.membership_edge(collection_id, parent_id).await? {
+                Some(_) => None,
+                None => Some(self.get_next_member_order(parent_id, collection_id).await?),
+            },
+            None => None,
+        };
 
-        // First get the relationship ID before deleting (Issue #813)
-        let check_query = "SELECT VALUE id FROM relationship WHERE in = $member AND out = $collection AND relationship_type = 'member_of';";
-        let mut check_response = self
-            .db
-            .query(check_query)
-            .bind(("member", member_thing.clone()))
-            .bind(("collection", collection_thing.clone()))
+        let mut transaction = String::from(
+            "BEGIN TRANSACTION;\nUPDATE $collection SET content = $name, modified_at = time::now(), version += 1;\n",
+        );
+        if removed_edge.is_some() {
+            transaction.push_str(
+                "DELETE relationship WHERE in = $collection AND out = $from_parent AND relationship_type = 'member_of';\n",
+            );
+        }
+        if new_order.is_some() {
+            transaction.push_str(
+                r#"RELATE $collection->relationship->$to_parent CONTENT {
+                    relationship_type: 'member_of',
+                    properties: { order: $order },
+                    created_at: time::now(),
+                    modified_at: time::now(),
+                    version: 1
+                };
+                "#,
+            );
+        }
+        transaction.push_str("COMMIT TRANSACTION;");
+
+        let mut query_builder = self
+            .query(&transaction)
+            .bind((
+                "collection",
+                Thing::from(("node".to_string(), collection_id.to_string())),
+            ))
+            .bind(("name", name.to_string()));
+        if let Some(parent_id) = from_parent_id {
+            query_builder = query_builder.bind((
+                "from_parent",
+                Thing::from(("node".to_string(), parent_id.to_string())),
+            ));
+        }
+        if let (Some(parent_id), Some(order)) = (to_parent_id, &new_order) {
+            query_builder = query_builder
+                .bind((
+                    "to_parent",
+                    Thing::from(("node".to_string(), parent_id.to_string())),
+                ))
+                .bind(("order", order.clone()));
+        }
+        query_builder
             .await
-            .context("Failed to get membership ID")?;
+            .context("Failed to move collection")?
+            .check()
+            .context("Failed to move collection")?;
 
-        let existing_ids: Vec<Thing> = check_response
-            .take(0)
-            .context("Failed to extract membership IDs")?;
+        let created_edge = match (to_parent_id, new_order) {
+            (Some(parent_id), Some(order)) => self
+                .membership_edge(collection_id, parent_id)
+                .await?
+                .map(|id| (id, order)),
+            _ => None,
+        };
 
-        // Delete the relationship
-        self.db
-            .query("DELETE FROM relationship WHERE in = $member AND out = $collection AND relationship_type = 'member_of';")
-            .bind(("member", member_thing))
-            .bind(("collection", collection_thing))
-            .await
-            .context("Failed to delete membership")?;
+        let collection = self
+            .get_node(collection_id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Collection not found after move: {}", collection_id))?;
+        self.notify(StoreChange {
+            operation: StoreOperation::Updated,
+            node: collection.clone(),
+            source,
+        });
 
-        // Return relationship ID for caller to emit event (Issue #813)
-        if let Some(rel_id) = existing_ids.first() {
-            return Ok(Some(rel_id.to_string()));
-        }
+        Ok(CollectionMove {
+            collection,
+            removed_edge,
+            created_edge,
+        })
+    }
 
-        Ok(None)
+    /// ID of the member_of edge from `member_id` to `collection_id`, if any
+    async fn membership_edge(
+        &self,
+        member_id: &str,
+        collection_id: &str,
+    ) -> Result<Option<String>> {
+        let mut response = self
+            .query("SELECT VALUE id FROM relationship WHERE in = $member AND out = $collection AND relationship_type = 'member_of';")
+            .bind(("member", Thing::from(("node".to_string(), member_id.to_string()))))
+            .bind((
+                "collection",
+                Thing::from(("node".to_string(), collection_id.to_string())),
+            ))
+            .await
+            .context("Failed to get membership")?;
+        let ids: Vec<Thing> = response.take(0).context("Failed to extract membership")?;
+        Ok(ids.first().map(|id| id.to_string()))
     }
 
     /// Get all collections a node belongs to
@@ -4732,7 +6922,6 @@ where
             "SELECT ->relationship[WHERE relationship_type = 'member_of']->node.id AS collection_ids FROM type::thing('node', $node_id);";
 
         let mut response = self
-            .db
             .query(query)
             .bind(("node_id", node_id.to_string()))
             .await
@@ -4789,7 +6978,6 @@ where
         // Uses LET to preserve the ordered array, then SELECT * FROM array preserves order
         // This should use idx_rel_member_order index on (out, relationship_type, properties.order)
         let mut response = self
-            .db
             .query(
                 r#"
                 LET $member_ids = (
@@ -4851,7 +7039,6 @@ where
         "#;
 
         let mut response = self
-            .db
             .query(query)
             .bind(("name", normalized_name))
             .await
@@ -4913,7 +7100,6 @@ where
 
         tracing::debug!("get_collections_by_names: executing query...");
         let mut response = self
-            .db
             .query(query)
             .bind(("names", normalized_names))
             .await
@@ -4956,6 +7142,128 @@ where
         Ok(collections)
     }
 
+    /// Counts, last activity and type breakdown of a collection's subtree
+    ///
+    /// The subtree is the collection and every collection nested below it
+    /// through member_of edges. All figures come from one round-trip without
+    /// loading any member.
+    pub async fn get_collection_stats(&self, collection_id: &str) -> Result<CollectionStats> {
+        #[derive(Deserialize)]
+        struct TypeCount {
+            node_type: String,
+            count: i64,
+        }
+
+        #[derive(Deserialize)]
+        struct Count {
+            count: i64,
+        }
+
+        let collection_thing = Thing::from(("node".to_string(), collection_id.to_string()));
+        let query = r#"
+            LET $reached = array::distinct($collection.{..+collect}<-relationship[WHERE relationship_type = 'member_of']<-node);
+            LET $direct = $collection<-relationship[WHERE relationship_type = 'member_of']<-node;
+            SELECT node_type, count() AS count FROM $reached GROUP BY node_type;
+            SELECT count() AS count FROM $direct WHERE node_type != 'collection' GROUP ALL;
+            SELECT VALUE modified_at FROM $reached WHERE node_type != 'collection' ORDER BY modified_at DESC LIMIT 1;
+            SELECT VALUE modified_at FROM relationship WHERE relationship_type = 'member_of' AND (out = $collection OR out IN $reached) ORDER BY modified_at DESC LIMIT 1;
+        "#;
+
+        let mut response = self
+            .query(query)
+            .bind(("collection", collection_thing))
+            .await
+            .context("Failed to get collection stats")?;
+
+        let type_counts: Vec<TypeCount> = response
+            .take(2)
+            .context("Failed to extract collection type counts")?;
+        let direct: Option<Count> = response
+            .take(3)
+            .context("Failed to extract direct member count")?;
+        let member_activity: Option<String> = response
+            .take(4)
+            .context("Failed to extract last member edit")?;
+        let membership_activity: Option<String> = response
+            .take(5)
+            .context("Failed to extract last membership change")?;
+
+        let mut stats = CollectionStats {
+            direct_members: direct.map_or(0, |c| c.count.max(0) as usize),
+            last_activity: [member_activity, membership_activity]
+                .into_iter()
+                .flatten()
+                .filter_map(|at| DateTime::parse_from_rfc3339(&at).ok())
+                .map(|at| at.with_timezone(&Utc))
+                .max(),
+            ..CollectionStats::default()
+        };
+        for row in type_counts {
+            let count = row.count.max(0) as usize;
+            if row.node_type == "collection" {
+                stats.subcollections = count;
+            } else {
+                stats.recursive_members += count;
+                stats.types.insert(row.node_type, count);
+            }
+        }
+        Ok(stats)
+    }
+
+    /// Collections nested below a collection, with their parent collections
+    ///
+    /// Covers every collection reachable through member_of edges at any
+    /// depth. Parents are limited to the collection and the returned
+    /// collections, so the entries describe the subtree on their own.
+    pub async fn get_subcollections(&self, collection_id: &str) -> Result<Vec<SubCollection>> {
+        #[derive(Deserialize)]
+        struct Row {
+            id: Thing,
+            content: String,
+            parents: Vec<Thing>,
+        }
+
+        let query = r#"
+            LET $reached = array::distinct($collection.{..+collect}<-relationship[WHERE relationship_type = 'member_of']<-node);
+            SELECT id, content, ->relationship[WHERE relationship_type = 'member_of']->node AS parents
+            FROM $reached WHERE node_type = 'collection';
+        "#;
+        let collection_thing = Thing::from(("node".to_string(), collection_id.to_string()));
+        let mut response = self
+            .query(query)
+            .bind(("collection", collection_thing))
+            .await
+            .context("Failed to get subcollections")?;
+        let rows: Vec<Row> = response
+            .take(1)
+            .context("Failed to extract subcollections")?;
+
+        let record_id = |thing: &Thing| match &thing.id {
+            Id::String(id) => Some(id.clone()),
+            _ => None,
+        };
+        let ids: HashSet<String> = rows
+            .iter()
+            .filter_map(|row| record_id(&row.id))
+            .chain(std::iter::once(collection_id.to_string()))
+            .collect();
+        Ok(rows
+            .iter()
+            .filter_map(|row| {
+                Some(SubCollection {
+                    id: record_id(&row.id)?,
+                    name: row.content.clone(),
+                    parent_ids: row
+                        .parents
+                        .iter()
+                        .filter_map(record_id)
+                        .filter(|id| ids.contains(id))
+                        .collect(),
+                })
+            })
+            .collect())
+    }
+
     /// Get all members of a collection recursively (including members of child collections)
     ///
     /// This method returns members of the specified collection and all its
@@ -4987,7 +7295,6 @@ where
         "#;
 
         let mut response = self
-            .db
             .query(query)
             .bind(("collection_thing", collection_thing))
             .await
@@ -5037,7 +7344,6 @@ where
         "#;
 
         let mut response = self
-            .db
             .query(query)
             .await
             .context("Failed to get all collections")?;
@@ -5093,7 +7399,6 @@ where
         "#;
 
         let mut response = self
-            .db
             .query(query)
             .await
             .context("Failed to get collections with member counts")?;
@@ -5145,6 +7450,7 @@ where
                     member_of: parent_collection_ids,
                     title: row.title,
                     lifecycle_status: row.lifecycle_status,
+                    rollups: BTreeMap::new(),
                 };
 
                 let member_count = row.member_count.max(0) as usize;
@@ -5195,18 +7501,22 @@ where
                 .push(node_id.as_str());
         }
 
-        // For each collection, get the current max order and assign sequential orders
-        let mut ordered_memberships: Vec<(String, String, f64)> =
+        // For each collection, append after its current last key
+        let mut ordered_memberships: Vec<(String, String, String)> =
             Vec::with_capacity(memberships.len());
 
         for (collection_id, node_ids) in by_collection {
-            // Get current max order for this collection
-            let base_order = self.get_next_member_order(collection_id).await?;
-
-            for (i, node_id) in node_ids.iter().enumerate() {
-                // Each subsequent member gets an incremented order
-                let order = base_order + (i as f64);
-                ordered_memberships.push((node_id.to_string(), collection_id.to_string(), order));
+            let mut last_order = self.last_member_order(collection_id).await?;
+
+            for node_id in node_ids {
+                let order = FractionalIndex::key_between(last_order.as_deref(), None)
+                    .map_err(|e| anyhow::anyhow!(e))?;
+                ordered_memberships.push((
+                    node_id.to_string(),
+                    collection_id.to_string(),
+                    order.clone(),
+                ));
+                last_order = Some(order);
             }
         }
 
@@ -5223,7 +7533,7 @@ where
                 IF array::len($existing) = 0 THEN
                     RELATE node:`{member}`->relationship->node:`{collection}` CONTENT {{
                         relationship_type: 'member_of',
-                        properties: {{ order: {order} }},
+                        properties: {{ order: '{order}' }},
                         created_at: time::now(),
                         modified_at: time::now(),
                         version: 1
@@ -5238,8 +7548,7 @@ where
 
         query.push_str("COMMIT TRANSACTION;\n");
 
-        self.db
-            .query(&query)
+        self.query(&query)
             .await
             .context("Failed to bulk add to collections")?;
 
@@ -5316,8 +7625,7 @@ where
 
         query.push_str("COMMIT TRANSACTION;\n");
 
-        self.db
-            .query(&query)
+        self.query(&query)
             .await
             .context("Failed to bulk create mentions")?;
 
@@ -5331,6 +7639,47 @@ where
         Ok(valid_mentions.len())
     }
 
+    /// Overwrite `created_at` / `modified_at` for imported nodes
+    ///
+    /// Importers from other tools (e.g. Roam exports) carry original timestamps;
+    /// bulk creation stamps `time::now()`, so they are restored afterwards in a
+    /// single transaction.
+    ///
+    /// # Arguments
+    ///
+    /// * `timestamps` - `(node_id, created_at, modified_at)` triples
+    ///
+    /// # Returns
+    ///
+    /// Number of nodes updated
+    pub async fn bulk_set_timestamps(
+        &self,
+        timestamps: &[(String, DateTime<Utc>, DateTime<Utc>)],
+    ) -> Result<usize> {
+        if timestamps.is_empty() {
+            return Ok(0);
+        }
+
+        let mut query = String::from("BEGIN TRANSACTION;\n");
+        for (id, created_at, modified_at) in timestamps {
+            query.push_str(&format!(
+                "UPDATE node:`{id}` SET created_at = <datetime>\"{created}\", modified_at = <datetime>\"{modified}\";\n",
+                id = id,
+                created = created_at.to_rfc3339(),
+                modified = modified_at.to_rfc3339(),
+            ));
+        }
+        query.push_str("COMMIT TRANSACTION;\n");
+
+        self.query(&query)
+            .await
+            .context("Failed to bulk set timestamps")?
+            .check()
+            .context("Bulk timestamp update transaction failed")?;
+
+        Ok(timestamps.len())
+    }
+
     /// Create a stale embedding marker for a new root node
     ///
     /// Creates an embedding record with a placeholder vector marked as stale to queue it for processing.
@@ -5362,8 +7711,7 @@ where
             };
         "#;
 
-        self.db
-            .query(query)
+        self.query(query)
             .bind(("node_id", node_id.to_string()))
             .await
             .context("Failed to create stale embedding marker")?;
@@ -5417,8 +7765,7 @@ where
 
         query.push_str("COMMIT TRANSACTION;\n");
 
-        self.db
-            .query(&query)
+        self.query(&query)
             .await
             .context("Failed to create bulk stale embedding markers")?;
 
@@ -5432,6 +7779,17 @@ where
     }
 }
 
+/// Column of a single-column index definition
+///
+/// `DEFINE INDEX idx ON node FIELDS properties.task.status` -> `properties.task.status`
+fn index_column(definition: &str) -> Option<&str> {
+    let (_, rest) = definition
+        .split_once(" FIELDS ")
+        .or_else(|| definition.split_once(" COLUMNS "))?;
+    let column = rest.split_whitespace().next()?.trim_end_matches(';');
+    (!column.is_empty() && !column.ends_with(',')).then_some(column)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -5439,26 +7797,220 @@ mod tests {
     use std::collections::HashSet;
     use tempfile::TempDir;
 
-    /// Test helper to create a SurrealStore with schemas seeded
-    ///
-    /// Since schema seeding moved to NodeService (Issue #704), we use NodeService
-    /// to seed schemas. The new() method now takes &mut Arc to update caches
-    /// incrementally during seeding - no rebuild needed.
-    async fn create_test_store() -> Result<(Arc<SurrealStore>, TempDir)> {
-        use crate::services::NodeService;
+    /// Test helper to create a SurrealStore with schemas seeded
+    ///
+    /// Since schema seeding moved to NodeService (Issue #704), we use NodeService
+    /// to seed schemas. The new() method now takes &mut Arc to update caches
+    /// incrementally during seeding - no rebuild needed.
+    async fn create_test_store() -> Result<(Arc<SurrealStore>, TempDir)> {
+        use crate::services::NodeService;
+
+        let temp_dir = TempDir::new()?;
+        let db_path = temp_dir.path().join("test_surreal.db");
+        let mut store_arc = Arc::new(SurrealStore::new(db_path).await?);
+
+        // Seed schemas via NodeService (Issue #704)
+        // NodeService::new() takes &mut Arc to update caches incrementally during seeding
+        let _ = NodeService::new(&mut store_arc)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to initialize NodeService: {}", e))?;
+
+        // Caches are now populated by NodeService::new() - no rebuild needed!
+        Ok((store_arc, temp_dir))
+    }
+
+    #[tokio::test]
+    async fn test_bulk_upsert_reports_per_item_and_notifies_roots() -> Result<()> {
+        use crate::services::NodeService;
+
+        let temp_dir = TempDir::new()?;
+        let mut store = Arc::new(SurrealStore::new(temp_dir.path().join("bulk.db")).await?);
+        let node_service = NodeService::new(&mut store)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to initialize NodeService: {}", e))?;
+        let mut events = node_service.subscribe_to_events();
+
+        let node = |id: &str, node_type: &str, content: &str| {
+            Node::new_with_id(id.into(), node_type.into(), content.into(), json!({}))
+        };
+        let edge = |from: &str, to: &str| EdgeUpsert {
+            from_id: from.into(),
+            to_id: to.into(),
+            relationship_type: "has_child".into(),
+            properties: json!({"order": "a0"}),
+        };
+        let nodes = vec![
+            node("bulk-root", "text", "# Imported"),
+            node("bulk-child", "text", "First"),
+            node("bulk-bad", "no-such-type", "Broken"),
+        ];
+        let edges = vec![
+            edge("bulk-root", "bulk-child"),
+            edge("bulk-root", "bulk-bad"),
+        ];
+
+        let result = store.bulk_upsert(nodes.clone(), edges.clone(), 2).await?;
+        assert_eq!(result.failed(), 2);
+        assert!(result.nodes[2].error.is_some());
+        assert_eq!(result.edges[0].error, None);
+        assert!(result.edges[1].error.is_some());
+        assert_eq!(store.get_children(Some("bulk-root")).await?.len(), 1);
+
+        // Only the top-level node is announced
+        assert!(matches!(
+            events.try_recv(),
+            Ok(DomainEvent::NodeCreated { node_id, .. }) if node_id == "bulk-root"
+        ));
+        assert!(events.try_recv().is_err());
+
+        // Upserting again replaces content and edges instead of duplicating them
+        let mut nodes = nodes;
+        nodes[1].content = "First, edited".into();
+        store.bulk_upsert(nodes, edges, 2).await?;
+        let child = store.get_node("bulk-child").await?.unwrap();
+        assert_eq!(child.content, "First, edited");
+        assert_eq!(child.version, 2);
+        assert_eq!(store.get_children(Some("bulk-root")).await?.len(), 1);
+        assert!(matches!(
+            events.try_recv(),
+            Ok(DomainEvent::NodeUpdated { node_id, .. }) if node_id == "bulk-root"
+        ));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_bulk_upsert_reparents_instead_of_adding_a_parent() -> Result<()> {
+        let (store, _temp_dir) = create_test_store().await?;
+        let node = |id: &str| Node::new_with_id(id.into(), "text".into(), id.into(), json!({}));
+        let edge = |from: &str, to: &str, properties: Value| EdgeUpsert {
+            from_id: from.into(),
+            to_id: to.into(),
+            relationship_type: "has_child".into(),
+            properties,
+        };
+        let order = || json!({"order": "a0"});
+        let nodes = vec![node("old-parent"), node("new-parent"), node("moved")];
+        store
+            .bulk_upsert(
+                nodes.clone(),
+                vec![
+                    edge("old-parent", "moved", order()),
+                    edge(
+                        "new-parent",
+                        "moved",
+                        json!({"order": "a0", "reference": true}),
+                    ),
+                ],
+                10,
+            )
+            .await?;
+
+        // Moving the node keeps nothing of the old owning edge; the reference
+        // under the new parent becomes the owning edge
+        let result = store
+            .bulk_upsert(
+                nodes.clone(),
+                vec![edge("new-parent", "moved", order())],
+                10,
+            )
+            .await?;
+        assert_eq!(result.failed(), 0);
+        assert!(store.get_children(Some("old-parent")).await?.is_empty());
+        assert_eq!(store.get_children(Some("new-parent")).await?.len(), 1);
+        assert_eq!(
+            store.get_parent_id("moved").await?.as_deref(),
+            Some("new-parent")
+        );
+
+        // A reference elsewhere is kept when the node moves again
+        store
+            .bulk_upsert(
+                nodes.clone(),
+                vec![edge(
+                    "old-parent",
+                    "moved",
+                    json!({"order": "a0", "reference": true}),
+                )],
+                10,
+            )
+            .await?;
+        store
+            .bulk_upsert(
+                nodes.clone(),
+                vec![edge("new-parent", "moved", order())],
+                10,
+            )
+            .await?;
+        let owners: Vec<Thing> = store
+            .db
+            .query("SELECT VALUE in FROM relationship WHERE out = node:moved AND relationship_type = 'has_child';")
+            .await?
+            .take(0)?;
+        assert_eq!(owners.len(), 2);
+
+        // Under its own child would make a cycle
+        let result = store
+            .bulk_upsert(nodes, vec![edge("moved", "new-parent", order())], 10)
+            .await?;
+        assert!(result.edges[0].error.is_some());
+        assert_eq!(
+            store.get_parent_id("moved").await?.as_deref(),
+            Some("new-parent")
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_typed_node_reads_namespaced_properties() -> Result<()> {
+        use crate::models::{Person, PersonNode};
+
+        let (store, _temp_dir) = create_test_store().await?;
+        let person = Node::new(
+            "person".to_string(),
+            "Ada Lovelace".to_string(),
+            json!({"person": {"email": "ada@example.com", "organization": "Analytical"}}),
+        );
+        let person = store.create_node(person, None).await?;
+        let text = store
+            .create_node(Node::new("text".into(), "Note".into(), json!({})), None)
+            .await?;
 
-        let temp_dir = TempDir::new()?;
-        let db_path = temp_dir.path().join("test_surreal.db");
-        let mut store_arc = Arc::new(SurrealStore::new(db_path).await?);
+        let typed: PersonNode = store.get_typed_node::<Person>(&person.id).await?.unwrap();
+        assert_eq!(typed.content, "Ada Lovelace");
+        assert_eq!(typed.fields.email.as_deref(), Some("ada@example.com"));
+        assert_eq!(typed.fields.phone, None);
+        assert!(store.get_typed_node::<Person>(&text.id).await?.is_none());
+        assert!(store.get_typed_node::<Person>("missing").await?.is_none());
+        Ok(())
+    }
 
-        // Seed schemas via NodeService (Issue #704)
-        // NodeService::new() takes &mut Arc to update caches incrementally during seeding
-        let _ = NodeService::new(&mut store_arc)
+    #[tokio::test]
+    async fn test_query_timeout_cancels_slow_operation() -> Result<()> {
+        let (store, _temp_dir) = create_test_store().await?;
+        assert_eq!(store.query_timeout(), Some(DEFAULT_QUERY_TIMEOUT));
+
+        store.set_query_timeout(Some(Duration::from_millis(10)));
+        let err = store
+            .with_query_timeout(std::future::pending::<Result<()>>())
             .await
-            .map_err(|e| anyhow::anyhow!("Failed to initialize NodeService: {}", e))?;
+            .unwrap_err();
+        assert!(DatabaseError::is_timeout(&err));
+
+        // Engine-side timeouts from the TIMEOUT clause map to the same error
+        let err = store
+            .with_query_timeout(async {
+                Err::<(), _>(anyhow::anyhow!(
+                    "The query was not executed because it exceeded the timeout"
+                ))
+            })
+            .await
+            .unwrap_err();
+        assert!(DatabaseError::is_timeout(&err));
 
-        // Caches are now populated by NodeService::new() - no rebuild needed!
-        Ok((store_arc, temp_dir))
+        store.set_query_timeout(None);
+        assert_eq!(store.timeout_clause(), "");
+        assert_eq!(store.with_query_timeout(async { Ok(7) }).await?, 7);
+        Ok(())
     }
 
     #[tokio::test]
@@ -5478,6 +8030,73 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_health_check_and_placeholder_repair() -> Result<()> {
+        let (mut store, _temp_dir) = create_test_store().await?;
+        assert_eq!(store.health_check().await?, StoreHealth::Healthy);
+
+        // A node whose schema was lost
+        let orphan = Node::new("recipe".to_string(), "Pancakes".to_string(), json!({}));
+        store.create_node(orphan, None).await?;
+        assert_eq!(
+            store.health_check().await?,
+            StoreHealth::SchemaMismatch {
+                unknown_types: vec!["recipe".to_string()]
+            }
+        );
+
+        let store = Arc::get_mut(&mut store).expect("store is not shared");
+        let created = store
+            .create_placeholder_schemas(&["recipe".to_string(), "task".to_string()])
+            .await?;
+        assert_eq!(created, vec!["recipe".to_string()]);
+        assert!(store.has_schema_type("recipe"));
+        assert_eq!(store.health_check().await?, StoreHealth::Healthy);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_property_indexes_listed_with_stats() -> Result<()> {
+        let (store, _temp_dir) = create_test_store().await?;
+
+        // Core task schema marks status as indexed
+        let indexes = store.list_indexes("node").await?;
+        assert!(indexes.iter().any(|i| i.name == "idx_node_type"));
+        assert!(indexes.iter().any(|i| i.name == "idx_prop_task__status"));
+
+        let task = Node::new(
+            "task".to_string(),
+            "Ship it".to_string(),
+            json!({ "task": { "status": "open" } }),
+        );
+        store.create_node(task, None).await?;
+
+        let stats = store.property_index_stats().await?;
+        let status = stats
+            .iter()
+            .find(|s| s.name == "idx_prop_task__status")
+            .expect("status index stats");
+        assert_eq!(status.entries, 1);
+        assert!(status.table_rows > status.entries);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_index_column() {
+        assert_eq!(
+            index_column(
+                "DEFINE INDEX idx_prop_task__status ON node FIELDS properties.task.status"
+            ),
+            Some("properties.task.status")
+        );
+        assert_eq!(
+            index_column("DEFINE INDEX idx_rel_in ON relationship FIELDS in, relationship_type"),
+            None
+        );
+    }
+
     #[tokio::test]
     async fn test_update_node() -> Result<()> {
         let (store, _temp_dir) = create_test_store().await?;
@@ -6504,10 +9123,10 @@ mod tests {
     /// - Orders are unique (distinct from each other)
     /// - Orders can be used for sorting
     ///
-    /// Note: We don't assert specific values (1.0, 2.0, 3.0) because RocksDB's
-    /// eventual consistency means sequential writes may not be immediately visible
-    /// to subsequent reads, causing multiple items to get the same base order + jitter.
-    /// The jitter ensures uniqueness even in these cases.
+    /// Note: We don't assert specific keys because RocksDB's eventual
+    /// consistency means sequential writes may not be immediately visible to
+    /// subsequent reads, so several members may be keyed after the same one.
+    /// The member ID suffix keeps the keys unique even in these cases.
     #[tokio::test]
     async fn test_add_to_collection_assigns_order() -> Result<()> {
         let (store, _temp) = create_test_store().await?;
@@ -6548,7 +9167,7 @@ mod tests {
         struct RelWithOrder {
             #[serde(rename = "in")]
             member: surrealdb::sql::Thing,
-            order: Option<f64>,
+            order: Option<String>,
         }
 
         let mut response = store
@@ -6571,28 +9190,14 @@ mod tests {
             );
         }
 
-        // Verify orders are valid for sorting
-        let mut orders: Vec<f64> = rels.iter().map(|r| r.order.unwrap()).collect();
-
-        // Orders should be positive (valid order values)
+        // Orders are fractional index keys, distinct even if a write was not
+        // yet visible to the next add (the key carries the member's ID)
+        let mut orders: Vec<String> = rels.into_iter().map(|r| r.order.unwrap()).collect();
         for order in &orders {
-            assert!(*order > 0.0, "Order should be positive, got {}", order);
+            assert!(FractionalIndex::is_valid(order), "Invalid key {}", order);
         }
-
-        // Sort to verify they're all distinct (jitter ensures uniqueness)
-        orders.sort_by(|a, b| a.partial_cmp(b).unwrap());
-        assert!(
-            orders[0] < orders[1],
-            "Orders should be distinct: {} < {}",
-            orders[0],
-            orders[1]
-        );
-        assert!(
-            orders[1] < orders[2],
-            "Orders should be distinct: {} < {}",
-            orders[1],
-            orders[2]
-        );
+        orders.dedup();
+        assert_eq!(orders.len(), 3, "Orders should be distinct");
 
         Ok(())
     }
@@ -6608,7 +9213,7 @@ mod tests {
     /// Note: We don't verify that insertion order == return order because
     /// RocksDB's eventual consistency means sequential inserts may not
     /// consistently see previous writes, causing order values to overlap.
-    /// The jitter ensures uniqueness but not strict insertion order.
+    /// The member ID suffix ensures uniqueness but not strict insertion order.
     #[tokio::test]
     async fn test_get_collection_members_returns_ordered() -> Result<()> {
         let (store, _temp) = create_test_store().await?;
@@ -6670,6 +9275,57 @@ mod tests {
         Ok(())
     }
 
+    /// Stats count the whole member_of subtree and break members down by type
+    #[tokio::test]
+    async fn test_get_collection_stats() -> Result<()> {
+        let (store, _temp) = create_test_store().await?;
+        let collection =
+            |name: &str| Node::new("collection".to_string(), name.to_string(), json!({}));
+        let engineering = store.create_node(collection("Engineering"), None).await?;
+        let backend = store.create_node(collection("Backend"), None).await?;
+        store
+            .add_to_collection(&backend.id, &engineering.id)
+            .await?;
+
+        let empty = store.get_collection_stats(&engineering.id).await?;
+        assert_eq!(empty.direct_members, 0);
+        assert_eq!(empty.subcollections, 1);
+
+        let mut members = Vec::new();
+        for (node_type, content) in [("text", "Spec"), ("task", "Ship it"), ("text", "Notes")] {
+            let node = store
+                .create_node(
+                    Node::new(node_type.to_string(), content.to_string(), json!({})),
+                    None,
+                )
+                .await?;
+            members.push(node.id);
+        }
+        store
+            .add_to_collection(&members[0], &engineering.id)
+            .await?;
+        store.add_to_collection(&members[1], &backend.id).await?;
+        store.add_to_collection(&members[2], &backend.id).await?;
+        // In both collections, counted once
+        store.add_to_collection(&members[0], &backend.id).await?;
+
+        let stats = store.get_collection_stats(&engineering.id).await?;
+        assert_eq!(stats.direct_members, 1);
+        assert_eq!(stats.recursive_members, 3);
+        assert_eq!(stats.subcollections, 1);
+        assert_eq!(
+            stats.types,
+            BTreeMap::from([("task".to_string(), 1), ("text".to_string(), 2)])
+        );
+        assert!(stats.last_activity.is_some());
+
+        let backend_stats = store.get_collection_stats(&backend.id).await?;
+        assert_eq!(backend_stats.direct_members, 3);
+        assert_eq!(backend_stats.subcollections, 0);
+
+        Ok(())
+    }
+
     /// Issue #839: get_next_member_order should calculate correct values
     #[tokio::test]
     async fn test_get_next_member_order() -> Result<()> {
@@ -6684,27 +9340,164 @@ mod tests {
         collection.title = Some("Order Test Collection".to_string());
         let collection = store.create_node(collection, None).await?;
 
-        // First call should return ~1.0 (first child pattern)
-        let order1 = store.get_next_member_order(&collection.id).await?;
-        assert!(
-            (order1 - 1.0).abs() < 0.01,
-            "First order should be ~1.0, got {}",
-            order1
-        );
+        // First call returns a key for an empty collection
+        let order1 = store
+            .get_next_member_order(&collection.id, "member-1")
+            .await?;
+        assert!(FractionalIndex::is_valid(&order1), "Invalid key {}", order1);
 
         // Add a member
         let member = Node::new("text".to_string(), "Test Member".to_string(), json!({}));
         let member = store.create_node(member, None).await?;
         store.add_to_collection(&member.id, &collection.id).await?;
 
-        // Second call should return ~2.0 (after first)
-        let order2 = store.get_next_member_order(&collection.id).await?;
-        assert!(
-            (order2 - 2.0).abs() < 0.01,
-            "Second order should be ~2.0, got {}",
-            order2
+        // Second call sorts after the first member
+        let first = store.last_member_order(&collection.id).await?.unwrap();
+        let order2 = store
+            .get_next_member_order(&collection.id, "member-2")
+            .await?;
+        assert!(order2 > first, "{} should sort after {}", order2, first);
+
+        Ok(())
+    }
+
+    /// Members move between their neighbours; float orders are migrated first
+    #[tokio::test]
+    async fn test_move_collection_member() -> Result<()> {
+        let (store, _temp) = create_test_store().await?;
+        let collection = store
+            .create_node(
+                Node::new("collection".to_string(), "Reading".to_string(), json!({})),
+                None,
+            )
+            .await?;
+        let mut members = Vec::new();
+        for name in ["A", "B", "C"] {
+            let member = store
+                .create_node(
+                    Node::new("text".to_string(), name.to_string(), json!({})),
+                    None,
+                )
+                .await?;
+            members.push(member.id);
+        }
+
+        // Legacy layout: B, C, A
+        for (member_id, order) in members.iter().zip([3.0, 1.0, 2.0]) {
+            store
+                .db
+                .query("RELATE $member->relationship->$collection CONTENT { relationship_type: 'member_of', properties: { order: $order }, created_at: time::now(), modified_at: time::now(), version: 1 };")
+                .bind(("member", Thing::from(("node".to_string(), member_id.clone()))))
+                .bind(("collection", Thing::from(("node".to_string(), collection.id.clone()))))
+                .bind(("order", order))
+                .await?;
+        }
+        store.migrate_order_keys().await?;
+
+        let ids = |nodes: Vec<Node>| -> Vec<String> { nodes.into_iter().map(|n| n.id).collect() };
+        assert_eq!(
+            ids(store.get_collection_members(&collection.id).await?),
+            vec![members[1].clone(), members[2].clone(), members[0].clone()]
+        );
+
+        // A between B and C, then C first
+        let moved = store
+            .move_collection_member(&collection.id, &members[0], Some(&members[1]))
+            .await?;
+        assert!(moved.is_some());
+        store
+            .move_collection_member(&collection.id, &members[2], None)
+            .await?;
+        assert_eq!(
+            ids(store.get_collection_members(&collection.id).await?),
+            vec![members[2].clone(), members[1].clone(), members[0].clone()]
+        );
+
+        // Not a member
+        assert!(store
+            .move_collection_member(&collection.id, &collection.id, None)
+            .await?
+            .is_none());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_move_collection() -> Result<()> {
+        let (store, _temp) = create_test_store().await?;
+        let mut ids = HashMap::new();
+        for name in ["hr", "legal", "policy", "vacation"] {
+            let collection = store
+                .create_node(
+                    Node::new("collection".to_string(), name.to_string(), json!({})),
+                    None,
+                )
+                .await?;
+            ids.insert(name, collection.id);
+        }
+        let note = store
+            .create_node(
+                Node::new("text".to_string(), "Leave rules".to_string(), json!({})),
+                None,
+            )
+            .await?;
+        store.add_to_collection(&ids["policy"], &ids["hr"]).await?;
+        store
+            .add_to_collection(&ids["vacation"], &ids["policy"])
+            .await?;
+        store.add_to_collection(&note.id, &ids["vacation"]).await?;
+
+        let subcollections = store.get_subcollections(&ids["policy"]).await?;
+        assert_eq!(
+            subcollections,
+            vec![SubCollection {
+                id: ids["vacation"].clone(),
+                name: "vacation".to_string(),
+                parent_ids: vec![ids["policy"].clone()],
+            }]
+        );
+
+        // hr:policy -> legal:policies
+        let moved = store
+            .move_collection(
+                &ids["policy"],
+                "policies",
+                Some(&ids["hr"]),
+                Some(&ids["legal"]),
+                None,
+            )
+            .await?;
+        assert_eq!(moved.collection.content, "policies");
+        assert!(moved.removed_edge.is_some());
+        assert!(moved.created_edge.is_some());
+        assert_eq!(
+            store.get_node_memberships(&ids["policy"]).await?,
+            vec![ids["legal"].clone()]
+        );
+
+        // Members below the collection are untouched
+        assert_eq!(
+            store.get_node_memberships(&ids["vacation"]).await?,
+            vec![ids["policy"].clone()]
+        );
+        assert_eq!(
+            store.get_node_memberships(&note.id).await?,
+            vec![ids["vacation"].clone()]
         );
 
+        // A plain rename leaves the edges alone
+        let renamed = store
+            .move_collection(
+                &ids["policy"],
+                "Policies",
+                Some(&ids["legal"]),
+                Some(&ids["legal"]),
+                None,
+            )
+            .await?;
+        assert_eq!(renamed.collection.content, "Policies");
+        assert!(renamed.removed_edge.is_none() && renamed.created_edge.is_none());
+
         Ok(())
     }
 
@@ -6717,30 +9510,161 @@ mod tests {
         let parent = Node::new("text".to_string(), "Parent Node".to_string(), json!({}));
         let parent = store.create_node(parent, None).await?;
 
-        // First call should return ~1.0 (first child pattern)
-        let order1 = store.get_next_child_order(&parent.id).await?;
-        assert!(
-            (order1 - 1.0).abs() < 0.01,
-            "First child order should be ~1.0, got {}",
-            order1
-        );
+        // First call returns a key for an empty list
+        let order1 = store.get_next_child_order(&parent.id, "child-1").await?;
+        assert!(FractionalIndex::is_valid(&order1), "Invalid key {}", order1);
 
         // Add a child using create_child_node_atomic (which creates has_child relationship with order)
         let _child = store
             .create_child_node_atomic(&parent.id, "text", "Child 1", json!({}), None)
             .await?;
 
-        // Second call should return ~2.0 (after first child)
-        let order2 = store.get_next_child_order(&parent.id).await?;
+        // Second call sorts after the first child
+        let first: Vec<String> = store
+            .db
+            .query("SELECT VALUE properties.order FROM relationship WHERE in = $parent AND relationship_type = 'has_child';")
+            .bind(("parent", Thing::from(("node".to_string(), parent.id.clone()))))
+            .await?
+            .take(0)?;
+        let order2 = store.get_next_child_order(&parent.id, "child-2").await?;
         assert!(
-            (order2 - 2.0).abs() < 0.01,
-            "Second child order should be ~2.0, got {}",
-            order2
+            order2 > first[0],
+            "{} should sort after {}",
+            order2,
+            first[0]
+        );
+
+        Ok(())
+    }
+
+    /// Float orders from older databases are converted to keys in place
+    #[tokio::test]
+    async fn test_migrate_child_order_keys_preserves_order() -> Result<()> {
+        let (store, _temp) = create_test_store().await?;
+        let parent = store
+            .create_node(
+                Node::new("text".to_string(), "Parent".to_string(), json!({})),
+                None,
+            )
+            .await?;
+        let mut children = Vec::new();
+        for name in ["A", "B", "C"] {
+            children.push(
+                store
+                    .create_child_node_atomic(&parent.id, "text", name, json!({}), None)
+                    .await?
+                    .id,
+            );
+        }
+
+        // Legacy layout: C, A, B
+        for (child_id, order) in children.iter().zip([2.0, 3.0, 1.0]) {
+            store
+                .db
+                .query("UPDATE relationship SET properties.order = $order WHERE out = $child AND relationship_type = 'has_child';")
+                .bind(("order", order))
+                .bind(("child", Thing::from(("node".to_string(), child_id.clone()))))
+                .await?;
+        }
+
+        store.migrate_order_keys().await?;
+
+        let orders: Vec<String> = store
+            .db
+            .query("SELECT VALUE properties.order FROM relationship WHERE in = $parent AND relationship_type = 'has_child' ORDER BY properties.order ASC;")
+            .bind(("parent", Thing::from(("node".to_string(), parent.id.clone()))))
+            .await?
+            .take(0)?;
+        assert_eq!(orders, vec!["a0", "a1", "a2"]);
+
+        let ordered: Vec<String> = store
+            .get_children(Some(&parent.id))
+            .await?
+            .into_iter()
+            .map(|n| n.id)
+            .collect();
+        assert_eq!(
+            ordered,
+            vec![
+                children[2].clone(),
+                children[0].clone(),
+                children[1].clone()
+            ]
         );
 
         Ok(())
     }
 
+    /// Reopen `db_path` once the previous handle's RocksDB lock is released
+    async fn reopen(db_path: &std::path::Path, read_only: bool) -> Result<SurrealStore> {
+        let mut attempts = 0;
+        loop {
+            match SurrealStore::open(db_path.to_path_buf(), read_only).await {
+                Ok(store) => return Ok(store),
+                Err(_) if attempts < 25 => {
+                    attempts += 1;
+                    tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Opening read-only neither migrates, seeds nor syncs indexes
+    #[tokio::test]
+    async fn test_open_read_only_leaves_database_unchanged() -> Result<()> {
+        use crate::services::{NodeService, NodeServiceError};
+
+        let temp_dir = TempDir::new()?;
+        let db_path = temp_dir.path().join("backup.db");
+        let parent_id;
+        {
+            let mut store = Arc::new(SurrealStore::new(db_path.clone()).await?);
+            NodeService::new(&mut store).await?;
+            let parent = store
+                .create_node(
+                    Node::new("text".to_string(), "Parent".to_string(), json!({})),
+                    None,
+                )
+                .await?;
+            for name in ["A", "B", "C"] {
+                store
+                    .create_child_node_atomic(&parent.id, "text", name, json!({}), None)
+                    .await?;
+            }
+            // An older database: float orders and a core schema it predates
+            store
+                .db
+                .query("UPDATE relationship SET properties.order = 1.0 WHERE in = $parent AND relationship_type = 'has_child';")
+                .bind(("parent", Thing::from(("node".to_string(), parent.id.clone()))))
+                .await?;
+            store.db.query("DELETE node:⟨quote-block⟩;").await?;
+            parent_id = parent.id;
+        }
+
+        let mut store = Arc::new(reopen(&db_path, true).await?);
+        assert!(store.is_read_only());
+        let service = NodeService::new(&mut store).await?;
+        assert!(service.is_read_only());
+
+        let orders: Vec<f64> = store
+            .db
+            .query("SELECT VALUE properties.order FROM relationship WHERE in = $parent AND relationship_type = 'has_child';")
+            .bind(("parent", Thing::from(("node".to_string(), parent_id.clone()))))
+            .await?
+            .take(0)?;
+        assert_eq!(orders, vec![1.0, 1.0, 1.0]);
+        assert!(store.get_node("quote-block").await?.is_none());
+        assert_eq!(store.get_children(Some(&parent_id)).await?.len(), 3);
+
+        let node = Node::new("text".to_string(), "New".to_string(), json!({}));
+        assert!(matches!(
+            service.create_node(node).await,
+            Err(NodeServiceError::ReadOnly)
+        ));
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_get_nodes_by_ids_basic() -> Result<()> {
         let (store, _temp_dir) = create_test_store().await?;
@@ -7127,6 +10051,91 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_repeated_midpoint_inserts_rebalance_siblings() -> Result<()> {
+        let (store, _temp_dir) = create_test_store().await?;
+        let parent = store
+            .create_node(
+                Node::new("text".to_string(), "Parent".to_string(), json!({})),
+                None,
+            )
+            .await?;
+        let first = store
+            .create_child_node_atomic(&parent.id, "text", "First", json!({}), None)
+            .await?;
+        let last = store
+            .create_child_node_atomic(&parent.id, "text", "Last", json!({}), None)
+            .await?;
+
+        // Each insert directly after `first` lands in the same shrinking gap,
+        // lengthening the keys until the siblings get renumbered
+        let mut expected = vec![last.id.clone()];
+        for i in 0..40 {
+            let child = store
+                .create_child_node_atomic(&parent.id, "text", &format!("C{}", i), json!({}), None)
+                .await?;
+            store
+                .move_node(&child.id, Some(&parent.id), Some(&first.id))
+                .await?;
+            expected.insert(0, child.id);
+        }
+        expected.insert(0, first.id.clone());
+
+        let children: Vec<String> = store
+            .get_children(Some(&parent.id))
+            .await?
+            .into_iter()
+            .map(|n| n.id)
+            .collect();
+        assert_eq!(children, expected);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_move_node_keeps_reference_edges() -> Result<()> {
+        let (store, _temp_dir) = create_test_store().await?;
+        let mut ids = Vec::new();
+        for name in ["Home", "Daily", "Project"] {
+            let node = Node::new("text".to_string(), name.to_string(), json!({}));
+            ids.push(store.create_node(node, None).await?.id);
+        }
+        let (home, daily, project) = (&ids[0], &ids[1], &ids[2]);
+        let block = store
+            .create_child_node_atomic(home, "text", "Block", json!({}), None)
+            .await?;
+
+        assert!(store.add_reference_child(daily, &block.id).await?.is_some());
+        assert!(store.add_reference_child(daily, &block.id).await?.is_none());
+        assert_eq!(
+            store.get_parent_id(&block.id).await?.as_deref(),
+            Some(home.as_str())
+        );
+
+        let edges_from = |parent: &str, rels: &[RelationshipRecord]| -> Vec<bool> {
+            rels.iter()
+                .filter(|r| r.in_node == parent && r.out_node == block.id)
+                .map(RelationshipRecord::is_reference)
+                .collect()
+        };
+
+        // Moving the block moves its owning edge only
+        store.move_node(&block.id, Some(project), None).await?;
+        assert_eq!(
+            store.get_parent_id(&block.id).await?.as_deref(),
+            Some(project.as_str())
+        );
+        let (_, rels) = store.get_subtree_with_references(daily).await?;
+        assert_eq!(edges_from(daily.as_str(), &rels), vec![true]);
+
+        // Moving it under a parent that references it replaces the reference
+        store.move_node(&block.id, Some(daily), None).await?;
+        let (_, rels) = store.get_subtree_with_references(daily).await?;
+        assert_eq!(edges_from(daily.as_str(), &rels), vec![false]);
+
+        Ok(())
+    }
+
     // ========================================================================
     // Bulk Create Mentions Tests (Issue #868)
     // ========================================================================
@@ -7429,6 +10438,34 @@ mod tests {
     // Tests for get_incoming_mention_containers (Issue #882)
     // ========================================================================
 
+    #[tokio::test]
+    async fn test_get_unlinked_roots_skips_linked_and_deleted() -> Result<()> {
+        let (store, _temp_dir) = create_test_store().await?;
+        let root = |content: &str| Node::new("text".to_string(), content.to_string(), json!({}));
+
+        let lonely = store.create_node(root("Lonely"), None).await?;
+        let mentioned = store.create_node(root("Mentioned"), None).await?;
+        let deleted = store.create_node(root("Deleted"), None).await?;
+        let source = store.create_node(root("Source"), None).await?;
+        store.create_mention(&source.id, &mentioned.id).await?;
+        store
+            .update_lifecycle_status(&deleted.id, "deleted")
+            .await?;
+
+        let ids: Vec<String> = store
+            .get_unlinked_roots(&["text"])
+            .await?
+            .into_iter()
+            .map(|node| node.id)
+            .collect();
+        assert!(ids.contains(&lonely.id));
+        assert!(ids.contains(&source.id));
+        assert!(!ids.contains(&mentioned.id));
+        assert!(!ids.contains(&deleted.id));
+        assert!(store.get_unlinked_roots(&["task"]).await?.is_empty());
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_get_incoming_mention_containers_basic() -> Result<()> {
         let (store, _temp_dir) = create_test_store().await?;
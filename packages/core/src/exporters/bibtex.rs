@@ -0,0 +1,224 @@
+//! BibTeX exporter
+//!
+//! Builds the bibliography of a subtree: every reference a node in the
+//! subtree cites through a `cite` edge, plus reference nodes stored in the
+//! subtree itself (such as an imported `.bib` file). Entries are written in
+//! the shape [`BibtexImporter`](crate::importers::BibtexImporter) reads, so a
+//! bibliography survives an export/import round trip.
+
+use crate::models::Node;
+use crate::services::{NodeService, NodeServiceError};
+use std::collections::{HashMap, HashSet};
+
+/// Exports the references of a subtree as a `.bib` file
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BibtexExporter;
+
+impl BibtexExporter {
+    /// Render the bibliography of `root_id`, sorted by citation key
+    pub async fn export_subtree<C>(
+        &self,
+        node_service: &NodeService<C>,
+        root_id: &str,
+    ) -> Result<String, NodeServiceError>
+    where
+        C: surrealdb::Connection,
+    {
+        let (root, node_map, _) = node_service.get_subtree_data(root_id).await?;
+        let root = root.ok_or_else(|| NodeServiceError::node_not_found(root_id))?;
+
+        let mut seen = HashSet::new();
+        let mut references = Vec::new();
+        for node in std::iter::once(&root).chain(node_map.values()) {
+            if node.node_type == "reference" && seen.insert(node.id.clone()) {
+                references.push(node.clone());
+            }
+            if node.node_type != "text" {
+                continue;
+            }
+            for cited in node_service
+                .get_related_nodes(&node.id, "cite", "out")
+                .await?
+            {
+                if seen.insert(cited.id.clone()) {
+                    references.push(cited);
+                }
+            }
+        }
+
+        let mut entries: Vec<(String, &Node)> = references
+            .iter()
+            .map(|node| (Self::citation_key(node), node))
+            .collect();
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+
+        // Keys must be unique within a file: knuth1974, knuth1974a, ...
+        let mut used: HashMap<String, usize> = HashMap::new();
+        let mut out = String::new();
+        for (key, node) in entries {
+            let count = used.entry(key.clone()).or_insert(0);
+            let key = match *count {
+                0 => key,
+                n => format!("{}{}", key, (b'a' + ((n - 1) % 26) as u8) as char),
+            };
+            *count += 1;
+            out.push_str(&Self::entry(node, &key));
+            out.push('\n');
+        }
+        Ok(out)
+    }
+
+    /// Stored citation key, or `<first author surname><year>` when missing
+    pub fn citation_key(node: &Node) -> String {
+        let props = reference_props(node);
+        if let Some(key) = props
+            .get("citation_key")
+            .and_then(|k| k.as_str())
+            .filter(|k| !k.trim().is_empty())
+        {
+            return key.trim().to_string();
+        }
+
+        let surname = props
+            .get("authors")
+            .and_then(|a| a.get(0))
+            .and_then(|a| a.as_str())
+            .map(|author| match author.split_once(',') {
+                Some((last, _)) => last,
+                None => author.split_whitespace().last().unwrap_or(author),
+            })
+            .unwrap_or("ref");
+        let year = props.get("year").map(value_text).unwrap_or_default();
+        let key: String = format!("{}{}", surname, year)
+            .chars()
+            .filter(|c| c.is_ascii_alphanumeric())
+            .collect();
+        key.to_lowercase()
+    }
+
+    /// Render one reference node as a BibTeX entry under `key`
+    pub fn entry(node: &Node, key: &str) -> String {
+        let props = reference_props(node);
+        let entry_type = props
+            .get("entry_type")
+            .and_then(|t| t.as_str())
+            .filter(|t| !t.is_empty())
+            .unwrap_or("misc");
+        let venue_field = match entry_type {
+            "article" => "journal",
+            "inproceedings" | "incollection" | "conference" => "booktitle",
+            "book" | "inbook" => "publisher",
+            "phdthesis" | "mastersthesis" => "school",
+            "techreport" => "institution",
+            _ => "howpublished",
+        };
+
+        let mut fields = vec![("title", node.content.trim().to_string())];
+        if let Some(authors) = props.get("authors").and_then(|a| a.as_array()) {
+            let names: Vec<&str> = authors.iter().filter_map(|a| a.as_str()).collect();
+            if !names.is_empty() {
+                fields.push(("author", names.join(" and ")));
+            }
+        }
+        for (property, field) in [
+            ("year", "year"),
+            ("venue", venue_field),
+            ("doi", "doi"),
+            ("url", "url"),
+        ] {
+            if let Some(value) = props.get(property).map(value_text) {
+                if !value.is_empty() {
+                    fields.push((field, value));
+                }
+            }
+        }
+
+        let mut out = format!("@{}{{{},\n", entry_type, key);
+        for (name, value) in fields {
+            out.push_str(&format!("  {} = {{{}}},\n", name, escape(&value)));
+        }
+        out.push_str("}\n");
+        out
+    }
+}
+
+/// Reference properties, namespaced or (on client-created nodes) flat
+fn reference_props(node: &Node) -> &serde_json::Value {
+    node.properties.get("reference").unwrap_or(&node.properties)
+}
+
+fn value_text(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(s) => s.trim().to_string(),
+        other => other.to_string(),
+    }
+}
+
+/// Escape characters BibTeX treats specially inside braced values
+fn escape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' | '%' | '$' | '#' | '_' | '{' | '}' => {
+                out.push('\\');
+                out.push(c);
+            }
+            '\n' => out.push(' '),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::importers::{BibtexImporter, Importer};
+    use serde_json::json;
+
+    #[test]
+    fn test_entry_round_trips_through_importer() {
+        let node = Node::new(
+            "reference".to_string(),
+            "Research & Development".to_string(),
+            json!({
+                "reference": {
+                    "citation_key": "lovelace1843",
+                    "entry_type": "article",
+                    "authors": ["Ada Lovelace", "Babbage, Charles"],
+                    "year": 1843,
+                    "venue": "Scientific Memoirs",
+                    "doi": "10.1000/xyz_1"
+                }
+            }),
+        );
+        let bib = BibtexExporter::entry(&node, "lovelace1843");
+        assert!(bib.starts_with("@article{lovelace1843,\n"));
+        assert!(bib.contains("  journal = {Scientific Memoirs},\n"));
+
+        let docs = BibtexImporter.import("refs", &bib).unwrap();
+        let imported = &docs[0].nodes[0];
+        assert_eq!(imported.content, node.content);
+        assert_eq!(imported.properties, node.properties["reference"]);
+    }
+
+    #[test]
+    fn test_citation_key_falls_back_to_author_and_year() {
+        let reference = |props| Node::new("reference".to_string(), "T".to_string(), props);
+
+        assert_eq!(
+            BibtexExporter::citation_key(&reference(json!({ "citation_key": "k1" }))),
+            "k1"
+        );
+        assert_eq!(
+            BibtexExporter::citation_key(&reference(json!({
+                "reference": { "authors": ["Grace Hopper"], "year": 1952 }
+            }))),
+            "hopper1952"
+        );
+        assert_eq!(
+            BibtexExporter::citation_key(&reference(json!({ "authors": ["Liskov, Barbara"] }))),
+            "liskov"
+        );
+    }
+}
@@ -0,0 +1,450 @@
+//! HTML exporter
+//!
+//! Renders a subtree as one standalone HTML page for sharing read-only copies.
+//! Node content is rendered as markdown; raw HTML inside content is escaped
+//! rather than passed through. `nodespace://` mentions of nodes inside the
+//! subtree become in-page anchors, and mentions of nodes outside it are kept as
+//! plain styled text since the reader cannot follow them. `attachment://`
+//! references are resolved against an [`AttachmentStore`]: images can be
+//! embedded as data URIs so the page stays self-contained.
+
+use crate::models::{Node, TaskNode, TaskStatus};
+use crate::services::{AttachmentStore, NodeService, NodeServiceError, ATTACHMENT_URI_SCHEME};
+use crate::utils::truncate_chars;
+use base64::Engine;
+use pulldown_cmark::{html, CowStr, Event, Options, Parser, Tag, TagEnd};
+use std::collections::HashMap;
+
+const NODESPACE_URI_SCHEME: &str = "nodespace://";
+
+/// Default nesting limit, matching the markdown export
+const DEFAULT_MAX_DEPTH: usize = 20;
+
+/// Longest `<title>` taken from a root's first line
+const MAX_TITLE_CHARS: usize = 120;
+
+const STYLESHEET: &str = r#"
+body { font-family: -apple-system, BlinkMacSystemFont, "Segoe UI", Helvetica, Arial, sans-serif;
+       max-width: 46rem; margin: 2.5rem auto; padding: 0 1.5rem; line-height: 1.55; color: #1f2328; }
+h1, h2, h3, h4, h5, h6 { line-height: 1.25; margin: 1.2em 0 0.4em; }
+p { margin: 0.25em 0; }
+.children { margin-left: 1.4rem; padding-left: 0.8rem; border-left: 1px solid #d0d7de; }
+.node-task { display: flex; gap: 0.5rem; align-items: baseline; }
+.node-task.done .content { color: #656d76; text-decoration: line-through; }
+.mention { color: #0969da; }
+span.mention { color: #656d76; }
+pre { background: #f6f8fa; padding: 0.75rem; border-radius: 6px; overflow-x: auto; }
+blockquote { margin: 0.25em 0; padding-left: 0.8rem; border-left: 3px solid #d0d7de; color: #656d76; }
+img { max-width: 100%; }
+footer { margin-top: 3rem; font-size: 0.8rem; color: #656d76; }
+@media print { body { margin: 0; } a { color: inherit; } }
+"#;
+
+/// How `attachment://` images are written into the page
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AttachmentMode {
+    /// Embed images as `data:` URIs (self-contained, larger file)
+    #[default]
+    Inline,
+    /// Link to the stored files with `file://` URLs
+    Link,
+}
+
+/// Renders subtrees to standalone HTML documents
+///
+/// # Examples
+///
+/// ```no_run
+/// # use nodespace_core::exporters::{AttachmentMode, HtmlExporter};
+/// # use nodespace_core::services::{AttachmentStore, NodeService};
+/// # async fn example(service: NodeService) -> Result<(), Box<dyn std::error::Error>> {
+/// let store = AttachmentStore::new("/path/to/attachments");
+/// let html = HtmlExporter::new()
+///     .with_attachments(&store, AttachmentMode::Inline)
+///     .export_subtree(&service, "root-id")
+///     .await?;
+/// std::fs::write("export.html", html)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct HtmlExporter<'a> {
+    attachments: Option<&'a AttachmentStore>,
+    attachment_mode: AttachmentMode,
+    max_depth: usize,
+}
+
+impl Default for HtmlExporter<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> HtmlExporter<'a> {
+    pub fn new() -> Self {
+        Self {
+            attachments: None,
+            attachment_mode: AttachmentMode::default(),
+            max_depth: DEFAULT_MAX_DEPTH,
+        }
+    }
+
+    /// Resolve `attachment://` references against `store`
+    ///
+    /// Without a store, attachment references are left untouched.
+    pub fn with_attachments(mut self, store: &'a AttachmentStore, mode: AttachmentMode) -> Self {
+        self.attachments = Some(store);
+        self.attachment_mode = mode;
+        self
+    }
+
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Fetch a subtree and render it
+    pub async fn export_subtree<C>(
+        &self,
+        node_service: &NodeService<C>,
+        root_id: &str,
+    ) -> Result<String, NodeServiceError>
+    where
+        C: surrealdb::Connection,
+    {
+        let (root, node_map, adjacency_list) = node_service.get_subtree_data(root_id).await?;
+        let root = root.ok_or_else(|| NodeServiceError::node_not_found(root_id))?;
+        Ok(self.render(&root, &node_map, &adjacency_list))
+    }
+
+    /// Render pre-fetched subtree data (see [`NodeService::get_subtree_data`])
+    pub fn render(
+        &self,
+        root: &Node,
+        node_map: &HashMap<String, Node>,
+        adjacency_list: &HashMap<String, Vec<String>>,
+    ) -> String {
+        let title = document_title(&root.content);
+        // The root always renders as the page heading, whatever its type
+        let heading = if root.content.trim_start().starts_with('#') {
+            root.content.clone()
+        } else {
+            format!("# {}", root.content)
+        };
+
+        let mut body = format!(
+            "<header id=\"node-{}\">{}</header>\n",
+            escape(&root.id),
+            self.render_markdown(&heading, node_map)
+        );
+        if let Some(children) = adjacency_list.get(&root.id) {
+            for child_id in children {
+                if let Some(child) = node_map.get(child_id) {
+                    self.render_node(child, node_map, adjacency_list, 1, &mut body);
+                }
+            }
+        }
+
+        format!(
+            "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+             <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+             <title>{}</title>\n<style>{}</style>\n</head>\n<body>\n<main>\n{}</main>\n\
+             <footer>Exported from NodeSpace on {}</footer>\n</body>\n</html>\n",
+            escape(&title),
+            STYLESHEET,
+            body,
+            chrono::Utc::now().format("%Y-%m-%d")
+        )
+    }
+
+    fn render_node(
+        &self,
+        node: &Node,
+        node_map: &HashMap<String, Node>,
+        adjacency_list: &HashMap<String, Vec<String>>,
+        depth: usize,
+        out: &mut String,
+    ) {
+        if depth >= self.max_depth {
+            tracing::warn!("Max depth {} reached at node {}", self.max_depth, node.id);
+            return;
+        }
+
+        let content = self.render_markdown(&node.content, node_map);
+        if node.node_type == "task" {
+            let done = TaskNode::from_node(node.clone())
+                .map(|t| t.status() == TaskStatus::Done)
+                .unwrap_or(false);
+            out.push_str(&format!(
+                "<div class=\"node node-task{}\" id=\"node-{}\"><input type=\"checkbox\" disabled{}><div class=\"content\">{}</div></div>\n",
+                if done { " done" } else { "" },
+                escape(&node.id),
+                if done { " checked" } else { "" },
+                content
+            ));
+        } else {
+            out.push_str(&format!(
+                "<div class=\"node node-{}\" id=\"node-{}\">{}</div>\n",
+                escape(&node.node_type),
+                escape(&node.id),
+                content
+            ));
+        }
+
+        let children = adjacency_list
+            .get(&node.id)
+            .map(|v| v.as_slice())
+            .unwrap_or(&[]);
+        if children.is_empty() {
+            return;
+        }
+        out.push_str("<div class=\"children\">\n");
+        for child_id in children {
+            if let Some(child) = node_map.get(child_id) {
+                self.render_node(child, node_map, adjacency_list, depth + 1, out);
+            }
+        }
+        out.push_str("</div>\n");
+    }
+
+    /// Render node content, rewriting mention and attachment URLs
+    fn render_markdown(&self, content: &str, node_map: &HashMap<String, Node>) -> String {
+        let mut options = Options::empty();
+        options.insert(Options::ENABLE_STRIKETHROUGH);
+        options.insert(Options::ENABLE_TABLES);
+
+        // Links to nodes outside the export are unwrapped; remember which
+        // link starts were replaced so the matching ends are too
+        let mut unwrapped: Vec<bool> = Vec::new();
+        let events = Parser::new_ext(content, options).map(|event| match event {
+            Event::Html(raw) | Event::InlineHtml(raw) => Event::Text(raw),
+            Event::Start(Tag::Link {
+                link_type,
+                dest_url,
+                title,
+                id,
+            }) => {
+                if let Some(target) = dest_url.strip_prefix(NODESPACE_URI_SCHEME) {
+                    if !node_map.contains_key(target) {
+                        unwrapped.push(true);
+                        return Event::InlineHtml(CowStr::from("<span class=\"mention\">"));
+                    }
+                    unwrapped.push(false);
+                    return Event::InlineHtml(CowStr::from(format!(
+                        "<a class=\"mention\" href=\"#node-{}\">",
+                        escape(target)
+                    )));
+                }
+                unwrapped.push(false);
+                Event::Start(Tag::Link {
+                    link_type,
+                    dest_url: self.resolve_attachment(dest_url, false),
+                    title,
+                    id,
+                })
+            }
+            Event::End(TagEnd::Link) => {
+                if unwrapped.pop().unwrap_or(false) {
+                    Event::InlineHtml(CowStr::from("</span>"))
+                } else {
+                    Event::End(TagEnd::Link)
+                }
+            }
+            Event::Start(Tag::Image {
+                link_type,
+                dest_url,
+                title,
+                id,
+            }) => Event::Start(Tag::Image {
+                link_type,
+                dest_url: self.resolve_attachment(dest_url, true),
+                title,
+                id,
+            }),
+            other => other,
+        });
+
+        let mut out = String::new();
+        html::push_html(&mut out, events);
+        out
+    }
+
+    /// Map an `attachment://` URL to a data URI or file URL
+    fn resolve_attachment<'u>(&self, url: CowStr<'u>, is_image: bool) -> CowStr<'u> {
+        let Some(store) = self.attachments else {
+            return url;
+        };
+        if !url.starts_with(ATTACHMENT_URI_SCHEME) {
+            return url;
+        }
+        let Some(path) = store.resolve_uri(&url) else {
+            return url;
+        };
+
+        if is_image && self.attachment_mode == AttachmentMode::Inline {
+            if let Ok(data) = std::fs::read(&path) {
+                return CowStr::from(format!(
+                    "data:{};base64,{}",
+                    sniff_mime(&data),
+                    base64::engine::general_purpose::STANDARD.encode(&data)
+                ));
+            }
+        }
+        CowStr::from(format!("file://{}", path.display()))
+    }
+}
+
+/// Plain-text page title from the root content's first line
+fn document_title(content: &str) -> String {
+    let first = content.lines().next().unwrap_or("").trim();
+    let title = first.trim_start_matches('#').trim();
+    if title.is_empty() {
+        "Untitled".to_string()
+    } else {
+        truncate_chars(title, MAX_TITLE_CHARS)
+    }
+}
+
+/// Best-effort image MIME type from magic bytes
+fn sniff_mime(data: &[u8]) -> &'static str {
+    match data {
+        [0x89, b'P', b'N', b'G', ..] => "image/png",
+        [0xFF, 0xD8, 0xFF, ..] => "image/jpeg",
+        [b'G', b'I', b'F', b'8', ..] => "image/gif",
+        [b'R', b'I', b'F', b'F', _, _, _, _, b'W', b'E', b'B', b'P', ..] => "image/webp",
+        _ if data.starts_with(b"<svg") || data.starts_with(b"<?xml") => "image/svg+xml",
+        _ => "application/octet-stream",
+    }
+}
+
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn node(id: &str, node_type: &str, content: &str, properties: serde_json::Value) -> Node {
+        let mut node = Node::new(node_type.to_string(), content.to_string(), properties);
+        node.id = id.to_string();
+        node
+    }
+
+    fn tree(
+        nodes: Vec<(Option<&str>, Node)>,
+    ) -> (HashMap<String, Node>, HashMap<String, Vec<String>>) {
+        let mut node_map = HashMap::new();
+        let mut adjacency: HashMap<String, Vec<String>> = HashMap::new();
+        for (parent, node) in nodes {
+            if let Some(parent) = parent {
+                adjacency
+                    .entry(parent.to_string())
+                    .or_default()
+                    .push(node.id.clone());
+            }
+            node_map.insert(node.id.clone(), node);
+        }
+        (node_map, adjacency)
+    }
+
+    #[test]
+    fn test_renders_standalone_document() {
+        let root = node("root", "text", "Project <Plan>", json!({}));
+        let (node_map, adjacency) = tree(vec![
+            (None, root.clone()),
+            (Some("root"), node("a", "header", "## Goals", json!({}))),
+            (Some("a"), node("b", "text", "Ship **v1**", json!({}))),
+            (
+                Some("a"),
+                node(
+                    "c",
+                    "task",
+                    "Write docs",
+                    json!({ "task": { "status": "done" } }),
+                ),
+            ),
+            (
+                Some("a"),
+                node("d", "text", "<script>alert(1)</script>", json!({})),
+            ),
+        ]);
+
+        let html = HtmlExporter::new().render(&root, &node_map, &adjacency);
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<title>Project &lt;Plan&gt;</title>"));
+        assert!(html.contains("<h2>Goals</h2>"));
+        assert!(html.contains("<strong>v1</strong>"));
+        assert!(html.contains("node-task done"));
+        assert!(html.contains("<input type=\"checkbox\" disabled checked>"));
+        assert!(!html.contains("<script>"));
+    }
+
+    #[test]
+    fn test_mentions_become_anchors() {
+        let root = node("root", "text", "# Notes", json!({}));
+        let (node_map, adjacency) = tree(vec![
+            (None, root.clone()),
+            (Some("root"), node("a", "text", "Target", json!({}))),
+            (
+                Some("root"),
+                node(
+                    "b",
+                    "text",
+                    "See [Target](nodespace://a) and [Elsewhere](nodespace://zzz)",
+                    json!({}),
+                ),
+            ),
+        ]);
+
+        let html = HtmlExporter::new().render(&root, &node_map, &adjacency);
+
+        assert!(html.contains("id=\"node-a\""));
+        assert!(html.contains("<a class=\"mention\" href=\"#node-a\">Target</a>"));
+        assert!(html.contains("<span class=\"mention\">Elsewhere</span>"));
+        assert!(!html.contains("nodespace://"));
+    }
+
+    #[test]
+    fn test_attachments_inlined_or_linked() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = AttachmentStore::new(dir.path());
+        let png = [0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+        let image = store.put(&png, "map.png", "image/png").unwrap();
+        let pdf = store
+            .put(b"%PDF-1.4", "doc.pdf", "application/pdf")
+            .unwrap();
+
+        let content = format!("![map]({}) [doc]({})", image.uri(), pdf.uri());
+        let root = node("root", "text", "# Files", json!({}));
+        let (node_map, adjacency) = tree(vec![
+            (None, root.clone()),
+            (Some("root"), node("a", "text", &content, json!({}))),
+        ]);
+
+        let inline = HtmlExporter::new()
+            .with_attachments(&store, AttachmentMode::Inline)
+            .render(&root, &node_map, &adjacency);
+        assert!(inline.contains("src=\"data:image/png;base64,iVBORw0KGgo=\""));
+        assert!(inline.contains(&format!("href=\"file://{}", dir.path().display())));
+
+        let linked = HtmlExporter::new()
+            .with_attachments(&store, AttachmentMode::Link)
+            .render(&root, &node_map, &adjacency);
+        assert!(!linked.contains("data:image/png"));
+        assert!(!linked.contains("attachment://"));
+    }
+}
@@ -0,0 +1,22 @@
+//! File Exporters
+//!
+//! Render stored node trees into external formats. Exporters read through
+//! [`NodeService`](crate::services::NodeService) and return the rendered file
+//! content; writing it anywhere is left to the caller.
+//!
+//! | Format | Exporter |
+//! |--------|----------|
+//! | BibTeX | [`BibtexExporter`] |
+//! | HTML | [`HtmlExporter`] |
+//! | Share bundle (HTML + signed JSON) | [`ShareExporter`] |
+//! | todo.txt | [`TodoTxtExporter`] |
+
+mod bibtex;
+mod html;
+mod share;
+mod todo_txt;
+
+pub use bibtex::BibtexExporter;
+pub use html::{AttachmentMode, HtmlExporter};
+pub use share::{ShareBundle, ShareDocument, ShareExporter, SharedNode, SignedShareDocument};
+pub use todo_txt::TodoTxtExporter;
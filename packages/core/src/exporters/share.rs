@@ -0,0 +1,371 @@
+//! Share bundle exporter
+//!
+//! Turns a subtree into a read-only bundle that can be handed to someone
+//! without the app: a standalone HTML page (rendered by [`HtmlExporter`]) and
+//! a JSON document signed with the share's secret.
+//!
+//! Bundles never carry stored node IDs. Every node gets a share-scoped ID
+//! (`n1`, `n2`, ... in document order) and references inside content are
+//! rewritten to match: `nodespace://` links and `((id))` embeds of shared
+//! nodes point at their scoped IDs, while references to nodes outside the
+//! share are reduced to their link text or dropped. Only type, content and
+//! task status are kept; properties, timestamps and versions are not.
+
+use super::HtmlExporter;
+use crate::models::{Node, ShareRecord, TaskNode};
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use regex::{Captures, Regex};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// Matches, in order of precedence: a markdown `nodespace://` link (groups
+/// 1-2), a plain `nodespace://` URI (group 3) and a `((id))` embed (group 4)
+const REFERENCE_PATTERN: &str = r"\[([^\]]+)\]\(nodespace://(?:node/)?([^\s)?]+)(?:\?[^)]*)?\)|nodespace://(?:node/)?([^\s)?]+)|\(\(([^\s()]+)\)\)";
+
+/// A node as it appears in a share: scoped ID, type, content, children
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SharedNode {
+    pub id: String,
+    pub node_type: String,
+    pub content: String,
+    /// Task status, for task nodes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<SharedNode>,
+}
+
+/// The shared subtree with the share's identity
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShareDocument {
+    pub share_id: String,
+    pub created_at: DateTime<Utc>,
+    pub root: SharedNode,
+}
+
+/// JSON form of a share: the document plus its hex HMAC-SHA256 signature
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SignedShareDocument {
+    pub document: ShareDocument,
+    pub signature: String,
+}
+
+/// Output of `NodeService::create_share_bundle`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShareBundle {
+    pub share_id: String,
+    pub created_at: DateTime<Utc>,
+    /// Standalone HTML page
+    pub html: String,
+    /// Serialized [`SignedShareDocument`]
+    pub json: String,
+}
+
+/// Builds share bundles from pre-fetched subtree data
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ShareExporter;
+
+impl ShareExporter {
+    /// Render both forms of a share of `root`'s subtree
+    pub fn bundle(
+        &self,
+        share: &ShareRecord,
+        root: &Node,
+        node_map: &HashMap<String, Node>,
+        adjacency_list: &HashMap<String, Vec<String>>,
+    ) -> ShareBundle {
+        let document = self.document(share, root, node_map, adjacency_list);
+        let html = Self::render_html(&document);
+        let signed = SignedShareDocument {
+            signature: Self::sign(&share.secret, &document),
+            document,
+        };
+        ShareBundle {
+            share_id: share.share_id.clone(),
+            created_at: share.created_at,
+            html,
+            json: serde_json::to_string_pretty(&signed).unwrap_or_default(),
+        }
+    }
+
+    /// Scope a subtree for sharing (see the module docs for what is kept)
+    pub fn document(
+        &self,
+        share: &ShareRecord,
+        root: &Node,
+        node_map: &HashMap<String, Node>,
+        adjacency_list: &HashMap<String, Vec<String>>,
+    ) -> ShareDocument {
+        // Assign scoped IDs up front so forward references can be rewritten
+        let mut scoped_ids = HashMap::new();
+        let mut stack = vec![root.id.as_str()];
+        while let Some(id) = stack.pop() {
+            if scoped_ids.contains_key(id) {
+                continue;
+            }
+            scoped_ids.insert(id.to_string(), format!("n{}", scoped_ids.len() + 1));
+            if let Some(children) = adjacency_list.get(id) {
+                stack.extend(children.iter().rev().map(String::as_str));
+            }
+        }
+
+        ShareDocument {
+            share_id: share.share_id.clone(),
+            created_at: share.created_at,
+            root: Self::shared_node(root, node_map, adjacency_list, &scoped_ids),
+        }
+    }
+
+    fn shared_node(
+        node: &Node,
+        node_map: &HashMap<String, Node>,
+        adjacency_list: &HashMap<String, Vec<String>>,
+        scoped_ids: &HashMap<String, String>,
+    ) -> SharedNode {
+        let status = (node.node_type == "task")
+            .then(|| TaskNode::from_node(node.clone()).ok())
+            .flatten()
+            .map(|task| task.status().as_str().to_string());
+        let children = adjacency_list
+            .get(&node.id)
+            .into_iter()
+            .flatten()
+            .filter_map(|id| node_map.get(id))
+            .map(|child| Self::shared_node(child, node_map, adjacency_list, scoped_ids))
+            .collect();
+
+        SharedNode {
+            id: scoped_ids[&node.id].clone(),
+            node_type: node.node_type.clone(),
+            content: scope_content(&node.content, scoped_ids),
+            status,
+            children,
+        }
+    }
+
+    /// Hex HMAC-SHA256 of the document's JSON serialization
+    pub fn sign(secret: &str, document: &ShareDocument) -> String {
+        share_mac(secret, document)
+            .finalize()
+            .into_bytes()
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect()
+    }
+
+    /// Check a signed document against its share's secret (constant time)
+    pub fn verify(secret: &str, signed: &SignedShareDocument) -> bool {
+        let Some(signature) = decode_hex(&signed.signature) else {
+            return false;
+        };
+        share_mac(secret, &signed.document)
+            .verify_slice(&signature)
+            .is_ok()
+    }
+
+    /// Render a scoped document with [`HtmlExporter`], expanding embeds
+    fn render_html(document: &ShareDocument) -> String {
+        let mut contents = HashMap::new();
+        collect_contents(&document.root, &mut contents);
+
+        let mut node_map = HashMap::new();
+        let mut adjacency_list: HashMap<String, Vec<String>> = HashMap::new();
+        let mut stack = vec![&document.root];
+        while let Some(shared) = stack.pop() {
+            let properties = match &shared.status {
+                Some(status) => serde_json::json!({ "task": { "status": status } }),
+                None => serde_json::json!({}),
+            };
+            let mut node = Node::new(
+                shared.node_type.clone(),
+                expand_embeds(&shared.content, &contents),
+                properties,
+            );
+            node.id = shared.id.clone();
+            node_map.insert(node.id.clone(), node);
+            adjacency_list.insert(
+                shared.id.clone(),
+                shared.children.iter().map(|c| c.id.clone()).collect(),
+            );
+            stack.extend(&shared.children);
+        }
+
+        HtmlExporter::new().render(&node_map[&document.root.id], &node_map, &adjacency_list)
+    }
+}
+
+fn share_mac(secret: &str, document: &ShareDocument) -> Hmac<Sha256> {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(&serde_json::to_vec(document).unwrap_or_default());
+    mac
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    hex.as_bytes()
+        .chunks(2)
+        .map(|pair| match pair {
+            [hi, lo] => u8::from_str_radix(std::str::from_utf8(&[*hi, *lo]).ok()?, 16).ok(),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Rewrite node references in `content` to share-scoped IDs
+///
+/// References to nodes outside `scoped_ids` keep only their link text.
+fn scope_content(content: &str, scoped_ids: &HashMap<String, String>) -> String {
+    static REFERENCE_REGEX: OnceLock<Regex> = OnceLock::new();
+    let regex = REFERENCE_REGEX.get_or_init(|| Regex::new(REFERENCE_PATTERN).unwrap());
+
+    regex
+        .replace_all(content, |caps: &Captures| {
+            if let (Some(text), Some(id)) = (caps.get(1), caps.get(2)) {
+                return match scoped_ids.get(id.as_str()) {
+                    Some(scoped) => format!("[{}](nodespace://{})", text.as_str(), scoped),
+                    None => text.as_str().to_string(),
+                };
+            }
+            if let Some(id) = caps.get(3) {
+                return scoped_ids
+                    .get(id.as_str())
+                    .map(|scoped| format!("nodespace://{}", scoped))
+                    .unwrap_or_default();
+            }
+            let id = &caps[4];
+            scoped_ids
+                .get(id)
+                .map(|scoped| format!("(({}))", scoped))
+                .unwrap_or_default()
+        })
+        .into_owned()
+}
+
+fn collect_contents<'a>(node: &'a SharedNode, out: &mut HashMap<&'a str, &'a str>) {
+    out.insert(node.id.as_str(), node.content.as_str());
+    for child in &node.children {
+        collect_contents(child, out);
+    }
+}
+
+/// Replace scoped `((id))` embeds with the embedded node's content
+fn expand_embeds(content: &str, contents: &HashMap<&str, &str>) -> String {
+    static EMBED_REGEX: OnceLock<Regex> = OnceLock::new();
+    let regex = EMBED_REGEX.get_or_init(|| Regex::new(r"\(\((n\d+)\)\)").unwrap());
+
+    regex
+        .replace_all(content, |caps: &Captures| {
+            contents
+                .get(&caps[1])
+                .copied()
+                .unwrap_or_default()
+                .to_string()
+        })
+        .into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn node(id: &str, node_type: &str, content: &str, properties: serde_json::Value) -> Node {
+        let mut node = Node::new(node_type.to_string(), content.to_string(), properties);
+        node.id = id.to_string();
+        node
+    }
+
+    fn share() -> ShareRecord {
+        ShareRecord::new("root-id-secret")
+    }
+
+    fn sample() -> (Node, HashMap<String, Node>, HashMap<String, Vec<String>>) {
+        let root = node("root-id-secret", "text", "# Trip", json!({ "private": 1 }));
+        let nodes = [
+            node(
+                "a-id-secret",
+                "text",
+                "See [packing](nodespace://b-id-secret) and [diary](nodespace://outside-id)",
+                json!({}),
+            ),
+            node(
+                "b-id-secret",
+                "task",
+                "Pack bags",
+                json!({ "task": { "status": "done" } }),
+            ),
+            node(
+                "c-id-secret",
+                "text",
+                "((a-id-secret)) ((outside-id)) nodespace://outside-id",
+                json!({}),
+            ),
+        ];
+        let node_map: HashMap<String, Node> = std::iter::once(root.clone())
+            .chain(nodes)
+            .map(|n| (n.id.clone(), n))
+            .collect();
+        let adjacency = HashMap::from([(
+            "root-id-secret".to_string(),
+            vec![
+                "a-id-secret".to_string(),
+                "b-id-secret".to_string(),
+                "c-id-secret".to_string(),
+            ],
+        )]);
+        (root, node_map, adjacency)
+    }
+
+    #[test]
+    fn test_bundle_uses_scoped_ids_and_drops_private_data() {
+        let (root, node_map, adjacency) = sample();
+        let share = share();
+        let bundle = ShareExporter.bundle(&share, &root, &node_map, &adjacency);
+
+        for output in [&bundle.html, &bundle.json] {
+            assert!(!output.contains("-secret"), "stored IDs leaked");
+            assert!(!output.contains("outside-id"));
+            assert!(!output.contains("private"));
+            assert!(!output.contains(&share.secret));
+        }
+
+        let signed: SignedShareDocument = serde_json::from_str(&bundle.json).unwrap();
+        let children = &signed.document.root.children;
+        assert_eq!(signed.document.root.id, "n1");
+        assert_eq!(
+            children[0].content,
+            "See [packing](nodespace://n3) and diary"
+        );
+        assert_eq!(children[1].status.as_deref(), Some("done"));
+        assert_eq!(children[2].content, "((n2))  ");
+
+        assert!(bundle
+            .html
+            .contains("<a class=\"mention\" href=\"#node-n3\">packing</a>"));
+        assert!(bundle.html.contains("node-task done"));
+        assert!(!bundle.html.contains("((n2))"));
+    }
+
+    #[test]
+    fn test_signature_detects_tampering() {
+        let (root, node_map, adjacency) = sample();
+        let share = share();
+        let bundle = ShareExporter.bundle(&share, &root, &node_map, &adjacency);
+        let mut signed: SignedShareDocument = serde_json::from_str(&bundle.json).unwrap();
+
+        assert!(ShareExporter::verify(&share.secret, &signed));
+        assert!(!ShareExporter::verify(
+            &ShareRecord::new("x").secret,
+            &signed
+        ));
+
+        signed.document.root.content = "# Edited".to_string();
+        assert!(!ShareExporter::verify(&share.secret, &signed));
+    }
+}
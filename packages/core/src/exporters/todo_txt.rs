@@ -0,0 +1,163 @@
+//! todo.txt exporter
+//!
+//! Writes the task nodes of a subtree as todo.txt lines, the reverse of
+//! [`TodoTxtImporter`](crate::importers::TodoTxtImporter): collection
+//! memberships become `+project` tags and the `contexts` property becomes
+//! `@context` tags. Non-task nodes are skipped but their task descendants are
+//! still exported.
+
+use crate::importers::TodoTxtTask;
+use crate::models::{Node, TaskNode, TaskPriority, TaskStatus};
+use crate::services::{CollectionService, NodeService, NodeServiceError};
+use std::collections::HashMap;
+
+/// Exports task subtrees in todo.txt format
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TodoTxtExporter;
+
+impl TodoTxtExporter {
+    /// Render every task under `root_id` (depth-first, in outline order)
+    pub async fn export_subtree<C>(
+        &self,
+        node_service: &NodeService<C>,
+        root_id: &str,
+    ) -> Result<String, NodeServiceError>
+    where
+        C: surrealdb::Connection,
+    {
+        let (root, node_map, adjacency_list) = node_service.get_subtree_data(root_id).await?;
+        let root = root.ok_or_else(|| NodeServiceError::node_not_found(root_id))?;
+
+        let mut tasks = Vec::new();
+        collect_tasks(&root, &node_map, &adjacency_list, &mut tasks);
+
+        let collections = CollectionService::new(node_service.store(), node_service);
+        let mut collection_names: HashMap<String, String> = HashMap::new();
+        let mut out = String::new();
+        for task in tasks {
+            let mut projects = Vec::new();
+            for collection_id in collections.get_node_collections(&task.id).await? {
+                if !collection_names.contains_key(&collection_id) {
+                    let name = node_service
+                        .get_node(&collection_id)
+                        .await?
+                        .map(|c| c.content)
+                        .unwrap_or_default();
+                    collection_names.insert(collection_id.clone(), name);
+                }
+                let name = &collection_names[&collection_id];
+                if !name.is_empty() {
+                    projects.push(name.clone());
+                }
+            }
+
+            if let Some(line) = Self::task_line(task, &projects) {
+                out.push_str(&line.to_string());
+                out.push('\n');
+            }
+        }
+        Ok(out)
+    }
+
+    /// Map a task node to a todo.txt line (`None` for non-task nodes)
+    ///
+    /// Whitespace in project names is replaced with `-`, since todo.txt tags
+    /// end at the first space.
+    pub fn task_line(node: &Node, projects: &[String]) -> Option<TodoTxtTask> {
+        let task = TaskNode::from_node(node.clone()).ok()?;
+        let props = node.properties.get("task").unwrap_or(&node.properties);
+
+        let priority = match task.priority {
+            Some(TaskPriority::High) => Some('A'),
+            Some(TaskPriority::Medium) => Some('B'),
+            Some(TaskPriority::Low) => Some('C'),
+            Some(TaskPriority::User(_)) | None => None,
+        };
+        let contexts = props
+            .get("contexts")
+            .and_then(|v| v.as_array())
+            .map(|values| {
+                values
+                    .iter()
+                    .filter_map(|v| v.as_str())
+                    .map(|c| c.split_whitespace().collect::<Vec<_>>().join("-"))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Some(TodoTxtTask {
+            done: task.status == TaskStatus::Done,
+            priority,
+            completed: task.completed_at.map(|d| d.date_naive()),
+            created: Some(node.created_at.date_naive()),
+            description: node
+                .content
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" "),
+            projects: projects
+                .iter()
+                .map(|p| p.split_whitespace().collect::<Vec<_>>().join("-"))
+                .collect(),
+            contexts,
+            due: task.due_date.map(|d| d.date_naive()),
+        })
+    }
+}
+
+fn collect_tasks<'a>(
+    node: &'a Node,
+    node_map: &'a HashMap<String, Node>,
+    adjacency_list: &HashMap<String, Vec<String>>,
+    out: &mut Vec<&'a Node>,
+) {
+    if node.node_type == "task" {
+        out.push(node);
+    }
+    for child_id in adjacency_list.get(&node.id).into_iter().flatten() {
+        if let Some(child) = node_map.get(child_id) {
+            collect_tasks(child, node_map, adjacency_list, out);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{NaiveDate, TimeZone, Utc};
+    use serde_json::json;
+
+    #[test]
+    fn test_task_line_round_trips_import_mapping() {
+        let line = "x (A) 2024-03-02 2024-03-01 Call mom +Family @phone due:2024-03-05";
+        let parsed = TodoTxtTask::parse(line).unwrap();
+
+        let mut node = Node::new(
+            "task".to_string(),
+            parsed.description.clone(),
+            json!({ "task": parsed.to_properties() }),
+        );
+        node.created_at = Utc.with_ymd_and_hms(2024, 3, 1, 0, 0, 0).unwrap();
+
+        let exported = TodoTxtExporter::task_line(&node, &parsed.projects).unwrap();
+        assert_eq!(exported, parsed);
+        assert_eq!(exported.to_string(), line);
+    }
+
+    #[test]
+    fn test_task_line_skips_non_tasks_and_sanitizes_tags() {
+        let text = Node::new("text".to_string(), "Note".to_string(), json!({}));
+        assert!(TodoTxtExporter::task_line(&text, &[]).is_none());
+
+        let mut task = Node::new(
+            "task".to_string(),
+            "Plan\nroute".to_string(),
+            json!({ "task": { "status": "open" } }),
+        );
+        task.created_at = Utc.with_ymd_and_hms(2024, 1, 2, 9, 0, 0).unwrap();
+        let line = TodoTxtExporter::task_line(&task, &["Summer Trip".to_string()]).unwrap();
+
+        assert_eq!(line.created, NaiveDate::from_ymd_opt(2024, 1, 2));
+        assert_eq!(line.to_string(), "2024-01-02 Plan route +Summer-Trip");
+    }
+}
@@ -0,0 +1,492 @@
+//! Synthetic Workspaces - Realistic Graphs for Benchmarks and Tests
+//!
+//! [`SyntheticWorkspace::generate`] builds a workspace whose shape follows a
+//! [`WorkspaceShape`]:
+//!
+//! - document sizes are Zipf-distributed: a few large documents, a long tail
+//!   of small ones
+//! - children nest below earlier siblings, so documents have some depth
+//! - a share of nodes mention other documents, with popular (large)
+//!   documents mentioned most, as a `[title](nodespace://id)` link plus a
+//!   `mentions` edge
+//! - collections form a tree of the given depth and fanout, and most
+//!   documents belong to one
+//!
+//! The same shape and seed always give the same workspace, IDs included, so a
+//! slow benchmark or a failing test can be reproduced. The result is written
+//! with [`SurrealStore::bulk_upsert`].
+
+use crate::db::{BulkUpsertResult, EdgeUpsert, FractionalIndex, SurrealStore};
+use crate::models::Node;
+use anyhow::Result;
+use serde_json::json;
+use std::collections::HashMap;
+
+/// Words the generated content is made of
+pub const VOCABULARY: &[&str] = &[
+    "meeting",
+    "roadmap",
+    "budget",
+    "design",
+    "review",
+    "launch",
+    "hiring",
+    "customer",
+    "research",
+    "release",
+    "feedback",
+    "migration",
+    "security",
+    "pricing",
+    "onboarding",
+    "metrics",
+    "planning",
+    "retro",
+    "incident",
+    "partner",
+    "workshop",
+    "draft",
+    "contract",
+    "support",
+    "vendor",
+    "travel",
+    "quarterly",
+    "weekly",
+    "notes",
+    "ideas",
+    "questions",
+    "decision",
+    "follow-up",
+    "summary",
+    "proposal",
+    "estimate",
+    "deadline",
+    "prototype",
+    "interview",
+    "analysis",
+];
+
+/// Distributions of a synthetic workspace
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorkspaceShape {
+    /// Total nodes, collections and documents included
+    pub nodes: usize,
+    /// Documents (root nodes)
+    pub documents: usize,
+    /// Zipf exponent of document sizes; 0 makes them equal, larger is more skewed
+    pub size_skew: f64,
+    /// Mean mentions per child node
+    pub mention_density: f64,
+    /// Share of child nodes that are tasks
+    pub task_ratio: f64,
+    /// Levels of the collection tree, 0 for no collections
+    pub collection_depth: usize,
+    /// Sub-collections per collection
+    pub collection_fanout: usize,
+}
+
+impl Default for WorkspaceShape {
+    fn default() -> Self {
+        Self::with_nodes(10_000)
+    }
+}
+
+impl WorkspaceShape {
+    /// Typical shape for `nodes` nodes: one document per 50 nodes
+    pub fn with_nodes(nodes: usize) -> Self {
+        Self {
+            nodes,
+            documents: (nodes / 50).max(1),
+            size_skew: 1.1,
+            mention_density: 0.05,
+            task_ratio: 0.1,
+            collection_depth: 3,
+            collection_fanout: 4,
+        }
+    }
+
+    /// Collections the tree holds: fanout + fanout² + … over its depth
+    fn collection_count(&self) -> usize {
+        (1..=self.collection_depth as u32)
+            .map(|level| self.collection_fanout.saturating_pow(level))
+            .fold(0, usize::saturating_add)
+    }
+}
+
+/// A generated workspace, ready to write to a store
+#[derive(Debug, Clone)]
+pub struct SyntheticWorkspace {
+    pub nodes: Vec<Node>,
+    pub edges: Vec<EdgeUpsert>,
+    /// Document IDs, largest document first
+    pub documents: Vec<String>,
+    /// Collection IDs, top level first
+    pub collections: Vec<String>,
+}
+
+impl SyntheticWorkspace {
+    pub fn generate(shape: &WorkspaceShape, seed: u64) -> Self {
+        let mut generator = Generator {
+            rng: SplitMix64(seed),
+            workspace: SyntheticWorkspace {
+                nodes: Vec::with_capacity(shape.nodes),
+                edges: Vec::with_capacity(shape.nodes),
+                documents: Vec::new(),
+                collections: Vec::new(),
+            },
+            child_counts: HashMap::new(),
+        };
+
+        let collections = shape.collection_count().min(shape.nodes.saturating_sub(1));
+        let documents = shape
+            .documents
+            .clamp(1, shape.nodes.saturating_sub(collections).max(1));
+        let children = shape.nodes.saturating_sub(collections + documents);
+
+        generator.collections(shape, collections);
+        let sizes = zipf_sizes(children, documents, shape.size_skew);
+        let weights = zipf_weights(documents, shape.size_skew);
+        for (rank, size) in sizes.into_iter().enumerate() {
+            generator.document(shape, rank, size, &weights);
+        }
+        generator.workspace
+    }
+
+    /// Write the workspace to `store`; items that failed are reported in the result
+    pub async fn write_to<C>(
+        self,
+        store: &SurrealStore<C>,
+        chunk_size: usize,
+    ) -> Result<BulkUpsertResult>
+    where
+        C: surrealdb::Connection,
+    {
+        store.bulk_upsert(self.nodes, self.edges, chunk_size).await
+    }
+}
+
+struct Generator {
+    rng: SplitMix64,
+    workspace: SyntheticWorkspace,
+    /// Children added per parent, for their order keys
+    child_counts: HashMap<String, usize>,
+}
+
+impl Generator {
+    fn node(&mut self, node_type: &str, content: String, properties: serde_json::Value) -> String {
+        let id = uuid::Builder::from_random_bytes(self.rng.bytes())
+            .into_uuid()
+            .to_string();
+        self.workspace.nodes.push(Node::new_with_id(
+            id.clone(),
+            node_type.to_string(),
+            content,
+            properties,
+        ));
+        id
+    }
+
+    fn edge(&mut self, from_id: &str, to_id: &str, relationship_type: &str) {
+        let properties = match relationship_type {
+            "mentions" => json!({}),
+            _ => {
+                let count = self.child_counts.entry(from_id.to_string()).or_default();
+                *count += 1;
+                json!({ "order": FractionalIndex::nth(*count - 1) })
+            }
+        };
+        // member_of points from the member to its collection
+        let (from_id, to_id) = match relationship_type {
+            "member_of" => (to_id, from_id),
+            _ => (from_id, to_id),
+        };
+        self.workspace.edges.push(EdgeUpsert {
+            from_id: from_id.to_string(),
+            to_id: to_id.to_string(),
+            relationship_type: relationship_type.to_string(),
+            properties,
+        });
+    }
+
+    /// The collection tree, breadth first, up to `count` collections
+    fn collections(&mut self, shape: &WorkspaceShape, count: usize) {
+        let mut level: Vec<Option<String>> = vec![None];
+        let mut path = vec![String::new()];
+        while self.workspace.collections.len() < count {
+            let mut next_level = Vec::new();
+            let mut next_path = Vec::new();
+            for (parent, parent_path) in level.iter().zip(&path) {
+                for i in 1..=shape.collection_fanout {
+                    if self.workspace.collections.len() == count {
+                        break;
+                    }
+                    let name = format!("{}{}", parent_path, i);
+                    let id = self.node("collection", format!("Area {}", name), json!({}));
+                    if let Some(parent) = parent {
+                        self.edge(parent, &id, "member_of");
+                    }
+                    self.workspace.collections.push(id.clone());
+                    next_level.push(Some(id));
+                    next_path.push(format!("{}.", name));
+                }
+            }
+            if next_level.is_empty() {
+                break;
+            }
+            level = next_level;
+            path = next_path;
+        }
+    }
+
+    /// Document `rank` (0 being the largest) with `size` children
+    fn document(&mut self, shape: &WorkspaceShape, rank: usize, size: usize, weights: &[f64]) {
+        let title = format!("{} {}", self.words(2), rank + 1);
+        let root = self.node("text", format!("# {}", title), json!({}));
+        self.workspace.documents.push(root.clone());
+        if !self.workspace.collections.is_empty() && self.rng.chance(0.8) {
+            let collection = self.workspace.collections
+                [self.rng.below(self.workspace.collections.len())]
+            .clone();
+            self.edge(&collection, &root, "member_of");
+        }
+
+        let mut children: Vec<String> = Vec::with_capacity(size);
+        for _ in 0..size {
+            // Most nodes sit at the top of the document, some nest below an earlier one
+            let parent = match children.len() {
+                0 => root.clone(),
+                n if self.rng.chance(0.3) => children[n - 1 - self.rng.below(n.min(5))].clone(),
+                _ => root.clone(),
+            };
+
+            let length = 4 + self.rng.below(12);
+            let mut content = self.words(length);
+            let mut mentioned = Vec::new();
+            for _ in 0..self.rng.count(shape.mention_density) {
+                let target = self.rng.weighted(weights);
+                // Only documents generated so far exist to be linked
+                if target >= self.workspace.documents.len() || target == rank {
+                    continue;
+                }
+                let target_id = self.workspace.documents[target].clone();
+                if !mentioned.contains(&target_id) {
+                    content.push_str(&format!(
+                        " [Document {}](nodespace://{})",
+                        target + 1,
+                        target_id
+                    ));
+                    mentioned.push(target_id);
+                }
+            }
+
+            let id = if self.rng.chance(shape.task_ratio) {
+                self.node("task", content, json!({"status": "open"}))
+            } else {
+                self.node("text", content, json!({}))
+            };
+            self.edge(&parent, &id, "has_child");
+            for target in mentioned {
+                self.edge(&id, &target, "mentions");
+            }
+            children.push(id);
+        }
+    }
+
+    fn words(&mut self, count: usize) -> String {
+        let words: Vec<&str> = (0..count)
+            .map(|_| VOCABULARY[self.rng.below(VOCABULARY.len())])
+            .collect();
+        let mut text = words.join(" ");
+        if let Some(first) = text.get_mut(0..1) {
+            first.make_ascii_uppercase();
+        }
+        text
+    }
+}
+
+/// Relative sizes 1/kˢ of `n` ranks
+fn zipf_weights(n: usize, exponent: f64) -> Vec<f64> {
+    (1..=n).map(|k| 1.0 / (k as f64).powf(exponent)).collect()
+}
+
+/// `total` items split over `n` ranks in Zipf proportions
+fn zipf_sizes(total: usize, n: usize, exponent: f64) -> Vec<usize> {
+    let weights = zipf_weights(n, exponent);
+    let sum: f64 = weights.iter().sum();
+    let mut sizes: Vec<usize> = weights
+        .iter()
+        .map(|w| (total as f64 * w / sum) as usize)
+        .collect();
+    // Rounding down leaves a few items, which go to the largest ranks
+    let missing = total - sizes.iter().sum::<usize>();
+    for size in sizes.iter_mut().take(missing) {
+        *size += 1;
+    }
+    sizes
+}
+
+/// Small seedable PRNG (SplitMix64); good enough for test data, not for secrets
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in [0, 1)
+    fn unit(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Uniform in [0, n)
+    fn below(&mut self, n: usize) -> usize {
+        (self.unit() * n as f64) as usize
+    }
+
+    fn chance(&mut self, probability: f64) -> bool {
+        self.unit() < probability
+    }
+
+    /// A count with mean `mean`: its whole part, plus one with the
+    /// probability of its fraction
+    fn count(&mut self, mean: f64) -> usize {
+        let mean = mean.max(0.0);
+        mean.trunc() as usize + usize::from(self.chance(mean.fract()))
+    }
+
+    /// An index drawn in proportion to `weights`
+    fn weighted(&mut self, weights: &[f64]) -> usize {
+        let target = self.unit() * weights.iter().sum::<f64>();
+        let mut cumulative = 0.0;
+        for (i, weight) in weights.iter().enumerate() {
+            cumulative += weight;
+            if target < cumulative {
+                return i;
+            }
+        }
+        weights.len().saturating_sub(1)
+    }
+
+    fn bytes(&mut self) -> [u8; 16] {
+        let mut bytes = [0; 16];
+        bytes[..8].copy_from_slice(&self.next_u64().to_le_bytes());
+        bytes[8..].copy_from_slice(&self.next_u64().to_le_bytes());
+        bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    fn shape(nodes: usize) -> WorkspaceShape {
+        WorkspaceShape {
+            mention_density: 0.5,
+            ..WorkspaceShape::with_nodes(nodes)
+        }
+    }
+
+    #[test]
+    fn test_same_seed_gives_same_workspace() {
+        let a = SyntheticWorkspace::generate(&shape(2_000), 42);
+        let b = SyntheticWorkspace::generate(&shape(2_000), 42);
+        let c = SyntheticWorkspace::generate(&shape(2_000), 43);
+
+        let ids = |w: &SyntheticWorkspace| w.nodes.iter().map(|n| n.id.clone()).collect::<Vec<_>>();
+        let contents = |w: &SyntheticWorkspace| {
+            w.nodes
+                .iter()
+                .map(|n| n.content.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(ids(&a), ids(&b));
+        assert_eq!(contents(&a), contents(&b));
+        assert_eq!(a.edges.len(), b.edges.len());
+        assert_ne!(ids(&a), ids(&c));
+    }
+
+    #[test]
+    fn test_workspace_has_the_requested_shape() {
+        let shape = shape(5_000);
+        let workspace = SyntheticWorkspace::generate(&shape, 7);
+        assert_eq!(workspace.nodes.len(), shape.nodes);
+        assert_eq!(workspace.documents.len(), shape.documents);
+        assert_eq!(workspace.collections.len(), 4 + 16 + 64);
+
+        let children_of = |id: &str| {
+            workspace
+                .edges
+                .iter()
+                .filter(|e| e.relationship_type == "has_child" && e.from_id == id)
+                .count()
+        };
+        // Zipf: the first document is far larger than the last
+        let first = children_of(&workspace.documents[0]);
+        let last = children_of(workspace.documents.last().unwrap());
+        assert!(first > 10 * last.max(1), "{} vs {}", first, last);
+
+        let mentions = workspace
+            .edges
+            .iter()
+            .filter(|e| e.relationship_type == "mentions")
+            .count();
+        assert!(mentions > 0);
+    }
+
+    #[test]
+    fn test_generated_graph_is_consistent() {
+        let workspace = SyntheticWorkspace::generate(&shape(3_000), 1);
+        let ids: HashSet<&str> = workspace.nodes.iter().map(|n| n.id.as_str()).collect();
+        assert_eq!(ids.len(), workspace.nodes.len(), "duplicate IDs");
+
+        let mut parents: HashMap<&str, usize> = HashMap::new();
+        for edge in &workspace.edges {
+            assert!(ids.contains(edge.from_id.as_str()));
+            assert!(ids.contains(edge.to_id.as_str()));
+            assert_ne!(edge.from_id, edge.to_id);
+            if edge.relationship_type == "has_child" {
+                *parents.entry(edge.to_id.as_str()).or_default() += 1;
+            }
+        }
+        // Every node but documents and collections has exactly one parent
+        let roots = workspace.documents.len() + workspace.collections.len();
+        assert_eq!(parents.len(), workspace.nodes.len() - roots);
+        assert!(parents.values().all(|count| *count == 1));
+
+        // Linked documents in the content match the mention edges
+        for node in &workspace.nodes {
+            let linked = node.content.matches("nodespace://").count();
+            let edges = workspace
+                .edges
+                .iter()
+                .filter(|e| e.relationship_type == "mentions" && e.from_id == node.id)
+                .count();
+            assert_eq!(linked, edges);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_workspace_writes_to_store() -> Result<()> {
+        use crate::services::NodeService;
+        use std::sync::Arc;
+
+        let temp_dir = tempfile::TempDir::new()?;
+        let mut store = Arc::new(SurrealStore::new(temp_dir.path().join("test.db")).await?);
+        // Node types are only valid once NodeService has seeded their schemas
+        let _node_service = NodeService::new(&mut store)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to initialize NodeService: {}", e))?;
+        let workspace = SyntheticWorkspace::generate(&shape(300), 3);
+        let document = workspace.documents[0].clone();
+
+        let result = workspace.write_to(&store, 100).await?;
+        assert_eq!(result.failed(), 0);
+        assert!(!store.get_children(Some(&document)).await?.is_empty());
+        Ok(())
+    }
+}
@@ -0,0 +1,183 @@
+//! German messages
+
+pub(super) const MESSAGES: &[(&str, &str)] = &[
+    ("NODE_NOT_FOUND", "Knoten {id} wurde nicht gefunden"),
+    (
+        "VERSION_CONFLICT",
+        "Dieser Knoten wurde anderswo geändert (jetzt Version {actual_version}, erwartet {expected_version}). Bitte neu laden und erneut versuchen.",
+    ),
+    (
+        "IDEMPOTENCY_KEY_CONFLICT",
+        "Diese Anfrage wird noch bearbeitet oder ihr Wiederholungsschlüssel wurde bereits verwendet. Bitte neu laden und erneut versuchen.",
+    ),
+    (
+        "INVALID_PARENT",
+        "Knoten {parent_id} kann hier nicht als übergeordneter Knoten verwendet werden",
+    ),
+    (
+        "INVALID_ROOT",
+        "Knoten {root_node_id} kann hier nicht als Wurzel verwendet werden",
+    ),
+    (
+        "CIRCULAR_REFERENCE",
+        "Ein Knoten kann nicht in sich selbst verschoben werden",
+    ),
+    (
+        "HIERARCHY_VIOLATION",
+        "Der Knoten kann dort nicht platziert werden",
+    ),
+    (
+        "INVALID_INPUT",
+        "Die Eingabe konnte nicht verarbeitet werden",
+    ),
+    (
+        "INVALID_UPDATE",
+        "Die Änderung konnte nicht übernommen werden",
+    ),
+    ("FIELD_MISSING", "{field} ist erforderlich"),
+    (
+        "WRONG_NODE_TYPE",
+        "Erwartet wurde ein Knoten vom Typ {expected}, dies ist aber ein Knoten vom Typ {actual}",
+    ),
+    (
+        "INVALID_NODE_ID",
+        "'{id}' ist hier keine gültige ID (erwartet: {format})",
+    ),
+    (
+        "SELF_MENTION",
+        "Ein Knoten kann weder sich selbst noch den Knoten erwähnen, zu dem er gehört",
+    ),
+    ("PROPERTY_TYPE_MISMATCH", "{field} muss vom Typ {expected} sein"),
+    ("MALFORMED_PROPERTY", "{field} konnte nicht gelesen werden"),
+    (
+        "NON_NEGATIVE_INTEGER_REQUIRED",
+        "{field} muss eine ganze Zahl ab null sein",
+    ),
+    (
+        "INVALID_PROPERTY_PATH",
+        "'{path}' ist kein gültiger Eigenschaftspfad",
+    ),
+    (
+        "INVALID_SCHEMA_FIELD_NAME",
+        "'{field}' ist kein gültiger Feldname. Verwende nur Buchstaben, Ziffern und Unterstriche.",
+    ),
+    (
+        "ENUM_WITHOUT_VALUES",
+        "{field} braucht mindestens einen erlaubten Wert",
+    ),
+    (
+        "SCHEMA_VERSION_NOT_POSITIVE",
+        "Die Schemaversion muss größer als null sein",
+    ),
+    (
+        "DUPLICATE_SCHEMA_FIELDS",
+        "Jedes Feld eines Schemas braucht einen eindeutigen Namen",
+    ),
+    (
+        "COLLECTION_NAME_HAS_DELIMITER",
+        "Sammlungsnamen dürfen kein ':' enthalten",
+    ),
+    (
+        "HIGHLIGHT_RANGE_REVERSED",
+        "Eine Markierung kann nicht vor ihrem Anfang enden",
+    ),
+    ("INVALID_APPEARANCE_ICON", "'{value}' ist kein einzelnes Emoji"),
+    (
+        "INVALID_APPEARANCE_COLOR",
+        "'{value}' ist keine Farbe im Format #rgb oder #rrggbb",
+    ),
+    (
+        "INVALID_APPEARANCE_COVER",
+        "'{value}' ist kein angehängtes Bild",
+    ),
+    (
+        "CHILD_VIEW_INCOMPLETE",
+        "Ansichten brauchen eine ID und einen Namen",
+    ),
+    (
+        "RELATIONSHIP_FILTER_WITHOUT_NODE",
+        "Die Beziehungsfilter der Ansicht {view} brauchen einen Knoten",
+    ),
+    (
+        "DUPLICATE_CHILD_VIEW",
+        "Es gibt bereits eine Ansicht mit der ID {id}",
+    ),
+    ("ROLLUP_NAME_MISSING", "Rollups brauchen einen Namen"),
+    (
+        "ROLLUP_PROPERTY_MISSING",
+        "Rollup {name} braucht eine Eigenschaft, die zusammengefasst wird",
+    ),
+    (
+        "DUPLICATE_ROLLUP",
+        "Es gibt bereits ein Rollup namens {name}",
+    ),
+    (
+        "REQUIRED_FIELD_MISSING",
+        "{field} ist für Knoten vom Typ {node_type} erforderlich",
+    ),
+    (
+        "INVALID_ENUM_VALUE",
+        "'{value}' ist kein gültiger Wert für {field}. Erlaubt sind: {allowed}",
+    ),
+    (
+        "ENUM_NOT_STRING",
+        "{field} muss einer der vorgegebenen Werte sein",
+    ),
+    (
+        "EDGE_DATA_NOT_OBJECT",
+        "Die Felder einer {relationship}-Beziehung müssen ein Objekt sein",
+    ),
+    (
+        "UNKNOWN_EDGE_FIELD",
+        "{relationship}-Beziehungen haben kein Feld {field}",
+    ),
+    (
+        "REQUIRED_EDGE_FIELD_MISSING",
+        "{field} ist für {relationship}-Beziehungen erforderlich",
+    ),
+    (
+        "INVALID_EDGE_FIELD_TYPE",
+        "{field} muss vom Typ {expected} sein",
+    ),
+    (
+        "WORKSPACE_LOCKED",
+        "Der Arbeitsbereich ist gesperrt. Entsperre ihn, um fortzufahren.",
+    ),
+    ("INCORRECT_PASSPHRASE", "Die Passphrase ist falsch"),
+    (
+        "PASSPHRASE_TOO_SHORT",
+        "Die Passphrase muss mindestens {min_chars} Zeichen lang sein",
+    ),
+    (
+        "NO_PASSPHRASE",
+        "Lege eine Passphrase fest, bevor du den Arbeitsbereich sperrst",
+    ),
+    (
+        "READ_ONLY",
+        "Der Arbeitsbereich ist schreibgeschützt geöffnet",
+    ),
+    (
+        "QUERY_TIMEOUT",
+        "Die Abfrage hat zu lange gedauert. Schränke die Filter ein und versuche es erneut.",
+    ),
+    (
+        "COLLECTION_NOT_FOUND",
+        "Sammlung {name} wurde nicht gefunden",
+    ),
+    (
+        "INVALID_COLLECTION_PATH",
+        "Dieser Sammlungspfad ist ungültig",
+    ),
+    (
+        "COLLECTION_CYCLE",
+        "Eine Sammlung kann nicht in eine ihrer eigenen Untersammlungen verschoben werden",
+    ),
+    (
+        "COLLECTION_DEPTH_EXCEEDED",
+        "Sammlungen können höchstens {max_depth} Ebenen tief verschachtelt werden",
+    ),
+    (
+        "NODE_SERVICE_ERROR",
+        "Etwas ist schiefgelaufen. Bitte versuche es erneut.",
+    ),
+];
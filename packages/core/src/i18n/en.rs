@@ -0,0 +1,117 @@
+//! English messages, the fallback for every other locale
+
+pub(super) const MESSAGES: &[(&str, &str)] = &[
+    ("NODE_NOT_FOUND", "Node {id} was not found"),
+    (
+        "VERSION_CONFLICT",
+        "This node was changed elsewhere (now version {actual_version}, expected {expected_version}). Reload it and try again.",
+    ),
+    (
+        "IDEMPOTENCY_KEY_CONFLICT",
+        "This request is still being handled, or its retry key was already used. Reload and try again.",
+    ),
+    ("INVALID_PARENT", "Node {parent_id} can't be used as the parent here"),
+    ("INVALID_ROOT", "Node {root_node_id} can't be used as the root here"),
+    ("CIRCULAR_REFERENCE", "A node can't be moved inside itself"),
+    ("HIERARCHY_VIOLATION", "The node can't be placed there"),
+    ("INVALID_INPUT", "The input couldn't be processed"),
+    ("INVALID_UPDATE", "The change couldn't be applied"),
+    ("FIELD_MISSING", "{field} is required"),
+    (
+        "WRONG_NODE_TYPE",
+        "Expected a {expected} node, but this is a {actual} node",
+    ),
+    ("INVALID_NODE_ID", "'{id}' is not a valid ID here (expected {format})"),
+    (
+        "SELF_MENTION",
+        "A node can't mention itself or the node it belongs to",
+    ),
+    ("PROPERTY_TYPE_MISMATCH", "{field} must be of type {expected}"),
+    ("MALFORMED_PROPERTY", "{field} couldn't be read"),
+    (
+        "NON_NEGATIVE_INTEGER_REQUIRED",
+        "{field} must be a whole number of zero or more",
+    ),
+    ("INVALID_PROPERTY_PATH", "'{path}' is not a valid property path"),
+    (
+        "INVALID_SCHEMA_FIELD_NAME",
+        "'{field}' is not a valid field name. Use only letters, digits and underscores.",
+    ),
+    ("ENUM_WITHOUT_VALUES", "{field} needs at least one allowed value"),
+    (
+        "SCHEMA_VERSION_NOT_POSITIVE",
+        "The schema version must be greater than zero",
+    ),
+    (
+        "DUPLICATE_SCHEMA_FIELDS",
+        "Each field of a schema needs a unique name",
+    ),
+    (
+        "COLLECTION_NAME_HAS_DELIMITER",
+        "Collection names can't contain ':'",
+    ),
+    ("HIGHLIGHT_RANGE_REVERSED", "A highlight can't end before it starts"),
+    ("INVALID_APPEARANCE_ICON", "'{value}' is not a single emoji"),
+    ("INVALID_APPEARANCE_COLOR", "'{value}' is not a #rgb or #rrggbb color"),
+    ("INVALID_APPEARANCE_COVER", "'{value}' is not an attached image"),
+    ("CHILD_VIEW_INCOMPLETE", "Views need an ID and a name"),
+    (
+        "RELATIONSHIP_FILTER_WITHOUT_NODE",
+        "The relationship filters of view {view} need a node",
+    ),
+    ("DUPLICATE_CHILD_VIEW", "There is already a view with ID {id}"),
+    ("ROLLUP_NAME_MISSING", "Rollups need a name"),
+    (
+        "ROLLUP_PROPERTY_MISSING",
+        "Rollup {name} needs a property to aggregate",
+    ),
+    ("DUPLICATE_ROLLUP", "There is already a rollup named {name}"),
+    ("REQUIRED_FIELD_MISSING", "{field} is required for {node_type} nodes"),
+    (
+        "INVALID_ENUM_VALUE",
+        "'{value}' is not a valid {field}. Choose one of: {allowed}",
+    ),
+    ("ENUM_NOT_STRING", "{field} must be one of its listed values"),
+    (
+        "EDGE_DATA_NOT_OBJECT",
+        "The fields of a {relationship} relationship must be an object",
+    ),
+    (
+        "UNKNOWN_EDGE_FIELD",
+        "{relationship} relationships have no field {field}",
+    ),
+    (
+        "REQUIRED_EDGE_FIELD_MISSING",
+        "{field} is required for {relationship} relationships",
+    ),
+    ("INVALID_EDGE_FIELD_TYPE", "{field} must be a {expected}"),
+    ("WORKSPACE_LOCKED", "The workspace is locked. Unlock it to continue."),
+    ("INCORRECT_PASSPHRASE", "The passphrase is incorrect"),
+    (
+        "PASSPHRASE_TOO_SHORT",
+        "The passphrase must be at least {min_chars} characters long",
+    ),
+    (
+        "NO_PASSPHRASE",
+        "Set a passphrase before locking the workspace",
+    ),
+    ("READ_ONLY", "The workspace is open read-only"),
+    (
+        "QUERY_TIMEOUT",
+        "The query took too long. Narrow its filters and try again.",
+    ),
+    ("COLLECTION_NOT_FOUND", "Collection {name} was not found"),
+    (
+        "INVALID_COLLECTION_PATH",
+        "This collection path isn't valid",
+    ),
+    (
+        "COLLECTION_CYCLE",
+        "A collection can't be placed inside one of its own sub-collections",
+    ),
+    (
+        "COLLECTION_DEPTH_EXCEEDED",
+        "Collections can be nested at most {max_depth} levels deep",
+    ),
+    ("NODE_SERVICE_ERROR", "Something went wrong. Please try again."),
+];
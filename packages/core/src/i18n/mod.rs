@@ -0,0 +1,171 @@
+//! User-Facing Messages
+//!
+//! Errors shown to users are identified by a stable code plus named
+//! parameters ([`UserMessage`]) instead of English prose. The code is what
+//! the UI and MCP clients match on; the text comes from a per-locale message
+//! catalog, so the frontend can either use [`UserMessage::render`] for the
+//! selected [`Locale`] or translate `code` + `params` itself.
+//!
+//! Templates reference parameters as `{name}`. Array parameters are joined
+//! with ", ". A code missing from a locale falls back to English, and a code
+//! missing from English renders as the code itself.
+
+mod de;
+mod en;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// Languages with a message catalog
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+#[repr(u8)]
+pub enum Locale {
+    #[default]
+    En,
+    De,
+}
+
+impl Locale {
+    /// Locale for a BCP 47 tag such as `de-AT`, falling back to English
+    pub fn from_tag(tag: &str) -> Self {
+        let language = tag.split(['-', '_']).next().unwrap_or_default();
+        match language.to_ascii_lowercase().as_str() {
+            "de" => Self::De,
+            _ => Self::En,
+        }
+    }
+
+    pub fn tag(self) -> &'static str {
+        match self {
+            Self::En => "en",
+            Self::De => "de",
+        }
+    }
+
+    fn catalog(self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Self::En => en::MESSAGES,
+            Self::De => de::MESSAGES,
+        }
+    }
+
+    fn template(self, code: &str) -> Option<&'static str> {
+        self.catalog()
+            .iter()
+            .find(|(key, _)| *key == code)
+            .map(|(_, template)| *template)
+    }
+}
+
+static CURRENT_LOCALE: AtomicU8 = AtomicU8::new(Locale::En as u8);
+
+/// Locale used to render messages for the desktop UI
+pub fn current_locale() -> Locale {
+    match CURRENT_LOCALE.load(Ordering::Relaxed) {
+        x if x == Locale::De as u8 => Locale::De,
+        _ => Locale::En,
+    }
+}
+
+pub fn set_locale(locale: Locale) {
+    CURRENT_LOCALE.store(locale as u8, Ordering::Relaxed);
+}
+
+/// A localizable message: catalog code plus the values it interpolates
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserMessage {
+    pub code: String,
+    pub params: Map<String, Value>,
+}
+
+impl UserMessage {
+    pub fn new(code: impl Into<String>) -> Self {
+        Self {
+            code: code.into(),
+            params: Map::new(),
+        }
+    }
+
+    pub fn with_param(mut self, name: &str, value: impl Into<Value>) -> Self {
+        self.params.insert(name.to_string(), value.into());
+        self
+    }
+
+    /// Text of this message in `locale`
+    pub fn render(&self, locale: Locale) -> String {
+        let Some(template) = locale
+            .template(&self.code)
+            .or_else(|| Locale::En.template(&self.code))
+        else {
+            return self.code.clone();
+        };
+
+        let mut rendered = template.to_string();
+        for (name, value) in &self.params {
+            rendered = rendered.replace(&format!("{{{}}}", name), &param_text(value));
+        }
+        rendered
+    }
+}
+
+fn param_text(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Array(items) => items.iter().map(param_text).collect::<Vec<_>>().join(", "),
+        other => other.to_string(),
+    }
+}
+
+/// Field name as users know it, without a namespace prefix (`custom:rating` → `rating`)
+pub fn display_field_name(name: &str) -> &str {
+    name.rsplit(':').next().unwrap_or(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_interpolates_params_per_locale() {
+        let message = UserMessage::new("INVALID_ENUM_VALUE")
+            .with_param("field", "status")
+            .with_param("value", "someday")
+            .with_param("allowed", vec!["Open", "Done"]);
+
+        assert_eq!(
+            message.render(Locale::En),
+            "'someday' is not a valid status. Choose one of: Open, Done"
+        );
+        assert!(message.render(Locale::De).contains("Open, Done"));
+        assert_ne!(message.render(Locale::De), message.render(Locale::En));
+        assert_eq!(
+            UserMessage::new("NO_SUCH_CODE").render(Locale::De),
+            "NO_SUCH_CODE"
+        );
+    }
+
+    #[test]
+    fn test_every_catalog_covers_the_english_codes() {
+        for locale in [Locale::De] {
+            for (code, _) in en::MESSAGES {
+                assert!(
+                    locale.template(code).is_some(),
+                    "{} is missing from the {} catalog",
+                    code,
+                    locale.tag()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_locale_from_tag() {
+        assert_eq!(Locale::from_tag("de-AT"), Locale::De);
+        assert_eq!(Locale::from_tag("en_US"), Locale::En);
+        assert_eq!(Locale::from_tag("fr"), Locale::En);
+        assert_eq!(display_field_name("custom:rating"), "rating");
+    }
+}
@@ -0,0 +1,430 @@
+//! BibTeX importer
+//!
+//! Each entry becomes a `reference` node whose content is the entry title,
+//! with `citation_key`, `entry_type`, `authors`, `year`, `doi`, `venue` and
+//! `url` properties. The venue is the first of `journal`, `booktitle`,
+//! `publisher`, `school`, `institution` and `howpublished` present. An
+//! abstract becomes a child text node.
+//!
+//! `@string` macros are expanded, `@comment` and `@preamble` blocks are
+//! skipped. Values keep their text but lose BibTeX braces and escapes
+//! (`{\&}` → `&`), so titles read naturally in the outline.
+
+use super::{ImportError, ImportedDocument, Importer, NodeTreeBuilder};
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+
+/// Imports `.bib` bibliographies
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BibtexImporter;
+
+/// Fields holding the venue, in order of preference
+const VENUE_FIELDS: &[&str] = &[
+    "journal",
+    "booktitle",
+    "publisher",
+    "school",
+    "institution",
+    "howpublished",
+];
+
+/// One parsed entry; field names are lowercase, values raw (braces kept)
+#[derive(Debug, Clone, PartialEq)]
+struct BibEntry {
+    entry_type: String,
+    key: String,
+    fields: Vec<(String, String)>,
+}
+
+impl BibEntry {
+    fn field(&self, name: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+    }
+
+    fn to_properties(&self) -> Value {
+        let mut props = Map::new();
+        props.insert("citation_key".to_string(), json!(self.key));
+        props.insert("entry_type".to_string(), json!(self.entry_type));
+        if let Some(authors) = self.field("author").or_else(|| self.field("editor")) {
+            props.insert("authors".to_string(), json!(split_authors(authors)));
+        }
+        if let Some(year) = self.field("year").map(clean) {
+            let value = year.parse::<u64>().map(Value::from).unwrap_or(json!(year));
+            props.insert("year".to_string(), value);
+        }
+        if let Some(venue) = VENUE_FIELDS.iter().find_map(|f| self.field(f)) {
+            props.insert("venue".to_string(), json!(clean(venue)));
+        }
+        for name in ["doi", "url"] {
+            if let Some(value) = self.field(name) {
+                props.insert(name.to_string(), json!(clean(value)));
+            }
+        }
+        Value::Object(props)
+    }
+}
+
+impl Importer for BibtexImporter {
+    fn name(&self) -> &'static str {
+        "BibTeX"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["bib"]
+    }
+
+    fn import(&self, file_stem: &str, content: &str) -> Result<Vec<ImportedDocument>, ImportError> {
+        let entries = parse_entries(content).map_err(|e| ImportError::parse(self.name(), e))?;
+
+        let mut builder = NodeTreeBuilder::default();
+        for entry in entries {
+            let title = entry
+                .field("title")
+                .map(clean)
+                .filter(|t| !t.is_empty())
+                .unwrap_or_else(|| entry.key.clone());
+            let id = builder.push(None, "reference", title, entry.to_properties());
+            if let Some(abstract_text) = entry.field("abstract").map(clean) {
+                if !abstract_text.is_empty() {
+                    builder.push(Some(&id), "text", abstract_text, json!({}));
+                }
+            }
+        }
+
+        Ok(vec![ImportedDocument::new(file_stem, builder.into_nodes())])
+    }
+}
+
+/// Parse all `@type{key, field = value, ...}` entries
+fn parse_entries(content: &str) -> Result<Vec<BibEntry>, String> {
+    let mut parser = Parser {
+        chars: content.chars().collect(),
+        pos: 0,
+        strings: HashMap::new(),
+    };
+    let mut entries = Vec::new();
+
+    while parser.skip_to('@') {
+        let entry_type = parser.identifier().to_lowercase();
+        parser.skip_whitespace();
+        let close = match parser.next() {
+            Some('{') => '}',
+            Some('(') => ')',
+            _ => continue, // A stray '@' in free text between entries
+        };
+
+        match entry_type.as_str() {
+            "comment" | "preamble" => parser.skip_block(close)?,
+            "string" => {
+                for (name, value) in parser.fields(close)? {
+                    parser.strings.insert(name, value);
+                }
+            }
+            _ => {
+                let key = parser.take_until(&[',', close]).trim().to_string();
+                let fields = if parser.peek() == Some(',') {
+                    parser.fields(close)?
+                } else {
+                    parser.expect(close)?;
+                    Vec::new()
+                };
+                entries.push(BibEntry {
+                    entry_type,
+                    key,
+                    fields,
+                });
+            }
+        }
+    }
+    Ok(entries)
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+    /// `@string` macros by lowercase name
+    strings: HashMap<String, String>,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek();
+        if c.is_some() {
+            self.pos += 1;
+        }
+        c
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), String> {
+        self.skip_whitespace();
+        match self.next() {
+            Some(c) if c == expected => Ok(()),
+            Some(c) => Err(format!("expected '{}', found '{}'", expected, c)),
+            None => Err(format!("expected '{}', found end of file", expected)),
+        }
+    }
+
+    /// Move past the next `c`; false when there is none
+    fn skip_to(&mut self, c: char) -> bool {
+        while let Some(next) = self.next() {
+            if next == c {
+                return true;
+            }
+        }
+        false
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.pos += 1;
+        }
+    }
+
+    fn identifier(&mut self) -> String {
+        let start = self.pos;
+        while self
+            .peek()
+            .is_some_and(|c| c.is_alphanumeric() || "_-:.+/".contains(c))
+        {
+            self.pos += 1;
+        }
+        self.chars[start..self.pos].iter().collect()
+    }
+
+    /// Text up to (not including) the first of `stops`
+    fn take_until(&mut self, stops: &[char]) -> String {
+        let start = self.pos;
+        while self.peek().is_some_and(|c| !stops.contains(&c)) {
+            self.pos += 1;
+        }
+        self.chars[start..self.pos].iter().collect()
+    }
+
+    /// Skip to the `close` matching an already consumed opener
+    fn skip_block(&mut self, close: char) -> Result<(), String> {
+        let open = if close == '}' { '{' } else { '(' };
+        let mut depth = 1;
+        while let Some(c) = self.next() {
+            if c == open {
+                depth += 1;
+            } else if c == close {
+                depth -= 1;
+                if depth == 0 {
+                    return Ok(());
+                }
+            }
+        }
+        Err("unterminated block".to_string())
+    }
+
+    /// `name = value` pairs up to and including `close`
+    fn fields(&mut self, close: char) -> Result<Vec<(String, String)>, String> {
+        let mut fields = Vec::new();
+        loop {
+            self.skip_whitespace();
+            match self.peek() {
+                Some(',') => self.pos += 1,
+                Some(c) if c == close => {
+                    self.pos += 1;
+                    return Ok(fields);
+                }
+                Some(_) => {
+                    let name = self.take_until(&['=', ',', close]).trim().to_lowercase();
+                    if name.is_empty() {
+                        return Err("field without a name".to_string());
+                    }
+                    self.expect('=')?;
+                    fields.push((name, self.value()?));
+                }
+                None => return Err("unterminated entry".to_string()),
+            }
+        }
+    }
+
+    /// A field value: `{...}`, `"..."`, a number or macro, joined by `#`
+    fn value(&mut self) -> Result<String, String> {
+        let mut value = String::new();
+        loop {
+            self.skip_whitespace();
+            match self.peek() {
+                Some('{') => {
+                    self.pos += 1;
+                    let start = self.pos;
+                    self.skip_block('}')?;
+                    value.extend(&self.chars[start..self.pos - 1]);
+                }
+                Some('"') => {
+                    self.pos += 1;
+                    let mut depth = 0;
+                    loop {
+                        match self.next() {
+                            Some('"') if depth == 0 => break,
+                            Some(c) => {
+                                match c {
+                                    '{' => depth += 1,
+                                    '}' => depth -= 1,
+                                    _ => {}
+                                }
+                                value.push(c);
+                            }
+                            None => return Err("unterminated quoted value".to_string()),
+                        }
+                    }
+                }
+                Some(_) => {
+                    let word = self.identifier();
+                    if word.is_empty() {
+                        return Err("missing field value".to_string());
+                    }
+                    match self.strings.get(&word.to_lowercase()) {
+                        Some(expanded) => value.push_str(expanded),
+                        None => value.push_str(&word),
+                    }
+                }
+                None => return Err("unterminated entry".to_string()),
+            }
+            self.skip_whitespace();
+            if self.peek() != Some('#') {
+                return Ok(value);
+            }
+            self.pos += 1;
+        }
+    }
+}
+
+/// Split an `author` value on top-level ` and `
+///
+/// Braced groups stay whole, so `{Barnes and Noble}` is one author.
+fn split_authors(raw: &str) -> Vec<String> {
+    let mut authors = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0;
+    for word in raw.split_whitespace() {
+        if depth == 0 && word.eq_ignore_ascii_case("and") {
+            authors.push(clean(&current));
+            current.clear();
+            continue;
+        }
+        depth += word.matches('{').count() as i32 - word.matches('}').count() as i32;
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(word);
+    }
+    authors.push(clean(&current));
+    authors.retain(|a| !a.is_empty());
+    authors
+}
+
+/// Strip BibTeX braces and escapes and collapse whitespace
+fn clean(raw: &str) -> String {
+    let mut out = String::with_capacity(raw.len());
+    let mut chars = raw.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' | '}' => {}
+            '\\' if chars.peek().is_some_and(|n| "&%$#_{}".contains(*n)) => {
+                out.extend(chars.next());
+            }
+            '~' => out.push(' '),
+            _ => out.push(c),
+        }
+    }
+    out.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = r#"
+@string{acm = "Communications of the {ACM}"}
+
+@comment{Exported from a reference manager}
+
+@article{knuth1974,
+  author  = {Donald E. Knuth},
+  title   = {Computer Programming as an {Art}},
+  journal = acm,
+  year    = 1974,
+  volume  = {17},
+  doi     = {10.1145/361604.361612},
+  abstract = {The art of programming.
+              Second line.}
+}
+
+@InProceedings{lamport78,
+  author = "Leslie Lamport and {Barnes and Noble} and Liskov, Barbara",
+  title = "Time, Clocks, and the Ordering of Events in a Distributed System",
+  booktitle = "Proc. " # "SOSP",
+  year = "1978",
+}
+"#;
+
+    #[test]
+    fn test_entries_become_reference_nodes() {
+        let docs = BibtexImporter.import("papers", SAMPLE).unwrap();
+        let doc = &docs[0];
+        assert_eq!(doc.title, "papers");
+
+        let refs: Vec<_> = doc.nodes.iter().filter(|n| n.parent_id.is_none()).collect();
+        assert_eq!(refs.len(), 2);
+        assert_eq!(refs[0].node_type, "reference");
+        assert_eq!(refs[0].content, "Computer Programming as an Art");
+        assert_eq!(
+            refs[0].properties,
+            json!({
+                "citation_key": "knuth1974",
+                "entry_type": "article",
+                "authors": ["Donald E. Knuth"],
+                "year": 1974,
+                "venue": "Communications of the ACM",
+                "doi": "10.1145/361604.361612"
+            })
+        );
+
+        let abstract_node = doc
+            .nodes
+            .iter()
+            .find(|n| n.parent_id.as_deref() == Some(refs[0].id.as_str()))
+            .unwrap();
+        assert_eq!(
+            abstract_node.content,
+            "The art of programming. Second line."
+        );
+
+        assert_eq!(refs[1].properties["entry_type"], "inproceedings");
+        assert_eq!(
+            refs[1].properties["authors"],
+            json!(["Leslie Lamport", "Barnes and Noble", "Liskov, Barbara"])
+        );
+        assert_eq!(refs[1].properties["venue"], "Proc. SOSP");
+        assert_eq!(refs[1].properties["year"], 1978);
+    }
+
+    #[test]
+    fn test_clean_strips_braces_and_escapes() {
+        assert_eq!(clean(r"{R\&D} at 50\%"), "R&D at 50%");
+        assert_eq!(clean("A~B  {C}\n D"), "A B C D");
+    }
+
+    #[test]
+    fn test_rejects_unterminated_entry() {
+        assert!(BibtexImporter
+            .import("x", "@article{key, title = {Open")
+            .is_err());
+        assert!(BibtexImporter
+            .import("x", "@book{key, title = {Done}")
+            .is_err());
+        assert!(BibtexImporter.import("x", "no entries").unwrap()[0]
+            .nodes
+            .is_empty());
+    }
+}
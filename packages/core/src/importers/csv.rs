@@ -0,0 +1,529 @@
+//! CSV importer
+//!
+//! Two entry points:
+//!
+//! - [`CsvImporter`] (registry, file drop): one text node per row, content from
+//!   the first column, remaining columns as snake_cased properties.
+//! - [`import_csv`] (explicit mapping): rows become typed nodes. A
+//!   [`CsvMapping`] maps columns onto schema fields; every cell is coerced to
+//!   the field type and checked against the schema, invalid rows are reported
+//!   per row, and valid rows are created in batches of [`CSV_BATCH_SIZE`].
+
+use super::{ImportError, ImportedDocument, Importer, NodeTreeBuilder};
+use crate::db::FractionalIndex;
+use crate::models::SchemaField;
+use crate::services::{NodeService, NodeServiceError};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+/// Rows inserted per `bulk_create_hierarchy` call
+pub const CSV_BATCH_SIZE: usize = 500;
+
+/// Imports `.csv` files without a mapping
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CsvImporter;
+
+impl Importer for CsvImporter {
+    fn name(&self) -> &'static str {
+        "CSV"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["csv"]
+    }
+
+    fn import(&self, file_stem: &str, content: &str) -> Result<Vec<ImportedDocument>, ImportError> {
+        let mut rows = parse_csv(content)?.into_iter();
+        let header: Vec<String> = rows
+            .next()
+            .unwrap_or_default()
+            .iter()
+            .map(|h| property_key(h))
+            .collect();
+
+        let mut builder = NodeTreeBuilder::default();
+        for row in rows {
+            let Some(first) = row.first().filter(|c| !c.trim().is_empty()) else {
+                continue;
+            };
+            let mut props = Map::new();
+            for (key, cell) in header.iter().zip(&row).skip(1) {
+                if !key.is_empty() && !cell.trim().is_empty() {
+                    props.insert(key.clone(), Value::String(cell.trim().to_string()));
+                }
+            }
+            builder.push(None, "text", first.trim(), Value::Object(props));
+        }
+
+        Ok(vec![ImportedDocument::new(file_stem, builder.into_nodes())])
+    }
+}
+
+/// Column → schema-field mapping for typed CSV import
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CsvMapping {
+    /// Node type to create (must have a schema)
+    pub node_type: String,
+    /// Column whose value becomes the node content
+    pub content_column: String,
+    /// Column header → schema field name
+    #[serde(default)]
+    pub fields: HashMap<String, String>,
+    /// Parent for created nodes (None = each row becomes a root)
+    #[serde(default)]
+    pub parent_id: Option<String>,
+}
+
+/// A row that failed validation or insertion
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CsvRowError {
+    /// Spreadsheet row number (header is row 1)
+    pub row: usize,
+    /// Offending column, if the error is cell-specific
+    pub column: Option<String>,
+    pub message: String,
+}
+
+/// Outcome of a typed CSV import
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CsvImportResult {
+    pub total_rows: usize,
+    /// IDs of created nodes, in row order
+    pub created_ids: Vec<String>,
+    pub errors: Vec<CsvRowError>,
+}
+
+/// A row that passed validation
+#[derive(Debug, Clone, PartialEq)]
+struct ValidRow {
+    row: usize,
+    content: String,
+    properties: Value,
+}
+
+impl CsvMapping {
+    /// Validate rows against schema fields without touching the database
+    ///
+    /// Returns an error only for mapping problems (unknown column or field);
+    /// row-level problems are collected as [`CsvRowError`]s.
+    fn validate_rows(
+        &self,
+        rows: &[Vec<String>],
+        fields: &[SchemaField],
+    ) -> Result<(Vec<ValidRow>, Vec<CsvRowError>), ImportError> {
+        let Some((header, data)) = rows.split_first() else {
+            return Ok((Vec::new(), Vec::new()));
+        };
+        let column_index = |name: &str| {
+            header
+                .iter()
+                .position(|h| h.trim() == name)
+                .ok_or_else(|| ImportError::InvalidMapping(format!("Column '{}' not found", name)))
+        };
+
+        let content_idx = column_index(&self.content_column)?;
+        let mut mapped: Vec<(usize, &str, &SchemaField)> = Vec::new();
+        for (column, field_name) in &self.fields {
+            let field = fields
+                .iter()
+                .find(|f| &f.name == field_name)
+                .ok_or_else(|| {
+                    ImportError::InvalidMapping(format!(
+                        "Field '{}' is not defined on the '{}' schema",
+                        field_name, self.node_type
+                    ))
+                })?;
+            mapped.push((column_index(column)?, column.as_str(), field));
+        }
+        mapped.sort_by_key(|(idx, _, _)| *idx);
+
+        let mut valid = Vec::new();
+        let mut errors = Vec::new();
+        for (i, cells) in data.iter().enumerate() {
+            let row = i + 2;
+            if cells.iter().all(|c| c.trim().is_empty()) {
+                continue;
+            }
+            let cell = |idx: usize| cells.get(idx).map(|c| c.trim()).unwrap_or("");
+
+            let content = cell(content_idx);
+            if content.is_empty() {
+                errors.push(CsvRowError {
+                    row,
+                    column: Some(self.content_column.clone()),
+                    message: "Content column is empty".to_string(),
+                });
+                continue;
+            }
+
+            let mut props = Map::new();
+            let mut row_errors = Vec::new();
+            for (idx, column, field) in &mapped {
+                let raw = cell(*idx);
+                if raw.is_empty() {
+                    continue;
+                }
+                match coerce_cell(field, raw) {
+                    Ok(value) => {
+                        props.insert(field.name.clone(), value);
+                    }
+                    Err(message) => row_errors.push(CsvRowError {
+                        row,
+                        column: Some(column.to_string()),
+                        message,
+                    }),
+                }
+            }
+
+            for field in fields {
+                if field.required.unwrap_or(false)
+                    && field.default.is_none()
+                    && !props.contains_key(&field.name)
+                {
+                    row_errors.push(CsvRowError {
+                        row,
+                        column: None,
+                        message: format!("Required field '{}' is missing", field.name),
+                    });
+                }
+            }
+
+            if row_errors.is_empty() {
+                valid.push(ValidRow {
+                    row,
+                    content: content.to_string(),
+                    properties: Value::Object(props),
+                });
+            } else {
+                errors.extend(row_errors);
+            }
+        }
+
+        Ok((valid, errors))
+    }
+}
+
+/// Import CSV content as typed nodes using a column mapping
+///
+/// # Errors
+///
+/// - `InvalidInput`: unparseable CSV, a mapping referencing unknown columns or
+///   fields, or a node type without a schema
+/// - `QueryFailed`: schema lookup or reading the parent's last child order failed
+///
+/// Row validation and insertion failures do not abort the import; they are
+/// returned in [`CsvImportResult::errors`].
+pub async fn import_csv<C>(
+    node_service: &NodeService<C>,
+    content: &str,
+    mapping: &CsvMapping,
+) -> Result<CsvImportResult, NodeServiceError>
+where
+    C: surrealdb::Connection,
+{
+    let schema = node_service
+        .get_schema_for_type(&mapping.node_type)
+        .await?
+        .ok_or_else(|| {
+            NodeServiceError::invalid_input(format!(
+                "No schema found for node type '{}'",
+                mapping.node_type
+            ))
+        })?;
+    let fields: Vec<SchemaField> = schema
+        .get("fields")
+        .and_then(|f| serde_json::from_value(f.clone()).ok())
+        .unwrap_or_default();
+
+    let invalid_input = |e: ImportError| NodeServiceError::invalid_input(e.to_string());
+    let rows = parse_csv(content).map_err(invalid_input)?;
+    let (valid, mut errors) = mapping
+        .validate_rows(&rows, &fields)
+        .map_err(invalid_input)?;
+
+    let mut result = CsvImportResult {
+        total_rows: rows.len().saturating_sub(1),
+        ..Default::default()
+    };
+
+    // Rows are appended after the parent's existing children, each key
+    // following the previous row's
+    let mut last_order: Option<String> = None;
+    for batch in valid.chunks(CSV_BATCH_SIZE) {
+        let mut nodes = Vec::with_capacity(batch.len());
+        for r in batch {
+            let id = uuid::Uuid::new_v4().to_string();
+            let order = match (&last_order, &mapping.parent_id) {
+                (Some(prev), _) => FractionalIndex::key_for_node(Some(prev), None, &id)
+                    .map_err(NodeServiceError::query_failed)?,
+                (None, Some(parent_id)) => node_service
+                    .store()
+                    .get_next_child_order(parent_id, &id)
+                    .await
+                    .map_err(|e| NodeServiceError::query_failed(e.to_string()))?,
+                (None, None) => FractionalIndex::nth(0),
+            };
+            last_order = Some(order.clone());
+            nodes.push((
+                id,
+                mapping.node_type.clone(),
+                r.content.clone(),
+                mapping.parent_id.clone(),
+                order,
+                r.properties.clone(),
+            ));
+        }
+
+        match node_service.bulk_create_hierarchy(nodes).await {
+            Ok(ids) => result.created_ids.extend(ids),
+            Err(e) => errors.extend(batch.iter().map(|r| CsvRowError {
+                row: r.row,
+                column: None,
+                message: format!("Failed to create node: {}", e),
+            })),
+        }
+    }
+
+    errors.sort_by_key(|e| e.row);
+    result.errors = errors;
+    Ok(result)
+}
+
+/// Parse RFC 4180 CSV (quoted fields, `""` escapes, embedded newlines)
+pub fn parse_csv(content: &str) -> Result<Vec<Vec<String>>, ImportError> {
+    let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = content.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match (c, in_quotes) {
+            ('"', true) if chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            ('"', true) => in_quotes = false,
+            ('"', false) if field.is_empty() => in_quotes = true,
+            (',', false) => row.push(std::mem::take(&mut field)),
+            ('\r', false) if chars.peek() == Some(&'\n') => {}
+            ('\n', false) => {
+                row.push(std::mem::take(&mut field));
+                rows.push(std::mem::take(&mut row));
+            }
+            _ => field.push(c),
+        }
+    }
+
+    if in_quotes {
+        return Err(ImportError::parse("CSV", "unterminated quoted field"));
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+    Ok(rows)
+}
+
+/// Coerce a cell to the JSON type required by a schema field
+fn coerce_cell(field: &SchemaField, raw: &str) -> Result<Value, String> {
+    match field.field_type.as_str() {
+        "number" => {
+            if let Ok(i) = raw.parse::<i64>() {
+                Ok(Value::from(i))
+            } else {
+                raw.parse::<f64>()
+                    .ok()
+                    .filter(|f| f.is_finite())
+                    .map(Value::from)
+                    .ok_or_else(|| format!("'{}' is not a number", raw))
+            }
+        }
+        "boolean" => match raw.to_lowercase().as_str() {
+            "true" | "yes" | "y" | "1" | "x" => Ok(Value::Bool(true)),
+            "false" | "no" | "n" | "0" => Ok(Value::Bool(false)),
+            _ => Err(format!("'{}' is not a boolean", raw)),
+        },
+        "enum" => field
+            .core_values
+            .iter()
+            .chain(&field.user_values)
+            .flatten()
+            .find(|ev| ev.value.eq_ignore_ascii_case(raw) || ev.label.eq_ignore_ascii_case(raw))
+            .map(|ev| Value::String(ev.value.clone()))
+            .ok_or_else(|| format!("'{}' is not a valid {} value", raw, field.name)),
+        "date" => {
+            if chrono::NaiveDate::parse_from_str(raw, "%Y-%m-%d").is_ok()
+                || chrono::DateTime::parse_from_rfc3339(raw).is_ok()
+            {
+                Ok(Value::String(raw.to_string()))
+            } else {
+                Err(format!("'{}' is not a date (expected YYYY-MM-DD)", raw))
+            }
+        }
+        "array" => Ok(Value::Array(
+            raw.split([';', ','])
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(|s| Value::String(s.to_string()))
+                .collect(),
+        )),
+        _ => Ok(Value::String(raw.to_string())),
+    }
+}
+
+/// Convert a column header to a property key (`Due Date` → `due_date`)
+fn property_key(header: &str) -> String {
+    header
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(|w| w.to_lowercase())
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::core_schemas::get_core_schemas;
+
+    fn task_fields() -> Vec<SchemaField> {
+        get_core_schemas()
+            .into_iter()
+            .find(|s| s.id == "task")
+            .unwrap()
+            .fields
+    }
+
+    fn task_mapping() -> CsvMapping {
+        CsvMapping {
+            node_type: "task".to_string(),
+            content_column: "Title".to_string(),
+            fields: HashMap::from([
+                ("State".to_string(), "status".to_string()),
+                ("Due".to_string(), "due_date".to_string()),
+            ]),
+            parent_id: None,
+        }
+    }
+
+    #[test]
+    fn test_parse_csv_quotes_and_newlines() {
+        let rows = parse_csv("\u{feff}a,b\r\n\"x, y\",\"say \"\"hi\"\"\nthere\"\n1,").unwrap();
+        assert_eq!(
+            rows,
+            vec![
+                vec!["a", "b"],
+                vec!["x, y", "say \"hi\"\nthere"],
+                vec!["1", ""],
+            ]
+        );
+        assert!(parse_csv("\"open").is_err());
+    }
+
+    #[test]
+    fn test_validate_rows_coerces_and_reports_errors() {
+        let rows = parse_csv(
+            "Title,State,Due\n\
+             Write spec,In Progress,2025-03-01\n\
+             Review,blocked,2025-03-02\n\
+             ,open,\n\
+             Ship,done,next week\n",
+        )
+        .unwrap();
+
+        let (valid, errors) = task_mapping().validate_rows(&rows, &task_fields()).unwrap();
+
+        assert_eq!(valid.len(), 1);
+        assert_eq!(valid[0].row, 2);
+        assert_eq!(valid[0].content, "Write spec");
+        assert_eq!(valid[0].properties["status"], "in_progress");
+        assert_eq!(valid[0].properties["due_date"], "2025-03-01");
+
+        let rows_with_errors: Vec<_> = errors.iter().map(|e| e.row).collect();
+        assert_eq!(rows_with_errors, vec![3, 4, 5]);
+        assert_eq!(errors[0].column.as_deref(), Some("State"));
+        assert_eq!(errors[2].column.as_deref(), Some("Due"));
+    }
+
+    #[tokio::test]
+    async fn test_import_csv_appends_after_existing_children() {
+        use crate::db::SurrealStore;
+        use crate::models::Node;
+        use std::sync::Arc;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut store = Arc::new(
+            SurrealStore::new(temp_dir.path().join("test.db"))
+                .await
+                .unwrap(),
+        );
+        let service = NodeService::new(&mut store).await.unwrap();
+        let parent = Node::new(
+            "text".to_string(),
+            "Tasks".to_string(),
+            serde_json::json!({}),
+        );
+        let parent_id = service.create_node(parent).await.unwrap();
+
+        let mapping = CsvMapping {
+            fields: HashMap::new(),
+            parent_id: Some(parent_id.clone()),
+            ..task_mapping()
+        };
+        for content in ["Title\nFirst\nSecond\n", "Title\nThird\nFourth\n"] {
+            let result = import_csv(&service, content, &mapping).await.unwrap();
+            assert!(result.errors.is_empty(), "{:?}", result.errors);
+        }
+
+        let children = service.get_children(&parent_id).await.unwrap();
+        let contents: Vec<&str> = children.iter().map(|c| c.content.as_str()).collect();
+        assert_eq!(contents, vec!["First", "Second", "Third", "Fourth"]);
+    }
+
+    #[test]
+    fn test_invalid_mapping_is_rejected() {
+        let rows = parse_csv("Title,State\nA,open\n").unwrap();
+        let fields = task_fields();
+
+        let mut mapping = task_mapping();
+        assert!(matches!(
+            mapping.validate_rows(&rows, &fields),
+            Err(ImportError::InvalidMapping(_))
+        ));
+
+        mapping.fields = HashMap::from([("State".to_string(), "colour".to_string())]);
+        assert!(matches!(
+            mapping.validate_rows(&rows, &fields),
+            Err(ImportError::InvalidMapping(_))
+        ));
+    }
+
+    #[test]
+    fn test_coerce_number_and_boolean() {
+        let mut field = task_fields().remove(0);
+        field.field_type = "number".to_string();
+        assert_eq!(coerce_cell(&field, "3").unwrap(), Value::from(3));
+        assert_eq!(coerce_cell(&field, "2.5").unwrap(), Value::from(2.5));
+        assert!(coerce_cell(&field, "abc").is_err());
+
+        field.field_type = "boolean".to_string();
+        assert_eq!(coerce_cell(&field, "Yes").unwrap(), Value::Bool(true));
+        assert!(coerce_cell(&field, "maybe").is_err());
+    }
+
+    #[test]
+    fn test_registry_import_uses_first_column_as_content() {
+        let docs = CsvImporter
+            .import("contacts", "Name,E-mail Address\nAda,ada@example.com\n")
+            .unwrap();
+        assert_eq!(docs[0].title, "contacts");
+        assert_eq!(docs[0].nodes[0].content, "Ada");
+        assert_eq!(
+            docs[0].nodes[0].properties["e_mail_address"],
+            "ada@example.com"
+        );
+    }
+}
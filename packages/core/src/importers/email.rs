@@ -0,0 +1,707 @@
+//! Email importer (`.eml` / `.mbox`)
+//!
+//! Each message becomes a document: the subject is the root, the body (plain
+//! text preferred, HTML converted to markdown otherwise) becomes its children,
+//! and MIME attachments are returned for the attachment store and listed at
+//! the end of the body. Inline images referenced as `cid:` are rewritten to
+//! their `attachment://` URIs.
+//!
+//! Sender and recipients are stored on the root as `email.from`, `email.to`
+//! and `email.cc`, holding IDs of `person` nodes. The importer can't see the
+//! database, so it assigns placeholder IDs and lists the people in
+//! [`ImportedDocument::people`]; [`resolve_people`] maps them onto existing
+//! person nodes (matched by email address) or creates new ones.
+//!
+//! Files starting with a `From ` line are read as mbox (one document per
+//! message), everything else as a single RFC 5322 message.
+
+use super::{ImportError, ImportedAttachment, ImportedDocument, ImportedPerson, Importer};
+use crate::mcp::handlers::markdown::prepare_nodes_from_markdown;
+use crate::models::NodeFilter;
+use crate::services::attachment_store::{attachment_hash, attachment_uri};
+use crate::services::{NodeService, NodeServiceError};
+use crate::utils::html_to_markdown;
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use regex::Regex;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// Node type used for senders and recipients
+pub const PERSON_NODE_TYPE: &str = "person";
+
+/// Imports `.eml` messages and `.mbox` archives
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EmailImporter;
+
+/// One MIME entity: unfolded headers plus the raw (still encoded) body
+struct MimePart<'a> {
+    headers: Vec<(String, String)>,
+    body: &'a str,
+}
+
+impl<'a> MimePart<'a> {
+    fn parse(raw: &'a str) -> Self {
+        let (head, body) = match raw.find("\n\n") {
+            Some(i) => (&raw[..i], &raw[i + 2..]),
+            None if raw.starts_with('\n') => ("", &raw[1..]),
+            None => (raw, ""),
+        };
+
+        let mut headers: Vec<(String, String)> = Vec::new();
+        for line in head.lines() {
+            if line.starts_with([' ', '\t']) {
+                if let Some((_, value)) = headers.last_mut() {
+                    value.push(' ');
+                    value.push_str(line.trim());
+                }
+            } else if let Some((name, value)) = line.split_once(':') {
+                headers.push((name.trim().to_lowercase(), value.trim().to_string()));
+            }
+        }
+        Self { headers, body }
+    }
+
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Lowercase MIME type and its parameters (`text/plain` by default)
+    fn content_type(&self) -> (String, HashMap<String, String>) {
+        match self.header("content-type") {
+            Some(value) => parse_header_params(value),
+            None => ("text/plain".to_string(), HashMap::new()),
+        }
+    }
+
+    /// Body with the transfer encoding removed
+    fn decoded_body(&self) -> Vec<u8> {
+        let encoding = self
+            .header("content-transfer-encoding")
+            .unwrap_or("")
+            .trim()
+            .to_lowercase();
+        match encoding.as_str() {
+            "base64" => {
+                let compact: String = self.body.split_whitespace().collect();
+                base64::engine::general_purpose::STANDARD
+                    .decode(compact)
+                    .unwrap_or_default()
+            }
+            "quoted-printable" => decode_quoted_printable(self.body),
+            _ => self.body.as_bytes().to_vec(),
+        }
+    }
+}
+
+/// Bodies and attachments collected from a MIME tree
+#[derive(Default)]
+struct MessageContent {
+    plain: Option<String>,
+    html: Option<String>,
+    attachments: Vec<ImportedAttachment>,
+    /// Content-ID → attachment URI, for `cid:` references in HTML
+    content_ids: HashMap<String, String>,
+}
+
+impl Importer for EmailImporter {
+    fn name(&self) -> &'static str {
+        "Email"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["eml", "mbox"]
+    }
+
+    fn import(&self, file_stem: &str, content: &str) -> Result<Vec<ImportedDocument>, ImportError> {
+        let content = content.replace("\r\n", "\n");
+        let messages = if content.starts_with("From ") {
+            split_mbox(&content)
+        } else {
+            vec![content]
+        };
+
+        // Placeholder person IDs are shared by all messages of the file
+        let mut people: HashMap<String, ImportedPerson> = HashMap::new();
+        let mut docs = Vec::with_capacity(messages.len());
+        for message in &messages {
+            docs.push(self.convert(file_stem, message, &mut people)?);
+        }
+        if docs.is_empty() {
+            return Err(ImportError::parse(self.name(), "no messages found"));
+        }
+        Ok(docs)
+    }
+}
+
+impl EmailImporter {
+    fn convert(
+        &self,
+        file_stem: &str,
+        raw: &str,
+        people: &mut HashMap<String, ImportedPerson>,
+    ) -> Result<ImportedDocument, ImportError> {
+        let message = MimePart::parse(raw);
+        if message.headers.is_empty() {
+            return Err(ImportError::parse(self.name(), "message has no headers"));
+        }
+
+        let mut content = MessageContent::default();
+        collect_parts(&message, &mut content);
+
+        let markdown = match (&content.plain, &content.html) {
+            (Some(plain), _) => reflow_plain_text(plain),
+            (None, Some(html)) => {
+                let mut html = html.clone();
+                for (cid, uri) in &content.content_ids {
+                    html = html.replace(&format!("cid:{}", cid), uri);
+                }
+                html_to_markdown(&html)
+            }
+            (None, None) => String::new(),
+        };
+        let mut markdown = markdown.trim().to_string();
+        if !content.attachments.is_empty() {
+            markdown.push_str("\n\n## Attachments\n");
+            for attachment in &content.attachments {
+                let uri = attachment_uri(&attachment_hash(&attachment.data));
+                let bang = if attachment.mime.starts_with("image/") {
+                    "!"
+                } else {
+                    ""
+                };
+                markdown.push_str(&format!("\n- {}[{}]({})", bang, attachment.file_name, uri));
+            }
+        }
+        let nodes = prepare_nodes_from_markdown(&markdown, None)
+            .map_err(|e| ImportError::parse(self.name(), e.message))?;
+
+        let title = message
+            .header("subject")
+            .map(decode_encoded_words)
+            .filter(|s| !s.trim().is_empty())
+            .unwrap_or_else(|| file_stem.to_string());
+        let mut doc = ImportedDocument::new(title.trim(), nodes);
+
+        let mut email = serde_json::Map::new();
+        let mut doc_people: Vec<ImportedPerson> = Vec::new();
+        for (header, many) in [("from", false), ("to", true), ("cc", true)] {
+            let ids: Vec<String> = message
+                .header(header)
+                .map(|v| parse_addresses(&decode_encoded_words(v)))
+                .unwrap_or_default()
+                .into_iter()
+                .map(|(name, address)| {
+                    let person = people
+                        .entry(address.to_lowercase())
+                        .or_insert_with(|| ImportedPerson {
+                            id: uuid::Uuid::new_v4().to_string(),
+                            name,
+                            email: address,
+                        })
+                        .clone();
+                    let id = person.id.clone();
+                    if !doc_people.iter().any(|p| p.id == id) {
+                        doc_people.push(person);
+                    }
+                    id
+                })
+                .collect();
+            match (many, ids.first()) {
+                (false, Some(id)) => {
+                    email.insert(header.to_string(), json!(id));
+                }
+                (true, Some(_)) => {
+                    email.insert(header.to_string(), json!(ids));
+                }
+                (_, None) => {}
+            }
+        }
+        if let Some(id) = message.header("message-id") {
+            let id = id.trim().trim_start_matches('<').trim_end_matches('>');
+            email.insert("message_id".to_string(), json!(id));
+        }
+        let sent = message
+            .header("date")
+            .and_then(|d| DateTime::parse_from_rfc2822(d.trim()).ok())
+            .map(|d| d.with_timezone(&Utc));
+        if let Some(sent) = sent {
+            email.insert("sent_at".to_string(), json!(sent.to_rfc3339()));
+            doc.timestamps.push((doc.root_id.clone(), sent, sent));
+        }
+
+        doc.root_properties = json!({ "email": Value::Object(email) });
+        doc.people = doc_people;
+        doc.attachments = content.attachments;
+        Ok(doc)
+    }
+}
+
+/// Map placeholder person IDs onto `person` nodes
+///
+/// People are matched to existing person nodes by email address
+/// (case-insensitive, read from `email` or `person.email`); the rest are
+/// created as root nodes. Returns placeholder ID → node ID for
+/// [`ImportedDocument::remap_ids`].
+pub async fn resolve_people<C>(
+    node_service: &NodeService<C>,
+    people: &[ImportedPerson],
+) -> Result<HashMap<String, String>, NodeServiceError>
+where
+    C: surrealdb::Connection,
+{
+    let mut ids = HashMap::new();
+    if people.is_empty() {
+        return Ok(ids);
+    }
+
+    let existing = node_service
+        .query_nodes(NodeFilter::new().with_node_type(PERSON_NODE_TYPE.to_string()))
+        .await?;
+    let mut by_email: HashMap<String, String> = existing
+        .into_iter()
+        .filter_map(|node| {
+            let props = node
+                .properties
+                .get(PERSON_NODE_TYPE)
+                .unwrap_or(&node.properties);
+            let email = props.get("email")?.as_str()?.to_lowercase();
+            Some((email, node.id))
+        })
+        .collect();
+
+    let mut rows = Vec::new();
+    for person in people {
+        if ids.contains_key(&person.id) {
+            continue;
+        }
+        let key = person.email.to_lowercase();
+        let id = match by_email.get(&key) {
+            Some(id) => id.clone(),
+            None => {
+                let name = if person.name.is_empty() {
+                    person.email.clone()
+                } else {
+                    person.name.clone()
+                };
+                rows.push((
+                    person.id.clone(),
+                    PERSON_NODE_TYPE.to_string(),
+                    name,
+                    None,
+                    String::new(),
+                    json!({ "email": person.email }),
+                ));
+                by_email.insert(key, person.id.clone());
+                person.id.clone()
+            }
+        };
+        ids.insert(person.id.clone(), id);
+    }
+
+    // Each person is its own root, so they are created one hierarchy at a time
+    for row in rows {
+        node_service
+            .bulk_create_hierarchy_root_notify(vec![row])
+            .await?;
+    }
+    Ok(ids)
+}
+
+/// Walk a MIME tree, keeping the first text bodies and every attachment
+fn collect_parts(part: &MimePart<'_>, out: &mut MessageContent) {
+    let (mime, params) = part.content_type();
+
+    if mime.starts_with("multipart/") {
+        if let Some(boundary) = params.get("boundary") {
+            for child in split_multipart(part.body, boundary) {
+                collect_parts(&MimePart::parse(child), out);
+            }
+        }
+        return;
+    }
+
+    let (disposition, disposition_params) = part
+        .header("content-disposition")
+        .map(parse_header_params)
+        .unwrap_or_default();
+    let file_name = disposition_params
+        .get("filename")
+        .or_else(|| params.get("name"))
+        .map(|n| decode_encoded_words(n));
+    let is_body = disposition != "attachment"
+        && file_name.is_none()
+        && (mime == "text/plain" || mime == "text/html");
+
+    let data = part.decoded_body();
+    if is_body {
+        let charset = params.get("charset").map(String::as_str).unwrap_or("utf-8");
+        let text = decode_charset(&data, charset);
+        let slot = if mime == "text/plain" {
+            &mut out.plain
+        } else {
+            &mut out.html
+        };
+        if slot.is_none() {
+            *slot = Some(text);
+        }
+        return;
+    }
+
+    let file_name = file_name
+        .unwrap_or_else(|| format!("attachment-{}", out.attachments.len() + 1))
+        .trim()
+        .to_string();
+    if let Some(cid) = part.header("content-id") {
+        let cid = cid.trim().trim_start_matches('<').trim_end_matches('>');
+        out.content_ids
+            .insert(cid.to_string(), attachment_uri(&attachment_hash(&data)));
+    }
+    out.attachments.push(ImportedAttachment {
+        file_name,
+        mime,
+        data,
+    });
+}
+
+/// Parts between `--boundary` delimiters (preamble and epilogue dropped)
+fn split_multipart<'a>(body: &'a str, boundary: &str) -> Vec<&'a str> {
+    let delimiter = format!("--{}", boundary);
+    let mut parts = Vec::new();
+    let mut start: Option<usize> = None;
+    let mut offset = 0;
+    for line in body.split_inclusive('\n') {
+        let trimmed = line.trim_end();
+        if trimmed.starts_with(&delimiter) {
+            if let Some(s) = start {
+                parts.push(body[s..offset].trim_end_matches('\n'));
+            }
+            if trimmed[delimiter.len()..].starts_with("--") {
+                return parts;
+            }
+            start = Some(offset + line.len());
+        }
+        offset += line.len();
+    }
+    if let Some(s) = start {
+        parts.push(&body[s..]);
+    }
+    parts
+}
+
+/// Split an mbox archive into messages, undoing `>From ` quoting
+fn split_mbox(content: &str) -> Vec<String> {
+    let mut messages = Vec::new();
+    let mut current: Option<String> = None;
+    let mut previous_blank = true;
+    for line in content.lines() {
+        if line.starts_with("From ") && previous_blank {
+            messages.extend(current.take());
+            current = Some(String::new());
+        } else if let Some(message) = current.as_mut() {
+            let quoted = line.starts_with('>') && line.trim_start_matches('>').starts_with("From ");
+            let unquoted = if quoted { &line[1..] } else { line };
+            message.push_str(unquoted);
+            message.push('\n');
+        }
+        previous_blank = line.is_empty();
+    }
+    messages.extend(current);
+    messages
+        .into_iter()
+        .filter(|m| !m.trim().is_empty())
+        .collect()
+}
+
+/// Split `type/subtype; key=value; key="quoted"` into lowercase type and params
+///
+/// RFC 2231 extended values (`filename*=UTF-8''na%C3%AFve.txt`) are decoded.
+fn parse_header_params(value: &str) -> (String, HashMap<String, String>) {
+    let mut segments = value.split(';');
+    let main = segments.next().unwrap_or("").trim().to_lowercase();
+    let mut params = HashMap::new();
+    for segment in segments {
+        let Some((key, val)) = segment.split_once('=') else {
+            continue;
+        };
+        let key = key.trim().to_lowercase();
+        let val = val.trim().trim_matches('"');
+        match key.strip_suffix('*') {
+            Some(key) => {
+                let encoded = val.split("''").last().unwrap_or(val);
+                params.insert(key.to_string(), percent_decode(encoded));
+            }
+            None => {
+                params.insert(key, val.to_string());
+            }
+        }
+    }
+    (main, params)
+}
+
+/// Parse an address list into `(display name, address)` pairs
+fn parse_addresses(value: &str) -> Vec<(String, String)> {
+    let mut entries = Vec::new();
+    let mut current = String::new();
+    let (mut in_quotes, mut in_angle) = (false, false);
+    for c in value.chars() {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            '<' if !in_quotes => in_angle = true,
+            '>' if !in_quotes => in_angle = false,
+            ',' if !in_quotes && !in_angle => {
+                entries.push(std::mem::take(&mut current));
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
+    }
+    entries.push(current);
+
+    entries
+        .iter()
+        .filter_map(|entry| {
+            let entry = entry.trim();
+            let (name, address) = match (entry.rfind('<'), entry.rfind('>')) {
+                (Some(open), Some(close)) if open < close => {
+                    (&entry[..open], &entry[open + 1..close])
+                }
+                _ => ("", entry),
+            };
+            let address = address.trim();
+            if !address.contains('@') {
+                return None;
+            }
+            let name = name.trim().trim_matches('"').trim();
+            Some((name.to_string(), address.to_string()))
+        })
+        .collect()
+}
+
+fn encoded_word_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"=\?([^?]+)\?([BbQq])\?([^?]*)\?=").expect("valid regex"))
+}
+
+fn encoded_word_gap_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"\?=\s+=\?").expect("valid regex"))
+}
+
+/// Decode RFC 2047 encoded words (`=?UTF-8?B?...?=`) in a header value
+fn decode_encoded_words(value: &str) -> String {
+    // Whitespace between adjacent encoded words is not part of the text
+    let value = encoded_word_gap_regex().replace_all(value, "?==?");
+    encoded_word_regex()
+        .replace_all(&value, |caps: &regex::Captures| {
+            let bytes = match &caps[2] {
+                "B" | "b" => base64::engine::general_purpose::STANDARD
+                    .decode(&caps[3])
+                    .unwrap_or_default(),
+                _ => decode_quoted_printable(&caps[3].replace('_', " ")),
+            };
+            decode_charset(&bytes, &caps[1])
+        })
+        .into_owned()
+}
+
+fn decode_quoted_printable(text: &str) -> Vec<u8> {
+    let bytes = text.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'=' {
+            // Soft line break
+            if bytes.get(i + 1) == Some(&b'\n') {
+                i += 2;
+                continue;
+            }
+            if let Some(byte) = text
+                .get(i + 1..i + 3)
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+            {
+                out.push(byte);
+                i += 3;
+                continue;
+            }
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+    out
+}
+
+fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            if let Some(byte) = text
+                .get(i + 1..i + 3)
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+            {
+                out.push(byte);
+                i += 3;
+                continue;
+            }
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+/// Text in UTF-8/ASCII or Latin-1; other charsets are decoded lossily as UTF-8
+fn decode_charset(bytes: &[u8], charset: &str) -> String {
+    match charset.trim().to_lowercase().as_str() {
+        "iso-8859-1" | "latin1" | "windows-1252" | "cp1252" => {
+            bytes.iter().map(|&b| b as char).collect()
+        }
+        _ => String::from_utf8_lossy(bytes).into_owned(),
+    }
+}
+
+/// Join hard-wrapped lines so each paragraph becomes one node
+///
+/// Quoted replies, lists and indented blocks keep their line breaks.
+fn reflow_plain_text(text: &str) -> String {
+    text.split("\n\n")
+        .map(str::trim_end)
+        .filter(|p| !p.trim().is_empty())
+        .map(|paragraph| {
+            let structured = paragraph.lines().any(|line| {
+                let t = line.trim_start();
+                t.starts_with('>')
+                    || t.starts_with("- ")
+                    || t.starts_with("* ")
+                    || line.starts_with([' ', '\t'])
+            });
+            if structured {
+                paragraph.to_string()
+            } else {
+                paragraph
+                    .lines()
+                    .map(str::trim)
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MULTIPART: &str = "From: \"Doe, Jane\" <jane@example.com>\r\n\
+To: Bob <bob@example.com>, carol@example.com\r\n\
+Subject: =?UTF-8?B?UXVhcnRlcmx5IHBsYW4g4pyU?=\r\n\
+Date: Tue, 14 May 2024 09:30:00 +0200\r\n\
+Message-ID: <abc@example.com>\r\n\
+MIME-Version: 1.0\r\n\
+Content-Type: multipart/mixed; boundary=\"XYZ\"\r\n\
+\r\n\
+preamble\r\n\
+--XYZ\r\n\
+Content-Type: text/plain; charset=utf-8\r\n\
+Content-Transfer-Encoding: quoted-printable\r\n\
+\r\n\
+Hi Bob,\r\n\
+\r\n\
+the plan is attached and =\r\n\
+covers Q3 caf=C3=A9 budgets.\r\n\
+--XYZ\r\n\
+Content-Type: application/pdf; name=\"plan.pdf\"\r\n\
+Content-Disposition: attachment; filename=\"plan.pdf\"\r\n\
+Content-Transfer-Encoding: base64\r\n\
+\r\n\
+JVBERi0=\r\n\
+--XYZ--\r\n";
+
+    #[test]
+    fn test_eml_maps_subject_body_people_and_attachments() {
+        let docs = EmailImporter.import("mail", MULTIPART).unwrap();
+        assert_eq!(docs.len(), 1);
+        let doc = &docs[0];
+
+        assert_eq!(doc.title, "Quarterly plan ✔");
+        assert_eq!(
+            doc.nodes[0].content, "Hi Bob,",
+            "greeting is its own paragraph"
+        );
+        assert_eq!(
+            doc.nodes[1].content,
+            "the plan is attached and covers Q3 café budgets."
+        );
+        assert!(doc
+            .nodes
+            .iter()
+            .any(|n| n.content.starts_with("[plan.pdf](attachment://")));
+
+        assert_eq!(doc.attachments.len(), 1);
+        assert_eq!(doc.attachments[0].mime, "application/pdf");
+        assert_eq!(doc.attachments[0].data, b"%PDF-");
+
+        let people: Vec<_> = doc.people.iter().map(|p| p.email.as_str()).collect();
+        assert_eq!(
+            people,
+            vec!["jane@example.com", "bob@example.com", "carol@example.com"]
+        );
+        assert_eq!(doc.people[0].name, "Doe, Jane");
+
+        let email = &doc.root_properties["email"];
+        assert_eq!(email["from"], doc.people[0].id.as_str());
+        assert_eq!(email["to"], json!([doc.people[1].id, doc.people[2].id]));
+        assert_eq!(email["message_id"], "abc@example.com");
+        assert_eq!(email["sent_at"], "2024-05-14T07:30:00+00:00");
+        assert_eq!(doc.timestamps.len(), 1);
+    }
+
+    #[test]
+    fn test_mbox_splits_messages_and_shares_people() {
+        let mbox = "From jane@example.com Tue May 14 09:30:00 2024\n\
+From: jane@example.com\n\
+Subject: First\n\
+\n\
+>From the archive\n\
+\n\
+From jane@example.com Wed May 15 09:30:00 2024\n\
+From: Jane <JANE@example.com>\n\
+Content-Type: text/html\n\
+\n\
+<p>Second <b>body</b></p>\n";
+        let docs = EmailImporter.import("archive", mbox).unwrap();
+        assert_eq!(docs.len(), 2);
+
+        assert_eq!(docs[0].title, "First");
+        assert_eq!(docs[0].nodes[0].content, "From the archive");
+        assert_eq!(docs[1].title, "archive");
+        assert_eq!(docs[1].nodes[0].content, "Second **body**");
+
+        assert_eq!(docs[0].people[0].id, docs[1].people[0].id);
+    }
+
+    #[test]
+    fn test_header_helpers() {
+        let (mime, params) =
+            parse_header_params("attachment; filename*=UTF-8''na%C3%AFve%20notes.txt");
+        assert_eq!(mime, "attachment");
+        assert_eq!(params["filename"], "naïve notes.txt");
+
+        assert_eq!(
+            decode_encoded_words("=?ISO-8859-1?Q?Caf=E9?= =?UTF-8?Q?_au_lait?="),
+            "Café au lait"
+        );
+        assert_eq!(
+            parse_addresses("undisclosed-recipients:;"),
+            Vec::<(String, String)>::new()
+        );
+    }
+}
@@ -0,0 +1,355 @@
+//! Evernote ENEX importer
+//!
+//! Each `<note>` becomes a document: the ENML body is converted to markdown
+//! (via [`html_to_markdown`]) and parsed into a node tree. Embedded
+//! `<resource>`s are decoded and returned as attachments; `<en-media>`
+//! references to them (matched by MD5, as ENEX requires) become
+//! `attachment://<sha256>` images or links. Note tags become collection
+//! memberships of the note root, and `created`/`updated` are preserved.
+
+use super::{ImportError, ImportedAttachment, ImportedDocument, Importer};
+use crate::mcp::handlers::markdown::prepare_nodes_from_markdown;
+use crate::services::attachment_store::{attachment_hash, attachment_uri};
+use crate::utils::html_to_markdown;
+use base64::Engine;
+use chrono::{DateTime, NaiveDateTime, Utc};
+use md5::{Digest, Md5};
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use regex::Regex;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// Imports Evernote `.enex` exports
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EnexImporter;
+
+#[derive(Default)]
+struct Note {
+    title: String,
+    content: String,
+    created: Option<DateTime<Utc>>,
+    updated: Option<DateTime<Utc>>,
+    tags: Vec<String>,
+    resources: Vec<Resource>,
+}
+
+#[derive(Default)]
+struct Resource {
+    data: String,
+    mime: String,
+    file_name: Option<String>,
+}
+
+impl Importer for EnexImporter {
+    fn name(&self) -> &'static str {
+        "Evernote ENEX"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["enex"]
+    }
+
+    fn import(
+        &self,
+        _file_stem: &str,
+        content: &str,
+    ) -> Result<Vec<ImportedDocument>, ImportError> {
+        let notes = self.read_notes(content)?;
+        notes.into_iter().map(|note| self.convert(note)).collect()
+    }
+}
+
+impl EnexImporter {
+    fn read_notes(&self, content: &str) -> Result<Vec<Note>, ImportError> {
+        let err = |e: &dyn std::fmt::Display| ImportError::parse(self.name(), e.to_string());
+
+        let mut reader = Reader::from_str(content);
+        let mut notes = Vec::new();
+        let mut note: Option<Note> = None;
+        let mut resource: Option<Resource> = None;
+        let mut path: Vec<String> = Vec::new();
+        let mut saw_export = false;
+
+        loop {
+            match reader.read_event().map_err(|e| err(&e))? {
+                Event::Start(e) => {
+                    let name = String::from_utf8_lossy(e.name().as_ref()).into_owned();
+                    match name.as_str() {
+                        "en-export" => saw_export = true,
+                        "note" => note = Some(Note::default()),
+                        "resource" if note.is_some() => resource = Some(Resource::default()),
+                        _ => {}
+                    }
+                    path.push(name);
+                }
+                Event::End(e) => {
+                    match e.name().as_ref() {
+                        b"note" => notes.extend(note.take()),
+                        b"resource" => {
+                            if let (Some(n), Some(r)) = (note.as_mut(), resource.take()) {
+                                n.resources.push(r);
+                            }
+                        }
+                        _ => {}
+                    }
+                    path.pop();
+                }
+                Event::Text(t) => {
+                    let text = t.unescape().map_err(|e| err(&e))?;
+                    self.apply_text(&path, &text, note.as_mut(), resource.as_mut());
+                }
+                Event::CData(c) => {
+                    let text = String::from_utf8_lossy(&c.into_inner()).into_owned();
+                    self.apply_text(&path, &text, note.as_mut(), resource.as_mut());
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+
+        if !saw_export {
+            return Err(ImportError::parse(
+                self.name(),
+                "missing <en-export> root element",
+            ));
+        }
+        Ok(notes)
+    }
+
+    /// Route element text to the field named by the innermost element
+    fn apply_text(
+        &self,
+        path: &[String],
+        text: &str,
+        note: Option<&mut Note>,
+        resource: Option<&mut Resource>,
+    ) {
+        let Some(note) = note else {
+            return;
+        };
+        let current = path.last().map(String::as_str).unwrap_or("");
+
+        if let Some(res) = resource {
+            match current {
+                "data" => res.data.push_str(text),
+                "mime" => res.mime.push_str(text.trim()),
+                "file-name" => res.file_name = Some(text.trim().to_string()),
+                _ => {}
+            }
+            return;
+        }
+
+        // Only direct children of <note> (ignore <note-attributes> etc.)
+        if path.len() < 2 || path[path.len() - 2] != "note" {
+            return;
+        }
+        match current {
+            "title" => note.title.push_str(text),
+            "content" => note.content.push_str(text),
+            "created" => note.created = parse_enex_date(text),
+            "updated" => note.updated = parse_enex_date(text),
+            "tag" if !text.trim().is_empty() => note.tags.push(text.trim().to_string()),
+            _ => {}
+        }
+    }
+
+    fn convert(&self, note: Note) -> Result<ImportedDocument, ImportError> {
+        // Decode resources, keyed by the MD5 that <en-media hash="..."> uses
+        let mut attachments = Vec::with_capacity(note.resources.len());
+        let mut media: HashMap<String, (String, String, String)> = HashMap::new();
+        for (i, res) in note.resources.into_iter().enumerate() {
+            let compact: String = res.data.split_whitespace().collect();
+            let data = base64::engine::general_purpose::STANDARD
+                .decode(compact)
+                .map_err(|e| ImportError::parse(self.name(), format!("resource {}: {}", i, e)))?;
+            let md5: String = Md5::digest(&data)
+                .iter()
+                .map(|b| format!("{:02x}", b))
+                .collect();
+            let file_name = res
+                .file_name
+                .filter(|n| !n.is_empty())
+                .unwrap_or_else(|| format!("attachment-{}", i + 1));
+            media.insert(
+                md5,
+                (
+                    attachment_uri(&attachment_hash(&data)),
+                    file_name.clone(),
+                    res.mime.clone(),
+                ),
+            );
+            attachments.push(ImportedAttachment {
+                file_name,
+                mime: res.mime,
+                data,
+            });
+        }
+
+        let html = enml_to_html(&note.content, &media);
+        let markdown = html_to_markdown(&html);
+        let nodes = prepare_nodes_from_markdown(&markdown, None)
+            .map_err(|e| ImportError::parse(self.name(), e.message))?;
+
+        let title = match note.title.trim() {
+            "" => "Untitled note".to_string(),
+            t => t.to_string(),
+        };
+        let mut doc = ImportedDocument::new(title, nodes);
+        let times = match (note.created, note.updated) {
+            (Some(c), Some(u)) => Some((c, u)),
+            (Some(t), None) | (None, Some(t)) => Some((t, t)),
+            (None, None) => None,
+        };
+        if let Some((created, updated)) = times {
+            doc.timestamps.push((doc.root_id.clone(), created, updated));
+        }
+        doc.memberships = note
+            .tags
+            .into_iter()
+            .map(|tag| (doc.root_id.clone(), tag))
+            .collect();
+        doc.attachments = attachments;
+        Ok(doc)
+    }
+}
+
+/// Parse ENEX timestamps (`20240131T093000Z`)
+fn parse_enex_date(text: &str) -> Option<DateTime<Utc>> {
+    NaiveDateTime::parse_from_str(text.trim(), "%Y%m%dT%H%M%SZ")
+        .ok()
+        .map(|dt| dt.and_utc())
+}
+
+fn en_media_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r"(?s)<en-media\b([^>]*?)/?>(?:\s*</en-media>)?").expect("valid regex")
+    })
+}
+
+fn en_todo_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r"(?s)<en-todo\b([^>]*?)/?>(?:\s*</en-todo>)?").expect("valid regex")
+    })
+}
+
+fn attr_value<'a>(attrs: &'a str, name: &str) -> Option<&'a str> {
+    let start = attrs.find(&format!("{}=\"", name))? + name.len() + 2;
+    let end = attrs[start..].find('"')? + start;
+    Some(&attrs[start..end])
+}
+
+/// Rewrite ENML-specific elements into HTML the markdown converter understands
+///
+/// `media` maps resource MD5 → (attachment URI, file name, MIME type).
+fn enml_to_html(enml: &str, media: &HashMap<String, (String, String, String)>) -> String {
+    let with_media = en_media_regex().replace_all(enml, |caps: &regex::Captures| {
+        let attrs = &caps[1];
+        let Some((uri, name, mime)) = attr_value(attrs, "hash").and_then(|h| media.get(h)) else {
+            return String::new();
+        };
+        if mime.starts_with("image/") {
+            format!(r#"<img src="{}" alt="{}">"#, uri, name)
+        } else {
+            format!(r#"<a href="{}">{}</a>"#, uri, name)
+        }
+    });
+
+    en_todo_regex()
+        .replace_all(&with_media, |caps: &regex::Captures| {
+            if attr_value(&caps[1], "checked") == Some("true") {
+                r#"<input type="checkbox" checked>"#.to_string()
+            } else {
+                r#"<input type="checkbox">"#.to_string()
+            }
+        })
+        .into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PNG_BASE64: &str = "iVBORw0KGgo=";
+
+    fn sample() -> String {
+        let data = base64::engine::general_purpose::STANDARD
+            .decode(PNG_BASE64)
+            .unwrap();
+        let md5: String = Md5::digest(&data)
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect();
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE en-export SYSTEM "http://xml.evernote.com/pub/evernote-export3.dtd">
+<en-export>
+  <note>
+    <title>Trip &amp; Plans</title>
+    <content><![CDATA[<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE en-note SYSTEM "http://xml.evernote.com/pub/enml2.dtd">
+<en-note><h2>Packing</h2><ul><li><en-todo checked="true"/>Passport</li><li><en-todo/>Charger</li></ul><div>Map: <en-media hash="{md5}" type="image/png"/></div></en-note>]]></content>
+    <created>20240131T093000Z</created>
+    <updated>20240201T100000Z</updated>
+    <tag>travel</tag>
+    <tag>2024</tag>
+    <note-attributes><author>me</author></note-attributes>
+    <resource>
+      <data encoding="base64">
+{PNG_BASE64}
+      </data>
+      <mime>image/png</mime>
+      <resource-attributes><file-name>map.png</file-name></resource-attributes>
+    </resource>
+  </note>
+</en-export>"#
+        )
+    }
+
+    #[test]
+    fn test_note_becomes_document() {
+        let docs = EnexImporter.import("export", &sample()).unwrap();
+        assert_eq!(docs.len(), 1);
+        let doc = &docs[0];
+
+        assert_eq!(doc.title, "Trip & Plans");
+        assert!(doc.nodes.iter().any(|n| n.content == "## Packing"));
+        assert!(doc.nodes.iter().any(|n| n.content.contains("Passport")));
+
+        let (_, created, updated) = &doc.timestamps[0];
+        assert_eq!(created.to_rfc3339(), "2024-01-31T09:30:00+00:00");
+        assert_eq!(updated.to_rfc3339(), "2024-02-01T10:00:00+00:00");
+
+        assert_eq!(
+            doc.memberships,
+            vec![
+                (doc.root_id.clone(), "travel".to_string()),
+                (doc.root_id.clone(), "2024".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_resources_become_attachments() {
+        let docs = EnexImporter.import("export", &sample()).unwrap();
+        let doc = &docs[0];
+
+        assert_eq!(doc.attachments.len(), 1);
+        let attachment = &doc.attachments[0];
+        assert_eq!(attachment.file_name, "map.png");
+        assert_eq!(attachment.mime, "image/png");
+
+        let uri = attachment_uri(&attachment_hash(&attachment.data));
+        assert!(doc
+            .nodes
+            .iter()
+            .any(|n| n.content.contains(&format!("![map.png]({})", uri))));
+    }
+
+    #[test]
+    fn test_rejects_non_enex() {
+        assert!(EnexImporter.import("x", "<html/>").is_err());
+    }
+}
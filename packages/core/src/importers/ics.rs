@@ -0,0 +1,231 @@
+//! iCalendar importer
+//!
+//! Each `VEVENT` becomes a text node whose content is the event summary, with
+//! `start`, `end`, `location` and `uid` properties. Descriptions become child
+//! text nodes (one per paragraph). Events are sorted by start time.
+//!
+//! Dates are normalised to ISO 8601: `20240301` → `2024-03-01`,
+//! `20240301T100000Z` → `2024-03-01T10:00:00Z`. Floating and `TZID` times keep
+//! no offset.
+
+use super::{ImportError, ImportedDocument, Importer, NodeTreeBuilder};
+use serde_json::{json, Map, Value};
+
+/// Imports `.ics` calendar files
+#[derive(Debug, Clone, Copy, Default)]
+pub struct IcsImporter;
+
+#[derive(Default)]
+struct Event {
+    summary: Option<String>,
+    start: Option<String>,
+    end: Option<String>,
+    location: Option<String>,
+    uid: Option<String>,
+    description: Option<String>,
+}
+
+impl Importer for IcsImporter {
+    fn name(&self) -> &'static str {
+        "iCalendar"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["ics"]
+    }
+
+    fn import(&self, file_stem: &str, content: &str) -> Result<Vec<ImportedDocument>, ImportError> {
+        let lines = unfold(content);
+        if !lines
+            .first()
+            .is_some_and(|l| l.eq_ignore_ascii_case("BEGIN:VCALENDAR"))
+        {
+            return Err(ImportError::parse(self.name(), "missing BEGIN:VCALENDAR"));
+        }
+
+        let mut calendar_name = None;
+        let mut events = Vec::new();
+        let mut current: Option<Event> = None;
+
+        for line in &lines {
+            let Some((head, value)) = line.split_once(':') else {
+                continue;
+            };
+            let name = head.split(';').next().unwrap_or(head).to_ascii_uppercase();
+
+            match (name.as_str(), current.as_mut()) {
+                ("BEGIN", None) if value.eq_ignore_ascii_case("VEVENT") => {
+                    current = Some(Event::default());
+                }
+                ("END", Some(_)) if value.eq_ignore_ascii_case("VEVENT") => {
+                    events.extend(current.take());
+                }
+                ("SUMMARY", Some(ev)) => ev.summary = Some(unescape(value)),
+                ("DTSTART", Some(ev)) => ev.start = Some(normalize_date(value)),
+                ("DTEND", Some(ev)) => ev.end = Some(normalize_date(value)),
+                ("LOCATION", Some(ev)) => ev.location = Some(unescape(value)),
+                ("UID", Some(ev)) => ev.uid = Some(value.to_string()),
+                ("DESCRIPTION", Some(ev)) => ev.description = Some(unescape(value)),
+                ("X-WR-CALNAME", None) => calendar_name = Some(unescape(value)),
+                _ => {}
+            }
+        }
+
+        if current.is_some() {
+            return Err(ImportError::parse(self.name(), "unterminated VEVENT"));
+        }
+
+        events.sort_by(|a, b| a.start.cmp(&b.start));
+
+        let mut builder = NodeTreeBuilder::default();
+        for event in events {
+            let mut props = Map::new();
+            for (key, value) in [
+                ("start", event.start),
+                ("end", event.end),
+                ("location", event.location),
+                ("uid", event.uid),
+            ] {
+                if let Some(v) = value.filter(|v| !v.is_empty()) {
+                    props.insert(key.to_string(), Value::String(v));
+                }
+            }
+
+            let summary = event
+                .summary
+                .filter(|s| !s.trim().is_empty())
+                .unwrap_or_else(|| "Untitled event".to_string());
+            let id = builder.push(None, "text", summary, Value::Object(props));
+
+            for paragraph in event
+                .description
+                .iter()
+                .flat_map(|d| d.split("\n\n"))
+                .map(str::trim)
+                .filter(|p| !p.is_empty())
+            {
+                builder.push(Some(&id), "text", paragraph, json!({}));
+            }
+        }
+
+        let title = calendar_name
+            .filter(|n| !n.trim().is_empty())
+            .unwrap_or_else(|| file_stem.to_string());
+        Ok(vec![ImportedDocument::new(title, builder.into_nodes())])
+    }
+}
+
+/// Join RFC 5545 folded lines (continuations start with a space or tab)
+fn unfold(content: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for raw in content.lines() {
+        match (raw.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(rest), Some(last)) => last.push_str(rest),
+            _ if raw.trim().is_empty() => {}
+            _ => lines.push(raw.trim_end().to_string()),
+        }
+    }
+    lines
+}
+
+/// Decode iCalendar text escapes (`\n`, `\,`, `\;`, `\\`)
+fn unescape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') | Some('N') => out.push('\n'),
+            Some(other) => out.push(other),
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+/// Convert iCalendar DATE / DATE-TIME values to ISO 8601
+fn normalize_date(value: &str) -> String {
+    let v = value.trim();
+    let digits = |s: &str| s.chars().all(|c| c.is_ascii_digit());
+    match v.len() {
+        8 if digits(v) => format!("{}-{}-{}", &v[0..4], &v[4..6], &v[6..8]),
+        15 | 16 if digits(&v[0..8]) && &v[8..9] == "T" && digits(&v[9..15]) => format!(
+            "{}-{}-{}T{}:{}:{}{}",
+            &v[0..4],
+            &v[4..6],
+            &v[6..8],
+            &v[9..11],
+            &v[11..13],
+            &v[13..15],
+            if v.ends_with('Z') { "Z" } else { "" }
+        ),
+        _ => v.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "BEGIN:VCALENDAR\r\n\
+VERSION:2.0\r\n\
+X-WR-CALNAME:Work\r\n\
+BEGIN:VEVENT\r\n\
+UID:b@example.com\r\n\
+DTSTART;TZID=Europe/Berlin:20240302T090000\r\n\
+SUMMARY:Planning\\, Q2\r\n\
+END:VEVENT\r\n\
+BEGIN:VEVENT\r\n\
+UID:a@example.com\r\n\
+DTSTART:20240301T100000Z\r\n\
+DTEND:20240301T110000Z\r\n\
+SUMMARY:Team sync\r\n\
+LOCATION:Room 4\r\n\
+DESCRIPTION:Agenda first.\\n\\nThen a long descr\r\n iption line.\r\n\
+END:VEVENT\r\n\
+END:VCALENDAR\r\n";
+
+    #[test]
+    fn test_events_become_sorted_nodes() {
+        let docs = IcsImporter.import("calendar", SAMPLE).unwrap();
+        let doc = &docs[0];
+        assert_eq!(doc.title, "Work");
+
+        let top: Vec<_> = doc.nodes.iter().filter(|n| n.parent_id.is_none()).collect();
+        assert_eq!(top.len(), 2);
+        assert_eq!(top[0].content, "Team sync");
+        assert_eq!(top[0].properties["start"], "2024-03-01T10:00:00Z");
+        assert_eq!(top[0].properties["end"], "2024-03-01T11:00:00Z");
+        assert_eq!(top[0].properties["location"], "Room 4");
+        assert_eq!(top[1].content, "Planning, Q2");
+        assert_eq!(top[1].properties["start"], "2024-03-02T09:00:00");
+
+        let description: Vec<_> = doc
+            .nodes
+            .iter()
+            .filter(|n| n.parent_id.as_deref() == Some(top[0].id.as_str()))
+            .map(|n| n.content.as_str())
+            .collect();
+        assert_eq!(
+            description,
+            vec!["Agenda first.", "Then a long description line."]
+        );
+    }
+
+    #[test]
+    fn test_all_day_date() {
+        assert_eq!(normalize_date("20241231"), "2024-12-31");
+        assert_eq!(normalize_date("garbage"), "garbage");
+    }
+
+    #[test]
+    fn test_rejects_invalid_calendar() {
+        assert!(IcsImporter.import("x", "hello").is_err());
+        assert!(IcsImporter
+            .import("x", "BEGIN:VCALENDAR\nBEGIN:VEVENT\nSUMMARY:x\n")
+            .is_err());
+    }
+}
@@ -0,0 +1,61 @@
+//! Markdown importer
+//!
+//! Uses the first non-empty line as the document title and parses the rest
+//! with the shared markdown import parser, so a leading `# Heading` is not
+//! duplicated as a child node.
+
+use super::{ImportError, ImportedDocument, Importer};
+use crate::mcp::handlers::markdown::prepare_nodes_from_markdown;
+
+/// Imports `.md` / `.markdown` files
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MarkdownImporter;
+
+impl Importer for MarkdownImporter {
+    fn name(&self) -> &'static str {
+        "Markdown"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["md", "markdown"]
+    }
+
+    fn import(&self, file_stem: &str, content: &str) -> Result<Vec<ImportedDocument>, ImportError> {
+        let mut lines = content.lines().skip_while(|l| l.trim().is_empty());
+        let title = match lines.next() {
+            Some(first) => first.trim_end().to_string(),
+            None => file_stem.to_string(),
+        };
+        let body = lines.collect::<Vec<_>>().join("\n");
+
+        let nodes = prepare_nodes_from_markdown(&body, None)
+            .map_err(|e| ImportError::parse(self.name(), e.message))?;
+
+        Ok(vec![ImportedDocument::new(title, nodes)])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_first_line_becomes_title() {
+        let docs = MarkdownImporter
+            .import("file", "\n# Project Plan\n\nIntro\n\n## Goals\n- Ship")
+            .unwrap();
+
+        assert_eq!(docs.len(), 1);
+        assert_eq!(docs[0].title, "# Project Plan");
+        assert_eq!(docs[0].root_content(), "# Project Plan");
+        assert!(docs[0].nodes.iter().all(|n| n.content != "# Project Plan"));
+        assert!(docs[0].nodes.iter().any(|n| n.content == "## Goals"));
+    }
+
+    #[test]
+    fn test_empty_file_uses_file_stem() {
+        let docs = MarkdownImporter.import("meeting-notes", "  \n").unwrap();
+        assert_eq!(docs[0].root_content(), "# meeting-notes");
+        assert!(docs[0].nodes.is_empty());
+    }
+}
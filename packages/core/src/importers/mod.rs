@@ -0,0 +1,490 @@
+//! File Importers
+//!
+//! Converts external file formats into prepared node trees. Importers are pure:
+//! they parse file content into [`ImportedDocument`]s and never touch the
+//! database, so the caller decides how nodes are inserted (the desktop app uses
+//! bulk hierarchy creation). The exception is [`import_csv`], which needs the
+//! target schema to validate rows, and [`resolve_people`], which links email
+//! senders and recipients to person nodes.
+//!
+//! ## Registered Formats
+//!
+//! | Extension | Importer | Result |
+//! |-----------|----------|--------|
+//! | `bib` | [`BibtexImporter`] | One document, one reference node per entry |
+//! | `csv` | [`CsvImporter`] | One document, one node per row |
+//! | `eml`, `mbox` | [`EmailImporter`] | One document per message |
+//! | `enex` | [`EnexImporter`] | Evernote export, one document per note |
+//! | `md`, `markdown` | [`MarkdownImporter`] | One document, first line as title |
+//! | `json` | [`OutlinerJsonImporter`] | Roam/Logseq export, one document per page |
+//! | `opml` | [`OpmlImporter`] | One document, `<outline>` tree as nodes |
+//! | `ics` | [`IcsImporter`] | One document, one node per `VEVENT` |
+//!
+//! Some formats are recognised by file name instead of extension:
+//!
+//! | File name | Importer | Result |
+//! |-----------|----------|--------|
+//! | `todo.txt`, `done.txt` | [`TodoTxtImporter`] | One document, one task node per line, synced on re-import |
+//!
+//! Files with neither a known name nor a known extension are rejected with
+//! [`ImportError::UnsupportedFormat`].
+//!
+//! # Examples
+//!
+//! ```rust
+//! use nodespace_core::importers::ImporterRegistry;
+//! use std::path::Path;
+//!
+//! let registry = ImporterRegistry::with_defaults();
+//! let docs = registry
+//!     .import_file(Path::new("notes.md"), "# Notes\n\n- First\n- Second")
+//!     .unwrap();
+//!
+//! assert_eq!(docs[0].title, "# Notes");
+//! assert_eq!(docs[0].nodes.len(), 2);
+//! ```
+
+mod bibtex;
+mod csv;
+mod email;
+mod enex;
+mod ics;
+mod markdown;
+mod opml;
+mod outliner_json;
+mod report;
+mod todo_txt;
+
+pub use bibtex::BibtexImporter;
+pub use csv::{
+    import_csv, parse_csv, CsvImportResult, CsvImporter, CsvMapping, CsvRowError, CSV_BATCH_SIZE,
+};
+pub use email::{resolve_people, EmailImporter, PERSON_NODE_TYPE};
+pub use enex::EnexImporter;
+pub use ics::IcsImporter;
+pub use markdown::MarkdownImporter;
+pub use opml::OpmlImporter;
+pub use outliner_json::OutlinerJsonImporter;
+pub use report::{ImportReport, ImportReportEntry};
+pub use todo_txt::{TodoTxtImporter, TodoTxtTask};
+
+use crate::db::FractionalIndex;
+use crate::mcp::handlers::markdown::PreparedNode;
+use chrono::{DateTime, Utc};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+use thiserror::Error;
+
+/// Row accepted by `NodeService::bulk_create_hierarchy*`:
+/// `(id, node_type, content, parent_id, order, properties)`
+pub type HierarchyRow = (String, String, String, Option<String>, String, Value);
+
+/// Errors produced while importing a file
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum ImportError {
+    /// No importer is registered for the file extension
+    #[error("No importer registered for '{0}' files")]
+    UnsupportedFormat(String),
+
+    /// Import mapping references unknown columns or fields
+    #[error("Invalid import mapping: {0}")]
+    InvalidMapping(String),
+
+    /// File content could not be parsed
+    #[error("Failed to parse {format} file: {message}")]
+    Parse {
+        format: &'static str,
+        message: String,
+    },
+}
+
+impl ImportError {
+    /// Create an unsupported-format error from a file path's extension
+    pub fn unsupported_format(path: &Path) -> Self {
+        Self::UnsupportedFormat(
+            path.extension()
+                .and_then(|e| e.to_str())
+                .map(|e| format!(".{}", e.to_lowercase()))
+                .unwrap_or_else(|| "extensionless".to_string()),
+        )
+    }
+
+    /// Create a parse error for the given format
+    pub fn parse(format: &'static str, message: impl Into<String>) -> Self {
+        Self::Parse {
+            format,
+            message: message.into(),
+        }
+    }
+}
+
+/// A document produced by an importer, ready for insertion
+///
+/// The caller creates a root node from [`root_content`](Self::root_content);
+/// nodes with `parent_id: None` become its direct children. The root ID is
+/// assigned up front so importers can reference the root in mentions.
+#[derive(Debug, Clone)]
+pub struct ImportedDocument {
+    /// Pre-assigned root node ID
+    pub root_id: String,
+    /// Document title (raw, may already carry a `#` heading marker)
+    pub title: String,
+    /// Properties stored on the root node
+    pub root_properties: Value,
+    /// Descendant nodes, ordered parents-before-children
+    pub nodes: Vec<PreparedNode>,
+    /// Mention edges `(source_id, target_id)` to create once all documents
+    /// from the same file exist (targets may live in sibling documents)
+    pub mentions: Vec<(String, String)>,
+    /// Original `(node_id, created_at, modified_at)` timestamps to restore
+    pub timestamps: Vec<(String, DateTime<Utc>, DateTime<Utc>)>,
+    /// Collection memberships `(node_id, collection_path)` to assign
+    pub memberships: Vec<(String, String)>,
+    /// Binary files referenced from node content via `attachment://<hash>`
+    pub attachments: Vec<ImportedAttachment>,
+    /// People referenced from `root_properties` by placeholder ID
+    pub people: Vec<ImportedPerson>,
+    /// IDs are derived from the file, so importing it again updates this
+    /// document in place (see `NodeService::sync_hierarchy`) instead of
+    /// creating a copy
+    pub sync: bool,
+}
+
+/// Binary file extracted during import, to be written to the attachment store
+#[derive(Debug, Clone)]
+pub struct ImportedAttachment {
+    pub file_name: String,
+    pub mime: String,
+    pub data: Vec<u8>,
+}
+
+/// Person referenced by an imported document, linked to a node on insertion
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportedPerson {
+    /// Placeholder ID used in the document until [`resolve_people`] runs
+    pub id: String,
+    pub name: String,
+    pub email: String,
+}
+
+impl ImportedDocument {
+    pub fn new(title: impl Into<String>, nodes: Vec<PreparedNode>) -> Self {
+        Self {
+            root_id: uuid::Uuid::new_v4().to_string(),
+            title: title.into(),
+            root_properties: json!({}),
+            nodes,
+            mentions: Vec::new(),
+            timestamps: Vec::new(),
+            memberships: Vec::new(),
+            attachments: Vec::new(),
+            people: Vec::new(),
+            sync: false,
+        }
+    }
+
+    /// Replace placeholder IDs in root properties (see [`resolve_people`])
+    pub fn remap_ids(&mut self, ids: &HashMap<String, String>) {
+        fn remap(value: &mut Value, ids: &HashMap<String, String>) {
+            match value {
+                Value::String(s) => {
+                    if let Some(id) = ids.get(s.as_str()) {
+                        *s = id.clone();
+                    }
+                }
+                Value::Array(items) => items.iter_mut().for_each(|v| remap(v, ids)),
+                Value::Object(map) => map.values_mut().for_each(|v| remap(v, ids)),
+                _ => {}
+            }
+        }
+        remap(&mut self.root_properties, ids);
+    }
+
+    /// Root node content: the title as a level-1 header
+    pub fn root_content(&self) -> String {
+        if self.title.starts_with('#') {
+            self.title.clone()
+        } else {
+            format!("# {}", self.title)
+        }
+    }
+
+    /// Number of nodes created on insertion, including the root
+    pub fn node_count(&self) -> usize {
+        1 + self.nodes.len()
+    }
+
+    /// Flatten into bulk-insert rows
+    ///
+    /// Returns the root ID and rows ordered root-first. Mentions, timestamps,
+    /// memberships, attachments and people are not part of the rows; take
+    /// them before calling this.
+    pub fn into_hierarchy(self) -> (String, Vec<HierarchyRow>) {
+        let root_content = self.root_content();
+        let root_id = self.root_id;
+        let mut rows = Vec::with_capacity(1 + self.nodes.len());
+        rows.push((
+            root_id.clone(),
+            "header".to_string(),
+            root_content,
+            None,
+            String::new(),
+            self.root_properties,
+        ));
+        rows.extend(self.nodes.into_iter().map(|n| {
+            (
+                n.id,
+                n.node_type,
+                n.content,
+                Some(n.parent_id.unwrap_or_else(|| root_id.clone())),
+                n.order,
+                n.properties,
+            )
+        }));
+        (root_id, rows)
+    }
+}
+
+/// Converts one external file format into node trees
+pub trait Importer: Send + Sync {
+    /// Human-readable format name (e.g. "Markdown")
+    fn name(&self) -> &'static str;
+
+    /// Lowercase file extensions handled by this importer, without the dot
+    fn extensions(&self) -> &'static [&'static str];
+
+    /// Lowercase file names handled by this importer whatever their
+    /// extension says, for formats known by name (`todo.txt`); checked
+    /// before extensions
+    fn file_names(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Parse file content into documents
+    ///
+    /// `file_stem` is the file name without extension, used as a fallback title.
+    fn import(&self, file_stem: &str, content: &str) -> Result<Vec<ImportedDocument>, ImportError>;
+}
+
+/// Extension-based lookup of importers
+#[derive(Clone, Default)]
+pub struct ImporterRegistry {
+    importers: Vec<Arc<dyn Importer>>,
+}
+
+impl ImporterRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a registry with all built-in importers
+    pub fn with_defaults() -> Self {
+        let mut registry = Self::new();
+        registry.register(Arc::new(MarkdownImporter));
+        registry.register(Arc::new(OpmlImporter));
+        registry.register(Arc::new(IcsImporter));
+        registry.register(Arc::new(CsvImporter));
+        registry.register(Arc::new(OutlinerJsonImporter));
+        registry.register(Arc::new(EnexImporter));
+        registry.register(Arc::new(TodoTxtImporter));
+        registry.register(Arc::new(EmailImporter));
+        registry.register(Arc::new(BibtexImporter));
+        registry
+    }
+
+    /// Register an importer; later registrations win for shared extensions
+    pub fn register(&mut self, importer: Arc<dyn Importer>) {
+        self.importers.insert(0, importer);
+    }
+
+    /// Find the importer for a path by its (case-insensitive) file name,
+    /// then by its extension
+    pub fn importer_for(&self, path: &Path) -> Option<Arc<dyn Importer>> {
+        let file_name = path.file_name()?.to_str()?.to_lowercase();
+        if let Some(importer) = self
+            .importers
+            .iter()
+            .find(|i| i.file_names().contains(&file_name.as_str()))
+        {
+            return Some(importer.clone());
+        }
+        let ext = path.extension()?.to_str()?.to_lowercase();
+        self.importers
+            .iter()
+            .find(|i| i.extensions().contains(&ext.as_str()))
+            .cloned()
+    }
+
+    /// All extensions with a registered importer
+    pub fn supported_extensions(&self) -> Vec<&'static str> {
+        let mut exts: Vec<_> = self
+            .importers
+            .iter()
+            .flat_map(|i| i.extensions().iter().copied())
+            .collect();
+        exts.sort_unstable();
+        exts.dedup();
+        exts
+    }
+
+    /// Route file content to the matching importer
+    pub fn import_file(
+        &self,
+        path: &Path,
+        content: &str,
+    ) -> Result<Vec<ImportedDocument>, ImportError> {
+        let importer = self
+            .importer_for(path)
+            .ok_or_else(|| ImportError::unsupported_format(path))?;
+        let stem = path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("Untitled");
+        importer.import(stem, content)
+    }
+}
+
+/// Builds prepared nodes with explicit parents and per-parent ordering
+#[derive(Default)]
+pub(crate) struct NodeTreeBuilder {
+    nodes: Vec<PreparedNode>,
+    children_per_parent: HashMap<Option<String>, usize>,
+}
+
+impl NodeTreeBuilder {
+    /// Append a node as the last child of `parent_id`, returning its ID
+    pub(crate) fn push(
+        &mut self,
+        parent_id: Option<&str>,
+        node_type: &str,
+        content: impl Into<String>,
+        properties: Value,
+    ) -> String {
+        let id = uuid::Uuid::new_v4().to_string();
+        self.push_with_id(id, parent_id, node_type, content, properties)
+    }
+
+    /// [`Self::push`] with a given ID, for importers that sync
+    pub(crate) fn push_with_id(
+        &mut self,
+        id: String,
+        parent_id: Option<&str>,
+        node_type: &str,
+        content: impl Into<String>,
+        properties: Value,
+    ) -> String {
+        let parent_id = parent_id.map(str::to_string);
+        let count = self
+            .children_per_parent
+            .entry(parent_id.clone())
+            .or_insert(0);
+        let order = FractionalIndex::nth(*count);
+        *count += 1;
+        self.nodes.push(PreparedNode::new(
+            id.clone(),
+            node_type,
+            content.into(),
+            parent_id,
+            order,
+            properties,
+        ));
+        id
+    }
+
+    /// Number of nodes pushed so far (the index of the next node)
+    pub(crate) fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub(crate) fn into_nodes(self) -> Vec<PreparedNode> {
+        self.nodes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry_routes_by_extension() {
+        let registry = ImporterRegistry::with_defaults();
+
+        assert_eq!(
+            registry
+                .importer_for(Path::new("a/b/Notes.MD"))
+                .unwrap()
+                .name(),
+            "Markdown"
+        );
+        assert_eq!(
+            registry
+                .importer_for(Path::new("outline.opml"))
+                .unwrap()
+                .name(),
+            "OPML"
+        );
+        assert_eq!(
+            registry.importer_for(Path::new("cal.ics")).unwrap().name(),
+            "iCalendar"
+        );
+        assert_eq!(
+            registry
+                .importer_for(Path::new("tasks.csv"))
+                .unwrap()
+                .name(),
+            "CSV"
+        );
+        assert!(registry.importer_for(Path::new("README")).is_none());
+    }
+
+    #[test]
+    fn test_registry_routes_known_file_names_before_extensions() {
+        let registry = ImporterRegistry::with_defaults();
+
+        assert_eq!(
+            registry
+                .importer_for(Path::new("Dropbox/todo/TODO.txt"))
+                .unwrap()
+                .name(),
+            "todo.txt"
+        );
+        assert_eq!(
+            registry.importer_for(Path::new("done.txt")).unwrap().name(),
+            "todo.txt"
+        );
+        assert!(registry.importer_for(Path::new("notes.txt")).is_none());
+        assert!(!registry.supported_extensions().contains(&"txt"));
+    }
+
+    #[test]
+    fn test_unsupported_extension_error() {
+        let registry = ImporterRegistry::with_defaults();
+        let err = registry
+            .import_file(Path::new("photo.png"), "")
+            .unwrap_err();
+        assert_eq!(err, ImportError::UnsupportedFormat(".png".to_string()));
+        assert_eq!(err.to_string(), "No importer registered for '.png' files");
+    }
+
+    #[test]
+    fn test_into_hierarchy_parents_top_level_nodes_under_root() {
+        let mut builder = NodeTreeBuilder::default();
+        let first = builder.push(None, "text", "First", json!({}));
+        builder.push(Some(&first), "text", "Nested", json!({}));
+        builder.push(None, "text", "Second", json!({}));
+
+        let doc = ImportedDocument::new("Title", builder.into_nodes());
+        assert_eq!(doc.node_count(), 4);
+
+        let (root_id, rows) = doc.into_hierarchy();
+        assert_eq!(rows[0].0, root_id);
+        assert_eq!(rows[0].2, "# Title");
+        assert_eq!(rows[1].3.as_deref(), Some(root_id.as_str()));
+        assert_eq!(rows[2].3.as_deref(), Some(first.as_str()));
+        assert_eq!(rows[3].3.as_deref(), Some(root_id.as_str()));
+        assert_eq!(rows[3].4, "a1");
+    }
+}
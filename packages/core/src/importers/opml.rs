@@ -0,0 +1,204 @@
+//! OPML importer
+//!
+//! Maps the `<outline>` tree onto nodes. The `text` attribute (or `title` when
+//! absent) is the node content; a `_note` attribute (Workflowy/Dynalist style)
+//! becomes a child text node. `_complete="true"` outlines become done tasks.
+
+use super::{ImportError, ImportedDocument, Importer, NodeTreeBuilder};
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use serde_json::json;
+
+/// Imports `.opml` outline files
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OpmlImporter;
+
+/// Attributes of interest on an `<outline>` element
+struct Outline {
+    text: String,
+    note: Option<String>,
+    complete: bool,
+}
+
+impl OpmlImporter {
+    fn parse_outline(&self, element: &BytesStart) -> Result<Outline, ImportError> {
+        let mut text = None;
+        let mut title = None;
+        let mut note = None;
+        let mut complete = false;
+
+        for attr in element.attributes() {
+            let attr = attr.map_err(|e| ImportError::parse(self.name(), e.to_string()))?;
+            let value = attr
+                .unescape_value()
+                .map_err(|e| ImportError::parse(self.name(), e.to_string()))?
+                .into_owned();
+            match attr.key.as_ref() {
+                b"text" => text = Some(value),
+                b"title" => title = Some(value),
+                b"_note" if !value.trim().is_empty() => note = Some(value),
+                b"_complete" => complete = value == "true",
+                _ => {}
+            }
+        }
+
+        Ok(Outline {
+            text: text.or(title).unwrap_or_default(),
+            note,
+            complete,
+        })
+    }
+
+    /// Add an outline under `parent`, returning the new node's ID
+    fn push_outline(
+        builder: &mut NodeTreeBuilder,
+        parent: Option<&str>,
+        outline: Outline,
+    ) -> String {
+        let id = if outline.complete {
+            builder.push(parent, "task", outline.text, json!({"status": "done"}))
+        } else {
+            builder.push(parent, "text", outline.text, json!({}))
+        };
+        if let Some(note) = outline.note {
+            builder.push(Some(&id), "text", note, json!({}));
+        }
+        id
+    }
+}
+
+impl Importer for OpmlImporter {
+    fn name(&self) -> &'static str {
+        "OPML"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["opml"]
+    }
+
+    fn import(&self, file_stem: &str, content: &str) -> Result<Vec<ImportedDocument>, ImportError> {
+        let mut reader = Reader::from_str(content);
+        reader.config_mut().trim_text(true);
+
+        let mut builder = NodeTreeBuilder::default();
+        let mut stack: Vec<String> = Vec::new();
+        let mut title: Option<String> = None;
+        let mut in_title = false;
+        let mut saw_opml = false;
+
+        loop {
+            let event = reader.read_event().map_err(|e| {
+                ImportError::parse(
+                    self.name(),
+                    format!("{} at byte {}", e, reader.buffer_position()),
+                )
+            })?;
+            match event {
+                Event::Start(e) => match e.name().as_ref() {
+                    b"opml" => saw_opml = true,
+                    b"title" => in_title = true,
+                    b"outline" => {
+                        let outline = self.parse_outline(&e)?;
+                        let id = Self::push_outline(
+                            &mut builder,
+                            stack.last().map(String::as_str),
+                            outline,
+                        );
+                        stack.push(id);
+                    }
+                    _ => {}
+                },
+                Event::Empty(e) => match e.name().as_ref() {
+                    b"outline" => {
+                        let outline = self.parse_outline(&e)?;
+                        Self::push_outline(&mut builder, stack.last().map(String::as_str), outline);
+                    }
+                    b"opml" => saw_opml = true,
+                    _ => {}
+                },
+                Event::Text(t) if in_title => {
+                    let text = t
+                        .unescape()
+                        .map_err(|e| ImportError::parse(self.name(), e.to_string()))?;
+                    title = Some(text.trim().to_string());
+                }
+                Event::End(e) => match e.name().as_ref() {
+                    b"outline" => {
+                        stack.pop();
+                    }
+                    b"title" => in_title = false,
+                    _ => {}
+                },
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+
+        if !saw_opml {
+            return Err(ImportError::parse(
+                self.name(),
+                "missing <opml> root element",
+            ));
+        }
+
+        let title = title
+            .filter(|t| !t.is_empty())
+            .unwrap_or_else(|| file_stem.to_string());
+        Ok(vec![ImportedDocument::new(title, builder.into_nodes())])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = r#"<?xml version="1.0"?>
+<opml version="2.0">
+  <head><title>Reading &amp; Notes</title></head>
+  <body>
+    <outline text="Books">
+      <outline text="Dune" _note="Re-read in winter"/>
+      <outline text="Neuromancer" _complete="true"/>
+    </outline>
+    <outline title="Articles"/>
+  </body>
+</opml>"#;
+
+    #[test]
+    fn test_outline_tree_becomes_nodes() {
+        let docs = OpmlImporter.import("export", SAMPLE).unwrap();
+        let doc = &docs[0];
+        assert_eq!(doc.title, "Reading & Notes");
+
+        let books = doc.nodes.iter().find(|n| n.content == "Books").unwrap();
+        assert!(books.parent_id.is_none());
+
+        let dune = doc.nodes.iter().find(|n| n.content == "Dune").unwrap();
+        assert_eq!(dune.parent_id.as_deref(), Some(books.id.as_str()));
+
+        let note = doc
+            .nodes
+            .iter()
+            .find(|n| n.content == "Re-read in winter")
+            .unwrap();
+        assert_eq!(note.parent_id.as_deref(), Some(dune.id.as_str()));
+
+        let done = doc
+            .nodes
+            .iter()
+            .find(|n| n.content == "Neuromancer")
+            .unwrap();
+        assert_eq!(done.node_type, "task");
+        assert_eq!(done.properties["status"], "done");
+
+        let articles = doc.nodes.iter().find(|n| n.content == "Articles").unwrap();
+        assert!(articles.parent_id.is_none());
+        assert_eq!(articles.order, "a1");
+    }
+
+    #[test]
+    fn test_rejects_non_opml() {
+        assert!(OpmlImporter.import("x", "<html></html>").is_err());
+        assert!(OpmlImporter.import("x", "<opml><body><outline").is_err());
+    }
+}
@@ -0,0 +1,488 @@
+//! Roam Research / Logseq JSON importer
+//!
+//! Both tools export a list of pages holding nested blocks:
+//!
+//! - **Roam**: top-level array of `{ title, children, create-time, edit-time }`
+//!   pages; blocks carry `string`, `uid`, `heading` and `children`.
+//! - **Logseq**: `{ "blocks": [...] }` with `page-name` pages; blocks carry
+//!   `content`, `id` and `children`, plus inline `key:: value` property lines.
+//!
+//! Pages become document roots and blocks become nested nodes. `TODO`/`DONE`
+//! markers (Roam's `{{[[TODO]]}}` and Logseq's `TODO`/`DOING`/`DONE`/...) turn
+//! blocks into tasks. `((block-uid))` references and `[[Page]]` links that
+//! resolve inside the export are rewritten to `nodespace://` links and emitted
+//! as mention edges. Roam's millisecond `create-time`/`edit-time` (Logseq's
+//! `created-at`/`updated-at`) are carried over as node timestamps.
+
+use super::{ImportError, ImportedDocument, Importer, NodeTreeBuilder};
+use chrono::{DateTime, TimeZone, Utc};
+use regex::Regex;
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// Imports Roam and Logseq `.json` exports
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OutlinerJsonImporter;
+
+/// Longest block text used as the label of a rewritten block reference
+const BLOCK_REF_LABEL_MAX_CHARS: usize = 80;
+
+/// Export-format-independent page
+struct Page {
+    title: String,
+    created: Option<DateTime<Utc>>,
+    edited: Option<DateTime<Utc>>,
+    children: Vec<Value>,
+}
+
+/// Where a block was placed during the first pass
+struct PlacedBlock {
+    doc: usize,
+    node: usize,
+}
+
+impl Importer for OutlinerJsonImporter {
+    fn name(&self) -> &'static str {
+        "Roam/Logseq JSON"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["json"]
+    }
+
+    fn import(
+        &self,
+        _file_stem: &str,
+        content: &str,
+    ) -> Result<Vec<ImportedDocument>, ImportError> {
+        let value: Value = serde_json::from_str(content)
+            .map_err(|e| ImportError::parse(self.name(), e.to_string()))?;
+        let pages = read_pages(&value).ok_or_else(|| {
+            ImportError::parse(
+                self.name(),
+                "unrecognised export (expected a Roam page array or Logseq `blocks` object)",
+            )
+        })?;
+
+        // Pass 1: build the trees, remembering where every block uid landed
+        let mut docs = Vec::with_capacity(pages.len());
+        let mut blocks_by_uid: HashMap<String, PlacedBlock> = HashMap::new();
+        let mut roots_by_title: HashMap<String, String> = HashMap::new();
+
+        for page in pages {
+            let mut builder = NodeTreeBuilder::default();
+            let mut timestamps = Vec::new();
+            let mut uids = Vec::new();
+            for block in &page.children {
+                add_block(&mut builder, None, block, &mut timestamps, &mut uids);
+            }
+
+            let mut doc = ImportedDocument::new(page.title.clone(), builder.into_nodes());
+            if let Some((created, edited)) = resolve_times(page.created, page.edited) {
+                doc.timestamps.push((doc.root_id.clone(), created, edited));
+            }
+            doc.timestamps.extend(timestamps);
+
+            let doc_index = docs.len();
+            for (uid, node_index) in uids {
+                blocks_by_uid.insert(
+                    uid,
+                    PlacedBlock {
+                        doc: doc_index,
+                        node: node_index,
+                    },
+                );
+            }
+            roots_by_title.insert(page.title.to_lowercase(), doc.root_id.clone());
+            docs.push(doc);
+        }
+
+        // Snapshot labels before rewriting so nested refs don't cascade
+        let ref_targets: HashMap<&str, (String, String)> = blocks_by_uid
+            .iter()
+            .map(|(uid, placed)| {
+                let node = &docs[placed.doc].nodes[placed.node];
+                (
+                    uid.as_str(),
+                    (node.id.clone(), block_ref_label(&node.content)),
+                )
+            })
+            .collect();
+
+        // Pass 2: rewrite references and collect mention edges
+        let mut rewrites = Vec::new();
+        for (doc_index, doc) in docs.iter().enumerate() {
+            for (node_index, node) in doc.nodes.iter().enumerate() {
+                let (content, targets) = rewrite_refs(&node.content, &ref_targets, &roots_by_title);
+                if !targets.is_empty() {
+                    rewrites.push((doc_index, node_index, content, targets));
+                }
+            }
+        }
+        for (doc_index, node_index, content, targets) in rewrites {
+            let doc = &mut docs[doc_index];
+            let source = doc.nodes[node_index].id.clone();
+            doc.nodes[node_index].content = content;
+            doc.mentions
+                .extend(targets.into_iter().map(|target| (source.clone(), target)));
+        }
+
+        Ok(docs)
+    }
+}
+
+/// Normalise Roam or Logseq exports into pages
+fn read_pages(value: &Value) -> Option<Vec<Page>> {
+    let (pages, title_key) = match value {
+        Value::Array(pages) => (pages, "title"),
+        Value::Object(obj) => (obj.get("blocks")?.as_array()?, "page-name"),
+        _ => return None,
+    };
+
+    Some(
+        pages
+            .iter()
+            .filter_map(|page| {
+                let title = page.get(title_key)?.as_str()?.trim();
+                if title.is_empty() {
+                    return None;
+                }
+                Some(Page {
+                    title: title.to_string(),
+                    created: timestamp(page, &["create-time", "created-at"]),
+                    edited: timestamp(page, &["edit-time", "updated-at"]),
+                    children: children(page).to_vec(),
+                })
+            })
+            .collect(),
+    )
+}
+
+fn children(block: &Value) -> &[Value] {
+    block
+        .get("children")
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+}
+
+/// Read a millisecond epoch timestamp from the first present key
+fn timestamp(obj: &Value, keys: &[&str]) -> Option<DateTime<Utc>> {
+    keys.iter()
+        .find_map(|k| obj.get(*k).and_then(Value::as_i64))
+        .and_then(|ms| Utc.timestamp_millis_opt(ms).single())
+}
+
+/// Fill a missing created/edited time from the other one
+fn resolve_times(
+    created: Option<DateTime<Utc>>,
+    edited: Option<DateTime<Utc>>,
+) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+    match (created, edited) {
+        (Some(c), Some(e)) => Some((c, e)),
+        (Some(t), None) | (None, Some(t)) => Some((t, t)),
+        (None, None) => None,
+    }
+}
+
+/// Add a block and its descendants, recording `(uid, node index)` pairs
+fn add_block(
+    builder: &mut NodeTreeBuilder,
+    parent: Option<&str>,
+    block: &Value,
+    timestamps: &mut Vec<(String, DateTime<Utc>, DateTime<Utc>)>,
+    uids: &mut Vec<(String, usize)>,
+) {
+    let raw = block
+        .get("string")
+        .or_else(|| block.get("content"))
+        .and_then(Value::as_str)
+        .unwrap_or("");
+    let (text, mut properties) = split_logseq_properties(raw);
+    let inline_id = properties
+        .remove("id")
+        .and_then(|v| v.as_str().map(str::to_string));
+    let uid = block
+        .get("uid")
+        .or_else(|| block.get("id"))
+        .and_then(Value::as_str)
+        .map(str::to_string)
+        .or(inline_id);
+
+    let kids = children(block);
+    if text.is_empty() && kids.is_empty() {
+        return;
+    }
+
+    let heading = block
+        .get("heading")
+        .and_then(Value::as_u64)
+        .filter(|h| (1..=6).contains(h));
+    let (node_type, content) = if let Some((status, rest)) = task_marker(&text) {
+        properties.insert("status".to_string(), json!(status));
+        ("task", rest.to_string())
+    } else if let Some(level) = heading {
+        ("header", format!("{} {}", "#".repeat(level as usize), text))
+    } else if text.starts_with('#') && text.trim_start_matches('#').starts_with(' ') {
+        ("header", text)
+    } else {
+        ("text", text)
+    };
+
+    let node_index = builder.len();
+    let id = builder.push(parent, node_type, content, Value::Object(properties));
+
+    if let Some((created, edited)) = resolve_times(
+        timestamp(block, &["create-time", "created-at"]),
+        timestamp(block, &["edit-time", "updated-at"]),
+    ) {
+        timestamps.push((id.clone(), created, edited));
+    }
+    if let Some(uid) = uid {
+        uids.push((uid, node_index));
+    }
+
+    for child in kids {
+        add_block(builder, Some(&id), child, timestamps, uids);
+    }
+}
+
+/// Strip Logseq `key:: value` lines, returning the remaining text and properties
+///
+/// `collapsed` is UI state and dropped; `id` is kept so the caller can use it
+/// as the block uid.
+fn split_logseq_properties(raw: &str) -> (String, Map<String, Value>) {
+    let mut properties = Map::new();
+    let mut lines = Vec::new();
+    for line in raw.lines() {
+        match line.trim().split_once(":: ") {
+            Some((key, value))
+                if !key.is_empty()
+                    && key
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') =>
+            {
+                if key != "collapsed" {
+                    properties.insert(key.replace('-', "_"), json!(value.trim()));
+                }
+            }
+            _ => lines.push(line),
+        }
+    }
+    (lines.join("\n").trim().to_string(), properties)
+}
+
+/// Detect a leading task marker, returning the task status and remaining text
+fn task_marker(text: &str) -> Option<(&'static str, &str)> {
+    const MARKERS: &[(&str, &str)] = &[
+        ("{{[[TODO]]}}", "open"),
+        ("{{[[DONE]]}}", "done"),
+        ("{{TODO}}", "open"),
+        ("{{DONE}}", "done"),
+        ("TODO ", "open"),
+        ("LATER ", "open"),
+        ("NOW ", "in_progress"),
+        ("DOING ", "in_progress"),
+        ("DONE ", "done"),
+        ("CANCELED ", "cancelled"),
+        ("CANCELLED ", "cancelled"),
+    ];
+    MARKERS
+        .iter()
+        .find_map(|(marker, status)| Some((*status, text.strip_prefix(marker)?.trim_start())))
+}
+
+/// Plain single-line label for a block reference
+fn block_ref_label(content: &str) -> String {
+    let line = content
+        .lines()
+        .next()
+        .unwrap_or("")
+        .trim_start_matches('#')
+        .trim()
+        .replace(['[', ']'], "");
+    match line.char_indices().nth(BLOCK_REF_LABEL_MAX_CHARS) {
+        Some((idx, _)) => format!("{}…", &line[..idx]),
+        None => line,
+    }
+}
+
+fn ref_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r"\(\(([A-Za-z0-9_-]+)\)\)|\[\[([^\[\]]+)\]\]").expect("valid ref regex")
+    })
+}
+
+/// Rewrite resolvable `((uid))` and `[[Page]]` references to nodespace links
+///
+/// Returns the new content and the referenced node IDs (deduplicated).
+fn rewrite_refs(
+    content: &str,
+    blocks: &HashMap<&str, (String, String)>,
+    pages: &HashMap<String, String>,
+) -> (String, Vec<String>) {
+    let mut targets: Vec<String> = Vec::new();
+    let rewritten = ref_regex().replace_all(content, |caps: &regex::Captures| {
+        let resolved = if let Some(uid) = caps.get(1) {
+            blocks
+                .get(uid.as_str())
+                .map(|(id, label)| (label.clone(), id.clone()))
+        } else {
+            let title = &caps[2];
+            pages
+                .get(&title.to_lowercase())
+                .map(|id| (title.to_string(), id.clone()))
+        };
+        match resolved {
+            Some((label, id)) => {
+                if !targets.contains(&id) {
+                    targets.push(id.clone());
+                }
+                format!("[{}](nodespace://{})", label, id)
+            }
+            None => caps[0].to_string(),
+        }
+    });
+    (rewritten.into_owned(), targets)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ROAM: &str = r#"[
+      {
+        "title": "Project X",
+        "create-time": 1700000000000,
+        "edit-time": 1700000500000,
+        "children": [
+          {"string": "Goals", "uid": "g1", "heading": 2, "create-time": 1700000100000,
+           "children": [
+             {"string": "{{[[TODO]]}} Draft spec", "uid": "t1"},
+             {"string": "{{[[DONE]]}} Kickoff", "uid": "t2"}
+           ]},
+          {"string": "See ((t1)) and [[Reading]]", "uid": "r1"}
+        ]
+      },
+      {"title": "Reading", "children": [{"string": "Ref back to ((g1))", "uid": "b1"}]}
+    ]"#;
+
+    fn find<'a>(
+        doc: &'a ImportedDocument,
+        prefix: &str,
+    ) -> &'a crate::mcp::handlers::markdown::PreparedNode {
+        doc.nodes
+            .iter()
+            .find(|n| n.content.starts_with(prefix))
+            .unwrap_or_else(|| panic!("no node starting with {prefix}"))
+    }
+
+    #[test]
+    fn test_roam_pages_blocks_and_tasks() {
+        let docs = OutlinerJsonImporter.import("export", ROAM).unwrap();
+        assert_eq!(docs.len(), 2);
+        assert_eq!(docs[0].root_content(), "# Project X");
+
+        let goals = find(&docs[0], "## Goals");
+        assert_eq!(goals.node_type, "header");
+        assert!(goals.parent_id.is_none());
+
+        let todo = find(&docs[0], "Draft spec");
+        assert_eq!(todo.node_type, "task");
+        assert_eq!(todo.properties["status"], "open");
+        assert_eq!(todo.parent_id.as_deref(), Some(goals.id.as_str()));
+        assert_eq!(find(&docs[0], "Kickoff").properties["status"], "done");
+    }
+
+    #[test]
+    fn test_block_refs_become_links_and_mentions() {
+        let docs = OutlinerJsonImporter.import("export", ROAM).unwrap();
+        let todo_id = find(&docs[0], "Draft spec").id.clone();
+        let goals_id = find(&docs[0], "## Goals").id.clone();
+
+        let see = find(&docs[0], "See ");
+        assert_eq!(
+            see.content,
+            format!(
+                "See [Draft spec](nodespace://{}) and [Reading](nodespace://{})",
+                todo_id, docs[1].root_id
+            )
+        );
+        assert!(docs[0].mentions.contains(&(see.id.clone(), todo_id)));
+        assert!(docs[0]
+            .mentions
+            .contains(&(see.id.clone(), docs[1].root_id.clone())));
+
+        // Cross-page reference lands on the referencing document
+        let back = find(&docs[1], "Ref back");
+        assert_eq!(docs[1].mentions, vec![(back.id.clone(), goals_id)]);
+    }
+
+    #[test]
+    fn test_roam_timestamps_preserved() {
+        let docs = OutlinerJsonImporter.import("export", ROAM).unwrap();
+        let root_ts = docs[0]
+            .timestamps
+            .iter()
+            .find(|(id, _, _)| id == &docs[0].root_id)
+            .unwrap();
+        assert_eq!(root_ts.1.timestamp_millis(), 1_700_000_000_000);
+        assert_eq!(root_ts.2.timestamp_millis(), 1_700_000_500_000);
+
+        let goals_id = &find(&docs[0], "## Goals").id;
+        let goals_ts = docs[0]
+            .timestamps
+            .iter()
+            .find(|(id, _, _)| id == goals_id)
+            .unwrap();
+        assert_eq!(goals_ts.1, goals_ts.2);
+    }
+
+    #[test]
+    fn test_logseq_export() {
+        let logseq = r##"{"version": 1, "blocks": [
+          {"id": "p1", "page-name": "Daily", "children": [
+            {"id": "6650a1", "content": "DOING Write importer\ncollapsed:: true\npriority:: high",
+             "children": [{"id": "6650a2", "content": "Notes for ((6650a1))", "children": []}]},
+            {"id": "6650a3", "content": "# Section", "children": []}
+          ]}
+        ]}"##;
+
+        let docs = OutlinerJsonImporter.import("export", logseq).unwrap();
+        let doc = &docs[0];
+        assert_eq!(doc.title, "Daily");
+
+        let task = find(doc, "Write importer");
+        assert_eq!(task.node_type, "task");
+        assert_eq!(task.properties["status"], "in_progress");
+        assert_eq!(task.properties["priority"], "high");
+        assert!(task.properties.get("collapsed").is_none());
+
+        let notes = find(doc, "Notes for");
+        assert_eq!(
+            notes.content,
+            format!("Notes for [Write importer](nodespace://{})", task.id)
+        );
+        assert_eq!(find(doc, "# Section").node_type, "header");
+    }
+
+    #[test]
+    fn test_unresolved_refs_are_left_alone() {
+        let docs = OutlinerJsonImporter
+            .import(
+                "x",
+                r#"[{"title": "P", "children": [{"string": "((zzz)) [[Nowhere]]"}]}]"#,
+            )
+            .unwrap();
+        assert_eq!(docs[0].nodes[0].content, "((zzz)) [[Nowhere]]");
+        assert!(docs[0].mentions.is_empty());
+    }
+
+    #[test]
+    fn test_rejects_other_json() {
+        assert!(OutlinerJsonImporter.import("x", r#"{"foo": 1}"#).is_err());
+        assert!(OutlinerJsonImporter.import("x", "not json").is_err());
+    }
+}
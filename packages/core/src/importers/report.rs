@@ -0,0 +1,177 @@
+//! Import report
+//!
+//! Summarises a multi-file import so the outcome stays visible in the graph
+//! after the progress UI is gone. The report becomes a header node with one
+//! child per file.
+
+use super::{ImportedDocument, NodeTreeBuilder};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::json;
+
+/// Outcome of importing one file
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportReportEntry {
+    pub file_path: String,
+    /// Importer that handled the file (None if no importer matched)
+    pub importer: Option<String>,
+    /// Root node IDs of the created documents
+    pub root_ids: Vec<String>,
+    pub nodes_created: usize,
+    pub error: Option<String>,
+}
+
+impl ImportReportEntry {
+    pub fn success(
+        file_path: impl Into<String>,
+        importer: &str,
+        root_ids: Vec<String>,
+        nodes_created: usize,
+    ) -> Self {
+        Self {
+            file_path: file_path.into(),
+            importer: Some(importer.to_string()),
+            root_ids,
+            nodes_created,
+            error: None,
+        }
+    }
+
+    pub fn failure(
+        file_path: impl Into<String>,
+        importer: Option<&str>,
+        error: impl Into<String>,
+    ) -> Self {
+        Self {
+            file_path: file_path.into(),
+            importer: importer.map(str::to_string),
+            root_ids: Vec::new(),
+            nodes_created: 0,
+            error: Some(error.into()),
+        }
+    }
+
+    pub fn is_success(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// Summary of a multi-file import
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportReport {
+    pub started_at: DateTime<Utc>,
+    pub entries: Vec<ImportReportEntry>,
+}
+
+impl Default for ImportReport {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ImportReport {
+    pub fn new() -> Self {
+        Self {
+            started_at: Utc::now(),
+            entries: Vec::new(),
+        }
+    }
+
+    pub fn push(&mut self, entry: ImportReportEntry) {
+        self.entries.push(entry);
+    }
+
+    pub fn successful(&self) -> usize {
+        self.entries.iter().filter(|e| e.is_success()).count()
+    }
+
+    pub fn failed(&self) -> usize {
+        self.entries.len() - self.successful()
+    }
+
+    /// Render the report as a document
+    ///
+    /// Succeeded files link to their imported roots via `nodespace://` links,
+    /// as do failed files whose partially created nodes could not be removed.
+    pub fn to_document(&self) -> ImportedDocument {
+        let mut builder = NodeTreeBuilder::default();
+        for entry in &self.entries {
+            let file_name = std::path::Path::new(&entry.file_path)
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or(&entry.file_path);
+            let content = match (&entry.error, entry.root_ids.first()) {
+                (Some(err), Some(root)) => {
+                    format!("✗ [{}](nodespace://{}) — {}", file_name, root, err)
+                }
+                (Some(err), None) => format!("✗ {} — {}", file_name, err),
+                (None, Some(root)) => format!(
+                    "✓ [{}](nodespace://{}) — {} nodes",
+                    file_name, root, entry.nodes_created
+                ),
+                (None, None) => format!("✓ {} — {} nodes", file_name, entry.nodes_created),
+            };
+            builder.push(
+                None,
+                "text",
+                content,
+                json!({ "file_path": entry.file_path }),
+            );
+        }
+
+        let mut doc = ImportedDocument::new(
+            format!("Import report {}", self.started_at.format("%Y-%m-%d %H:%M")),
+            builder.into_nodes(),
+        );
+        doc.root_properties = json!({
+            "import_report": {
+                "successful": self.successful(),
+                "failed": self.failed(),
+            }
+        });
+        doc
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_document_lists_each_file() {
+        let mut report = ImportReport::new();
+        report.push(ImportReportEntry::success(
+            "/tmp/notes.md",
+            "Markdown",
+            vec!["root-1".to_string()],
+            12,
+        ));
+        report.push(ImportReportEntry::failure(
+            "/tmp/scan.pdf",
+            None,
+            "No importer registered for '.pdf' files",
+        ));
+        let mut partial = ImportReportEntry::failure("/tmp/inbox.mbox", Some("Email"), "disk full");
+        partial.root_ids = vec!["root-2".to_string()];
+        report.push(partial);
+
+        assert_eq!(report.successful(), 1);
+        assert_eq!(report.failed(), 2);
+
+        let doc = report.to_document();
+        assert!(doc.root_content().starts_with("# Import report "));
+        assert_eq!(doc.root_properties["import_report"]["failed"], 2);
+        assert_eq!(
+            doc.nodes[0].content,
+            "✓ [notes.md](nodespace://root-1) — 12 nodes"
+        );
+        assert_eq!(
+            doc.nodes[1].content,
+            "✗ scan.pdf — No importer registered for '.pdf' files"
+        );
+        assert_eq!(
+            doc.nodes[2].content,
+            "✗ [inbox.mbox](nodespace://root-2) — disk full"
+        );
+    }
+}
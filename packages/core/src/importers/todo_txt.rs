@@ -0,0 +1,351 @@
+//! todo.txt importer
+//!
+//! Parses [todo.txt](https://github.com/todotxt/todo.txt) files, one task per
+//! line. The format's fields map onto task nodes:
+//!
+//! | todo.txt | NodeSpace |
+//! |----------|-----------|
+//! | `x` (+ completion date) | `status: done`, `completed_at` |
+//! | `(A)` / `(B)` / `(C)` and below | `priority: high` / `medium` / `low` |
+//! | creation date | node `created_at` |
+//! | `due:YYYY-MM-DD` | `due_date` |
+//! | `@context` | `contexts` array property |
+//! | `+project` | membership of the `project` collection |
+//!
+//! [`TodoTxtTask`] is shared with the todo.txt exporter so both directions
+//! agree on the mapping.
+//!
+//! Only files named `todo.txt` or `done.txt` are routed here; other `.txt`
+//! files are not task lists. The file is usually one a mobile app keeps in
+//! sync, so it is imported again and again: the document's ID comes from the
+//! file name and each task's ID from a hash of its line, so a re-import
+//! updates the same document, keeps unchanged tasks and replaces the tasks
+//! whose lines changed or went away.
+
+use super::{ImportError, ImportedDocument, Importer, NodeTreeBuilder};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde_json::{json, Map, Value};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fmt;
+
+/// Imports todo.txt files as a document of task nodes
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TodoTxtImporter;
+
+/// One parsed todo.txt line
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct TodoTxtTask {
+    pub done: bool,
+    /// Priority letter `A`-`Z`
+    pub priority: Option<char>,
+    pub completed: Option<NaiveDate>,
+    pub created: Option<NaiveDate>,
+    /// Description with projects, contexts and `due:` removed
+    pub description: String,
+    pub projects: Vec<String>,
+    pub contexts: Vec<String>,
+    pub due: Option<NaiveDate>,
+}
+
+impl TodoTxtTask {
+    /// Parse a line; returns `None` for blank lines
+    pub fn parse(line: &str) -> Option<Self> {
+        let mut task = Self::default();
+        let mut tokens = line.split_whitespace().peekable();
+        tokens.peek()?;
+
+        if tokens.peek() == Some(&"x") {
+            task.done = true;
+            tokens.next();
+        }
+        if let Some(letter) = tokens.peek().and_then(|t| parse_priority(t)) {
+            task.priority = Some(letter);
+            tokens.next();
+        }
+        // A done task may carry "completion creation"; an open task only "creation"
+        if let Some(first) = tokens.peek().and_then(|t| parse_date(t)) {
+            tokens.next();
+            match tokens.peek().and_then(|t| parse_date(t)) {
+                Some(second) if task.done => {
+                    tokens.next();
+                    task.completed = Some(first);
+                    task.created = Some(second);
+                }
+                _ if task.done => task.completed = Some(first),
+                _ => task.created = Some(first),
+            }
+        }
+
+        let mut words = Vec::new();
+        for token in tokens {
+            if let Some(project) = token.strip_prefix('+').filter(|p| !p.is_empty()) {
+                task.projects.push(project.to_string());
+            } else if let Some(context) = token.strip_prefix('@').filter(|c| !c.is_empty()) {
+                task.contexts.push(context.to_string());
+            } else if let Some(due) = token.strip_prefix("due:").and_then(parse_date) {
+                task.due = Some(due);
+            } else {
+                words.push(token);
+            }
+        }
+        task.description = words.join(" ");
+        Some(task)
+    }
+
+    /// NodeSpace priority for the letter (`A` high, `B` medium, anything lower is low)
+    pub fn node_priority(&self) -> Option<&'static str> {
+        self.priority.map(|p| match p {
+            'A' => "high",
+            'B' => "medium",
+            _ => "low",
+        })
+    }
+
+    /// Task properties for this line (flat, as accepted by bulk creation)
+    pub fn to_properties(&self) -> Value {
+        let mut props = Map::new();
+        props.insert(
+            "status".to_string(),
+            json!(if self.done { "done" } else { "open" }),
+        );
+        if let Some(priority) = self.node_priority() {
+            props.insert("priority".to_string(), json!(priority));
+        }
+        if let Some(due) = self.due {
+            props.insert("due_date".to_string(), json!(date_to_rfc3339(due)));
+        }
+        if let Some(completed) = self.completed {
+            props.insert(
+                "completed_at".to_string(),
+                json!(date_to_rfc3339(completed)),
+            );
+        }
+        if !self.contexts.is_empty() {
+            props.insert("contexts".to_string(), json!(self.contexts));
+        }
+        Value::Object(props)
+    }
+}
+
+impl fmt::Display for TodoTxtTask {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut parts: Vec<String> = Vec::new();
+        if self.done {
+            parts.push("x".to_string());
+        }
+        if let Some(p) = self.priority {
+            parts.push(format!("({})", p));
+        }
+        // The spec only allows a completion date together with a creation date
+        if let (true, Some(completed), Some(_)) = (self.done, self.completed, self.created) {
+            parts.push(completed.to_string());
+        }
+        if let Some(created) = self.created {
+            parts.push(created.to_string());
+        }
+        if !self.description.is_empty() {
+            parts.push(self.description.clone());
+        }
+        parts.extend(self.projects.iter().map(|p| format!("+{}", p)));
+        parts.extend(self.contexts.iter().map(|c| format!("@{}", c)));
+        if let Some(due) = self.due {
+            parts.push(format!("due:{}", due));
+        }
+        write!(f, "{}", parts.join(" "))
+    }
+}
+
+impl Importer for TodoTxtImporter {
+    fn name(&self) -> &'static str {
+        "todo.txt"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    fn file_names(&self) -> &'static [&'static str] {
+        &["todo.txt", "done.txt"]
+    }
+
+    fn import(&self, file_stem: &str, content: &str) -> Result<Vec<ImportedDocument>, ImportError> {
+        let file_key = file_stem.to_lowercase();
+        let mut builder = NodeTreeBuilder::default();
+        let mut memberships = Vec::new();
+        let mut timestamps = Vec::new();
+        // Identical lines are distinct tasks; the nth copy keeps the nth ID
+        let mut copies: HashMap<&str, usize> = HashMap::new();
+
+        for line in content.lines() {
+            let Some(task) = TodoTxtTask::parse(line) else {
+                continue;
+            };
+            let raw = line.trim();
+            let copy = copies.entry(raw).or_insert(0);
+            *copy += 1;
+            let id = derived_id(&[&file_key, raw, &copy.to_string()]);
+            let id = builder.push_with_id(
+                id,
+                None,
+                "task",
+                task.description.clone(),
+                task.to_properties(),
+            );
+            memberships.extend(task.projects.iter().map(|p| (id.clone(), p.clone())));
+            if let Some(created) = task.created {
+                let created = date_to_utc(created);
+                timestamps.push((id, created, created));
+            }
+        }
+
+        let mut doc = ImportedDocument::new(file_stem, builder.into_nodes());
+        doc.root_id = derived_id(&[&file_key]);
+        doc.memberships = memberships;
+        doc.timestamps = timestamps;
+        doc.sync = true;
+        Ok(vec![doc])
+    }
+}
+
+/// Node ID that is the same on every import: a version 8 UUID made from the
+/// SHA-256 of `parts`
+fn derived_id(parts: &[&str]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(b"todo.txt");
+    for part in parts {
+        hasher.update([0]);
+        hasher.update(part.as_bytes());
+    }
+    let digest = hasher.finalize();
+    let mut bytes = [0; 16];
+    bytes.copy_from_slice(&digest[..16]);
+    uuid::Builder::from_custom_bytes(bytes)
+        .into_uuid()
+        .to_string()
+}
+
+fn parse_priority(token: &str) -> Option<char> {
+    let inner = token.strip_prefix('(')?.strip_suffix(')')?;
+    let mut chars = inner.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) if c.is_ascii_uppercase() => Some(c),
+        _ => None,
+    }
+}
+
+fn parse_date(token: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(token, "%Y-%m-%d").ok()
+}
+
+fn date_to_utc(date: NaiveDate) -> DateTime<Utc> {
+    date.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc()
+}
+
+fn date_to_rfc3339(date: NaiveDate) -> String {
+    date_to_utc(date).to_rfc3339()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_full_line() {
+        let task = TodoTxtTask::parse(
+            "x (A) 2024-03-02 2024-03-01 Call mom +Family @phone due:2024-03-05",
+        )
+        .unwrap();
+
+        assert!(task.done);
+        assert_eq!(task.priority, Some('A'));
+        assert_eq!(task.completed, NaiveDate::from_ymd_opt(2024, 3, 2));
+        assert_eq!(task.created, NaiveDate::from_ymd_opt(2024, 3, 1));
+        assert_eq!(task.description, "Call mom");
+        assert_eq!(task.projects, vec!["Family"]);
+        assert_eq!(task.contexts, vec!["phone"]);
+        assert_eq!(task.due, NaiveDate::from_ymd_opt(2024, 3, 5));
+
+        assert_eq!(
+            task.to_string(),
+            "x (A) 2024-03-02 2024-03-01 Call mom +Family @phone due:2024-03-05"
+        );
+    }
+
+    #[test]
+    fn test_open_task_date_is_creation_date() {
+        let task = TodoTxtTask::parse("2024-01-10 Buy milk").unwrap();
+        assert!(!task.done);
+        assert_eq!(task.created, NaiveDate::from_ymd_opt(2024, 1, 10));
+        assert_eq!(task.completed, None);
+        assert_eq!(task.priority, None);
+        assert_eq!(TodoTxtTask::parse("   "), None);
+    }
+
+    #[test]
+    fn test_import_maps_properties_and_collections() {
+        let content = "(B) Plan trip +Travel @computer due:2024-06-01\n\n\
+                       x 2024-05-02 2024-05-01 Book hotel +Travel\n\
+                       (D) Someday item";
+        let docs = TodoTxtImporter.import("todo", content).unwrap();
+        let doc = &docs[0];
+
+        assert_eq!(doc.title, "todo");
+        assert_eq!(doc.nodes.len(), 3);
+
+        let plan = &doc.nodes[0];
+        assert_eq!(plan.node_type, "task");
+        assert_eq!(plan.content, "Plan trip");
+        assert_eq!(plan.properties["priority"], "medium");
+        assert_eq!(plan.properties["status"], "open");
+        assert_eq!(plan.properties["due_date"], "2024-06-01T00:00:00+00:00");
+        assert_eq!(plan.properties["contexts"], json!(["computer"]));
+
+        let book = &doc.nodes[1];
+        assert_eq!(book.properties["status"], "done");
+        assert_eq!(book.properties["completed_at"], "2024-05-02T00:00:00+00:00");
+        assert_eq!(doc.timestamps.len(), 1);
+        assert_eq!(doc.timestamps[0].0, book.id);
+
+        assert_eq!(doc.nodes[2].properties["priority"], "low");
+
+        assert_eq!(
+            doc.memberships,
+            vec![
+                (plan.id.clone(), "Travel".to_string()),
+                (book.id.clone(), "Travel".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_reimport_keys_tasks_by_line() {
+        let ids = |content: &str| {
+            let doc = TodoTxtImporter.import("todo", content).unwrap().remove(0);
+            assert!(doc.sync);
+            let ids: Vec<String> = doc.nodes.into_iter().map(|n| n.id).collect();
+            (doc.root_id, ids)
+        };
+
+        let (root, first) = ids("Buy milk\nCall mom\nBuy milk");
+        assert!(uuid::Uuid::parse_str(&root).is_ok());
+        assert!(uuid::Uuid::parse_str(&first[0]).is_ok());
+        // Each copy of a repeated line is its own task
+        assert_ne!(first[0], first[2]);
+
+        // Unchanged lines keep their IDs wherever they move; a changed line is a new task
+        let (same_root, second) = ids("x Call mom\nBuy milk\nBuy milk");
+        assert_eq!(same_root, root);
+        assert_eq!(second[1], first[0]);
+        assert_eq!(second[2], first[2]);
+        assert!(!first.contains(&second[0]));
+
+        // Another file is another document
+        let done = TodoTxtImporter
+            .import("done", "Buy milk")
+            .unwrap()
+            .remove(0);
+        assert_ne!(done.root_id, root);
+        assert_ne!(done.nodes[0].id, first[0]);
+    }
+}
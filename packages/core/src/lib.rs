@@ -17,10 +17,20 @@
 //! - [`behaviors`] - Node type system and trait-based behaviors
 //! - [`services`] - Business services (NodeService, SchemaTableManager, etc.)
 //! - [`db`] - Database layer with SurrealDB integration
+//! - [`importers`] - File format importers (Markdown, OPML, iCalendar, ...)
+//! - [`exporters`] - File format exporters (HTML, todo.txt)
+//! - [`fixtures`] - Seedable synthetic workspaces for benchmarks and tests
+//! - [`i18n`] - Localizable user-facing messages (code + params, per-locale catalogs)
 //! - [`mcp`] - MCP stdio server for AI agent integration
+//! - [`api`] - Request/response DTOs shared by the Tauri, dev-proxy and MCP surfaces
 
+pub mod api;
 pub mod behaviors;
 pub mod db;
+pub mod exporters;
+pub mod fixtures;
+pub mod i18n;
+pub mod importers;
 pub mod mcp;
 pub mod models;
 pub mod services;
@@ -28,12 +38,13 @@ pub mod utils;
 
 // Re-exports
 pub use behaviors::{
-    CollectionNodeBehavior, CustomNodeBehavior, DateNodeBehavior, NodeBehavior,
-    NodeBehaviorRegistry, ProcessingError, TaskNodeBehavior, TextNodeBehavior,
+    CollectionNodeBehavior, CustomNodeBehavior, DateNodeBehavior, HighlightNodeBehavior,
+    MeetingNodeBehavior, MonthNodeBehavior, NodeBehavior, NodeBehaviorRegistry, ProcessingError,
+    ProjectNodeBehavior, QuarterNodeBehavior, TaskNodeBehavior, TextNodeBehavior, WeekNodeBehavior,
 };
 pub use db::{DatabaseError, DomainEvent, RelationshipEvent, RelationshipRecord, SurrealStore};
 pub use models::{
-    FilterOperator, Node, NodeFilter, NodeQuery, NodeUpdate, OrderBy, PropertyFilter, SchemaNode,
-    TaskNode, TaskNodeUpdate, TaskStatus, ValidationError,
+    FilterOperator, Node, NodeFilter, NodeQuery, NodeUpdate, OrderBy, Period, PropertyFilter,
+    SchemaNode, TaskNode, TaskNodeUpdate, TaskStatus, ValidationError,
 };
 pub use services::{CreateNodeParams, NodeService, NodeServiceError};
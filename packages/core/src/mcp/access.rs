@@ -0,0 +1,410 @@
+//! Agent Profiles - Per-Root Access Scoping
+//!
+//! An agent profile limits an MCP connection to the roots and collections it
+//! names, so an agent can manage a "work" collection without ever reading a
+//! journal. A connection selects its profile with a bearer token: the HTTP
+//! `Authorization` header, or [`MCP_TOKEN_ENV`] for the stdio transport.
+//!
+//! Enforcement happens in `tools/call`:
+//! - Every node ID argument is checked against the node's resolved root
+//!   before the tool runs
+//! - Listing results (`nodes`, `related_nodes`) are filtered afterwards
+//! - Workspace-wide writes (schema changes) are refused outright
+//!
+//! With no profiles configured the server stays unrestricted.
+
+use crate::mcp::types::MCPError;
+use crate::services::{parse_collection_path, CollectionPath, CollectionService, NodeService};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashSet;
+
+/// Environment variable holding the profile token for the stdio transport
+pub const MCP_TOKEN_ENV: &str = "NODESPACE_MCP_TOKEN";
+
+/// Which roots and collections an MCP connection may read or write
+///
+/// Write access implies read access. Collection scopes cover members of the
+/// collection and of its sub-collections, along with everything under those
+/// members' roots.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AgentProfile {
+    /// Display name, used in denial messages
+    pub name: String,
+    /// Bearer token that selects this profile
+    pub token: String,
+    /// Skip scoping entirely (e.g. for the owner's own assistant)
+    #[serde(default)]
+    pub full_access: bool,
+    /// Root node IDs the agent may read
+    #[serde(default)]
+    pub read_roots: Vec<String>,
+    /// Root node IDs the agent may read and write
+    #[serde(default)]
+    pub write_roots: Vec<String>,
+    /// Collection paths (e.g. "work:clients") the agent may read
+    #[serde(default)]
+    pub read_collections: Vec<String>,
+    /// Collection paths the agent may read and write
+    #[serde(default)]
+    pub write_collections: Vec<String>,
+}
+
+/// Pick the profile for a connection's token
+///
+/// Returns `None` when the connection is unrestricted: no profiles are
+/// configured, or the token selects a full-access profile. Once any profile
+/// exists, connections without a known token are refused.
+pub fn select_profile<'a>(
+    profiles: &'a [AgentProfile],
+    token: Option<&str>,
+) -> Result<Option<&'a AgentProfile>, MCPError> {
+    if profiles.is_empty() {
+        return Ok(None);
+    }
+
+    let token = token.ok_or_else(|| {
+        MCPError::access_denied("This server requires an agent profile token".to_string())
+    })?;
+    let profile = profiles
+        .iter()
+        .find(|profile| !profile.token.is_empty() && profile.token == token)
+        .ok_or_else(|| MCPError::access_denied("Unknown agent profile token".to_string()))?;
+
+    Ok((!profile.full_access).then_some(profile))
+}
+
+/// Token from an `Authorization: Bearer <token>` header value
+pub fn bearer_token(header: &str) -> Option<&str> {
+    header
+        .strip_prefix("Bearer ")
+        .map(str::trim)
+        .filter(|token| !token.is_empty())
+}
+
+/// Access a tool needs on a node
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Access {
+    Read,
+    Write,
+}
+
+impl Access {
+    fn as_str(self) -> &'static str {
+        match self {
+            Access::Read => "read",
+            Access::Write => "write",
+        }
+    }
+}
+
+/// Node ID arguments each tool targets, and the access they need
+///
+/// Returns `None` for tools a scoped agent may not call at all. Tools with no
+/// targets either touch no node content or have their results filtered.
+fn tool_targets(tool: &str) -> Option<&'static [(&'static str, Access)]> {
+    use Access::{Read, Write};
+
+    let targets: &'static [(&'static str, Access)] = match tool {
+        "search_tools"
+        | "get_relationship_graph"
+        | "get_inbound_relationships"
+        | "get_all_schemas"
+        | "query_nodes"
+        | "search_semantic"
+        | "get_overdue_tasks"
+        | "get_tasks_due_within"
+        | "get_nodes_batch"
+        | "update_nodes_batch"
+        | "create_nodes_from_markdown" => &[],
+
+        "get_node"
+        | "get_node_tree"
+        | "get_node_collections"
+        | "get_markdown_from_node_id"
+        | "get_related_nodes"
+        | "check_node_completeness"
+        | "summarize_node" => &[("node_id", Read)],
+        "get_children" | "get_child_at_index" => &[("parent_id", Read)],
+        "run_query" => &[("query_node_id", Read)],
+        "get_interaction_history" => &[("person_id", Read)],
+        "get_highlights" => &[("source_id", Read)],
+
+        "update_node" | "delete_node" | "set_node_appearance" | "move_child_to_index" => {
+            &[("node_id", Write)]
+        }
+        "create_node" => &[("parent_id", Write), ("root_id", Write)],
+        "insert_child_at_index" => &[("parent_id", Write)],
+        "update_root_from_markdown" => &[("root_id", Write)],
+        "extract_action_items" => &[("meeting_id", Write)],
+        "log_interaction" => &[("person_id", Write)],
+        "create_relationship" | "delete_relationship" => {
+            &[("source_id", Write), ("target_id", Read)]
+        }
+
+        _ => return None,
+    };
+    Some(targets)
+}
+
+/// Whether collection path `scope` is `path` or one of its ancestors
+fn path_covers(scope: &CollectionPath, path: &CollectionPath) -> bool {
+    scope.segments.len() <= path.segments.len()
+        && scope
+            .segments
+            .iter()
+            .zip(&path.segments)
+            .all(|(a, b)| a.normalized_name == b.normalized_name)
+}
+
+/// A profile resolved against the current workspace
+///
+/// Resolved per request so collection membership changes apply immediately.
+#[derive(Debug)]
+pub struct AccessScope {
+    name: String,
+    /// Root and collection node IDs readable by the agent
+    readable: HashSet<String>,
+    /// Root and collection node IDs writable by the agent
+    writable: HashSet<String>,
+    /// Collections the agent may create roots in or add nodes to
+    writable_paths: Vec<CollectionPath>,
+}
+
+impl AccessScope {
+    /// Expand a profile's collections into the root IDs they cover
+    pub async fn resolve<C>(
+        node_service: &NodeService<C>,
+        profile: &AgentProfile,
+    ) -> Result<Self, MCPError>
+    where
+        C: surrealdb::Connection,
+    {
+        let mut scope = Self {
+            name: profile.name.clone(),
+            readable: profile
+                .read_roots
+                .iter()
+                .chain(&profile.write_roots)
+                .cloned()
+                .collect(),
+            writable: profile.write_roots.iter().cloned().collect(),
+            writable_paths: Vec::new(),
+        };
+
+        let collection_service = CollectionService::new(&node_service.store, node_service);
+        let collections = profile
+            .read_collections
+            .iter()
+            .map(|path| (path, Access::Read))
+            .chain(
+                profile
+                    .write_collections
+                    .iter()
+                    .map(|path| (path, Access::Write)),
+            );
+        for (path, access) in collections {
+            let parsed = parse_collection_path(path).map_err(|e| {
+                MCPError::internal_error(format!(
+                    "Agent profile '{}' has an invalid collection path: {}",
+                    profile.name, e
+                ))
+            })?;
+            if access == Access::Write {
+                scope.writable_paths.push(parsed);
+            }
+
+            // Collections that don't exist yet cover nothing until created
+            let Some(collection) = collection_service
+                .find_collection_by_path(path)
+                .await
+                .map_err(|e| MCPError::internal_error(e.to_string()))?
+            else {
+                continue;
+            };
+            let mut ids = collection_service
+                .get_collection_members_recursive(&collection.id)
+                .await
+                .map_err(|e| MCPError::internal_error(e.to_string()))?;
+            ids.push(collection.id);
+
+            if access == Access::Write {
+                scope.writable.extend(ids.iter().cloned());
+            }
+            scope.readable.extend(ids);
+        }
+
+        Ok(scope)
+    }
+
+    /// Whether the agent has `access` to a node, via the node itself or its root
+    pub async fn permits<C>(
+        &self,
+        node_service: &NodeService<C>,
+        node_id: &str,
+        access: Access,
+    ) -> Result<bool, MCPError>
+    where
+        C: surrealdb::Connection,
+    {
+        let allowed = match access {
+            Access::Read => &self.readable,
+            Access::Write => &self.writable,
+        };
+        if allowed.contains(node_id) {
+            return Ok(true);
+        }
+
+        let root_id = node_service
+            .get_root_id(node_id)
+            .await
+            .map_err(|e| MCPError::internal_error(e.to_string()))?;
+        Ok(allowed.contains(&root_id))
+    }
+
+    /// Fail with an access error unless [`permits`](Self::permits) allows it
+    pub(crate) async fn require<C>(
+        &self,
+        node_service: &NodeService<C>,
+        node_id: &str,
+        access: Access,
+    ) -> Result<(), MCPError>
+    where
+        C: surrealdb::Connection,
+    {
+        if self.permits(node_service, node_id, access).await? {
+            Ok(())
+        } else {
+            Err(MCPError::access_denied(format!(
+                "Agent profile '{}' has no {} access to node {}",
+                self.name,
+                access.as_str(),
+                node_id
+            )))
+        }
+    }
+
+    fn require_writable_path(&self, path: &str) -> Result<(), MCPError> {
+        let path = parse_collection_path(path)
+            .map_err(|e| MCPError::invalid_params(format!("Invalid collection path: {}", e)))?;
+        if self
+            .writable_paths
+            .iter()
+            .any(|scope| path_covers(scope, &path))
+        {
+            Ok(())
+        } else {
+            Err(MCPError::access_denied(format!(
+                "Agent profile '{}' cannot write to collection '{}'",
+                self.name, path.original
+            )))
+        }
+    }
+
+    /// Check a tool call against the scope before it runs
+    pub async fn authorize<C>(
+        &self,
+        node_service: &NodeService<C>,
+        tool: &str,
+        arguments: &Value,
+    ) -> Result<(), MCPError>
+    where
+        C: surrealdb::Connection,
+    {
+        let targets = tool_targets(tool).ok_or_else(|| {
+            MCPError::access_denied(format!(
+                "Tool '{}' is not available to agent profile '{}'",
+                tool, self.name
+            ))
+        })?;
+        for (key, access) in targets {
+            if let Some(node_id) = arguments[*key].as_str() {
+                self.require(node_service, node_id, *access).await?;
+            }
+        }
+
+        match tool {
+            "create_node" => {
+                if let Some(path) = arguments["collection"].as_str() {
+                    self.require_writable_path(path)?;
+                } else if arguments["parent_id"].is_null() && arguments["root_id"].is_null() {
+                    return Err(MCPError::access_denied(format!(
+                        "Agent profile '{}' may only create root nodes inside its writable collections",
+                        self.name
+                    )));
+                }
+            }
+            "create_nodes_from_markdown" => match arguments["collection"].as_str() {
+                Some(path) => self.require_writable_path(path)?,
+                None => {
+                    return Err(MCPError::access_denied(format!(
+                        "Agent profile '{}' must import into one of its writable collections",
+                        self.name
+                    )))
+                }
+            },
+            "update_node" => {
+                if let Some(path) = arguments["add_to_collection"].as_str() {
+                    self.require_writable_path(path)?;
+                }
+                if let Some(collection_id) = arguments["remove_from_collection"].as_str() {
+                    self.require(node_service, collection_id, Access::Write)
+                        .await?;
+                }
+            }
+            "get_nodes_batch" => {
+                let ids = arguments["node_ids"].as_array().into_iter().flatten();
+                for node_id in ids.filter_map(Value::as_str) {
+                    self.require(node_service, node_id, Access::Read).await?;
+                }
+            }
+            "update_nodes_batch" => {
+                let updates = arguments["updates"].as_array().into_iter().flatten();
+                for node_id in updates.filter_map(|update| update["id"].as_str()) {
+                    self.require(node_service, node_id, Access::Write).await?;
+                }
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    /// Drop listed nodes the agent may not read, keeping `count` in step
+    pub async fn filter_result<C>(
+        &self,
+        node_service: &NodeService<C>,
+        mut data: Value,
+    ) -> Result<Value, MCPError>
+    where
+        C: surrealdb::Connection,
+    {
+        for key in ["nodes", "related_nodes"] {
+            let Some(items) = data.get_mut(key).and_then(Value::as_array_mut) else {
+                continue;
+            };
+
+            let mut visible = Vec::with_capacity(items.len());
+            for item in std::mem::take(items) {
+                let readable = match item["id"].as_str() {
+                    Some(node_id) => self.permits(node_service, node_id, Access::Read).await?,
+                    None => false,
+                };
+                if readable {
+                    visible.push(item);
+                }
+            }
+
+            let count = visible.len();
+            data[key] = Value::Array(visible);
+            if data.get("count").is_some() {
+                data["count"] = count.into();
+            }
+        }
+        Ok(data)
+    }
+}
+
+#[cfg(test)]
+#[path = "access_test.rs"]
+mod access_test;
@@ -0,0 +1,220 @@
+//! Tests for MCP agent profiles
+//!
+//! Verifies profile selection by token and root-based scoping of tool calls.
+
+#[cfg(test)]
+mod selection_tests {
+    use crate::mcp::access::{bearer_token, select_profile, AgentProfile};
+    use crate::mcp::types::ACCESS_DENIED;
+
+    fn profile(name: &str, token: &str) -> AgentProfile {
+        AgentProfile {
+            name: name.to_string(),
+            token: token.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_no_profiles_is_unrestricted() {
+        assert_eq!(select_profile(&[], None).unwrap(), None);
+        assert_eq!(select_profile(&[], Some("anything")).unwrap(), None);
+    }
+
+    #[test]
+    fn test_token_selects_profile() {
+        let owner = AgentProfile {
+            full_access: true,
+            ..profile("owner", "owner-token")
+        };
+        let profiles = vec![profile("work-agent", "work-token"), owner];
+
+        let selected = select_profile(&profiles, Some("work-token")).unwrap();
+        assert_eq!(selected.map(|p| p.name.as_str()), Some("work-agent"));
+        assert_eq!(
+            select_profile(&profiles, Some("owner-token")).unwrap(),
+            None
+        );
+
+        for token in [None, Some("wrong"), Some("")] {
+            let error = select_profile(&profiles, token).unwrap_err();
+            assert_eq!(error.code, ACCESS_DENIED);
+        }
+    }
+
+    #[test]
+    fn test_bearer_token() {
+        assert_eq!(bearer_token("Bearer abc123"), Some("abc123"));
+        assert_eq!(bearer_token("Bearer "), None);
+        assert_eq!(bearer_token("Basic abc123"), None);
+    }
+}
+
+#[cfg(test)]
+mod scope_tests {
+    use crate::db::SurrealStore;
+    use crate::mcp::access::{Access, AccessScope, AgentProfile};
+    use crate::mcp::types::ACCESS_DENIED;
+    use crate::services::{CollectionService, CreateNodeParams};
+    use crate::NodeService;
+    use serde_json::json;
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    async fn setup_test_service() -> Result<(Arc<NodeService>, TempDir), Box<dyn std::error::Error>>
+    {
+        let temp_dir = TempDir::new()?;
+        let db_path = temp_dir.path().join("test.db");
+
+        let mut store = Arc::new(SurrealStore::new(db_path).await?);
+        let node_service = Arc::new(NodeService::new(&mut store).await?);
+        Ok((node_service, temp_dir))
+    }
+
+    async fn create(node_service: &NodeService, content: &str, parent_id: Option<&str>) -> String {
+        node_service
+            .create_node_with_parent(CreateNodeParams {
+                id: None,
+                node_type: "text".to_string(),
+                content: content.to_string(),
+                parent_id: parent_id.map(str::to_string),
+                insert_after_node_id: None,
+                properties: json!({}),
+            })
+            .await
+            .unwrap()
+    }
+
+    fn work_agent() -> AgentProfile {
+        AgentProfile {
+            name: "work-agent".to_string(),
+            token: "work-token".to_string(),
+            write_collections: vec!["Work".to_string()],
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_scope_follows_collection_roots() {
+        let (node_service, _temp) = setup_test_service().await.unwrap();
+
+        let project = create(&node_service, "Project plan", None).await;
+        let task = create(&node_service, "Ship it", Some(&project)).await;
+        let journal = create(&node_service, "Dear diary", None).await;
+        let entry = create(&node_service, "Private thoughts", Some(&journal)).await;
+        CollectionService::new(&node_service.store, &node_service)
+            .add_to_collection_by_path(&project, "work:clients")
+            .await
+            .unwrap();
+
+        let scope = AccessScope::resolve(&node_service, &work_agent())
+            .await
+            .unwrap();
+
+        // Descendants inherit access from the root in the collection
+        for node_id in [&project, &task] {
+            assert!(scope
+                .permits(&node_service, node_id, Access::Write)
+                .await
+                .unwrap());
+        }
+        for node_id in [&journal, &entry] {
+            assert!(!scope
+                .permits(&node_service, node_id, Access::Read)
+                .await
+                .unwrap());
+        }
+
+        scope
+            .authorize(&node_service, "get_node", &json!({ "node_id": task }))
+            .await
+            .unwrap();
+        let denied = scope
+            .authorize(&node_service, "get_node", &json!({ "node_id": entry }))
+            .await
+            .unwrap_err();
+        assert_eq!(denied.code, ACCESS_DENIED);
+
+        // Cross-root relationships may only point at readable nodes
+        assert!(scope
+            .authorize(
+                &node_service,
+                "create_relationship",
+                &json!({ "source_id": task, "target_id": journal, "relationship_name": "mentions" })
+            )
+            .await
+            .is_err());
+        assert!(scope
+            .authorize(
+                &node_service,
+                "get_nodes_batch",
+                &json!({ "node_ids": [project, entry] })
+            )
+            .await
+            .is_err());
+
+        // New roots must land in a writable collection
+        assert!(scope
+            .authorize(
+                &node_service,
+                "create_node",
+                &json!({ "node_type": "text", "content": "x", "collection": "work:clients:acme" })
+            )
+            .await
+            .is_ok());
+        for arguments in [
+            json!({ "node_type": "text", "content": "x" }),
+            json!({ "node_type": "text", "content": "x", "collection": "journal" }),
+        ] {
+            assert!(scope
+                .authorize(&node_service, "create_node", &arguments)
+                .await
+                .is_err());
+        }
+
+        // Workspace-wide schema changes are out of scope for any profile
+        assert!(scope
+            .authorize(&node_service, "update_schema", &json!({}))
+            .await
+            .is_err());
+
+        let listed = scope
+            .filter_result(
+                &node_service,
+                json!({
+                    "nodes": [{ "id": task }, { "id": entry }, { "id": journal }],
+                    "count": 3
+                }),
+            )
+            .await
+            .unwrap();
+        assert_eq!(listed["nodes"], json!([{ "id": task }]));
+        assert_eq!(listed["count"], 1);
+    }
+
+    #[tokio::test]
+    async fn test_read_roots_do_not_grant_write() {
+        let (node_service, _temp) = setup_test_service().await.unwrap();
+
+        let reference = create(&node_service, "Style guide", None).await;
+        let profile = AgentProfile {
+            read_roots: vec![reference.clone()],
+            ..work_agent()
+        };
+        let scope = AccessScope::resolve(&node_service, &profile).await.unwrap();
+
+        scope
+            .authorize(&node_service, "get_node", &json!({ "node_id": reference }))
+            .await
+            .unwrap();
+        let denied = scope
+            .authorize(
+                &node_service,
+                "update_node",
+                &json!({ "node_id": reference, "content": "rewritten" }),
+            )
+            .await
+            .unwrap_err();
+        assert!(denied.message.contains("no write access"));
+    }
+}
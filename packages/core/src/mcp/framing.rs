@@ -0,0 +1,360 @@
+//! Stdio Message Framing
+//!
+//! MCP clients delimit stdio messages in one of two ways:
+//!
+//! - **Newline**: one JSON message per line (the MCP stdio transport)
+//! - **Content-Length**: LSP-style `Content-Length: N` headers, a blank line,
+//!   then exactly N bytes of JSON
+//!
+//! With [`StdioFraming::Auto`] the framing is taken from the first message:
+//! JSON starts with `{` or `[`, a header with a letter. Responses are written
+//! in the framing the client uses.
+//!
+//! Messages over the size limit are skipped without being buffered, and the
+//! reader stays in sync with the stream.
+
+use crate::mcp::types::{MCPError, MCPResponse};
+use std::io::ErrorKind;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tracing::{info, warn};
+
+/// Longest header line accepted in Content-Length framing
+const MAX_HEADER_LINE_BYTES: usize = 1024;
+
+/// How messages are delimited on stdio
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum StdioFraming {
+    /// Detect from the first message
+    #[default]
+    Auto,
+    /// Newline-delimited JSON
+    Newline,
+    /// LSP-style `Content-Length` headers
+    ContentLength,
+}
+
+impl std::str::FromStr for StdioFraming {
+    type Err = String;
+
+    /// Parse `auto`, `newline` or `content-length`
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "auto" => Ok(StdioFraming::Auto),
+            "newline" => Ok(StdioFraming::Newline),
+            "content-length" => Ok(StdioFraming::ContentLength),
+            other => Err(format!(
+                "Unknown framing '{}' (expected auto, newline or content-length)",
+                other
+            )),
+        }
+    }
+}
+
+/// A message read from stdio
+#[derive(Debug)]
+pub(crate) enum Frame {
+    Message(String),
+    /// The frame can't be a message (too large, bad headers, not UTF-8);
+    /// carries the error response to send
+    Rejected(MCPResponse),
+}
+
+/// Reads framed messages, settling the framing on the first one when `Auto`
+pub(crate) struct FrameReader<R> {
+    reader: R,
+    framing: StdioFraming,
+    max_bytes: usize,
+}
+
+impl<R> FrameReader<R>
+where
+    R: AsyncBufRead + Unpin,
+{
+    pub(crate) fn new(reader: R, framing: StdioFraming, max_bytes: usize) -> Self {
+        Self {
+            reader,
+            framing,
+            max_bytes,
+        }
+    }
+
+    /// Framing in use; `Auto` until the first message arrives
+    pub(crate) fn framing(&self) -> StdioFraming {
+        self.framing
+    }
+
+    /// Next message, or `None` at EOF
+    pub(crate) async fn next(&mut self) -> std::io::Result<Option<Frame>> {
+        if self.framing == StdioFraming::Auto {
+            match detect_framing(&mut self.reader).await? {
+                Some(framing) => {
+                    info!("🔌 MCP stdio framing: {:?}", framing);
+                    self.framing = framing;
+                }
+                None => return Ok(None),
+            }
+        }
+        match self.framing {
+            StdioFraming::Auto | StdioFraming::Newline => {
+                read_line_frame(&mut self.reader, self.max_bytes).await
+            }
+            StdioFraming::ContentLength => {
+                read_content_length_frame(&mut self.reader, self.max_bytes).await
+            }
+        }
+    }
+}
+
+/// Write `response` in `framing` and flush
+pub(crate) async fn write_frame<W>(
+    writer: &mut W,
+    framing: StdioFraming,
+    response: &MCPResponse,
+) -> anyhow::Result<()>
+where
+    W: AsyncWrite + Unpin,
+{
+    let json = serde_json::to_string(response)?;
+    if framing == StdioFraming::ContentLength {
+        writer
+            .write_all(format!("Content-Length: {}\r\n\r\n", json.len()).as_bytes())
+            .await?;
+        writer.write_all(json.as_bytes()).await?;
+    } else {
+        writer.write_all(json.as_bytes()).await?;
+        writer.write_all(b"\n").await?;
+    }
+    writer.flush().await?;
+    Ok(())
+}
+
+/// Framing of the next message, after skipping leading whitespace
+async fn detect_framing<R>(reader: &mut R) -> std::io::Result<Option<StdioFraming>>
+where
+    R: AsyncBufRead + Unpin,
+{
+    loop {
+        let available = reader.fill_buf().await?;
+        let Some(&first) = available.first() else {
+            return Ok(None);
+        };
+        if first.is_ascii_whitespace() {
+            reader.consume(1);
+            continue;
+        }
+        return Ok(Some(if first.is_ascii_alphabetic() {
+            StdioFraming::ContentLength
+        } else {
+            StdioFraming::Newline
+        }));
+    }
+}
+
+/// Read the next newline-delimited message, buffering at most `max_bytes`
+///
+/// An over-long line is skipped up to its newline. Returns `None` at EOF.
+async fn read_line_frame<R>(reader: &mut R, max_bytes: usize) -> std::io::Result<Option<Frame>>
+where
+    R: AsyncBufRead + Unpin,
+{
+    let mut line = Vec::new();
+    let mut too_large = false;
+    loop {
+        let available = reader.fill_buf().await?;
+        if available.is_empty() {
+            if line.is_empty() && !too_large {
+                return Ok(None);
+            }
+            break;
+        }
+        let (chunk_len, complete) = match available.iter().position(|&byte| byte == b'\n') {
+            Some(end) => (end, true),
+            None => (available.len(), false),
+        };
+        if !too_large {
+            if line.len() + chunk_len > max_bytes {
+                too_large = true;
+                line = Vec::new();
+            } else {
+                line.extend_from_slice(&available[..chunk_len]);
+            }
+        }
+        reader.consume(chunk_len + usize::from(complete));
+        if complete {
+            break;
+        }
+    }
+
+    if too_large {
+        return Ok(Some(oversized(max_bytes)));
+    }
+    if line.last() == Some(&b'\r') {
+        line.pop();
+    }
+    Ok(Some(utf8_frame(line)))
+}
+
+/// Read the next `Content-Length` framed message
+///
+/// Headers other than `Content-Length` (e.g. `Content-Type`) are ignored. An
+/// oversized body is read and dropped. Returns `None` at EOF.
+async fn read_content_length_frame<R>(
+    reader: &mut R,
+    max_bytes: usize,
+) -> std::io::Result<Option<Frame>>
+where
+    R: AsyncBufRead + Unpin,
+{
+    let mut length = None;
+    let mut has_headers = false;
+    loop {
+        let line = match read_line_frame(reader, MAX_HEADER_LINE_BYTES).await? {
+            None => return Ok(None),
+            Some(Frame::Message(line)) => line,
+            Some(Frame::Rejected(_)) => {
+                return Ok(Some(rejected(MCPError::invalid_request(
+                    "Invalid message header".to_string(),
+                ))))
+            }
+        };
+        if line.is_empty() {
+            // Blank lines between messages are tolerated
+            if has_headers {
+                break;
+            }
+            continue;
+        }
+        has_headers = true;
+        if let Some((name, value)) = line.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                length = value.trim().parse::<usize>().ok();
+            }
+        }
+    }
+
+    let Some(length) = length else {
+        return Ok(Some(rejected(MCPError::invalid_request(
+            "Missing or invalid Content-Length header".to_string(),
+        ))));
+    };
+    if length > max_bytes {
+        tokio::io::copy(
+            &mut (&mut *reader).take(length as u64),
+            &mut tokio::io::sink(),
+        )
+        .await?;
+        return Ok(Some(oversized(max_bytes)));
+    }
+    let mut body = vec![0; length];
+    match reader.read_exact(&mut body).await {
+        Ok(_) => Ok(Some(utf8_frame(body))),
+        Err(e) if e.kind() == ErrorKind::UnexpectedEof => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+fn utf8_frame(bytes: Vec<u8>) -> Frame {
+    match String::from_utf8(bytes) {
+        Ok(message) => Frame::Message(message),
+        Err(_) => rejected(MCPError::parse_error(
+            "Message is not valid UTF-8".to_string(),
+        )),
+    }
+}
+
+fn oversized(max_bytes: usize) -> Frame {
+    warn!("❌ Discarded MCP message over {} bytes", max_bytes);
+    rejected(MCPError::invalid_request(format!(
+        "Message exceeds the {} byte limit",
+        max_bytes
+    )))
+}
+
+fn rejected(error: MCPError) -> Frame {
+    Frame::Rejected(MCPResponse::error_for(None, error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mcp::types::{INVALID_REQUEST, PARSE_ERROR};
+    use tokio::io::BufReader;
+
+    async fn read_all(
+        input: &[u8],
+        framing: StdioFraming,
+    ) -> (Vec<Result<String, i32>>, StdioFraming) {
+        // A tiny buffer makes frames span several reads
+        let mut frames = FrameReader::new(BufReader::with_capacity(4, input), framing, 10);
+        let mut read = Vec::new();
+        while let Some(frame) = frames.next().await.unwrap() {
+            read.push(match frame {
+                Frame::Message(message) => Ok(message),
+                Frame::Rejected(response) => Err(response.error.unwrap().code),
+            });
+        }
+        (read, frames.framing())
+    }
+
+    #[tokio::test]
+    async fn test_newline_frames_are_bounded() {
+        let input = b"\n{\"a\":1}\r\n0123456789abcdef\n\xff\nlast";
+        let (frames, framing) = read_all(input, StdioFraming::Auto).await;
+        assert_eq!(framing, StdioFraming::Newline);
+        assert_eq!(
+            frames,
+            vec![
+                Ok("{\"a\":1}".to_string()),
+                Err(INVALID_REQUEST),
+                Err(PARSE_ERROR),
+                Ok("last".to_string()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_content_length_frames() {
+        let input = b"Content-Length: 7\r\nContent-Type: application/json\r\n\r\n{\"a\":1}\
+            \r\ncontent-length: 2\r\n\r\n[]\
+            Content-Length: 11\r\n\r\n0123456789a\
+            X-Other: 1\r\n\r\n\
+            Content-Length: 2\n\n{}";
+        let (frames, framing) = read_all(input, StdioFraming::Auto).await;
+        assert_eq!(framing, StdioFraming::ContentLength);
+        assert_eq!(
+            frames,
+            vec![
+                Ok("{\"a\":1}".to_string()),
+                Ok("[]".to_string()),
+                Err(INVALID_REQUEST),
+                Err(INVALID_REQUEST),
+                Ok("{}".to_string()),
+            ]
+        );
+
+        // Configured framing isn't second-guessed
+        let (frames, _) = read_all(b"{}\n\n", StdioFraming::ContentLength).await;
+        assert_eq!(frames, vec![Err(INVALID_REQUEST)]);
+    }
+
+    #[tokio::test]
+    async fn test_responses_use_the_framing() {
+        let response = MCPResponse::success(1.into(), serde_json::json!({}));
+        let json = serde_json::to_string(&response).unwrap();
+
+        let mut written = Vec::new();
+        write_frame(&mut written, StdioFraming::ContentLength, &response)
+            .await
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(written).unwrap(),
+            format!("Content-Length: {}\r\n\r\n{}", json.len(), json)
+        );
+
+        let mut written = Vec::new();
+        write_frame(&mut written, StdioFraming::Newline, &response)
+            .await
+            .unwrap();
+        assert_eq!(String::from_utf8(written).unwrap(), format!("{}\n", json));
+    }
+}
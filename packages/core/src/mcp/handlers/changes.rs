@@ -0,0 +1,95 @@
+//! Node Change Subscription Handlers
+//!
+//! `nodespace/subscribe` asks for a `nodespace/nodeChanged` notification
+//! whenever anything in a document (a root and its descendants) is created,
+//! edited, moved or deleted; see [`crate::mcp::session`] for delivery.
+
+use crate::mcp::access::{Access, AccessScope, AgentProfile};
+use crate::mcp::session::{ChangeFilter, Session};
+use crate::mcp::types::MCPError;
+use crate::services::NodeService;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SubscribeParams {
+    /// The root, or any node in it
+    #[serde(alias = "node_id", alias = "root_id", alias = "rootId")]
+    node_id: String,
+    #[serde(flatten)]
+    filter: ChangeFilter,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UnsubscribeParams {
+    #[serde(alias = "root_id")]
+    root_id: String,
+}
+
+fn require_stream(session: &Session) -> Result<(), MCPError> {
+    if session.supports_subscriptions() {
+        Ok(())
+    } else {
+        Err(MCPError::invalid_request(
+            "Change notifications need a Streamable HTTP session".to_string(),
+        ))
+    }
+}
+
+/// Handle `nodespace/subscribe`
+///
+/// Watches the root of the given node, returning its ID. Scoped agents can
+/// only watch roots they can read.
+pub async fn handle_subscribe<C>(
+    node_service: &NodeService<C>,
+    profile: Option<&AgentProfile>,
+    session: &Session,
+    params: Value,
+) -> Result<Value, MCPError>
+where
+    C: surrealdb::Connection,
+{
+    require_stream(session)?;
+    let params: SubscribeParams = serde_json::from_value(params)
+        .map_err(|e| MCPError::invalid_params(format!("Invalid parameters: {}", e)))?;
+
+    if node_service
+        .get_node(&params.node_id)
+        .await
+        .map_err(|e| MCPError::internal_error(e.to_string()))?
+        .is_none()
+    {
+        return Err(MCPError::node_not_found(&params.node_id));
+    }
+    let root_id = node_service
+        .get_root_id(&params.node_id)
+        .await
+        .map_err(|e| MCPError::internal_error(e.to_string()))?;
+    if let Some(profile) = profile {
+        AccessScope::resolve(node_service, profile)
+            .await?
+            .require(node_service, &root_id, Access::Read)
+            .await?;
+    }
+
+    // Deletions are attributed through the nodes already in the root
+    let members = node_service
+        .get_descendants(&root_id)
+        .await
+        .map_err(|e| MCPError::internal_error(e.to_string()))?
+        .into_iter()
+        .map(|node| node.id);
+    session.watch_root(&root_id, params.filter, members);
+
+    Ok(json!({ "rootId": root_id }))
+}
+
+/// Handle `nodespace/unsubscribe`
+pub fn handle_unsubscribe(session: &Session, params: Value) -> Result<Value, MCPError> {
+    require_stream(session)?;
+    let params: UnsubscribeParams = serde_json::from_value(params)
+        .map_err(|e| MCPError::invalid_params(format!("Invalid parameters: {}", e)))?;
+    Ok(json!({ "unsubscribed": session.unwatch_root(&params.root_id) }))
+}
@@ -143,8 +143,14 @@ where
             "tools": {
                 "listChanged": false  // Tool list is static, doesn't change after init
             },
-            "resources": {},  // Future: Add resource capabilities
-            "prompts": {}     // Future: Add prompt capabilities
+            "resources": {
+                "subscribe": true  // Node changes, on Streamable HTTP sessions
+            },
+            "prompts": {},    // Future: Add prompt capabilities
+            "experimental": {
+                // Document change notifications (nodespace/subscribe)
+                "nodespace/nodeChanged": {}
+            }
         },
         "collections": collections,  // Available collections for filtering
         "instructions": format!(
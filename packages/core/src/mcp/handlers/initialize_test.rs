@@ -8,6 +8,11 @@ use serde_json::json;
 use std::sync::Arc;
 use tempfile::TempDir;
 
+/// The registry as a server over the embedded database builds it
+fn tool_index() -> crate::mcp::handlers::tools::ToolIndex {
+    crate::mcp::handlers::tools::ToolIndex::new()
+}
+
 // Helper to create test services (NodeService + NodeEmbeddingService)
 async fn create_test_services() -> (Arc<NodeService>, Arc<NodeEmbeddingService>, TempDir) {
     let temp_dir = TempDir::new().unwrap();
@@ -20,7 +25,11 @@ async fn create_test_services() -> (Arc<NodeService>, Arc<NodeEmbeddingService>,
     nlp_engine.initialize().unwrap();
     let nlp_engine = Arc::new(nlp_engine);
 
-    let embedding_service = Arc::new(NodeEmbeddingService::new(nlp_engine, store.clone()));
+    let embedding_service = Arc::new(NodeEmbeddingService::new(
+        nlp_engine,
+        store.clone(),
+        node_service.workspace_lock().clone(),
+    ));
 
     (node_service, embedding_service, temp_dir)
 }
@@ -135,7 +144,7 @@ async fn test_initialize_empty_params() {
 fn test_tier1_core_tools_present() {
     // Use tools/list to get Tier 1 (Core) tool schemas for progressive disclosure
     // AI agents can discover additional tools via search_tools
-    let result = crate::mcp::handlers::tools::handle_tools_list(json!({})).unwrap();
+    let result = crate::mcp::handlers::tools::handle_tools_list(&tool_index(), json!({})).unwrap();
     let tools = result["tools"].as_array().unwrap();
 
     // Verify all Tier 1 (Core) tools are present
@@ -178,7 +187,8 @@ fn test_tier1_core_tools_present() {
 #[test]
 fn test_search_tools_discovers_tier2_tools() {
     // Verify search_tools can discover Tier 2 (Discoverable) tools
-    let result = crate::mcp::handlers::tools::handle_search_tools(json!({})).unwrap();
+    let result =
+        crate::mcp::handlers::tools::handle_search_tools(&tool_index(), json!({})).unwrap();
     let tools = result["tools"].as_array().unwrap();
 
     // Should find some discoverable tools (not exhaustive check)
@@ -211,7 +221,7 @@ fn test_search_tools_discovers_tier2_tools() {
 #[test]
 fn test_all_schemas_have_required_fields() {
     // Verify Tier 1 tool schemas have proper structure
-    let result = crate::mcp::handlers::tools::handle_tools_list(json!({})).unwrap();
+    let result = crate::mcp::handlers::tools::handle_tools_list(&tool_index(), json!({})).unwrap();
     let tools = result["tools"].as_array().unwrap();
 
     assert_eq!(
@@ -258,7 +268,7 @@ fn test_all_schemas_have_required_fields() {
 
 #[test]
 fn test_create_node_schema_has_required_fields() {
-    let result = crate::mcp::handlers::tools::handle_tools_list(json!({})).unwrap();
+    let result = crate::mcp::handlers::tools::handle_tools_list(&tool_index(), json!({})).unwrap();
     let tools = result["tools"].as_array().unwrap();
 
     let create_node = tools
@@ -292,7 +302,7 @@ fn test_create_node_schema_has_required_fields() {
 
 #[test]
 fn test_get_node_schema_structure() {
-    let result = crate::mcp::handlers::tools::handle_tools_list(json!({})).unwrap();
+    let result = crate::mcp::handlers::tools::handle_tools_list(&tool_index(), json!({})).unwrap();
     let tools = result["tools"].as_array().unwrap();
 
     let get_node = tools
@@ -315,7 +325,7 @@ fn test_get_node_schema_structure() {
 
 #[test]
 fn test_update_node_schema_structure() {
-    let result = crate::mcp::handlers::tools::handle_tools_list(json!({})).unwrap();
+    let result = crate::mcp::handlers::tools::handle_tools_list(&tool_index(), json!({})).unwrap();
     let tools = result["tools"].as_array().unwrap();
 
     let update_node = tools
@@ -340,7 +350,7 @@ fn test_update_node_schema_structure() {
 #[test]
 fn test_markdown_import_schema_structure() {
     // create_nodes_from_markdown is now a Tier 1 core tool
-    let result = crate::mcp::handlers::tools::handle_tools_list(json!({})).unwrap();
+    let result = crate::mcp::handlers::tools::handle_tools_list(&tool_index(), json!({})).unwrap();
     let tools = result["tools"].as_array().unwrap();
 
     let markdown_import = tools
@@ -31,9 +31,12 @@
 //! }
 //! ```
 
+use crate::db::FractionalIndex;
+use crate::mcp::handlers::nodes::service_error_to_mcp;
 use crate::mcp::types::MCPError;
 use crate::models::{Node, TaskNode, TaskStatus};
 use crate::services::{CollectionService, CreateNodeParams, NodeService, NodeServiceError};
+use crate::utils::truncate_chars;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use std::collections::HashMap;
@@ -74,8 +77,8 @@ pub struct PreparedNode {
     pub content: String,
     /// Parent node ID (references another PreparedNode.id or existing node)
     pub parent_id: Option<String>,
-    /// Pre-calculated fractional order for sibling positioning
-    pub order: f64,
+    /// Pre-calculated fractional index key for sibling positioning
+    pub order: String,
     /// Node properties (status for tasks, etc.)
     pub properties: Value,
 }
@@ -87,7 +90,7 @@ impl PreparedNode {
         node_type: &str,
         content: String,
         parent_id: Option<String>,
-        order: f64,
+        order: String,
         properties: Value,
     ) -> Self {
         Self {
@@ -108,8 +111,8 @@ impl PreparedNode {
 struct PrepareContext {
     /// Stack tracking heading hierarchy (h1 → h2 → h3)
     heading_stack: Vec<(String, usize)>, // (node_id, level)
-    /// Counter for fractional ordering per parent
-    order_per_parent: HashMap<String, f64>,
+    /// Number of children handed out per parent
+    order_per_parent: HashMap<String, usize>,
 }
 
 impl PrepareContext {
@@ -141,13 +144,14 @@ impl PrepareContext {
         self.heading_stack.push((node_id, level));
     }
 
-    /// Get next order value for a parent
-    fn next_order(&mut self, parent_id: &Option<String>) -> f64 {
-        let key = parent_id.clone().unwrap_or_default();
-        let current = self.order_per_parent.get(&key).copied().unwrap_or(0.0);
-        let next = current + 1.0;
-        self.order_per_parent.insert(key, next);
-        next
+    /// Get next order key for a parent
+    fn next_order(&mut self, parent_id: &Option<String>) -> String {
+        let count = self
+            .order_per_parent
+            .entry(parent_id.clone().unwrap_or_default())
+            .or_insert(0);
+        *count += 1;
+        FractionalIndex::nth(*count - 1)
     }
 }
 
@@ -1029,7 +1033,7 @@ where
                 String,
                 String,
                 Option<String>,
-                f64,
+                String,
                 serde_json::Value,
             )> = prepared_children
                 .iter()
@@ -1039,7 +1043,7 @@ where
                         n.node_type.clone(),
                         n.content.clone(),
                         n.parent_id.clone(),
-                        n.order,
+                        n.order.clone(),
                         n.properties.clone(),
                     )
                 })
@@ -1803,16 +1807,46 @@ where
     let (root_node, node_map, adjacency_list) = node_service
         .get_subtree_data(&params.node_id)
         .await
-        .map_err(|e| MCPError::internal_error(format!("Failed to get subtree data: {}", e)))?;
+        .map_err(service_error_to_mcp)?;
 
     let root_node = root_node.ok_or_else(|| MCPError::node_not_found(&params.node_id))?;
 
     // Build markdown by traversing hierarchy in memory (no database calls)
+    let (markdown, node_count) = render_subtree_markdown(
+        &root_node,
+        &node_map,
+        &adjacency_list,
+        params.include_children,
+        params.max_depth,
+        params.include_node_ids,
+    );
+
+    // Return result with version for easy OCC reference
+    Ok(json!({
+        "markdown": markdown,
+        "root_node_id": params.node_id,
+        "version": root_node.version,
+        "node_count": node_count
+    }))
+}
+
+/// Render a pre-fetched subtree (see `NodeService::get_subtree_data`) as markdown
+///
+/// Shared by the MCP export and the vault mirror. Returns the markdown and the
+/// number of nodes rendered.
+pub fn render_subtree_markdown(
+    root_node: &Node,
+    node_map: &HashMap<String, Node>,
+    adjacency_list: &HashMap<String, Vec<String>>,
+    include_children: bool,
+    max_depth: usize,
+    include_node_ids: bool,
+) -> (String, usize) {
     let mut markdown = String::new();
     let mut node_count: usize = 1; // Start with root node
 
     // Export the container node itself with version for OCC (if node IDs enabled)
-    if params.include_node_ids {
+    if include_node_ids {
         markdown.push_str(&format!(
             "<!-- {} v{} -->\n",
             root_node.id, root_node.version
@@ -1822,7 +1856,7 @@ where
     markdown.push_str("\n\n");
 
     // Export children if requested
-    if params.include_children {
+    if include_children {
         // Get direct children from adjacency list (already sorted by order)
         if let Some(child_ids) = adjacency_list.get(&root_node.id) {
             // Export each child and its descendants (children are already in correct order)
@@ -1830,26 +1864,20 @@ where
                 if let Some(child) = node_map.get(child_id) {
                     node_count += export_node_hierarchy(
                         child,
-                        &node_map,
-                        &adjacency_list,
+                        node_map,
+                        adjacency_list,
                         &mut markdown,
                         1, // Start at depth 1 (container is depth 0)
-                        params.max_depth,
+                        max_depth,
                         true, // Always include children when recursing
-                        params.include_node_ids,
+                        include_node_ids,
                     );
                 }
             }
         }
     }
 
-    // Return result with version for easy OCC reference
-    Ok(json!({
-        "markdown": markdown,
-        "root_node_id": params.node_id,
-        "version": root_node.version,
-        "node_count": node_count
-    }))
+    (markdown, node_count)
 }
 
 /// Format task node checkbox based on task status
@@ -1885,13 +1913,11 @@ fn export_node_hierarchy(
 ) -> usize {
     // Prevent infinite recursion
     if current_depth >= max_depth {
-        let content_preview: String = node.content.chars().take(50).collect();
         tracing::warn!(
-            "Max depth {} reached at node {} (content: {}{})",
+            "Max depth {} reached at node {} (content: {})",
             max_depth,
             node.id,
-            content_preview,
-            if node.content.len() > 50 { "..." } else { "" }
+            truncate_chars(&node.content, 50)
         );
         return 0; // Node not exported due to max_depth
     }
@@ -1990,13 +2016,11 @@ fn export_node_with_context(
 ) -> usize {
     // Prevent infinite recursion
     if current_depth >= params.max_depth {
-        let content_preview: String = node.content.chars().take(50).collect();
         tracing::warn!(
-            "Max depth {} reached at node {} (content: {}{})",
+            "Max depth {} reached at node {} (content: {})",
             params.max_depth,
             node.id,
-            content_preview,
-            if node.content.len() > 50 { "..." } else { "" }
+            truncate_chars(&node.content, 50)
         );
         return 0; // Node not exported due to max_depth
     }
@@ -2059,7 +2059,7 @@ mod link_transformation_tests {
             "text",
             "Check [Google](https://google.com) and [email](mailto:test@example.com)".to_string(),
             None,
-            1.0,
+            "a0".to_string(),
             json!({}),
         )];
 
@@ -2079,7 +2079,7 @@ mod link_transformation_tests {
             "text",
             "See [related](nodespace://abc-123-def) for details".to_string(),
             None,
-            1.0,
+            "a0".to_string(),
             json!({}),
         )];
 
@@ -2099,7 +2099,7 @@ mod link_transformation_tests {
             "text",
             "Jump to [Section 1](#section-1) below".to_string(),
             None,
-            1.0,
+            "a0".to_string(),
             json!({}),
         )];
 
@@ -2116,7 +2116,7 @@ mod link_transformation_tests {
             "text",
             "See [architecture](./docs/architecture.md) for details".to_string(),
             None,
-            1.0,
+            "a0".to_string(),
             json!({}),
         )];
 
@@ -2142,7 +2142,7 @@ mod link_transformation_tests {
             "text",
             "See [missing file](./nonexistent.md) for details".to_string(),
             None,
-            1.0,
+            "a0".to_string(),
             json!({}),
         )];
 
@@ -2161,7 +2161,7 @@ mod link_transformation_tests {
             "text",
             "See [parent](../overview.md) and [sibling](./sibling.md)".to_string(),
             None,
-            1.0,
+            "a0".to_string(),
             json!({}),
         )];
 
@@ -2186,7 +2186,7 @@ mod link_transformation_tests {
             "Read [intro](intro.md), then [guide](guide.md), and [external](https://docs.rs)"
                 .to_string(),
             None,
-            1.0,
+            "a0".to_string(),
             json!({}),
         )];
 
@@ -2235,7 +2235,7 @@ mod link_transformation_tests {
                 "text",
                 "See [intro](intro.md)".to_string(),
                 None,
-                1.0,
+                "a0".to_string(),
                 json!({}),
             ),
             PreparedNode::new(
@@ -2243,7 +2243,7 @@ mod link_transformation_tests {
                 "text",
                 "Also [guide](guide.md)".to_string(),
                 Some("node1".to_string()),
-                2.0,
+                "a1".to_string(),
                 json!({}),
             ),
         ];
@@ -2265,7 +2265,7 @@ mod link_transformation_tests {
             "text",
             "Just plain text without any links.".to_string(),
             None,
-            1.0,
+            "a0".to_string(),
             json!({}),
         )];
 
@@ -2294,7 +2294,7 @@ mod mention_collection_tests {
             "text",
             "See [architecture](./docs/architecture.md) for details".to_string(),
             None,
-            1.0,
+            "a0".to_string(),
             json!({}),
         )];
 
@@ -2325,7 +2325,7 @@ mod mention_collection_tests {
             "text",
             "Link to [self](./self.md)".to_string(),
             None,
-            1.0,
+            "a0".to_string(),
             json!({}),
         )];
 
@@ -2352,7 +2352,7 @@ mod mention_collection_tests {
             "text",
             "Check [Google](https://google.com) and [email](mailto:test@example.com)".to_string(),
             None,
-            1.0,
+            "a0".to_string(),
             json!({}),
         )];
 
@@ -2371,7 +2371,7 @@ mod mention_collection_tests {
             "text",
             "Jump to [Section 1](#section-1) below".to_string(),
             None,
-            1.0,
+            "a0".to_string(),
             json!({}),
         )];
 
@@ -2390,7 +2390,7 @@ mod mention_collection_tests {
             "text",
             "See [missing file](./nonexistent.md) for details".to_string(),
             None,
-            1.0,
+            "a0".to_string(),
             json!({}),
         )];
 
@@ -2414,7 +2414,7 @@ mod mention_collection_tests {
             "text",
             "See [related](nodespace://target-abc-123) for details".to_string(),
             None,
-            1.0,
+            "a0".to_string(),
             json!({}),
         )];
 
@@ -2436,7 +2436,7 @@ mod mention_collection_tests {
             "Read [intro](intro.md), then [guide](guide.md), and [external](https://docs.rs)"
                 .to_string(),
             None,
-            1.0,
+            "a0".to_string(),
             json!({}),
         )];
 
@@ -2463,7 +2463,7 @@ mod mention_collection_tests {
                 "text",
                 "See [intro](intro.md)".to_string(),
                 None,
-                1.0,
+                "a0".to_string(),
                 json!({}),
             ),
             PreparedNode::new(
@@ -2471,7 +2471,7 @@ mod mention_collection_tests {
                 "text",
                 "Also [guide](guide.md)".to_string(),
                 Some("node1".to_string()),
-                2.0,
+                "a1".to_string(),
                 json!({}),
             ),
         ];
@@ -2499,7 +2499,7 @@ mod mention_collection_tests {
             "text",
             "Just plain text without any links.".to_string(),
             None,
-            1.0,
+            "a0".to_string(),
             json!({}),
         )];
 
@@ -6,10 +6,12 @@
 //! Use generic CRUD (create_node, update_node, query_nodes) for schema management.
 //! Relationship CRUD is available via the relationships module (Issue #703).
 
+pub mod changes;
 pub mod initialize;
 pub mod markdown;
 pub mod nodes;
 pub mod relationships;
+pub mod resources;
 pub mod schema;
 pub mod search;
 pub mod tools;
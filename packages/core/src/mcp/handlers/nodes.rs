@@ -5,10 +5,17 @@
 //!
 //! As of Issue #676, all handlers use NodeService directly instead of NodeOperations.
 
-use crate::mcp::types::MCPError;
-use crate::models::{Node, NodeFilter, NodeUpdate, OrderBy};
-use crate::services::{CollectionService, NodeService, NodeServiceError};
-use chrono::NaiveDate;
+use crate::api::{CreateNodeRequest, QueryRequest};
+use crate::db::DatabaseError;
+use crate::mcp::pagination::{Page, DEFAULT_PAGE_LIMIT, MAX_PAGE_LIMIT};
+use crate::mcp::projection::Projection;
+use crate::mcp::types::{MCPError, VERSION_CONFLICT};
+use crate::models::{Node, NodeAppearance, NodeUpdate, TaskDueFilter, ValidationWarning};
+use crate::services::{
+    CollectionService, NodeService, NodeServiceError, QueryDefinition, QueryService,
+};
+use crate::utils::{parse_natural_date, truncate_chars};
+use chrono::{Local, NaiveDate};
 use serde::Deserialize;
 use serde_json::{json, Value};
 use std::sync::Arc;
@@ -30,8 +37,9 @@ fn nodes_to_typed_values(nodes: Vec<Node>) -> Result<Vec<Value>, MCPError> {
 /// Convert NodeServiceError to MCPError with proper formatting
 ///
 /// Special handling for VersionConflict errors to help client-side merge.
-fn service_error_to_mcp(error: NodeServiceError) -> MCPError {
-    match error {
+pub(crate) fn service_error_to_mcp(error: NodeServiceError) -> MCPError {
+    let message = error.user_message();
+    let mut mcp_error = match error {
         NodeServiceError::VersionConflict {
             node_id,
             expected_version,
@@ -51,27 +59,61 @@ fn service_error_to_mcp(error: NodeServiceError) -> MCPError {
         NodeServiceError::HierarchyViolation(msg) => {
             MCPError::validation_error(format!("Hierarchy violation: {}", msg))
         }
+        NodeServiceError::DatabaseError(e @ DatabaseError::Timeout { .. }) => {
+            MCPError::query_timeout(e.to_string())
+        }
         NodeServiceError::DatabaseError(e) => {
             MCPError::internal_error(format!("Database error: {}", e))
         }
+        NodeServiceError::InvalidInput(msg) => MCPError::invalid_params(msg),
+        NodeServiceError::SchemaViolation(_) => MCPError::validation_error(error.to_string()),
+        NodeServiceError::IdempotencyKeyConflict { .. }
+        | NodeServiceError::IncorrectPassphrase
+        | NodeServiceError::PassphraseTooShort { .. }
+        | NodeServiceError::NoPassphrase => MCPError::invalid_params(error.to_string()),
         _ => MCPError::internal_error(format!("Service error: {}", error)),
+    };
+    // Code + params let clients show the error in their own language
+    if mcp_error.data.is_none() {
+        mcp_error.data = Some(json!({ "code": message.code, "params": message.params }));
     }
+    mcp_error
+}
+
+/// Like [`service_error_to_mcp`], but a version conflict carries the node as
+/// it is now
+///
+/// An agent that hit the conflict can merge its change into `current_node`
+/// and retry with that node's version, without another read.
+async fn write_error_to_mcp<C>(node_service: &NodeService<C>, error: NodeServiceError) -> MCPError
+where
+    C: surrealdb::Connection,
+{
+    let NodeServiceError::VersionConflict {
+        node_id,
+        expected_version,
+        actual_version,
+    } = error
+    else {
+        return service_error_to_mcp(error);
+    };
+    let current_node = node_service.get_node(&node_id).await.ok().flatten();
+    // The node may have moved on again since the conflicting write
+    let actual_version = current_node
+        .as_ref()
+        .map_or(actual_version, |node| node.version);
+    let current_node = current_node.and_then(|node| node_to_typed_value(node).ok());
+    MCPError::version_conflict(node_id, expected_version, actual_version, current_node)
 }
 
 /// Parameters for create_node method from MCP clients
 ///
-/// Note: MCP clients don't provide IDs - they are generated server-side.
-/// This struct is used for deserialization from MCP requests.
+/// The node itself is the [`CreateNodeRequest`] shared with the desktop app
+/// and dev-proxy; MCP adds collection and lifecycle options.
 #[derive(Debug, Deserialize)]
 pub struct MCPCreateNodeParams {
-    pub node_type: String,
-    pub content: String,
-    #[serde(default)]
-    pub parent_id: Option<String>,
-    #[serde(default)]
-    pub root_id: Option<String>,
-    #[serde(default)]
-    pub properties: Value,
+    #[serde(flatten)]
+    pub node: CreateNodeRequest,
     /// Optional collection path to add this node to (e.g., "hr:policy:vacation")
     /// Creates collections along the path if they don't exist.
     #[serde(default)]
@@ -128,25 +170,58 @@ pub struct DeleteNodeParams {
     pub version: Option<i64>,
 }
 
-/// Parameters for query_nodes method
+/// Parameters for run_query method
 #[derive(Debug, Deserialize)]
-pub struct QueryNodesParams {
-    #[serde(default)]
-    pub node_type: Option<String>,
-    #[serde(default)]
-    pub parent_id: Option<String>,
-    #[serde(default)]
-    pub root_id: Option<String>,
-    #[serde(default)]
-    pub limit: Option<usize>,
+pub struct RunQueryParams {
+    pub query_node_id: String,
+    /// Values for `$name` placeholders in the saved filters
     #[serde(default)]
-    pub offset: Option<usize>,
-    /// Filter by collection membership - returns only nodes in this collection
+    pub params: serde_json::Map<String, Value>,
+}
+
+/// Parameters for get_overdue_tasks and get_tasks_due_within
+#[derive(Debug, Deserialize)]
+pub struct DueTasksParams {
+    /// Days after today to include (get_tasks_due_within only)
     #[serde(default)]
-    pub collection_id: Option<String>,
-    /// Filter by collection path - resolves path to collection ID first
+    pub days: u32,
+    #[serde(flatten)]
+    pub filter: TaskDueFilter,
+}
+
+/// Parameters for extract_action_items
+#[derive(Debug, Deserialize)]
+pub struct ExtractActionItemsParams {
+    pub meeting_id: String,
+}
+
+/// Parameters for log_interaction and get_interaction_history
+#[derive(Debug, Deserialize)]
+pub struct InteractionParams {
+    pub person_id: String,
+    /// What happened (log_interaction only)
     #[serde(default)]
-    pub collection: Option<String>,
+    pub note: String,
+}
+
+/// Parameters for get_highlights
+#[derive(Debug, Deserialize)]
+pub struct GetHighlightsParams {
+    pub source_id: String,
+}
+
+/// Parameters for get_or_create_period
+#[derive(Debug, Deserialize)]
+pub struct GetOrCreatePeriodParams {
+    pub period_id: String,
+}
+
+/// Parameters for set_node_appearance
+#[derive(Debug, Deserialize)]
+pub struct SetNodeAppearanceParams {
+    pub node_id: String,
+    #[serde(flatten)]
+    pub appearance: NodeAppearance,
 }
 
 /// Parameters for get_children method
@@ -196,8 +271,28 @@ pub struct GetNodeTreeParams {
     pub include_content: bool,
     #[serde(default)]
     pub include_metadata: bool,
+    #[serde(default)]
+    pub format: TreeFormat,
+    /// Longest line content in the outline format
+    #[serde(default)]
+    pub max_content_chars: Option<usize>,
+}
+
+/// Response format of get_node_tree
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TreeFormat {
+    /// Nested JSON ([`TreeNode`])
+    #[default]
+    Tree,
+    /// One indented line per node with its type and ID, for skimming large
+    /// subtrees at a fraction of the tokens
+    Outline,
 }
 
+/// Default longest line content in the outline format
+const OUTLINE_CONTENT_CHARS: usize = 80;
+
 fn default_true() -> bool {
     true
 }
@@ -255,17 +350,14 @@ where
 
     // Create node via NodeService (enforces all business rules)
     // Note: root_id is auto-derived from parent chain by backend
-    let parent_id = mcp_params.parent_id.clone();
+    let mut node = mcp_params.node;
+    node.id = None; // MCP generates IDs server-side
+    node.parent_id = node.parent_id.map(resolve_date_parent);
+    let node_type = node.node_type.clone();
+    let parent_id = node.parent_id.clone();
     let collection_path = mcp_params.collection.clone();
     let node_id = node_service
-        .create_node_with_parent(crate::services::CreateNodeParams {
-            id: None, // MCP generates IDs server-side
-            node_type: mcp_params.node_type.clone(),
-            content: mcp_params.content,
-            parent_id: mcp_params.parent_id,
-            insert_after_node_id: None, // Insert at beginning (this endpoint doesn't expose positioning)
-            properties: mcp_params.properties,
-        })
+        .create_node_with_parent(node.into())
         .await
         .map_err(|e| MCPError::node_creation_failed(format!("Failed to create node: {}", e)))?;
 
@@ -310,15 +402,17 @@ where
         .map_err(|e| MCPError::internal_error(format!("Failed to fetch created node: {}", e)))?
         .ok_or_else(|| MCPError::internal_error("Created node not found".to_string()))?;
 
+    let warnings = write_warnings(node_service, &created_node).await;
     let node_data = node_to_typed_value(created_node)?;
 
     Ok(json!({
         "node_id": node_id,
-        "node_type": mcp_params.node_type,
+        "node_type": node_type,
         "parent_id": parent_id,
         "collection_id": collection_id,
         "success": true,
-        "node_data": node_data
+        "node_data": node_data,
+        "warnings": warnings
     }))
 }
 
@@ -329,6 +423,8 @@ where
 ///
 /// This provides compile-time type safety for complex types while maintaining
 /// flexibility for simple content-only types.
+///
+/// `fields` and `max_content_chars` trim the node (see [`Projection`]).
 pub async fn handle_get_node<C>(
     node_service: &Arc<NodeService<C>>,
     params: Value,
@@ -336,6 +432,7 @@ pub async fn handle_get_node<C>(
 where
     C: surrealdb::Connection,
 {
+    let projection = Projection::from_params(&params)?;
     let params: GetNodeParams = serde_json::from_value(params)
         .map_err(|e| MCPError::invalid_params(format!("Invalid parameters: {}", e)))?;
 
@@ -345,9 +442,21 @@ where
         .await
         .map_err(|e| MCPError::internal_error(format!("Failed to get node: {}", e)))?
         .ok_or_else(|| MCPError::node_not_found(&params.node_id))?;
+    let is_project = node.node_type == "project";
 
     // Convert to strongly-typed JSON representation
-    node_to_typed_value(node)
+    let mut value = node_to_typed_value(node)?;
+
+    // Projects carry their task progress so listings need no tree walk
+    if is_project {
+        let rollup = node_service
+            .get_project_rollup(&params.node_id)
+            .await
+            .map_err(service_error_to_mcp)?;
+        value["rollup"] = json!(rollup);
+    }
+    projection.apply(&mut value, None);
+    Ok(value)
 }
 
 /// Handle update_node MCP request
@@ -405,26 +514,7 @@ where
         .await
     {
         Ok(node) => node,
-        Err(NodeServiceError::VersionConflict {
-            node_id,
-            expected_version,
-            actual_version,
-        }) => {
-            // Fetch current node state to include in error response for client-side merge
-            let current_node = node_service
-                .get_node(&node_id)
-                .await
-                .ok()
-                .flatten()
-                .and_then(|n| serde_json::to_value(&n).ok());
-            return Err(MCPError::version_conflict(
-                node_id,
-                expected_version,
-                actual_version,
-                current_node,
-            ));
-        }
-        Err(e) => return Err(service_error_to_mcp(e)),
+        Err(e) => return Err(write_error_to_mcp(node_service, e).await),
     };
 
     // Handle collection operations
@@ -466,6 +556,7 @@ where
     // Include full node data in response for:
     // 1. SSE broadcasting (callback can extract node_data)
     // 2. Client convenience (no need for separate fetch)
+    let warnings = write_warnings(node_service, &final_node).await;
     let node_data = node_to_typed_value(final_node)?;
 
     Ok(json!({
@@ -474,10 +565,34 @@ where
         "success": true,
         "node_data": node_data,
         "collection_added": collection_added,
-        "collection_removed": collection_removed
+        "collection_removed": collection_removed,
+        "warnings": warnings
     }))
 }
 
+/// Schema warnings for a node that was just written
+///
+/// The write already succeeded, so a failure here only drops the warnings.
+async fn write_warnings<C>(
+    node_service: &Arc<NodeService<C>>,
+    node: &Node,
+) -> Vec<ValidationWarning>
+where
+    C: surrealdb::Connection,
+{
+    match node_service.validation_report(node).await {
+        Ok(report) => report.warnings,
+        Err(e) => {
+            tracing::warn!(
+                "Failed to collect validation warnings for {}: {}",
+                node.id,
+                e
+            );
+            Vec::new()
+        }
+    }
+}
+
 /// Handle delete_node MCP request
 pub async fn handle_delete_node<C>(
     node_service: &Arc<NodeService<C>>,
@@ -504,10 +619,10 @@ where
     };
 
     // Delete node via NodeService
-    let result = node_service
-        .delete_node(&params.node_id, version)
-        .await
-        .map_err(service_error_to_mcp)?;
+    let result = match node_service.delete_node(&params.node_id, version).await {
+        Ok(result) => result,
+        Err(e) => return Err(write_error_to_mcp(node_service, e).await),
+    };
 
     Ok(json!({
         "node_id": params.node_id,
@@ -517,6 +632,9 @@ where
 }
 
 /// Handle query_nodes MCP request
+///
+/// With `max_content_chars`, content is cut to a snippet around the
+/// `content_contains` match.
 pub async fn handle_query_nodes<C>(
     node_service: &Arc<NodeService<C>>,
     params: Value,
@@ -524,114 +642,260 @@ pub async fn handle_query_nodes<C>(
 where
     C: surrealdb::Connection,
 {
-    let params: QueryNodesParams = serde_json::from_value(params)
+    let projection = Projection::from_params(&params)?;
+    let page = Page::from_params(&params, DEFAULT_PAGE_LIMIT, MAX_PAGE_LIMIT)?;
+    let mut query: QueryRequest = serde_json::from_value(params)
         .map_err(|e| MCPError::invalid_params(format!("Invalid parameters: {}", e)))?;
+    query.offset = Some(page.start());
+    query.limit = Some(page.fetch_count());
+    let result = query
+        .execute(node_service)
+        .await
+        .map_err(service_error_to_mcp)?;
+    let (nodes, next_cursor) = page.finish(result.nodes);
 
-    // Resolve collection ID if path provided
-    let collection_id = if let Some(path) = &params.collection {
-        let collection_service = CollectionService::new(&node_service.store, node_service);
-        // Use resolve_path to find the collection (don't create if doesn't exist)
-        match collection_service.resolve_path(path).await {
-            Ok(resolved) => Some(resolved.leaf_id().to_string()),
-            Err(NodeServiceError::CollectionNotFound(_)) => {
-                // Collection doesn't exist, return empty result
-                return Ok(json!({
-                    "nodes": [],
-                    "count": 0,
-                    "collection_id": null
-                }));
-            }
-            Err(e) => return Err(service_error_to_mcp(e)),
-        }
-    } else {
-        params.collection_id.clone()
-    };
+    // Convert nodes to strongly-typed JSON representations
+    let count = nodes.len();
+    let mut typed_nodes = nodes_to_typed_values(nodes)?;
+    projection.apply_all(&mut typed_nodes, query.content_contains.as_deref());
 
-    // If filtering by collection, get member IDs first
-    let collection_member_ids: Option<std::collections::HashSet<String>> =
-        if let Some(coll_id) = &collection_id {
-            let collection_service = CollectionService::new(&node_service.store, node_service);
-            let members = collection_service
-                .get_collection_members(coll_id)
-                .await
-                .map_err(service_error_to_mcp)?;
-            // Extract IDs from nodes for membership filtering
-            Some(members.into_iter().map(|n| n.id).collect())
-        } else {
-            None
-        };
+    Ok(json!({
+        "nodes": typed_nodes,
+        "count": count,
+        "collection_id": result.collection_id,
+        "next_cursor": next_cursor
+    }))
+}
 
-    // Build NodeFilter using builder pattern
-    let mut filter = NodeFilter::new();
+/// Run a saved query node
+///
+/// Loads the query definition from the node, fills `$name` placeholders in
+/// its filter values from `params` and executes it via QueryService, so
+/// agents can reuse the smart lists users have curated.
+pub async fn handle_run_query<C>(
+    node_service: &Arc<NodeService<C>>,
+    params: Value,
+) -> Result<Value, MCPError>
+where
+    C: surrealdb::Connection,
+{
+    let arguments = params.clone();
+    let params: RunQueryParams = serde_json::from_value(params)
+        .map_err(|e| MCPError::invalid_params(format!("Invalid parameters: {}", e)))?;
 
-    if let Some(node_type) = params.node_type {
-        filter = filter.with_node_type(node_type);
-    }
+    let query_node = node_service
+        .get_node(&params.query_node_id)
+        .await
+        .map_err(service_error_to_mcp)?
+        .ok_or_else(|| MCPError::node_not_found(&params.query_node_id))?;
+    let mut definition = QueryDefinition::from_saved_query(&query_node)
+        .and_then(|definition| definition.with_params(&params.params))
+        .map_err(|e| MCPError::invalid_params(format!("{:#}", e)))?;
+    // The saved limit is the default page size
+    let default_limit = definition
+        .limit
+        .unwrap_or(DEFAULT_PAGE_LIMIT)
+        .min(MAX_PAGE_LIMIT);
+    let page = Page::from_params(&arguments, default_limit, MAX_PAGE_LIMIT)?;
+    definition.offset = Some(page.start());
+    definition.limit = Some(page.fetch_count());
+
+    let nodes = QueryService::new(
+        node_service.store().clone(),
+        node_service.workspace_lock().clone(),
+    )
+    .execute(&definition)
+    .await
+    .map_err(|e| match e.downcast::<NodeServiceError>() {
+        Ok(e) => service_error_to_mcp(e),
+        Err(e) if DatabaseError::is_timeout(&e) => MCPError::query_timeout(e.to_string()),
+        Err(e) => MCPError::internal_error(format!("Failed to run query: {:#}", e)),
+    })?;
 
-    // Note: parent_id and root_id filters removed in graph-native refactor
-    // These parameters are kept in the MCP API for backward compatibility but ignored
-    // Clients should use graph queries to traverse relationships instead
-    if params.parent_id.is_some() {
-        tracing::warn!("parent_id filter ignored - use graph queries for relationship traversal");
-    }
+    let (nodes, next_cursor) = page.finish(nodes);
+    let count = nodes.len();
+    Ok(json!({
+        "query_node_id": params.query_node_id,
+        "nodes": nodes_to_typed_values(nodes)?,
+        "count": count,
+        "next_cursor": next_cursor
+    }))
+}
 
-    // DEPRECATED: root_id filter is ignored in graph-native architecture
-    // Kept for backward compatibility but clients should use graph queries
-    if params.root_id.is_some() {
-        tracing::warn!(
-            "root_id filter is deprecated. Filter ignored - use graph queries for relationship traversal"
-        );
-    }
+/// Open tasks due before today, optionally for one assignee or priority
+pub async fn handle_get_overdue_tasks<C>(
+    node_service: &Arc<NodeService<C>>,
+    params: Value,
+) -> Result<Value, MCPError>
+where
+    C: surrealdb::Connection,
+{
+    let params: DueTasksParams = serde_json::from_value(params)
+        .map_err(|e| MCPError::invalid_params(format!("Invalid parameters: {}", e)))?;
+    let nodes = node_service
+        .get_overdue_tasks(&params.filter)
+        .await
+        .map_err(service_error_to_mcp)?;
 
-    // When filtering by collection, we fetch more and filter client-side
-    // (because collection membership is stored in edges, not NodeFilter)
-    let effective_limit = if collection_member_ids.is_some() {
-        // Fetch more to compensate for post-filtering
-        params.limit.map(|l| l * 3).unwrap_or(1000)
-    } else {
-        params.limit.unwrap_or(100)
-    };
+    let count = nodes.len();
+    Ok(json!({
+        "nodes": nodes_to_typed_values(nodes)?,
+        "count": count
+    }))
+}
 
-    filter = filter.with_limit(effective_limit);
+/// Open tasks due today or within the next `days` days
+pub async fn handle_get_tasks_due_within<C>(
+    node_service: &Arc<NodeService<C>>,
+    params: Value,
+) -> Result<Value, MCPError>
+where
+    C: surrealdb::Connection,
+{
+    let params: DueTasksParams = serde_json::from_value(params)
+        .map_err(|e| MCPError::invalid_params(format!("Invalid parameters: {}", e)))?;
+    let nodes = node_service
+        .get_tasks_due_within(params.days, &params.filter)
+        .await
+        .map_err(service_error_to_mcp)?;
 
-    if let Some(offset) = params.offset {
-        filter = filter.with_offset(offset);
+    let count = nodes.len();
+    Ok(json!({
+        "days": params.days,
+        "nodes": nodes_to_typed_values(nodes)?,
+        "count": count
+    }))
+}
+
+/// Turn a meeting's action-item notes into tasks linked to the meeting
+pub async fn handle_extract_action_items<C>(
+    node_service: &Arc<NodeService<C>>,
+    params: Value,
+) -> Result<Value, MCPError>
+where
+    C: surrealdb::Connection,
+{
+    let params: ExtractActionItemsParams = serde_json::from_value(params)
+        .map_err(|e| MCPError::invalid_params(format!("Invalid parameters: {}", e)))?;
+    let task_ids = node_service
+        .extract_action_items(&params.meeting_id)
+        .await
+        .map_err(service_error_to_mcp)?;
+
+    let count = task_ids.len();
+    Ok(json!({
+        "meeting_id": params.meeting_id,
+        "task_ids": task_ids,
+        "count": count
+    }))
+}
+
+/// Log a dated interaction note against a person node
+pub async fn handle_log_interaction<C>(
+    node_service: &Arc<NodeService<C>>,
+    params: Value,
+) -> Result<Value, MCPError>
+where
+    C: surrealdb::Connection,
+{
+    let params: InteractionParams = serde_json::from_value(params)
+        .map_err(|e| MCPError::invalid_params(format!("Invalid parameters: {}", e)))?;
+    if params.note.trim().is_empty() {
+        return Err(MCPError::invalid_params("note cannot be empty".to_string()));
     }
+    let note_id = node_service
+        .log_interaction(&params.person_id, &params.note)
+        .await
+        .map_err(service_error_to_mcp)?;
 
-    filter = filter.with_order_by(OrderBy::CreatedDesc);
+    Ok(json!({
+        "person_id": params.person_id,
+        "note_id": note_id
+    }))
+}
 
-    // Query nodes via NodeService
+/// Interaction notes logged against a person node, newest first
+pub async fn handle_get_interaction_history<C>(
+    node_service: &Arc<NodeService<C>>,
+    params: Value,
+) -> Result<Value, MCPError>
+where
+    C: surrealdb::Connection,
+{
+    let params: InteractionParams = serde_json::from_value(params)
+        .map_err(|e| MCPError::invalid_params(format!("Invalid parameters: {}", e)))?;
     let nodes = node_service
-        .query_nodes(filter)
+        .get_interaction_history(&params.person_id)
         .await
-        .map_err(|e| MCPError::internal_error(format!("Failed to query nodes: {}", e)))?;
-
-    // Apply collection filter if specified
-    let filtered_nodes = if let Some(member_ids) = collection_member_ids {
-        let mut result: Vec<_> = nodes
-            .into_iter()
-            .filter(|n| member_ids.contains(&n.id))
-            .collect();
-        // Apply limit after filtering
-        if let Some(limit) = params.limit {
-            result.truncate(limit);
-        }
-        result
-    } else {
-        nodes
-    };
+        .map_err(service_error_to_mcp)?;
 
-    // Convert nodes to strongly-typed JSON representations
-    let count = filtered_nodes.len();
-    let typed_nodes = nodes_to_typed_values(filtered_nodes)?;
+    let count = nodes.len();
+    Ok(json!({
+        "person_id": params.person_id,
+        "nodes": nodes_to_typed_values(nodes)?,
+        "count": count
+    }))
+}
+
+/// Highlights taken from a source node, ordered by page and offset
+pub async fn handle_get_highlights<C>(
+    node_service: &Arc<NodeService<C>>,
+    params: Value,
+) -> Result<Value, MCPError>
+where
+    C: surrealdb::Connection,
+{
+    let params: GetHighlightsParams = serde_json::from_value(params)
+        .map_err(|e| MCPError::invalid_params(format!("Invalid parameters: {}", e)))?;
+    let nodes = node_service
+        .get_highlights(&params.source_id)
+        .await
+        .map_err(service_error_to_mcp)?;
 
+    let count = nodes.len();
     Ok(json!({
-        "nodes": typed_nodes,
-        "count": count,
-        "collection_id": collection_id
+        "source_id": params.source_id,
+        "nodes": nodes_to_typed_values(nodes)?,
+        "count": count
     }))
 }
 
+/// Week, month or quarter node for a period ID, created on first use
+pub async fn handle_get_or_create_period<C>(
+    node_service: &Arc<NodeService<C>>,
+    params: Value,
+) -> Result<Value, MCPError>
+where
+    C: surrealdb::Connection,
+{
+    let params: GetOrCreatePeriodParams = serde_json::from_value(params)
+        .map_err(|e| MCPError::invalid_params(format!("Invalid parameters: {}", e)))?;
+    let node = node_service
+        .get_or_create_period_node(&params.period_id)
+        .await
+        .map_err(service_error_to_mcp)?;
+
+    node_to_typed_value(node)
+}
+
+/// Replace the icon, color and cover of any node
+pub async fn handle_set_node_appearance<C>(
+    node_service: &Arc<NodeService<C>>,
+    params: Value,
+) -> Result<Value, MCPError>
+where
+    C: surrealdb::Connection,
+{
+    let params: SetNodeAppearanceParams = serde_json::from_value(params)
+        .map_err(|e| MCPError::invalid_params(format!("Invalid parameters: {}", e)))?;
+    let node = node_service
+        .set_node_appearance(&params.node_id, params.appearance)
+        .await
+        .map_err(service_error_to_mcp)?;
+
+    node_to_typed_value(node)
+}
+
 // =========================================================================
 // Helper Functions for Index-Based Operations
 // =========================================================================
@@ -641,6 +905,16 @@ fn is_valid_date_format(s: &str) -> bool {
     NaiveDate::parse_from_str(s, "%Y-%m-%d").is_ok()
 }
 
+/// Date container ID for a parent given as a phrase like "today" or "next friday"
+///
+/// Any other parent ID is returned unchanged.
+fn resolve_date_parent(parent_id: String) -> String {
+    match parse_natural_date(&parent_id, Local::now().date_naive()) {
+        Some(date) => date.format("%Y-%m-%d").to_string(),
+        None => parent_id,
+    }
+}
+
 /// Ensure parent node exists, auto-creating date nodes if needed
 async fn ensure_parent_exists<C>(
     node_service: &Arc<NodeService<C>>,
@@ -702,27 +976,32 @@ where
     let children = node_service
         .get_children(parent_id)
         .await
-        .map_err(|e| MCPError::internal_error(format!("Failed to query children: {}", e)))?;
+        .map_err(service_error_to_mcp)?;
 
     // Map to ChildInfo with index
     let ordered: Vec<ChildInfo> = children
         .into_iter()
         .enumerate()
-        .map(|(index, node)| ChildInfo {
-            index,
-            node_id: node.id,
-            node_type: node.node_type,
-            content: if include_content {
-                Some(node.content)
-            } else {
-                None
-            },
-        })
+        .map(|(index, node)| child_info(index, node, include_content))
         .collect();
 
     Ok(ordered)
 }
 
+/// A child at `index` among its siblings
+fn child_info(index: usize, node: crate::models::Node, include_content: bool) -> ChildInfo {
+    ChildInfo {
+        index,
+        node_id: node.id,
+        node_type: node.node_type,
+        content: if include_content {
+            Some(node.content)
+        } else {
+            None
+        },
+    }
+}
+
 /// Build tree node recursively
 fn build_tree_node<'a, C>(
     node_service: &'a Arc<NodeService<C>>,
@@ -815,6 +1094,9 @@ where
 // =========================================================================
 
 /// Handle get_children MCP request
+///
+/// `child_count` is the parent's total; `children` is one page of them, each
+/// with its index among all the children.
 pub async fn handle_get_children<C>(
     node_service: &Arc<NodeService<C>>,
     params: Value,
@@ -822,19 +1104,27 @@ pub async fn handle_get_children<C>(
 where
     C: surrealdb::Connection,
 {
+    let page = Page::from_params(&params, DEFAULT_PAGE_LIMIT, MAX_PAGE_LIMIT)?;
     let params: GetChildrenParams = serde_json::from_value(params)
         .map_err(|e| MCPError::invalid_params(format!("Invalid parameters: {}", e)))?;
 
-    // Get children in order
-    let children =
-        get_children_ordered(node_service, &params.parent_id, params.include_content).await?;
-
-    let child_count = children.len();
+    // Only this page's children are loaded, in order
+    let (children, child_count) = node_service
+        .get_children_page(&params.parent_id, page.start(), page.fetch_count())
+        .await
+        .map_err(service_error_to_mcp)?;
+    let children: Vec<ChildInfo> = children
+        .into_iter()
+        .enumerate()
+        .map(|(position, node)| child_info(page.start() + position, node, params.include_content))
+        .collect();
+    let (children, next_cursor) = page.finish(children);
 
     Ok(json!({
         "parent_id": params.parent_id,
         "child_count": child_count,
-        "children": children
+        "children": children,
+        "next_cursor": next_cursor
     }))
 }
 
@@ -846,8 +1136,9 @@ pub async fn handle_insert_child_at_index<C>(
 where
     C: surrealdb::Connection,
 {
-    let params: InsertChildAtIndexParams = serde_json::from_value(params)
+    let mut params: InsertChildAtIndexParams = serde_json::from_value(params)
         .map_err(|e| MCPError::invalid_params(format!("Invalid parameters: {}", e)))?;
+    params.parent_id = resolve_date_parent(params.parent_id);
 
     // 1. Ensure parent exists (auto-create if date format)
     ensure_parent_exists(node_service, &params.parent_id).await?;
@@ -1093,18 +1384,21 @@ where
     let params: GetChildAtIndexParams = serde_json::from_value(params)
         .map_err(|e| MCPError::invalid_params(format!("Invalid parameters: {}", e)))?;
 
-    // Get all children in order
-    let children =
-        get_children_ordered(node_service, &params.parent_id, params.include_content).await?;
-
-    // Get child at index
-    let child = children.get(params.index).ok_or_else(|| {
-        MCPError::invalid_params(format!(
-            "Index {} out of bounds (parent has {} children)",
-            params.index,
-            children.len()
-        ))
-    })?;
+    // Load only the child at index
+    let (children, child_count) = node_service
+        .get_children_page(&params.parent_id, params.index, 1)
+        .await
+        .map_err(service_error_to_mcp)?;
+    let child = children
+        .into_iter()
+        .next()
+        .map(|node| child_info(params.index, node, params.include_content))
+        .ok_or_else(|| {
+            MCPError::invalid_params(format!(
+                "Index {} out of bounds (parent has {} children)",
+                params.index, child_count
+            ))
+        })?;
 
     Ok(json!({
         "index": child.index,
@@ -1140,19 +1434,52 @@ where
         .map_err(|e| MCPError::internal_error(format!("Failed to get node: {}", e)))?
         .ok_or_else(|| MCPError::invalid_params(format!("Node '{}' not found", params.node_id)))?;
 
+    let outline = params.format == TreeFormat::Outline;
     let tree = build_tree_node(
         node_service,
         root,
         0,
         params.max_depth,
-        params.include_content,
-        params.include_metadata,
+        params.include_content || outline,
+        params.include_metadata && !outline,
     )
     .await?;
 
+    if outline {
+        let max_chars = params.max_content_chars.unwrap_or(OUTLINE_CONTENT_CHARS);
+        let mut text = String::new();
+        let node_count = write_outline(&tree, max_chars, &mut text);
+        return Ok(json!({
+            "node_id": tree.node_id,
+            "format": "outline",
+            "node_count": node_count,
+            "outline": text
+        }));
+    }
     Ok(json!(tree))
 }
 
+/// Append `node` and its descendants to `outline` as
+/// `- <first content line> [<type>:<id>]` lines indented by depth; returns
+/// the number of nodes written
+fn write_outline(node: &TreeNode, max_chars: usize, outline: &mut String) -> usize {
+    let content = node.content.as_deref().unwrap_or_default();
+    let line = content.lines().find(|line| !line.trim().is_empty());
+    outline.push_str(&"  ".repeat(node.depth));
+    outline.push_str("- ");
+    if let Some(line) = line {
+        outline.push_str(&truncate_chars(line.trim(), max_chars));
+        outline.push(' ');
+    }
+    outline.push_str(&format!("[{}:{}]\n", node.node_type, node.node_id));
+
+    1 + node
+        .children
+        .iter()
+        .map(|child| write_outline(child, max_chars, outline))
+        .sum::<usize>()
+}
+
 // =========================================================================
 // Batch Operations for Performance
 // =========================================================================
@@ -1194,6 +1521,7 @@ where
     C: surrealdb::Connection,
 {
     // Parse parameters
+    let projection = Projection::from_params(&params)?;
     let params: GetNodesBatchParams = serde_json::from_value(params)
         .map_err(|e| MCPError::invalid_params(format!("Invalid parameters: {}", e)))?;
 
@@ -1237,6 +1565,8 @@ where
         }
     }
 
+    projection.apply_all(&mut nodes, None);
+
     Ok(json!({
         "nodes": nodes,
         "not_found": not_found,
@@ -1275,6 +1605,9 @@ pub struct UpdateNodesBatchParams {
 pub struct BatchUpdateFailure {
     id: String,
     error: String,
+    /// Versions and current node state, when the update lost a version conflict
+    #[serde(skip_serializing_if = "Option::is_none")]
+    conflict: Option<Value>,
 }
 
 /// Update multiple nodes in a single request
@@ -1340,6 +1673,7 @@ where
                         failed.push(BatchUpdateFailure {
                             id: update.id.clone(),
                             error: format!("Node '{}' does not exist", update.id),
+                            conflict: None,
                         });
                         continue;
                     }
@@ -1349,6 +1683,7 @@ where
                         failed.push(BatchUpdateFailure {
                             id: update.id,
                             error: mcp_error.message,
+                            conflict: None,
                         });
                         continue;
                     }
@@ -1377,10 +1712,14 @@ where
             }
             Err(e) => {
                 tracing::warn!("Failed to update node {}: {}", update.id, e);
-                let mcp_error = service_error_to_mcp(e);
+                let mcp_error = write_error_to_mcp(node_service, e).await;
+                let conflict = (mcp_error.code == VERSION_CONFLICT)
+                    .then_some(mcp_error.data)
+                    .flatten();
                 failed.push(BatchUpdateFailure {
                     id: update.id,
                     error: mcp_error.message,
+                    conflict,
                 });
             }
         }
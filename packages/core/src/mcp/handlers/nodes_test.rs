@@ -4,7 +4,9 @@
 
 #[cfg(test)]
 mod tests {
-    use crate::mcp::types::{MCPError, INVALID_PARAMS, NODE_NOT_FOUND, VALIDATION_ERROR};
+    use crate::mcp::types::{
+        MCPError, INVALID_PARAMS, NODE_NOT_FOUND, QUERY_TIMEOUT, VALIDATION_ERROR,
+    };
     use serde_json::json;
 
     #[test]
@@ -104,6 +106,19 @@ mod tests {
         assert!(error.message.contains("Invalid"));
     }
 
+    #[test]
+    fn test_store_timeout_reaches_client_as_query_timeout() {
+        use crate::db::DatabaseError;
+        use crate::mcp::handlers::nodes::service_error_to_mcp;
+        use crate::services::NodeServiceError;
+
+        let timeout = DatabaseError::timeout(std::time::Duration::from_secs(30));
+        let error = NodeServiceError::from_query_error("Failed to get children", timeout.into());
+        let error = service_error_to_mcp(error);
+        assert_eq!(error.code, QUERY_TIMEOUT);
+        assert!(error.message.contains("timed out"));
+    }
+
     #[test]
     fn test_success_response_format() {
         let result = json!({
@@ -139,6 +154,7 @@ mod occ_tests {
     use crate::db::SurrealStore;
     use crate::mcp::handlers::nodes::handle_delete_node;
     use crate::mcp::handlers::nodes::handle_update_node;
+    use crate::mcp::handlers::nodes::handle_update_nodes_batch;
     use crate::mcp::types::VERSION_CONFLICT;
     use crate::services::CreateNodeParams;
     use crate::NodeService;
@@ -305,6 +321,39 @@ mod occ_tests {
         assert_eq!(data["current_node"]["version"], 2);
     }
 
+    /// Verifies a conflicting batch item reports the node to merge with
+    #[tokio::test]
+    async fn test_batch_update_conflict_includes_current_node() {
+        let (node_service, _temp) = setup_test_service().await.unwrap();
+
+        let node_id = node_service
+            .create_node_with_parent(CreateNodeParams {
+                id: None,
+                node_type: "text".to_string(),
+                content: "Original".to_string(),
+                parent_id: None,
+                insert_after_node_id: None,
+                properties: json!({}),
+            })
+            .await
+            .unwrap();
+        let params = json!({"node_id": node_id, "version": 1, "content": "First update"});
+        handle_update_node(&node_service, params).await.unwrap();
+
+        let params = json!({"updates": [
+            {"id": node_id, "version": 1, "content": "Stale update"}
+        ]});
+        let result = handle_update_nodes_batch(&node_service, params)
+            .await
+            .unwrap();
+
+        assert_eq!(result["count"], 0);
+        let conflict = &result["failed"][0]["conflict"];
+        assert_eq!(conflict["expected_version"], 1);
+        assert_eq!(conflict["actual_version"], 2);
+        assert_eq!(conflict["current_node"]["content"], "First update");
+    }
+
     /// Verifies delete operation checks version
     #[tokio::test]
     async fn test_delete_with_version_check() {
@@ -1382,7 +1431,7 @@ mod integration_tests {
 mod typed_response_tests {
     use crate::db::SurrealStore;
     use crate::mcp::handlers::nodes::{
-        handle_get_node, handle_get_nodes_batch, handle_query_nodes,
+        handle_get_node, handle_get_nodes_batch, handle_query_nodes, handle_run_query,
     };
     use crate::services::CreateNodeParams;
     use crate::NodeService;
@@ -1808,4 +1857,52 @@ mod property_namespace_tests {
             "Client should receive flat properties"
         );
     }
+
+    /// Verifies run_query executes a saved query node with parameters
+    #[tokio::test]
+    async fn test_run_query_fills_parameters() {
+        let (node_service, _temp_dir) = setup_test_service().await.unwrap();
+
+        for (content, status) in [("Open task", "open"), ("Done task", "done")] {
+            node_service
+                .create_node_with_parent(CreateNodeParams {
+                    id: None,
+                    node_type: "task".to_string(),
+                    content: content.to_string(),
+                    parent_id: None,
+                    insert_after_node_id: None,
+                    properties: json!({"task": {"status": status}}),
+                })
+                .await
+                .unwrap();
+        }
+        let query_id = node_service
+            .create_node_with_parent(CreateNodeParams {
+                id: None,
+                node_type: "query".to_string(),
+                content: "Tasks by status".to_string(),
+                parent_id: None,
+                insert_after_node_id: None,
+                properties: json!({"query": {
+                    "target_type": "task",
+                    "filters": [{
+                        "type": "property",
+                        "operator": "equals",
+                        "property": "status",
+                        "value": "$status"
+                    }]
+                }}),
+            })
+            .await
+            .unwrap();
+
+        let params = json!({ "query_node_id": query_id, "params": { "status": "open" } });
+        let result = handle_run_query(&node_service, params).await.unwrap();
+        assert_eq!(result["count"], 1);
+        assert_eq!(result["nodes"][0]["content"], "Open task");
+
+        // Placeholders without a value are rejected
+        let params = json!({ "query_node_id": query_id });
+        assert!(handle_run_query(&node_service, params).await.is_err());
+    }
 }
@@ -28,8 +28,9 @@
 //!
 //! This follows MCP and JavaScript conventions for wire format.
 
+use crate::mcp::pagination::{Page, DEFAULT_PAGE_LIMIT, MAX_PAGE_LIMIT};
 use crate::mcp::types::MCPError;
-use crate::services::{node_service::CompletenessResult, NodeService};
+use crate::services::{node_service::CompletenessResult, NodeService, NodeServiceError};
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use std::sync::Arc;
@@ -112,6 +113,9 @@ pub struct GetRelatedNodesOutput {
     pub direction: String,
     pub related_nodes: Vec<Value>,
     pub count: usize,
+    /// Named like the other list tools' cursors
+    #[serde(rename = "next_cursor")]
+    pub next_cursor: Option<String>,
 }
 
 /// Output for relationship graph
@@ -198,7 +202,10 @@ where
             edge_data,
         )
         .await
-        .map_err(|e| MCPError::internal_error(format!("Failed to create relationship: {}", e)))?;
+        .map_err(|e| match e {
+            NodeServiceError::SchemaViolation(_) => MCPError::validation_error(e.to_string()),
+            _ => MCPError::internal_error(format!("Failed to create relationship: {}", e)),
+        })?;
 
     Ok(serde_json::to_value(CreateRelationshipOutput {
         success: true,
@@ -256,6 +263,7 @@ where
 /// - `node_id` - ID of the node to get relationships for
 /// - `relationship_name` - Name of the relationship
 /// - `direction` - "out" for forward, "in" for reverse (default: "out")
+/// - `limit`, `cursor` - page size and the previous page's `next_cursor`
 ///
 /// ## Example
 /// ```json
@@ -272,18 +280,22 @@ pub async fn handle_get_related_nodes<C>(
 where
     C: surrealdb::Connection,
 {
+    let page = Page::from_params(&params, DEFAULT_PAGE_LIMIT, MAX_PAGE_LIMIT)?;
     let params: GetRelatedNodesParams = serde_json::from_value(params)
         .map_err(|e| MCPError::invalid_params(format!("Invalid parameters: {}", e)))?;
 
     let nodes = node_service
-        .get_related_nodes(
+        .get_related_nodes_page(
             &params.node_id,
             &params.relationship_name,
             &params.direction,
+            page.start(),
+            page.fetch_count(),
         )
         .await
         .map_err(|e| MCPError::internal_error(format!("Failed to get related nodes: {}", e)))?;
 
+    let (nodes, next_cursor) = page.finish(nodes);
     let count = nodes.len();
     let related_nodes: Vec<Value> = nodes
         .into_iter()
@@ -296,6 +308,7 @@ where
         direction: params.direction,
         related_nodes,
         count,
+        next_cursor,
     })
     .unwrap())
 }
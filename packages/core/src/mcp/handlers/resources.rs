@@ -0,0 +1,50 @@
+//! MCP Resource Subscription Handlers
+//!
+//! Nodes are resources addressed by their deep link (`nodespace://node/<id>`).
+//! Subscribing to one asks for `notifications/resources/updated` whenever it
+//! or its relationships change; see [`crate::mcp::session`].
+
+use crate::mcp::session::Session;
+use crate::mcp::types::MCPError;
+use crate::services::DeepLink;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+#[derive(Debug, Deserialize)]
+struct SubscriptionParams {
+    uri: String,
+}
+
+/// Node ID of a subscription's resource URI
+fn subscribed_node(session: &Session, params: Value) -> Result<String, MCPError> {
+    if !session.supports_subscriptions() {
+        return Err(MCPError::invalid_request(
+            "Resource subscriptions need a Streamable HTTP session".to_string(),
+        ));
+    }
+    let params: SubscriptionParams = serde_json::from_value(params)
+        .map_err(|e| MCPError::invalid_params(format!("Invalid parameters: {}", e)))?;
+    match DeepLink::parse(&params.uri) {
+        Ok(DeepLink::Node(id)) => Ok(id),
+        Ok(DeepLink::Date(date)) => Ok(date.format("%Y-%m-%d").to_string()),
+        Ok(_) => Err(MCPError::invalid_params(format!(
+            "Only nodes can be subscribed to, not '{}'",
+            params.uri
+        ))),
+        Err(e) => Err(MCPError::invalid_params(e.to_string())),
+    }
+}
+
+/// Handle `resources/subscribe`
+pub fn handle_subscribe(session: &Session, params: Value) -> Result<Value, MCPError> {
+    let node_id = subscribed_node(session, params)?;
+    session.subscribe(&node_id);
+    Ok(json!({}))
+}
+
+/// Handle `resources/unsubscribe`
+pub fn handle_unsubscribe(session: &Session, params: Value) -> Result<Value, MCPError> {
+    let node_id = subscribed_node(session, params)?;
+    session.unsubscribe(&node_id);
+    Ok(json!({}))
+}
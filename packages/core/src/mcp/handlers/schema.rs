@@ -3,10 +3,14 @@
 //! Provides the `create_schema` tool for creating custom schemas with fields and relationships.
 //! Supports both explicit field/relationship definitions and natural language descriptions
 //! with intelligent type inference.
+//!
+//! `define_schema` takes a complete schema instead and checks it against the
+//! stored version before writing, so agents can evolve their own types safely.
 
+use crate::behaviors::SchemaNodeBehavior;
 use crate::mcp::types::MCPError;
-use crate::models::schema::{EnumValue, SchemaField, SchemaProtectionLevel};
-use crate::models::NodeUpdate;
+use crate::models::schema::{EnumValue, SchemaField, SchemaProtectionLevel, SchemaRelationship};
+use crate::models::{diff_schemas, NodeUpdate, SchemaChange, SchemaChangeKind, SchemaNode};
 use crate::services::{CreateNodeParams, NodeService};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -554,6 +558,237 @@ where
         .map_err(|e| MCPError::internal_error(format!("Failed to serialize output: {}", e)))
 }
 
+// ============================================================================
+// Schema Definition
+// ============================================================================
+
+/// Parameters for define_schema
+#[derive(Debug, Deserialize)]
+pub struct DefineSchemaParams {
+    /// Schema name (e.g., "Invoice"); the schema ID is its snake_case
+    pub name: String,
+    /// Schema description (keeps the current one when omitted)
+    #[serde(default)]
+    pub description: Option<String>,
+    /// The complete field list of the new version
+    #[serde(default)]
+    pub fields: Vec<SchemaField>,
+    /// The complete relationship list of the new version
+    #[serde(default)]
+    pub relationships: Vec<SchemaRelationship>,
+    /// Apply the definition even though it has breaking changes
+    #[serde(default)]
+    pub confirm_breaking: bool,
+    /// Only validate and plan, never write
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// Output of define_schema
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DefineSchemaOutput {
+    pub schema_id: String,
+    /// Whether the schema was written
+    pub applied: bool,
+    /// Whether the schema is new
+    pub created: bool,
+    /// Schema version after the write (or that the write would produce)
+    pub version: u32,
+    /// Changes from the stored version; empty for new schemas
+    pub changes: Vec<SchemaChange>,
+    pub breaking: bool,
+    /// DDL the write runs along with the schema node
+    pub ddl: Vec<String>,
+    /// Why nothing was written, if it wasn't
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// Create a schema or replace it with a new version
+///
+/// # MCP Tool: define_schema
+///
+/// Takes the complete definition of the schema and:
+/// 1. validates it with [`SchemaNodeBehavior`],
+/// 2. diffs it against the stored version, if any (see [`diff_schemas`]),
+/// 3. plans the relationship and index DDL, and
+/// 4. writes it, unless it's a dry run or has breaking changes that
+///    `confirm_breaking` doesn't acknowledge.
+///
+/// Core- and system-protected fields of the stored version can't be changed
+/// or removed at all.
+///
+/// # Parameters
+/// - `name`: Schema name (e.g., "Invoice")
+/// - `description`: Optional schema description
+/// - `fields`: All fields of the new version
+/// - `relationships`: All relationships of the new version
+/// - `confirm_breaking`: Apply breaking changes
+/// - `dry_run`: Validate and plan only
+///
+/// # Returns
+/// - `applied`: Whether the schema was written
+/// - `changes`: Changes from the stored version, each flagged `breaking`
+/// - `ddl`: The DDL plan
+/// - `reason`: Why nothing was written
+///
+/// # Errors
+/// - `INVALID_PARAMS`: If the name is empty or a protected field changes
+/// - `VALIDATION_ERROR`: If the definition is invalid
+pub async fn handle_define_schema<C>(
+    node_service: &Arc<NodeService<C>>,
+    params: Value,
+) -> Result<Value, MCPError>
+where
+    C: surrealdb::Connection,
+{
+    let params: DefineSchemaParams = serde_json::from_value(params)
+        .map_err(|e| MCPError::invalid_params(format!("Invalid parameters: {}", e)))?;
+
+    if params.name.trim().is_empty() {
+        return Err(MCPError::invalid_params("name cannot be empty".to_string()));
+    }
+    let schema_id = normalize_schema_id(&params.name);
+
+    let existing = node_service
+        .get_schema_node(&schema_id)
+        .await
+        .map_err(|e| MCPError::internal_error(format!("Failed to get schema: {}", e)))?;
+
+    let now = chrono::Utc::now();
+    let proposed = SchemaNode {
+        id: schema_id.clone(),
+        content: params.name.clone(),
+        version: existing.as_ref().map_or(1, |schema| schema.version),
+        created_at: existing.as_ref().map_or(now, |schema| schema.created_at),
+        modified_at: now,
+        is_core: existing.as_ref().is_some_and(|schema| schema.is_core),
+        schema_version: existing
+            .as_ref()
+            .map_or(1, |schema| schema.schema_version + 1),
+        description: params
+            .description
+            .clone()
+            .or_else(|| existing.as_ref().map(|schema| schema.description.clone()))
+            .unwrap_or_else(|| format!("Schema for {}", params.name)),
+        fields: params.fields,
+        relationships: params.relationships,
+    };
+    SchemaNodeBehavior
+        .validate_schema_node(&proposed)
+        .map_err(|e| MCPError::validation_error(format!("Invalid schema: {}", e)))?;
+
+    let changes = match &existing {
+        Some(current) => {
+            let changes = diff_schemas(current, &proposed);
+            check_protected_fields(current, &changes)?;
+            changes
+        }
+        None => Vec::new(),
+    };
+    let breaking = changes.iter().any(|change| change.breaking);
+
+    let mut output = DefineSchemaOutput {
+        schema_id: schema_id.clone(),
+        applied: false,
+        created: existing.is_none(),
+        version: proposed.schema_version,
+        changes,
+        breaking,
+        ddl: Vec::new(),
+        reason: None,
+    };
+
+    if let Some(current) = &existing {
+        if output.changes.is_empty()
+            && current.content == proposed.content
+            && current.description == proposed.description
+        {
+            output.version = current.schema_version;
+            output.reason = Some("The schema is unchanged".to_string());
+            return output_value(&output);
+        }
+    }
+
+    let properties = serde_json::json!({
+        "isCore": proposed.is_core,
+        "schemaVersion": proposed.schema_version,
+        "description": proposed.description,
+        "fields": proposed.fields,
+        "relationships": proposed.relationships
+    });
+    output.ddl = node_service
+        .schema_ddl(&schema_id, &properties)
+        .await
+        .map_err(|e| MCPError::validation_error(format!("Invalid schema: {}", e)))?;
+
+    if params.dry_run {
+        output.reason = Some("Dry run".to_string());
+        return output_value(&output);
+    }
+    if breaking && !params.confirm_breaking {
+        output.reason = Some(
+            "The definition has breaking changes; call again with confirm_breaking: true to apply it"
+                .to_string(),
+        );
+        return output_value(&output);
+    }
+
+    if existing.is_some() {
+        let update = NodeUpdate {
+            content: Some(params.name),
+            properties: Some(properties),
+            ..Default::default()
+        };
+        node_service
+            .update_node_unchecked(&schema_id, update)
+            .await
+            .map_err(|e| MCPError::internal_error(format!("Failed to update schema: {}", e)))?;
+    } else {
+        node_service
+            .create_node_with_parent(CreateNodeParams {
+                id: Some(schema_id.clone()),
+                node_type: "schema".to_string(),
+                content: params.name,
+                parent_id: None,
+                insert_after_node_id: None,
+                properties,
+            })
+            .await
+            .map_err(|e| MCPError::internal_error(format!("Failed to create schema: {}", e)))?;
+    }
+    output.applied = true;
+    output_value(&output)
+}
+
+/// Reject changes to fields of `current` that aren't user-protected
+fn check_protected_fields(current: &SchemaNode, changes: &[SchemaChange]) -> Result<(), MCPError> {
+    let field_changes = changes.iter().filter(|change| {
+        matches!(
+            change.kind,
+            SchemaChangeKind::FieldAdded
+                | SchemaChangeKind::FieldRemoved
+                | SchemaChangeKind::FieldChanged
+        )
+    });
+    for change in field_changes {
+        let name = change.root_name();
+        if current.get_field(name).is_some() && !current.can_modify_field(name) {
+            return Err(MCPError::invalid_params(format!(
+                "Field '{}' of schema '{}' is protected and cannot change ({}: {})",
+                name, current.id, change.path, change.detail
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn output_value(output: &DefineSchemaOutput) -> Result<Value, MCPError> {
+    serde_json::to_value(output)
+        .map_err(|e| MCPError::internal_error(format!("Failed to serialize output: {}", e)))
+}
+
 /// Parse natural language description and extract fields
 fn parse_field_descriptions(description: &str) -> Vec<InferredField> {
     let mut fields = Vec::new();
@@ -906,6 +1141,7 @@ fn normalize_and_namespace_fields(inferred_fields: Vec<InferredField>) -> Vec<Sc
                 values.iter().map(|v| EnumValue {
                     value: v.clone(),
                     label: to_title_case(v),
+                    deprecated: None,
                 }).collect()
             });
 
@@ -917,6 +1153,7 @@ fn normalize_and_namespace_fields(inferred_fields: Vec<InferredField>) -> Vec<Sc
                 user_values,
                 indexed: false, // Not indexed by default
                 required: Some(inferred.required),
+                recommended: None,
                 extensible: Some(inferred.field_type == "enum"), // Enums are extensible by default
                 default: None,
                 description: None,
@@ -3,9 +3,14 @@
 //! Semantic search operations for AI agent access.
 //! Pure business logic - no Tauri dependencies.
 
+use crate::api::SearchResultDto;
+use crate::db::DatabaseError;
+use crate::mcp::pagination::{Page, MAX_PAGE_LIMIT};
+use crate::mcp::projection::Projection;
 use crate::mcp::types::MCPError;
 use crate::models::Node;
 use crate::services::{CollectionService, NodeEmbeddingService, NodeService, NodeServiceError};
+use crate::utils::truncate_tokens;
 use serde::Deserialize;
 use serde_json::{json, Value};
 use std::collections::{HashMap, HashSet};
@@ -14,6 +19,12 @@ use std::sync::Arc;
 /// Maximum depth for markdown tree traversal (prevents stack overflow on deeply nested documents)
 const MARKDOWN_MAX_DEPTH: usize = 20;
 
+/// Token budget for each document's inline markdown in search results
+const MARKDOWN_MAX_TOKENS: usize = 4000;
+
+/// Results per page when the caller doesn't set `limit`
+const SEARCH_DEFAULT_LIMIT: usize = 20;
+
 /// Recursively build markdown from a node tree
 /// This is a simplified version that produces clean markdown without node ID comments
 fn build_markdown_recursive(
@@ -99,7 +110,8 @@ pub struct SearchSemanticParams {
     #[serde(default)]
     pub threshold: Option<f32>,
 
-    /// Maximum number of results
+    /// Results per page; pass the previous page's `next_cursor` as `cursor`
+    /// for the next one
     /// Default: 20
     #[serde(default)]
     pub limit: Option<usize>,
@@ -120,7 +132,8 @@ pub struct SearchSemanticParams {
     /// Number of top results to include full markdown content for (0-5)
     /// This saves AI agents from needing to call get_markdown_from_node_id separately.
     /// Default: 1 (include markdown for top result only)
-    /// Set to 0 to disable, max 5 to limit response size.
+    /// Set to 0 to disable, max 5 to limit response size. Long documents are
+    /// cut at a word boundary and end in "…".
     #[serde(default)]
     pub include_markdown: Option<usize>,
 
@@ -148,6 +161,9 @@ pub struct SearchSemanticParams {
 /// let result = handle_search_semantic(&node_service, &embedding_service, params).await?;
 /// // Returns top 10 most relevant root nodes
 /// ```
+///
+/// `fields` and `max_content_chars` trim each result node (see
+/// [`Projection`]); content snippets centre on the query's words.
 pub async fn handle_search_semantic<C>(
     node_service: &Arc<NodeService<C>>,
     embedding_service: &Arc<NodeEmbeddingService<C>>,
@@ -157,13 +173,14 @@ where
     C: surrealdb::Connection,
 {
     // Parse parameters
+    let projection = Projection::from_params(&params)?;
+    let page = Page::from_params(&params, SEARCH_DEFAULT_LIMIT, MAX_PAGE_LIMIT)?;
     let params: SearchSemanticParams = serde_json::from_value(params)
         .map_err(|e| MCPError::invalid_params(format!("Invalid parameters: {}", e)))?;
 
     // Apply defaults (authoritative - schema defaults are client hints only)
     // These values override any client-side defaults from the JSON schema
     let threshold = params.threshold.unwrap_or(0.7);
-    let limit = params.limit.unwrap_or(20);
     let include_markdown = params.include_markdown.unwrap_or(1).min(5); // Default 1, max 5
     let include_archived = params.include_archived.unwrap_or(false);
 
@@ -174,12 +191,6 @@ where
         ));
     }
 
-    if limit > 1000 {
-        return Err(MCPError::invalid_params(
-            "limit cannot exceed 1000".to_string(),
-        ));
-    }
-
     if params.query.trim().is_empty() {
         return Err(MCPError::invalid_params(
             "query cannot be empty or whitespace".to_string(),
@@ -216,7 +227,8 @@ where
                         "count": 0,
                         "query": params.query,
                         "threshold": threshold,
-                        "collection_id": null
+                        "collection_id": null,
+                        "next_cursor": null
                     }));
                 }
                 Err(e) => {
@@ -278,15 +290,25 @@ where
 
     // When filtering by collection, excluding collections, or excluding archived nodes, fetch more results to compensate for post-filtering
     // Note: We always filter out archived/deleted nodes unless include_archived is true, so we always have some post-filtering
+    // Hits are ranked by similarity and filtered after the search, so the
+    // page's leading hits are fetched too and skipped below
     let has_post_filters =
         collection_member_ids.is_some() || !excluded_node_ids.is_empty() || !include_archived;
-    let effective_limit = if has_post_filters { limit * 3 } else { limit };
+    let fetch_count = page.start() + page.fetch_count();
+    let effective_limit = if has_post_filters {
+        fetch_count * 3
+    } else {
+        fetch_count
+    };
 
     // Call the embedding service's semantic search
     let results = embedding_service
         .semantic_search_nodes(&params.query, effective_limit, threshold)
         .await
         .map_err(|e| {
+            if let NodeServiceError::DatabaseError(e @ DatabaseError::Timeout { .. }) = e {
+                return MCPError::query_timeout(e.to_string());
+            }
             let err_msg = e.to_string();
 
             // Check for specific error types to provide actionable feedback
@@ -330,8 +352,9 @@ where
             }
             true
         })
-        .take(limit)
+        .skip(page.start())
         .collect();
+    let (filtered_results, next_cursor) = page.finish(filtered_results);
 
     // Fetch markdown for top N results if requested
     // This saves AI agents from needing to call get_markdown_from_node_id separately
@@ -364,38 +387,30 @@ where
                     }
                 }
 
-                markdown_contents.insert(node.id.clone(), markdown.trim().to_string());
+                markdown_contents.insert(
+                    node.id.clone(),
+                    truncate_tokens(markdown.trim(), MARKDOWN_MAX_TOKENS),
+                );
             }
         }
     }
 
-    // Transform results into JSON-serializable format
-    let nodes: Vec<Value> = filtered_results
-        .iter()
-        .enumerate()
-        .map(|(idx, (node, similarity))| {
-            let mut node_json = json!({
-                "id": node.id,
-                "nodeType": node.node_type,
-                "content": node.content,
-                "title": node.title,
-                "version": node.version,
-                "createdAt": node.created_at,
-                "modifiedAt": node.modified_at,
-                "properties": node.properties,
-                "similarity": similarity
-            });
-
-            // Include markdown for top N results
-            if idx < include_markdown {
-                if let Some(markdown) = markdown_contents.get(&node.id) {
-                    node_json["markdown"] = json!(markdown);
-                }
-            }
-
-            node_json
-        })
-        .collect();
+    // Shared search result shape (typed node + similarity)
+    let mut nodes = Vec::with_capacity(filtered_results.len());
+    for (idx, (node, similarity)) in filtered_results.into_iter().enumerate() {
+        // Include markdown for top N results
+        let markdown = if idx < include_markdown {
+            markdown_contents.remove(&node.id)
+        } else {
+            None
+        };
+        let mut result =
+            SearchResultDto::new(node, similarity).map_err(MCPError::internal_error)?;
+        result.markdown = markdown;
+        // Similarity, excerpt and markdown are kept; they are what the search adds
+        projection.apply(&mut result.node.0, Some(&params.query));
+        nodes.push(result);
+    }
 
     // Return results with metadata
     Ok(json!({
@@ -405,7 +420,69 @@ where
         "threshold": threshold,
         "collection_id": collection_id,
         "include_markdown": include_markdown,
-        "include_archived": include_archived
+        "include_archived": include_archived,
+        "next_cursor": next_cursor
+    }))
+}
+
+/// Default summary length in tokens
+const SUMMARY_DEFAULT_TOKENS: usize = 150;
+
+/// Longest summary a caller may request
+const SUMMARY_MAX_TOKENS: usize = 1000;
+
+/// Parameters for summarize_node method
+#[derive(Debug, Deserialize)]
+pub struct SummarizeNodeParams {
+    /// Node whose content (including its subtree) is summarized
+    pub node_id: String,
+
+    /// Approximate summary length in tokens
+    /// Default: 150, max 1000
+    #[serde(default)]
+    pub max_tokens: Option<usize>,
+}
+
+/// Summarize a node and its subtree with the local summarization model
+///
+/// Runs entirely offline. Hosts without a generation model return an
+/// extractive summary (leading sentences), flagged by `"generated": false`.
+pub async fn handle_summarize_node<C>(
+    embedding_service: &Arc<NodeEmbeddingService<C>>,
+    params: Value,
+) -> Result<Value, MCPError>
+where
+    C: surrealdb::Connection,
+{
+    let params: SummarizeNodeParams = serde_json::from_value(params)
+        .map_err(|e| MCPError::invalid_params(format!("Invalid parameters: {}", e)))?;
+
+    let max_tokens = params.max_tokens.unwrap_or(SUMMARY_DEFAULT_TOKENS);
+    if max_tokens == 0 || max_tokens > SUMMARY_MAX_TOKENS {
+        return Err(MCPError::invalid_params(format!(
+            "max_tokens must be between 1 and {}",
+            SUMMARY_MAX_TOKENS
+        )));
+    }
+
+    let generated = embedding_service
+        .summarizer()
+        .is_some_and(|summarizer| summarizer.has_model());
+    let summary = embedding_service
+        .summarize_node(&params.node_id, max_tokens)
+        .await
+        .map_err(|e| match e {
+            NodeServiceError::NodeNotFound { id } => MCPError::node_not_found(&id),
+            NodeServiceError::InitializationError(_) => {
+                MCPError::internal_error("Summarization service not ready".to_string())
+            }
+            e => MCPError::internal_error(format!("Summarization failed: {}", e)),
+        })?;
+
+    Ok(json!({
+        "node_id": params.node_id,
+        "summary": summary,
+        "generated": generated
     }))
 }
 
@@ -12,16 +12,41 @@
 //!
 //! All tools remain callable via tools/call regardless of tier.
 //!
+//! ## Tool Registry
+//!
+//! Every tool is declared once in `tool_registry` with its name, tier,
+//! category, input schema and handler; tools/list, search_tools and the
+//! tools/call dispatch are all derived from it. Each server builds it once
+//! as a [`ToolIndex`] (kept in `McpServices`), which tools/call looks tools
+//! up in by name.
+//!
 //! As of Issue #676, all handlers use NodeService directly instead of NodeOperations.
 //! As of Issue #690, SchemaService was removed - schema nodes use generic CRUD.
 
+use crate::mcp::access::{AccessScope, AgentProfile};
 use crate::mcp::handlers::{markdown, nodes, relationships, schema, search};
-use crate::mcp::types::MCPError;
-use crate::services::{NodeEmbeddingService, NodeService};
+use crate::mcp::types::{MCPError, VERSION_CONFLICT};
+use crate::services::{IdempotencyService, IdempotentStart, NodeEmbeddingService, NodeService};
+use crate::utils::current_correlation_id;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
 use std::sync::Arc;
 
+/// Mutating tools that accept an `idempotency_key` argument, so a retried
+/// call doesn't create, move or delete twice
+const IDEMPOTENT_TOOLS: &[&str] = &[
+    "create_node",
+    "update_node",
+    "delete_node",
+    "insert_child_at_index",
+    "move_child_to_index",
+    "create_nodes_from_markdown",
+    "update_nodes_batch",
+];
+
 /// Tool exposure tier for progressive disclosure
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ToolTier {
@@ -43,9 +68,9 @@ pub enum ToolCategory {
     Hierarchy,
     /// Markdown import/export (create_nodes_from_markdown, etc.)
     Markdown,
-    /// Semantic search operations (search_semantic)
+    /// Semantic search and summaries (search_semantic, summarize_node)
     Search,
-    /// Schema management (create_schema, get_all_schemas, update_schema)
+    /// Schema management (create_schema, define_schema, get_all_schemas, update_schema)
     Schema,
     /// Relationship operations (create_relationship, get_related_nodes, etc.)
     Relationships,
@@ -77,76 +102,103 @@ fn default_search_limit() -> usize {
     10
 }
 
-/// Get the tier for a given tool
-///
-/// FUTURE: This will be user-configurable, allowing users to customize which tools
-/// are exposed as "core" vs "discoverable" based on their workflows and preferences.
-/// For now, we hardcode based on common use cases (CRUD, markdown, search).
-fn get_tool_tier(tool_name: &str) -> ToolTier {
-    match tool_name {
-        // Tier 1: Core CRUD operations
-        "create_node" | "get_node" | "update_node" | "delete_node" => ToolTier::Core,
-
-        // Tier 1: Essential query
-        "query_nodes" => ToolTier::Core,
+/// Future returned by a tool handler
+pub type ToolFuture<'a> = Pin<Box<dyn Future<Output = Result<Value, MCPError>> + Send + 'a>>;
 
-        // Tier 1: Basic hierarchy
-        "get_children" | "insert_child_at_index" => ToolTier::Core,
+/// Executes a tool call with its `arguments`
+pub type ToolHandler<C> = for<'a> fn(ToolContext<'a, C>, Value) -> ToolFuture<'a>;
 
-        // Tier 1: Semantic search (core value proposition)
-        "search_semantic" => ToolTier::Core,
-
-        // Tier 1: Markdown import/export (primary workflows)
-        "create_nodes_from_markdown" | "get_markdown_from_node_id" => ToolTier::Core,
-
-        // Tier 1: Discovery
-        "get_all_schemas" | "search_tools" => ToolTier::Core,
+/// Services available to tool handlers
+pub struct ToolContext<'a, C>
+where
+    C: surrealdb::Connection,
+{
+    pub node_service: &'a Arc<NodeService<C>>,
+    pub embedding_service: &'a Arc<NodeEmbeddingService<C>>,
+    pub tools: &'a ToolIndex<C>,
+}
 
-        // Tier 1: Core relationships (Issue #814)
-        // create_relationship handles built-in types (member_of, has_child, mentions)
-        // that are essential for collection membership and node linking
-        "create_relationship" => ToolTier::Core,
+/// A tool of the registry: what tools/list and search_tools describe, and
+/// what tools/call runs
+pub struct Tool<C = surrealdb::engine::local::Db>
+where
+    C: surrealdb::Connection,
+{
+    pub name: &'static str,
+    pub description: &'static str,
+    /// FUTURE: user-configurable, so workflows can promote the tools they
+    /// rely on to core
+    pub tier: ToolTier,
+    pub category: ToolCategory,
+    /// JSON schema of the arguments
+    pub input_schema: Value,
+    pub handler: ToolHandler<C>,
+}
 
-        // Tier 2: Everything else is discoverable
-        _ => ToolTier::Discoverable,
+impl<C> Tool<C>
+where
+    C: surrealdb::Connection,
+{
+    /// MCP tool definition (name, description, inputSchema)
+    pub fn definition(&self) -> Value {
+        let mut input_schema = self.input_schema.clone();
+        if IDEMPOTENT_TOOLS.contains(&self.name) {
+            input_schema["properties"]["idempotency_key"] = json!({
+                "type": "string",
+                "description": "Optional key for safe retries: repeating the call with the same key and arguments returns the first call's result instead of running again"
+            });
+        }
+        json!({
+            "name": self.name,
+            "description": self.description,
+            "inputSchema": input_schema
+        })
     }
 }
 
-/// Get the category for a given tool
-fn get_tool_category(tool_name: &str) -> ToolCategory {
-    match tool_name {
-        "create_node" | "get_node" | "update_node" | "delete_node" => ToolCategory::Crud,
-
-        "query_nodes" | "get_nodes_batch" | "update_nodes_batch" => ToolCategory::Query,
-
-        "get_children"
-        | "insert_child_at_index"
-        | "move_child_to_index"
-        | "get_child_at_index"
-        | "get_node_tree" => ToolCategory::Hierarchy,
-
-        "get_node_collections" => ToolCategory::Query,
-
-        "create_nodes_from_markdown"
-        | "get_markdown_from_node_id"
-        | "update_root_from_markdown" => ToolCategory::Markdown,
-
-        "search_semantic" => ToolCategory::Search,
+/// The tool registry, indexed by tool name
+///
+/// Built once per server and kept in `McpServices`; lookups need no lock.
+pub struct ToolIndex<C = surrealdb::engine::local::Db>
+where
+    C: surrealdb::Connection,
+{
+    tools: Vec<Tool<C>>,
+    by_name: HashMap<&'static str, usize>,
+}
 
-        "create_schema" | "get_all_schemas" | "update_schema" => ToolCategory::Schema,
+impl<C> ToolIndex<C>
+where
+    C: surrealdb::Connection,
+{
+    pub fn new() -> Self {
+        let tools = tool_registry::<C>();
+        let by_name = tools
+            .iter()
+            .enumerate()
+            .map(|(position, tool)| (tool.name, position))
+            .collect();
+        Self { tools, by_name }
+    }
 
-        "create_relationship"
-        | "delete_relationship"
-        | "get_related_nodes"
-        | "get_relationship_graph"
-        | "get_inbound_relationships"
-        | "check_node_completeness"
-        | "add_schema_relationship"
-        | "remove_schema_relationship" => ToolCategory::Relationships,
+    pub fn get(&self, name: &str) -> Option<&Tool<C>> {
+        self.by_name
+            .get(name)
+            .map(|&position| &self.tools[position])
+    }
 
-        "search_tools" => ToolCategory::Discovery,
+    /// All tools, in registry order
+    pub fn tools(&self) -> &[Tool<C>] {
+        &self.tools
+    }
+}
 
-        _ => ToolCategory::Query, // Default fallback
+impl<C> Default for ToolIndex<C>
+where
+    C: surrealdb::Connection,
+{
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -179,35 +231,29 @@ fn tool_supports_node_type(_tool_name: &str, _node_type: &str) -> bool {
 /// # Returns
 ///
 /// Returns filtered tool definitions matching the search criteria
-pub fn handle_search_tools(params: Value) -> Result<Value, MCPError> {
+pub fn handle_search_tools<C>(tools: &ToolIndex<C>, params: Value) -> Result<Value, MCPError>
+where
+    C: surrealdb::Connection,
+{
     let params: SearchToolsParams = serde_json::from_value(params)
         .map_err(|e| MCPError::invalid_params(format!("Invalid parameters: {}", e)))?;
 
-    // Get all tool schemas
-    let all_tools = get_tool_schemas();
-    let tools_array = all_tools
-        .as_array()
-        .ok_or_else(|| MCPError::internal_error("Tool schemas not an array".to_string()))?;
-
     // Apply filters (only return Tier 2 Discoverable tools)
-    let filtered: Vec<Value> = tools_array
+    let filtered: Vec<Value> = tools
+        .tools()
         .iter()
         .filter(|tool| {
-            let name = tool["name"].as_str().unwrap_or("");
-            let desc = tool["description"].as_str().unwrap_or("");
-            let category = get_tool_category(name);
-
             // Only include Tier 2 (Discoverable) tools
             // Tier 1 tools are already exposed via tools/list
-            if matches!(get_tool_tier(name), ToolTier::Core) {
+            if tool.tier == ToolTier::Core {
                 return false;
             }
 
             // Query filter (case-insensitive search in name and description)
             if let Some(q) = &params.query {
                 let query_lower = q.to_lowercase();
-                if !name.to_lowercase().contains(&query_lower)
-                    && !desc.to_lowercase().contains(&query_lower)
+                if !tool.name.to_lowercase().contains(&query_lower)
+                    && !tool.description.to_lowercase().contains(&query_lower)
                 {
                     return false;
                 }
@@ -215,14 +261,14 @@ pub fn handle_search_tools(params: Value) -> Result<Value, MCPError> {
 
             // Category filter
             if let Some(cat) = &params.category {
-                if category != *cat {
+                if tool.category != *cat {
                     return false;
                 }
             }
 
             // Node type filter
             if let Some(node_type) = &params.node_type {
-                if !tool_supports_node_type(name, node_type) {
+                if !tool_supports_node_type(tool.name, node_type) {
                     return false;
                 }
             }
@@ -230,7 +276,7 @@ pub fn handle_search_tools(params: Value) -> Result<Value, MCPError> {
             true
         })
         .take(params.limit)
-        .cloned()
+        .map(Tool::definition)
         .collect();
 
     Ok(json!({
@@ -265,21 +311,16 @@ pub fn handle_search_tools(params: Value) -> Result<Value, MCPError> {
 ///   ]
 /// }
 /// ```
-pub fn handle_tools_list(_params: Value) -> Result<Value, MCPError> {
-    // Get all tool schemas
-    let all_tools = get_tool_schemas();
-    let tools_array = all_tools
-        .as_array()
-        .ok_or_else(|| MCPError::internal_error("Tool schemas not an array".to_string()))?;
-
+pub fn handle_tools_list<C>(tools: &ToolIndex<C>, _params: Value) -> Result<Value, MCPError>
+where
+    C: surrealdb::Connection,
+{
     // Filter to only Tier 1 (Core) tools
-    let tier1_tools: Vec<Value> = tools_array
+    let tier1_tools: Vec<Value> = tools
+        .tools()
         .iter()
-        .filter(|tool| {
-            let name = tool["name"].as_str().unwrap_or("");
-            matches!(get_tool_tier(name), ToolTier::Core)
-        })
-        .cloned()
+        .filter(|tool| tool.tier == ToolTier::Core)
+        .map(Tool::definition)
         .collect();
 
     Ok(json!({
@@ -320,22 +361,35 @@ pub fn handle_tools_list(_params: Value) -> Result<Value, MCPError> {
 ///     "type": "text",
 ///     "text": "Error message"
 ///   }],
-///   "isError": true
+///   "isError": true,
+///   "structuredContent": { "error": { "code": -32005, "message": "...", "data": {} } }
 /// }
 /// ```
 ///
+/// `structuredContent` is present when the error carries data, which
+/// includes the request's `correlation_id`. A version conflict's data
+/// (versions and `current_node`) is also appended to the text.
+///
+/// Mutating tools take an optional `idempotency_key` (see
+/// `IDEMPOTENT_TOOLS`): a successful result is remembered under it for an
+/// hour and returned to a retry with the same arguments.
+///
 /// # Arguments
 ///
+/// * `tools` - The server's tool registry
 /// * `node_service` - Arc reference to NodeService for node operations
 /// * `embedding_service` - Arc reference to NodeEmbeddingService for search
+/// * `profile` - Agent profile scoping the connection (`None` = unrestricted)
 /// * `params` - Request parameters containing `name` and `arguments`
 ///
 /// # Returns
 ///
 /// Returns JSON result with content array and isError flag per MCP spec
 pub async fn handle_tools_call<C>(
+    tools: &ToolIndex<C>,
     node_service: &Arc<NodeService<C>>,
     embedding_service: &Arc<NodeEmbeddingService<C>>,
+    profile: Option<&AgentProfile>,
     params: Value,
 ) -> Result<Value, MCPError>
 where
@@ -346,96 +400,67 @@ where
         .as_str()
         .ok_or_else(|| MCPError::invalid_params("Missing 'name' parameter".to_string()))?;
 
-    // Extract arguments (defaults to empty object if missing)
-    let arguments = params.get("arguments").cloned().unwrap_or(json!({}));
-
-    // Route to appropriate handler based on tool name
-    let result = match tool_name {
-        // Core Node CRUD
-        "create_node" => nodes::handle_create_node(node_service, arguments).await,
-        "get_node" => nodes::handle_get_node(node_service, arguments).await,
-        "update_node" => nodes::handle_update_node(node_service, arguments).await,
-        "delete_node" => nodes::handle_delete_node(node_service, arguments).await,
-        "query_nodes" => nodes::handle_query_nodes(node_service, arguments).await,
-
-        // Hierarchy & Children (Index-Based Operations)
-        "get_children" => nodes::handle_get_children(node_service, arguments).await,
-        "get_child_at_index" => nodes::handle_get_child_at_index(node_service, arguments).await,
-        "insert_child_at_index" => {
-            nodes::handle_insert_child_at_index(node_service, arguments).await
-        }
-        "move_child_to_index" => nodes::handle_move_child_to_index(node_service, arguments).await,
-        "get_node_tree" => nodes::handle_get_node_tree(node_service, arguments).await,
-        "get_node_collections" => nodes::handle_get_node_collections(node_service, arguments).await,
-
-        // Markdown Import/Export
-        "create_nodes_from_markdown" => {
-            markdown::handle_create_nodes_from_markdown(node_service, arguments).await
-        }
-        "get_markdown_from_node_id" => {
-            markdown::handle_get_markdown_from_node_id(node_service, arguments).await
-        }
-        // Root node bulk replacement
-        "update_root_from_markdown" => {
-            markdown::handle_update_root_from_markdown(node_service, arguments).await
-        }
-
-        // Batch Operations
-        "get_nodes_batch" => nodes::handle_get_nodes_batch(node_service, arguments).await,
-        "update_nodes_batch" => nodes::handle_update_nodes_batch(node_service, arguments).await,
-
-        // Search
-        "search_semantic" => {
-            search::handle_search_semantic(node_service, embedding_service, arguments).await
-        }
-
-        // Discovery
-        "search_tools" => handle_search_tools(arguments),
+    let tool = tools
+        .get(tool_name)
+        .ok_or_else(|| MCPError::invalid_params(format!("Unknown tool: {}", tool_name)))?;
 
-        // Schema creation (uses generic node creation)
-        "create_schema" => schema::handle_create_schema(node_service, arguments).await,
-
-        // Relationship CRUD (Issue #703)
-        "create_relationship" => {
-            relationships::handle_create_relationship(node_service, arguments).await
-        }
-        "delete_relationship" => {
-            relationships::handle_delete_relationship(node_service, arguments).await
-        }
-        "get_related_nodes" => {
-            relationships::handle_get_related_nodes(node_service, arguments).await
+    // Extract arguments (defaults to empty object if missing)
+    let mut arguments = params.get("arguments").cloned().unwrap_or(json!({}));
+    let idempotency_key = match arguments
+        .as_object_mut()
+        .filter(|_| IDEMPOTENT_TOOLS.contains(&tool_name))
+        .and_then(|arguments| arguments.remove("idempotency_key"))
+    {
+        Some(Value::String(key)) => Some(key),
+        None | Some(Value::Null) => None,
+        Some(_) => {
+            return tool_response(Err(MCPError::invalid_params(
+                "idempotency_key must be a string".to_string(),
+            )))
         }
+    };
 
-        // NLP Discovery API (Issue #703)
-        "get_relationship_graph" => {
-            relationships::handle_get_relationship_graph(node_service, arguments).await
-        }
-        "get_inbound_relationships" => {
-            relationships::handle_get_inbound_relationships(node_service, arguments).await
-        }
-        "get_all_schemas" => relationships::handle_get_all_schemas(node_service, arguments).await,
-        "check_node_completeness" => {
-            relationships::handle_check_node_completeness(node_service, arguments).await
+    // Scoped agents only reach nodes under the roots their profile covers
+    let scope = match profile {
+        Some(profile) => Some(AccessScope::resolve(node_service, profile).await?),
+        None => None,
+    };
+    if let Some(scope) = &scope {
+        if let Err(e) = scope.authorize(node_service, tool_name, &arguments).await {
+            return tool_response(Err(e));
         }
+    }
 
-        // Schema Definition Management (Issue #703)
-        "add_schema_relationship" => {
-            schema::handle_add_schema_relationship(node_service, arguments).await
-        }
-        "remove_schema_relationship" => {
-            schema::handle_remove_schema_relationship(node_service, arguments).await
+    let context = ToolContext {
+        node_service,
+        embedding_service,
+        tools,
+    };
+    // A retry with the same key gets the first call's result back
+    let idempotency = IdempotencyService::new(node_service);
+    let result = match idempotency
+        .start(idempotency_key.as_deref(), tool_name, &arguments)
+        .await
+    {
+        Ok(IdempotentStart::Replay(data)) => Ok(data),
+        Ok(IdempotentStart::Run(claim)) => {
+            let result = (tool.handler)(context, arguments).await;
+            idempotency.finish(claim, &result).await;
+            result
         }
-        "update_schema" => schema::handle_update_schema(node_service, arguments).await,
+        Err(e) => Err(nodes::service_error_to_mcp(e)),
+    };
 
-        _ => {
-            return Err(MCPError::invalid_params(format!(
-                "Unknown tool: {}",
-                tool_name
-            )))
-        }
+    let result = match (&scope, result) {
+        (Some(scope), Ok(data)) => scope.filter_result(node_service, data).await,
+        (_, result) => result,
     };
 
-    // Format response per MCP spec with content array and isError flag
+    tool_response(result)
+}
+
+/// Format a tool result per MCP spec with content array and isError flag
+fn tool_response(result: Result<Value, MCPError>) -> Result<Value, MCPError> {
     match result {
         Ok(data) => {
             // Success: Serialize result as pretty JSON text in content array
@@ -455,41 +480,52 @@ where
             // Error: Return error message in content array with isError=true
             // This follows MCP spec: tool execution errors are returned as successful
             // responses with isError=true, not as JSON-RPC errors
-            Ok(json!({
+            let e = match current_correlation_id() {
+                Some(correlation_id) => e.with_correlation_id(&correlation_id),
+                None => e,
+            };
+            let mut text = e.message.clone();
+            if e.code == VERSION_CONFLICT {
+                // Agents that only read the text still get the node to merge with
+                if let Some(data) = &e.data {
+                    text.push_str("\n\n");
+                    text.push_str(&serde_json::to_string_pretty(data).unwrap_or_default());
+                }
+            }
+            let mut response = json!({
                 "content": [{
                     "type": "text",
-                    "text": e.message
+                    "text": text
                 }],
                 "isError": true
-            }))
+            });
+            if e.data.is_some() {
+                response["structuredContent"] = json!({
+                    "error": { "code": e.code, "message": e.message, "data": e.data }
+                });
+            }
+            Ok(response)
         }
     }
 }
 
-/// Generate JSON schemas for all available MCP tools
-///
-/// This function defines the complete tool catalog exposed by the MCP server.
-/// Schemas are manually maintained to provide high-quality descriptions and
-/// precise control over the API surface.
+/// The complete tool catalog exposed by the MCP server
 ///
-/// # Design Rationale
-///
-/// Manual schemas (vs auto-generated) allow for:
-/// - Human-crafted explanations optimized for AI understanding
-/// - Detailed field-level documentation with examples
-/// - Specific enum values that may differ from internal types
-/// - Fine-grained control over what's exposed to MCP clients
-///
-/// # Future Enhancement
-///
-/// Consider auto-generating schemas from Rust types with proc macros,
-/// while preserving ability to override descriptions (see Issue #312).
-fn get_tool_schemas() -> Value {
-    json!([
-        {
-            "name": "create_node",
-            "description": "Create a new node in NodeSpace",
-            "inputSchema": {
+/// Each tool's schema sits next to the handler that parses its arguments, so
+/// tools/list, search_tools and tools/call can't disagree about which tools
+/// exist. Schemas are written by hand for descriptions tuned to AI agents
+/// and precise control over what's exposed.
+fn tool_registry<C>() -> Vec<Tool<C>>
+where
+    C: surrealdb::Connection,
+{
+    vec![
+        Tool {
+            name: "create_node",
+            description: "Create a new node in NodeSpace",
+            tier: ToolTier::Core,
+            category: ToolCategory::Crud,
+            input_schema: json!({
                 "type": "object",
                 "properties": {
                     "node_type": {
@@ -503,11 +539,7 @@ fn get_tool_schemas() -> Value {
                     },
                     "parent_id": {
                         "type": "string",
-                        "description": "Optional parent node ID for hierarchy"
-                    },
-                    "root_id": {
-                        "type": "string",
-                        "description": "Optional root/document ID"
+                        "description": "Optional parent node ID for hierarchy. YYYY-MM-DD or a date phrase like 'today' or 'next friday' files the node under that date's container"
                     },
                     "properties": {
                         "type": "object",
@@ -524,32 +556,51 @@ fn get_tool_schemas() -> Value {
                     }
                 },
                 "required": ["node_type", "content"]
-            }
+            }),
+            handler: |ctx, args| Box::pin(nodes::handle_create_node(ctx.node_service, args)),
         },
-        {
-            "name": "get_node",
-            "description": "Retrieve a single node by ID",
-            "inputSchema": {
+        Tool {
+            name: "get_node",
+            description: "Retrieve a single node by ID. Project nodes include a 'rollup' with open/done task counts, completion percent and next due date",
+            tier: ToolTier::Core,
+            category: ToolCategory::Crud,
+            input_schema: json!({
                 "type": "object",
                 "properties": {
                     "node_id": {
                         "type": "string",
                         "description": "ID of the node to retrieve"
+                    },
+                    "fields": {
+                        "type": "array",
+                        "items": { "type": "string" },
+                        "description": "Only return these keys of the node, dotted for nested ones (e.g., ['content', 'properties.status']). 'id' is always included"
+                    },
+                    "max_content_chars": {
+                        "type": "number",
+                        "description": "Cut content to about this many characters"
                     }
                 },
                 "required": ["node_id"]
-            }
+            }),
+            handler: |ctx, args| Box::pin(nodes::handle_get_node(ctx.node_service, args)),
         },
-        {
-            "name": "update_node",
-            "description": "Update an existing node's content or properties. Note: Core schema fields are protected - they cannot be deleted and enum values must match allowed values. User-defined fields can be freely modified.",
-            "inputSchema": {
+        Tool {
+            name: "update_node",
+            description: "Update an existing node's content or properties. Note: Core schema fields are protected - they cannot be deleted and enum values must match allowed values. User-defined fields can be freely modified.",
+            tier: ToolTier::Core,
+            category: ToolCategory::Crud,
+            input_schema: json!({
                 "type": "object",
                 "properties": {
                     "node_id": {
                         "type": "string",
                         "description": "ID of the node to update"
                     },
+                    "version": {
+                        "type": "number",
+                        "description": "Version from your last read. If the node has changed since, nothing is written and the error returns current_node and its version to merge with and retry. Omit to update whatever version is current"
+                    },
                     "content": {
                         "type": "string",
                         "description": "Updated content"
@@ -573,26 +624,36 @@ fn get_tool_schemas() -> Value {
                     }
                 },
                 "required": ["node_id"]
-            }
+            }),
+            handler: |ctx, args| Box::pin(nodes::handle_update_node(ctx.node_service, args)),
         },
-        {
-            "name": "delete_node",
-            "description": "Delete a node and optionally its children",
-            "inputSchema": {
+        Tool {
+            name: "delete_node",
+            description: "Delete a node and optionally its children",
+            tier: ToolTier::Core,
+            category: ToolCategory::Crud,
+            input_schema: json!({
                 "type": "object",
                 "properties": {
                     "node_id": {
                         "type": "string",
                         "description": "ID of the node to delete"
+                    },
+                    "version": {
+                        "type": "number",
+                        "description": "Version from your last read; a changed node is not deleted and the error returns current_node"
                     }
                 },
                 "required": ["node_id"]
-            }
+            }),
+            handler: |ctx, args| Box::pin(nodes::handle_delete_node(ctx.node_service, args)),
         },
-        {
-            "name": "query_nodes",
-            "description": "Query nodes with filters",
-            "inputSchema": {
+        Tool {
+            name: "query_nodes",
+            description: "Query nodes with filters",
+            tier: ToolTier::Core,
+            category: ToolCategory::Query,
+            input_schema: json!({
                 "type": "object",
                 "properties": {
                     "filters": {
@@ -601,12 +662,28 @@ fn get_tool_schemas() -> Value {
                     },
                     "limit": {
                         "type": "number",
-                        "description": "Maximum number of results"
+                        "description": "Results per page (default: 100, max: 1000)"
+                    },
+                    "cursor": {
+                        "type": "string",
+                        "description": "next_cursor from the previous page, with the same other arguments"
+                    },
+                    "offset": {
+                        "type": "number",
+                        "description": "Number of results to skip (ignored with cursor)"
                     },
                     "node_type": {
                         "type": "string",
                         "description": "Filter by node type"
                     },
+                    "content_contains": {
+                        "type": "string",
+                        "description": "Case-insensitive substring of the node content"
+                    },
+                    "mentioned_by": {
+                        "type": "string",
+                        "description": "Return the nodes that mention this node ID (backlinks)"
+                    },
                     "collection_id": {
                         "type": "string",
                         "description": "Filter by collection membership - returns only nodes in this collection"
@@ -614,14 +691,229 @@ fn get_tool_schemas() -> Value {
                     "collection": {
                         "type": "string",
                         "description": "Filter by collection path (e.g., 'hr:policy') - resolves path to collection ID"
+                    },
+                    "fields": {
+                        "type": "array",
+                        "items": { "type": "string" },
+                        "description": "Only return these keys of each node, dotted for nested ones (e.g., ['content', 'properties.status']). 'id' is always included"
+                    },
+                    "max_content_chars": {
+                        "type": "number",
+                        "description": "Cut content to about this many characters, around the content_contains match when given"
                     }
                 }
-            }
+            }),
+            handler: |ctx, args| Box::pin(nodes::handle_query_nodes(ctx.node_service, args)),
+        },
+        Tool {
+            name: "run_query",
+            description: "Run a saved query node (a user's smart list) and return its results. Filter values written as \"$name\" in the saved query are filled from params.",
+            tier: ToolTier::Discoverable,
+            category: ToolCategory::Query,
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "query_node_id": {
+                        "type": "string",
+                        "description": "ID of the query node"
+                    },
+                    "params": {
+                        "type": "object",
+                        "description": "Values for $name placeholders, e.g. {\"status\": \"open\"}"
+                    },
+                    "limit": {
+                        "type": "number",
+                        "description": "Results per page (default: the saved limit, or 100; max: 1000)"
+                    },
+                    "cursor": {
+                        "type": "string",
+                        "description": "next_cursor from the previous page, with the same other arguments"
+                    }
+                },
+                "required": ["query_node_id"]
+            }),
+            handler: |ctx, args| Box::pin(nodes::handle_run_query(ctx.node_service, args)),
+        },
+        Tool {
+            name: "get_overdue_tasks",
+            description: "List open tasks whose due date is before today, earliest first. Optionally narrow to one assignee or priority.",
+            tier: ToolTier::Discoverable,
+            category: ToolCategory::Query,
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "assignee": {
+                        "type": "string",
+                        "description": "Only tasks assigned to this assignee"
+                    },
+                    "priority": {
+                        "type": "string",
+                        "description": "Only tasks with this priority (low, medium, high or a user-defined value)"
+                    }
+                }
+            }),
+            handler: |ctx, args| Box::pin(nodes::handle_get_overdue_tasks(ctx.node_service, args)),
+        },
+        Tool {
+            name: "get_tasks_due_within",
+            description: "List open tasks due today or within the next N days, earliest first. Overdue tasks are not included; use get_overdue_tasks for those.",
+            tier: ToolTier::Discoverable,
+            category: ToolCategory::Query,
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "days": {
+                        "type": "number",
+                        "description": "Days after today to include (0 = due today only)",
+                        "minimum": 0
+                    },
+                    "assignee": {
+                        "type": "string",
+                        "description": "Only tasks assigned to this assignee"
+                    },
+                    "priority": {
+                        "type": "string",
+                        "description": "Only tasks with this priority (low, medium, high or a user-defined value)"
+                    }
+                },
+                "required": ["days"]
+            }),
+            handler: |ctx, args| {
+                Box::pin(nodes::handle_get_tasks_due_within(ctx.node_service, args))
+            },
         },
-        {
-            "name": "get_children",
-            "description": "Get all children of a parent node in order with their positions (0-based indexes). Returns minimal info by default - use include_content=true to see node content.",
-            "inputSchema": {
+        Tool {
+            name: "extract_action_items",
+            description: "Convert the action-item notes of a meeting node (lines like 'TODO: ...', 'Action: ...' or '- [ ] ...') into open tasks in place, each linked back to the meeting via task.meeting_id. Returns the new task IDs.",
+            tier: ToolTier::Discoverable,
+            category: ToolCategory::Query,
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "meeting_id": {
+                        "type": "string",
+                        "description": "ID of the meeting node"
+                    }
+                },
+                "required": ["meeting_id"]
+            }),
+            handler: |ctx, args| {
+                Box::pin(nodes::handle_extract_action_items(ctx.node_service, args))
+            },
+        },
+        Tool {
+            name: "log_interaction",
+            description: "Log an interaction with a person node (call, meeting, email). The note is added to today's daily note and linked from the person via an interacted_with edge. Returns the note ID.",
+            tier: ToolTier::Discoverable,
+            category: ToolCategory::Query,
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "person_id": {
+                        "type": "string",
+                        "description": "ID of the person node"
+                    },
+                    "note": {
+                        "type": "string",
+                        "description": "What happened"
+                    }
+                },
+                "required": ["person_id", "note"]
+            }),
+            handler: |ctx, args| Box::pin(nodes::handle_log_interaction(ctx.node_service, args)),
+        },
+        Tool {
+            name: "get_interaction_history",
+            description: "Get the interaction notes logged against a person node, newest first.",
+            tier: ToolTier::Discoverable,
+            category: ToolCategory::Query,
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "person_id": {
+                        "type": "string",
+                        "description": "ID of the person node"
+                    }
+                },
+                "required": ["person_id"]
+            }),
+            handler: |ctx, args| {
+                Box::pin(nodes::handle_get_interaction_history(ctx.node_service, args))
+            },
+        },
+        Tool {
+            name: "get_highlights",
+            description: "Get the highlight nodes taken from a source text node (one embedding an attachment or bookmarked link), ordered by page and start offset. A highlight is created as a 'highlight' node whose content is the excerpt, linked to its source with a 'highlights' relationship.",
+            tier: ToolTier::Discoverable,
+            category: ToolCategory::Query,
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "source_id": {
+                        "type": "string",
+                        "description": "ID of the highlighted source node"
+                    }
+                },
+                "required": ["source_id"]
+            }),
+            handler: |ctx, args| Box::pin(nodes::handle_get_highlights(ctx.node_service, args)),
+        },
+        Tool {
+            name: "get_or_create_period",
+            description: "Get the week, month or quarter node for a period, creating it if it doesn't exist yet. Period nodes are containers for weekly reviews and monthly or quarterly rollups; they are linked to the date nodes they cover with 'includes' relationships (use get_related_nodes to list those days).",
+            tier: ToolTier::Discoverable,
+            category: ToolCategory::Query,
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "period_id": {
+                        "type": "string",
+                        "description": "ISO week (2025-W03), month (2025-05) or quarter (2025-Q2)"
+                    }
+                },
+                "required": ["period_id"]
+            }),
+            handler: |ctx, args| {
+                Box::pin(nodes::handle_get_or_create_period(ctx.node_service, args))
+            },
+        },
+        Tool {
+            name: "set_node_appearance",
+            description: "Set the icon, accent color and cover image of any node. Omitted fields are cleared; pass none of them to remove the appearance entirely.",
+            tier: ToolTier::Discoverable,
+            category: ToolCategory::Crud,
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "node_id": {
+                        "type": "string",
+                        "description": "ID of the node to style"
+                    },
+                    "icon": {
+                        "type": "string",
+                        "description": "A single emoji"
+                    },
+                    "color": {
+                        "type": "string",
+                        "description": "Accent color as #rgb or #rrggbb"
+                    },
+                    "cover": {
+                        "type": "string",
+                        "description": "Cover image as an attachment://<hash> URI"
+                    }
+                },
+                "required": ["node_id"]
+            }),
+            handler: |ctx, args| {
+                Box::pin(nodes::handle_set_node_appearance(ctx.node_service, args))
+            },
+        },
+        Tool {
+            name: "get_children",
+            description: "Get the children of a parent node in order with their positions (0-based indexes), a page at a time. Returns minimal info by default - use include_content=true to see node content.",
+            tier: ToolTier::Core,
+            category: ToolCategory::Hierarchy,
+            input_schema: json!({
                 "type": "object",
                 "properties": {
                     "parent_id": {
@@ -632,15 +924,26 @@ fn get_tool_schemas() -> Value {
                         "type": "boolean",
                         "description": "Include node content in response (default: false). Set to true only if you need content and don't already have it from get_markdown_from_node_id.",
                         "default": false
+                    },
+                    "limit": {
+                        "type": "number",
+                        "description": "Children per page (default: 100, max: 1000)"
+                    },
+                    "cursor": {
+                        "type": "string",
+                        "description": "next_cursor from the previous page, with the same other arguments"
                     }
                 },
                 "required": ["parent_id"]
-            }
+            }),
+            handler: |ctx, args| Box::pin(nodes::handle_get_children(ctx.node_service, args)),
         },
-        {
-            "name": "get_child_at_index",
-            "description": "Get a specific child by its position under a parent. Returns the child node at the specified index (0-based).",
-            "inputSchema": {
+        Tool {
+            name: "get_child_at_index",
+            description: "Get a specific child by its position under a parent. Returns the child node at the specified index (0-based).",
+            tier: ToolTier::Discoverable,
+            category: ToolCategory::Hierarchy,
+            input_schema: json!({
                 "type": "object",
                 "properties": {
                     "parent_id": {
@@ -659,17 +962,20 @@ fn get_tool_schemas() -> Value {
                     }
                 },
                 "required": ["parent_id", "index"]
-            }
+            }),
+            handler: |ctx, args| Box::pin(nodes::handle_get_child_at_index(ctx.node_service, args)),
         },
-        {
-            "name": "insert_child_at_index",
-            "description": "Insert a new child node at a specific position (0-based index) under a parent. Index 0 = first child, index 1 = second child, etc. If index >= child count, appends at end.\n\nDATE NODES: If parent_id is in YYYY-MM-DD format, it references a date container which auto-exists. You don't need to create date nodes first.\n\nExample: parent_id='2025-10-23' automatically uses that date's container.",
-            "inputSchema": {
+        Tool {
+            name: "insert_child_at_index",
+            description: "Insert a new child node at a specific position (0-based index) under a parent. Index 0 = first child, index 1 = second child, etc. If index >= child count, appends at end.\n\nDATE NODES: If parent_id is in YYYY-MM-DD format, it references a date container which auto-exists. You don't need to create date nodes first.\n\nExample: parent_id='2025-10-23' automatically uses that date's container.",
+            tier: ToolTier::Core,
+            category: ToolCategory::Hierarchy,
+            input_schema: json!({
                 "type": "object",
                 "properties": {
                     "parent_id": {
                         "type": "string",
-                        "description": "Parent node ID (any node ID, or YYYY-MM-DD for date containers)"
+                        "description": "Parent node ID (any node ID, or YYYY-MM-DD / a date phrase like 'tomorrow' for date containers)"
                     },
                     "index": {
                         "type": "number",
@@ -691,12 +997,17 @@ fn get_tool_schemas() -> Value {
                     }
                 },
                 "required": ["parent_id", "index", "node_type", "content"]
-            }
+            }),
+            handler: |ctx, args| {
+                Box::pin(nodes::handle_insert_child_at_index(ctx.node_service, args))
+            },
         },
-        {
-            "name": "move_child_to_index",
-            "description": "Move an existing child node to a different position among its siblings. The node stays under the same parent, only the position changes. Index 0 = first position, 1 = second, etc.",
-            "inputSchema": {
+        Tool {
+            name: "move_child_to_index",
+            description: "Move an existing child node to a different position among its siblings. The node stays under the same parent, only the position changes. Index 0 = first position, 1 = second, etc.",
+            tier: ToolTier::Discoverable,
+            category: ToolCategory::Hierarchy,
+            input_schema: json!({
                 "type": "object",
                 "properties": {
                     "node_id": {
@@ -710,12 +1021,17 @@ fn get_tool_schemas() -> Value {
                     }
                 },
                 "required": ["node_id", "index"]
-            }
+            }),
+            handler: |ctx, args| {
+                Box::pin(nodes::handle_move_child_to_index(ctx.node_service, args))
+            },
         },
-        {
-            "name": "get_node_tree",
-            "description": "Get hierarchical tree structure of a node and its descendants. Returns minimal structure by default (IDs, types, relationships). Use include_content=true if you need to see node content.",
-            "inputSchema": {
+        Tool {
+            name: "get_node_tree",
+            description: "Get hierarchical tree structure of a node and its descendants. Returns minimal structure by default (IDs, types, relationships). Use include_content=true if you need to see node content.",
+            tier: ToolTier::Discoverable,
+            category: ToolCategory::Hierarchy,
+            input_schema: json!({
                 "type": "object",
                 "properties": {
                     "node_id": {
@@ -738,15 +1054,28 @@ fn get_tool_schemas() -> Value {
                         "type": "boolean",
                         "description": "Include created_at, modified_at, properties (default: false)",
                         "default": false
+                    },
+                    "format": {
+                        "type": "string",
+                        "enum": ["tree", "outline"],
+                        "description": "'tree' (default) returns nested JSON. 'outline' returns one indented line per node ('- <content> [<type>:<id>]'), the cheapest way to skim a large subtree",
+                        "default": "tree"
+                    },
+                    "max_content_chars": {
+                        "type": "number",
+                        "description": "Longest content per outline line (default: 80)"
                     }
                 },
                 "required": ["node_id"]
-            }
+            }),
+            handler: |ctx, args| Box::pin(nodes::handle_get_node_tree(ctx.node_service, args)),
         },
-        {
-            "name": "get_node_collections",
-            "description": "Get the collections that a node belongs to. Returns collection IDs and names.",
-            "inputSchema": {
+        Tool {
+            name: "get_node_collections",
+            description: "Get the collections that a node belongs to. Returns collection IDs and names.",
+            tier: ToolTier::Discoverable,
+            category: ToolCategory::Query,
+            input_schema: json!({
                 "type": "object",
                 "properties": {
                     "node_id": {
@@ -755,12 +1084,17 @@ fn get_tool_schemas() -> Value {
                     }
                 },
                 "required": ["node_id"]
-            }
+            }),
+            handler: |ctx, args| {
+                Box::pin(nodes::handle_get_node_collections(ctx.node_service, args))
+            },
         },
-        {
-            "name": "create_nodes_from_markdown",
-            "description": "Parse markdown and create hierarchical nodes. IMPORTANT: When 'title' is provided, ALL of markdown_content becomes children - the title is NOT auto-removed from content. When 'title' is omitted, the first line of markdown_content is extracted as the root and removed from children.",
-            "inputSchema": {
+        Tool {
+            name: "create_nodes_from_markdown",
+            description: "Parse markdown and create hierarchical nodes. IMPORTANT: When 'title' is provided, ALL of markdown_content becomes children - the title is NOT auto-removed from content. When 'title' is omitted, the first line of markdown_content is extracted as the root and removed from children.",
+            tier: ToolTier::Core,
+            category: ToolCategory::Markdown,
+            input_schema: json!({
                 "type": "object",
                 "properties": {
                     "markdown_content": {
@@ -777,12 +1111,17 @@ fn get_tool_schemas() -> Value {
                     }
                 },
                 "required": ["markdown_content"]
-            }
+            }),
+            handler: |ctx, args| {
+                Box::pin(markdown::handle_create_nodes_from_markdown(ctx.node_service, args))
+            },
         },
-        {
-            "name": "get_markdown_from_node_id",
-            "description": "Export node and its children as clean markdown for reading and analysis",
-            "inputSchema": {
+        Tool {
+            name: "get_markdown_from_node_id",
+            description: "Export node and its children as clean markdown for reading and analysis",
+            tier: ToolTier::Core,
+            category: ToolCategory::Markdown,
+            input_schema: json!({
                 "type": "object",
                 "properties": {
                     "node_id": {
@@ -806,12 +1145,17 @@ fn get_tool_schemas() -> Value {
                     }
                 },
                 "required": ["node_id"]
-            }
+            }),
+            handler: |ctx, args| {
+                Box::pin(markdown::handle_get_markdown_from_node_id(ctx.node_service, args))
+            },
         },
-        {
-            "name": "get_nodes_batch",
-            "description": "Get multiple nodes in a single request (more efficient than multiple get_node calls). Useful when you need details for many nodes after parsing markdown export.",
-            "inputSchema": {
+        Tool {
+            name: "get_nodes_batch",
+            description: "Get multiple nodes in a single request (more efficient than multiple get_node calls). Useful when you need details for many nodes after parsing markdown export.",
+            tier: ToolTier::Discoverable,
+            category: ToolCategory::Query,
+            input_schema: json!({
                 "type": "object",
                 "properties": {
                     "node_ids": {
@@ -820,15 +1164,27 @@ fn get_tool_schemas() -> Value {
                         "description": "Array of node IDs to retrieve (max 100)",
                         "maxItems": 100,
                         "minItems": 1
+                    },
+                    "fields": {
+                        "type": "array",
+                        "items": { "type": "string" },
+                        "description": "Only return these keys of each node, dotted for nested ones (e.g., ['content', 'properties.status']). 'id' is always included"
+                    },
+                    "max_content_chars": {
+                        "type": "number",
+                        "description": "Cut content to about this many characters"
                     }
                 },
                 "required": ["node_ids"]
-            }
+            }),
+            handler: |ctx, args| Box::pin(nodes::handle_get_nodes_batch(ctx.node_service, args)),
         },
-        {
-            "name": "update_nodes_batch",
-            "description": "Update multiple nodes in a single request (surgical updates). More efficient than calling update_node multiple times. Use this for bulk content updates like marking tasks complete.",
-            "inputSchema": {
+        Tool {
+            name: "update_nodes_batch",
+            description: "Update multiple nodes in a single request (surgical updates). More efficient than calling update_node multiple times. Use this for bulk content updates like marking tasks complete.",
+            tier: ToolTier::Discoverable,
+            category: ToolCategory::Query,
+            input_schema: json!({
                 "type": "object",
                 "properties": {
                     "updates": {
@@ -840,6 +1196,7 @@ fn get_tool_schemas() -> Value {
                             "type": "object",
                             "properties": {
                                 "id": { "type": "string", "description": "Node ID to update" },
+                                "version": { "type": "number", "description": "Version from your last read; on conflict the failed entry's 'conflict' holds current_node" },
                                 "content": { "type": "string", "description": "Updated content" },
                                 "node_type": { "type": "string", "description": "Updated node type" },
                                 "properties": { "type": "object", "description": "Updated properties" }
@@ -849,12 +1206,15 @@ fn get_tool_schemas() -> Value {
                     }
                 },
                 "required": ["updates"]
-            }
+            }),
+            handler: |ctx, args| Box::pin(nodes::handle_update_nodes_batch(ctx.node_service, args)),
         },
-        {
-            "name": "update_root_from_markdown",
-            "description": "Replace all children of a root node (document/page/file) with new structure parsed from markdown (bulk replacement, GitHub-style). Deletes all existing children and creates new hierarchy. Use this when AI needs to reorganize or rewrite entire document structures. Note: The root node itself is preserved - only its children are replaced.",
-            "inputSchema": {
+        Tool {
+            name: "update_root_from_markdown",
+            description: "Replace all children of a root node (document/page/file) with new structure parsed from markdown (bulk replacement, GitHub-style). Deletes all existing children and creates new hierarchy. Use this when AI needs to reorganize or rewrite entire document structures. Note: The root node itself is preserved - only its children are replaced.",
+            tier: ToolTier::Discoverable,
+            category: ToolCategory::Markdown,
+            input_schema: json!({
                 "type": "object",
                 "properties": {
                     "root_id": {
@@ -867,12 +1227,17 @@ fn get_tool_schemas() -> Value {
                     }
                 },
                 "required": ["root_id", "markdown"]
-            }
+            }),
+            handler: |ctx, args| {
+                Box::pin(markdown::handle_update_root_from_markdown(ctx.node_service, args))
+            },
         },
-        {
-            "name": "search_semantic",
-            "description": "Search root nodes by semantic similarity using vector embeddings. Returns root nodes (documents/pages) with optional full markdown content. By default, includes the complete markdown for the top result (include_markdown: 1), eliminating the need to call get_markdown_from_node_id separately. Supports filtering by collection (include) and exclude_collections (exclude). Examples: 'Q4 planning documents', 'machine learning research notes'",
-            "inputSchema": {
+        Tool {
+            name: "search_semantic",
+            description: "Search root nodes by semantic similarity using vector embeddings. Returns root nodes (documents/pages) with a short plain-text excerpt and optional full markdown content. By default, includes the complete markdown for the top result (include_markdown: 1), eliminating the need to call get_markdown_from_node_id separately. Supports filtering by collection (include) and exclude_collections (exclude). Examples: 'Q4 planning documents', 'machine learning research notes'",
+            tier: ToolTier::Core,
+            category: ToolCategory::Search,
+            input_schema: json!({
                 "type": "object",
                 "properties": {
                     "query": {
@@ -888,9 +1253,13 @@ fn get_tool_schemas() -> Value {
                     },
                     "limit": {
                         "type": "number",
-                        "description": "Maximum number of results (default: 20)",
+                        "description": "Results per page (default: 20, max: 1000)",
                         "default": 20
                     },
+                    "cursor": {
+                        "type": "string",
+                        "description": "next_cursor from the previous page, with the same other arguments"
+                    },
                     "include_markdown": {
                         "type": "number",
                         "description": "Number of top results to include full markdown content for (0-5). Default: 1 (top result only). This saves a separate get_markdown_from_node_id call.",
@@ -915,16 +1284,60 @@ fn get_tool_schemas() -> Value {
                         "type": "boolean",
                         "description": "Include archived nodes in search results (default: false). By default, search only returns active nodes. Set to true to also include archived content.",
                         "default": false
+                    },
+                    "fields": {
+                        "type": "array",
+                        "items": { "type": "string" },
+                        "description": "Only return these keys of each result node, dotted for nested ones (e.g., ['content', 'nodeType']). 'id', similarity, excerpt and markdown are always included"
+                    },
+                    "max_content_chars": {
+                        "type": "number",
+                        "description": "Cut content to a snippet of about this many characters around the query's words"
                     }
                 },
                 "required": ["query"]
-            }
+            }),
+            handler: |ctx, args| {
+                Box::pin(search::handle_search_semantic(
+                    ctx.node_service,
+                    ctx.embedding_service,
+                    args,
+                ))
+            },
+        },
+        Tool {
+            name: "summarize_node",
+            description: "Summarize a node and everything beneath it in a few sentences, using a local model (nothing leaves the machine). Useful for skimming long documents before deciding whether to fetch their markdown. When no generation model is installed the summary is the leading sentences of the content and 'generated' is false.",
+            tier: ToolTier::Discoverable,
+            category: ToolCategory::Search,
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "node_id": {
+                        "type": "string",
+                        "description": "ID of the node to summarize (usually a root document)"
+                    },
+                    "max_tokens": {
+                        "type": "number",
+                        "description": "Approximate summary length in tokens (default: 150)",
+                        "minimum": 1,
+                        "maximum": 1000,
+                        "default": 150
+                    }
+                },
+                "required": ["node_id"]
+            }),
+            handler: |ctx, args| {
+                Box::pin(search::handle_summarize_node(ctx.embedding_service, args))
+            },
         },
         // Progressive disclosure - tool discovery
-        {
-            "name": "search_tools",
-            "description": "Discover additional NodeSpace tools by category, node type, or keyword search. Use this to find specialized tools beyond the core set (create_node, get_node, update_node, delete_node, query_nodes, get_children, insert_child_at_index, get_all_schemas).",
-            "inputSchema": {
+        Tool {
+            name: "search_tools",
+            description: "Discover additional NodeSpace tools by category, node type, or keyword search. Use this to find specialized tools beyond the core set (create_node, get_node, update_node, delete_node, query_nodes, get_children, insert_child_at_index, get_all_schemas).",
+            tier: ToolTier::Core,
+            category: ToolCategory::Discovery,
+            input_schema: json!({
                 "type": "object",
                 "properties": {
                     "query": {
@@ -948,13 +1361,18 @@ fn get_tool_schemas() -> Value {
                         "maximum": 50
                     }
                 }
-            }
+            }),
+            handler: |context, args| {
+                Box::pin(async move { handle_search_tools(context.tools, args) })
+            },
         },
         // Schema creation tool
-        {
-            "name": "create_schema",
-            "description": "Create a custom schema with fields and relationships. Fields can be provided explicitly or inferred from a natural language description. Relationships define edges to other node types.",
-            "inputSchema": {
+        Tool {
+            name: "create_schema",
+            description: "Create a custom schema with fields and relationships. Fields can be provided explicitly or inferred from a natural language description. Relationships define edges to other node types.",
+            tier: ToolTier::Discoverable,
+            category: ToolCategory::Schema,
+            input_schema: json!({
                 "type": "object",
                 "properties": {
                     "name": {
@@ -1026,13 +1444,16 @@ fn get_tool_schemas() -> Value {
                     }
                 },
                 "required": ["name"]
-            }
+            }),
+            handler: |ctx, args| Box::pin(schema::handle_create_schema(ctx.node_service, args)),
         },
         // Relationship CRUD tools (Issue #703, #814)
-        {
-            "name": "create_relationship",
-            "description": "Create a relationship between two nodes. BUILT-IN RELATIONSHIPS: 'member_of' (add any node to a collection - target must be collection type), 'has_child' (parent-child hierarchy), 'mentions' (bidirectional link between any nodes). These are universally available on ALL node types. SCHEMA-DEFINED: Custom relationships must be defined in the source node's schema.",
-            "inputSchema": {
+        Tool {
+            name: "create_relationship",
+            description: "Create a relationship between two nodes. BUILT-IN RELATIONSHIPS: 'member_of' (add any node to a collection - target must be collection type), 'has_child' (parent-child hierarchy), 'mentions' (bidirectional link between any nodes). These are universally available on ALL node types. SCHEMA-DEFINED: Custom relationships must be defined in the source node's schema.",
+            tier: ToolTier::Core,
+            category: ToolCategory::Relationships,
+            input_schema: json!({
                 "type": "object",
                 "properties": {
                     "source_id": {
@@ -1053,12 +1474,17 @@ fn get_tool_schemas() -> Value {
                     }
                 },
                 "required": ["source_id", "relationship_name", "target_id"]
-            }
+            }),
+            handler: |ctx, args| {
+                Box::pin(relationships::handle_create_relationship(ctx.node_service, args))
+            },
         },
-        {
-            "name": "delete_relationship",
-            "description": "Delete a relationship between two nodes. This is idempotent - succeeds even if the edge doesn't exist.",
-            "inputSchema": {
+        Tool {
+            name: "delete_relationship",
+            description: "Delete a relationship between two nodes. This is idempotent - succeeds even if the edge doesn't exist.",
+            tier: ToolTier::Discoverable,
+            category: ToolCategory::Relationships,
+            input_schema: json!({
                 "type": "object",
                 "properties": {
                     "source_id": {
@@ -1075,12 +1501,17 @@ fn get_tool_schemas() -> Value {
                     }
                 },
                 "required": ["source_id", "relationship_name", "target_id"]
-            }
+            }),
+            handler: |ctx, args| {
+                Box::pin(relationships::handle_delete_relationship(ctx.node_service, args))
+            },
         },
-        {
-            "name": "get_related_nodes",
-            "description": "Get all nodes connected via a specific relationship. Supports both forward ('out') and reverse ('in') directions.",
-            "inputSchema": {
+        Tool {
+            name: "get_related_nodes",
+            description: "Get the nodes connected via a specific relationship, a page at a time. Supports both forward ('out') and reverse ('in') directions.",
+            tier: ToolTier::Discoverable,
+            category: ToolCategory::Relationships,
+            input_schema: json!({
                 "type": "object",
                 "properties": {
                     "node_id": {
@@ -1095,25 +1526,43 @@ fn get_tool_schemas() -> Value {
                         "type": "string",
                         "enum": ["out", "in"],
                         "description": "Direction to traverse: 'out' for forward, 'in' for reverse (default: 'out')"
+                    },
+                    "limit": {
+                        "type": "number",
+                        "description": "Nodes per page (default: 100, max: 1000)"
+                    },
+                    "cursor": {
+                        "type": "string",
+                        "description": "next_cursor from the previous page, with the same other arguments"
                     }
                 },
                 "required": ["node_id", "relationship_name"]
-            }
+            }),
+            handler: |ctx, args| {
+                Box::pin(relationships::handle_get_related_nodes(ctx.node_service, args))
+            },
         },
         // NLP Discovery tools (Issue #703)
-        {
-            "name": "get_relationship_graph",
-            "description": "Get a summary of all relationships defined in schemas. Returns the complete relationship graph for understanding the data model structure.",
-            "inputSchema": {
+        Tool {
+            name: "get_relationship_graph",
+            description: "Get a summary of all relationships defined in schemas. Returns the complete relationship graph for understanding the data model structure.",
+            tier: ToolTier::Discoverable,
+            category: ToolCategory::Relationships,
+            input_schema: json!({
                 "type": "object",
                 "properties": {},
                 "required": []
-            }
+            }),
+            handler: |ctx, args| {
+                Box::pin(relationships::handle_get_relationship_graph(ctx.node_service, args))
+            },
         },
-        {
-            "name": "get_inbound_relationships",
-            "description": "Discover all relationships from other schemas that point TO a specific node type. Useful for understanding reverse relationships without mutating target schemas.",
-            "inputSchema": {
+        Tool {
+            name: "get_inbound_relationships",
+            description: "Discover all relationships from other schemas that point TO a specific node type. Useful for understanding reverse relationships without mutating target schemas.",
+            tier: ToolTier::Discoverable,
+            category: ToolCategory::Relationships,
+            input_schema: json!({
                 "type": "object",
                 "properties": {
                     "target_type": {
@@ -1122,21 +1571,31 @@ fn get_tool_schemas() -> Value {
                     }
                 },
                 "required": ["target_type"]
-            }
+            }),
+            handler: |ctx, args| {
+                Box::pin(relationships::handle_get_inbound_relationships(ctx.node_service, args))
+            },
         },
-        {
-            "name": "get_all_schemas",
-            "description": "Get all schema definitions including their fields and relationships. This is the primary entry point for understanding the complete data model.",
-            "inputSchema": {
+        Tool {
+            name: "get_all_schemas",
+            description: "Get all schema definitions including their fields and relationships. This is the primary entry point for understanding the complete data model.",
+            tier: ToolTier::Core,
+            category: ToolCategory::Schema,
+            input_schema: json!({
                 "type": "object",
                 "properties": {},
                 "required": []
-            }
+            }),
+            handler: |ctx, args| {
+                Box::pin(relationships::handle_get_all_schemas(ctx.node_service, args))
+            },
         },
-        {
-            "name": "check_node_completeness",
-            "description": "Check whether a node satisfies all required relationships defined in its schema. Returns isComplete and a list of missing required relationship names. This is a read-only introspection tool for workflows and UI — it does NOT block node creation or updates.",
-            "inputSchema": {
+        Tool {
+            name: "check_node_completeness",
+            description: "Check whether a node satisfies all required relationships defined in its schema. Returns isComplete and a list of missing required relationship names. This is a read-only introspection tool for workflows and UI — it does NOT block node creation or updates.",
+            tier: ToolTier::Discoverable,
+            category: ToolCategory::Relationships,
+            input_schema: json!({
                 "type": "object",
                 "properties": {
                     "node_id": {
@@ -1145,13 +1604,18 @@ fn get_tool_schemas() -> Value {
                     }
                 },
                 "required": ["node_id"]
-            }
+            }),
+            handler: |ctx, args| {
+                Box::pin(relationships::handle_check_node_completeness(ctx.node_service, args))
+            },
         },
         // Schema Definition Management (Issue #703)
-        {
-            "name": "add_schema_relationship",
-            "description": "Add a relationship definition to an existing schema. This creates the edge table DDL and enables relationship CRUD operations between nodes of this schema and the target type.",
-            "inputSchema": {
+        Tool {
+            name: "add_schema_relationship",
+            description: "Add a relationship definition to an existing schema. This creates the edge table DDL and enables relationship CRUD operations between nodes of this schema and the target type.",
+            tier: ToolTier::Discoverable,
+            category: ToolCategory::Relationships,
+            input_schema: json!({
                 "type": "object",
                 "properties": {
                     "schema_id": {
@@ -1207,12 +1671,17 @@ fn get_tool_schemas() -> Value {
                     }
                 },
                 "required": ["schema_id", "relationship"]
-            }
+            }),
+            handler: |ctx, args| {
+                Box::pin(schema::handle_add_schema_relationship(ctx.node_service, args))
+            },
         },
-        {
-            "name": "remove_schema_relationship",
-            "description": "Remove a relationship definition from a schema. This is a soft-delete: the edge table and existing data are preserved, but the relationship is hidden from the schema definition. Use this to deprecate relationships without losing historical data.",
-            "inputSchema": {
+        Tool {
+            name: "remove_schema_relationship",
+            description: "Remove a relationship definition from a schema. This is a soft-delete: the edge table and existing data are preserved, but the relationship is hidden from the schema definition. Use this to deprecate relationships without losing historical data.",
+            tier: ToolTier::Discoverable,
+            category: ToolCategory::Relationships,
+            input_schema: json!({
                 "type": "object",
                 "properties": {
                     "schema_id": {
@@ -1225,12 +1694,17 @@ fn get_tool_schemas() -> Value {
                     }
                 },
                 "required": ["schema_id", "relationship_name"]
-            }
+            }),
+            handler: |ctx, args| {
+                Box::pin(schema::handle_remove_schema_relationship(ctx.node_service, args))
+            },
         },
-        {
-            "name": "update_schema",
-            "description": "Update a schema's mutable properties in a single operation. Supports updating description, adding/removing fields, and adding/removing relationships. For bulk changes, this is more efficient than individual operations.",
-            "inputSchema": {
+        Tool {
+            name: "update_schema",
+            description: "Update a schema's mutable properties in a single operation. Supports updating description, adding/removing fields, and adding/removing relationships. For bulk changes, this is more efficient than individual operations.",
+            tier: ToolTier::Discoverable,
+            category: ToolCategory::Schema,
+            input_schema: json!({
                 "type": "object",
                 "properties": {
                     "schema_id": {
@@ -1284,9 +1758,88 @@ fn get_tool_schemas() -> Value {
                     }
                 },
                 "required": ["schema_id"]
-            }
-        }
-    ])
+            }),
+            handler: |ctx, args| Box::pin(schema::handle_update_schema(ctx.node_service, args)),
+        },
+        Tool {
+            name: "define_schema",
+            description: "Create a schema or replace it with a new version, safely. Pass the complete definition (all fields and relationships). The definition is validated and compared with the stored version; breaking changes (removed fields or relationships, type changes, new required fields without defaults, removed enum values, narrowed cardinality) are refused unless confirm_breaking is true. Returns the changes and the DDL the write runs. Use dry_run to preview.",
+            tier: ToolTier::Discoverable,
+            category: ToolCategory::Schema,
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "name": {
+                        "type": "string",
+                        "description": "Schema name (e.g., 'Invoice'); the schema ID is its snake_case"
+                    },
+                    "description": {
+                        "type": "string",
+                        "description": "Schema description (the current one is kept when omitted)"
+                    },
+                    "fields": {
+                        "type": "array",
+                        "description": "All fields of the new version",
+                        "items": {
+                            "type": "object",
+                            "properties": {
+                                "name": {"type": "string"},
+                                "type": {"type": "string", "enum": ["string", "number", "boolean", "date", "enum", "array", "object"]},
+                                "protection": {"type": "string", "enum": ["user"]},
+                                "indexed": {"type": "boolean"},
+                                "required": {"type": "boolean"},
+                                "default": {"description": "Value for nodes that don't set the field"},
+                                "userValues": {
+                                    "type": "array",
+                                    "description": "Enum values",
+                                    "items": {
+                                        "type": "object",
+                                        "properties": {
+                                            "value": {"type": "string"},
+                                            "label": {"type": "string"}
+                                        },
+                                        "required": ["value", "label"]
+                                    }
+                                },
+                                "description": {"type": "string"}
+                            },
+                            "required": ["name", "type", "protection", "indexed"]
+                        }
+                    },
+                    "relationships": {
+                        "type": "array",
+                        "description": "All relationships of the new version",
+                        "items": {
+                            "type": "object",
+                            "properties": {
+                                "name": {"type": "string"},
+                                "targetType": {"type": "string"},
+                                "direction": {"type": "string", "enum": ["out", "in"]},
+                                "cardinality": {"type": "string", "enum": ["one", "many"]},
+                                "required": {"type": "boolean"},
+                                "reverseName": {"type": "string"},
+                                "reverseCardinality": {"type": "string", "enum": ["one", "many"]},
+                                "description": {"type": "string"}
+                            },
+                            "required": ["name", "direction", "cardinality"]
+                        }
+                    },
+                    "confirm_breaking": {
+                        "type": "boolean",
+                        "default": false,
+                        "description": "Apply the definition even though it has breaking changes"
+                    },
+                    "dry_run": {
+                        "type": "boolean",
+                        "default": false,
+                        "description": "Only validate and return the changes and DDL plan"
+                    }
+                },
+                "required": ["name"]
+            }),
+            handler: |ctx, args| Box::pin(schema::handle_define_schema(ctx.node_service, args)),
+        },
+    ]
 }
 
 // Include tests
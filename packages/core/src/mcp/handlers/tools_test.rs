@@ -5,12 +5,17 @@
 use super::*;
 use serde_json::json;
 
+/// The registry as a server over the embedded database builds it
+fn tool_index() -> ToolIndex {
+    ToolIndex::new()
+}
+
 #[test]
 fn test_tools_list_returns_tier1_core_tools() {
     // Call tools/list with empty params
     // Progressive disclosure: Only Tier 1 (Core) tools exposed initially (65% token savings)
     // AI agents discover additional tools via search_tools
-    let result = handle_tools_list(json!({}));
+    let result = handle_tools_list(&tool_index(), json!({}));
 
     assert!(result.is_ok());
     let response = result.unwrap();
@@ -74,7 +79,7 @@ fn test_tools_list_returns_tier1_core_tools() {
 
 #[test]
 fn test_tools_list_tool_schema_structure() {
-    let result = handle_tools_list(json!({})).unwrap();
+    let result = handle_tools_list(&tool_index(), json!({})).unwrap();
     let tools = result["tools"].as_array().unwrap();
 
     // Verify each tool has required fields
@@ -126,6 +131,59 @@ fn test_tools_call_missing_arguments() {
     assert!(params.get("arguments").is_none());
 }
 
+#[test]
+fn test_every_registered_tool_is_listed_once() {
+    // Handlers are a required field, so a tool can't be registered without one;
+    // this checks the registry for a concrete connection type as tools/call uses it
+    let registry = tool_registry::<surrealdb::engine::local::Db>();
+    let mut names: Vec<&str> = registry.iter().map(|tool| tool.name).collect();
+    names.sort_unstable();
+    names.dedup();
+    assert_eq!(names.len(), registry.len(), "Duplicate tool names");
+
+    // tools/list and search_tools together expose the whole registry
+    let listed = handle_tools_list(&tool_index(), json!({})).unwrap()["tools"]
+        .as_array()
+        .unwrap()
+        .len();
+    let discoverable = handle_search_tools(&tool_index(), json!({ "limit": registry.len() }))
+        .unwrap()["tools"]
+        .as_array()
+        .unwrap()
+        .len();
+    assert_eq!(listed + discoverable, registry.len());
+}
+
+#[test]
+fn test_tool_index_finds_tools_by_name() {
+    let index = tool_index();
+    for tool in index.tools() {
+        assert_eq!(
+            index.get(tool.name).map(|found| found.name),
+            Some(tool.name)
+        );
+    }
+    assert!(index.get("no_such_tool").is_none());
+}
+
+#[test]
+fn test_registered_schemas_require_declared_properties() {
+    for tool in tool_index().tools() {
+        let schema = &tool.input_schema;
+        assert_eq!(schema["type"], json!("object"), "{}", tool.name);
+        let properties = schema["properties"].as_object().unwrap();
+        for required in schema["required"].as_array().into_iter().flatten() {
+            let required = required.as_str().unwrap();
+            assert!(
+                properties.contains_key(required),
+                "{} requires undeclared '{}'",
+                tool.name,
+                required
+            );
+        }
+    }
+}
+
 /// Integration tests for async tools/call execution
 #[cfg(test)]
 mod async_integration_tests {
@@ -148,7 +206,11 @@ mod async_integration_tests {
         nlp_engine.initialize().unwrap();
         let nlp_engine = Arc::new(nlp_engine);
 
-        let embedding_service = Arc::new(NodeEmbeddingService::new(nlp_engine, store.clone()));
+        let embedding_service = Arc::new(NodeEmbeddingService::new(
+            nlp_engine,
+            store.clone(),
+            node_service.workspace_lock().clone(),
+        ));
 
         (node_service, embedding_service, temp_dir)
     }
@@ -162,7 +224,14 @@ mod async_integration_tests {
             "arguments": {}
         });
 
-        let result = handle_tools_call(&node_service, &embedding_service, params).await;
+        let result = handle_tools_call(
+            &ToolIndex::new(),
+            &node_service,
+            &embedding_service,
+            None,
+            params,
+        )
+        .await;
 
         // Should return Err with invalid params error
         assert!(result.is_err());
@@ -179,7 +248,14 @@ mod async_integration_tests {
             "arguments": {"content": "test"}
         });
 
-        let result = handle_tools_call(&node_service, &embedding_service, params).await;
+        let result = handle_tools_call(
+            &ToolIndex::new(),
+            &node_service,
+            &embedding_service,
+            None,
+            params,
+        )
+        .await;
 
         // Should return Err with invalid params error
         assert!(result.is_err());
@@ -200,7 +276,14 @@ mod async_integration_tests {
             }
         });
 
-        let result = handle_tools_call(&node_service, &embedding_service, params).await;
+        let result = handle_tools_call(
+            &ToolIndex::new(),
+            &node_service,
+            &embedding_service,
+            None,
+            params,
+        )
+        .await;
 
         // Should return Ok with MCP spec-compliant response
         assert!(result.is_ok(), "tools/call should succeed");
@@ -239,7 +322,14 @@ mod async_integration_tests {
             }
         });
 
-        let result = handle_tools_call(&node_service, &embedding_service, params).await;
+        let result = handle_tools_call(
+            &ToolIndex::new(),
+            &node_service,
+            &embedding_service,
+            None,
+            params,
+        )
+        .await;
 
         // Should return Ok with isError=true (per MCP spec, tool errors are not JSON-RPC errors)
         assert!(result.is_ok());
@@ -268,9 +358,15 @@ mod async_integration_tests {
                 "content": "Searchable content"
             }
         });
-        handle_tools_call(&node_service, &embedding_service, create_params)
-            .await
-            .unwrap();
+        handle_tools_call(
+            &ToolIndex::new(),
+            &node_service,
+            &embedding_service,
+            None,
+            create_params,
+        )
+        .await
+        .unwrap();
 
         // Now query for nodes
         let query_params = json!({
@@ -281,7 +377,14 @@ mod async_integration_tests {
             }
         });
 
-        let result = handle_tools_call(&node_service, &embedding_service, query_params).await;
+        let result = handle_tools_call(
+            &ToolIndex::new(),
+            &node_service,
+            &embedding_service,
+            None,
+            query_params,
+        )
+        .await;
 
         assert!(result.is_ok());
         let response = result.unwrap();
@@ -297,6 +400,119 @@ mod async_integration_tests {
         assert!(!nodes.is_empty());
     }
 
+    #[tokio::test]
+    async fn test_tools_call_version_conflict_returns_current_node() {
+        let (node_service, embedding_service, _temp_dir) = setup_test_services().await;
+
+        let call = |name: &str, arguments: serde_json::Value| json!({"name": name, "arguments": arguments});
+        let created = handle_tools_call(
+            &ToolIndex::new(),
+            &node_service,
+            &embedding_service,
+            None,
+            call(
+                "create_node",
+                json!({"node_type": "text", "content": "Original"}),
+            ),
+        )
+        .await
+        .unwrap();
+        let created: serde_json::Value =
+            serde_json::from_str(created["content"][0]["text"].as_str().unwrap()).unwrap();
+        let node_id = created["node_id"].as_str().unwrap();
+
+        let update = |content: &str| {
+            call(
+                "update_node",
+                json!({"node_id": node_id, "version": 1, "content": content}),
+            )
+        };
+        handle_tools_call(
+            &ToolIndex::new(),
+            &node_service,
+            &embedding_service,
+            None,
+            update("First"),
+        )
+        .await
+        .unwrap();
+        let response = handle_tools_call(
+            &ToolIndex::new(),
+            &node_service,
+            &embedding_service,
+            None,
+            update("Stale"),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(response["isError"], true);
+        let error = &response["structuredContent"]["error"];
+        assert_eq!(error["code"], crate::mcp::types::VERSION_CONFLICT);
+        assert_eq!(error["data"]["actual_version"], 2);
+        assert_eq!(error["data"]["current_node"]["content"], "First");
+        // The text carries the same data for agents that only read text
+        let text = response["content"][0]["text"].as_str().unwrap();
+        assert!(text.contains("\"current_node\""), "{}", text);
+    }
+
+    #[tokio::test]
+    async fn test_tools_call_retry_with_idempotency_key_creates_once() {
+        let (node_service, embedding_service, _temp_dir) = setup_test_services().await;
+
+        let create = |content: &str| {
+            json!({
+                "name": "create_node",
+                "arguments": {
+                    "node_type": "text",
+                    "content": content,
+                    "idempotency_key": "retry-1"
+                }
+            })
+        };
+        let first = handle_tools_call(
+            &ToolIndex::new(),
+            &node_service,
+            &embedding_service,
+            None,
+            create("Once"),
+        )
+        .await
+        .unwrap();
+        let retried = handle_tools_call(
+            &ToolIndex::new(),
+            &node_service,
+            &embedding_service,
+            None,
+            create("Once"),
+        )
+        .await
+        .unwrap();
+        assert_eq!(first["isError"], false);
+        assert_eq!(first["content"], retried["content"]);
+
+        let nodes = node_service
+            .query_nodes_simple(crate::models::NodeQuery {
+                content_contains: Some("Once".to_string()),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(nodes.len(), 1);
+
+        // Reusing the key for other arguments is an error, not a replay
+        let reused = handle_tools_call(
+            &ToolIndex::new(),
+            &node_service,
+            &embedding_service,
+            None,
+            create("Twice"),
+        )
+        .await
+        .unwrap();
+        assert_eq!(reused["isError"], true);
+    }
+
     #[tokio::test]
     async fn test_tools_call_with_missing_arguments_uses_default() {
         let (node_service, embedding_service, _temp_dir) = setup_test_services().await;
@@ -306,7 +522,14 @@ mod async_integration_tests {
             "name": "query_nodes"
         });
 
-        let result = handle_tools_call(&node_service, &embedding_service, params).await;
+        let result = handle_tools_call(
+            &ToolIndex::new(),
+            &node_service,
+            &embedding_service,
+            None,
+            params,
+        )
+        .await;
 
         // Should work with default empty arguments
         assert!(result.is_ok());
@@ -31,11 +31,35 @@
 //! }
 //! ```
 //!
+//! # Access Scoping
+//!
+//! Agent profiles (see [`access`]) restrict a connection to the roots and
+//! collections it names; without configured profiles the server is unrestricted.
+//!
+//! # Sessions
+//!
+//! Each HTTP client gets its own [`session`], with its own client ID for
+//! event attribution and its own resource subscriptions.
+//!
+//! # Pagination
+//!
+//! List tools return a page at a time with a `next_cursor` (see
+//! [`pagination`]).
+//!
 //! See `/docs/architecture/business-logic/mcp-integration.md` for full details.
 
+pub mod access;
+pub mod framing;
 pub mod handlers;
+pub mod pagination;
+pub mod projection;
 pub mod server;
+pub mod session;
 pub mod types;
 
+pub use access::AgentProfile;
+pub use framing::StdioFraming;
+pub use projection::Projection;
 pub use server::{run_mcp_server, run_mcp_server_with_callback, ResponseCallback};
-pub use types::{MCPError, MCPRequest, MCPResponse};
+pub use session::{Session, SessionManager};
+pub use types::{MCPError, MCPRequest, MCPResponse, RequestId};
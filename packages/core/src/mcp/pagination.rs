@@ -0,0 +1,215 @@
+//! Cursor Pagination for MCP List Tools
+//!
+//! The list tools (`get_children`, `query_nodes`, `run_query`,
+//! `get_related_nodes`, `search_semantic`) return at most `limit` items and,
+//! when more follow, a `next_cursor`. Passing it back as `cursor` with the
+//! same other arguments returns the next page; `next_cursor` is null on the
+//! last one.
+//!
+//! Cursors are opaque to agents. They carry the page's position and a
+//! fingerprint of the arguments they were issued for, so a cursor replayed
+//! with different filters is rejected instead of quietly returning a page of
+//! some other listing. Pages are positional: nodes created or deleted
+//! between calls shift later pages by as many items.
+//!
+//! Tools fetch a page straight from the store, starting at [`Page::start`]
+//! and [`Page::fetch_count`] items long, and hand it to [`Page::finish`].
+
+use crate::mcp::types::MCPError;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use serde_json::Value;
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+/// Page size of list tools that don't set their own
+pub const DEFAULT_PAGE_LIMIT: usize = 100;
+
+/// Largest page any list tool returns
+pub const MAX_PAGE_LIMIT: usize = 1000;
+
+/// Arguments that may change between the pages of one listing
+const PAGE_ARGUMENTS: &[&str] = &[
+    "cursor",
+    "limit",
+    "offset",
+    "fields",
+    "max_content_chars",
+    "include_markdown",
+    "include_content",
+];
+
+/// One page of a listing
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Page {
+    start: usize,
+    limit: usize,
+    fingerprint: u64,
+}
+
+impl Page {
+    /// The page a tool's arguments ask for
+    ///
+    /// `limit` defaults to `default_limit` and may not exceed `max_limit`.
+    /// Without a `cursor` the page starts at `offset`, or at the beginning.
+    pub fn from_params(
+        params: &Value,
+        default_limit: usize,
+        max_limit: usize,
+    ) -> Result<Self, MCPError> {
+        let limit = match params.get("limit").filter(|limit| !limit.is_null()) {
+            Some(limit) => limit.as_u64().filter(|limit| *limit > 0).ok_or_else(|| {
+                MCPError::invalid_params("limit must be a positive integer".to_string())
+            })? as usize,
+            None => default_limit,
+        };
+        if limit > max_limit {
+            return Err(MCPError::invalid_params(format!(
+                "limit cannot exceed {}",
+                max_limit
+            )));
+        }
+
+        let fingerprint = fingerprint(params);
+        let start = match params.get("cursor").and_then(Value::as_str) {
+            Some(cursor) => decode_cursor(cursor, fingerprint)?,
+            None => params
+                .get("offset")
+                .and_then(Value::as_u64)
+                .unwrap_or_default() as usize,
+        };
+        Ok(Self {
+            start,
+            limit,
+            fingerprint,
+        })
+    }
+
+    /// Position of the page's first item
+    pub fn start(&self) -> usize {
+        self.start
+    }
+
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+
+    /// Items to fetch from [`Self::start`] to fill this page and tell
+    /// whether another follows
+    pub fn fetch_count(&self) -> usize {
+        self.limit + 1
+    }
+
+    /// This page of `items` (fetched from [`Self::start`], up to
+    /// [`Self::fetch_count`] long) and the cursor of the next page, if any
+    pub fn finish<T>(&self, mut items: Vec<T>) -> (Vec<T>, Option<String>) {
+        let next_cursor = (items.len() > self.limit)
+            .then(|| encode_cursor(self.start + self.limit, self.fingerprint));
+        items.truncate(self.limit);
+        (items, next_cursor)
+    }
+}
+
+/// Hash of the arguments that select the listing
+fn fingerprint(params: &Value) -> u64 {
+    let mut selecting = params.clone();
+    if let Some(arguments) = selecting.as_object_mut() {
+        arguments.retain(|key, _| !PAGE_ARGUMENTS.contains(&key.as_str()));
+    }
+    let mut hasher = DefaultHasher::new();
+    selecting.to_string().hash(&mut hasher);
+    hasher.finish()
+}
+
+fn encode_cursor(start: usize, fingerprint: u64) -> String {
+    URL_SAFE_NO_PAD.encode(format!("{}:{:x}", start, fingerprint))
+}
+
+fn decode_cursor(cursor: &str, fingerprint: u64) -> Result<usize, MCPError> {
+    let invalid = || MCPError::invalid_params(format!("Invalid cursor '{}'", cursor));
+    let decoded = URL_SAFE_NO_PAD.decode(cursor).map_err(|_| invalid())?;
+    let decoded = String::from_utf8(decoded).map_err(|_| invalid())?;
+    let (start, issued_for) = decoded.split_once(':').ok_or_else(invalid)?;
+    let start = start.parse().map_err(|_| invalid())?;
+    let issued_for = u64::from_str_radix(issued_for, 16).map_err(|_| invalid())?;
+    if issued_for != fingerprint {
+        return Err(MCPError::invalid_params(
+            "cursor was issued for different arguments; repeat the original arguments with it"
+                .to_string(),
+        ));
+    }
+    Ok(start)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn page(params: Value) -> Result<Page, MCPError> {
+        Page::from_params(&params, 2, 10)
+    }
+
+    /// The listing's items a store query would return for `page`
+    fn fetch(items: &[u32], page: &Page) -> Vec<u32> {
+        items
+            .iter()
+            .copied()
+            .skip(page.start())
+            .take(page.fetch_count())
+            .collect()
+    }
+
+    #[test]
+    fn test_cursors_walk_the_listing() {
+        let items: Vec<u32> = (0..5).collect();
+        let params = json!({"parent_id": "p"});
+
+        let first = page(params.clone()).unwrap();
+        assert_eq!(first.fetch_count(), 3);
+        let (items_1, cursor) = first.finish(fetch(&items, &first));
+        assert_eq!(items_1, vec![0, 1]);
+
+        let mut params = params;
+        params["cursor"] = json!(cursor.unwrap());
+        let second = page(params.clone()).unwrap();
+        assert_eq!(second.start(), 2);
+        let (items_2, cursor) = second.finish(fetch(&items, &second));
+        assert_eq!(items_2, vec![2, 3]);
+
+        params["cursor"] = json!(cursor.unwrap());
+        params["limit"] = json!(5);
+        let third = page(params).unwrap();
+        let (items_3, cursor) = third.finish(fetch(&items, &third));
+        assert_eq!(items_3, vec![4]);
+        assert_eq!(cursor, None);
+    }
+
+    #[test]
+    fn test_offset_starts_the_first_page() {
+        let page = page(json!({"offset": 3, "limit": 1})).unwrap();
+        assert_eq!(page.start(), 3);
+        let (items, cursor) = page.finish(fetch(&[0, 1, 2, 3, 4], &page));
+        assert_eq!(items, vec![3]);
+        assert!(cursor.is_some());
+    }
+
+    #[test]
+    fn test_cursor_is_tied_to_its_arguments() {
+        let first = page(json!({"node_type": "task"})).unwrap();
+        let (_, cursor) = first.finish(vec![0, 1, 2]);
+        let cursor = cursor.unwrap();
+
+        assert!(page(json!({"node_type": "task", "cursor": cursor, "fields": ["id"]})).is_ok());
+        let error = page(json!({"node_type": "text", "cursor": cursor})).unwrap_err();
+        assert!(error.message.contains("different arguments"));
+        assert!(page(json!({"cursor": "not a cursor"})).is_err());
+    }
+
+    #[test]
+    fn test_limit_is_bounded() {
+        assert_eq!(page(json!({})).unwrap().limit(), 2);
+        assert!(page(json!({"limit": 0})).is_err());
+        assert!(page(json!({"limit": 11})).is_err());
+        assert!(page(json!({"limit": "5"})).is_err());
+    }
+}
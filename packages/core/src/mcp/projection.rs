@@ -0,0 +1,223 @@
+//! Response Projection - Trimming Nodes for Agent Context
+//!
+//! A full node (properties, timestamps, mentions, backlinks) costs far more
+//! context than most agent reads need. The node read tools (`get_node`,
+//! `get_nodes_batch`, `query_nodes`, `search_semantic`) accept two options
+//! and trim every node they return:
+//!
+//! - `fields`: the keys to keep. Nested keys are dotted
+//!   (`properties.status`) and snake_case names are accepted for camelCase
+//!   keys (`node_type`). `id` is always kept, since every follow-up call needs it.
+//! - `max_content_chars`: cut `content` to about this many characters. When
+//!   the tool searched for text, the kept part is a snippet around the first
+//!   match rather than the start.
+//!
+//! For subtrees, `get_node_tree` has a compact `outline` format instead.
+
+use crate::mcp::types::MCPError;
+use crate::utils::{snippet_around, truncate_chars};
+use regex::RegexBuilder;
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+/// Shortest query word that snippets are centred on
+const MIN_SNIPPET_TERM_CHARS: usize = 3;
+
+/// How much of each node a read tool returns
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct Projection {
+    #[serde(default)]
+    pub fields: Option<Vec<String>>,
+    #[serde(default)]
+    pub max_content_chars: Option<usize>,
+}
+
+impl Projection {
+    /// The projection options among a tool's arguments
+    pub fn from_params(params: &Value) -> Result<Self, MCPError> {
+        Self::deserialize(params)
+            .map_err(|e| MCPError::invalid_params(format!("Invalid parameters: {}", e)))
+    }
+
+    /// Whether nodes are returned whole
+    pub fn is_full(&self) -> bool {
+        self.fields.is_none() && self.max_content_chars.is_none()
+    }
+
+    /// Trim `node` (the JSON of one node)
+    ///
+    /// `search` is the text the tool looked for, if any; content snippets
+    /// are centred on its first match, or failing that on the first match of
+    /// one of its words.
+    pub fn apply(&self, node: &mut Value, search: Option<&str>) {
+        if let Some(max_chars) = self.max_content_chars {
+            if let Some(content) = node.get("content").and_then(Value::as_str) {
+                let shortened = shorten(content, max_chars, search);
+                node["content"] = Value::String(shortened);
+            }
+        }
+        if let Some(fields) = &self.fields {
+            *node = select(node, fields);
+        }
+    }
+
+    /// Trim each of `nodes`
+    pub fn apply_all(&self, nodes: &mut [Value], search: Option<&str>) {
+        if self.is_full() {
+            return;
+        }
+        for node in nodes {
+            self.apply(node, search);
+        }
+    }
+}
+
+/// `content` cut to about `max_chars`, around `search` when it occurs
+fn shorten(content: &str, max_chars: usize, search: Option<&str>) -> String {
+    match search.and_then(|search| match_offset(content, search)) {
+        Some(offset) => snippet_around(content, offset, max_chars),
+        None => truncate_chars(content, max_chars),
+    }
+}
+
+/// Byte offset of the first case-insensitive match of `search`, or of its
+/// first word that occurs
+fn match_offset(content: &str, search: &str) -> Option<usize> {
+    let find = |term: &str| {
+        RegexBuilder::new(&regex::escape(term))
+            .case_insensitive(true)
+            .build()
+            .ok()
+            .and_then(|re| re.find(content))
+            .map(|m| m.start())
+    };
+    let search = search.trim();
+    if search.is_empty() {
+        return None;
+    }
+    find(search).or_else(|| {
+        search
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|word| word.chars().count() >= MIN_SNIPPET_TERM_CHARS)
+            .find_map(find)
+    })
+}
+
+/// The `fields` of `node`, plus its `id`
+fn select(node: &Value, fields: &[String]) -> Value {
+    let mut selected = Map::new();
+    if let Some(id) = node.get("id") {
+        selected.insert("id".to_string(), id.clone());
+    }
+    for field in fields {
+        let path: Vec<&str> = field.split('.').filter(|key| !key.is_empty()).collect();
+        copy_path(node, &mut selected, &path);
+    }
+    Value::Object(selected)
+}
+
+/// Copy the value at `path` in `source` to the same place in `target`
+fn copy_path(source: &Value, target: &mut Map<String, Value>, path: &[&str]) {
+    let Some((first, rest)) = path.split_first() else {
+        return;
+    };
+    let Some(object) = source.as_object() else {
+        return;
+    };
+    let Some((key, value)) = object
+        .get_key_value(*first)
+        .or_else(|| object.get_key_value(&snake_to_camel(first)))
+    else {
+        return;
+    };
+    if rest.is_empty() {
+        target.insert(key.clone(), value.clone());
+        return;
+    }
+    let entry = target
+        .entry(key.clone())
+        .or_insert_with(|| Value::Object(Map::new()));
+    if let Some(nested) = entry.as_object_mut() {
+        copy_path(value, nested, rest);
+    }
+}
+
+fn snake_to_camel(name: &str) -> String {
+    let mut camel = String::with_capacity(name.len());
+    let mut upper = false;
+    for c in name.chars() {
+        if c == '_' {
+            upper = true;
+        } else if upper {
+            camel.extend(c.to_uppercase());
+            upper = false;
+        } else {
+            camel.push(c);
+        }
+    }
+    camel
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn node() -> Value {
+        json!({
+            "id": "n1",
+            "nodeType": "task",
+            "content": "Plan the offsite. Book the venue before Friday and confirm catering.",
+            "createdAt": "2026-01-01T00:00:00Z",
+            "properties": {"status": "open", "priority": "high"},
+            "mentionedIn": [{"id": "n2"}]
+        })
+    }
+
+    fn projection(params: Value) -> Projection {
+        Projection::from_params(&params).unwrap()
+    }
+
+    #[test]
+    fn test_no_options_returns_nodes_whole() {
+        let mut nodes = vec![node()];
+        let projection = projection(json!({"node_id": "n1"}));
+        assert!(projection.is_full());
+        projection.apply_all(&mut nodes, Some("venue"));
+        assert_eq!(nodes[0], node());
+    }
+
+    #[test]
+    fn test_fields_select_keys_and_keep_the_id() {
+        let mut value = node();
+        projection(json!({"fields": ["node_type", "properties.status", "missing.key"]}))
+            .apply(&mut value, None);
+        assert_eq!(
+            value,
+            json!({"id": "n1", "nodeType": "task", "properties": {"status": "open"}})
+        );
+    }
+
+    #[test]
+    fn test_content_is_truncated_or_snipped_around_the_search() {
+        let shorten = |search: Option<&str>| {
+            let mut value = node();
+            projection(json!({"max_content_chars": 20})).apply(&mut value, search);
+            value["content"].as_str().unwrap().to_string()
+        };
+
+        assert_eq!(shorten(None), "Plan the offsite.…");
+        let snippet = shorten(Some("VENUE"));
+        assert!(snippet.starts_with('…'), "{}", snippet);
+        assert!(snippet.contains("venue"), "{}", snippet);
+        // Falls back to the first word of the search that occurs
+        assert!(shorten(Some("catering menu")).contains("catering"));
+        assert_eq!(shorten(Some("budget")), "Plan the offsite.…");
+    }
+
+    #[test]
+    fn test_invalid_options_are_rejected() {
+        assert!(Projection::from_params(&json!({"fields": "content"})).is_err());
+        assert!(Projection::from_params(&json!({"max_content_chars": -1})).is_err());
+    }
+}
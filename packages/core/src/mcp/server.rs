@@ -5,32 +5,51 @@
 //! - HTTP: for GUI apps and Claude Code integration
 //!
 //! Both transports share the same request handler logic and optional callbacks.
+//! Each HTTP client gets its own session (see [`crate::mcp::session`]).
 //!
 //! As of Issue #676, MCP handlers route through NodeService directly.
 
-use crate::mcp::types::{MCPError, MCPNotification, MCPRequest, MCPResponse};
+use crate::mcp::access::{bearer_token, select_profile, AgentProfile, MCP_TOKEN_ENV};
+use crate::mcp::framing::{write_frame, Frame, FrameReader, StdioFraming};
+use crate::mcp::handlers::tools::ToolIndex;
+use crate::mcp::session::{
+    ChangeKind, NodeChange, Session, SessionManager, SESSION_IDLE_TIMEOUT, SESSION_ID_HEADER,
+};
+use crate::mcp::types::{
+    IncomingMessage, MCPError, MCPNotification, MCPRequest, MCPResponse, RequestId,
+};
 use crate::services::{NodeEmbeddingService, NodeService};
+use crate::utils::{new_correlation_id, with_correlation_id};
 use axum::{
     body::{Body, Bytes},
-    extract::State,
-    http::{HeaderMap, StatusCode},
+    extract::{DefaultBodyLimit, State},
+    http::{header::AUTHORIZATION, HeaderMap, HeaderValue, StatusCode},
     response::{IntoResponse, Response},
-    routing::get,
-    routing::post,
+    routing::{delete, get, post},
     Json, Router,
 };
 use serde_json::{json, Value};
-use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader, BufWriter};
+use std::time::{Duration, Instant};
+use tokio::io::{BufReader, BufWriter};
+use tokio::sync::broadcast::error::RecvError;
 use tower_http::trace::TraceLayer;
 use tracing::{debug, error, info, instrument, warn};
 
+/// Largest message accepted by default (4 MiB)
+///
+/// Bounds what a client can make the server buffer; see
+/// [`McpServices::max_message_bytes`].
+pub const DEFAULT_MAX_MESSAGE_BYTES: usize = 4 * 1024 * 1024;
+
+/// How often idle HTTP sessions are looked for
+const SESSION_CLEANUP_INTERVAL: Duration = Duration::from_secs(60);
+
 /// Transport mode for MCP server
 #[derive(Debug, Clone, Copy)]
 pub enum McpTransport {
     /// Stdio transport (CLI tools, testing, headless mode)
-    Stdio,
+    Stdio { framing: StdioFraming },
     /// HTTP transport (GUI apps, Claude Code integration)
     Http { port: u16 },
 }
@@ -46,22 +65,13 @@ where
 {
     pub node_service: Arc<NodeService<C>>,
     pub embedding_service: Arc<NodeEmbeddingService<C>>,
-}
-
-/// Server state tracking initialization status
-///
-/// Uses Arc<AtomicBool> for thread-safe sharing across async tasks.
-/// The initialized flag must persist across requests in HTTP mode
-/// to maintain session state according to the MCP protocol.
-///
-/// The MCP protocol requires:
-/// 1. Client sends `initialize` request
-/// 2. Server responds with capabilities
-/// 3. Client sends `initialized` notification
-/// 4. Only then can other methods be called
-struct ServerState {
-    /// Whether the client has completed the initialization handshake
-    initialized: Arc<AtomicBool>,
+    /// Profiles that connections select by token (empty = unrestricted)
+    pub agent_profiles: Vec<AgentProfile>,
+    /// Messages (stdio lines, HTTP bodies) larger than this are rejected
+    /// without being parsed
+    pub max_message_bytes: usize,
+    /// Tool registry shared by every connection of the server
+    pub tools: ToolIndex<C>,
 }
 
 /// Callback type for handling successful responses
@@ -118,7 +128,7 @@ where
     C: surrealdb::Connection,
 {
     match transport {
-        McpTransport::Stdio => run_stdio_server(services, callback).await,
+        McpTransport::Stdio { framing } => run_stdio_server(services, framing, callback).await,
         McpTransport::Http { port } => run_http_server(services, port, callback).await,
     }
 }
@@ -126,10 +136,12 @@ where
 /// Run the MCP server over stdio
 ///
 /// Reads JSON-RPC requests from stdin, processes them via handlers,
-/// and writes responses to stdout. Runs indefinitely until EOF on stdin.
+/// and writes responses to stdout in the same framing (see
+/// [`crate::mcp::framing`]). Runs indefinitely until EOF on stdin.
 #[instrument(skip(services, callback))]
 async fn run_stdio_server<C>(
     services: McpServices<C>,
+    framing: StdioFraming,
     callback: Option<ResponseCallback>,
 ) -> anyhow::Result<()>
 where
@@ -140,26 +152,35 @@ where
     let stdin = tokio::io::stdin();
     let stdout = tokio::io::stdout();
 
-    let reader = BufReader::new(stdin);
+    let mut frames = FrameReader::new(BufReader::new(stdin), framing, services.max_message_bytes);
     let mut writer = BufWriter::new(stdout);
-    let mut lines = reader.lines();
 
-    // Initialize server state
-    let state = ServerState {
-        initialized: Arc::new(AtomicBool::new(false)),
-    };
+    // The one peer's session
+    let session = Session::single();
 
-    while let Some(line) = lines.next_line().await? {
+    // A stdio server serves a single agent, so its profile comes from the environment
+    let token = std::env::var(MCP_TOKEN_ENV).ok();
+
+    while let Some(frame) = frames.next().await? {
+        let line = match frame {
+            Frame::Message(line) => line,
+            Frame::Rejected(response) => {
+                write_frame(&mut writer, frames.framing(), &response).await?;
+                continue;
+            }
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
         debug!("📥 MCP message: {}", line);
 
-        // Try parsing as request first (has id field)
-        match serde_json::from_str::<MCPRequest>(&line) {
-            Ok(request) => {
-                let request_id = request.id;
+        match IncomingMessage::parse(&line) {
+            IncomingMessage::Request(request) => {
+                let request_id = request.id.clone();
                 let method = request.method.clone();
 
                 // Handle request with state tracking
-                let response = handle_request(&services, &state, request).await;
+                let response = handle_request(&services, &session, token.as_deref(), request).await;
 
                 // Invoke callback on successful response
                 if let Some(ref callback) = callback {
@@ -173,54 +194,15 @@ where
                     method, request_id
                 );
 
-                // Write response
-                write_response(&mut writer, &response).await?;
-                continue;
+                write_frame(&mut writer, frames.framing(), &response).await?;
             }
-            Err(request_err) => {
-                // Try parsing as notification (no id field)
-                match serde_json::from_str::<MCPNotification>(&line) {
-                    Ok(notification) => {
-                        handle_notification(&state, notification).await;
-                        continue; // No response for notifications
-                    }
-                    Err(notification_err) => {
-                        // Neither request nor notification - provide detailed diagnostics
-                        warn!(
-                            "❌ Failed to parse JSON-RPC message. Request parse error: {}. Notification parse error: {}. Message: {}",
-                            request_err, notification_err, line
-                        );
-
-                        // Try to determine the issue type for better error message
-                        let error_message = if line.trim().is_empty() {
-                            "Empty message received".to_string()
-                        } else if let Ok(value) = serde_json::from_str::<serde_json::Value>(&line) {
-                            // Valid JSON but not a valid JSON-RPC message
-                            if value.get("jsonrpc").is_none() {
-                                "Missing 'jsonrpc' field (must be \"2.0\")".to_string()
-                            } else if value.get("jsonrpc").and_then(|v| v.as_str()) != Some("2.0") {
-                                format!(
-                                    "Invalid 'jsonrpc' version: {:?} (must be \"2.0\")",
-                                    value.get("jsonrpc")
-                                )
-                            } else if value.get("method").is_none() {
-                                "Missing 'method' field".to_string()
-                            } else if value.get("id").is_some() {
-                                // Has id, so should be request
-                                format!("Invalid request structure: {}", request_err)
-                            } else {
-                                // No id, so should be notification
-                                format!("Invalid notification structure: {}", notification_err)
-                            }
-                        } else {
-                            format!("Invalid JSON: {}", request_err)
-                        };
-
-                        let error_response =
-                            MCPResponse::error(0, MCPError::parse_error(error_message));
-                        write_response(&mut writer, &error_response).await?;
-                    }
-                }
+            // No response for notifications
+            IncomingMessage::Notification(notification) => {
+                handle_notification(&session, notification).await;
+            }
+            IncomingMessage::Invalid(response) => {
+                warn!("❌ Invalid JSON-RPC message: {}", line);
+                write_frame(&mut writer, frames.framing(), &response).await?;
             }
         }
     }
@@ -231,12 +213,12 @@ where
 
 /// Type alias for HTTP server state
 ///
-/// Used by Axum handlers to extract the shared services, callback, and server state.
+/// Used by Axum handlers to extract the shared services, callback, and sessions.
 /// Generic over the database connection type.
 type HttpState<C> = (
     Arc<McpServices<C>>,
     Option<ResponseCallback>,
-    Arc<ServerState>,
+    Arc<SessionManager>,
 );
 
 /// Run the MCP server over HTTP with Streamable HTTP transport (2025-03-26 spec)
@@ -244,6 +226,8 @@ type HttpState<C> = (
 /// Implements the MCP Streamable HTTP transport:
 /// - Single endpoint (POST /mcp) - Client sends JSON-RPC messages, receives responses
 /// - Supports both application/json and text/event-stream Accept headers
+/// - Sessions via the `Mcp-Session-Id` header; GET /mcp with it streams the
+///   session's notifications and DELETE /mcp ends it
 /// - Backward compatible with HTTP+SSE (2024-11-05) via GET /mcp (deprecated)
 ///
 /// Also provides a /health endpoint for monitoring.
@@ -261,21 +245,26 @@ where
         port
     );
 
-    // Wrap services, callback, and state in Arc for sharing across requests
+    let max_message_bytes = services.max_message_bytes;
+
+    let sessions = Arc::new(SessionManager::new(SESSION_IDLE_TIMEOUT));
+    spawn_session_tasks(services.node_service.clone(), &sessions);
+
+    // Wrap services, callback, and sessions in Arc for sharing across requests
     let shared_services = Arc::new(services);
     let shared_callback = callback;
-    let shared_state = Arc::new(ServerState {
-        initialized: Arc::new(AtomicBool::new(false)),
-    });
 
-    let state: HttpState<C> = (shared_services, shared_callback, shared_state);
+    let state: HttpState<C> = (shared_services, shared_callback, sessions);
 
     // Create router with unified /mcp endpoint and health check
     let app = Router::new()
         .route("/mcp", post(handle_streamable_http_request::<C>))
-        .route("/mcp", get(handle_sse_connection::<C>)) // Backward compat (deprecated)
+        .route("/mcp", get(handle_sse_connection::<C>))
+        .route("/mcp", delete(handle_session_delete::<C>))
         .route("/mcp/message", post(handle_http_mcp_request::<C>)) // Backward compat (deprecated)
         .route("/health", get(handle_health_check))
+        // Oversized bodies are refused with 413 before they are buffered
+        .layer(DefaultBodyLimit::max(max_message_bytes))
         .layer(TraceLayer::new_for_http())
         .with_state(state);
 
@@ -304,142 +293,266 @@ where
     Ok(())
 }
 
+/// Feed domain events to the sessions' subscriptions and drop idle sessions
+///
+/// Node changes are only traced to their roots while some session watches a
+/// root. Both tasks end once the session manager is gone.
+fn spawn_session_tasks<C>(node_service: Arc<NodeService<C>>, sessions: &Arc<SessionManager>)
+where
+    C: surrealdb::Connection + 'static,
+{
+    let mut events = node_service.subscribe_to_events();
+    let weak = Arc::downgrade(sessions);
+    tokio::spawn(async move {
+        loop {
+            match events.recv().await {
+                Ok(event) => {
+                    let Some(sessions) = weak.upgrade() else {
+                        break;
+                    };
+                    sessions.dispatch(&event);
+                    if !sessions.watches_roots() {
+                        continue;
+                    }
+                    let Some(mut change) = NodeChange::from_event(&event) else {
+                        continue;
+                    };
+                    if change.kind != ChangeKind::Deleted {
+                        match node_service.get_root_id(&change.node_id).await {
+                            Ok(root_id) => change.root_id = Some(root_id),
+                            Err(e) => {
+                                debug!("No root for changed node {}: {}", change.node_id, e);
+                                continue;
+                            }
+                        }
+                    }
+                    sessions.dispatch_node_change(&change);
+                }
+                Err(RecvError::Lagged(missed)) => {
+                    warn!("MCP sessions missed {} domain events", missed)
+                }
+                Err(RecvError::Closed) => break,
+            }
+        }
+    });
+
+    let weak = Arc::downgrade(sessions);
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(SESSION_CLEANUP_INTERVAL);
+        loop {
+            interval.tick().await;
+            let Some(sessions) = weak.upgrade() else {
+                break;
+            };
+            for id in sessions.remove_idle(Instant::now()) {
+                info!("🔌 MCP session {} expired", id);
+            }
+        }
+    });
+}
+
 /// Streamable HTTP request endpoint (MCP 2025-03-26 Streamable HTTP transport)
 ///
 /// Unified endpoint that handles JSON-RPC requests with flexible response modes:
 /// - Notifications (no id field) → 202 Accepted with no body
 /// - Requests with Accept: application/json → 200 OK with JSON response
 /// - Requests with Accept: text/event-stream → 200 OK with SSE stream
+/// - Invalid messages → 400 Bad Request with a JSON-RPC error response
+///
+/// `initialize` starts a session and returns its ID in the `Mcp-Session-Id`
+/// header; every other message must carry it (400 without it, 404 once the
+/// session has ended).
 ///
 /// Supports protocol version negotiation (2024-11-05, 2025-03-26, 2025-06-18).
 async fn handle_streamable_http_request<C>(
-    State((services, callback, state)): State<HttpState<C>>,
+    State((services, callback, sessions)): State<HttpState<C>>,
     headers: HeaderMap,
     body_bytes: Bytes,
 ) -> Result<Response, StatusCode>
 where
     C: surrealdb::Connection,
 {
-    let body = String::from_utf8(body_bytes.to_vec()).map_err(|_| StatusCode::BAD_REQUEST)?;
     info!("📥 Streamable HTTP request received");
 
-    // Parse the JSON-RPC message
-    // First try parsing as a request (has id field)
-    if let Ok(request) = serde_json::from_str::<MCPRequest>(&body) {
-        let request_id = request.id;
-        let method = request.method.clone();
+    let request = match parse_http_message(&body_bytes) {
+        IncomingMessage::Request(request) => request,
+        IncomingMessage::Notification(notification) => {
+            info!("📥 Streamable HTTP notification received");
+            let session = match header_session(&sessions, &headers, None) {
+                Ok(session) => session,
+                Err(response) => return Ok(response),
+            };
+            handle_notification(&session, notification).await;
+
+            // Return 202 Accepted with no body (per spec)
+            return Ok(Response::builder()
+                .status(StatusCode::ACCEPTED)
+                .body(Body::empty())
+                .unwrap());
+        }
+        IncomingMessage::Invalid(response) => {
+            return Ok((StatusCode::BAD_REQUEST, Json(response)).into_response())
+        }
+    };
 
-        // Special handling for initialize: automatically mark as initialized after successful response
-        let is_initialize = method == "initialize";
+    let request_id = request.id.clone();
+    let method = request.method.clone();
 
-        info!(
-            "📥 Streamable HTTP request: {} (id: {})",
-            method, request_id
-        );
+    // Initialize starts a session, which is initialized once the request succeeds
+    let is_initialize = method == "initialize";
 
-        // Handle the request using shared state
-        let response = handle_request(services.as_ref(), &state, request).await;
+    info!(
+        "📥 Streamable HTTP request: {} (id: {})",
+        method, request_id
+    );
 
-        // Auto-initialize for HTTP transport after successful initialize request
-        if is_initialize && response.result.is_some() {
-            state.initialized.store(true, Ordering::SeqCst);
-            info!("✅ MCP session initialized (Streamable HTTP) - ready for operations");
+    let session = if is_initialize {
+        sessions.create()
+    } else {
+        match header_session(&sessions, &headers, Some(request_id.clone())) {
+            Ok(session) => session,
+            Err(response) => return Ok(response),
         }
+    };
 
-        // Invoke callback on successful response
-        if let Some(ref cb) = callback {
-            if let Some(ref result) = response.result {
-                cb(&method, result);
-            }
+    let response = handle_request(
+        services.as_ref(),
+        &session,
+        request_token(&headers),
+        request,
+    )
+    .await;
+
+    let started_session = is_initialize && response.result.is_some();
+    if started_session {
+        session.mark_initialized();
+        info!(
+            "✅ MCP session {} initialized (Streamable HTTP) - ready for operations",
+            session.id()
+        );
+    } else if is_initialize {
+        sessions.remove(session.id());
+    }
+
+    // Invoke callback on successful response
+    if let Some(ref cb) = callback {
+        if let Some(ref result) = response.result {
+            cb(&method, result);
         }
+    }
 
-        // Check Accept header to determine response format
-        let accept = headers
-            .get("accept")
-            .and_then(|v| v.to_str().ok())
-            .unwrap_or("application/json");
+    // Check Accept header to determine response format
+    let accept = headers
+        .get("accept")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("application/json");
 
-        debug!(
-            "📤 Streamable HTTP response for method '{}' (id={}, accept={})",
-            method, request_id, accept
-        );
+    debug!(
+        "📤 Streamable HTTP response for method '{}' (id={}, accept={})",
+        method, request_id, accept
+    );
 
-        // LIMITATION: Simple substring matching for Accept header.
-        // Does not parse q-values per RFC 7231. This works correctly for known MCP clients:
-        // - Claude Code sends: "application/json, text/event-stream" → chooses JSON ✓
-        // - SSE-only clients send: "text/event-stream" → chooses SSE ✓
-        // - Default (no header): defaults to JSON ✓
-        //
-        // Edge case not handled:
-        // - "application/json;q=0.1, text/event-stream;q=0.9" → would incorrectly choose JSON
-        //
-        // Future improvement: Use proper Accept header parsing library with q-value support.
-        // For now, this simple approach is sufficient for all known MCP client implementations.
-        let prefer_sse =
-            accept.contains("text/event-stream") && !accept.contains("application/json");
-
-        if prefer_sse {
-            // SSE streaming mode
-            // For simplicity, we'll send a single SSE message with the response
-            use axum::response::sse::{Event, KeepAlive, Sse};
-            use std::convert::Infallible;
-            use tokio_stream::wrappers::BroadcastStream;
-            use tokio_stream::StreamExt;
-
-            let (tx, rx) = tokio::sync::broadcast::channel::<Result<Event, Infallible>>(10);
-
-            // Send the response as SSE message event
-            let response_json = serde_json::to_string(&response).map_err(|e| {
-                error!("Failed to serialize response: {}", e);
-                StatusCode::INTERNAL_SERVER_ERROR
-            })?;
-
-            tokio::spawn(async move {
-                let event = Event::default().event("message").data(response_json);
-                let _ = tx.send(Ok(event));
-            });
-
-            let stream = BroadcastStream::new(rx).filter_map(|result| result.ok());
-
-            Ok(Sse::new(stream)
-                .keep_alive(KeepAlive::default())
-                .into_response())
-        } else {
-            // JSON response mode (default)
-            Ok(Json(response).into_response())
-        }
-    } else if let Ok(_notification) = serde_json::from_str::<MCPNotification>(&body) {
-        // Notification (no id field) - return 202 Accepted with no body
-        info!("📥 Streamable HTTP notification received");
+    // LIMITATION: Simple substring matching for Accept header.
+    // Does not parse q-values per RFC 7231. This works correctly for known MCP clients:
+    // - Claude Code sends: "application/json, text/event-stream" → chooses JSON ✓
+    // - SSE-only clients send: "text/event-stream" → chooses SSE ✓
+    // - Default (no header): defaults to JSON ✓
+    //
+    // Edge case not handled:
+    // - "application/json;q=0.1, text/event-stream;q=0.9" → would incorrectly choose JSON
+    //
+    // Future improvement: Use proper Accept header parsing library with q-value support.
+    // For now, this simple approach is sufficient for all known MCP client implementations.
+    let prefer_sse = accept.contains("text/event-stream") && !accept.contains("application/json");
+
+    let mut http_response = if prefer_sse {
+        // SSE streaming mode
+        // For simplicity, we'll send a single SSE message with the response
+        use axum::response::sse::{Event, KeepAlive, Sse};
+        use std::convert::Infallible;
+        use tokio_stream::wrappers::BroadcastStream;
+        use tokio_stream::StreamExt;
+
+        let (tx, rx) = tokio::sync::broadcast::channel::<Result<Event, Infallible>>(10);
+
+        // Send the response as SSE message event
+        let response_json = serde_json::to_string(&response).map_err(|e| {
+            error!("Failed to serialize response: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+        tokio::spawn(async move {
+            let event = Event::default().event("message").data(response_json);
+            let _ = tx.send(Ok(event));
+        });
 
-        // Handle the notification
-        let notification: MCPNotification = serde_json::from_str(&body).unwrap();
-        handle_notification(&state, notification).await;
+        let stream = BroadcastStream::new(rx).filter_map(|result| result.ok());
 
-        // Return 202 Accepted with no body (per spec)
-        Ok(Response::builder()
-            .status(StatusCode::ACCEPTED)
-            .body(Body::empty())
-            .unwrap())
+        Sse::new(stream)
+            .keep_alive(KeepAlive::default())
+            .into_response()
     } else {
-        // Invalid JSON-RPC message
-        warn!("❌ Invalid JSON-RPC message: {}", body);
-        Err(StatusCode::BAD_REQUEST)
+        // JSON response mode (default)
+        Json(response).into_response()
+    };
+
+    if started_session {
+        let session_id =
+            HeaderValue::from_str(session.id()).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        http_response
+            .headers_mut()
+            .insert(SESSION_ID_HEADER, session_id);
     }
+    Ok(http_response)
 }
 
-/// SSE connection endpoint (MCP 2024-11-05 HTTP+SSE transport - DEPRECATED)
+/// The live session named by a request's `Mcp-Session-Id` header
 ///
-/// When a client connects via SSE, this endpoint sends an `endpoint` event
-/// containing the URI where the client should POST JSON-RPC messages.
+/// Fails with the HTTP response to send instead: 400 without the header, 404
+/// for a session that has ended (the client should initialize again).
+fn header_session(
+    sessions: &SessionManager,
+    headers: &HeaderMap,
+    request_id: Option<RequestId>,
+) -> Result<Arc<Session>, Response> {
+    let rejected = |status, message: &str| {
+        let error = MCPError::invalid_request(message.to_string());
+        (
+            status,
+            Json(MCPResponse::error_for(request_id.clone(), error)),
+        )
+            .into_response()
+    };
+    let Some(id) = headers
+        .get(SESSION_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+    else {
+        return Err(rejected(
+            StatusCode::BAD_REQUEST,
+            "Missing Mcp-Session-Id header. Send initialize request first.",
+        ));
+    };
+    sessions.get(id).ok_or_else(|| {
+        rejected(
+            StatusCode::NOT_FOUND,
+            "Session not found or expired. Send initialize request again.",
+        )
+    })
+}
+
+/// SSE stream endpoint
 ///
-/// The SSE stream remains open for potential server-to-client notifications,
-/// though the current implementation focuses on client-initiated requests.
+/// With an `Mcp-Session-Id` header this is the session's notification stream
+/// (Streamable HTTP): resource updates for its subscriptions arrive here as
+/// `message` events.
 ///
-/// NOTE: This endpoint is DEPRECATED. Use POST /mcp with Streamable HTTP instead.
+/// Without one it is the MCP 2024-11-05 HTTP+SSE transport (DEPRECATED): the
+/// endpoint sends an `endpoint` event containing the URI where the client
+/// should POST JSON-RPC messages. Use POST /mcp with Streamable HTTP instead.
 async fn handle_sse_connection<C>(
-    State((_, _, _)): State<HttpState<C>>,
-) -> impl axum::response::IntoResponse
+    State((_, _, sessions)): State<HttpState<C>>,
+    headers: HeaderMap,
+) -> Response
 where
     C: surrealdb::Connection,
 {
@@ -448,6 +561,31 @@ where
     use tokio_stream::wrappers::BroadcastStream;
     use tokio_stream::StreamExt;
 
+    if headers.contains_key(SESSION_ID_HEADER) {
+        let session = match header_session(&sessions, &headers, None) {
+            Ok(session) => session,
+            Err(response) => return response,
+        };
+        let Some(notifications) = session.notifications() else {
+            return StatusCode::METHOD_NOT_ALLOWED.into_response();
+        };
+        info!("📡 SSE stream opened for MCP session {}", session.id());
+
+        // Lagging streams skip what they missed; the client can re-read nodes
+        let stream = BroadcastStream::new(notifications).filter_map(|notification| {
+            notification.ok().map(|notification| {
+                Ok::<_, Infallible>(
+                    Event::default()
+                        .event("message")
+                        .data(notification.to_string()),
+                )
+            })
+        });
+        return Sse::new(stream)
+            .keep_alive(KeepAlive::default())
+            .into_response();
+    }
+
     info!("📡 SSE client connected");
 
     // Create a stream that sends the endpoint event immediately
@@ -479,7 +617,31 @@ where
     // Convert broadcast receiver to stream
     let stream = BroadcastStream::new(rx).filter_map(|result| result.ok());
 
-    Sse::new(stream).keep_alive(KeepAlive::default())
+    Sse::new(stream)
+        .keep_alive(KeepAlive::default())
+        .into_response()
+}
+
+/// End a Streamable HTTP session (DELETE /mcp)
+async fn handle_session_delete<C>(
+    State((_, _, sessions)): State<HttpState<C>>,
+    headers: HeaderMap,
+) -> StatusCode
+where
+    C: surrealdb::Connection,
+{
+    let Some(id) = headers
+        .get(SESSION_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+    else {
+        return StatusCode::BAD_REQUEST;
+    };
+    if sessions.remove(id) {
+        info!("🔌 MCP session {} ended by client", id);
+        StatusCode::NO_CONTENT
+    } else {
+        StatusCode::NOT_FOUND
+    }
 }
 
 /// Health check endpoint
@@ -501,31 +663,46 @@ async fn handle_health_check() -> Json<Value> {
 /// Note: For HTTP transport, the `initialized` notification is automatically
 /// handled after a successful `initialize` request, since HTTP is stateless
 /// and doesn't support notifications without responses.
+///
+/// This transport has no session IDs, so all its clients share one session.
 async fn handle_http_mcp_request<C>(
-    State((services, callback, state)): State<HttpState<C>>,
-    Json(request): Json<MCPRequest>,
-) -> Result<Json<MCPResponse>, StatusCode>
+    State((services, callback, sessions)): State<HttpState<C>>,
+    headers: HeaderMap,
+    body_bytes: Bytes,
+) -> Response
 where
     C: surrealdb::Connection,
 {
+    let request = match parse_http_message(&body_bytes) {
+        IncomingMessage::Request(request) => request,
+        IncomingMessage::Notification(notification) => {
+            handle_notification(sessions.legacy(), notification).await;
+            return StatusCode::ACCEPTED.into_response();
+        }
+        IncomingMessage::Invalid(response) => {
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response()
+        }
+    };
+
     info!(
         "📥 HTTP MCP request: {} (id: {})",
         request.method, request.id
     );
 
-    let request_id = request.id;
+    let request_id = request.id.clone();
     let method = request.method.clone();
 
     // Special handling for initialize: automatically mark as initialized after successful response
     // This is necessary because HTTP transport can't receive the separate `initialized` notification
     let is_initialize = method == "initialize";
 
-    // Handle the request using shared state
-    let response = handle_request(services.as_ref(), &state, request).await;
+    let session = sessions.legacy();
+    let response =
+        handle_request(services.as_ref(), session, request_token(&headers), request).await;
 
     // Auto-initialize for HTTP transport after successful initialize request
     if is_initialize && response.result.is_some() {
-        state.initialized.store(true, Ordering::SeqCst);
+        session.mark_initialized();
         info!("✅ MCP session initialized (HTTP mode) - ready for operations");
     }
 
@@ -541,25 +718,75 @@ where
         method, request_id
     );
 
-    Ok(Json(response))
+    Json(response).into_response()
+}
+
+/// Classify an HTTP request body as a JSON-RPC message
+fn parse_http_message(body: &[u8]) -> IncomingMessage {
+    match std::str::from_utf8(body) {
+        Ok(body) => IncomingMessage::parse(body),
+        Err(_) => IncomingMessage::Invalid(MCPResponse::error_for(
+            None,
+            MCPError::parse_error("Message is not valid UTF-8".to_string()),
+        )),
+    }
+}
+
+/// Agent profile token from an HTTP request's `Authorization` header
+fn request_token(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(bearer_token)
 }
 
 /// Handle a JSON-RPC request and return a response
-#[instrument(skip(services, state), fields(method = %request.method, id = %request.id))]
+///
+/// The request runs under a new correlation ID, which an error response
+/// carries in its `data`.
 async fn handle_request<C>(
     services: &McpServices<C>,
-    state: &ServerState,
+    session: &Session,
+    token: Option<&str>,
     request: MCPRequest,
 ) -> MCPResponse
 where
     C: surrealdb::Connection,
 {
+    let correlation_id = new_correlation_id();
+    let mut response = with_correlation_id(
+        correlation_id.clone(),
+        dispatch_request(services, session, token, request),
+    )
+    .await;
+    response.error = response
+        .error
+        .map(|error| error.with_correlation_id(&correlation_id));
+    response
+}
+
+/// Run a JSON-RPC request's method
+///
+/// Operations run as the session's client, so the events they cause name it.
+#[instrument(skip(services, session, token), fields(method = %request.method, id = %request.id))]
+async fn dispatch_request<C>(
+    services: &McpServices<C>,
+    session: &Session,
+    token: Option<&str>,
+    request: MCPRequest,
+) -> MCPResponse
+where
+    C: surrealdb::Connection,
+{
+    // Once agent profiles are configured, every connection must present a token
+    let profile = match select_profile(&services.agent_profiles, token) {
+        Ok(profile) => profile,
+        Err(error) => return MCPResponse::error(request.id, error),
+    };
+
     // Check initialization state before processing operations
     // Allow only 'initialize' and 'ping' methods before initialization is complete
-    if request.method != "initialize"
-        && request.method != "ping"
-        && !state.initialized.load(Ordering::SeqCst)
-    {
+    if request.method != "initialize" && request.method != "ping" && !session.is_initialized() {
         return MCPResponse::error(
             request.id,
             MCPError::invalid_request(
@@ -568,12 +795,14 @@ where
         );
     }
 
+    let node_service = Arc::new(services.node_service.with_client(session.client_id()));
+
     let result = match request.method.as_str() {
         // CRITICAL: Initialize must be first interaction (now fetches schemas for dynamic instructions)
         // Also warms up the embedding model for fast first semantic search
         "initialize" => {
             crate::mcp::handlers::initialize::handle_initialize(
-                &services.node_service,
+                &node_service,
                 &services.embedding_service,
                 request.params,
             )
@@ -584,16 +813,42 @@ where
         "ping" => Ok(json!({})),
 
         // MCP-compliant tool discovery and execution (per 2024-11-05 spec)
-        "tools/list" => crate::mcp::handlers::tools::handle_tools_list(request.params),
+        "tools/list" => {
+            crate::mcp::handlers::tools::handle_tools_list(&services.tools, request.params)
+        }
         "tools/call" => {
             crate::mcp::handlers::tools::handle_tools_call(
-                &services.node_service,
+                &services.tools,
+                &node_service,
                 &services.embedding_service,
+                profile,
                 request.params,
             )
             .await
         }
 
+        // Change notifications for nodes, on the session's GET /mcp stream
+        "resources/subscribe" => {
+            crate::mcp::handlers::resources::handle_subscribe(session, request.params)
+        }
+        "resources/unsubscribe" => {
+            crate::mcp::handlers::resources::handle_unsubscribe(session, request.params)
+        }
+
+        // `nodespace/nodeChanged` notifications for whole documents
+        "nodespace/subscribe" => {
+            crate::mcp::handlers::changes::handle_subscribe(
+                &node_service,
+                profile,
+                session,
+                request.params,
+            )
+            .await
+        }
+        "nodespace/unsubscribe" => {
+            crate::mcp::handlers::changes::handle_unsubscribe(session, request.params)
+        }
+
         _ => {
             warn!("⚠️  Unknown MCP method: {}", request.method);
             Err(MCPError::method_not_found(&request.method))
@@ -616,11 +871,11 @@ where
 }
 
 /// Handle a JSON-RPC notification (no response expected)
-#[instrument(skip(state), fields(method = %notification.method))]
-async fn handle_notification(state: &ServerState, notification: MCPNotification) {
+#[instrument(skip(session), fields(method = %notification.method))]
+async fn handle_notification(session: &Session, notification: MCPNotification) {
     match notification.method.as_str() {
         "initialized" | "notifications/initialized" => {
-            state.initialized.store(true, Ordering::SeqCst);
+            session.mark_initialized();
             info!("✅ MCP session initialized - ready for operations");
         }
         _ => {
@@ -629,18 +884,6 @@ async fn handle_notification(state: &ServerState, notification: MCPNotification)
     }
 }
 
-/// Write a JSON-RPC response to stdout
-async fn write_response(
-    writer: &mut BufWriter<tokio::io::Stdout>,
-    response: &MCPResponse,
-) -> anyhow::Result<()> {
-    let json = serde_json::to_string(response)?;
-    writer.write_all(json.as_bytes()).await?;
-    writer.write_all(b"\n").await?;
-    writer.flush().await?;
-    Ok(())
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -651,17 +894,21 @@ mod tests {
 
     #[test]
     fn test_transport_enum_creation() {
-        let _stdio = McpTransport::Stdio;
+        let _stdio = McpTransport::Stdio {
+            framing: StdioFraming::Auto,
+        };
         let _http = McpTransport::Http { port: 3001 };
         // Tests compile successfully
     }
 
     #[test]
     fn test_transport_stdio_variant() {
-        let transport = McpTransport::Stdio;
+        let transport = McpTransport::Stdio {
+            framing: StdioFraming::ContentLength,
+        };
         match transport {
-            McpTransport::Stdio => {
-                // Expected
+            McpTransport::Stdio { framing } => {
+                assert_eq!(framing, StdioFraming::ContentLength);
             }
             McpTransport::Http { .. } => {
                 panic!("Expected Stdio variant");
@@ -677,7 +924,7 @@ mod tests {
             McpTransport::Http { port: p } => {
                 assert_eq!(p, 3001);
             }
-            McpTransport::Stdio => {
+            McpTransport::Stdio { .. } => {
                 panic!("Expected Http variant");
             }
         }
@@ -727,20 +974,25 @@ mod tests {
         nlp_engine.initialize().unwrap();
         let nlp_engine = Arc::new(nlp_engine);
 
-        let embedding_service = Arc::new(NodeEmbeddingService::new(nlp_engine, store.clone()));
+        let embedding_service = Arc::new(NodeEmbeddingService::new(
+            nlp_engine,
+            store.clone(),
+            node_service.workspace_lock().clone(),
+        ));
 
         McpServices {
             node_service,
             embedding_service,
+            agent_profiles: Vec::new(),
+            max_message_bytes: DEFAULT_MAX_MESSAGE_BYTES,
+            tools: ToolIndex::new(),
         }
     }
 
     #[tokio::test]
     async fn test_http_health_check() {
         let services = create_test_services().await;
-        let shared_state = Arc::new(ServerState {
-            initialized: Arc::new(AtomicBool::new(false)),
-        });
+        let shared_state = Arc::new(SessionManager::new(SESSION_IDLE_TIMEOUT));
 
         let app = Router::new()
             .route("/health", get(handle_health_check))
@@ -767,9 +1019,7 @@ mod tests {
     #[tokio::test]
     async fn test_http_initialize_request() {
         let services = create_test_services().await;
-        let shared_state = Arc::new(ServerState {
-            initialized: Arc::new(AtomicBool::new(false)),
-        });
+        let shared_state = Arc::new(SessionManager::new(SESSION_IDLE_TIMEOUT));
 
         let app = Router::new()
             .route("/mcp", post(handle_http_mcp_request))
@@ -813,15 +1063,13 @@ mod tests {
         assert!(json["result"]["capabilities"].is_object());
 
         // Verify state was updated (HTTP auto-initializes)
-        assert!(shared_state.initialized.load(Ordering::SeqCst));
+        assert!(shared_state.legacy().is_initialized());
     }
 
     #[tokio::test]
     async fn test_http_ping_without_initialization() {
         let services = create_test_services().await;
-        let shared_state = Arc::new(ServerState {
-            initialized: Arc::new(AtomicBool::new(false)),
-        });
+        let shared_state = Arc::new(SessionManager::new(SESSION_IDLE_TIMEOUT));
 
         let app = Router::new()
             .route("/mcp", post(handle_http_mcp_request))
@@ -856,9 +1104,7 @@ mod tests {
     #[tokio::test]
     async fn test_http_request_before_initialization_fails() {
         let services = create_test_services().await;
-        let shared_state = Arc::new(ServerState {
-            initialized: Arc::new(AtomicBool::new(false)),
-        });
+        let shared_state = Arc::new(SessionManager::new(SESSION_IDLE_TIMEOUT));
 
         let app = Router::new()
             .route("/mcp", post(handle_http_mcp_request))
@@ -897,14 +1143,31 @@ mod tests {
             .contains("not initialized"));
     }
 
+    #[tokio::test]
+    async fn test_error_response_carries_correlation_id() {
+        let services = create_test_services().await;
+        let session = Session::single();
+        let request: MCPRequest = serde_json::from_value(json!({
+            "jsonrpc": "2.0",
+            "id": 7,
+            "method": "tools/list"
+        }))
+        .unwrap();
+
+        // Not initialized yet
+        let response = handle_request(&services, &session, None, request).await;
+        let data = response.error.unwrap().data.unwrap();
+        let correlation_id = data["correlation_id"].as_str().unwrap();
+        assert_eq!(correlation_id.len(), 32);
+    }
+
     #[tokio::test]
     async fn test_http_callback_invoked() {
         use std::sync::Mutex;
 
         let services = create_test_services().await;
-        let shared_state = Arc::new(ServerState {
-            initialized: Arc::new(AtomicBool::new(true)), // Pre-initialized
-        });
+        let shared_state = Arc::new(SessionManager::new(SESSION_IDLE_TIMEOUT));
+        shared_state.legacy().mark_initialized();
 
         let callback_invoked = Arc::new(Mutex::new(false));
         let callback_invoked_clone = callback_invoked.clone();
@@ -935,4 +1198,221 @@ mod tests {
 
         assert!(*callback_invoked.lock().unwrap());
     }
+
+    async fn send_streamable(
+        state: HttpState<surrealdb::engine::local::Db>,
+        method: &str,
+        session_id: Option<&str>,
+        body: &str,
+    ) -> Response {
+        let app = Router::new()
+            .route("/mcp", post(handle_streamable_http_request))
+            .route("/mcp", get(handle_sse_connection))
+            .route("/mcp", delete(handle_session_delete))
+            .layer(DefaultBodyLimit::max(state.0.max_message_bytes))
+            .with_state(state);
+        let mut request = Request::builder()
+            .uri("/mcp")
+            .method(method)
+            .header("content-type", "application/json");
+        if let Some(session_id) = session_id {
+            request = request.header(SESSION_ID_HEADER, session_id);
+        }
+        app.oneshot(request.body(Body::from(body.to_string())).unwrap())
+            .await
+            .unwrap()
+    }
+
+    async fn post_streamable(
+        state: HttpState<surrealdb::engine::local::Db>,
+        session_id: Option<&str>,
+        body: &str,
+    ) -> Response {
+        send_streamable(state, "POST", session_id, body).await
+    }
+
+    /// Initialize a Streamable HTTP session, returning its ID
+    async fn start_session(state: HttpState<surrealdb::engine::local::Db>) -> String {
+        let response = post_streamable(
+            state,
+            None,
+            r#"{"jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {"protocolVersion": "2025-03-26", "capabilities": {}, "clientInfo": {"name": "test-client", "version": "1.0.0"}}}"#,
+        )
+        .await;
+        assert_eq!(response.status(), HttpStatusCode::OK);
+        response.headers()[SESSION_ID_HEADER]
+            .to_str()
+            .unwrap()
+            .to_string()
+    }
+
+    async fn response_json(response: Response) -> Value {
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_streamable_http_conformance() {
+        let mut services = create_test_services().await;
+        services.max_message_bytes = 1024;
+        let sessions = Arc::new(SessionManager::new(SESSION_IDLE_TIMEOUT));
+        let state: HttpState<_> = (Arc::new(services), None, sessions.clone());
+
+        // Invalid JSON: 400 with a parse error and a null id
+        let response = post_streamable(state.clone(), None, "{not json").await;
+        assert_eq!(response.status(), HttpStatusCode::BAD_REQUEST);
+        let json = response_json(response).await;
+        assert_eq!(json["error"]["code"], crate::mcp::types::PARSE_ERROR);
+        assert!(json["id"].is_null());
+
+        let session_id = start_session(state.clone()).await;
+
+        // String ids are echoed
+        let response = post_streamable(
+            state.clone(),
+            Some(&session_id),
+            r#"{"jsonrpc": "2.0", "id": "req-1", "method": "ping"}"#,
+        )
+        .await;
+        assert_eq!(response.status(), HttpStatusCode::OK);
+        let json = response_json(response).await;
+        assert_eq!(json["id"], "req-1");
+        assert!(json["result"].is_object());
+
+        // Notifications are accepted without a body
+        let response = post_streamable(
+            state.clone(),
+            Some(&session_id),
+            r#"{"jsonrpc": "2.0", "method": "notifications/initialized"}"#,
+        )
+        .await;
+        assert_eq!(response.status(), HttpStatusCode::ACCEPTED);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        assert!(body.is_empty());
+        assert!(sessions.get(&session_id).unwrap().is_initialized());
+
+        // Oversized bodies are refused before parsing
+        let padding = "x".repeat(1100);
+        let response = post_streamable(
+            state,
+            Some(&session_id),
+            &format!(
+                r#"{{"jsonrpc": "2.0", "id": 2, "method": "ping", "params": {{"p": "{}"}}}}"#,
+                padding
+            ),
+        )
+        .await;
+        assert_eq!(response.status(), HttpStatusCode::PAYLOAD_TOO_LARGE);
+    }
+
+    #[tokio::test]
+    async fn test_streamable_http_sessions() {
+        let services = create_test_services().await;
+        let sessions = Arc::new(SessionManager::new(SESSION_IDLE_TIMEOUT));
+        let state: HttpState<_> = (Arc::new(services), None, sessions.clone());
+
+        let first = start_session(state.clone()).await;
+        let second = start_session(state.clone()).await;
+        assert_ne!(first, second);
+        assert_ne!(
+            sessions.get(&first).unwrap().client_id(),
+            sessions.get(&second).unwrap().client_id()
+        );
+
+        let tools_list = r#"{"jsonrpc": "2.0", "id": 7, "method": "tools/list"}"#;
+
+        // Requests must name a live session
+        let response = post_streamable(state.clone(), None, tools_list).await;
+        assert_eq!(response.status(), HttpStatusCode::BAD_REQUEST);
+        assert_eq!(response_json(response).await["id"], 7);
+        let response = post_streamable(state.clone(), Some("unknown"), tools_list).await;
+        assert_eq!(response.status(), HttpStatusCode::NOT_FOUND);
+
+        let response = post_streamable(state.clone(), Some(&first), tools_list).await;
+        assert_eq!(response.status(), HttpStatusCode::OK);
+        assert!(response_json(response).await["result"]["tools"].is_array());
+
+        // Subscriptions are per session
+        let subscribe = r#"{"jsonrpc": "2.0", "id": 8, "method": "resources/subscribe", "params": {"uri": "nodespace://node/n1"}}"#;
+        let response = post_streamable(state.clone(), Some(&first), subscribe).await;
+        assert!(response_json(response).await["result"].is_object());
+        let mut stream = sessions.get(&first).unwrap().notifications().unwrap();
+        sessions.dispatch(&crate::db::events::DomainEvent::NodeUpdated {
+            node_id: "n1".to_string(),
+            source_client_id: Some(sessions.get(&second).unwrap().client_id().to_string()),
+            correlation_id: None,
+        });
+        assert_eq!(
+            stream.try_recv().unwrap()["params"]["uri"],
+            "nodespace://node/n1"
+        );
+
+        // DELETE ends the session
+        let response = send_streamable(state.clone(), "DELETE", Some(&first), "").await;
+        assert_eq!(response.status(), HttpStatusCode::NO_CONTENT);
+        let response = post_streamable(state.clone(), Some(&first), tools_list).await;
+        assert_eq!(response.status(), HttpStatusCode::NOT_FOUND);
+        let response = send_streamable(state, "GET", Some(&first), "").await;
+        assert_eq!(response.status(), HttpStatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_root_subscription_reports_app_edits() {
+        use crate::services::CreateNodeParams;
+
+        let services = create_test_services().await;
+        let node_service = services.node_service.clone();
+        let create = |parent_id: Option<String>| CreateNodeParams {
+            id: None,
+            node_type: "text".to_string(),
+            content: "Plan".to_string(),
+            parent_id,
+            insert_after_node_id: None,
+            properties: json!({}),
+        };
+        let root_id = node_service
+            .create_node_with_parent(create(None))
+            .await
+            .unwrap();
+        let child_id = node_service
+            .create_node_with_parent(create(Some(root_id.clone())))
+            .await
+            .unwrap();
+
+        let sessions = Arc::new(SessionManager::new(SESSION_IDLE_TIMEOUT));
+        spawn_session_tasks(node_service.clone(), &sessions);
+        let state: HttpState<_> = (Arc::new(services), None, sessions.clone());
+        let session_id = start_session(state.clone()).await;
+        let mut stream = sessions.get(&session_id).unwrap().notifications().unwrap();
+
+        // Subscribing through any node in the document watches its root
+        let subscribe = json!({
+            "jsonrpc": "2.0",
+            "id": 2,
+            "method": "nodespace/subscribe",
+            "params": { "nodeId": child_id }
+        });
+        let response = post_streamable(state, Some(&session_id), &subscribe.to_string()).await;
+        assert_eq!(
+            response_json(response).await["result"]["rootId"],
+            json!(root_id)
+        );
+
+        // A deletion in the app (no MCP client) reaches the agent
+        node_service.delete_node_unchecked(&child_id).await.unwrap();
+        let deleted = tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                let notification = stream.recv().await.unwrap();
+                if notification["params"]["change"] == "deleted" {
+                    return notification;
+                }
+            }
+        })
+        .await
+        .unwrap();
+        assert_eq!(deleted["method"], "nodespace/nodeChanged");
+        assert_eq!(deleted["params"]["rootId"], json!(root_id));
+        assert_eq!(deleted["params"]["nodeId"], json!(child_id));
+        assert_eq!(deleted["params"]["byAgent"], false);
+    }
 }
@@ -0,0 +1,642 @@
+//! MCP Sessions - Per-Client State for the HTTP Transport
+//!
+//! A stdio server has exactly one peer, but any number of agents can connect
+//! to the HTTP server at once. Each of them gets a [`Session`] when it calls
+//! `initialize`; the session ID travels in the `Mcp-Session-Id` header of
+//! every later request (Streamable HTTP, MCP 2025-03-26).
+//!
+//! A session carries:
+//! - its own client ID (`mcp-server:<session>`), recorded as the source of the
+//!   domain events its requests cause, so the desktop app and other sessions
+//!   can tell agents apart
+//! - its own initialization state
+//! - resource subscriptions (`resources/subscribe` with a
+//!   `nodespace://node/<id>` URI): changes to a subscribed node made by anyone
+//!   else are sent as `notifications/resources/updated` on the session's
+//!   `GET /mcp` stream
+//! - root subscriptions (`nodespace/subscribe`): changes anywhere in a
+//!   document are sent on the same stream as `nodespace/nodeChanged`, which
+//!   names the node and the kind of change, so agents can react to the
+//!   user's edits instead of polling `get_node`
+//!
+//! Sessions that send nothing for the idle timeout, and have no notification
+//! stream open, are dropped; their client must initialize again.
+
+use crate::db::events::DomainEvent;
+use crate::services::{is_mcp_client_id, DEEP_LINK_SCHEME, MCP_CLIENT_ID};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
+use tokio::sync::broadcast;
+
+/// HTTP header carrying the session ID
+pub const SESSION_ID_HEADER: &str = "mcp-session-id";
+
+/// How long a session may go without requests before it is dropped
+pub const SESSION_IDLE_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+
+/// Notifications buffered per session while its stream is slow or closed
+const NOTIFICATION_CAPACITY: usize = 64;
+
+/// Client ID for the events caused by a session's requests
+pub fn session_client_id(session_id: &str) -> String {
+    format!("{}:{}", MCP_CLIENT_ID, session_id)
+}
+
+/// Resource URI of a node
+pub fn node_resource_uri(node_id: &str) -> String {
+    format!("{}://node/{}", DEEP_LINK_SCHEME, node_id)
+}
+
+/// Kind of change reported by `nodespace/nodeChanged`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ChangeKind {
+    Created,
+    Updated,
+    Deleted,
+    /// Moved to another parent or position
+    Moved,
+}
+
+/// A change to a node, as reported to sessions watching its root
+#[derive(Debug, Clone, PartialEq)]
+pub struct NodeChange {
+    pub node_id: String,
+    pub kind: ChangeKind,
+    /// Root the node is in after the change; unknown for deletions, which
+    /// are matched against the nodes each watch has seen
+    pub root_id: Option<String>,
+    pub source_client_id: Option<String>,
+}
+
+impl NodeChange {
+    /// The change an event describes, without its root
+    ///
+    /// Hierarchy edges only report moves; a created child already has its own
+    /// `NodeCreated` event.
+    pub fn from_event(event: &DomainEvent) -> Option<Self> {
+        let (node_id, kind, source_client_id) = match event {
+            DomainEvent::NodeCreated {
+                node_id,
+                source_client_id,
+                ..
+            } => (node_id, ChangeKind::Created, source_client_id),
+            DomainEvent::NodeUpdated {
+                node_id,
+                source_client_id,
+                ..
+            } => (node_id, ChangeKind::Updated, source_client_id),
+            DomainEvent::NodeDeleted {
+                id,
+                source_client_id,
+                ..
+            } => (id, ChangeKind::Deleted, source_client_id),
+            DomainEvent::RelationshipUpdated {
+                relationship,
+                source_client_id,
+                ..
+            } if relationship.relationship_type == "has_child" => {
+                (&relationship.to_id, ChangeKind::Moved, source_client_id)
+            }
+            _ => return None,
+        };
+        Some(Self {
+            node_id: node_id.clone(),
+            kind,
+            root_id: None,
+            source_client_id: source_client_id.clone(),
+        })
+    }
+}
+
+/// Which changes in a watched root a session is told about
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangeFilter {
+    /// Only these kinds of change (all when absent)
+    #[serde(default, alias = "changes")]
+    pub kinds: Option<HashSet<ChangeKind>>,
+    /// Also report the session's own changes
+    #[serde(default, alias = "include_own_changes")]
+    pub include_own_changes: bool,
+}
+
+impl ChangeFilter {
+    fn accepts(&self, kind: ChangeKind, own: bool) -> bool {
+        (self.include_own_changes || !own)
+            && self
+                .kinds
+                .as_ref()
+                .is_none_or(|kinds| kinds.contains(&kind))
+    }
+}
+
+/// A session's subscription to one root
+struct RootWatch {
+    filter: ChangeFilter,
+    /// Nodes known to be in the root, so deletions and moves out of it can
+    /// be attributed after the node has left
+    members: HashSet<String>,
+}
+
+/// State of one MCP client
+pub struct Session {
+    id: String,
+    client_id: String,
+    initialized: AtomicBool,
+    last_seen: Mutex<Instant>,
+    /// IDs of the nodes the client subscribed to
+    subscriptions: Mutex<HashSet<String>>,
+    /// Watched roots by ID
+    root_watches: Mutex<HashMap<String, RootWatch>>,
+    /// Server-to-client notifications; `None` for transports without a
+    /// stream to send them on
+    notifications: Option<broadcast::Sender<Value>>,
+}
+
+impl Session {
+    fn new(id: String, client_id: String, notifications: bool) -> Self {
+        Self {
+            id,
+            client_id,
+            initialized: AtomicBool::new(false),
+            last_seen: Mutex::new(Instant::now()),
+            subscriptions: Mutex::new(HashSet::new()),
+            root_watches: Mutex::new(HashMap::new()),
+            notifications: notifications.then(|| broadcast::channel(NOTIFICATION_CAPACITY).0),
+        }
+    }
+
+    /// The session of a transport with a single peer (stdio, and the
+    /// deprecated HTTP+SSE transport, which has no session IDs)
+    ///
+    /// Its requests are attributed to [`MCP_CLIENT_ID`] and it can't
+    /// subscribe to resources.
+    pub fn single() -> Self {
+        Self::new(String::new(), MCP_CLIENT_ID.to_string(), false)
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn client_id(&self) -> &str {
+        &self.client_id
+    }
+
+    /// Whether the client has completed the initialization handshake
+    pub fn is_initialized(&self) -> bool {
+        self.initialized.load(Ordering::SeqCst)
+    }
+
+    pub fn mark_initialized(&self) {
+        self.initialized.store(true, Ordering::SeqCst);
+    }
+
+    /// Whether the session can receive resource notifications
+    pub fn supports_subscriptions(&self) -> bool {
+        self.notifications.is_some()
+    }
+
+    /// Subscribe to changes of a node; false if already subscribed
+    pub fn subscribe(&self, node_id: &str) -> bool {
+        self.subscriptions
+            .lock()
+            .unwrap()
+            .insert(node_id.to_string())
+    }
+
+    /// Stop notifying about a node; false if it wasn't subscribed
+    pub fn unsubscribe(&self, node_id: &str) -> bool {
+        self.subscriptions.lock().unwrap().remove(node_id)
+    }
+
+    /// Report changes anywhere in a root, replacing any earlier filter
+    ///
+    /// `members` are the nodes currently in the root.
+    pub fn watch_root(
+        &self,
+        root_id: &str,
+        filter: ChangeFilter,
+        members: impl IntoIterator<Item = String>,
+    ) {
+        self.root_watches.lock().unwrap().insert(
+            root_id.to_string(),
+            RootWatch {
+                filter,
+                members: members.into_iter().collect(),
+            },
+        );
+    }
+
+    /// Stop reporting changes in a root; false if it wasn't watched
+    pub fn unwatch_root(&self, root_id: &str) -> bool {
+        self.root_watches.lock().unwrap().remove(root_id).is_some()
+    }
+
+    fn watches_roots(&self) -> bool {
+        !self.root_watches.lock().unwrap().is_empty()
+    }
+
+    /// Stream of the session's notifications
+    pub fn notifications(&self) -> Option<broadcast::Receiver<Value>> {
+        self.notifications.as_ref().map(|tx| tx.subscribe())
+    }
+
+    fn has_open_stream(&self) -> bool {
+        self.notifications
+            .as_ref()
+            .is_some_and(|tx| tx.receiver_count() > 0)
+    }
+
+    fn touch(&self) {
+        *self.last_seen.lock().unwrap() = Instant::now();
+    }
+
+    fn idle_since(&self, now: Instant) -> Duration {
+        now.saturating_duration_since(*self.last_seen.lock().unwrap())
+    }
+
+    /// Notify the client if `event` touches a node it subscribed to
+    ///
+    /// The session's own changes are skipped; its client already knows.
+    fn notify(&self, event: &DomainEvent) {
+        let Some(tx) = &self.notifications else {
+            return;
+        };
+        let (node_ids, source) = event_nodes(event);
+        if source == Some(self.client_id.as_str()) {
+            return;
+        }
+
+        let subscriptions = self.subscriptions.lock().unwrap();
+        for node_id in node_ids
+            .into_iter()
+            .filter(|id| subscriptions.contains(*id))
+        {
+            // No receivers just means no stream is open right now
+            let _ = tx.send(json!({
+                "jsonrpc": "2.0",
+                "method": "notifications/resources/updated",
+                "params": { "uri": node_resource_uri(node_id) }
+            }));
+        }
+    }
+
+    /// Report a node change to the session's watches of the roots it touches
+    ///
+    /// A node moved out of a watched root is reported to that root's watch
+    /// as moved, just as it is to the watch of the root it moved into.
+    fn notify_node_change(&self, change: &NodeChange) {
+        let Some(tx) = &self.notifications else {
+            return;
+        };
+        let own = change.source_client_id.as_deref() == Some(self.client_id.as_str());
+        let by_agent = change
+            .source_client_id
+            .as_deref()
+            .is_some_and(is_mcp_client_id);
+
+        let mut watches = self.root_watches.lock().unwrap();
+        for (root_id, watch) in watches.iter_mut() {
+            let in_root = match (&change.root_id, change.kind) {
+                (_, ChangeKind::Deleted) => {
+                    watch.members.remove(&change.node_id) || *root_id == change.node_id
+                }
+                (Some(changed_root), _) if changed_root == root_id => {
+                    watch.members.insert(change.node_id.clone());
+                    true
+                }
+                (_, ChangeKind::Moved) => watch.members.remove(&change.node_id),
+                _ => false,
+            };
+            if !in_root || !watch.filter.accepts(change.kind, own) {
+                continue;
+            }
+            let _ = tx.send(json!({
+                "jsonrpc": "2.0",
+                "method": "nodespace/nodeChanged",
+                "params": {
+                    "rootId": root_id,
+                    "nodeId": change.node_id,
+                    "change": change.kind,
+                    "byAgent": by_agent
+                }
+            }));
+        }
+    }
+}
+
+/// Nodes an event changes, and the client that caused it
+///
+/// Relationship changes count as changes to both of their ends.
+fn event_nodes(event: &DomainEvent) -> (Vec<&str>, Option<&str>) {
+    match event {
+        DomainEvent::NodeCreated {
+            node_id,
+            source_client_id,
+            ..
+        }
+        | DomainEvent::NodeUpdated {
+            node_id,
+            source_client_id,
+            ..
+        }
+        | DomainEvent::NodeDeleted {
+            id: node_id,
+            source_client_id,
+            ..
+        } => (vec![node_id.as_str()], source_client_id.as_deref()),
+        DomainEvent::RelationshipCreated {
+            relationship,
+            source_client_id,
+            ..
+        }
+        | DomainEvent::RelationshipUpdated {
+            relationship,
+            source_client_id,
+            ..
+        } => (
+            vec![relationship.from_id.as_str(), relationship.to_id.as_str()],
+            source_client_id.as_deref(),
+        ),
+        DomainEvent::RelationshipDeleted {
+            from_id,
+            to_id,
+            source_client_id,
+            ..
+        } => (
+            vec![from_id.as_str(), to_id.as_str()],
+            source_client_id.as_deref(),
+        ),
+    }
+}
+
+/// The sessions of an HTTP server
+pub struct SessionManager {
+    sessions: RwLock<HashMap<String, Arc<Session>>>,
+    /// Shared by clients of the deprecated HTTP+SSE transport
+    legacy: Arc<Session>,
+    idle_timeout: Duration,
+}
+
+impl SessionManager {
+    pub fn new(idle_timeout: Duration) -> Self {
+        Self {
+            sessions: RwLock::new(HashMap::new()),
+            legacy: Arc::new(Session::single()),
+            idle_timeout,
+        }
+    }
+
+    /// Start a session for a client that is initializing
+    pub fn create(&self) -> Arc<Session> {
+        let id = uuid::Uuid::new_v4().simple().to_string();
+        let session = Arc::new(Session::new(id.clone(), session_client_id(&id), true));
+        self.sessions.write().unwrap().insert(id, session.clone());
+        session
+    }
+
+    /// A session by ID, counting as activity
+    pub fn get(&self, id: &str) -> Option<Arc<Session>> {
+        let session = self.sessions.read().unwrap().get(id).cloned()?;
+        session.touch();
+        Some(session)
+    }
+
+    /// End a session; false if there was none with this ID
+    pub fn remove(&self, id: &str) -> bool {
+        self.sessions.write().unwrap().remove(id).is_some()
+    }
+
+    /// The session of clients that don't send session IDs
+    pub fn legacy(&self) -> &Arc<Session> {
+        &self.legacy
+    }
+
+    /// Drop the sessions idle for longer than the idle timeout as of `now`,
+    /// returning their IDs
+    ///
+    /// A session whose client is listening for notifications isn't idle.
+    pub fn remove_idle(&self, now: Instant) -> Vec<String> {
+        let mut sessions = self.sessions.write().unwrap();
+        let expired: Vec<String> = sessions
+            .values()
+            .filter(|session| {
+                !session.has_open_stream() && session.idle_since(now) > self.idle_timeout
+            })
+            .map(|session| session.id.clone())
+            .collect();
+        for id in &expired {
+            sessions.remove(id);
+        }
+        expired
+    }
+
+    /// Pass a domain event to every session's subscriptions
+    pub fn dispatch(&self, event: &DomainEvent) {
+        for session in self.sessions.read().unwrap().values() {
+            session.notify(event);
+        }
+    }
+
+    /// Whether any session watches a root, i.e. whether node changes need
+    /// their roots looked up
+    pub fn watches_roots(&self) -> bool {
+        self.sessions
+            .read()
+            .unwrap()
+            .values()
+            .any(|session| session.watches_roots())
+    }
+
+    /// Pass a node change, with its root resolved, to every session's
+    /// root watches
+    pub fn dispatch_node_change(&self, change: &NodeChange) {
+        for session in self.sessions.read().unwrap().values() {
+            session.notify_node_change(change);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn updated(node_id: &str, source: Option<&str>) -> DomainEvent {
+        DomainEvent::NodeUpdated {
+            node_id: node_id.to_string(),
+            source_client_id: source.map(str::to_string),
+            correlation_id: None,
+        }
+    }
+
+    #[test]
+    fn test_sessions_are_separate_clients() {
+        let manager = SessionManager::new(SESSION_IDLE_TIMEOUT);
+        let a = manager.create();
+        let b = manager.create();
+        assert_ne!(a.id(), b.id());
+        assert_eq!(a.client_id(), session_client_id(a.id()));
+        assert_ne!(a.client_id(), b.client_id());
+
+        a.mark_initialized();
+        assert!(manager.get(a.id()).unwrap().is_initialized());
+        assert!(!manager.get(b.id()).unwrap().is_initialized());
+
+        assert!(manager.remove(b.id()));
+        assert!(manager.get(b.id()).is_none());
+        assert!(!manager.remove(b.id()));
+
+        assert_eq!(manager.legacy().client_id(), MCP_CLIENT_ID);
+        assert!(!manager.legacy().supports_subscriptions());
+    }
+
+    #[test]
+    fn test_subscribers_are_notified_of_changes_by_others() {
+        let manager = SessionManager::new(SESSION_IDLE_TIMEOUT);
+        let watcher = manager.create();
+        let writer = manager.create();
+        let mut stream = watcher.notifications().unwrap();
+        assert!(watcher.subscribe("n1"));
+        assert!(!watcher.subscribe("n1"));
+
+        manager.dispatch(&updated("n2", Some(writer.client_id())));
+        manager.dispatch(&updated("n1", Some(watcher.client_id())));
+        assert!(stream.try_recv().is_err());
+
+        manager.dispatch(&updated("n1", Some(writer.client_id())));
+        manager.dispatch(&DomainEvent::RelationshipDeleted {
+            id: "relationship:r1".to_string(),
+            from_id: "n1".to_string(),
+            to_id: "n3".to_string(),
+            relationship_type: "has_child".to_string(),
+            source_client_id: None,
+            correlation_id: None,
+        });
+        for _ in 0..2 {
+            let notification = stream.try_recv().unwrap();
+            assert_eq!(notification["method"], "notifications/resources/updated");
+            assert_eq!(notification["params"]["uri"], "nodespace://node/n1");
+        }
+        assert!(stream.try_recv().is_err());
+
+        assert!(watcher.unsubscribe("n1"));
+        manager.dispatch(&updated("n1", None));
+        assert!(stream.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_root_watches_report_changes_in_the_root() {
+        let manager = SessionManager::new(SESSION_IDLE_TIMEOUT);
+        let watcher = manager.create();
+        let mut stream = watcher.notifications().unwrap();
+        watcher.watch_root("doc", ChangeFilter::default(), ["a".to_string()]);
+        assert!(manager.watches_roots());
+
+        let change =
+            |node_id: &str, kind, root_id: Option<&str>, source: Option<&str>| NodeChange {
+                node_id: node_id.to_string(),
+                kind,
+                root_id: root_id.map(str::to_string),
+                source_client_id: source.map(str::to_string),
+            };
+
+        // A user edit, a change elsewhere and the watcher's own edit
+        manager.dispatch_node_change(&change("b", ChangeKind::Created, Some("doc"), None));
+        manager.dispatch_node_change(&change("x", ChangeKind::Updated, Some("other"), None));
+        manager.dispatch_node_change(&change(
+            "a",
+            ChangeKind::Updated,
+            Some("doc"),
+            Some(watcher.client_id()),
+        ));
+        let notification = stream.try_recv().unwrap();
+        assert_eq!(notification["method"], "nodespace/nodeChanged");
+        assert_eq!(
+            notification["params"],
+            json!({ "rootId": "doc", "nodeId": "b", "change": "created", "byAgent": false })
+        );
+        assert!(stream.try_recv().is_err());
+
+        // Deletions and moves out are matched against the nodes seen in the root
+        manager.dispatch_node_change(&change(
+            "b",
+            ChangeKind::Deleted,
+            None,
+            Some("mcp-server:s2"),
+        ));
+        manager.dispatch_node_change(&change("a", ChangeKind::Moved, Some("other"), None));
+        manager.dispatch_node_change(&change("a", ChangeKind::Deleted, None, None));
+        let deleted = stream.try_recv().unwrap();
+        assert_eq!(deleted["params"]["change"], "deleted");
+        assert_eq!(deleted["params"]["byAgent"], true);
+        assert_eq!(stream.try_recv().unwrap()["params"]["change"], "moved");
+        assert!(stream.try_recv().is_err());
+
+        // Filters
+        let filter: ChangeFilter =
+            serde_json::from_value(json!({ "changes": ["deleted"] })).unwrap();
+        watcher.watch_root("doc", filter, Vec::new());
+        manager.dispatch_node_change(&change("c", ChangeKind::Created, Some("doc"), None));
+        manager.dispatch_node_change(&change("c", ChangeKind::Deleted, None, None));
+        assert_eq!(stream.try_recv().unwrap()["params"]["change"], "deleted");
+        assert!(stream.try_recv().is_err());
+
+        assert!(watcher.unwatch_root("doc"));
+        assert!(!manager.watches_roots());
+    }
+
+    #[test]
+    fn test_node_changes_from_events() {
+        let moved = DomainEvent::RelationshipUpdated {
+            relationship: crate::db::events::RelationshipEvent {
+                id: "relationship:p:n1".to_string(),
+                from_id: "p".to_string(),
+                to_id: "n1".to_string(),
+                relationship_type: "has_child".to_string(),
+                properties: json!({ "order": 1.0 }),
+            },
+            source_client_id: None,
+            correlation_id: None,
+        };
+        let change = NodeChange::from_event(&moved).unwrap();
+        assert_eq!(change.node_id, "n1");
+        assert_eq!(change.kind, ChangeKind::Moved);
+
+        let change = NodeChange::from_event(&updated("n2", Some("tauri"))).unwrap();
+        assert_eq!(change.kind, ChangeKind::Updated);
+        assert_eq!(change.source_client_id.as_deref(), Some("tauri"));
+
+        let mention = DomainEvent::RelationshipDeleted {
+            id: "relationship:r1".to_string(),
+            from_id: "n1".to_string(),
+            to_id: "n2".to_string(),
+            relationship_type: "mentions".to_string(),
+            source_client_id: None,
+            correlation_id: None,
+        };
+        assert!(NodeChange::from_event(&mention).is_none());
+    }
+
+    #[test]
+    fn test_idle_sessions_expire() {
+        let manager = SessionManager::new(Duration::from_secs(60));
+        let idle = manager.create();
+        let active = manager.create();
+        let listening = manager.create();
+        let _stream = listening.notifications().unwrap();
+
+        let later = Instant::now() + Duration::from_secs(61);
+        assert!(manager.remove_idle(Instant::now()).is_empty());
+        *active.last_seen.lock().unwrap() = later;
+        assert_eq!(manager.remove_idle(later), vec![idle.id().to_string()]);
+        assert!(manager.get(idle.id()).is_none());
+        assert!(manager.get(active.id()).is_some());
+        assert!(manager.get(listening.id()).is_some());
+    }
+}
@@ -2,9 +2,46 @@
 //!
 //! Type definitions for Model Context Protocol communication.
 //! Implements JSON-RPC 2.0 specification for stdio-based MCP transport.
+//!
+//! Every transport classifies what it receives with [`IncomingMessage::parse`]:
+//! requests are answered with their `id` echoed back, notifications are never
+//! answered, and anything else gets an error response whose `id` is the
+//! message's own when it could be read and `null` otherwise.
 
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::fmt;
+
+/// JSON-RPC request identifier, echoed verbatim in the response
+///
+/// MCP forbids `null` ids, so only numbers and strings are accepted.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum RequestId {
+    Number(i64),
+    String(String),
+}
+
+impl fmt::Display for RequestId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RequestId::Number(id) => write!(f, "{}", id),
+            RequestId::String(id) => write!(f, "\"{}\"", id),
+        }
+    }
+}
+
+impl From<i64> for RequestId {
+    fn from(id: i64) -> Self {
+        RequestId::Number(id)
+    }
+}
+
+impl From<&str> for RequestId {
+    fn from(id: &str) -> Self {
+        RequestId::String(id.to_string())
+    }
+}
 
 /// JSON-RPC 2.0 request structure
 ///
@@ -29,7 +66,7 @@ pub struct MCPRequest {
     pub jsonrpc: String,
 
     /// Request identifier (used to match responses)
-    pub id: u64,
+    pub id: RequestId,
 
     /// Method name to invoke
     pub method: String,
@@ -112,8 +149,9 @@ pub struct MCPResponse {
     /// JSON-RPC version (always "2.0")
     pub jsonrpc: String,
 
-    /// Request identifier (matches request)
-    pub id: u64,
+    /// Request identifier (matches request); `null` when the message it
+    /// answers had no readable id
+    pub id: Option<RequestId>,
 
     /// Success result (mutually exclusive with error)
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -152,6 +190,8 @@ pub const NODE_UPDATE_FAILED: i32 = -32002;
 pub const NODE_DELETE_FAILED: i32 = -32003;
 pub const VALIDATION_ERROR: i32 = -32004;
 pub const VERSION_CONFLICT: i32 = -32005;
+pub const ACCESS_DENIED: i32 = -32006;
+pub const QUERY_TIMEOUT: i32 = -32007;
 
 impl MCPError {
     /// Create a parse error
@@ -244,6 +284,24 @@ impl MCPError {
         }
     }
 
+    /// Create an access denied error (outside the agent profile's scope)
+    pub fn access_denied(message: String) -> Self {
+        Self {
+            code: ACCESS_DENIED,
+            message,
+            data: None,
+        }
+    }
+
+    /// Create a query timeout error (query cancelled after the store's timeout)
+    pub fn query_timeout(message: String) -> Self {
+        Self {
+            code: QUERY_TIMEOUT,
+            message,
+            data: None,
+        }
+    }
+
     /// Create a version conflict error with current node state for client-side merge
     ///
     /// # Arguments
@@ -291,21 +349,42 @@ impl MCPError {
             })),
         }
     }
+
+    /// Record the correlation ID of the request that failed in `data`
+    ///
+    /// Data that isn't an object is left alone.
+    pub fn with_correlation_id(mut self, correlation_id: &str) -> Self {
+        match &mut self.data {
+            Some(Value::Object(data)) => {
+                data.insert("correlation_id".to_string(), correlation_id.into());
+            }
+            Some(_) => {}
+            None => {
+                self.data = Some(serde_json::json!({ "correlation_id": correlation_id }));
+            }
+        }
+        self
+    }
 }
 
 impl MCPResponse {
     /// Create a success response
-    pub fn success(id: u64, result: Value) -> Self {
+    pub fn success(id: RequestId, result: Value) -> Self {
         Self {
             jsonrpc: "2.0".to_string(),
-            id,
+            id: Some(id),
             result: Some(result),
             error: None,
         }
     }
 
     /// Create an error response
-    pub fn error(id: u64, error: MCPError) -> Self {
+    pub fn error(id: RequestId, error: MCPError) -> Self {
+        Self::error_for(Some(id), error)
+    }
+
+    /// Create an error response to a message whose id may be unknown
+    pub fn error_for(id: Option<RequestId>, error: MCPError) -> Self {
         Self {
             jsonrpc: "2.0".to_string(),
             id,
@@ -315,6 +394,94 @@ impl MCPResponse {
     }
 }
 
+/// A message received from a client
+#[derive(Debug)]
+pub enum IncomingMessage {
+    Request(MCPRequest),
+    /// Has no `id`, so it is handled without a response
+    Notification(MCPNotification),
+    /// Not a valid JSON-RPC 2.0 message; carries the error response to send
+    Invalid(MCPResponse),
+}
+
+impl IncomingMessage {
+    /// Classify a single JSON-RPC message
+    ///
+    /// - malformed JSON is a parse error
+    /// - a non-object (including a batch), a missing or wrong `jsonrpc`, a
+    ///   non-string `method`, an unusable `id` or unknown members make an
+    ///   invalid request
+    /// - request `params` that are neither an object nor an array are
+    ///   invalid params
+    pub fn parse(text: &str) -> Self {
+        let value: Value = match serde_json::from_str(text) {
+            Ok(value) => value,
+            Err(e) => {
+                return Self::invalid(None, MCPError::parse_error(format!("Invalid JSON: {}", e)))
+            }
+        };
+        let Value::Object(object) = &value else {
+            let message = if value.is_array() {
+                "Batch requests are not supported"
+            } else {
+                "Message must be a JSON object"
+            };
+            return Self::invalid(None, MCPError::invalid_request(message.to_string()));
+        };
+
+        let id = match object.get("id") {
+            None => None,
+            Some(id) => match RequestId::deserialize(id) {
+                Ok(id) => Some(id),
+                Err(_) => {
+                    return Self::invalid(
+                        None,
+                        MCPError::invalid_request(
+                            "'id' must be a string or an integer".to_string(),
+                        ),
+                    )
+                }
+            },
+        };
+        if object.get("jsonrpc").and_then(Value::as_str) != Some("2.0") {
+            return Self::invalid(
+                id,
+                MCPError::invalid_request("'jsonrpc' must be \"2.0\"".to_string()),
+            );
+        }
+        if !object.get("method").is_some_and(Value::is_string) {
+            return Self::invalid(
+                id,
+                MCPError::invalid_request("'method' must be a string".to_string()),
+            );
+        }
+
+        let Some(id) = id else {
+            return match serde_json::from_value(value) {
+                Ok(notification) => Self::Notification(notification),
+                Err(e) => Self::invalid(None, MCPError::invalid_request(e.to_string())),
+            };
+        };
+        if object
+            .get("params")
+            .is_some_and(|params| !params.is_object() && !params.is_array())
+        {
+            return Self::invalid(
+                Some(id),
+                MCPError::invalid_params("'params' must be an object or an array".to_string()),
+            );
+        }
+        match serde_json::from_value(value) {
+            Ok(request) => Self::Request(request),
+            Err(e) => Self::invalid(Some(id), MCPError::invalid_request(e.to_string())),
+        }
+    }
+
+    fn invalid(id: Option<RequestId>, error: MCPError) -> Self {
+        Self::Invalid(MCPResponse::error_for(id, error))
+    }
+}
+
 // Include tests
 #[cfg(test)]
 #[path = "types_test.rs"]
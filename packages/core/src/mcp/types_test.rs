@@ -5,9 +5,9 @@
 #[cfg(test)]
 mod tests {
     use crate::mcp::types::{
-        MCPError, MCPNotification, MCPRequest, MCPResponse, INTERNAL_ERROR, INVALID_PARAMS,
-        INVALID_REQUEST, METHOD_NOT_FOUND, NODE_CREATION_FAILED, NODE_DELETE_FAILED,
-        NODE_NOT_FOUND, NODE_UPDATE_FAILED, PARSE_ERROR, VALIDATION_ERROR,
+        IncomingMessage, MCPError, MCPNotification, MCPRequest, MCPResponse, RequestId,
+        INTERNAL_ERROR, INVALID_PARAMS, INVALID_REQUEST, METHOD_NOT_FOUND, NODE_CREATION_FAILED,
+        NODE_DELETE_FAILED, NODE_NOT_FOUND, NODE_UPDATE_FAILED, PARSE_ERROR, VALIDATION_ERROR,
     };
     use serde_json::json;
 
@@ -26,7 +26,7 @@ mod tests {
         let request: MCPRequest = serde_json::from_str(json_str).unwrap();
 
         assert_eq!(request.jsonrpc, "2.0");
-        assert_eq!(request.id, 123);
+        assert_eq!(request.id, RequestId::Number(123));
         assert_eq!(request.method, "create_node");
         assert!(request.params.is_object());
     }
@@ -47,7 +47,7 @@ mod tests {
     fn test_serialize_success_response() {
         let response = MCPResponse {
             jsonrpc: "2.0".to_string(),
-            id: 42,
+            id: Some(RequestId::Number(42)),
             result: Some(json!({"success": true, "node_id": "abc123"})),
             error: None,
         };
@@ -65,7 +65,7 @@ mod tests {
     fn test_serialize_error_response() {
         let response = MCPResponse {
             jsonrpc: "2.0".to_string(),
-            id: 99,
+            id: Some(RequestId::Number(99)),
             result: None,
             error: Some(MCPError {
                 code: NODE_NOT_FOUND,
@@ -117,7 +117,7 @@ mod tests {
     fn test_response_serialization() {
         let response = MCPResponse {
             jsonrpc: "2.0".to_string(),
-            id: 777,
+            id: Some(RequestId::Number(777)),
             result: Some(json!({"data": [1, 2, 3]})),
             error: None,
         };
@@ -162,14 +162,14 @@ mod tests {
 
     #[test]
     fn test_mcp_response_helper_methods() {
-        let success = MCPResponse::success(42, json!({"result": "ok"}));
-        assert_eq!(success.id, 42);
+        let success = MCPResponse::success(42.into(), json!({"result": "ok"}));
+        assert_eq!(success.id, Some(RequestId::Number(42)));
         assert_eq!(success.jsonrpc, "2.0");
         assert!(success.error.is_none());
         assert!(success.result.is_some());
 
-        let error_resp = MCPResponse::error(99, MCPError::node_not_found("xyz"));
-        assert_eq!(error_resp.id, 99);
+        let error_resp = MCPResponse::error(99.into(), MCPError::node_not_found("xyz"));
+        assert_eq!(error_resp.id, Some(RequestId::Number(99)));
         assert_eq!(error_resp.jsonrpc, "2.0");
         assert!(error_resp.result.is_none());
         assert!(error_resp.error.is_some());
@@ -275,4 +275,122 @@ mod tests {
         let result: Result<MCPRequest, _> = serde_json::from_str(json_str);
         assert!(result.is_err());
     }
+
+    // JSON-RPC 2.0 conformance (classification of incoming messages)
+
+    fn error_reply(text: &str) -> serde_json::Value {
+        match IncomingMessage::parse(text) {
+            IncomingMessage::Invalid(response) => serde_json::to_value(&response).unwrap(),
+            other => panic!("expected an error reply for {}, got {:?}", text, other),
+        }
+    }
+
+    #[test]
+    fn test_string_and_number_ids_are_echoed() {
+        let IncomingMessage::Request(request) =
+            IncomingMessage::parse(r#"{"jsonrpc": "2.0", "id": "abc-1", "method": "ping"}"#)
+        else {
+            panic!("expected a request");
+        };
+        assert_eq!(request.id, RequestId::from("abc-1"));
+        let response = MCPResponse::success(request.id, json!({}));
+        assert_eq!(serde_json::to_value(&response).unwrap()["id"], "abc-1");
+
+        let IncomingMessage::Request(request) =
+            IncomingMessage::parse(r#"{"jsonrpc": "2.0", "id": -7, "method": "ping"}"#)
+        else {
+            panic!("expected a request");
+        };
+        assert_eq!(request.id, RequestId::Number(-7));
+    }
+
+    #[test]
+    fn test_notification_is_not_answered() {
+        assert!(matches!(
+            IncomingMessage::parse(r#"{"jsonrpc": "2.0", "method": "notifications/initialized"}"#),
+            IncomingMessage::Notification(_)
+        ));
+        // Notification params aren't checked, as there is no way to report an error
+        assert!(matches!(
+            IncomingMessage::parse(r#"{"jsonrpc": "2.0", "method": "progress", "params": 5}"#),
+            IncomingMessage::Notification(_)
+        ));
+    }
+
+    #[test]
+    fn test_parse_error_has_null_id() {
+        let reply = error_reply(r#"{"jsonrpc": "2.0", "method": "foobar, "params": "bar", "baz]"#);
+        assert_eq!(reply["error"]["code"], PARSE_ERROR);
+        assert!(reply["id"].is_null());
+        assert!(reply.as_object().unwrap().contains_key("id"));
+        assert!(reply.get("result").is_none());
+
+        assert_eq!(error_reply("")["error"]["code"], PARSE_ERROR);
+    }
+
+    #[test]
+    fn test_invalid_requests() {
+        // Not an object, or a batch
+        for text in [
+            "1",
+            r#""ping""#,
+            "[]",
+            r#"[{"jsonrpc": "2.0", "id": 1, "method": "ping"}]"#,
+        ] {
+            let reply = error_reply(text);
+            assert_eq!(reply["error"]["code"], INVALID_REQUEST, "{}", text);
+            assert!(reply["id"].is_null());
+        }
+
+        // Readable ids are echoed even when the rest is invalid
+        let reply = error_reply(r#"{"jsonrpc": "1.0", "id": 5, "method": "ping"}"#);
+        assert_eq!(reply["error"]["code"], INVALID_REQUEST);
+        assert_eq!(reply["id"], 5);
+        let reply = error_reply(r#"{"jsonrpc": "2.0", "id": "a", "method": 1}"#);
+        assert_eq!(reply["error"]["code"], INVALID_REQUEST);
+        assert_eq!(reply["id"], "a");
+        let reply = error_reply(r#"{"jsonrpc": "2.0", "id": 3, "method": "ping", "extra": 1}"#);
+        assert_eq!(reply["error"]["code"], INVALID_REQUEST);
+        assert_eq!(reply["id"], 3);
+
+        // Missing method without an id still gets a reply
+        let reply = error_reply(r#"{"jsonrpc": "2.0"}"#);
+        assert_eq!(reply["error"]["code"], INVALID_REQUEST);
+        assert!(reply["id"].is_null());
+
+        // Unusable ids
+        for id in ["null", "1.5", "{}", "true"] {
+            let text = format!(r#"{{"jsonrpc": "2.0", "id": {}, "method": "ping"}}"#, id);
+            let reply = error_reply(&text);
+            assert_eq!(reply["error"]["code"], INVALID_REQUEST, "{}", text);
+            assert!(reply["id"].is_null());
+        }
+    }
+
+    #[test]
+    fn test_unstructured_params_are_invalid_params() {
+        let reply =
+            error_reply(r#"{"jsonrpc": "2.0", "id": 9, "method": "ping", "params": "bar"}"#);
+        assert_eq!(reply["error"]["code"], INVALID_PARAMS);
+        assert_eq!(reply["id"], 9);
+
+        assert!(matches!(
+            IncomingMessage::parse(
+                r#"{"jsonrpc": "2.0", "id": 9, "method": "ping", "params": [1]}"#
+            ),
+            IncomingMessage::Request(_)
+        ));
+    }
+
+    #[test]
+    fn test_correlation_id_is_added_to_error_data() {
+        let error = MCPError::invalid_params("bad".to_string()).with_correlation_id("c1");
+        assert_eq!(error.data, Some(json!({"correlation_id": "c1"})));
+
+        let error =
+            MCPError::version_conflict("n1".to_string(), 1, 2, None).with_correlation_id("c2");
+        let data = error.data.unwrap();
+        assert_eq!(data["correlation_id"], "c2");
+        assert_eq!(data["actual_version"], 2);
+    }
 }
@@ -0,0 +1,127 @@
+//! Node Appearance - Icon, Accent Color and Cover
+//!
+//! Presentation metadata shared by every node type. It is stored under its
+//! own `appearance` properties namespace, so it survives type changes, and is
+//! passed through to API clients alongside the flattened type properties
+//! (see `flatten_properties_for_api`).
+
+use super::ValidationError;
+use crate::i18n::UserMessage;
+use crate::services::ATTACHMENT_URI_SCHEME;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Properties key holding a node's appearance
+pub const APPEARANCE_KEY: &str = "appearance";
+
+/// Longest accepted icon, in chars (emoji ZWJ sequences run to ~10)
+const MAX_ICON_CHARS: usize = 16;
+
+/// Visual identity of a node in lists, links and page headers
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NodeAppearance {
+    /// A single emoji
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+    /// Accent color as `#rgb` or `#rrggbb`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    /// Cover image as an `attachment://<hash>` URI
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cover: Option<String>,
+}
+
+impl NodeAppearance {
+    /// Appearance stored in `properties`, if any
+    pub fn from_properties(properties: &Value) -> Option<Self> {
+        properties
+            .get(APPEARANCE_KEY)
+            .and_then(|value| serde_json::from_value(value.clone()).ok())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.icon.is_none() && self.color.is_none() && self.cover.is_none()
+    }
+
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        if let Some(icon) = &self.icon {
+            let chars = icon.chars().count();
+            if chars == 0 || chars > MAX_ICON_CHARS || icon.chars().any(|c| c.is_ascii()) {
+                return Err(ValidationError::InvalidProperties(
+                    UserMessage::new("INVALID_APPEARANCE_ICON").with_param("value", icon.as_str()),
+                ));
+            }
+        }
+        if let Some(color) = &self.color {
+            let hex = color.strip_prefix('#').unwrap_or_default();
+            if !matches!(hex.len(), 3 | 6) || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+                return Err(ValidationError::InvalidProperties(
+                    UserMessage::new("INVALID_APPEARANCE_COLOR")
+                        .with_param("value", color.as_str()),
+                ));
+            }
+        }
+        if let Some(cover) = &self.cover {
+            let hash = cover
+                .strip_prefix(ATTACHMENT_URI_SCHEME)
+                .unwrap_or_default();
+            if hash.len() != 64 || !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+                return Err(ValidationError::InvalidProperties(
+                    UserMessage::new("INVALID_APPEARANCE_COVER")
+                        .with_param("value", cover.as_str()),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Validate the appearance stored in `properties`, if present
+    pub fn validate_properties(properties: &Value) -> Result<(), ValidationError> {
+        let Some(value) = properties.get(APPEARANCE_KEY) else {
+            return Ok(());
+        };
+        let appearance: Self = serde_json::from_value(value.clone())
+            .map_err(|e| ValidationError::malformed_property(APPEARANCE_KEY, e))?;
+        appearance.validate()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_validate_appearance() {
+        let cover = format!("attachment://{}", "ab".repeat(32));
+        let valid = NodeAppearance {
+            icon: Some("🚀".to_string()),
+            color: Some("#FF8800".to_string()),
+            cover: Some(cover),
+        };
+        assert!(valid.validate().is_ok());
+        assert!(NodeAppearance {
+            icon: Some("👩‍💻".to_string()),
+            color: Some("#f80".to_string()),
+            cover: None,
+        }
+        .validate()
+        .is_ok());
+
+        for invalid in [
+            json!({ "icon": "rocket" }),
+            json!({ "icon": "" }),
+            json!({ "color": "orange" }),
+            json!({ "color": "#12345" }),
+            json!({ "cover": "https://example.com/a.png" }),
+            json!({ "icon": 42 }),
+        ] {
+            assert!(
+                NodeAppearance::validate_properties(&json!({ "appearance": invalid })).is_err(),
+                "{} should be rejected",
+                invalid
+            );
+        }
+        assert!(NodeAppearance::validate_properties(&json!({ "text": {} })).is_ok());
+    }
+}
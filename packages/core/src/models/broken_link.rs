@@ -0,0 +1,31 @@
+//! Broken Links - Mentions Left Behind by Deleted Nodes
+//!
+//! Deleting a node (or purging it from the trash) removes its relationships,
+//! but the `nodespace://` links and `((embeds))` in other nodes' content stay.
+//! Instead of letting them dangle silently, each one is recorded in the
+//! `broken_link` table when its target goes away. A record is dropped when the
+//! link is re-pointed or turned into plain text
+//! (`NodeService::relink_broken_link`), when the referring content no longer
+//! holds it, or together with the referring node.
+//!
+//! Date nodes are never recorded: they are recreated when a link to them is
+//! opened.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// A link whose target was deleted
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BrokenLink {
+    pub id: String,
+    /// Node whose content holds the link
+    pub source_id: String,
+    /// ID of the deleted node
+    pub target_id: String,
+    /// Title of the deleted node, used when the link is turned into text
+    pub target_title: String,
+    /// `mentions` or `embeds`
+    pub link_type: String,
+    pub detected_at: DateTime<Utc>,
+}
@@ -0,0 +1,232 @@
+//! Child Views - Saved Filter, Sort and Grouping of a Node's Children
+//!
+//! Any node can carry named views of its direct children, e.g. a project
+//! with "Open tasks by priority" and "All notes by date". Views are stored as
+//! a list under the type-independent `views` properties namespace, so they
+//! survive type changes, and run server-side through the query service: a
+//! view's filters and sorts are those of a saved query, restricted to the
+//! node's children (see `ChildViewService`).
+
+use super::{Node, ValidationError};
+use crate::i18n::UserMessage;
+use crate::services::{
+    FilterOperator, FilterType, QueryDefinition, QueryFilter, RelationshipType, SortConfig,
+    SortDirection,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Properties key holding a node's child views
+pub const VIEWS_KEY: &str = "views";
+
+/// A saved view of a node's children
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChildView {
+    pub id: String,
+    pub name: String,
+    /// Node type of the children shown, `*` for all
+    #[serde(default = "any_type")]
+    pub target_type: String,
+    #[serde(default)]
+    pub filters: Vec<QueryFilter>,
+    /// Sort order; children keep their outline order when empty
+    #[serde(default)]
+    pub sorting: Vec<SortConfig>,
+    /// Field whose value groups the children (`node_type`, a metadata field
+    /// or a property of the child's type); ungrouped when absent
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group_by: Option<String>,
+}
+
+/// One group of a view's children
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChildViewGroup {
+    /// Value of the `groupBy` field, `null` for children without one (and
+    /// for the single group of an ungrouped view)
+    pub key: Value,
+    pub nodes: Vec<Node>,
+}
+
+fn any_type() -> String {
+    "*".to_string()
+}
+
+impl ChildView {
+    /// Views stored in `properties`, in their saved order
+    pub fn from_properties(properties: &Value) -> Vec<Self> {
+        properties
+            .get(VIEWS_KEY)
+            .and_then(|value| serde_json::from_value(value.clone()).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        if self.id.trim().is_empty() || self.name.trim().is_empty() {
+            return Err(ValidationError::InvalidProperties(UserMessage::new(
+                "CHILD_VIEW_INCOMPLETE",
+            )));
+        }
+        if self.filters.iter().any(|filter| {
+            filter.filter_type == FilterType::Relationship && filter.node_id.is_none()
+        }) {
+            return Err(ValidationError::InvalidProperties(
+                UserMessage::new("RELATIONSHIP_FILTER_WITHOUT_NODE")
+                    .with_param("view", self.name.as_str()),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Validate the views stored in `properties`, if present
+    pub fn validate_properties(properties: &Value) -> Result<(), ValidationError> {
+        let Some(value) = properties.get(VIEWS_KEY) else {
+            return Ok(());
+        };
+        let views: Vec<Self> = serde_json::from_value(value.clone())
+            .map_err(|e| ValidationError::malformed_property(VIEWS_KEY, e))?;
+        for (index, view) in views.iter().enumerate() {
+            view.validate()?;
+            if views[..index].iter().any(|other| other.id == view.id) {
+                return Err(ValidationError::InvalidProperties(
+                    UserMessage::new("DUPLICATE_CHILD_VIEW").with_param("id", view.id.as_str()),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// The view as a query over the children of `parent_id`
+    pub fn to_query(&self, parent_id: &str) -> QueryDefinition {
+        let mut filters = vec![QueryFilter {
+            filter_type: FilterType::Relationship,
+            operator: FilterOperator::Equals,
+            property: None,
+            value: None,
+            case_sensitive: None,
+            relationship_type: Some(RelationshipType::Children),
+            node_id: Some(parent_id.to_string()),
+            edge_filters: None,
+            max_depth: None,
+        }];
+        filters.extend(self.filters.iter().cloned());
+        let sorting = if self.sorting.is_empty() {
+            vec![SortConfig {
+                field: "edge.order".to_string(),
+                direction: SortDirection::Ascending,
+            }]
+        } else {
+            self.sorting.clone()
+        };
+        QueryDefinition {
+            target_type: self.target_type.clone(),
+            filters,
+            sorting: Some(sorting),
+            limit: None,
+            offset: None,
+        }
+    }
+
+    /// Split `nodes` by the `groupBy` field, keeping their order within and
+    /// across groups (groups appear where their first node does)
+    pub fn group(&self, nodes: Vec<Node>) -> Vec<ChildViewGroup> {
+        let Some(field) = &self.group_by else {
+            return vec![ChildViewGroup {
+                key: Value::Null,
+                nodes,
+            }];
+        };
+        let mut groups: Vec<ChildViewGroup> = Vec::new();
+        for node in nodes {
+            let key = group_key(&node, field);
+            match groups.iter_mut().find(|group| group.key == key) {
+                Some(group) => group.nodes.push(node),
+                None => groups.push(ChildViewGroup {
+                    key,
+                    nodes: vec![node],
+                }),
+            }
+        }
+        groups
+    }
+}
+
+/// Value of `field` on `node`: metadata, then the type's namespace, then flat
+fn group_key(node: &Node, field: &str) -> Value {
+    match field {
+        "node_type" => Value::String(node.node_type.clone()),
+        "created_at" => Value::String(node.created_at.date_naive().to_string()),
+        "modified_at" => Value::String(node.modified_at.date_naive().to_string()),
+        _ => node
+            .properties
+            .get(&node.node_type)
+            .and_then(|namespace| namespace.get(field))
+            .or_else(|| node.properties.get(field))
+            .cloned()
+            .unwrap_or(Value::Null),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn view(value: Value) -> ChildView {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_group_keeps_order_and_collects_missing_values() {
+        let open_tasks = view(json!({
+            "id": "open",
+            "name": "Open tasks by priority",
+            "targetType": "task",
+            "groupBy": "priority",
+        }));
+        let task = |content: &str, priority: Value| {
+            Node::new(
+                "task".to_string(),
+                content.to_string(),
+                json!({ "task": { "status": "open", "priority": priority } }),
+            )
+        };
+        let groups = open_tasks.group(vec![
+            task("Ship", json!("high")),
+            task("Tidy", Value::Null),
+            task("Fix", json!("high")),
+        ]);
+        let summary: Vec<(Value, Vec<&str>)> = groups
+            .iter()
+            .map(|g| {
+                let contents = g.nodes.iter().map(|n| n.content.as_str()).collect();
+                (g.key.clone(), contents)
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (json!("high"), vec!["Ship", "Fix"]),
+                (Value::Null, vec!["Tidy"]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_properties_rejects_duplicate_ids() {
+        let views = json!({
+            "views": [
+                { "id": "a", "name": "All notes" },
+                { "id": "a", "name": "Again" },
+            ]
+        });
+        assert!(ChildView::validate_properties(&views).is_err());
+        assert!(ChildView::validate_properties(&json!({})).is_ok());
+
+        let query = view(json!({ "id": "a", "name": "All" })).to_query("project");
+        assert_eq!(query.target_type, "*");
+        assert_eq!(query.filters[0].node_id.as_deref(), Some("project"));
+        assert_eq!(query.sorting.unwrap()[0].field, "edge.order");
+    }
+}
@@ -3,6 +3,10 @@
 //! Provides ergonomic access to collection nodes while maintaining the universal Node
 //! storage model. Collections are organizational containers with globally unique names.
 //!
+//! A *smart* collection stores a [`QueryDefinition`] in its `query` property
+//! instead of having member_of edges; its members are the query's results
+//! (see `SmartCollectionService`).
+//!
 //! # Examples
 //!
 //! ```rust
@@ -23,7 +27,11 @@
 //! ```
 
 use crate::models::{Node, ValidationError};
-use serde_json::json;
+use crate::services::QueryDefinition;
+use serde_json::{json, Value};
+
+/// Property of a smart collection holding its query
+pub const SMART_QUERY_FIELD: &str = "query";
 
 /// Type-safe wrapper for collection nodes
 ///
@@ -67,10 +75,10 @@ impl CollectionNode {
     /// ```
     pub fn from_node(node: Node) -> Result<Self, ValidationError> {
         if node.node_type != "collection" {
-            return Err(ValidationError::InvalidNodeType(format!(
-                "Expected 'collection', got '{}'",
-                node.node_type
-            )));
+            return Err(ValidationError::invalid_node_type(
+                "collection",
+                node.node_type,
+            ));
         }
         Ok(Self { node })
     }
@@ -103,6 +111,39 @@ impl CollectionNode {
         &self.node.id
     }
 
+    /// Query defining the members of a smart collection, `None` for a
+    /// collection with explicit members
+    ///
+    /// An unreadable query also gives `None`; validation keeps them out.
+    pub fn smart_query(&self) -> Option<QueryDefinition> {
+        let value = Self::smart_query_value(&self.node)?;
+        serde_json::from_value(value.clone()).ok()
+    }
+
+    /// Whether membership comes from a query rather than member_of edges
+    pub fn is_smart(&self) -> bool {
+        Self::smart_query_value(&self.node).is_some()
+    }
+
+    /// Check that a smart collection's query is a valid [`QueryDefinition`]
+    pub fn validate_smart_query(node: &Node) -> Result<(), ValidationError> {
+        let Some(value) = Self::smart_query_value(node) else {
+            return Ok(());
+        };
+        serde_json::from_value::<QueryDefinition>(value.clone())
+            .map(|_| ())
+            .map_err(|e| ValidationError::malformed_property("smart collection query", e))
+    }
+
+    /// The stored query, in the collection namespace or flat
+    fn smart_query_value(node: &Node) -> Option<&Value> {
+        node.properties
+            .get("collection")
+            .and_then(|namespace| namespace.get(SMART_QUERY_FIELD))
+            .or_else(|| node.properties.get(SMART_QUERY_FIELD))
+            .filter(|value| !value.is_null())
+    }
+
     /// Get a reference to the underlying Node
     pub fn as_node(&self) -> &Node {
         &self.node
@@ -166,6 +207,30 @@ mod tests {
         assert_eq!(collection.as_node().node_type, "collection");
     }
 
+    #[test]
+    fn test_smart_query() {
+        let plain = CollectionNode::builder("Reading".to_string()).build();
+        assert!(!plain.is_smart());
+        assert!(plain.smart_query().is_none());
+
+        let node = Node::new(
+            "collection".to_string(),
+            "Inbox".to_string(),
+            json!({ "collection": { "query": { "targetType": "text", "filters": [] } } }),
+        );
+        assert!(CollectionNode::validate_smart_query(&node).is_ok());
+        let inbox = CollectionNode::from_node(node).unwrap();
+        assert!(inbox.is_smart());
+        assert_eq!(inbox.smart_query().unwrap().target_type, "text");
+
+        let broken = Node::new(
+            "collection".to_string(),
+            "Broken".to_string(),
+            json!({ "query": { "filters": "all" } }),
+        );
+        assert!(CollectionNode::validate_smart_query(&broken).is_err());
+    }
+
     #[test]
     fn test_collection_node_into_node() {
         let collection = CollectionNode::builder("test".to_string()).build();
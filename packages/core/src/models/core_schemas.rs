@@ -8,16 +8,25 @@
 //! - **task** - Task tracking with status, priority, dates
 //! - **text** - Plain text content
 //! - **date** - Daily note containers
+//! - **week**, **month**, **quarter** - Period containers that `includes` their date nodes
 //! - **header** - Markdown headers (h1-h6)
 //! - **code-block** - Code blocks with syntax highlighting
 //! - **quote-block** - Blockquotes for citations
 //! - **ordered-list** - Numbered list items
+//! - **script** - Sandboxed lifecycle hook scripts
+//! - **project** - Task containers with progress rollups
+//! - **person** - Contacts with organization, phone and an interaction log
+//! - **highlight** - Excerpts of attachments and bookmarks, with their position
+//! - **reference** - Bibliography entries; text nodes cite them with `cite` edges
 //!
 //! ## Usage
 //!
 //! Call `get_core_schemas()` to get all core schema definitions.
 
-use crate::models::schema::{EnumValue, SchemaField, SchemaProtectionLevel};
+use crate::models::schema::{
+    EdgeField, EnumValue, RelationshipCardinality, RelationshipDirection, SchemaField,
+    SchemaProtectionLevel, SchemaRelationship,
+};
 use crate::models::SchemaNode;
 use chrono::Utc;
 
@@ -48,23 +57,28 @@ pub fn get_core_schemas() -> Vec<SchemaNode> {
                         EnumValue {
                             value: "open".to_string(),
                             label: "Open".to_string(),
+                            deprecated: None,
                         },
                         EnumValue {
                             value: "in_progress".to_string(),
                             label: "In Progress".to_string(),
+                            deprecated: None,
                         },
                         EnumValue {
                             value: "done".to_string(),
                             label: "Done".to_string(),
+                            deprecated: None,
                         },
                         EnumValue {
                             value: "cancelled".to_string(),
                             label: "Cancelled".to_string(),
+                            deprecated: None,
                         },
                     ]),
                     user_values: Some(vec![]),
                     indexed: true,
                     required: Some(true),
+                    recommended: None,
                     extensible: Some(true),
                     default: Some(serde_json::json!("open")),
                     description: Some("Task status".to_string()),
@@ -80,19 +94,23 @@ pub fn get_core_schemas() -> Vec<SchemaNode> {
                         EnumValue {
                             value: "low".to_string(),
                             label: "Low".to_string(),
+                            deprecated: None,
                         },
                         EnumValue {
                             value: "medium".to_string(),
                             label: "Medium".to_string(),
+                            deprecated: None,
                         },
                         EnumValue {
                             value: "high".to_string(),
                             label: "High".to_string(),
+                            deprecated: None,
                         },
                     ]),
                     user_values: Some(vec![]),
                     indexed: true,
                     required: Some(false),
+                    recommended: None,
                     extensible: Some(true),
                     default: None,
                     description: Some("Task priority".to_string()),
@@ -108,6 +126,7 @@ pub fn get_core_schemas() -> Vec<SchemaNode> {
                     user_values: None,
                     indexed: true,
                     required: Some(false),
+                    recommended: None,
                     extensible: None,
                     default: None,
                     description: Some("Due date".to_string()),
@@ -123,6 +142,7 @@ pub fn get_core_schemas() -> Vec<SchemaNode> {
                     user_values: None,
                     indexed: false,
                     required: Some(false),
+                    recommended: None,
                     extensible: None,
                     default: None,
                     description: Some("Started at".to_string()),
@@ -138,6 +158,7 @@ pub fn get_core_schemas() -> Vec<SchemaNode> {
                     user_values: None,
                     indexed: false,
                     required: Some(false),
+                    recommended: None,
                     extensible: None,
                     default: None,
                     description: Some("Completed at".to_string()),
@@ -153,6 +174,7 @@ pub fn get_core_schemas() -> Vec<SchemaNode> {
                     user_values: None,
                     indexed: true,
                     required: Some(false),
+                    recommended: None,
                     extensible: None,
                     default: None,
                     description: Some("Assignee".to_string()),
@@ -160,10 +182,59 @@ pub fn get_core_schemas() -> Vec<SchemaNode> {
                     fields: None,
                     item_fields: None,
                 },
+                SchemaField {
+                    name: "time_sessions".to_string(),
+                    field_type: "array".to_string(),
+                    protection: SchemaProtectionLevel::User,
+                    core_values: None,
+                    user_values: None,
+                    indexed: false,
+                    required: Some(false),
+                    recommended: None,
+                    extensible: None,
+                    default: None,
+                    description: Some("Tracked time sessions".to_string()),
+                    item_type: Some("object".to_string()),
+                    fields: None,
+                    item_fields: Some(vec![
+                        SchemaField {
+                            name: "started_at".to_string(),
+                            field_type: "date".to_string(),
+                            protection: SchemaProtectionLevel::User,
+                            core_values: None,
+                            user_values: None,
+                            indexed: false,
+                            required: Some(true),
+                            recommended: None,
+                            extensible: None,
+                            default: None,
+                            description: Some("Session start".to_string()),
+                            item_type: None,
+                            fields: None,
+                            item_fields: None,
+                        },
+                        SchemaField {
+                            name: "ended_at".to_string(),
+                            field_type: "date".to_string(),
+                            protection: SchemaProtectionLevel::User,
+                            core_values: None,
+                            user_values: None,
+                            indexed: false,
+                            required: Some(false),
+                            recommended: None,
+                            extensible: None,
+                            default: None,
+                            description: Some("Session end (unset while running)".to_string()),
+                            item_type: None,
+                            fields: None,
+                            item_fields: None,
+                        },
+                    ]),
+                },
             ],
             relationships: vec![],
         },
-        // Text schema - plain text content (no extra fields)
+        // Text schema - plain text content (no extra fields), may cite references
         SchemaNode {
             id: "text".to_string(),
             content: "Text".to_string(),
@@ -174,7 +245,18 @@ pub fn get_core_schemas() -> Vec<SchemaNode> {
             schema_version: 1,
             description: "Plain text content".to_string(),
             fields: vec![],
-            relationships: vec![],
+            relationships: vec![SchemaRelationship {
+                name: "cite".to_string(),
+                target_type: Some("reference".to_string()),
+                direction: RelationshipDirection::Out,
+                cardinality: RelationshipCardinality::Many,
+                required: None,
+                reverse_name: Some("cited_by".to_string()),
+                reverse_cardinality: Some(RelationshipCardinality::Many),
+                edge_table: None,
+                edge_fields: None,
+                description: Some("Works this note cites".to_string()),
+            }],
         },
         // Date schema - daily note containers (no extra fields)
         SchemaNode {
@@ -189,6 +271,78 @@ pub fn get_core_schemas() -> Vec<SchemaNode> {
             fields: vec![],
             relationships: vec![],
         },
+        // Week schema - period container linked to its date nodes
+        SchemaNode {
+            id: "week".to_string(),
+            content: "Week".to_string(),
+            version: 1,
+            created_at: now,
+            modified_at: now,
+            is_core: true,
+            schema_version: 1,
+            description: "Weekly review container (ISO week, e.g. 2025-W03)".to_string(),
+            fields: vec![],
+            relationships: vec![SchemaRelationship {
+                name: "includes".to_string(),
+                target_type: Some("date".to_string()),
+                direction: RelationshipDirection::Out,
+                cardinality: RelationshipCardinality::Many,
+                required: None,
+                reverse_name: Some("in_period".to_string()),
+                reverse_cardinality: Some(RelationshipCardinality::Many),
+                edge_table: None,
+                edge_fields: None,
+                description: Some("Days of the week".to_string()),
+            }],
+        },
+        // Month schema - period container linked to its date nodes
+        SchemaNode {
+            id: "month".to_string(),
+            content: "Month".to_string(),
+            version: 1,
+            created_at: now,
+            modified_at: now,
+            is_core: true,
+            schema_version: 1,
+            description: "Monthly rollup container (e.g. 2025-05)".to_string(),
+            fields: vec![],
+            relationships: vec![SchemaRelationship {
+                name: "includes".to_string(),
+                target_type: Some("date".to_string()),
+                direction: RelationshipDirection::Out,
+                cardinality: RelationshipCardinality::Many,
+                required: None,
+                reverse_name: Some("in_period".to_string()),
+                reverse_cardinality: Some(RelationshipCardinality::Many),
+                edge_table: None,
+                edge_fields: None,
+                description: Some("Days of the month".to_string()),
+            }],
+        },
+        // Quarter schema - period container linked to its date nodes
+        SchemaNode {
+            id: "quarter".to_string(),
+            content: "Quarter".to_string(),
+            version: 1,
+            created_at: now,
+            modified_at: now,
+            is_core: true,
+            schema_version: 1,
+            description: "Quarterly rollup container (e.g. 2025-Q2)".to_string(),
+            fields: vec![],
+            relationships: vec![SchemaRelationship {
+                name: "includes".to_string(),
+                target_type: Some("date".to_string()),
+                direction: RelationshipDirection::Out,
+                cardinality: RelationshipCardinality::Many,
+                required: None,
+                reverse_name: Some("in_period".to_string()),
+                reverse_cardinality: Some(RelationshipCardinality::Many),
+                edge_table: None,
+                edge_fields: None,
+                description: Some("Days of the quarter".to_string()),
+            }],
+        },
         // Header schema - markdown headers (no extra fields)
         SchemaNode {
             id: "header".to_string(),
@@ -286,6 +440,7 @@ pub fn get_core_schemas() -> Vec<SchemaNode> {
                     user_values: None,
                     indexed: true,
                     required: Some(true),
+                    recommended: None,
                     extensible: None,
                     default: Some(serde_json::json!("*")),
                     description: Some("Target node type to query (* for all)".to_string()),
@@ -301,6 +456,7 @@ pub fn get_core_schemas() -> Vec<SchemaNode> {
                     user_values: None,
                     indexed: false,
                     required: Some(true),
+                    recommended: None,
                     extensible: None,
                     default: Some(serde_json::json!([])),
                     description: Some("Filter conditions array".to_string()),
@@ -316,6 +472,7 @@ pub fn get_core_schemas() -> Vec<SchemaNode> {
                     user_values: None,
                     indexed: false,
                     required: Some(false),
+                    recommended: None,
                     extensible: None,
                     default: None,
                     description: Some("Sorting configuration array".to_string()),
@@ -331,6 +488,7 @@ pub fn get_core_schemas() -> Vec<SchemaNode> {
                     user_values: None,
                     indexed: false,
                     required: Some(false),
+                    recommended: None,
                     extensible: None,
                     default: Some(serde_json::json!(50)),
                     description: Some("Result limit".to_string()),
@@ -346,15 +504,18 @@ pub fn get_core_schemas() -> Vec<SchemaNode> {
                         EnumValue {
                             value: "ai".to_string(),
                             label: "AI Generated".to_string(),
+                            deprecated: None,
                         },
                         EnumValue {
                             value: "user".to_string(),
                             label: "User Created".to_string(),
+                            deprecated: None,
                         },
                     ]),
                     user_values: Some(vec![]),
                     indexed: true,
                     required: Some(true),
+                    recommended: None,
                     extensible: Some(false),
                     default: Some(serde_json::json!("user")),
                     description: Some("Who created the query".to_string()),
@@ -370,6 +531,7 @@ pub fn get_core_schemas() -> Vec<SchemaNode> {
                     user_values: None,
                     indexed: true,
                     required: Some(false),
+                    recommended: None,
                     extensible: None,
                     default: None,
                     description: Some("Parent chat ID for AI-generated queries".to_string()),
@@ -385,6 +547,7 @@ pub fn get_core_schemas() -> Vec<SchemaNode> {
                     user_values: None,
                     indexed: false,
                     required: Some(false),
+                    recommended: None,
                     extensible: None,
                     default: Some(serde_json::json!(0)),
                     description: Some("Number of times query has been executed".to_string()),
@@ -400,6 +563,7 @@ pub fn get_core_schemas() -> Vec<SchemaNode> {
                     user_values: None,
                     indexed: false,
                     required: Some(false),
+                    recommended: None,
                     extensible: None,
                     default: None,
                     description: Some("Timestamp of last execution".to_string()),
@@ -410,75 +574,954 @@ pub fn get_core_schemas() -> Vec<SchemaNode> {
             ],
             relationships: vec![],
         },
-    ]
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_get_core_schemas_returns_all_ten() {
-        let schemas = get_core_schemas();
-        assert_eq!(schemas.len(), 10);
-    }
-
-    #[test]
-    fn test_all_schemas_are_core() {
-        let schemas = get_core_schemas();
-        for schema in &schemas {
-            assert!(schema.is_core, "Schema {} should be core", schema.id);
-        }
-    }
-
-    #[test]
-    fn test_task_schema_has_fields() {
-        let schemas = get_core_schemas();
-        let task = schemas.iter().find(|s| s.id == "task").unwrap();
-
-        assert_eq!(task.fields.len(), 6);
-        assert!(task.get_field("status").is_some());
-        assert!(task.get_field("priority").is_some());
-        assert!(task.get_field("due_date").is_some());
-    }
-
-    #[test]
-    fn test_simple_schemas_have_no_fields() {
-        let schemas = get_core_schemas();
-
-        for id in &[
-            "text",
-            "date",
-            "header",
-            "code-block",
-            "quote-block",
-            "ordered-list",
-            "collection",
-            "checkbox",
-        ] {
-            let schema = schemas.iter().find(|s| s.id == *id).unwrap();
-            assert!(
-                schema.fields.is_empty(),
-                "Schema {} should have no fields",
-                id
-            );
-        }
-    }
-
-    #[test]
-    fn test_query_schema_has_fields() {
-        let schemas = get_core_schemas();
-        let query = schemas.iter().find(|s| s.id == "query").unwrap();
-
-        assert_eq!(query.fields.len(), 8);
-        assert!(query.get_field("target_type").is_some());
-        assert!(query.get_field("filters").is_some());
-        assert!(query.get_field("sorting").is_some());
-        assert!(query.get_field("limit").is_some());
-        assert!(query.get_field("generated_by").is_some());
-        assert!(query.get_field("generator_context").is_some());
-        assert!(query.get_field("execution_count").is_some());
-        assert!(query.get_field("last_executed").is_some());
+        // Script schema - sandboxed lifecycle hooks (source stored in content)
+        SchemaNode {
+            id: "script".to_string(),
+            content: "Script".to_string(),
+            version: 1,
+            created_at: now,
+            modified_at: now,
+            is_core: true,
+            schema_version: 1,
+            description: "User script bound to a node lifecycle hook".to_string(),
+            fields: vec![
+                SchemaField {
+                    name: "target_type".to_string(),
+                    field_type: "text".to_string(),
+                    protection: SchemaProtectionLevel::Core,
+                    core_values: None,
+                    user_values: None,
+                    indexed: true,
+                    required: Some(true),
+                    recommended: None,
+                    extensible: None,
+                    default: None,
+                    description: Some("Node type whose lifecycle triggers the script".to_string()),
+                    item_type: None,
+                    fields: None,
+                    item_fields: None,
+                },
+                SchemaField {
+                    name: "hook".to_string(),
+                    field_type: "enum".to_string(),
+                    protection: SchemaProtectionLevel::Core,
+                    core_values: Some(vec![
+                        EnumValue {
+                            value: "on_create".to_string(),
+                            label: "On Create".to_string(),
+                            deprecated: None,
+                        },
+                        EnumValue {
+                            value: "on_update".to_string(),
+                            label: "On Update".to_string(),
+                            deprecated: None,
+                        },
+                    ]),
+                    user_values: Some(vec![]),
+                    indexed: true,
+                    required: Some(true),
+                    recommended: None,
+                    extensible: Some(false),
+                    default: Some(serde_json::json!("on_create")),
+                    description: Some("Lifecycle hook the script runs on".to_string()),
+                    item_type: None,
+                    fields: None,
+                    item_fields: None,
+                },
+                SchemaField {
+                    name: "enabled".to_string(),
+                    field_type: "boolean".to_string(),
+                    protection: SchemaProtectionLevel::Core,
+                    core_values: None,
+                    user_values: None,
+                    indexed: false,
+                    required: Some(false),
+                    recommended: None,
+                    extensible: None,
+                    default: Some(serde_json::json!(true)),
+                    description: Some("Whether the script is active".to_string()),
+                    item_type: None,
+                    fields: None,
+                    item_fields: None,
+                },
+            ],
+            relationships: vec![],
+        },
+        // Project schema - progress is rolled up from descendant tasks, not stored
+        SchemaNode {
+            id: "project".to_string(),
+            content: "Project".to_string(),
+            version: 1,
+            created_at: now,
+            modified_at: now,
+            is_core: true,
+            schema_version: 1,
+            description: "Project grouping tasks beneath it".to_string(),
+            fields: vec![
+                SchemaField {
+                    name: "status".to_string(),
+                    field_type: "enum".to_string(),
+                    protection: SchemaProtectionLevel::Core,
+                    core_values: Some(vec![
+                        EnumValue {
+                            value: "active".to_string(),
+                            label: "Active".to_string(),
+                            deprecated: None,
+                        },
+                        EnumValue {
+                            value: "on_hold".to_string(),
+                            label: "On Hold".to_string(),
+                            deprecated: None,
+                        },
+                        EnumValue {
+                            value: "completed".to_string(),
+                            label: "Completed".to_string(),
+                            deprecated: None,
+                        },
+                        EnumValue {
+                            value: "archived".to_string(),
+                            label: "Archived".to_string(),
+                            deprecated: None,
+                        },
+                    ]),
+                    user_values: Some(vec![]),
+                    indexed: true,
+                    required: Some(false),
+                    recommended: None,
+                    extensible: Some(true),
+                    default: Some(serde_json::json!("active")),
+                    description: Some("Project status".to_string()),
+                    item_type: None,
+                    fields: None,
+                    item_fields: None,
+                },
+                SchemaField {
+                    name: "due_date".to_string(),
+                    field_type: "date".to_string(),
+                    protection: SchemaProtectionLevel::User,
+                    core_values: None,
+                    user_values: None,
+                    indexed: true,
+                    required: Some(false),
+                    recommended: None,
+                    extensible: None,
+                    default: None,
+                    description: Some("Target completion date".to_string()),
+                    item_type: None,
+                    fields: None,
+                    item_fields: None,
+                },
+            ],
+            relationships: vec![],
+        },
+        // Goal schema - measured by the key_result nodes beneath it
+        SchemaNode {
+            id: "goal".to_string(),
+            content: "Goal".to_string(),
+            version: 1,
+            created_at: now,
+            modified_at: now,
+            is_core: true,
+            schema_version: 1,
+            description: "Objective measured by key results".to_string(),
+            fields: vec![
+                SchemaField {
+                    name: "status".to_string(),
+                    field_type: "enum".to_string(),
+                    protection: SchemaProtectionLevel::Core,
+                    core_values: Some(vec![
+                        EnumValue {
+                            value: "active".to_string(),
+                            label: "Active".to_string(),
+                            deprecated: None,
+                        },
+                        EnumValue {
+                            value: "achieved".to_string(),
+                            label: "Achieved".to_string(),
+                            deprecated: None,
+                        },
+                        EnumValue {
+                            value: "missed".to_string(),
+                            label: "Missed".to_string(),
+                            deprecated: None,
+                        },
+                        EnumValue {
+                            value: "abandoned".to_string(),
+                            label: "Abandoned".to_string(),
+                            deprecated: None,
+                        },
+                    ]),
+                    user_values: Some(vec![]),
+                    indexed: true,
+                    required: Some(false),
+                    recommended: None,
+                    extensible: Some(false),
+                    default: Some(serde_json::json!("active")),
+                    description: Some("Goal status".to_string()),
+                    item_type: None,
+                    fields: None,
+                    item_fields: None,
+                },
+                SchemaField {
+                    name: "target_date".to_string(),
+                    field_type: "date".to_string(),
+                    protection: SchemaProtectionLevel::User,
+                    core_values: None,
+                    user_values: None,
+                    indexed: true,
+                    required: Some(false),
+                    recommended: None,
+                    extensible: None,
+                    default: None,
+                    description: Some("Date the goal should be reached by".to_string()),
+                    item_type: None,
+                    fields: None,
+                    item_fields: None,
+                },
+            ],
+            relationships: vec![],
+        },
+        // Key result schema - metric source, target and daily progress snapshots
+        SchemaNode {
+            id: "key_result".to_string(),
+            content: "Key Result".to_string(),
+            version: 1,
+            created_at: now,
+            modified_at: now,
+            is_core: true,
+            schema_version: 1,
+            description: "Measurable outcome of a goal".to_string(),
+            fields: vec![
+                SchemaField {
+                    name: "metric_source".to_string(),
+                    field_type: "enum".to_string(),
+                    protection: SchemaProtectionLevel::Core,
+                    core_values: Some(vec![
+                        EnumValue {
+                            value: "manual".to_string(),
+                            label: "Manual".to_string(),
+                            deprecated: None,
+                        },
+                        EnumValue {
+                            value: "property".to_string(),
+                            label: "Node Property".to_string(),
+                            deprecated: None,
+                        },
+                        EnumValue {
+                            value: "query".to_string(),
+                            label: "Query Result Count".to_string(),
+                            deprecated: None,
+                        },
+                    ]),
+                    user_values: Some(vec![]),
+                    indexed: true,
+                    required: Some(true),
+                    recommended: None,
+                    extensible: Some(false),
+                    default: Some(serde_json::json!("manual")),
+                    description: Some("Where the current value comes from".to_string()),
+                    item_type: None,
+                    fields: None,
+                    item_fields: None,
+                },
+                SchemaField {
+                    name: "current_value".to_string(),
+                    field_type: "number".to_string(),
+                    protection: SchemaProtectionLevel::User,
+                    core_values: None,
+                    user_values: None,
+                    indexed: false,
+                    required: Some(false),
+                    recommended: None,
+                    extensible: None,
+                    default: None,
+                    description: Some("Current value (manual source)".to_string()),
+                    item_type: None,
+                    fields: None,
+                    item_fields: None,
+                },
+                SchemaField {
+                    name: "metric_node_id".to_string(),
+                    field_type: "text".to_string(),
+                    protection: SchemaProtectionLevel::User,
+                    core_values: None,
+                    user_values: None,
+                    indexed: false,
+                    required: Some(false),
+                    recommended: None,
+                    extensible: None,
+                    default: None,
+                    description: Some("Node holding the metric (property source)".to_string()),
+                    item_type: None,
+                    fields: None,
+                    item_fields: None,
+                },
+                SchemaField {
+                    name: "metric_property".to_string(),
+                    field_type: "text".to_string(),
+                    protection: SchemaProtectionLevel::User,
+                    core_values: None,
+                    user_values: None,
+                    indexed: false,
+                    required: Some(false),
+                    recommended: None,
+                    extensible: None,
+                    default: None,
+                    description: Some("JSON pointer to the metric in its properties".to_string()),
+                    item_type: None,
+                    fields: None,
+                    item_fields: None,
+                },
+                SchemaField {
+                    name: "query_id".to_string(),
+                    field_type: "text".to_string(),
+                    protection: SchemaProtectionLevel::User,
+                    core_values: None,
+                    user_values: None,
+                    indexed: false,
+                    required: Some(false),
+                    recommended: None,
+                    extensible: None,
+                    default: None,
+                    description: Some("Saved query whose result count is the metric".to_string()),
+                    item_type: None,
+                    fields: None,
+                    item_fields: None,
+                },
+                SchemaField {
+                    name: "start_value".to_string(),
+                    field_type: "number".to_string(),
+                    protection: SchemaProtectionLevel::User,
+                    core_values: None,
+                    user_values: None,
+                    indexed: false,
+                    required: Some(false),
+                    recommended: None,
+                    extensible: None,
+                    default: Some(serde_json::json!(0)),
+                    description: Some("Value progress is measured from".to_string()),
+                    item_type: None,
+                    fields: None,
+                    item_fields: None,
+                },
+                SchemaField {
+                    name: "target_value".to_string(),
+                    field_type: "number".to_string(),
+                    protection: SchemaProtectionLevel::User,
+                    core_values: None,
+                    user_values: None,
+                    indexed: false,
+                    required: Some(true),
+                    recommended: None,
+                    extensible: None,
+                    default: None,
+                    description: Some("Value that completes the key result".to_string()),
+                    item_type: None,
+                    fields: None,
+                    item_fields: None,
+                },
+                SchemaField {
+                    name: "snapshots".to_string(),
+                    field_type: "array".to_string(),
+                    protection: SchemaProtectionLevel::Core,
+                    core_values: None,
+                    user_values: None,
+                    indexed: false,
+                    required: Some(false),
+                    recommended: None,
+                    extensible: None,
+                    default: None,
+                    description: Some("Daily values recorded by goal evaluation".to_string()),
+                    item_type: Some("object".to_string()),
+                    fields: None,
+                    item_fields: Some(vec![
+                        SchemaField {
+                            name: "date".to_string(),
+                            field_type: "date".to_string(),
+                            protection: SchemaProtectionLevel::Core,
+                            core_values: None,
+                            user_values: None,
+                            indexed: false,
+                            required: Some(true),
+                            recommended: None,
+                            extensible: None,
+                            default: None,
+                            description: Some("Day the value was recorded".to_string()),
+                            item_type: None,
+                            fields: None,
+                            item_fields: None,
+                        },
+                        SchemaField {
+                            name: "value".to_string(),
+                            field_type: "number".to_string(),
+                            protection: SchemaProtectionLevel::Core,
+                            core_values: None,
+                            user_values: None,
+                            indexed: false,
+                            required: Some(true),
+                            recommended: None,
+                            extensible: None,
+                            default: None,
+                            description: Some("Metric value on that day".to_string()),
+                            item_type: None,
+                            fields: None,
+                            item_fields: None,
+                        },
+                    ]),
+                },
+            ],
+            relationships: vec![],
+        },
+        // Meeting schema - notes (and action items) are children
+        SchemaNode {
+            id: "meeting".to_string(),
+            content: "Meeting".to_string(),
+            version: 1,
+            created_at: now,
+            modified_at: now,
+            is_core: true,
+            schema_version: 1,
+            description: "Meeting notes with attendees".to_string(),
+            fields: vec![
+                SchemaField {
+                    name: "attendees".to_string(),
+                    field_type: "array".to_string(),
+                    protection: SchemaProtectionLevel::User,
+                    core_values: None,
+                    user_values: None,
+                    indexed: false,
+                    required: Some(false),
+                    recommended: None,
+                    extensible: None,
+                    default: Some(serde_json::json!([])),
+                    description: Some("IDs of the attending person nodes".to_string()),
+                    item_type: Some("text".to_string()),
+                    fields: None,
+                    item_fields: None,
+                },
+                SchemaField {
+                    name: "date".to_string(),
+                    field_type: "date".to_string(),
+                    protection: SchemaProtectionLevel::User,
+                    core_values: None,
+                    user_values: None,
+                    indexed: true,
+                    required: Some(false),
+                    recommended: None,
+                    extensible: None,
+                    default: None,
+                    description: Some("When the meeting takes place".to_string()),
+                    item_type: None,
+                    fields: None,
+                    item_fields: None,
+                },
+                SchemaField {
+                    name: "location".to_string(),
+                    field_type: "text".to_string(),
+                    protection: SchemaProtectionLevel::User,
+                    core_values: None,
+                    user_values: None,
+                    indexed: false,
+                    required: Some(false),
+                    recommended: None,
+                    extensible: None,
+                    default: None,
+                    description: Some("Room, address or call link".to_string()),
+                    item_type: None,
+                    fields: None,
+                    item_fields: None,
+                },
+            ],
+            relationships: vec![],
+        },
+        // Person schema - contact details; interactions are linked notes
+        SchemaNode {
+            id: "person".to_string(),
+            content: "Person".to_string(),
+            version: 1,
+            created_at: now,
+            modified_at: now,
+            is_core: true,
+            schema_version: 1,
+            description: "Contacts with an interaction log".to_string(),
+            fields: vec![
+                SchemaField {
+                    name: "email".to_string(),
+                    field_type: "text".to_string(),
+                    protection: SchemaProtectionLevel::User,
+                    core_values: None,
+                    user_values: None,
+                    indexed: true,
+                    required: Some(false),
+                    recommended: None,
+                    extensible: None,
+                    default: None,
+                    description: Some("Primary email address".to_string()),
+                    item_type: None,
+                    fields: None,
+                    item_fields: None,
+                },
+                SchemaField {
+                    name: "organization".to_string(),
+                    field_type: "text".to_string(),
+                    protection: SchemaProtectionLevel::User,
+                    core_values: None,
+                    user_values: None,
+                    indexed: true,
+                    required: Some(false),
+                    recommended: None,
+                    extensible: None,
+                    default: None,
+                    description: Some("Company or group the person belongs to".to_string()),
+                    item_type: None,
+                    fields: None,
+                    item_fields: None,
+                },
+                SchemaField {
+                    name: "phone".to_string(),
+                    field_type: "text".to_string(),
+                    protection: SchemaProtectionLevel::User,
+                    core_values: None,
+                    user_values: None,
+                    indexed: false,
+                    required: Some(false),
+                    recommended: None,
+                    extensible: None,
+                    default: None,
+                    description: Some("Phone number".to_string()),
+                    item_type: None,
+                    fields: None,
+                    item_fields: None,
+                },
+                SchemaField {
+                    name: "custom".to_string(),
+                    field_type: "object".to_string(),
+                    protection: SchemaProtectionLevel::User,
+                    core_values: None,
+                    user_values: None,
+                    indexed: false,
+                    required: Some(false),
+                    recommended: None,
+                    extensible: None,
+                    default: Some(serde_json::json!({})),
+                    description: Some(
+                        "Free-form contact fields (e.g. linkedin, birthday)".to_string(),
+                    ),
+                    item_type: None,
+                    fields: None,
+                    item_fields: None,
+                },
+            ],
+            relationships: vec![SchemaRelationship {
+                name: "interacted_with".to_string(),
+                target_type: Some("text".to_string()),
+                direction: RelationshipDirection::Out,
+                cardinality: RelationshipCardinality::Many,
+                required: None,
+                reverse_name: None,
+                reverse_cardinality: None,
+                edge_table: None,
+                edge_fields: Some(vec![EdgeField {
+                    name: "date".to_string(),
+                    field_type: "date".to_string(),
+                    indexed: Some(true),
+                    required: None,
+                    default: None,
+                    target_type: None,
+                    description: Some("Day the interaction happened".to_string()),
+                }]),
+                description: Some("Notes logged about interactions with this person".to_string()),
+            }],
+        },
+        // Highlight schema - excerpts of attachments and bookmarks
+        SchemaNode {
+            id: "highlight".to_string(),
+            content: "Highlight".to_string(),
+            version: 1,
+            created_at: now,
+            modified_at: now,
+            is_core: true,
+            schema_version: 1,
+            description: "Annotated excerpts of a source document".to_string(),
+            fields: vec![
+                SchemaField {
+                    name: "page".to_string(),
+                    field_type: "number".to_string(),
+                    protection: SchemaProtectionLevel::User,
+                    core_values: None,
+                    user_values: None,
+                    indexed: false,
+                    required: Some(false),
+                    recommended: None,
+                    extensible: None,
+                    default: None,
+                    description: Some("Page of the source the excerpt is on".to_string()),
+                    item_type: None,
+                    fields: None,
+                    item_fields: None,
+                },
+                SchemaField {
+                    name: "start_offset".to_string(),
+                    field_type: "number".to_string(),
+                    protection: SchemaProtectionLevel::User,
+                    core_values: None,
+                    user_values: None,
+                    indexed: false,
+                    required: Some(false),
+                    recommended: None,
+                    extensible: None,
+                    default: None,
+                    description: Some("Character offset where the excerpt starts".to_string()),
+                    item_type: None,
+                    fields: None,
+                    item_fields: None,
+                },
+                SchemaField {
+                    name: "end_offset".to_string(),
+                    field_type: "number".to_string(),
+                    protection: SchemaProtectionLevel::User,
+                    core_values: None,
+                    user_values: None,
+                    indexed: false,
+                    required: Some(false),
+                    recommended: None,
+                    extensible: None,
+                    default: None,
+                    description: Some("Character offset where the excerpt ends".to_string()),
+                    item_type: None,
+                    fields: None,
+                    item_fields: None,
+                },
+                SchemaField {
+                    name: "color".to_string(),
+                    field_type: "text".to_string(),
+                    protection: SchemaProtectionLevel::User,
+                    core_values: None,
+                    user_values: None,
+                    indexed: false,
+                    required: Some(false),
+                    recommended: None,
+                    extensible: None,
+                    default: None,
+                    description: Some("Highlighter color".to_string()),
+                    item_type: None,
+                    fields: None,
+                    item_fields: None,
+                },
+            ],
+            relationships: vec![SchemaRelationship {
+                name: "highlights".to_string(),
+                target_type: Some("text".to_string()),
+                direction: RelationshipDirection::Out,
+                cardinality: RelationshipCardinality::One,
+                required: None,
+                reverse_name: None,
+                reverse_cardinality: None,
+                edge_table: None,
+                edge_fields: None,
+                description: Some("Source document the excerpt is taken from".to_string()),
+            }],
+        },
+        // Reference schema - bibliography entries, cited from notes
+        SchemaNode {
+            id: "reference".to_string(),
+            content: "Reference".to_string(),
+            version: 1,
+            created_at: now,
+            modified_at: now,
+            is_core: true,
+            schema_version: 1,
+            description: "Citable works (papers, books, talks)".to_string(),
+            fields: vec![
+                SchemaField {
+                    name: "citation_key".to_string(),
+                    field_type: "text".to_string(),
+                    protection: SchemaProtectionLevel::User,
+                    core_values: None,
+                    user_values: None,
+                    indexed: true,
+                    required: Some(false),
+                    recommended: None,
+                    extensible: None,
+                    default: None,
+                    description: Some("BibTeX key, e.g. knuth1974".to_string()),
+                    item_type: None,
+                    fields: None,
+                    item_fields: None,
+                },
+                SchemaField {
+                    name: "entry_type".to_string(),
+                    field_type: "text".to_string(),
+                    protection: SchemaProtectionLevel::User,
+                    core_values: None,
+                    user_values: None,
+                    indexed: false,
+                    required: Some(false),
+                    recommended: None,
+                    extensible: None,
+                    default: None,
+                    description: Some(
+                        "BibTeX entry type (article, book, inproceedings, ...)".to_string(),
+                    ),
+                    item_type: None,
+                    fields: None,
+                    item_fields: None,
+                },
+                SchemaField {
+                    name: "authors".to_string(),
+                    field_type: "array".to_string(),
+                    protection: SchemaProtectionLevel::User,
+                    core_values: None,
+                    user_values: None,
+                    indexed: false,
+                    required: Some(false),
+                    recommended: None,
+                    extensible: None,
+                    default: None,
+                    description: Some("Author names in citation order".to_string()),
+                    item_type: Some("text".to_string()),
+                    fields: None,
+                    item_fields: None,
+                },
+                SchemaField {
+                    name: "year".to_string(),
+                    field_type: "number".to_string(),
+                    protection: SchemaProtectionLevel::User,
+                    core_values: None,
+                    user_values: None,
+                    indexed: true,
+                    required: Some(false),
+                    recommended: None,
+                    extensible: None,
+                    default: None,
+                    description: Some("Publication year".to_string()),
+                    item_type: None,
+                    fields: None,
+                    item_fields: None,
+                },
+                SchemaField {
+                    name: "doi".to_string(),
+                    field_type: "text".to_string(),
+                    protection: SchemaProtectionLevel::User,
+                    core_values: None,
+                    user_values: None,
+                    indexed: true,
+                    required: Some(false),
+                    recommended: None,
+                    extensible: None,
+                    default: None,
+                    description: Some("Digital Object Identifier".to_string()),
+                    item_type: None,
+                    fields: None,
+                    item_fields: None,
+                },
+                SchemaField {
+                    name: "venue".to_string(),
+                    field_type: "text".to_string(),
+                    protection: SchemaProtectionLevel::User,
+                    core_values: None,
+                    user_values: None,
+                    indexed: false,
+                    required: Some(false),
+                    recommended: None,
+                    extensible: None,
+                    default: None,
+                    description: Some("Journal, conference or publisher".to_string()),
+                    item_type: None,
+                    fields: None,
+                    item_fields: None,
+                },
+                SchemaField {
+                    name: "url".to_string(),
+                    field_type: "text".to_string(),
+                    protection: SchemaProtectionLevel::User,
+                    core_values: None,
+                    user_values: None,
+                    indexed: false,
+                    required: Some(false),
+                    recommended: None,
+                    extensible: None,
+                    default: None,
+                    description: Some("Where the work can be read".to_string()),
+                    item_type: None,
+                    fields: None,
+                    item_fields: None,
+                },
+            ],
+            relationships: vec![],
+        },
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_core_schemas_returns_all_twenty_one() {
+        let schemas = get_core_schemas();
+        assert_eq!(schemas.len(), 21);
+    }
+
+    #[test]
+    fn test_all_schemas_are_core() {
+        let schemas = get_core_schemas();
+        for schema in &schemas {
+            assert!(schema.is_core, "Schema {} should be core", schema.id);
+        }
+    }
+
+    #[test]
+    fn test_task_schema_has_fields() {
+        let schemas = get_core_schemas();
+        let task = schemas.iter().find(|s| s.id == "task").unwrap();
+
+        assert_eq!(task.fields.len(), 7);
+        assert!(task.get_field("status").is_some());
+        assert!(task.get_field("priority").is_some());
+        assert!(task.get_field("due_date").is_some());
+        assert!(task.get_field("time_sessions").is_some());
+    }
+
+    #[test]
+    fn test_simple_schemas_have_no_fields() {
+        let schemas = get_core_schemas();
+
+        for id in &[
+            "text",
+            "date",
+            "header",
+            "code-block",
+            "quote-block",
+            "ordered-list",
+            "collection",
+            "checkbox",
+        ] {
+            let schema = schemas.iter().find(|s| s.id == *id).unwrap();
+            assert!(
+                schema.fields.is_empty(),
+                "Schema {} should have no fields",
+                id
+            );
+        }
+    }
+
+    #[test]
+    fn test_query_schema_has_fields() {
+        let schemas = get_core_schemas();
+        let query = schemas.iter().find(|s| s.id == "query").unwrap();
+
+        assert_eq!(query.fields.len(), 8);
+        assert!(query.get_field("target_type").is_some());
+        assert!(query.get_field("filters").is_some());
+        assert!(query.get_field("sorting").is_some());
+        assert!(query.get_field("limit").is_some());
+        assert!(query.get_field("generated_by").is_some());
+        assert!(query.get_field("generator_context").is_some());
+        assert!(query.get_field("execution_count").is_some());
+        assert!(query.get_field("last_executed").is_some());
+    }
+
+    #[test]
+    fn test_script_schema_has_fields() {
+        let schemas = get_core_schemas();
+        let script = schemas.iter().find(|s| s.id == "script").unwrap();
+
+        assert_eq!(script.fields.len(), 3);
+        assert!(script.get_field("target_type").is_some());
+        assert!(script.get_field("hook").is_some());
+        assert!(script.get_field("enabled").is_some());
+    }
+
+    #[test]
+    fn test_project_schema_has_fields() {
+        let schemas = get_core_schemas();
+        let project = schemas.iter().find(|s| s.id == "project").unwrap();
+
+        assert_eq!(project.fields.len(), 2);
+        assert!(project.get_field("status").is_some());
+        assert!(project.get_field("due_date").is_some());
+    }
+
+    #[test]
+    fn test_goal_and_key_result_schemas_have_fields() {
+        let schemas = get_core_schemas();
+        let goal = schemas.iter().find(|s| s.id == "goal").unwrap();
+        assert_eq!(goal.fields.len(), 2);
+
+        let key_result = schemas.iter().find(|s| s.id == "key_result").unwrap();
+        assert_eq!(key_result.fields.len(), 8);
+        assert_eq!(
+            key_result.get_field("target_value").unwrap().required,
+            Some(true)
+        );
+        assert!(key_result.get_field("snapshots").is_some());
+    }
+
+    #[test]
+    fn test_meeting_schema_has_fields() {
+        let schemas = get_core_schemas();
+        let meeting = schemas.iter().find(|s| s.id == "meeting").unwrap();
+
+        assert_eq!(meeting.fields.len(), 3);
+        assert!(meeting.get_field("attendees").is_some());
+        assert!(meeting.get_field("date").is_some());
+        assert!(meeting.get_field("location").is_some());
+    }
+
+    #[test]
+    fn test_person_schema_has_fields_and_interactions() {
+        let schemas = get_core_schemas();
+        let person = schemas.iter().find(|s| s.id == "person").unwrap();
+
+        assert_eq!(person.fields.len(), 4);
+        assert!(person.get_field("organization").is_some());
+        assert!(person.get_field("phone").is_some());
+        assert!(person.get_field("custom").is_some());
+        assert_eq!(person.relationships.len(), 1);
+        assert_eq!(person.relationships[0].name, "interacted_with");
+    }
+
+    #[test]
+    fn test_highlight_schema_links_to_source() {
+        let schemas = get_core_schemas();
+        let highlight = schemas.iter().find(|s| s.id == "highlight").unwrap();
+
+        assert_eq!(highlight.fields.len(), 4);
+        assert!(highlight.get_field("page").is_some());
+        assert!(highlight.get_field("start_offset").is_some());
+        assert!(highlight.get_field("end_offset").is_some());
+        assert_eq!(highlight.relationships[0].name, "highlights");
+        assert_eq!(
+            highlight.relationships[0].cardinality,
+            RelationshipCardinality::One
+        );
+    }
+
+    #[test]
+    fn test_reference_schema_is_citable_from_text() {
+        let schemas = get_core_schemas();
+        let reference = schemas.iter().find(|s| s.id == "reference").unwrap();
+        assert_eq!(reference.fields.len(), 7);
+        assert!(reference.get_field("authors").is_some());
+        assert!(reference.get_field("doi").is_some());
+
+        let text = schemas.iter().find(|s| s.id == "text").unwrap();
+        assert_eq!(text.relationships[0].name, "cite");
+        assert_eq!(
+            text.relationships[0].target_type.as_deref(),
+            Some("reference")
+        );
+    }
+
+    #[test]
+    fn test_period_schemas_include_dates() {
+        let schemas = get_core_schemas();
+        for id in crate::models::PERIOD_NODE_TYPES {
+            let period = schemas.iter().find(|s| s.id == id).unwrap();
+            assert_eq!(period.relationships[0].name, "includes");
+            assert_eq!(period.relationships[0].target_type.as_deref(), Some("date"));
+        }
     }
 
     #[test]
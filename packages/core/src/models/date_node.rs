@@ -77,18 +77,12 @@ impl DateNode {
     /// ```
     pub fn from_node(node: Node) -> Result<Self, ValidationError> {
         if node.node_type != "date" {
-            return Err(ValidationError::InvalidNodeType(format!(
-                "Expected 'date', got '{}'",
-                node.node_type
-            )));
+            return Err(ValidationError::invalid_node_type("date", node.node_type));
         }
 
         // Validate ID format (YYYY-MM-DD)
         if !Self::is_valid_date_id(&node.id) {
-            return Err(ValidationError::InvalidId(format!(
-                "Date node ID must be in YYYY-MM-DD format, got '{}'",
-                node.id
-            )));
+            return Err(ValidationError::invalid_id(node.id, "YYYY-MM-DD"));
         }
 
         Ok(Self { node })
@@ -24,7 +24,7 @@ mod tests {
         );
         let result = DateNode::from_node(wrong_type);
         assert!(result.is_err());
-        assert!(result.unwrap_err().to_string().contains("Expected 'date'"));
+        assert!(result.unwrap_err().to_string().contains("expected 'date'"));
     }
 
     #[test]
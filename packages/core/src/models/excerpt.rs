@@ -0,0 +1,135 @@
+//! Root Excerpts - Plain-Text Previews of Documents
+//!
+//! Result lists (search, @mention autocomplete) show a line of what a root
+//! contains. Rather than fetching children for every hit, each root keeps an
+//! excerpt built from its first meaningful children, stored as
+//! `{ "excerpt": { "text": "..." } }` in its properties. Like `appearance` it
+//! sits outside the type namespaces and is passed through to API clients.
+//!
+//! The excerpt is maintained by `RootExcerptService`; nothing else writes it.
+//! When a local generation model is available the service stores a summary of
+//! [`summary_source`] instead of the leading children.
+
+use super::Node;
+use crate::utils::{strip_markdown, truncate_chars};
+use serde_json::Value;
+
+/// Properties key holding a root's excerpt
+pub const EXCERPT_KEY: &str = "excerpt";
+
+/// Children contributing to an excerpt
+pub const EXCERPT_MAX_CHILDREN: usize = 3;
+
+/// Longest excerpt in characters, before the ellipsis
+pub const EXCERPT_MAX_CHARS: usize = 200;
+
+/// Longest text handed to the summarizer, in characters
+pub const SUMMARY_SOURCE_MAX_CHARS: usize = 4000;
+
+/// Separator between the contributions of consecutive children
+const SEPARATOR: &str = " · ";
+
+/// Excerpt of a root with `children` (in order), `None` when none has text
+///
+/// Each child contributes its markdown-stripped content on a single line.
+/// Children without any letters or digits (blank lines, rules, lone
+/// punctuation) are skipped.
+pub fn build_excerpt<'a>(children: impl IntoIterator<Item = &'a Node>) -> Option<String> {
+    let parts: Vec<String> = children
+        .into_iter()
+        .filter_map(child_line)
+        .take(EXCERPT_MAX_CHILDREN)
+        .collect();
+    if parts.is_empty() {
+        return None;
+    }
+    Some(truncate_chars(&parts.join(SEPARATOR), EXCERPT_MAX_CHARS))
+}
+
+/// Text of all meaningful `children`, one per line, for a generated excerpt
+///
+/// `None` when none has text; capped at [`SUMMARY_SOURCE_MAX_CHARS`].
+pub fn summary_source<'a>(children: impl IntoIterator<Item = &'a Node>) -> Option<String> {
+    let lines: Vec<String> = children.into_iter().filter_map(child_line).collect();
+    if lines.is_empty() {
+        return None;
+    }
+    Some(truncate_chars(&lines.join("\n"), SUMMARY_SOURCE_MAX_CHARS))
+}
+
+/// Plain text of `child` on a single line, `None` without letters or digits
+fn child_line(child: &Node) -> Option<String> {
+    let text = strip_markdown(&child.content)
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    text.chars().any(char::is_alphanumeric).then_some(text)
+}
+
+/// Excerpt stored in `properties`, if any
+pub fn excerpt_text(properties: &Value) -> Option<&str> {
+    properties.get(EXCERPT_KEY)?.get("text")?.as_str()
+}
+
+/// Stored form of an excerpt
+pub fn excerpt_value(text: &str) -> Value {
+    serde_json::json!({ "text": text })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn child(content: &str) -> Node {
+        Node::new("text".to_string(), content.to_string(), json!({}))
+    }
+
+    #[test]
+    fn test_build_excerpt_skips_empty_children() {
+        let children = vec![
+            child("## Goals"),
+            child("   "),
+            child("---"),
+            child("Ship **v2** by\nJune"),
+            child("See [@Roadmap](nodespace://abc)"),
+            child("Not included"),
+        ];
+        assert_eq!(
+            build_excerpt(&children).as_deref(),
+            Some("Goals · Ship v2 by June · See @Roadmap")
+        );
+        assert_eq!(build_excerpt(&[child(""), child("***")]), None);
+    }
+
+    #[test]
+    fn test_build_excerpt_is_capped() {
+        let long = "word ".repeat(100);
+        let excerpt = build_excerpt(&[child(&long)]).unwrap();
+        assert!(excerpt.chars().count() <= EXCERPT_MAX_CHARS + 1);
+        assert!(excerpt.ends_with('…'));
+    }
+
+    #[test]
+    fn test_summary_source_covers_all_children() {
+        let children = vec![
+            child("# One"),
+            child("---"),
+            child("Two"),
+            child("Three"),
+            child("Four"),
+        ];
+        assert_eq!(
+            summary_source(&children).as_deref(),
+            Some("One\nTwo\nThree\nFour")
+        );
+        assert_eq!(summary_source(&[child("")]), None);
+    }
+
+    #[test]
+    fn test_excerpt_round_trips_through_properties() {
+        let properties = json!({ "text": {}, EXCERPT_KEY: excerpt_value("Hello") });
+        assert_eq!(excerpt_text(&properties), Some("Hello"));
+        assert_eq!(excerpt_text(&json!({ "text": {} })), None);
+    }
+}
@@ -0,0 +1,208 @@
+//! Find and Replace - Mass Edits of Node Content
+//!
+//! `NodeService::find_and_replace` runs a [`ReplacePattern`] over the content
+//! of every node in a [`ReplaceScope`]. A dry run only reports what would
+//! change, as a few characters of context around each match; otherwise the
+//! changes are written in batches and the overwritten contents kept as a
+//! single [`ReplaceUndo`] entry, so the whole replace can be taken back in one
+//! step.
+
+use chrono::{DateTime, Utc};
+use regex::{Captures, Regex};
+use serde::{Deserialize, Serialize};
+
+/// Characters of content shown on each side of a match in previews
+pub const REPLACE_PREVIEW_CONTEXT: usize = 30;
+
+/// Node types whose content is never replaced
+pub const REPLACE_EXCLUDED_TYPES: &[&str] =
+    &["schema", "script", "date", "week", "month", "quarter"];
+
+/// What to look for
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "mode", content = "pattern", rename_all = "camelCase")]
+pub enum ReplacePattern {
+    /// Exact, case-sensitive text; the replacement is inserted as is
+    Literal(String),
+    /// Regular expression (Rust syntax, `(?i)` for case-insensitive); the
+    /// replacement may refer to groups as `$1` or `${name}`
+    Regex(String),
+}
+
+/// Which nodes to search
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum ReplaceScope {
+    /// Every live node
+    Workspace,
+    /// A node and everything below it
+    #[serde(rename_all = "camelCase")]
+    Subtree { node_id: String },
+}
+
+/// One match, with its surroundings before and after replacing
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplaceMatch {
+    /// Byte offset of the match in the original content
+    pub offset: usize,
+    pub before: String,
+    pub after: String,
+}
+
+/// The matches in one node
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeReplacePreview {
+    pub node_id: String,
+    pub node_type: String,
+    /// Title, or first line of the content for nodes without one
+    pub title: String,
+    pub matches: Vec<ReplaceMatch>,
+}
+
+/// Outcome of a find-and-replace (or of undoing one)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplaceReport {
+    pub dry_run: bool,
+    /// Nodes changed (or that would be, in a dry run)
+    pub nodes: Vec<NodeReplacePreview>,
+    pub total_matches: usize,
+    /// Nodes left alone because they were edited while the replace ran
+    pub skipped: Vec<String>,
+}
+
+/// Content of one node before and after a replace
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContentChange {
+    pub node_id: String,
+    pub before: String,
+    pub after: String,
+}
+
+/// The last applied replace, kept so it can be undone
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplaceUndo {
+    pub pattern: ReplacePattern,
+    pub replacement: String,
+    pub replaced_at: DateTime<Utc>,
+    pub changes: Vec<ContentChange>,
+}
+
+/// A compiled pattern and replacement
+pub(crate) struct Replacer {
+    regex: Regex,
+    replacement: String,
+    expand: bool,
+}
+
+impl Replacer {
+    pub(crate) fn new(pattern: &ReplacePattern, replacement: &str) -> Result<Self, String> {
+        let (source, expand) = match pattern {
+            ReplacePattern::Literal(text) => (regex::escape(text), false),
+            ReplacePattern::Regex(source) => (source.clone(), true),
+        };
+        if source.is_empty() {
+            return Err("Search pattern is empty".to_string());
+        }
+        let regex = Regex::new(&source).map_err(|e| format!("Invalid pattern: {}", e))?;
+        Ok(Self {
+            regex,
+            replacement: replacement.to_string(),
+            expand,
+        })
+    }
+
+    /// `content` with every match replaced, and a preview of each match;
+    /// `None` when nothing matches or replacing changes nothing
+    pub(crate) fn apply(&self, content: &str) -> Option<(String, Vec<ReplaceMatch>)> {
+        let mut replaced = String::with_capacity(content.len());
+        let mut matches = Vec::new();
+        let mut last = 0;
+        for cap in self.regex.captures_iter(content) {
+            let whole = cap.get(0).expect("group 0 is the whole match");
+            if whole.is_empty() {
+                continue;
+            }
+            let text = self.replacement_for(&cap);
+            let head = context_before(&content[..whole.start()]);
+            let tail = context_after(&content[whole.end()..]);
+            matches.push(ReplaceMatch {
+                offset: whole.start(),
+                before: format!("{}{}{}", head, whole.as_str(), tail),
+                after: format!("{}{}{}", head, text, tail),
+            });
+            replaced.push_str(&content[last..whole.start()]);
+            replaced.push_str(&text);
+            last = whole.end();
+        }
+        replaced.push_str(&content[last..]);
+        (!matches.is_empty() && replaced != content).then_some((replaced, matches))
+    }
+
+    fn replacement_for(&self, cap: &Captures) -> String {
+        if self.expand {
+            let mut text = String::new();
+            cap.expand(&self.replacement, &mut text);
+            text
+        } else {
+            self.replacement.clone()
+        }
+    }
+}
+
+fn context_before(text: &str) -> &str {
+    let start = text
+        .char_indices()
+        .rev()
+        .nth(REPLACE_PREVIEW_CONTEXT - 1)
+        .map_or(0, |(i, _)| i);
+    &text[start..]
+}
+
+fn context_after(text: &str) -> &str {
+    let end = text
+        .char_indices()
+        .nth(REPLACE_PREVIEW_CONTEXT)
+        .map_or(text.len(), |(i, _)| i);
+    &text[..end]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_literal_replace_does_not_expand_groups() {
+        let replacer =
+            Replacer::new(&ReplacePattern::Literal("PRJ-1".to_string()), "$0 (old)").unwrap();
+        let (content, matches) = replacer.apply("PRJ-1 and PRJ-12").unwrap();
+        assert_eq!(content, "$0 (old) and $0 (old)2");
+        assert_eq!(matches.len(), 2);
+        assert_eq!(matches[1].offset, 10);
+
+        assert!(replacer.apply("Nothing here").is_none());
+        assert!(Replacer::new(&ReplacePattern::Literal(String::new()), "x").is_err());
+    }
+
+    #[test]
+    fn test_regex_replace_with_groups_and_preview() {
+        let replacer =
+            Replacer::new(&ReplacePattern::Regex(r"PRJ-(\d+)".to_string()), "ACME-$1").unwrap();
+        let content = format!("{}PRJ-7 ships", "x".repeat(40));
+        let (replaced, matches) = replacer.apply(&content).unwrap();
+        assert_eq!(replaced, format!("{}ACME-7 ships", "x".repeat(40)));
+        assert_eq!(
+            matches,
+            vec![ReplaceMatch {
+                offset: 40,
+                before: format!("{}PRJ-7 ships", "x".repeat(REPLACE_PREVIEW_CONTEXT)),
+                after: format!("{}ACME-7 ships", "x".repeat(REPLACE_PREVIEW_CONTEXT)),
+            }]
+        );
+
+        assert!(Replacer::new(&ReplacePattern::Regex("(".to_string()), "x").is_err());
+    }
+}
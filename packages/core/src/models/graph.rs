@@ -0,0 +1,399 @@
+//! Graph View Data - Nodes and Edges for Force-Directed Rendering
+//!
+//! The graph view draws documents, not blocks: every relationship is lifted
+//! to its *containers* (the root of each endpoint's tree, or the endpoint
+//! itself when it is a task, matching how backlinks are grouped). A mention
+//! from a paragraph of page A to a block of page B becomes one `A -> B` edge;
+//! repeated links add to the edge's `weight`, and links within a page vanish.
+//!
+//! Each node carries its degree (for sizing) and a cluster number from label
+//! propagation (for colouring and as a layout hint); clusters are numbered
+//! from the largest down. See `NodeService::get_graph_data` for how a
+//! [`GraphScope`] selects the nodes.
+//!
+//! The same document-level edges feed [`NodeImportance`], a PageRank over
+//! the link graph used to boost search results and list central notes.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+
+/// Edge types drawn when none are requested
+pub const DEFAULT_GRAPH_EDGE_TYPES: &[&str] = &["mentions", "embeds"];
+
+/// Node types never drawn
+pub const GRAPH_EXCLUDED_TYPES: &[&str] = &["schema", "script"];
+
+/// Deepest neighbourhood a local graph expands to
+pub const MAX_GRAPH_DEPTH: usize = 5;
+
+/// Nodes per page of the global graph when no limit is given
+pub const DEFAULT_GRAPH_PAGE_SIZE: usize = 500;
+
+/// Label propagation rounds before clusters are taken as they are
+const CLUSTER_ROUNDS: usize = 10;
+
+/// Probability of following a link rather than jumping to a random document
+const RANK_DAMPING: f64 = 0.85;
+
+/// Most PageRank iterations; ranks usually settle well before
+const RANK_ITERATIONS: usize = 50;
+
+/// Total rank change below which iteration stops
+const RANK_TOLERANCE: f64 = 1e-6;
+
+/// Which part of the graph to return
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum GraphScope {
+    /// Every document, most connected first, `limit` nodes per page
+    ///
+    /// Each edge is returned with the page holding the later of its two
+    /// endpoints, so loading pages in order yields every edge exactly once.
+    #[serde(rename_all = "camelCase")]
+    Global {
+        #[serde(default)]
+        offset: usize,
+        #[serde(default = "default_page_size")]
+        limit: usize,
+    },
+    /// Documents within `depth` hops of `node_id`'s container, edges taken
+    /// in either direction
+    #[serde(rename_all = "camelCase")]
+    Local { node_id: String },
+}
+
+fn default_page_size() -> usize {
+    DEFAULT_GRAPH_PAGE_SIZE
+}
+
+/// A document in the graph view
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphNode {
+    pub id: String,
+    pub node_type: String,
+    pub title: String,
+    /// Edges touching this node in the whole graph, not just the page
+    pub degree: usize,
+    /// Cluster number, 0 for the largest cluster
+    pub cluster: usize,
+    /// Hops from the centre of a local graph
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub distance: Option<usize>,
+}
+
+/// A link between two documents
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphEdge {
+    pub source: String,
+    pub target: String,
+    pub edge_type: String,
+    /// Relationships merged into this edge
+    pub weight: usize,
+}
+
+/// Nodes and edges of one graph view request
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphData {
+    pub nodes: Vec<GraphNode>,
+    pub edges: Vec<GraphEdge>,
+    /// Nodes in scope across all pages
+    pub total_nodes: usize,
+    /// Offset of the next page of a global graph, if any
+    pub next_offset: Option<usize>,
+}
+
+/// How central a document is in the link graph
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeImportance {
+    pub node_id: String,
+    /// PageRank scaled so the most central document scores 1.0
+    pub score: f64,
+    /// Position by score, 1 for the most central
+    pub rank: usize,
+    /// Documents linking here
+    pub inbound: usize,
+    /// Documents linked from here
+    pub outbound: usize,
+}
+
+/// Merge parallel edges into one with their summed weight, dropping loops
+///
+/// Sorted by source, target and type.
+pub(crate) fn merge_edges(edges: impl IntoIterator<Item = GraphEdge>) -> Vec<GraphEdge> {
+    let mut merged: BTreeMap<(String, String, String), usize> = BTreeMap::new();
+    for edge in edges {
+        if edge.source != edge.target {
+            *merged
+                .entry((edge.source, edge.target, edge.edge_type))
+                .or_default() += edge.weight;
+        }
+    }
+    merged
+        .into_iter()
+        .map(|((source, target, edge_type), weight)| GraphEdge {
+            source,
+            target,
+            edge_type,
+            weight,
+        })
+        .collect()
+}
+
+/// Number of edges touching each node
+pub(crate) fn degrees(edges: &[GraphEdge]) -> HashMap<&str, usize> {
+    let mut degrees = HashMap::new();
+    for edge in edges {
+        *degrees.entry(edge.source.as_str()).or_default() += 1;
+        *degrees.entry(edge.target.as_str()).or_default() += 1;
+    }
+    degrees
+}
+
+/// Hops from `center` to every node within `depth`, ignoring direction
+pub(crate) fn neighbourhood(
+    center: &str,
+    depth: usize,
+    edges: &[GraphEdge],
+) -> HashMap<String, usize> {
+    let adjacency = adjacency(edges);
+    let mut distances = HashMap::from([(center.to_string(), 0)]);
+    let mut queue = VecDeque::from([(center, 0)]);
+    while let Some((id, distance)) = queue.pop_front() {
+        if distance == depth {
+            continue;
+        }
+        for &(neighbour, _) in adjacency.get(id).into_iter().flatten() {
+            if !distances.contains_key(neighbour) {
+                distances.insert(neighbour.to_string(), distance + 1);
+                queue.push_back((neighbour, distance + 1));
+            }
+        }
+    }
+    distances
+}
+
+/// Cluster number of each of `ids`, found by label propagation over `edges`
+///
+/// Nodes are visited in ID order and adopt the label with the most edge
+/// weight among their neighbours, ties going to the smallest label, so the
+/// result is deterministic. Edges to nodes outside `ids` are ignored.
+pub(crate) fn clusters(ids: &[String], edges: &[GraphEdge]) -> HashMap<String, usize> {
+    let mut order: Vec<&str> = ids.iter().map(String::as_str).collect();
+    order.sort_unstable();
+    let included: HashSet<&str> = order.iter().copied().collect();
+    let edges: Vec<&GraphEdge> = edges
+        .iter()
+        .filter(|e| included.contains(e.source.as_str()) && included.contains(e.target.as_str()))
+        .collect();
+    let adjacency = adjacency(edges.iter().copied());
+
+    let mut labels: HashMap<&str, &str> = order.iter().map(|&id| (id, id)).collect();
+    for _ in 0..CLUSTER_ROUNDS {
+        let mut changed = false;
+        for &id in &order {
+            let mut votes: BTreeMap<&str, usize> = BTreeMap::new();
+            for &(neighbour, weight) in adjacency.get(id).into_iter().flatten() {
+                *votes.entry(labels[neighbour]).or_default() += weight;
+            }
+            // max_by_key keeps the last maximum, which in reverse label order
+            // is the smallest label
+            let best = votes.into_iter().rev().max_by_key(|&(_, weight)| weight);
+            if let Some((label, _)) = best {
+                if labels[id] != label {
+                    labels.insert(id, label);
+                    changed = true;
+                }
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+
+    let mut sizes: HashMap<&str, usize> = HashMap::new();
+    for label in labels.values() {
+        *sizes.entry(label).or_default() += 1;
+    }
+    let mut ranked: Vec<(&str, usize)> = sizes.into_iter().collect();
+    ranked.sort_by(|(a, a_size), (b, b_size)| b_size.cmp(a_size).then_with(|| a.cmp(b)));
+    let numbers: HashMap<&str, usize> = ranked
+        .into_iter()
+        .enumerate()
+        .map(|(number, (label, _))| (label, number))
+        .collect();
+    labels
+        .into_iter()
+        .map(|(id, label)| (id.to_string(), numbers[label]))
+        .collect()
+}
+
+/// Importance of each of `ids`, most central first
+///
+/// Weighted PageRank over `edges` with edge types merged; rank held by
+/// documents without outgoing links is spread evenly over all of them.
+/// Edges to nodes outside `ids` are ignored. Ties are ordered by ID.
+pub(crate) fn importance(ids: &[String], edges: &[GraphEdge]) -> Vec<NodeImportance> {
+    let n = ids.len();
+    if n == 0 {
+        return Vec::new();
+    }
+    let index: HashMap<&str, usize> = ids
+        .iter()
+        .enumerate()
+        .map(|(i, id)| (id.as_str(), i))
+        .collect();
+    let mut links: BTreeMap<(usize, usize), f64> = BTreeMap::new();
+    for edge in edges {
+        if let (Some(&source), Some(&target)) = (
+            index.get(edge.source.as_str()),
+            index.get(edge.target.as_str()),
+        ) {
+            if source != target {
+                *links.entry((source, target)).or_default() += edge.weight as f64;
+            }
+        }
+    }
+
+    let mut out_weight = vec![0.0; n];
+    let mut inbound = vec![0; n];
+    let mut outbound = vec![0; n];
+    for (&(source, target), &weight) in &links {
+        out_weight[source] += weight;
+        outbound[source] += 1;
+        inbound[target] += 1;
+    }
+
+    let mut rank = vec![1.0 / n as f64; n];
+    for _ in 0..RANK_ITERATIONS {
+        let dangling: f64 = (0..n).filter(|&i| outbound[i] == 0).map(|i| rank[i]).sum();
+        let mut next = vec![(1.0 - RANK_DAMPING + RANK_DAMPING * dangling) / n as f64; n];
+        for (&(source, target), &weight) in &links {
+            next[target] += RANK_DAMPING * rank[source] * weight / out_weight[source];
+        }
+        let change: f64 = next.iter().zip(&rank).map(|(a, b)| (a - b).abs()).sum();
+        rank = next;
+        if change < RANK_TOLERANCE {
+            break;
+        }
+    }
+
+    let top = rank.iter().copied().fold(f64::MIN_POSITIVE, f64::max);
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|&a, &b| {
+        rank[b]
+            .total_cmp(&rank[a])
+            .then_with(|| ids[a].cmp(&ids[b]))
+    });
+    order
+        .into_iter()
+        .enumerate()
+        .map(|(position, i)| NodeImportance {
+            node_id: ids[i].clone(),
+            score: rank[i] / top,
+            rank: position + 1,
+            inbound: inbound[i],
+            outbound: outbound[i],
+        })
+        .collect()
+}
+
+/// Weighted neighbours of each node, ignoring direction
+fn adjacency<'a>(
+    edges: impl IntoIterator<Item = &'a GraphEdge>,
+) -> HashMap<&'a str, Vec<(&'a str, usize)>> {
+    let mut adjacency: HashMap<&str, Vec<(&str, usize)>> = HashMap::new();
+    for edge in edges {
+        adjacency
+            .entry(edge.source.as_str())
+            .or_default()
+            .push((edge.target.as_str(), edge.weight));
+        adjacency
+            .entry(edge.target.as_str())
+            .or_default()
+            .push((edge.source.as_str(), edge.weight));
+    }
+    adjacency
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edge(source: &str, target: &str) -> GraphEdge {
+        GraphEdge {
+            source: source.to_string(),
+            target: target.to_string(),
+            edge_type: "mentions".to_string(),
+            weight: 1,
+        }
+    }
+
+    fn ids(ids: &[&str]) -> Vec<String> {
+        ids.iter().map(|id| id.to_string()).collect()
+    }
+
+    #[test]
+    fn test_merge_edges_sums_weights_and_drops_loops() {
+        let merged = merge_edges(vec![
+            edge("b", "a"),
+            edge("a", "b"),
+            edge("a", "b"),
+            edge("a", "a"),
+        ]);
+        assert_eq!(merged.len(), 2);
+        assert_eq!((merged[0].source.as_str(), merged[0].weight), ("a", 2));
+        assert_eq!((merged[1].source.as_str(), merged[1].weight), ("b", 1));
+        assert_eq!(degrees(&merged)["a"], 2);
+    }
+
+    #[test]
+    fn test_neighbourhood_respects_depth() {
+        let edges = vec![edge("a", "b"), edge("c", "b"), edge("c", "d")];
+        let near = neighbourhood("a", 2, &edges);
+        assert_eq!(near.len(), 3);
+        assert_eq!(near["c"], 2);
+        assert!(!near.contains_key("d"));
+        assert_eq!(neighbourhood("a", 0, &edges).len(), 1);
+    }
+
+    #[test]
+    fn test_clusters_separate_components() {
+        let edges = vec![
+            edge("a", "b"),
+            edge("b", "c"),
+            edge("a", "c"),
+            edge("x", "y"),
+        ];
+        let clusters = clusters(&ids(&["a", "b", "c", "x", "y", "lone"]), &edges);
+        assert_eq!(clusters["a"], 0);
+        assert_eq!(clusters["b"], 0);
+        assert_eq!(clusters["c"], 0);
+        assert_eq!(clusters["x"], clusters["y"]);
+        assert_ne!(clusters["x"], 0);
+        assert_ne!(clusters["lone"], clusters["x"]);
+    }
+
+    #[test]
+    fn test_importance_ranks_linked_documents_first() {
+        let edges = vec![
+            edge("a", "hub"),
+            edge("b", "hub"),
+            edge("c", "hub"),
+            edge("hub", "a"),
+        ];
+        let ranked = importance(&ids(&["a", "b", "c", "hub", "lone"]), &edges);
+        assert_eq!(ranked[0].node_id, "hub");
+        assert_eq!(ranked[0].rank, 1);
+        assert_eq!(ranked[0].score, 1.0);
+        assert_eq!((ranked[0].inbound, ranked[0].outbound), (3, 1));
+        assert_eq!(ranked[1].node_id, "a");
+        let lone = ranked.iter().find(|r| r.node_id == "lone").unwrap();
+        assert!(lone.score < ranked[1].score);
+        assert_eq!(ranked.len(), 5);
+        assert!(importance(&[], &edges).is_empty());
+    }
+}
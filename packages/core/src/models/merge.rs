@@ -0,0 +1,133 @@
+//! Node Merge - Folding Duplicate Nodes into One
+//!
+//! `NodeService::merge_nodes` keeps a target node and folds one or more
+//! sources into it. This module holds its result type and the property merge:
+//! values from the sources fill in what the target lacks, and where both
+//! hold different values the target's is kept and the difference reported as
+//! a [`PropertyConflict`].
+
+use super::{Node, EXCERPT_KEY};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// A property the target and a source disagreed on; the target's value was kept
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PropertyConflict {
+    pub source_id: String,
+    /// Dotted path into the properties, e.g. `task.status`
+    pub path: String,
+    pub kept: Value,
+    pub discarded: Value,
+}
+
+/// Outcome of a merge
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeResult {
+    /// The target after the merge
+    pub target: Node,
+    /// Children moved from the sources to the end of the target's children
+    pub moved_children: usize,
+    /// Nodes whose content linked to a source and now links to the target
+    pub rewritten_nodes: Vec<String>,
+    /// Sources, now in the trash
+    pub trashed: Vec<String>,
+    pub conflicts: Vec<PropertyConflict>,
+}
+
+/// Merge the properties of `source` into `target`, recording conflicts
+///
+/// Objects are merged key by key; any other value of the source is only
+/// taken when the target has none. The derived excerpt is skipped, as it is
+/// rebuilt from the merged children.
+pub(crate) fn merge_properties(
+    target: &mut Value,
+    source: &Value,
+    source_id: &str,
+    conflicts: &mut Vec<PropertyConflict>,
+) {
+    let Some(source) = source.as_object() else {
+        return;
+    };
+    if !target.is_object() {
+        *target = Value::Object(Default::default());
+    }
+    for (key, value) in source {
+        if key == EXCERPT_KEY {
+            continue;
+        }
+        merge_value(target, key, value, key, source_id, conflicts);
+    }
+}
+
+fn merge_value(
+    target: &mut Value,
+    key: &str,
+    value: &Value,
+    path: &str,
+    source_id: &str,
+    conflicts: &mut Vec<PropertyConflict>,
+) {
+    let Some(object) = target.as_object_mut() else {
+        return;
+    };
+    let Some(existing) = object.get_mut(key).filter(|existing| !existing.is_null()) else {
+        object.insert(key.to_string(), value.clone());
+        return;
+    };
+    match value.as_object() {
+        Some(fields) if existing.is_object() => {
+            for (field, value) in fields {
+                let path = format!("{}.{}", path, field);
+                merge_value(existing, field, value, &path, source_id, conflicts);
+            }
+        }
+        _ if value.is_null() || *existing == *value => {}
+        _ => conflicts.push(PropertyConflict {
+            source_id: source_id.to_string(),
+            path: path.to_string(),
+            kept: existing.clone(),
+            discarded: value.clone(),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_merge_properties_fills_gaps_and_reports_conflicts() {
+        let mut target = json!({
+            "task": { "status": "open", "priority": null },
+            "excerpt": { "text": "Kept" }
+        });
+        let source = json!({
+            "task": { "status": "done", "priority": "high", "dueDate": "2025-03-01" },
+            "person": { "email": "a@example.com" },
+            "excerpt": { "text": "Dropped" }
+        });
+        let mut conflicts = Vec::new();
+        merge_properties(&mut target, &source, "dup", &mut conflicts);
+
+        assert_eq!(
+            target,
+            json!({
+                "task": { "status": "open", "priority": "high", "dueDate": "2025-03-01" },
+                "person": { "email": "a@example.com" },
+                "excerpt": { "text": "Kept" }
+            })
+        );
+        assert_eq!(
+            conflicts,
+            vec![PropertyConflict {
+                source_id: "dup".to_string(),
+                path: "task.status".to_string(),
+                kept: json!("open"),
+                discarded: json!("done"),
+            }]
+        );
+    }
+}
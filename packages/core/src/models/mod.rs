@@ -4,22 +4,47 @@
 //!
 //! - `Node` - Universal node model for all content types
 //! - `Embedding` - Vector embeddings for semantic search (root-aggregate model)
-//! - Type-safe wrappers (TaskNode, TextNode, DateNode, CodeBlockNode, QuoteBlockNode, OrderedListNode, CollectionNode) for ergonomic access
+//! - Type-safe wrappers (TaskNode, TextNode, DateNode, CodeBlockNode, QuoteBlockNode, OrderedListNode, CollectionNode, ScriptNode) for ergonomic access
+//! - `TypedNode<T>` for schema-backed types declared with `spoke_model!` (e.g. `PersonNode`)
+//! - `Period` for the deterministic IDs of week, month and quarter nodes
+//! - `GraphData` and friends for the graph view
+//! - `BrokenLink` for mentions and embeds whose target was deleted
+//! - `MergeResult` and `PropertyConflict` for node merges
+//! - `ChildView` for saved views of a node's children
+//! - `Rollup` for aggregates a node declares over its children
+//! - `ReplacePattern`, `ReplaceReport` and friends for find-and-replace
+//! - `SchemaChange` and `diff_schemas` for comparing schema versions
 //! - Core schema definitions for built-in node types
 //!
 //! All entities use the Pure JSON schema approach with data stored in the
 //! `properties` field of the universal `nodes` table.
 
+mod appearance;
+mod broken_link;
+mod child_view;
 pub mod core_schemas;
 pub mod embedding;
+mod excerpt;
+mod find_replace;
+mod graph;
+mod merge;
 mod node;
+mod passphrase;
+mod period;
+mod property_normalization;
+mod rollup;
 pub mod schema;
+mod schema_diff;
+mod share;
 pub mod time;
+mod validation;
 
 // Type-safe node wrappers
 mod collection_node;
 mod date_node;
 mod schema_node;
+mod script_node;
+mod spoke;
 mod task_node;
 mod text_node;
 
@@ -51,25 +76,57 @@ pub mod ordered_list_node;
 #[path = "ordered_list_node_test.rs"]
 mod ordered_list_node_test;
 
+pub use appearance::{NodeAppearance, APPEARANCE_KEY};
+pub use broken_link::BrokenLink;
+pub use child_view::{ChildView, ChildViewGroup, VIEWS_KEY};
 pub use code_block_node::{CodeBlockNode, CodeBlockValidationError};
+pub use excerpt::{
+    build_excerpt, excerpt_text, excerpt_value, summary_source, EXCERPT_KEY, EXCERPT_MAX_CHARS,
+    EXCERPT_MAX_CHILDREN, SUMMARY_SOURCE_MAX_CHARS,
+};
+pub(crate) use find_replace::Replacer;
+pub use find_replace::{
+    ContentChange, NodeReplacePreview, ReplaceMatch, ReplacePattern, ReplaceReport, ReplaceScope,
+    ReplaceUndo, REPLACE_EXCLUDED_TYPES, REPLACE_PREVIEW_CONTEXT,
+};
+pub(crate) use graph::{clusters, degrees, importance, merge_edges, neighbourhood};
+pub use graph::{
+    GraphData, GraphEdge, GraphNode, GraphScope, NodeImportance, DEFAULT_GRAPH_EDGE_TYPES,
+    DEFAULT_GRAPH_PAGE_SIZE, GRAPH_EXCLUDED_TYPES, MAX_GRAPH_DEPTH,
+};
+pub(crate) use merge::merge_properties;
+pub use merge::{MergeResult, PropertyConflict};
 pub use node::{
     DeleteResult, FilterOperator, Node, NodeFilter, NodeQuery, NodeReference, NodeRelationship,
     NodeUpdate, OrderBy, PropertyFilter, RelationshipDirection, ValidationError,
 };
 pub use ordered_list_node::{OrderedListNode, OrderedListValidationError};
+pub use passphrase::{PassphraseVerifier, MIN_PASSPHRASE_CHARS};
+pub use period::{Period, PERIOD_NODE_TYPES};
+pub use property_normalization::normalize_properties;
 pub use quote_block_node::{QuoteBlockNode, QuoteBlockValidationError};
+pub use rollup::{Rollup, RollupCondition, RollupFunction, ROLLUPS_KEY};
 pub use schema::{SchemaField, SchemaProtectionLevel};
+pub use schema_diff::{diff_schemas, SchemaChange, SchemaChangeKind};
+pub use share::ShareRecord;
 pub use time::{SystemTimeProvider, TimeProvider};
+pub use validation::{schema_warnings, ValidationReport, ValidationWarning, ValidationWarningKind};
 
 // Export type-safe wrappers
-pub use collection_node::CollectionNode;
+pub use collection_node::{CollectionNode, SMART_QUERY_FIELD};
 pub use date_node::DateNode;
 pub use embedding::{
     is_embeddable_type, ChunkInfo, Embedding, EmbeddingConfig, EmbeddingSearchResult, NewEmbedding,
     EMBEDDABLE_NODE_TYPES,
 };
 pub use schema_node::SchemaNode;
-pub use task_node::{TaskNode, TaskNodeUpdate, TaskPriority, TaskStatus};
+pub use script_node::{ScriptHook, ScriptNode, ScriptNodeBuilder};
+pub(crate) use spoke::TypedNodeRow;
+pub use spoke::{Person, PersonNode, SpokeModel, TypedNode};
+pub use task_node::{
+    TaskDueFilter, TaskNode, TaskNodeUpdate, TaskPriority, TaskStatus, TimeSession,
+    TrackedTimeEntry,
+};
 pub use text_node::TextNode;
 
 /// Convert a Node to its strongly-typed JSON representation (Issue #673)
@@ -119,23 +176,31 @@ pub fn node_to_typed_value(node: Node) -> Result<serde_json::Value, String> {
 /// `{ "status": "open" }`
 ///
 /// Dormant namespaces (from previous type changes) are NOT exposed to clients.
-fn flatten_properties_for_api(node: &mut Node) {
+/// The type-independent `appearance`, `excerpt`, `rollups` and `views`
+/// namespaces are kept as nested values.
+pub(crate) fn flatten_properties_for_api(node: &mut Node) {
     let node_type = node.node_type.clone();
 
     let Some(props_obj) = node.properties.as_object() else {
         return;
     };
+    let shared: Vec<(String, serde_json::Value)> =
+        [APPEARANCE_KEY, EXCERPT_KEY, ROLLUPS_KEY, VIEWS_KEY]
+            .into_iter()
+            .filter_map(|key| Some((key.to_string(), props_obj.get(key)?.clone())))
+            .collect();
 
     // Extract properties from the current type's namespace
     if let Some(type_namespace) = props_obj.get(&node_type) {
         if let Some(type_props) = type_namespace.as_object() {
             // Clone the namespaced properties as the new flat properties
             // Exclude internal fields like _schema_version
-            let flat: serde_json::Map<String, serde_json::Value> = type_props
+            let mut flat: serde_json::Map<String, serde_json::Value> = type_props
                 .iter()
                 .filter(|(k, _)| !k.starts_with('_'))
                 .map(|(k, v)| (k.clone(), v.clone()))
                 .collect();
+            flat.extend(shared);
             node.properties = serde_json::Value::Object(flat);
             return;
         }
@@ -150,11 +215,12 @@ fn flatten_properties_for_api(node: &mut Node) {
     // simple types (strings, numbers, booleans, arrays), this is safe. If
     // object-typed properties are added in the future, this filter would need
     // to distinguish between namespace objects and legitimate complex properties.
-    let flat: serde_json::Map<String, serde_json::Value> = props_obj
+    let mut flat: serde_json::Map<String, serde_json::Value> = props_obj
         .iter()
         .filter(|(k, v)| !v.is_object() && !k.starts_with('_'))
         .map(|(k, v)| (k.clone(), v.clone()))
         .collect();
+    flat.extend(shared);
     node.properties = serde_json::Value::Object(flat);
 }
 
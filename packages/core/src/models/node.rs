@@ -35,8 +35,10 @@
 //! );
 //! ```
 
+use crate::i18n::{Locale, UserMessage};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use thiserror::Error;
 use uuid::Uuid;
 
@@ -66,6 +68,9 @@ pub struct NodeReference {
     pub title: Option<String>,
     /// Node type (e.g., "text", "task", "date")
     pub node_type: String,
+    /// Icon, color and cover, when the node has any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub appearance: Option<super::NodeAppearance>,
 }
 
 /// Direction of a relationship relative to a node
@@ -107,25 +112,90 @@ pub struct NodeRelationship {
 }
 
 /// Validation errors for Node operations
+///
+/// Variants carry the values shown to users rather than prose; see
+/// [`ValidationError::user_message`].
 #[derive(Error, Debug)]
 pub enum ValidationError {
     #[error("Missing required field: {0}")]
     MissingField(String),
 
-    #[error("Invalid node type: {0}")]
-    InvalidNodeType(String),
+    #[error("Invalid node type: expected '{expected}', got '{actual}'")]
+    InvalidNodeType { expected: String, actual: String },
 
-    #[error("Invalid node ID format: {0}")]
-    InvalidId(String),
+    #[error("Invalid node ID format: '{id}' (expected {format})")]
+    InvalidId { id: String, format: String },
 
-    #[error("Invalid parent reference: {0}")]
-    InvalidParent(String),
+    #[error("Node {node_id} can't mention itself or its own root")]
+    SelfMention { node_id: String },
 
     #[error("Invalid root reference: {0}")]
     InvalidRoot(String),
 
-    #[error("Properties validation failed: {0}")]
-    InvalidProperties(String),
+    #[error("Properties validation failed: {}", .0.render(Locale::En))]
+    InvalidProperties(UserMessage),
+
+    #[error("Invalid {field}: {reason}")]
+    MalformedProperty { field: String, reason: String },
+}
+
+impl ValidationError {
+    /// Create an invalid node type error
+    pub fn invalid_node_type(expected: impl Into<String>, actual: impl Into<String>) -> Self {
+        Self::InvalidNodeType {
+            expected: expected.into(),
+            actual: actual.into(),
+        }
+    }
+
+    /// Create an invalid ID error; `format` is the ID pattern, e.g. `YYYY-MM-DD`
+    pub fn invalid_id(id: impl Into<String>, format: impl Into<String>) -> Self {
+        Self::InvalidId {
+            id: id.into(),
+            format: format.into(),
+        }
+    }
+
+    /// Create a `{field} must be a {expected}` properties error
+    pub fn property_type_mismatch(field: &str, expected: &str) -> Self {
+        Self::InvalidProperties(
+            UserMessage::new("PROPERTY_TYPE_MISMATCH")
+                .with_param("field", field)
+                .with_param("expected", expected),
+        )
+    }
+
+    /// Create an error for a property value that couldn't be deserialized
+    pub fn malformed_property(field: &str, reason: impl ToString) -> Self {
+        Self::MalformedProperty {
+            field: field.to_string(),
+            reason: reason.to_string(),
+        }
+    }
+
+    /// Code and parameters for showing this error to users
+    pub fn user_message(&self) -> UserMessage {
+        match self {
+            Self::MissingField(field) => UserMessage::new("FIELD_MISSING")
+                .with_param("field", crate::i18n::display_field_name(field)),
+            Self::InvalidNodeType { expected, actual } => UserMessage::new("WRONG_NODE_TYPE")
+                .with_param("expected", expected.as_str())
+                .with_param("actual", actual.as_str()),
+            Self::InvalidId { id, format } => UserMessage::new("INVALID_NODE_ID")
+                .with_param("id", id.as_str())
+                .with_param("format", format.as_str()),
+            Self::SelfMention { node_id } => {
+                UserMessage::new("SELF_MENTION").with_param("node_id", node_id.as_str())
+            }
+            Self::InvalidRoot(root_node_id) => {
+                UserMessage::new("INVALID_ROOT").with_param("root_node_id", root_node_id.as_str())
+            }
+            Self::InvalidProperties(message) => message.clone(),
+            Self::MalformedProperty { field, .. } => {
+                UserMessage::new("MALFORMED_PROPERTY").with_param("field", field.as_str())
+            }
+        }
+    }
 }
 
 /// Universal Node structure for all content types in NodeSpace.
@@ -242,6 +312,12 @@ pub struct Node {
     #[serde(default = "default_lifecycle_status")]
     #[serde(skip_serializing_if = "is_active_lifecycle")]
     pub lifecycle_status: String,
+
+    /// Values of the rollups declared in `properties.rollups`, by rollup name
+    /// Read-only field, populated by NodeService::get_node
+    #[serde(default)]
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub rollups: BTreeMap<String, serde_json::Value>,
 }
 
 impl Node {
@@ -289,6 +365,7 @@ impl Node {
             member_of: Vec::new(),
             title: None, // Title is set by NodeService based on root/task status
             lifecycle_status: "active".to_string(),
+            rollups: BTreeMap::new(),
         }
     }
 
@@ -334,6 +411,7 @@ impl Node {
             member_of: Vec::new(),
             title: None, // Title is set by NodeService based on root/task status
             lifecycle_status: "active".to_string(),
+            rollups: BTreeMap::new(),
         }
     }
 
@@ -379,8 +457,9 @@ impl Node {
         // Related to Issue #479: Phase 1 - Ephemeral node elimination
 
         if !self.properties.is_object() {
-            return Err(ValidationError::InvalidProperties(
-                "properties must be a JSON object".to_string(),
+            return Err(ValidationError::property_type_mismatch(
+                "properties",
+                "object",
             ));
         }
 
@@ -399,15 +478,21 @@ impl Node {
         self.modified_at = Utc::now();
     }
 
-    /// Merge properties with existing properties (shallow merge)
-    pub fn merge_properties(&mut self, updates: serde_json::Value) {
-        if let (Some(existing), Some(new)) = (self.properties.as_object_mut(), updates.as_object())
-        {
-            for (key, value) in new {
-                existing.insert(key.clone(), value.clone());
-            }
-            self.modified_at = Utc::now();
-        }
+    /// Value of a property of the node's type, falling back to a top-level
+    /// property of the same name
+    pub fn property(&self, name: &str) -> Option<&serde_json::Value> {
+        self.properties
+            .get(&self.node_type)
+            .and_then(|namespace| namespace.get(name))
+            .or_else(|| self.properties.get(name))
+    }
+
+    /// Apply `patch` to the properties as a JSON merge patch (RFC 7386)
+    ///
+    /// Objects merge recursively and `null` removes a key.
+    pub fn merge_properties(&mut self, patch: serde_json::Value) {
+        crate::utils::merge_patch(&mut self.properties, &patch);
+        self.modified_at = Utc::now();
     }
 }
 
@@ -445,7 +530,8 @@ pub struct NodeUpdate {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub content: Option<String>,
 
-    /// Update or merge properties
+    /// JSON merge patch (RFC 7386) for properties: objects merge
+    /// recursively, `null` removes a key, other values replace
     #[serde(skip_serializing_if = "Option::is_none")]
     pub properties: Option<serde_json::Value>,
 
@@ -472,12 +558,28 @@ impl NodeUpdate {
         self
     }
 
-    /// Set properties update
+    /// Set properties update (a merge patch, see [`NodeUpdate::properties`])
     pub fn with_properties(mut self, properties: serde_json::Value) -> Self {
         self.properties = Some(properties);
         self
     }
 
+    /// Add `value` at `key` to the properties patch
+    pub fn with_property(mut self, key: &str, value: serde_json::Value) -> Self {
+        let patch = self.properties.get_or_insert_with(|| serde_json::json!({}));
+        crate::utils::merge_patch(patch, &serde_json::json!({ key: value }));
+        self
+    }
+
+    /// Remove `key` from the properties when the update is applied
+    pub fn without_property(mut self, key: &str) -> Self {
+        let patch = self.properties.get_or_insert_with(|| serde_json::json!({}));
+        if let Some(patch) = patch.as_object_mut() {
+            patch.insert(key.to_string(), serde_json::Value::Null);
+        }
+        self
+    }
+
     /// Set node type update
     pub fn with_node_type(mut self, node_type: String) -> Self {
         self.node_type = Some(node_type);
@@ -661,28 +763,25 @@ impl PropertyFilter {
         operator: FilterOperator,
         value: serde_json::Value,
     ) -> Result<Self, ValidationError> {
+        let invalid_path = |path: &str| {
+            ValidationError::InvalidProperties(
+                UserMessage::new("INVALID_PROPERTY_PATH").with_param("path", path),
+            )
+        };
+
         // Validate path starts with "$"
         if !path.starts_with('$') {
-            return Err(ValidationError::InvalidProperties(format!(
-                "JSON path must start with '$': {}",
-                path
-            )));
+            return Err(invalid_path(&path));
         }
 
         // Validate no consecutive dots (invalid JSONPath)
         if path.contains("..") {
-            return Err(ValidationError::InvalidProperties(format!(
-                "JSON path contains invalid consecutive dots: {}",
-                path
-            )));
+            return Err(invalid_path(&path));
         }
 
         // Validate path doesn't end with a dot (incomplete path)
         if path.len() > 1 && path.ends_with('.') {
-            return Err(ValidationError::InvalidProperties(format!(
-                "JSON path cannot end with '.': {}",
-                path
-            )));
+            return Err(invalid_path(&path));
         }
 
         Ok(Self {
@@ -1079,6 +1178,29 @@ mod tests {
         assert!(!update.is_empty());
     }
 
+    #[test]
+    fn test_node_update_property_patch() {
+        let update = NodeUpdate::new()
+            .with_property("task", json!({"status": "done"}))
+            .with_property("task", json!({"priority": "high"}))
+            .without_property("legacy");
+        assert_eq!(
+            update.properties,
+            Some(json!({"task": {"status": "done", "priority": "high"}, "legacy": null}))
+        );
+
+        let mut node = Node::new(
+            "task".to_string(),
+            "Ship it".to_string(),
+            json!({"task": {"status": "open", "due_date": "2025-01-01"}, "legacy": 1}),
+        );
+        node.merge_properties(update.properties.unwrap());
+        assert_eq!(
+            node.properties,
+            json!({"task": {"status": "done", "priority": "high", "due_date": "2025-01-01"}})
+        );
+    }
+
     #[test]
     fn test_node_update_is_empty() {
         let update = NodeUpdate::new();
@@ -0,0 +1,110 @@
+//! Passphrase Verifier - Salted Hash of the Workspace Passphrase
+//!
+//! The workspace lock never stores the passphrase itself. The
+//! `workspace_lock` table keeps a random salt and the PBKDF2-HMAC-SHA256 of
+//! the passphrase, which is enough to check an unlock attempt.
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use uuid::Uuid;
+
+/// PBKDF2 rounds for newly set passphrases
+pub const PASSPHRASE_ITERATIONS: u32 = 100_000;
+
+/// Shortest accepted passphrase, in chars
+pub const MIN_PASSPHRASE_CHARS: usize = 8;
+
+/// Salted PBKDF2 hash of the workspace passphrase
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PassphraseVerifier {
+    /// Random salt, base64
+    pub salt: String,
+    /// Derived key, base64
+    pub hash: String,
+    /// PBKDF2 rounds the hash was derived with
+    pub iterations: u32,
+}
+
+impl PassphraseVerifier {
+    /// Hash `passphrase` with a fresh random salt
+    pub fn new(passphrase: &str) -> Self {
+        let salt = *Uuid::new_v4().as_bytes();
+        let hash = pbkdf2_sha256(passphrase.as_bytes(), &salt, PASSPHRASE_ITERATIONS);
+        Self {
+            salt: STANDARD.encode(salt),
+            hash: STANDARD.encode(hash),
+            iterations: PASSPHRASE_ITERATIONS,
+        }
+    }
+
+    /// Whether `passphrase` is the one this verifier was created from
+    pub fn verify(&self, passphrase: &str) -> bool {
+        let (Ok(salt), Ok(expected)) = (STANDARD.decode(&self.salt), STANDARD.decode(&self.hash))
+        else {
+            return false;
+        };
+        let derived = pbkdf2_sha256(passphrase.as_bytes(), &salt, self.iterations);
+
+        // Compare without an early exit so timing doesn't leak the match length
+        expected.len() == derived.len()
+            && expected
+                .iter()
+                .zip(derived)
+                .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+                == 0
+    }
+}
+
+/// PBKDF2-HMAC-SHA256 with a 32-byte output (a single PBKDF2 block)
+fn pbkdf2_sha256(passphrase: &[u8], salt: &[u8], iterations: u32) -> [u8; 32] {
+    let prf = Hmac::<Sha256>::new_from_slice(passphrase).expect("HMAC accepts keys of any length");
+
+    let mut mac = prf.clone();
+    mac.update(salt);
+    mac.update(&1u32.to_be_bytes());
+    let mut block = [0u8; 32];
+    block.copy_from_slice(&mac.finalize().into_bytes());
+
+    let mut key = block;
+    for _ in 1..iterations {
+        let mut mac = prf.clone();
+        mac.update(&block);
+        block.copy_from_slice(&mac.finalize().into_bytes());
+        for (k, b) in key.iter_mut().zip(block) {
+            *k ^= b;
+        }
+    }
+    key
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pbkdf2_sha256_known_vector() {
+        // RFC 7914 section 11: P = "passwd", S = "salt", c = 1 (first 32 bytes)
+        let key = pbkdf2_sha256(b"passwd", b"salt", 1);
+        let hex: String = key.iter().map(|b| format!("{:02x}", b)).collect();
+        assert_eq!(
+            hex,
+            "55ac046e56e3089fec1691c22544b605f94185216dde0465e68b9d57c20dacbc"
+        );
+    }
+
+    #[test]
+    fn test_verifier_round_trip() {
+        let verifier = PassphraseVerifier::new("correct horse");
+        assert!(verifier.verify("correct horse"));
+        assert!(!verifier.verify("correct horsE"));
+        assert!(!verifier.verify(""));
+
+        // Same passphrase, different salt
+        let other = PassphraseVerifier::new("correct horse");
+        assert_ne!(verifier.hash, other.hash);
+        assert!(other.verify("correct horse"));
+    }
+}
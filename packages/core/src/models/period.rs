@@ -0,0 +1,230 @@
+//! Calendar Periods
+//!
+//! Week, month and quarter nodes are the coarser siblings of date nodes: their
+//! IDs are derived from the period itself, so there is exactly one node per
+//! period and it can be found without a lookup table.
+//!
+//! | Node type | ID format  | Example    | Covers                       |
+//! |-----------|------------|------------|------------------------------|
+//! | `week`    | `YYYY-Www` | `2025-W03` | Monday to Sunday of ISO week |
+//! | `month`   | `YYYY-MM`  | `2025-05`  | the calendar month           |
+//! | `quarter` | `YYYY-Qn`  | `2025-Q2`  | three calendar months        |
+//!
+//! Weeks follow ISO 8601, so `2025-W01` starts on 2024-12-30 and a year has
+//! 52 or 53 weeks.
+
+use chrono::{Datelike, Days, Months, NaiveDate, Weekday};
+use std::fmt;
+
+/// Node types of the calendar periods
+pub const PERIOD_NODE_TYPES: [&str; 3] = ["week", "month", "quarter"];
+
+/// A week, month or quarter with a deterministic node ID
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Period {
+    /// ISO 8601 week of an ISO week-numbering year
+    Week {
+        year: i32,
+        week: u32,
+    },
+    Month {
+        year: i32,
+        month: u32,
+    },
+    Quarter {
+        year: i32,
+        quarter: u32,
+    },
+}
+
+impl Period {
+    /// Period for a node ID, `None` unless the ID names a real period
+    ///
+    /// Only the canonical form is accepted (`2025-W03`, not `2025-W3`), so
+    /// every period has exactly one ID.
+    pub fn parse(id: &str) -> Option<Self> {
+        let (year, rest) = id.split_once('-')?;
+        if year.len() != 4 || !year.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        let year: i32 = year.parse().ok()?;
+
+        let period = if let Some(week) = rest.strip_prefix('W') {
+            Self::Week {
+                year,
+                week: two_digits(week)?,
+            }
+        } else if let Some(quarter) = rest.strip_prefix('Q') {
+            if quarter.len() != 1 {
+                return None;
+            }
+            Self::Quarter {
+                year,
+                quarter: quarter.parse().ok()?,
+            }
+        } else {
+            Self::Month {
+                year,
+                month: two_digits(rest)?,
+            }
+        };
+
+        let valid = match period {
+            Self::Week { year, week } => {
+                NaiveDate::from_isoywd_opt(year, week, Weekday::Mon).is_some()
+            }
+            Self::Month { month, .. } => (1..=12).contains(&month),
+            Self::Quarter { quarter, .. } => (1..=4).contains(&quarter),
+        };
+        valid.then_some(period)
+    }
+
+    /// The week, month and quarter `date` falls in
+    pub fn containing(date: NaiveDate) -> [Self; 3] {
+        let iso = date.iso_week();
+        [
+            Self::Week {
+                year: iso.year(),
+                week: iso.week(),
+            },
+            Self::Month {
+                year: date.year(),
+                month: date.month(),
+            },
+            Self::Quarter {
+                year: date.year(),
+                quarter: (date.month() - 1) / 3 + 1,
+            },
+        ]
+    }
+
+    /// Deterministic node ID, e.g. `2025-W03`
+    pub fn id(&self) -> String {
+        self.to_string()
+    }
+
+    pub fn node_type(&self) -> &'static str {
+        match self {
+            Self::Week { .. } => "week",
+            Self::Month { .. } => "month",
+            Self::Quarter { .. } => "quarter",
+        }
+    }
+
+    /// Default content of a new period node, e.g. `Week 3, 2025`
+    pub fn label(&self) -> String {
+        match *self {
+            Self::Week { year, week } => format!("Week {}, {}", week, year),
+            Self::Month { .. } => self.first_day().format("%B %Y").to_string(),
+            Self::Quarter { year, quarter } => format!("Q{} {}", quarter, year),
+        }
+    }
+
+    pub fn first_day(&self) -> NaiveDate {
+        match *self {
+            Self::Week { year, week } => NaiveDate::from_isoywd_opt(year, week, Weekday::Mon),
+            Self::Month { year, month } => NaiveDate::from_ymd_opt(year, month, 1),
+            Self::Quarter { year, quarter } => NaiveDate::from_ymd_opt(year, quarter * 3 - 2, 1),
+        }
+        .expect("periods are validated on construction")
+    }
+
+    pub fn last_day(&self) -> NaiveDate {
+        let first = self.first_day();
+        match self {
+            Self::Week { .. } => first + Days::new(6),
+            Self::Month { .. } => first + Months::new(1) - Days::new(1),
+            Self::Quarter { .. } => first + Months::new(3) - Days::new(1),
+        }
+    }
+
+    /// Every date in the period, in order
+    pub fn dates(&self) -> impl Iterator<Item = NaiveDate> {
+        let last = self.last_day();
+        self.first_day()
+            .iter_days()
+            .take_while(move |date| *date <= last)
+    }
+}
+
+impl fmt::Display for Period {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Week { year, week } => write!(f, "{:04}-W{:02}", year, week),
+            Self::Month { year, month } => write!(f, "{:04}-{:02}", year, month),
+            Self::Quarter { year, quarter } => write!(f, "{:04}-Q{}", year, quarter),
+        }
+    }
+}
+
+fn two_digits(s: &str) -> Option<u32> {
+    if s.len() != 2 || !s.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    s.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn test_parse_accepts_only_canonical_ids() {
+        for id in ["2025-W03", "2020-W53", "2025-05", "2025-12", "2025-Q2"] {
+            let period = Period::parse(id).unwrap();
+            assert_eq!(period.id(), id);
+        }
+        for id in [
+            "2025-W3",
+            "2025-W00",
+            "2025-W53",
+            "2025-5",
+            "2025-13",
+            "2025-Q0",
+            "2025-Q5",
+            "2025-q2",
+            "25-05",
+            "2025-05-01",
+            "",
+            "2025-",
+        ] {
+            assert_eq!(Period::parse(id), None, "{} should be rejected", id);
+        }
+    }
+
+    #[test]
+    fn test_period_bounds_and_labels() {
+        let week = Period::parse("2025-W01").unwrap();
+        assert_eq!(week.first_day(), date("2024-12-30"));
+        assert_eq!(week.last_day(), date("2025-01-05"));
+        assert_eq!(week.dates().count(), 7);
+        assert_eq!(week.label(), "Week 1, 2025");
+
+        let month = Period::parse("2024-02").unwrap();
+        assert_eq!(month.last_day(), date("2024-02-29"));
+        assert_eq!(month.dates().count(), 29);
+        assert_eq!(month.label(), "February 2024");
+
+        let quarter = Period::parse("2025-Q2").unwrap();
+        assert_eq!(quarter.first_day(), date("2025-04-01"));
+        assert_eq!(quarter.last_day(), date("2025-06-30"));
+        assert_eq!(quarter.label(), "Q2 2025");
+    }
+
+    #[test]
+    fn test_containing_uses_iso_week_year() {
+        let ids: Vec<_> = Period::containing(date("2024-12-31"))
+            .iter()
+            .map(Period::id)
+            .collect();
+        assert_eq!(ids, vec!["2025-W01", "2024-12", "2024-Q4"]);
+
+        for period in Period::containing(date("2025-05-14")) {
+            assert!(period.dates().any(|d| d == date("2025-05-14")));
+        }
+    }
+}
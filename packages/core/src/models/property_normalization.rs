@@ -0,0 +1,251 @@
+//! Property Normalization
+//!
+//! MCP agents and importers often send values that are almost right: `"5"`
+//! for a number, `"High"` for the enum value `high`, `"2025/01/15"` for a
+//! date. Stored as-is they never match typed queries, so `NodeService`
+//! coerces each property to its schema field type before validating:
+//!
+//! | Field type | Accepted input                     | Stored as                    |
+//! |------------|------------------------------------|------------------------------|
+//! | `number`   | `"5"`, `" 2.5 "`                   | JSON number                  |
+//! | `boolean`  | `"true"`, `"no"`, `1`              | JSON boolean                 |
+//! | `enum`     | any case of a value or its label   | the canonical value          |
+//! | `date`     | ISO, `YYYY/MM/DD`, RFC 2822, naive | `YYYY-MM-DD` or UTC RFC 3339 |
+//! |            | phrases like `next friday`         | `YYYY-MM-DD`                 |
+//! | `string`   | padded strings, numbers, booleans  | trimmed string               |
+//!
+//! Arrays are normalized item by item and objects by their nested fields.
+//! Values that can't be coerced are left for schema validation to report.
+
+use super::schema::SchemaField;
+use crate::utils::parse_natural_date;
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, SecondsFormat, Utc};
+use serde_json::{Map, Value};
+
+const DATE_FORMATS: &[&str] = &["%Y-%m-%d", "%Y/%m/%d", "%Y.%m.%d"];
+
+const DATETIME_FORMATS: &[&str] = &[
+    "%Y-%m-%dT%H:%M:%S%.f",
+    "%Y-%m-%d %H:%M:%S%.f",
+    "%Y-%m-%dT%H:%M",
+    "%Y-%m-%d %H:%M",
+];
+
+/// Normalize the values of one type namespace in place
+///
+/// Only keys with a matching schema field are touched; `_schema_version`
+/// and fields unknown to the schema pass through unchanged.
+pub fn normalize_properties(fields: &[SchemaField], properties: &mut Map<String, Value>) {
+    for field in fields {
+        if let Some(value) = properties.get_mut(&field.name) {
+            *value = field.normalize_value(std::mem::take(value));
+        }
+    }
+}
+
+impl SchemaField {
+    /// Coerce `value` to this field's type, or return it unchanged
+    pub fn normalize_value(&self, value: Value) -> Value {
+        match self.field_type.as_str() {
+            "array" => match value {
+                Value::Array(items) => Value::Array(
+                    items
+                        .into_iter()
+                        .map(|item| self.normalize_item(item))
+                        .collect(),
+                ),
+                value => value,
+            },
+            "object" => match (value, &self.fields) {
+                (Value::Object(mut properties), Some(fields)) => {
+                    normalize_properties(fields, &mut properties);
+                    Value::Object(properties)
+                }
+                (value, _) => value,
+            },
+            field_type => self.normalize_scalar(field_type, value),
+        }
+    }
+
+    fn normalize_item(&self, item: Value) -> Value {
+        match (self.item_type.as_deref(), item) {
+            (Some("object"), Value::Object(mut properties)) => {
+                if let Some(fields) = &self.item_fields {
+                    normalize_properties(fields, &mut properties);
+                }
+                Value::Object(properties)
+            }
+            (Some(item_type), item) => self.normalize_scalar(item_type, item),
+            (None, item) => item,
+        }
+    }
+
+    fn normalize_scalar(&self, field_type: &str, value: Value) -> Value {
+        let normalized = match (field_type, &value) {
+            ("number", Value::String(s)) => parse_number(s.trim()),
+            ("boolean", Value::String(s)) => parse_boolean(s.trim()),
+            ("boolean", Value::Number(n)) => match n.as_i64() {
+                Some(0) => Some(Value::Bool(false)),
+                Some(1) => Some(Value::Bool(true)),
+                _ => None,
+            },
+            ("enum", Value::String(s)) => Some(Value::String(self.canonical_enum_value(s.trim()))),
+            ("date", Value::String(s)) => Some(Value::String(
+                normalize_date(s.trim()).unwrap_or_else(|| s.trim().to_string()),
+            )),
+            ("string" | "text", Value::String(s)) => Some(Value::String(s.trim().to_string())),
+            ("string" | "text", Value::Number(n)) => Some(Value::String(n.to_string())),
+            ("string" | "text", Value::Bool(b)) => Some(Value::String(b.to_string())),
+            _ => None,
+        };
+        normalized.unwrap_or(value)
+    }
+
+    /// Value whose `value` or `label` matches `raw` ignoring case, else `raw` lowercased
+    fn canonical_enum_value(&self, raw: &str) -> String {
+        self.core_values
+            .iter()
+            .chain(&self.user_values)
+            .flatten()
+            .find(|ev| ev.value.eq_ignore_ascii_case(raw) || ev.label.eq_ignore_ascii_case(raw))
+            .map(|ev| ev.value.clone())
+            .unwrap_or_else(|| raw.to_lowercase())
+    }
+}
+
+fn parse_number(raw: &str) -> Option<Value> {
+    if let Ok(i) = raw.parse::<i64>() {
+        return Some(Value::from(i));
+    }
+    raw.parse::<f64>()
+        .ok()
+        .filter(|f| f.is_finite())
+        .map(Value::from)
+}
+
+fn parse_boolean(raw: &str) -> Option<Value> {
+    match raw.to_lowercase().as_str() {
+        "true" | "yes" | "1" => Some(Value::Bool(true)),
+        "false" | "no" | "0" => Some(Value::Bool(false)),
+        _ => None,
+    }
+}
+
+/// Date-only input becomes `YYYY-MM-DD`; input with a time becomes UTC RFC 3339
+///
+/// Natural-language dates resolve against today's local date.
+fn normalize_date(raw: &str) -> Option<String> {
+    if let Some(date) = DATE_FORMATS
+        .iter()
+        .find_map(|format| NaiveDate::parse_from_str(raw, format).ok())
+    {
+        return Some(date.format("%Y-%m-%d").to_string());
+    }
+    let utc = DateTime::parse_from_rfc3339(raw)
+        .or_else(|_| DateTime::parse_from_rfc2822(raw))
+        .map(|dt| dt.with_timezone(&Utc))
+        .ok()
+        .or_else(|| {
+            DATETIME_FORMATS
+                .iter()
+                .find_map(|format| NaiveDateTime::parse_from_str(raw, format).ok())
+                .map(|naive| naive.and_utc())
+        });
+    match utc {
+        Some(utc) => Some(utc.to_rfc3339_opts(SecondsFormat::AutoSi, true)),
+        None => parse_natural_date(raw, Local::now().date_naive())
+            .map(|date| date.format("%Y-%m-%d").to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn field(value: Value) -> SchemaField {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_scalars_are_coerced_to_field_types() {
+        let fields: Vec<SchemaField> = serde_json::from_value(json!([
+            {"name": "estimate", "type": "number", "protection": "user", "indexed": false},
+            {"name": "billable", "type": "boolean", "protection": "user", "indexed": false},
+            {"name": "owner", "type": "string", "protection": "user", "indexed": false},
+            {"name": "due_date", "type": "date", "protection": "user", "indexed": false},
+            {"name": "starts_at", "type": "date", "protection": "user", "indexed": false},
+            {"name": "priority", "type": "enum", "protection": "core", "indexed": false,
+             "coreValues": [{"value": "in_progress", "label": "In Progress"}]},
+            {"name": "stage", "type": "enum", "protection": "user", "indexed": false,
+             "userValues": [{"value": "todo", "label": "To Do"}]}
+        ]))
+        .unwrap();
+        let mut properties = json!({
+            "estimate": " 5 ",
+            "billable": "Yes",
+            "owner": "  Ada ",
+            "due_date": "2025/01/15",
+            "starts_at": "2025-01-15T09:30:00+02:00",
+            "priority": "in progress",
+            "stage": "TODO",
+            "_schema_version": 2
+        });
+
+        normalize_properties(&fields, properties.as_object_mut().unwrap());
+        assert_eq!(
+            properties,
+            json!({
+                "estimate": 5,
+                "billable": true,
+                "owner": "Ada",
+                "due_date": "2025-01-15",
+                "starts_at": "2025-01-15T07:30:00Z",
+                "priority": "in_progress",
+                "stage": "todo",
+                "_schema_version": 2
+            })
+        );
+    }
+
+    #[test]
+    fn test_uncoercible_values_are_left_for_validation() {
+        let estimate = field(json!({
+            "name": "estimate", "type": "number", "protection": "user", "indexed": false
+        }));
+        assert_eq!(estimate.normalize_value(json!("soon")), json!("soon"));
+        assert_eq!(estimate.normalize_value(json!("2.5")), json!(2.5));
+
+        let due = field(json!({
+            "name": "due", "type": "date", "protection": "user", "indexed": false
+        }));
+        assert_eq!(due.normalize_value(json!(" someday ")), json!("someday"));
+        assert_eq!(
+            due.normalize_value(json!("tomorrow")),
+            json!((Local::now().date_naive() + chrono::Days::new(1))
+                .format("%Y-%m-%d")
+                .to_string())
+        );
+    }
+
+    #[test]
+    fn test_arrays_and_objects_are_normalized_recursively() {
+        let contacts = field(json!({
+            "name": "contacts", "type": "array", "protection": "user", "indexed": false,
+            "itemType": "object",
+            "itemFields": [
+                {"name": "age", "type": "number", "protection": "user", "indexed": false}
+            ]
+        }));
+        assert_eq!(
+            contacts.normalize_value(json!([{"age": "41"}, {"age": 7}])),
+            json!([{"age": 41}, {"age": 7}])
+        );
+
+        let scores = field(json!({
+            "name": "scores", "type": "array", "protection": "user", "indexed": false,
+            "itemType": "number"
+        }));
+        assert_eq!(scores.normalize_value(json!(["1", 2])), json!([1, 2]));
+    }
+}
@@ -0,0 +1,258 @@
+//! Rollups - Aggregates of a Node's Children
+//!
+//! A node can declare rollups over its direct children, e.g. a project with
+//! "open tasks" (count where status is open), "estimate" (sum of
+//! `custom:estimate`) and "last due" (max of `due_date`). Declarations live
+//! in the type-independent `rollups` properties namespace; their values are
+//! kept up to date by NodeService and returned in [`Node::rollups`].
+//!
+//! Each child contributes one value per rollup (see [`Rollup::contribution`]),
+//! so a changed child only needs its own contribution recomputed before the
+//! values are aggregated again.
+
+use super::{Node, ValidationError};
+use crate::i18n::UserMessage;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::cmp::Ordering;
+
+/// Properties key holding a node's rollup declarations
+pub const ROLLUPS_KEY: &str = "rollups";
+
+/// How the contributions of the children are combined
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RollupFunction {
+    /// Number of matching children
+    Count,
+    /// Sum of a numeric property
+    Sum,
+    /// Smallest value of a property (numbers, or strings such as ISO dates)
+    Min,
+    /// Largest value of a property
+    Max,
+}
+
+/// Children only count when `property` equals `equals`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RollupCondition {
+    pub property: String,
+    pub equals: Value,
+}
+
+/// A rollup declared on a parent node
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Rollup {
+    /// Key of the value in [`Node::rollups`]
+    pub name: String,
+    pub function: RollupFunction,
+    /// Property aggregated; required for everything but `count`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub property: Option<String>,
+    #[serde(default, rename = "where", skip_serializing_if = "Option::is_none")]
+    pub condition: Option<RollupCondition>,
+    /// Node type of the children considered, all children when absent
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_type: Option<String>,
+}
+
+impl Rollup {
+    /// Rollups declared in `properties`, in their saved order
+    pub fn from_properties(properties: &Value) -> Vec<Self> {
+        properties
+            .get(ROLLUPS_KEY)
+            .and_then(|value| serde_json::from_value(value.clone()).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        if self.name.trim().is_empty() {
+            return Err(ValidationError::InvalidProperties(UserMessage::new(
+                "ROLLUP_NAME_MISSING",
+            )));
+        }
+        if self.function != RollupFunction::Count && self.property.is_none() {
+            return Err(ValidationError::InvalidProperties(
+                UserMessage::new("ROLLUP_PROPERTY_MISSING").with_param("name", self.name.as_str()),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Validate the rollups declared in `properties`, if present
+    pub fn validate_properties(properties: &Value) -> Result<(), ValidationError> {
+        let Some(value) = properties.get(ROLLUPS_KEY) else {
+            return Ok(());
+        };
+        let rollups: Vec<Self> = serde_json::from_value(value.clone())
+            .map_err(|e| ValidationError::malformed_property(ROLLUPS_KEY, e))?;
+        for (index, rollup) in rollups.iter().enumerate() {
+            rollup.validate()?;
+            if rollups[..index]
+                .iter()
+                .any(|other| other.name == rollup.name)
+            {
+                return Err(ValidationError::InvalidProperties(
+                    UserMessage::new("DUPLICATE_ROLLUP").with_param("name", rollup.name.as_str()),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// What `child` adds to the rollup: `None` when it doesn't match or
+    /// lacks the property
+    pub fn contribution(&self, child: &Node) -> Option<Value> {
+        if self
+            .target_type
+            .as_ref()
+            .is_some_and(|node_type| *node_type != child.node_type)
+        {
+            return None;
+        }
+        if let Some(condition) = &self.condition {
+            if child.property(&condition.property) != Some(&condition.equals) {
+                return None;
+            }
+        }
+        match &self.property {
+            None => Some(Value::Bool(true)),
+            Some(property) => child
+                .property(property)
+                .filter(|value| !value.is_null())
+                .cloned(),
+        }
+    }
+
+    /// Combine the contributions of the children
+    ///
+    /// Sums are integers while every value is one. Min and max compare
+    /// numbers with numbers and strings with strings, and are `null` when
+    /// no child contributes.
+    pub fn aggregate<'a>(&self, values: impl IntoIterator<Item = &'a Value>) -> Value {
+        let values = values.into_iter();
+        match self.function {
+            RollupFunction::Count => json!(values.count()),
+            RollupFunction::Sum => {
+                let numbers: Vec<&Value> = values.filter(|value| value.is_number()).collect();
+                if numbers.iter().all(|value| value.is_i64()) {
+                    json!(numbers
+                        .iter()
+                        .filter_map(|value| value.as_i64())
+                        .sum::<i64>())
+                } else {
+                    json!(numbers
+                        .iter()
+                        .filter_map(|value| value.as_f64())
+                        .sum::<f64>())
+                }
+            }
+            RollupFunction::Min => values
+                .min_by(|a, b| compare(a, b))
+                .cloned()
+                .unwrap_or(Value::Null),
+            RollupFunction::Max => values
+                .max_by(|a, b| compare(a, b))
+                .cloned()
+                .unwrap_or(Value::Null),
+        }
+    }
+}
+
+/// Order of two contributions; numbers sort before strings, anything else last
+fn compare(a: &Value, b: &Value) -> Ordering {
+    match (a, b) {
+        (Value::Number(a), Value::Number(b)) => a
+            .as_f64()
+            .partial_cmp(&b.as_f64())
+            .unwrap_or(Ordering::Equal),
+        (Value::String(a), Value::String(b)) => a.cmp(b),
+        _ => rank(a).cmp(&rank(b)),
+    }
+}
+
+fn rank(value: &Value) -> u8 {
+    match value {
+        Value::Number(_) => 0,
+        Value::String(_) => 1,
+        _ => 2,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rollup(value: Value) -> Rollup {
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn task(status: &str, properties: Value) -> Node {
+        let mut task = json!({ "status": status });
+        crate::utils::merge_patch(&mut task, &properties);
+        Node::new(
+            "task".to_string(),
+            "Task".to_string(),
+            json!({ "task": task }),
+        )
+    }
+
+    #[test]
+    fn test_rollups_over_children() {
+        let children = [
+            task(
+                "open",
+                json!({ "custom:estimate": 3, "due_date": "2025-03-01" }),
+            ),
+            task(
+                "done",
+                json!({ "custom:estimate": 5, "due_date": "2025-04-01" }),
+            ),
+            task("open", json!({})),
+            Node::new("text".to_string(), "Notes".to_string(), json!({})),
+        ];
+        let value = |rollup: Rollup| {
+            let contributions: Vec<Value> = children
+                .iter()
+                .filter_map(|child| rollup.contribution(child))
+                .collect();
+            rollup.aggregate(&contributions)
+        };
+
+        let open = rollup(json!({
+            "name": "open",
+            "function": "count",
+            "where": { "property": "status", "equals": "open" }
+        }));
+        assert_eq!(value(open), json!(2));
+        let estimate = rollup(json!({
+            "name": "estimate", "function": "sum", "property": "custom:estimate"
+        }));
+        assert_eq!(value(estimate), json!(8));
+        let last_due = rollup(json!({
+            "name": "lastDue", "function": "max", "property": "due_date", "targetType": "task"
+        }));
+        assert_eq!(value(last_due), json!("2025-04-01"));
+        let first_due = rollup(json!({
+            "name": "firstDue", "function": "min", "property": "due_date"
+        }));
+        assert_eq!(first_due.aggregate([]), Value::Null);
+    }
+
+    #[test]
+    fn test_validate_properties() {
+        assert!(Rollup::validate_properties(&json!({})).is_ok());
+        assert!(Rollup::validate_properties(&json!({
+            "rollups": [{ "name": "total", "function": "sum" }]
+        }))
+        .is_err());
+        assert!(Rollup::validate_properties(&json!({
+            "rollups": [
+                { "name": "n", "function": "count" },
+                { "name": "n", "function": "count" }
+            ]
+        }))
+        .is_err());
+    }
+}
@@ -62,12 +62,16 @@
 //!
 //! - **Edge table storage**: Edge table is single source of truth
 //! - **Bidirectional querying**: Both directions query the same edge table
+//! - **Inverse labels**: `reverseName` (e.g. `blocked_by` for `blocks`) names
+//!   the relationship from the target's side and can be used wherever the
+//!   relationship name can; the edge is still stored once, as `blocks`
 //! - **Edge fields**: Custom properties on the relationship itself
 //! - **Cardinality**: "one" or "many" constraints (enforced at application level)
 //!
 //! See [`docs/architecture/data/schema-relational-fields.md`] for complete details.
 
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 /// A single enum value with its display label
 ///
@@ -84,6 +88,10 @@ pub struct EnumValue {
     pub value: String,
     /// Human-readable display label for UI/MCP clients
     pub label: String,
+    /// Still accepted, but reported as a validation warning so existing
+    /// data can migrate off it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<bool>,
 }
 
 /// Protection level for schema fields
@@ -146,6 +154,10 @@ pub struct SchemaField {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub required: Option<bool>,
 
+    /// Whether a missing value is reported as a warning (the write still succeeds)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recommended: Option<bool>,
+
     /// Whether enum values can be extended by users
     #[serde(skip_serializing_if = "Option::is_none")]
     pub extensible: Option<bool>,
@@ -228,6 +240,27 @@ pub struct EdgeField {
     pub description: Option<String>,
 }
 
+impl EdgeField {
+    /// Whether `value` has this field's type
+    ///
+    /// Dates are RFC 3339 timestamps or `YYYY-MM-DD` strings and records are
+    /// node IDs. Unknown types accept anything; saving the schema already
+    /// rejects them.
+    pub fn accepts(&self, value: &serde_json::Value) -> bool {
+        match self.field_type.as_str() {
+            "string" | "text" => value.is_string(),
+            "number" => value.is_number(),
+            "boolean" => value.is_boolean(),
+            "date" => value.as_str().is_some_and(|date| {
+                chrono::DateTime::parse_from_rfc3339(date).is_ok()
+                    || chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").is_ok()
+            }),
+            "record" => value.as_str().is_some_and(|id| !id.is_empty()),
+            _ => true,
+        }
+    }
+}
+
 /// Definition of a relationship between node types
 ///
 /// Relationships create edge tables in SurrealDB. Both directions of a
@@ -260,7 +293,7 @@ pub struct SchemaRelationship {
     /// Relationship name (e.g., "billed_to", "assigned_to")
     ///
     /// Must start with a letter and contain only alphanumeric characters,
-    /// underscores, and hyphens. Reserved names: has_child, mentions, node, data
+    /// underscores, and hyphens. Reserved names: has_child, mentions, embeds, node, data
     pub name: String,
 
     /// Target node type (e.g., "customer", "person")
@@ -286,10 +319,13 @@ pub struct SchemaRelationship {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub required: Option<bool>,
 
-    /// Suggested reverse relationship name (for NLP discovery)
+    /// Inverse label: the relationship as seen from the target (e.g.
+    /// `blocked_by` for `blocks`)
     ///
-    /// This does NOT mutate the target schema. It's metadata for NLP to
-    /// understand how to describe the relationship from the target's perspective.
+    /// This does NOT mutate the target schema. Creating, deleting or listing
+    /// relationships by this label works on the edges of this relationship
+    /// with their ends swapped. A label equal to `name` marks a symmetric
+    /// relationship and is not an inverse.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub reverse_name: Option<String>,
 
@@ -326,6 +362,55 @@ impl SchemaRelationship {
                 None => format!("{}_{}", source_type, self.name),
             })
     }
+
+    /// The inverse label, unless the relationship is symmetric
+    pub fn inverse_label(&self) -> Option<&str> {
+        self.reverse_name
+            .as_deref()
+            .filter(|label| *label != self.name)
+    }
+}
+
+/// Relationship name behind each inverse label among `relationships`
+///
+/// Labels that are also relationship names are left out, and a label
+/// declared by relationships of different names stands for the first;
+/// schemas with either can't be saved (see [`check_inverse_labels`]).
+pub fn inverse_labels<'a>(relationships: &[&'a SchemaRelationship]) -> HashMap<&'a str, &'a str> {
+    let mut labels = HashMap::new();
+    for relationship in relationships {
+        if let Some(label) = relationship.inverse_label() {
+            if !relationships.iter().any(|other| other.name == label) {
+                labels.entry(label).or_insert(relationship.name.as_str());
+            }
+        }
+    }
+    labels
+}
+
+/// Check that every inverse label among `relationships` stands for a single
+/// relationship name and isn't a relationship name itself
+pub fn check_inverse_labels(relationships: &[&SchemaRelationship]) -> Result<(), String> {
+    let mut labels: HashMap<&str, &str> = HashMap::new();
+    for relationship in relationships {
+        let Some(label) = relationship.inverse_label() else {
+            continue;
+        };
+        if relationships.iter().any(|other| other.name == label) {
+            return Err(format!(
+                "Inverse label '{}' of '{}' is already a relationship name",
+                label, relationship.name
+            ));
+        }
+        let name = *labels.entry(label).or_insert(relationship.name.as_str());
+        if name != relationship.name {
+            return Err(format!(
+                "Inverse label '{}' is used by both '{}' and '{}'",
+                label, name, relationship.name
+            ));
+        }
+    }
+    Ok(())
 }
 
 /// Direction of a relationship
@@ -362,18 +447,22 @@ mod tests {
                 EnumValue {
                     value: "open".to_string(),
                     label: "Open".to_string(),
+                    deprecated: None,
                 },
                 EnumValue {
                     value: "done".to_string(),
                     label: "Done".to_string(),
+                    deprecated: None,
                 },
             ]),
             user_values: Some(vec![EnumValue {
                 value: "blocked".to_string(),
                 label: "Blocked".to_string(),
+                deprecated: None,
             }]),
             indexed: true,
             required: Some(true),
+            recommended: None,
             extensible: Some(true),
             default: Some(json!("open")),
             description: Some("Task status".to_string()),
@@ -448,6 +537,7 @@ mod tests {
             user_values: None,
             indexed: false,
             required: Some(false),
+            recommended: None,
             extensible: None,
             default: None,
             description: Some("Address information".to_string()),
@@ -461,6 +551,7 @@ mod tests {
                     user_values: None,
                     indexed: false,
                     required: Some(false),
+                    recommended: None,
                     extensible: None,
                     default: None,
                     description: Some("Street address".to_string()),
@@ -476,6 +567,7 @@ mod tests {
                     user_values: None,
                     indexed: true,
                     required: Some(false),
+                    recommended: None,
                     extensible: None,
                     default: None,
                     description: Some("City".to_string()),
@@ -532,6 +624,7 @@ mod tests {
             user_values: None,
             indexed: false,
             required: Some(false),
+            recommended: None,
             extensible: None,
             default: None,
             description: Some("Contact list".to_string()),
@@ -545,6 +638,7 @@ mod tests {
                 user_values: None,
                 indexed: true,
                 required: Some(false),
+                recommended: None,
                 extensible: None,
                 default: None,
                 description: Some("Email address".to_string()),
@@ -958,4 +1052,31 @@ mod tests {
         let edge_table = relationship.compute_edge_table_name("invoice");
         assert_eq!(edge_table, "invoice_billed_to_customer");
     }
+
+    #[test]
+    fn test_inverse_labels() {
+        let relationship = |name: &str, reverse_name: Option<&str>| SchemaRelationship {
+            name: name.to_string(),
+            target_type: None,
+            direction: RelationshipDirection::Out,
+            cardinality: RelationshipCardinality::Many,
+            required: None,
+            reverse_name: reverse_name.map(str::to_string),
+            reverse_cardinality: None,
+            edge_table: None,
+            edge_fields: None,
+            description: None,
+        };
+        let blocks = relationship("blocks", Some("blocked_by"));
+        let related = relationship("related_to", Some("related_to"));
+        let labels = inverse_labels(&[&blocks, &related]);
+        assert_eq!(labels, HashMap::from([("blocked_by", "blocks")]));
+        assert!(check_inverse_labels(&[&blocks, &related]).is_ok());
+
+        let named = relationship("blocked_by", None);
+        assert!(check_inverse_labels(&[&blocks, &named]).is_err());
+        assert!(inverse_labels(&[&blocks, &named]).is_empty());
+        let other = relationship("prevents", Some("blocked_by"));
+        assert!(check_inverse_labels(&[&blocks, &other]).is_err());
+    }
 }
@@ -0,0 +1,641 @@
+//! Schema Diff
+//!
+//! Compares two versions of a schema and classifies each difference as
+//! breaking or not. A change is breaking when nodes or edges that were valid
+//! under the old version may no longer be valid (or reachable) under the new
+//! one: removing a field, changing its type, making it required without a
+//! default, dropping enum values, retargeting a relationship and so on.
+//! Additions and cosmetic changes (labels, descriptions, indexing) are not.
+//!
+//! The diff is pure; callers decide what to do with breaking changes (the
+//! MCP `define_schema` tool refuses them unless the agent confirms).
+
+use crate::models::schema::{
+    EdgeField, EnumValue, RelationshipCardinality, SchemaField, SchemaRelationship,
+};
+use crate::models::SchemaNode;
+use serde::Serialize;
+
+/// What a [`SchemaChange`] changes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SchemaChangeKind {
+    FieldAdded,
+    FieldRemoved,
+    FieldChanged,
+    RelationshipAdded,
+    RelationshipRemoved,
+    RelationshipChanged,
+}
+
+/// One difference between two versions of a schema
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SchemaChange {
+    pub kind: SchemaChangeKind,
+    /// Dotted field path (`address.street`, with `[]` for array items) or
+    /// relationship name
+    pub path: String,
+    /// Whether existing data may stop conforming to the schema
+    pub breaking: bool,
+    /// What changed, e.g. "type changed from string to number"
+    pub detail: String,
+}
+
+impl SchemaChange {
+    fn new(kind: SchemaChangeKind, path: &str, breaking: bool, detail: impl Into<String>) -> Self {
+        Self {
+            kind,
+            path: path.to_string(),
+            breaking,
+            detail: detail.into(),
+        }
+    }
+
+    /// Top-level field or relationship the change is in
+    pub fn root_name(&self) -> &str {
+        self.path
+            .split(['.', '['])
+            .next()
+            .unwrap_or(self.path.as_str())
+    }
+}
+
+/// Differences from `old` to `new`, fields first, in `new`'s order with
+/// removals last
+pub fn diff_schemas(old: &SchemaNode, new: &SchemaNode) -> Vec<SchemaChange> {
+    let mut changes = Vec::new();
+    diff_fields("", &old.fields, &new.fields, &mut changes);
+    diff_relationships(&old.relationships, &new.relationships, &mut changes);
+    changes
+}
+
+fn field_path(prefix: &str, name: &str) -> String {
+    if prefix.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}.{}", prefix, name)
+    }
+}
+
+/// Whether a new field or relationship leaves existing nodes invalid
+fn demands_value(required: Option<bool>, default: Option<&serde_json::Value>) -> bool {
+    required == Some(true) && default.is_none()
+}
+
+fn diff_fields(
+    prefix: &str,
+    old: &[SchemaField],
+    new: &[SchemaField],
+    changes: &mut Vec<SchemaChange>,
+) {
+    for field in new {
+        let path = field_path(prefix, &field.name);
+        match old.iter().find(|f| f.name == field.name) {
+            Some(previous) => diff_field(&path, previous, field, changes),
+            None => {
+                let breaking = demands_value(field.required, field.default.as_ref());
+                let detail = if breaking {
+                    format!(
+                        "added {} field, required without a default",
+                        field.field_type
+                    )
+                } else {
+                    format!("added {} field", field.field_type)
+                };
+                changes.push(SchemaChange::new(
+                    SchemaChangeKind::FieldAdded,
+                    &path,
+                    breaking,
+                    detail,
+                ));
+            }
+        }
+    }
+    for field in old {
+        if !new.iter().any(|f| f.name == field.name) {
+            changes.push(SchemaChange::new(
+                SchemaChangeKind::FieldRemoved,
+                &field_path(prefix, &field.name),
+                true,
+                "removed; existing values are no longer described by the schema",
+            ));
+        }
+    }
+}
+
+fn enum_values(field: &SchemaField) -> Vec<&EnumValue> {
+    field
+        .core_values
+        .iter()
+        .chain(&field.user_values)
+        .flatten()
+        .collect()
+}
+
+fn diff_field(path: &str, old: &SchemaField, new: &SchemaField, changes: &mut Vec<SchemaChange>) {
+    let mut change = |breaking: bool, detail: String| {
+        changes.push(SchemaChange::new(
+            SchemaChangeKind::FieldChanged,
+            path,
+            breaking,
+            detail,
+        ))
+    };
+
+    if old.field_type != new.field_type {
+        change(
+            true,
+            format!("type changed from {} to {}", old.field_type, new.field_type),
+        );
+    }
+    if old.item_type != new.item_type {
+        change(
+            true,
+            format!(
+                "item type changed from {} to {}",
+                old.item_type.as_deref().unwrap_or("none"),
+                new.item_type.as_deref().unwrap_or("none")
+            ),
+        );
+    }
+    if old.required != Some(true) && new.required == Some(true) {
+        if new.default.is_none() {
+            change(true, "now required without a default".to_string());
+        } else {
+            change(false, "now required, with a default".to_string());
+        }
+    } else if old.required == Some(true) && new.required != Some(true) {
+        change(false, "no longer required".to_string());
+    }
+
+    let old_values = enum_values(old);
+    let new_values = enum_values(new);
+    let removed: Vec<&str> = old_values
+        .iter()
+        .filter(|v| !new_values.iter().any(|n| n.value == v.value))
+        .map(|v| v.value.as_str())
+        .collect();
+    let added: Vec<&str> = new_values
+        .iter()
+        .filter(|v| !old_values.iter().any(|o| o.value == v.value))
+        .map(|v| v.value.as_str())
+        .collect();
+    if !removed.is_empty() {
+        change(true, format!("enum values removed: {}", removed.join(", ")));
+    }
+    if !added.is_empty() {
+        change(false, format!("enum values added: {}", added.join(", ")));
+    }
+
+    let mut cosmetic = Vec::new();
+    if old.protection != new.protection {
+        cosmetic.push("protection");
+    }
+    if old.indexed != new.indexed {
+        cosmetic.push("indexed");
+    }
+    if old.recommended != new.recommended {
+        cosmetic.push("recommended");
+    }
+    if old.extensible != new.extensible {
+        cosmetic.push("extensible");
+    }
+    if old.default != new.default {
+        cosmetic.push("default");
+    }
+    if old.description != new.description {
+        cosmetic.push("description");
+    }
+    let relabelled = old_values.iter().any(|v| {
+        new_values
+            .iter()
+            .any(|n| n.value == v.value && (n.label != v.label || n.deprecated != v.deprecated))
+    });
+    if relabelled {
+        cosmetic.push("enum labels");
+    }
+    if !cosmetic.is_empty() {
+        change(false, format!("{} changed", cosmetic.join(", ")));
+    }
+
+    diff_fields(
+        path,
+        old.fields.as_deref().unwrap_or_default(),
+        new.fields.as_deref().unwrap_or_default(),
+        changes,
+    );
+    diff_fields(
+        &format!("{}[]", path),
+        old.item_fields.as_deref().unwrap_or_default(),
+        new.item_fields.as_deref().unwrap_or_default(),
+        changes,
+    );
+}
+
+fn diff_relationships(
+    old: &[SchemaRelationship],
+    new: &[SchemaRelationship],
+    changes: &mut Vec<SchemaChange>,
+) {
+    for relationship in new {
+        match old.iter().find(|r| r.name == relationship.name) {
+            Some(previous) => diff_relationship(previous, relationship, changes),
+            None => {
+                let breaking = demands_value(relationship.required, None);
+                let target = relationship.target_type.as_deref().unwrap_or("any type");
+                let detail = if breaking {
+                    format!(
+                        "added relationship to {}, required for existing nodes",
+                        target
+                    )
+                } else {
+                    format!("added relationship to {}", target)
+                };
+                changes.push(SchemaChange::new(
+                    SchemaChangeKind::RelationshipAdded,
+                    &relationship.name,
+                    breaking,
+                    detail,
+                ));
+            }
+        }
+    }
+    for relationship in old {
+        if !new.iter().any(|r| r.name == relationship.name) {
+            changes.push(SchemaChange::new(
+                SchemaChangeKind::RelationshipRemoved,
+                &relationship.name,
+                true,
+                "removed; existing edges are kept but no longer reachable through the schema",
+            ));
+        }
+    }
+}
+
+fn diff_relationship(
+    old: &SchemaRelationship,
+    new: &SchemaRelationship,
+    changes: &mut Vec<SchemaChange>,
+) {
+    let mut change = |breaking: bool, detail: String| {
+        changes.push(SchemaChange::new(
+            SchemaChangeKind::RelationshipChanged,
+            &new.name,
+            breaking,
+            detail,
+        ))
+    };
+
+    if old.target_type != new.target_type {
+        change(
+            true,
+            format!(
+                "target changed from {} to {}",
+                old.target_type.as_deref().unwrap_or("any type"),
+                new.target_type.as_deref().unwrap_or("any type")
+            ),
+        );
+    }
+    if old.direction != new.direction {
+        change(true, "direction changed".to_string());
+    }
+    // Target and name are part of the default table name
+    if old.compute_edge_table_name("") != new.compute_edge_table_name("") {
+        change(
+            true,
+            "edge table changed; existing edges stay in the old one".to_string(),
+        );
+    }
+    if old.cardinality == RelationshipCardinality::Many
+        && new.cardinality == RelationshipCardinality::One
+    {
+        change(true, "cardinality narrowed from many to one".to_string());
+    } else if old.cardinality != new.cardinality {
+        change(false, "cardinality widened from one to many".to_string());
+    }
+    if old.required != Some(true) && new.required == Some(true) {
+        change(true, "now required for existing nodes".to_string());
+    }
+
+    let old_fields = old.edge_fields.as_deref().unwrap_or_default();
+    let new_fields = new.edge_fields.as_deref().unwrap_or_default();
+    for field in new_fields {
+        match old_fields.iter().find(|f| f.name == field.name) {
+            Some(previous) => diff_edge_field(previous, field, &mut change),
+            None => {
+                if demands_value(field.required, field.default.as_ref()) {
+                    change(
+                        true,
+                        format!(
+                            "edge field '{}' added, required without a default",
+                            field.name
+                        ),
+                    );
+                } else {
+                    change(false, format!("edge field '{}' added", field.name));
+                }
+            }
+        }
+    }
+    for field in old_fields {
+        if !new_fields.iter().any(|f| f.name == field.name) {
+            change(true, format!("edge field '{}' removed", field.name));
+        }
+    }
+
+    let mut cosmetic = Vec::new();
+    if old.required == Some(true) && new.required != Some(true) {
+        cosmetic.push("required");
+    }
+    if old.reverse_name != new.reverse_name {
+        cosmetic.push("reverseName");
+    }
+    if old.reverse_cardinality != new.reverse_cardinality {
+        cosmetic.push("reverseCardinality");
+    }
+    if old.description != new.description {
+        cosmetic.push("description");
+    }
+    if !cosmetic.is_empty() {
+        change(false, format!("{} changed", cosmetic.join(", ")));
+    }
+}
+
+fn diff_edge_field(old: &EdgeField, new: &EdgeField, change: &mut impl FnMut(bool, String)) {
+    if old.field_type != new.field_type || old.target_type != new.target_type {
+        change(
+            true,
+            format!(
+                "edge field '{}' type changed from {} to {}",
+                new.name, old.field_type, new.field_type
+            ),
+        );
+    }
+    if old.required != Some(true) && demands_value(new.required, new.default.as_ref()) {
+        change(
+            true,
+            format!("edge field '{}' now required without a default", new.name),
+        );
+    }
+    if old.indexed != new.indexed
+        || old.default != new.default
+        || old.description != new.description
+    {
+        change(false, format!("edge field '{}' changed", new.name));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::schema::{RelationshipDirection, SchemaProtectionLevel};
+    use chrono::Utc;
+    use serde_json::json;
+
+    fn field(name: &str, field_type: &str) -> SchemaField {
+        SchemaField {
+            name: name.to_string(),
+            field_type: field_type.to_string(),
+            protection: SchemaProtectionLevel::User,
+            core_values: None,
+            user_values: None,
+            indexed: false,
+            required: None,
+            recommended: None,
+            extensible: None,
+            default: None,
+            description: None,
+            item_type: None,
+            fields: None,
+            item_fields: None,
+        }
+    }
+
+    fn enum_field(name: &str, values: &[&str]) -> SchemaField {
+        SchemaField {
+            user_values: Some(
+                values
+                    .iter()
+                    .map(|value| EnumValue {
+                        value: value.to_string(),
+                        label: value.to_uppercase(),
+                        deprecated: None,
+                    })
+                    .collect(),
+            ),
+            ..field(name, "enum")
+        }
+    }
+
+    fn relationship(name: &str, target: &str) -> SchemaRelationship {
+        SchemaRelationship {
+            name: name.to_string(),
+            target_type: Some(target.to_string()),
+            direction: RelationshipDirection::Out,
+            cardinality: RelationshipCardinality::Many,
+            required: None,
+            reverse_name: None,
+            reverse_cardinality: None,
+            edge_table: None,
+            edge_fields: None,
+            description: None,
+        }
+    }
+
+    fn schema(fields: Vec<SchemaField>, relationships: Vec<SchemaRelationship>) -> SchemaNode {
+        SchemaNode {
+            id: "invoice".to_string(),
+            content: "Invoice".to_string(),
+            version: 1,
+            created_at: Utc::now(),
+            modified_at: Utc::now(),
+            is_core: false,
+            schema_version: 1,
+            description: String::new(),
+            fields,
+            relationships,
+        }
+    }
+
+    fn summary(changes: &[SchemaChange]) -> Vec<(SchemaChangeKind, &str, bool)> {
+        changes
+            .iter()
+            .map(|c| (c.kind, c.path.as_str(), c.breaking))
+            .collect()
+    }
+
+    #[test]
+    fn test_identical_schemas_have_no_changes() {
+        let old = schema(
+            vec![enum_field("status", &["draft", "paid"])],
+            vec![relationship("billed_to", "customer")],
+        );
+        assert!(diff_schemas(&old, &old.clone()).is_empty());
+    }
+
+    #[test]
+    fn test_field_changes() {
+        let mut amount = field("amount", "number");
+        amount.required = Some(true);
+        let old = schema(
+            vec![
+                field("number", "string"),
+                enum_field("status", &["draft", "sent", "paid"]),
+                field("notes", "string"),
+                field("memo", "string"),
+            ],
+            vec![],
+        );
+
+        let mut notes = field("notes", "string");
+        notes.indexed = true;
+        let mut memo = field("memo", "string");
+        memo.required = Some(true);
+        memo.default = Some(json!(""));
+        let new = schema(
+            vec![
+                field("number", "number"),
+                enum_field("status", &["draft", "paid", "void"]),
+                notes,
+                memo,
+                amount,
+                field("due", "date"),
+            ],
+            vec![],
+        );
+
+        let changes = diff_schemas(&old, &new);
+        assert_eq!(
+            summary(&changes),
+            vec![
+                (SchemaChangeKind::FieldChanged, "number", true),
+                (SchemaChangeKind::FieldChanged, "status", true),
+                (SchemaChangeKind::FieldChanged, "status", false),
+                (SchemaChangeKind::FieldChanged, "notes", false),
+                (SchemaChangeKind::FieldChanged, "memo", false),
+                (SchemaChangeKind::FieldChanged, "memo", false),
+                (SchemaChangeKind::FieldAdded, "amount", true),
+                (SchemaChangeKind::FieldAdded, "due", false),
+            ]
+        );
+        assert_eq!(changes[1].detail, "enum values removed: sent");
+        assert_eq!(changes[2].detail, "enum values added: void");
+
+        let removed = diff_schemas(&new, &schema(vec![], vec![]));
+        assert!(removed
+            .iter()
+            .all(|c| c.kind == SchemaChangeKind::FieldRemoved && c.breaking));
+        assert_eq!(removed.len(), 6);
+    }
+
+    #[test]
+    fn test_nested_fields_use_paths() {
+        let mut address = field("address", "object");
+        address.fields = Some(vec![field("street", "string")]);
+        let mut contacts = field("contacts", "array");
+        contacts.item_type = Some("object".to_string());
+        contacts.item_fields = Some(vec![field("email", "string")]);
+        let old = schema(vec![address.clone(), contacts.clone()], vec![]);
+
+        address.fields = Some(vec![field("street", "string"), field("zip", "string")]);
+        contacts.item_fields = Some(vec![]);
+        let new = schema(vec![address, contacts], vec![]);
+
+        let changes = diff_schemas(&old, &new);
+        assert_eq!(
+            summary(&changes),
+            vec![
+                (SchemaChangeKind::FieldAdded, "address.zip", false),
+                (SchemaChangeKind::FieldRemoved, "contacts[].email", true),
+            ]
+        );
+        assert_eq!(changes[1].root_name(), "contacts");
+    }
+
+    #[test]
+    fn test_relationship_changes() {
+        let mut owner = relationship("owner", "person");
+        owner.cardinality = RelationshipCardinality::One;
+        let old = schema(
+            vec![],
+            vec![
+                relationship("billed_to", "customer"),
+                relationship("tags", "tag"),
+                owner.clone(),
+                relationship("approver", "person"),
+            ],
+        );
+
+        let mut tags = relationship("tags", "tag");
+        tags.cardinality = RelationshipCardinality::One;
+        owner.cardinality = RelationshipCardinality::Many;
+        owner.reverse_name = Some("owned".to_string());
+        let mut reviewer = relationship("reviewer", "person");
+        reviewer.required = Some(true);
+        let new = schema(
+            vec![],
+            vec![
+                relationship("billed_to", "company"),
+                tags,
+                owner,
+                relationship("project", "project"),
+                reviewer,
+            ],
+        );
+
+        let changes = diff_schemas(&old, &new);
+        assert_eq!(
+            summary(&changes),
+            vec![
+                (SchemaChangeKind::RelationshipChanged, "billed_to", true),
+                (SchemaChangeKind::RelationshipChanged, "billed_to", true),
+                (SchemaChangeKind::RelationshipChanged, "tags", true),
+                (SchemaChangeKind::RelationshipChanged, "owner", false),
+                (SchemaChangeKind::RelationshipChanged, "owner", false),
+                (SchemaChangeKind::RelationshipAdded, "project", false),
+                (SchemaChangeKind::RelationshipAdded, "reviewer", true),
+                (SchemaChangeKind::RelationshipRemoved, "approver", true),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_edge_field_changes() {
+        let mut old_relationship = relationship("assigned_to", "person");
+        old_relationship.edge_fields = Some(vec![EdgeField {
+            name: "role".to_string(),
+            field_type: "string".to_string(),
+            indexed: None,
+            required: None,
+            default: None,
+            target_type: None,
+            description: None,
+        }]);
+        let mut new_relationship = relationship("assigned_to", "person");
+        new_relationship.edge_fields = Some(vec![EdgeField {
+            name: "hours".to_string(),
+            field_type: "number".to_string(),
+            indexed: None,
+            required: Some(true),
+            default: None,
+            target_type: None,
+            description: None,
+        }]);
+
+        let changes = diff_schemas(
+            &schema(vec![], vec![old_relationship]),
+            &schema(vec![], vec![new_relationship]),
+        );
+        assert_eq!(
+            changes
+                .iter()
+                .map(|c| (c.detail.as_str(), c.breaking))
+                .collect::<Vec<_>>(),
+            vec![
+                ("edge field 'hours' added, required without a default", true),
+                ("edge field 'role' removed", true),
+            ]
+        );
+    }
+}
@@ -40,6 +40,7 @@ use crate::models::schema::{EnumValue, SchemaField, SchemaProtectionLevel, Schem
 use crate::models::{Node, ValidationError};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 /// Strongly-typed schema node with direct field access
 ///
@@ -117,10 +118,7 @@ impl SchemaNode {
     /// Returns `ValidationError::InvalidNodeType` if the node type is not "schema".
     pub fn from_node(node: Node) -> Result<Self, ValidationError> {
         if node.node_type != "schema" {
-            return Err(ValidationError::InvalidNodeType(format!(
-                "Expected 'schema', got '{}'",
-                node.node_type
-            )));
+            return Err(ValidationError::invalid_node_type("schema", node.node_type));
         }
 
         // Extract fields from properties JSON
@@ -195,6 +193,7 @@ impl SchemaNode {
             member_of: Vec::new(),
             title: None, // Schema nodes don't have indexed titles
             lifecycle_status: "active".to_string(),
+            rollups: BTreeMap::new(),
         }
     }
 
@@ -350,7 +349,7 @@ mod tests {
         assert!(result
             .unwrap_err()
             .to_string()
-            .contains("Expected 'schema'"));
+            .contains("expected 'schema'"));
     }
 
     #[test]
@@ -417,6 +416,7 @@ mod tests {
             user_values: None,
             indexed: false,
             required: Some(false),
+            recommended: None,
             extensible: None,
             default: Some(json!(0)),
             description: Some("Priority level".to_string()),
@@ -0,0 +1,252 @@
+//! Type-Safe ScriptNode Wrapper
+//!
+//! Script nodes hold small user scripts that run when nodes of a given type are
+//! created or updated. The script source lives in `content`; binding metadata
+//! lives in the `script` property namespace:
+//!
+//! - `target_type` - Node type the script is bound to (e.g. `"task"`)
+//! - `hook` - Lifecycle hook (`"on_create"` or `"on_update"`)
+//! - `enabled` - Whether the script currently runs (defaults to `true`)
+//!
+//! Execution is handled by `ScriptService`; this wrapper only exposes the
+//! binding in a typed form.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use nodespace_core::models::{ScriptHook, ScriptNode};
+//!
+//! let script = ScriptNode::builder("task", ScriptHook::OnCreate, r#"set_property("priority", "high");"#)
+//!     .build();
+//!
+//! assert_eq!(script.target_type(), "task");
+//! assert_eq!(script.hook(), Some(ScriptHook::OnCreate));
+//! assert!(script.is_enabled());
+//! ```
+
+use crate::models::{Node, ValidationError};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+/// Lifecycle hook a script is bound to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScriptHook {
+    /// Runs after a node of the target type is created
+    OnCreate,
+    /// Runs after a node of the target type is updated
+    OnUpdate,
+}
+
+impl ScriptHook {
+    /// Storage representation used in the `hook` property
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ScriptHook::OnCreate => "on_create",
+            ScriptHook::OnUpdate => "on_update",
+        }
+    }
+
+    /// Parse the storage representation, returning `None` for unknown hooks
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "on_create" => Some(ScriptHook::OnCreate),
+            "on_update" => Some(ScriptHook::OnUpdate),
+            _ => None,
+        }
+    }
+}
+
+/// Type-safe wrapper for script nodes
+#[derive(Debug, Clone)]
+pub struct ScriptNode {
+    node: Node,
+}
+
+impl ScriptNode {
+    /// Create a ScriptNode from an existing Node
+    ///
+    /// # Errors
+    ///
+    /// Returns `ValidationError::InvalidNodeType` if the node type is not "script".
+    pub fn from_node(node: Node) -> Result<Self, ValidationError> {
+        if node.node_type != "script" {
+            return Err(ValidationError::invalid_node_type("script", node.node_type));
+        }
+        Ok(Self { node })
+    }
+
+    /// Create a builder for a new ScriptNode
+    ///
+    /// # Arguments
+    ///
+    /// * `target_type` - Node type whose lifecycle triggers the script
+    /// * `hook` - Lifecycle hook to bind to
+    /// * `source` - Script source code
+    pub fn builder(
+        target_type: impl Into<String>,
+        hook: ScriptHook,
+        source: impl Into<String>,
+    ) -> ScriptNodeBuilder {
+        ScriptNodeBuilder {
+            target_type: target_type.into(),
+            hook,
+            source: source.into(),
+            enabled: true,
+        }
+    }
+
+    /// Get the script ID
+    pub fn id(&self) -> &str {
+        &self.node.id
+    }
+
+    /// Get the script source code (stored in `content`)
+    pub fn source(&self) -> &str {
+        &self.node.content
+    }
+
+    /// Get the node type this script is bound to
+    ///
+    /// Returns an empty string if the binding is missing.
+    pub fn target_type(&self) -> &str {
+        self.property("target_type")
+            .and_then(|v| v.as_str())
+            .unwrap_or("")
+    }
+
+    /// Get the lifecycle hook, or `None` if missing or unrecognised
+    pub fn hook(&self) -> Option<ScriptHook> {
+        self.property("hook")
+            .and_then(|v| v.as_str())
+            .and_then(ScriptHook::parse)
+    }
+
+    /// Whether the script is enabled (defaults to `true` when unset)
+    pub fn is_enabled(&self) -> bool {
+        self.property("enabled")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(true)
+    }
+
+    /// Whether this script should run for the given node type and hook
+    pub fn matches(&self, node_type: &str, hook: ScriptHook) -> bool {
+        self.is_enabled() && self.hook() == Some(hook) && self.target_type() == node_type
+    }
+
+    /// Get a reference to the underlying Node
+    pub fn as_node(&self) -> &Node {
+        &self.node
+    }
+
+    /// Convert back to universal Node (consumes wrapper)
+    pub fn into_node(self) -> Node {
+        self.node
+    }
+
+    /// Read a binding property, preferring the `script` namespace over flat storage
+    fn property(&self, key: &str) -> Option<&serde_json::Value> {
+        self.node
+            .properties
+            .get("script")
+            .and_then(|ns| ns.get(key))
+            .or_else(|| self.node.properties.get(key))
+    }
+}
+
+/// Builder for creating new ScriptNode instances
+pub struct ScriptNodeBuilder {
+    target_type: String,
+    hook: ScriptHook,
+    source: String,
+    enabled: bool,
+}
+
+impl ScriptNodeBuilder {
+    /// Set whether the script is enabled
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    /// Build the ScriptNode
+    pub fn build(self) -> ScriptNode {
+        let node = Node::new(
+            "script".to_string(),
+            self.source,
+            json!({
+                "script": {
+                    "target_type": self.target_type,
+                    "hook": self.hook.as_str(),
+                    "enabled": self.enabled,
+                }
+            }),
+        );
+
+        ScriptNode { node }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_script_node_builder() {
+        let script = ScriptNode::builder("task", ScriptHook::OnUpdate, "let x = 1;")
+            .enabled(false)
+            .build();
+
+        assert_eq!(script.as_node().node_type, "script");
+        assert_eq!(script.source(), "let x = 1;");
+        assert_eq!(script.target_type(), "task");
+        assert_eq!(script.hook(), Some(ScriptHook::OnUpdate));
+        assert!(!script.is_enabled());
+    }
+
+    #[test]
+    fn test_script_node_from_wrong_type() {
+        let node = Node::new("text".to_string(), "x".to_string(), json!({}));
+        assert!(ScriptNode::from_node(node).is_err());
+    }
+
+    #[test]
+    fn test_script_node_reads_flat_properties() {
+        let node = Node::new(
+            "script".to_string(),
+            "1".to_string(),
+            json!({"target_type": "text", "hook": "on_create"}),
+        );
+        let script = ScriptNode::from_node(node).unwrap();
+
+        assert_eq!(script.target_type(), "text");
+        assert_eq!(script.hook(), Some(ScriptHook::OnCreate));
+        assert!(script.is_enabled());
+    }
+
+    #[test]
+    fn test_script_node_matches() {
+        let script = ScriptNode::builder("task", ScriptHook::OnCreate, "").build();
+
+        assert!(script.matches("task", ScriptHook::OnCreate));
+        assert!(!script.matches("task", ScriptHook::OnUpdate));
+        assert!(!script.matches("text", ScriptHook::OnCreate));
+
+        let disabled = ScriptNode::builder("task", ScriptHook::OnCreate, "")
+            .enabled(false)
+            .build();
+        assert!(!disabled.matches("task", ScriptHook::OnCreate));
+    }
+
+    #[test]
+    fn test_unknown_hook_is_none() {
+        let node = Node::new(
+            "script".to_string(),
+            String::new(),
+            json!({"script": {"target_type": "task", "hook": "on_delete"}}),
+        );
+        let script = ScriptNode::from_node(node).unwrap();
+        assert_eq!(script.hook(), None);
+        assert!(!script.matches("task", ScriptHook::OnCreate));
+    }
+}
@@ -0,0 +1,41 @@
+//! Share Records - Revocation Tracking for Published Subtrees
+//!
+//! Each bundle created by `NodeService::create_share_bundle` gets a record in
+//! the `share` table. The record holds the per-share signing secret, so a
+//! signed JSON bundle can be verified later, and the revocation timestamp.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A published share of a subtree
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShareRecord {
+    pub share_id: String,
+    /// Root of the shared subtree (never written into the bundle itself)
+    pub root_id: String,
+    /// HMAC-SHA256 key for the signed JSON bundle; kept out of API responses
+    #[serde(skip_serializing, default)]
+    pub secret: String,
+    pub created_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+impl ShareRecord {
+    /// A fresh, unrevoked share of `root_id` with a random ID and secret
+    pub fn new(root_id: &str) -> Self {
+        Self {
+            share_id: Uuid::new_v4().to_string(),
+            root_id: root_id.to_string(),
+            secret: format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple()),
+            created_at: Utc::now(),
+            revoked_at: None,
+        }
+    }
+
+    pub fn is_revoked(&self) -> bool {
+        self.revoked_at.is_some()
+    }
+}
@@ -0,0 +1,149 @@
+//! Generic Typed Spoke Nodes
+//!
+//! Task and schema nodes have hand-written store getters. Other schema-backed
+//! types only need their namespaced properties (`properties[node_type]`)
+//! deserialized into a struct, so they share one getter,
+//! `SurrealStore::get_typed_node`, driven by the [`SpokeModel`] trait.
+//!
+//! Declare a new type with [`spoke_model!`](crate::spoke_model), which
+//! derives serde for the fields and implements [`SpokeModel`]:
+//!
+//! ```rust
+//! nodespace_core::spoke_model! {
+//!     /// Invoice fields
+//!     pub struct Invoice: "invoice" {
+//!         pub number: Option<String>,
+//!         pub amount: Option<f64>,
+//!     }
+//! }
+//! ```
+//!
+//! `TypedNode<Invoice>` then serializes as the node's metadata with the
+//! invoice fields alongside, like `TaskNode` does for tasks.
+
+use chrono::{DateTime, Utc};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+/// Fields of a schema-backed node type, read from `properties[NODE_TYPE]`
+pub trait SpokeModel: DeserializeOwned + Serialize + Send {
+    const NODE_TYPE: &'static str;
+}
+
+/// A node with its type-specific fields deserialized into `T`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TypedNode<T> {
+    pub id: String,
+    pub node_type: String,
+    pub content: String,
+    pub version: i64,
+    pub created_at: DateTime<Utc>,
+    pub modified_at: DateTime<Utc>,
+    #[serde(flatten)]
+    pub fields: T,
+}
+
+/// Row shape of the typed-node query, before `fields` is converted
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TypedNodeRow {
+    pub id: String,
+    pub node_type: String,
+    #[serde(default)]
+    pub content: String,
+    #[serde(default = "default_version")]
+    pub version: i64,
+    pub created_at: DateTime<Utc>,
+    pub modified_at: DateTime<Utc>,
+    #[serde(default)]
+    pub fields: serde_json::Value,
+}
+
+fn default_version() -> i64 {
+    1
+}
+
+impl TypedNodeRow {
+    pub(crate) fn into_typed<T: SpokeModel>(self) -> Result<TypedNode<T>, serde_json::Error> {
+        let fields = match self.fields {
+            serde_json::Value::Null => serde_json::json!({}),
+            fields => fields,
+        };
+        Ok(TypedNode {
+            id: self.id,
+            node_type: self.node_type,
+            content: self.content,
+            version: self.version,
+            created_at: self.created_at,
+            modified_at: self.modified_at,
+            fields: serde_json::from_value(fields)?,
+        })
+    }
+}
+
+/// Declare a spoke struct and implement [`SpokeModel`] for it
+///
+/// Every field must implement `Default`; missing properties take it.
+#[macro_export]
+macro_rules! spoke_model {
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident : $node_type:literal {
+            $($(#[$field_meta:meta])* $field_vis:vis $field:ident : $field_ty:ty),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Default, PartialEq, ::serde::Serialize, ::serde::Deserialize)]
+        #[serde(default)]
+        $vis struct $name {
+            $($(#[$field_meta])* $field_vis $field: $field_ty),*
+        }
+
+        impl $crate::models::SpokeModel for $name {
+            const NODE_TYPE: &'static str = $node_type;
+        }
+    };
+}
+
+spoke_model! {
+    /// Contact details of a `person` node
+    pub struct Person: "person" {
+        pub email: Option<String>,
+        pub organization: Option<String>,
+        pub phone: Option<String>,
+        /// Free-form fields such as `linkedin` or `birthday`
+        pub custom: serde_json::Value,
+    }
+}
+
+pub type PersonNode = TypedNode<Person>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_row_converts_to_typed_node_with_defaults() {
+        let row: TypedNodeRow = serde_json::from_value(json!({
+            "id": "p1",
+            "nodeType": "person",
+            "content": "Ada Lovelace",
+            "version": 3,
+            "createdAt": "2025-01-01T00:00:00Z",
+            "modifiedAt": "2025-01-02T00:00:00Z",
+            "fields": {"email": "ada@example.com", "unknown": true}
+        }))
+        .unwrap();
+
+        let person: PersonNode = row.into_typed().unwrap();
+        assert_eq!(person.fields.email.as_deref(), Some("ada@example.com"));
+        assert_eq!(person.fields.phone, None);
+        assert_eq!(Person::NODE_TYPE, "person");
+
+        let json = serde_json::to_value(&person).unwrap();
+        assert_eq!(json["nodeType"], "person");
+        assert_eq!(json["email"], "ada@example.com");
+    }
+}
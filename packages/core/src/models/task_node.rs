@@ -49,6 +49,7 @@ use crate::models::{Node, ValidationError};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
+use std::collections::BTreeMap;
 use std::str::FromStr;
 
 /// Custom deserializer for flexible date parsing
@@ -93,8 +94,15 @@ mod flexible_date {
                 datetime, Utc,
             ))));
         }
+        // Typed entry such as "tomorrow" or "next friday"
+        if let Some(date) = crate::utils::parse_natural_date(s, chrono::Local::now().date_naive()) {
+            let datetime = date.and_time(NaiveTime::MIN);
+            return Ok(Some(Some(DateTime::from_naive_utc_and_offset(
+                datetime, Utc,
+            ))));
+        }
         Err(format!(
-            "Invalid date format: '{}'. Expected YYYY-MM-DD or ISO8601",
+            "Invalid date format: '{}'. Expected YYYY-MM-DD, ISO8601 or a date like 'next friday'",
             s
         ))
     }
@@ -263,6 +271,41 @@ impl<'de> Deserialize<'de> for TaskPriority {
     }
 }
 
+/// One stretch of tracked work on a task
+///
+/// Stored as an element of `properties.task.time_sessions`. Only the last
+/// session may be running, i.e. have no `ended_at`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimeSession {
+    pub started_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ended_at: Option<DateTime<Utc>>,
+}
+
+impl TimeSession {
+    /// Whether the timer for this session is still running
+    pub fn is_running(&self) -> bool {
+        self.ended_at.is_none()
+    }
+
+    /// Length of the session, counting a running session up to `now`
+    pub fn duration(&self, now: DateTime<Utc>) -> chrono::Duration {
+        (self.ended_at.unwrap_or(now) - self.started_at).max(chrono::Duration::zero())
+    }
+}
+
+/// Time tracked on one day within one root node (document or date page)
+///
+/// Rows of `NodeService::tracked_time_report`. A session counts towards the
+/// local day it started on.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrackedTimeEntry {
+    pub date: chrono::NaiveDate,
+    pub root_id: String,
+    pub seconds: i64,
+}
+
 /// Strongly-typed task node with direct field access
 ///
 /// Uses Universal Graph Architecture - properties stored in node.properties JSON.
@@ -366,6 +409,10 @@ pub struct TaskNode {
     /// Completed at timestamp (when task moved to done)
     #[serde(default)]
     pub completed_at: Option<DateTime<Utc>>,
+
+    /// Tracked work sessions, oldest first
+    #[serde(default)]
+    pub time_sessions: Vec<TimeSession>,
 }
 
 fn default_version() -> i64 {
@@ -392,10 +439,7 @@ impl TaskNode {
     /// Returns `ValidationError::InvalidNodeType` if the node type is not "task".
     pub fn from_node(node: Node) -> Result<Self, ValidationError> {
         if node.node_type != "task" {
-            return Err(ValidationError::InvalidNodeType(format!(
-                "Expected 'task', got '{}'",
-                node.node_type
-            )));
+            return Err(ValidationError::invalid_node_type("task", node.node_type));
         }
 
         // Try new nested format first, fall back to old flat format (Issue #397)
@@ -450,6 +494,12 @@ impl TaskNode {
             .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
             .map(|dt| dt.with_timezone(&Utc));
 
+        // Extract time tracking sessions; malformed entries drop the whole list
+        let time_sessions: Vec<TimeSession> = props
+            .get("time_sessions")
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+            .unwrap_or_default();
+
         // Build properties object for schema-driven UI compatibility
         // Use camelCase keys per naming conventions (snake_case in DB, camelCase in JSON API)
         let mut props = serde_json::Map::new();
@@ -469,6 +519,9 @@ impl TaskNode {
         if let Some(ref c) = completed_at {
             props.insert("completedAt".to_string(), json!(c.to_rfc3339()));
         }
+        if !time_sessions.is_empty() {
+            props.insert("timeSessions".to_string(), json!(time_sessions));
+        }
         props.insert("_schemaVersion".to_string(), json!(1));
 
         Ok(Self {
@@ -485,6 +538,7 @@ impl TaskNode {
             assignee,
             started_at,
             completed_at,
+            time_sessions,
         })
     }
 
@@ -527,6 +581,10 @@ impl TaskNode {
             properties.insert("completedAt".to_string(), json!(completed_at.to_rfc3339()));
         }
 
+        if !self.time_sessions.is_empty() {
+            properties.insert("timeSessions".to_string(), json!(self.time_sessions));
+        }
+
         Node {
             id: self.id,
             node_type: "task".to_string(),
@@ -540,6 +598,7 @@ impl TaskNode {
             member_of: Vec::new(),
             title: Some(crate::utils::strip_markdown(&self.content)), // Task nodes have indexed titles
             lifecycle_status: "active".to_string(),
+            rollups: BTreeMap::new(),
         }
     }
 
@@ -598,6 +657,21 @@ impl TaskNode {
         self.assignee = assignee_id;
         self.modified_at = Utc::now();
     }
+
+    /// The session whose timer is running, if any
+    pub fn running_session(&self) -> Option<&TimeSession> {
+        self.time_sessions
+            .last()
+            .filter(|session| session.is_running())
+    }
+
+    /// Total tracked time, counting a running session up to `now`
+    pub fn tracked_time(&self, now: DateTime<Utc>) -> chrono::Duration {
+        self.time_sessions
+            .iter()
+            .map(|session| session.duration(now))
+            .fold(chrono::Duration::zero(), |total, d| total + d)
+    }
 }
 
 /// Builder for creating new TaskNode instances
@@ -678,6 +752,7 @@ impl TaskNodeBuilder {
             assignee: self.assignee,
             started_at: None,
             completed_at: None,
+            time_sessions: Vec::new(),
         }
     }
 }
@@ -835,6 +910,31 @@ impl TaskNodeUpdate {
     }
 }
 
+/// Narrows the due-date task queries (`NodeService::get_overdue_tasks`,
+/// `NodeService::get_tasks_due_within`); unset fields match every task
+///
+/// # Examples
+///
+/// ```rust
+/// use nodespace_core::models::{TaskDueFilter, TaskPriority};
+///
+/// let filter = TaskDueFilter {
+///     assignee: Some("person-1".to_string()),
+///     priority: Some(TaskPriority::High),
+/// };
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskDueFilter {
+    /// Only tasks assigned to this assignee
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub assignee: Option<String>,
+
+    /// Only tasks with this priority
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<TaskPriority>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -878,4 +978,27 @@ mod tests {
             "Empty JSON should result in empty update"
         );
     }
+
+    #[test]
+    fn test_time_sessions_from_properties() {
+        let node = Node::new(
+            "task".to_string(),
+            "Write report".to_string(),
+            json!({ "task": { "status": "in_progress", "time_sessions": [
+                { "started_at": "2025-01-15T09:00:00Z", "ended_at": "2025-01-15T10:30:00Z" },
+                { "started_at": "2025-01-15T13:00:00Z" }
+            ]}}),
+        );
+        let task = TaskNode::from_node(node).unwrap();
+
+        assert_eq!(task.time_sessions.len(), 2);
+        assert_eq!(
+            task.running_session().map(|s| s.started_at.to_rfc3339()),
+            Some("2025-01-15T13:00:00+00:00".to_string())
+        );
+        let now = DateTime::parse_from_rfc3339("2025-01-15T13:15:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        assert_eq!(task.tracked_time(now), chrono::Duration::minutes(105));
+    }
 }
@@ -14,7 +14,7 @@ mod tests {
         let wrong_type = Node::new("text".to_string(), "Test".to_string(), json!({}));
         let result = TaskNode::from_node(wrong_type);
         assert!(result.is_err());
-        assert!(result.unwrap_err().to_string().contains("Expected 'task'"));
+        assert!(result.unwrap_err().to_string().contains("expected 'task'"));
     }
 
     #[test]
@@ -65,10 +65,7 @@ impl TextNode {
     /// ```
     pub fn from_node(node: Node) -> Result<Self, ValidationError> {
         if node.node_type != "text" {
-            return Err(ValidationError::InvalidNodeType(format!(
-                "Expected 'text', got '{}'",
-                node.node_type
-            )));
+            return Err(ValidationError::invalid_node_type("text", node.node_type));
         }
         Ok(Self { node })
     }
@@ -13,7 +13,7 @@ mod tests {
         let wrong_type = Node::new("task".to_string(), "Content".to_string(), json!({}));
         let result = TextNode::from_node(wrong_type);
         assert!(result.is_err());
-        assert!(result.unwrap_err().to_string().contains("Expected 'text'"));
+        assert!(result.unwrap_err().to_string().contains("expected 'text'"));
     }
 
     #[test]
@@ -0,0 +1,146 @@
+//! Validation Reports
+//!
+//! Behavior and schema validation reject a write with a hard error. Some
+//! issues are worth telling the caller about without rejecting anything:
+//!
+//! - a property the type's schema doesn't define (often a typo)
+//! - an enum value the schema marks `deprecated`
+//! - a missing field the schema marks `recommended`
+//!
+//! [`ValidationReport`] carries both kinds, so the UI and MCP clients can
+//! show warnings next to a write that succeeded.
+
+use super::schema::SchemaField;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// What a [`ValidationWarning`] is about
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ValidationWarningKind {
+    UnknownProperty,
+    DeprecatedEnumValue,
+    MissingRecommendedField,
+}
+
+/// A non-fatal validation issue
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidationWarning {
+    pub kind: ValidationWarningKind,
+    /// Property the warning refers to
+    pub field: String,
+    pub message: String,
+}
+
+/// Outcome of validating a node: errors reject a write, warnings don't
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidationReport {
+    pub errors: Vec<String>,
+    pub warnings: Vec<ValidationWarning>,
+}
+
+impl ValidationReport {
+    pub fn is_valid(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+/// Warnings for one type namespace checked against its schema fields
+///
+/// Keys starting with `_` (such as `_schema_version`) are internal and
+/// never reported as unknown.
+pub fn schema_warnings(
+    node_type: &str,
+    fields: &[SchemaField],
+    properties: Option<&Map<String, Value>>,
+) -> Vec<ValidationWarning> {
+    let mut warnings = Vec::new();
+
+    if let Some(properties) = properties {
+        for key in properties.keys() {
+            if !key.starts_with('_') && !fields.iter().any(|field| &field.name == key) {
+                warnings.push(ValidationWarning {
+                    kind: ValidationWarningKind::UnknownProperty,
+                    field: key.clone(),
+                    message: format!("'{}' is not a field of the {} schema", key, node_type),
+                });
+            }
+        }
+    }
+
+    for field in fields {
+        let value = properties
+            .and_then(|properties| properties.get(&field.name))
+            .filter(|value| !value.is_null());
+
+        if field.recommended == Some(true) && value.is_none() {
+            warnings.push(ValidationWarning {
+                kind: ValidationWarningKind::MissingRecommendedField,
+                field: field.name.clone(),
+                message: format!("Recommended field '{}' is not set", field.name),
+            });
+        }
+
+        if field.field_type != "enum" {
+            continue;
+        }
+        let Some(value) = value.and_then(Value::as_str) else {
+            continue;
+        };
+        let deprecated = field
+            .core_values
+            .iter()
+            .chain(&field.user_values)
+            .flatten()
+            .any(|ev| ev.value == value && ev.deprecated == Some(true));
+        if deprecated {
+            warnings.push(ValidationWarning {
+                kind: ValidationWarningKind::DeprecatedEnumValue,
+                field: field.name.clone(),
+                message: format!("Value '{}' of '{}' is deprecated", value, field.name),
+            });
+        }
+    }
+
+    warnings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_schema_warnings_cover_each_kind() {
+        let fields: Vec<SchemaField> = serde_json::from_value(json!([
+            {"name": "stage", "type": "enum", "protection": "user", "indexed": false,
+             "userValues": [
+                 {"value": "todo", "label": "To Do"},
+                 {"value": "later", "label": "Later", "deprecated": true}
+             ]},
+            {"name": "owner", "type": "string", "protection": "user", "indexed": false,
+             "recommended": true}
+        ]))
+        .unwrap();
+        let properties = json!({"stage": "later", "stgae": "todo", "_schema_version": 1});
+
+        let warnings = schema_warnings("ticket", &fields, properties.as_object());
+        let kinds: Vec<_> = warnings
+            .iter()
+            .map(|w| (w.kind, w.field.as_str()))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (ValidationWarningKind::UnknownProperty, "stgae"),
+                (ValidationWarningKind::DeprecatedEnumValue, "stage"),
+                (ValidationWarningKind::MissingRecommendedField, "owner"),
+            ]
+        );
+
+        let clean = json!({"stage": "todo", "owner": "ada"});
+        assert!(schema_warnings("ticket", &fields, clean.as_object()).is_empty());
+    }
+}
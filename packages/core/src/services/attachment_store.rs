@@ -0,0 +1,129 @@
+//! Attachment Store - Content-Addressed Binary Storage
+//!
+//! Binary files (images, PDFs, ...) live on disk next to the database rather
+//! than inside node content. Files are stored under their SHA-256 hash, so the
+//! same file imported twice is stored once, and nodes reference them with
+//! `attachment://<hash>` URIs that can be computed before anything is written.
+
+use sha2::{Digest, Sha256};
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// URI scheme used in node content to reference stored attachments
+pub const ATTACHMENT_URI_SCHEME: &str = "attachment://";
+
+/// Metadata for a stored attachment
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Attachment {
+    /// Lowercase hex SHA-256 of the content
+    pub hash: String,
+    /// Original file name (for display and downloads)
+    pub file_name: String,
+    pub mime: String,
+    pub size: u64,
+}
+
+impl Attachment {
+    /// `attachment://<hash>` URI for this attachment
+    pub fn uri(&self) -> String {
+        attachment_uri(&self.hash)
+    }
+}
+
+/// Hex SHA-256 of attachment content
+pub fn attachment_hash(data: &[u8]) -> String {
+    Sha256::digest(data)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// Build the URI for an attachment hash
+pub fn attachment_uri(hash: &str) -> String {
+    format!("{}{}", ATTACHMENT_URI_SCHEME, hash)
+}
+
+/// Filesystem-backed attachment storage
+#[derive(Debug, Clone)]
+pub struct AttachmentStore {
+    root: PathBuf,
+}
+
+impl AttachmentStore {
+    /// Create a store rooted at `root` (created lazily on first write)
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Store content, returning its metadata
+    ///
+    /// Writing content that already exists is a no-op.
+    pub fn put(&self, data: &[u8], file_name: &str, mime: &str) -> io::Result<Attachment> {
+        let hash = attachment_hash(data);
+        let path = self.path_for_hash(&hash)?;
+        if !path.exists() {
+            std::fs::create_dir_all(&self.root)?;
+            // Write to a temp file first so a crash never leaves a truncated blob
+            let tmp = path.with_extension("tmp");
+            std::fs::write(&tmp, data)?;
+            std::fs::rename(&tmp, &path)?;
+        }
+
+        Ok(Attachment {
+            hash,
+            file_name: file_name.to_string(),
+            mime: mime.to_string(),
+            size: data.len() as u64,
+        })
+    }
+
+    /// Read stored content by hash
+    pub fn get(&self, hash: &str) -> io::Result<Vec<u8>> {
+        std::fs::read(self.path_for_hash(hash)?)
+    }
+
+    /// Resolve an `attachment://` URI to its file path, if stored
+    pub fn resolve_uri(&self, uri: &str) -> Option<PathBuf> {
+        let hash = uri.strip_prefix(ATTACHMENT_URI_SCHEME)?;
+        self.path_for_hash(hash).ok().filter(|p| p.exists())
+    }
+
+    /// On-disk path for a hash; rejects anything that is not a hex digest
+    fn path_for_hash(&self, hash: &str) -> io::Result<PathBuf> {
+        if hash.len() != 64 || !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("Invalid attachment hash: {}", hash),
+            ));
+        }
+        Ok(self.root.join(hash.to_ascii_lowercase()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_put_get_and_dedup() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = AttachmentStore::new(dir.path().join("attachments"));
+
+        let a = store.put(b"hello", "a.txt", "text/plain").unwrap();
+        let b = store.put(b"hello", "b.txt", "text/plain").unwrap();
+        assert_eq!(a.hash, b.hash);
+        assert_eq!(a.size, 5);
+        assert_eq!(store.get(&a.hash).unwrap(), b"hello");
+        assert_eq!(std::fs::read_dir(store.root()).unwrap().count(), 1);
+
+        assert_eq!(
+            store.resolve_uri(&a.uri()),
+            Some(store.root().join(&a.hash))
+        );
+        assert!(store.resolve_uri("attachment://../../etc/passwd").is_none());
+    }
+}
@@ -0,0 +1,289 @@
+//! Child View Service - Saved Views of a Node's Children
+//!
+//! Reads, saves and runs the [`ChildView`]s stored on a node. Running a view
+//! turns it into a query restricted to the node's children (see
+//! [`ChildView::to_query`]), so filtering and sorting happen in the database
+//! where they can; only the grouping is done here.
+
+use super::error::NodeServiceError;
+use super::node_service::NodeService;
+use super::query_service::QueryService;
+use crate::models::{ChildView, ChildViewGroup, Node, VIEWS_KEY};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// A view together with its current children
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChildViewResult {
+    pub view: ChildView,
+    pub groups: Vec<ChildViewGroup>,
+    /// Children shown across all groups
+    pub total: usize,
+}
+
+/// Manages and evaluates the child views of nodes
+pub struct ChildViewService {
+    node_service: Arc<NodeService>,
+    query_service: Arc<QueryService>,
+}
+
+impl ChildViewService {
+    pub fn new(node_service: Arc<NodeService>, query_service: Arc<QueryService>) -> Self {
+        Self {
+            node_service,
+            query_service,
+        }
+    }
+
+    /// Views saved on `node_id`, in order
+    pub async fn views(&self, node_id: &str) -> Result<Vec<ChildView>, NodeServiceError> {
+        let node = self.load(node_id).await?;
+        Ok(ChildView::from_properties(&node.properties))
+    }
+
+    /// Add `view` to the node, or replace the view with the same ID
+    pub async fn save_view(
+        &self,
+        node_id: &str,
+        view: ChildView,
+    ) -> Result<Vec<ChildView>, NodeServiceError> {
+        view.validate()
+            .map_err(|e| NodeServiceError::invalid_input(e.to_string()))?;
+        let mut views = self.views(node_id).await?;
+        match views.iter_mut().find(|existing| existing.id == view.id) {
+            Some(existing) => *existing = view,
+            None => views.push(view),
+        }
+        self.write(node_id, views).await
+    }
+
+    /// Remove the view `view_id`; removing an unknown view is a no-op
+    pub async fn delete_view(
+        &self,
+        node_id: &str,
+        view_id: &str,
+    ) -> Result<Vec<ChildView>, NodeServiceError> {
+        let mut views = self.views(node_id).await?;
+        let count = views.len();
+        views.retain(|view| view.id != view_id);
+        if views.len() == count {
+            return Ok(views);
+        }
+        self.write(node_id, views).await
+    }
+
+    /// Children of `node_id` as shown by its view `view_id`
+    pub async fn get_children_view(
+        &self,
+        node_id: &str,
+        view_id: &str,
+    ) -> Result<ChildViewResult, NodeServiceError> {
+        let view = self
+            .views(node_id)
+            .await?
+            .into_iter()
+            .find(|view| view.id == view_id)
+            .ok_or_else(|| {
+                NodeServiceError::invalid_input(format!(
+                    "Node {} has no view '{}'",
+                    node_id, view_id
+                ))
+            })?;
+        let nodes = self
+            .query_service
+            .execute(&view.to_query(node_id))
+            .await
+            .map_err(|e| NodeServiceError::query_failed(e.to_string()))?;
+        let total = nodes.len();
+        let groups = view.group(nodes);
+        Ok(ChildViewResult {
+            view,
+            groups,
+            total,
+        })
+    }
+
+    async fn load(&self, node_id: &str) -> Result<Node, NodeServiceError> {
+        self.node_service
+            .get_node(node_id)
+            .await?
+            .ok_or_else(|| NodeServiceError::node_not_found(node_id))
+    }
+
+    async fn write(
+        &self,
+        node_id: &str,
+        views: Vec<ChildView>,
+    ) -> Result<Vec<ChildView>, NodeServiceError> {
+        let path = format!("/{}", VIEWS_KEY);
+        let node = if views.is_empty() {
+            self.node_service.remove_property(node_id, &path).await?
+        } else {
+            let value = serde_json::to_value(&views)
+                .map_err(|e| NodeServiceError::serialization_error(e.to_string()))?;
+            self.node_service
+                .set_property(node_id, &path, value)
+                .await?
+        };
+        Ok(ChildView::from_properties(&node.properties))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::SurrealStore;
+    use crate::services::CreateNodeParams;
+    use serde_json::{json, Value};
+    use tempfile::TempDir;
+
+    async fn create_test_services() -> (ChildViewService, Arc<NodeService>, TempDir) {
+        let temp_dir = TempDir::new().unwrap();
+        let mut store = Arc::new(
+            SurrealStore::new(temp_dir.path().join("test.db"))
+                .await
+                .unwrap(),
+        );
+        let node_service = Arc::new(NodeService::new(&mut store).await.unwrap());
+        let query_service = Arc::new(QueryService::new(
+            store,
+            node_service.workspace_lock().clone(),
+        ));
+        (
+            ChildViewService::new(node_service.clone(), query_service),
+            node_service,
+            temp_dir,
+        )
+    }
+
+    fn params(node_type: &str, content: &str, parent_id: Option<&str>) -> CreateNodeParams {
+        CreateNodeParams {
+            id: None,
+            node_type: node_type.to_string(),
+            content: content.to_string(),
+            parent_id: parent_id.map(str::to_string),
+            insert_after_node_id: None,
+            properties: json!({}),
+        }
+    }
+
+    async fn create(node_service: &NodeService, params: CreateNodeParams) -> String {
+        node_service.create_node_with_parent(params).await.unwrap()
+    }
+
+    fn task(
+        content: &str,
+        parent_id: Option<&str>,
+        status: &str,
+        priority: &str,
+    ) -> CreateNodeParams {
+        CreateNodeParams {
+            properties: json!({ "task": { "status": status, "priority": priority } }),
+            ..params("task", content, parent_id)
+        }
+    }
+
+    fn view(value: Value) -> ChildView {
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn contents(group: &ChildViewGroup) -> Vec<&str> {
+        group
+            .nodes
+            .iter()
+            .map(|node| node.content.as_str())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_view_filters_sorts_and_groups_children() {
+        let (service, node_service, _temp_dir) = create_test_services().await;
+        let project = create(&node_service, params("text", "Project", None)).await;
+        let parent = Some(project.as_str());
+        create(&node_service, task("Ship", parent, "open", "high")).await;
+        create(&node_service, task("Fix", parent, "open", "low")).await;
+        create(&node_service, task("Audit", parent, "done", "high")).await;
+        create(&node_service, task("Draft", parent, "open", "high")).await;
+        create(&node_service, params("text", "Notes", parent)).await;
+        // Not a child of the project
+        create(&node_service, task("Elsewhere", None, "open", "high")).await;
+
+        service
+            .save_view(
+                &project,
+                view(json!({
+                    "id": "open",
+                    "name": "Open tasks by priority",
+                    "targetType": "task",
+                    "filters": [{
+                        "type": "property",
+                        "operator": "equals",
+                        "property": "status",
+                        "value": "open",
+                    }],
+                    "sorting": [{ "field": "content", "direction": "ascending" }],
+                    "groupBy": "priority",
+                })),
+            )
+            .await
+            .unwrap();
+
+        let result = service.get_children_view(&project, "open").await.unwrap();
+        assert_eq!(result.total, 3);
+        assert_eq!(result.groups.len(), 2);
+        assert_eq!(result.groups[0].key, json!("high"));
+        assert_eq!(contents(&result.groups[0]), vec!["Draft", "Ship"]);
+        assert_eq!(result.groups[1].key, json!("low"));
+        assert_eq!(contents(&result.groups[1]), vec!["Fix"]);
+    }
+
+    #[tokio::test]
+    async fn test_view_without_sorting_keeps_outline_order() {
+        let (service, node_service, _temp_dir) = create_test_services().await;
+        let parent = create(&node_service, params("text", "Parent", None)).await;
+        let first = create(&node_service, params("text", "Zulu", Some(&parent))).await;
+        let second = CreateNodeParams {
+            insert_after_node_id: Some(first),
+            ..params("text", "Alpha", Some(&parent))
+        };
+        create(&node_service, second).await;
+
+        service
+            .save_view(&parent, view(json!({ "id": "all", "name": "All" })))
+            .await
+            .unwrap();
+
+        let result = service.get_children_view(&parent, "all").await.unwrap();
+        assert_eq!(result.total, 2);
+        assert_eq!(result.groups.len(), 1);
+        assert_eq!(result.groups[0].key, Value::Null);
+        assert_eq!(contents(&result.groups[0]), vec!["Zulu", "Alpha"]);
+    }
+
+    #[tokio::test]
+    async fn test_view_of_node_without_children_is_empty() {
+        let (service, node_service, _temp_dir) = create_test_services().await;
+        let leaf = create(&node_service, params("text", "Leaf", None)).await;
+        service
+            .save_view(&leaf, view(json!({ "id": "all", "name": "All" })))
+            .await
+            .unwrap();
+        service
+            .save_view(
+                &leaf,
+                view(json!({ "id": "by-type", "name": "By type", "groupBy": "node_type" })),
+            )
+            .await
+            .unwrap();
+
+        let ungrouped = service.get_children_view(&leaf, "all").await.unwrap();
+        assert_eq!(ungrouped.total, 0);
+        assert_eq!(ungrouped.groups.len(), 1);
+        assert!(ungrouped.groups[0].nodes.is_empty());
+
+        let grouped = service.get_children_view(&leaf, "by-type").await.unwrap();
+        assert_eq!(grouped.total, 0);
+        assert!(grouped.groups.is_empty());
+    }
+}
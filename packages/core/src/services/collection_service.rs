@@ -263,8 +263,9 @@ pub fn build_path_string(segments: &[&str]) -> String {
 // CollectionService - High-level operations that integrate with the store
 // ============================================================================
 
-use crate::db::{DatabaseError, SurrealStore};
+use crate::db::{CollectionStats, DatabaseError, SubCollection, SurrealStore};
 use crate::models::Node;
+use serde::Serialize;
 use serde_json::json;
 use std::sync::Arc;
 
@@ -300,6 +301,87 @@ impl ResolvedPath {
     }
 }
 
+/// Old and new string of a collection path changed by a rename
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PathChange {
+    pub from: String,
+    pub to: String,
+}
+
+/// Result of [`CollectionService::rename_path`]
+#[derive(Debug, Clone, Serialize)]
+pub struct RenamedPath {
+    /// The renamed collection
+    pub collection: Node,
+    /// The collection's own path and every path below it, sorted by old path
+    pub paths: Vec<PathChange>,
+}
+
+/// A collection as listed in the sidebar, with its member count and parents
+///
+/// Returned by the desktop `get_all_collections` command and the dev proxy's
+/// `GET /api/collections`, so both surfaces serve the same shape.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CollectionInfo {
+    /// The collection node in its typed JSON form
+    #[serde(flatten)]
+    pub node: serde_json::Value,
+    /// Number of direct members in this collection
+    pub member_count: usize,
+    /// IDs of parent collections (collections this collection is nested under)
+    pub parent_collection_ids: Vec<String>,
+}
+
+impl CollectionInfo {
+    /// Build the listing entry for `collection`; fails if the node can't be
+    /// converted to its typed form
+    pub fn new(collection: Node, member_count: usize) -> Result<Self, String> {
+        let parent_collection_ids = collection.member_of.clone();
+        Ok(Self {
+            node: crate::models::node_to_typed_value(collection)?,
+            member_count,
+            parent_collection_ids,
+        })
+    }
+}
+
+/// Paths of a collection moving from `from` to `to` and of all the
+/// collections below it
+///
+/// A collection with several parents in the subtree appears once per path.
+/// Descent stops one level past the maximum depth, which is enough to report
+/// the violation and keeps a corrupt (cyclic) hierarchy from looping.
+fn subtree_paths(
+    collection_id: &str,
+    from: String,
+    to: String,
+    subcollections: &[SubCollection],
+) -> Vec<PathChange> {
+    let mut paths = Vec::new();
+    let mut pending = vec![(collection_id, PathChange { from, to })];
+    while let Some((id, change)) = pending.pop() {
+        let depth = change.to.split(COLLECTION_PATH_DELIMITER).count();
+        if depth <= MAX_COLLECTION_DEPTH {
+            for child in subcollections
+                .iter()
+                .filter(|child| child.parent_ids.iter().any(|parent| parent == id))
+            {
+                pending.push((
+                    child.id.as_str(),
+                    PathChange {
+                        from: format!("{}{}{}", change.from, COLLECTION_PATH_DELIMITER, child.name),
+                        to: format!("{}{}{}", change.to, COLLECTION_PATH_DELIMITER, child.name),
+                    },
+                ));
+            }
+        }
+        paths.push(change);
+    }
+    paths.sort_by(|a, b| a.from.cmp(&b.from));
+    paths
+}
+
 /// High-level collection operations
 ///
 /// This service provides path resolution, membership management, and collection
@@ -567,6 +649,146 @@ where
         Ok(())
     }
 
+    /// Move a member to another position within a collection
+    ///
+    /// The member is placed right after `insert_after_member_id`, or first
+    /// when `None`. Membership order is a fractional index key on the
+    /// member_of edge, so only the moved member's edge is written.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `member_id` is not a member of the collection
+    pub async fn reorder_collection_member(
+        &self,
+        collection_id: &str,
+        member_id: &str,
+        insert_after_member_id: Option<&str>,
+    ) -> Result<(), NodeServiceError> {
+        self.node_service
+            .reorder_collection_member(collection_id, member_id, insert_after_member_id)
+            .await
+    }
+
+    /// Rename or move the collection at `old_path` to `new_path`
+    ///
+    /// The last segment of `new_path` becomes the collection's name and the
+    /// rest its parent, created as needed (`hr:policy` -> `hr:policies`
+    /// renames, `hr:policy` -> `legal:policy` moves). The collection keeps its
+    /// ID, members and sub-collections, so everything below it follows
+    /// without being recreated; the returned paths let callers update any
+    /// path strings they hold. The rename and the parent change are written
+    /// in one transaction through NodeService, which emits the events.
+    ///
+    /// # Errors
+    ///
+    /// - `CollectionNotFound` if no collection exists at `old_path`
+    /// - `InvalidCollectionPath` if another collection already has the new name
+    /// - `CollectionCycle` if `new_path` lies inside the collection's subtree
+    /// - `CollectionDepthExceeded` if a path below it would become too deep
+    pub async fn rename_path(
+        &self,
+        old_path: &str,
+        new_path: &str,
+    ) -> Result<RenamedPath, NodeServiceError> {
+        let old = parse_collection_path(old_path)?;
+        let new = parse_collection_path(new_path)?;
+        let not_found = || NodeServiceError::collection_not_found(old_path);
+
+        let collection = self
+            .find_collection_by_path(old_path)
+            .await?
+            .ok_or_else(not_found)?;
+        let from_parent_id = match old.parent() {
+            Some(parent) => {
+                let parent = self
+                    .find_collection_by_path(&parent.original)
+                    .await?
+                    .ok_or_else(not_found)?;
+                if !self
+                    .get_node_collections(&collection.id)
+                    .await?
+                    .contains(&parent.id)
+                {
+                    return Err(not_found());
+                }
+                Some(parent.id)
+            }
+            None => None,
+        };
+
+        let name = new
+            .final_segment()
+            .ok_or_else(|| NodeServiceError::invalid_collection_path("path has no segments"))?;
+        let name = validate_collection_name(&name.name)?;
+        if let Some(existing) = self.get_collection_by_name(&name).await? {
+            if existing.id != collection.id {
+                return Err(NodeServiceError::invalid_collection_path(format!(
+                    "collection '{}' already exists",
+                    name
+                )));
+            }
+        }
+
+        let subcollections = self
+            .store
+            .get_subcollections(&collection.id)
+            .await
+            .map_err(|e| db_error(e, "Failed to get subcollections"))?;
+        let new_parent = new.parent();
+        if let Some(parent) = &new_parent {
+            let subtree: Vec<String> = [collection.content.as_str(), name.as_str()]
+                .into_iter()
+                .chain(subcollections.iter().map(|child| child.name.as_str()))
+                .map(normalize_collection_name)
+                .collect();
+            if parent
+                .segments
+                .iter()
+                .any(|segment| subtree.contains(&segment.normalized_name))
+            {
+                return Err(NodeServiceError::collection_cycle(format!(
+                    "'{}' cannot be moved inside itself to '{}'",
+                    old_path, new_path
+                )));
+            }
+        }
+
+        let mut new_segments: Vec<&str> = new.segments.iter().map(|s| s.name.as_str()).collect();
+        new_segments.pop();
+        new_segments.push(&name);
+        let old_segments: Vec<&str> = old.segments.iter().map(|s| s.name.as_str()).collect();
+        let paths = subtree_paths(
+            &collection.id,
+            build_path_string(&old_segments),
+            build_path_string(&new_segments),
+            &subcollections,
+        );
+        if let Some(change) = paths.iter().find(|change| {
+            change.to.split(COLLECTION_PATH_DELIMITER).count() > MAX_COLLECTION_DEPTH
+        }) {
+            return Err(NodeServiceError::collection_depth_exceeded(
+                change.to.clone(),
+                MAX_COLLECTION_DEPTH,
+            ));
+        }
+
+        let to_parent_id = match new_parent {
+            Some(parent) => Some(self.resolve_path(&parent.original).await?.leaf.id),
+            None => None,
+        };
+        let collection = self
+            .node_service
+            .move_collection(
+                &collection.id,
+                &name,
+                from_parent_id.as_deref(),
+                to_parent_id.as_deref(),
+            )
+            .await?;
+
+        Ok(RenamedPath { collection, paths })
+    }
+
     /// Get all collections a node belongs to
     ///
     /// Returns the IDs of all collections the node is a member of.
@@ -582,7 +804,8 @@ where
 
     /// Get all members of a collection
     ///
-    /// Returns full Node structs for all members of the collection.
+    /// Returns full Node structs for all members of the collection, in
+    /// membership order (see [`Self::reorder_collection_member`]).
     /// Single query that traverses the member_of relationship.
     pub async fn get_collection_members(
         &self,
@@ -608,6 +831,25 @@ where
             .map_err(|e| db_error(e, "Failed to get recursive collection members"))
     }
 
+    /// Member counts, last activity and type breakdown of the collection at
+    /// `path`, without loading its members
+    ///
+    /// # Errors
+    ///
+    /// Returns `CollectionNotFound` if no collection exists at `path`
+    pub async fn get_collection_stats(
+        &self,
+        path: &str,
+    ) -> Result<CollectionStats, NodeServiceError> {
+        let collection = self.find_collection_by_path(path).await?.ok_or_else(|| {
+            NodeServiceError::CollectionNotFound(format!("Collection not found: '{}'", path))
+        })?;
+        self.store
+            .get_collection_stats(&collection.id)
+            .await
+            .map_err(|e| db_error(e, "Failed to get collection stats"))
+    }
+
     /// Get a collection by name (case-insensitive)
     pub async fn get_collection_by_name(
         &self,
@@ -1027,4 +1269,44 @@ mod tests {
         assert_eq!(build_path_string(&["engineering"]), "engineering");
         assert_eq!(build_path_string(&[]), "");
     }
+
+    // ========================================================================
+    // rename_path tests
+    // ========================================================================
+
+    #[test]
+    fn test_subtree_paths_follow_the_rename() {
+        let child = |id: &str, name: &str, parent_ids: &[&str]| SubCollection {
+            id: id.to_string(),
+            name: name.to_string(),
+            parent_ids: parent_ids.iter().map(|id| id.to_string()).collect(),
+        };
+        // Berlin sits both directly under policy and under vacation
+        let subcollections = [
+            child("vacation", "vacation", &["policy"]),
+            child("berlin", "Berlin", &["vacation", "policy"]),
+        ];
+        let paths = subtree_paths(
+            "policy",
+            "hr:policy".to_string(),
+            "legal:policies".to_string(),
+            &subcollections,
+        );
+        let pairs: Vec<(&str, &str)> = paths
+            .iter()
+            .map(|change| (change.from.as_str(), change.to.as_str()))
+            .collect();
+        assert_eq!(
+            pairs,
+            vec![
+                ("hr:policy", "legal:policies"),
+                ("hr:policy:Berlin", "legal:policies:Berlin"),
+                ("hr:policy:vacation", "legal:policies:vacation"),
+                (
+                    "hr:policy:vacation:Berlin",
+                    "legal:policies:vacation:Berlin"
+                ),
+            ]
+        );
+    }
 }
@@ -0,0 +1,285 @@
+//! Deep Links - `nodespace://` URIs opened from outside the app
+//!
+//! The same URIs that appear inside node content (see the mention patterns in
+//! `node_service`) can be clicked in other apps or in MCP responses. The OS
+//! hands them to the desktop app, which parses them with [`DeepLink::parse`]
+//! and turns them into a [`NavigationTarget`] via [`resolve_deep_link`].
+//!
+//! Supported forms:
+//!
+//! | URI                                   | Target                          |
+//! |---------------------------------------|---------------------------------|
+//! | `nodespace://<id>`                    | node                            |
+//! | `nodespace://node/<id>?…`             | node (query parameters ignored) |
+//! | `nodespace://2025-01-15`              | date node (created on demand)   |
+//! | `nodespace://date/<YYYY-MM-DD>`       | date node (created on demand)   |
+//! | `nodespace://date/today`              | today's date node               |
+//! | `nodespace://search?q=<query>`        | search results                  |
+//! | `nodespace://collection/<path>`       | collection node                 |
+//!
+//! Path segments and query values are percent-decoded, so
+//! `nodespace://collection/work%3Aideas` refers to the `work:ideas` path.
+
+use super::collection_service::CollectionService;
+use super::error::NodeServiceError;
+use super::node_service::NodeService;
+use chrono::{Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+/// URI scheme registered with the operating system
+pub const DEEP_LINK_SCHEME: &str = "nodespace";
+
+/// Maximum number of search results returned for a search link
+const SEARCH_RESULT_LIMIT: usize = 20;
+
+/// A parsed `nodespace://` URI
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeepLink {
+    /// A node by ID
+    Node(String),
+    /// A date node (`YYYY-MM-DD`)
+    Date(NaiveDate),
+    /// A content search
+    Search(String),
+    /// A collection by path (e.g. `work:ideas`)
+    Collection(String),
+}
+
+impl DeepLink {
+    /// Parse a `nodespace://` URI
+    pub fn parse(uri: &str) -> Result<Self, NodeServiceError> {
+        let invalid = |reason: &str| {
+            NodeServiceError::invalid_input(format!("Invalid deep link '{}': {}", uri, reason))
+        };
+
+        let rest = uri
+            .trim()
+            .strip_prefix(DEEP_LINK_SCHEME)
+            .and_then(|rest| rest.strip_prefix("://"))
+            .ok_or_else(|| invalid("expected nodespace:// scheme"))?;
+        let rest = rest.split('#').next().unwrap_or_default();
+        let (path, query) = rest.split_once('?').unwrap_or((rest, ""));
+        let path = path.trim_end_matches('/');
+        let (head, tail) = path.split_once('/').unwrap_or((path, ""));
+
+        match head {
+            "" => Err(invalid("missing target")),
+            "node" => {
+                let id = percent_decode(tail);
+                if id.is_empty() {
+                    return Err(invalid("missing node ID"));
+                }
+                Ok(Self::node_or_date(id))
+            }
+            "date" => {
+                let date = match percent_decode(tail).as_str() {
+                    "today" => Local::now().date_naive(),
+                    value => NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                        .map_err(|_| invalid("expected a YYYY-MM-DD date or 'today'"))?,
+                };
+                Ok(Self::Date(date))
+            }
+            "search" => {
+                let text = query
+                    .split('&')
+                    .filter_map(|pair| pair.split_once('='))
+                    .find(|(key, _)| *key == "q")
+                    .map(|(_, value)| percent_decode(value))
+                    .unwrap_or_default();
+                if text.trim().is_empty() {
+                    return Err(invalid("missing search query (?q=)"));
+                }
+                Ok(Self::Search(text.trim().to_string()))
+            }
+            "collection" => {
+                let path = percent_decode(tail);
+                if path.is_empty() {
+                    return Err(invalid("missing collection path"));
+                }
+                Ok(Self::Collection(path))
+            }
+            _ if tail.is_empty() => Ok(Self::node_or_date(percent_decode(head))),
+            _ => Err(invalid("unknown link type")),
+        }
+    }
+
+    fn node_or_date(id: String) -> Self {
+        match NaiveDate::parse_from_str(&id, "%Y-%m-%d") {
+            Ok(date) => Self::Date(date),
+            Err(_) => Self::Node(id),
+        }
+    }
+}
+
+/// Where the UI should navigate for a deep link
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(
+    tag = "type",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub enum NavigationTarget {
+    /// Open a node (date nodes included)
+    Node { node_id: String },
+    /// Show search results, best match first
+    Search {
+        query: String,
+        node_ids: Vec<String>,
+    },
+    /// Open a collection
+    Collection { path: String, collection_id: String },
+}
+
+/// Resolve a parsed deep link against the database
+///
+/// Node links must point at an existing node, date links create their date
+/// node if needed, and collection links must name an existing collection.
+pub async fn resolve_deep_link<C>(
+    node_service: &NodeService<C>,
+    link: DeepLink,
+) -> Result<NavigationTarget, NodeServiceError>
+where
+    C: surrealdb::Connection,
+{
+    match link {
+        DeepLink::Node(id) => {
+            if node_service.get_node(&id).await?.is_none() {
+                return Err(NodeServiceError::node_not_found(&id));
+            }
+            Ok(NavigationTarget::Node { node_id: id })
+        }
+        DeepLink::Date(date) => {
+            let id = date.format("%Y-%m-%d").to_string();
+            node_service.ensure_date_exists(&id).await?;
+            Ok(NavigationTarget::Node { node_id: id })
+        }
+        DeepLink::Search(query) => {
+            let node_ids = node_service
+                .mention_autocomplete(&query, Some(SEARCH_RESULT_LIMIT))
+                .await?
+                .into_iter()
+                .map(|node| node.id)
+                .collect();
+            Ok(NavigationTarget::Search { query, node_ids })
+        }
+        DeepLink::Collection(path) => {
+            let collection = CollectionService::new(node_service.store(), node_service)
+                .find_collection_by_path(&path)
+                .await?
+                .ok_or_else(|| NodeServiceError::collection_not_found(&path))?;
+            Ok(NavigationTarget::Collection {
+                path,
+                collection_id: collection.id,
+            })
+        }
+    }
+}
+
+/// Decode `%XX` escapes (and `+` as space); malformed escapes are kept as-is
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'%' => {
+                let escaped = bytes
+                    .get(i + 1..i + 3)
+                    .and_then(|hex| std::str::from_utf8(hex).ok())
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+                match escaped {
+                    Some(byte) => {
+                        decoded.push(byte);
+                        i += 3;
+                        continue;
+                    }
+                    None => decoded.push(b'%'),
+                }
+            }
+            b'+' => decoded.push(b' '),
+            byte => decoded.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn date(value: &str) -> DeepLink {
+        DeepLink::Date(NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap())
+    }
+
+    #[test]
+    fn test_parse_node_and_date_links() {
+        let id = "550e8400-e29b-41d4-a716-446655440000";
+        assert_eq!(
+            DeepLink::parse(&format!("nodespace://{}", id)).unwrap(),
+            DeepLink::Node(id.to_string())
+        );
+        assert_eq!(
+            DeepLink::parse(&format!("nodespace://node/{}?view=edit", id)).unwrap(),
+            DeepLink::Node(id.to_string())
+        );
+        assert_eq!(
+            DeepLink::parse("nodespace://2025-10-24").unwrap(),
+            date("2025-10-24")
+        );
+        assert_eq!(
+            DeepLink::parse("nodespace://date/2025-10-24/").unwrap(),
+            date("2025-10-24")
+        );
+        assert_eq!(
+            DeepLink::parse("nodespace://date/today").unwrap(),
+            DeepLink::Date(Local::now().date_naive())
+        );
+    }
+
+    #[test]
+    fn test_parse_search_and_collection_links() {
+        assert_eq!(
+            DeepLink::parse("nodespace://search?limit=5&q=meeting%20notes").unwrap(),
+            DeepLink::Search("meeting notes".to_string())
+        );
+        assert_eq!(
+            DeepLink::parse("nodespace://search?q=quarterly+plan").unwrap(),
+            DeepLink::Search("quarterly plan".to_string())
+        );
+        assert_eq!(
+            DeepLink::parse("nodespace://collection/work%3Aideas").unwrap(),
+            DeepLink::Collection("work:ideas".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed_links() {
+        for uri in [
+            "https://example.com",
+            "nodespace://",
+            "nodespace://node/",
+            "nodespace://date/yesterday",
+            "nodespace://search?q=",
+            "nodespace://collection/",
+            "nodespace://unknown/thing",
+        ] {
+            assert!(DeepLink::parse(uri).is_err(), "{} should not parse", uri);
+        }
+        assert_eq!(percent_decode("100%"), "100%");
+        assert_eq!(percent_decode("%zz"), "%zz");
+    }
+
+    #[test]
+    fn test_navigation_target_wire_format() {
+        assert_eq!(
+            serde_json::to_value(NavigationTarget::Collection {
+                path: "work:ideas".to_string(),
+                collection_id: "c1".to_string(),
+            })
+            .unwrap(),
+            json!({ "type": "collection", "path": "work:ideas", "collectionId": "c1" })
+        );
+    }
+}
@@ -0,0 +1,562 @@
+//! Digest Service - Weekly Review Notes
+//!
+//! Assembles a review of one ISO week and files it under the week node (see
+//! [`Period`]). The review lists:
+//!
+//! - tasks completed during the week
+//! - notes (document roots) created during the week
+//! - the notes with the most nodes edited in their subtree
+//! - open tasks that are overdue at the end of the week
+//! - new notes that are orphaned: nothing mentions them and no collection
+//!   holds them
+//!
+//! Time windows are evaluated by the [`QueryService`]; overdue tasks come from
+//! the due-date index. With a [`SummarizationService`] that has a generation
+//! model loaded, a short summary of the week opens the review.
+//!
+//! The review is a text node marked with `text.digest_for = <week ID>` and a
+//! child per non-empty section. Generating the digest of a week again
+//! replaces the previous one.
+
+use super::error::NodeServiceError;
+use super::node_service::{CreateNodeParams, NodeService};
+use super::query_service::{
+    FilterOperator, FilterType, QueryDefinition, QueryFilter, QueryService,
+};
+use crate::models::{Node, Period, TaskDueFilter, TaskNode, TaskStatus};
+use chrono::{DateTime, Datelike, Days, Local, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use nodespace_nlp_engine::SummarizationService;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Root types that count as notes
+pub(super) const NOTE_TYPES: &[&str] = &["text", "header", "code-block"];
+
+/// Entries listed per section; the rest are counted
+const MAX_LISTED: usize = 10;
+
+/// Notes shown under "Most edited"
+const MOST_EDITED_LIMIT: usize = 5;
+
+/// Token budget of the generated summary
+const SUMMARY_TOKENS: usize = 120;
+
+/// A node listed in a digest
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DigestEntry {
+    pub node_id: String,
+    /// First line of the node's content
+    pub title: String,
+    /// Extra context, e.g. `due 2025-01-10` or `12 nodes edited`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+/// The review of one week
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WeeklyDigest {
+    /// Week reviewed, e.g. `2025-W03`
+    pub week_id: String,
+    pub completed_tasks: Vec<DigestEntry>,
+    pub new_notes: Vec<DigestEntry>,
+    /// Most edited first
+    pub most_edited: Vec<DigestEntry>,
+    pub overdue_tasks: Vec<DigestEntry>,
+    pub orphaned_notes: Vec<DigestEntry>,
+    /// Generated summary, when a summarization model is available
+    pub summary: Option<String>,
+    /// Review node under the week, absent when the week had nothing to report
+    pub digest_node_id: Option<String>,
+}
+
+impl WeeklyDigest {
+    pub fn is_empty(&self) -> bool {
+        self.sections()
+            .iter()
+            .all(|(_, entries)| entries.is_empty())
+    }
+
+    /// Section headings with their entries, in display order
+    fn sections(&self) -> [(&'static str, &[DigestEntry]); 5] {
+        [
+            ("Completed tasks", &self.completed_tasks),
+            ("New notes", &self.new_notes),
+            ("Most edited", &self.most_edited),
+            ("Overdue", &self.overdue_tasks),
+            ("Orphaned notes", &self.orphaned_notes),
+        ]
+    }
+}
+
+/// Builds weekly review nodes
+pub struct DigestService<C = surrealdb::engine::local::Db>
+where
+    C: surrealdb::Connection,
+{
+    node_service: Arc<NodeService<C>>,
+    query_service: Arc<QueryService<C>>,
+    summarizer: Option<Arc<SummarizationService>>,
+}
+
+impl<C> DigestService<C>
+where
+    C: surrealdb::Connection,
+{
+    pub fn new(node_service: Arc<NodeService<C>>, query_service: Arc<QueryService<C>>) -> Self {
+        Self {
+            node_service,
+            query_service,
+            summarizer: None,
+        }
+    }
+
+    /// Open reviews with a summary written by `summarizer`
+    ///
+    /// Only used while the summarizer has a generation model loaded.
+    pub fn with_summarizer(mut self, summarizer: Arc<SummarizationService>) -> Self {
+        self.summarizer = Some(summarizer);
+        self
+    }
+
+    /// Build the review of `week_id` and store it under the week node
+    ///
+    /// `today` bounds the overdue section for the current week. A week with
+    /// nothing to report gets no review node.
+    pub async fn generate(
+        &self,
+        week_id: &str,
+        today: NaiveDate,
+    ) -> Result<WeeklyDigest, NodeServiceError> {
+        let week = match Period::parse(week_id) {
+            Some(week @ Period::Week { .. }) => week,
+            _ => {
+                return Err(NodeServiceError::invalid_input(format!(
+                    "'{}' is not a week ID (expected YYYY-Www)",
+                    week_id
+                )))
+            }
+        };
+
+        if let Some(previous) = self.find_digest(week_id).await? {
+            self.node_service
+                .delete_node(&previous.id, previous.version)
+                .await?;
+        }
+
+        let mut digest = self.collect(week, today).await?;
+        if digest.is_empty() {
+            return Ok(digest);
+        }
+        digest.summary = self.summarize(&digest).await;
+        digest.digest_node_id = Some(self.write(&week, &digest).await?);
+        Ok(digest)
+    }
+
+    /// Review the previous week now if it has no review yet, then every Monday
+    ///
+    /// Never returns; callers typically spawn this and cancel it on shutdown.
+    pub async fn run<F>(&self, on_digest: F)
+    where
+        F: Fn(WeeklyDigest),
+    {
+        loop {
+            let today = Local::now().date_naive();
+            let [last_week, ..] = Period::containing(today - Days::new(7));
+            let week_id = last_week.id();
+            match self.find_digest(&week_id).await {
+                Ok(Some(_)) => {}
+                Ok(None) => match self.generate(&week_id, today).await {
+                    Ok(digest) if !digest.is_empty() => on_digest(digest),
+                    Ok(_) => {}
+                    Err(e) => tracing::warn!("Weekly digest for {} failed: {}", week_id, e),
+                },
+                Err(e) => tracing::warn!("Weekly digest lookup failed: {}", e),
+            }
+            tokio::time::sleep(until_next_week(Local::now().naive_local())).await;
+        }
+    }
+
+    /// Existing review node of `week_id`
+    async fn find_digest(&self, week_id: &str) -> Result<Option<Node>, NodeServiceError> {
+        if self.node_service.get_node(week_id).await?.is_none() {
+            return Ok(None);
+        }
+        let children = self.node_service.get_children(week_id).await?;
+        Ok(children.into_iter().find(|child| {
+            child
+                .properties
+                .get("text")
+                .and_then(|text| text.get("digest_for"))
+                .and_then(Value::as_str)
+                == Some(week_id)
+        }))
+    }
+
+    /// Gather the sections of the review of `week`
+    async fn collect(
+        &self,
+        week: Period,
+        today: NaiveDate,
+    ) -> Result<WeeklyDigest, NodeServiceError> {
+        let start = local_midnight(week.first_day());
+        let end_day = week.last_day() + Days::new(1);
+        let end = local_midnight(end_day);
+
+        // Completed tasks: done, last touched this week, completed this week when recorded
+        let (modified_from, modified_until) = within("modified_at", start, end);
+        let done = self
+            .query(QueryDefinition {
+                target_type: "task".to_string(),
+                filters: vec![
+                    property_equals("status", json!("done")),
+                    modified_from.clone(),
+                    modified_until.clone(),
+                ],
+                sorting: None,
+                limit: None,
+                offset: None,
+            })
+            .await?;
+        let completed_tasks = done
+            .into_iter()
+            .filter(|node| {
+                TaskNode::from_node(node.clone()).is_ok_and(|task| {
+                    task.status == TaskStatus::Done
+                        && task
+                            .completed_at
+                            .is_none_or(|completed| completed >= start && completed < end)
+                })
+            })
+            .map(|node| entry(&node, None))
+            .collect();
+
+        // New notes, and which of them nothing links to
+        let (created_from, created_until) = within("created_at", start, end);
+        let mut created = self
+            .query(QueryDefinition {
+                target_type: "*".to_string(),
+                filters: vec![created_from, created_until, note_types()],
+                sorting: None,
+                limit: None,
+                offset: None,
+            })
+            .await?;
+        created.sort_by_key(|node| node.created_at);
+        let store = self.node_service.store();
+        let mut new_notes = Vec::new();
+        let mut orphaned_notes = Vec::new();
+        for node in created {
+            if store
+                .get_parent_id(&node.id)
+                .await
+                .map_err(query_failed)?
+                .is_some()
+            {
+                continue;
+            }
+            let mentioned = !store
+                .get_incoming_mentions(&node.id)
+                .await
+                .map_err(query_failed)?
+                .is_empty();
+            let collected = !store
+                .get_node_memberships(&node.id)
+                .await
+                .map_err(query_failed)?
+                .is_empty();
+            if !mentioned && !collected {
+                orphaned_notes.push(entry(&node, None));
+            }
+            new_notes.push(entry(&node, None));
+        }
+
+        // Most edited: nodes modified this week, counted per note they belong to
+        let modified = self
+            .query(QueryDefinition {
+                target_type: "*".to_string(),
+                filters: vec![modified_from, modified_until],
+                sorting: None,
+                limit: None,
+                offset: None,
+            })
+            .await?;
+        let mut edits: HashMap<String, usize> = HashMap::new();
+        for node in modified {
+            let root_id = self.node_service.get_root_id(&node.id).await?;
+            *edits.entry(root_id).or_default() += 1;
+        }
+        let root_ids: Vec<String> = edits.keys().cloned().collect();
+        let roots = store
+            .get_nodes_by_ids(&root_ids)
+            .await
+            .map_err(query_failed)?;
+        let mut ranked: Vec<(&Node, usize)> = roots
+            .values()
+            .filter(|root| NOTE_TYPES.contains(&root.node_type.as_str()))
+            .map(|root| (root, edits[&root.id]))
+            .collect();
+        ranked.sort_by(|(a, a_edits), (b, b_edits)| {
+            b_edits.cmp(a_edits).then_with(|| a.id.cmp(&b.id))
+        });
+        let most_edited = ranked
+            .into_iter()
+            .take(MOST_EDITED_LIMIT)
+            .map(|(root, count)| {
+                let detail = format!("{} node{} edited", count, if count == 1 { "" } else { "s" });
+                entry(root, Some(detail))
+            })
+            .collect();
+
+        // Overdue at the end of the week (or today, for the current week)
+        let overdue = store
+            .get_open_tasks_due(None, end_day.min(today), &TaskDueFilter::default())
+            .await
+            .map_err(query_failed)?;
+        let overdue_tasks = overdue
+            .into_iter()
+            .map(|node| {
+                let due = TaskNode::from_node(node.clone())
+                    .ok()
+                    .and_then(|task| task.due_date)
+                    .map(|due| format!("due {}", due.format("%Y-%m-%d")));
+                entry(&node, due)
+            })
+            .collect();
+
+        Ok(WeeklyDigest {
+            week_id: week.id(),
+            completed_tasks,
+            new_notes,
+            most_edited,
+            overdue_tasks,
+            orphaned_notes,
+            summary: None,
+            digest_node_id: None,
+        })
+    }
+
+    async fn query(&self, query: QueryDefinition) -> Result<Vec<Node>, NodeServiceError> {
+        self.query_service
+            .execute(&query)
+            .await
+            .map_err(|e| NodeServiceError::query_failed(e.to_string()))
+    }
+
+    /// Generated summary of the review, `None` without a generation model
+    async fn summarize(&self, digest: &WeeklyDigest) -> Option<String> {
+        let summarizer = self.summarizer.clone().filter(|s| s.has_model())?;
+        let text = digest
+            .sections()
+            .iter()
+            .filter(|(_, entries)| !entries.is_empty())
+            .map(|(heading, entries)| {
+                let titles: Vec<&str> = entries.iter().map(|e| e.title.as_str()).collect();
+                format!("{}: {}", heading, titles.join("; "))
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        match tokio::task::spawn_blocking(move || summarizer.summarize(&text, SUMMARY_TOKENS)).await
+        {
+            Ok(Ok(summary)) if !summary.trim().is_empty() => Some(summary.trim().to_string()),
+            Ok(Ok(_)) => None,
+            Ok(Err(e)) => {
+                tracing::warn!("Weekly digest summary failed: {}", e);
+                None
+            }
+            Err(e) => {
+                tracing::warn!("Weekly digest summary task panicked: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Create the review node and its sections under the week node
+    async fn write(
+        &self,
+        week: &Period,
+        digest: &WeeklyDigest,
+    ) -> Result<String, NodeServiceError> {
+        let week_id = week.id();
+        self.node_service
+            .get_or_create_period_node(&week_id)
+            .await?;
+
+        let digest_id = self
+            .node_service
+            .create_node_with_parent(CreateNodeParams {
+                id: None,
+                node_type: "text".to_string(),
+                content: format!("Weekly review: {}", week.label()),
+                parent_id: Some(week_id.clone()),
+                insert_after_node_id: None,
+                properties: json!({ "text": { "digest_for": week_id } }),
+            })
+            .await?;
+
+        let mut blocks = Vec::new();
+        if let Some(summary) = &digest.summary {
+            blocks.push(summary.clone());
+        }
+        blocks.extend(
+            digest
+                .sections()
+                .iter()
+                .filter(|(_, entries)| !entries.is_empty())
+                .map(|(heading, entries)| render_section(heading, entries)),
+        );
+
+        let mut previous = None;
+        for content in blocks {
+            let id = self
+                .node_service
+                .create_node_with_parent(CreateNodeParams {
+                    id: None,
+                    node_type: "text".to_string(),
+                    content,
+                    parent_id: Some(digest_id.clone()),
+                    insert_after_node_id: previous,
+                    properties: json!({}),
+                })
+                .await?;
+            previous = Some(id);
+        }
+        Ok(digest_id)
+    }
+}
+
+fn query_failed(e: anyhow::Error) -> NodeServiceError {
+    NodeServiceError::query_failed(e.to_string())
+}
+
+fn entry(node: &Node, detail: Option<String>) -> DigestEntry {
+    DigestEntry {
+        node_id: node.id.clone(),
+        title: node.content.lines().next().unwrap_or_default().to_string(),
+        detail,
+    }
+}
+
+/// Metadata filters keeping `field` in `[start, end)`
+fn within(field: &str, start: DateTime<Utc>, end: DateTime<Utc>) -> (QueryFilter, QueryFilter) {
+    let bound = |operator, at: DateTime<Utc>| QueryFilter {
+        filter_type: FilterType::Metadata,
+        operator,
+        property: Some(field.to_string()),
+        value: Some(json!(at.to_rfc3339())),
+        case_sensitive: None,
+        relationship_type: None,
+        node_id: None,
+        edge_filters: None,
+        max_depth: None,
+    };
+    (
+        bound(FilterOperator::GreaterThanOrEqual, start),
+        bound(FilterOperator::LessThan, end),
+    )
+}
+
+fn property_equals(property: &str, value: Value) -> QueryFilter {
+    QueryFilter {
+        filter_type: FilterType::Property,
+        operator: FilterOperator::Equals,
+        property: Some(property.to_string()),
+        value: Some(value),
+        case_sensitive: None,
+        relationship_type: None,
+        node_id: None,
+        edge_filters: None,
+        max_depth: None,
+    }
+}
+
+fn note_types() -> QueryFilter {
+    QueryFilter {
+        filter_type: FilterType::Metadata,
+        operator: FilterOperator::In,
+        property: Some("node_type".to_string()),
+        value: Some(json!(NOTE_TYPES)),
+        case_sensitive: None,
+        relationship_type: None,
+        node_id: None,
+        edge_filters: None,
+        max_depth: None,
+    }
+}
+
+/// Section node content: a heading line and one linked line per entry
+fn render_section(heading: &str, entries: &[DigestEntry]) -> String {
+    let mut lines = vec![format!("**{}** ({})", heading, entries.len())];
+    for entry in entries.iter().take(MAX_LISTED) {
+        let mut line = format!("- [@{}](nodespace://{})", entry.title, entry.node_id);
+        if let Some(detail) = &entry.detail {
+            line.push_str(&format!(" - {}", detail));
+        }
+        lines.push(line);
+    }
+    if entries.len() > MAX_LISTED {
+        lines.push(format!("- and {} more", entries.len() - MAX_LISTED));
+    }
+    lines.join("\n")
+}
+
+/// Start of `date` in local time
+fn local_midnight(date: NaiveDate) -> DateTime<Utc> {
+    let midnight = date.and_time(chrono::NaiveTime::MIN);
+    Local
+        .from_local_datetime(&midnight)
+        .earliest()
+        .map_or_else(|| midnight.and_utc(), |local| local.with_timezone(&Utc))
+}
+
+/// Time from `now` until the start of next Monday
+fn until_next_week(now: NaiveDateTime) -> std::time::Duration {
+    let days = 7 - u64::from(now.weekday().num_days_from_monday());
+    let next = (now.date() + Days::new(days)).and_time(chrono::NaiveTime::MIN);
+    (next - now).to_std().unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entries(count: usize) -> Vec<DigestEntry> {
+        (0..count)
+            .map(|i| DigestEntry {
+                node_id: format!("n{}", i),
+                title: format!("Note {}", i),
+                detail: None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_render_section_caps_entries() {
+        let mut listed = entries(2);
+        listed[1].detail = Some("3 nodes edited".to_string());
+        assert_eq!(
+            render_section("Most edited", &listed),
+            "**Most edited** (2)\n- [@Note 0](nodespace://n0)\n- [@Note 1](nodespace://n1) - 3 nodes edited"
+        );
+
+        let rendered = render_section("New notes", &entries(MAX_LISTED + 3));
+        assert!(rendered.starts_with("**New notes** (13)"));
+        assert!(rendered.ends_with("- and 3 more"));
+        assert_eq!(rendered.lines().count(), MAX_LISTED + 2);
+    }
+
+    #[test]
+    fn test_until_next_week() {
+        let wednesday = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+        let noon = wednesday.and_hms_opt(12, 0, 0).unwrap();
+        assert_eq!(until_next_week(noon).as_secs(), (4 * 24 + 12) * 60 * 60);
+
+        let monday = NaiveDate::from_ymd_opt(2025, 1, 13).unwrap();
+        let midnight = monday.and_time(chrono::NaiveTime::MIN);
+        assert_eq!(until_next_week(midnight).as_secs(), 7 * 24 * 60 * 60);
+    }
+}
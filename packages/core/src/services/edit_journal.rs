@@ -0,0 +1,375 @@
+//! Edit Journal - Write-Ahead Log of Unsaved Editor Content
+//!
+//! The editor debounces saves, so a crash between a keystroke and the next
+//! save would lose whatever was typed in between. The journal closes that
+//! window: each change is appended (and synced) to a JSON-lines file as it
+//! happens, and acknowledged once NodeService has stored it. On the next
+//! start, [`EditJournal::replay`] writes back any content that was recorded
+//! but never acknowledged.
+//!
+//! Only the latest unacknowledged content of each node is replayed. A node
+//! modified after its content was recorded (in another window, by sync or an
+//! agent) is left as stored and the entry is reported as a conflict. The file
+//! is truncated whenever nothing is pending, so it stays small.
+
+use super::error::NodeServiceError;
+use super::node_service::NodeService;
+use crate::models::NodeUpdate;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+
+/// File name of the journal, kept next to the database
+pub const EDIT_JOURNAL_FILE: &str = "edit-journal.jsonl";
+
+/// Editor content recorded before it was saved
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JournalEntry {
+    pub seq: u64,
+    pub node_id: String,
+    pub content: String,
+    pub recorded_at: DateTime<Utc>,
+}
+
+/// One line of the journal file
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case", rename_all_fields = "camelCase")]
+enum JournalRecord {
+    Edit(JournalEntry),
+    /// Everything recorded for `node_id` up to `seq` has been saved
+    Ack {
+        node_id: String,
+        seq: u64,
+    },
+}
+
+/// Outcome of replaying the journal
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JournalReplay {
+    /// Nodes whose unsaved content was written back
+    pub restored: Vec<String>,
+    /// Nodes with pending content that no longer exist
+    pub missing: Vec<String>,
+    /// Pending content not written back because its node changed after it
+    /// was recorded
+    pub conflicts: Vec<JournalEntry>,
+}
+
+impl JournalReplay {
+    pub fn is_empty(&self) -> bool {
+        self.restored.is_empty() && self.missing.is_empty() && self.conflicts.is_empty()
+    }
+}
+
+#[derive(Debug)]
+struct JournalState {
+    file: File,
+    next_seq: u64,
+    /// Latest unacknowledged entry of each node
+    pending: BTreeMap<String, JournalEntry>,
+}
+
+/// Append-only journal of editor changes not yet saved to the database
+#[derive(Debug)]
+pub struct EditJournal {
+    path: PathBuf,
+    state: Mutex<JournalState>,
+}
+
+impl EditJournal {
+    /// Open the journal at `path`, loading entries left by a previous run
+    ///
+    /// A line cut short by a crash mid-write is ignored.
+    pub fn open(path: impl Into<PathBuf>) -> io::Result<Self> {
+        let path = path.into();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let mut next_seq = 1;
+        let mut pending = BTreeMap::new();
+        if path.exists() {
+            for line in BufReader::new(File::open(&path)?).lines() {
+                let Ok(record) = serde_json::from_str::<JournalRecord>(&line?) else {
+                    tracing::warn!("Skipping unreadable line in {}", path.display());
+                    continue;
+                };
+                match record {
+                    JournalRecord::Edit(entry) => {
+                        next_seq = next_seq.max(entry.seq + 1);
+                        pending.insert(entry.node_id.clone(), entry);
+                    }
+                    JournalRecord::Ack { node_id, seq } => {
+                        if pending.get(&node_id).is_some_and(|entry| entry.seq <= seq) {
+                            pending.remove(&node_id);
+                        }
+                    }
+                }
+            }
+        }
+
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        Ok(Self {
+            path,
+            state: Mutex::new(JournalState {
+                file,
+                next_seq,
+                pending,
+            }),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Durably record the editor's current content of a node
+    ///
+    /// Returns the sequence number to acknowledge once the content is saved.
+    pub fn record(&self, node_id: &str, content: &str) -> io::Result<u64> {
+        let mut state = self.state();
+        let entry = JournalEntry {
+            seq: state.next_seq,
+            node_id: node_id.to_string(),
+            content: content.to_string(),
+            recorded_at: Utc::now(),
+        };
+        append(&mut state.file, &JournalRecord::Edit(entry.clone()))?;
+
+        state.next_seq += 1;
+        state.pending.insert(entry.node_id.clone(), entry.clone());
+        Ok(entry.seq)
+    }
+
+    /// Mark a node's content up to `seq` as saved
+    ///
+    /// Later edits of the node stay pending.
+    pub fn acknowledge(&self, node_id: &str, seq: u64) -> io::Result<()> {
+        let mut state = self.state();
+        if !matches!(state.pending.get(node_id), Some(entry) if entry.seq <= seq) {
+            return Ok(());
+        }
+        state.pending.remove(node_id);
+
+        if state.pending.is_empty() {
+            truncate(&state.file)
+        } else {
+            let ack = JournalRecord::Ack {
+                node_id: node_id.to_string(),
+                seq,
+            };
+            append(&mut state.file, &ack)
+        }
+    }
+
+    /// Latest unsaved content of each node, oldest first
+    pub fn pending(&self) -> Vec<JournalEntry> {
+        let mut entries: Vec<_> = self.state().pending.values().cloned().collect();
+        entries.sort_by_key(|entry| entry.seq);
+        entries
+    }
+
+    /// Drop every pending entry
+    pub fn clear(&self) -> io::Result<()> {
+        let mut state = self.state();
+        state.pending.clear();
+        truncate(&state.file)
+    }
+
+    /// Flush the journal file and its metadata to disk, e.g. before exit
+    pub fn sync(&self) -> io::Result<()> {
+        self.state().file.sync_all()
+    }
+
+    /// Save pending content back to its nodes, then clear the journal
+    ///
+    /// Content that already matches the stored node was saved before the
+    /// crash and is left alone. Content of a node modified after it was
+    /// recorded would overwrite that newer change, so it is reported in
+    /// [`JournalReplay::conflicts`] instead. On error the journal is kept, so
+    /// a later replay (for example after unlocking the workspace) can finish
+    /// the job.
+    pub async fn replay<C>(
+        &self,
+        node_service: &NodeService<C>,
+    ) -> Result<JournalReplay, NodeServiceError>
+    where
+        C: surrealdb::Connection,
+    {
+        let mut replay = JournalReplay::default();
+        for entry in self.pending() {
+            let Some(node) = node_service.get_node(&entry.node_id).await? else {
+                replay.missing.push(entry.node_id);
+                continue;
+            };
+            if node.content == entry.content {
+                continue;
+            }
+            if node.modified_at > entry.recorded_at {
+                tracing::warn!(
+                    "Not replaying journaled content of {}: modified since it was recorded",
+                    entry.node_id
+                );
+                replay.conflicts.push(entry);
+            } else {
+                node_service
+                    .update_node(
+                        &entry.node_id,
+                        node.version,
+                        NodeUpdate::new().with_content(entry.content),
+                    )
+                    .await?;
+                replay.restored.push(entry.node_id);
+            }
+        }
+
+        self.clear().map_err(|e| {
+            NodeServiceError::query_failed(format!("Failed to clear edit journal: {}", e))
+        })?;
+        Ok(replay)
+    }
+
+    fn state(&self) -> MutexGuard<'_, JournalState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+fn append(file: &mut File, record: &JournalRecord) -> io::Result<()> {
+    let mut line = serde_json::to_vec(record)?;
+    line.push(b'\n');
+    file.write_all(&line)?;
+    file.sync_data()
+}
+
+fn truncate(file: &File) -> io::Result<()> {
+    file.set_len(0)?;
+    file.sync_data()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::SurrealStore;
+    use crate::models::Node;
+    use serde_json::json;
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_unacknowledged_edits_survive_reopen() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join(EDIT_JOURNAL_FILE);
+
+        let journal = EditJournal::open(&path).unwrap();
+        journal.record("a", "Hel").unwrap();
+        let saved = journal.record("b", "Saved").unwrap();
+        journal.record("a", "Hello").unwrap();
+        journal.acknowledge("b", saved).unwrap();
+        drop(journal);
+
+        let reopened = EditJournal::open(&path).unwrap();
+        let pending = reopened.pending();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].node_id, "a");
+        assert_eq!(pending[0].content, "Hello");
+
+        // Sequence numbers keep increasing across runs
+        assert!(reopened.record("c", "x").unwrap() > pending[0].seq);
+    }
+
+    #[test]
+    fn test_acknowledge_keeps_later_edits() {
+        let temp = TempDir::new().unwrap();
+        let journal = EditJournal::open(temp.path().join(EDIT_JOURNAL_FILE)).unwrap();
+
+        let first = journal.record("a", "one").unwrap();
+        journal.record("a", "one two").unwrap();
+        journal.acknowledge("a", first).unwrap();
+        assert_eq!(journal.pending()[0].content, "one two");
+
+        journal.acknowledge("a", first + 1).unwrap();
+        assert!(journal.pending().is_empty());
+        assert_eq!(fs::metadata(journal.path()).unwrap().len(), 0);
+    }
+
+    #[test]
+    fn test_torn_final_line_is_ignored() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join(EDIT_JOURNAL_FILE);
+
+        let journal = EditJournal::open(&path).unwrap();
+        journal.record("a", "intact").unwrap();
+        drop(journal);
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(br#"{"op":"edit","seq":2,"nodeId":"#)
+            .unwrap();
+
+        let reopened = EditJournal::open(&path).unwrap();
+        assert_eq!(reopened.pending().len(), 1);
+        assert_eq!(reopened.pending()[0].content, "intact");
+    }
+
+    #[tokio::test]
+    async fn test_replay_skips_nodes_modified_after_recording() {
+        let temp = TempDir::new().unwrap();
+        let mut store = Arc::new(
+            SurrealStore::new(temp.path().join("test.db"))
+                .await
+                .unwrap(),
+        );
+        let service = NodeService::new(&mut store).await.unwrap();
+        let unsaved = service
+            .create_node(Node::new(
+                "text".to_string(),
+                "Draft".to_string(),
+                json!({}),
+            ))
+            .await
+            .unwrap();
+        let edited = service
+            .create_node(Node::new(
+                "text".to_string(),
+                "Draft".to_string(),
+                json!({}),
+            ))
+            .await
+            .unwrap();
+
+        let journal = EditJournal::open(temp.path().join(EDIT_JOURNAL_FILE)).unwrap();
+        journal.record(&unsaved, "Draft, continued").unwrap();
+        journal
+            .record(&edited, "Draft, from the crashed window")
+            .unwrap();
+        // Another window saves a newer edit of the second node
+        let node = service.get_node(&edited).await.unwrap().unwrap();
+        service
+            .update_node(
+                &edited,
+                node.version,
+                NodeUpdate::new().with_content("Draft, from the other window".to_string()),
+            )
+            .await
+            .unwrap();
+
+        let replay = journal.replay(&service).await.unwrap();
+        assert_eq!(replay.restored, vec![unsaved.clone()]);
+        assert_eq!(replay.conflicts.len(), 1);
+        assert_eq!(replay.conflicts[0].node_id, edited);
+        assert_eq!(
+            replay.conflicts[0].content,
+            "Draft, from the crashed window"
+        );
+
+        let node = service.get_node(&edited).await.unwrap().unwrap();
+        assert_eq!(node.content, "Draft, from the other window");
+        let node = service.get_node(&unsaved).await.unwrap().unwrap();
+        assert_eq!(node.content, "Draft, continued");
+        assert!(journal.pending().is_empty());
+    }
+}
@@ -4,7 +4,9 @@
 //! - Purely event-driven: only wakes when nodes change
 //! - Per-root debounce: each root waits 30s after last change before embedding
 //! - Processes embeddings that have passed their debounce window
-//! - Graceful shutdown support
+//! - Graceful shutdown: [`EmbeddingProcessor::shutdown`] lets the batch in
+//!   progress finish and waits for the task to exit
+//! - Pausable: while paused, stale markers accumulate and are processed on resume
 //!
 //! ## Event-Driven Model with Per-Root Debounce
 //!
@@ -30,9 +32,11 @@
 
 use crate::services::error::NodeServiceError;
 use crate::services::NodeEmbeddingService;
-use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
 
 /// Handle to wake the embedding processor
 ///
@@ -79,7 +83,9 @@ where
     C: surrealdb::Connection + 'static,
 {
     waker: EmbeddingWaker,
-    _shutdown_tx: mpsc::Sender<()>,
+    paused: Arc<AtomicBool>,
+    shutdown_tx: mpsc::Sender<()>,
+    task: Mutex<Option<JoinHandle<()>>>,
     _phantom: std::marker::PhantomData<C>,
 }
 
@@ -122,10 +128,13 @@ where
         let debounce_secs = embedding_service.config().debounce_duration_secs;
         let debounce_duration = Duration::from_secs(debounce_secs);
 
+        let paused = Arc::new(AtomicBool::new(false));
+
         // Spawn purely event-driven background task
         let service_clone = embedding_service.clone();
         let trigger_tx_clone = trigger_tx.clone();
-        tokio::spawn(async move {
+        let paused_clone = paused.clone();
+        let task = tokio::spawn(async move {
             loop {
                 tokio::select! {
                     biased; // Check shutdown first
@@ -140,8 +149,15 @@ where
                         // Drain any additional pending triggers (coalesce rapid triggers)
                         while trigger_rx.try_recv().is_ok() {}
 
+                        // Stale markers stay in place; resume() wakes us again
+                        if paused_clone.load(Ordering::Relaxed) {
+                            tracing::debug!("EmbeddingProcessor paused, ignoring wake");
+                            continue;
+                        }
+
                         // Process embeddings that have passed their debounce window
-                        let has_pending = Self::process_until_empty(&service_clone).await;
+                        let has_pending =
+                            Self::process_until_empty(&service_clone, &paused_clone).await;
 
                         // If there are pending embeddings that haven't passed debounce yet,
                         // schedule a delayed wake to process them later
@@ -166,7 +182,9 @@ where
 
         Ok(Self {
             waker,
-            _shutdown_tx: shutdown_tx,
+            paused,
+            shutdown_tx,
+            task: Mutex::new(Some(task)),
             _phantom: std::marker::PhantomData,
         })
     }
@@ -187,11 +205,22 @@ where
     ///
     /// Returns true if there are pending stale embeddings that haven't passed
     /// their debounce window yet (requiring a delayed wake to be scheduled).
-    async fn process_until_empty(service: &Arc<NodeEmbeddingService<C>>) -> bool {
+    /// Stops early, returning false, when the processor is paused mid-run.
+    async fn process_until_empty(
+        service: &Arc<NodeEmbeddingService<C>>,
+        paused: &AtomicBool,
+    ) -> bool {
         const BATCH_SIZE: usize = 10;
         let mut total_processed = 0;
 
         loop {
+            if paused.load(Ordering::Relaxed) {
+                tracing::info!(
+                    "EmbeddingProcessor paused after {} embeddings",
+                    total_processed
+                );
+                return false;
+            }
             match service.process_stale_embeddings(Some(BATCH_SIZE)).await {
                 Ok(0) => {
                     // No more stale embeddings ready to process
@@ -260,14 +289,41 @@ where
         Ok(())
     }
 
+    /// Pause or resume background processing
+    ///
+    /// Pausing finishes the batch in progress and then leaves stale embeddings
+    /// queued. Resuming wakes the processor so the backlog is picked up.
+    pub fn set_paused(&self, paused: bool) {
+        let was_paused = self.paused.swap(paused, Ordering::Relaxed);
+        if was_paused && !paused {
+            tracing::info!("EmbeddingProcessor resumed");
+            self.waker.wake();
+        } else if !was_paused && paused {
+            tracing::info!("EmbeddingProcessor paused");
+        }
+    }
+
+    /// Whether background processing is paused
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::Relaxed)
+    }
+
     /// Shutdown processor gracefully
     ///
-    /// Sends shutdown signal to background task. The task will complete
-    /// any in-progress operations and exit cleanly.
-    pub fn shutdown(self) -> Result<(), NodeServiceError> {
+    /// Pauses processing so the run in progress stops after its current
+    /// batch, then signals the background task and waits for it to exit.
+    /// Stale embeddings left over are picked up on the next start.
+    pub async fn shutdown(&self) {
         tracing::info!("Shutting down EmbeddingProcessor");
-        // Channels will be dropped, signaling shutdown
-        Ok(())
+        self.paused.store(true, Ordering::Relaxed);
+        let _ = self.shutdown_tx.try_send(());
+
+        let task = self.task.lock().unwrap_or_else(|e| e.into_inner()).take();
+        if let Some(task) = task {
+            if let Err(e) = task.await {
+                tracing::warn!("EmbeddingProcessor task ended abnormally: {}", e);
+            }
+        }
     }
 }
 
@@ -25,7 +25,8 @@
 //!
 //! When embedding a root node:
 //! 1. Fetch root + all descendants via `get_nodes_in_subtree()`
-//! 2. Aggregate content in hierarchical order
+//! 2. Aggregate content in hierarchical order, followed by any `((id))`
+//!    embedded blocks from outside the subtree
 //! 3. Chunk if > 512 tokens with ~100 token overlap
 //! 4. Generate embedding per chunk
 //! 5. Store in `embedding` table
@@ -35,7 +36,9 @@ use crate::models::{
     is_embeddable_type, EmbeddingConfig, EmbeddingSearchResult, NewEmbedding, Node,
 };
 use crate::services::error::NodeServiceError;
-use nodespace_nlp_engine::EmbeddingService;
+use crate::services::node_service::extract_embeds;
+use crate::services::workspace_lock::WorkspaceLock;
+use nodespace_nlp_engine::{EmbeddingService, SummarizationService};
 use sha2::{Digest, Sha256};
 use std::collections::HashSet;
 use std::sync::Arc;
@@ -49,6 +52,10 @@ pub const DEFAULT_BATCH_SIZE: usize = 50;
 /// Maximum depth for parent chain traversal (safety limit to prevent infinite loops)
 pub const MAX_PARENT_CHAIN_DEPTH: usize = 100;
 
+/// Score boost of the most central document (see `ImportanceService`);
+/// others are boosted in proportion to their importance
+pub const IMPORTANCE_BOOST: f64 = 0.2;
+
 /// Root-aggregate embedding service
 ///
 /// Manages semantic embeddings using the root-aggregate model where only
@@ -64,6 +71,10 @@ where
     store: Arc<SurrealStore<C>>,
     /// Configuration for embedding behavior
     config: EmbeddingConfig,
+    /// Local summarizer for `summarize_node`, when the host provides one
+    summarizer: Option<Arc<SummarizationService>>,
+    /// The NodeService's lock; search and summaries fail while it is locked
+    workspace_lock: Arc<WorkspaceLock>,
 }
 
 impl<C> NodeEmbeddingService<C>
@@ -75,12 +86,19 @@ where
     /// # Arguments
     /// * `nlp_engine` - The NLP engine for generating embeddings
     /// * `store` - The SurrealDB store for persisting embeddings
-    pub fn new(nlp_engine: Arc<EmbeddingService>, store: Arc<SurrealStore<C>>) -> Self {
+    /// * `workspace_lock` - The NodeService's [`WorkspaceLock`]
+    pub fn new(
+        nlp_engine: Arc<EmbeddingService>,
+        store: Arc<SurrealStore<C>>,
+        workspace_lock: Arc<WorkspaceLock>,
+    ) -> Self {
         tracing::info!("NodeEmbeddingService initialized with root-aggregate model");
         Self {
             nlp_engine,
             store,
             config: EmbeddingConfig::default(),
+            summarizer: None,
+            workspace_lock,
         }
     }
 
@@ -88,6 +106,7 @@ where
     pub fn with_config(
         nlp_engine: Arc<EmbeddingService>,
         store: Arc<SurrealStore<C>>,
+        workspace_lock: Arc<WorkspaceLock>,
         config: EmbeddingConfig,
     ) -> Self {
         tracing::info!(
@@ -98,9 +117,17 @@ where
             nlp_engine,
             store,
             config,
+            summarizer: None,
+            workspace_lock,
         }
     }
 
+    /// Attach a summarization service, enabling [`Self::summarize_node`]
+    pub fn with_summarizer(mut self, summarizer: Arc<SummarizationService>) -> Self {
+        self.summarizer = Some(summarizer);
+        self
+    }
+
     /// Get reference to the NLP engine
     pub fn nlp_engine(&self) -> &Arc<EmbeddingService> {
         &self.nlp_engine
@@ -116,6 +143,11 @@ where
         &self.config
     }
 
+    /// Get reference to the summarization service, if attached
+    pub fn summarizer(&self) -> Option<&Arc<SummarizationService>> {
+        self.summarizer.as_ref()
+    }
+
     // =========================================================================
     // Root Node Detection
     // =========================================================================
@@ -210,9 +242,34 @@ where
 
         // Add descendant content
         let limit = self.config.max_descendants.min(descendants.len());
-        for node in descendants.into_iter().take(limit) {
+        let descendants: Vec<Node> = descendants.into_iter().take(limit).collect();
+        for node in &descendants {
             if !node.content.trim().is_empty() {
-                parts.push(node.content);
+                parts.push(node.content.clone());
+            }
+        }
+
+        // Add blocks embedded with `((id))` from outside the subtree, so a
+        // root is searchable by the content it transcludes
+        let in_subtree: HashSet<&str> = std::iter::once(root.id.as_str())
+            .chain(descendants.iter().map(|n| n.id.as_str()))
+            .collect();
+        let mut embed_ids: Vec<String> = Vec::new();
+        for node in std::iter::once(&root).chain(descendants.iter()) {
+            for id in extract_embeds(&node.content) {
+                if !in_subtree.contains(id.as_str()) && !embed_ids.contains(&id) {
+                    embed_ids.push(id);
+                }
+            }
+        }
+        let mut embedded = self.store.get_nodes_by_ids(&embed_ids).await.map_err(|e| {
+            NodeServiceError::query_failed(format!("Failed to get embedded nodes: {}", e))
+        })?;
+        for id in &embed_ids {
+            if let Some(node) = embedded.remove(id) {
+                if !node.content.trim().is_empty() {
+                    parts.push(node.content);
+                }
             }
         }
 
@@ -620,13 +677,15 @@ where
     /// Search for nodes by semantic similarity
     ///
     /// Generates an embedding for the query text and searches for similar
-    /// root nodes. Returns the node IDs and similarity scores.
+    /// root nodes. Returns the node IDs and similarity scores, with scores
+    /// raised by up to [`IMPORTANCE_BOOST`] for central documents.
     pub async fn semantic_search(
         &self,
         query: &str,
         limit: usize,
         threshold: f32,
     ) -> Result<Vec<EmbeddingSearchResult>, NodeServiceError> {
+        self.workspace_lock.ensure_unlocked()?;
         let total_start = std::time::Instant::now();
 
         if query.trim().is_empty() {
@@ -651,10 +710,9 @@ where
             .store
             .search_embeddings(&query_vector, limit as i64, Some(threshold as f64))
             .await
-            .map_err(|e| {
-                NodeServiceError::query_failed(format!("Semantic search failed: {}", e))
-            })?;
+            .map_err(|e| NodeServiceError::from_query_error("Semantic search failed", e))?;
         let search_time = search_start.elapsed();
+        let results = self.boost_by_importance(results).await;
 
         let total_time = total_start.elapsed();
 
@@ -666,6 +724,31 @@ where
         Ok(results)
     }
 
+    /// Scale scores by document importance and re-sort
+    ///
+    /// Search still works before importance has been computed, so a failed
+    /// lookup leaves the results as they are.
+    async fn boost_by_importance(
+        &self,
+        mut results: Vec<EmbeddingSearchResult>,
+    ) -> Vec<EmbeddingSearchResult> {
+        let ids: Vec<String> = results.iter().map(|r| r.node_id.clone()).collect();
+        let importance = match self.store.get_node_importance(&ids).await {
+            Ok(importance) => importance,
+            Err(e) => {
+                tracing::debug!("Search results not boosted: {}", e);
+                return results;
+            }
+        };
+        for result in &mut results {
+            if let Some(importance) = importance.get(&result.node_id) {
+                result.score *= 1.0 + IMPORTANCE_BOOST * importance.score;
+            }
+        }
+        results.sort_by(|a, b| b.score.total_cmp(&a.score));
+        results
+    }
+
     /// Search and return full nodes
     ///
     /// Convenience method that fetches the full Node objects for search results.
@@ -700,6 +783,39 @@ where
         Ok(nodes_with_scores)
     }
 
+    // =========================================================================
+    // Summarization
+    // =========================================================================
+
+    /// Summarize a node and its subtree in about `max_tokens` tokens
+    ///
+    /// Uses the same aggregated content as embeddings. Generation runs on a
+    /// blocking thread; without a generation model the summarizer returns an
+    /// extractive summary instead.
+    pub async fn summarize_node(
+        &self,
+        node_id: &str,
+        max_tokens: usize,
+    ) -> Result<String, NodeServiceError> {
+        self.workspace_lock.ensure_unlocked()?;
+        let summarizer = self.summarizer.clone().ok_or_else(|| {
+            NodeServiceError::initialization_error("Summarization service not available")
+        })?;
+        let content = self.aggregate_subtree_content(node_id).await?;
+        if content.trim().is_empty() {
+            return Ok(String::new());
+        }
+
+        tokio::task::spawn_blocking(move || summarizer.summarize(&content, max_tokens))
+            .await
+            .map_err(|e| {
+                NodeServiceError::query_failed(format!("Summarization task panicked: {}", e))
+            })?
+            .map_err(|e| {
+                NodeServiceError::SerializationError(format!("Summarization failed: {}", e))
+            })
+    }
+
     // =========================================================================
     // Cleanup
     // =========================================================================
@@ -4,6 +4,7 @@
 //! detailed error handling for business logic failures.
 
 use crate::db::DatabaseError;
+use crate::i18n::{Locale, UserMessage};
 use crate::models::ValidationError;
 use thiserror::Error;
 
@@ -69,6 +70,11 @@ pub enum NodeServiceError {
         actual_version: i64,
     },
 
+    /// Idempotency key reused for a different request, or retried while
+    /// its first request is still running
+    #[error("Idempotency key {key} can't be used: {reason}")]
+    IdempotencyKeyConflict { key: String, reason: String },
+
     /// Service initialization failed
     #[error("Initialization error: {0}")]
     InitializationError(String),
@@ -88,6 +94,34 @@ pub enum NodeServiceError {
     /// Maximum collection depth exceeded
     #[error("Collection path exceeds maximum depth of {max_depth} levels: {path}")]
     CollectionDepthExceeded { path: String, max_depth: usize },
+
+    /// External input (clipboard payload, imported file) could not be processed
+    #[error("Invalid input: {0}")]
+    InvalidInput(String),
+
+    /// Workspace is locked; unlock it with the passphrase first
+    #[error("Workspace is locked")]
+    WorkspaceLocked,
+
+    /// Passphrase given to unlock the workspace or change its passphrase is wrong
+    #[error("Incorrect passphrase")]
+    IncorrectPassphrase,
+
+    /// New workspace passphrase is too short
+    #[error("Passphrase must be at least {min_chars} characters")]
+    PassphraseTooShort { min_chars: usize },
+
+    /// Locking requires a workspace passphrase
+    #[error("Set a passphrase before locking the workspace")]
+    NoPassphrase,
+
+    /// Workspace was opened read-only; reads, search and export still work
+    #[error("Workspace is read-only")]
+    ReadOnly,
+
+    /// Properties violate the node type's schema
+    #[error("{}", .0.render(Locale::En))]
+    SchemaViolation(UserMessage),
 }
 
 impl NodeServiceError {
@@ -151,6 +185,22 @@ impl NodeServiceError {
         Self::QueryFailed(msg.into())
     }
 
+    /// Create an error for a failed store query, keeping timeouts typed
+    ///
+    /// A [`DatabaseError::Timeout`] anywhere in `error`'s chain becomes
+    /// [`Self::DatabaseError`] so callers can report it as such; any other
+    /// failure is a [`Self::QueryFailed`] prefixed with `context`.
+    pub fn from_query_error(context: &str, error: anyhow::Error) -> Self {
+        let timeout = error.chain().find_map(|cause| match cause.downcast_ref() {
+            Some(DatabaseError::Timeout { after }) => Some(*after),
+            _ => None,
+        });
+        match timeout {
+            Some(after) => Self::DatabaseError(DatabaseError::timeout(after)),
+            None => Self::query_failed(format!("{}: {}", context, error)),
+        }
+    }
+
     /// Create a version conflict error
     pub fn version_conflict(
         node_id: impl Into<String>,
@@ -164,6 +214,14 @@ impl NodeServiceError {
         }
     }
 
+    /// Create an idempotency key conflict error
+    pub fn idempotency_key_conflict(key: impl Into<String>, reason: impl Into<String>) -> Self {
+        Self::IdempotencyKeyConflict {
+            key: key.into(),
+            reason: reason.into(),
+        }
+    }
+
     /// Create an initialization error
     pub fn initialization_error(msg: impl Into<String>) -> Self {
         Self::InitializationError(msg.into())
@@ -191,12 +249,137 @@ impl NodeServiceError {
             max_depth,
         }
     }
+
+    /// Create an invalid input error
+    pub fn invalid_input(msg: impl Into<String>) -> Self {
+        Self::InvalidInput(msg.into())
+    }
+
+    /// Code and parameters for showing this error to users
+    ///
+    /// Internal failures (database, serialization, transactions) map to the
+    /// generic `NODE_SERVICE_ERROR`; their text belongs in logs, not the UI.
+    pub fn user_message(&self) -> UserMessage {
+        match self {
+            Self::NodeNotFound { id } => {
+                UserMessage::new("NODE_NOT_FOUND").with_param("id", id.as_str())
+            }
+            Self::VersionConflict {
+                node_id,
+                expected_version,
+                actual_version,
+            } => UserMessage::new("VERSION_CONFLICT")
+                .with_param("node_id", node_id.as_str())
+                .with_param("expected_version", *expected_version)
+                .with_param("actual_version", *actual_version),
+            Self::IdempotencyKeyConflict { .. } => UserMessage::new("IDEMPOTENCY_KEY_CONFLICT"),
+            Self::InvalidParent { parent_id } => {
+                UserMessage::new("INVALID_PARENT").with_param("parent_id", parent_id.as_str())
+            }
+            Self::InvalidRoot { root_node_id } => {
+                UserMessage::new("INVALID_ROOT").with_param("root_node_id", root_node_id.as_str())
+            }
+            Self::CircularReference { .. } => UserMessage::new("CIRCULAR_REFERENCE"),
+            Self::HierarchyViolation(_) => UserMessage::new("HIERARCHY_VIOLATION"),
+            Self::InvalidInput(_) => UserMessage::new("INVALID_INPUT"),
+            Self::InvalidUpdate(_) => UserMessage::new("INVALID_UPDATE"),
+            Self::ValidationFailed(e) => e.user_message(),
+            Self::SchemaViolation(message) => message.clone(),
+            Self::WorkspaceLocked => UserMessage::new("WORKSPACE_LOCKED"),
+            Self::IncorrectPassphrase => UserMessage::new("INCORRECT_PASSPHRASE"),
+            Self::PassphraseTooShort { min_chars } => {
+                UserMessage::new("PASSPHRASE_TOO_SHORT").with_param("min_chars", *min_chars)
+            }
+            Self::NoPassphrase => UserMessage::new("NO_PASSPHRASE"),
+            Self::ReadOnly => UserMessage::new("READ_ONLY"),
+            Self::DatabaseError(DatabaseError::Timeout { .. }) => UserMessage::new("QUERY_TIMEOUT"),
+            Self::CollectionNotFound(name) => {
+                UserMessage::new("COLLECTION_NOT_FOUND").with_param("name", name.as_str())
+            }
+            Self::InvalidCollectionPath(_) => UserMessage::new("INVALID_COLLECTION_PATH"),
+            Self::CollectionCycle(_) => UserMessage::new("COLLECTION_CYCLE"),
+            Self::CollectionDepthExceeded { max_depth, .. } => {
+                UserMessage::new("COLLECTION_DEPTH_EXCEEDED").with_param("max_depth", *max_depth)
+            }
+            _ => UserMessage::new("NODE_SERVICE_ERROR"),
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_user_message_hides_internal_details() {
+        let err = NodeServiceError::version_conflict("node-1", 2, 3);
+        let message = err.user_message();
+        assert_eq!(message.code, "VERSION_CONFLICT");
+        assert_eq!(message.params["actual_version"], 3);
+
+        let err = NodeServiceError::transaction_failed("RocksDB: lock timeout on key 0x1f");
+        let message = err.user_message();
+        assert_eq!(message.code, "NODE_SERVICE_ERROR");
+        assert!(message.params.is_empty());
+    }
+
+    #[test]
+    fn test_validation_errors_have_their_own_codes() {
+        let err: NodeServiceError = ValidationError::invalid_id("2025-13-45", "YYYY-MM-DD").into();
+        let message = err.user_message();
+        assert_eq!(message.code, "INVALID_NODE_ID");
+        assert_eq!(message.params["id"], "2025-13-45");
+        assert_eq!(message.params["format"], "YYYY-MM-DD");
+        assert_eq!(
+            message.render(Locale::En),
+            "'2025-13-45' is not a valid ID here (expected YYYY-MM-DD)"
+        );
+
+        let err: NodeServiceError =
+            ValidationError::property_type_mismatch("priority", "integer").into();
+        let message = err.user_message();
+        assert_eq!(message.code, "PROPERTY_TYPE_MISMATCH");
+        assert_eq!(message.params["field"], "priority");
+        assert!(err.to_string().contains("priority must be of type integer"));
+
+        let err: NodeServiceError =
+            ValidationError::malformed_property("views", "expected a sequence").into();
+        let message = err.user_message();
+        assert_eq!(message.code, "MALFORMED_PROPERTY");
+        assert!(!message.params.contains_key("reason"));
+        assert!(err.to_string().contains("expected a sequence"));
+    }
+
+    #[test]
+    fn test_user_message_omits_free_text_details() {
+        for err in [
+            NodeServiceError::invalid_input("Paste would create 5001 nodes (maximum 5000)"),
+            NodeServiceError::invalid_update("TaskNodeUpdate contains no changes"),
+            NodeServiceError::hierarchy_violation("Date node '2025-01-01' cannot be merged"),
+            NodeServiceError::invalid_collection_path("segment 'a:b' cannot contain ':'"),
+        ] {
+            assert!(err.user_message().params.is_empty(), "{:?}", err);
+        }
+    }
+
+    #[test]
+    fn test_query_error_keeps_timeouts_typed() {
+        let after = std::time::Duration::from_secs(30);
+        let timeout =
+            anyhow::Error::from(DatabaseError::timeout(after)).context("Failed to get children");
+        let err = NodeServiceError::from_query_error("Failed to list children", timeout);
+        assert!(matches!(
+            err,
+            NodeServiceError::DatabaseError(DatabaseError::Timeout { after: a }) if a == after
+        ));
+
+        let err =
+            NodeServiceError::from_query_error("Failed to list children", anyhow::anyhow!("boom"));
+        assert!(
+            matches!(err, NodeServiceError::QueryFailed(ref msg) if msg == "Failed to list children: boom")
+        );
+    }
+
     #[test]
     fn test_node_not_found_error() {
         let err = NodeServiceError::node_not_found("test-id");
@@ -330,6 +513,14 @@ mod tests {
         assert!(msg.contains("maximum depth of 5"));
     }
 
+    #[test]
+    fn test_invalid_input_error() {
+        let err = NodeServiceError::invalid_input("Payload too large");
+        let msg = err.to_string();
+        assert!(matches!(err, NodeServiceError::InvalidInput(_)));
+        assert!(msg.contains("Invalid input"));
+    }
+
     #[test]
     fn test_error_from_validation_error() {
         let validation_err = ValidationError::MissingField("test_field".to_string());
@@ -0,0 +1,411 @@
+//! Goal Evaluation Service - Key Result Progress Over Time
+//!
+//! Goals are `goal` nodes; their key results are `key_result` nodes placed
+//! beneath them. A key result measures one number, taken from
+//! `key_result.metric_source`:
+//!
+//! - `manual` - its own `current_value`, entered by the user
+//! - `property` - a numeric property of another node (`metric_node_id`,
+//!   addressed by the JSON pointer `metric_property`, e.g. `/custom/revenue`)
+//! - `query` - the number of results of the saved query `query_id`
+//!
+//! [`GoalEvaluationService::snapshot`] records the value of every key result
+//! in its `snapshots` list, one entry per day (later runs on the same day
+//! replace that day's entry), which is what trend charts plot.
+
+use super::error::NodeServiceError;
+use super::node_service::NodeService;
+use super::query_service::{QueryDefinition, QueryService};
+use crate::models::{Node, NodeFilter, ValidationError};
+use chrono::{Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// How often [`GoalEvaluationService::run`] re-evaluates key results
+pub const DEFAULT_GOAL_EVALUATION_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// A key result's value on one day
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProgressSnapshot {
+    pub date: NaiveDate,
+    pub value: f64,
+}
+
+/// Current standing of one key result
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyResultProgress {
+    pub key_result_id: String,
+    /// Absent when the metric can't be read (missing node, non-numeric property)
+    pub value: Option<f64>,
+    pub start_value: f64,
+    pub target_value: f64,
+    /// Share of the way from start to target, clamped to 0.0-1.0
+    pub progress: f64,
+    /// Recorded values, oldest first
+    pub snapshots: Vec<ProgressSnapshot>,
+}
+
+/// Current standing of a goal and its key results
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GoalProgress {
+    pub goal_id: String,
+    /// Mean progress of the key results (0.0 without any)
+    pub progress: f64,
+    pub key_results: Vec<KeyResultProgress>,
+}
+
+/// Where a key result reads its value from
+#[derive(Debug, Clone, PartialEq)]
+enum Metric {
+    Manual(Option<f64>),
+    Property { node_id: String, pointer: String },
+    Query { query_id: String },
+}
+
+/// The `key_result` properties evaluation works with
+#[derive(Debug, Clone, PartialEq)]
+struct KeyResultSpec {
+    metric: Metric,
+    start_value: f64,
+    target_value: f64,
+    snapshots: Vec<ProgressSnapshot>,
+}
+
+impl KeyResultSpec {
+    fn from_node(node: &Node) -> Result<Self, NodeServiceError> {
+        let props = node.properties.get("key_result").unwrap_or(&Value::Null);
+        let text = |field: &str| {
+            props
+                .get(field)
+                .and_then(Value::as_str)
+                .filter(|value| !value.is_empty())
+                .map(str::to_string)
+        };
+        let required = |field: &str| {
+            text(field).ok_or_else(|| {
+                NodeServiceError::invalid_input(format!(
+                    "Key result '{}' has no {}",
+                    node.id, field
+                ))
+            })
+        };
+
+        let metric = match text("metric_source").as_deref().unwrap_or("manual") {
+            "manual" => Metric::Manual(props.get("current_value").and_then(Value::as_f64)),
+            "property" => Metric::Property {
+                node_id: required("metric_node_id")?,
+                pointer: required("metric_property")?,
+            },
+            "query" => Metric::Query {
+                query_id: required("query_id")?,
+            },
+            other => {
+                return Err(NodeServiceError::invalid_input(format!(
+                    "Key result '{}' has unknown metric source '{}'",
+                    node.id, other
+                )))
+            }
+        };
+        let target_value = props
+            .get("target_value")
+            .and_then(Value::as_f64)
+            .ok_or_else(|| {
+                NodeServiceError::invalid_input(format!(
+                    "Key result '{}' has no numeric target_value",
+                    node.id
+                ))
+            })?;
+
+        Ok(Self {
+            metric,
+            start_value: props
+                .get("start_value")
+                .and_then(Value::as_f64)
+                .unwrap_or(0.0),
+            target_value,
+            snapshots: props
+                .get("snapshots")
+                .cloned()
+                .map(serde_json::from_value)
+                .transpose()
+                .map_err(|e| {
+                    NodeServiceError::invalid_input(format!(
+                        "Key result '{}' has malformed snapshots: {}",
+                        node.id, e
+                    ))
+                })?
+                .unwrap_or_default(),
+        })
+    }
+
+    /// Share of the way from start to target, 0.0-1.0
+    ///
+    /// Works for decreasing targets too (start 100, target 20).
+    fn progress(&self, value: Option<f64>) -> f64 {
+        let Some(value) = value else {
+            return 0.0;
+        };
+        let span = self.target_value - self.start_value;
+        if span == 0.0 {
+            return if value == self.target_value { 1.0 } else { 0.0 };
+        }
+        ((value - self.start_value) / span).clamp(0.0, 1.0)
+    }
+}
+
+/// Record `value` as the snapshot of `date`, returning whether anything changed
+fn record_snapshot(snapshots: &mut Vec<ProgressSnapshot>, date: NaiveDate, value: f64) -> bool {
+    match snapshots.iter_mut().find(|snapshot| snapshot.date == date) {
+        Some(snapshot) if snapshot.value == value => false,
+        Some(snapshot) => {
+            snapshot.value = value;
+            true
+        }
+        None => {
+            snapshots.push(ProgressSnapshot { date, value });
+            snapshots.sort_by_key(|snapshot| snapshot.date);
+            true
+        }
+    }
+}
+
+/// Evaluates key results and records their progress
+pub struct GoalEvaluationService<C = surrealdb::engine::local::Db>
+where
+    C: surrealdb::Connection,
+{
+    node_service: Arc<NodeService<C>>,
+    query_service: Arc<QueryService<C>>,
+}
+
+impl<C> GoalEvaluationService<C>
+where
+    C: surrealdb::Connection,
+{
+    pub fn new(node_service: Arc<NodeService<C>>, query_service: Arc<QueryService<C>>) -> Self {
+        Self {
+            node_service,
+            query_service,
+        }
+    }
+
+    /// Progress of a goal, evaluating its key results now
+    pub async fn goal_progress(&self, goal_id: &str) -> Result<GoalProgress, NodeServiceError> {
+        let goal = self
+            .node_service
+            .get_node(goal_id)
+            .await?
+            .ok_or_else(|| NodeServiceError::node_not_found(goal_id))?;
+        if goal.node_type != "goal" {
+            return Err(ValidationError::invalid_node_type("goal", goal.node_type).into());
+        }
+
+        let mut key_results = Vec::new();
+        for node in self.node_service.get_children(goal_id).await? {
+            if node.node_type != "key_result" {
+                continue;
+            }
+            let spec = KeyResultSpec::from_node(&node)?;
+            let value = self.evaluate(&spec).await?;
+            key_results.push(KeyResultProgress {
+                key_result_id: node.id,
+                value,
+                start_value: spec.start_value,
+                target_value: spec.target_value,
+                progress: spec.progress(value),
+                snapshots: spec.snapshots,
+            });
+        }
+
+        let progress = if key_results.is_empty() {
+            0.0
+        } else {
+            key_results.iter().map(|kr| kr.progress).sum::<f64>() / key_results.len() as f64
+        };
+        Ok(GoalProgress {
+            goal_id: goal_id.to_string(),
+            progress,
+            key_results,
+        })
+    }
+
+    /// Record today's value of every key result, returning the IDs whose
+    /// snapshots changed
+    ///
+    /// Key results that can't be evaluated are skipped with a warning, so
+    /// one broken metric doesn't stop the others from being recorded.
+    pub async fn snapshot(&self, date: NaiveDate) -> Result<Vec<String>, NodeServiceError> {
+        let key_results = self
+            .node_service
+            .query_nodes(NodeFilter::new().with_node_type("key_result".to_string()))
+            .await?;
+
+        let mut updated = Vec::new();
+        for node in key_results {
+            match self.changed_snapshots(&node, date).await {
+                Ok(Some(snapshots)) => {
+                    self.node_service
+                        .set_property(&node.id, "/key_result/snapshots", json!(snapshots))
+                        .await?;
+                    updated.push(node.id);
+                }
+                Ok(None) => {}
+                Err(e) => tracing::warn!("Skipping key result {}: {}", node.id, e),
+            }
+        }
+        Ok(updated)
+    }
+
+    /// Snapshot every `interval`, passing the changed key result IDs to
+    /// `on_snapshot`
+    ///
+    /// Never returns; callers typically spawn this and cancel it on shutdown.
+    pub async fn run<F>(&self, interval: Duration, on_snapshot: F)
+    where
+        F: Fn(Vec<String>),
+    {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            match self.snapshot(Local::now().date_naive()).await {
+                Ok(updated) if !updated.is_empty() => on_snapshot(updated),
+                Ok(_) => {}
+                Err(e) => tracing::warn!("Goal evaluation failed: {}", e),
+            }
+        }
+    }
+
+    /// Snapshots of `node` with the value of `date` recorded, if that changed them
+    async fn changed_snapshots(
+        &self,
+        node: &Node,
+        date: NaiveDate,
+    ) -> Result<Option<Vec<ProgressSnapshot>>, NodeServiceError> {
+        let mut spec = KeyResultSpec::from_node(node)?;
+        let Some(value) = self.evaluate(&spec).await? else {
+            return Ok(None);
+        };
+        Ok(record_snapshot(&mut spec.snapshots, date, value).then_some(spec.snapshots))
+    }
+
+    /// Current value of a key result's metric
+    async fn evaluate(&self, spec: &KeyResultSpec) -> Result<Option<f64>, NodeServiceError> {
+        match &spec.metric {
+            Metric::Manual(value) => Ok(*value),
+            Metric::Property { node_id, pointer } => Ok(self
+                .node_service
+                .get_node(node_id)
+                .await?
+                .and_then(|node| node.properties.pointer(pointer).and_then(Value::as_f64))),
+            Metric::Query { query_id } => {
+                let query = self
+                    .node_service
+                    .get_node(query_id)
+                    .await?
+                    .ok_or_else(|| NodeServiceError::node_not_found(query_id))?;
+                let mut definition = QueryDefinition::from_saved_query(&query)
+                    .map_err(|e| NodeServiceError::invalid_input(format!("{:#}", e)))?;
+                // The metric is the full result count, not the display page
+                definition.limit = None;
+                let nodes = self
+                    .query_service
+                    .execute(&definition)
+                    .await
+                    .map_err(|e| NodeServiceError::query_failed(format!("{:#}", e)))?;
+                Ok(Some(nodes.len() as f64))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key_result(props: Value) -> Node {
+        Node::new(
+            "key_result".to_string(),
+            "Ship 10 releases".to_string(),
+            json!({ "key_result": props }),
+        )
+    }
+
+    #[test]
+    fn test_spec_from_node() {
+        let spec = KeyResultSpec::from_node(&key_result(json!({
+            "metric_source": "property",
+            "metric_node_id": "node-1",
+            "metric_property": "/custom/revenue",
+            "target_value": 5000,
+            "snapshots": [{ "date": "2025-01-01", "value": 1200.0 }]
+        })))
+        .unwrap();
+        assert_eq!(
+            spec.metric,
+            Metric::Property {
+                node_id: "node-1".to_string(),
+                pointer: "/custom/revenue".to_string()
+            }
+        );
+        assert_eq!((spec.start_value, spec.target_value), (0.0, 5000.0));
+        assert_eq!(spec.snapshots.len(), 1);
+
+        let manual = KeyResultSpec::from_node(&key_result(
+            json!({ "current_value": 3, "target_value": 10 }),
+        ))
+        .unwrap();
+        assert_eq!(manual.metric, Metric::Manual(Some(3.0)));
+
+        assert!(KeyResultSpec::from_node(&key_result(json!({ "target_value": "ten" }))).is_err());
+        assert!(KeyResultSpec::from_node(&key_result(
+            json!({ "metric_source": "query", "target_value": 1 })
+        ))
+        .is_err());
+    }
+
+    #[test]
+    fn test_progress() {
+        let spec = |start_value, target_value| KeyResultSpec {
+            metric: Metric::Manual(None),
+            start_value,
+            target_value,
+            snapshots: Vec::new(),
+        };
+        assert_eq!(spec(0.0, 10.0).progress(Some(5.0)), 0.5);
+        assert_eq!(spec(0.0, 10.0).progress(Some(15.0)), 1.0);
+        assert_eq!(spec(0.0, 10.0).progress(None), 0.0);
+        // Decreasing target: churn from 100 down to 20
+        assert_eq!(spec(100.0, 20.0).progress(Some(60.0)), 0.5);
+        assert_eq!(spec(5.0, 5.0).progress(Some(5.0)), 1.0);
+    }
+
+    #[test]
+    fn test_record_snapshot_keeps_one_per_day() {
+        let day = |d| NaiveDate::from_ymd_opt(2025, 1, d).unwrap();
+        let mut snapshots = vec![ProgressSnapshot {
+            date: day(2),
+            value: 1.0,
+        }];
+
+        assert!(!record_snapshot(&mut snapshots, day(2), 1.0));
+        assert!(record_snapshot(&mut snapshots, day(2), 2.0));
+        assert!(record_snapshot(&mut snapshots, day(1), 0.5));
+        assert_eq!(
+            snapshots,
+            vec![
+                ProgressSnapshot {
+                    date: day(1),
+                    value: 0.5
+                },
+                ProgressSnapshot {
+                    date: day(2),
+                    value: 2.0
+                },
+            ]
+        );
+    }
+}
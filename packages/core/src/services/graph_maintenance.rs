@@ -0,0 +1,217 @@
+//! Graph Maintenance - Finding Notes That Need Tidying
+//!
+//! [`get_maintenance_report`] lists the notes (document roots) a "tidy your
+//! graph" pass should look at:
+//!
+//! - orphaned notes: nothing mentions or embeds them and no collection holds
+//!   them, so they can only be found by search
+//! - stale notes: neither the root nor anything under it was modified in the
+//!   last N months
+//! - empty notes: no text anywhere in the root or its subtree
+//! - broken mentions: `nodespace://` links whose target no longer exists or
+//!   sits in the trash
+//!
+//! Date links are never reported as broken: date nodes are created when a
+//! link to them is opened. The report only reads; acting on it is left to the
+//! user.
+
+use super::digest::NOTE_TYPES;
+use super::error::NodeServiceError;
+use super::node_service::{extract_mentions, NodeService};
+use crate::models::{build_excerpt, Node};
+use chrono::{DateTime, Months, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// Months without edits before a note is reported as stale
+pub const DEFAULT_STALE_MONTHS: u32 = 6;
+
+/// A note listed in a maintenance report
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MaintenanceEntry {
+    pub node_id: String,
+    /// First line of the note's content
+    pub title: String,
+    /// Latest modification of the note or anything under it
+    pub last_modified: DateTime<Utc>,
+}
+
+/// A link to a node that is gone
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BrokenMention {
+    /// Node whose content holds the link
+    pub node_id: String,
+    /// Note containing that node
+    pub root_id: String,
+    /// Missing or deleted target
+    pub target_id: String,
+}
+
+/// Notes that need attention, oldest first within each list
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphMaintenanceReport {
+    pub stale_after_months: u32,
+    pub orphaned_notes: Vec<MaintenanceEntry>,
+    pub stale_notes: Vec<MaintenanceEntry>,
+    pub empty_notes: Vec<MaintenanceEntry>,
+    pub broken_mentions: Vec<BrokenMention>,
+}
+
+/// Check every live note for the problems listed in the module docs
+///
+/// Notes with no edits in the last `stale_after_months` months are stale.
+/// Reads each note's subtree once, so the cost grows with the size of the
+/// graph; meant for an on-demand report, not for every render.
+pub async fn get_maintenance_report<C>(
+    node_service: &NodeService<C>,
+    stale_after_months: u32,
+) -> Result<GraphMaintenanceReport, NodeServiceError>
+where
+    C: surrealdb::Connection,
+{
+    let store = node_service.store();
+    let query_failed = |e: anyhow::Error| NodeServiceError::query_failed(e.to_string());
+    let cutoff = stale_cutoff(Utc::now(), stale_after_months);
+
+    let roots: Vec<Node> = store
+        .get_children(None)
+        .await
+        .map_err(query_failed)?
+        .into_iter()
+        .filter(|root| is_live(root) && NOTE_TYPES.contains(&root.node_type.as_str()))
+        .collect();
+
+    let mut last_modified = HashMap::new();
+    let mut stale_notes = Vec::new();
+    let mut empty_notes = Vec::new();
+    let mut links = Vec::new();
+    for root in &roots {
+        let descendants: Vec<Node> = store
+            .get_nodes_in_subtree(&root.id)
+            .await
+            .map_err(query_failed)?
+            .into_iter()
+            .filter(is_live)
+            .collect();
+        let nodes: Vec<&Node> = std::iter::once(root).chain(&descendants).collect();
+
+        let latest = nodes
+            .iter()
+            .map(|node| node.modified_at)
+            .max()
+            .unwrap_or(root.modified_at);
+        last_modified.insert(root.id.clone(), latest);
+        let entry = entry(root, latest);
+        if build_excerpt(nodes.iter().copied()).is_none() {
+            empty_notes.push(entry);
+        } else if latest < cutoff {
+            stale_notes.push(entry);
+        }
+
+        for node in nodes {
+            for target_id in mention_targets(&node.content) {
+                links.push(BrokenMention {
+                    node_id: node.id.clone(),
+                    root_id: root.id.clone(),
+                    target_id,
+                });
+            }
+        }
+    }
+
+    let target_ids: Vec<String> = links
+        .iter()
+        .map(|link| link.target_id.clone())
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    let targets = store
+        .get_nodes_by_ids(&target_ids)
+        .await
+        .map_err(query_failed)?;
+    let broken_mentions = links
+        .into_iter()
+        .filter(|link| !targets.get(&link.target_id).is_some_and(is_live))
+        .collect();
+
+    let mut orphaned_notes: Vec<MaintenanceEntry> = store
+        .get_unlinked_roots(NOTE_TYPES)
+        .await
+        .map_err(query_failed)?
+        .iter()
+        .map(|root| {
+            let latest = last_modified
+                .get(&root.id)
+                .copied()
+                .unwrap_or(root.modified_at);
+            entry(root, latest)
+        })
+        .collect();
+
+    for entries in [&mut orphaned_notes, &mut stale_notes, &mut empty_notes] {
+        entries.sort_by_key(|entry| entry.last_modified);
+    }
+    Ok(GraphMaintenanceReport {
+        stale_after_months,
+        orphaned_notes,
+        stale_notes,
+        empty_notes,
+        broken_mentions,
+    })
+}
+
+fn entry(root: &Node, last_modified: DateTime<Utc>) -> MaintenanceEntry {
+    MaintenanceEntry {
+        node_id: root.id.clone(),
+        title: root.content.lines().next().unwrap_or_default().to_string(),
+        last_modified,
+    }
+}
+
+fn is_live(node: &Node) -> bool {
+    node.lifecycle_status != "deleted"
+}
+
+/// Start of the window a note must have been edited in to be current
+fn stale_cutoff(now: DateTime<Utc>, months: u32) -> DateTime<Utc> {
+    now.checked_sub_months(Months::new(months))
+        .unwrap_or(DateTime::<Utc>::MIN_UTC)
+}
+
+/// Node IDs linked from `content`, leaving out date nodes
+fn mention_targets(content: &str) -> Vec<String> {
+    extract_mentions(content)
+        .into_iter()
+        .filter(|id| NaiveDate::parse_from_str(id, "%Y-%m-%d").is_err())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_mention_targets_skip_dates() {
+        let content = "See [@Plan](nodespace://550e8400-e29b-41d4-a716-446655440000) \
+                       on nodespace://2025-01-15";
+        assert_eq!(
+            mention_targets(content),
+            vec!["550e8400-e29b-41d4-a716-446655440000"]
+        );
+        assert!(mention_targets("No links").is_empty());
+    }
+
+    #[test]
+    fn test_stale_cutoff() {
+        let now = Utc.with_ymd_and_hms(2025, 8, 31, 12, 0, 0).unwrap();
+        assert_eq!(
+            stale_cutoff(now, 6),
+            Utc.with_ymd_and_hms(2025, 2, 28, 12, 0, 0).unwrap()
+        );
+        assert_eq!(stale_cutoff(now, 0), now);
+    }
+}
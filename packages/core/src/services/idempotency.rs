@@ -0,0 +1,273 @@
+//! Idempotency Service - Running a Retried Mutation Once
+//!
+//! Create, update, delete and move requests from the Tauri commands, the dev
+//! server and the MCP tools may carry an idempotency key. The first request
+//! with a key runs and its response is remembered (see
+//! [`crate::db::idempotency`]); a retry with the same key and the same
+//! request gets that response back, so a timed-out create doesn't produce a
+//! second node and a retried move doesn't move twice.
+//!
+//! Reusing a key for a different request, or retrying while the first
+//! attempt is still running, fails with
+//! [`NodeServiceError::IdempotencyKeyConflict`]. A failed request gives its
+//! key up, so a retry runs it again. A running request only holds its key for
+//! [`IDEMPOTENCY_CLAIM_LEASE`], so one that never finishes (a crash, a
+//! dropped future) stops blocking retries once the lease runs out.
+
+use super::error::NodeServiceError;
+use super::node_service::NodeService;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::future::Future;
+use std::time::Duration;
+
+/// How long a key's response is remembered
+pub const IDEMPOTENCY_KEY_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// How long a running request holds its key before a retry may take it over
+pub const IDEMPOTENCY_CLAIM_LEASE: Duration = Duration::from_secs(2 * 60);
+
+/// Longest accepted idempotency key
+pub const MAX_IDEMPOTENCY_KEY_LEN: usize = 255;
+
+/// How a request with an idempotency key proceeds
+pub enum IdempotentStart<T> {
+    /// The key's earlier request succeeded with this response
+    Replay(T),
+    /// Run the request, then pass its result to [`IdempotencyService::finish`]
+    Run(IdempotencyClaim),
+}
+
+/// A key held by the running request
+#[derive(Debug)]
+pub struct IdempotencyClaim {
+    key: Option<String>,
+}
+
+/// Deduplication of keyed mutations
+pub struct IdempotencyService<'a, C = surrealdb::engine::local::Db>
+where
+    C: surrealdb::Connection,
+{
+    node_service: &'a NodeService<C>,
+}
+
+impl<'a, C> IdempotencyService<'a, C>
+where
+    C: surrealdb::Connection,
+{
+    pub fn new(node_service: &'a NodeService<C>) -> Self {
+        Self { node_service }
+    }
+
+    /// Run `run` unless a request with the same `key` already succeeded
+    ///
+    /// `operation` and `request` identify what the key stands for. Without a
+    /// key, `run` simply runs.
+    pub async fn run<T, E, F, Fut>(
+        &self,
+        key: Option<&str>,
+        operation: &str,
+        request: &impl Serialize,
+        run: F,
+    ) -> Result<T, E>
+    where
+        T: Serialize + DeserializeOwned,
+        E: From<NodeServiceError>,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        match self.start(key, operation, request).await? {
+            IdempotentStart::Replay(response) => Ok(response),
+            IdempotentStart::Run(claim) => {
+                let result = run().await;
+                self.finish(claim, &result).await;
+                result
+            }
+        }
+    }
+
+    /// Claim `key` for a request, or find the response it already has
+    pub async fn start<T>(
+        &self,
+        key: Option<&str>,
+        operation: &str,
+        request: &impl Serialize,
+    ) -> Result<IdempotentStart<T>, NodeServiceError>
+    where
+        T: DeserializeOwned,
+    {
+        let Some(key) = key else {
+            return Ok(IdempotentStart::Run(IdempotencyClaim { key: None }));
+        };
+        if key.is_empty() || key.len() > MAX_IDEMPOTENCY_KEY_LEN {
+            return Err(NodeServiceError::invalid_input(format!(
+                "Idempotency keys must be 1 to {} bytes long",
+                MAX_IDEMPOTENCY_KEY_LEN
+            )));
+        }
+
+        let request_hash = request_hash(operation, request)?;
+        let existing = self
+            .node_service
+            .store()
+            .idempotency()
+            .claim(key, operation, &request_hash, IDEMPOTENCY_CLAIM_LEASE)
+            .await
+            .map_err(|e| NodeServiceError::query_failed(format!("{:#}", e)))?;
+        let Some(existing) = existing else {
+            return Ok(IdempotentStart::Run(IdempotencyClaim {
+                key: Some(key.to_string()),
+            }));
+        };
+
+        if existing.request_hash != request_hash {
+            return Err(NodeServiceError::idempotency_key_conflict(
+                key,
+                format!("it was used for a different {} request", existing.operation),
+            ));
+        }
+        let Some(response) = existing.response else {
+            return Err(NodeServiceError::idempotency_key_conflict(
+                key,
+                "its first request is still running",
+            ));
+        };
+        tracing::debug!(
+            "Replaying {} response for idempotency key {}",
+            operation,
+            key
+        );
+        serde_json::from_str(&response)
+            .map(IdempotentStart::Replay)
+            .map_err(|e| NodeServiceError::serialization_error(e.to_string()))
+    }
+
+    /// Remember a successful response under the claimed key, or give the
+    /// key up after a failure
+    ///
+    /// Bookkeeping failures are logged: the request itself already ran.
+    pub async fn finish<T, E>(&self, claim: IdempotencyClaim, result: &Result<T, E>)
+    where
+        T: Serialize,
+    {
+        let Some(key) = claim.key else {
+            return;
+        };
+        let keys = self.node_service.store().idempotency();
+        let response = match result {
+            Ok(response) => serde_json::to_string(response).ok(),
+            Err(_) => None,
+        };
+        if let Some(response) = response {
+            match keys.complete(&key, &response, IDEMPOTENCY_KEY_TTL).await {
+                Ok(()) => return,
+                Err(e) => tracing::warn!("Failed to store idempotent response: {:#}", e),
+            }
+        }
+        // Unreleased, the key would block every retry until it expires
+        if let Err(e) = keys.release(&key).await {
+            tracing::warn!("Failed to release idempotency key {}: {:#}", key, e);
+        }
+    }
+}
+
+/// Fingerprint of what a key stands for
+fn request_hash(operation: &str, request: &impl Serialize) -> Result<String, NodeServiceError> {
+    let request = serde_json::to_string(request)
+        .map_err(|e| NodeServiceError::serialization_error(e.to_string()))?;
+    let mut hasher = Sha256::new();
+    hasher.update(operation.as_bytes());
+    hasher.update([0]);
+    hasher.update(request.as_bytes());
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::SurrealStore;
+    use crate::services::CreateNodeParams;
+    use serde_json::json;
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    async fn create_test_service() -> (NodeService, TempDir) {
+        let temp_dir = TempDir::new().unwrap();
+        let mut store = Arc::new(
+            SurrealStore::new(temp_dir.path().join("test.db"))
+                .await
+                .unwrap(),
+        );
+        let node_service = NodeService::new(&mut store).await.unwrap();
+        (node_service, temp_dir)
+    }
+
+    fn params(content: &str) -> CreateNodeParams {
+        CreateNodeParams {
+            id: None,
+            node_type: "text".to_string(),
+            content: content.to_string(),
+            parent_id: None,
+            insert_after_node_id: None,
+            properties: json!({}),
+        }
+    }
+
+    async fn create(
+        node_service: &NodeService,
+        key: &str,
+        content: &str,
+    ) -> Result<String, NodeServiceError> {
+        IdempotencyService::new(node_service)
+            .run(Some(key), "create_node", &content, || {
+                node_service.create_node_with_parent(params(content))
+            })
+            .await
+    }
+
+    #[tokio::test]
+    async fn test_retry_with_key_returns_first_response() {
+        let (node_service, _temp_dir) = create_test_service().await;
+
+        let first = create(&node_service, "key-1", "Once").await.unwrap();
+        let retried = create(&node_service, "key-1", "Once").await.unwrap();
+        assert_eq!(first, retried);
+        let created = node_service
+            .query_nodes_simple(crate::models::NodeQuery {
+                content_contains: Some("Once".to_string()),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(created.len(), 1);
+
+        // The same key with a different request is refused
+        let error = create(&node_service, "key-1", "Twice").await.unwrap_err();
+        assert!(matches!(
+            error,
+            NodeServiceError::IdempotencyKeyConflict { .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_failed_request_releases_its_key() {
+        let (node_service, _temp_dir) = create_test_service().await;
+        let idempotency = IdempotencyService::new(&node_service);
+
+        let failed: Result<String, NodeServiceError> = idempotency
+            .run(Some("key-2"), "delete_node", &"n1", || async {
+                Err(NodeServiceError::node_not_found("n1"))
+            })
+            .await;
+        assert!(failed.is_err());
+
+        let retried: Result<String, NodeServiceError> = idempotency
+            .run(Some("key-2"), "delete_node", &"n1", || async {
+                Ok("deleted".to_string())
+            })
+            .await;
+        assert_eq!(retried.unwrap(), "deleted");
+    }
+}
@@ -0,0 +1,271 @@
+//! Importance Service - Central Notes in the Mention Graph
+//!
+//! Periodically ranks every document by how central it is in the link graph:
+//! mentions and embeds are lifted to document level (as for the graph view)
+//! and scored with PageRank, so a note linked from other well-linked notes
+//! outranks one with many links from notes nobody visits. See
+//! [`NodeImportance`] for what is stored.
+//!
+//! Scores live in the derived `node_importance` table rather than on the
+//! nodes, so a refresh neither bumps versions nor emits events. They feed
+//! the search boost in [`NodeEmbeddingService`](super::NodeEmbeddingService)
+//! and the "most central notes" list.
+
+use super::error::NodeServiceError;
+use super::node_service::NodeService;
+use crate::models::{
+    importance, merge_edges, Node, NodeImportance, DEFAULT_GRAPH_EDGE_TYPES, GRAPH_EXCLUDED_TYPES,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// How often [`ImportanceService::run`] recomputes scores
+pub const DEFAULT_IMPORTANCE_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+
+/// A document in the "most central notes" list
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CentralNote {
+    pub node_type: String,
+    pub title: String,
+    /// Includes the node ID
+    #[serde(flatten)]
+    pub importance: NodeImportance,
+}
+
+/// Computes and serves document importance scores
+pub struct ImportanceService<C = surrealdb::engine::local::Db>
+where
+    C: surrealdb::Connection,
+{
+    node_service: Arc<NodeService<C>>,
+}
+
+impl<C> ImportanceService<C>
+where
+    C: surrealdb::Connection,
+{
+    pub fn new(node_service: Arc<NodeService<C>>) -> Self {
+        Self { node_service }
+    }
+
+    /// Recompute and store the importance of every document
+    ///
+    /// Returns the number of documents scored. Refused in read-only sessions.
+    pub async fn refresh(&self) -> Result<usize, NodeServiceError> {
+        if self.node_service.is_read_only() {
+            return Err(NodeServiceError::ReadOnly);
+        }
+        let store = self.node_service.store();
+        let query_failed = |e: anyhow::Error| NodeServiceError::query_failed(e.to_string());
+
+        let edge_types: Vec<String> = DEFAULT_GRAPH_EDGE_TYPES
+            .iter()
+            .map(|t| t.to_string())
+            .collect();
+        let edges = merge_edges(
+            store
+                .get_container_edges(&edge_types)
+                .await
+                .map_err(query_failed)?,
+        );
+        let roots = store.get_children(None).await.map_err(query_failed)?;
+        let mut ids: HashSet<String> = roots
+            .into_iter()
+            .filter(is_ranked)
+            .map(|root| root.id)
+            .collect();
+        // Tasks are containers of their own, so linked tasks are ranked too
+        for edge in &edges {
+            ids.insert(edge.source.clone());
+            ids.insert(edge.target.clone());
+        }
+
+        let ids: Vec<String> = ids.into_iter().collect();
+        let nodes = store.get_nodes_by_ids(&ids).await.map_err(query_failed)?;
+        let ids: Vec<String> = ids
+            .into_iter()
+            .filter(|id| nodes.get(id).is_some_and(is_ranked))
+            .collect();
+
+        let scores = importance(&ids, &edges);
+        store
+            .replace_node_importance(&scores)
+            .await
+            .map_err(query_failed)?;
+        Ok(scores.len())
+    }
+
+    /// The `limit` most central documents, from the last refresh
+    pub async fn most_central(&self, limit: usize) -> Result<Vec<CentralNote>, NodeServiceError> {
+        let store = self.node_service.store();
+        let query_failed = |e: anyhow::Error| NodeServiceError::query_failed(e.to_string());
+        let scores = store
+            .get_top_node_importance(limit)
+            .await
+            .map_err(query_failed)?;
+        let ids: Vec<String> = scores.iter().map(|s| s.node_id.clone()).collect();
+        let nodes = store.get_nodes_by_ids(&ids).await.map_err(query_failed)?;
+        Ok(scores
+            .into_iter()
+            .filter_map(|importance| {
+                let node = nodes.get(&importance.node_id)?;
+                Some(CentralNote {
+                    node_type: node.node_type.clone(),
+                    title: node.title.clone().unwrap_or_else(|| {
+                        node.content.lines().next().unwrap_or_default().to_string()
+                    }),
+                    importance,
+                })
+            })
+            .collect())
+    }
+
+    /// Refresh every `interval`, passing the number of scored documents to
+    /// `on_refresh`
+    ///
+    /// Never returns; callers typically spawn this and cancel it on shutdown.
+    pub async fn run<F>(&self, interval: Duration, on_refresh: F)
+    where
+        F: Fn(usize),
+    {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            match self.refresh().await {
+                Ok(scored) => on_refresh(scored),
+                Err(e) => tracing::warn!("Importance refresh failed: {}", e),
+            }
+        }
+    }
+}
+
+fn is_ranked(node: &Node) -> bool {
+    node.lifecycle_status != "deleted" && !GRAPH_EXCLUDED_TYPES.contains(&node.node_type.as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::SurrealStore;
+    use crate::models::NodeUpdate;
+    use crate::services::CreateNodeParams;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    async fn create_test_service() -> (Arc<NodeService>, TempDir) {
+        let temp_dir = TempDir::new().unwrap();
+        let mut store = Arc::new(
+            SurrealStore::new(temp_dir.path().join("test.db"))
+                .await
+                .unwrap(),
+        );
+        let node_service = NodeService::new(&mut store).await.unwrap();
+        (Arc::new(node_service), temp_dir)
+    }
+
+    async fn create_root(node_service: &NodeService, content: &str) -> String {
+        node_service
+            .create_node_with_parent(CreateNodeParams {
+                id: None,
+                node_type: "text".to_string(),
+                content: content.to_string(),
+                parent_id: None,
+                insert_after_node_id: None,
+                properties: json!({}),
+            })
+            .await
+            .unwrap()
+    }
+
+    fn ids(notes: &[CentralNote]) -> Vec<&str> {
+        notes
+            .iter()
+            .map(|note| note.importance.node_id.as_str())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_refresh_ranks_linked_documents_first() {
+        let (node_service, _temp_dir) = create_test_service().await;
+        let hub = create_root(&node_service, "Hub").await;
+        let a = create_root(&node_service, "Page A").await;
+        let b = create_root(&node_service, "Page B").await;
+        let lonely = create_root(&node_service, "Lonely").await;
+        node_service.add_mention(&a, &hub).await.unwrap();
+        node_service.add_mention(&b, &hub).await.unwrap();
+
+        let service = ImportanceService::new(node_service.clone());
+        assert_eq!(service.refresh().await.unwrap(), 4);
+
+        let central = service.most_central(10).await.unwrap();
+        assert_eq!(central.len(), 4);
+        assert_eq!(central[0].importance.node_id, hub);
+        assert_eq!(central[0].importance.rank, 1);
+        assert_eq!(central[0].importance.inbound, 2);
+        assert_eq!(central[0].node_type, "text");
+        assert_eq!(central[0].title, "Hub");
+        assert!(ids(&central).contains(&lonely.as_str()));
+
+        let top = service.most_central(1).await.unwrap();
+        assert_eq!(ids(&top), vec![hub.as_str()]);
+    }
+
+    #[tokio::test]
+    async fn test_refresh_leaves_out_excluded_node_types() {
+        let (node_service, _temp_dir) = create_test_service().await;
+        let page = create_root(&node_service, "Page").await;
+        // Core schemas are seeded as root nodes
+        assert!(node_service.get_node("task").await.unwrap().is_some());
+
+        let service = ImportanceService::new(node_service.clone());
+        assert_eq!(service.refresh().await.unwrap(), 1);
+        let central = service.most_central(10).await.unwrap();
+        assert_eq!(ids(&central), vec![page.as_str()]);
+    }
+
+    #[tokio::test]
+    async fn test_refresh_drops_deleted_documents() {
+        let (node_service, _temp_dir) = create_test_service().await;
+        let a = create_root(&node_service, "Page A").await;
+        let b = create_root(&node_service, "Page B").await;
+        node_service.add_mention(&a, &b).await.unwrap();
+
+        let service = ImportanceService::new(node_service.clone());
+        assert_eq!(service.refresh().await.unwrap(), 2);
+
+        let version = node_service.get_node(&b).await.unwrap().unwrap().version;
+        node_service
+            .update_node(
+                &b,
+                version,
+                NodeUpdate::new().with_lifecycle_status("deleted".to_string()),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(service.refresh().await.unwrap(), 1);
+        let central = service.most_central(10).await.unwrap();
+        assert_eq!(ids(&central), vec![a.as_str()]);
+        assert_eq!(central[0].importance.outbound, 0);
+    }
+
+    #[tokio::test]
+    async fn test_refresh_is_refused_in_read_only_mode() {
+        let (node_service, _temp_dir) = create_test_service().await;
+        let page = create_root(&node_service, "Page").await;
+        let service = ImportanceService::new(node_service.clone());
+        service.refresh().await.unwrap();
+
+        node_service.set_read_only(true);
+        assert!(matches!(
+            service.refresh().await,
+            Err(NodeServiceError::ReadOnly)
+        ));
+        // Scores of the last refresh are still served
+        let central = service.most_central(10).await.unwrap();
+        assert_eq!(ids(&central), vec![page.as_str()]);
+    }
+}
@@ -0,0 +1,248 @@
+//! Inbox Service - Quick Capture and Triage
+//!
+//! Captured thoughts are created as standalone root nodes and filed into the
+//! `Inbox` collection, so capturing never has to decide where something goes.
+//! Triage later moves each item to its destination and takes it out of the
+//! inbox:
+//!
+//! - [`InboxDestination::Parent`] - move under a node (a project page, a date)
+//! - [`InboxDestination::Collection`] - keep as a root, file into a collection
+//! - [`InboxDestination::Keep`] - keep as a root, just leave the inbox
+//! - [`InboxDestination::Delete`] - discard
+
+use super::collection_service::CollectionService;
+use super::error::NodeServiceError;
+use super::node_service::{CreateNodeParams, NodeService};
+use crate::models::Node;
+use crate::utils::find_natural_date;
+use chrono::{Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+/// Collection that captured nodes are filed into
+pub const INBOX_COLLECTION: &str = "Inbox";
+
+/// Node types accepted by [`InboxService::capture`]
+const CAPTURE_NODE_TYPES: &[&str] = &["text", "task"];
+
+/// Where a triaged inbox item goes
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum InboxDestination {
+    /// Move under `parent_id`; date IDs (`YYYY-MM-DD`) are created on demand
+    Parent { parent_id: String },
+    /// Stay a root and join the collection at `path`
+    Collection { path: String },
+    /// Stay a root without further filing
+    Keep,
+    /// Delete the item
+    Delete,
+}
+
+/// Quick capture into, and triage out of, the inbox collection
+pub struct InboxService<'a, C = surrealdb::engine::local::Db>
+where
+    C: surrealdb::Connection,
+{
+    node_service: &'a NodeService<C>,
+}
+
+impl<'a, C> InboxService<'a, C>
+where
+    C: surrealdb::Connection,
+{
+    pub fn new(node_service: &'a NodeService<C>) -> Self {
+        Self { node_service }
+    }
+
+    fn collections(&self) -> CollectionService<'_, C> {
+        CollectionService::new(self.node_service.store(), self.node_service)
+    }
+
+    /// Create a root node from `text` and file it into the inbox
+    ///
+    /// `node_type` defaults to `text`; `task` captures an open task. A date
+    /// phrase in a task ("Call the bank by friday") becomes its due date and
+    /// is taken out of the content.
+    pub async fn capture(
+        &self,
+        text: &str,
+        node_type: Option<&str>,
+    ) -> Result<Node, NodeServiceError> {
+        let content = text.trim();
+        if content.is_empty() {
+            return Err(NodeServiceError::invalid_update(
+                "Captured text must not be empty",
+            ));
+        }
+        let node_type = node_type.unwrap_or("text");
+        if !CAPTURE_NODE_TYPES.contains(&node_type) {
+            return Err(NodeServiceError::invalid_update(format!(
+                "Cannot capture '{}' nodes (expected one of: {})",
+                node_type,
+                CAPTURE_NODE_TYPES.join(", ")
+            )));
+        }
+
+        let (content, properties) = if node_type == "task" {
+            match split_due_date(content, Local::now().date_naive()) {
+                (content, Some(due)) => (content, json!({ "due_date": due.to_string() })),
+                (content, None) => (content, json!({})),
+            }
+        } else {
+            (content.to_string(), json!({}))
+        };
+
+        let id = self
+            .node_service
+            .create_node_with_parent(CreateNodeParams {
+                id: None,
+                node_type: node_type.to_string(),
+                content,
+                parent_id: None,
+                insert_after_node_id: None,
+                properties,
+            })
+            .await?;
+        self.collections()
+            .add_to_collection_by_path(&id, INBOX_COLLECTION)
+            .await?;
+
+        self.node_service
+            .get_node(&id)
+            .await?
+            .ok_or_else(|| NodeServiceError::node_not_found(&id))
+    }
+
+    /// Items waiting in the inbox, oldest first
+    pub async fn items(&self) -> Result<Vec<Node>, NodeServiceError> {
+        let Some(inbox) = self.inbox_collection().await? else {
+            return Ok(Vec::new());
+        };
+        let mut items = self.collections().get_collection_members(&inbox.id).await?;
+        items.sort_by_key(|item| item.created_at);
+        Ok(items)
+    }
+
+    /// Move an inbox item to `destination` and remove it from the inbox
+    ///
+    /// Returns the updated node, or `None` when it was deleted.
+    pub async fn process(
+        &self,
+        node_id: &str,
+        destination: InboxDestination,
+    ) -> Result<Option<Node>, NodeServiceError> {
+        let node = self
+            .node_service
+            .get_node(node_id)
+            .await?
+            .ok_or_else(|| NodeServiceError::node_not_found(node_id))?;
+        let inbox = self
+            .inbox_collection()
+            .await?
+            .filter(|inbox| node.member_of.contains(&inbox.id))
+            .ok_or_else(|| {
+                NodeServiceError::invalid_update(format!("Node {} is not in the inbox", node_id))
+            })?;
+
+        match destination {
+            InboxDestination::Delete => {
+                self.node_service.delete_node(node_id, node.version).await?;
+                return Ok(None);
+            }
+            InboxDestination::Parent { parent_id } => {
+                if NaiveDate::parse_from_str(&parent_id, "%Y-%m-%d").is_ok() {
+                    self.node_service.ensure_date_exists(&parent_id).await?;
+                }
+                self.node_service
+                    .move_node(node_id, node.version, Some(&parent_id), None)
+                    .await?;
+            }
+            InboxDestination::Collection { path } => {
+                self.collections()
+                    .add_to_collection_by_path(node_id, &path)
+                    .await?;
+            }
+            InboxDestination::Keep => {}
+        }
+
+        self.collections()
+            .remove_from_collection(node_id, &inbox.id)
+            .await?;
+        self.node_service.get_node(node_id).await
+    }
+
+    async fn inbox_collection(&self) -> Result<Option<Node>, NodeServiceError> {
+        self.collections()
+            .get_collection_by_name(INBOX_COLLECTION)
+            .await
+    }
+}
+
+/// Task text without its date phrase, and the date it names
+///
+/// Text that is nothing but a date phrase is kept as it is.
+fn split_due_date(text: &str, today: NaiveDate) -> (String, Option<NaiveDate>) {
+    match find_natural_date(text, today) {
+        Some(found) => {
+            let rest = found.remove_from(text);
+            if rest.is_empty() {
+                (text.to_string(), None)
+            } else {
+                (rest, Some(found.date))
+            }
+        }
+        None => (text.to_string(), None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_destination_wire_format() {
+        let parent: InboxDestination =
+            serde_json::from_value(json!({ "type": "parent", "parent_id": "2025-01-15" })).unwrap();
+        assert_eq!(
+            parent,
+            InboxDestination::Parent {
+                parent_id: "2025-01-15".to_string()
+            }
+        );
+
+        let collection: InboxDestination =
+            serde_json::from_value(json!({ "type": "collection", "path": "work:ideas" })).unwrap();
+        assert_eq!(
+            collection,
+            InboxDestination::Collection {
+                path: "work:ideas".to_string()
+            }
+        );
+
+        assert_eq!(
+            serde_json::to_value(InboxDestination::Keep).unwrap(),
+            json!({ "type": "keep" })
+        );
+    }
+
+    #[test]
+    fn test_split_due_date_from_task_text() {
+        let today = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+        assert_eq!(
+            split_due_date("Call the bank by Friday", today),
+            (
+                "Call the bank".to_string(),
+                NaiveDate::from_ymd_opt(2025, 1, 17)
+            )
+        );
+        assert_eq!(
+            split_due_date("Buy milk", today),
+            ("Buy milk".to_string(), None)
+        );
+        assert_eq!(
+            split_due_date("tomorrow", today),
+            ("tomorrow".to_string(), None)
+        );
+    }
+}
@@ -13,8 +13,10 @@
 //!
 //! The service wraps the core MCP server (`mcp::server`) and handles:
 //! - Port configuration (via `MCP_PORT` env var or default 3100)
+//! - Message size limit (via `MCP_MAX_MESSAGE_BYTES` env var or default 4 MiB)
+//! - Agent profiles scoping what each connection may access
 //! - Service lifecycle (start, with future stop/restart support)
-//! - Transport selection (HTTP by default, stdio available)
+//! - Transport selection (HTTP by default, stdio via [`McpServerService::start_stdio`])
 //!
 //! # Example (Browser Mode - No Callbacks)
 //!
@@ -39,11 +41,28 @@
 //! ```
 
 use crate::mcp;
+use crate::mcp::AgentProfile;
 use crate::services::{NodeEmbeddingService, NodeService};
 use serde_json::Value;
 use std::sync::Arc;
 use tracing::info;
 
+/// Client ID attached to domain events caused by MCP requests
+///
+/// Lets subscribers tell agent-made changes apart, e.g. to surface
+/// destructive operations to the user.
+pub const MCP_CLIENT_ID: &str = "mcp-server";
+
+/// Whether events from `client_id` were caused by MCP requests
+///
+/// HTTP sessions extend [`MCP_CLIENT_ID`] with their session ID (see
+/// [`crate::mcp::session`]).
+pub fn is_mcp_client_id(client_id: &str) -> bool {
+    client_id
+        .strip_prefix(MCP_CLIENT_ID)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with(':'))
+}
+
 /// Callback type for handling successful MCP responses
 ///
 /// Invoked after each successful MCP operation with (method_name, result_value).
@@ -70,6 +89,8 @@ where
     node_service: Arc<NodeService<C>>,
     embedding_service: Arc<NodeEmbeddingService<C>>,
     port: u16,
+    agent_profiles: Vec<AgentProfile>,
+    max_message_bytes: usize,
 }
 
 impl<C> McpServerService<C>
@@ -81,6 +102,7 @@ where
     /// # Arguments
     ///
     /// * `node_service` - Shared NodeService instance for node operations
+    ///   (scoped to [`MCP_CLIENT_ID`] for the server's requests)
     /// * `embedding_service` - Shared embedding service for semantic search
     /// * `port` - HTTP port to listen on (typically 3100)
     ///
@@ -100,12 +122,29 @@ where
         port: u16,
     ) -> Self {
         Self {
-            node_service,
+            node_service: Arc::new(node_service.with_client(MCP_CLIENT_ID)),
             embedding_service,
             port,
+            agent_profiles: Vec::new(),
+            max_message_bytes: default_max_message_bytes(),
         }
     }
 
+    /// Restrict connections to the given agent profiles
+    ///
+    /// Once any profile is set, each connection must present a profile's
+    /// token and is limited to that profile's roots and collections.
+    pub fn with_agent_profiles(mut self, agent_profiles: Vec<AgentProfile>) -> Self {
+        self.agent_profiles = agent_profiles;
+        self
+    }
+
+    /// Reject messages larger than `max_message_bytes`
+    pub fn with_max_message_bytes(mut self, max_message_bytes: usize) -> Self {
+        self.max_message_bytes = max_message_bytes;
+        self
+    }
+
     /// Get the configured port
     pub fn port(&self) -> u16 {
         self.port
@@ -125,6 +164,9 @@ where
         let services = mcp::server::McpServices {
             node_service: self.node_service.clone(),
             embedding_service: self.embedding_service.clone(),
+            agent_profiles: self.agent_profiles.clone(),
+            max_message_bytes: self.max_message_bytes,
+            tools: mcp::handlers::tools::ToolIndex::new(),
         };
 
         mcp::run_mcp_server_with_callback(
@@ -135,6 +177,24 @@ where
         .await
     }
 
+    /// Serve a single agent over stdin/stdout instead of HTTP (headless mode)
+    ///
+    /// The agent profile token, if profiles are set, comes from the
+    /// `NODESPACE_MCP_TOKEN` environment variable. Returns when stdin closes.
+    pub async fn start_stdio(&self, framing: mcp::StdioFraming) -> anyhow::Result<()> {
+        info!("Starting MCP server on stdio ({:?} framing)...", framing);
+
+        let services = mcp::server::McpServices {
+            node_service: self.node_service.clone(),
+            embedding_service: self.embedding_service.clone(),
+            agent_profiles: self.agent_profiles.clone(),
+            max_message_bytes: self.max_message_bytes,
+            tools: mcp::handlers::tools::ToolIndex::new(),
+        };
+
+        mcp::run_mcp_server(services, mcp::server::McpTransport::Stdio { framing }).await
+    }
+
     /// Start the MCP server with a response callback (Tauri mode)
     ///
     /// Starts the HTTP server with a callback that's invoked after each
@@ -157,6 +217,9 @@ where
         let services = mcp::server::McpServices {
             node_service: self.node_service.clone(),
             embedding_service: self.embedding_service.clone(),
+            agent_profiles: self.agent_profiles.clone(),
+            max_message_bytes: self.max_message_bytes,
+            tools: mcp::handlers::tools::ToolIndex::new(),
         };
 
         mcp::run_mcp_server_with_callback(
@@ -179,3 +242,14 @@ pub fn default_mcp_port() -> u16 {
         .and_then(|p| p.parse().ok())
         .unwrap_or(3100)
 }
+
+/// Get the MCP message size limit from environment variable or fallback
+///
+/// Reads `MCP_MAX_MESSAGE_BYTES`, falling back to
+/// [`DEFAULT_MAX_MESSAGE_BYTES`](mcp::server::DEFAULT_MAX_MESSAGE_BYTES).
+pub fn default_max_message_bytes() -> usize {
+    std::env::var("MCP_MAX_MESSAGE_BYTES")
+        .ok()
+        .and_then(|bytes| bytes.parse().ok())
+        .unwrap_or(mcp::server::DEFAULT_MAX_MESSAGE_BYTES)
+}
@@ -10,7 +10,28 @@
 //! - `InboundRelationshipCache` - Fast NLP discovery of inbound relationships
 //! - `McpServerService` - MCP server lifecycle management (Issue #715)
 //! - `QueryService` - Query execution with SQL translation (Issue #440)
+//! - `QueryWatchService` - Added/removed/moved diffs of watched saved queries
+//! - `ChildViewService` - Saved filter/sort/group views of a node's children
 //! - `CollectionService` - Collection path parsing and membership management (Issue #756)
+//! - `SmartCollectionService` - Cached members of collections defined by a saved query
+//! - `ScriptService` - Sandboxed user scripts bound to node lifecycle hooks
+//! - `PasteProcessor` - Structured clipboard paste into node trees
+//! - `AttachmentStore` - Content-addressed storage for imported binary files
+//! - `VaultMirrorService` - Mirror of document roots into a markdown folder (optionally two-way)
+//! - `InboxService` - Quick capture into the Inbox collection and triage out of it
+//! - `ReminderService` - Detection of tasks that have come due
+//! - `TaskRolloverService` - Carrying open tasks from past date nodes to today
+//! - `ProjectRollupCache` - Cached task progress of project nodes
+//! - `RollupCache` - Incrementally maintained values of rollups declared on parent nodes
+//! - `GoalEvaluationService` - Key result evaluation and daily progress snapshots
+//! - `DigestService` - Weekly review nodes filed under week period nodes
+//! - `get_maintenance_report` - Orphaned, stale and empty notes and broken mentions
+//! - `ImportanceService` - PageRank-style importance of documents in the mention graph
+//! - `RenamePropagationService` - Mention text kept in step with renamed nodes
+//! - `WorkspaceLock` - Passphrase lock and idle auto-lock of the workspace
+//! - `EditJournal` - Write-ahead journal of editor content replayed after a crash
+//! - `resolve_deep_link` - Parsing and resolution of `nodespace://` URIs opened from other apps
+//! - `IdempotencyService` - Deduplication of retried mutations carrying an idempotency key
 //!
 //! Schema nodes are managed via generic NodeService CRUD operations (Issue #690).
 //! Validation is handled by SchemaNodeBehavior. DDL generation by SchemaTableManager.
@@ -18,32 +39,105 @@
 //! Services coordinate between the database layer and application logic,
 //! implementing business rules and orchestrating complex operations.
 
+pub mod attachment_store;
+pub mod child_views;
 pub mod collection_service;
+pub mod deep_link;
+pub mod digest;
+pub mod edit_journal;
 pub mod embedding_processor;
 pub mod embedding_service;
 pub mod error;
+pub mod goal_evaluation;
+pub mod graph_maintenance;
+pub mod idempotency;
+pub mod importance;
+pub mod inbox;
 pub mod mcp_server_service;
 pub mod migration_registry;
 pub mod migrations;
 pub mod node_service;
+pub mod paste_processor;
+pub mod project_rollup;
 pub mod query_service;
+pub mod query_watch;
 pub mod relationship_cache;
+pub mod reminder_service;
+pub mod rename_propagation;
+pub mod rollups;
+pub mod root_excerpt;
 pub mod schema_table_manager;
+pub mod script_service;
+pub mod smart_collections;
+pub mod task_rollover;
+pub mod vault_mirror;
+pub mod workspace_lock;
 
+pub use attachment_store::{
+    attachment_hash, attachment_uri, Attachment, AttachmentStore, ATTACHMENT_URI_SCHEME,
+};
+pub use child_views::{ChildViewResult, ChildViewService};
 pub use collection_service::{
     build_path_string, normalize_collection_name, parse_collection_path, validate_collection_name,
-    CollectionPath, CollectionSegment, CollectionService, ResolvedCollection, ResolvedPath,
-    COLLECTION_PATH_DELIMITER, MAX_COLLECTION_DEPTH,
+    CollectionInfo, CollectionPath, CollectionSegment, CollectionService, PathChange, RenamedPath,
+    ResolvedCollection, ResolvedPath, COLLECTION_PATH_DELIMITER, MAX_COLLECTION_DEPTH,
 };
+pub use deep_link::{resolve_deep_link, DeepLink, NavigationTarget, DEEP_LINK_SCHEME};
+pub use digest::{DigestEntry, DigestService, WeeklyDigest};
+pub use edit_journal::{EditJournal, JournalEntry, JournalReplay, EDIT_JOURNAL_FILE};
 pub use embedding_processor::{EmbeddingProcessor, EmbeddingWaker};
 pub use embedding_service::{NodeEmbeddingService, EMBEDDING_DIMENSION};
 pub use error::NodeServiceError;
-pub use mcp_server_service::{default_mcp_port, McpResponseCallback, McpServerService};
+pub use goal_evaluation::{
+    GoalEvaluationService, GoalProgress, KeyResultProgress, ProgressSnapshot,
+    DEFAULT_GOAL_EVALUATION_INTERVAL,
+};
+pub use graph_maintenance::{
+    get_maintenance_report, BrokenMention, GraphMaintenanceReport, MaintenanceEntry,
+    DEFAULT_STALE_MONTHS,
+};
+pub use idempotency::{
+    IdempotencyClaim, IdempotencyService, IdempotentStart, IDEMPOTENCY_CLAIM_LEASE,
+    IDEMPOTENCY_KEY_TTL, MAX_IDEMPOTENCY_KEY_LEN,
+};
+pub use importance::{CentralNote, ImportanceService, DEFAULT_IMPORTANCE_INTERVAL};
+pub use inbox::{InboxDestination, InboxService, INBOX_COLLECTION};
+pub use mcp_server_service::{
+    default_mcp_port, is_mcp_client_id, McpResponseCallback, McpServerService, MCP_CLIENT_ID,
+};
 pub use migration_registry::{MigrationRegistry, MigrationTransform};
-pub use node_service::{CreateNodeParams, NodeService, SubtreeData, DEFAULT_QUERY_LIMIT};
+pub use node_service::{
+    CreateNodeParams, NodeService, SubtreeData, AUDIT_LOG_TARGET, DEFAULT_QUERY_LIMIT,
+    REPLACE_BATCH_SIZE,
+};
+pub use paste_processor::{
+    PasteFormat, PasteOutcome, PastePayload, PasteProcessor, PasteResult, MAX_PASTE_NODES,
+    MAX_PASTE_SIZE,
+};
+pub use project_rollup::{ProjectRollup, ProjectRollupCache};
 pub use query_service::{
-    FilterOperator, FilterType, QueryDefinition, QueryFilter, QueryService, RelationshipType,
-    SortConfig, SortDirection,
+    EdgeFilter, FilterOperator, FilterType, QueryDefinition, QueryExplanation, QueryFilter,
+    QueryPath, QueryService, RelationshipType, SortConfig, SortDirection,
+};
+pub use query_watch::{QueryResultDiff, QueryWatchService, ResultPosition, QUERY_WATCH_DEBOUNCE};
+pub use relationship_cache::{
+    CacheStats, InboundRelationship, InboundRelationshipCache, DEFAULT_HOT_ROOTS,
+    DEFAULT_MAX_TARGET_TYPES,
+};
+pub use reminder_service::{Reminder, ReminderService, DEFAULT_REMINDER_INTERVAL};
+pub use rename_propagation::{
+    PendingRename, RenamePropagationService, RENAME_CLIENT_ID, RENAME_DEBOUNCE,
 };
-pub use relationship_cache::{CacheStats, InboundRelationship, InboundRelationshipCache};
+pub use rollups::{RollupCache, RollupRefresh};
+pub use root_excerpt::{RootExcerptService, EXCERPT_CLIENT_ID, EXCERPT_DEBOUNCE};
 pub use schema_table_manager::SchemaTableManager;
+pub use script_service::{
+    ScriptEffects, ScriptError, ScriptLimits, ScriptSandbox, ScriptService, SCRIPT_CLIENT_ID,
+};
+pub use smart_collections::{SmartCollectionCache, SmartCollectionService};
+pub use task_rollover::{CarriedOverCopy, RolloverMode, RolloverReport, TaskRolloverService};
+pub use vault_mirror::{
+    MirrorSyncStats, VaultMirrorConfig, VaultMirrorService, DEFAULT_EXTERNAL_EDIT_POLL,
+    DEFAULT_MIRROR_DEBOUNCE, MIRROR_MANIFEST_FILE,
+};
+pub use workspace_lock::WorkspaceLock;
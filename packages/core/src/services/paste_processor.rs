@@ -0,0 +1,583 @@
+//! Paste Processor - Structured Clipboard Import
+//!
+//! Converts raw clipboard payloads into node trees so multi-line pastes become
+//! properly nested nodes instead of a single text node.
+//!
+//! ## Supported Formats
+//!
+//! | Format | Detection | Conversion |
+//! |--------|-----------|------------|
+//! | Tana Paste | First line is `%%tana%%` | Outline with `key:: value` fields and `[ ]` todos |
+//! | Notion | HTML contains Notion block markers | Markdown from plain text (HTML fallback) |
+//! | Markdown | Headings, bullets, fences, quotes | Markdown import parser |
+//! | HTML | HTML present without markdown-looking text | HTML → markdown → parser |
+//! | Plain text | Anything else | One node per line, indentation → hierarchy |
+//!
+//! Parsing is pure ([`PasteProcessor::process`]); [`PasteProcessor::paste`] inserts
+//! the result below a parent, after an optional sibling.
+
+use crate::db::FractionalIndex;
+use crate::mcp::handlers::markdown::{prepare_nodes_from_markdown, PreparedNode};
+use crate::services::{CreateNodeParams, NodeService, NodeServiceError};
+use crate::utils::html_to_markdown;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+/// Maximum clipboard payload size (1MB), matching the markdown import limit
+pub const MAX_PASTE_SIZE: usize = 1_000_000;
+
+/// Maximum number of nodes created by a single paste
+pub const MAX_PASTE_NODES: usize = 1000;
+
+/// Clipboard format of a paste payload
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PasteFormat {
+    Markdown,
+    Html,
+    #[serde(rename = "plain")]
+    PlainText,
+    Notion,
+    Tana,
+}
+
+/// Raw clipboard payload as provided by the frontend
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PastePayload {
+    /// `text/plain` clipboard content
+    #[serde(default)]
+    pub text: Option<String>,
+    /// `text/html` clipboard content
+    #[serde(default)]
+    pub html: Option<String>,
+    /// Explicit format, overriding detection (e.g. when the frontend saw a Notion MIME type)
+    #[serde(default)]
+    pub format: Option<PasteFormat>,
+}
+
+impl PastePayload {
+    /// Payload with only plain text
+    pub fn text(text: impl Into<String>) -> Self {
+        Self {
+            text: Some(text.into()),
+            ..Default::default()
+        }
+    }
+
+    /// Payload with only HTML
+    pub fn html(html: impl Into<String>) -> Self {
+        Self {
+            html: Some(html.into()),
+            ..Default::default()
+        }
+    }
+}
+
+/// Parsed paste, ready for insertion
+///
+/// Top-level nodes have `parent_id: None`; descendants reference other nodes in
+/// the list. Nodes are ordered parents-before-children.
+#[derive(Debug, Clone)]
+pub struct PasteResult {
+    pub format: PasteFormat,
+    pub nodes: Vec<PreparedNode>,
+}
+
+/// Result of inserting a paste into the graph
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PasteOutcome {
+    pub format: PasteFormat,
+    /// IDs of the top-level pasted nodes, in order
+    pub root_ids: Vec<String>,
+    /// IDs of every created node
+    pub node_ids: Vec<String>,
+}
+
+/// Converts clipboard payloads into node trees
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PasteProcessor;
+
+impl PasteProcessor {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Detect the payload format when no explicit format is given
+    pub fn detect_format(&self, payload: &PastePayload) -> PasteFormat {
+        if let Some(format) = payload.format {
+            return format;
+        }
+
+        let text = payload.text.as_deref().unwrap_or("");
+        if text.trim_start().starts_with("%%tana%%") {
+            return PasteFormat::Tana;
+        }
+
+        if let Some(html) = payload.html.as_deref() {
+            if html.contains("notion-") || html.contains("data-block-id") {
+                return PasteFormat::Notion;
+            }
+        }
+
+        if looks_like_markdown(text) {
+            return PasteFormat::Markdown;
+        }
+
+        if payload
+            .html
+            .as_deref()
+            .is_some_and(|h| !h.trim().is_empty())
+        {
+            return PasteFormat::Html;
+        }
+
+        PasteFormat::PlainText
+    }
+
+    /// Parse a payload into prepared nodes without touching the database
+    pub fn process(&self, payload: &PastePayload) -> Result<PasteResult, NodeServiceError> {
+        let size = payload.text.as_ref().map_or(0, String::len)
+            + payload.html.as_ref().map_or(0, String::len);
+        if size > MAX_PASTE_SIZE {
+            return Err(NodeServiceError::invalid_input(format!(
+                "Paste exceeds maximum size of {} bytes (got {} bytes)",
+                MAX_PASTE_SIZE, size
+            )));
+        }
+
+        let format = self.detect_format(payload);
+        let text = payload.text.as_deref().unwrap_or("");
+        let html = payload.html.as_deref().unwrap_or("");
+
+        let nodes = match format {
+            PasteFormat::Tana => parse_tana(text),
+            PasteFormat::Markdown => parse_markdown(text)?,
+            PasteFormat::Html => parse_markdown(&html_to_markdown(html))?,
+            PasteFormat::Notion if !text.trim().is_empty() => parse_markdown(text)?,
+            PasteFormat::Notion => parse_markdown(&html_to_markdown(html))?,
+            PasteFormat::PlainText if text.trim().is_empty() && !html.trim().is_empty() => {
+                parse_plain_text(&html_to_markdown(html))
+            }
+            PasteFormat::PlainText => parse_plain_text(text),
+        };
+
+        if nodes.len() > MAX_PASTE_NODES {
+            return Err(NodeServiceError::invalid_input(format!(
+                "Paste would create {} nodes (maximum {})",
+                nodes.len(),
+                MAX_PASTE_NODES
+            )));
+        }
+
+        Ok(PasteResult { format, nodes })
+    }
+
+    /// Parse a payload and insert it under `parent_id`, after `insert_after_node_id`
+    ///
+    /// Top-level nodes are created one at a time so they are positioned relative to
+    /// existing siblings; their descendants are then inserted in a single batch.
+    pub async fn paste<C>(
+        &self,
+        node_service: &NodeService<C>,
+        payload: &PastePayload,
+        parent_id: Option<String>,
+        insert_after_node_id: Option<String>,
+    ) -> Result<PasteOutcome, NodeServiceError>
+    where
+        C: surrealdb::Connection,
+    {
+        let PasteResult { format, nodes } = self.process(payload)?;
+
+        let (top_level, descendants): (Vec<_>, Vec<_>) =
+            nodes.into_iter().partition(|n| n.parent_id.is_none());
+
+        let mut root_ids = Vec::with_capacity(top_level.len());
+        let mut insert_after = insert_after_node_id;
+        for node in top_level {
+            let id = node_service
+                .create_node_with_parent(CreateNodeParams {
+                    id: Some(node.id),
+                    node_type: node.node_type,
+                    content: node.content,
+                    parent_id: parent_id.clone(),
+                    insert_after_node_id: insert_after.take(),
+                    properties: node.properties,
+                })
+                .await?;
+            insert_after = Some(id.clone());
+            root_ids.push(id);
+        }
+
+        let mut node_ids = root_ids.clone();
+        if !descendants.is_empty() {
+            let batch = descendants
+                .into_iter()
+                .map(|n| {
+                    (
+                        n.id,
+                        n.node_type,
+                        n.content,
+                        n.parent_id,
+                        n.order,
+                        n.properties,
+                    )
+                })
+                .collect();
+            node_ids.extend(node_service.bulk_create_hierarchy(batch).await?);
+        }
+
+        Ok(PasteOutcome {
+            format,
+            root_ids,
+            node_ids,
+        })
+    }
+}
+
+/// Heuristic: does plain text carry markdown block structure?
+fn looks_like_markdown(text: &str) -> bool {
+    text.lines().any(|line| {
+        let t = line.trim_start();
+        let heading = t.starts_with('#') && t.trim_start_matches('#').starts_with(' ');
+        let ordered = t.split_once(". ").is_some_and(|(n, _)| {
+            !n.is_empty() && n.len() < 5 && n.chars().all(|c| c.is_ascii_digit())
+        });
+        heading
+            || ordered
+            || t.starts_with("- ")
+            || t.starts_with("* ")
+            || t.starts_with("+ ")
+            || t.starts_with("```")
+            || t.starts_with("> ")
+    })
+}
+
+/// Normalise bullet markers the markdown parser doesn't recognise, then parse
+fn parse_markdown(markdown: &str) -> Result<Vec<PreparedNode>, NodeServiceError> {
+    let normalized: Vec<String> = markdown
+        .lines()
+        .map(|line| {
+            let indent_len = line.len() - line.trim_start().len();
+            let (indent, rest) = line.split_at(indent_len);
+            match rest
+                .strip_prefix("* ")
+                .or_else(|| rest.strip_prefix("+ "))
+                .or_else(|| rest.strip_prefix("• "))
+            {
+                Some(item) => format!("{}- {}", indent, item),
+                None => line.to_string(),
+            }
+        })
+        .collect();
+
+    prepare_nodes_from_markdown(&normalized.join("\n"), None)
+        .map_err(|e| NodeServiceError::invalid_input(e.message))
+}
+
+/// Indentation width of a line (tab = 4 columns)
+fn indent_width(line: &str) -> usize {
+    line.chars()
+        .take_while(|c| *c == ' ' || *c == '\t')
+        .map(|c| if c == '\t' { 4 } else { 1 })
+        .sum()
+}
+
+/// Builds prepared nodes from an indentation outline
+#[derive(Default)]
+struct OutlineBuilder {
+    nodes: Vec<PreparedNode>,
+    /// (node index, indent) of open ancestors
+    stack: Vec<(usize, usize)>,
+    children_per_parent: HashMap<Option<String>, usize>,
+}
+
+impl OutlineBuilder {
+    /// Close ancestors at or deeper than `indent` and return the parent ID
+    fn parent_for(&mut self, indent: usize) -> Option<String> {
+        while self.stack.last().is_some_and(|(_, i)| *i >= indent) {
+            self.stack.pop();
+        }
+        self.stack
+            .last()
+            .map(|(idx, _)| self.nodes[*idx].id.clone())
+    }
+
+    fn push(&mut self, indent: usize, node_type: &str, content: String, properties: Value) {
+        let parent_id = self.parent_for(indent);
+        let count = self
+            .children_per_parent
+            .entry(parent_id.clone())
+            .or_insert(0);
+        let order = FractionalIndex::nth(*count);
+        *count += 1;
+        self.nodes.push(PreparedNode::new(
+            uuid::Uuid::new_v4().to_string(),
+            node_type,
+            content,
+            parent_id,
+            order,
+            properties,
+        ));
+        self.stack.push((self.nodes.len() - 1, indent));
+    }
+
+    /// Attach a property to the most recent node at a shallower indent
+    fn set_field(&mut self, indent: usize, key: String, value: Value) -> bool {
+        while self.stack.last().is_some_and(|(_, i)| *i >= indent) {
+            self.stack.pop();
+        }
+        let Some((idx, _)) = self.stack.last() else {
+            return false;
+        };
+        if let Some(props) = self.nodes[*idx].properties.as_object_mut() {
+            props.insert(key, value);
+        }
+        true
+    }
+}
+
+/// Plain text: one text node per non-empty line, nested by indentation
+fn parse_plain_text(text: &str) -> Vec<PreparedNode> {
+    let mut builder = OutlineBuilder::default();
+    for line in text.lines() {
+        let content = line
+            .trim()
+            .trim_start_matches(['•', '◦', '▪', '‣'])
+            .trim_start();
+        if content.is_empty() {
+            continue;
+        }
+        builder.push(indent_width(line), "text", content.to_string(), json!({}));
+    }
+    builder.nodes
+}
+
+/// Tana Paste: `- ` outline with `Field:: value` lines and `[ ]`/`[x]` todos
+///
+/// Fields become properties on their parent node (keys snake_cased); todos
+/// become task nodes; `!! ` marks a heading.
+fn parse_tana(text: &str) -> Vec<PreparedNode> {
+    let mut builder = OutlineBuilder::default();
+    for line in text.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with("%%") {
+            continue;
+        }
+
+        let indent = indent_width(line);
+        let item = trimmed.strip_prefix("- ").unwrap_or(trimmed);
+
+        if let Some((key, value)) = item.split_once("::") {
+            let key = field_key(key);
+            if !key.is_empty() && builder.set_field(indent, key, json!(value.trim())) {
+                continue;
+            }
+        }
+
+        if let Some(title) = item.strip_prefix("!! ") {
+            builder.push(indent, "header", format!("# {}", title.trim()), json!({}));
+        } else if let Some(rest) = item.strip_prefix("[ ] ") {
+            builder.push(indent, "task", rest.to_string(), json!({"status": "open"}));
+        } else if let Some(rest) = item
+            .strip_prefix("[x] ")
+            .or_else(|| item.strip_prefix("[X] "))
+        {
+            builder.push(indent, "task", rest.to_string(), json!({"status": "done"}));
+        } else {
+            builder.push(indent, "text", item.to_string(), json!({}));
+        }
+    }
+    builder.nodes
+}
+
+/// Convert a Tana field name to a property key (`Due Date` → `due_date`)
+fn field_key(name: &str) -> String {
+    name.split_whitespace()
+        .map(|w| w.to_lowercase())
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn process(payload: PastePayload) -> PasteResult {
+        PasteProcessor::new().process(&payload).unwrap()
+    }
+
+    fn children_of<'a>(nodes: &'a [PreparedNode], parent: &PreparedNode) -> Vec<&'a PreparedNode> {
+        nodes
+            .iter()
+            .filter(|n| n.parent_id.as_deref() == Some(parent.id.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn test_plain_text_indentation_builds_tree() {
+        let result = process(PastePayload::text(
+            "Groceries\n\tMilk\n\tEggs\n\t\tFree range\nChores",
+        ));
+
+        assert_eq!(result.format, PasteFormat::PlainText);
+        assert_eq!(result.nodes.len(), 5);
+        let roots: Vec<_> = result
+            .nodes
+            .iter()
+            .filter(|n| n.parent_id.is_none())
+            .collect();
+        assert_eq!(roots.len(), 2);
+        assert_eq!(roots[0].content, "Groceries");
+        assert_eq!(children_of(&result.nodes, roots[0]).len(), 2);
+        let eggs = &result.nodes[2];
+        assert_eq!(children_of(&result.nodes, eggs)[0].content, "Free range");
+    }
+
+    #[test]
+    fn test_plain_lines_become_siblings() {
+        let result = process(PastePayload::text("one\ntwo\n\nthree"));
+        assert_eq!(result.nodes.len(), 3);
+        assert!(result.nodes.iter().all(|n| n.parent_id.is_none()));
+        assert_eq!(result.nodes[2].order, "a2");
+    }
+
+    #[test]
+    fn test_markdown_detected_and_parsed() {
+        let result = process(PastePayload::text("# Heading\n\n* first\n* second"));
+
+        assert_eq!(result.format, PasteFormat::Markdown);
+        assert_eq!(result.nodes[0].node_type, "header");
+        let bullets = children_of(&result.nodes, &result.nodes[0]);
+        assert_eq!(bullets.len(), 2);
+        assert_eq!(bullets[0].content, "first");
+    }
+
+    #[test]
+    fn test_html_converted_to_tree() {
+        let result = process(PastePayload {
+            text: Some("Plan One Two".to_string()),
+            html: Some("<h2>Plan</h2><ul><li>One</li><li>Two</li></ul>".to_string()),
+            format: None,
+        });
+
+        assert_eq!(result.format, PasteFormat::Html);
+        assert_eq!(result.nodes[0].content, "## Plan");
+        assert_eq!(children_of(&result.nodes, &result.nodes[0]).len(), 2);
+    }
+
+    #[test]
+    fn test_notion_detected_from_html_markers() {
+        let payload = PastePayload {
+            text: Some("- a\n    - b".to_string()),
+            html: Some(r#"<div class="notion-selectable">a</div>"#.to_string()),
+            format: None,
+        };
+        let result = process(payload);
+
+        assert_eq!(result.format, PasteFormat::Notion);
+        assert_eq!(result.nodes.len(), 2);
+        assert_eq!(
+            result.nodes[1].parent_id.as_deref(),
+            Some(result.nodes[0].id.as_str())
+        );
+    }
+
+    #[test]
+    fn test_tana_paste_fields_and_todos() {
+        let text = "%%tana%%\n- Project X\n  - Status:: Active\n  - [ ] Write spec\n  - [x] Kickoff\n  - Notes";
+        let result = process(PastePayload::text(text));
+
+        assert_eq!(result.format, PasteFormat::Tana);
+        assert_eq!(result.nodes.len(), 4);
+        let project = &result.nodes[0];
+        assert_eq!(project.properties["status"], json!("Active"));
+
+        let children = children_of(&result.nodes, project);
+        assert_eq!(children.len(), 3);
+        assert_eq!(children[0].node_type, "task");
+        assert_eq!(children[0].properties["status"], json!("open"));
+        assert_eq!(children[1].properties["status"], json!("done"));
+        assert_eq!(children[2].node_type, "text");
+    }
+
+    #[test]
+    fn test_explicit_format_overrides_detection() {
+        let result = process(PastePayload {
+            text: Some("# not a heading".to_string()),
+            html: None,
+            format: Some(PasteFormat::PlainText),
+        });
+        assert_eq!(result.format, PasteFormat::PlainText);
+        assert_eq!(result.nodes[0].node_type, "text");
+    }
+
+    #[test]
+    fn test_oversized_paste_rejected() {
+        let huge = "x".repeat(MAX_PASTE_SIZE + 1);
+        let err = PasteProcessor::new()
+            .process(&PastePayload::text(huge))
+            .unwrap_err();
+        assert!(matches!(err, NodeServiceError::InvalidInput(_)));
+    }
+
+    #[tokio::test]
+    async fn test_paste_inserts_after_sibling() {
+        use crate::db::SurrealStore;
+        use std::sync::Arc;
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().unwrap();
+        let mut store = Arc::new(
+            SurrealStore::new(temp_dir.path().join("test.db"))
+                .await
+                .unwrap(),
+        );
+        let service = NodeService::new(&mut store).await.unwrap();
+
+        let parent = service
+            .create_node_with_parent(CreateNodeParams {
+                id: None,
+                node_type: "text".to_string(),
+                content: "Parent".to_string(),
+                parent_id: None,
+                insert_after_node_id: None,
+                properties: json!({}),
+            })
+            .await
+            .unwrap();
+        let existing = service
+            .create_node_with_parent(CreateNodeParams {
+                id: None,
+                node_type: "text".to_string(),
+                content: "Existing".to_string(),
+                parent_id: Some(parent.clone()),
+                insert_after_node_id: None,
+                properties: json!({}),
+            })
+            .await
+            .unwrap();
+
+        let outcome = PasteProcessor::new()
+            .paste(
+                &service,
+                &PastePayload::text("A\n  A1\nB"),
+                Some(parent.clone()),
+                Some(existing.clone()),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(outcome.root_ids.len(), 2);
+        assert_eq!(outcome.node_ids.len(), 3);
+
+        let children = service.get_children(&parent).await.unwrap();
+        let contents: Vec<_> = children.iter().map(|n| n.content.as_str()).collect();
+        assert_eq!(contents, vec!["Existing", "A", "B"]);
+
+        let nested = service.get_children(&outcome.root_ids[0]).await.unwrap();
+        assert_eq!(nested[0].content, "A1");
+    }
+}
@@ -0,0 +1,264 @@
+//! Project Rollups - Task Progress of Project Nodes
+//!
+//! A project's rollup summarises the tasks anywhere beneath it: open and done
+//! counts, completion percentage and the nearest due date among open tasks.
+//! `NodeService::get_project_rollup` computes it from one subtree fetch and
+//! keeps it in a [`ProjectRollupCache`], so project lists read progress
+//! without walking trees.
+//!
+//! # Invalidation
+//!
+//! Each cached rollup remembers the nodes it covered. NodeService passes every
+//! domain event it emits through [`ProjectRollupCache::invalidate`], which
+//! drops rollups whose project or covered nodes the event touches. Creating a
+//! task drops every rollup, since the hierarchy event placing it may not
+//! follow (bulk imports).
+//!
+//! A rollup computed while a relevant event arrived may already be stale, so
+//! [`ProjectRollupCache::insert`] only accepts rollups computed since the
+//! last invalidation ([`ProjectRollupCache::generation`]).
+
+use crate::db::events::DomainEvent;
+use crate::models::{Node, TaskNode, TaskStatus};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+/// Task progress beneath a project node
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectRollup {
+    /// Tasks not yet done or cancelled
+    pub open_tasks: usize,
+    pub done_tasks: usize,
+    /// Done share of open and done tasks (cancelled tasks don't count), 0-100
+    pub completion_percent: u8,
+    /// Earliest `due_date` among open tasks
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_due_date: Option<String>,
+}
+
+impl ProjectRollup {
+    /// Roll up the tasks among `nodes`; other node types are ignored
+    pub fn from_nodes<'a>(nodes: impl IntoIterator<Item = &'a Node>) -> Self {
+        let mut rollup = Self::default();
+        for node in nodes {
+            if node.node_type != "task" {
+                continue;
+            }
+            let due_date = node
+                .properties
+                .get("task")
+                .and_then(|task| task.get("due_date"))
+                .and_then(|due| due.as_str())
+                .map(str::to_string);
+            let Ok(task) = TaskNode::from_node(node.clone()) else {
+                continue;
+            };
+            match task.status {
+                TaskStatus::Done => rollup.done_tasks += 1,
+                TaskStatus::Cancelled => {}
+                _ => {
+                    rollup.open_tasks += 1;
+                    if let Some(due) = due_date {
+                        if rollup.next_due_date.as_ref().is_none_or(|next| due < *next) {
+                            rollup.next_due_date = Some(due);
+                        }
+                    }
+                }
+            }
+        }
+
+        if let Some(percent) =
+            (rollup.done_tasks * 100).checked_div(rollup.open_tasks + rollup.done_tasks)
+        {
+            rollup.completion_percent = percent as u8;
+        }
+        rollup
+    }
+}
+
+struct CachedRollup {
+    rollup: ProjectRollup,
+    /// Descendants the rollup was computed from
+    covered: HashSet<String>,
+}
+
+#[derive(Default)]
+struct CacheState {
+    entries: HashMap<String, CachedRollup>,
+    /// Bumped by every relevant event
+    generation: u64,
+}
+
+/// Rollups by project ID, dropped when domain events touch them
+#[derive(Default)]
+pub struct ProjectRollupCache {
+    state: RwLock<CacheState>,
+}
+
+impl ProjectRollupCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self, project_id: &str) -> Option<ProjectRollup> {
+        self.read()
+            .entries
+            .get(project_id)
+            .map(|cached| cached.rollup.clone())
+    }
+
+    /// Invalidation counter; read it before fetching what a rollup is computed from
+    pub fn generation(&self) -> u64 {
+        self.read().generation
+    }
+
+    /// Cache `rollup`, computed from the descendants in `covered`
+    ///
+    /// Ignored when an invalidation happened after `generation` was read.
+    pub fn insert(
+        &self,
+        project_id: String,
+        rollup: ProjectRollup,
+        covered: HashSet<String>,
+        generation: u64,
+    ) {
+        let mut state = self.write();
+        if state.generation == generation {
+            state
+                .entries
+                .insert(project_id, CachedRollup { rollup, covered });
+        }
+    }
+
+    /// Drop the rollups `event` may have changed
+    pub fn invalidate(&self, event: &DomainEvent) {
+        let touched: Vec<&str> = match event {
+            DomainEvent::NodeCreated { node_type, .. } if node_type == "task" => {
+                self.clear();
+                return;
+            }
+            DomainEvent::NodeCreated { .. } => return,
+            DomainEvent::NodeUpdated { node_id, .. } => vec![node_id],
+            DomainEvent::NodeDeleted { id, .. } => vec![id],
+            DomainEvent::RelationshipCreated { relationship, .. }
+            | DomainEvent::RelationshipUpdated { relationship, .. } => {
+                if relationship.relationship_type != "has_child" {
+                    return;
+                }
+                vec![&relationship.from_id, &relationship.to_id]
+            }
+            DomainEvent::RelationshipDeleted {
+                from_id,
+                to_id,
+                relationship_type,
+                ..
+            } => {
+                if relationship_type != "has_child" {
+                    return;
+                }
+                vec![from_id, to_id]
+            }
+        };
+
+        let mut state = self.write();
+        state.generation += 1;
+        state.entries.retain(|project_id, cached| {
+            !touched
+                .iter()
+                .any(|id| *id == project_id || cached.covered.contains(*id))
+        });
+    }
+
+    /// Drop every cached rollup
+    pub fn clear(&self) {
+        let mut state = self.write();
+        state.generation += 1;
+        state.entries.clear();
+    }
+
+    fn read(&self) -> RwLockReadGuard<'_, CacheState> {
+        self.state.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, CacheState> {
+        self.state.write().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn task(status: &str, due_date: Option<&str>) -> Node {
+        let mut props = json!({ "task": { "status": status } });
+        if let Some(due) = due_date {
+            props["task"]["due_date"] = json!(due);
+        }
+        Node::new("task".to_string(), "Task".to_string(), props)
+    }
+
+    #[test]
+    fn test_rollup_from_nodes() {
+        let nodes = [
+            task("done", Some("2025-01-01")),
+            task("open", Some("2025-03-01")),
+            task("in_progress", Some("2025-02-01")),
+            task("cancelled", Some("2024-12-01")),
+            Node::new("text".to_string(), "Notes".to_string(), json!({})),
+        ];
+        let rollup = ProjectRollup::from_nodes(&nodes);
+
+        assert_eq!(rollup.open_tasks, 2);
+        assert_eq!(rollup.done_tasks, 1);
+        assert_eq!(rollup.completion_percent, 33);
+        assert_eq!(rollup.next_due_date.as_deref(), Some("2025-02-01"));
+        assert_eq!(ProjectRollup::from_nodes(&[]), ProjectRollup::default());
+    }
+
+    #[test]
+    fn test_invalidate_drops_touched_rollups() {
+        let cache = ProjectRollupCache::new();
+        let covered = |ids: &[&str]| ids.iter().map(|id| id.to_string()).collect();
+        cache.insert(
+            "p1".into(),
+            ProjectRollup::default(),
+            covered(&["a", "b"]),
+            0,
+        );
+        cache.insert("p2".into(), ProjectRollup::default(), covered(&["c"]), 0);
+
+        cache.invalidate(&DomainEvent::NodeUpdated {
+            node_id: "b".into(),
+            source_client_id: None,
+            correlation_id: None,
+        });
+        assert!(cache.get("p1").is_none());
+        assert!(cache.get("p2").is_some());
+
+        // A rollup computed before that event is stale
+        cache.insert("p1".into(), ProjectRollup::default(), covered(&["a"]), 0);
+        assert!(cache.get("p1").is_none());
+
+        // Unrelated edges leave the cache alone
+        cache.invalidate(&DomainEvent::RelationshipDeleted {
+            id: "relationship:x".into(),
+            from_id: "c".into(),
+            to_id: "z".into(),
+            relationship_type: "mentions".into(),
+            source_client_id: None,
+            correlation_id: None,
+        });
+        assert!(cache.get("p2").is_some());
+
+        cache.invalidate(&DomainEvent::NodeCreated {
+            node_id: "t".into(),
+            node_type: "task".into(),
+            source_client_id: None,
+            correlation_id: None,
+        });
+        assert!(cache.get("p2").is_none());
+    }
+}
@@ -16,23 +16,37 @@
 //! - Type filter: `SELECT * FROM node WHERE node_type = 'task'`
 //! - Property filter: `SELECT * FROM node WHERE properties.status = 'open'`
 //! - Relationship: `SELECT * FROM node WHERE id IN (SELECT VALUE out FROM relationship WHERE in = node:⟨parent⟩ AND relationship_type = 'has_child')`
+//! - Subtree: `SELECT * FROM node WHERE id IN node:⟨root⟩.{..3+collect}->relationship[WHERE relationship_type = 'has_child' AND properties.reference != true]->node`
+//!
+//! # Hub-Only Types
+//!
+//! Built-in content types (text, header, code-block, ...) have no schema
+//! fields: their data sits in the content itself and in flat or namespaced
+//! properties depending on which client wrote it. For these, property filters
+//! and sorts run in Rust after the hub query, reading the namespaced property,
+//! then the flat one, with a few values derived from content (a code block's
+//! fence language, a header's level). E.g. "code blocks with language=rust
+//! modified this month" is a metadata filter in SQL plus a property filter on
+//! the fetched nodes.
 //!
 //! # Examples
 //!
 //! ```rust,no_run
-//! use nodespace_core::services::{QueryService, QueryDefinition};
+//! use nodespace_core::services::{NodeService, QueryService, QueryDefinition};
 //! use nodespace_core::db::SurrealStore;
 //! use std::sync::Arc;
 //!
 //! # async fn example() -> anyhow::Result<()> {
-//! let store = Arc::new(SurrealStore::new("./data/db".into()).await?);
-//! let query_service = QueryService::new(store);
+//! let mut store = Arc::new(SurrealStore::new("./data/db".into()).await?);
+//! let node_service = NodeService::new(&mut store).await?;
+//! let query_service = QueryService::new(store, node_service.workspace_lock().clone());
 //!
 //! let query = QueryDefinition {
 //!     target_type: "task".to_string(),
 //!     filters: vec![],
 //!     sorting: None,
 //!     limit: Some(50),
+//!     offset: None,
 //! };
 //!
 //! let results = query_service.execute(&query).await?;
@@ -41,11 +55,56 @@
 //! ```
 
 use crate::db::SurrealStore;
+use crate::models::schema::{inverse_labels, SchemaRelationship};
 use crate::models::Node;
+use crate::services::workspace_lock::WorkspaceLock;
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::HashMap;
 use std::sync::Arc;
 
+/// Prefix of sort fields that refer to edge data
+const EDGE_SORT_PREFIX: &str = "edge.";
+
+/// Edge fields stored on the relationship itself rather than in its properties
+const EDGE_TIMESTAMP_FIELDS: &[&str] = &["created_at", "modified_at"];
+
+/// Node fields addressed directly rather than through `properties`
+const METADATA_FIELDS: &[&str] = &["created_at", "modified_at", "content", "node_type"];
+
+/// Sort fields derived per node by a subquery, with their SurrealQL
+///
+/// - `backlink_count`: nodes mentioning this one ("most referenced notes")
+/// - `child_count`: direct children
+/// - `last_descendant_modified`: latest `modified_at` in the subtree,
+///   including the node itself ("recently active projects")
+const COMPUTED_SORT_FIELDS: &[(&str, &str)] = &[
+    (
+        "backlink_count",
+        "count(<-relationship[WHERE relationship_type = 'mentions'])",
+    ),
+    (
+        "child_count",
+        "count(->relationship[WHERE relationship_type = 'has_child'])",
+    ),
+    (
+        "last_descendant_modified",
+        "array::max(array::concat([modified_at], \
+         id.{..+collect}(->relationship[WHERE relationship_type = 'has_child']->node).modified_at))",
+    ),
+];
+
+/// Built-in types whose properties are resolved in Rust rather than SurrealQL
+const HUB_ONLY_TYPES: &[&str] = &[
+    "text",
+    "header",
+    "code-block",
+    "quote-block",
+    "ordered-list",
+    "checkbox",
+];
+
 /// Structured query definition matching QueryNode fields
 ///
 /// This struct matches the TypeScript QueryNode interface from
@@ -61,6 +120,87 @@ pub struct QueryDefinition {
     pub sorting: Option<Vec<SortConfig>>,
     /// Optional result limit (default: 50)
     pub limit: Option<usize>,
+    /// Results to skip before the first one returned, for paging
+    pub offset: Option<usize>,
+}
+
+impl QueryDefinition {
+    /// Definition stored on a saved `query` node
+    ///
+    /// Query nodes keep their fields snake_case under `properties.query`
+    /// (older nodes flat in `properties`); a missing target type means `*`.
+    pub fn from_saved_query(node: &Node) -> Result<Self> {
+        if node.node_type != "query" {
+            anyhow::bail!("Node {} is not a query", node.id);
+        }
+        let props = node.properties.get("query").unwrap_or(&node.properties);
+        let field = |name: &str| props.get(name).cloned().unwrap_or(serde_json::Value::Null);
+        let target_type = match field("target_type") {
+            serde_json::Value::Null => serde_json::json!("*"),
+            value => value,
+        };
+        let filters = match field("filters") {
+            serde_json::Value::Null => serde_json::json!([]),
+            value => value,
+        };
+        serde_json::from_value(serde_json::json!({
+            "targetType": target_type,
+            "filters": filters,
+            "sorting": field("sorting"),
+            "limit": field("limit"),
+        }))
+        .with_context(|| format!("Invalid query definition on node {}", node.id))
+    }
+
+    /// Fill `$name` placeholders in filter values from `params`
+    ///
+    /// A placeholder is a whole string value such as `"$status"` (a `$` and
+    /// an identifier, so `"$100"` stays literal) and the parameter keeps its
+    /// JSON type. Placeholders without a parameter are an error; unused
+    /// parameters are ignored.
+    pub fn with_params(self, params: &serde_json::Map<String, serde_json::Value>) -> Result<Self> {
+        let mut filters = serde_json::to_value(&self.filters)?;
+        substitute_params(&mut filters, params)?;
+        Ok(Self {
+            filters: serde_json::from_value(filters)?,
+            ..self
+        })
+    }
+}
+
+fn is_param_name(name: &str) -> bool {
+    name.chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn substitute_params(
+    value: &mut serde_json::Value,
+    params: &serde_json::Map<String, serde_json::Value>,
+) -> Result<()> {
+    match value {
+        serde_json::Value::String(text) => {
+            if let Some(name) = text.strip_prefix('$').filter(|name| is_param_name(name)) {
+                let param = params
+                    .get(name)
+                    .ok_or_else(|| anyhow::anyhow!("Missing query parameter '{}'", name))?;
+                *value = param.clone();
+            }
+        }
+        serde_json::Value::Array(items) => {
+            for item in items {
+                substitute_params(item, params)?;
+            }
+        }
+        serde_json::Value::Object(map) => {
+            for item in map.values_mut() {
+                substitute_params(item, params)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
 }
 
 /// Filter type category
@@ -100,6 +240,67 @@ pub enum RelationshipType {
     Mentions,
     #[serde(rename = "mentioned_by")]
     MentionedBy,
+    /// Members of the collection `nodeId`
+    #[serde(rename = "member_of")]
+    MemberOf,
+    /// Nodes anywhere below `nodeId` in the hierarchy, up to `maxDepth` levels
+    #[serde(rename = "descendants_of")]
+    DescendantsOf,
+    /// Nodes on the path from `nodeId` up to its root, up to `maxDepth` levels
+    #[serde(rename = "ancestors_of")]
+    AncestorsOf,
+    /// An inverse label resolved to the relationship it names; set by the
+    /// query service only
+    #[serde(skip)]
+    Inverse(String),
+    /// Nodes `nodeId` points to through a schema relationship (e.g.
+    /// `blocks`), or, by the relationship's inverse label (`blocked_by`),
+    /// the nodes pointing to `nodeId`
+    #[serde(untagged)]
+    Named(String),
+}
+
+impl RelationshipType {
+    /// `relationship_type` of the edges this filter follows
+    ///
+    /// A [`RelationshipType::Named`] inverse label is only known to be one
+    /// once the query service resolves it.
+    pub fn edge_type(&self) -> &str {
+        match self {
+            RelationshipType::Children
+            | RelationshipType::Parent
+            | RelationshipType::DescendantsOf
+            | RelationshipType::AncestorsOf => "has_child",
+            RelationshipType::Mentions | RelationshipType::MentionedBy => "mentions",
+            RelationshipType::MemberOf => "member_of",
+            RelationshipType::Inverse(name) | RelationshipType::Named(name) => name,
+        }
+    }
+
+    /// Edge type, the side holding `nodeId`, and the side holding results
+    ///
+    /// `None` for hierarchy traversals, which span several edges.
+    fn edge(&self) -> Option<(&str, &'static str, &'static str)> {
+        match self {
+            RelationshipType::Children => Some(("has_child", "in", "out")),
+            RelationshipType::Parent => Some(("has_child", "out", "in")),
+            RelationshipType::Mentions => Some(("mentions", "in", "out")),
+            RelationshipType::MentionedBy => Some(("mentions", "out", "in")),
+            RelationshipType::MemberOf => Some(("member_of", "out", "in")),
+            RelationshipType::Named(name) => Some((name, "in", "out")),
+            RelationshipType::Inverse(name) => Some((name, "out", "in")),
+            RelationshipType::DescendantsOf | RelationshipType::AncestorsOf => None,
+        }
+    }
+
+    /// Graph arrow followed by hierarchy traversals
+    fn traversal_arrow(&self) -> Option<&'static str> {
+        match self {
+            RelationshipType::DescendantsOf => Some("->"),
+            RelationshipType::AncestorsOf => Some("<-"),
+            _ => None,
+        }
+    }
 }
 
 /// Sort direction
@@ -131,6 +332,27 @@ pub struct QueryFilter {
     pub relationship_type: Option<RelationshipType>,
     /// Target node ID for relationship filters
     pub node_id: Option<String>,
+    /// Conditions on the matched edges of a relationship filter
+    #[serde(default)]
+    pub edge_filters: Option<Vec<EdgeFilter>>,
+    /// Levels to traverse for `descendants_of` / `ancestors_of` (unbounded if absent)
+    #[serde(default)]
+    pub max_depth: Option<u32>,
+}
+
+/// Condition on edge data of a relationship filter
+///
+/// `property` is an edge property (`order` for children and members,
+/// `context` for mentions) or an edge timestamp: `created_at` (alias
+/// `added_at`) or `modified_at`. Timestamps accept RFC 3339 values or a
+/// negative duration relative to now, e.g. `"-7d"` for "in the last week".
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EdgeFilter {
+    pub property: String,
+    pub operator: FilterOperator,
+    pub value: Option<serde_json::Value>,
+    pub case_sensitive: Option<bool>,
 }
 
 /// Sorting configuration
@@ -138,20 +360,66 @@ pub struct QueryFilter {
 #[serde(rename_all = "camelCase")]
 pub struct SortConfig {
     /// Property or field to sort by
+    ///
+    /// `edge.<property>` sorts by data on the edges matched by the query's
+    /// first relationship filter, e.g. `edge.order` or `edge.added_at`.
+    /// `backlink_count`, `child_count` and `last_descendant_modified` are
+    /// computed per node.
     pub field: String,
     /// Sort direction
     pub direction: SortDirection,
 }
 
+/// Where a query's filters and sorts are evaluated
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryPath {
+    /// Everything runs in SurrealQL, including the limit and offset
+    Database,
+    /// The database returns candidates; property filters of hub-only types
+    /// and edge or hub property sorts run in Rust, then the offset and limit
+    /// apply
+    Hub,
+}
+
+/// How a query will execute, from [`QueryService::explain`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryExplanation {
+    /// Generated SurrealQL
+    pub sql: String,
+    pub path: QueryPath,
+    /// Indexes SurrealDB iterates; empty means a table scan
+    pub indexes: Vec<String>,
+    /// Rows the database would return (before any filtering in Rust)
+    pub estimated_rows: Option<u64>,
+    /// Raw `EXPLAIN FULL` output
+    pub plan: serde_json::Value,
+}
+
 /// Service for executing queries against the database
-pub struct QueryService {
-    store: Arc<SurrealStore>,
+pub struct QueryService<C = surrealdb::engine::local::Db>
+where
+    C: surrealdb::Connection,
+{
+    store: Arc<SurrealStore<C>>,
+    workspace_lock: Arc<WorkspaceLock>,
 }
 
-impl QueryService {
+impl<C> QueryService<C>
+where
+    C: surrealdb::Connection,
+{
     /// Create a new QueryService
-    pub fn new(store: Arc<SurrealStore>) -> Self {
-        Self { store }
+    ///
+    /// Queries fail with
+    /// [`NodeServiceError::WorkspaceLocked`](crate::services::NodeServiceError::WorkspaceLocked)
+    /// while `workspace_lock` (the NodeService's) is locked.
+    pub fn new(store: Arc<SurrealStore<C>>, workspace_lock: Arc<WorkspaceLock>) -> Self {
+        Self {
+            store,
+            workspace_lock,
+        }
     }
 
     /// Execute a query and return matching nodes
@@ -164,7 +432,18 @@ impl QueryService {
     /// - Query building fails (invalid filter syntax)
     /// - Database query execution fails
     /// - Result deserialization fails
+    /// - It runs longer than the store's query timeout
+    ///   ([`DatabaseError::Timeout`](crate::db::DatabaseError::Timeout))
+    /// - The workspace is locked
     pub async fn execute(&self, query: &QueryDefinition) -> Result<Vec<Node>> {
+        self.workspace_lock.ensure_unlocked()?;
+        self.store
+            .with_query_timeout(self.execute_unbounded(query))
+            .await
+    }
+
+    async fn execute_unbounded(&self, query: &QueryDefinition) -> Result<Vec<Node>> {
+        let query = &*self.resolve_inverse_labels(query).await?;
         let sql = self.build_query(query)?;
 
         // Execute query to get basic node data (without FETCH to avoid Thing deserialization)
@@ -187,24 +466,174 @@ impl QueryService {
             }
         }
 
+        if is_hub_only(&query.target_type) {
+            let property_filters: Vec<&QueryFilter> = query
+                .filters
+                .iter()
+                .filter(|f| f.filter_type == FilterType::Property)
+                .collect();
+            let mut kept = Vec::with_capacity(nodes.len());
+            for node in nodes {
+                let mut matches = true;
+                for filter in &property_filters {
+                    if !self.matches_hub_property_filter(&node, filter)? {
+                        matches = false;
+                        break;
+                    }
+                }
+                if matches {
+                    kept.push(node);
+                }
+            }
+            nodes = kept;
+        }
+
         // Re-apply sorting in Rust to guarantee sort order
         // This ensures consistent sorting even if database ordering behaves unexpectedly
         if let Some(sorting) = &query.sorting {
-            self.sort_nodes(&mut nodes, sorting);
+            let edges = match edge_sort_source(query) {
+                Some(filter) => self.load_edges(filter).await?,
+                None => HashMap::new(),
+            };
+            self.sort_nodes(&mut nodes, sorting, &edges);
+        }
+
+        // Hub-only and edge-sorted queries finish after the database, so the
+        // offset and limit apply here
+        if is_post_processed(query) {
+            let offset = query.offset.unwrap_or_default().min(nodes.len());
+            nodes.drain(..offset);
+            if let Some(limit) = query.limit {
+                nodes.truncate(limit);
+            }
         }
 
         Ok(nodes)
     }
 
+    /// Describe how `query` executes without fetching its results
+    ///
+    /// Runs the generated statement with `EXPLAIN FULL`, which reports the
+    /// chosen iterators (index or table scan) and the number of rows fetched.
+    pub async fn explain(&self, query: &QueryDefinition) -> Result<QueryExplanation> {
+        self.workspace_lock.ensure_unlocked()?;
+        let query = &*self.resolve_inverse_labels(query).await?;
+        let sql = self.build_query(query)?;
+        let explain_sql = format!(
+            "{}{} EXPLAIN FULL;",
+            sql.trim_end_matches(';'),
+            self.store.timeout_clause()
+        );
+
+        let steps: Vec<serde_json::Value> = self
+            .store
+            .with_query_timeout(async {
+                let mut response = self
+                    .store
+                    .query(explain_sql)
+                    .await
+                    .context("Failed to explain query")?;
+                response.take(0).context("Failed to extract query plan")
+            })
+            .await?;
+
+        let mut indexes = Vec::new();
+        let mut estimated_rows = None;
+        for step in &steps {
+            let detail = &step["detail"];
+            match step["operation"].as_str() {
+                Some("Iterate Index") => {
+                    if let Some(index) = detail["plan"]["index"].as_str() {
+                        if !indexes.iter().any(|known| known == index) {
+                            indexes.push(index.to_string());
+                        }
+                    }
+                }
+                Some("Fetch") => estimated_rows = detail["count"].as_u64(),
+                _ => {}
+            }
+        }
+
+        Ok(QueryExplanation {
+            sql,
+            path: if is_post_processed(query) {
+                QueryPath::Hub
+            } else {
+                QueryPath::Database
+            },
+            indexes,
+            estimated_rows,
+            plan: serde_json::Value::Array(steps),
+        })
+    }
+
+    /// `query` with named relationship filters that use an inverse label
+    /// turned into [`RelationshipType::Inverse`]
+    async fn resolve_inverse_labels<'q>(
+        &self,
+        query: &'q QueryDefinition,
+    ) -> Result<Cow<'q, QueryDefinition>> {
+        let is_named = |filter: &QueryFilter| {
+            matches!(filter.relationship_type, Some(RelationshipType::Named(_)))
+        };
+        if !query.filters.iter().any(is_named) {
+            return Ok(Cow::Borrowed(query));
+        }
+        let schemas = self.store.get_all_schemas().await?;
+        let relationships: Vec<&SchemaRelationship> = schemas
+            .iter()
+            .flat_map(|schema| &schema.relationships)
+            .collect();
+        let labels = inverse_labels(&relationships);
+
+        let mut resolved = query.clone();
+        for filter in &mut resolved.filters {
+            if let Some(RelationshipType::Named(label)) = &filter.relationship_type {
+                if let Some(name) = labels.get(label.as_str()) {
+                    filter.relationship_type = Some(RelationshipType::Inverse(name.to_string()));
+                }
+            }
+        }
+        Ok(Cow::Owned(resolved))
+    }
+
     /// Sort nodes in-place according to the sort configuration
-    fn sort_nodes(&self, nodes: &mut [Node], sorting: &[SortConfig]) {
+    ///
+    /// `edges` maps node IDs to their edge record for `edge.*` sort fields.
+    fn sort_nodes(
+        &self,
+        nodes: &mut [Node],
+        sorting: &[SortConfig],
+        edges: &HashMap<String, serde_json::Value>,
+    ) {
         if sorting.is_empty() {
             return;
         }
 
+        // Computed fields are only known to the database; keep its order for them
+        let db_rank: HashMap<String, usize> = nodes
+            .iter()
+            .enumerate()
+            .map(|(rank, node)| (node.id.clone(), rank))
+            .collect();
+
         nodes.sort_by(|a, b| {
             for sort_config in sorting {
-                let ordering = self.compare_nodes_by_field(a, b, &sort_config.field);
+                if computed_sort_expr(&sort_config.field).is_some() {
+                    let ordering = db_rank[&a.id].cmp(&db_rank[&b.id]);
+                    if ordering != std::cmp::Ordering::Equal {
+                        return ordering;
+                    }
+                    continue;
+                }
+                let ordering = match sort_config.field.strip_prefix(EDGE_SORT_PREFIX) {
+                    Some(edge_field) => {
+                        let val_a = edge_value(edges, &a.id, edge_field);
+                        let val_b = edge_value(edges, &b.id, edge_field);
+                        self.compare_json_values(val_a, val_b)
+                    }
+                    None => self.compare_nodes_by_field(a, b, &sort_config.field),
+                };
                 let ordering = match sort_config.direction {
                     SortDirection::Ascending => ordering,
                     SortDirection::Descending => ordering.reverse(),
@@ -227,6 +656,11 @@ impl QueryService {
             "node_type" => a.node_type.cmp(&b.node_type),
             // Type-specific properties (accessed via namespaced properties JSON)
             // Access properties[node_type][field] for proper namespaced access
+            _ if is_hub_only(&a.node_type) || is_hub_only(&b.node_type) => {
+                let val_a = hub_property(a, field);
+                let val_b = hub_property(b, field);
+                self.compare_json_values(val_a.as_ref(), val_b.as_ref())
+            }
             _ => {
                 let val_a = a.properties.get(&a.node_type).and_then(|ns| ns.get(field));
                 let val_b = b.properties.get(&b.node_type).and_then(|ns| ns.get(field));
@@ -267,9 +701,14 @@ impl QueryService {
         // Keep SELECT * for proper sorting, but extract only IDs from results
         // This avoids "Missing order idiom" errors with SELECT VALUE id
 
+        // Let the engine abandon the scan too, not only the awaiting caller
+        let sql = format!(
+            "{}{};",
+            sql.trim_end_matches(';'),
+            self.store.timeout_clause()
+        );
         let mut response = self
             .store
-            .db()
             .query(sql)
             .await
             .context("Failed to execute ID query")?;
@@ -301,6 +740,41 @@ impl QueryService {
         Ok(ids)
     }
 
+    /// Edge records of a relationship filter, keyed by the result-side node ID
+    async fn load_edges(&self, filter: &QueryFilter) -> Result<HashMap<String, serde_json::Value>> {
+        let (Some(rel_type), Some(node_id)) = (&filter.relationship_type, &filter.node_id) else {
+            return Ok(HashMap::new());
+        };
+        let Some((edge_type, anchor, result)) = rel_type.edge() else {
+            return Ok(HashMap::new());
+        };
+        let sql = format!(
+            "SELECT record::id({}) AS node_id, <string> created_at AS created_at, \
+             <string> modified_at AS modified_at, properties FROM relationship \
+             WHERE {} = node:⟨{}⟩ AND relationship_type = '{}';",
+            result,
+            anchor,
+            self.escape_string(node_id),
+            self.escape_string(edge_type)
+        );
+
+        let mut response = self
+            .store
+            .query(sql)
+            .await
+            .context("Failed to load edges for sorting")?;
+        let records: Vec<serde_json::Value> =
+            response.take(0).context("Failed to extract edge records")?;
+
+        Ok(records
+            .into_iter()
+            .filter_map(|record| {
+                let node_id = record.get("node_id")?.as_str()?.to_string();
+                Some((node_id, record))
+            })
+            .collect())
+    }
+
     /// Translate QueryDefinition to SurrealQL
     ///
     /// Builds queries against the unified node table with JSON properties.
@@ -309,7 +783,22 @@ impl QueryService {
     /// Issue #794: Properties are now stored in namespaced format:
     /// properties[node_type][field_name] instead of properties[field_name]
     fn build_query(&self, query: &QueryDefinition) -> Result<String> {
-        let mut sql = String::from("SELECT * FROM node");
+        let hub_only = is_hub_only(&query.target_type);
+
+        // Edge fields, and properties of hub-only types, are sorted in Rust
+        let sorting = query.sorting.as_deref().unwrap_or_default();
+        let (rust_sorting, sql_sorting): (Vec<&SortConfig>, Vec<&SortConfig>) = sorting
+            .iter()
+            .partition(|s| is_sorted_in_rust(&s.field, hub_only));
+
+        // Computed sort fields must be selected to be ordered by
+        let mut sql = String::from("SELECT *");
+        for sort in &sql_sorting {
+            if let Some(expr) = computed_sort_expr(&sort.field) {
+                sql.push_str(&format!(", {} AS {}", expr, sort.field));
+            }
+        }
+        sql.push_str(" FROM node");
         let mut conditions = Vec::new();
 
         // Add type filter if not wildcard
@@ -320,6 +809,8 @@ impl QueryService {
         // Build filter conditions (pass target_type for namespaced property access)
         for filter in &query.filters {
             match filter.filter_type {
+                // Applied to the fetched nodes in `execute`
+                FilterType::Property if hub_only => {}
                 FilterType::Property => {
                     conditions.push(self.build_property_filter(filter, &query.target_type)?)
                 }
@@ -338,30 +829,41 @@ impl QueryService {
         }
 
         // Add sorting (pass target_type for namespaced property access)
-        if let Some(sorting) = &query.sorting {
-            if !sorting.is_empty() {
-                sql.push_str(" ORDER BY ");
-                let clauses: Vec<String> = sorting
-                    .iter()
-                    .map(|s| {
-                        let direction = match s.direction {
-                            SortDirection::Ascending => "ASC",
-                            SortDirection::Descending => "DESC",
-                        };
-                        format!(
-                            "{} {}",
-                            self.resolve_field(&s.field, &query.target_type),
-                            direction
-                        )
-                    })
-                    .collect();
-                sql.push_str(&clauses.join(", "));
+        for sort in &rust_sorting {
+            if let Some(edge_field) = sort.field.strip_prefix(EDGE_SORT_PREFIX) {
+                resolve_edge_field(edge_field)?;
+                if edge_sort_source(query).is_none() {
+                    anyhow::bail!("Sorting by '{}' requires a relationship filter", sort.field);
+                }
             }
         }
+        if !sql_sorting.is_empty() {
+            sql.push_str(" ORDER BY ");
+            let clauses: Vec<String> = sql_sorting
+                .iter()
+                .map(|s| {
+                    let direction = match s.direction {
+                        SortDirection::Ascending => "ASC",
+                        SortDirection::Descending => "DESC",
+                    };
+                    format!(
+                        "{} {}",
+                        self.resolve_field(&s.field, &query.target_type),
+                        direction
+                    )
+                })
+                .collect();
+            sql.push_str(&clauses.join(", "));
+        }
 
-        // Add limit
-        if let Some(limit) = query.limit {
-            sql.push_str(&format!(" LIMIT {}", limit));
+        // Add limit and offset, unless rows are still filtered or sorted after the query
+        if !is_post_processed(query) {
+            if let Some(limit) = query.limit {
+                sql.push_str(&format!(" LIMIT {}", limit));
+            }
+            if let Some(offset) = query.offset {
+                sql.push_str(&format!(" START {}", offset));
+            }
         }
 
         sql.push(';');
@@ -375,7 +877,7 @@ impl QueryService {
     ///
     /// For wildcard queries (*), we can't namespace, so we fall back to flat access.
     fn resolve_field(&self, field: &str, target_type: &str) -> String {
-        if ["created_at", "modified_at", "content", "node_type"].contains(&field) {
+        if METADATA_FIELDS.contains(&field) || computed_sort_expr(field).is_some() {
             field.to_string()
         } else if target_type == "*" {
             // Wildcard query - can't namespace (would need to check each node's type)
@@ -405,7 +907,12 @@ impl QueryService {
             // Namespaced property access
             format!("properties.{}.{}", target_type, property)
         };
-        self.build_filter_condition(&field, &filter.operator, filter)
+        self.build_filter_condition(
+            &field,
+            &filter.operator,
+            filter.value.as_ref(),
+            filter.case_sensitive,
+        )
     }
 
     /// Build content filter
@@ -424,7 +931,9 @@ impl QueryService {
 
     /// Build metadata filter
     ///
-    /// Direct access: created_at >= '2025-01-01'
+    /// Direct access: created_at >= <datetime> '2025-01-01T00:00:00Z'.
+    /// Timestamps take the same values as edge timestamps (RFC 3339 or a
+    /// relative `-7d`).
     fn build_metadata_filter(&self, filter: &QueryFilter) -> Result<String> {
         let property = filter
             .property
@@ -435,7 +944,18 @@ impl QueryService {
             anyhow::bail!("Invalid metadata field: {}", property);
         }
 
-        self.build_filter_condition(property, &filter.operator, filter)
+        if let Some(serde_json::Value::String(value)) = &filter.value {
+            if matches!(property.as_str(), "created_at" | "modified_at") {
+                return self.build_timestamp_condition(property, &filter.operator, value);
+            }
+        }
+
+        self.build_filter_condition(
+            property,
+            &filter.operator,
+            filter.value.as_ref(),
+            filter.case_sensitive,
+        )
     }
 
     // ========== Shared Filter Building Logic ==========
@@ -445,20 +965,19 @@ impl QueryService {
         &self,
         field: &str,
         operator: &FilterOperator,
-        filter: &QueryFilter,
+        value: Option<&serde_json::Value>,
+        case_sensitive: Option<bool>,
     ) -> Result<String> {
         match operator {
             FilterOperator::Equals => {
-                let value = self.format_value(filter.value.as_ref())?;
+                let value = self.format_value(value)?;
                 Ok(format!("{} = {}", field, value))
             }
             FilterOperator::Contains => {
-                let value = filter
-                    .value
-                    .as_ref()
+                let value = value
                     .and_then(|v| v.as_str())
                     .ok_or_else(|| anyhow::anyhow!("Contains requires string value"))?;
-                if filter.case_sensitive.unwrap_or(true) {
+                if case_sensitive.unwrap_or(true) {
                     Ok(format!(
                         "{} CONTAINS '{}'",
                         field,
@@ -473,25 +992,23 @@ impl QueryService {
                 }
             }
             FilterOperator::GreaterThan => {
-                let value = self.format_value(filter.value.as_ref())?;
+                let value = self.format_value(value)?;
                 Ok(format!("{} > {}", field, value))
             }
             FilterOperator::LessThan => {
-                let value = self.format_value(filter.value.as_ref())?;
+                let value = self.format_value(value)?;
                 Ok(format!("{} < {}", field, value))
             }
             FilterOperator::GreaterThanOrEqual => {
-                let value = self.format_value(filter.value.as_ref())?;
+                let value = self.format_value(value)?;
                 Ok(format!("{} >= {}", field, value))
             }
             FilterOperator::LessThanOrEqual => {
-                let value = self.format_value(filter.value.as_ref())?;
+                let value = self.format_value(value)?;
                 Ok(format!("{} <= {}", field, value))
             }
             FilterOperator::In => {
-                let values = filter
-                    .value
-                    .as_ref()
+                let values = value
                     .and_then(|v| v.as_array())
                     .ok_or_else(|| anyhow::anyhow!("In requires array value"))?;
                 let list: Vec<String> = values
@@ -548,29 +1065,115 @@ impl QueryService {
             .as_ref()
             .ok_or_else(|| anyhow::anyhow!("Missing nodeId"))?;
 
+        if let Some(arrow) = rel_type.traversal_arrow() {
+            return self.build_traversal_condition(id_field, node_id, arrow, filter);
+        }
+
         // Issue #788: Universal Relationship Architecture - use relationship table with type filter
-        match rel_type {
-            RelationshipType::Children => Ok(format!(
-                "{} IN (SELECT VALUE out FROM relationship WHERE in = node:⟨{}⟩ AND relationship_type = 'has_child')",
-                id_field,
-                self.escape_string(node_id)
-            )),
-            RelationshipType::Parent => Ok(format!(
-                "{} IN (SELECT VALUE in FROM relationship WHERE out = node:⟨{}⟩ AND relationship_type = 'has_child')",
-                id_field,
-                self.escape_string(node_id)
-            )),
-            RelationshipType::Mentions => Ok(format!(
-                "{} IN (SELECT VALUE out FROM relationship WHERE in = node:⟨{}⟩ AND relationship_type = 'mentions')",
-                id_field,
-                self.escape_string(node_id)
-            )),
-            RelationshipType::MentionedBy => Ok(format!(
-                "{} IN (SELECT VALUE in FROM relationship WHERE out = node:⟨{}⟩ AND relationship_type = 'mentions')",
-                id_field,
-                self.escape_string(node_id)
-            )),
+        let (edge_type, anchor, result) = rel_type
+            .edge()
+            .ok_or_else(|| anyhow::anyhow!("Unsupported relationshipType: {:?}", rel_type))?;
+        let mut edge_conditions = vec![
+            format!("{} = node:⟨{}⟩", anchor, self.escape_string(node_id)),
+            format!("relationship_type = '{}'", self.escape_string(edge_type)),
+        ];
+        for edge_filter in filter.edge_filters.iter().flatten() {
+            edge_conditions.push(self.build_edge_condition(edge_filter)?);
+        }
+        Ok(format!(
+            "{} IN (SELECT VALUE {} FROM relationship WHERE {})",
+            id_field,
+            result,
+            edge_conditions.join(" AND ")
+        ))
+    }
+
+    /// Build a hierarchy traversal condition using recursive graph syntax
+    ///
+    /// `{..N+collect}` follows `has_child` edges up to N levels from the
+    /// anchor and collects every node reached, so the anchor itself is not
+    /// part of the result. Reference edges are skipped: a transcluded node
+    /// is not a descendant of the place it is shown.
+    fn build_traversal_condition(
+        &self,
+        id_field: &str,
+        node_id: &str,
+        arrow: &str,
+        filter: &QueryFilter,
+    ) -> Result<String> {
+        if filter.edge_filters.as_ref().is_some_and(|f| !f.is_empty()) {
+            anyhow::bail!("Edge filters are not supported for hierarchy traversals");
+        }
+        let depth = match filter.max_depth {
+            Some(0) => anyhow::bail!("maxDepth must be at least 1"),
+            Some(depth) => depth.to_string(),
+            None => String::new(),
+        };
+        Ok(format!(
+            "{} IN node:⟨{}⟩.{{..{}+collect}}{}relationship[WHERE relationship_type = 'has_child' AND properties.reference != true]{}node",
+            id_field,
+            self.escape_string(node_id),
+            depth,
+            arrow,
+            arrow
+        ))
+    }
+
+    /// Build a condition on relationship records
+    fn build_edge_condition(&self, filter: &EdgeFilter) -> Result<String> {
+        let field = resolve_edge_field(&filter.property)?;
+        let is_timestamp = EDGE_TIMESTAMP_FIELDS.contains(&field.as_str());
+        match &filter.value {
+            Some(serde_json::Value::String(value)) if is_timestamp => {
+                self.build_timestamp_condition(&field, &filter.operator, value)
+            }
+            _ => self.build_filter_condition(
+                &field,
+                &filter.operator,
+                filter.value.as_ref(),
+                filter.case_sensitive,
+            ),
+        }
+    }
+
+    /// Compare the datetime `field` with a timestamp value
+    fn build_timestamp_condition(
+        &self,
+        field: &str,
+        operator: &FilterOperator,
+        value: &str,
+    ) -> Result<String> {
+        let op = match operator {
+            FilterOperator::Equals => "=",
+            FilterOperator::GreaterThan => ">",
+            FilterOperator::LessThan => "<",
+            FilterOperator::GreaterThanOrEqual => ">=",
+            FilterOperator::LessThanOrEqual => "<=",
+            _ => anyhow::bail!("Unsupported operator for {}: {:?}", field, operator),
+        };
+        Ok(format!(
+            "{} {} {}",
+            field,
+            op,
+            self.format_timestamp(value)?
+        ))
+    }
+
+    /// SurrealQL datetime for an RFC 3339 value or a `-<duration>` offset from now
+    fn format_timestamp(&self, value: &str) -> Result<String> {
+        if let Some(duration) = value.strip_prefix('-') {
+            let split = duration
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(duration.len());
+            let (amount, unit) = duration.split_at(split);
+            if amount.is_empty() || !["m", "h", "d", "w", "y"].contains(&unit) {
+                anyhow::bail!("Invalid relative time '{}': expected e.g. -7d", value);
+            }
+            return Ok(format!("time::now() - {}{}", amount, unit));
         }
+        chrono::DateTime::parse_from_rfc3339(value)
+            .with_context(|| format!("Invalid timestamp '{}'", value))?;
+        Ok(format!("<datetime> '{}'", value))
     }
 
     /// Format a JSON value for SQL
@@ -589,6 +1192,194 @@ impl QueryService {
     fn escape_string(&self, s: &str) -> String {
         s.replace('\'', "\\'")
     }
+
+    // ========== Hub-Only Property Filters ==========
+
+    /// Evaluate a property filter against a fetched hub-only node
+    ///
+    /// Mirrors the SurrealQL semantics of `build_filter_condition`.
+    fn matches_hub_property_filter(&self, node: &Node, filter: &QueryFilter) -> Result<bool> {
+        use serde_json::Value;
+        use std::cmp::Ordering;
+
+        let property = filter
+            .property
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Property filter missing 'property' field"))?;
+        let actual = hub_property(node, property);
+        let expected = || {
+            filter
+                .value
+                .as_ref()
+                .ok_or_else(|| anyhow::anyhow!("Missing value"))
+        };
+        let compare = |accept: fn(Ordering) -> bool| -> Result<bool> {
+            let expected = expected()?;
+            Ok(match (&actual, expected) {
+                (Some(Value::Number(_)), Value::Number(_))
+                | (Some(Value::String(_)), Value::String(_)) => {
+                    accept(self.compare_json_values(actual.as_ref(), Some(expected)))
+                }
+                _ => false,
+            })
+        };
+
+        match filter.operator {
+            FilterOperator::Equals => Ok(match expected()? {
+                Value::Null => actual.is_none(),
+                expected => actual.as_ref() == Some(expected),
+            }),
+            FilterOperator::Contains => {
+                let needle = expected()?
+                    .as_str()
+                    .ok_or_else(|| anyhow::anyhow!("Contains requires string value"))?;
+                let case_sensitive = filter.case_sensitive.unwrap_or(true);
+                let contains = |haystack: &str| {
+                    if case_sensitive {
+                        haystack.contains(needle)
+                    } else {
+                        haystack.to_lowercase().contains(&needle.to_lowercase())
+                    }
+                };
+                Ok(match &actual {
+                    Some(Value::String(s)) => contains(s),
+                    Some(Value::Array(items)) => items
+                        .iter()
+                        .any(|item| item.as_str().is_some_and(&contains)),
+                    _ => false,
+                })
+            }
+            FilterOperator::GreaterThan => compare(Ordering::is_gt),
+            FilterOperator::LessThan => compare(Ordering::is_lt),
+            FilterOperator::GreaterThanOrEqual => compare(Ordering::is_ge),
+            FilterOperator::LessThanOrEqual => compare(Ordering::is_le),
+            FilterOperator::In => {
+                let values = expected()?
+                    .as_array()
+                    .ok_or_else(|| anyhow::anyhow!("In requires array value"))?;
+                Ok(actual
+                    .as_ref()
+                    .is_some_and(|actual| values.contains(actual)))
+            }
+            FilterOperator::Exists => Ok(actual.is_some()),
+        }
+    }
+}
+
+/// Relationship filter whose edges `edge.*` sort fields refer to
+/// Whether a sort field is applied in Rust rather than `ORDER BY`
+///
+/// Edge fields, and properties of hub-only types, are.
+fn is_sorted_in_rust(field: &str, hub_only: bool) -> bool {
+    field.starts_with(EDGE_SORT_PREFIX)
+        || (hub_only && !METADATA_FIELDS.contains(&field) && computed_sort_expr(field).is_none())
+}
+
+/// SurrealQL expression of a computed sort field
+fn computed_sort_expr(field: &str) -> Option<&'static str> {
+    COMPUTED_SORT_FIELDS
+        .iter()
+        .find(|(name, _)| *name == field)
+        .map(|(_, expr)| *expr)
+}
+
+/// Whether rows are still filtered or sorted after the database query
+fn is_post_processed(query: &QueryDefinition) -> bool {
+    let hub_only = is_hub_only(&query.target_type);
+    let rust_sorted = query
+        .sorting
+        .iter()
+        .flatten()
+        .any(|s| is_sorted_in_rust(&s.field, hub_only));
+    rust_sorted
+        || (hub_only
+            && query
+                .filters
+                .iter()
+                .any(|f| f.filter_type == FilterType::Property))
+}
+
+fn edge_sort_source(query: &QueryDefinition) -> Option<&QueryFilter> {
+    let sorts_by_edge = query
+        .sorting
+        .iter()
+        .flatten()
+        .any(|s| s.field.starts_with(EDGE_SORT_PREFIX));
+    if !sorts_by_edge {
+        return None;
+    }
+    query.filters.iter().find(|f| {
+        f.filter_type == FilterType::Relationship
+            && f.relationship_type
+                .as_ref()
+                .is_some_and(|t| t.edge().is_some())
+    })
+}
+
+/// SurrealQL field on a relationship record for an edge property name
+fn resolve_edge_field(property: &str) -> Result<String> {
+    match property {
+        "added_at" => Ok("created_at".to_string()),
+        _ if EDGE_TIMESTAMP_FIELDS.contains(&property) => Ok(property.to_string()),
+        _ if !property.is_empty()
+            && property
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_') =>
+        {
+            Ok(format!("properties.{}", property))
+        }
+        _ => anyhow::bail!("Invalid edge property: {}", property),
+    }
+}
+
+/// Value of an edge property from a record loaded by `load_edges`
+fn edge_value<'a>(
+    edges: &'a HashMap<String, serde_json::Value>,
+    node_id: &str,
+    property: &str,
+) -> Option<&'a serde_json::Value> {
+    let record = edges.get(node_id)?;
+    match property {
+        "added_at" => record.get("created_at"),
+        _ if EDGE_TIMESTAMP_FIELDS.contains(&property) => record.get(property),
+        _ => record.get("properties")?.get(property),
+    }
+}
+
+fn is_hub_only(node_type: &str) -> bool {
+    HUB_ONLY_TYPES.contains(&node_type)
+}
+
+/// Property of a hub-only node: derived from content, then namespaced, then flat
+fn hub_property(node: &Node, field: &str) -> Option<serde_json::Value> {
+    derived_property(node, field)
+        .or_else(|| {
+            node.properties
+                .get(&node.node_type)
+                .and_then(|ns| ns.get(field))
+                .cloned()
+        })
+        .or_else(|| node.properties.get(field).cloned())
+}
+
+/// Values encoded in the content of built-in types
+///
+/// - code-block `language`: the opening fence's info string (```` ```rust ````)
+/// - header `level`: the number of leading `#`
+fn derived_property(node: &Node, field: &str) -> Option<serde_json::Value> {
+    let first_line = node.content.lines().next()?.trim();
+    match (node.node_type.as_str(), field) {
+        ("code-block", "language") => {
+            let language = first_line.strip_prefix("```")?.trim();
+            (!language.is_empty()).then(|| language.into())
+        }
+        ("header", "level") => {
+            let level = first_line.chars().take_while(|c| *c == '#').count();
+            let heading = (1..=6).contains(&level) && first_line[level..].starts_with(' ');
+            heading.then(|| level.into())
+        }
+        _ => None,
+    }
 }
 
 #[cfg(test)]
@@ -7,10 +7,11 @@
 #[cfg(test)]
 mod tests {
     use crate::db::SurrealStore;
+    use crate::services::error::NodeServiceError;
     use crate::services::node_service::{CreateNodeParams, NodeService};
     use crate::services::query_service::{
-        FilterOperator, FilterType, QueryDefinition, QueryFilter, QueryService, RelationshipType,
-        SortConfig, SortDirection,
+        EdgeFilter, FilterOperator, FilterType, QueryDefinition, QueryFilter, QueryPath,
+        QueryService, RelationshipType, SortConfig, SortDirection,
     };
     use serde_json::json;
     use std::sync::Arc;
@@ -23,11 +24,44 @@ mod tests {
 
         let mut store = Arc::new(SurrealStore::new(db_path).await.unwrap());
         let node_service = Arc::new(NodeService::new(&mut store).await.unwrap());
-        let query_service = Arc::new(QueryService::new(store.clone()));
+        let query_service = Arc::new(QueryService::new(
+            store.clone(),
+            node_service.workspace_lock().clone(),
+        ));
 
         (query_service, node_service, temp_dir)
     }
 
+    #[tokio::test]
+    async fn test_execute_refuses_locked_workspace() {
+        let (query_service, node_service, _temp) = create_test_services().await;
+        node_service
+            .set_passphrase(None, Some("correct horse"))
+            .await
+            .unwrap();
+        node_service.lock().unwrap();
+
+        let query = QueryDefinition {
+            target_type: "task".to_string(),
+            filters: vec![],
+            sorting: None,
+            limit: None,
+            offset: None,
+        };
+        for error in [
+            query_service.execute(&query).await.unwrap_err(),
+            query_service.explain(&query).await.unwrap_err(),
+        ] {
+            assert!(matches!(
+                error.downcast_ref::<NodeServiceError>(),
+                Some(NodeServiceError::WorkspaceLocked)
+            ));
+        }
+
+        node_service.unlock("correct horse").await.unwrap();
+        assert!(query_service.execute(&query).await.unwrap().is_empty());
+    }
+
     #[tokio::test]
     async fn test_simple_type_filter() {
         let (query_service, node_service, _temp) = create_test_services().await;
@@ -68,6 +102,7 @@ mod tests {
             filters: vec![],
             sorting: None,
             limit: None,
+            offset: None,
         };
 
         let results = query_service.execute(&query).await.unwrap();
@@ -124,9 +159,12 @@ mod tests {
                 case_sensitive: None,
                 relationship_type: None,
                 node_id: None,
+                edge_filters: None,
+                max_depth: None,
             }],
             sorting: None,
             limit: None,
+            offset: None,
         };
 
         let results = query_service.execute(&query).await.unwrap();
@@ -171,9 +209,12 @@ mod tests {
                 case_sensitive: None,
                 relationship_type: None,
                 node_id: None,
+                edge_filters: None,
+                max_depth: None,
             }],
             sorting: None,
             limit: None,
+            offset: None,
         };
 
         let results = query_service.execute(&query).await.unwrap();
@@ -223,9 +264,12 @@ mod tests {
                 case_sensitive: Some(true),
                 relationship_type: None,
                 node_id: None,
+                edge_filters: None,
+                max_depth: None,
             }],
             sorting: None,
             limit: None,
+            offset: None,
         };
 
         let results = query_service.execute(&query).await.unwrap();
@@ -276,9 +320,12 @@ mod tests {
                 case_sensitive: Some(false),
                 relationship_type: None,
                 node_id: None,
+                edge_filters: None,
+                max_depth: None,
             }],
             sorting: None,
             limit: None,
+            offset: None,
         };
 
         let results = query_service.execute(&query).await.unwrap();
@@ -337,9 +384,12 @@ mod tests {
                 case_sensitive: None,
                 relationship_type: Some(RelationshipType::Children),
                 node_id: Some(parent_id.clone()),
+                edge_filters: None,
+                max_depth: None,
             }],
             sorting: None,
             limit: None,
+            offset: None,
         };
 
         let results = query_service.execute(&query).await.unwrap();
@@ -379,6 +429,7 @@ mod tests {
                 direction: SortDirection::Descending,
             }]),
             limit: None,
+            offset: None,
         };
 
         let results = query_service.execute(&query).await.unwrap();
@@ -413,6 +464,7 @@ mod tests {
             filters: vec![],
             sorting: None,
             limit: Some(5),
+            offset: None,
         };
 
         let results = query_service.execute(&query).await.unwrap();
@@ -461,6 +513,8 @@ mod tests {
                     case_sensitive: None,
                     relationship_type: None,
                     node_id: None,
+                    edge_filters: None,
+                    max_depth: None,
                 },
                 QueryFilter {
                     filter_type: FilterType::Property,
@@ -470,10 +524,13 @@ mod tests {
                     case_sensitive: None,
                     relationship_type: None,
                     node_id: None,
+                    edge_filters: None,
+                    max_depth: None,
                 },
             ],
             sorting: None,
             limit: None,
+            offset: None,
         };
 
         let results = query_service.execute(&query).await.unwrap();
@@ -523,9 +580,12 @@ mod tests {
                 case_sensitive: None,
                 relationship_type: None,
                 node_id: None,
+                edge_filters: None,
+                max_depth: None,
             }],
             sorting: None,
             limit: None,
+            offset: None,
         };
 
         let results = query_service.execute(&query).await.unwrap();
@@ -549,9 +609,12 @@ mod tests {
                 case_sensitive: None,
                 relationship_type: None,
                 node_id: None,
+                edge_filters: None,
+                max_depth: None,
             }],
             sorting: None,
             limit: None,
+            offset: None,
         };
 
         let results = query_service.execute(&query).await.unwrap();
@@ -594,9 +657,12 @@ mod tests {
                 case_sensitive: None,
                 relationship_type: None,
                 node_id: None,
+                edge_filters: None,
+                max_depth: None,
             }],
             sorting: None,
             limit: None,
+            offset: None,
         };
 
         let results = query_service.execute(&query).await.unwrap();
@@ -630,6 +696,7 @@ mod tests {
                 direction: SortDirection::Ascending,
             }]),
             limit: None,
+            offset: None,
         };
 
         let results = query_service.execute(&query).await.unwrap();
@@ -663,6 +730,7 @@ mod tests {
             filters: vec![],
             sorting: None,
             limit: None,
+            offset: None,
         };
 
         let result = query_service.execute(&query).await;
@@ -708,9 +776,12 @@ mod tests {
                 case_sensitive: None,
                 relationship_type: Some(RelationshipType::Parent),
                 node_id: Some(child_id.clone()),
+                edge_filters: None,
+                max_depth: None,
             }],
             sorting: None,
             limit: None,
+            offset: None,
         };
 
         let results = query_service.execute(&query).await.unwrap();
@@ -758,9 +829,12 @@ mod tests {
                 case_sensitive: None,
                 relationship_type: None,
                 node_id: None,
+                edge_filters: None,
+                max_depth: None,
             }],
             sorting: None,
             limit: None,
+            offset: None,
         };
 
         let results = query_service.execute(&query).await.unwrap();
@@ -803,9 +877,12 @@ mod tests {
                 case_sensitive: Some(true),
                 relationship_type: None,
                 node_id: None,
+                edge_filters: None,
+                max_depth: None,
             }],
             sorting: None,
             limit: None,
+            offset: None,
         };
 
         let results = query_service.execute(&query).await.unwrap();
@@ -841,12 +918,15 @@ mod tests {
                 case_sensitive: None,
                 relationship_type: None,
                 node_id: None,
+                edge_filters: None,
+                max_depth: None,
             }],
             sorting: Some(vec![SortConfig {
                 field: "content".to_string(),
                 direction: SortDirection::Ascending,
             }]),
             limit: Some(2),
+            offset: None,
         };
 
         let results = query_service.execute(&query).await.unwrap();
@@ -855,6 +935,38 @@ mod tests {
         assert_eq!(results[1].content, "Beta");
     }
 
+    #[tokio::test]
+    async fn test_offset_skips_leading_results() {
+        let (query_service, node_service, _temp) = create_test_services().await;
+
+        for content in &["Zebra", "Alpha", "Beta"] {
+            let task = CreateNodeParams {
+                id: None,
+                node_type: "task".to_string(),
+                content: content.to_string(),
+                parent_id: None,
+                insert_after_node_id: None,
+                properties: json!({"task": {"status": "open"}}),
+            };
+            node_service.create_node_with_parent(task).await.unwrap();
+        }
+
+        let query = QueryDefinition {
+            target_type: "task".to_string(),
+            filters: vec![],
+            sorting: Some(vec![SortConfig {
+                field: "content".to_string(),
+                direction: SortDirection::Ascending,
+            }]),
+            limit: Some(1),
+            offset: Some(1),
+        };
+
+        let results = query_service.execute(&query).await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].content, "Beta");
+    }
+
     // =========================================================================
     // Error Condition Tests
     // =========================================================================
@@ -874,9 +986,12 @@ mod tests {
                 case_sensitive: None,
                 relationship_type: None,
                 node_id: None,
+                edge_filters: None,
+                max_depth: None,
             }],
             sorting: None,
             limit: None,
+            offset: None,
         };
 
         let result = query_service.execute(&query).await;
@@ -900,9 +1015,12 @@ mod tests {
                 case_sensitive: None,
                 relationship_type: Some(RelationshipType::Children),
                 node_id: None, // Missing!
+                edge_filters: None,
+                max_depth: None,
             }],
             sorting: None,
             limit: None,
+            offset: None,
         };
 
         let result = query_service.execute(&query).await;
@@ -926,9 +1044,12 @@ mod tests {
                 case_sensitive: None,
                 relationship_type: None, // Missing!
                 node_id: Some("test-id".to_string()),
+                edge_filters: None,
+                max_depth: None,
             }],
             sorting: None,
             limit: None,
+            offset: None,
         };
 
         let result = query_service.execute(&query).await;
@@ -952,9 +1073,12 @@ mod tests {
                 case_sensitive: None,
                 relationship_type: None,
                 node_id: None,
+                edge_filters: None,
+                max_depth: None,
             }],
             sorting: None,
             limit: None,
+            offset: None,
         };
 
         let result = query_service.execute(&query).await;
@@ -991,6 +1115,7 @@ mod tests {
                 direction: SortDirection::Descending,
             }]),
             limit: None,
+            offset: None,
         };
 
         let results = query_service.execute(&query).await.unwrap();
@@ -1033,6 +1158,7 @@ mod tests {
                 direction: SortDirection::Ascending,
             }]),
             limit: None,
+            offset: None,
         };
 
         let results = query_service.execute(&query).await.unwrap();
@@ -1071,9 +1197,12 @@ mod tests {
                 case_sensitive: None,
                 relationship_type: None,
                 node_id: None,
+                edge_filters: None,
+                max_depth: None,
             }],
             sorting: None,
             limit: None,
+            offset: None,
         };
 
         let results = query_service.execute(&query).await.unwrap();
@@ -1110,9 +1239,12 @@ mod tests {
                 case_sensitive: None,
                 relationship_type: None,
                 node_id: None,
+                edge_filters: None,
+                max_depth: None,
             }],
             sorting: None,
             limit: None,
+            offset: None,
         };
 
         let results = query_service.execute(&query).await.unwrap();
@@ -1160,6 +1292,8 @@ mod tests {
                     case_sensitive: None,
                     relationship_type: None,
                     node_id: None,
+                    edge_filters: None,
+                    max_depth: None,
                 },
                 QueryFilter {
                     filter_type: FilterType::Content,
@@ -1169,10 +1303,13 @@ mod tests {
                     case_sensitive: Some(true),
                     relationship_type: None,
                     node_id: None,
+                    edge_filters: None,
+                    max_depth: None,
                 },
             ],
             sorting: None,
             limit: None,
+            offset: None,
         };
 
         let results = query_service.execute(&query).await.unwrap();
@@ -1199,12 +1336,15 @@ mod tests {
                 case_sensitive: None,
                 relationship_type: None,
                 node_id: None,
+                edge_filters: None,
+                max_depth: None,
             }],
             sorting: Some(vec![SortConfig {
                 field: "content".to_string(),
                 direction: SortDirection::Ascending,
             }]),
             limit: None,
+            offset: None,
         };
 
         let results = query_service.execute(&query).await.unwrap();
@@ -1246,6 +1386,7 @@ mod tests {
                 direction: SortDirection::Ascending,
             }]),
             limit: None,
+            offset: None,
         };
 
         let results = query_service.execute(&query).await.unwrap();
@@ -1282,6 +1423,7 @@ mod tests {
                 direction: SortDirection::Descending,
             }]),
             limit: None,
+            offset: None,
         };
 
         let results = query_service.execute(&query).await.unwrap();
@@ -1327,6 +1469,7 @@ mod tests {
                 },
             ]),
             limit: None,
+            offset: None,
         };
 
         let results = query_service.execute(&query).await.unwrap();
@@ -1336,4 +1479,505 @@ mod tests {
         assert_eq!(results[1].content, "Alpha Task"); // open, comes before Beta
         assert_eq!(results[2].content, "Beta Task"); // open
     }
+
+    #[tokio::test]
+    async fn test_hub_only_type_property_filter() {
+        let (query_service, node_service, _temp) = create_test_services().await;
+
+        let blocks = [
+            ("```rust\nfn main() {}\n```", json!({})),
+            ("```python\nprint()\n```", json!({})),
+            ("```rust\nlet x = 1;\n```", json!({})),
+            ("```\nplain\n```", json!({"language": "rust"})),
+        ];
+        for (content, properties) in blocks {
+            let block = CreateNodeParams {
+                id: None,
+                node_type: "code-block".to_string(),
+                content: content.to_string(),
+                parent_id: None,
+                insert_after_node_id: None,
+                properties,
+            };
+            node_service.create_node_with_parent(block).await.unwrap();
+        }
+
+        let query = QueryDefinition {
+            target_type: "code-block".to_string(),
+            filters: vec![QueryFilter {
+                filter_type: FilterType::Property,
+                operator: FilterOperator::Equals,
+                property: Some("language".to_string()),
+                value: Some(json!("rust")),
+                case_sensitive: None,
+                relationship_type: None,
+                node_id: None,
+                edge_filters: None,
+                max_depth: None,
+            }],
+            sorting: None,
+            limit: Some(2),
+            offset: None,
+        };
+
+        // Fence language and the flat property both count; limit applies after filtering
+        let results = query_service.execute(&query).await.unwrap();
+        assert_eq!(results.len(), 2);
+        assert!(results.iter().all(|n| n.node_type == "code-block"));
+        assert!(results.iter().all(|n| !n.content.contains("python")));
+    }
+
+    #[tokio::test]
+    async fn test_relationship_edge_filters_and_sorting() {
+        let (query_service, node_service, _temp) = create_test_services().await;
+
+        let parent = CreateNodeParams {
+            id: None,
+            node_type: "text".to_string(),
+            content: "Parent".to_string(),
+            parent_id: None,
+            insert_after_node_id: None,
+            properties: json!({}),
+        };
+        let parent_id = node_service.create_node_with_parent(parent).await.unwrap();
+
+        let mut previous = None;
+        for content in ["First", "Second", "Third"] {
+            let child = CreateNodeParams {
+                id: None,
+                node_type: "text".to_string(),
+                content: content.to_string(),
+                parent_id: Some(parent_id.clone()),
+                insert_after_node_id: previous.clone(),
+                properties: json!({}),
+            };
+            previous = Some(node_service.create_node_with_parent(child).await.unwrap());
+        }
+
+        let children_added_within = |since: &str| QueryFilter {
+            filter_type: FilterType::Relationship,
+            operator: FilterOperator::Equals,
+            property: None,
+            value: None,
+            case_sensitive: None,
+            relationship_type: Some(RelationshipType::Children),
+            node_id: Some(parent_id.clone()),
+            edge_filters: Some(vec![EdgeFilter {
+                property: "added_at".to_string(),
+                operator: FilterOperator::GreaterThanOrEqual,
+                value: Some(json!(since)),
+                case_sensitive: None,
+            }]),
+            max_depth: None,
+        };
+
+        // Children added in the last week, in reverse sibling order
+        let query = QueryDefinition {
+            target_type: "*".to_string(),
+            filters: vec![children_added_within("-7d")],
+            sorting: Some(vec![SortConfig {
+                field: "edge.order".to_string(),
+                direction: SortDirection::Descending,
+            }]),
+            limit: Some(2),
+            offset: None,
+        };
+        let results = query_service.execute(&query).await.unwrap();
+        let contents: Vec<&str> = results.iter().map(|n| n.content.as_str()).collect();
+        assert_eq!(contents, vec!["Third", "Second"]);
+
+        // Nothing was added after a point in the future
+        let query = QueryDefinition {
+            target_type: "*".to_string(),
+            filters: vec![children_added_within("2999-01-01T00:00:00Z")],
+            sorting: None,
+            limit: None,
+            offset: None,
+        };
+        assert!(query_service.execute(&query).await.unwrap().is_empty());
+
+        // Edge sorts need a relationship filter to take edges from
+        let query = QueryDefinition {
+            target_type: "text".to_string(),
+            filters: vec![],
+            sorting: Some(vec![SortConfig {
+                field: "edge.order".to_string(),
+                direction: SortDirection::Ascending,
+            }]),
+            limit: None,
+            offset: None,
+        };
+        assert!(query_service.execute(&query).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_metadata_timestamp_filters() {
+        let (query_service, node_service, _temp) = create_test_services().await;
+        let params = CreateNodeParams {
+            id: None,
+            node_type: "text".to_string(),
+            content: "Fresh note".to_string(),
+            parent_id: None,
+            insert_after_node_id: None,
+            properties: json!({}),
+        };
+        node_service.create_node_with_parent(params).await.unwrap();
+
+        let created = |operator: FilterOperator, value: &str| QueryDefinition {
+            target_type: "text".to_string(),
+            filters: vec![QueryFilter {
+                filter_type: FilterType::Metadata,
+                operator,
+                property: Some("created_at".to_string()),
+                value: Some(json!(value)),
+                case_sensitive: None,
+                relationship_type: None,
+                node_id: None,
+                edge_filters: None,
+                max_depth: None,
+            }],
+            sorting: None,
+            limit: None,
+            offset: None,
+        };
+
+        let recent = query_service
+            .execute(&created(FilterOperator::GreaterThanOrEqual, "-1d"))
+            .await
+            .unwrap();
+        assert!(recent.iter().any(|node| node.content == "Fresh note"));
+
+        let older = query_service
+            .execute(&created(FilterOperator::LessThan, "2000-01-01T00:00:00Z"))
+            .await
+            .unwrap();
+        assert!(older.is_empty());
+
+        assert!(query_service
+            .execute(&created(FilterOperator::Contains, "-1d"))
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_descendants_and_ancestors_with_max_depth() {
+        let (query_service, node_service, _temp) = create_test_services().await;
+
+        // root -> project -> task -> subtask
+        let mut parent_id: Option<String> = None;
+        let mut ids = Vec::new();
+        for (node_type, content) in [
+            ("text", "Projects"),
+            ("text", "ACME"),
+            ("task", "Ship release"),
+            ("task", "Write notes"),
+        ] {
+            let params = CreateNodeParams {
+                id: None,
+                node_type: node_type.to_string(),
+                content: content.to_string(),
+                parent_id: parent_id.clone(),
+                insert_after_node_id: None,
+                properties: if node_type == "task" {
+                    json!({"task": {"status": "open"}})
+                } else {
+                    json!({})
+                },
+            };
+            let id = node_service.create_node_with_parent(params).await.unwrap();
+            parent_id = Some(id.clone());
+            ids.push(id);
+        }
+
+        // A task shown under ACME by reference is not one of its descendants
+        let elsewhere = CreateNodeParams {
+            id: None,
+            node_type: "task".to_string(),
+            content: "Borrowed task".to_string(),
+            parent_id: None,
+            insert_after_node_id: None,
+            properties: json!({"task": {"status": "open"}}),
+        };
+        let elsewhere = node_service
+            .create_node_with_parent(elsewhere)
+            .await
+            .unwrap();
+        node_service
+            .add_reference_child(&ids[1], &elsewhere)
+            .await
+            .unwrap();
+
+        let traversal =
+            |relationship_type: RelationshipType, node_id: &str, max_depth| QueryFilter {
+                filter_type: FilterType::Relationship,
+                operator: FilterOperator::Equals,
+                property: None,
+                value: None,
+                case_sensitive: None,
+                relationship_type: Some(relationship_type),
+                node_id: Some(node_id.to_string()),
+                edge_filters: None,
+                max_depth,
+            };
+        let contents = |nodes: Vec<crate::models::Node>| {
+            let mut contents: Vec<String> = nodes.into_iter().map(|n| n.content).collect();
+            contents.sort();
+            contents
+        };
+
+        // All tasks anywhere under the root
+        let query = QueryDefinition {
+            target_type: "task".to_string(),
+            filters: vec![traversal(RelationshipType::DescendantsOf, &ids[0], None)],
+            sorting: None,
+            limit: None,
+            offset: None,
+        };
+        assert_eq!(
+            contents(query_service.execute(&query).await.unwrap()),
+            vec!["Ship release", "Write notes"]
+        );
+
+        // Two levels down stops at the first task
+        let query = QueryDefinition {
+            target_type: "*".to_string(),
+            filters: vec![traversal(RelationshipType::DescendantsOf, &ids[0], Some(2))],
+            sorting: None,
+            limit: None,
+            offset: None,
+        };
+        assert_eq!(
+            contents(query_service.execute(&query).await.unwrap()),
+            vec!["ACME", "Ship release"]
+        );
+
+        // Ancestors of the subtask, one level up
+        let query = QueryDefinition {
+            target_type: "*".to_string(),
+            filters: vec![traversal(RelationshipType::AncestorsOf, &ids[3], Some(1))],
+            sorting: None,
+            limit: None,
+            offset: None,
+        };
+        assert_eq!(
+            contents(query_service.execute(&query).await.unwrap()),
+            vec!["Ship release"]
+        );
+
+        // Nor is ACME an ancestor of the referenced task
+        let query = QueryDefinition {
+            target_type: "*".to_string(),
+            filters: vec![traversal(RelationshipType::AncestorsOf, &elsewhere, None)],
+            sorting: None,
+            limit: None,
+            offset: None,
+        };
+        assert!(query_service.execute(&query).await.unwrap().is_empty());
+
+        // Depth zero is rejected
+        let query = QueryDefinition {
+            target_type: "*".to_string(),
+            filters: vec![traversal(RelationshipType::AncestorsOf, &ids[3], Some(0))],
+            sorting: None,
+            limit: None,
+            offset: None,
+        };
+        assert!(query_service.execute(&query).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_explain_reports_path_and_indexes() {
+        let (query_service, node_service, _temp) = create_test_services().await;
+
+        let task = CreateNodeParams {
+            id: None,
+            node_type: "task".to_string(),
+            content: "Task".to_string(),
+            parent_id: None,
+            insert_after_node_id: None,
+            properties: json!({"task": {"status": "open"}}),
+        };
+        node_service.create_node_with_parent(task).await.unwrap();
+
+        let query = QueryDefinition {
+            target_type: "task".to_string(),
+            filters: vec![],
+            sorting: None,
+            limit: Some(10),
+            offset: None,
+        };
+        let explanation = query_service.explain(&query).await.unwrap();
+        assert_eq!(
+            explanation.sql,
+            "SELECT * FROM node WHERE node_type = 'task' LIMIT 10;"
+        );
+        assert_eq!(explanation.path, QueryPath::Database);
+        assert!(explanation.indexes.contains(&"idx_node_type".to_string()));
+
+        // Property filters on hub-only types finish in Rust, so the limit moves there too
+        let query = QueryDefinition {
+            target_type: "code-block".to_string(),
+            filters: vec![QueryFilter {
+                filter_type: FilterType::Property,
+                operator: FilterOperator::Equals,
+                property: Some("language".to_string()),
+                value: Some(json!("rust")),
+                case_sensitive: None,
+                relationship_type: None,
+                node_id: None,
+                edge_filters: None,
+                max_depth: None,
+            }],
+            sorting: None,
+            limit: Some(10),
+            offset: None,
+        };
+        let explanation = query_service.explain(&query).await.unwrap();
+        assert_eq!(explanation.path, QueryPath::Hub);
+        assert!(!explanation.sql.contains("LIMIT"));
+    }
+
+    #[tokio::test]
+    async fn test_sort_by_computed_fields() {
+        let (query_service, node_service, _temp) = create_test_services().await;
+
+        let mut notes = Vec::new();
+        for content in ["Rarely cited", "Often cited", "Never cited"] {
+            let params = CreateNodeParams {
+                id: None,
+                node_type: "text".to_string(),
+                content: content.to_string(),
+                parent_id: None,
+                insert_after_node_id: None,
+                properties: json!({}),
+            };
+            notes.push(node_service.create_node_with_parent(params).await.unwrap());
+        }
+        // Children double as mentioning nodes
+        for (parent, target) in [
+            (&notes[0], &notes[1]),
+            (&notes[1], &notes[1]),
+            (&notes[1], &notes[0]),
+        ] {
+            let child = CreateNodeParams {
+                id: None,
+                node_type: "text".to_string(),
+                content: "Child".to_string(),
+                parent_id: Some(parent.clone()),
+                insert_after_node_id: None,
+                properties: json!({}),
+            };
+            let child_id = node_service.create_node_with_parent(child).await.unwrap();
+            node_service.add_mention(&child_id, target).await.unwrap();
+        }
+
+        let sorted_by = |field: &str| QueryDefinition {
+            target_type: "text".to_string(),
+            filters: vec![],
+            sorting: Some(vec![SortConfig {
+                field: field.to_string(),
+                direction: SortDirection::Descending,
+            }]),
+            limit: Some(2),
+            offset: None,
+        };
+
+        let results = query_service
+            .execute(&sorted_by("backlink_count"))
+            .await
+            .unwrap();
+        let contents: Vec<&str> = results.iter().map(|n| n.content.as_str()).collect();
+        assert_eq!(contents, vec!["Often cited", "Rarely cited"]);
+
+        let results = query_service
+            .execute(&sorted_by("child_count"))
+            .await
+            .unwrap();
+        assert_eq!(results[0].id, notes[1]);
+        assert_eq!(results.len(), 2);
+
+        let results = query_service
+            .execute(&sorted_by("last_descendant_modified"))
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_named_relationship_by_inverse_label() {
+        let (query_service, node_service, _temp) = create_test_services().await;
+        let schema = crate::models::Node::new_with_id(
+            "ticket".to_string(),
+            "schema".to_string(),
+            "Ticket".to_string(),
+            json!({
+                "isCore": false,
+                "version": 1,
+                "fields": [],
+                "relationships": [{
+                    "name": "blocks",
+                    "targetType": "ticket",
+                    "direction": "out",
+                    "cardinality": "many",
+                    "reverseName": "blocked_by"
+                }]
+            }),
+        );
+        node_service.create_node(schema).await.unwrap();
+        let mut tickets = Vec::new();
+        for content in ["Schema", "API", "UI"] {
+            let ticket =
+                crate::models::Node::new("ticket".to_string(), content.to_string(), json!({}));
+            tickets.push(node_service.create_node(ticket).await.unwrap());
+        }
+        // The schema blocks the API and the UI
+        for blocked in &tickets[1..] {
+            node_service
+                .create_relationship(&tickets[0], "blocks", blocked, json!({}))
+                .await
+                .unwrap();
+        }
+
+        let related = |name: &str, node_id: &str| QueryDefinition {
+            target_type: "ticket".to_string(),
+            filters: vec![QueryFilter {
+                filter_type: FilterType::Relationship,
+                operator: FilterOperator::Equals,
+                property: None,
+                value: None,
+                case_sensitive: None,
+                relationship_type: Some(RelationshipType::Named(name.to_string())),
+                node_id: Some(node_id.to_string()),
+                edge_filters: None,
+                max_depth: None,
+            }],
+            sorting: None,
+            limit: None,
+            offset: None,
+        };
+
+        let blocked = query_service
+            .execute(&related("blocks", &tickets[0]))
+            .await
+            .unwrap();
+        assert_eq!(blocked.len(), 2);
+        let blockers = query_service
+            .execute(&related("blocked_by", &tickets[2]))
+            .await
+            .unwrap();
+        let contents: Vec<&str> = blockers.iter().map(|n| n.content.as_str()).collect();
+        assert_eq!(contents, vec!["Schema"]);
+
+        let filter: QueryFilter = serde_json::from_value(json!({
+            "type": "relationship",
+            "operator": "equals",
+            "relationshipType": "blocked_by",
+            "nodeId": "x"
+        }))
+        .unwrap();
+        assert_eq!(
+            filter.relationship_type,
+            Some(RelationshipType::Named("blocked_by".to_string()))
+        );
+    }
 }
@@ -0,0 +1,388 @@
+//! Query Watch Service - Live Results for Saved Queries
+//!
+//! Keeps the last result list of each watched saved query and re-runs the
+//! query when a domain event may have changed it. Instead of the UI
+//! re-running and re-rendering the whole query, subscribers get a
+//! [`QueryResultDiff`] naming the nodes that were added, removed or moved.
+//! Re-runs that leave the results unchanged emit nothing.
+//!
+//! Events are batched: a burst (a paste, a subtree delete) triggers a single
+//! re-evaluation once no event has arrived for [`QUERY_WATCH_DEBOUNCE`].
+
+use super::error::NodeServiceError;
+use super::node_service::NodeService;
+use super::query_service::{FilterType, QueryDefinition, QueryService, RelationshipType};
+use crate::db::events::DomainEvent;
+use crate::models::Node;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{broadcast, Mutex};
+
+/// Quiet period before watched queries are re-evaluated
+pub const QUERY_WATCH_DEBOUNCE: Duration = Duration::from_millis(150);
+
+/// A node and its index in the new result list
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResultPosition {
+    pub node_id: String,
+    pub index: usize,
+}
+
+/// Change in the results of a saved query
+///
+/// Indexes refer to the new result list. Nodes not listed keep their
+/// relative order, so dropping `removed` and placing `moved` and `added` at
+/// their indexes turns the old list into the new one.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryResultDiff {
+    pub query_id: String,
+    pub added: Vec<ResultPosition>,
+    pub removed: Vec<String>,
+    pub moved: Vec<ResultPosition>,
+}
+
+impl QueryResultDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.moved.is_empty()
+    }
+}
+
+struct WatchedQuery {
+    definition: QueryDefinition,
+    node_ids: Vec<String>,
+}
+
+/// Re-evaluates watched saved queries on domain events
+pub struct QueryWatchService {
+    node_service: Arc<NodeService>,
+    query_service: Arc<QueryService>,
+    /// Query node ID -> definition and last results
+    watched: Mutex<HashMap<String, WatchedQuery>>,
+}
+
+impl QueryWatchService {
+    pub fn new(node_service: Arc<NodeService>, query_service: Arc<QueryService>) -> Self {
+        Self {
+            node_service,
+            query_service,
+            watched: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Start watching a saved query and return its current results
+    ///
+    /// Watching an already watched query resets its baseline.
+    pub async fn watch(&self, query_id: &str) -> Result<Vec<Node>, NodeServiceError> {
+        let definition = self.load_definition(query_id).await?;
+        let nodes = self.run_query(&definition).await?;
+        self.watched.lock().await.insert(
+            query_id.to_string(),
+            WatchedQuery {
+                definition,
+                node_ids: nodes.iter().map(|node| node.id.clone()).collect(),
+            },
+        );
+        Ok(nodes)
+    }
+
+    pub async fn unwatch(&self, query_id: &str) {
+        self.watched.lock().await.remove(query_id);
+    }
+
+    /// Re-run the watched queries `events` may affect, returning their diffs
+    pub async fn reevaluate(&self, events: &[DomainEvent]) -> Vec<QueryResultDiff> {
+        let mut watched = self.watched.lock().await;
+
+        // Deleting a query node ends its watch; edits reload its definition
+        for event in events {
+            match event {
+                DomainEvent::NodeDeleted { id, .. } => {
+                    watched.remove(id);
+                }
+                DomainEvent::NodeUpdated { node_id, .. } if watched.contains_key(node_id) => {
+                    match self.load_definition(node_id).await {
+                        Ok(definition) => {
+                            if let Some(query) = watched.get_mut(node_id) {
+                                query.definition = definition;
+                            }
+                        }
+                        Err(e) => tracing::warn!("Keeping old definition of {}: {}", node_id, e),
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let affected: Vec<String> = watched
+            .iter()
+            .filter(|(query_id, query)| events.iter().any(|event| affects(query_id, query, event)))
+            .map(|(query_id, _)| query_id.clone())
+            .collect();
+        self.refresh(&mut watched, affected).await
+    }
+
+    /// Re-run every watched query, e.g. after missing events
+    pub async fn reevaluate_all(&self) -> Vec<QueryResultDiff> {
+        let mut watched = self.watched.lock().await;
+        let all = watched.keys().cloned().collect();
+        self.refresh(&mut watched, all).await
+    }
+
+    /// Follow domain events, passing each non-empty diff to `on_diff`
+    ///
+    /// Runs until the event channel closes; callers typically spawn this and
+    /// cancel it on shutdown.
+    pub async fn run<F>(&self, on_diff: F)
+    where
+        F: Fn(QueryResultDiff),
+    {
+        let mut rx = self.node_service.subscribe_to_events();
+        let mut pending: Vec<DomainEvent> = Vec::new();
+        let mut lagged = false;
+        loop {
+            let waiting = lagged || !pending.is_empty();
+            tokio::select! {
+                received = rx.recv() => match received {
+                    Ok(event) => pending.push(event),
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        tracing::debug!("Query watch lagged {} events, re-running all", skipped);
+                        lagged = true;
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                },
+                _ = tokio::time::sleep(QUERY_WATCH_DEBOUNCE), if waiting => {
+                    let events = std::mem::take(&mut pending);
+                    let diffs = if std::mem::take(&mut lagged) {
+                        self.reevaluate_all().await
+                    } else {
+                        self.reevaluate(&events).await
+                    };
+                    diffs.into_iter().for_each(&on_diff);
+                }
+            }
+        }
+    }
+
+    async fn refresh(
+        &self,
+        watched: &mut HashMap<String, WatchedQuery>,
+        query_ids: Vec<String>,
+    ) -> Vec<QueryResultDiff> {
+        let mut diffs = Vec::new();
+        for query_id in query_ids {
+            let Some(query) = watched.get_mut(&query_id) else {
+                continue;
+            };
+            let node_ids: Vec<String> = match self.run_query(&query.definition).await {
+                Ok(nodes) => nodes.into_iter().map(|node| node.id).collect(),
+                Err(e) => {
+                    tracing::warn!("Re-running query {} failed: {}", query_id, e);
+                    continue;
+                }
+            };
+            let diff = diff_results(&query_id, &query.node_ids, &node_ids);
+            query.node_ids = node_ids;
+            if !diff.is_empty() {
+                diffs.push(diff);
+            }
+        }
+        diffs
+    }
+
+    async fn load_definition(&self, query_id: &str) -> Result<QueryDefinition, NodeServiceError> {
+        let node = self
+            .node_service
+            .get_node(query_id)
+            .await?
+            .ok_or_else(|| NodeServiceError::node_not_found(query_id))?;
+        QueryDefinition::from_saved_query(&node)
+            .map_err(|e| NodeServiceError::invalid_input(format!("{:#}", e)))
+    }
+
+    async fn run_query(&self, definition: &QueryDefinition) -> Result<Vec<Node>, NodeServiceError> {
+        self.query_service
+            .execute(definition)
+            .await
+            .map_err(|e| NodeServiceError::query_failed(format!("{:#}", e)))
+    }
+}
+
+/// Whether `event` may change the results of `query`
+fn affects(query_id: &str, query: &WatchedQuery, event: &DomainEvent) -> bool {
+    matches!(event, DomainEvent::NodeDeleted { id, .. } if id == query_id)
+        || may_change_results(&query.definition, &query.node_ids, event)
+}
+
+/// Whether `event` may change the results of `definition`, last seen as
+/// `node_ids`
+///
+/// Node updates always count: without the node's type or old values there is
+/// no telling whether it started or stopped matching.
+pub(crate) fn may_change_results(
+    definition: &QueryDefinition,
+    node_ids: &[String],
+    event: &DomainEvent,
+) -> bool {
+    match event {
+        DomainEvent::NodeCreated { node_type, .. } => {
+            definition.target_type == "*" || definition.target_type == *node_type
+        }
+        DomainEvent::NodeUpdated { .. } => true,
+        DomainEvent::NodeDeleted { id, .. } => node_ids.contains(id),
+        DomainEvent::RelationshipCreated { relationship, .. }
+        | DomainEvent::RelationshipUpdated { relationship, .. } => {
+            follows_edges(definition, &relationship.relationship_type)
+        }
+        DomainEvent::RelationshipDeleted {
+            relationship_type, ..
+        } => follows_edges(definition, relationship_type),
+    }
+}
+
+/// Whether a relationship filter of `definition` follows edges of `edge_type`
+///
+/// A named relationship may be an inverse label, known only once the query
+/// runs, so it is assumed to follow any schema relationship.
+fn follows_edges(definition: &QueryDefinition, edge_type: &str) -> bool {
+    definition.filters.iter().any(|filter| {
+        filter.filter_type == FilterType::Relationship
+            && filter
+                .relationship_type
+                .as_ref()
+                .is_some_and(|rel| match rel {
+                    RelationshipType::Named(_) => {
+                        !matches!(edge_type, "has_child" | "mentions" | "member_of" | "embeds")
+                    }
+                    _ => rel.edge_type() == edge_type,
+                })
+    })
+}
+
+/// Diff two result lists
+///
+/// Kept nodes outside the longest run that preserved its relative order are
+/// reported as moved, so moving one node does not report its neighbours.
+fn diff_results(query_id: &str, old: &[String], new: &[String]) -> QueryResultDiff {
+    let old_index: HashMap<&str, usize> = old
+        .iter()
+        .enumerate()
+        .map(|(index, id)| (id.as_str(), index))
+        .collect();
+    let new_ids: HashSet<&str> = new.iter().map(String::as_str).collect();
+
+    let removed = old
+        .iter()
+        .filter(|id| !new_ids.contains(id.as_str()))
+        .cloned()
+        .collect();
+
+    let mut added = Vec::new();
+    // (new index, old index) of nodes in both lists, in new order
+    let mut kept = Vec::new();
+    for (index, id) in new.iter().enumerate() {
+        match old_index.get(id.as_str()) {
+            Some(&old_index) => kept.push((index, old_index)),
+            None => added.push(ResultPosition {
+                node_id: id.clone(),
+                index,
+            }),
+        }
+    }
+
+    let old_order: Vec<usize> = kept.iter().map(|&(_, old_index)| old_index).collect();
+    let in_place = longest_increasing(&old_order);
+    let moved = kept
+        .iter()
+        .zip(in_place)
+        .filter(|(_, in_place)| !in_place)
+        .map(|(&(index, _), _)| ResultPosition {
+            node_id: new[index].clone(),
+            index,
+        })
+        .collect();
+
+    QueryResultDiff {
+        query_id: query_id.to_string(),
+        added,
+        removed,
+        moved,
+    }
+}
+
+/// Marks the elements of one longest strictly increasing subsequence
+fn longest_increasing(values: &[usize]) -> Vec<bool> {
+    // tails[k]: index of the smallest tail of an increasing run of length k + 1
+    let mut tails: Vec<usize> = Vec::new();
+    let mut previous: Vec<Option<usize>> = vec![None; values.len()];
+    for (i, &value) in values.iter().enumerate() {
+        let len = tails.partition_point(|&t| values[t] < value);
+        if len > 0 {
+            previous[i] = Some(tails[len - 1]);
+        }
+        if len == tails.len() {
+            tails.push(i);
+        } else {
+            tails[len] = i;
+        }
+    }
+
+    let mut marked = vec![false; values.len()];
+    let mut cursor = tails.last().copied();
+    while let Some(i) = cursor {
+        marked[i] = true;
+        cursor = previous[i];
+    }
+    marked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(ids: &[&str]) -> Vec<String> {
+        ids.iter().map(|id| id.to_string()).collect()
+    }
+
+    fn at(node_id: &str, index: usize) -> ResultPosition {
+        ResultPosition {
+            node_id: node_id.to_string(),
+            index,
+        }
+    }
+
+    #[test]
+    fn test_diff_reports_added_removed_and_moved() {
+        let diff = diff_results(
+            "q",
+            &ids(&["a", "b", "c", "d", "e"]),
+            &ids(&["e", "a", "b", "x", "d"]),
+        );
+        assert_eq!(diff.added, vec![at("x", 3)]);
+        assert_eq!(diff.removed, ids(&["c"]));
+        // Only `e` jumped; a, b, d kept their relative order
+        assert_eq!(diff.moved, vec![at("e", 0)]);
+    }
+
+    #[test]
+    fn test_diff_of_unchanged_results_is_empty() {
+        let results = ids(&["a", "b", "c"]);
+        assert!(diff_results("q", &results, &results).is_empty());
+        // Shifting positions alone is not a move
+        let diff = diff_results("q", &results, &ids(&["b", "c"]));
+        assert_eq!(diff.removed, ids(&["a"]));
+        assert!(diff.moved.is_empty());
+    }
+
+    #[test]
+    fn test_longest_increasing() {
+        assert_eq!(
+            longest_increasing(&[4, 0, 1, 3, 2]),
+            vec![false, true, true, false, true]
+        );
+        assert!(longest_increasing(&[]).is_empty());
+    }
+}
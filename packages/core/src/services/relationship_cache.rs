@@ -8,11 +8,21 @@
 //! The cache maintains an index of `target_type → Vec<InboundRelationship>` which allows O(1)
 //! lookup of all relationships pointing to a given node type.
 //!
-//! # Cache Invalidation
+//! # Lifecycle
 //!
-//! The cache uses a hybrid invalidation strategy:
-//! - **Time-based**: Cache is considered stale after 60 seconds
-//! - **Event-driven**: Schema change flag triggers immediate refresh on next access
+//! - **Warm-up**: [`InboundRelationshipCache::warm_up`] loads the index at startup and marks
+//!   the node types of the most recently modified roots as most recently used, so they
+//!   survive eviction.
+//! - **Eviction**: at most `max_target_types` target types are kept (default
+//!   [`DEFAULT_MAX_TARGET_TYPES`]); the least recently used go first. Untyped relationships
+//!   (the `"*"` entry) are never evicted. Once a type has been evicted the index is
+//!   incomplete, and a lookup of a type it lacks reloads the schemas (a miss).
+//! - **Invalidation**: [`InboundRelationshipCache::run`] follows the domain events NodeService
+//!   derives from store change notifications and marks the cache stale when a schema node is
+//!   created, updated or deleted. Entries also go stale after the TTL (60 seconds by default).
+//!
+//! [`InboundRelationshipCache::stats`] reports hits, misses and evictions, so the cache's
+//! effect can be checked in the diagnostics.
 //!
 //! # Performance
 //!
@@ -20,14 +30,24 @@
 //! - **Cache refresh**: ~50-200ms (loads all schemas once)
 //! - **Memory overhead**: ~10KB per 100 schemas
 //!
+use crate::db::events::DomainEvent;
 use crate::db::SurrealStore;
 use crate::models::schema::RelationshipCardinality;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
-use tokio::sync::RwLock;
+use tokio::sync::{broadcast, RwLock};
+
+/// Target types kept by default before the least recently used are evicted
+pub const DEFAULT_MAX_TARGET_TYPES: usize = 256;
+
+/// Roots whose node types are warmed up at startup
+pub const DEFAULT_HOT_ROOTS: usize = 50;
+
+/// Key of untyped relationships, which apply to every target type
+const WILDCARD: &str = "*";
 
 /// Metadata about a relationship pointing TO a node type
 ///
@@ -63,25 +83,58 @@ pub struct InboundRelationship {
     pub description: Option<String>,
 }
 
+/// Relationships pointing to one target type
+struct CacheEntry {
+    relationships: Vec<InboundRelationship>,
+    /// Tick of the last lookup, for LRU eviction
+    last_used: AtomicU64,
+}
+
+impl CacheEntry {
+    fn new(relationships: Vec<InboundRelationship>, last_used: u64) -> Self {
+        Self {
+            relationships,
+            last_used: AtomicU64::new(last_used),
+        }
+    }
+}
+
+#[derive(Default)]
+struct CacheState {
+    /// Map: target_type → relationships (may be empty for a type looked up on a miss)
+    entries: HashMap<String, CacheEntry>,
+    /// No type has been evicted since the last refresh, so a missing type has no
+    /// inbound relationships
+    complete: bool,
+    last_refresh: Option<Instant>,
+}
+
 /// Cache for fast inbound relationship discovery
 ///
 /// Maintains an index of relationships by target type, enabling O(1) lookup
 /// of "what points to this type" without scanning all schemas.
 pub struct InboundRelationshipCache {
-    /// Map: target_type → Vec<InboundRelationship>
-    cache: Arc<RwLock<HashMap<String, Vec<InboundRelationship>>>>,
-
-    /// Last cache refresh time
-    last_refresh: Arc<RwLock<Option<Instant>>>,
+    state: RwLock<CacheState>,
 
     /// Flag indicating schema has changed (set by event handlers)
-    schema_change_flag: Arc<AtomicBool>,
+    schema_change_flag: AtomicBool,
+
+    /// IDs of the schema nodes seen at the last refresh
+    schema_ids: std::sync::RwLock<HashSet<String>>,
 
     /// Database store for querying schemas
     store: Arc<SurrealStore>,
 
     /// Cache TTL (defaults to 60 seconds)
     cache_ttl: Duration,
+
+    max_target_types: usize,
+
+    /// Lookup counter, the clock of LRU eviction
+    tick: AtomicU64,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    evictions: AtomicU64,
 }
 
 impl InboundRelationshipCache {
@@ -89,26 +142,31 @@ impl InboundRelationshipCache {
     ///
     /// The cache starts empty and will be populated on first access.
     pub fn new(store: Arc<SurrealStore>) -> Self {
-        Self {
-            cache: Arc::new(RwLock::new(HashMap::new())),
-            last_refresh: Arc::new(RwLock::new(None)),
-            schema_change_flag: Arc::new(AtomicBool::new(false)),
-            store,
-            cache_ttl: Duration::from_secs(60),
-        }
+        Self::with_ttl(store, Duration::from_secs(60))
     }
 
     /// Create a cache with custom TTL (primarily for testing)
     pub fn with_ttl(store: Arc<SurrealStore>, ttl: Duration) -> Self {
         Self {
-            cache: Arc::new(RwLock::new(HashMap::new())),
-            last_refresh: Arc::new(RwLock::new(None)),
-            schema_change_flag: Arc::new(AtomicBool::new(false)),
+            state: RwLock::new(CacheState::default()),
+            schema_change_flag: AtomicBool::new(false),
+            schema_ids: std::sync::RwLock::new(HashSet::new()),
             store,
             cache_ttl: ttl,
+            max_target_types: DEFAULT_MAX_TARGET_TYPES,
+            tick: AtomicU64::new(0),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+            evictions: AtomicU64::new(0),
         }
     }
 
+    /// Keep at most `max_target_types` target types (at least one)
+    pub fn with_max_target_types(mut self, max_target_types: usize) -> Self {
+        self.max_target_types = max_target_types.max(1);
+        self
+    }
+
     /// Get all relationships pointing TO a specific node type
     ///
     /// Returns an empty vec if no relationships point to this type.
@@ -131,17 +189,18 @@ impl InboundRelationshipCache {
     ) -> anyhow::Result<Vec<InboundRelationship>> {
         // Check if cache needs refresh
         if self.needs_refresh().await {
-            self.refresh_cache().await?;
+            self.refresh_cache(&[]).await?;
         }
 
-        // Return from cache, merging typed and wildcard (untyped) entries
-        let cache = self.cache.read().await;
-        let mut result = cache.get(target_type).cloned().unwrap_or_default();
-        // Untyped relationships (target_type: None) are stored under "*" and apply to all types
-        if let Some(wildcards) = cache.get("*") {
-            result.extend(wildcards.iter().cloned());
+        if let Some(result) = self.lookup(target_type).await {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            return Ok(result);
         }
-        Ok(result)
+
+        // Evicted earlier: reload, keeping this type
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        self.refresh_cache(&[target_type.to_string()]).await?;
+        Ok(self.lookup(target_type).await.unwrap_or_default())
     }
 
     /// Get all inbound relationships for all types
@@ -156,12 +215,29 @@ impl InboundRelationshipCache {
     pub async fn get_all_inbound_relationships(
         &self,
     ) -> anyhow::Result<HashMap<String, Vec<InboundRelationship>>> {
-        if self.needs_refresh().await {
-            self.refresh_cache().await?;
+        if self.needs_refresh().await || !self.state.read().await.complete {
+            // The cache may not hold every type afterwards, so answer from the load
+            let (index, schema_ids) = self.load_index().await?;
+            self.install(index.clone(), schema_ids, &[]).await;
+            return Ok(index);
         }
 
-        let cache = self.cache.read().await;
-        Ok(cache.clone())
+        let state = self.state.read().await;
+        Ok(state
+            .entries
+            .iter()
+            .map(|(target_type, entry)| (target_type.clone(), entry.relationships.clone()))
+            .collect())
+    }
+
+    /// Load the index and mark the node types of the `hot_roots` most
+    /// recently modified roots as most recently used
+    ///
+    /// Returns the number of target types warmed up.
+    pub async fn warm_up(&self, hot_roots: usize) -> anyhow::Result<usize> {
+        let hot_types = self.store.get_recent_root_types(hot_roots).await?;
+        self.refresh_cache(&hot_types).await?;
+        Ok(hot_types.len())
     }
 
     /// Signal that a schema has changed, triggering refresh on next access
@@ -174,12 +250,52 @@ impl InboundRelationshipCache {
         self.schema_change_flag.store(true, Ordering::Release);
     }
 
+    /// Invalidate the cache if `event` touches a schema node
+    pub fn apply(&self, event: &DomainEvent) {
+        let schema_id = match event {
+            DomainEvent::NodeCreated { node_type, .. } => {
+                if node_type == "schema" {
+                    self.invalidate();
+                }
+                return;
+            }
+            DomainEvent::NodeUpdated { node_id, .. } => node_id,
+            DomainEvent::NodeDeleted { id, .. } => id,
+            _ => return,
+        };
+        let known = self
+            .schema_ids
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .contains(schema_id);
+        if known {
+            self.invalidate();
+        }
+    }
+
+    /// Follow domain events until the channel closes, invalidating on schema changes
+    ///
+    /// Callers typically spawn this with `NodeService::subscribe_to_events()`
+    /// and cancel it on shutdown.
+    pub async fn run(&self, mut events: broadcast::Receiver<DomainEvent>) {
+        loop {
+            match events.recv().await {
+                Ok(event) => self.apply(&event),
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    tracing::debug!("Relationship cache lagged {} events, invalidating", skipped);
+                    self.invalidate();
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    }
+
     /// Force immediate cache refresh
     ///
     /// Useful for testing or when you know schemas have changed and need
     /// immediate consistency.
     pub async fn force_refresh(&self) -> anyhow::Result<()> {
-        self.refresh_cache().await
+        self.refresh_cache(&[]).await
     }
 
     /// Check if cache needs refresh
@@ -190,23 +306,51 @@ impl InboundRelationshipCache {
         }
 
         // Check time-based staleness
-        let last = self.last_refresh.read().await;
-        match *last {
+        match self.state.read().await.last_refresh {
             None => true, // Never refreshed
             Some(instant) => instant.elapsed() > self.cache_ttl,
         }
     }
 
-    /// Refresh the cache by querying all schemas
-    async fn refresh_cache(&self) -> anyhow::Result<()> {
+    /// Relationships for `target_type`, or `None` if it was evicted
+    async fn lookup(&self, target_type: &str) -> Option<Vec<InboundRelationship>> {
+        let state = self.state.read().await;
+        let tick = self.tick.fetch_add(1, Ordering::Relaxed) + 1;
+        let mut result = match state.entries.get(target_type) {
+            Some(entry) => {
+                entry.last_used.store(tick, Ordering::Relaxed);
+                entry.relationships.clone()
+            }
+            None if state.complete => Vec::new(),
+            None => return None,
+        };
+        // Untyped relationships (target_type: None) are stored under "*" and apply to all types
+        if let Some(wildcards) = state.entries.get(WILDCARD) {
+            result.extend(wildcards.relationships.iter().cloned());
+        }
+        Some(result)
+    }
+
+    /// Refresh the cache by querying all schemas, marking `used` as just used
+    async fn refresh_cache(&self, used: &[String]) -> anyhow::Result<()> {
+        let (index, schema_ids) = self.load_index().await?;
+        self.install(index, schema_ids, used).await;
+        Ok(())
+    }
+
+    /// Build the index of inbound relationships from all schemas
+    async fn load_index(
+        &self,
+    ) -> anyhow::Result<(HashMap<String, Vec<InboundRelationship>>, HashSet<String>)> {
         // Query all schemas
         let schemas = self.store.get_all_schemas().await?;
 
-        // Build index of inbound relationships
         let mut index: HashMap<String, Vec<InboundRelationship>> = HashMap::new();
+        let mut schema_ids = HashSet::new();
 
         for schema in schemas {
             let source_type = &schema.id;
+            schema_ids.insert(source_type.clone());
 
             for relationship in &schema.relationships {
                 let inbound = InboundRelationship {
@@ -224,37 +368,82 @@ impl InboundRelationshipCache {
                 let key = relationship
                     .target_type
                     .clone()
-                    .unwrap_or_else(|| "*".to_string());
+                    .unwrap_or_else(|| WILDCARD.to_string());
                 index.entry(key).or_default().push(inbound);
             }
         }
+        Ok((index, schema_ids))
+    }
 
-        // Atomic swap
-        {
-            let mut cache = self.cache.write().await;
-            *cache = index;
+    /// Swap in a freshly loaded index, keeping when each type was last used,
+    /// and evict down to the size limit
+    async fn install(
+        &self,
+        index: HashMap<String, Vec<InboundRelationship>>,
+        schema_ids: HashSet<String>,
+        used: &[String],
+    ) {
+        let mut state = self.state.write().await;
+        let mut entries: HashMap<String, CacheEntry> = index
+            .into_iter()
+            .map(|(target_type, relationships)| {
+                let last_used = state
+                    .entries
+                    .get(&target_type)
+                    .map_or(0, |entry| entry.last_used.load(Ordering::Relaxed));
+                (target_type, CacheEntry::new(relationships, last_used))
+            })
+            .collect();
+        for target_type in used {
+            let tick = self.tick.fetch_add(1, Ordering::Relaxed) + 1;
+            entries
+                .entry(target_type.clone())
+                .or_insert_with(|| CacheEntry::new(Vec::new(), 0))
+                .last_used
+                .store(tick, Ordering::Relaxed);
         }
 
-        // Update refresh time and clear change flag
-        {
-            let mut last = self.last_refresh.write().await;
-            *last = Some(Instant::now());
+        let mut complete = true;
+        while entries.len() > self.max_target_types {
+            let Some(coldest) = entries
+                .iter()
+                .filter(|(target_type, _)| *target_type != WILDCARD)
+                .min_by_key(|(_, entry)| entry.last_used.load(Ordering::Relaxed))
+                .map(|(target_type, _)| target_type.clone())
+            else {
+                break;
+            };
+            entries.remove(&coldest);
+            self.evictions.fetch_add(1, Ordering::Relaxed);
+            complete = false;
         }
-        self.schema_change_flag.store(false, Ordering::Release);
 
-        Ok(())
+        state.entries = entries;
+        state.complete = complete;
+        // Update refresh time and clear change flag
+        state.last_refresh = Some(Instant::now());
+        *self.schema_ids.write().unwrap_or_else(|e| e.into_inner()) = schema_ids;
+        self.schema_change_flag.store(false, Ordering::Release);
     }
 
     /// Get cache statistics (for debugging/monitoring)
     pub async fn stats(&self) -> CacheStats {
-        let cache = self.cache.read().await;
-        let last = self.last_refresh.read().await;
+        let is_stale = self.needs_refresh().await;
+        let state = self.state.read().await;
 
         CacheStats {
-            target_types: cache.len(),
-            total_relationships: cache.values().map(|v| v.len()).sum(),
-            last_refresh: *last,
-            is_stale: self.needs_refresh().await,
+            target_types: state
+                .entries
+                .values()
+                .filter(|entry| !entry.relationships.is_empty())
+                .count(),
+            total_relationships: state.entries.values().map(|e| e.relationships.len()).sum(),
+            last_refresh: state.last_refresh,
+            is_stale,
+            max_target_types: self.max_target_types,
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            evictions: self.evictions.load(Ordering::Relaxed),
         }
     }
 }
@@ -270,6 +459,14 @@ pub struct CacheStats {
     pub last_refresh: Option<Instant>,
     /// Whether the cache is currently considered stale
     pub is_stale: bool,
+    /// Target types kept before eviction starts
+    pub max_target_types: usize,
+    /// Lookups answered from the cache
+    pub hits: u64,
+    /// Lookups of an evicted type, which reloaded the schemas
+    pub misses: u64,
+    /// Target types evicted since the cache was created
+    pub evictions: u64,
 }
 
 #[cfg(test)]
@@ -0,0 +1,189 @@
+//! Reminder Service - Due Task Notifications
+//!
+//! Periodically scans task nodes and reports each open task whose `due_date`
+//! falls within the lead time. A task is reported once per due date, so
+//! rescheduling it arms the reminder again. Delivery (OS notification, UI
+//! toast) is up to the caller's callback.
+//!
+//! `due_date` accepts RFC 3339 timestamps and plain `YYYY-MM-DD` dates, which
+//! count as due at local midnight.
+
+use super::error::NodeServiceError;
+use super::node_service::NodeService;
+use crate::models::{Node, NodeFilter, TaskNode, TaskStatus};
+use chrono::{DateTime, Local, NaiveDate, TimeZone, Utc};
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// How often [`ReminderService::run`] scans for due tasks
+pub const DEFAULT_REMINDER_INTERVAL: Duration = Duration::from_secs(60);
+
+/// A task that has come due
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reminder {
+    pub node_id: String,
+    /// First line of the task's content
+    pub title: String,
+    pub due_at: DateTime<Utc>,
+}
+
+/// Scans tasks for due dates and reports each one once
+pub struct ReminderService<C = surrealdb::engine::local::Db>
+where
+    C: surrealdb::Connection,
+{
+    node_service: Arc<NodeService<C>>,
+    lead_time: chrono::Duration,
+    /// `(node_id, due_at)` pairs already reported
+    reported: HashSet<(String, DateTime<Utc>)>,
+}
+
+impl<C> ReminderService<C>
+where
+    C: surrealdb::Connection,
+{
+    pub fn new(node_service: Arc<NodeService<C>>) -> Self {
+        Self {
+            node_service,
+            lead_time: chrono::Duration::zero(),
+            reported: HashSet::new(),
+        }
+    }
+
+    /// Report tasks this long before they are due (default: when due)
+    pub fn with_lead_time(mut self, lead_time: chrono::Duration) -> Self {
+        self.lead_time = lead_time;
+        self
+    }
+
+    /// Tasks that came due since the last check
+    pub async fn check(&mut self, now: DateTime<Utc>) -> Result<Vec<Reminder>, NodeServiceError> {
+        let tasks = self
+            .node_service
+            .query_nodes(NodeFilter::new().with_node_type("task".to_string()))
+            .await?;
+        Ok(collect_due(tasks, now + self.lead_time, &mut self.reported))
+    }
+
+    /// Check every `interval`, passing each reminder to `on_due`
+    ///
+    /// Never returns; callers typically spawn this and cancel it on shutdown.
+    pub async fn run<F>(mut self, interval: Duration, on_due: F)
+    where
+        F: Fn(Reminder),
+    {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            match self.check(Utc::now()).await {
+                Ok(reminders) => reminders.into_iter().for_each(&on_due),
+                Err(e) => tracing::warn!("Reminder check failed: {}", e),
+            }
+        }
+    }
+}
+
+/// Open tasks due at or before `cutoff` that are not yet in `reported`
+fn collect_due(
+    tasks: Vec<Node>,
+    cutoff: DateTime<Utc>,
+    reported: &mut HashSet<(String, DateTime<Utc>)>,
+) -> Vec<Reminder> {
+    let mut due = Vec::new();
+    for node in tasks {
+        let Some(due_at) = due_date(&node) else {
+            continue;
+        };
+        let title = node.content.lines().next().unwrap_or_default().to_string();
+        let Ok(task) = TaskNode::from_node(node) else {
+            continue;
+        };
+        if matches!(task.status, TaskStatus::Done | TaskStatus::Cancelled) || due_at > cutoff {
+            continue;
+        }
+        if reported.insert((task.id.clone(), due_at)) {
+            due.push(Reminder {
+                node_id: task.id,
+                title,
+                due_at,
+            });
+        }
+    }
+    due.sort_by_key(|reminder| reminder.due_at);
+    due
+}
+
+/// `due_date` from either property format, as RFC 3339 or `YYYY-MM-DD`
+fn due_date(node: &Node) -> Option<DateTime<Utc>> {
+    let props = &node.properties;
+    let value = props
+        .get("task")
+        .and_then(|task| task.get("due_date"))
+        .or_else(|| props.get("due_date"))?
+        .as_str()?;
+
+    if let Ok(at) = DateTime::parse_from_rfc3339(value) {
+        return Some(at.with_timezone(&Utc));
+    }
+    let midnight = NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .ok()?
+        .and_hms_opt(0, 0, 0)?;
+    Local
+        .from_local_datetime(&midnight)
+        .earliest()
+        .map(|at| at.with_timezone(&Utc))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn task(id: &str, status: &str, due_date: &str) -> Node {
+        Node::new_with_id(
+            id.to_string(),
+            "task".to_string(),
+            format!("{}\nnotes", id),
+            json!({ "task": { "status": status, "due_date": due_date } }),
+        )
+    }
+
+    #[test]
+    fn test_collect_due_reports_open_tasks_once() {
+        let now = DateTime::parse_from_rfc3339("2025-01-15T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let tasks = || {
+            vec![
+                task("later", "open", "2025-01-15T13:00:00Z"),
+                task("due", "in_progress", "2025-01-15T11:00:00Z"),
+                task("finished", "done", "2025-01-15T10:00:00Z"),
+                task("overdue", "open", "2025-01-14T09:00:00+01:00"),
+            ]
+        };
+        let mut reported = HashSet::new();
+
+        let due = collect_due(tasks(), now, &mut reported);
+        let ids: Vec<_> = due.iter().map(|r| r.node_id.as_str()).collect();
+        assert_eq!(ids, vec!["overdue", "due"]);
+        assert_eq!(due[1].title, "due");
+
+        assert!(collect_due(tasks(), now, &mut reported).is_empty());
+
+        // Rescheduling re-arms the reminder
+        let moved = vec![task("due", "open", "2025-01-15T11:30:00Z")];
+        assert_eq!(collect_due(moved, now, &mut reported).len(), 1);
+    }
+
+    #[test]
+    fn test_due_date_accepts_plain_dates() {
+        let node = task("t", "open", "2025-01-15");
+        let expected = Local
+            .with_ymd_and_hms(2025, 1, 15, 0, 0, 0)
+            .unwrap()
+            .with_timezone(&Utc);
+        assert_eq!(due_date(&node), Some(expected));
+        assert_eq!(due_date(&task("t", "open", "soon")), None);
+    }
+}
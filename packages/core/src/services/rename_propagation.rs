@@ -0,0 +1,295 @@
+//! Rename Propagation - Keeping Mention Text in Step with Titles
+//!
+//! A mention is written as `[@Title](nodespace://id)`, so renaming the
+//! mentioned node leaves the old title in every note that links to it. This
+//! optional service follows domain events and, when the title of a mentioned
+//! node changes, rewrites the text of those links to the new title.
+//!
+//! Only links whose text is the old title (with or without the `@`) are
+//! changed; links the user gave their own text are left alone. A rename is
+//! announced once titles have been quiet for [`RENAME_DEBOUNCE`], so typing a
+//! title produces one rename, and propagated after a further quiet period
+//! unless skipped with [`RenamePropagationService::skip`]. All referring nodes
+//! of one rename are updated in a single transaction.
+//!
+//! Writes go through a NodeService scoped to [`RENAME_CLIENT_ID`] and events
+//! from that client are ignored.
+
+use super::error::NodeServiceError;
+use super::node_service::{rewrite_links, LinkRewrite, NodeService};
+use crate::db::events::DomainEvent;
+use crate::models::NodeUpdate;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+use tokio::sync::{broadcast, Mutex};
+
+/// Client ID used for link text writes (filtered out of event processing)
+pub const RENAME_CLIENT_ID: &str = "rename-hook";
+
+/// Quiet period before a rename is announced, and again before it is applied
+pub const RENAME_DEBOUNCE: Duration = Duration::from_secs(3);
+
+/// A title change whose links have not been updated yet
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingRename {
+    pub node_id: String,
+    pub old_title: String,
+    pub new_title: String,
+}
+
+/// Rewrites mention text in referring nodes when a mentioned node is renamed
+pub struct RenamePropagationService {
+    node_service: NodeService,
+    /// Node ID -> rename announced but not yet applied
+    pending: Mutex<HashMap<String, PendingRename>>,
+}
+
+impl RenamePropagationService {
+    pub fn new(node_service: &NodeService) -> Self {
+        Self {
+            node_service: node_service.with_client(RENAME_CLIENT_ID),
+            pending: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Renames announced but not yet propagated
+    pub async fn pending(&self) -> Vec<PendingRename> {
+        self.pending.lock().await.values().cloned().collect()
+    }
+
+    /// Leave the links to `node_id` as they are for its pending rename
+    ///
+    /// Returns `false` if no rename of the node is pending. A later rename
+    /// of the same node is propagated as usual.
+    pub async fn skip(&self, node_id: &str) -> bool {
+        self.pending.lock().await.remove(node_id).is_some()
+    }
+
+    /// Rewrite the links for `rename` in one transaction
+    ///
+    /// Returns the number of referring nodes updated.
+    pub async fn propagate(&self, rename: &PendingRename) -> Result<usize, NodeServiceError> {
+        let store = self.node_service.store();
+        let query_failed = |e: anyhow::Error| NodeServiceError::query_failed(e.to_string());
+        let referrers = store
+            .get_incoming_mentions(&rename.node_id)
+            .await
+            .map_err(query_failed)?;
+        let nodes = store
+            .get_nodes_by_ids(&referrers)
+            .await
+            .map_err(query_failed)?;
+
+        let rewrite = LinkRewrite::Retitle {
+            from: &rename.old_title,
+            to: &rename.new_title,
+        };
+        let updates: Vec<(String, NodeUpdate)> = nodes
+            .into_values()
+            .filter_map(|node| {
+                let content = rewrite_links(&node.content, &rename.node_id, &rewrite);
+                (content != node.content)
+                    .then(|| (node.id, NodeUpdate::new().with_content(content)))
+            })
+            .collect();
+        let updated = updates.len();
+        self.node_service.bulk_update(updates).await?;
+        Ok(updated)
+    }
+
+    /// Follow domain events until the channel closes
+    ///
+    /// `on_pending` is called when a rename is announced (or its new title
+    /// changes again), `on_propagated` with the number of nodes updated once
+    /// it is applied. Callers typically spawn this and cancel it on shutdown.
+    pub async fn run<P, A>(&self, on_pending: P, on_propagated: A)
+    where
+        P: Fn(&PendingRename),
+        A: Fn(&PendingRename, usize),
+    {
+        let mut rx = self.node_service.subscribe_to_events();
+        let mut titles = match self.node_service.store().get_mentioned_titles().await {
+            Ok(titles) => titles,
+            Err(e) => {
+                tracing::warn!("Loading mentioned titles failed: {}", e);
+                HashMap::new()
+            }
+        };
+
+        let mut changed: HashSet<String> = HashSet::new();
+        loop {
+            let waiting = !changed.is_empty() || !self.pending.lock().await.is_empty();
+            tokio::select! {
+                received = rx.recv() => match received {
+                    Ok(event) => collect(event, &titles, &mut changed),
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        tracing::debug!("Rename propagation lagged {} events, rechecking titles", skipped);
+                        changed.extend(titles.keys().cloned());
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                },
+                _ = tokio::time::sleep(RENAME_DEBOUNCE), if waiting => {
+                    let changed = std::mem::take(&mut changed);
+                    let announced = self.detect(changed, &mut titles, &on_pending).await;
+                    self.apply(&announced, &on_propagated).await;
+                }
+            }
+        }
+    }
+
+    /// Compare the titles of `node_ids` with the last known ones, announcing
+    /// renames; returns the IDs announced now
+    async fn detect<P>(
+        &self,
+        node_ids: HashSet<String>,
+        titles: &mut HashMap<String, String>,
+        on_pending: &P,
+    ) -> HashSet<String>
+    where
+        P: Fn(&PendingRename),
+    {
+        let mut announced = HashSet::new();
+        for node_id in node_ids {
+            let title = match self.node_service.get_node(&node_id).await {
+                Ok(node) => node
+                    .filter(|node| node.lifecycle_status != "deleted")
+                    .and_then(|node| node.title),
+                Err(e) => {
+                    tracing::debug!("Skipping title check of {}: {}", node_id, e);
+                    continue;
+                }
+            };
+            let Some(title) = title else {
+                titles.remove(&node_id);
+                self.pending.lock().await.remove(&node_id);
+                continue;
+            };
+            let Some(previous) = titles.insert(node_id.clone(), title.clone()) else {
+                // Newly mentioned: remember the title for later renames
+                continue;
+            };
+            if previous == title {
+                continue;
+            }
+
+            let mut pending = self.pending.lock().await;
+            let old_title = pending
+                .remove(&node_id)
+                .map_or(previous, |rename| rename.old_title);
+            if old_title == title || !is_link_text(&title) {
+                continue;
+            }
+            let rename = PendingRename {
+                node_id: node_id.clone(),
+                old_title,
+                new_title: title,
+            };
+            on_pending(&rename);
+            pending.insert(node_id.clone(), rename);
+            announced.insert(node_id);
+        }
+        announced
+    }
+
+    /// Propagate the pending renames not in `announced`
+    async fn apply<A>(&self, announced: &HashSet<String>, on_propagated: &A)
+    where
+        A: Fn(&PendingRename, usize),
+    {
+        let due: Vec<PendingRename> = {
+            let mut pending = self.pending.lock().await;
+            let ids: Vec<String> = pending
+                .keys()
+                .filter(|id| !announced.contains(*id))
+                .cloned()
+                .collect();
+            ids.iter().filter_map(|id| pending.remove(id)).collect()
+        };
+        for rename in due {
+            match self.propagate(&rename).await {
+                Ok(updated) => on_propagated(&rename, updated),
+                Err(e) => tracing::warn!("Failed to propagate rename of {}: {}", rename.node_id, e),
+            }
+        }
+    }
+}
+
+/// Record the nodes whose title may have changed, or that became mentioned
+fn collect(event: DomainEvent, titles: &HashMap<String, String>, changed: &mut HashSet<String>) {
+    match event {
+        DomainEvent::NodeUpdated {
+            node_id,
+            source_client_id,
+            ..
+        } if source_client_id.as_deref() != Some(RENAME_CLIENT_ID)
+            && titles.contains_key(&node_id) =>
+        {
+            changed.insert(node_id);
+        }
+        DomainEvent::NodeDeleted { id, .. } if titles.contains_key(&id) => {
+            changed.insert(id);
+        }
+        DomainEvent::RelationshipCreated { relationship, .. }
+            if relationship.relationship_type == "mentions"
+                && !titles.contains_key(&relationship.to_id) =>
+        {
+            changed.insert(relationship.to_id);
+        }
+        _ => {}
+    }
+}
+
+/// Whether `title` can be the text of a markdown link as is
+fn is_link_text(title: &str) -> bool {
+    !title.trim().is_empty() && !title.contains(['[', ']', '\n'])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::events::RelationshipEvent;
+
+    #[test]
+    fn test_collect_follows_mentioned_nodes() {
+        let titles = HashMap::from([("plan".to_string(), "Plan".to_string())]);
+        let mut changed = HashSet::new();
+        let updated = |node_id: &str, client: &str| DomainEvent::NodeUpdated {
+            node_id: node_id.to_string(),
+            source_client_id: Some(client.to_string()),
+            correlation_id: None,
+        };
+        collect(updated("plan", RENAME_CLIENT_ID), &titles, &mut changed);
+        collect(updated("other", "ui"), &titles, &mut changed);
+        assert!(changed.is_empty());
+
+        collect(updated("plan", "ui"), &titles, &mut changed);
+        collect(
+            DomainEvent::RelationshipCreated {
+                relationship: RelationshipEvent {
+                    id: "edge".to_string(),
+                    from_id: "notes".to_string(),
+                    to_id: "roadmap".to_string(),
+                    relationship_type: "mentions".to_string(),
+                    properties: serde_json::json!({}),
+                },
+                source_client_id: None,
+                correlation_id: None,
+            },
+            &titles,
+            &mut changed,
+        );
+        let mut ids: Vec<_> = changed.into_iter().collect();
+        ids.sort();
+        assert_eq!(ids, vec!["plan", "roadmap"]);
+    }
+
+    #[test]
+    fn test_is_link_text() {
+        assert!(is_link_text("Q3 Roadmap (draft)"));
+        assert!(!is_link_text("  "));
+        assert!(!is_link_text("Notes [old]"));
+        assert!(!is_link_text("Two\nlines"));
+    }
+}
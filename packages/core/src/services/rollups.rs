@@ -0,0 +1,330 @@
+//! Rollup Cache - Incrementally Maintained Rollup Values
+//!
+//! `NodeService::get_node` fills [`Node::rollups`] for nodes that declare
+//! [`Rollup`]s. The [`RollupCache`] keeps, per parent, what each child
+//! contributes to each rollup, so after a child changes only that child is
+//! fetched again before the values are re-aggregated.
+//!
+//! # Maintenance
+//!
+//! NodeService passes every domain event it emits through
+//! [`RollupCache::apply`]:
+//!
+//! - an update of a cached child marks just that child for refetching
+//! - a deleted child stops contributing
+//! - an update of the parent itself (its declarations may have changed) and
+//!   hierarchy changes under it drop the parent's entry
+//! - node creation drops every entry, since the new node's parent is only
+//!   known once it is placed, and bulk imports place nodes without events
+//!
+//! A full computation that raced an event is not cached
+//! ([`RollupCache::generation`]), as with project rollups.
+
+use crate::db::events::DomainEvent;
+use crate::models::{Node, Rollup};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+/// What has to be fetched before a parent's rollup values are current
+#[derive(Debug, Clone, PartialEq)]
+pub enum RollupRefresh {
+    /// Nothing; the values are current
+    Current(BTreeMap<String, Value>),
+    /// These children changed since their contributions were recorded
+    Children(Vec<String>),
+    /// All children, as nothing usable is cached
+    All,
+}
+
+struct CachedParent {
+    rollups: Vec<Rollup>,
+    /// Child ID -> contribution to each rollup, in declaration order
+    contributions: HashMap<String, Vec<Option<Value>>>,
+    /// Children to fetch again
+    stale: HashSet<String>,
+}
+
+impl CachedParent {
+    fn record(&mut self, child: &Node) {
+        let contribution = self
+            .rollups
+            .iter()
+            .map(|rollup| rollup.contribution(child))
+            .collect();
+        self.contributions.insert(child.id.clone(), contribution);
+    }
+
+    fn values(&self) -> BTreeMap<String, Value> {
+        self.rollups
+            .iter()
+            .enumerate()
+            .map(|(index, rollup)| {
+                let contributions = self
+                    .contributions
+                    .values()
+                    .filter_map(|contribution| contribution[index].as_ref());
+                (rollup.name.clone(), rollup.aggregate(contributions))
+            })
+            .collect()
+    }
+}
+
+#[derive(Default)]
+struct CacheState {
+    entries: HashMap<String, CachedParent>,
+    /// Child ID -> parent ID, for cached parents
+    parents: HashMap<String, String>,
+    /// Bumped whenever an entry is dropped
+    generation: u64,
+}
+
+impl CacheState {
+    fn drop_entry(&mut self, parent_id: &str) {
+        if let Some(entry) = self.entries.remove(parent_id) {
+            for child_id in entry.contributions.keys() {
+                self.parents.remove(child_id);
+            }
+        }
+        self.generation += 1;
+    }
+}
+
+/// Per-child rollup contributions by parent ID
+#[derive(Default)]
+pub struct RollupCache {
+    state: RwLock<CacheState>,
+}
+
+impl RollupCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Decide what `parent_id` needs fetched for its `rollups`
+    ///
+    /// Children returned as [`RollupRefresh::Children`] are no longer marked
+    /// stale; pass them to [`RollupCache::update_children`] once fetched.
+    pub fn refresh(&self, parent_id: &str, rollups: &[Rollup]) -> RollupRefresh {
+        let mut state = self.write();
+        let Some(entry) = state.entries.get_mut(parent_id) else {
+            return RollupRefresh::All;
+        };
+        if entry.rollups != rollups {
+            state.drop_entry(parent_id);
+            return RollupRefresh::All;
+        }
+        if entry.stale.is_empty() {
+            return RollupRefresh::Current(entry.values());
+        }
+        RollupRefresh::Children(entry.stale.drain().collect())
+    }
+
+    /// Invalidation counter; read it before fetching all children
+    pub fn generation(&self) -> u64 {
+        self.read().generation
+    }
+
+    /// Record the contributions of all `children` of `parent_id` and return
+    /// the rollup values
+    ///
+    /// Not cached when an entry was dropped after `generation` was read.
+    pub fn replace(
+        &self,
+        parent_id: &str,
+        rollups: Vec<Rollup>,
+        children: &[Node],
+        generation: u64,
+    ) -> BTreeMap<String, Value> {
+        let mut entry = CachedParent {
+            rollups,
+            contributions: HashMap::new(),
+            stale: HashSet::new(),
+        };
+        for child in children {
+            entry.record(child);
+        }
+        let values = entry.values();
+
+        let mut state = self.write();
+        if state.generation == generation {
+            for child_id in entry.contributions.keys() {
+                state
+                    .parents
+                    .insert(child_id.clone(), parent_id.to_string());
+            }
+            state.entries.insert(parent_id.to_string(), entry);
+        }
+        values
+    }
+
+    /// Record the contributions of the `requested` children of `parent_id`
+    /// found in `fetched`; requested children not found (or deleted) stop
+    /// contributing. Returns the rollup values, or `None` if the entry was
+    /// dropped meanwhile.
+    pub fn update_children(
+        &self,
+        parent_id: &str,
+        requested: &[String],
+        fetched: &HashMap<String, Node>,
+    ) -> Option<BTreeMap<String, Value>> {
+        let mut state = self.write();
+        let entry = state.entries.get_mut(parent_id)?;
+        let mut gone = Vec::new();
+        for child_id in requested {
+            match fetched
+                .get(child_id)
+                .filter(|child| child.lifecycle_status != "deleted")
+            {
+                Some(child) => entry.record(child),
+                None => {
+                    entry.contributions.remove(child_id);
+                    gone.push(child_id);
+                }
+            }
+        }
+        let values = entry.values();
+        for child_id in gone {
+            state.parents.remove(child_id);
+        }
+        Some(values)
+    }
+
+    /// Bring the cache in line with `event`
+    pub fn apply(&self, event: &DomainEvent) {
+        let mut state = self.write();
+        match event {
+            DomainEvent::NodeCreated { .. } => {
+                if !state.entries.is_empty() {
+                    state.entries.clear();
+                    state.parents.clear();
+                    state.generation += 1;
+                }
+            }
+            DomainEvent::NodeUpdated { node_id, .. } => {
+                if state.entries.contains_key(node_id) {
+                    state.drop_entry(node_id);
+                }
+                if let Some(parent_id) = state.parents.get(node_id).cloned() {
+                    if let Some(entry) = state.entries.get_mut(&parent_id) {
+                        entry.stale.insert(node_id.clone());
+                    }
+                }
+            }
+            DomainEvent::NodeDeleted { id, .. } => {
+                if state.entries.contains_key(id) {
+                    state.drop_entry(id);
+                }
+                if let Some(parent_id) = state.parents.remove(id) {
+                    if let Some(entry) = state.entries.get_mut(&parent_id) {
+                        entry.contributions.remove(id);
+                        entry.stale.remove(id);
+                    }
+                }
+            }
+            DomainEvent::RelationshipCreated { relationship, .. }
+            | DomainEvent::RelationshipUpdated { relationship, .. } => {
+                if relationship.relationship_type == "has_child" {
+                    drop_hierarchy(&mut state, &relationship.from_id, &relationship.to_id);
+                }
+            }
+            DomainEvent::RelationshipDeleted {
+                from_id,
+                to_id,
+                relationship_type,
+                ..
+            } => {
+                if relationship_type == "has_child" {
+                    drop_hierarchy(&mut state, from_id, to_id);
+                }
+            }
+        }
+    }
+
+    /// Drop every entry
+    pub fn clear(&self) {
+        let mut state = self.write();
+        state.entries.clear();
+        state.parents.clear();
+        state.generation += 1;
+    }
+
+    fn read(&self) -> RwLockReadGuard<'_, CacheState> {
+        self.state.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, CacheState> {
+        self.state.write().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Drop the entries whose children a has_child edge change may have moved
+fn drop_hierarchy(state: &mut CacheState, parent_id: &str, child_id: &str) {
+    let previous = state.parents.get(child_id).cloned();
+    for id in std::iter::once(parent_id.to_string()).chain(previous) {
+        if state.entries.contains_key(&id) {
+            state.drop_entry(&id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn estimated(estimate: i64) -> Node {
+        Node::new(
+            "task".to_string(),
+            "Task".to_string(),
+            json!({ "task": { "status": "open", "custom:estimate": estimate } }),
+        )
+    }
+
+    #[test]
+    fn test_changed_child_is_refetched_alone() {
+        let rollups: Vec<Rollup> = serde_json::from_value(json!([
+            { "name": "estimate", "function": "sum", "property": "custom:estimate" }
+        ]))
+        .unwrap();
+        let cache = RollupCache::new();
+        let children = [estimated(3), estimated(5)];
+        let generation = cache.generation();
+        let values = cache.replace("p", rollups.clone(), &children, generation);
+        assert_eq!(values["estimate"], json!(8));
+
+        cache.apply(&DomainEvent::NodeUpdated {
+            node_id: children[0].id.clone(),
+            source_client_id: None,
+            correlation_id: None,
+        });
+        let RollupRefresh::Children(stale) = cache.refresh("p", &rollups) else {
+            panic!("expected the updated child to be refetched");
+        };
+        assert_eq!(stale, vec![children[0].id.clone()]);
+
+        let mut updated = estimated(10);
+        updated.id = children[0].id.clone();
+        let fetched = HashMap::from([(updated.id.clone(), updated)]);
+        let values = cache.update_children("p", &stale, &fetched).unwrap();
+        assert_eq!(values["estimate"], json!(15));
+
+        cache.apply(&DomainEvent::NodeDeleted {
+            id: children[1].id.clone(),
+            source_client_id: None,
+            correlation_id: None,
+        });
+        assert_eq!(
+            cache.refresh("p", &rollups),
+            RollupRefresh::Current(BTreeMap::from([("estimate".to_string(), json!(10))]))
+        );
+
+        // The parent changing drops its entry
+        cache.apply(&DomainEvent::NodeUpdated {
+            node_id: "p".to_string(),
+            source_client_id: None,
+            correlation_id: None,
+        });
+        assert_eq!(cache.refresh("p", &rollups), RollupRefresh::All);
+    }
+}
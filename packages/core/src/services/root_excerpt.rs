@@ -0,0 +1,269 @@
+//! Root Excerpt Service - Keeps Document Excerpts Current
+//!
+//! Follows domain events and rebuilds the excerpt (see
+//! [`build_excerpt`](crate::models::build_excerpt)) of every root whose
+//! children were created, edited, moved or removed. Bursts are batched: once
+//! no event has arrived for [`EXCERPT_DEBOUNCE`], each affected root is
+//! refreshed once.
+//!
+//! With a [`SummarizationService`] that has a generation model attached, a
+//! root whose children do not fit an extractive excerpt gets a generated
+//! summary of them instead. Generation is greedy, so unchanged children give
+//! an unchanged summary and no write.
+//!
+//! Writes go through a NodeService scoped to [`EXCERPT_CLIENT_ID`] and events
+//! from that client are ignored, so storing an excerpt does not trigger
+//! another refresh.
+
+use super::error::NodeServiceError;
+use super::node_service::NodeService;
+use crate::db::events::DomainEvent;
+use crate::models::{build_excerpt, summary_source, Node, EXCERPT_KEY, EXCERPT_MAX_CHARS};
+use crate::utils::truncate_chars;
+use nodespace_nlp_engine::SummarizationService;
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::broadcast;
+
+/// Client ID used for excerpt writes (filtered out of event processing)
+pub const EXCERPT_CLIENT_ID: &str = "excerpt-hook";
+
+/// Quiet period before affected roots are refreshed
+pub const EXCERPT_DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Token budget for a generated excerpt (roughly [`EXCERPT_MAX_CHARS`])
+const SUMMARY_TOKENS: usize = 48;
+
+/// Maintains the excerpt stored on each root
+pub struct RootExcerptService {
+    node_service: NodeService,
+    summarizer: Option<Arc<SummarizationService>>,
+}
+
+impl RootExcerptService {
+    pub fn new(node_service: &NodeService) -> Self {
+        Self {
+            node_service: node_service.with_client(EXCERPT_CLIENT_ID),
+            summarizer: None,
+        }
+    }
+
+    /// Generate excerpts of longer roots with `summarizer`
+    ///
+    /// Only used while the summarizer has a generation model loaded.
+    pub fn with_summarizer(mut self, summarizer: Arc<SummarizationService>) -> Self {
+        self.summarizer = Some(summarizer);
+        self
+    }
+
+    /// Build excerpts for roots that have none yet, returning how many were written
+    ///
+    /// Covers roots created before excerpts existed and changes made while
+    /// the service was not running.
+    pub async fn backfill(&self) -> Result<usize, NodeServiceError> {
+        self.refresh_roots(true).await
+    }
+
+    /// Backfill, then follow domain events until the channel closes
+    ///
+    /// Callers typically spawn this and cancel it on shutdown.
+    pub async fn run(&self) {
+        let mut rx = self.node_service.subscribe_to_events();
+        match self.backfill().await {
+            Ok(written) => tracing::info!("Backfilled {} root excerpts", written),
+            Err(e) => tracing::warn!("Root excerpt backfill failed: {}", e),
+        }
+
+        let mut dirty: HashSet<String> = HashSet::new();
+        let mut lagged = false;
+        loop {
+            let waiting = lagged || !dirty.is_empty();
+            tokio::select! {
+                received = rx.recv() => match received {
+                    Ok(event) => collect(event, &mut dirty),
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        tracing::debug!("Root excerpts lagged {} events, refreshing all", skipped);
+                        lagged = true;
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                },
+                _ = tokio::time::sleep(EXCERPT_DEBOUNCE), if waiting => {
+                    let dirty = std::mem::take(&mut dirty);
+                    if std::mem::take(&mut lagged) {
+                        if let Err(e) = self.refresh_roots(false).await {
+                            tracing::warn!("Refreshing root excerpts failed: {}", e);
+                        }
+                    } else {
+                        self.refresh(dirty).await;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Refresh the roots containing `node_ids`
+    async fn refresh(&self, node_ids: HashSet<String>) {
+        let mut roots = HashSet::new();
+        for id in node_ids {
+            match self.node_service.get_root_id(&id).await {
+                Ok(root_id) => {
+                    roots.insert(root_id);
+                }
+                Err(e) => tracing::debug!("Skipping excerpt update for {}: {}", id, e),
+            }
+        }
+        for root_id in roots {
+            if let Err(e) = self.refresh_root(&root_id).await {
+                tracing::warn!("Failed to refresh excerpt of {}: {}", root_id, e);
+            }
+        }
+    }
+
+    /// Refresh every root, or only those without an excerpt
+    async fn refresh_roots(&self, missing_only: bool) -> Result<usize, NodeServiceError> {
+        let roots = self
+            .node_service
+            .store()
+            .get_children(None)
+            .await
+            .map_err(|e| NodeServiceError::query_failed(e.to_string()))?;
+
+        let mut written = 0;
+        for root in roots {
+            let before = root.properties.get(EXCERPT_KEY);
+            if missing_only && before.is_some() {
+                continue;
+            }
+            match self.refresh_root(&root.id).await {
+                Ok(refreshed) if refreshed.properties.get(EXCERPT_KEY) != before => written += 1,
+                Ok(_) => {}
+                Err(e) => tracing::warn!("Failed to refresh excerpt of {}: {}", root.id, e),
+            }
+        }
+        Ok(written)
+    }
+
+    /// Rebuild and store the excerpt of one root
+    async fn refresh_root(&self, root_id: &str) -> Result<Node, NodeServiceError> {
+        let children = self.node_service.get_children(root_id).await?;
+        let excerpt = self.excerpt(&children).await;
+        self.node_service
+            .set_root_excerpt(root_id, excerpt.as_deref())
+            .await
+    }
+
+    /// Excerpt for a root with `children`, generated when worthwhile
+    ///
+    /// Falls back to the extractive excerpt if generation fails.
+    async fn excerpt(&self, children: &[Node]) -> Option<String> {
+        let extractive = build_excerpt(children)?;
+        let Some(summarizer) = self.summarizer.as_ref().filter(|s| s.has_model()) else {
+            return Some(extractive);
+        };
+        let source = summary_source(children)?;
+        if source.chars().count() <= EXCERPT_MAX_CHARS {
+            return Some(extractive);
+        }
+
+        let summarizer = Arc::clone(summarizer);
+        let generated =
+            tokio::task::spawn_blocking(move || summarizer.summarize(&source, SUMMARY_TOKENS))
+                .await;
+        match generated {
+            Ok(Ok(summary)) if !summary.trim().is_empty() => {
+                let summary = summary.split_whitespace().collect::<Vec<_>>().join(" ");
+                Some(truncate_chars(&summary, EXCERPT_MAX_CHARS))
+            }
+            Ok(Ok(_)) => Some(extractive),
+            Ok(Err(e)) => {
+                tracing::warn!("Excerpt summarization failed: {}", e);
+                Some(extractive)
+            }
+            Err(e) => {
+                tracing::warn!("Excerpt summarization task panicked: {}", e);
+                Some(extractive)
+            }
+        }
+    }
+}
+
+/// Record the nodes whose root may need a new excerpt
+///
+/// A removed child is covered by the `has_child` deletion naming its parent.
+fn collect(event: DomainEvent, dirty: &mut HashSet<String>) {
+    match event {
+        DomainEvent::NodeCreated {
+            node_id,
+            source_client_id,
+            ..
+        }
+        | DomainEvent::NodeUpdated {
+            node_id,
+            source_client_id,
+            ..
+        } => {
+            if source_client_id.as_deref() != Some(EXCERPT_CLIENT_ID) {
+                dirty.insert(node_id);
+            }
+        }
+        DomainEvent::RelationshipCreated { relationship, .. }
+        | DomainEvent::RelationshipUpdated { relationship, .. } => {
+            if relationship.relationship_type == "has_child" {
+                dirty.insert(relationship.from_id);
+            }
+        }
+        DomainEvent::RelationshipDeleted {
+            from_id,
+            relationship_type,
+            ..
+        } => {
+            if relationship_type == "has_child" {
+                dirty.insert(from_id);
+            }
+        }
+        DomainEvent::NodeDeleted { .. } => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collect_ignores_own_writes() {
+        let mut dirty = HashSet::new();
+        collect(
+            DomainEvent::NodeUpdated {
+                node_id: "root".to_string(),
+                source_client_id: Some(EXCERPT_CLIENT_ID.to_string()),
+                correlation_id: None,
+            },
+            &mut dirty,
+        );
+        assert!(dirty.is_empty());
+
+        collect(
+            DomainEvent::NodeUpdated {
+                node_id: "child".to_string(),
+                source_client_id: Some("ui".to_string()),
+                correlation_id: None,
+            },
+            &mut dirty,
+        );
+        collect(
+            DomainEvent::RelationshipDeleted {
+                id: "edge".to_string(),
+                from_id: "parent".to_string(),
+                to_id: "gone".to_string(),
+                relationship_type: "has_child".to_string(),
+                source_client_id: None,
+                correlation_id: None,
+            },
+            &mut dirty,
+        );
+        let mut ids: Vec<_> = dirty.into_iter().collect();
+        ids.sort();
+        assert_eq!(ids, vec!["child", "parent"]);
+    }
+}
@@ -97,6 +97,10 @@ impl SchemaTableManager {
         for relationship in relationships {
             // Validate relationship name
             Self::validate_relationship_name(&relationship.name)?;
+            // Inverse labels stand in for relationship names
+            if let Some(label) = relationship.inverse_label() {
+                Self::validate_relationship_name(label)?;
+            }
 
             // Validate source type
             Self::validate_type_name(source_type)?;
@@ -266,13 +270,13 @@ impl SchemaTableManager {
     /// Relationship names must:
     /// - Start with a letter
     /// - Contain only alphanumeric characters, underscores, and hyphens
-    /// - Not be a reserved name (has_child, mentions, node, data)
+    /// - Not be a reserved name (has_child, mentions, embeds, node, data)
     fn validate_relationship_name(name: &str) -> Result<(), NodeServiceError> {
         // Check reserved names
-        const RESERVED_NAMES: [&str; 4] = ["has_child", "mentions", "node", "data"];
+        const RESERVED_NAMES: [&str; 5] = ["has_child", "mentions", "embeds", "node", "data"];
         if RESERVED_NAMES.contains(&name) {
             return Err(NodeServiceError::invalid_update(format!(
-                "Relationship name '{}' is reserved. Cannot use: has_child, mentions, node, data",
+                "Relationship name '{}' is reserved. Cannot use: has_child, mentions, embeds, node, data",
                 name
             )));
         }
@@ -327,6 +331,7 @@ mod tests {
     fn test_validate_relationship_name_reserved() {
         assert!(SchemaTableManager::validate_relationship_name("has_child").is_err());
         assert!(SchemaTableManager::validate_relationship_name("mentions").is_err());
+        assert!(SchemaTableManager::validate_relationship_name("embeds").is_err());
         assert!(SchemaTableManager::validate_relationship_name("node").is_err());
         assert!(SchemaTableManager::validate_relationship_name("data").is_err());
     }
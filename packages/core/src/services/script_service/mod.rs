@@ -0,0 +1,486 @@
+//! Script Service - Lifecycle Hook Scripts
+//!
+//! Runs user scripts (stored as `script` nodes) when nodes of a bound type are
+//! created or updated. Scripts execute in a sandboxed rhai engine (see [`sandbox`])
+//! and can only read nodes, set properties on the triggering node, and create
+//! children under it.
+//!
+//! ## Event Flow
+//!
+//! 1. `run_event_loop()` subscribes to NodeService domain events
+//! 2. On `NodeCreated` / `NodeUpdated`, matching enabled scripts run on the blocking pool
+//! 3. Recorded effects are applied through a NodeService scoped to [`SCRIPT_CLIENT_ID`]
+//!
+//! Events originating from [`SCRIPT_CLIENT_ID`] are ignored, so a script that sets
+//! a property on update does not retrigger itself.
+//!
+//! ## Script Cache
+//!
+//! Script nodes are loaded lazily and cached. Any create/update/delete of a script
+//! node invalidates the cache.
+
+pub mod sandbox;
+
+pub use sandbox::{
+    ScriptChild, ScriptEffects, ScriptError, ScriptLimits, ScriptNodeReader, ScriptSandbox,
+};
+
+use crate::db::events::DomainEvent;
+use crate::models::{NodeQuery, NodeUpdate, ScriptHook, ScriptNode};
+use crate::services::error::NodeServiceError;
+use crate::services::{CreateNodeParams, NodeService};
+use std::sync::Arc;
+use tokio::sync::{broadcast, RwLock};
+
+/// Client ID used for writes made by scripts (filtered out of hook processing)
+pub const SCRIPT_CLIENT_ID: &str = "script-runtime";
+
+/// Upper bound on script nodes loaded into the cache
+const MAX_SCRIPTS: usize = 500;
+
+/// Runs script hooks in response to node lifecycle events
+///
+/// Generic over connection type `C` to support both local and HTTP SurrealDB connections.
+pub struct ScriptService<C = surrealdb::engine::local::Db>
+where
+    C: surrealdb::Connection + 'static,
+{
+    node_service: NodeService<C>,
+    sandbox: ScriptSandbox,
+    scripts: Arc<RwLock<Option<Vec<ScriptNode>>>>,
+}
+
+impl<C> Clone for ScriptService<C>
+where
+    C: surrealdb::Connection + 'static,
+{
+    fn clone(&self) -> Self {
+        Self {
+            node_service: self.node_service.clone(),
+            sandbox: self.sandbox.clone(),
+            scripts: self.scripts.clone(),
+        }
+    }
+}
+
+impl<C> ScriptService<C>
+where
+    C: surrealdb::Connection + 'static,
+{
+    /// Create a script service with default sandbox limits
+    pub fn new(node_service: &NodeService<C>) -> Self {
+        Self::with_limits(node_service, ScriptLimits::default())
+    }
+
+    /// Create a script service with custom sandbox limits
+    pub fn with_limits(node_service: &NodeService<C>, limits: ScriptLimits) -> Self {
+        Self {
+            node_service: node_service.with_client(SCRIPT_CLIENT_ID),
+            sandbox: ScriptSandbox::new(limits),
+            scripts: Arc::new(RwLock::new(None)),
+        }
+    }
+
+    /// Get the sandbox (e.g. to compile-check a script before saving it)
+    pub fn sandbox(&self) -> &ScriptSandbox {
+        &self.sandbox
+    }
+
+    /// Process domain events until the channel closes
+    ///
+    /// Callers typically spawn this and cancel it on shutdown.
+    pub async fn run_event_loop(&self) {
+        let mut rx = self.node_service.subscribe_to_events();
+        tracing::info!("ScriptService listening for lifecycle events");
+
+        loop {
+            match rx.recv().await {
+                Ok(event) => self.handle_event(event).await,
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    tracing::warn!("ScriptService lagged, skipped {} events", skipped);
+                    self.invalidate_cache().await;
+                }
+                Err(broadcast::error::RecvError::Closed) => {
+                    tracing::info!("ScriptService event channel closed");
+                    break;
+                }
+            }
+        }
+    }
+
+    /// Handle a single domain event
+    pub async fn handle_event(&self, event: DomainEvent) {
+        let result = match event {
+            DomainEvent::NodeCreated {
+                node_id,
+                node_type,
+                source_client_id,
+                ..
+            } => {
+                if source_client_id.as_deref() == Some(SCRIPT_CLIENT_ID) {
+                    return;
+                }
+                if node_type == "script" {
+                    self.invalidate_cache().await;
+                    return;
+                }
+                self.run_hooks(&node_id, ScriptHook::OnCreate).await
+            }
+            DomainEvent::NodeUpdated {
+                node_id,
+                source_client_id,
+                ..
+            } => {
+                if source_client_id.as_deref() == Some(SCRIPT_CLIENT_ID) {
+                    return;
+                }
+                self.run_hooks(&node_id, ScriptHook::OnUpdate).await
+            }
+            DomainEvent::NodeDeleted { id, .. } => {
+                if self.is_cached_script(&id).await {
+                    self.invalidate_cache().await;
+                }
+                return;
+            }
+            _ => return,
+        };
+
+        if let Err(e) = result {
+            tracing::warn!("Script hook processing failed: {}", e);
+        }
+    }
+
+    /// Run all enabled scripts bound to the node's type and the given hook
+    ///
+    /// Script failures are logged and skipped; they never abort other scripts.
+    ///
+    /// # Returns
+    ///
+    /// Number of scripts that ran successfully
+    pub async fn run_hooks(
+        &self,
+        node_id: &str,
+        hook: ScriptHook,
+    ) -> Result<usize, NodeServiceError> {
+        let Some(node) = self.node_service.get_node(node_id).await? else {
+            return Ok(0);
+        };
+
+        if node.node_type == "script" {
+            self.invalidate_cache().await;
+            return Ok(0);
+        }
+
+        let scripts: Vec<ScriptNode> = self
+            .load_scripts()
+            .await?
+            .into_iter()
+            .filter(|s| s.matches(&node.node_type, hook))
+            .collect();
+
+        let mut succeeded = 0;
+        for script in scripts {
+            // Re-read so each script sees the previous script's writes
+            let Some(current) = self.node_service.get_node(node_id).await? else {
+                break;
+            };
+
+            let sandbox = self.sandbox.clone();
+            let source = script.source().to_string();
+            let reader: Arc<dyn ScriptNodeReader> = Arc::new(ServiceNodeReader {
+                node_service: self.node_service.clone(),
+                handle: tokio::runtime::Handle::current(),
+            });
+
+            let outcome =
+                tokio::task::spawn_blocking(move || sandbox.run(&source, &current, reader))
+                    .await
+                    .map_err(|e| {
+                        NodeServiceError::query_failed(format!("Script task panicked: {}", e))
+                    })?;
+
+            match outcome {
+                Ok(effects) => {
+                    self.apply_effects(node_id, effects).await?;
+                    succeeded += 1;
+                }
+                Err(e) => {
+                    tracing::warn!(
+                        script_id = %script.id(),
+                        node_id = %node_id,
+                        "Script failed: {}",
+                        e
+                    );
+                }
+            }
+        }
+
+        Ok(succeeded)
+    }
+
+    /// Apply effects recorded by a script run
+    ///
+    /// Property writes use `update_node_unchecked`: scripts only touch the keys
+    /// they set, and properties are deep-merged, so concurrent edits to other
+    /// fields are preserved.
+    async fn apply_effects(
+        &self,
+        node_id: &str,
+        effects: ScriptEffects,
+    ) -> Result<(), NodeServiceError> {
+        if effects.is_empty() {
+            return Ok(());
+        }
+
+        if !effects.properties.is_empty() {
+            let update =
+                NodeUpdate::new().with_properties(serde_json::Value::Object(effects.properties));
+            self.node_service
+                .update_node_unchecked(node_id, update)
+                .await?;
+        }
+
+        // Append children after any existing ones, preserving call order
+        let mut insert_after = self
+            .node_service
+            .get_children(node_id)
+            .await?
+            .last()
+            .map(|n| n.id.clone());
+
+        for child in effects.children {
+            let id = self
+                .node_service
+                .create_node_with_parent(CreateNodeParams {
+                    id: Some(child.id),
+                    node_type: child.node_type,
+                    content: child.content,
+                    parent_id: Some(node_id.to_string()),
+                    insert_after_node_id: insert_after.take(),
+                    properties: child.properties,
+                })
+                .await?;
+            insert_after = Some(id);
+        }
+
+        Ok(())
+    }
+
+    /// Load script nodes, using the cache when populated
+    async fn load_scripts(&self) -> Result<Vec<ScriptNode>, NodeServiceError> {
+        if let Some(scripts) = self.scripts.read().await.as_ref() {
+            return Ok(scripts.clone());
+        }
+
+        let nodes = self
+            .node_service
+            .query_nodes_simple(NodeQuery::by_type("script".to_string()).with_limit(MAX_SCRIPTS))
+            .await?;
+        let scripts: Vec<ScriptNode> = nodes
+            .into_iter()
+            .filter_map(|n| ScriptNode::from_node(n).ok())
+            .collect();
+
+        tracing::debug!("ScriptService loaded {} scripts", scripts.len());
+        *self.scripts.write().await = Some(scripts.clone());
+        Ok(scripts)
+    }
+
+    async fn is_cached_script(&self, id: &str) -> bool {
+        self.scripts
+            .read()
+            .await
+            .as_ref()
+            .is_some_and(|scripts| scripts.iter().any(|s| s.id() == id))
+    }
+
+    async fn invalidate_cache(&self) {
+        *self.scripts.write().await = None;
+    }
+}
+
+/// Bridges sync `get_node` calls from the sandbox to async NodeService lookups
+///
+/// Only used from `spawn_blocking`, where blocking on the runtime handle is allowed.
+struct ServiceNodeReader<C>
+where
+    C: surrealdb::Connection + 'static,
+{
+    node_service: NodeService<C>,
+    handle: tokio::runtime::Handle,
+}
+
+impl<C> ScriptNodeReader for ServiceNodeReader<C>
+where
+    C: surrealdb::Connection + 'static,
+{
+    fn read_node(&self, id: &str) -> Option<crate::models::Node> {
+        self.handle
+            .block_on(self.node_service.get_node(id))
+            .ok()
+            .flatten()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::SurrealStore;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    async fn create_test_service() -> (NodeService, TempDir) {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        let mut store = Arc::new(SurrealStore::new(db_path).await.unwrap());
+        let service = NodeService::new(&mut store).await.unwrap();
+        (service, temp_dir)
+    }
+
+    async fn create_script(service: &NodeService, target: &str, hook: ScriptHook, source: &str) {
+        let node = ScriptNode::builder(target, hook, source)
+            .build()
+            .into_node();
+        service.create_node(node).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_on_create_hook_sets_property_and_creates_child() {
+        let (service, _temp) = create_test_service().await;
+        create_script(
+            &service,
+            "task",
+            ScriptHook::OnCreate,
+            r#"set_property("priority", "high"); create_child("text", "Checklist");"#,
+        )
+        .await;
+
+        let task_id = service
+            .create_node_with_parent(CreateNodeParams {
+                id: None,
+                node_type: "task".to_string(),
+                content: "Ship release".to_string(),
+                parent_id: None,
+                insert_after_node_id: None,
+                properties: json!({"status": "open"}),
+            })
+            .await
+            .unwrap();
+
+        let scripts = ScriptService::new(&service);
+        let ran = scripts
+            .run_hooks(&task_id, ScriptHook::OnCreate)
+            .await
+            .unwrap();
+        assert_eq!(ran, 1);
+
+        let task = service.get_node(&task_id).await.unwrap().unwrap();
+        assert_eq!(task.properties["task"]["priority"], json!("high"));
+
+        let children = service.get_children(&task_id).await.unwrap();
+        assert_eq!(children.len(), 1);
+        assert_eq!(children[0].content, "Checklist");
+    }
+
+    #[tokio::test]
+    async fn test_hooks_only_run_for_matching_type_and_hook() {
+        let (service, _temp) = create_test_service().await;
+        create_script(
+            &service,
+            "task",
+            ScriptHook::OnUpdate,
+            r#"set_property("touched", true);"#,
+        )
+        .await;
+
+        let text = crate::models::Node::new("text".to_string(), "Hi".to_string(), json!({}));
+        let text_id = service.create_node(text).await.unwrap();
+
+        let scripts = ScriptService::new(&service);
+        assert_eq!(
+            scripts
+                .run_hooks(&text_id, ScriptHook::OnUpdate)
+                .await
+                .unwrap(),
+            0
+        );
+        assert_eq!(
+            scripts
+                .run_hooks(&text_id, ScriptHook::OnCreate)
+                .await
+                .unwrap(),
+            0
+        );
+    }
+
+    #[tokio::test]
+    async fn test_failing_script_does_not_write() {
+        let (service, _temp) = create_test_service().await;
+        create_script(
+            &service,
+            "text",
+            ScriptHook::OnCreate,
+            r#"set_property("x", 1); throw "nope";"#,
+        )
+        .await;
+
+        let text = crate::models::Node::new("text".to_string(), "Hi".to_string(), json!({}));
+        let text_id = service.create_node(text).await.unwrap();
+
+        let scripts = ScriptService::new(&service);
+        let ran = scripts
+            .run_hooks(&text_id, ScriptHook::OnCreate)
+            .await
+            .unwrap();
+        assert_eq!(ran, 0);
+
+        let node = service.get_node(&text_id).await.unwrap().unwrap();
+        assert!(node
+            .properties
+            .get("text")
+            .and_then(|t| t.get("x"))
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_script_node_changes_invalidate_cache() {
+        let (service, _temp) = create_test_service().await;
+        let scripts = ScriptService::new(&service);
+
+        let text = crate::models::Node::new("text".to_string(), "Hi".to_string(), json!({}));
+        let text_id = service.create_node(text).await.unwrap();
+        assert_eq!(
+            scripts
+                .run_hooks(&text_id, ScriptHook::OnUpdate)
+                .await
+                .unwrap(),
+            0
+        );
+
+        let script = ScriptNode::builder(
+            "text",
+            ScriptHook::OnUpdate,
+            r#"set_property("seen", true);"#,
+        )
+        .build()
+        .into_node();
+        let script_type = script.node_type.clone();
+        let script_id = service.create_node(script).await.unwrap();
+        scripts
+            .handle_event(DomainEvent::NodeCreated {
+                node_id: script_id,
+                node_type: script_type,
+                source_client_id: None,
+                correlation_id: None,
+            })
+            .await;
+
+        assert_eq!(
+            scripts
+                .run_hooks(&text_id, ScriptHook::OnUpdate)
+                .await
+                .unwrap(),
+            1
+        );
+    }
+}
@@ -0,0 +1,422 @@
+//! Sandboxed Script Execution
+//!
+//! Runs user scripts in an embedded [rhai](https://rhai.rs) engine with a deliberately
+//! small API surface. Scripts cannot touch the filesystem, network, or module system;
+//! they can only observe the triggering node and record effects:
+//!
+//! | Function | Effect |
+//! |----------|--------|
+//! | `node` | Map with `id`, `node_type`, `content`, `version`, `properties` |
+//! | `get_node(id)` | Read another node (returns `()` if missing) |
+//! | `set_property(key, value)` | Set a property on the triggering node |
+//! | `create_child(node_type, content[, properties])` | Create a child under the triggering node |
+//!
+//! Execution is pure: effects are collected into [`ScriptEffects`] and applied by
+//! `ScriptService` afterwards, so a failing script never leaves partial writes.
+
+use crate::models::{flatten_properties_for_api, Node};
+use rhai::{Dynamic, Engine, EvalAltResult, Map, Scope};
+use serde_json::Value;
+use std::sync::{Arc, Mutex};
+use thiserror::Error;
+
+/// Read-only node access exposed to scripts through `get_node(id)`
+///
+/// Called from a blocking context; implementations may block on async lookups.
+pub trait ScriptNodeReader: Send + Sync {
+    fn read_node(&self, id: &str) -> Option<Node>;
+}
+
+/// Resource limits applied to every script run
+#[derive(Debug, Clone)]
+pub struct ScriptLimits {
+    /// Maximum number of engine operations before the run is aborted
+    pub max_operations: u64,
+    /// Maximum function call nesting depth
+    pub max_call_depth: usize,
+    /// Maximum string length in bytes
+    pub max_string_size: usize,
+    /// Maximum array length
+    pub max_array_size: usize,
+    /// Maximum map size
+    pub max_map_size: usize,
+    /// Maximum number of `create_child` calls per run
+    pub max_children: usize,
+}
+
+impl Default for ScriptLimits {
+    fn default() -> Self {
+        Self {
+            max_operations: 100_000,
+            max_call_depth: 32,
+            max_string_size: 64 * 1024,
+            max_array_size: 10_000,
+            max_map_size: 10_000,
+            max_children: 50,
+        }
+    }
+}
+
+/// A child node requested by a script via `create_child`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScriptChild {
+    /// Pre-assigned ID (returned to the script so it can reference the child)
+    pub id: String,
+    pub node_type: String,
+    pub content: String,
+    /// Flat properties for the child
+    pub properties: Value,
+}
+
+/// Effects recorded during a script run
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ScriptEffects {
+    /// Flat property updates for the triggering node
+    pub properties: serde_json::Map<String, Value>,
+    /// Children to create under the triggering node, in call order
+    pub children: Vec<ScriptChild>,
+}
+
+impl ScriptEffects {
+    /// Whether the script requested no changes
+    pub fn is_empty(&self) -> bool {
+        self.properties.is_empty() && self.children.is_empty()
+    }
+}
+
+/// Errors raised while compiling or running a script
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum ScriptError {
+    #[error("Script compilation failed: {0}")]
+    Compile(String),
+
+    #[error("Script execution failed: {0}")]
+    Runtime(String),
+
+    #[error("Script exceeded resource limit: {0}")]
+    LimitExceeded(String),
+}
+
+impl From<Box<EvalAltResult>> for ScriptError {
+    fn from(err: Box<EvalAltResult>) -> Self {
+        match *err {
+            EvalAltResult::ErrorTooManyOperations(_)
+            | EvalAltResult::ErrorStackOverflow(_)
+            | EvalAltResult::ErrorDataTooLarge(_, _) => ScriptError::LimitExceeded(err.to_string()),
+            EvalAltResult::ErrorParsing(_, _) => ScriptError::Compile(err.to_string()),
+            _ => ScriptError::Runtime(err.to_string()),
+        }
+    }
+}
+
+/// Sandboxed script executor
+///
+/// A fresh engine is built per run so registered functions can capture the
+/// run's effect collector and node reader without shared mutable state.
+#[derive(Debug, Clone, Default)]
+pub struct ScriptSandbox {
+    limits: ScriptLimits,
+}
+
+impl ScriptSandbox {
+    /// Create a sandbox with the given limits
+    pub fn new(limits: ScriptLimits) -> Self {
+        Self { limits }
+    }
+
+    /// Get the configured limits
+    pub fn limits(&self) -> &ScriptLimits {
+        &self.limits
+    }
+
+    /// Check that a script compiles without running it
+    pub fn compile_check(&self, source: &str) -> Result<(), ScriptError> {
+        self.base_engine()
+            .compile(source)
+            .map(|_| ())
+            .map_err(|e| ScriptError::Compile(e.to_string()))
+    }
+
+    /// Run a script against a node and return the recorded effects
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - Script source code
+    /// * `node` - The node whose lifecycle event triggered the script
+    /// * `reader` - Backing store for `get_node(id)`
+    pub fn run(
+        &self,
+        source: &str,
+        node: &Node,
+        reader: Arc<dyn ScriptNodeReader>,
+    ) -> Result<ScriptEffects, ScriptError> {
+        let effects = Arc::new(Mutex::new(ScriptEffects::default()));
+        let mut engine = self.base_engine();
+
+        engine.register_fn("get_node", move |id: &str| -> Dynamic {
+            reader
+                .read_node(id)
+                .and_then(|n| rhai::serde::to_dynamic(node_to_script_value(n)).ok())
+                .unwrap_or(Dynamic::UNIT)
+        });
+
+        let collector = effects.clone();
+        engine.register_fn(
+            "set_property",
+            move |key: &str, value: Dynamic| -> Result<(), Box<EvalAltResult>> {
+                let value: Value = rhai::serde::from_dynamic(&value)?;
+                lock(&collector).properties.insert(key.to_string(), value);
+                Ok(())
+            },
+        );
+
+        let collector = effects.clone();
+        let max_children = self.limits.max_children;
+        engine.register_fn(
+            "create_child",
+            move |node_type: &str, content: &str| -> Result<String, Box<EvalAltResult>> {
+                record_child(&collector, max_children, node_type, content, Value::Null)
+            },
+        );
+
+        let collector = effects.clone();
+        engine.register_fn(
+            "create_child",
+            move |node_type: &str,
+                  content: &str,
+                  properties: Map|
+                  -> Result<String, Box<EvalAltResult>> {
+                let properties: Value = rhai::serde::from_dynamic(&properties.into())?;
+                record_child(&collector, max_children, node_type, content, properties)
+            },
+        );
+
+        let ast = engine
+            .compile(source)
+            .map_err(|e| ScriptError::Compile(e.to_string()))?;
+
+        let node_value = rhai::serde::to_dynamic(node_to_script_value(node.clone()))
+            .map_err(|e| ScriptError::Runtime(e.to_string()))?;
+        let mut scope = Scope::new();
+        scope.push_constant("node", node_value);
+
+        engine.run_ast_with_scope(&mut scope, &ast)?;
+
+        let effects = lock(&effects).clone();
+        Ok(effects)
+    }
+
+    /// Build an engine with limits applied and unsafe symbols disabled
+    fn base_engine(&self) -> Engine {
+        let mut engine = Engine::new();
+        engine
+            .set_max_operations(self.limits.max_operations)
+            .set_max_call_levels(self.limits.max_call_depth)
+            .set_max_string_size(self.limits.max_string_size)
+            .set_max_array_size(self.limits.max_array_size)
+            .set_max_map_size(self.limits.max_map_size)
+            .set_max_expr_depths(64, 32);
+        engine.disable_symbol("eval");
+        engine.on_print(|msg| tracing::info!(target: "nodespace::script", "{}", msg));
+        engine.on_debug(
+            |msg, _, pos| tracing::debug!(target: "nodespace::script", "{} ({})", msg, pos),
+        );
+        engine
+    }
+}
+
+/// Convert a node to the map exposed to scripts (flat properties, snake_case keys)
+fn node_to_script_value(mut node: Node) -> Value {
+    flatten_properties_for_api(&mut node);
+    serde_json::json!({
+        "id": node.id,
+        "node_type": node.node_type,
+        "content": node.content,
+        "version": node.version,
+        "properties": node.properties,
+    })
+}
+
+fn record_child(
+    effects: &Mutex<ScriptEffects>,
+    max_children: usize,
+    node_type: &str,
+    content: &str,
+    properties: Value,
+) -> Result<String, Box<EvalAltResult>> {
+    let mut effects = lock(effects);
+    if effects.children.len() >= max_children {
+        return Err(format!("create_child limit of {} reached", max_children).into());
+    }
+    let id = uuid::Uuid::new_v4().to_string();
+    effects.children.push(ScriptChild {
+        id: id.clone(),
+        node_type: node_type.to_string(),
+        content: content.to_string(),
+        properties: if properties.is_null() {
+            serde_json::json!({})
+        } else {
+            properties
+        },
+    });
+    Ok(id)
+}
+
+fn lock(effects: &Mutex<ScriptEffects>) -> std::sync::MutexGuard<'_, ScriptEffects> {
+    effects.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::collections::HashMap;
+
+    struct MapReader(HashMap<String, Node>);
+
+    impl ScriptNodeReader for MapReader {
+        fn read_node(&self, id: &str) -> Option<Node> {
+            self.0.get(id).cloned()
+        }
+    }
+
+    fn empty_reader() -> Arc<dyn ScriptNodeReader> {
+        Arc::new(MapReader(HashMap::new()))
+    }
+
+    fn task_node() -> Node {
+        Node::new_with_id(
+            "task-1".to_string(),
+            "task".to_string(),
+            "Write report".to_string(),
+            json!({"task": {"status": "open"}}),
+        )
+    }
+
+    #[test]
+    fn test_script_reads_node_and_sets_property() {
+        let sandbox = ScriptSandbox::default();
+        let effects = sandbox
+            .run(
+                r#"
+                if node.properties.status == "open" {
+                    set_property("priority", "high");
+                }
+                set_property("title_length", node.content.len());
+                "#,
+                &task_node(),
+                empty_reader(),
+            )
+            .unwrap();
+
+        assert_eq!(effects.properties.get("priority"), Some(&json!("high")));
+        assert_eq!(effects.properties.get("title_length"), Some(&json!(12)));
+        assert!(effects.children.is_empty());
+    }
+
+    #[test]
+    fn test_script_creates_children() {
+        let sandbox = ScriptSandbox::default();
+        let effects = sandbox
+            .run(
+                r#"
+                let id = create_child("text", "Notes");
+                create_child("task", "Follow up", #{ status: "open" });
+                "#,
+                &task_node(),
+                empty_reader(),
+            )
+            .unwrap();
+
+        assert_eq!(effects.children.len(), 2);
+        assert_eq!(effects.children[0].node_type, "text");
+        assert_eq!(effects.children[0].properties, json!({}));
+        assert_eq!(effects.children[1].properties, json!({"status": "open"}));
+    }
+
+    #[test]
+    fn test_script_reads_other_nodes() {
+        let mut nodes = HashMap::new();
+        nodes.insert(
+            "other".to_string(),
+            Node::new_with_id(
+                "other".to_string(),
+                "text".to_string(),
+                "Referenced".to_string(),
+                json!({}),
+            ),
+        );
+        let reader: Arc<dyn ScriptNodeReader> = Arc::new(MapReader(nodes));
+
+        let effects = ScriptSandbox::default()
+            .run(
+                r#"
+                let other = get_node("other");
+                set_property("copied", other.content);
+                set_property("missing", get_node("nope") == ());
+                "#,
+                &task_node(),
+                reader,
+            )
+            .unwrap();
+
+        assert_eq!(effects.properties.get("copied"), Some(&json!("Referenced")));
+        assert_eq!(effects.properties.get("missing"), Some(&json!(true)));
+    }
+
+    #[test]
+    fn test_infinite_loop_hits_operation_limit() {
+        let sandbox = ScriptSandbox::new(ScriptLimits {
+            max_operations: 1_000,
+            ..Default::default()
+        });
+        let err = sandbox
+            .run("loop { }", &task_node(), empty_reader())
+            .unwrap_err();
+
+        assert!(matches!(err, ScriptError::LimitExceeded(_)));
+    }
+
+    #[test]
+    fn test_child_limit_enforced() {
+        let sandbox = ScriptSandbox::new(ScriptLimits {
+            max_children: 2,
+            ..Default::default()
+        });
+        let err = sandbox
+            .run(
+                r#"for i in 0..5 { create_child("text", "x"); }"#,
+                &task_node(),
+                empty_reader(),
+            )
+            .unwrap_err();
+
+        assert!(matches!(err, ScriptError::Runtime(_)));
+    }
+
+    #[test]
+    fn test_eval_is_disabled() {
+        let sandbox = ScriptSandbox::default();
+        assert!(sandbox.compile_check(r#"eval("1 + 1")"#).is_err());
+    }
+
+    #[test]
+    fn test_compile_error_reported() {
+        let sandbox = ScriptSandbox::default();
+        let err = sandbox
+            .run("let = ;", &task_node(), empty_reader())
+            .unwrap_err();
+        assert!(matches!(err, ScriptError::Compile(_)));
+    }
+
+    #[test]
+    fn test_failed_script_returns_no_effects() {
+        let sandbox = ScriptSandbox::default();
+        let result = sandbox.run(
+            r#"set_property("a", 1); throw "boom";"#,
+            &task_node(),
+            empty_reader(),
+        );
+        assert!(matches!(result, Err(ScriptError::Runtime(_))));
+    }
+}
@@ -0,0 +1,252 @@
+//! Smart Collections - Collections Defined by a Saved Query
+//!
+//! A collection that stores a [`QueryDefinition`] (see
+//! [`CollectionNode::smart_query`]) has no member_of edges: its members are
+//! the query's results, e.g. an "Inbox" of untagged notes from the last week.
+//!
+//! # Evaluation
+//!
+//! Results are computed on first read and cached per collection. The cache
+//! follows domain events ([`SmartCollectionService::run`]) and drops the
+//! results an event may change, using the same test as watched queries; the
+//! next read runs the query again. An edit of the collection itself drops its
+//! entry too, since its query may have changed. A read that raced an event is
+//! not cached ([`SmartCollectionCache::generation`]), as with rollups.
+
+use super::error::NodeServiceError;
+use super::node_service::NodeService;
+use super::query_service::{QueryDefinition, QueryService};
+use super::query_watch::may_change_results;
+use crate::db::events::DomainEvent;
+use crate::models::{CollectionNode, Node};
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard};
+use tokio::sync::broadcast;
+
+struct CachedMembers {
+    definition: QueryDefinition,
+    node_ids: Vec<String>,
+    nodes: Vec<Node>,
+}
+
+#[derive(Default)]
+struct CacheState {
+    entries: HashMap<String, CachedMembers>,
+    /// Bumped whenever an entry is dropped
+    generation: u64,
+}
+
+/// Last results of each smart collection by collection ID
+#[derive(Default)]
+pub struct SmartCollectionCache {
+    state: RwLock<CacheState>,
+}
+
+impl SmartCollectionCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cached members of `collection_id`, if current
+    pub fn get(&self, collection_id: &str) -> Option<Vec<Node>> {
+        self.read()
+            .entries
+            .get(collection_id)
+            .map(|entry| entry.nodes.clone())
+    }
+
+    /// Invalidation counter; read it before running a collection's query
+    pub fn generation(&self) -> u64 {
+        self.read().generation
+    }
+
+    /// Record the members `definition` returned, unless an entry was dropped
+    /// after `generation` was read
+    pub fn insert(
+        &self,
+        collection_id: &str,
+        definition: QueryDefinition,
+        nodes: Vec<Node>,
+        generation: u64,
+    ) {
+        let mut state = self.write();
+        if state.generation != generation {
+            return;
+        }
+        let node_ids = nodes.iter().map(|node| node.id.clone()).collect();
+        state.entries.insert(
+            collection_id.to_string(),
+            CachedMembers {
+                definition,
+                node_ids,
+                nodes,
+            },
+        );
+    }
+
+    /// Drop the entries `event` may change
+    pub fn apply(&self, event: &DomainEvent) {
+        let mut state = self.write();
+        let count = state.entries.len();
+        state.entries.retain(|collection_id, entry| {
+            let own_change = match event {
+                DomainEvent::NodeUpdated { node_id, .. } => node_id == collection_id,
+                DomainEvent::NodeDeleted { id, .. } => id == collection_id,
+                _ => false,
+            };
+            !own_change && !may_change_results(&entry.definition, &entry.node_ids, event)
+        });
+        if state.entries.len() != count {
+            state.generation += 1;
+        }
+    }
+
+    /// Drop every entry
+    pub fn clear(&self) {
+        let mut state = self.write();
+        state.entries.clear();
+        state.generation += 1;
+    }
+
+    fn read(&self) -> RwLockReadGuard<'_, CacheState> {
+        self.state.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, CacheState> {
+        self.state.write().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Evaluates smart collections and caches their members
+pub struct SmartCollectionService {
+    node_service: Arc<NodeService>,
+    query_service: Arc<QueryService>,
+    cache: SmartCollectionCache,
+}
+
+impl SmartCollectionService {
+    pub fn new(node_service: Arc<NodeService>, query_service: Arc<QueryService>) -> Self {
+        Self {
+            node_service,
+            query_service,
+            cache: SmartCollectionCache::new(),
+        }
+    }
+
+    /// Members of `collection_id` if it is a smart collection, `None` for a
+    /// collection with explicit members
+    pub async fn members(
+        &self,
+        collection_id: &str,
+    ) -> Result<Option<Vec<Node>>, NodeServiceError> {
+        if let Some(nodes) = self.cache.get(collection_id) {
+            return Ok(Some(nodes));
+        }
+
+        let generation = self.cache.generation();
+        let node = self
+            .node_service
+            .get_node(collection_id)
+            .await?
+            .ok_or_else(|| NodeServiceError::node_not_found(collection_id))?;
+        let collection = CollectionNode::from_node(node)
+            .map_err(|e| NodeServiceError::invalid_input(e.to_string()))?;
+        let Some(definition) = collection.smart_query() else {
+            return Ok(None);
+        };
+
+        let nodes = self
+            .query_service
+            .execute(&definition)
+            .await
+            .map_err(|e| NodeServiceError::query_failed(format!("{:#}", e)))?;
+        self.cache
+            .insert(collection_id, definition, nodes.clone(), generation);
+        Ok(Some(nodes))
+    }
+
+    /// Follow domain events, dropping the cached members they may change
+    ///
+    /// Runs until the event channel closes.
+    pub async fn run(&self, mut events: broadcast::Receiver<DomainEvent>) {
+        loop {
+            match events.recv().await {
+                Ok(event) => self.cache.apply(&event),
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    tracing::debug!("Smart collections lagged {} events, clearing", skipped);
+                    self.cache.clear();
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::events::RelationshipEvent;
+    use serde_json::json;
+
+    fn inbox() -> QueryDefinition {
+        serde_json::from_value(json!({ "targetType": "text", "filters": [] })).unwrap()
+    }
+
+    fn text(content: &str) -> Node {
+        Node::new("text".to_string(), content.to_string(), json!({}))
+    }
+
+    #[test]
+    fn test_events_drop_affected_members() {
+        let cache = SmartCollectionCache::new();
+        let note = text("Untagged");
+        cache.insert("inbox", inbox(), vec![note.clone()], cache.generation());
+        assert_eq!(cache.get("inbox").unwrap().len(), 1);
+
+        // A task can't be a member of a text-only collection
+        cache.apply(&DomainEvent::NodeCreated {
+            node_id: "t1".to_string(),
+            node_type: "task".to_string(),
+            source_client_id: None,
+            correlation_id: None,
+        });
+        assert!(cache.get("inbox").is_some());
+
+        // Membership edges don't matter to a query without relationship filters
+        cache.apply(&DomainEvent::RelationshipCreated {
+            relationship: RelationshipEvent {
+                id: "relationship:r1".to_string(),
+                from_id: note.id.clone(),
+                to_id: "reading".to_string(),
+                relationship_type: "member_of".to_string(),
+                properties: json!({}),
+            },
+            source_client_id: None,
+            correlation_id: None,
+        });
+        assert!(cache.get("inbox").is_some());
+
+        cache.apply(&DomainEvent::NodeDeleted {
+            id: note.id.clone(),
+            source_client_id: None,
+            correlation_id: None,
+        });
+        assert!(cache.get("inbox").is_none());
+    }
+
+    #[test]
+    fn test_stale_read_is_not_cached() {
+        let cache = SmartCollectionCache::new();
+        cache.insert("inbox", inbox(), vec![], cache.generation());
+        let generation = cache.generation();
+        cache.apply(&DomainEvent::NodeUpdated {
+            node_id: "inbox".to_string(),
+            source_client_id: None,
+            correlation_id: None,
+        });
+        assert!(cache.get("inbox").is_none());
+
+        cache.insert("inbox", inbox(), vec![text("Late")], generation);
+        assert!(cache.get("inbox").is_none());
+    }
+}
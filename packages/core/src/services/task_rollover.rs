@@ -0,0 +1,299 @@
+//! Task Rollover Service - Carry Open Tasks Forward
+//!
+//! Finds open tasks filed under past date nodes and brings them to today,
+//! either by moving them ([`RolloverMode::Move`]) or by creating a copy under
+//! today that mentions the original ([`RolloverMode::Copy`]), which leaves
+//! each day's page as it was. A text node under today summarises what was
+//! carried over.
+//!
+//! Only the outermost open task of a branch is rolled; its subtasks travel
+//! with it when moved. A copied original is marked with
+//! `task.carried_over_to`, so it is not copied again on the next run.
+
+use super::error::NodeServiceError;
+use super::node_service::{CreateNodeParams, NodeService};
+use crate::models::{Node, NodeFilter, TaskNode, TaskStatus};
+use chrono::{Local, NaiveDate, NaiveTime};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::sync::Arc;
+
+/// How open tasks reach today
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RolloverMode {
+    /// Move the task under today
+    Move,
+    /// Leave the task in place and create a linked copy under today
+    Copy,
+}
+
+/// A task copied forward by [`RolloverMode::Copy`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CarriedOverCopy {
+    pub original_id: String,
+    pub copy_id: String,
+}
+
+/// Outcome of a rollover run
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RolloverReport {
+    /// The day tasks were carried to
+    pub date: NaiveDate,
+    /// Tasks moved under `date`
+    pub moved: Vec<String>,
+    /// Copies created under `date`
+    pub copied: Vec<CarriedOverCopy>,
+    /// Summary node under `date`, absent when nothing was carried over
+    pub report_node_id: Option<String>,
+}
+
+impl RolloverReport {
+    pub fn is_empty(&self) -> bool {
+        self.moved.is_empty() && self.copied.is_empty()
+    }
+}
+
+/// An open task found under a past date
+struct OpenTask {
+    node: Node,
+    from_date: String,
+}
+
+/// Carries open tasks from past date nodes to today
+pub struct TaskRolloverService<C = surrealdb::engine::local::Db>
+where
+    C: surrealdb::Connection,
+{
+    node_service: Arc<NodeService<C>>,
+}
+
+impl<C> TaskRolloverService<C>
+where
+    C: surrealdb::Connection,
+{
+    pub fn new(node_service: Arc<NodeService<C>>) -> Self {
+        Self { node_service }
+    }
+
+    /// Carry open tasks from dates before `today` to `today`
+    pub async fn roll_over(
+        &self,
+        today: NaiveDate,
+        mode: RolloverMode,
+    ) -> Result<RolloverReport, NodeServiceError> {
+        let tasks = self.open_tasks_before(today).await?;
+        let mut report = RolloverReport {
+            date: today,
+            moved: Vec::new(),
+            copied: Vec::new(),
+            report_node_id: None,
+        };
+        if tasks.is_empty() {
+            return Ok(report);
+        }
+
+        let today_id = today.format("%Y-%m-%d").to_string();
+        self.node_service.ensure_date_exists(&today_id).await?;
+
+        let mut lines = Vec::with_capacity(tasks.len());
+        for task in tasks {
+            let title = task.node.content.lines().next().unwrap_or_default();
+            match mode {
+                RolloverMode::Move => {
+                    self.node_service
+                        .move_node(&task.node.id, task.node.version, Some(&today_id), None)
+                        .await?;
+                    lines.push(format!(
+                        "- [@{}](nodespace://{}) from {}",
+                        title, task.node.id, task.from_date
+                    ));
+                    report.moved.push(task.node.id);
+                }
+                RolloverMode::Copy => {
+                    let copy_id = self.copy_task(&task.node, &today_id).await?;
+                    lines.push(format!(
+                        "- [@{}](nodespace://{}) from {}",
+                        title, copy_id, task.from_date
+                    ));
+                    report.copied.push(CarriedOverCopy {
+                        original_id: task.node.id,
+                        copy_id,
+                    });
+                }
+            }
+        }
+
+        let count = lines.len();
+        let summary = format!(
+            "Carried over {} open task{}\n{}",
+            count,
+            if count == 1 { "" } else { "s" },
+            lines.join("\n")
+        );
+        let report_node_id = self
+            .node_service
+            .create_node_with_parent(CreateNodeParams {
+                id: None,
+                node_type: "text".to_string(),
+                content: summary,
+                parent_id: Some(today_id),
+                insert_after_node_id: None,
+                properties: json!({}),
+            })
+            .await?;
+        report.report_node_id = Some(report_node_id);
+        Ok(report)
+    }
+
+    /// Roll over now, then every day at `at` (local time)
+    ///
+    /// Running once on start catches up on days the app was closed. Never
+    /// returns; callers typically spawn this and cancel it on shutdown.
+    pub async fn run<F>(&self, mode: RolloverMode, at: NaiveTime, on_report: F)
+    where
+        F: Fn(RolloverReport),
+    {
+        loop {
+            match self.roll_over(Local::now().date_naive(), mode).await {
+                Ok(report) if !report.is_empty() => on_report(report),
+                Ok(_) => {}
+                Err(e) => tracing::warn!("Task rollover failed: {}", e),
+            }
+            tokio::time::sleep(until_next(Local::now().naive_local(), at)).await;
+        }
+    }
+
+    /// Outermost open tasks under date nodes before `today`, oldest date first
+    async fn open_tasks_before(&self, today: NaiveDate) -> Result<Vec<OpenTask>, NodeServiceError> {
+        let mut dates: Vec<String> = self
+            .node_service
+            .query_nodes(NodeFilter::new().with_node_type("date".to_string()))
+            .await?
+            .into_iter()
+            .map(|node| node.id)
+            .filter(|id| NaiveDate::parse_from_str(id, "%Y-%m-%d").is_ok_and(|date| date < today))
+            .collect();
+        dates.sort();
+
+        let mut tasks = Vec::new();
+        for date in dates {
+            let (_, mut nodes, children) = self.node_service.get_subtree_data(&date).await?;
+            // Depth-first in document order, not descending into rolled tasks
+            let mut stack: Vec<String> = children.get(&date).cloned().unwrap_or_default();
+            stack.reverse();
+            while let Some(id) = stack.pop() {
+                let Some(node) = nodes.remove(&id) else {
+                    continue;
+                };
+                if is_rollable(&node) {
+                    tasks.push(OpenTask {
+                        node,
+                        from_date: date.clone(),
+                    });
+                    continue;
+                }
+                if let Some(ids) = children.get(&id) {
+                    stack.extend(ids.iter().rev().cloned());
+                }
+            }
+        }
+        Ok(tasks)
+    }
+
+    /// Create a linked copy of `task` under `date_id`, marking the original
+    async fn copy_task(&self, task: &Node, date_id: &str) -> Result<String, NodeServiceError> {
+        let mut properties = task.properties.clone();
+        if let Some(namespace) = properties.get_mut("task").and_then(|t| t.as_object_mut()) {
+            namespace.remove("carried_over_to");
+        }
+        crate::utils::set_pointer(&mut properties, "/task/carried_over_from", json!(task.id))
+            .map_err(NodeServiceError::invalid_update)?;
+
+        let copy_id = self
+            .node_service
+            .create_node_with_parent(CreateNodeParams {
+                id: None,
+                node_type: "task".to_string(),
+                content: task.content.clone(),
+                parent_id: Some(date_id.to_string()),
+                insert_after_node_id: None,
+                properties,
+            })
+            .await?;
+        self.node_service.add_mention(&copy_id, &task.id).await?;
+        self.node_service
+            .set_property(&task.id, "/task/carried_over_to", json!(copy_id))
+            .await?;
+        Ok(copy_id)
+    }
+}
+
+/// Open task that has not been copied forward already
+fn is_rollable(node: &Node) -> bool {
+    if node.node_type != "task" {
+        return false;
+    }
+    let copied = node
+        .properties
+        .get("task")
+        .and_then(|task| task.get("carried_over_to"))
+        .is_some();
+    !copied
+        && TaskNode::from_node(node.clone())
+            .is_ok_and(|task| !matches!(task.status, TaskStatus::Done | TaskStatus::Cancelled))
+}
+
+/// Time from `now` until the next `at`
+fn until_next(now: chrono::NaiveDateTime, at: NaiveTime) -> std::time::Duration {
+    let mut next = now.date().and_time(at);
+    if next <= now {
+        next += chrono::Duration::days(1);
+    }
+    (next - now).to_std().unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task(status: &str, extra: serde_json::Value) -> Node {
+        let mut props = json!({ "task": { "status": status } });
+        if let Some(extra) = extra.as_object() {
+            for (key, value) in extra {
+                props["task"][key] = value.clone();
+            }
+        }
+        Node::new("task".to_string(), "Task".to_string(), props)
+    }
+
+    #[test]
+    fn test_is_rollable() {
+        assert!(is_rollable(&task("open", json!({}))));
+        assert!(is_rollable(&task("in_progress", json!({}))));
+        assert!(!is_rollable(&task("done", json!({}))));
+        assert!(!is_rollable(&task(
+            "open",
+            json!({ "carried_over_to": "x" })
+        )));
+        assert!(!is_rollable(&Node::new(
+            "text".to_string(),
+            "Note".to_string(),
+            json!({})
+        )));
+    }
+
+    #[test]
+    fn test_until_next() {
+        let date = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+        let at = NaiveTime::from_hms_opt(6, 0, 0).unwrap();
+
+        let before = date.and_hms_opt(5, 30, 0).unwrap();
+        assert_eq!(until_next(before, at).as_secs(), 30 * 60);
+
+        let after = date.and_hms_opt(6, 0, 0).unwrap();
+        assert_eq!(until_next(after, at).as_secs(), 24 * 60 * 60);
+    }
+}
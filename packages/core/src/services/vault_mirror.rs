@@ -0,0 +1,1171 @@
+//! Vault Mirror Service - Markdown Folder Sync
+//!
+//! Keeps a folder of plain markdown files in step with the database so external
+//! tools (git, grep, Obsidian) can work with the data while NodeSpace stays the
+//! source of truth.
+//!
+//! ## Layout
+//!
+//! Each document root becomes one file named `<title-slug>-<id prefix>.md` (date
+//! roots are just `<date>.md`). Names only change when a root's title changes.
+//! A manifest (`.nodespace-mirror.json`) records which file belongs to which
+//! root so renamed and deleted roots don't leave stale files behind.
+//!
+//! Files start with a small YAML frontmatter holding the root ID and type,
+//! followed by the same markdown the MCP export produces (without ID comments).
+//! Output is deterministic and files are only rewritten when their content
+//! changes, so history in a git repository stays readable.
+//!
+//! ## Event Flow
+//!
+//! 1. `run_event_loop()` performs a full sync, then subscribes to domain events
+//! 2. Changed node IDs are collected until no event arrives for the debounce window
+//! 3. Each affected root is re-rendered; roots that disappeared lose their file
+//!
+//! If the event channel lags, the next flush falls back to a full sync. When
+//! the loop is told to stop, pending changes are written before it returns,
+//! so no file is left half-written on exit.
+//!
+//! ## Two-Way Sync
+//!
+//! With [`VaultMirrorConfig::with_two_way_sync`] the mirrored files are also
+//! polled for external edits. The manifest records when each file and its
+//! root last agreed (`synced_at`); a file modified after that point is read
+//! back into the database:
+//!
+//! - The title (text before the first blank line) updates the root's content
+//! - A changed body is diffed against the existing children: unchanged nodes
+//!   are kept, edited ones updated in place, and only added or removed nodes
+//!   are created or deleted, so IDs, mentions and properties survive the edit
+//!
+//! When a root's nodes were also modified after `synced_at`, both sides changed
+//! and the edit is a conflict. The external version is kept next to the file as
+//! `<name>.conflict-<timestamp>.md` and the file is rewritten from the database.
+//! Files created in the folder by other tools are not imported.
+
+use crate::db::events::DomainEvent;
+use crate::mcp::handlers::markdown::{
+    prepare_nodes_from_markdown, render_subtree_markdown, PreparedNode,
+};
+use crate::models::{Node, NodeUpdate};
+use crate::services::error::NodeServiceError;
+use crate::services::node_service::CreateNodeParams;
+use crate::services::NodeService;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{broadcast, Mutex};
+
+/// Manifest file written into the mirror folder
+pub const MIRROR_MANIFEST_FILE: &str = ".nodespace-mirror.json";
+
+/// Quiet period before pending changes are written
+pub const DEFAULT_MIRROR_DEBOUNCE: Duration = Duration::from_secs(2);
+
+/// How often mirrored files are checked for external edits in two-way mode
+pub const DEFAULT_EXTERNAL_EDIT_POLL: Duration = Duration::from_secs(2);
+
+/// Root node types that are configuration rather than documents
+const EXCLUDED_ROOT_TYPES: &[&str] = &["schema", "collection", "query", "script"];
+
+/// Depth limit for rendered subtrees (matches the MCP export default)
+const MAX_RENDER_DEPTH: usize = 20;
+
+/// Maximum characters taken from the title for a file name
+const MAX_SLUG_LEN: usize = 60;
+
+/// Configuration for [`VaultMirrorService`]
+#[derive(Debug, Clone)]
+pub struct VaultMirrorConfig {
+    /// Folder receiving the markdown files (created if missing)
+    pub folder: PathBuf,
+    pub debounce: Duration,
+    /// Poll interval for external edits; `None` keeps the mirror one-way
+    pub external_edit_poll: Option<Duration>,
+}
+
+impl VaultMirrorConfig {
+    pub fn new(folder: impl Into<PathBuf>) -> Self {
+        Self {
+            folder: folder.into(),
+            debounce: DEFAULT_MIRROR_DEBOUNCE,
+            external_edit_poll: None,
+        }
+    }
+
+    pub fn with_debounce(mut self, debounce: Duration) -> Self {
+        self.debounce = debounce;
+        self
+    }
+
+    /// Apply external edits of mirrored files back to the database
+    pub fn with_two_way_sync(mut self, poll_interval: Duration) -> Self {
+        self.external_edit_poll = Some(poll_interval);
+        self
+    }
+}
+
+/// Result of a full sync
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct MirrorSyncStats {
+    /// Files created or rewritten
+    pub written: usize,
+    /// Files left untouched because their content was unchanged
+    pub unchanged: usize,
+    /// Files removed for roots that no longer exist
+    pub removed: usize,
+    /// External edits applied to the database
+    pub imported: usize,
+    /// External edits set aside because the root changed as well
+    pub conflicts: usize,
+}
+
+/// Root ID → mirrored file, persisted in the mirror folder
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Manifest {
+    files: BTreeMap<String, MirroredFile>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct MirroredFile {
+    file: String,
+    /// Last time the file and the root were known to match
+    synced_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Default)]
+struct MirrorState {
+    manifest: Manifest,
+    /// Last known root for every mirrored node, used to re-render the old root
+    /// when a node moves or is deleted
+    node_roots: HashMap<String, String>,
+    loaded: bool,
+}
+
+enum WriteOutcome {
+    Written,
+    Unchanged,
+    Removed,
+    Skipped,
+    Imported,
+    Conflict,
+}
+
+/// Mirrors document roots into a folder of markdown files
+///
+/// Generic over connection type `C` to support both local and HTTP SurrealDB connections.
+pub struct VaultMirrorService<C = surrealdb::engine::local::Db>
+where
+    C: surrealdb::Connection + 'static,
+{
+    node_service: NodeService<C>,
+    config: VaultMirrorConfig,
+    state: Arc<Mutex<MirrorState>>,
+}
+
+impl<C> Clone for VaultMirrorService<C>
+where
+    C: surrealdb::Connection + 'static,
+{
+    fn clone(&self) -> Self {
+        Self {
+            node_service: self.node_service.clone(),
+            config: self.config.clone(),
+            state: self.state.clone(),
+        }
+    }
+}
+
+impl<C> VaultMirrorService<C>
+where
+    C: surrealdb::Connection + 'static,
+{
+    pub fn new(node_service: &NodeService<C>, config: VaultMirrorConfig) -> Self {
+        Self {
+            node_service: node_service.clone(),
+            config,
+            state: Arc::new(Mutex::new(MirrorState::default())),
+        }
+    }
+
+    pub fn folder(&self) -> &Path {
+        &self.config.folder
+    }
+
+    /// File currently mirroring `root_id`, if any
+    pub async fn file_for_root(&self, root_id: &str) -> Option<PathBuf> {
+        let mut state = self.state.lock().await;
+        self.load_manifest(&mut state).await;
+        state
+            .manifest
+            .files
+            .get(root_id)
+            .map(|entry| self.config.folder.join(&entry.file))
+    }
+
+    /// Sync then keep the folder up to date until `shutdown` completes or
+    /// the event channel closes
+    ///
+    /// Changes still waiting for the debounce window are written on shutdown.
+    /// Dropping this future instead can cut a file write short.
+    pub async fn run_event_loop(&self, shutdown: impl Future<Output = ()>) {
+        tokio::pin!(shutdown);
+        let mut rx = self.node_service.subscribe_to_events();
+        tracing::info!("VaultMirrorService mirroring into {:?}", self.config.folder);
+
+        if let Err(e) = self.sync_all().await {
+            tracing::error!("Initial vault mirror sync failed: {}", e);
+        }
+
+        let mut dirty: HashSet<String> = HashSet::new();
+        let mut deleted: HashSet<String> = HashSet::new();
+        let mut full_resync = false;
+        let mut poll = self.config.external_edit_poll.map(tokio::time::interval);
+
+        loop {
+            let pending = full_resync || !dirty.is_empty() || !deleted.is_empty();
+            tokio::select! {
+                _ = &mut shutdown => {
+                    if pending {
+                        self.write_pending(&mut dirty, &mut deleted, &mut full_resync)
+                            .await;
+                    }
+                    tracing::info!("VaultMirrorService shutting down");
+                    break;
+                }
+                received = rx.recv() => match received {
+                    Ok(event) => Self::collect(event, &mut dirty, &mut deleted),
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        tracing::warn!("VaultMirrorService lagged, skipped {} events", skipped);
+                        full_resync = true;
+                    }
+                    Err(broadcast::error::RecvError::Closed) => {
+                        tracing::info!("VaultMirrorService event channel closed");
+                        break;
+                    }
+                },
+                _ = tokio::time::sleep(self.config.debounce), if pending => {
+                    self.write_pending(&mut dirty, &mut deleted, &mut full_resync)
+                        .await;
+                }
+                _ = next_poll(&mut poll) => {
+                    if let Err(e) = self.sync_external_edits().await {
+                        tracing::warn!("Vault mirror could not apply external edits: {}", e);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Write collected changes (or everything, after a lag) and reset them
+    async fn write_pending(
+        &self,
+        dirty: &mut HashSet<String>,
+        deleted: &mut HashSet<String>,
+        full_resync: &mut bool,
+    ) {
+        let result = if *full_resync {
+            self.sync_all().await.map(|_| ())
+        } else {
+            self.flush(dirty, deleted).await
+        };
+        if let Err(e) = result {
+            tracing::warn!("Vault mirror update failed: {}", e);
+        }
+        dirty.clear();
+        deleted.clear();
+        *full_resync = false;
+    }
+
+    /// Record the nodes an event touches
+    fn collect(event: DomainEvent, dirty: &mut HashSet<String>, deleted: &mut HashSet<String>) {
+        match event {
+            DomainEvent::NodeCreated { node_id, .. } | DomainEvent::NodeUpdated { node_id, .. } => {
+                dirty.insert(node_id);
+            }
+            DomainEvent::NodeDeleted { id, .. } => {
+                deleted.insert(id);
+            }
+            DomainEvent::RelationshipCreated { relationship, .. }
+            | DomainEvent::RelationshipUpdated { relationship, .. } => {
+                if relationship.relationship_type == "has_child" {
+                    dirty.insert(relationship.from_id);
+                    dirty.insert(relationship.to_id);
+                }
+            }
+            DomainEvent::RelationshipDeleted {
+                from_id,
+                to_id,
+                relationship_type,
+                ..
+            } => {
+                if relationship_type == "has_child" {
+                    dirty.insert(from_id);
+                    dirty.insert(to_id);
+                }
+            }
+        }
+    }
+
+    /// Write every document root and remove files of roots that no longer exist
+    pub async fn sync_all(&self) -> Result<MirrorSyncStats, NodeServiceError> {
+        let roots = self
+            .node_service
+            .store()
+            .get_children(None)
+            .await
+            .map_err(|e| NodeServiceError::query_failed(e.to_string()))?;
+
+        let mut state = self.state.lock().await;
+        self.load_manifest(&mut state).await;
+
+        let mut stats = MirrorSyncStats::default();
+        let mut live: HashSet<String> = HashSet::new();
+        for root in roots.iter().filter(|r| is_mirrored_type(&r.node_type)) {
+            live.insert(root.id.clone());
+            match self.write_root(&mut state, &root.id).await? {
+                WriteOutcome::Written => stats.written += 1,
+                WriteOutcome::Unchanged => stats.unchanged += 1,
+                WriteOutcome::Removed => stats.removed += 1,
+                WriteOutcome::Imported => stats.imported += 1,
+                WriteOutcome::Conflict => stats.conflicts += 1,
+                WriteOutcome::Skipped => {}
+            }
+        }
+
+        let stale: Vec<String> = state
+            .manifest
+            .files
+            .keys()
+            .filter(|id| !live.contains(*id))
+            .cloned()
+            .collect();
+        for root_id in stale {
+            self.remove_root_file(&mut state, &root_id).await?;
+            stats.removed += 1;
+        }
+
+        self.save_manifest(&state).await?;
+        Ok(stats)
+    }
+
+    /// Re-render the roots containing the given nodes
+    pub async fn sync_nodes(&self, node_ids: &HashSet<String>) -> Result<(), NodeServiceError> {
+        self.flush(node_ids, &HashSet::new()).await
+    }
+
+    /// Apply mirrored files edited since their last sync
+    ///
+    /// Runs on the poll interval in two-way mode; edits are also picked up by
+    /// any sync that re-renders the affected root.
+    pub async fn sync_external_edits(&self) -> Result<MirrorSyncStats, NodeServiceError> {
+        let mut state = self.state.lock().await;
+        self.load_manifest(&mut state).await;
+
+        let mut edited = Vec::new();
+        for (root_id, entry) in &state.manifest.files {
+            let path = self.config.folder.join(&entry.file);
+            if modified_time(&path)
+                .await
+                .is_some_and(|t| t > entry.synced_at)
+            {
+                edited.push(root_id.clone());
+            }
+        }
+
+        let mut stats = MirrorSyncStats::default();
+        if edited.is_empty() {
+            return Ok(stats);
+        }
+        for root_id in edited {
+            match self.write_root(&mut state, &root_id).await? {
+                WriteOutcome::Imported => stats.imported += 1,
+                WriteOutcome::Conflict => stats.conflicts += 1,
+                WriteOutcome::Removed => stats.removed += 1,
+                WriteOutcome::Written => stats.written += 1,
+                WriteOutcome::Unchanged | WriteOutcome::Skipped => {}
+            }
+        }
+        self.save_manifest(&state).await?;
+        Ok(stats)
+    }
+
+    async fn flush(
+        &self,
+        dirty: &HashSet<String>,
+        deleted: &HashSet<String>,
+    ) -> Result<(), NodeServiceError> {
+        let mut state = self.state.lock().await;
+        self.load_manifest(&mut state).await;
+
+        let mut roots: HashSet<String> = HashSet::new();
+        for id in deleted {
+            if let Some(root) = state.node_roots.remove(id) {
+                roots.insert(root);
+            }
+            // A deleted root is picked up by write_root finding nothing
+            if state.manifest.files.contains_key(id) {
+                roots.insert(id.clone());
+            }
+        }
+        for id in dirty {
+            // The previous root needs re-rendering too when a node moved away
+            if let Some(previous) = state.node_roots.get(id) {
+                roots.insert(previous.clone());
+            }
+            if state.manifest.files.contains_key(id) {
+                roots.insert(id.clone());
+            }
+            match self.node_service.get_root_id(id).await {
+                Ok(root) => {
+                    roots.insert(root);
+                }
+                Err(e) => tracing::debug!("Skipping mirror of {}: {}", id, e),
+            }
+        }
+
+        for root_id in roots {
+            self.write_root(&mut state, &root_id).await?;
+        }
+        self.save_manifest(&state).await
+    }
+
+    /// Render one root into its file, removing the file if the node is no
+    /// longer a mirrored root
+    async fn write_root(
+        &self,
+        state: &mut MirrorState,
+        root_id: &str,
+    ) -> Result<WriteOutcome, NodeServiceError> {
+        let Some((mut root, mut node_map, mut adjacency_list)) =
+            self.load_mirrored_root(root_id).await?
+        else {
+            return Ok(if state.manifest.files.contains_key(root_id) {
+                self.remove_root_file(state, root_id).await?;
+                WriteOutcome::Removed
+            } else {
+                WriteOutcome::Skipped
+            });
+        };
+
+        let mut external_outcome = None;
+        if let Some(previous) = state.manifest.files.get(root_id).cloned() {
+            let path = self.config.folder.join(&previous.file);
+            if let Some(external) = self.read_external_edit(&path, previous.synced_at).await {
+                let root_changed = node_map
+                    .values()
+                    .any(|n| n.modified_at > previous.synced_at);
+                let applied = !root_changed
+                    && self
+                        .apply_external_edit(&root, &node_map, &adjacency_list, &external)
+                        .await?;
+                if applied {
+                    tracing::info!("Applied external edit of {:?} to {}", path, root_id);
+                    match self.load_mirrored_root(root_id).await? {
+                        Some(reloaded) => (root, node_map, adjacency_list) = reloaded,
+                        None => return Ok(WriteOutcome::Imported),
+                    }
+                    external_outcome = Some(WriteOutcome::Imported);
+                } else {
+                    let copy = conflict_copy_path(&path, Utc::now());
+                    tracing::warn!(
+                        "External edit of {:?} not applied to {}, kept as {:?}",
+                        path,
+                        root_id,
+                        copy
+                    );
+                    write_atomic(&copy, &external).await?;
+                    external_outcome = Some(WriteOutcome::Conflict);
+                }
+            }
+        }
+
+        let file_name = mirror_file_name(&root);
+        let content = render_mirror_file(&root, &node_map, &adjacency_list);
+
+        if let Some(previous) = state.manifest.files.get(root_id) {
+            if previous.file != file_name {
+                remove_file_if_exists(&self.config.folder.join(&previous.file)).await?;
+            }
+        }
+        for id in node_map.keys() {
+            state.node_roots.insert(id.clone(), root_id.to_string());
+        }
+
+        let path = self.config.folder.join(&file_name);
+        let unchanged =
+            tokio::fs::read_to_string(&path).await.ok().as_deref() == Some(content.as_str());
+        if !unchanged {
+            write_atomic(&path, &content).await?;
+        }
+
+        // Taken after writing so neither our own write nor the nodes just
+        // rendered look newer than the sync point
+        let synced_at = modified_time(&path)
+            .await
+            .map_or_else(Utc::now, |t| t.max(Utc::now()));
+        state.manifest.files.insert(
+            root_id.to_string(),
+            MirroredFile {
+                file: file_name,
+                synced_at,
+            },
+        );
+
+        Ok(match external_outcome {
+            Some(outcome) => outcome,
+            None if unchanged => WriteOutcome::Unchanged,
+            None => WriteOutcome::Written,
+        })
+    }
+
+    /// Subtree of `root_id` if it exists and is a mirrored document root
+    async fn load_mirrored_root(
+        &self,
+        root_id: &str,
+    ) -> Result<Option<(Node, HashMap<String, Node>, HashMap<String, Vec<String>>)>, NodeServiceError>
+    {
+        let (root, node_map, adjacency_list) = self.node_service.get_subtree_data(root_id).await?;
+        let Some(root) = root.filter(|r| is_mirrored_type(&r.node_type)) else {
+            return Ok(None);
+        };
+        if self.node_service.get_parent(root_id).await?.is_some() {
+            return Ok(None);
+        }
+        Ok(Some((root, node_map, adjacency_list)))
+    }
+
+    /// Content of `path` if two-way sync is on and it was edited after `synced_at`
+    async fn read_external_edit(&self, path: &Path, synced_at: DateTime<Utc>) -> Option<String> {
+        self.config.external_edit_poll?;
+        if modified_time(path).await? <= synced_at {
+            return None;
+        }
+        tokio::fs::read_to_string(path).await.ok()
+    }
+
+    /// Write an edited mirror file back to its root
+    ///
+    /// Returns `false` when the edit can't be applied safely: the file belongs
+    /// to another root, the subtree is deeper than the file can show (the
+    /// hidden nodes would be lost), or the edit can't be matched to the
+    /// existing nodes (see [`plan_child_edits`]). Nothing is written then.
+    async fn apply_external_edit(
+        &self,
+        root: &Node,
+        node_map: &HashMap<String, Node>,
+        adjacency_list: &HashMap<String, Vec<String>>,
+        external: &str,
+    ) -> Result<bool, NodeServiceError> {
+        let (file_id, body) = split_frontmatter(external);
+        if file_id.is_some_and(|id| id != root.id) {
+            return Ok(false);
+        }
+        let (markdown, rendered_count) = render_subtree_markdown(
+            root,
+            node_map,
+            adjacency_list,
+            true,
+            MAX_RENDER_DEPTH,
+            false,
+        );
+        if rendered_count < node_map.len() {
+            return Ok(false);
+        }
+
+        let (title, children) = split_title(body);
+        let (current_title, current_children) = split_title(&markdown);
+
+        let rendered = parse_file_nodes(current_children)?;
+        let edited = parse_file_nodes(children)?;
+        let mut deletes = Vec::new();
+        let Some(edits) = plan_child_edits(
+            &root.id,
+            &rendered,
+            &edited,
+            node_map,
+            adjacency_list,
+            &mut deletes,
+        ) else {
+            return Ok(false);
+        };
+
+        if !title.is_empty() && title != current_title {
+            self.node_service
+                .update_node(
+                    &root.id,
+                    root.version,
+                    NodeUpdate::new().with_content(title.to_string()),
+                )
+                .await?;
+        }
+        for id in deletes {
+            // Versions move as deletes cascade, so read them just before
+            if let Some(node) = self.node_service.get_node(&id).await? {
+                self.node_service.delete_node(&id, node.version).await?;
+            }
+        }
+        self.apply_child_edits(&root.id, &edits).await?;
+        Ok(true)
+    }
+
+    /// Apply planned edits to the children of `parent_id`, in file order
+    async fn apply_child_edits(
+        &self,
+        parent_id: &str,
+        edits: &[ChildEdit<'_>],
+    ) -> Result<(), NodeServiceError> {
+        let mut previous: Option<String> = None;
+        for edit in edits {
+            let id = match edit {
+                ChildEdit::Keep {
+                    id,
+                    version,
+                    content,
+                    children,
+                } => {
+                    if let Some(content) = content {
+                        self.node_service
+                            .update_node(
+                                id,
+                                *version,
+                                NodeUpdate::new().with_content(content.clone()),
+                            )
+                            .await?;
+                    }
+                    Box::pin(self.apply_child_edits(id, children)).await?;
+                    id.clone()
+                }
+                ChildEdit::Create(node) => {
+                    Box::pin(self.create_file_node(parent_id, previous.clone(), node)).await?
+                }
+            };
+            previous = Some(id);
+        }
+        Ok(())
+    }
+
+    /// Create a node added in a mirror file, with its children
+    async fn create_file_node(
+        &self,
+        parent_id: &str,
+        insert_after_node_id: Option<String>,
+        node: &FileNode,
+    ) -> Result<String, NodeServiceError> {
+        let id = self
+            .node_service
+            .create_node_with_parent(CreateNodeParams {
+                id: None,
+                node_type: node.node_type.clone(),
+                content: node.content.clone(),
+                parent_id: Some(parent_id.to_string()),
+                insert_after_node_id,
+                properties: json!({}),
+            })
+            .await?;
+        let mut previous = None;
+        for child in &node.children {
+            previous = Some(Box::pin(self.create_file_node(&id, previous, child)).await?);
+        }
+        Ok(id)
+    }
+
+    async fn remove_root_file(
+        &self,
+        state: &mut MirrorState,
+        root_id: &str,
+    ) -> Result<(), NodeServiceError> {
+        if let Some(entry) = state.manifest.files.remove(root_id) {
+            remove_file_if_exists(&self.config.folder.join(entry.file)).await?;
+        }
+        state.node_roots.retain(|_, root| root != root_id);
+        Ok(())
+    }
+
+    async fn load_manifest(&self, state: &mut MirrorState) {
+        if state.loaded {
+            return;
+        }
+        let path = self.config.folder.join(MIRROR_MANIFEST_FILE);
+        if let Ok(raw) = tokio::fs::read_to_string(&path).await {
+            match serde_json::from_str(&raw) {
+                Ok(manifest) => state.manifest = manifest,
+                Err(e) => tracing::warn!("Ignoring unreadable mirror manifest: {}", e),
+            }
+        }
+        state.loaded = true;
+    }
+
+    async fn save_manifest(&self, state: &MirrorState) -> Result<(), NodeServiceError> {
+        let raw = serde_json::to_string_pretty(&state.manifest)
+            .map_err(|e| NodeServiceError::serialization_error(e.to_string()))?;
+        write_atomic(&self.config.folder.join(MIRROR_MANIFEST_FILE), &raw).await
+    }
+}
+
+fn is_mirrored_type(node_type: &str) -> bool {
+    !EXCLUDED_ROOT_TYPES.contains(&node_type)
+}
+
+/// Stable file name for a root
+///
+/// Lowercase slug of the title's first line plus an 8-character ID prefix so
+/// roots with the same title don't collide. Roots whose ID already is their
+/// slug (date nodes) use it alone.
+pub fn mirror_file_name(root: &Node) -> String {
+    let title = root.content.lines().next().unwrap_or("");
+    let mut slug = String::new();
+    for c in title.trim_start_matches('#').trim().chars() {
+        if c.is_alphanumeric() {
+            slug.extend(c.to_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+        if slug.chars().count() >= MAX_SLUG_LEN {
+            break;
+        }
+    }
+    let slug = slug.trim_end_matches('-');
+
+    if slug == root.id {
+        return format!("{}.md", slug);
+    }
+    let id_prefix: String = root
+        .id
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .take(8)
+        .collect();
+    if slug.is_empty() {
+        format!("untitled-{}.md", id_prefix)
+    } else {
+        format!("{}-{}.md", slug, id_prefix)
+    }
+}
+
+/// File content for a root: frontmatter followed by the markdown export
+pub fn render_mirror_file(
+    root: &Node,
+    node_map: &HashMap<String, Node>,
+    adjacency_list: &HashMap<String, Vec<String>>,
+) -> String {
+    let (markdown, _) = render_subtree_markdown(
+        root,
+        node_map,
+        adjacency_list,
+        true,
+        MAX_RENDER_DEPTH,
+        false,
+    );
+    format!(
+        "---\nnodespace_id: {}\nnode_type: {}\n---\n\n{}\n",
+        root.id,
+        root.node_type,
+        markdown.trim_end()
+    )
+}
+
+/// Split off the mirror frontmatter, returning its `nodespace_id` and the body
+fn split_frontmatter(content: &str) -> (Option<&str>, &str) {
+    let Some(rest) = content.strip_prefix("---\n") else {
+        return (None, content);
+    };
+    let Some(end) = rest.find("\n---\n") else {
+        return (None, content);
+    };
+    let id = rest[..end]
+        .lines()
+        .find_map(|line| line.strip_prefix("nodespace_id:"))
+        .map(str::trim);
+    (id, &rest[end + "\n---\n".len()..])
+}
+
+/// Split a rendered body into the root's title and the children's markdown
+fn split_title(body: &str) -> (&str, &str) {
+    let body = body.trim();
+    match body.split_once("\n\n") {
+        Some((title, children)) => (title.trim_end(), children.trim()),
+        None => (body, ""),
+    }
+}
+
+/// A node of a mirror file's body, as the markdown parser reads it
+#[derive(Debug)]
+struct FileNode {
+    node_type: String,
+    content: String,
+    children: Vec<FileNode>,
+}
+
+/// Same type and content; children are matched separately
+impl PartialEq for FileNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.node_type == other.node_type && self.content == other.content
+    }
+}
+
+/// Parse the children part of a mirror file into a tree
+fn parse_file_nodes(markdown: &str) -> Result<Vec<FileNode>, NodeServiceError> {
+    // Stand-in parent of the top-level nodes
+    const TOP: &str = "";
+    let prepared = prepare_nodes_from_markdown(markdown, Some(TOP.to_string()))
+        .map_err(|e| NodeServiceError::invalid_input(e.message))?;
+
+    // Prepared nodes come in file order, so siblings stay in order
+    let mut by_parent: HashMap<String, Vec<PreparedNode>> = HashMap::new();
+    for node in prepared {
+        by_parent
+            .entry(node.parent_id.clone().unwrap_or_default())
+            .or_default()
+            .push(node);
+    }
+    fn build(parent: &str, by_parent: &mut HashMap<String, Vec<PreparedNode>>) -> Vec<FileNode> {
+        by_parent
+            .remove(parent)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|node| FileNode {
+                children: build(&node.id, by_parent),
+                node_type: node.node_type,
+                content: node.content,
+            })
+            .collect()
+    }
+    Ok(build(TOP, &mut by_parent))
+}
+
+/// What an edited mirror file does to one child of an existing node
+#[derive(Debug, PartialEq)]
+enum ChildEdit<'a> {
+    /// Existing node, with new content when the file changed it
+    Keep {
+        id: String,
+        version: i64,
+        content: Option<String>,
+        children: Vec<ChildEdit<'a>>,
+    },
+    /// Node added in the file, to be created with its children
+    Create(&'a FileNode),
+}
+
+/// Match the edited children of `parent_id` against its existing children
+///
+/// `rendered` is the parse of the file as last written, which lines up one to
+/// one with the existing nodes. Nodes the edit left alone are matched as the
+/// longest common subsequence; between two matches, nodes of the same type
+/// at the same position count as edited. Existing nodes left unmatched are
+/// pushed to `deletes` (deleting one deletes its subtree).
+///
+/// Returns `None` when the existing nodes don't line up with the rendering,
+/// or an edited node's content isn't what its markdown shows (a task renders
+/// as a checkbox line): writing the edit back would lose data.
+fn plan_child_edits<'a>(
+    parent_id: &str,
+    rendered: &[FileNode],
+    edited: &'a [FileNode],
+    node_map: &HashMap<String, Node>,
+    adjacency_list: &HashMap<String, Vec<String>>,
+    deletes: &mut Vec<String>,
+) -> Option<Vec<ChildEdit<'a>>> {
+    let existing = adjacency_list
+        .get(parent_id)
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+    if existing.len() != rendered.len() {
+        return None;
+    }
+
+    // common[i][j]: longest common subsequence of rendered[i..] and edited[j..]
+    let (n, m) = (rendered.len(), edited.len());
+    let mut common = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            common[i][j] = if rendered[i] == edited[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+    let mut matches = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if rendered[i] == edited[j] {
+            matches.push((i, j));
+            i += 1;
+            j += 1;
+        } else if common[i + 1][j] >= common[i][j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    matches.push((n, m));
+
+    let keep = |i: usize, edited: &'a FileNode, deletes: &mut Vec<String>| {
+        let id = &existing[i];
+        let node = node_map.get(id)?;
+        let content = (edited.content != rendered[i].content).then(|| edited.content.clone());
+        if content.is_some()
+            && (node.node_type != rendered[i].node_type || node.content != rendered[i].content)
+        {
+            return None;
+        }
+        Some(ChildEdit::Keep {
+            id: id.clone(),
+            version: node.version,
+            content,
+            children: plan_child_edits(
+                id,
+                &rendered[i].children,
+                &edited.children,
+                node_map,
+                adjacency_list,
+                deletes,
+            )?,
+        })
+    };
+
+    let mut edits = Vec::with_capacity(m);
+    let (mut i, mut j) = (0, 0);
+    for (matched_i, matched_j) in matches {
+        while i < matched_i || j < matched_j {
+            if i < matched_i && j < matched_j && rendered[i].node_type == edited[j].node_type {
+                edits.push(keep(i, &edited[j], deletes)?);
+                i += 1;
+                j += 1;
+            } else if i < matched_i {
+                deletes.push(existing[i].clone());
+                i += 1;
+            } else {
+                edits.push(ChildEdit::Create(&edited[j]));
+                j += 1;
+            }
+        }
+        if matched_i < n {
+            edits.push(keep(matched_i, &edited[matched_j], deletes)?);
+            i += 1;
+            j += 1;
+        }
+    }
+    Some(edits)
+}
+
+/// `notes-1a2b3c4d.md` → `notes-1a2b3c4d.conflict-20250115-093000.md`
+fn conflict_copy_path(path: &Path, at: DateTime<Utc>) -> PathBuf {
+    let stem = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    path.with_file_name(format!(
+        "{}.conflict-{}.md",
+        stem,
+        at.format("%Y%m%d-%H%M%S")
+    ))
+}
+
+async fn modified_time(path: &Path) -> Option<DateTime<Utc>> {
+    let metadata = tokio::fs::metadata(path).await.ok()?;
+    metadata.modified().ok().map(DateTime::<Utc>::from)
+}
+
+/// Wait for the next external edit poll, or forever in one-way mode
+async fn next_poll(poll: &mut Option<tokio::time::Interval>) {
+    match poll {
+        Some(interval) => {
+            interval.tick().await;
+        }
+        None => std::future::pending().await,
+    }
+}
+
+/// Write via a temp file so readers never see a half-written file
+async fn write_atomic(path: &Path, content: &str) -> Result<(), NodeServiceError> {
+    let io_err = |e: std::io::Error| {
+        NodeServiceError::query_failed(format!("Vault mirror write to {:?} failed: {}", path, e))
+    };
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent).await.map_err(io_err)?;
+    }
+    let tmp = path.with_extension("tmp");
+    tokio::fs::write(&tmp, content).await.map_err(io_err)?;
+    tokio::fs::rename(&tmp, path).await.map_err(io_err)
+}
+
+async fn remove_file_if_exists(path: &Path) -> Result<(), NodeServiceError> {
+    match tokio::fs::remove_file(path).await {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(NodeServiceError::query_failed(format!(
+            "Vault mirror could not remove {:?}: {}",
+            path, e
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn node(id: &str, node_type: &str, content: &str) -> Node {
+        let mut node = Node::new(node_type.to_string(), content.to_string(), json!({}));
+        node.id = id.to_string();
+        node
+    }
+
+    #[test]
+    fn test_file_names_are_stable_slugs() {
+        let root = node("3f2a9c1e-77b0-4c11", "text", "# Project Plan: Q3 / Launch!");
+        assert_eq!(
+            mirror_file_name(&root),
+            "project-plan-q3-launch-3f2a9c1e.md"
+        );
+
+        let date = node("2025-01-15", "date", "2025-01-15");
+        assert_eq!(mirror_file_name(&date), "2025-01-15.md");
+
+        let empty = node("abcdef123456", "text", "   ");
+        assert_eq!(mirror_file_name(&empty), "untitled-abcdef12.md");
+    }
+
+    #[test]
+    fn test_render_mirror_file_has_frontmatter_and_children() {
+        let root = node("root-1", "text", "# Notes");
+        let mut node_map = HashMap::new();
+        node_map.insert("root-1".to_string(), root.clone());
+        node_map.insert(
+            "child-1".to_string(),
+            node("child-1", "text", "First point"),
+        );
+        let adjacency = HashMap::from([("root-1".to_string(), vec!["child-1".to_string()])]);
+
+        let content = render_mirror_file(&root, &node_map, &adjacency);
+        assert_eq!(
+            content,
+            "---\nnodespace_id: root-1\nnode_type: text\n---\n\n# Notes\n\nFirst point\n"
+        );
+    }
+
+    #[test]
+    fn test_mirror_file_splits_back_into_title_and_children() {
+        let content =
+            "---\nnodespace_id: root-1\nnode_type: text\n---\n\n# Notes\n\n- one\n  - two\n";
+        let (id, body) = split_frontmatter(content);
+        assert_eq!(id, Some("root-1"));
+        assert_eq!(split_title(body), ("# Notes", "- one\n  - two"));
+
+        assert_eq!(split_frontmatter("# Plain"), (None, "# Plain"));
+        assert_eq!(split_title("Only a title\n"), ("Only a title", ""));
+    }
+
+    #[test]
+    fn test_external_edit_keeps_unchanged_nodes() {
+        let root = node("root-1", "text", "# Notes");
+        let mut node_map = HashMap::new();
+        for n in [
+            root.clone(),
+            node("first", "text", "First point"),
+            node("second", "header", "## Second"),
+            node("nested", "text", "Nested point"),
+            node("aside", "text", "Aside"),
+            node("task", "task", "Call Sam"),
+        ] {
+            node_map.insert(n.id.clone(), n);
+        }
+        let adjacency = HashMap::from([
+            (
+                "root-1".to_string(),
+                vec!["first".to_string(), "second".to_string()],
+            ),
+            (
+                "second".to_string(),
+                vec![
+                    "nested".to_string(),
+                    "aside".to_string(),
+                    "task".to_string(),
+                ],
+            ),
+        ]);
+        let (markdown, _) =
+            render_subtree_markdown(&root, &node_map, &adjacency, true, MAX_RENDER_DEPTH, false);
+        let current = split_title(&markdown).1;
+        let rendered = parse_file_nodes(current).unwrap();
+
+        let edited_markdown = format!(
+            "Opening line\n\n{}\n\nClosing thoughts",
+            current
+                .replace("Nested point", "Nested point, reworded")
+                .replace("- Aside\n\n", "")
+        );
+        let edited = parse_file_nodes(&edited_markdown).unwrap();
+        let mut deletes = Vec::new();
+        let edits = plan_child_edits(
+            "root-1",
+            &rendered,
+            &edited,
+            &node_map,
+            &adjacency,
+            &mut deletes,
+        )
+        .unwrap();
+
+        assert_eq!(deletes, vec!["aside".to_string()]);
+        assert!(matches!(
+            &edits[..],
+            [
+                ChildEdit::Create(FileNode { content, .. }),
+                ChildEdit::Keep { id: first, content: None, .. },
+                ChildEdit::Keep { id: second, content: None, .. },
+            ] if content == "Opening line" && first == "first" && second == "second"
+        ));
+        let ChildEdit::Keep { children, .. } = &edits[2] else {
+            unreachable!()
+        };
+        assert!(matches!(
+            &children[..],
+            [
+                ChildEdit::Keep { id: nested, content: Some(reworded), .. },
+                ChildEdit::Keep { id: task, content: None, .. },
+                ChildEdit::Create(FileNode { content: closing, .. }),
+            ] if nested == "nested"
+                && reworded == "Nested point, reworded"
+                && task == "task"
+                && closing == "Closing thoughts"
+        ));
+
+        // A task renders as a checkbox line, so editing it can't be written back
+        let edited = parse_file_nodes(&current.replace("Call Sam", "Call Alex")).unwrap();
+        assert!(plan_child_edits(
+            "root-1",
+            &rendered,
+            &edited,
+            &node_map,
+            &adjacency,
+            &mut Vec::new(),
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn test_conflict_copy_sits_next_to_file() {
+        let at = Utc.with_ymd_and_hms(2025, 1, 15, 9, 30, 0).unwrap();
+        assert_eq!(
+            conflict_copy_path(Path::new("/vault/notes-1a2b3c4d.md"), at),
+            PathBuf::from("/vault/notes-1a2b3c4d.conflict-20250115-093000.md")
+        );
+    }
+
+    #[test]
+    fn test_excluded_types_are_not_mirrored() {
+        assert!(is_mirrored_type("text"));
+        assert!(is_mirrored_type("date"));
+        assert!(!is_mirrored_type("schema"));
+        assert!(!is_mirrored_type("collection"));
+    }
+}
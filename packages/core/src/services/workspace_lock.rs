@@ -0,0 +1,149 @@
+//! Workspace Lock - Passphrase Lock and Idle Timeout
+//!
+//! Once a passphrase is set, the workspace starts locked and can be locked
+//! again by hand or after a stretch without user activity. While locked,
+//! NodeService refuses to read or write nodes
+//! ([`NodeServiceError::WorkspaceLocked`]) and its cached project rollups are
+//! dropped; unlocking with the passphrase is the only way back.
+//!
+//! The lock guards access through NodeService (Tauri commands, MCP, scripts),
+//! whose entry points all go through `NodeService::ensure_unlocked`; the
+//! QueryService and semantic search hold the same lock. It does not encrypt
+//! the database file.
+//!
+//! # Activity
+//!
+//! Idle time is measured from the last [`WorkspaceLock::record_activity`]
+//! call, which the UI makes on user input. Background work (embeddings,
+//! schedulers) does not count as activity, so it cannot keep a forgotten
+//! workspace open.
+
+use super::error::NodeServiceError;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+/// Lock state shared by every clone of a NodeService
+#[derive(Debug)]
+pub struct WorkspaceLock {
+    locked: AtomicBool,
+    has_passphrase: AtomicBool,
+    idle: Mutex<IdleState>,
+}
+
+#[derive(Debug)]
+struct IdleState {
+    last_activity: Instant,
+    /// Lock after this long without activity (never when unset)
+    auto_lock_after: Option<Duration>,
+}
+
+impl WorkspaceLock {
+    /// Lock state for a workspace with or without a passphrase
+    ///
+    /// A workspace with a passphrase starts locked.
+    pub fn new(has_passphrase: bool) -> Self {
+        Self {
+            locked: AtomicBool::new(has_passphrase),
+            has_passphrase: AtomicBool::new(has_passphrase),
+            idle: Mutex::new(IdleState {
+                last_activity: Instant::now(),
+                auto_lock_after: None,
+            }),
+        }
+    }
+
+    pub fn is_locked(&self) -> bool {
+        self.locked.load(Ordering::SeqCst)
+    }
+
+    pub fn has_passphrase(&self) -> bool {
+        self.has_passphrase.load(Ordering::SeqCst)
+    }
+
+    /// Fail with [`NodeServiceError::WorkspaceLocked`] while locked
+    pub fn ensure_unlocked(&self) -> Result<(), NodeServiceError> {
+        if self.is_locked() {
+            Err(NodeServiceError::WorkspaceLocked)
+        } else {
+            Ok(())
+        }
+    }
+
+    pub(crate) fn set_has_passphrase(&self, has_passphrase: bool) {
+        self.has_passphrase.store(has_passphrase, Ordering::SeqCst);
+    }
+
+    /// Lock or unlock; unlocking counts as activity
+    pub(crate) fn set_locked(&self, locked: bool) {
+        if !locked {
+            self.record_activity();
+        }
+        self.locked.store(locked, Ordering::SeqCst);
+    }
+
+    /// Note user activity, restarting the idle timeout
+    pub fn record_activity(&self) {
+        self.idle().last_activity = Instant::now();
+    }
+
+    pub fn auto_lock_after(&self) -> Option<Duration> {
+        self.idle().auto_lock_after
+    }
+
+    /// Set the idle timeout (`None` disables auto-lock)
+    pub fn set_auto_lock_after(&self, after: Option<Duration>) {
+        let mut idle = self.idle();
+        idle.auto_lock_after = after;
+        idle.last_activity = Instant::now();
+    }
+
+    /// Whether an unlocked workspace with a passphrase has been idle too long
+    pub fn is_idle_expired(&self) -> bool {
+        if self.is_locked() || !self.has_passphrase() {
+            return false;
+        }
+        let idle = self.idle();
+        idle.auto_lock_after
+            .is_some_and(|after| idle.last_activity.elapsed() >= after)
+    }
+
+    fn idle(&self) -> MutexGuard<'_, IdleState> {
+        self.idle.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_passphrase_workspace_starts_locked() {
+        let lock = WorkspaceLock::new(true);
+        assert!(matches!(
+            lock.ensure_unlocked(),
+            Err(NodeServiceError::WorkspaceLocked)
+        ));
+        lock.set_locked(false);
+        assert!(lock.ensure_unlocked().is_ok());
+
+        assert!(!WorkspaceLock::new(false).is_locked());
+    }
+
+    #[test]
+    fn test_idle_expiry() {
+        let lock = WorkspaceLock::new(true);
+        lock.set_locked(false);
+        assert!(!lock.is_idle_expired(), "auto-lock is off by default");
+
+        lock.set_auto_lock_after(Some(Duration::ZERO));
+        assert!(lock.is_idle_expired());
+        lock.set_auto_lock_after(Some(Duration::from_secs(3600)));
+        assert!(!lock.is_idle_expired());
+
+        // Nothing to unlock with, so never expire into a locked state
+        let open = WorkspaceLock::new(false);
+        open.set_auto_lock_after(Some(Duration::ZERO));
+        assert!(!open.is_idle_expired());
+    }
+}
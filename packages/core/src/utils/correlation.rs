@@ -0,0 +1,56 @@
+//! Correlation IDs of Tauri, MCP and dev-server requests
+//!
+//! Each request runs under a fresh correlation ID (see
+//! [`with_correlation_id`]). The ID is a field of the request's tracing span,
+//! so every log line from the command handler down to the SurrealDB query
+//! carries it; domain events and audit log entries record it, and error
+//! envelopes return it, so a failure reported from the UI can be found in
+//! the logs.
+//!
+//! The ID lives in a task-local: work spawned onto other tasks doesn't
+//! inherit it.
+
+use std::future::Future;
+use tracing::Instrument;
+
+tokio::task_local! {
+    static CORRELATION_ID: String;
+}
+
+/// A new correlation ID
+pub fn new_correlation_id() -> String {
+    uuid::Uuid::new_v4().simple().to_string()
+}
+
+/// The correlation ID of the request the current task is handling
+pub fn current_correlation_id() -> Option<String> {
+    CORRELATION_ID.try_with(Clone::clone).ok()
+}
+
+/// Run `future` as the request identified by `correlation_id`
+pub async fn with_correlation_id<F>(correlation_id: String, future: F) -> F::Output
+where
+    F: Future,
+{
+    let span = tracing::info_span!("request", correlation_id = %correlation_id);
+    CORRELATION_ID
+        .scope(correlation_id, future.instrument(span))
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_correlation_id_is_scoped_to_the_request() {
+        assert_eq!(current_correlation_id(), None);
+
+        let id = new_correlation_id();
+        let seen = with_correlation_id(id.clone(), async { current_correlation_id() }).await;
+        assert_eq!(seen, Some(id));
+
+        assert_eq!(current_correlation_id(), None);
+        assert_ne!(new_correlation_id(), new_correlation_id());
+    }
+}
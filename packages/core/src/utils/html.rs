@@ -0,0 +1,437 @@
+//! HTML to markdown conversion for clipboard and web imports
+//!
+//! A small, dependency-free converter that maps the block structure of HTML
+//! (headings, paragraphs, nested lists, code blocks, blockquotes) onto the
+//! markdown dialect understood by the markdown import parser. Inline styling
+//! (bold, italic, code, links, images) is preserved; unknown tags are dropped and their
+//! text kept. This is intentionally lenient: clipboard HTML is rarely well-formed.
+
+/// Convert an HTML fragment to markdown
+///
+/// # Examples
+///
+/// ```rust
+/// use nodespace_core::utils::html_to_markdown;
+///
+/// let md = html_to_markdown("<h2>Plan</h2><ul><li>One<ul><li>Nested</li></ul></li></ul>");
+/// assert_eq!(md, "## Plan\n\n- One\n  - Nested");
+/// ```
+pub fn html_to_markdown(html: &str) -> String {
+    let mut converter = Converter::default();
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix("<!--") {
+            rest = after.find("-->").map(|end| &after[end + 3..]).unwrap_or("");
+            continue;
+        }
+
+        if rest.starts_with('<') {
+            if let Some(end) = rest.find('>') {
+                let tag = Tag::parse(&rest[1..end]);
+                rest = &rest[end + 1..];
+
+                // Skip raw-text elements entirely
+                if !tag.closing && matches!(tag.name.as_str(), "script" | "style" | "head") {
+                    let close = format!("</{}", tag.name);
+                    rest = find_ascii_case_insensitive(rest, &close)
+                        .and_then(|pos| rest[pos..].find('>').map(|e| &rest[pos + e + 1..]))
+                        .unwrap_or("");
+                    continue;
+                }
+
+                converter.tag(tag);
+                continue;
+            }
+        }
+
+        let next = rest[1..].find('<').map(|i| i + 1).unwrap_or(rest.len());
+        converter.text(&decode_entities(&rest[..next]));
+        rest = &rest[next..];
+    }
+
+    converter.finish()
+}
+
+/// Decode the HTML entities commonly found in clipboard payloads
+pub fn decode_entities(text: &str) -> String {
+    if !text.contains('&') {
+        return text.to_string();
+    }
+
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(pos) = rest.find('&') {
+        out.push_str(&rest[..pos]);
+        rest = &rest[pos..];
+        let Some(end) = rest.find(';').filter(|e| *e <= 10) else {
+            out.push('&');
+            rest = &rest[1..];
+            continue;
+        };
+
+        let entity = &rest[1..end];
+        let decoded = match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" | "#39" => Some('\''),
+            "nbsp" => Some(' '),
+            _ if entity.starts_with("#x") || entity.starts_with("#X") => {
+                u32::from_str_radix(&entity[2..], 16)
+                    .ok()
+                    .and_then(char::from_u32)
+            }
+            _ if entity.starts_with('#') => entity[1..].parse().ok().and_then(char::from_u32),
+            _ => None,
+        };
+
+        match decoded {
+            Some(c) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+fn find_ascii_case_insensitive(haystack: &str, needle: &str) -> Option<usize> {
+    haystack
+        .to_ascii_lowercase()
+        .find(&needle.to_ascii_lowercase())
+}
+
+/// A parsed start or end tag
+struct Tag {
+    name: String,
+    closing: bool,
+    attrs: String,
+}
+
+impl Tag {
+    fn parse(raw: &str) -> Self {
+        let raw = raw.trim().trim_end_matches('/');
+        let (closing, raw) = match raw.strip_prefix('/') {
+            Some(rest) => (true, rest),
+            None => (false, raw),
+        };
+        let name_end = raw.find(|c: char| c.is_whitespace()).unwrap_or(raw.len());
+        Self {
+            name: raw[..name_end].to_ascii_lowercase(),
+            closing,
+            attrs: raw[name_end..].to_string(),
+        }
+    }
+
+    /// Read an attribute value (quoted or bare)
+    fn attr(&self, name: &str) -> Option<String> {
+        let lower = self.attrs.to_ascii_lowercase();
+        let mut search_from = 0;
+        while let Some(pos) = lower[search_from..].find(name) {
+            let start = search_from + pos;
+            search_from = start + name.len();
+            let boundary_ok = start == 0
+                || lower[..start]
+                    .chars()
+                    .last()
+                    .is_some_and(|c| c.is_whitespace());
+            let rest = self.attrs[start + name.len()..].trim_start();
+            if !boundary_ok {
+                continue;
+            }
+            let Some(value) = rest.strip_prefix('=') else {
+                return Some(String::new());
+            };
+            let value = value.trim_start();
+            let parsed = match value.chars().next() {
+                Some(q @ ('"' | '\'')) => value[1..].split(q).next().unwrap_or(""),
+                _ => value.split_whitespace().next().unwrap_or(""),
+            };
+            return Some(decode_entities(parsed));
+        }
+        None
+    }
+}
+
+#[derive(Clone, Copy)]
+enum ListKind {
+    Unordered,
+    Ordered,
+}
+
+#[derive(Default)]
+struct Converter {
+    out: String,
+    /// Current line being built (flushed on block boundaries)
+    line: String,
+    lists: Vec<ListKind>,
+    quote_depth: usize,
+    in_pre: bool,
+    link_href: Vec<Option<String>>,
+}
+
+impl Converter {
+    fn tag(&mut self, tag: Tag) {
+        match (tag.name.as_str(), tag.closing) {
+            ("h1" | "h2" | "h3" | "h4" | "h5" | "h6", false) => {
+                self.block_break();
+                let level = tag.name[1..].parse::<usize>().unwrap_or(1);
+                self.line.push_str(&"#".repeat(level));
+                self.line.push(' ');
+            }
+            ("h1" | "h2" | "h3" | "h4" | "h5" | "h6", true) => self.block_break(),
+            ("p" | "div" | "section" | "article" | "tr" | "table", _) if self.lists.is_empty() => {
+                self.block_break();
+            }
+            ("br", _) => {
+                if self.in_pre {
+                    self.line.push('\n');
+                } else if self.lists.is_empty() {
+                    self.flush_line();
+                } else {
+                    self.push_space();
+                }
+            }
+            ("hr", _) => self.block_break(),
+            ("ul", false) => {
+                self.start_list_block();
+                self.lists.push(ListKind::Unordered);
+            }
+            ("ol", false) => {
+                self.start_list_block();
+                self.lists.push(ListKind::Ordered);
+            }
+            ("ul" | "ol", true) => {
+                self.flush_line();
+                self.lists.pop();
+                if self.lists.is_empty() {
+                    self.block_break();
+                }
+            }
+            ("li", false) => {
+                self.flush_line();
+                let depth = self.lists.len().max(1);
+                self.line.push_str(&"  ".repeat(depth - 1));
+                match self.lists.last() {
+                    Some(ListKind::Ordered) if depth == 1 => self.line.push_str("1. "),
+                    _ => self.line.push_str("- "),
+                }
+            }
+            ("li", true) => self.flush_line(),
+            ("input", false) if tag.attr("type").as_deref() == Some("checkbox") => {
+                let checked = tag.attr("checked").is_some();
+                self.line.push_str(if checked { "[x] " } else { "[ ] " });
+            }
+            ("pre", false) => {
+                self.block_break();
+                self.out.push_str("```\n");
+                self.in_pre = true;
+            }
+            ("pre", true) => {
+                self.in_pre = false;
+                let code = std::mem::take(&mut self.line);
+                self.out.push_str(code.trim_end_matches('\n'));
+                self.out.push_str("\n```\n\n");
+            }
+            ("blockquote", false) => {
+                self.block_break();
+                self.quote_depth += 1;
+            }
+            ("blockquote", true) => {
+                self.flush_line();
+                self.quote_depth = self.quote_depth.saturating_sub(1);
+                self.block_break();
+            }
+            ("strong" | "b", _) => self.push_inline("**"),
+            ("em" | "i", _) => self.push_inline("*"),
+            ("s" | "del" | "strike", _) => self.push_inline("~~"),
+            ("code", _) if !self.in_pre => self.push_inline("`"),
+            ("a", false) => {
+                let href = tag.attr("href");
+                if href.is_some() {
+                    self.push_inline("[");
+                }
+                self.link_href.push(href);
+            }
+            ("img", false) => {
+                if let Some(src) = tag.attr("src") {
+                    let alt = tag.attr("alt").unwrap_or_default();
+                    self.line.push_str(&format!("![{}]({})", alt, src));
+                }
+            }
+            ("a", true) => {
+                if let Some(Some(href)) = self.link_href.pop() {
+                    self.line.push_str(&format!("]({})", href));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn text(&mut self, text: &str) {
+        if self.in_pre {
+            self.line.push_str(text);
+            return;
+        }
+
+        let mut words = text.split_whitespace().peekable();
+        if words.peek().is_none() {
+            if !text.is_empty() {
+                self.push_space();
+            }
+            return;
+        }
+        if text.starts_with(char::is_whitespace) {
+            self.push_space();
+        }
+        while let Some(word) = words.next() {
+            self.line.push_str(word);
+            if words.peek().is_some() {
+                self.line.push(' ');
+            }
+        }
+        if text.ends_with(char::is_whitespace) {
+            self.push_space();
+        }
+    }
+
+    fn push_inline(&mut self, marker: &str) {
+        self.line.push_str(marker);
+    }
+
+    /// Push a single separating space (never at line start, never doubled)
+    fn push_space(&mut self) {
+        let content = self.line.trim_start();
+        if !content.is_empty()
+            && !self.line.ends_with(' ')
+            && !content.ends_with("- ")
+            && !self.line.ends_with('\n')
+        {
+            self.line.push(' ');
+        }
+    }
+
+    fn start_list_block(&mut self) {
+        if self.lists.is_empty() {
+            self.block_break();
+        } else {
+            self.flush_line();
+        }
+    }
+
+    /// Emit the current line if it has content
+    fn flush_line(&mut self) {
+        let line = std::mem::take(&mut self.line);
+        let trimmed = line.trim_end();
+        let is_bare_marker = {
+            let t = trimmed.trim_start();
+            t.is_empty() || t == "-" || t == "1." || t.chars().all(|c| c == '#')
+        };
+        if is_bare_marker {
+            return;
+        }
+        for _ in 0..self.quote_depth {
+            self.out.push_str("> ");
+        }
+        self.out.push_str(trimmed);
+        self.out.push('\n');
+    }
+
+    /// End the current block with a blank line separator
+    ///
+    /// Inside blockquotes lines stay contiguous so they form a single quote block.
+    fn block_break(&mut self) {
+        self.flush_line();
+        if self.quote_depth == 0 && !self.out.is_empty() && !self.out.ends_with("\n\n") {
+            self.out.push('\n');
+        }
+    }
+
+    fn finish(mut self) -> String {
+        self.flush_line();
+        self.out.trim().to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_headings_and_paragraphs() {
+        let md = html_to_markdown("<h1>Title</h1><p>First para</p><p>Second <b>bold</b></p>");
+        assert_eq!(md, "# Title\n\nFirst para\n\nSecond **bold**");
+    }
+
+    #[test]
+    fn test_nested_lists() {
+        let md = html_to_markdown(
+            "<ul><li>One<ul><li>Nested <em>item</em></li></ul></li><li>Two</li></ul>",
+        );
+        assert_eq!(md, "- One\n  - Nested *item*\n- Two");
+    }
+
+    #[test]
+    fn test_ordered_list() {
+        let md = html_to_markdown("<ol><li>First</li><li>Second</li></ol>");
+        assert_eq!(md, "1. First\n1. Second");
+    }
+
+    #[test]
+    fn test_checkbox_list() {
+        let md = html_to_markdown(
+            r#"<ul><li><input type="checkbox" checked> Done</li><li><input type="checkbox"> Todo</li></ul>"#,
+        );
+        assert_eq!(md, "- [x] Done\n- [ ] Todo");
+    }
+
+    #[test]
+    fn test_code_block_preserves_whitespace() {
+        let md = html_to_markdown("<p>Code:</p><pre><code>fn main() {\n    x();\n}</code></pre>");
+        assert_eq!(md, "Code:\n\n```\nfn main() {\n    x();\n}\n```");
+    }
+
+    #[test]
+    fn test_links_and_entities() {
+        let md = html_to_markdown(
+            r#"<p>See <a href="https://a.com?x=1&amp;y=2">docs</a> &lt;here&gt;</p>"#,
+        );
+        assert_eq!(md, "See [docs](https://a.com?x=1&y=2) <here>");
+    }
+
+    #[test]
+    fn test_images() {
+        let md =
+            html_to_markdown(r#"<p>Logo: <img src="logo.png" alt="Logo"><img alt="none"></p>"#);
+        assert_eq!(md, "Logo: ![Logo](logo.png)");
+    }
+
+    #[test]
+    fn test_blockquote() {
+        let md = html_to_markdown("<blockquote><p>Quoted</p></blockquote><p>After</p>");
+        assert_eq!(md, "> Quoted\n\nAfter");
+    }
+
+    #[test]
+    fn test_skips_script_style_and_comments() {
+        let md = html_to_markdown(
+            "<style>p{}</style><!-- note --><p>Visible</p><script>alert(1)</script>",
+        );
+        assert_eq!(md, "Visible");
+    }
+
+    #[test]
+    fn test_decode_numeric_entities() {
+        assert_eq!(
+            decode_entities("&#65;&#x42;&nbsp;&unknown; & x"),
+            "AB &unknown; & x"
+        );
+    }
+}
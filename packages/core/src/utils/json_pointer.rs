@@ -0,0 +1,157 @@
+//! JSON Pointer (RFC 6901) edits for node properties
+//!
+//! `serde_json::Value::pointer_mut` only reaches existing locations. These
+//! helpers also create missing intermediate objects on set, so callers can
+//! write `/task/due_date` into a node that has no `task` namespace yet.
+
+use serde_json::{Map, Value};
+
+/// Split a pointer into unescaped reference tokens
+///
+/// The pointer must be non-empty and start with `/`; replacing the whole
+/// document is not a property edit.
+fn parse_pointer(pointer: &str) -> Result<Vec<String>, String> {
+    let Some(rest) = pointer.strip_prefix('/') else {
+        return Err(format!(
+            "Invalid property path '{}': must start with '/'",
+            pointer
+        ));
+    };
+    Ok(rest
+        .split('/')
+        .map(|token| token.replace("~1", "/").replace("~0", "~"))
+        .collect())
+}
+
+/// Set the value at `pointer`, creating intermediate objects as needed
+///
+/// Array elements are addressed by index; `-` appends.
+pub fn set_pointer(target: &mut Value, pointer: &str, value: Value) -> Result<(), String> {
+    let tokens = parse_pointer(pointer)?;
+    let (last, parents) = tokens.split_last().expect("split yields a token");
+
+    let mut current = target;
+    for token in parents {
+        if current.is_null() {
+            *current = Value::Object(Map::new());
+        }
+        current = match current {
+            Value::Object(map) => map
+                .entry(token.clone())
+                .or_insert_with(|| Value::Object(Map::new())),
+            Value::Array(items) => {
+                let index = array_index(token, items.len(), pointer)?;
+                &mut items[index]
+            }
+            _ => return Err(format!("Property path '{}' crosses a scalar", pointer)),
+        };
+    }
+
+    if current.is_null() {
+        *current = Value::Object(Map::new());
+    }
+    match current {
+        Value::Object(map) => {
+            map.insert(last.clone(), value);
+        }
+        Value::Array(items) if last == "-" => items.push(value),
+        Value::Array(items) => {
+            let index = array_index(last, items.len(), pointer)?;
+            items[index] = value;
+        }
+        _ => return Err(format!("Property path '{}' crosses a scalar", pointer)),
+    }
+    Ok(())
+}
+
+/// Remove and return the value at `pointer`, if present
+pub fn remove_pointer(target: &mut Value, pointer: &str) -> Result<Option<Value>, String> {
+    let tokens = parse_pointer(pointer)?;
+    let (last, parents) = tokens.split_last().expect("split yields a token");
+
+    let mut current = target;
+    for token in parents {
+        let next = match current {
+            Value::Object(map) => map.get_mut(token),
+            Value::Array(items) => token
+                .parse::<usize>()
+                .ok()
+                .and_then(move |index| items.get_mut(index)),
+            _ => None,
+        };
+        match next {
+            Some(next) => current = next,
+            None => return Ok(None),
+        }
+    }
+
+    Ok(match current {
+        Value::Object(map) => map.remove(last),
+        Value::Array(items) => match last.parse::<usize>() {
+            Ok(index) if index < items.len() => Some(items.remove(index)),
+            _ => None,
+        },
+        _ => None,
+    })
+}
+
+fn array_index(token: &str, len: usize, pointer: &str) -> Result<usize, String> {
+    match token.parse::<usize>() {
+        Ok(index) if index < len => Ok(index),
+        _ => Err(format!(
+            "Property path '{}' has invalid array index '{}'",
+            pointer, token
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_set_pointer_creates_namespaces() {
+        let mut props = json!({});
+        set_pointer(&mut props, "/task/due_date", json!("2025-01-15")).unwrap();
+        set_pointer(&mut props, "/task/status", json!("open")).unwrap();
+        set_pointer(&mut props, "/custom/a~1b", json!(1)).unwrap();
+
+        assert_eq!(
+            props,
+            json!({
+                "task": { "due_date": "2025-01-15", "status": "open" },
+                "custom": { "a/b": 1 }
+            })
+        );
+    }
+
+    #[test]
+    fn test_set_pointer_arrays() {
+        let mut props = json!({ "tags": ["a", "b"] });
+        set_pointer(&mut props, "/tags/-", json!("c")).unwrap();
+        set_pointer(&mut props, "/tags/0", json!("z")).unwrap();
+        assert_eq!(props, json!({ "tags": ["z", "b", "c"] }));
+
+        assert!(set_pointer(&mut props, "/tags/7", json!("x")).is_err());
+        assert!(set_pointer(&mut props, "/tags/0/deep", json!("x")).is_err());
+        assert!(set_pointer(&mut props, "tags", json!("x")).is_err());
+        assert!(set_pointer(&mut props, "", json!("x")).is_err());
+    }
+
+    #[test]
+    fn test_remove_pointer() {
+        let mut props = json!({ "task": { "status": "open", "priority": "high" }, "tags": [1, 2] });
+
+        assert_eq!(
+            remove_pointer(&mut props, "/task/priority").unwrap(),
+            Some(json!("high"))
+        );
+        assert_eq!(
+            remove_pointer(&mut props, "/tags/0").unwrap(),
+            Some(json!(1))
+        );
+        assert_eq!(remove_pointer(&mut props, "/missing/key").unwrap(), None);
+        assert_eq!(props, json!({ "task": { "status": "open" }, "tags": [2] }));
+    }
+}
@@ -0,0 +1,120 @@
+//! JSON Merge Patch (RFC 7386) for node properties
+//!
+//! Property updates are patches: objects merge recursively, `null` removes a
+//! key and any other value replaces what was there. Removing a property thus
+//! takes `{"task": {"due_date": null}}` instead of rewriting the whole blob.
+//!
+//! [`merge_patch_diff`] goes the other way and builds the patch between two
+//! property blobs, for callers that edit a full copy and store the change.
+
+use serde_json::{Map, Value};
+
+/// Apply `patch` to `target` in place
+pub fn merge_patch(target: &mut Value, patch: &Value) {
+    let Value::Object(patch) = patch else {
+        *target = patch.clone();
+        return;
+    };
+    if !target.is_object() {
+        *target = Value::Object(Map::new());
+    }
+    let Value::Object(target) = target else {
+        unreachable!("target was just made an object");
+    };
+    for (key, value) in patch {
+        if value.is_null() {
+            target.remove(key);
+        } else {
+            merge_patch(target.entry(key.clone()).or_insert(Value::Null), value);
+        }
+    }
+}
+
+/// Patch that turns `source` into `target` when applied with [`merge_patch`]
+///
+/// A `null` inside `target` can't be expressed (the patch would remove the
+/// key instead), which is fine for properties where null means unset.
+pub fn merge_patch_diff(source: &Value, target: &Value) -> Value {
+    let (Value::Object(source), Value::Object(target)) = (source, target) else {
+        return target.clone();
+    };
+    let mut patch = Map::new();
+    for key in source.keys() {
+        if !target.contains_key(key) {
+            patch.insert(key.clone(), Value::Null);
+        }
+    }
+    for (key, value) in target {
+        match source.get(key) {
+            Some(old) if old == value => {}
+            Some(old) => {
+                patch.insert(key.clone(), merge_patch_diff(old, value));
+            }
+            None => {
+                patch.insert(key.clone(), value.clone());
+            }
+        }
+    }
+    Value::Object(patch)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_merge_patch_rfc_7386_examples() {
+        let cases = [
+            (json!({"a": "b"}), json!({"a": "c"}), json!({"a": "c"})),
+            (
+                json!({"a": "b"}),
+                json!({"b": "c"}),
+                json!({"a": "b", "b": "c"}),
+            ),
+            (json!({"a": "b"}), json!({"a": null}), json!({})),
+            (json!({"a": ["b"]}), json!({"a": "c"}), json!({"a": "c"})),
+            (json!({"a": "c"}), json!({"a": ["b"]}), json!({"a": ["b"]})),
+            (
+                json!({"a": {"b": "c"}}),
+                json!({"a": {"b": "d", "c": null}}),
+                json!({"a": {"b": "d"}}),
+            ),
+            (
+                json!({"e": null}),
+                json!({"a": 1}),
+                json!({"e": null, "a": 1}),
+            ),
+            (
+                json!([1, 2]),
+                json!({"a": "b", "c": null}),
+                json!({"a": "b"}),
+            ),
+            (
+                json!({}),
+                json!({"a": {"bb": {"ccc": null}}}),
+                json!({"a": {"bb": {}}}),
+            ),
+            (json!({"a": "b"}), json!(["c"]), json!(["c"])),
+        ];
+        for (mut target, patch, expected) in cases {
+            merge_patch(&mut target, &patch);
+            assert_eq!(target, expected, "patch {}", patch);
+        }
+    }
+
+    #[test]
+    fn test_diff_round_trips_through_merge_patch() {
+        let source = json!({"task": {"status": "open", "due_date": "2025-01-01"}, "old": 1});
+        let target = json!({"task": {"status": "done"}, "text": {}});
+
+        let patch = merge_patch_diff(&source, &target);
+        assert_eq!(
+            patch,
+            json!({"old": null, "task": {"status": "done", "due_date": null}, "text": {}})
+        );
+        let mut patched = source.clone();
+        merge_patch(&mut patched, &patch);
+        assert_eq!(patched, target);
+    }
+}
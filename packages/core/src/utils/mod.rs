@@ -2,6 +2,18 @@
 //!
 //! This module provides common utility functions used across the codebase.
 
+mod correlation;
+mod html;
+mod json_pointer;
 mod markdown;
+mod merge_patch;
+mod natural_date;
+mod text;
 
+pub use correlation::{current_correlation_id, new_correlation_id, with_correlation_id};
+pub use html::{decode_entities, html_to_markdown};
+pub use json_pointer::{remove_pointer, set_pointer};
 pub use markdown::strip_markdown;
+pub use merge_patch::{merge_patch, merge_patch_diff};
+pub use natural_date::{find_natural_date, parse_natural_date, DateMatch};
+pub use text::{count_tokens, snippet_around, truncate_chars, truncate_tokens};
@@ -0,0 +1,398 @@
+//! Natural-Language Dates
+//!
+//! Resolves phrases such as "tomorrow", "next friday" or "jan 15" to a
+//! calendar date relative to `today`. [`find_natural_date`] also reports
+//! where the phrase sits in a longer text, so quick capture can turn
+//! "Call the bank by friday" into the task "Call the bank" due on Friday.
+//!
+//! Recognized phrases (case-insensitive):
+//!
+//! | Phrase                                     | Resolves to                       |
+//! |--------------------------------------------|-----------------------------------|
+//! | `today`, `tomorrow`, `yesterday`           | relative to today                 |
+//! | `friday`, `this friday`                    | the next Friday, today included   |
+//! | `next friday`, `last friday`               | Friday of next week, the last one |
+//! | `next week`, `next month`, `next year`     | first day of that period          |
+//! | `in 3 days`, `in a week`, `in two months`  | offset from today                 |
+//! | `jan 15`, `15th of january`, `jan 15 2026` | that day; this year unless passed |
+//! | `2026-01-15`, `2026/01/15`                 | that day                          |
+//!
+//! A preceding `on`, `by`, `due` or `until` is part of the matched phrase.
+//! Abbreviated weekdays (`fri`, `sat`) only count after one of those words
+//! or `this`/`next`/`last`, so "sat down" isn't a date.
+
+use chrono::{Datelike, Days, Months, NaiveDate, Weekday};
+
+/// Words that introduce a date and are removed together with it
+const PREPOSITIONS: &[&str] = &["on", "by", "due", "until"];
+
+const MONTHS: &[&[&str]] = &[
+    &["january", "jan"],
+    &["february", "feb"],
+    &["march", "mar"],
+    &["april", "apr"],
+    &["may"],
+    &["june", "jun"],
+    &["july", "jul"],
+    &["august", "aug"],
+    &["september", "sep", "sept"],
+    &["october", "oct"],
+    &["november", "nov"],
+    &["december", "dec"],
+];
+
+const NUMBER_WORDS: &[&str] = &[
+    "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten",
+    "eleven", "twelve",
+];
+
+/// A date phrase found in a text
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DateMatch {
+    pub date: NaiveDate,
+    /// Byte offset where the phrase starts
+    pub start: usize,
+    /// Byte offset just past the phrase
+    pub end: usize,
+}
+
+impl DateMatch {
+    /// `text` without the matched phrase
+    pub fn remove_from(&self, text: &str) -> String {
+        let before = text[..self.start].trim_end();
+        let after = text[self.end..].trim_start();
+        if before.is_empty()
+            || after.is_empty()
+            || after.starts_with(|c: char| ",.;:!?".contains(c))
+        {
+            format!("{}{}", before, after)
+        } else {
+            format!("{} {}", before, after)
+        }
+    }
+}
+
+/// Date named by the whole of `text`, e.g. "next friday" or "by jan 15"
+pub fn parse_natural_date(text: &str, today: NaiveDate) -> Option<NaiveDate> {
+    let tokens = tokenize(text);
+    let skip = usize::from(tokens.len() > 1 && is_preposition(&tokens[0]));
+    let (date, len) = match_at(&tokens[skip..], today, skip == 1)?;
+    (skip + len == tokens.len()).then_some(date)
+}
+
+/// First date phrase in `text`, with its position
+pub fn find_natural_date(text: &str, today: NaiveDate) -> Option<DateMatch> {
+    let tokens = tokenize(text);
+    (0..tokens.len()).find_map(|i| {
+        if is_preposition(&tokens[i]) {
+            if let Some((date, len)) = match_at(&tokens[i + 1..], today, true) {
+                return Some(DateMatch {
+                    date,
+                    start: tokens[i].start,
+                    end: tokens[i + len].end,
+                });
+            }
+        }
+        match_at(&tokens[i..], today, false).map(|(date, len)| DateMatch {
+            date,
+            start: tokens[i].start,
+            end: tokens[i + len - 1].end,
+        })
+    })
+}
+
+struct Token {
+    /// Lowercased word
+    word: String,
+    start: usize,
+    end: usize,
+}
+
+fn tokenize(text: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut start = None;
+    for (i, c) in text
+        .char_indices()
+        .chain(std::iter::once((text.len(), ' ')))
+    {
+        let in_word = c.is_alphanumeric() || c == '-' || c == '/';
+        match (start, in_word) {
+            (None, true) => start = Some(i),
+            (Some(s), false) => {
+                tokens.push(Token {
+                    word: text[s..i].to_lowercase(),
+                    start: s,
+                    end: i,
+                });
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    tokens
+}
+
+fn is_preposition(token: &Token) -> bool {
+    PREPOSITIONS.contains(&token.word.as_str())
+}
+
+/// Date phrase at the start of `tokens` and how many tokens it spans
+///
+/// `qualified` is set when a preposition precedes, which lets abbreviated
+/// weekdays match on their own.
+fn match_at(tokens: &[Token], today: NaiveDate, qualified: bool) -> Option<(NaiveDate, usize)> {
+    let word = |i: usize| tokens.get(i).map(|t| t.word.as_str());
+    let first = word(0)?;
+
+    match first {
+        "today" => return Some((today, 1)),
+        "tomorrow" | "tmrw" => return Some((today + Days::new(1), 1)),
+        "yesterday" => return Some((today - Days::new(1), 1)),
+        "this" | "next" | "last" => {
+            if let Some(date) = word(1).and_then(|unit| relative(first, unit, today)) {
+                return Some((date, 2));
+            }
+        }
+        "in" => {
+            if let Some(date) = word(1)
+                .zip(word(2))
+                .and_then(|(n, unit)| offset(n, unit, today))
+            {
+                return Some((date, 3));
+            }
+        }
+        _ => {}
+    }
+
+    if let Some((weekday, abbreviated)) = parse_weekday(first) {
+        if qualified || !abbreviated {
+            return Some((upcoming(today, weekday), 1));
+        }
+    }
+    if let Some(date) = parse_iso(first) {
+        return Some((date, 1));
+    }
+    month_day(tokens, today)
+}
+
+/// `this`/`next`/`last` followed by a weekday or `week`/`month`/`year`
+fn relative(which: &str, unit: &str, today: NaiveDate) -> Option<NaiveDate> {
+    let monday = today - Days::new(u64::from(today.weekday().num_days_from_monday()));
+    if let Some((weekday, _)) = parse_weekday(unit) {
+        let day = Days::new(u64::from(weekday.num_days_from_monday()));
+        return match which {
+            "this" => Some(upcoming(today, weekday)),
+            "next" => Some(monday + Days::new(7) + day),
+            _ => {
+                let back = (today.weekday().num_days_from_monday() + 7
+                    - weekday.num_days_from_monday())
+                    % 7;
+                Some(today - Days::new(u64::from(if back == 0 { 7 } else { back })))
+            }
+        };
+    }
+
+    let step = |date: NaiveDate, months: u32| match which {
+        "next" => date.checked_add_months(Months::new(months)),
+        "last" => date.checked_sub_months(Months::new(months)),
+        _ => None,
+    };
+    match unit {
+        "week" => match which {
+            "next" => Some(monday + Days::new(7)),
+            "last" => Some(monday - Days::new(7)),
+            _ => None,
+        },
+        "month" => step(today.with_day(1)?, 1),
+        "year" => step(NaiveDate::from_ymd_opt(today.year(), 1, 1)?, 12),
+        _ => None,
+    }
+}
+
+/// `in <n> <unit>`, where `n` may be a digit string, a number word or `a`/`an`
+fn offset(n: &str, unit: &str, today: NaiveDate) -> Option<NaiveDate> {
+    let n: u32 = match n {
+        "a" | "an" => 1,
+        _ => n
+            .parse()
+            .ok()
+            .or_else(|| NUMBER_WORDS.iter().position(|w| *w == n).map(|i| i as u32))?,
+    };
+    match unit.strip_suffix('s').unwrap_or(unit) {
+        "day" => today.checked_add_days(Days::new(u64::from(n))),
+        "week" => today.checked_add_days(Days::new(u64::from(n) * 7)),
+        "month" => today.checked_add_months(Months::new(n)),
+        "year" => today.checked_add_months(Months::new(n.checked_mul(12)?)),
+        _ => None,
+    }
+}
+
+/// `<month> <day> [year]`, `<day> <month> [year]` or `<day> of <month> [year]`
+fn month_day(tokens: &[Token], today: NaiveDate) -> Option<(NaiveDate, usize)> {
+    let word = |i: usize| tokens.get(i).map(|t| t.word.as_str());
+
+    let (month, day, len) = if let Some(month) = word(0).and_then(parse_month) {
+        (month, word(1).and_then(parse_day)?, 2)
+    } else {
+        let day = word(0).and_then(parse_day)?;
+        let of = usize::from(word(1) == Some("of"));
+        (word(1 + of).and_then(parse_month)?, day, 2 + of)
+    };
+
+    if let Some(year) = word(len).and_then(parse_year) {
+        return NaiveDate::from_ymd_opt(year, month, day).map(|date| (date, len + 1));
+    }
+    let this_year = NaiveDate::from_ymd_opt(today.year(), month, day);
+    match this_year {
+        Some(date) if date >= today => Some((date, len)),
+        _ => NaiveDate::from_ymd_opt(today.year() + 1, month, day).map(|date| (date, len)),
+    }
+}
+
+/// Weekday for a full or abbreviated name, and whether it was abbreviated
+fn parse_weekday(word: &str) -> Option<(Weekday, bool)> {
+    let full = match word {
+        "monday" => Some(Weekday::Mon),
+        "tuesday" => Some(Weekday::Tue),
+        "wednesday" => Some(Weekday::Wed),
+        "thursday" => Some(Weekday::Thu),
+        "friday" => Some(Weekday::Fri),
+        "saturday" => Some(Weekday::Sat),
+        "sunday" => Some(Weekday::Sun),
+        _ => None,
+    };
+    if let Some(weekday) = full {
+        return Some((weekday, false));
+    }
+    let abbreviated = match word {
+        "mon" => Weekday::Mon,
+        "tue" | "tues" => Weekday::Tue,
+        "wed" => Weekday::Wed,
+        "thu" | "thur" | "thurs" => Weekday::Thu,
+        "fri" => Weekday::Fri,
+        "sat" => Weekday::Sat,
+        "sun" => Weekday::Sun,
+        _ => return None,
+    };
+    Some((abbreviated, true))
+}
+
+/// First `weekday` on or after `today`
+fn upcoming(today: NaiveDate, weekday: Weekday) -> NaiveDate {
+    let ahead = (weekday.num_days_from_monday() + 7 - today.weekday().num_days_from_monday()) % 7;
+    today + Days::new(u64::from(ahead))
+}
+
+fn parse_month(word: &str) -> Option<u32> {
+    MONTHS
+        .iter()
+        .position(|names| names.contains(&word))
+        .map(|i| i as u32 + 1)
+}
+
+/// Day of month, optionally with an ordinal suffix (`15th`)
+fn parse_day(word: &str) -> Option<u32> {
+    let digits = ["st", "nd", "rd", "th"]
+        .iter()
+        .find_map(|suffix| word.strip_suffix(suffix))
+        .unwrap_or(word);
+    if digits.is_empty() || digits.len() > 2 || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    digits.parse().ok().filter(|day| (1..=31).contains(day))
+}
+
+fn parse_year(word: &str) -> Option<i32> {
+    (word.len() == 4 && word.bytes().all(|b| b.is_ascii_digit()))
+        .then(|| word.parse().ok())
+        .flatten()
+}
+
+fn parse_iso(word: &str) -> Option<NaiveDate> {
+    if word.len() != 10 {
+        return None;
+    }
+    ["%Y-%m-%d", "%Y/%m/%d"]
+        .iter()
+        .find_map(|format| NaiveDate::parse_from_str(word, format).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    /// A Wednesday
+    fn today() -> NaiveDate {
+        date("2025-01-15")
+    }
+
+    #[test]
+    fn test_parse_natural_date_phrases() {
+        for (phrase, expected) in [
+            ("today", "2025-01-15"),
+            ("Tomorrow", "2025-01-16"),
+            ("yesterday", "2025-01-14"),
+            ("friday", "2025-01-17"),
+            ("wednesday", "2025-01-15"),
+            ("this fri", "2025-01-17"),
+            ("next friday", "2025-01-24"),
+            ("last friday", "2025-01-10"),
+            ("last wednesday", "2025-01-08"),
+            ("next week", "2025-01-20"),
+            ("next month", "2025-02-01"),
+            ("next year", "2026-01-01"),
+            ("in 3 days", "2025-01-18"),
+            ("in a week", "2025-01-22"),
+            ("in two months", "2025-03-15"),
+            ("jan 20", "2025-01-20"),
+            ("Jan 10", "2026-01-10"),
+            ("15th of March 2026", "2026-03-15"),
+            ("March 15th, 2026", "2026-03-15"),
+            ("by 2025-02-01", "2025-02-01"),
+            ("due sat", "2025-01-18"),
+        ] {
+            assert_eq!(
+                parse_natural_date(phrase, today()),
+                Some(date(expected)),
+                "{}",
+                phrase
+            );
+        }
+    }
+
+    #[test]
+    fn test_non_dates_are_rejected() {
+        for text in [
+            "",
+            "sat",
+            "in the morning",
+            "42",
+            "may",
+            "feb 30",
+            "tomorrow morning",
+            "next meeting",
+        ] {
+            assert_eq!(parse_natural_date(text, today()), None, "{}", text);
+        }
+    }
+
+    #[test]
+    fn test_find_natural_date_reports_span_for_rewriting() {
+        let text = "Call the bank by Friday";
+        let found = find_natural_date(text, today()).unwrap();
+        assert_eq!(found.date, date("2025-01-17"));
+        assert_eq!(&text[found.start..found.end], "by Friday");
+        assert_eq!(found.remove_from(text), "Call the bank");
+
+        let text = "Pay rent on 1 feb, before noon";
+        let found = find_natural_date(text, today()).unwrap();
+        assert_eq!(found.date, date("2025-02-01"));
+        assert_eq!(found.remove_from(text), "Pay rent, before noon");
+
+        assert_eq!(find_natural_date("I sat down and may call", today()), None);
+    }
+}
@@ -0,0 +1,265 @@
+//! Token counting, truncation and snippets for node content
+//!
+//! Search results, MCP responses and exporters all show shortened content.
+//! Cutting at an arbitrary character can leave half a `nodespace://` link
+//! (which then renders as garbage) or an unterminated code fence (which turns
+//! everything after it into code), so these helpers treat links and fences as
+//! units:
+//!
+//! - [`count_tokens`] - estimated model tokens, for embedding and response budgets
+//! - [`truncate_chars`] / [`truncate_tokens`] - shorten at a word boundary,
+//!   ending in `…`
+//! - [`snippet_around`] - a window of text around a match offset
+
+use regex::Regex;
+use std::sync::LazyLock;
+
+/// Markdown links to nodes and bare node URIs
+static NODE_LINK_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\[[^\]\n]*\]\(nodespace://[^)\s]*\)|nodespace://[^\s)\]]+").unwrap()
+});
+
+const ELLIPSIS: &str = "…";
+const FENCE: &str = "```";
+
+/// Estimated number of model tokens in `text`
+///
+/// Approximates a BPE tokenizer: a run of letters or digits costs one token
+/// per four characters (at least one), every other visible character costs
+/// one, and whitespace is free. Ideographic scripts are counted per
+/// character. The estimate errs on the high side for prose.
+pub fn count_tokens(text: &str) -> usize {
+    let mut tokens = 0;
+    let mut run: usize = 0;
+    for c in text.chars() {
+        if is_word_char(c) {
+            run += 1;
+            continue;
+        }
+        tokens += run.div_ceil(4);
+        run = 0;
+        if !c.is_whitespace() {
+            tokens += 1;
+        }
+    }
+    tokens + run.div_ceil(4)
+}
+
+/// `text` cut to at most `max_chars` characters plus an ellipsis
+///
+/// Returns `text` unchanged when it fits.
+pub fn truncate_chars(text: &str, max_chars: usize) -> String {
+    match text.char_indices().nth(max_chars) {
+        Some((cut, _)) => truncate_at(text, cut),
+        None => text.to_string(),
+    }
+}
+
+/// `text` cut to at most `max_tokens` estimated tokens plus an ellipsis
+///
+/// Uses the same estimate as [`count_tokens`]; returns `text` unchanged when
+/// it fits.
+pub fn truncate_tokens(text: &str, max_tokens: usize) -> String {
+    let mut tokens = 0;
+    let mut run = 0;
+    for (i, c) in text.char_indices() {
+        if is_word_char(c) {
+            run += 1;
+            if run % 4 == 1 {
+                tokens += 1;
+            }
+        } else {
+            run = 0;
+            if !c.is_whitespace() {
+                tokens += 1;
+            }
+        }
+        if tokens > max_tokens {
+            return truncate_at(text, i);
+        }
+    }
+    text.to_string()
+}
+
+/// About `max_chars` characters of `text` centred on byte offset `offset`
+///
+/// The window is widened to keep node links whole and narrowed to word
+/// boundaries; an ellipsis marks each side that was cut.
+pub fn snippet_around(text: &str, offset: usize, max_chars: usize) -> String {
+    let offset = floor_char_boundary(text, offset.min(text.len()));
+    let before = max_chars / 2;
+
+    let mut start = text[..offset]
+        .char_indices()
+        .rev()
+        .nth(before.saturating_sub(1))
+        .map_or(0, |(i, _)| i);
+    if let Some(link) = NODE_LINK_RE
+        .find_iter(text)
+        .find(|m| m.start() < start && start < m.end())
+    {
+        start = link.start();
+    } else if start > 0 && !text[..start].ends_with(char::is_whitespace) {
+        start = text[start..offset]
+            .find(char::is_whitespace)
+            .map_or(start, |i| start + i);
+    }
+
+    let rest = &text[start..];
+    let mut snippet = truncate_chars(rest.trim_start(), max_chars);
+    if start > 0 {
+        snippet.insert_str(0, ELLIPSIS);
+    }
+    snippet
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() && !is_ideographic(c)
+}
+
+/// CJK ideographs and kana, which tokenizers split per character
+fn is_ideographic(c: char) -> bool {
+    matches!(c as u32,
+        0x3040..=0x30FF | 0x3400..=0x4DBF | 0x4E00..=0x9FFF | 0xAC00..=0xD7AF | 0xF900..=0xFAFF)
+}
+
+fn floor_char_boundary(text: &str, mut index: usize) -> usize {
+    while !text.is_char_boundary(index) {
+        index -= 1;
+    }
+    index
+}
+
+/// Byte ranges of code fences, an unterminated fence running to the end
+fn fence_spans(text: &str) -> Vec<(usize, usize)> {
+    let mut spans = Vec::new();
+    let mut open = None;
+    let mut line_start = 0;
+    for line in text.split_inclusive('\n') {
+        if line.trim_start().starts_with(FENCE) {
+            match open.take() {
+                Some(start) => spans.push((start, line_start + line.trim_end().len())),
+                None => open = Some(line_start),
+            }
+        }
+        line_start += line.len();
+    }
+    if let Some(start) = open {
+        spans.push((start, text.len()));
+    }
+    spans
+}
+
+/// Shorten `text` to end before byte `cut`, keeping links and fences intact
+fn truncate_at(text: &str, cut: usize) -> String {
+    let mut cut = floor_char_boundary(text, cut);
+
+    // Prefer ending on a word boundary
+    if !text[cut..].starts_with(char::is_whitespace) {
+        if let Some(space) = text[..cut].rfind(char::is_whitespace) {
+            if space > 0 {
+                cut = space;
+            }
+        }
+    }
+
+    // A link that starts the text is kept even if it overruns the budget
+    if let Some(link) = NODE_LINK_RE
+        .find_iter(text)
+        .find(|m| m.start() < cut && cut < m.end())
+    {
+        cut = if text[..link.start()].trim().is_empty() {
+            link.end()
+        } else {
+            link.start()
+        };
+    }
+
+    // A fence that starts the text is cut inside and closed again, so a long
+    // code block still shows its beginning; any other fence is dropped whole
+    let mut close_fence = false;
+    if let Some(&(start, _)) = fence_spans(text)
+        .iter()
+        .find(|(start, end)| *start < cut && cut < *end)
+    {
+        if text[..start].trim().is_empty() {
+            close_fence = true;
+        } else {
+            cut = start;
+        }
+    }
+
+    let mut truncated = text[..cut].trim_end().to_string();
+    truncated.push_str(ELLIPSIS);
+    if close_fence {
+        truncated.push('\n');
+        truncated.push_str(FENCE);
+    }
+    truncated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_count_tokens_estimate() {
+        assert_eq!(count_tokens(""), 0);
+        assert_eq!(count_tokens("hello world"), 4);
+        assert_eq!(count_tokens("a, b."), 4);
+        assert_eq!(count_tokens("日本語"), 3);
+        assert!(count_tokens(&"word ".repeat(100)) >= 100);
+    }
+
+    #[test]
+    fn test_truncate_keeps_links_whole() {
+        let text = "See [@Roadmap 2025](nodespace://abc-123) for details";
+        assert_eq!(truncate_chars(text, 100), text);
+        assert_eq!(truncate_chars(text, 20), "See…");
+        assert_eq!(
+            truncate_chars(text, 42),
+            "See [@Roadmap 2025](nodespace://abc-123)…"
+        );
+        assert_eq!(
+            truncate_chars("[@Roadmap 2025](nodespace://abc-123) is next", 10),
+            "[@Roadmap 2025](nodespace://abc-123)…"
+        );
+
+        let bare = "Linked nodespace://node/abc-123 here";
+        assert_eq!(truncate_chars(bare, 15), "Linked…");
+    }
+
+    #[test]
+    fn test_truncate_never_splits_code_fences() {
+        let text = "Intro text\n```rust\nfn main() {}\n```\nAfter";
+        assert_eq!(truncate_chars(text, 25), "Intro text…");
+
+        let code = "```rust\nlet a = 1;\nlet b = 2;\n```";
+        assert_eq!(truncate_chars(code, 20), "```rust\nlet a = 1;…\n```");
+    }
+
+    #[test]
+    fn test_truncate_tokens_fits_budget() {
+        let text = "one two three four five six";
+        assert_eq!(truncate_tokens(text, 100), text);
+        let truncated = truncate_tokens(text, 4);
+        assert_eq!(truncated, "one two three…");
+        assert!(count_tokens(truncated.trim_end_matches(ELLIPSIS)) <= 4);
+    }
+
+    #[test]
+    fn test_snippet_around_match() {
+        let text = "The quick brown fox jumps over the lazy dog near the riverbank";
+        let offset = text.find("lazy").unwrap();
+        let snippet = snippet_around(text, offset, 24);
+        assert!(snippet.starts_with('…'));
+        assert!(snippet.contains("lazy"));
+        assert!(snippet.ends_with('…'));
+
+        assert_eq!(snippet_around("short text", 2, 80), "short text");
+
+        let linked = "Intro words then [@Plan](nodespace://p1) mentions it";
+        let snippet = snippet_around(linked, linked.find("mentions").unwrap(), 20);
+        assert!(snippet.contains("[@Plan](nodespace://p1)"));
+    }
+}
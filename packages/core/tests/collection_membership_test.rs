@@ -1384,6 +1384,7 @@ mod collection_service_tests {
             DomainEvent::RelationshipCreated {
                 relationship,
                 source_client_id: _,
+                ..
             } => {
                 assert_eq!(relationship.from_id, "event-doc");
                 assert_eq!(relationship.to_id, collection_id);
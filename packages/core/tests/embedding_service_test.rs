@@ -48,7 +48,11 @@ async fn create_unified_test_env() -> Result<(
 
     // Create embedding service using the SAME store
     let nlp_engine = create_test_nlp_engine();
-    let embedding_service = NodeEmbeddingService::new(nlp_engine, store.clone());
+    let embedding_service = NodeEmbeddingService::new(
+        nlp_engine,
+        store.clone(),
+        node_service.workspace_lock().clone(),
+    );
 
     Ok((embedding_service, node_service, store, temp_dir))
 }
@@ -71,7 +75,12 @@ async fn create_unified_test_env_with_config(
 
     // Create embedding service with custom config using the SAME store
     let nlp_engine = create_test_nlp_engine();
-    let embedding_service = NodeEmbeddingService::with_config(nlp_engine, store.clone(), config);
+    let embedding_service = NodeEmbeddingService::with_config(
+        nlp_engine,
+        store.clone(),
+        node_service.workspace_lock().clone(),
+        config,
+    );
 
     Ok((embedding_service, node_service, store, temp_dir))
 }
@@ -81,6 +81,7 @@ mod event_emission_tests {
                 node_id,
                 node_type,
                 source_client_id,
+                ..
             } => {
                 assert_eq!(node_id, expected_id);
                 assert_eq!(node_type, "text");
@@ -126,6 +127,7 @@ mod event_emission_tests {
             DomainEvent::NodeUpdated {
                 node_id: updated_id,
                 source_client_id,
+                ..
             } => {
                 assert_eq!(updated_id, node_id);
                 assert_eq!(source_client_id, Some(TEST_CLIENT_ID.to_string()));
@@ -165,6 +167,7 @@ mod event_emission_tests {
             DomainEvent::NodeDeleted {
                 id,
                 source_client_id,
+                ..
             } => {
                 assert_eq!(id, node_id);
                 assert_eq!(source_client_id, Some(TEST_CLIENT_ID.to_string()));
@@ -210,6 +213,7 @@ mod event_emission_tests {
             DomainEvent::RelationshipUpdated {
                 relationship,
                 source_client_id,
+                ..
             } => {
                 assert_eq!(relationship.relationship_type, "has_child");
                 assert_eq!(relationship.from_id, parent2.id);
@@ -250,6 +254,7 @@ mod event_emission_tests {
             DomainEvent::RelationshipCreated {
                 relationship,
                 source_client_id,
+                ..
             } => {
                 assert_eq!(relationship.relationship_type, "mentions");
                 assert_eq!(relationship.from_id, source_node.id);
@@ -298,6 +303,7 @@ mod event_emission_tests {
                 to_id,
                 relationship_type,
                 source_client_id,
+                ..
             } => {
                 // Universal relationship table IDs contain "relationship:"
                 assert!(
@@ -13,7 +13,7 @@ use nodespace_core::{
         relationships::{
             handle_get_all_schemas, handle_get_inbound_relationships, handle_get_relationship_graph,
         },
-        schema::{handle_create_schema, handle_update_schema},
+        schema::{handle_create_schema, handle_define_schema, handle_update_schema},
     },
     services::{NodeEmbeddingService, NodeService},
 };
@@ -45,7 +45,11 @@ async fn create_test_env_with_embedding(
     nlp_engine.initialize()?;
     let nlp_engine = Arc::new(nlp_engine);
 
-    let embedding_service = Arc::new(NodeEmbeddingService::new(nlp_engine, store.clone()));
+    let embedding_service = Arc::new(NodeEmbeddingService::new(
+        nlp_engine,
+        store.clone(),
+        node_service.workspace_lock().clone(),
+    ));
 
     Ok((node_service, embedding_service, temp_dir))
 }
@@ -286,6 +290,62 @@ async fn test_handle_get_children() {
     assert_eq!(children.unwrap().len(), 3);
 }
 
+#[tokio::test]
+async fn test_handle_get_children_pages_with_cursor() {
+    let (node_service, _temp_dir) = create_test_env().await.unwrap();
+
+    let parent = handle_create_node(
+        &node_service,
+        json!({"node_type": "text", "content": "Parent"}),
+    )
+    .await
+    .unwrap();
+    let parent_id = parent["node_id"].as_str().unwrap();
+    for i in 1..=5 {
+        handle_create_node(
+            &node_service,
+            json!({
+                "node_type": "text",
+                "content": format!("Child {}", i),
+                "parent_id": parent_id
+            }),
+        )
+        .await
+        .unwrap();
+    }
+
+    let mut indexes = Vec::new();
+    let mut cursor = serde_json::Value::Null;
+    loop {
+        let page = handle_get_children(
+            &node_service,
+            json!({"parent_id": parent_id, "limit": 2, "cursor": cursor}),
+        )
+        .await
+        .unwrap();
+        assert_eq!(page["child_count"], 5);
+        for child in page["children"].as_array().unwrap() {
+            indexes.push(child["index"].as_u64().unwrap());
+        }
+        cursor = page["next_cursor"].clone();
+        if cursor.is_null() {
+            break;
+        }
+    }
+    assert_eq!(indexes, vec![0, 1, 2, 3, 4]);
+
+    // A cursor only continues the listing it came from
+    let first = handle_get_children(&node_service, json!({"parent_id": parent_id, "limit": 2}))
+        .await
+        .unwrap();
+    let result = handle_get_children(
+        &node_service,
+        json!({"parent_id": "other-parent", "cursor": first["next_cursor"]}),
+    )
+    .await;
+    assert!(result.is_err());
+}
+
 // ============================================================================
 // Relationship Handler Tests
 // ============================================================================
@@ -466,6 +526,119 @@ async fn test_handle_create_schema_with_relationships() {
     assert!(has_relationship, "Relationship should appear in graph");
 }
 
+#[tokio::test]
+async fn test_handle_define_schema_refuses_unconfirmed_breaking_changes() {
+    let (node_service, _temp_dir) = create_test_env().await.unwrap();
+
+    let v1 = json!({
+        "name": "Ticket",
+        "fields": [
+            {"name": "title", "type": "string", "indexed": true, "protection": "user"},
+            {"name": "estimate", "type": "number", "indexed": false, "protection": "user"}
+        ]
+    });
+    let created = handle_define_schema(&node_service, v1).await.unwrap();
+    assert_eq!(created["applied"], true);
+    assert_eq!(created["created"], true);
+    assert_eq!(created["version"], 1);
+    assert!(created["ddl"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|statement| statement.as_str().unwrap().contains("title")));
+
+    // Adding an optional field is safe
+    let v2 = json!({
+        "name": "Ticket",
+        "fields": [
+            {"name": "title", "type": "string", "indexed": true, "protection": "user"},
+            {"name": "estimate", "type": "number", "indexed": false, "protection": "user"},
+            {"name": "labels", "type": "string", "indexed": false, "protection": "user"}
+        ]
+    });
+    let result = handle_define_schema(&node_service, v2).await.unwrap();
+    assert_eq!(result["applied"], true);
+    assert_eq!(result["breaking"], false);
+    assert_eq!(result["version"], 2);
+    assert_eq!(result["changes"][0]["kind"], "fieldAdded");
+
+    // Dropping a field and retyping another needs confirmation
+    let v3 = json!({
+        "name": "Ticket",
+        "fields": [
+            {"name": "title", "type": "string", "indexed": true, "protection": "user"},
+            {"name": "estimate", "type": "string", "indexed": false, "protection": "user"}
+        ]
+    });
+    let refused = handle_define_schema(&node_service, v3.clone())
+        .await
+        .unwrap();
+    assert_eq!(refused["applied"], false);
+    assert_eq!(refused["breaking"], true);
+    assert_eq!(refused["changes"].as_array().unwrap().len(), 2);
+    let schema = node_service
+        .get_schema_node("ticket")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(schema.schema_version, 2);
+    assert_eq!(schema.fields.len(), 3);
+
+    let mut confirmed = v3;
+    confirmed["confirm_breaking"] = json!(true);
+    let applied = handle_define_schema(&node_service, confirmed)
+        .await
+        .unwrap();
+    assert_eq!(applied["applied"], true);
+    let schema = node_service
+        .get_schema_node("ticket")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(schema.schema_version, 3);
+    assert_eq!(schema.get_field("estimate").unwrap().field_type, "string");
+}
+
+#[tokio::test]
+async fn test_handle_define_schema_validates_and_protects_core_fields() {
+    let (node_service, _temp_dir) = create_test_env().await.unwrap();
+
+    let duplicate = json!({
+        "name": "Broken",
+        "fields": [
+            {"name": "a", "type": "string", "indexed": false, "protection": "user"},
+            {"name": "a", "type": "number", "indexed": false, "protection": "user"}
+        ]
+    });
+    assert!(handle_define_schema(&node_service, duplicate)
+        .await
+        .is_err());
+    assert!(node_service
+        .get_schema_node("broken")
+        .await
+        .unwrap()
+        .is_none());
+
+    // Redefining the core task schema without its status field
+    let task = json!({"name": "Task", "fields": [], "confirm_breaking": true});
+    let error = handle_define_schema(&node_service, task).await.unwrap_err();
+    assert!(error.message.contains("status"), "{}", error.message);
+
+    // Dry runs plan without writing
+    let dry_run = json!({
+        "name": "Draft",
+        "fields": [{"name": "body", "type": "string", "indexed": false, "protection": "user"}],
+        "dry_run": true
+    });
+    let planned = handle_define_schema(&node_service, dry_run).await.unwrap();
+    assert_eq!(planned["applied"], false);
+    assert!(node_service
+        .get_schema_node("draft")
+        .await
+        .unwrap()
+        .is_none());
+}
+
 // ============================================================================
 // Error Handling Tests
 // ============================================================================
@@ -1628,14 +1801,14 @@ async fn test_handle_delete_relationship() {
     handle_create_schema(
         &node_service,
         json!({
-            "name": "project",
+            "name": "initiative",
             "fields": [
                 {"name": "title", "type": "string", "indexed": false, "protection": "user"}
             ],
             "relationships": [
                 {
                     "name": "owned_by",
-                    "targetType": "person",
+                    "targetType": "owner",
                     "direction": "out",
                     "cardinality": "one"
                 }
@@ -1648,7 +1821,7 @@ async fn test_handle_delete_relationship() {
     handle_create_schema(
         &node_service,
         json!({
-            "name": "person",
+            "name": "owner",
             "fields": [
                 {"name": "name", "type": "string", "indexed": false, "protection": "user"}
             ]
@@ -1661,7 +1834,7 @@ async fn test_handle_delete_relationship() {
     let project = handle_create_node(
         &node_service,
         json!({
-            "node_type": "project",
+            "node_type": "initiative",
             "content": "Project Alpha",
             "properties": {"title": "Alpha"}
         }),
@@ -1673,7 +1846,7 @@ async fn test_handle_delete_relationship() {
     let person = handle_create_node(
         &node_service,
         json!({
-            "node_type": "person",
+            "node_type": "owner",
             "content": "John Doe",
             "properties": {"name": "John Doe"}
         }),
@@ -2402,6 +2575,90 @@ async fn test_handle_get_node_tree_with_metadata() {
     assert!(tree.get("created_at").is_some() || tree.get("properties").is_some());
 }
 
+#[tokio::test]
+async fn test_handle_get_node_tree_outline() {
+    let (node_service, _temp_dir) = create_test_env().await.unwrap();
+
+    let root = handle_create_node(
+        &node_service,
+        json!({"node_type": "text", "content": "Offsite plan\nDetails below"}),
+    )
+    .await
+    .unwrap();
+    let root_id = root["node_id"].as_str().unwrap();
+    let child = handle_create_node(
+        &node_service,
+        json!({
+            "node_type": "task",
+            "content": "Book the venue for the whole team",
+            "parent_id": root_id
+        }),
+    )
+    .await
+    .unwrap();
+    let child_id = child["node_id"].as_str().unwrap();
+
+    let result = nodespace_core::mcp::handlers::nodes::handle_get_node_tree(
+        &node_service,
+        json!({
+            "node_id": root_id,
+            "format": "outline",
+            "max_content_chars": 12
+        }),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(result["format"], "outline");
+    assert_eq!(result["node_count"], 2);
+    assert_eq!(
+        result["outline"],
+        format!(
+            "- Offsite plan [text:{}]\n  - Book the… [task:{}]\n",
+            root_id, child_id
+        )
+    );
+}
+
+#[tokio::test]
+async fn test_node_reads_select_fields_and_snip_content() {
+    let (node_service, _temp_dir) = create_test_env().await.unwrap();
+
+    let node = handle_create_node(
+        &node_service,
+        json!({
+            "node_type": "text",
+            "content": "Quarterly planning notes. The budget review moves to Thursday afternoon."
+        }),
+    )
+    .await
+    .unwrap();
+    let node_id = node["node_id"].as_str().unwrap();
+
+    let trimmed = handle_get_node(
+        &node_service,
+        json!({"node_id": node_id, "fields": ["node_type"]}),
+    )
+    .await
+    .unwrap();
+    assert_eq!(trimmed, json!({"id": node_id, "nodeType": "text"}));
+
+    let result = nodespace_core::mcp::handlers::nodes::handle_query_nodes(
+        &node_service,
+        json!({
+            "content_contains": "budget",
+            "fields": ["content"],
+            "max_content_chars": 30
+        }),
+    )
+    .await
+    .unwrap();
+    let content = result["nodes"][0]["content"].as_str().unwrap();
+    assert!(content.starts_with('…'), "{}", content);
+    assert!(content.contains("budget"), "{}", content);
+    assert!(result["nodes"][0].get("createdAt").is_none());
+}
+
 // ============================================================================
 // Index-Based Operations Edge Cases
 // ============================================================================
@@ -2584,6 +2841,43 @@ async fn test_handle_query_nodes_with_offset() {
     assert!(nodes.len() <= 2);
 }
 
+#[tokio::test]
+async fn test_handle_query_nodes_next_cursor() {
+    let (node_service, _temp_dir) = create_test_env().await.unwrap();
+
+    for i in 1..=3 {
+        handle_create_node(
+            &node_service,
+            json!({"node_type": "text", "content": format!("Paged {}", i)}),
+        )
+        .await
+        .unwrap();
+    }
+
+    let query = json!({"content_contains": "Paged", "limit": 2});
+    let first = nodespace_core::mcp::handlers::nodes::handle_query_nodes(&node_service, query)
+        .await
+        .unwrap();
+    assert_eq!(first["count"], 2);
+    let cursor = first["next_cursor"].as_str().unwrap();
+
+    let second = nodespace_core::mcp::handlers::nodes::handle_query_nodes(
+        &node_service,
+        json!({"content_contains": "Paged", "limit": 2, "cursor": cursor}),
+    )
+    .await
+    .unwrap();
+    assert_eq!(second["count"], 1);
+    assert!(second["next_cursor"].is_null());
+    let first_ids: Vec<_> = first["nodes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|n| &n["id"])
+        .collect();
+    assert!(!first_ids.contains(&&second["nodes"][0]["id"]));
+}
+
 // ============================================================================
 // Markdown Handler Additional Edge Cases
 // ============================================================================
@@ -492,12 +492,15 @@ fn test_query_serialization_round_trip() {
             case_sensitive: None,
             relationship_type: None,
             node_id: None,
+            edge_filters: None,
+            max_depth: None,
         }],
         sorting: Some(vec![SortConfig {
             field: "created_at".to_string(),
             direction: SortDirection::Descending,
         }]),
         limit: Some(50),
+        offset: None,
     };
 
     // Serialize to JSON
@@ -534,6 +537,8 @@ fn test_filter_serialization() {
         case_sensitive: Some(false),
         relationship_type: None,
         node_id: None,
+        edge_filters: None,
+        max_depth: None,
     };
 
     let json = serde_json::to_value(&filter).unwrap();
@@ -163,6 +163,84 @@ async fn test_ttl_based_invalidation() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_schema_events_invalidate_cache() -> Result<()> {
+    use nodespace_core::db::events::DomainEvent;
+
+    let (store, _node_service, _temp_dir) = create_test_env().await?;
+
+    let cache = InboundRelationshipCache::new(store);
+    cache.force_refresh().await?;
+
+    // Updates of ordinary nodes leave the cache alone
+    cache.apply(&DomainEvent::NodeUpdated {
+        node_id: "some-note".to_string(),
+        source_client_id: None,
+        correlation_id: None,
+    });
+    assert!(!cache.stats().await.is_stale);
+
+    // The core task schema was loaded, so an update of it invalidates
+    cache.apply(&DomainEvent::NodeUpdated {
+        node_id: "task".to_string(),
+        source_client_id: None,
+        correlation_id: None,
+    });
+    assert!(cache.stats().await.is_stale);
+
+    cache.force_refresh().await?;
+    cache.apply(&DomainEvent::NodeCreated {
+        node_id: "invoice".to_string(),
+        node_type: "schema".to_string(),
+        source_client_id: None,
+        correlation_id: None,
+    });
+    assert!(cache.stats().await.is_stale);
+    Ok(())
+}
+
+// =========================================================================
+// Eviction Tests
+// =========================================================================
+
+#[tokio::test]
+async fn test_lru_eviction_reloads_on_miss() -> Result<()> {
+    use nodespace_core::models::Node;
+    use serde_json::json;
+
+    let (store, _node_service, _temp_dir) = create_test_env().await?;
+    let schema_node = Node::new_with_id(
+        "invoice_lru_test".to_string(),
+        "schema".to_string(),
+        "Invoice Schema".to_string(),
+        json!({
+            "name": "invoice_lru_test",
+            "relationships": [
+                { "name": "billed_to", "direction": "out", "cardinality": "one", "targetType": "customer" },
+                { "name": "for_project", "direction": "out", "cardinality": "one", "targetType": "project" }
+            ]
+        }),
+    );
+    store.create_node(schema_node, None).await?;
+
+    let cache = InboundRelationshipCache::new(store).with_max_target_types(1);
+    assert_eq!(cache.get_inbound_relationships("customer").await?.len(), 1);
+    assert_eq!(cache.get_inbound_relationships("project").await?.len(), 1);
+    // A repeated lookup is a hit
+    assert_eq!(cache.get_inbound_relationships("project").await?.len(), 1);
+
+    let stats = cache.stats().await;
+    assert_eq!(stats.target_types, 1, "Only one target type should be kept");
+    assert!(stats.evictions >= 2);
+    assert!(stats.misses >= 1);
+    assert!(stats.hits >= 1);
+
+    // The full index is still available
+    let all = cache.get_all_inbound_relationships().await?;
+    assert!(all.contains_key("customer") && all.contains_key("project"));
+    Ok(())
+}
+
 // =========================================================================
 // Get All Relationships Tests
 // =========================================================================
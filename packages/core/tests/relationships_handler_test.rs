@@ -221,6 +221,7 @@ fn test_get_related_nodes_output_camel_case() {
         direction: "out".to_string(),
         related_nodes: vec![json!({"id": "customer-001"})],
         count: 1,
+        next_cursor: None,
     };
 
     let json = serde_json::to_value(&output).unwrap();
@@ -231,6 +232,8 @@ fn test_get_related_nodes_output_camel_case() {
     assert_eq!(json["direction"], "out");
     assert_eq!(json["relatedNodes"].as_array().unwrap().len(), 1);
     assert_eq!(json["count"], 1);
+    // The cursor key matches the other list tools
+    assert!(json["next_cursor"].is_null());
 
     // Verify snake_case NOT used
     assert!(json.get("node_id").is_none());
@@ -340,6 +343,7 @@ fn test_get_related_nodes_output_serialization() {
         direction: "out".to_string(),
         related_nodes: vec![json!({"id": "node-002"}), json!({"id": "node-003"})],
         count: 2,
+        next_cursor: None,
     };
 
     let json = serde_json::to_value(&output).unwrap();
@@ -392,6 +396,7 @@ fn test_output_with_empty_related_nodes() {
         direction: "out".to_string(),
         related_nodes: vec![],
         count: 0,
+        next_cursor: None,
     };
 
     let json = serde_json::to_value(&output).unwrap();
@@ -36,7 +36,11 @@ async fn create_test_services() -> Result<(
 
     let node_service = Arc::new(NodeService::new(&mut store).await?);
     let nlp_engine = create_test_nlp_engine();
-    let embedding_service = Arc::new(NodeEmbeddingService::new(nlp_engine, store.clone()));
+    let embedding_service = Arc::new(NodeEmbeddingService::new(
+        nlp_engine,
+        store.clone(),
+        node_service.workspace_lock().clone(),
+    ));
 
     Ok((embedding_service, node_service, store, temp_dir))
 }
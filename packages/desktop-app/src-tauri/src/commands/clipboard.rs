@@ -0,0 +1,53 @@
+//! Clipboard commands
+//!
+//! Multi-line pastes are parsed in core by `PasteProcessor` (markdown, HTML,
+//! indented plain text, Notion and Tana) and inserted as a node tree.
+
+use nodespace_core::services::{PasteOutcome, PastePayload, PasteProcessor};
+use nodespace_core::NodeService;
+use tauri::State;
+
+use super::nodes::CommandError;
+
+use crate::constants::TAURI_CLIENT_ID;
+
+/// Paste clipboard content as structured nodes
+///
+/// Detects the clipboard format (unless `payload.format` is given), converts it
+/// into a node hierarchy, and inserts the top-level nodes under `parent_id`
+/// after `insert_after_node_id`.
+///
+/// # Arguments
+/// * `service` - NodeService instance from Tauri state
+/// * `payload` - Clipboard `text/plain` and/or `text/html` content
+/// * `parent_id` - Parent for the pasted top-level nodes (None = root level)
+/// * `insert_after_node_id` - Sibling to insert after (None = first child)
+///
+/// # Returns
+/// * `Ok(PasteOutcome)` - Detected format plus root and created node IDs
+/// * `Err(CommandError)` - Payload too large or node creation failed
+///
+/// # Example Frontend Usage
+/// ```typescript
+/// const outcome = await invoke('paste_structured', {
+///   payload: {
+///     text: event.clipboardData.getData('text/plain'),
+///     html: event.clipboardData.getData('text/html')
+///   },
+///   parentId: currentParentId,
+///   insertAfterNodeId: focusedNodeId
+/// });
+/// ```
+#[tauri::command]
+pub async fn paste_structured(
+    service: State<'_, NodeService>,
+    payload: PastePayload,
+    parent_id: Option<String>,
+    insert_after_node_id: Option<String>,
+) -> Result<PasteOutcome, CommandError> {
+    let service = service.with_client(TAURI_CLIENT_ID);
+    PasteProcessor::new()
+        .paste(&service, &payload, parent_id, insert_after_node_id)
+        .await
+        .map_err(Into::into)
+}
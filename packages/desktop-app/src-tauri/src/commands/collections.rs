@@ -5,10 +5,13 @@
 //! - Managing collection membership (add/remove nodes)
 //! - Path-based collection operations
 
-use nodespace_core::services::CollectionService;
+use nodespace_core::db::CollectionStats;
+use nodespace_core::services::{
+    CollectionInfo, CollectionService, RenamedPath, SmartCollectionService,
+};
 use nodespace_core::{models, Node, NodeService};
-use serde::Serialize;
 use serde_json::Value;
+use std::sync::Arc;
 use tauri::State;
 
 use super::nodes::CommandError;
@@ -21,6 +24,8 @@ fn node_to_typed_value(node: Node) -> Result<Value, CommandError> {
         message: e.clone(),
         code: "CONVERSION_ERROR".to_string(),
         details: Some(e),
+        params: None,
+        correlation_id: None,
     })
 }
 
@@ -30,22 +35,11 @@ fn nodes_to_typed_values(nodes: Vec<Node>) -> Result<Vec<Value>, CommandError> {
         message: e.clone(),
         code: "CONVERSION_ERROR".to_string(),
         details: Some(e),
+        params: None,
+        correlation_id: None,
     })
 }
 
-/// Collection with member count and hierarchy info for UI display
-#[derive(Debug, Serialize)]
-#[serde(rename_all = "camelCase")]
-pub struct CollectionInfo {
-    /// The collection node
-    #[serde(flatten)]
-    pub node: Value,
-    /// Number of direct members in this collection
-    pub member_count: usize,
-    /// IDs of parent collections (collections this collection is nested under)
-    pub parent_collection_ids: Vec<String>,
-}
-
 /// Get all collection nodes in the database
 ///
 /// Returns all nodes with node_type = 'collection', useful for building
@@ -81,31 +75,76 @@ pub async fn get_all_collections(
             message: format!("Failed to query collections: {}", e),
             code: "QUERY_ERROR".to_string(),
             details: Some(format!("{}", e)),
+            params: None,
+            correlation_id: None,
         })?;
 
-    let mut result = Vec::with_capacity(collections_with_counts.len());
-    for (collection, member_count) in collections_with_counts {
-        // Extract parent collection IDs from member_of field
-        // Collections can be nested (a collection is member_of another collection)
-        let parent_collection_ids = collection.member_of.clone();
-        let node_value = node_to_typed_value(collection)?;
-        result.push(CollectionInfo {
-            node: node_value,
-            member_count,
-            parent_collection_ids,
-        });
-    }
+    collections_with_counts
+        .into_iter()
+        .map(|(collection, member_count)| {
+            CollectionInfo::new(collection, member_count).map_err(|e| CommandError {
+                message: e.clone(),
+                code: "CONVERSION_ERROR".to_string(),
+                details: Some(e),
+                params: None,
+                correlation_id: None,
+            })
+        })
+        .collect()
+}
+
+/// Get member counts and activity for a collection path
+///
+/// Counts come from one aggregated query, so the sidebar can show them
+/// without loading the member lists.
+///
+/// # Arguments
+/// * `service` - NodeService instance from Tauri state
+/// * `collection_path` - Path like "hr:policy:vacation"
+///
+/// # Returns
+/// * `Ok(CollectionStats)` - Direct and recursive member counts, number of
+///   sub-collections, last activity and recursive members by node type
+/// * `Err(CommandError)` - Error if no collection exists at the path
+///
+/// # Example Frontend Usage
+/// ```typescript
+/// const stats = await invoke('get_collection_stats', {
+///   collectionPath: 'engineering'
+/// });
+/// // { directMembers: 4, recursiveMembers: 12, subcollections: 2,
+/// //   lastActivity: '2025-03-01T09:30:00Z', types: { task: 7, text: 5 } }
+/// ```
+#[tauri::command]
+pub async fn get_collection_stats(
+    service: State<'_, NodeService>,
+    collection_path: String,
+) -> Result<CollectionStats, CommandError> {
+    let store = service.store();
+    let collection_service = CollectionService::new(store, &service);
 
-    Ok(result)
+    collection_service
+        .get_collection_stats(&collection_path)
+        .await
+        .map_err(|e| CommandError {
+            message: format!("Failed to get collection stats: {}", e),
+            code: "COLLECTION_ERROR".to_string(),
+            details: Some(format!("{}", e)),
+            params: None,
+            correlation_id: None,
+        })
 }
 
 /// Get members of a specific collection
 ///
 /// Returns all nodes that belong to the specified collection via member_of edge.
 /// Single query that traverses the relationship and returns full Node data.
+/// A smart collection returns its query's results instead, cached until a
+/// change may affect them.
 ///
 /// # Arguments
 /// * `service` - NodeService instance from Tauri state
+/// * `smart_collections` - SmartCollectionService instance from Tauri state
 /// * `collection_id` - ID of the collection to get members for
 ///
 /// # Returns
@@ -121,19 +160,32 @@ pub async fn get_all_collections(
 #[tauri::command]
 pub async fn get_collection_members(
     service: State<'_, NodeService>,
+    smart_collections: State<'_, Arc<SmartCollectionService>>,
     collection_id: String,
 ) -> Result<Vec<Value>, CommandError> {
+    let query_error = |e: String| CommandError {
+        message: format!("Failed to get collection members: {}", e),
+        code: "QUERY_ERROR".to_string(),
+        details: Some(e),
+        params: None,
+        correlation_id: None,
+    };
+
+    if let Some(members) = smart_collections
+        .members(&collection_id)
+        .await
+        .map_err(|e| query_error(e.to_string()))?
+    {
+        return nodes_to_typed_values(members);
+    }
+
     let store = service.store();
 
     // Single query: traverse relationship and get full node data
     let members = store
         .get_collection_members(&collection_id)
         .await
-        .map_err(|e| CommandError {
-            message: format!("Failed to get collection members: {}", e),
-            code: "QUERY_ERROR".to_string(),
-            details: Some(format!("{}", e)),
-        })?;
+        .map_err(|e| query_error(e.to_string()))?;
 
     nodes_to_typed_values(members)
 }
@@ -165,6 +217,8 @@ pub async fn get_collection_members_recursive(
             message: format!("Failed to get recursive collection members: {}", e),
             code: "QUERY_ERROR".to_string(),
             details: Some(format!("{}", e)),
+            params: None,
+            correlation_id: None,
         })?;
 
     // Batch fetch all nodes in a single query (avoids N+1 problem)
@@ -175,6 +229,8 @@ pub async fn get_collection_members_recursive(
             message: format!("Failed to batch fetch nodes: {}", e),
             code: "QUERY_ERROR".to_string(),
             details: Some(format!("{}", e)),
+            params: None,
+            correlation_id: None,
         })?;
 
     // Preserve order from member_ids and collect found nodes
@@ -219,6 +275,8 @@ pub async fn get_node_collections(
             message: format!("Failed to get node collections: {}", e),
             code: "QUERY_ERROR".to_string(),
             details: Some(format!("{}", e)),
+            params: None,
+            correlation_id: None,
         })
 }
 
@@ -258,6 +316,8 @@ pub async fn add_node_to_collection(
             message: format!("Failed to add node to collection: {}", e),
             code: "COLLECTION_ERROR".to_string(),
             details: Some(format!("{}", e)),
+            params: None,
+            correlation_id: None,
         })
 }
 
@@ -297,6 +357,8 @@ pub async fn add_node_to_collection_path(
             message: format!("Failed to add node to collection path: {}", e),
             code: "COLLECTION_ERROR".to_string(),
             details: Some(format!("{}", e)),
+            params: None,
+            correlation_id: None,
         })?;
 
     Ok(resolved.leaf_id().to_string())
@@ -338,6 +400,54 @@ pub async fn remove_node_from_collection(
             message: format!("Failed to remove node from collection: {}", e),
             code: "COLLECTION_ERROR".to_string(),
             details: Some(format!("{}", e)),
+            params: None,
+            correlation_id: None,
+        })
+}
+
+/// Move a member to another position within a collection
+///
+/// # Arguments
+/// * `service` - NodeService instance from Tauri state
+/// * `collection_id` - ID of the collection
+/// * `member_id` - ID of the member to move
+/// * `insert_after_member_id` - Member to place it after (None = first)
+///
+/// # Returns
+/// * `Ok(())` - Member moved successfully
+/// * `Err(CommandError)` - Error if the node is not a member or the move fails
+///
+/// # Example Frontend Usage
+/// ```typescript
+/// await invoke('reorder_collection_member', {
+///   collectionId: 'collection-456',
+///   memberId: 'node-123',
+///   insertAfterMemberId: 'node-789'
+/// });
+/// ```
+#[tauri::command]
+pub async fn reorder_collection_member(
+    service: State<'_, NodeService>,
+    collection_id: String,
+    member_id: String,
+    insert_after_member_id: Option<String>,
+) -> Result<(), CommandError> {
+    let store = service.store();
+    let collection_service = CollectionService::new(store, &service);
+
+    collection_service
+        .reorder_collection_member(
+            &collection_id,
+            &member_id,
+            insert_after_member_id.as_deref(),
+        )
+        .await
+        .map_err(|e| CommandError {
+            message: format!("Failed to reorder collection member: {}", e),
+            code: "COLLECTION_ERROR".to_string(),
+            details: Some(format!("{}", e)),
+            params: None,
+            correlation_id: None,
         })
 }
 
@@ -368,6 +478,8 @@ pub async fn find_collection_by_path(
             message: format!("Failed to find collection: {}", e),
             code: "QUERY_ERROR".to_string(),
             details: Some(format!("{}", e)),
+            params: None,
+            correlation_id: None,
         })?;
 
     match result {
@@ -401,6 +513,8 @@ pub async fn get_collection_by_name(
             message: format!("Failed to get collection by name: {}", e),
             code: "QUERY_ERROR".to_string(),
             details: Some(format!("{}", e)),
+            params: None,
+            correlation_id: None,
         })?;
 
     match result {
@@ -441,6 +555,8 @@ pub async fn create_collection(
             message: format!("Failed to check collection: {}", e),
             code: "QUERY_ERROR".to_string(),
             details: Some(format!("{}", e)),
+            params: None,
+            correlation_id: None,
         })?
         .is_some()
     {
@@ -448,6 +564,8 @@ pub async fn create_collection(
             message: format!("Collection '{}' already exists", name),
             code: "COLLECTION_EXISTS".to_string(),
             details: None,
+            params: None,
+            correlation_id: None,
         });
     }
 
@@ -472,6 +590,8 @@ pub async fn create_collection(
             message: format!("Failed to create collection: {}", e),
             code: "CREATE_ERROR".to_string(),
             details: Some(format!("{}", e)),
+            params: None,
+            correlation_id: None,
         })?;
 
     Ok(node_id)
@@ -510,6 +630,8 @@ pub async fn rename_collection(
             message: format!("Failed to check collection: {}", e),
             code: "QUERY_ERROR".to_string(),
             details: Some(format!("{}", e)),
+            params: None,
+            correlation_id: None,
         })?
     {
         if existing.id != collection_id {
@@ -517,6 +639,8 @@ pub async fn rename_collection(
                 message: format!("Collection '{}' already exists", new_name),
                 code: "COLLECTION_EXISTS".to_string(),
                 details: None,
+                params: None,
+                correlation_id: None,
             });
         }
     }
@@ -535,11 +659,61 @@ pub async fn rename_collection(
             message: format!("Failed to rename collection: {}", e),
             code: "UPDATE_ERROR".to_string(),
             details: Some(format!("{}", e)),
+            params: None,
+            correlation_id: None,
         })?;
 
     node_to_typed_value(node)
 }
 
+/// Rename or move a collection by path, keeping its whole subtree
+///
+/// The last segment of `new_path` is the new name and the rest the new
+/// parent, created as needed. Members and sub-collections stay attached, so
+/// every path below the collection changes with it.
+///
+/// # Arguments
+/// * `service` - NodeService instance from Tauri state
+/// * `old_path` - Current path like "hr:policy"
+/// * `new_path` - New path like "legal:policies"
+///
+/// # Returns
+/// * `Ok(RenamedPath)` - The renamed collection and the old and new string of
+///   its path and of every path below it
+/// * `Err(CommandError)` - Error if the path doesn't exist, the new name is
+///   taken or the move would nest the collection inside itself
+///
+/// # Example Frontend Usage
+/// ```typescript
+/// const { collection, paths } = await invoke('rename_collection_path', {
+///   oldPath: 'hr:policy',
+///   newPath: 'legal:policies'
+/// });
+/// // paths: [{ from: 'hr:policy', to: 'legal:policies' },
+/// //         { from: 'hr:policy:vacation', to: 'legal:policies:vacation' }]
+/// ```
+#[tauri::command]
+pub async fn rename_collection_path(
+    service: State<'_, NodeService>,
+    old_path: String,
+    new_path: String,
+) -> Result<RenamedPath, CommandError> {
+    let service = service.with_client(TAURI_CLIENT_ID);
+    let store = service.store();
+    let collection_service = CollectionService::new(store, &service);
+
+    collection_service
+        .rename_path(&old_path, &new_path)
+        .await
+        .map_err(|e| CommandError {
+            message: format!("Failed to rename collection path: {}", e),
+            code: "COLLECTION_ERROR".to_string(),
+            details: Some(format!("{}", e)),
+            params: None,
+            correlation_id: None,
+        })
+}
+
 /// Delete a collection
 ///
 /// Deletes the collection node. Member nodes are NOT deleted, only their
@@ -568,6 +742,8 @@ pub async fn delete_collection(
             message: format!("Failed to delete collection: {}", e),
             code: "DELETE_ERROR".to_string(),
             details: Some(format!("{}", e)),
+            params: None,
+            correlation_id: None,
         })?;
 
     Ok(())
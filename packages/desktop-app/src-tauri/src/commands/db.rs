@@ -4,22 +4,30 @@
 //! As of Issue #690, SchemaService is removed - schema operations use NodeService directly.
 
 use crate::commands::embeddings::EmbeddingState;
-use nodespace_core::services::{EmbeddingProcessor, NodeEmbeddingService};
+use nodespace_core::db::{
+    health, LockOwner, ProcessLock, ProcessLockError, RecoveryAction, StoreHealth,
+};
+use nodespace_core::services::{
+    AttachmentStore, EditJournal, EmbeddingProcessor, NodeEmbeddingService, EDIT_JOURNAL_FILE,
+};
 use nodespace_core::{NodeService, SurrealStore};
-use nodespace_nlp_engine::{EmbeddingConfig, EmbeddingService};
+use nodespace_nlp_engine::{
+    EmbeddingConfig, EmbeddingService, ModelManager, SummarizationConfig, SummarizationService,
+};
 use std::path::PathBuf;
 use std::sync::Arc;
 use tauri::path::BaseDirectory;
 use tauri::{AppHandle, Manager};
 use tokio::fs;
 
-use crate::constants::EMBEDDING_MODEL_FILENAME;
+use crate::constants::{EMBEDDING_MODEL_FILENAME, EMBEDDING_MODEL_NAME};
 
 /// Resolve the path to the bundled NLP model (GGUF format for llama.cpp)
 ///
 /// Checks multiple locations in order:
 /// 1. Bundled resources (for production builds)
 /// 2. User's ~/.nodespace/models/ directory (fallback for dev)
+/// 3. The newest version downloaded by the `ModelManager`
 ///
 /// # Arguments
 /// * `app` - Tauri application handle for resource resolution
@@ -27,7 +35,7 @@ use crate::constants::EMBEDDING_MODEL_FILENAME;
 /// # Returns
 /// * `Ok(PathBuf)` - Path to the GGUF model file
 /// * `Err(String)` - Error if model not found anywhere
-fn resolve_bundled_model_path(app: &AppHandle) -> Result<PathBuf, String> {
+async fn resolve_bundled_model_path(app: &AppHandle) -> Result<PathBuf, String> {
     // Try bundled resources first (production builds)
     if let Ok(resource_path) = app.path().resolve(
         format!("resources/models/{}", EMBEDDING_MODEL_FILENAME),
@@ -51,12 +59,102 @@ fn resolve_bundled_model_path(app: &AppHandle) -> Result<PathBuf, String> {
         }
     }
 
+    if let Some(manager) = app.try_state::<Arc<ModelManager>>() {
+        if let Ok(Some(model)) = manager.latest(EMBEDDING_MODEL_NAME).await {
+            tracing::info!("Found downloaded model at: {:?}", model.path);
+            return Ok(model.path);
+        }
+    }
+
     Err(format!(
         "Model file not found. Please download {} to ~/.nodespace/models/",
         EMBEDDING_MODEL_FILENAME
     ))
 }
 
+/// Error from `initialize_database`
+///
+/// When the database itself is the problem, `health` says what is wrong and
+/// `actions` lists the recovery options to offer; retry with
+/// `initialize_database(recovery)` or `restore_database_from_backup`.
+#[derive(Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DatabaseInitError {
+    pub message: String,
+    pub health: Option<StoreHealth>,
+    pub actions: Vec<RecoveryAction>,
+}
+
+impl DatabaseInitError {
+    fn unhealthy(health: StoreHealth) -> Self {
+        eprintln!("❌ [init_services] {}", health.message());
+        tracing::error!("❌ [init_services] {}", health.message());
+        Self {
+            message: health.message(),
+            actions: health.actions(),
+            health: Some(health),
+        }
+    }
+}
+
+impl From<String> for DatabaseInitError {
+    fn from(message: String) -> Self {
+        Self {
+            message,
+            health: None,
+            actions: Vec::new(),
+        }
+    }
+}
+
+/// Open the database, diagnosing any failure with a `StoreHealth`
+///
+/// With `repair`, a stale lock file is removed and placeholder schemas are
+/// created for node types whose schema is missing. A `read_only` workspace
+/// opens despite missing schemas, since nothing will be written.
+async fn open_store(
+    db_path: &std::path::Path,
+    repair: bool,
+    read_only: bool,
+) -> Result<SurrealStore, DatabaseInitError> {
+    let mut store = match SurrealStore::open(db_path.to_path_buf(), read_only).await {
+        Ok(store) => store,
+        Err(e) => match StoreHealth::from_open_error(db_path, &e) {
+            StoreHealth::Locked { lock_file } if repair => {
+                health::remove_stale_lock(db_path)
+                    .map_err(|e| format!("Failed to remove {}: {}", lock_file.display(), e))?;
+                tracing::warn!("Removed stale database lock file {}", lock_file.display());
+                SurrealStore::open(db_path.to_path_buf(), read_only)
+                    .await
+                    .map_err(|e| {
+                        DatabaseInitError::unhealthy(StoreHealth::from_open_error(db_path, &e))
+                    })?
+            }
+            health => return Err(DatabaseInitError::unhealthy(health)),
+        },
+    };
+
+    match store
+        .health_check()
+        .await
+        .map_err(|e| format!("Database health check failed: {}", e))?
+    {
+        StoreHealth::Healthy => {}
+        StoreHealth::SchemaMismatch { unknown_types } if repair => {
+            let created = store
+                .create_placeholder_schemas(&unknown_types)
+                .await
+                .map_err(|e| format!("Failed to create placeholder schemas: {}", e))?;
+            tracing::warn!("Created placeholder schemas for {:?}", created);
+        }
+        StoreHealth::SchemaMismatch { unknown_types } if read_only => {
+            tracing::warn!("Opening read-only with unknown types {:?}", unknown_types);
+        }
+        health => return Err(DatabaseInitError::unhealthy(health)),
+    }
+    Ok(store)
+}
+
 /// Initialize database services using AppConfig from Tauri state.
 ///
 /// Reads database path, model path, and client ID from AppConfig which
@@ -66,7 +164,10 @@ fn resolve_bundled_model_path(app: &AppHandle) -> Result<PathBuf, String> {
 /// Uses Tauri's state management via `app.manage()`. Once initialized,
 /// services persist for the application lifetime. To change database location,
 /// the application must be restarted.
-async fn init_services(app: &AppHandle) -> Result<(), String> {
+async fn init_services(
+    app: &AppHandle,
+    recovery: Option<RecoveryAction>,
+) -> Result<(), DatabaseInitError> {
     eprintln!("🔧 [init_services] Starting service initialization...");
     tracing::info!("🔧 [init_services] Starting service initialization...");
 
@@ -75,33 +176,55 @@ async fn init_services(app: &AppHandle) -> Result<(), String> {
     let db_path = config.database_path.clone();
     let model_path = config.model_path.clone();
     let client_id = config.tauri_client_id.clone();
+    // Imported binaries live in a sibling folder so they move with the database
+    let attachments_path = db_path.with_file_name("attachments");
+    let journal_path = db_path.with_file_name(EDIT_JOURNAL_FILE);
 
     // Check if state already exists to prevent reinitialization
     if app.try_state::<SurrealStore>().is_some() {
         eprintln!("⚠️  [init_services] Database already initialized");
         return Err(
-            "Database already initialized. Restart the app to change location.".to_string(),
+            "Database already initialized. Restart the app to change location."
+                .to_string()
+                .into(),
         );
     }
 
+    // Keep the standalone MCP server (nodespace-mcp) off the workspace while the app has it
+    let process_lock = match ProcessLock::acquire(&db_path, &LockOwner::current("nodespace-app")) {
+        Ok(lock) => lock,
+        Err(e @ ProcessLockError::Held(_)) => {
+            return Err(DatabaseInitError {
+                message: format!("{}. Quit it, then try again.", e),
+                health: None,
+                actions: vec![RecoveryAction::Retry],
+            })
+        }
+        Err(e) => return Err(e.to_string().into()),
+    };
+
     // Initialize SurrealDB store
     eprintln!("🔧 [init_services] Initializing SurrealDB store...");
     tracing::info!("🔧 [init_services] Initializing SurrealDB store...");
-    let mut store = Arc::new(SurrealStore::new(db_path).await.map_err(|e| {
-        let msg = format!("Failed to initialize database: {}", e);
-        eprintln!("❌ [init_services] {}", msg);
-        msg
-    })?);
+    // Read-only for this session (recovery) or by preference
+    let read_only = recovery == Some(RecoveryAction::OpenReadOnly)
+        || crate::preferences::load_preferences(app).await?.read_only;
+    let repair = recovery == Some(RecoveryAction::Repair);
+    let mut store = Arc::new(open_store(&db_path, repair, read_only).await?);
     eprintln!("✅ [init_services] SurrealDB store initialized");
     tracing::info!("✅ [init_services] SurrealDB store initialized");
 
     // Initialize node service with SurrealStore
-    // NodeService::new() takes &mut Arc to enable cache updates during seeding (Issue #704)
+    // NodeService::new() takes &mut Arc to enable cache updates during seeding (Issue #704);
+    // on a read-only store it seeds nothing and starts read-only
     tracing::info!("🔧 [init_services] Initializing NodeService...");
     let mut node_service = NodeService::new(&mut store)
         .await
         .map_err(|e| format!("Failed to initialize node service: {}", e))?;
-    tracing::info!("✅ [init_services] NodeService initialized");
+    tracing::info!(
+        "✅ [init_services] NodeService initialized (read-only: {})",
+        read_only
+    );
 
     // Initialize NLP engine for embeddings
     tracing::info!("🔧 [init_services] Initializing NLP engine...");
@@ -110,6 +233,13 @@ async fn init_services(app: &AppHandle) -> Result<(), String> {
     let embedding_config = EmbeddingConfig {
         model_path: Some(model_path),
         ..Default::default()
+    }
+    .with_env_overrides();
+    // The summarization model runs wherever the embedding model was told to
+    let summarization_config = SummarizationConfig {
+        backend: embedding_config.backend,
+        n_threads: embedding_config.n_threads,
+        ..Default::default()
     };
 
     let mut nlp_engine = EmbeddingService::new(embedding_config)
@@ -121,10 +251,31 @@ async fn init_services(app: &AppHandle) -> Result<(), String> {
         .map_err(|e| format!("Failed to load NLP model: {}", e))?;
 
     let nlp_engine_arc = Arc::new(nlp_engine);
-    tracing::info!("✅ [init_services] NLP engine initialized");
+    tracing::info!(
+        "✅ [init_services] NLP engine initialized ({})",
+        nlp_engine_arc.device_info()
+    );
+
+    // Summaries are optional: without a generation model in ~/.nodespace/models
+    // the service returns extractive summaries
+    let mut summarizer = SummarizationService::new(summarization_config)
+        .map_err(|e| format!("Failed to initialize summarization service: {}", e))?;
+    summarizer
+        .initialize()
+        .map_err(|e| format!("Failed to load summarization model: {}", e))?;
+    let summarizer_arc = Arc::new(summarizer);
+    tracing::info!(
+        "✅ [init_services] Summarization service initialized (model: {})",
+        summarizer_arc.has_model()
+    );
 
     // Initialize embedding service with SurrealStore
-    let embedding_service = NodeEmbeddingService::new(nlp_engine_arc.clone(), store.clone());
+    let embedding_service = NodeEmbeddingService::new(
+        nlp_engine_arc.clone(),
+        store.clone(),
+        node_service.workspace_lock().clone(),
+    )
+    .with_summarizer(summarizer_arc.clone());
     let embedding_service_arc = Arc::new(embedding_service);
 
     // Initialize background embedding processor (event-driven, Issue #729)
@@ -138,10 +289,15 @@ async fn init_services(app: &AppHandle) -> Result<(), String> {
 
     // Wake processor on startup to process any existing stale embeddings
     // This handles cases where stale markers exist from previous sessions
-    processor.wake();
-    tracing::info!("🔔 [init_services] EmbeddingProcessor woken to process stale embeddings");
+    // (not in read-only mode, which leaves stored embeddings as they are)
+    if !read_only {
+        processor.wake();
+        tracing::info!("🔔 [init_services] EmbeddingProcessor woken to process stale embeddings");
+    }
 
     let node_service_arc = Arc::new(node_service);
+    let edit_journal = EditJournal::open(journal_path)
+        .map_err(|e| format!("Failed to open edit journal: {}", e))?;
     let processor_arc = Arc::new(processor);
 
     // Manage all services
@@ -155,16 +311,33 @@ async fn init_services(app: &AppHandle) -> Result<(), String> {
         service: embedding_service_arc,
         processor: processor_arc.clone(),
     });
-    app.manage(processor_arc);
+    app.manage(processor_arc.clone());
+    app.manage(AttachmentStore::new(attachments_path));
+    app.manage(edit_journal);
+    app.manage(process_lock);
     eprintln!("✅ [init_services] All services registered with Tauri");
     tracing::info!("✅ [init_services] All services registered with Tauri");
 
-    // Retrieve the shutdown token from Tauri state for background task coordination
-    let shutdown_token: tauri::State<crate::ShutdownToken> = app.state();
+    // Write back edits that a crash kept from being saved
+    crate::commands::journal::replay_pending_edits(
+        app,
+        &node_service_arc,
+        &app.state::<EditJournal>(),
+    )
+    .await;
+
+    // Retrieve the shutdown coordinator from Tauri state for background task coordination
+    let shutdown: tauri::State<crate::shutdown::ShutdownCoordinator> = app.state();
 
     // Initialize MCP server now that NodeService is available
     // MCP will use the same NodeService as Tauri commands
-    if let Err(e) = crate::initialize_mcp_server(app.clone(), shutdown_token.child_token()) {
+    let agent_profiles = crate::preferences::load_preferences(app)
+        .await
+        .map(|prefs| prefs.mcp_agent_profiles)
+        .unwrap_or_default();
+    if let Err(e) =
+        crate::initialize_mcp_server(app.clone(), agent_profiles, shutdown.child_token())
+    {
         tracing::error!("❌ Failed to initialize MCP server: {}", e);
         // Don't fail database init if MCP fails - MCP is optional
     }
@@ -175,12 +348,201 @@ async fn init_services(app: &AppHandle) -> Result<(), String> {
         app.clone(),
         node_service_arc.clone(),
         client_id,
-        shutdown_token.child_token(),
+        shutdown.child_token(),
     ) {
         tracing::error!("❌ Failed to initialize domain event forwarder: {}", e);
         // Don't fail database init if event forwarding fails - it's not critical
     }
 
+    // Run user lifecycle scripts against node events
+    crate::initialize_script_service(node_service_arc.clone(), shutdown.child_token());
+
+    // Keep the excerpts shown in search and autocomplete results current
+    if !read_only {
+        crate::initialize_root_excerpts(
+            node_service_arc.clone(),
+            summarizer_arc.clone(),
+            shutdown.child_token(),
+        );
+    }
+
+    // Mirror document roots to a markdown folder when the user has enabled it
+    match crate::preferences::load_preferences(app).await {
+        Ok(prefs) => {
+            if let Some(folder) = prefs.vault_mirror_path {
+                let mirror = crate::initialize_vault_mirror(
+                    node_service_arc.clone(),
+                    folder,
+                    prefs.vault_mirror_two_way && !read_only,
+                    shutdown.child_token(),
+                );
+                shutdown.add_flush_hook("vault mirror", async move {
+                    let _ = mirror.await;
+                });
+            }
+        }
+        Err(e) => tracing::warn!("Vault mirror not started: {}", e),
+    }
+
+    // Export documents to Spotlight / Windows Search when enabled
+    match crate::preferences::load_preferences(app).await {
+        Ok(prefs) if prefs.os_search_index => match crate::search_index_folder(app) {
+            Ok(folder) => crate::initialize_search_index(
+                node_service_arc.clone(),
+                folder,
+                shutdown.child_token(),
+            ),
+            Err(e) => tracing::warn!("Search index not started: {}", e),
+        },
+        Ok(_) => {}
+        Err(e) => tracing::warn!("Search index not started: {}", e),
+    }
+
+    // Carry open tasks from past days to today when the user has enabled it
+    match crate::preferences::load_preferences(app).await {
+        Ok(_) if read_only => {}
+        Ok(prefs) => {
+            if let Some(mode) = prefs.task_rollover {
+                crate::initialize_task_rollover(
+                    app.clone(),
+                    node_service_arc.clone(),
+                    mode,
+                    shutdown.child_token(),
+                );
+            }
+        }
+        Err(e) => tracing::warn!("Task roll-over not started: {}", e),
+    }
+
+    // Weekly reviews under week nodes, written every Monday when enabled
+    let weekly_digest = match crate::preferences::load_preferences(app).await {
+        Ok(prefs) => prefs.weekly_digest && !read_only,
+        Err(e) => {
+            tracing::warn!("Weekly digest not scheduled: {}", e);
+            false
+        }
+    };
+    crate::initialize_weekly_digest(
+        app.clone(),
+        node_service_arc.clone(),
+        store.clone(),
+        summarizer_arc,
+        weekly_digest,
+        shutdown.child_token(),
+    );
+
+    // Mention text following renames, when enabled
+    let propagate_renames = match crate::preferences::load_preferences(app).await {
+        Ok(prefs) => prefs.propagate_renames && !read_only,
+        Err(e) => {
+            tracing::warn!("Rename propagation not started: {}", e);
+            false
+        }
+    };
+    crate::initialize_rename_propagation(
+        app.clone(),
+        node_service_arc.clone(),
+        propagate_renames,
+        shutdown.child_token(),
+    );
+
+    // Idle auto-lock for passphrase-protected workspaces
+    match crate::preferences::load_preferences(app).await {
+        Ok(prefs) => node_service_arc.workspace_lock().set_auto_lock_after(
+            crate::commands::workspace_lock::auto_lock_duration(prefs.auto_lock_minutes),
+        ),
+        Err(e) => tracing::warn!("Auto-lock timeout not applied: {}", e),
+    }
+
+    // Language of user-facing error messages
+    match crate::preferences::load_preferences(app).await {
+        Ok(prefs) => {
+            if let Some(language) = prefs.display.language.as_deref() {
+                nodespace_core::i18n::set_locale(nodespace_core::i18n::Locale::from_tag(language));
+            }
+        }
+        Err(e) => tracing::warn!("Message language not applied: {}", e),
+    }
+    crate::initialize_workspace_auto_lock(
+        app.clone(),
+        node_service_arc.clone(),
+        shutdown.child_token(),
+    );
+
+    // Due-task reminders and MCP deletion notices
+    crate::initialize_notifications(
+        app.clone(),
+        node_service_arc.clone(),
+        shutdown.child_token(),
+    );
+
+    // Daily key result snapshots for goal trend charts
+    crate::initialize_goal_evaluation(
+        app.clone(),
+        node_service_arc.clone(),
+        store.clone(),
+        shutdown.child_token(),
+    );
+
+    // Document importance for search boosts and the central notes list
+    crate::initialize_importance(
+        app.clone(),
+        node_service_arc.clone(),
+        !read_only,
+        shutdown.child_token(),
+    );
+
+    // Purge expired trash and orphaned embeddings while the app sits idle
+    crate::initialize_database_maintenance(
+        node_service_arc.clone(),
+        store.clone(),
+        shutdown.child_token(),
+    );
+
+    // Live result diffs for saved queries the frontend watches
+    crate::initialize_query_watch(
+        app.clone(),
+        node_service_arc.clone(),
+        store.clone(),
+        shutdown.child_token(),
+    );
+
+    // Inbound relationship lookups, invalidated when schemas change
+    crate::initialize_relationship_cache(
+        app.clone(),
+        node_service_arc.clone(),
+        store.clone(),
+        shutdown.child_token(),
+    );
+
+    // Tell the frontend when store queries keep running over their budget
+    crate::initialize_slow_query_alerts(app.clone(), store.clone(), shutdown.child_token());
+
+    // ...and when the embedding cache is too small for the working set
+    crate::initialize_embedding_cache_pressure(
+        app.clone(),
+        nlp_engine_arc.clone(),
+        shutdown.child_token(),
+    );
+
+    // On exit, after the mirror: let the embedding batch in progress finish,
+    // then make sure the edit journal is on disk
+    shutdown.add_flush_hook("embedding queue", async move {
+        processor_arc.shutdown().await;
+    });
+    let app_for_journal = app.clone();
+    shutdown.add_flush_hook("edit journal", async move {
+        if let Err(e) = app_for_journal.state::<EditJournal>().sync() {
+            tracing::error!("Failed to sync edit journal: {}", e);
+        }
+    });
+
+    // Links opened before the database was ready can be resolved now
+    crate::commands::deep_link::notify_pending(app);
+
+    #[cfg(desktop)]
+    crate::tray::refresh_status(app);
+
     let _ = store; // Store still available for direct access if needed
 
     tracing::info!("✅ [init_services] Service initialization complete");
@@ -198,10 +560,14 @@ async fn init_services(app: &AppHandle) -> Result<(), String> {
 ///
 /// # Arguments
 /// * `app` - Tauri application handle
+/// * `recovery` - `repair` to fix the problem a previous attempt reported
+///   (stale lock file, missing schemas), `open_read_only` to open without
+///   writing anything this session; `retry` or none to just try again
 ///
 /// # Returns
 /// * `Ok(String)` - Path to the initialized database file
-/// * `Err(String)` - Error if initialization fails
+/// * `Err(DatabaseInitError)` - Error if initialization fails, with the
+///   database health and recovery actions when the database is the cause
 ///
 /// # Default Location (New Unified Path)
 /// - All platforms: ~/.nodespace/database/nodespace.db
@@ -216,7 +582,10 @@ async fn init_services(app: &AppHandle) -> Result<(), String> {
 /// - Cannot determine home directory
 /// - Database initialization fails
 #[tauri::command]
-pub async fn initialize_database(app: AppHandle) -> Result<String, String> {
+pub async fn initialize_database(
+    app: AppHandle,
+    recovery: Option<RecoveryAction>,
+) -> Result<String, DatabaseInitError> {
     // Attempt migration from old location
     crate::preferences::migrate_legacy_database_if_needed(&app).await?;
 
@@ -224,10 +593,7 @@ pub async fn initialize_database(app: AppHandle) -> Result<String, String> {
     let prefs = crate::preferences::load_preferences(&app).await?;
 
     // Determine database path (needed for directory creation)
-    let db_path = match &prefs.database_path {
-        Some(p) => p.clone(),
-        None => crate::preferences::get_default_database_path()?,
-    };
+    let db_path = database_path(&prefs)?;
 
     // Ensure database directory exists
     if let Some(parent) = db_path.parent() {
@@ -237,7 +603,7 @@ pub async fn initialize_database(app: AppHandle) -> Result<String, String> {
     }
 
     // Resolve model path
-    let model_path = resolve_bundled_model_path(&app)?;
+    let model_path = resolve_bundled_model_path(&app).await?;
 
     // Determine MCP port
     let mcp_port = std::env::var("MCP_PORT")
@@ -250,7 +616,43 @@ pub async fn initialize_database(app: AppHandle) -> Result<String, String> {
     app.manage(config);
 
     // Initialize services (reads config from Tauri state)
-    init_services(&app).await?;
+    init_services(&app, recovery).await?;
 
     Ok(db_path.to_string_lossy().to_string())
 }
+
+/// Replace an unusable database with a backup copy, then initialize it
+///
+/// `backup_path` is a database folder (e.g. from Time Machine or a manual
+/// copy). The current database is moved aside next to it, not deleted.
+#[tauri::command]
+pub async fn restore_database_from_backup(
+    app: AppHandle,
+    backup_path: String,
+) -> Result<String, DatabaseInitError> {
+    if app.try_state::<SurrealStore>().is_some() {
+        return Err(
+            "Cannot restore while the database is open. Restart the app first."
+                .to_string()
+                .into(),
+        );
+    }
+
+    let prefs = crate::preferences::load_preferences(&app).await?;
+    let db_path = database_path(&prefs)?;
+    let moved_to = health::restore_from_backup(&db_path, std::path::Path::new(&backup_path))
+        .map_err(|e| format!("Failed to restore backup: {}", e))?;
+    if let Some(moved_to) = moved_to {
+        tracing::warn!("Moved previous database to {}", moved_to.display());
+    }
+
+    initialize_database(app, None).await
+}
+
+/// Database location from preferences, or the default one
+fn database_path(prefs: &crate::preferences::AppPreferences) -> Result<PathBuf, String> {
+    match &prefs.database_path {
+        Some(p) => Ok(p.clone()),
+        None => crate::preferences::get_default_database_path(),
+    }
+}
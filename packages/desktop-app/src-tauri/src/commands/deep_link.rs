@@ -0,0 +1,110 @@
+//! `nodespace://` deep links opened from other applications
+//!
+//! The OS delivers URLs through the deep-link plugin, possibly before the
+//! database is open (cold start from a link). They are queued in
+//! [`PendingDeepLinks`] and the frontend is told via [`DEEP_LINK_EVENT`] to
+//! drain the queue with `take_deep_links` once it can navigate. Parsing and
+//! resolution live in core (`nodespace_core::services::deep_link`).
+
+use std::sync::Mutex;
+
+use nodespace_core::services::deep_link::{self, DeepLink, NavigationTarget};
+use nodespace_core::NodeService;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use super::nodes::CommandError;
+
+/// Event emitted to the main window when deep links are waiting
+pub const DEEP_LINK_EVENT: &str = "deep-link-opened";
+
+/// Deep links received from the OS that the frontend has not handled yet
+#[derive(Default)]
+pub struct PendingDeepLinks(Mutex<Vec<String>>);
+
+/// Queue URLs delivered by the OS and bring the main window forward
+pub fn handle_deep_links(app: &AppHandle, urls: Vec<String>) {
+    if urls.is_empty() {
+        return;
+    }
+    tracing::info!("Received {} deep link(s)", urls.len());
+    app.state::<PendingDeepLinks>()
+        .0
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .extend(urls);
+    notify_pending(app);
+}
+
+/// Tell the frontend to drain the queue, if anything is queued
+///
+/// Called again once the database is ready, since links that arrive earlier
+/// cannot be resolved.
+pub fn notify_pending(app: &AppHandle) {
+    let has_pending = app.try_state::<PendingDeepLinks>().is_some_and(|pending| {
+        !pending
+            .0
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .is_empty()
+    });
+    if !has_pending {
+        return;
+    }
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.unminimize();
+        let _ = window.show();
+        let _ = window.set_focus();
+        let _ = window.emit(DEEP_LINK_EVENT, ());
+    }
+}
+
+/// Resolve and clear queued deep links
+///
+/// Links stay queued until the database is initialized. Links that fail to
+/// parse or resolve (e.g. a deleted node) are logged and dropped.
+///
+/// # Example Frontend Usage
+/// ```typescript
+/// const targets = await invoke('take_deep_links');
+/// // [{ type: 'node', nodeId: '2025-01-15' },
+/// //  { type: 'search', query: 'roadmap', nodeIds: [...] },
+/// //  { type: 'collection', path: 'work:ideas', collectionId: '...' }]
+/// ```
+#[tauri::command]
+pub async fn take_deep_links(
+    app: AppHandle,
+    pending: State<'_, PendingDeepLinks>,
+) -> Result<Vec<NavigationTarget>, CommandError> {
+    let Some(service) = app.try_state::<NodeService>() else {
+        return Ok(Vec::new());
+    };
+    let urls = std::mem::take(&mut *pending.0.lock().unwrap_or_else(|e| e.into_inner()));
+
+    let mut targets = Vec::with_capacity(urls.len());
+    for url in urls {
+        let resolved = match DeepLink::parse(&url) {
+            Ok(link) => deep_link::resolve_deep_link(&service, link).await,
+            Err(e) => Err(e),
+        };
+        match resolved {
+            Ok(target) => targets.push(target),
+            Err(e) => tracing::warn!("Ignoring deep link {}: {}", url, e),
+        }
+    }
+    Ok(targets)
+}
+
+/// Resolve a single `nodespace://` URI
+///
+/// # Example Frontend Usage
+/// ```typescript
+/// const target = await invoke('resolve_deep_link', { uri: 'nodespace://date/today' });
+/// ```
+#[tauri::command]
+pub async fn resolve_deep_link(
+    service: State<'_, NodeService>,
+    uri: String,
+) -> Result<NavigationTarget, CommandError> {
+    let link = DeepLink::parse(&uri)?;
+    Ok(deep_link::resolve_deep_link(&service, link).await?)
+}
@@ -3,14 +3,25 @@
 //! These commands provide insight into the database state for debugging
 //! issues where nodes don't persist on some machines.
 
-use nodespace_core::services::CreateNodeParams;
+use nodespace_core::db::maintenance::DEFAULT_TRASH_RETENTION;
+use nodespace_core::db::{MaintenanceReport, SlowQuery, TableSize};
+use nodespace_core::services::{
+    get_maintenance_report as build_maintenance_report, CacheStats, CreateNodeParams,
+    GraphMaintenanceReport, InboundRelationshipCache, NodeServiceError, QueryDefinition,
+    QueryExplanation, QueryService, DEFAULT_STALE_MONTHS,
+};
 use nodespace_core::{NodeQuery, NodeService, SurrealStore};
+use nodespace_nlp_engine::{BackendReport, EmbeddingCacheStats};
 use serde::Serialize;
 use std::fs;
 use std::path::PathBuf;
 use std::sync::Arc;
 use tauri::State;
 
+use super::embeddings::EmbeddingState;
+use super::nodes::CommandError;
+use crate::services::{BackgroundTaskStatus, TaskSupervisor};
+
 /// Diagnostic info about the database state
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -29,10 +40,55 @@ pub struct DatabaseDiagnostics {
     pub recent_node_ids: Vec<String>,
     /// Schema count
     pub schema_count: i64,
+    /// Inbound relationship cache statistics
+    pub relationship_cache: RelationshipCacheDiagnostics,
+    /// Embedding cache size and memory use
+    pub embedding_cache: EmbeddingCacheStats,
+    /// Backend the embedding model runs on (None without a model)
+    pub embedding_backend: Option<BackendReport>,
     /// Last error message if any operation failed
     pub errors: Vec<String>,
 }
 
+/// How well the inbound relationship cache is doing
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RelationshipCacheDiagnostics {
+    pub target_types: usize,
+    pub total_relationships: usize,
+    pub max_target_types: usize,
+    pub hits: u64,
+    pub misses: u64,
+    pub evictions: u64,
+    pub is_stale: bool,
+    /// Seconds since the schemas were last loaded (None if never)
+    pub seconds_since_refresh: Option<u64>,
+}
+
+impl From<CacheStats> for RelationshipCacheDiagnostics {
+    fn from(stats: CacheStats) -> Self {
+        Self {
+            target_types: stats.target_types,
+            total_relationships: stats.total_relationships,
+            max_target_types: stats.max_target_types,
+            hits: stats.hits,
+            misses: stats.misses,
+            evictions: stats.evictions,
+            is_stale: stats.is_stale,
+            seconds_since_refresh: stats.last_refresh.map(|at| at.elapsed().as_secs()),
+        }
+    }
+}
+
+/// Size of the database on disk and how it splits across tables
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DatabaseStorage {
+    /// Size of the database directory in bytes (if it exists)
+    pub database_size_bytes: Option<u64>,
+    pub tables: Vec<TableSize>,
+}
+
 /// Get directory size recursively
 fn get_directory_size(path: &PathBuf) -> Option<u64> {
     if !path.exists() {
@@ -64,6 +120,8 @@ fn get_directory_size(path: &PathBuf) -> Option<u64> {
 ///
 /// # Arguments
 /// * `store` - SurrealStore instance from Tauri state
+/// * `relationship_cache` - Inbound relationship cache, for its hit/miss statistics
+/// * `embeddings` - Embedding services, for the cache's memory use and the model's backend
 ///
 /// # Returns
 /// * `DatabaseDiagnostics` - Struct with all diagnostic info
@@ -73,10 +131,15 @@ fn get_directory_size(path: &PathBuf) -> Option<u64> {
 /// const diagnostics = await invoke('get_database_diagnostics');
 /// console.log('Database path:', diagnostics.databasePath);
 /// console.log('Node count:', diagnostics.totalNodeCount);
+/// console.log('Relationship cache hits:', diagnostics.relationshipCache.hits);
+/// console.log('Embedding cache bytes:', diagnostics.embeddingCache.bytes);
+/// console.log('Embedding backend:', diagnostics.embeddingBackend?.active);
 /// ```
 #[tauri::command]
 pub async fn get_database_diagnostics(
     store: State<'_, Arc<SurrealStore>>,
+    relationship_cache: State<'_, Arc<InboundRelationshipCache>>,
+    embeddings: State<'_, EmbeddingState>,
 ) -> Result<DatabaseDiagnostics, String> {
     let mut errors: Vec<String> = Vec::new();
 
@@ -156,6 +219,9 @@ pub async fn get_database_diagnostics(
         root_node_count,
         recent_node_ids,
         schema_count,
+        relationship_cache: relationship_cache.stats().await.into(),
+        embedding_cache: embeddings.service.nlp_engine().cache_stats(),
+        embedding_backend: embeddings.service.nlp_engine().backend_report().cloned(),
         errors,
     })
 }
@@ -270,6 +336,119 @@ pub async fn test_node_persistence(
     })
 }
 
+/// Explain how a saved query executes (development builds only)
+///
+/// Reports the generated SurrealQL, whether filtering finishes in Rust, the
+/// indexes used and the rows fetched, for diagnosing slow saved queries.
+///
+/// # Example Frontend Usage
+/// ```typescript
+/// const plan = await invoke('explain_query', { queryId });
+/// console.log(plan.sql, plan.path, plan.indexes, plan.estimatedRows);
+/// ```
+#[tauri::command]
+pub async fn explain_query(
+    service: State<'_, NodeService>,
+    query_service: State<'_, Arc<QueryService>>,
+    query_id: String,
+) -> Result<QueryExplanation, String> {
+    if !cfg!(debug_assertions) {
+        return Err("explain_query is only available in development builds".to_string());
+    }
+
+    let node = service
+        .get_node(&query_id)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Query node not found: {}", query_id))?;
+    let definition = QueryDefinition::from_saved_query(&node).map_err(|e| format!("{:#}", e))?;
+    query_service
+        .explain(&definition)
+        .await
+        .map_err(|e| format!("{:#}", e))
+}
+
+/// Report the database's size on disk and per-table row counts and sizes
+#[tauri::command]
+pub async fn get_database_storage(
+    config: State<'_, crate::config::AppConfig>,
+    store: State<'_, Arc<SurrealStore>>,
+) -> Result<DatabaseStorage, String> {
+    let tables = store
+        .maintenance()
+        .table_sizes()
+        .await
+        .map_err(|e| format!("{:#}", e))?;
+    Ok(DatabaseStorage {
+        database_size_bytes: get_directory_size(&config.database_path),
+        tables,
+    })
+}
+
+/// Store queries that took longer than the slow query threshold, oldest first
+///
+/// Only the most recent ones are kept. String literals in the SQL are masked
+/// and bound parameters are listed by name only.
+///
+/// # Example Frontend Usage
+/// ```typescript
+/// const slow = await invoke('get_slow_queries');
+/// // slow: [{ sql, binds, durationMs, at, correlationId }]
+/// ```
+#[tauri::command]
+pub fn get_slow_queries(store: State<'_, Arc<SurrealStore>>) -> Vec<SlowQuery> {
+    store.slow_queries().entries()
+}
+
+/// Purge expired trash and orphaned embeddings now
+///
+/// Also runs on its own while the app is idle (see
+/// `initialize_database_maintenance`). Refused while the workspace is locked
+/// or read-only.
+#[tauri::command]
+pub async fn run_database_maintenance(
+    service: State<'_, NodeService>,
+    store: State<'_, Arc<SurrealStore>>,
+) -> Result<MaintenanceReport, CommandError> {
+    service.ensure_unlocked()?;
+    if service.is_read_only() {
+        return Err(NodeServiceError::ReadOnly.into());
+    }
+    store
+        .maintenance()
+        .run(DEFAULT_TRASH_RETENTION)
+        .await
+        .map_err(|e| NodeServiceError::query_failed(format!("{:#}", e)).into())
+}
+
+/// Notes worth tidying: orphaned, stale, empty, and broken mentions
+///
+/// Notes without edits for `stale_after_months` (default 6) are stale.
+///
+/// # Example Frontend Usage
+/// ```typescript
+/// const report = await invoke('get_maintenance_report', { staleAfterMonths: 12 });
+/// // report: { staleAfterMonths, orphanedNotes, staleNotes, emptyNotes, brokenMentions }
+/// ```
+#[tauri::command]
+pub async fn get_maintenance_report(
+    service: State<'_, NodeService>,
+    stale_after_months: Option<u32>,
+) -> Result<GraphMaintenanceReport, CommandError> {
+    let stale_after_months = stale_after_months.unwrap_or(DEFAULT_STALE_MONTHS);
+    Ok(build_maintenance_report(&service, stale_after_months).await?)
+}
+
+/// Health of supervised background services (MCP server, event forwarder)
+///
+/// Changes are also pushed as `background-task-status` events.
+#[tauri::command]
+pub fn get_background_task_status(
+    supervisor: State<'_, TaskSupervisor>,
+) -> Vec<BackgroundTaskStatus> {
+    supervisor.statuses()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,34 @@
+//! Weekly review commands
+//!
+//! Reviews are written every Monday when enabled in settings (see
+//! `initialize_weekly_digest`); `generate_weekly_digest` writes one on
+//! demand, replacing an earlier review of the same week.
+
+use chrono::Local;
+use nodespace_core::models::Period;
+use nodespace_core::services::{DigestService, WeeklyDigest};
+use std::sync::Arc;
+use tauri::State;
+
+use super::nodes::CommandError;
+
+/// Write the review of `week_id` (defaults to the current week)
+///
+/// # Example Frontend Usage
+/// ```typescript
+/// const digest = await invoke('generate_weekly_digest', { weekId: '2025-W03' });
+/// // digest: { weekId, completedTasks, newNotes, mostEdited, overdueTasks,
+/// //           orphanedNotes, summary, digestNodeId }
+/// ```
+#[tauri::command]
+pub async fn generate_weekly_digest(
+    digest: State<'_, Arc<DigestService>>,
+    week_id: Option<String>,
+) -> Result<WeeklyDigest, CommandError> {
+    let today = Local::now().date_naive();
+    let week_id = week_id.unwrap_or_else(|| {
+        let [week, ..] = Period::containing(today);
+        week.id()
+    });
+    Ok(digest.generate(&week_id, today).await?)
+}
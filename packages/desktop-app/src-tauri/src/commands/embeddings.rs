@@ -6,7 +6,7 @@
 //! - Updating embeddings on content changes
 
 use crate::commands::nodes::CommandError;
-use nodespace_core::models::Node;
+use nodespace_core::api::SearchResultDto;
 use nodespace_core::services::{EmbeddingProcessor, NodeEmbeddingService};
 use nodespace_core::NodeService;
 use serde::{Deserialize, Serialize};
@@ -27,6 +27,8 @@ fn command_error(message: impl Into<String>, code: impl Into<String>) -> Command
         message: message.into(),
         code: code.into(),
         details: None,
+        params: None,
+        correlation_id: None,
     }
 }
 
@@ -40,6 +42,8 @@ fn command_error_with_details(
         message: message.into(),
         code: code.into(),
         details: Some(details.into()),
+        params: None,
+        correlation_id: None,
     }
 }
 
@@ -144,7 +148,7 @@ pub struct SearchRootsParams {
 ///
 /// # Returns
 ///
-/// Vector of nodes sorted by similarity score descending (most similar first)
+/// Search results (typed node plus similarity score), most similar first
 ///
 /// # Example (from frontend)
 ///
@@ -166,7 +170,9 @@ pub async fn search_roots(
     state: State<'_, EmbeddingState>,
     node_service: State<'_, NodeService>,
     params: SearchRootsParams,
-) -> Result<Vec<Node>, CommandError> {
+) -> Result<Vec<SearchResultDto>, CommandError> {
+    node_service.ensure_unlocked()?;
+
     // Validate query parameter
     if params.query.trim().is_empty() {
         return Err(command_error(
@@ -217,14 +223,16 @@ pub async fn search_roots(
         })?;
 
     // Fetch actual nodes for each search result
-    let mut nodes = Vec::with_capacity(search_results.len());
+    let mut results = Vec::with_capacity(search_results.len());
     for result in search_results {
         if let Ok(Some(node)) = store.get_node(&result.node_id).await {
-            nodes.push(node);
+            let hit = SearchResultDto::new(node, result.score)
+                .map_err(|e| command_error_with_details(e.clone(), "CONVERSION_ERROR", e))?;
+            results.push(hit);
         }
     }
 
-    Ok(nodes)
+    Ok(results)
 }
 
 /// Update embedding for a topic/root node immediately
@@ -0,0 +1,281 @@
+//! Export commands
+//!
+//! Subtrees are rendered to standalone HTML by core's `HtmlExporter`. PDF
+//! export prints that HTML with a headless Chromium-based browser, so PDFs look
+//! the same as the HTML export and no PDF layout engine has to be bundled.
+//!
+//! todo.txt export writes the tasks of a subtree for mobile todo.txt apps,
+//! and BibTeX export writes the references a subtree cites; the matching
+//! imports go through `import_files`.
+//!
+//! Share bundles are the read-only form meant for other people: an HTML page
+//! and a signed JSON document with share-scoped IDs, tracked so they can be
+//! revoked.
+
+use nodespace_core::exporters::{AttachmentMode, BibtexExporter, HtmlExporter, TodoTxtExporter};
+use nodespace_core::models::ShareRecord;
+use nodespace_core::services::AttachmentStore;
+use nodespace_core::NodeService;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use tauri::State;
+
+/// Environment variable overriding the browser used for PDF rendering
+const PDF_RENDERER_ENV: &str = "NODESPACE_PDF_RENDERER";
+
+/// Browser executables tried on `PATH`, in order
+const PATH_CANDIDATES: &[&str] = &[
+    "chromium",
+    "chromium-browser",
+    "google-chrome",
+    "google-chrome-stable",
+    "microsoft-edge",
+    "msedge",
+];
+
+/// Well-known install locations for platforms where browsers are not on `PATH`
+const INSTALL_CANDIDATES: &[&str] = &[
+    "/Applications/Google Chrome.app/Contents/MacOS/Google Chrome",
+    "/Applications/Chromium.app/Contents/MacOS/Chromium",
+    "/Applications/Microsoft Edge.app/Contents/MacOS/Microsoft Edge",
+    "/Applications/Brave Browser.app/Contents/MacOS/Brave Browser",
+    r"C:\Program Files\Google\Chrome\Application\chrome.exe",
+    r"C:\Program Files (x86)\Microsoft\Edge\Application\msedge.exe",
+];
+
+/// Export a subtree as a standalone HTML file
+///
+/// Images stored as attachments are embedded, so the file can be shared on
+/// its own. Returns the written path.
+///
+/// # Example Frontend Usage
+/// ```typescript
+/// const path = await invoke('export_subtree_html', {
+///   rootId: currentRootId,
+///   outputPath: '/Users/me/Desktop/plan.html'
+/// });
+/// ```
+#[tauri::command]
+pub async fn export_subtree_html(
+    node_service: State<'_, NodeService>,
+    attachments: State<'_, AttachmentStore>,
+    root_id: String,
+    output_path: String,
+) -> Result<String, String> {
+    let html = render_subtree(&node_service, &attachments, &root_id).await?;
+    tokio::fs::write(&output_path, html)
+        .await
+        .map_err(|e| format!("Failed to write {}: {}", output_path, e))?;
+    Ok(output_path)
+}
+
+/// Export a subtree as a PDF for sharing a read-only copy
+///
+/// Renders the same document as `export_subtree_html` and prints it with a
+/// headless Chromium-based browser (Chrome, Chromium, Edge or Brave). Set
+/// `NODESPACE_PDF_RENDERER` to a browser executable to override detection.
+/// Returns the written path.
+///
+/// # Example Frontend Usage
+/// ```typescript
+/// const path = await invoke('export_subtree_pdf', {
+///   rootId: currentRootId,
+///   outputPath: '/Users/me/Desktop/plan.pdf'
+/// });
+/// ```
+#[tauri::command]
+pub async fn export_subtree_pdf(
+    node_service: State<'_, NodeService>,
+    attachments: State<'_, AttachmentStore>,
+    root_id: String,
+    output_path: String,
+) -> Result<String, String> {
+    let html = render_subtree(&node_service, &attachments, &root_id).await?;
+    let browser = find_pdf_renderer().ok_or_else(|| {
+        format!(
+            "PDF export needs Chrome, Chromium or Edge installed (or {} set to a browser executable)",
+            PDF_RENDERER_ENV
+        )
+    })?;
+
+    // The browser needs a file to load; it is removed once printing finishes
+    let html_path =
+        std::env::temp_dir().join(format!("nodespace-export-{}.html", uuid::Uuid::new_v4()));
+    tokio::fs::write(&html_path, html)
+        .await
+        .map_err(|e| format!("Failed to write temporary HTML: {}", e))?;
+
+    let output = tokio::process::Command::new(&browser)
+        .arg("--headless")
+        .arg("--disable-gpu")
+        .arg("--no-pdf-header-footer")
+        .arg(format!("--print-to-pdf={}", output_path))
+        .arg(format!("file://{}", html_path.display()))
+        .output()
+        .await;
+    let _ = tokio::fs::remove_file(&html_path).await;
+
+    let output = output.map_err(|e| format!("Failed to run {}: {}", browser.display(), e))?;
+    if !output.status.success() || !PathBuf::from(&output_path).exists() {
+        return Err(format!(
+            "PDF rendering failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    tracing::info!("Exported {} to {}", root_id, output_path);
+    Ok(output_path)
+}
+
+/// Export the tasks under a root as a todo.txt file
+///
+/// Collection memberships are written as `+project` tags and the `contexts`
+/// property as `@context` tags. Returns the written path.
+///
+/// # Example Frontend Usage
+/// ```typescript
+/// const path = await invoke('export_todo_txt', {
+///   rootId: tasksRootId,
+///   outputPath: '/Users/me/Dropbox/todo/todo.txt'
+/// });
+/// ```
+#[tauri::command]
+pub async fn export_todo_txt(
+    node_service: State<'_, NodeService>,
+    root_id: String,
+    output_path: String,
+) -> Result<String, String> {
+    let content = TodoTxtExporter
+        .export_subtree(&node_service, &root_id)
+        .await
+        .map_err(|e| e.to_string())?;
+    tokio::fs::write(&output_path, content)
+        .await
+        .map_err(|e| format!("Failed to write {}: {}", output_path, e))?;
+    Ok(output_path)
+}
+
+/// Export the bibliography of a subtree as a BibTeX file
+///
+/// Includes every reference cited from the subtree and reference nodes
+/// stored in it. Returns the written path.
+///
+/// # Example Frontend Usage
+/// ```typescript
+/// const path = await invoke('export_bibtex', {
+///   rootId: paperRootId,
+///   outputPath: '/Users/me/paper/references.bib'
+/// });
+/// ```
+#[tauri::command]
+pub async fn export_bibtex(
+    node_service: State<'_, NodeService>,
+    root_id: String,
+    output_path: String,
+) -> Result<String, String> {
+    let content = BibtexExporter
+        .export_subtree(&node_service, &root_id)
+        .await
+        .map_err(|e| e.to_string())?;
+    tokio::fs::write(&output_path, content)
+        .await
+        .map_err(|e| format!("Failed to write {}: {}", output_path, e))?;
+    Ok(output_path)
+}
+
+/// Files written for a share bundle
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShareFiles {
+    pub share_id: String,
+    pub html_path: String,
+    pub json_path: String,
+}
+
+/// Create a read-only share bundle of a subtree
+///
+/// Writes `share-<id>.html` and `share-<id>.json` into `outputDir`.
+///
+/// # Example Frontend Usage
+/// ```typescript
+/// const files = await invoke('create_share_bundle', {
+///   rootId: currentRootId,
+///   outputDir: '/Users/me/Desktop'
+/// });
+/// ```
+#[tauri::command]
+pub async fn create_share_bundle(
+    node_service: State<'_, NodeService>,
+    root_id: String,
+    output_dir: String,
+) -> Result<ShareFiles, String> {
+    let bundle = node_service
+        .create_share_bundle(&root_id)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let stem = Path::new(&output_dir).join(format!("share-{}", bundle.share_id));
+    let html_path = stem.with_extension("html");
+    let json_path = stem.with_extension("json");
+    for (path, content) in [(&html_path, &bundle.html), (&json_path, &bundle.json)] {
+        tokio::fs::write(path, content)
+            .await
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    }
+
+    Ok(ShareFiles {
+        share_id: bundle.share_id,
+        html_path: html_path.display().to_string(),
+        json_path: json_path.display().to_string(),
+    })
+}
+
+/// List the shares published from a root, newest first
+#[tauri::command]
+pub async fn list_shares(
+    node_service: State<'_, NodeService>,
+    root_id: String,
+) -> Result<Vec<ShareRecord>, String> {
+    node_service
+        .list_shares(&root_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Revoke a share so its signed bundle no longer verifies
+#[tauri::command]
+pub async fn revoke_share(
+    node_service: State<'_, NodeService>,
+    share_id: String,
+) -> Result<(), String> {
+    node_service
+        .revoke_share(&share_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+async fn render_subtree(
+    node_service: &NodeService,
+    attachments: &AttachmentStore,
+    root_id: &str,
+) -> Result<String, String> {
+    HtmlExporter::new()
+        .with_attachments(attachments, AttachmentMode::Inline)
+        .export_subtree(node_service, root_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Locate a browser that supports `--print-to-pdf`
+fn find_pdf_renderer() -> Option<PathBuf> {
+    if let Some(path) = std::env::var_os(PDF_RENDERER_ENV) {
+        return Some(PathBuf::from(path));
+    }
+
+    let path_var = std::env::var_os("PATH").unwrap_or_default();
+    PATH_CANDIDATES
+        .iter()
+        .flat_map(|name| std::env::split_paths(&path_var).map(move |dir| dir.join(name)))
+        .chain(INSTALL_CANDIDATES.iter().map(PathBuf::from))
+        .find(|path| path.is_file())
+}
@@ -0,0 +1,26 @@
+//! Goal commands
+//!
+//! Key results are snapshotted in the background (see
+//! `initialize_goal_evaluation`); `get_goal_progress` evaluates a goal's key
+//! results on demand and returns them with their recorded trend.
+
+use nodespace_core::services::{GoalEvaluationService, GoalProgress};
+use std::sync::Arc;
+use tauri::State;
+
+use super::nodes::CommandError;
+
+/// Current progress of a goal and its key results, with snapshot history
+///
+/// # Example Frontend Usage
+/// ```typescript
+/// const progress = await invoke('get_goal_progress', { goalId });
+/// console.log(`${Math.round(progress.progress * 100)}% there`);
+/// ```
+#[tauri::command]
+pub async fn get_goal_progress(
+    evaluation: State<'_, Arc<GoalEvaluationService>>,
+    goal_id: String,
+) -> Result<GoalProgress, CommandError> {
+    Ok(evaluation.goal_progress(&goal_id).await?)
+}
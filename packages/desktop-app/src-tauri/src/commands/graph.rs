@@ -0,0 +1,60 @@
+//! Graph view commands
+//!
+//! The graph view shows documents and the links between them; see
+//! `NodeService::get_graph_data` for how relationships are lifted to
+//! documents and how the global graph is paged. Importance scores are
+//! refreshed in the background (see `initialize_importance`).
+
+use nodespace_core::models::{GraphData, GraphScope};
+use nodespace_core::services::{CentralNote, ImportanceService};
+use nodespace_core::NodeService;
+use std::sync::Arc;
+use tauri::State;
+
+use super::nodes::CommandError;
+
+/// Nodes and edges of the local or global graph
+///
+/// `depth` defaults to 1 and only applies to a local graph; `edgeTypes`
+/// defaults to mentions and embeds.
+///
+/// # Example Frontend Usage
+/// ```typescript
+/// const local = await invoke('get_graph_data', {
+///   scope: { type: 'local', nodeId },
+///   depth: 2,
+/// });
+/// const page = await invoke('get_graph_data', {
+///   scope: { type: 'global', offset: 0, limit: 500 },
+/// });
+/// // page: { nodes, edges, totalNodes, nextOffset }
+/// ```
+#[tauri::command]
+pub async fn get_graph_data(
+    service: State<'_, NodeService>,
+    scope: GraphScope,
+    depth: Option<usize>,
+    edge_types: Option<Vec<String>>,
+) -> Result<GraphData, CommandError> {
+    let edge_types = edge_types.unwrap_or_default();
+    Ok(service
+        .get_graph_data(scope, depth.unwrap_or(1), &edge_types)
+        .await?)
+}
+
+/// The most central documents by mention-graph importance
+///
+/// Reflects the last background refresh; empty until the first one.
+///
+/// # Example Frontend Usage
+/// ```typescript
+/// const notes = await invoke('get_central_notes', { limit: 10 });
+/// // notes: [{ nodeId, nodeType, title, score, rank, inbound, outbound }]
+/// ```
+#[tauri::command]
+pub async fn get_central_notes(
+    importance: State<'_, Arc<ImportanceService>>,
+    limit: Option<usize>,
+) -> Result<Vec<CentralNote>, CommandError> {
+    Ok(importance.most_central(limit.unwrap_or(20)).await?)
+}
@@ -26,11 +26,20 @@
 //! 2. **Phase 2 (Async)**: Spawn background task for bulk DB operations
 //!
 //! This returns immediately to the UI while heavy database work happens in background.
+//!
+//! ## Multi-Format File Drop
+//!
+//! `import_files` routes dropped files by extension to the core
+//! [`ImporterRegistry`] and records the outcome in an import-report node.
 
+use nodespace_core::importers::{
+    import_csv, resolve_people, CsvImportResult, CsvMapping, ImportError, ImportReport,
+    ImportReportEntry, ImporterRegistry,
+};
 use nodespace_core::mcp::handlers::markdown::{
     prepare_nodes_from_markdown, transform_links_in_nodes_with_mentions, PreparedNode,
 };
-use nodespace_core::services::{CollectionService, CreateNodeParams, NodeService};
+use nodespace_core::services::{AttachmentStore, CollectionService, CreateNodeParams, NodeService};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
@@ -828,7 +837,7 @@ pub async fn import_markdown_files(
             String,
             String,
             Option<String>,
-            f64,
+            String,
             serde_json::Value,
         )> = Vec::new();
         let mut collection_assignments: Vec<(String, String)> = Vec::new();
@@ -844,7 +853,7 @@ pub async fn import_markdown_files(
                 "header".to_string(),
                 prepared.root_content.clone(),
                 None, // Root has no parent
-                1.0,
+                String::new(),
                 root_props,
             ));
 
@@ -859,7 +868,7 @@ pub async fn import_markdown_files(
                     child.node_type.clone(),
                     child.content.clone(),
                     parent,
-                    child.order,
+                    child.order.clone(),
                     child.properties.clone(),
                 ));
             }
@@ -981,6 +990,23 @@ pub async fn import_markdown_files(
             },
         );
 
+        // The user has likely moved on while the import ran
+        if !crate::notifications::main_window_focused(&app_clone) {
+            let link = match prepared_files.as_slice() {
+                [only] => Some(crate::notifications::node_link(&only.root_id)),
+                _ => None,
+            };
+            crate::notifications::notify(
+                &app_clone,
+                "Import complete",
+                &format!(
+                    "Imported {} files ({} nodes)",
+                    successful_files_clone, total_nodes_clone
+                ),
+                link,
+            );
+        }
+
         tracing::info!(
             "Background import complete: {} files, {} nodes in {:?}",
             prepared_files.len(),
@@ -1050,6 +1076,348 @@ pub async fn import_markdown_directory(
     import_markdown_files(app, node_service, md_files, Some(import_options)).await
 }
 
+/// Progress event emitted by `import_files` (event name: `import-files-progress`)
+#[derive(Debug, Clone, Serialize)]
+pub struct FileDropProgressEvent {
+    /// File being processed (None for the final report step)
+    pub file_path: Option<String>,
+    /// Stage: "importing", "imported", "failed", "report", or "complete"
+    pub stage: String,
+    /// User-friendly message (e.g., "Importing: calendar.ics")
+    pub message: String,
+    /// Files processed so far
+    pub current: usize,
+    pub total: usize,
+}
+
+/// Result of a multi-format file drop import
+#[derive(Debug, Serialize)]
+pub struct FileDropImportResult {
+    pub total_files: usize,
+    pub successful: usize,
+    pub failed: usize,
+    /// Root ID of the import-report node (None if it could not be created)
+    pub report_id: Option<String>,
+    pub entries: Vec<ImportReportEntry>,
+    pub duration_ms: u128,
+}
+
+/// Import dropped files of any supported format
+///
+/// Routes each path by extension (md, csv, opml, ics, json, ...) to the matching core
+/// importer and creates one root per imported document. Files are processed
+/// sequentially with `import-files-progress` events after each file; failures
+/// (unreadable file, unsupported extension, parse error) are recorded and do
+/// not abort the batch. Finally an import-report node summarising every file
+/// is created.
+///
+/// # Example Frontend Usage
+/// ```typescript
+/// await listen('import-files-progress', (e) => updateProgress(e.payload));
+/// const result = await invoke('import_files', { paths: droppedPaths });
+/// ```
+#[tauri::command]
+pub async fn import_files(
+    app: AppHandle,
+    node_service: State<'_, NodeService>,
+    attachments: State<'_, AttachmentStore>,
+    paths: Vec<String>,
+) -> Result<FileDropImportResult, String> {
+    let start = std::time::Instant::now();
+    let registry = ImporterRegistry::with_defaults();
+    let total = paths.len();
+    let mut report = ImportReport::new();
+
+    for (index, file_path) in paths.iter().enumerate() {
+        let path = PathBuf::from(file_path);
+        let filename = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or(file_path)
+            .to_string();
+
+        let _ = app.emit(
+            "import-files-progress",
+            FileDropProgressEvent {
+                file_path: Some(file_path.clone()),
+                stage: "importing".to_string(),
+                message: format!("Importing: {}", filename),
+                current: index,
+                total,
+            },
+        );
+
+        let entry =
+            import_single_file(&node_service, &attachments, &registry, &path, file_path).await;
+        let (stage, message) = match &entry.error {
+            None => ("imported", format!("Imported: {}", filename)),
+            Some(e) => ("failed", format!("Failed: {} ({})", filename, e)),
+        };
+        report.push(entry);
+
+        let _ = app.emit(
+            "import-files-progress",
+            FileDropProgressEvent {
+                file_path: Some(file_path.clone()),
+                stage: stage.to_string(),
+                message,
+                current: index + 1,
+                total,
+            },
+        );
+    }
+
+    let _ = app.emit(
+        "import-files-progress",
+        FileDropProgressEvent {
+            file_path: None,
+            stage: "report".to_string(),
+            message: "Creating import report...".to_string(),
+            current: total,
+            total,
+        },
+    );
+
+    let (report_id, rows) = report.to_document().into_hierarchy();
+    let report_id = match node_service.bulk_create_hierarchy_root_notify(rows).await {
+        Ok(_) => Some(report_id),
+        Err(e) => {
+            tracing::error!("Failed to create import report: {}", e);
+            None
+        }
+    };
+
+    let successful = report.successful();
+    let failed = report.failed();
+    if !crate::notifications::main_window_focused(&app) {
+        crate::notifications::notify(
+            &app,
+            "Import complete",
+            &format!("Imported {} of {} files", successful, total),
+            report_id.as_deref().map(crate::notifications::node_link),
+        );
+    }
+    let _ = app.emit(
+        "import-files-progress",
+        FileDropProgressEvent {
+            file_path: None,
+            stage: "complete".to_string(),
+            message: format!("Imported {} of {} files", successful, total),
+            current: total,
+            total,
+        },
+    );
+
+    Ok(FileDropImportResult {
+        total_files: total,
+        successful,
+        failed,
+        report_id,
+        entries: report.entries,
+        duration_ms: start.elapsed().as_millis(),
+    })
+}
+
+/// Import a CSV file as typed nodes using a column mapping
+///
+/// Each row becomes a `mapping.node_type` node; mapped cells are coerced to
+/// their schema field types and validated. Invalid rows are skipped and
+/// reported in `errors` with their spreadsheet row number.
+///
+/// # Example Frontend Usage
+/// ```typescript
+/// const result = await invoke('import_csv_file', {
+///   filePath: '/Users/me/tasks.csv',
+///   mapping: {
+///     node_type: 'task',
+///     content_column: 'Title',
+///     fields: { 'State': 'status', 'Due': 'due_date' }
+///   }
+/// });
+/// ```
+#[tauri::command]
+pub async fn import_csv_file(
+    node_service: State<'_, NodeService>,
+    file_path: String,
+    mapping: CsvMapping,
+) -> Result<CsvImportResult, String> {
+    let content =
+        std::fs::read_to_string(&file_path).map_err(|e| format!("Failed to read file: {}", e))?;
+
+    import_csv(&node_service, &content, &mapping)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Read, parse and insert one dropped file, capturing any failure in the entry
+async fn import_single_file(
+    node_service: &NodeService,
+    attachments: &AttachmentStore,
+    registry: &ImporterRegistry,
+    path: &Path,
+    file_path: &str,
+) -> ImportReportEntry {
+    let Some(importer) = registry.importer_for(path) else {
+        let err = ImportError::unsupported_format(path);
+        return ImportReportEntry::failure(file_path, None, err.to_string());
+    };
+    let name = importer.name();
+
+    let content = match std::fs::read_to_string(path) {
+        Ok(c) => c,
+        Err(e) => {
+            return ImportReportEntry::failure(
+                file_path,
+                Some(name),
+                format!("Failed to read file: {}", e),
+            )
+        }
+    };
+
+    let stem = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("Untitled");
+    let mut documents = match importer.import(stem, &content) {
+        Ok(docs) => docs,
+        Err(e) => return ImportReportEntry::failure(file_path, Some(name), e.to_string()),
+    };
+
+    // Link senders/recipients to person nodes before their IDs are stored
+    let people: Vec<_> = documents
+        .iter_mut()
+        .flat_map(|d| std::mem::take(&mut d.people))
+        .collect();
+    match resolve_people(node_service, &people).await {
+        Ok(ids) => documents.iter_mut().for_each(|d| d.remap_ids(&ids)),
+        Err(e) => {
+            return ImportReportEntry::failure(
+                file_path,
+                Some(name),
+                format!("Failed to link people: {}", e),
+            )
+        }
+    }
+
+    let mut root_ids = Vec::with_capacity(documents.len());
+    let mut created_ids = Vec::new();
+    let mut nodes_created = 0;
+    let mut mentions = Vec::new();
+    let mut timestamps = Vec::new();
+    let mut memberships = Vec::new();
+    for mut document in documents {
+        // Attachments are written first so links in the new nodes never dangle
+        for attachment in document.attachments.drain(..) {
+            if let Err(e) =
+                attachments.put(&attachment.data, &attachment.file_name, &attachment.mime)
+            {
+                let error = format!("Failed to store attachment {}: {}", attachment.file_name, e);
+                return partial_import_failure(
+                    node_service,
+                    file_path,
+                    name,
+                    root_ids,
+                    created_ids,
+                    error,
+                )
+                .await;
+            }
+        }
+        mentions.append(&mut document.mentions);
+        timestamps.append(&mut document.timestamps);
+        memberships.append(&mut document.memberships);
+        let sync = document.sync;
+        let (root_id, rows) = document.into_hierarchy();
+        // Documents keyed by their file's content are updated in place on re-import
+        let written = if sync {
+            let count = rows.len();
+            node_service.sync_hierarchy(rows).await.map(|_| count)
+        } else {
+            node_service
+                .bulk_create_hierarchy_root_notify(rows)
+                .await
+                .map(|ids| {
+                    let count = ids.len();
+                    created_ids.extend(ids);
+                    count
+                })
+        };
+        match written {
+            Ok(count) => {
+                nodes_created += count;
+                root_ids.push(root_id);
+            }
+            Err(e) => {
+                let error = format!("Failed to create nodes: {}", e);
+                return partial_import_failure(
+                    node_service,
+                    file_path,
+                    name,
+                    root_ids,
+                    created_ids,
+                    error,
+                )
+                .await;
+            }
+        }
+    }
+
+    // Mentions may cross documents, so they are created once every document exists
+    let store = node_service.store();
+    if let Err(e) = store.bulk_create_mentions(&mentions).await {
+        tracing::warn!("Failed to create mentions for {}: {}", file_path, e);
+    }
+    if let Err(e) = store.bulk_set_timestamps(&timestamps).await {
+        tracing::warn!("Failed to restore timestamps for {}: {}", file_path, e);
+    }
+    let collection_service = CollectionService::new(store, node_service);
+    for (node_id, collection_path) in &memberships {
+        if let Err(e) = collection_service
+            .add_to_collection_by_path(node_id, collection_path)
+            .await
+        {
+            tracing::warn!(
+                "Failed to add {} to collection '{}': {}",
+                node_id,
+                collection_path,
+                e
+            );
+        }
+    }
+
+    ImportReportEntry::success(file_path, name, root_ids, nodes_created)
+}
+
+/// Failure entry for a file whose earlier documents were already created
+///
+/// The created nodes are deleted so a failed file leaves no partial import
+/// behind. Should that fail as well, the entry keeps their roots so they can
+/// still be found from the report.
+async fn partial_import_failure(
+    node_service: &NodeService,
+    file_path: &str,
+    importer: &str,
+    root_ids: Vec<String>,
+    created_ids: Vec<String>,
+    error: String,
+) -> ImportReportEntry {
+    let mut entry = ImportReportEntry::failure(file_path, Some(importer), error);
+    if created_ids.is_empty() {
+        return entry;
+    }
+    let nodes_created = created_ids.len();
+    if let Err(e) = node_service.bulk_delete(created_ids).await {
+        tracing::warn!(
+            "Failed to remove nodes of partially imported {}: {}",
+            file_path,
+            e
+        );
+        entry.root_ids = root_ids;
+        entry.nodes_created = nodes_created;
+    }
+    entry
+}
+
 /// Recursively collect markdown files from a directory with exclusion patterns
 fn collect_markdown_files_with_exclusions(
     dir: &PathBuf,
@@ -1181,7 +1549,7 @@ async fn import_markdown_content(
             String,
             String,
             Option<String>,
-            f64,
+            String,
             serde_json::Value,
         )> = prepared_nodes
             .iter()
@@ -1192,7 +1560,7 @@ async fn import_markdown_content(
                     n.node_type.clone(),
                     n.content.clone(),
                     parent,
-                    n.order,
+                    n.order.clone(),
                     n.properties.clone(),
                 )
             })
@@ -0,0 +1,125 @@
+//! Quick capture and inbox triage commands
+//!
+//! A global shortcut (`CommandOrControl+Shift+Space`) opens a small
+//! always-on-top capture window, so a thought can be written down without
+//! bringing the main window forward. Captured nodes land in the `Inbox`
+//! collection (see core's `InboxService`) and are triaged from there.
+
+use nodespace_core::services::{InboxDestination, InboxService};
+use nodespace_core::{models, Node, NodeService};
+use serde_json::Value;
+use tauri::{AppHandle, Manager, State, WebviewUrl, WebviewWindow, WebviewWindowBuilder};
+
+use super::nodes::CommandError;
+
+/// Global shortcut that opens the quick-capture window
+pub const QUICK_CAPTURE_SHORTCUT: &str = "CommandOrControl+Shift+Space";
+
+/// Window label of the quick-capture window
+pub const QUICK_CAPTURE_WINDOW: &str = "quick-capture";
+
+fn node_to_typed_value(node: Node) -> Result<Value, CommandError> {
+    models::node_to_typed_value(node).map_err(|e| CommandError {
+        message: e.clone(),
+        code: "CONVERSION_ERROR".to_string(),
+        details: Some(e),
+        params: None,
+        correlation_id: None,
+    })
+}
+
+/// Show the quick-capture window, creating it on first use
+pub fn show_quick_capture_window(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window(QUICK_CAPTURE_WINDOW) {
+        let _ = window.show();
+        let _ = window.set_focus();
+        return;
+    }
+
+    let built = WebviewWindowBuilder::new(
+        app,
+        QUICK_CAPTURE_WINDOW,
+        WebviewUrl::App("quick-capture.html".into()),
+    )
+    .title("Quick Capture")
+    .inner_size(560.0, 72.0)
+    .resizable(false)
+    .decorations(false)
+    .always_on_top(true)
+    .skip_taskbar(true)
+    .center()
+    .build();
+    if let Err(e) = built {
+        tracing::error!("Failed to open quick-capture window: {}", e);
+    }
+}
+
+/// Create a node in the inbox
+///
+/// When called from the quick-capture window the window is hidden afterwards,
+/// leaving whatever app was in front undisturbed.
+///
+/// # Example Frontend Usage
+/// ```typescript
+/// const node = await invoke('quick_capture', { text: 'Call the bank', nodeType: 'task' });
+/// ```
+#[tauri::command]
+pub async fn quick_capture(
+    window: WebviewWindow,
+    service: State<'_, NodeService>,
+    text: String,
+    node_type: Option<String>,
+) -> Result<Value, CommandError> {
+    let node = InboxService::new(&service)
+        .capture(&text, node_type.as_deref())
+        .await?;
+
+    if window.label() == QUICK_CAPTURE_WINDOW {
+        let _ = window.hide();
+    }
+    node_to_typed_value(node)
+}
+
+/// Items waiting in the inbox, oldest first
+///
+/// # Example Frontend Usage
+/// ```typescript
+/// const items = await invoke('get_inbox');
+/// ```
+#[tauri::command]
+pub async fn get_inbox(service: State<'_, NodeService>) -> Result<Vec<Value>, CommandError> {
+    let items = InboxService::new(&service).items().await?;
+    models::nodes_to_typed_values(items).map_err(|e| CommandError {
+        message: e.clone(),
+        code: "CONVERSION_ERROR".to_string(),
+        details: Some(e),
+        params: None,
+        correlation_id: None,
+    })
+}
+
+/// Move an inbox item to its destination and out of the inbox
+///
+/// Returns the updated node, or `null` when the item was deleted.
+///
+/// # Example Frontend Usage
+/// ```typescript
+/// await invoke('process_inbox_item', {
+///   id: itemId,
+///   destination: { type: 'parent', parent_id: '2025-01-15' }
+/// });
+/// // Other destinations: { type: 'collection', path: 'work:ideas' },
+/// // { type: 'keep' }, { type: 'delete' }
+/// ```
+#[tauri::command]
+pub async fn process_inbox_item(
+    service: State<'_, NodeService>,
+    id: String,
+    destination: InboxDestination,
+) -> Result<Option<Value>, CommandError> {
+    InboxService::new(&service)
+        .process(&id, destination)
+        .await?
+        .map(node_to_typed_value)
+        .transpose()
+}
@@ -0,0 +1,90 @@
+//! Crash-safe autosave journal commands
+//!
+//! The editor calls `journal_edit` on every change, before its debounced
+//! `update_node`, and `acknowledge_edit` with the returned sequence number
+//! once that save succeeds. Content still unacknowledged when the app dies
+//! is written back on the next start (see core's `EditJournal`), and the
+//! restored node IDs are sent in an `edits-restored` event.
+
+use nodespace_core::services::{EditJournal, JournalReplay, NodeServiceError};
+use nodespace_core::NodeService;
+use tauri::{AppHandle, Emitter, State};
+
+use super::nodes::CommandError;
+
+/// Emitted after unsaved edits from a previous run were written back
+pub const EDITS_RESTORED_EVENT: &str = "edits-restored";
+
+fn journal_error(e: std::io::Error) -> CommandError {
+    CommandError {
+        message: format!("Failed to write edit journal: {}", e),
+        code: "JOURNAL_ERROR".to_string(),
+        details: Some(e.to_string()),
+        params: None,
+        correlation_id: None,
+    }
+}
+
+/// Write back edits left in the journal by a crashed run
+///
+/// Skipped while the workspace is locked (`unlock_workspace` calls this
+/// again) or read-only (the edits wait for a writable session).
+pub async fn replay_pending_edits(app: &AppHandle, service: &NodeService, journal: &EditJournal) {
+    if service.is_locked() || service.is_read_only() || journal.pending().is_empty() {
+        return;
+    }
+
+    match journal.replay(service).await {
+        Ok(replay) => {
+            tracing::info!(
+                "Edit journal replayed: {} restored, {} missing, {} conflicts",
+                replay.restored.len(),
+                replay.missing.len(),
+                replay.conflicts.len()
+            );
+            if !replay.is_empty() {
+                if let Err(e) = app.emit(EDITS_RESTORED_EVENT, &replay) {
+                    tracing::error!("Failed to emit {}: {}", EDITS_RESTORED_EVENT, e);
+                }
+            }
+        }
+        Err(e) => tracing::error!("Failed to replay edit journal: {}", e),
+    }
+}
+
+/// Record the editor's current content of a node before it is saved
+///
+/// Returns the sequence number to pass to `acknowledge_edit`. Refused in
+/// read-only mode, where the save would fail and the edit must not be
+/// replayed later.
+#[tauri::command]
+pub fn journal_edit(
+    service: State<'_, NodeService>,
+    journal: State<'_, EditJournal>,
+    node_id: String,
+    content: String,
+) -> Result<u64, CommandError> {
+    if service.is_read_only() {
+        return Err(NodeServiceError::ReadOnly.into());
+    }
+    journal.record(&node_id, &content).map_err(journal_error)
+}
+
+/// Mark a node's journaled content up to `seq` as saved
+#[tauri::command]
+pub fn acknowledge_edit(
+    journal: State<'_, EditJournal>,
+    node_id: String,
+    seq: u64,
+) -> Result<(), CommandError> {
+    journal.acknowledge(&node_id, seq).map_err(journal_error)
+}
+
+/// Replay the journal on demand, e.g. when the frontend missed the startup event
+#[tauri::command]
+pub async fn replay_edit_journal(
+    service: State<'_, NodeService>,
+    journal: State<'_, EditJournal>,
+) -> Result<JournalReplay, CommandError> {
+    Ok(journal.replay(&service).await?)
+}
@@ -2,12 +2,25 @@
 //!
 //! This module exposes Rust functionality to the frontend via Tauri commands.
 
+pub mod clipboard;
 pub mod collections;
 pub mod db;
+pub mod deep_link;
 pub mod diagnostics;
+pub mod digest;
 pub mod embeddings;
+pub mod export;
+pub mod goals;
+pub mod graph;
 pub mod import;
+pub mod inbox;
+pub mod journal;
 pub mod models;
 pub mod nodes;
+pub mod people;
+pub mod queries;
+pub mod relationships;
 pub mod schemas;
 pub mod settings;
+pub mod tasks;
+pub mod workspace_lock;
@@ -1,83 +1,115 @@
-//! Model management - extract bundled models to ~/.nodespace/models/
-use std::path::PathBuf;
-use tauri::{AppHandle, Manager};
-use tokio::fs;
+//! Model management - download, list and remove GGUF models in ~/.nodespace/models/
+//!
+//! Downloads resume where an interrupted one stopped and are verified against
+//! the SHA-256 in their `ModelSpec` before they are installed. Progress is
+//! emitted as `models:download-progress` events. Names and versions that
+//! would reach outside the models directory are rejected.
+use nodespace_nlp_engine::{DownloadProgress, InstalledModel, ModelManager, ModelSpec};
+use std::sync::Arc;
+use tauri::{AppHandle, Emitter, State};
 
-/// Extract bundled models to ~/.nodespace/models/ on first run
-///
-/// Models are bundled with the application in the resources directory.
-/// On first run, they are extracted to ~/.nodespace/models/ for use.
+/// Event carrying a `DownloadProgress` while a model downloads
+pub const MODEL_DOWNLOAD_PROGRESS_EVENT: &str = "models:download-progress";
+
+/// Install a model version unless it is already installed
 ///
 /// # Arguments
-/// * `app` - Tauri application handle
+/// * `app` - Tauri application handle, for progress events
+/// * `spec` - Model name, version, download URL and expected SHA-256
 ///
 /// # Returns
-/// * `Ok(String)` - Path where models were extracted
-/// * `Err(String)` - Error if extraction fails
+/// * `Ok(String)` - Path of the installed model file
+/// * `Err(String)` - Error if the download fails or the checksum doesn't match
 #[tauri::command]
-pub async fn ensure_models_installed(app: AppHandle) -> Result<String, String> {
-    let home_dir = dirs::home_dir().ok_or_else(|| "Failed to get home directory".to_string())?;
-
-    let models_dir = home_dir.join(".nodespace").join("models");
-    let model_dir = models_dir.join("BAAI-bge-small-en-v1.5");
-
-    // Check if model already exists
-    if model_dir.exists() && model_dir.join("model.onnx").exists() {
-        tracing::info!("Models already installed at {:?}", model_dir);
-        return Ok(model_dir.to_string_lossy().to_string());
-    }
-
-    tracing::info!("Extracting bundled models to {:?}", model_dir);
+pub async fn ensure_model_installed(
+    app: AppHandle,
+    manager: State<'_, Arc<ModelManager>>,
+    spec: ModelSpec,
+) -> Result<String, String> {
+    install(&manager, &spec, |progress| {
+        let _ = app.emit(MODEL_DOWNLOAD_PROGRESS_EVENT, progress);
+    })
+    .await
+}
 
-    // Create models directory
-    fs::create_dir_all(&models_dir)
+async fn install(
+    manager: &ModelManager,
+    spec: &ModelSpec,
+    on_progress: impl Fn(&DownloadProgress),
+) -> Result<String, String> {
+    let path = manager
+        .ensure(spec, on_progress)
         .await
-        .map_err(|e| format!("Failed to create models directory: {}", e))?;
-
-    // Get bundled resources path
-    let resource_path = app
-        .path()
-        .resource_dir()
-        .map_err(|e| format!("Failed to get resource directory: {}", e))?;
-
-    let bundled_model = resource_path
-        .join("resources")
-        .join("models")
-        .join("BAAI-bge-small-en-v1.5");
-
-    if !bundled_model.exists() {
-        return Err(format!(
-            "Bundled model not found at {:?}. Please ensure models are downloaded during build.",
-            bundled_model
-        ));
-    }
+        .map_err(|e| format!("Failed to install {} {}: {}", spec.name, spec.version, e))?;
+    Ok(path.to_string_lossy().to_string())
+}
 
-    // Copy bundled model to user's home directory
-    copy_dir_all(&bundled_model, &model_dir)
+/// Installed model versions
+#[tauri::command]
+pub async fn list_installed_models(
+    manager: State<'_, Arc<ModelManager>>,
+) -> Result<Vec<InstalledModel>, String> {
+    manager
+        .installed()
         .await
-        .map_err(|e| format!("Failed to copy model files: {}", e))?;
+        .map_err(|e| format!("Failed to list models: {}", e))
+}
 
-    tracing::info!("Successfully extracted models to {:?}", model_dir);
+/// Remove one installed model version
+///
+/// The running embedding service keeps the file it loaded open; the removal
+/// takes effect on the next start.
+#[tauri::command]
+pub async fn remove_model(
+    manager: State<'_, Arc<ModelManager>>,
+    name: String,
+    version: String,
+) -> Result<(), String> {
+    uninstall(&manager, &name, &version).await
+}
 
-    Ok(model_dir.to_string_lossy().to_string())
+async fn uninstall(manager: &ModelManager, name: &str, version: &str) -> Result<(), String> {
+    manager
+        .remove(name, version)
+        .await
+        .map_err(|e| format!("Failed to remove {} {}: {}", name, version, e))
 }
 
-/// Recursively copy a directory (async to avoid blocking runtime)
-async fn copy_dir_all(src: &PathBuf, dst: &PathBuf) -> Result<(), std::io::Error> {
-    fs::create_dir_all(dst).await?;
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TRAVERSALS: [(&str, &str); 6] = [
+        ("", "Q8_0"),
+        ("..", "Q8_0"),
+        ("nomic", ".."),
+        ("nomic", "../.."),
+        ("../../etc", "Q8_0"),
+        ("/tmp", "Q8_0"),
+    ];
 
-    let mut entries = fs::read_dir(src).await?;
-    while let Some(entry) = entries.next_entry().await? {
-        let file_type = entry.file_type().await?;
-        let src_path = entry.path();
-        let dst_path = dst.join(entry.file_name());
+    #[tokio::test]
+    async fn test_commands_reject_traversal() {
+        let root = std::env::temp_dir().join(format!("nodespace-models-{}", uuid::Uuid::new_v4()));
+        let models_dir = root.join("models");
+        let installed = models_dir.join("nomic").join("Q8_0");
+        std::fs::create_dir_all(&installed).unwrap();
+        let manager = ModelManager::new(&models_dir);
 
-        if file_type.is_dir() {
-            Box::pin(copy_dir_all(&src_path, &dst_path)).await?;
-        } else {
-            fs::copy(&src_path, &dst_path).await?;
+        for (name, version) in TRAVERSALS {
+            let spec = ModelSpec {
+                name: name.to_string(),
+                version: version.to_string(),
+                url: "http://127.0.0.1:9/model.gguf".to_string(),
+                sha256: String::new(),
+            };
+            let error = install(&manager, &spec, |_| {}).await.unwrap_err();
+            assert!(error.contains("Invalid model"), "{}", error);
+            let error = uninstall(&manager, name, version).await.unwrap_err();
+            assert!(error.contains("Invalid model"), "{}", error);
         }
-    }
 
-    Ok(())
+        assert!(installed.exists());
+        std::fs::remove_dir_all(&root).unwrap();
+    }
 }
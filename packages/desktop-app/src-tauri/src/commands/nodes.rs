@@ -6,33 +6,25 @@
 //! As of Issue #690, SchemaService was removed. Schema validation is done
 //! via NodeService.get_schema_for_type() and SchemaNodeBehavior.
 
-use nodespace_core::models::{self, NodeReference};
-use nodespace_core::services::CreateNodeParams;
-use nodespace_core::{Node, NodeQuery, NodeService, NodeServiceError, NodeUpdate};
+use nodespace_core::api::{CreateNodeRequest, NodeDto, QueryRequest};
+use nodespace_core::i18n;
+use nodespace_core::models::{
+    self, BrokenLink, MergeResult, NodeAppearance, NodeReference, ReplacePattern, ReplaceReport,
+    ReplaceScope,
+};
+use nodespace_core::services::{
+    CreateNodeParams, IdempotencyService, PendingRename, RenamePropagationService,
+};
+use nodespace_core::utils::{new_correlation_id, with_correlation_id};
+use nodespace_core::{Node, NodeService, NodeServiceError, NodeUpdate};
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
+use serde_json::{Map, Value};
+use std::future::Future;
+use std::sync::Arc;
 use tauri::State;
 
 use crate::constants::TAURI_CLIENT_ID;
 
-/// Input for creating a node - timestamps generated server-side
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct CreateNodeInput {
-    pub id: String,
-    pub node_type: String,
-    pub content: String,
-    pub parent_id: Option<String>,
-    // root_id removed - backend auto-derives root from parent chain (Issue #533)
-    /// Sibling node ID to insert after (None = insert at beginning of siblings)
-    /// Used for correct ordering when creating child nodes via Enter key
-    #[serde(default)]
-    pub insert_after_node_id: Option<String>,
-    pub properties: serde_json::Value,
-    #[serde(default)]
-    pub embedding_vector: Option<Vec<u8>>,
-}
-
 /// Structured error type for Tauri commands
 ///
 /// Provides better observability and debugging by including error codes
@@ -47,27 +39,44 @@ pub struct CommandError {
     /// Optional detailed error information for debugging
     #[serde(skip_serializing_if = "Option::is_none")]
     pub details: Option<String>,
+    /// Values interpolated into the message for `code`, for localizing it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub params: Option<Map<String, Value>>,
+    /// Correlation ID of the failed command (see [`traced`]), to find it in
+    /// the logs
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub correlation_id: Option<String>,
 }
 
 impl From<NodeServiceError> for CommandError {
     fn from(err: NodeServiceError) -> Self {
-        // Map specific error types to appropriate codes
-        let code = match &err {
-            NodeServiceError::NodeNotFound { .. } => "NODE_NOT_FOUND",
-            NodeServiceError::VersionConflict { .. } => "VERSION_CONFLICT",
-            NodeServiceError::InvalidParent { .. } => "INVALID_PARENT",
-            NodeServiceError::CircularReference { .. } => "CIRCULAR_REFERENCE",
-            NodeServiceError::HierarchyViolation(_) => "HIERARCHY_VIOLATION",
-            _ => "NODE_SERVICE_ERROR",
-        };
+        let message = err.user_message();
         CommandError {
-            message: format!("Node operation failed: {}", err),
-            code: code.to_string(),
+            message: message.render(i18n::current_locale()),
+            code: message.code,
             details: Some(format!("{}", err)),
+            params: Some(message.params),
+            correlation_id: None,
         }
     }
 }
 
+/// Run a command's body under a new correlation ID
+///
+/// Its logs, the domain events and audit log entries of its writes, and its
+/// error carry the ID.
+pub(crate) async fn traced<T>(
+    command: impl Future<Output = Result<T, CommandError>>,
+) -> Result<T, CommandError> {
+    let correlation_id = new_correlation_id();
+    with_correlation_id(correlation_id.clone(), command)
+        .await
+        .map_err(|error| CommandError {
+            correlation_id: Some(correlation_id),
+            ..error
+        })
+}
+
 /// Validate that node type has a schema
 ///
 /// Checks if a schema exists for the given node type. This enables
@@ -98,6 +107,8 @@ async fn validate_node_type(node_type: &str, service: &NodeService) -> Result<()
                  Use the schema API to create a schema before creating nodes of this type.",
                 node_type
             )),
+            params: None,
+            correlation_id: None,
         }),
         Err(e) => Err(CommandError::from(e)),
     }
@@ -111,6 +122,8 @@ fn node_to_typed_value(node: Node) -> Result<Value, CommandError> {
         message: e.clone(),
         code: "CONVERSION_ERROR".to_string(),
         details: Some(e),
+        params: None,
+        correlation_id: None,
     })
 }
 
@@ -120,6 +133,8 @@ fn nodes_to_typed_values(nodes: Vec<Node>) -> Result<Vec<Value>, CommandError> {
         message: e.clone(),
         code: "CONVERSION_ERROR".to_string(),
         details: Some(e),
+        params: None,
+        correlation_id: None,
     })
 }
 
@@ -131,6 +146,8 @@ fn nodes_to_typed_values(nodes: Vec<Node>) -> Result<Vec<Value>, CommandError> {
 /// # Arguments
 /// * `service` - NodeService instance from Tauri state
 /// * `node` - Node data to create (node_type must have a schema)
+/// * `idempotency_key` - Optional key; a retry with the same key returns the
+///   first call's ID instead of creating another node
 ///
 /// # Returns
 /// * `Ok(String)` - ID of the created node
@@ -146,7 +163,8 @@ fn nodes_to_typed_values(nodes: Vec<Node>) -> Result<Vec<Value>, CommandError> {
 /// ```typescript
 /// const nodeId = await invoke('create_node', {
 ///   node: {
-///     node_type: 'text',
+///     id: crypto.randomUUID(), // optional, generated when omitted
+///     nodeType: 'text',
 ///     content: 'Hello World',
 ///     properties: {}
 ///   }
@@ -155,26 +173,25 @@ fn nodes_to_typed_values(nodes: Vec<Node>) -> Result<Vec<Value>, CommandError> {
 #[tauri::command]
 pub async fn create_node(
     service: State<'_, NodeService>,
-    node: CreateNodeInput,
+    node: CreateNodeRequest,
+    idempotency_key: Option<String>,
 ) -> Result<String, CommandError> {
-    validate_node_type(&node.node_type, &service).await?;
+    traced(async move {
+        validate_node_type(&node.node_type, &service).await?;
 
-    // Use NodeService to create node with business rule enforcement
-    // Pass frontend-generated ID so frontend can track node before persistence completes
-    service
-        .with_client(TAURI_CLIENT_ID)
-        .create_node_with_parent(CreateNodeParams {
-            id: Some(node.id), // Frontend provides UUID for local state tracking
-            node_type: node.node_type,
-            content: node.content,
-            parent_id: node.parent_id,
-            // root_id removed - backend auto-derives root from parent chain (Issue #533)
-            // insert_after_node_id: Sibling to insert after for correct ordering (Issue #657)
-            insert_after_node_id: node.insert_after_node_id,
-            properties: node.properties,
-        })
-        .await
-        .map_err(Into::into)
+        // The frontend usually passes its own ID so it can track the node before
+        // persistence completes
+        IdempotencyService::new(&service)
+            .run(idempotency_key.as_deref(), "create_node", &node, || async {
+                service
+                    .with_client(TAURI_CLIENT_ID)
+                    .create_node_with_parent(node.clone().into())
+                    .await
+                    .map_err(CommandError::from)
+            })
+            .await
+    })
+    .await
 }
 
 /// Input for creating a root node (top-level container)
@@ -231,30 +248,33 @@ pub async fn create_root_node(
     service: State<'_, NodeService>,
     input: CreateRootNodeInput,
 ) -> Result<String, CommandError> {
-    validate_node_type(&input.node_type, &service).await?;
+    traced(async move {
+        validate_node_type(&input.node_type, &service).await?;
 
-    // Create root node with NodeService (parent_id = None means root)
-    let node_id = service
-        .with_client(TAURI_CLIENT_ID)
-        .create_node_with_parent(CreateNodeParams {
-            id: None, // Let NodeService generate ID for root nodes
-            node_type: input.node_type,
-            content: input.content,
-            parent_id: None,            // parent_id = None for root nodes
-            insert_after_node_id: None, // No sibling positioning for root nodes
-            properties: input.properties,
-        })
-        .await?;
-
-    // If mentioned_by is provided, create mention relationship
-    if let Some(mentioning_node_id) = input.mentioned_by {
-        service
+        // Create root node with NodeService (parent_id = None means root)
+        let node_id = service
             .with_client(TAURI_CLIENT_ID)
-            .create_mention(&mentioning_node_id, &node_id)
+            .create_node_with_parent(CreateNodeParams {
+                id: None, // Let NodeService generate ID for root nodes
+                node_type: input.node_type,
+                content: input.content,
+                parent_id: None,            // parent_id = None for root nodes
+                insert_after_node_id: None, // No sibling positioning for root nodes
+                properties: input.properties,
+            })
             .await?;
-    }
 
-    Ok(node_id)
+        // If mentioned_by is provided, create mention relationship
+        if let Some(mentioning_node_id) = input.mentioned_by {
+            service
+                .with_client(TAURI_CLIENT_ID)
+                .create_mention(&mentioning_node_id, &node_id)
+                .await?;
+        }
+
+        Ok(node_id)
+    })
+    .await
 }
 
 /// Create a mention relationship between two nodes
@@ -284,15 +304,47 @@ pub async fn create_node_mention(
     mentioning_node_id: String,
     mentioned_node_id: String,
 ) -> Result<(), CommandError> {
-    service
-        .with_client(TAURI_CLIENT_ID)
-        .create_mention(&mentioning_node_id, &mentioned_node_id)
-        .await
-        .map_err(Into::into)
+    traced(async move {
+        service
+            .with_client(TAURI_CLIENT_ID)
+            .create_mention(&mentioning_node_id, &mentioned_node_id)
+            .await
+            .map_err(Into::into)
+    })
+    .await
+}
+
+/// Validate node data without saving it
+///
+/// Returns hard errors (the write would be rejected) and warnings such as
+/// unknown properties or deprecated enum values (the write would succeed),
+/// so the editor can flag issues as the user types.
+///
+/// # Example Frontend Usage
+/// ```typescript
+/// const report = await invoke('validate_node', {
+///   nodeType: 'task',
+///   content: 'Ship it',
+///   properties: { status: 'open', stauts: 'done' }
+/// });
+/// // report.warnings[0].kind === 'unknown_property'
+/// ```
+#[tauri::command]
+pub async fn validate_node(
+    service: State<'_, NodeService>,
+    node_type: String,
+    content: String,
+    properties: Value,
+) -> Result<models::ValidationReport, CommandError> {
+    let node = Node::new(node_type, content, properties);
+    service.validation_report(&node).await.map_err(Into::into)
 }
 
 /// Get a node by ID
 ///
+/// Project nodes come with a `rollup` of the tasks beneath them
+/// (`ProjectRollup`), served from NodeService's rollup cache.
+///
 /// # Arguments
 /// * `service` - Node service instance from Tauri state
 /// * `id` - Unique identifier of the node to retrieve
@@ -325,10 +377,16 @@ pub async fn get_node(
         .await
         .map_err(CommandError::from)?;
 
-    match node {
-        Some(n) => Ok(Some(node_to_typed_value(n)?)),
-        None => Ok(None),
+    let Some(node) = node else {
+        return Ok(None);
+    };
+    let is_project = node.node_type == "project";
+    let mut value = node_to_typed_value(node)?;
+    if is_project {
+        let rollup = service.get_project_rollup(&id).await?;
+        value["rollup"] = serde_json::json!(rollup);
     }
+    Ok(Some(value))
 }
 
 /// Update an existing node
@@ -368,39 +426,130 @@ pub async fn update_node(
     id: String,
     version: i64,
     update: NodeUpdate,
+    idempotency_key: Option<String>,
 ) -> Result<Value, CommandError> {
-    // DEBUG: Log incoming update to trace @mention persistence issue
-    let content_preview = update.content.as_ref().map(|c| {
-        if c.len() > 50 {
-            format!("{}...", &c[..50])
-        } else {
-            c.clone()
-        }
-    });
-    tracing::debug!(
-        "update_node: id={}, version={}, content={:?}, node_type={:?}",
-        id,
-        version,
-        content_preview,
-        update.node_type
-    );
+    traced(async move {
+        // DEBUG: Log incoming update to trace @mention persistence issue
+        let content_preview = update.content.as_ref().map(|c| {
+            if c.len() > 50 {
+                format!("{}...", &c[..50])
+            } else {
+                c.clone()
+            }
+        });
+        tracing::debug!(
+            "update_node: id={}, version={}, content={:?}, node_type={:?}",
+            id,
+            version,
+            content_preview,
+            update.node_type
+        );
 
-    // Use update_node for OCC-protected updates (safe by default)
-    // Returns the updated Node so frontend can refresh its local version
-    let node = service
-        .with_client(TAURI_CLIENT_ID)
-        .update_node(&id, version, update)
-        .await
-        .map_err(CommandError::from)?;
+        // Use update_node for OCC-protected updates (safe by default)
+        // Returns the updated Node so frontend can refresh its local version
+        let request = serde_json::json!({ "id": id, "version": version, "update": update });
+        IdempotencyService::new(&service)
+            .run(
+                idempotency_key.as_deref(),
+                "update_node",
+                &request,
+                || async {
+                    let node = service
+                        .with_client(TAURI_CLIENT_ID)
+                        .update_node(&id, version, update)
+                        .await
+                        .map_err(CommandError::from)?;
 
-    // DEBUG: Log successful update
-    tracing::debug!(
-        "update_node: SUCCESS id={}, new_version={}",
-        id,
-        node.version
-    );
+                    // DEBUG: Log successful update
+                    tracing::debug!(
+                        "update_node: SUCCESS id={}, new_version={}",
+                        id,
+                        node.version
+                    );
 
-    node_to_typed_value(node)
+                    node_to_typed_value(node)
+                },
+            )
+            .await
+    })
+    .await
+}
+
+/// Set one property by JSON pointer
+///
+/// The edit is merged server-side against the latest stored version, so no
+/// `version` is needed and concurrent edits of other fields are preserved.
+///
+/// # Example Frontend Usage
+/// ```typescript
+/// const node = await invoke('set_node_property', {
+///   id: 'task-123',
+///   path: '/task/due_date',
+///   value: '2025-01-15'
+/// });
+/// ```
+#[tauri::command]
+pub async fn set_node_property(
+    service: State<'_, NodeService>,
+    id: String,
+    path: String,
+    value: Value,
+) -> Result<Value, CommandError> {
+    traced(async move {
+        let node = service
+            .with_client(TAURI_CLIENT_ID)
+            .set_property(&id, &path, value)
+            .await?;
+        node_to_typed_value(node)
+    })
+    .await
+}
+
+/// Remove one property by JSON pointer
+///
+/// # Example Frontend Usage
+/// ```typescript
+/// const node = await invoke('remove_node_property', { id: 'task-123', path: '/task/due_date' });
+/// ```
+#[tauri::command]
+pub async fn remove_node_property(
+    service: State<'_, NodeService>,
+    id: String,
+    path: String,
+) -> Result<Value, CommandError> {
+    traced(async move {
+        let node = service
+            .with_client(TAURI_CLIENT_ID)
+            .remove_property(&id, &path)
+            .await?;
+        node_to_typed_value(node)
+    })
+    .await
+}
+
+/// Replace a node's icon, accent color and cover
+///
+/// # Example Frontend Usage
+/// ```typescript
+/// const node = await invoke('set_node_appearance', {
+///   id: 'node-123',
+///   appearance: { icon: '🚀', color: '#ff8800' }
+/// });
+/// ```
+#[tauri::command]
+pub async fn set_node_appearance(
+    service: State<'_, NodeService>,
+    id: String,
+    appearance: NodeAppearance,
+) -> Result<Value, CommandError> {
+    traced(async move {
+        let node = service
+            .with_client(TAURI_CLIENT_ID)
+            .set_node_appearance(&id, appearance)
+            .await?;
+        node_to_typed_value(node)
+    })
+    .await
 }
 
 /// Delete a node by ID with cascade deletion
@@ -436,12 +585,26 @@ pub async fn delete_node(
     service: State<'_, NodeService>,
     id: String,
     version: i64,
+    idempotency_key: Option<String>,
 ) -> Result<nodespace_core::models::DeleteResult, CommandError> {
-    service
-        .with_client(TAURI_CLIENT_ID)
-        .delete_node(&id, version)
-        .await
-        .map_err(Into::into)
+    traced(async move {
+        let request = serde_json::json!({ "id": id, "version": version });
+        IdempotencyService::new(&service)
+            .run(
+                idempotency_key.as_deref(),
+                "delete_node",
+                &request,
+                || async {
+                    service
+                        .with_client(TAURI_CLIENT_ID)
+                        .delete_node(&id, version)
+                        .await
+                        .map_err(CommandError::from)
+                },
+            )
+            .await
+    })
+    .await
 }
 
 /// Atomically move a node to a new parent with new sibling position (with OCC)
@@ -483,19 +646,38 @@ pub async fn move_node(
     version: i64,
     new_parent_id: Option<String>,
     insert_after_node_id: Option<String>,
+    idempotency_key: Option<String>,
 ) -> Result<Value, CommandError> {
-    let node = service
-        .with_client(TAURI_CLIENT_ID)
-        .move_node(
-            &node_id,
-            version,
-            new_parent_id.as_deref(),
-            insert_after_node_id.as_deref(),
-        )
-        .await
-        .map_err(CommandError::from)?;
+    traced(async move {
+        let request = serde_json::json!({
+            "node_id": node_id,
+            "version": version,
+            "new_parent_id": new_parent_id,
+            "insert_after_node_id": insert_after_node_id,
+        });
+        IdempotencyService::new(&service)
+            .run(
+                idempotency_key.as_deref(),
+                "move_node",
+                &request,
+                || async {
+                    let node = service
+                        .with_client(TAURI_CLIENT_ID)
+                        .move_node(
+                            &node_id,
+                            version,
+                            new_parent_id.as_deref(),
+                            insert_after_node_id.as_deref(),
+                        )
+                        .await
+                        .map_err(CommandError::from)?;
 
-    node_to_typed_value(node)
+                    node_to_typed_value(node)
+                },
+            )
+            .await
+    })
+    .await
 }
 
 /// Reorder a node by changing its sibling position
@@ -530,11 +712,14 @@ pub async fn reorder_node(
     version: i64,
     insert_after_node_id: Option<String>,
 ) -> Result<(), CommandError> {
-    service
-        .with_client(TAURI_CLIENT_ID)
-        .reorder_node(&node_id, version, insert_after_node_id.as_deref())
-        .await
-        .map_err(Into::into)
+    traced(async move {
+        service
+            .with_client(TAURI_CLIENT_ID)
+            .reorder_node(&node_id, version, insert_after_node_id.as_deref())
+            .await
+            .map_err(Into::into)
+    })
+    .await
 }
 
 /// Get child nodes of a parent node
@@ -672,23 +857,20 @@ pub async fn get_nodes_by_root_id(
 /// - mentioned_by (finds nodes that mention the specified node ID)
 /// - content_contains (case-insensitive substring search)
 /// - node_type (filter by type)
-/// - limit (maximum results to return)
+/// - collection / collection_id (members of a collection, by path or ID)
+/// - limit and offset
+///
+/// Shares [`QueryRequest`] with the dev-proxy `/api/query` endpoint and the
+/// MCP `query_nodes` tool.
 ///
 /// # Arguments
 /// * `service` - Node service instance from Tauri state
 /// * `query` - Query parameters (all fields optional)
 ///
 /// # Returns
-/// * `Ok(Vec<Node>)` - Matching nodes (empty if no matches)
+/// * `Ok(Vec<NodeDto>)` - Matching nodes (empty if no matches)
 /// * `Err(CommandError)` - Error with details if operation fails
 ///
-/// # Query Priority
-/// The query uses priority-based selection - first matching field determines query type:
-/// 1. ID query (exact match, returns 0 or 1 result)
-/// 2. mentioned_by query (finds backlinks)
-/// 3. content_contains query (can be combined with node_type)
-/// 4. node_type query (type filter only)
-///
 /// # Example Frontend Usage
 /// ```typescript
 /// // Find nodes that mention a specific node (backlinks)
@@ -701,23 +883,28 @@ pub async fn get_nodes_by_root_id(
 ///   query: { contentContains: 'project', nodeType: 'text' }
 /// });
 ///
-/// // Get specific node
-/// const node = await invoke('query_nodes_simple', {
-///   query: { id: 'node-123' }
+/// // Tasks in a collection
+/// const tasks = await invoke('query_nodes_simple', {
+///   query: { nodeType: 'task', collection: 'work:launch' }
 /// });
 /// ```
 #[tauri::command]
 pub async fn query_nodes_simple(
     service: State<'_, NodeService>,
-    query: NodeQuery,
-) -> Result<Vec<Value>, CommandError> {
-    let nodes = service
-        .with_client(TAURI_CLIENT_ID)
-        .query_nodes_simple(query)
+    query: QueryRequest,
+) -> Result<Vec<NodeDto>, CommandError> {
+    let result = query
+        .execute(&service.with_client(TAURI_CLIENT_ID))
         .await
         .map_err(CommandError::from)?;
 
-    nodes_to_typed_values(nodes)
+    NodeDto::from_nodes(result.nodes).map_err(|e| CommandError {
+        message: e.clone(),
+        code: "CONVERSION_ERROR".to_string(),
+        details: Some(e),
+        params: None,
+        correlation_id: None,
+    })
 }
 
 /// Mention autocomplete query - specialized endpoint for @mention feature
@@ -817,21 +1004,24 @@ pub async fn save_node_with_parent(
     service: State<'_, NodeService>,
     input: SaveNodeWithParentInput,
 ) -> Result<(), CommandError> {
-    validate_node_type(&input.node_type, &service).await?;
+    traced(async move {
+        validate_node_type(&input.node_type, &service).await?;
 
-    // Use single-transaction upsert method (bypasses NodeOperations for transactional reasons)
-    service
-        .with_client(TAURI_CLIENT_ID)
-        .upsert_node_with_parent(
-            &input.node_id,
-            &input.content,
-            &input.node_type,
-            &input.parent_id,
-            &input.root_id,
-            None, // before_sibling_id removed - backend uses fractional ordering (Issue #616)
-        )
-        .await
-        .map_err(Into::into)
+        // Use single-transaction upsert method (bypasses NodeOperations for transactional reasons)
+        service
+            .with_client(TAURI_CLIENT_ID)
+            .upsert_node_with_parent(
+                &input.node_id,
+                &input.content,
+                &input.node_type,
+                &input.parent_id,
+                &input.root_id,
+                None, // before_sibling_id removed - backend uses fractional ordering (Issue #616)
+            )
+            .await
+            .map_err(Into::into)
+    })
+    .await
 }
 
 /// Get outgoing mentions (nodes that this node mentions)
@@ -936,6 +1126,165 @@ pub async fn get_mentioning_roots(
         .map_err(Into::into)
 }
 
+/// Mentions and embeds of deleted nodes, newest first
+///
+/// # Example Frontend Usage
+/// ```typescript
+/// const links = await invoke('get_broken_links');
+/// // links: [{ id, sourceId, targetId, targetTitle, linkType, detectedAt }]
+/// ```
+#[tauri::command]
+pub async fn get_broken_links(
+    service: State<'_, NodeService>,
+) -> Result<Vec<BrokenLink>, CommandError> {
+    Ok(service.get_broken_links().await?)
+}
+
+/// Point a broken link at another node, or turn it into plain text when
+/// `newTargetId` is omitted; returns the updated source node
+///
+/// # Example Frontend Usage
+/// ```typescript
+/// const source = await invoke('relink_broken_link', {
+///   linkId: link.id,
+///   newTargetId: 'node-456'
+/// });
+/// ```
+#[tauri::command]
+pub async fn relink_broken_link(
+    service: State<'_, NodeService>,
+    link_id: String,
+    new_target_id: Option<String>,
+) -> Result<Value, CommandError> {
+    let source = service
+        .relink_broken_link(&link_id, new_target_id.as_deref())
+        .await?;
+    node_to_typed_value(source)
+}
+
+/// Merge `sourceIds` into `targetId`: children are appended, links are
+/// re-pointed and the sources moved to the trash
+///
+/// # Example Frontend Usage
+/// ```typescript
+/// const result = await invoke('merge_nodes', {
+///   targetId: 'node-123',
+///   sourceIds: ['node-456']
+/// });
+/// // result: { target, movedChildren, rewrittenNodes, trashed, conflicts }
+/// ```
+#[tauri::command]
+pub async fn merge_nodes(
+    service: State<'_, NodeService>,
+    target_id: String,
+    source_ids: Vec<String>,
+) -> Result<MergeResult, CommandError> {
+    traced(async move { Ok(service.merge_nodes(&target_id, &source_ids).await?) }).await
+}
+
+/// Replace text in the content of every node in `scope`
+///
+/// With `dryRun` nothing is written and the result previews each match. An
+/// applied replace can be taken back with `undo_find_and_replace`.
+///
+/// # Example Frontend Usage
+/// ```typescript
+/// const preview = await invoke('find_and_replace', {
+///   pattern: { mode: 'regex', pattern: 'PRJ-(\\d+)' },
+///   replacement: 'ACME-$1',
+///   scope: { type: 'workspace' },
+///   dryRun: true
+/// });
+/// // preview: { dryRun, nodes: [{ nodeId, nodeType, title, matches }], totalMatches, skipped }
+/// ```
+#[tauri::command]
+pub async fn find_and_replace(
+    service: State<'_, NodeService>,
+    pattern: ReplacePattern,
+    replacement: String,
+    scope: ReplaceScope,
+    dry_run: bool,
+) -> Result<ReplaceReport, CommandError> {
+    traced(async move {
+        Ok(service
+            .find_and_replace(&pattern, &replacement, &scope, dry_run)
+            .await?)
+    })
+    .await
+}
+
+/// Undo the last applied find-and-replace
+///
+/// # Example Frontend Usage
+/// ```typescript
+/// const restored = await invoke('undo_find_and_replace');
+/// ```
+#[tauri::command]
+pub async fn undo_find_and_replace(
+    service: State<'_, NodeService>,
+) -> Result<ReplaceReport, CommandError> {
+    Ok(service.undo_find_and_replace().await?)
+}
+
+/// Renames waiting to be applied to mention links
+///
+/// # Example Frontend Usage
+/// ```typescript
+/// const renames = await invoke('get_pending_renames');
+/// // renames: [{ nodeId, oldTitle, newTitle }]
+/// ```
+#[tauri::command]
+pub async fn get_pending_renames(
+    renames: State<'_, Arc<RenamePropagationService>>,
+) -> Result<Vec<PendingRename>, CommandError> {
+    Ok(renames.pending().await)
+}
+
+/// Keep the mention links to a node as they are for its pending rename;
+/// returns whether a rename was pending
+///
+/// # Example Frontend Usage
+/// ```typescript
+/// await invoke('skip_rename_propagation', { nodeId: rename.nodeId });
+/// ```
+#[tauri::command]
+pub async fn skip_rename_propagation(
+    renames: State<'_, Arc<RenamePropagationService>>,
+    node_id: String,
+) -> Result<bool, CommandError> {
+    Ok(renames.skip(&node_id).await)
+}
+
+/// Get the highlights taken from a source node, ordered by page and offset
+///
+/// # Example Frontend Usage
+/// ```typescript
+/// const highlights = await invoke('get_highlights', { sourceId: 'node-123' });
+/// ```
+#[tauri::command]
+pub async fn get_highlights(
+    service: State<'_, NodeService>,
+    source_id: String,
+) -> Result<Vec<Value>, CommandError> {
+    let highlights = service.get_highlights(&source_id).await?;
+    nodes_to_typed_values(highlights)
+}
+
+/// Get the week, month or quarter node for a period, creating it on first use
+///
+/// # Example Frontend Usage
+/// ```typescript
+/// const week = await invoke('get_or_create_period_node', { periodId: '2025-W03' });
+/// ```
+#[tauri::command]
+pub async fn get_or_create_period_node(
+    service: State<'_, NodeService>,
+    period_id: String,
+) -> Result<Value, CommandError> {
+    let node = service.get_or_create_period_node(&period_id).await?;
+    node_to_typed_value(node)
+}
+
 /// Update a task node with type-safe spoke field updates
 ///
 /// Provides end-to-end type safety for task updates by routing through
@@ -977,16 +1326,21 @@ pub async fn update_task_node(
     version: i64,
     update: models::TaskNodeUpdate,
 ) -> Result<Value, CommandError> {
-    let task = service
-        .update_task_node(&id, version, update)
-        .await
-        .map_err(CommandError::from)?;
+    traced(async move {
+        let task = service
+            .update_task_node(&id, version, update)
+            .await
+            .map_err(CommandError::from)?;
 
-    serde_json::to_value(task).map_err(|e| CommandError {
-        message: format!("Failed to serialize task node: {}", e),
-        code: "SERIALIZATION_ERROR".to_string(),
-        details: None,
+        serde_json::to_value(task).map_err(|e| CommandError {
+            message: format!("Failed to serialize task node: {}", e),
+            code: "SERIALIZATION_ERROR".to_string(),
+            details: None,
+            params: None,
+            correlation_id: None,
+        })
     })
+    .await
 }
 
 /// Delete a mention relationship between two nodes
@@ -1016,11 +1370,14 @@ pub async fn delete_node_mention(
     mentioning_node_id: String,
     mentioned_node_id: String,
 ) -> Result<(), CommandError> {
-    service
-        .with_client(TAURI_CLIENT_ID)
-        .remove_mention(&mentioning_node_id, &mentioned_node_id)
-        .await
-        .map_err(Into::into)
+    traced(async move {
+        service
+            .with_client(TAURI_CLIENT_ID)
+            .remove_mention(&mentioning_node_id, &mentioned_node_id)
+            .await
+            .map_err(Into::into)
+    })
+    .await
 }
 
 #[cfg(test)]
@@ -1037,6 +1394,8 @@ mod tests {
             message: "Test error".to_string(),
             code: "TEST_ERROR".to_string(),
             details: Some("Debug info".to_string()),
+            params: None,
+            correlation_id: None,
         };
 
         let json = serde_json::to_string(&err).unwrap();
@@ -1051,6 +1410,8 @@ mod tests {
             message: "Simple error".to_string(),
             code: "SIMPLE".to_string(),
             details: None,
+            params: None,
+            correlation_id: None,
         };
 
         let json = serde_json::to_string(&err).unwrap();
@@ -0,0 +1,51 @@
+//! Person commands
+//!
+//! Interactions with a contact are notes in the daily journal linked from the
+//! person node by an `interacted_with` edge.
+
+use nodespace_core::models;
+use nodespace_core::NodeService;
+use serde_json::Value;
+use tauri::State;
+
+use super::nodes::CommandError;
+use crate::constants::TAURI_CLIENT_ID;
+
+/// Log an interaction note against a person; returns the note ID
+///
+/// # Example Frontend Usage
+/// ```typescript
+/// const noteId = await invoke('log_interaction', { personId, note: 'Intro call' });
+/// ```
+#[tauri::command]
+pub async fn log_interaction(
+    service: State<'_, NodeService>,
+    person_id: String,
+    note: String,
+) -> Result<String, CommandError> {
+    Ok(service
+        .with_client(TAURI_CLIENT_ID)
+        .log_interaction(&person_id, &note)
+        .await?)
+}
+
+/// Interaction notes of a person, newest first
+///
+/// # Example Frontend Usage
+/// ```typescript
+/// const notes = await invoke('get_interaction_history', { personId });
+/// ```
+#[tauri::command]
+pub async fn get_interaction_history(
+    service: State<'_, NodeService>,
+    person_id: String,
+) -> Result<Vec<Value>, CommandError> {
+    let notes = service.get_interaction_history(&person_id).await?;
+    models::nodes_to_typed_values(notes).map_err(|e| CommandError {
+        message: e.clone(),
+        code: "CONVERSION_ERROR".to_string(),
+        details: Some(e),
+        params: None,
+        correlation_id: None,
+    })
+}
@@ -0,0 +1,164 @@
+//! Saved query watch and child view commands
+//!
+//! A view showing a saved query watches it once and then patches its list
+//! from `query:results-changed` events:
+//!
+//! ```typescript
+//! listen('query:results-changed', ({ payload }) => {
+//!   // payload: { queryId, added: [{ nodeId, index }], removed: [nodeId], moved: [{ nodeId, index }] }
+//! });
+//! ```
+
+use nodespace_core::models;
+use nodespace_core::models::ChildView;
+use nodespace_core::services::{ChildViewService, QueryWatchService};
+use serde::Serialize;
+use serde_json::Value;
+use std::sync::Arc;
+use tauri::State;
+
+use super::nodes::CommandError;
+
+/// Watch a saved query node and return its current results
+///
+/// # Example Frontend Usage
+/// ```typescript
+/// const nodes = await invoke('watch_query', { queryId });
+/// ```
+#[tauri::command]
+pub async fn watch_query(
+    watch: State<'_, Arc<QueryWatchService>>,
+    query_id: String,
+) -> Result<Vec<Value>, CommandError> {
+    let nodes = watch.watch(&query_id).await?;
+    models::nodes_to_typed_values(nodes).map_err(|e| CommandError {
+        message: e.clone(),
+        code: "CONVERSION_ERROR".to_string(),
+        details: Some(e),
+        params: None,
+        correlation_id: None,
+    })
+}
+
+/// Stop sending result diffs for a saved query
+///
+/// # Example Frontend Usage
+/// ```typescript
+/// await invoke('unwatch_query', { queryId });
+/// ```
+#[tauri::command]
+pub async fn unwatch_query(
+    watch: State<'_, Arc<QueryWatchService>>,
+    query_id: String,
+) -> Result<(), CommandError> {
+    watch.unwatch(&query_id).await;
+    Ok(())
+}
+
+/// A child view with its children converted for the frontend
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChildViewResponse {
+    pub view: ChildView,
+    pub groups: Vec<ChildViewGroupResponse>,
+    pub total: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ChildViewGroupResponse {
+    pub key: Value,
+    pub nodes: Vec<Value>,
+}
+
+/// Views saved on a node
+///
+/// # Example Frontend Usage
+/// ```typescript
+/// const views = await invoke('get_child_views', { nodeId });
+/// ```
+#[tauri::command]
+pub async fn get_child_views(
+    views: State<'_, Arc<ChildViewService>>,
+    node_id: String,
+) -> Result<Vec<ChildView>, CommandError> {
+    Ok(views.views(&node_id).await?)
+}
+
+/// Add a view to a node, or replace the view with the same ID; returns the
+/// node's views
+///
+/// # Example Frontend Usage
+/// ```typescript
+/// const views = await invoke('save_child_view', {
+///   nodeId,
+///   view: {
+///     id: 'open-tasks',
+///     name: 'Open tasks by priority',
+///     targetType: 'task',
+///     filters: [{ type: 'property', operator: 'equals', property: 'status', value: 'open' }],
+///     sorting: [{ field: 'priority', direction: 'desc' }],
+///     groupBy: 'priority'
+///   }
+/// });
+/// ```
+#[tauri::command]
+pub async fn save_child_view(
+    views: State<'_, Arc<ChildViewService>>,
+    node_id: String,
+    view: ChildView,
+) -> Result<Vec<ChildView>, CommandError> {
+    Ok(views.save_view(&node_id, view).await?)
+}
+
+/// Remove a view from a node; returns the remaining views
+///
+/// # Example Frontend Usage
+/// ```typescript
+/// const views = await invoke('delete_child_view', { nodeId, viewId });
+/// ```
+#[tauri::command]
+pub async fn delete_child_view(
+    views: State<'_, Arc<ChildViewService>>,
+    node_id: String,
+    view_id: String,
+) -> Result<Vec<ChildView>, CommandError> {
+    Ok(views.delete_view(&node_id, &view_id).await?)
+}
+
+/// Children of a node as shown by one of its views
+///
+/// # Example Frontend Usage
+/// ```typescript
+/// const result = await invoke('get_children_view', { nodeId, viewId: 'open-tasks' });
+/// // result: { view, groups: [{ key, nodes }], total }
+/// ```
+#[tauri::command]
+pub async fn get_children_view(
+    views: State<'_, Arc<ChildViewService>>,
+    node_id: String,
+    view_id: String,
+) -> Result<ChildViewResponse, CommandError> {
+    let result = views.get_children_view(&node_id, &view_id).await?;
+    let groups = result
+        .groups
+        .into_iter()
+        .map(|group| {
+            Ok(ChildViewGroupResponse {
+                key: group.key,
+                nodes: models::nodes_to_typed_values(group.nodes)?,
+            })
+        })
+        .collect::<Result<Vec<_>, String>>()
+        .map_err(|e| CommandError {
+            message: e.clone(),
+            code: "CONVERSION_ERROR".to_string(),
+            details: Some(e),
+            params: None,
+            correlation_id: None,
+        })?;
+    Ok(ChildViewResponse {
+        view: result.view,
+        groups,
+        total: result.total,
+    })
+}
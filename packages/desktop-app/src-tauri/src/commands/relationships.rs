@@ -0,0 +1,111 @@
+//! Relationship commands
+//!
+//! Typed edges between nodes, using the relationships declared in the source
+//! node's schema. These mirror the MCP `create_relationship`,
+//! `delete_relationship` and `get_related_nodes` tools.
+
+use nodespace_core::models;
+use nodespace_core::NodeService;
+use serde_json::{json, Value};
+use tauri::State;
+
+use super::nodes::CommandError;
+use crate::constants::TAURI_CLIENT_ID;
+
+/// Create an edge from `sourceId` to `targetId`
+///
+/// `edgeData` holds the values of the relationship's edge fields.
+///
+/// # Example Frontend Usage
+/// ```typescript
+/// await invoke('create_relationship', {
+///   sourceId: 'invoice-001',
+///   relationshipName: 'billed_to',
+///   targetId: 'customer-acme',
+///   edgeData: { billing_date: '2025-01-15' }
+/// });
+/// ```
+#[tauri::command]
+pub async fn create_relationship(
+    service: State<'_, NodeService>,
+    source_id: String,
+    relationship_name: String,
+    target_id: String,
+    edge_data: Option<Value>,
+) -> Result<(), CommandError> {
+    Ok(service
+        .with_client(TAURI_CLIENT_ID)
+        .create_relationship(
+            &source_id,
+            &relationship_name,
+            &target_id,
+            edge_data.unwrap_or(json!({})),
+        )
+        .await?)
+}
+
+/// Remove the edge from `sourceId` to `targetId`; succeeds if there is none
+///
+/// # Example Frontend Usage
+/// ```typescript
+/// await invoke('delete_relationship', {
+///   sourceId: 'invoice-001',
+///   relationshipName: 'billed_to',
+///   targetId: 'customer-acme'
+/// });
+/// ```
+#[tauri::command]
+pub async fn delete_relationship(
+    service: State<'_, NodeService>,
+    source_id: String,
+    relationship_name: String,
+    target_id: String,
+) -> Result<(), CommandError> {
+    Ok(service
+        .with_client(TAURI_CLIENT_ID)
+        .delete_relationship(&source_id, &relationship_name, &target_id)
+        .await?)
+}
+
+/// Nodes connected to `nodeId` by `edgeType` edges
+///
+/// `direction` is `"out"` (the node is the source, the default) or `"in"`.
+/// `edgeType` may also be a relationship's inverse label, which reads its
+/// edges from the other end: `invoices` (for `billed_to`) out of a customer
+/// are the invoices billed to it.
+///
+/// # Example Frontend Usage
+/// ```typescript
+/// const customers = await invoke('get_relationships', {
+///   nodeId: 'invoice-001',
+///   edgeType: 'billed_to'
+/// });
+/// const invoices = await invoke('get_relationships', {
+///   nodeId: 'customer-acme',
+///   edgeType: 'billed_to',
+///   direction: 'in'
+/// });
+/// const sameInvoices = await invoke('get_relationships', {
+///   nodeId: 'customer-acme',
+///   edgeType: 'invoices'
+/// });
+/// ```
+#[tauri::command]
+pub async fn get_relationships(
+    service: State<'_, NodeService>,
+    node_id: String,
+    edge_type: String,
+    direction: Option<String>,
+) -> Result<Vec<Value>, CommandError> {
+    let direction = direction.as_deref().unwrap_or("out");
+    let nodes = service
+        .get_related_nodes(&node_id, &edge_type, direction)
+        .await?;
+    models::nodes_to_typed_values(nodes).map_err(|e| CommandError {
+        message: e.clone(),
+        code: "CONVERSION_ERROR".to_string(),
+        details: Some(e),
+        params: None,
+        correlation_id: None,
+    })
+}
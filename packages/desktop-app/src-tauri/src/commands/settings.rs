@@ -16,6 +16,22 @@ pub struct SettingsResponse {
     pub saved_database_path: Option<String>,
     /// Display preferences
     pub display: DisplaySettingsResponse,
+    /// Folder receiving the markdown mirror, if enabled
+    pub vault_mirror_folder: Option<String>,
+    /// Whether edits to mirrored files are applied back
+    pub vault_mirror_two_way: bool,
+    /// Whether closing the main window hides it to the tray
+    pub close_to_tray: bool,
+    /// Whether documents are exported to the OS search index
+    pub os_search_index: bool,
+    /// How open tasks on past days are carried to today, if at all
+    pub task_rollover: Option<nodespace_core::services::RolloverMode>,
+    /// Whether a weekly review is written every Monday
+    pub weekly_digest: bool,
+    /// Whether mention text follows renames of the mentioned node
+    pub propagate_renames: bool,
+    /// Whether this session refuses changes (by preference or from recovery)
+    pub read_only: bool,
 }
 
 #[derive(serde::Serialize)]
@@ -23,6 +39,7 @@ pub struct SettingsResponse {
 pub struct DisplaySettingsResponse {
     pub render_markdown: bool,
     pub theme: String,
+    pub language: Option<String>,
 }
 
 /// Get current app settings for the Settings UI
@@ -37,7 +54,20 @@ pub async fn get_settings(app: AppHandle) -> Result<SettingsResponse, String> {
         display: DisplaySettingsResponse {
             render_markdown: prefs.display.render_markdown,
             theme: prefs.display.theme,
+            language: prefs.display.language,
         },
+        vault_mirror_folder: prefs
+            .vault_mirror_path
+            .map(|p| p.to_string_lossy().to_string()),
+        vault_mirror_two_way: prefs.vault_mirror_two_way,
+        close_to_tray: prefs.close_to_tray,
+        os_search_index: prefs.os_search_index,
+        task_rollover: prefs.task_rollover,
+        weekly_digest: prefs.weekly_digest,
+        propagate_renames: prefs.propagate_renames,
+        read_only: app
+            .try_state::<nodespace_core::NodeService>()
+            .map_or(prefs.read_only, |service| service.is_read_only()),
     })
 }
 
@@ -137,3 +167,125 @@ pub async fn reset_database_to_default(app: tauri::AppHandle) -> Result<String,
     let default_path = crate::preferences::get_default_database_path()?;
     Ok(default_path.to_string_lossy().to_string())
 }
+
+/// Set (or clear with `None`) the folder that documents are mirrored into
+/// as markdown, and whether edits made there are synced back. Takes effect
+/// on restart, like a database change.
+#[tauri::command]
+pub async fn set_vault_mirror_folder(
+    app: tauri::AppHandle,
+    folder: Option<String>,
+    two_way: Option<bool>,
+) -> Result<(), String> {
+    let mut prefs = crate::preferences::load_preferences(&app).await?;
+    prefs.vault_mirror_path = folder
+        .filter(|f| !f.trim().is_empty())
+        .map(std::path::PathBuf::from);
+    if let Some(two_way) = two_way {
+        prefs.vault_mirror_two_way = two_way;
+    }
+    crate::preferences::save_preferences(&app, &prefs).await
+}
+
+/// Enable or disable close-to-tray (takes effect immediately)
+#[tauri::command]
+pub async fn set_close_to_tray(app: tauri::AppHandle, enabled: bool) -> Result<(), String> {
+    let mut prefs = crate::preferences::load_preferences(&app).await?;
+    prefs.close_to_tray = enabled;
+    crate::preferences::save_preferences(&app, &prefs).await?;
+
+    #[cfg(desktop)]
+    if let Some(state) = app.try_state::<crate::tray::TrayState>() {
+        state.set_close_to_tray(enabled);
+    }
+    Ok(())
+}
+
+/// Enable or disable the OS search index export (takes effect on restart)
+///
+/// Disabling it also removes the exported files, so they drop out of
+/// Spotlight / Windows Search.
+#[tauri::command]
+pub async fn set_os_search_index(app: tauri::AppHandle, enabled: bool) -> Result<(), String> {
+    let mut prefs = crate::preferences::load_preferences(&app).await?;
+    prefs.os_search_index = enabled;
+    crate::preferences::save_preferences(&app, &prefs).await?;
+
+    if !enabled {
+        let folder = crate::search_index_folder(&app)?;
+        if let Err(e) = tokio::fs::remove_dir_all(&folder).await {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                return Err(format!("Failed to remove search index: {}", e));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Choose how open tasks on past days are carried to today (takes effect on
+/// restart); `None` turns the daily roll-over off
+#[tauri::command]
+pub async fn set_task_rollover(
+    app: tauri::AppHandle,
+    mode: Option<nodespace_core::services::RolloverMode>,
+) -> Result<(), String> {
+    let mut prefs = crate::preferences::load_preferences(&app).await?;
+    prefs.task_rollover = mode;
+    crate::preferences::save_preferences(&app, &prefs).await
+}
+
+/// Enable or disable the weekly review (takes effect on restart)
+#[tauri::command]
+pub async fn set_weekly_digest(app: tauri::AppHandle, enabled: bool) -> Result<(), String> {
+    let mut prefs = crate::preferences::load_preferences(&app).await?;
+    prefs.weekly_digest = enabled;
+    crate::preferences::save_preferences(&app, &prefs).await
+}
+
+/// Enable or disable rename propagation to mention links (takes effect on restart)
+#[tauri::command]
+pub async fn set_propagate_renames(app: tauri::AppHandle, enabled: bool) -> Result<(), String> {
+    let mut prefs = crate::preferences::load_preferences(&app).await?;
+    prefs.propagate_renames = enabled;
+    crate::preferences::save_preferences(&app, &prefs).await
+}
+
+/// Choose the language of error messages (takes effect immediately);
+/// `None` goes back to English
+///
+/// Errors still carry their code and params, so the frontend can render
+/// them itself for languages without a backend catalog.
+#[tauri::command]
+pub async fn set_language(app: tauri::AppHandle, language: Option<String>) -> Result<(), String> {
+    let mut prefs = crate::preferences::load_preferences(&app).await?;
+    nodespace_core::i18n::set_locale(
+        language
+            .as_deref()
+            .map_or_else(Default::default, nodespace_core::i18n::Locale::from_tag),
+    );
+    prefs.display.language = language;
+    crate::preferences::save_preferences(&app, &prefs).await
+}
+
+/// Turn read-only mode on or off (takes effect immediately)
+///
+/// Node changes are refused right away; background writers (embeddings,
+/// task roll-over, two-way vault mirroring) follow on restart. A database
+/// opened read-only skipped its migrations, so turning the mode off there
+/// also waits for a restart. Emits `read-only-changed` with the new state.
+#[tauri::command]
+pub async fn set_read_only_mode(app: tauri::AppHandle, enabled: bool) -> Result<(), String> {
+    use tauri::Emitter;
+
+    let mut prefs = crate::preferences::load_preferences(&app).await?;
+    prefs.read_only = enabled;
+    crate::preferences::save_preferences(&app, &prefs).await?;
+
+    if let Some(service) = app.try_state::<nodespace_core::NodeService>() {
+        if enabled || !service.store().is_read_only() {
+            service.set_read_only(enabled);
+        }
+    }
+    let _ = app.emit("read-only-changed", enabled);
+    Ok(())
+}
@@ -0,0 +1,144 @@
+//! Task commands
+//!
+//! Due-date views (overdue, due this week), time tracking and the roll-over
+//! of open tasks from past days, plus turning meeting action items into
+//! tasks. The daily roll-over itself is started from
+//! `init_services` when enabled in settings; `roll_over_tasks` runs it on
+//! demand, e.g. from a "Carry over open tasks" button on today's page.
+
+use chrono::{Local, NaiveDate};
+use nodespace_core::models::{self, TaskDueFilter, TaskNode, TrackedTimeEntry};
+use nodespace_core::services::{RolloverMode, RolloverReport, TaskRolloverService};
+use nodespace_core::{Node, NodeService};
+use serde_json::Value;
+use std::sync::Arc;
+use tauri::State;
+
+use super::nodes::CommandError;
+use crate::constants::TAURI_CLIENT_ID;
+
+fn nodes_to_typed_values(nodes: Vec<Node>) -> Result<Vec<Value>, CommandError> {
+    models::nodes_to_typed_values(nodes).map_err(|e| CommandError {
+        message: e.clone(),
+        code: "CONVERSION_ERROR".to_string(),
+        details: Some(e),
+        params: None,
+        correlation_id: None,
+    })
+}
+
+/// Open tasks due before today, earliest first
+///
+/// # Example Frontend Usage
+/// ```typescript
+/// const tasks = await invoke('get_overdue_tasks', { filter: { assignee: personId } });
+/// ```
+#[tauri::command]
+pub async fn get_overdue_tasks(
+    service: State<'_, NodeService>,
+    filter: Option<TaskDueFilter>,
+) -> Result<Vec<Value>, CommandError> {
+    let tasks = service
+        .get_overdue_tasks(&filter.unwrap_or_default())
+        .await?;
+    nodes_to_typed_values(tasks)
+}
+
+/// Open tasks due today or within the next `days` days, earliest first
+///
+/// # Example Frontend Usage
+/// ```typescript
+/// const thisWeek = await invoke('get_tasks_due_within', { days: 7, filter: { priority: 'high' } });
+/// ```
+#[tauri::command]
+pub async fn get_tasks_due_within(
+    service: State<'_, NodeService>,
+    days: u32,
+    filter: Option<TaskDueFilter>,
+) -> Result<Vec<Value>, CommandError> {
+    let tasks = service
+        .get_tasks_due_within(days, &filter.unwrap_or_default())
+        .await?;
+    nodes_to_typed_values(tasks)
+}
+
+/// Start timing work on a task (no-op if its timer already runs)
+///
+/// # Example Frontend Usage
+/// ```typescript
+/// const task = await invoke('start_timer', { taskId });
+/// ```
+#[tauri::command]
+pub async fn start_timer(
+    service: State<'_, NodeService>,
+    task_id: String,
+) -> Result<TaskNode, CommandError> {
+    Ok(service.start_timer(&task_id).await?)
+}
+
+/// Stop the running timer of a task
+///
+/// # Example Frontend Usage
+/// ```typescript
+/// const task = await invoke('stop_timer', { taskId });
+/// ```
+#[tauri::command]
+pub async fn stop_timer(
+    service: State<'_, NodeService>,
+    task_id: String,
+) -> Result<TaskNode, CommandError> {
+    Ok(service.stop_timer(&task_id).await?)
+}
+
+/// Tracked time per day and root node between two dates (inclusive)
+///
+/// # Example Frontend Usage
+/// ```typescript
+/// const rows = await invoke('get_tracked_time', { from: '2025-01-01', to: '2025-01-31' });
+/// // rows: [{ date, rootId, seconds }]
+/// ```
+#[tauri::command]
+pub async fn get_tracked_time(
+    service: State<'_, NodeService>,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Result<Vec<TrackedTimeEntry>, CommandError> {
+    Ok(service.tracked_time_report(from, to).await?)
+}
+
+/// Carry open tasks from past date nodes to `today` (defaults to the local date)
+///
+/// # Example Frontend Usage
+/// ```typescript
+/// const report = await invoke('roll_over_tasks', { mode: 'copy' });
+/// // report: { date, moved: [id], copied: [{ originalId, copyId }], reportNodeId }
+/// ```
+#[tauri::command]
+pub async fn roll_over_tasks(
+    service: State<'_, NodeService>,
+    mode: RolloverMode,
+    today: Option<NaiveDate>,
+) -> Result<RolloverReport, CommandError> {
+    let today = today.unwrap_or_else(|| Local::now().date_naive());
+    let report = TaskRolloverService::new(Arc::new(service.inner().clone()))
+        .roll_over(today, mode)
+        .await?;
+    Ok(report)
+}
+
+/// Convert a meeting's action-item notes into tasks linked to the meeting
+///
+/// # Example Frontend Usage
+/// ```typescript
+/// const taskIds = await invoke('extract_action_items', { meetingId });
+/// ```
+#[tauri::command]
+pub async fn extract_action_items(
+    service: State<'_, NodeService>,
+    meeting_id: String,
+) -> Result<Vec<String>, CommandError> {
+    Ok(service
+        .with_client(TAURI_CLIENT_ID)
+        .extract_action_items(&meeting_id)
+        .await?)
+}
@@ -0,0 +1,123 @@
+//! Workspace lock commands for the lock screen
+//!
+//! With a passphrase set, the workspace opens locked and node commands fail
+//! with `WORKSPACE_LOCKED` until `unlock_workspace` succeeds. The frontend
+//! shows its lock screen on the `workspace-locked` event, which fires on a
+//! manual lock and when the idle timeout passes (see
+//! `initialize_workspace_auto_lock`).
+
+use std::time::Duration;
+
+use nodespace_core::services::EditJournal;
+use nodespace_core::NodeService;
+use tauri::{AppHandle, Emitter, State};
+
+use super::nodes::CommandError;
+
+/// Emitted whenever the workspace locks
+pub const WORKSPACE_LOCKED_EVENT: &str = "workspace-locked";
+
+/// Emitted after a successful unlock
+pub const WORKSPACE_UNLOCKED_EVENT: &str = "workspace-unlocked";
+
+/// Lock state sent to the frontend
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LockStatus {
+    pub locked: bool,
+    pub has_passphrase: bool,
+    /// Idle minutes before auto-lock, if enabled
+    pub auto_lock_minutes: Option<u32>,
+}
+
+/// Idle timeout for a preference value in minutes
+pub fn auto_lock_duration(minutes: Option<u32>) -> Option<Duration> {
+    minutes.map(|minutes| Duration::from_secs(u64::from(minutes) * 60))
+}
+
+fn emit(app: &AppHandle, event: &str) {
+    if let Err(e) = app.emit(event, ()) {
+        tracing::error!("Failed to emit {}: {}", event, e);
+    }
+}
+
+#[tauri::command]
+pub async fn get_lock_status(
+    app: AppHandle,
+    service: State<'_, NodeService>,
+) -> Result<LockStatus, String> {
+    let prefs = crate::preferences::load_preferences(&app).await?;
+    let lock = service.workspace_lock();
+    Ok(LockStatus {
+        locked: lock.is_locked(),
+        has_passphrase: lock.has_passphrase(),
+        auto_lock_minutes: prefs.auto_lock_minutes,
+    })
+}
+
+/// Lock the workspace now (requires a passphrase)
+#[tauri::command]
+pub async fn lock_workspace(
+    app: AppHandle,
+    service: State<'_, NodeService>,
+) -> Result<(), CommandError> {
+    service.lock()?;
+    emit(&app, WORKSPACE_LOCKED_EVENT);
+    Ok(())
+}
+
+/// Unlock the workspace; a wrong passphrase fails with `INCORRECT_PASSPHRASE`
+///
+/// Edits journaled before a crash are written back once unlocked.
+#[tauri::command]
+pub async fn unlock_workspace(
+    app: AppHandle,
+    service: State<'_, NodeService>,
+    journal: State<'_, EditJournal>,
+    passphrase: String,
+) -> Result<(), CommandError> {
+    service.unlock(&passphrase).await?;
+    emit(&app, WORKSPACE_UNLOCKED_EVENT);
+    super::journal::replay_pending_edits(&app, &service, &journal).await;
+    Ok(())
+}
+
+/// Set, change or remove (`new_passphrase` of `null`) the workspace passphrase
+#[tauri::command]
+pub async fn set_workspace_passphrase(
+    service: State<'_, NodeService>,
+    current_passphrase: Option<String>,
+    new_passphrase: Option<String>,
+) -> Result<(), CommandError> {
+    service
+        .set_passphrase(current_passphrase.as_deref(), new_passphrase.as_deref())
+        .await?;
+    Ok(())
+}
+
+/// Set the idle timeout in minutes (`null` disables auto-lock)
+#[tauri::command]
+pub async fn set_auto_lock_timeout(
+    app: AppHandle,
+    service: State<'_, NodeService>,
+    minutes: Option<u32>,
+) -> Result<(), String> {
+    if minutes == Some(0) {
+        return Err("Auto-lock timeout must be at least one minute".to_string());
+    }
+
+    let mut prefs = crate::preferences::load_preferences(&app).await?;
+    prefs.auto_lock_minutes = minutes;
+    crate::preferences::save_preferences(&app, &prefs).await?;
+
+    service
+        .workspace_lock()
+        .set_auto_lock_after(auto_lock_duration(minutes));
+    Ok(())
+}
+
+/// Restart the idle timeout; the frontend calls this on user input
+#[tauri::command]
+pub fn record_workspace_activity(service: State<'_, NodeService>) {
+    service.workspace_lock().record_activity();
+}
@@ -11,3 +11,7 @@ pub const TAURI_CLIENT_ID: &str = "tauri-main";
 /// GGUF model filename for nomic-embed-text-v1.5 embeddings (768 dimensions).
 /// Used by resolve_bundled_model_path() in commands/db.rs to find the model file.
 pub const EMBEDDING_MODEL_FILENAME: &str = "nomic-embed-text-v1.5.Q8_0.gguf";
+
+/// Name the embedding model is downloaded under by the model manager; the
+/// most recently installed version is used.
+pub const EMBEDDING_MODEL_NAME: &str = "nomic-embed-text-v1.5";
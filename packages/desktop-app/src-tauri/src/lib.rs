@@ -16,6 +16,16 @@ pub mod mcp_integration;
 // Background services
 pub mod services;
 
+// Native OS notifications
+pub mod notifications;
+
+// Cancellation and flush-on-exit for background services
+pub mod shutdown;
+
+// System tray and close-to-tray background mode
+#[cfg(desktop)]
+pub mod tray;
+
 // Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
 #[tauri::command]
 fn greet(name: &str) -> String {
@@ -42,46 +52,645 @@ mod tests;
 ///
 /// The `cancel_token` is used for graceful shutdown - when cancelled, the forwarder
 /// will stop its event loop and exit cleanly before the Tokio runtime drops.
+/// Runs under the [`TaskSupervisor`](services::TaskSupervisor), which restarts
+/// it after an error or panic.
 pub fn initialize_domain_event_forwarder(
     app: tauri::AppHandle,
     node_service: std::sync::Arc<nodespace_core::NodeService>,
     client_id: String,
     cancel_token: tokio_util::sync::CancellationToken,
 ) -> anyhow::Result<()> {
-    use crate::services::DomainEventForwarder;
-    use futures::FutureExt;
+    use crate::services::{DomainEventForwarder, TaskSupervisor};
+    use tauri::Manager;
 
     tracing::info!(
         "🔧 Initializing domain event forwarding service (client_id: {})...",
         client_id
     );
 
-    // Spawn domain event forwarding service background task
+    let supervisor: tauri::State<TaskSupervisor> = app.state();
+    let forwarder_token = cancel_token.clone();
+    supervisor.spawn("domain_event_forwarder", cancel_token, move || {
+        let forwarder =
+            DomainEventForwarder::new(node_service.clone(), app.clone(), client_id.clone());
+        forwarder.run(forwarder_token.clone())
+    });
+
+    Ok(())
+}
+
+/// Initialize the script hook service
+///
+/// Spawns a background task that runs user `script` nodes when nodes of their
+/// bound type are created or updated. Stops when `cancel_token` is cancelled.
+pub fn initialize_script_service(
+    node_service: std::sync::Arc<nodespace_core::NodeService>,
+    cancel_token: tokio_util::sync::CancellationToken,
+) {
+    use nodespace_core::services::ScriptService;
+
+    let script_service = ScriptService::new(node_service.as_ref());
+
     tauri::async_runtime::spawn(async move {
-        let result = std::panic::AssertUnwindSafe(async {
-            let forwarder = DomainEventForwarder::new(node_service, app, client_id);
-            forwarder.run(cancel_token).await
-        })
-        .catch_unwind()
-        .await;
+        tokio::select! {
+            _ = cancel_token.cancelled() => {
+                tracing::info!("ScriptService shutting down");
+            }
+            _ = script_service.run_event_loop() => {
+                tracing::info!("ScriptService exited normally");
+            }
+        }
+    });
+}
 
-        match result {
-            Ok(Ok(_)) => {
-                tracing::info!("✅ Domain event forwarding service exited normally");
+/// Start maintaining the plain-text excerpt stored on each root
+///
+/// Longer roots get a generated summary when `summarizer` has a model loaded.
+pub fn initialize_root_excerpts(
+    node_service: std::sync::Arc<nodespace_core::NodeService>,
+    summarizer: std::sync::Arc<nodespace_nlp_engine::SummarizationService>,
+    cancel_token: tokio_util::sync::CancellationToken,
+) {
+    use nodespace_core::services::RootExcerptService;
+
+    let excerpts = RootExcerptService::new(node_service.as_ref()).with_summarizer(summarizer);
+
+    tauri::async_runtime::spawn(async move {
+        tokio::select! {
+            _ = cancel_token.cancelled() => {
+                tracing::info!("RootExcerptService shutting down");
             }
-            Ok(Err(e)) => {
-                tracing::error!("❌ Domain event forwarding error: {}", e);
+            _ = excerpts.run() => {
+                tracing::info!("RootExcerptService exited normally");
             }
-            Err(panic_info) => {
-                tracing::error!(
-                    "💥 Domain event forwarding service panicked: {:?}",
-                    panic_info
-                );
+        }
+    });
+}
+
+/// Keep the text of mention links in step with renamed nodes
+///
+/// The service is managed either way, so the pending-rename commands work;
+/// it only follows events when `enabled`. Each announced rename is emitted as
+/// `links:rename-pending` (the frontend may skip it), each applied one as
+/// `links:renamed` with the number of notes updated.
+pub fn initialize_rename_propagation(
+    app: tauri::AppHandle,
+    node_service: std::sync::Arc<nodespace_core::NodeService>,
+    enabled: bool,
+    cancel_token: tokio_util::sync::CancellationToken,
+) {
+    use nodespace_core::services::{PendingRename, RenamePropagationService};
+    use tauri::{Emitter, Manager};
+
+    let renames = std::sync::Arc::new(RenamePropagationService::new(node_service.as_ref()));
+    app.manage(renames.clone());
+    if !enabled {
+        return;
+    }
+
+    tauri::async_runtime::spawn(async move {
+        let on_pending = |rename: &PendingRename| {
+            if let Err(e) = app.emit("links:rename-pending", rename) {
+                tracing::error!("Failed to emit links:rename-pending: {}", e);
+            }
+        };
+        let on_propagated = |rename: &PendingRename, updated: usize| {
+            let payload = serde_json::json!({ "rename": rename, "updatedNodes": updated });
+            if let Err(e) = app.emit("links:renamed", payload) {
+                tracing::error!("Failed to emit links:renamed: {}", e);
+            }
+        };
+        tokio::select! {
+            _ = cancel_token.cancelled() => {
+                tracing::info!("RenamePropagationService shutting down");
+            }
+            _ = renames.run(on_pending, on_propagated) => {
+                tracing::info!("RenamePropagationService exited normally");
             }
         }
     });
+}
 
-    Ok(())
+/// Start mirroring document roots into `folder` as markdown files
+///
+/// NodeSpace stays the source of truth and the folder is rewritten (debounced)
+/// as nodes change. With `two_way`, edits made to the files in other editors
+/// are applied back to the database.
+///
+/// Cancelling `cancel_token` writes pending changes and ends the task; await
+/// the returned handle to know the folder is up to date.
+pub fn initialize_vault_mirror(
+    node_service: std::sync::Arc<nodespace_core::NodeService>,
+    folder: std::path::PathBuf,
+    two_way: bool,
+    cancel_token: tokio_util::sync::CancellationToken,
+) -> tauri::async_runtime::JoinHandle<()> {
+    use nodespace_core::services::{
+        VaultMirrorConfig, VaultMirrorService, DEFAULT_EXTERNAL_EDIT_POLL,
+    };
+
+    let mut config = VaultMirrorConfig::new(folder);
+    if two_way {
+        config = config.with_two_way_sync(DEFAULT_EXTERNAL_EDIT_POLL);
+    }
+    let mirror = VaultMirrorService::new(node_service.as_ref(), config);
+
+    // Not raced against the token: the loop writes pending files before it returns
+    tauri::async_runtime::spawn(async move {
+        mirror.run_event_loop(cancel_token.cancelled_owned()).await;
+    })
+}
+
+/// Folder holding the OS search index files
+pub fn search_index_folder(app: &tauri::AppHandle) -> Result<std::path::PathBuf, String> {
+    use tauri::Manager;
+
+    app.path()
+        .app_data_dir()
+        .map(|dir| dir.join("Search Index"))
+        .map_err(|e| format!("Failed to resolve app data directory: {}", e))
+}
+
+/// Start exporting document roots to the OS search index
+pub fn initialize_search_index(
+    node_service: std::sync::Arc<nodespace_core::NodeService>,
+    folder: std::path::PathBuf,
+    cancel_token: tokio_util::sync::CancellationToken,
+) {
+    let index = services::SearchIndexService::new(node_service, folder);
+
+    tauri::async_runtime::spawn(async move {
+        tokio::select! {
+            _ = cancel_token.cancelled() => {
+                tracing::info!("SearchIndexService shutting down");
+            }
+            _ = index.run() => {
+                tracing::info!("SearchIndexService exited normally");
+            }
+        }
+    });
+}
+
+/// Start re-evaluating watched saved queries on domain events
+///
+/// Each change to a watched query's results reaches the frontend as a
+/// `query:results-changed` event carrying the added/removed/moved diff. The
+/// child view and smart collection services, which run their queries through
+/// the same query service, are managed here too.
+pub fn initialize_query_watch(
+    app: tauri::AppHandle,
+    node_service: std::sync::Arc<nodespace_core::NodeService>,
+    store: std::sync::Arc<nodespace_core::SurrealStore>,
+    cancel_token: tokio_util::sync::CancellationToken,
+) {
+    use nodespace_core::services::{
+        ChildViewService, QueryService, QueryWatchService, SmartCollectionService,
+    };
+    use tauri::{Emitter, Manager};
+
+    let query_service = std::sync::Arc::new(QueryService::new(
+        store,
+        node_service.workspace_lock().clone(),
+    ));
+    let watch = std::sync::Arc::new(QueryWatchService::new(
+        node_service.clone(),
+        query_service.clone(),
+    ));
+    let smart_collections = std::sync::Arc::new(SmartCollectionService::new(
+        node_service.clone(),
+        query_service.clone(),
+    ));
+    let events = node_service.subscribe_to_events();
+    app.manage(std::sync::Arc::new(ChildViewService::new(
+        node_service,
+        query_service.clone(),
+    )));
+    app.manage(query_service);
+    app.manage(watch.clone());
+    app.manage(smart_collections.clone());
+
+    let smart_cancel = cancel_token.clone();
+    tauri::async_runtime::spawn(async move {
+        tokio::select! {
+            _ = smart_cancel.cancelled() => {
+                tracing::info!("Smart collection invalidation shutting down");
+            }
+            _ = smart_collections.run(events) => {}
+        }
+    });
+
+    tauri::async_runtime::spawn(async move {
+        let on_diff = |diff: nodespace_core::services::QueryResultDiff| {
+            if let Err(e) = app.emit("query:results-changed", &diff) {
+                tracing::error!("Failed to emit query:results-changed: {}", e);
+            }
+        };
+        tokio::select! {
+            _ = cancel_token.cancelled() => {
+                tracing::info!("QueryWatchService shutting down");
+            }
+            _ = watch.run(on_diff) => {
+                tracing::info!("QueryWatchService exited normally");
+            }
+        }
+    });
+}
+
+/// Warm up the inbound relationship cache and keep it in step with schema changes
+///
+/// The node types of the most recently modified roots are loaded first so
+/// they survive eviction; schema node changes then invalidate the cache. Its
+/// statistics are reported by `get_database_diagnostics`.
+pub fn initialize_relationship_cache(
+    app: tauri::AppHandle,
+    node_service: std::sync::Arc<nodespace_core::NodeService>,
+    store: std::sync::Arc<nodespace_core::SurrealStore>,
+    cancel_token: tokio_util::sync::CancellationToken,
+) {
+    use nodespace_core::services::{InboundRelationshipCache, DEFAULT_HOT_ROOTS};
+    use tauri::Manager;
+
+    let cache = std::sync::Arc::new(InboundRelationshipCache::new(store));
+    app.manage(cache.clone());
+    let events = node_service.subscribe_to_events();
+
+    tauri::async_runtime::spawn(async move {
+        match cache.warm_up(DEFAULT_HOT_ROOTS).await {
+            Ok(types) => tracing::info!("Relationship cache warmed up for {} node types", types),
+            Err(e) => tracing::warn!("Relationship cache warm-up failed: {}", e),
+        }
+        tokio::select! {
+            _ = cancel_token.cancelled() => {
+                tracing::info!("Relationship cache invalidation shutting down");
+            }
+            _ = cache.run(events) => {}
+        }
+    });
+}
+
+/// Forward slow query budget alerts to the frontend
+///
+/// Repeated slow store queries (see `nodespace_core::db::slow_query`) are
+/// emitted as `performance:budget-exceeded` with the
+/// [`SlowQueryAlert`](nodespace_core::db::SlowQueryAlert); the queries
+/// themselves are listed by `get_slow_queries`.
+pub fn initialize_slow_query_alerts(
+    app: tauri::AppHandle,
+    store: std::sync::Arc<nodespace_core::SurrealStore>,
+    cancel_token: tokio_util::sync::CancellationToken,
+) {
+    use tauri::Emitter;
+    use tokio::sync::broadcast::error::RecvError;
+
+    let mut alerts = store.slow_queries().subscribe();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let alert = tokio::select! {
+                _ = cancel_token.cancelled() => break,
+                alert = alerts.recv() => alert,
+            };
+            match alert {
+                Ok(alert) => {
+                    if let Err(e) = app.emit("performance:budget-exceeded", &alert) {
+                        tracing::error!("Failed to emit performance:budget-exceeded: {}", e);
+                    }
+                }
+                Err(RecvError::Lagged(_)) => {}
+                Err(RecvError::Closed) => break,
+            }
+        }
+    });
+}
+
+/// Forward embedding cache eviction pressure to the frontend
+///
+/// Emits `embeddings:cache-pressure` with the
+/// [`EvictionPressure`](nodespace_nlp_engine::EvictionPressure) when the cache
+/// evicts faster than it is useful; its size and memory are reported by
+/// `get_database_diagnostics`.
+pub fn initialize_embedding_cache_pressure(
+    app: tauri::AppHandle,
+    nlp_engine: std::sync::Arc<nodespace_nlp_engine::EmbeddingService>,
+    cancel_token: tokio_util::sync::CancellationToken,
+) {
+    use tauri::Emitter;
+    use tokio::sync::broadcast::error::RecvError;
+
+    let mut pressure = nlp_engine.subscribe_cache_pressure();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let event = tokio::select! {
+                _ = cancel_token.cancelled() => break,
+                event = pressure.recv() => event,
+            };
+            match event {
+                Ok(event) => {
+                    if let Err(e) = app.emit("embeddings:cache-pressure", &event) {
+                        tracing::error!("Failed to emit embeddings:cache-pressure: {}", e);
+                    }
+                }
+                Err(RecvError::Lagged(_)) => {}
+                Err(RecvError::Closed) => break,
+            }
+        }
+    });
+}
+
+/// Start the daily roll-over of open tasks from past days to today
+///
+/// Runs once right away (catching up on days the app was closed) and then
+/// every night at midnight. Each non-empty run emits `tasks:rolled-over`
+/// with the [`RolloverReport`](nodespace_core::services::RolloverReport).
+pub fn initialize_task_rollover(
+    app: tauri::AppHandle,
+    node_service: std::sync::Arc<nodespace_core::NodeService>,
+    mode: nodespace_core::services::RolloverMode,
+    cancel_token: tokio_util::sync::CancellationToken,
+) {
+    use nodespace_core::services::TaskRolloverService;
+    use tauri::Emitter;
+
+    let rollover = TaskRolloverService::new(node_service);
+    tauri::async_runtime::spawn(async move {
+        let on_report = |report: nodespace_core::services::RolloverReport| {
+            if let Err(e) = app.emit("tasks:rolled-over", &report) {
+                tracing::error!("Failed to emit tasks:rolled-over: {}", e);
+            }
+        };
+        tokio::select! {
+            _ = cancel_token.cancelled() => {
+                tracing::info!("TaskRolloverService shutting down");
+            }
+            _ = rollover.run(mode, chrono::NaiveTime::MIN, on_report) => {}
+        }
+    });
+}
+
+/// Set up weekly reviews, written every Monday when `scheduled`
+///
+/// The service is managed for `generate_weekly_digest` either way. Scheduled
+/// runs review the previous week if it has no review yet (catching up after
+/// the app was closed) and emit `digest:generated` with the
+/// [`WeeklyDigest`](nodespace_core::services::WeeklyDigest).
+pub fn initialize_weekly_digest(
+    app: tauri::AppHandle,
+    node_service: std::sync::Arc<nodespace_core::NodeService>,
+    store: std::sync::Arc<nodespace_core::SurrealStore>,
+    summarizer: std::sync::Arc<nodespace_nlp_engine::SummarizationService>,
+    scheduled: bool,
+    cancel_token: tokio_util::sync::CancellationToken,
+) {
+    use nodespace_core::services::{DigestService, QueryService};
+    use tauri::{Emitter, Manager};
+
+    let query_service = std::sync::Arc::new(QueryService::new(
+        store,
+        node_service.workspace_lock().clone(),
+    ));
+    let digest = std::sync::Arc::new(
+        DigestService::new(node_service, query_service).with_summarizer(summarizer),
+    );
+    app.manage(digest.clone());
+    if !scheduled {
+        return;
+    }
+
+    tauri::async_runtime::spawn(async move {
+        let on_digest = |digest: nodespace_core::services::WeeklyDigest| {
+            if let Err(e) = app.emit("digest:generated", &digest) {
+                tracing::error!("Failed to emit digest:generated: {}", e);
+            }
+        };
+        tokio::select! {
+            _ = cancel_token.cancelled() => {
+                tracing::info!("DigestService shutting down");
+            }
+            _ = digest.run(on_digest) => {}
+        }
+    });
+}
+
+/// Check the workspace idle timeout every few seconds
+///
+/// Locks the workspace once the timeout set through
+/// `set_auto_lock_timeout` passes without user activity, emitting
+/// `workspace-locked` so the frontend shows its lock screen.
+pub fn initialize_workspace_auto_lock(
+    app: tauri::AppHandle,
+    node_service: std::sync::Arc<nodespace_core::NodeService>,
+    cancel_token: tokio_util::sync::CancellationToken,
+) {
+    use commands::workspace_lock::WORKSPACE_LOCKED_EVENT;
+    use tauri::Emitter;
+
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(5));
+        loop {
+            tokio::select! {
+                _ = cancel_token.cancelled() => {
+                    tracing::info!("Workspace auto-lock shutting down");
+                    break;
+                }
+                _ = interval.tick() => {
+                    if node_service.lock_if_idle() {
+                        tracing::info!("Workspace locked after idle timeout");
+                        if let Err(e) = app.emit(WORKSPACE_LOCKED_EVENT, ()) {
+                            tracing::error!("Failed to emit {}: {}", WORKSPACE_LOCKED_EVENT, e);
+                        }
+                    }
+                }
+            }
+        }
+    });
+}
+
+/// Start hourly evaluation of goal key results
+///
+/// Records each key result's value as a daily snapshot and emits
+/// `goals:snapshotted` with the IDs of key results whose snapshots changed.
+/// The service is also managed for `get_goal_progress`.
+pub fn initialize_goal_evaluation(
+    app: tauri::AppHandle,
+    node_service: std::sync::Arc<nodespace_core::NodeService>,
+    store: std::sync::Arc<nodespace_core::SurrealStore>,
+    cancel_token: tokio_util::sync::CancellationToken,
+) {
+    use nodespace_core::services::{
+        GoalEvaluationService, QueryService, DEFAULT_GOAL_EVALUATION_INTERVAL,
+    };
+    use tauri::{Emitter, Manager};
+
+    let query_service = std::sync::Arc::new(QueryService::new(
+        store,
+        node_service.workspace_lock().clone(),
+    ));
+    let evaluation = std::sync::Arc::new(GoalEvaluationService::new(node_service, query_service));
+    app.manage(evaluation.clone());
+
+    tauri::async_runtime::spawn(async move {
+        let on_snapshot = |key_result_ids: Vec<String>| {
+            if let Err(e) = app.emit("goals:snapshotted", &key_result_ids) {
+                tracing::error!("Failed to emit goals:snapshotted: {}", e);
+            }
+        };
+        tokio::select! {
+            _ = cancel_token.cancelled() => {
+                tracing::info!("GoalEvaluationService shutting down");
+            }
+            _ = evaluation.run(DEFAULT_GOAL_EVALUATION_INTERVAL, on_snapshot) => {}
+        }
+    });
+}
+
+/// Rank documents by importance in the mention graph every few hours
+///
+/// The service is managed for `get_central_notes` either way; scores are
+/// only recomputed when `scheduled` (not in read-only sessions). Each refresh
+/// emits `importance:refreshed` with the number of documents scored.
+pub fn initialize_importance(
+    app: tauri::AppHandle,
+    node_service: std::sync::Arc<nodespace_core::NodeService>,
+    scheduled: bool,
+    cancel_token: tokio_util::sync::CancellationToken,
+) {
+    use nodespace_core::services::{ImportanceService, DEFAULT_IMPORTANCE_INTERVAL};
+    use tauri::{Emitter, Manager};
+
+    let importance = std::sync::Arc::new(ImportanceService::new(node_service));
+    app.manage(importance.clone());
+    if !scheduled {
+        return;
+    }
+
+    tauri::async_runtime::spawn(async move {
+        let on_refresh = |scored: usize| {
+            if let Err(e) = app.emit("importance:refreshed", scored) {
+                tracing::error!("Failed to emit importance:refreshed: {}", e);
+            }
+        };
+        tokio::select! {
+            _ = cancel_token.cancelled() => {
+                tracing::info!("ImportanceService shutting down");
+            }
+            _ = importance.run(DEFAULT_IMPORTANCE_INTERVAL, on_refresh) => {}
+        }
+    });
+}
+
+/// Quiet period before idle database maintenance may start
+pub const MAINTENANCE_IDLE_DELAY: std::time::Duration = std::time::Duration::from_secs(10 * 60);
+
+/// Minimum time between idle database maintenance runs
+pub const MAINTENANCE_INTERVAL: std::time::Duration = std::time::Duration::from_secs(24 * 60 * 60);
+
+/// Run database maintenance once a day, while nobody is editing
+///
+/// Waits for [`MAINTENANCE_IDLE_DELAY`] without node changes, then purges
+/// expired trash and orphaned embeddings if the last run was at least
+/// [`MAINTENANCE_INTERVAL`] ago. Skipped while the workspace is locked or
+/// read-only.
+pub fn initialize_database_maintenance(
+    node_service: std::sync::Arc<nodespace_core::NodeService>,
+    store: std::sync::Arc<nodespace_core::SurrealStore>,
+    cancel_token: tokio_util::sync::CancellationToken,
+) {
+    use nodespace_core::db::maintenance::DEFAULT_TRASH_RETENTION;
+    use tokio::sync::broadcast::error::RecvError;
+
+    tauri::async_runtime::spawn(async move {
+        let mut events = node_service.subscribe_to_events();
+        let mut last_run: Option<std::time::Instant> = None;
+        loop {
+            tokio::select! {
+                _ = cancel_token.cancelled() => {
+                    tracing::info!("Database maintenance shutting down");
+                    break;
+                }
+                received = events.recv() => {
+                    // Any change restarts the idle delay
+                    if let Err(RecvError::Closed) = received {
+                        break;
+                    }
+                }
+                _ = tokio::time::sleep(MAINTENANCE_IDLE_DELAY) => {
+                    let due = match last_run {
+                        Some(at) => at.elapsed() >= MAINTENANCE_INTERVAL,
+                        None => true,
+                    };
+                    if !due || node_service.is_locked() || node_service.is_read_only() {
+                        continue;
+                    }
+                    last_run = Some(std::time::Instant::now());
+                    if let Err(e) = store.maintenance().run(DEFAULT_TRASH_RETENTION).await {
+                        tracing::warn!("Database maintenance failed: {:#}", e);
+                    }
+                }
+            }
+        }
+    });
+}
+
+/// Start the notification sources: due-task reminders and an audit of nodes
+/// deleted through MCP
+///
+/// Deletions arrive as one domain event per node (a subtree delete emits many),
+/// so they are batched until a second passes without another.
+pub fn initialize_notifications(
+    app: tauri::AppHandle,
+    node_service: std::sync::Arc<nodespace_core::NodeService>,
+    cancel_token: tokio_util::sync::CancellationToken,
+) {
+    use nodespace_core::services::{is_mcp_client_id, ReminderService, DEFAULT_REMINDER_INTERVAL};
+    use nodespace_core::DomainEvent;
+    use tokio::sync::broadcast::error::RecvError;
+
+    let reminders = ReminderService::new(node_service.clone());
+    let reminder_app = app.clone();
+    let reminder_token = cancel_token.clone();
+    tauri::async_runtime::spawn(async move {
+        let on_due = |reminder: nodespace_core::services::Reminder| {
+            notifications::notify(
+                &reminder_app,
+                "Task due",
+                &reminder.title,
+                Some(notifications::node_link(&reminder.node_id)),
+            );
+        };
+        tokio::select! {
+            _ = reminder_token.cancelled() => {
+                tracing::info!("ReminderService shutting down");
+            }
+            _ = reminders.run(DEFAULT_REMINDER_INTERVAL, on_due) => {}
+        }
+    });
+
+    let mut rx = node_service.subscribe_to_events();
+    tauri::async_runtime::spawn(async move {
+        let mut deleted = 0usize;
+        loop {
+            tokio::select! {
+                _ = cancel_token.cancelled() => break,
+                received = rx.recv() => match received {
+                    Ok(DomainEvent::NodeDeleted { source_client_id, .. })
+                        if source_client_id.as_deref().is_some_and(is_mcp_client_id) =>
+                    {
+                        deleted += 1;
+                    }
+                    Ok(_) | Err(RecvError::Lagged(_)) => {}
+                    Err(RecvError::Closed) => break,
+                },
+                _ = tokio::time::sleep(std::time::Duration::from_secs(1)), if deleted > 0 => {
+                    let body = if deleted == 1 {
+                        "An AI agent deleted a node".to_string()
+                    } else {
+                        format!("An AI agent deleted {} nodes", deleted)
+                    };
+                    notifications::notify(&app, "Nodes deleted via MCP", &body, None);
+                    deleted = 0;
+                }
+            }
+        }
+    });
 }
 
 /// Initialize MCP server with shared services from Tauri state
@@ -92,15 +701,21 @@ pub fn initialize_domain_event_forwarder(
 /// ensuring MCP and Tauri commands operate on the same database.
 ///
 /// The `cancel_token` is used for graceful shutdown - when cancelled, the MCP
-/// server task will be aborted before the Tokio runtime drops.
+/// server task will be aborted before the Tokio runtime drops. Until then the
+/// [`TaskSupervisor`](services::TaskSupervisor) restarts the server if it
+/// fails or panics.
+///
+/// `agent_profiles` come from preferences; when non-empty, each MCP
+/// connection is limited to the roots of the profile its token selects.
 ///
 /// As of Issue #715, uses McpServerService from nodespace-core for managed lifecycle.
 pub fn initialize_mcp_server(
     app: tauri::AppHandle,
+    agent_profiles: Vec<nodespace_core::mcp::AgentProfile>,
     cancel_token: tokio_util::sync::CancellationToken,
 ) -> anyhow::Result<()> {
     use crate::commands::embeddings::EmbeddingState;
-    use futures::FutureExt;
+    use crate::services::TaskSupervisor;
     use nodespace_core::NodeService;
     use std::sync::Arc;
     use tauri::Manager;
@@ -119,6 +734,7 @@ pub fn initialize_mcp_server(
     let (mcp_service, callback) = mcp_integration::create_mcp_service_with_events(
         node_service_arc,
         embedding_service_arc,
+        agent_profiles,
         app.clone(),
     );
 
@@ -130,77 +746,40 @@ pub fn initialize_mcp_server(
     // Register MCP service as managed state for potential future access
     app.manage(mcp_service.clone());
 
-    // Spawn MCP server task with Tauri event emissions
-    // Uses panic protection to prevent silent background task failures
-    // Monitors cancel_token for graceful shutdown before runtime drops
-    tauri::async_runtime::spawn(async move {
-        // Use FutureExt::catch_unwind for proper async panic catching
-        // This avoids the deadlock risk of using block_on inside an async task
-        // AssertUnwindSafe is needed because services contain non-UnwindSafe types
-        let result = std::panic::AssertUnwindSafe(async {
-            tokio::select! {
-                res = mcp_service.start_with_callback(callback) => res,
-                _ = cancel_token.cancelled() => {
-                    tracing::info!("MCP server received shutdown signal");
-                    Ok(())
-                }
-            }
-        })
-        .catch_unwind()
-        .await;
-
-        match result {
-            Ok(Ok(_)) => {
-                tracing::info!("✅ MCP server exited normally");
-            }
-            Ok(Err(e)) => {
-                tracing::error!("❌ MCP server error: {}", e);
-            }
-            Err(panic_info) => {
-                tracing::error!("💥 MCP server panicked: {:?}", panic_info);
-            }
-        }
+    // Spawn MCP server task with Tauri event emissions, restarted on failure
+    let supervisor: tauri::State<TaskSupervisor> = app.state();
+    supervisor.spawn("mcp_server", cancel_token, move || {
+        let mcp_service = mcp_service.clone();
+        let callback = callback.clone();
+        async move { mcp_service.start_with_callback(callback).await }
     });
 
     Ok(())
 }
 
-/// Shared shutdown token for graceful background task termination.
-///
-/// Managed as Tauri state so it can be accessed from both the setup phase
-/// (where background tasks are spawned) and the run event handler (where
-/// shutdown is triggered). When cancelled, all background tasks (MCP server,
-/// domain event forwarder) exit their loops before the Tokio runtime drops.
-#[derive(Clone)]
-pub struct ShutdownToken(tokio_util::sync::CancellationToken);
-
-impl ShutdownToken {
-    fn new() -> Self {
-        Self(tokio_util::sync::CancellationToken::new())
-    }
-
-    /// Create a child token for a background task.
-    /// Cancelling the parent automatically cancels all children.
-    pub fn child_token(&self) -> tokio_util::sync::CancellationToken {
-        self.0.child_token()
-    }
-
-    /// Signal all background tasks to shut down.
-    /// Idempotent - safe to call multiple times.
-    pub fn cancel(&self) {
-        self.0.cancel();
-    }
-}
-
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     use tauri::{menu::*, Emitter, Manager, RunEvent};
 
-    // Create shutdown token for coordinating graceful background task termination
-    let shutdown_token = ShutdownToken::new();
-    let shutdown_token_for_setup = shutdown_token.clone();
+    // Coordinates graceful background task termination and flush-on-exit
+    let shutdown = shutdown::ShutdownCoordinator::new();
+    let shutdown_for_setup = shutdown.clone();
 
     let app = tauri::Builder::default()
+        // Must be registered first: a second launch (e.g. from a nodespace:// link
+        // on Windows/Linux) forwards its URL to this instance and exits
+        .plugin(tauri_plugin_single_instance::init(|app, argv, _cwd| {
+            if let Some(window) = app.get_webview_window("main") {
+                let _ = window.show();
+                let _ = window.set_focus();
+            }
+            // Search hits (index files) opened on Windows/Linux arrive as arguments
+            for arg in argv.iter().skip(1) {
+                services::search_index::open_index_file(app, std::path::Path::new(arg));
+            }
+        }))
+        .plugin(tauri_plugin_deep_link::init())
+        .plugin(tauri_plugin_notification::init())
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_dialog::init())
         .setup(move |app| {
@@ -287,9 +866,91 @@ pub fn run() {
             // Set the menu
             app.set_menu(menu)?;
 
-            // Register shutdown token as managed state so commands/db.rs can access it
-            // when spawning background tasks (MCP server, domain event forwarder)
-            app.manage(shutdown_token_for_setup);
+            // Quick capture works while NodeSpace is in the background.
+            // Another app may already own the shortcut, which is not fatal.
+            #[cfg(desktop)]
+            {
+                use tauri_plugin_global_shortcut::{GlobalShortcutExt, ShortcutState};
+
+                app.handle().plugin(
+                    tauri_plugin_global_shortcut::Builder::new()
+                        .with_handler(|app, _shortcut, event| {
+                            if event.state() == ShortcutState::Pressed {
+                                commands::inbox::show_quick_capture_window(app);
+                            }
+                        })
+                        .build(),
+                )?;
+                if let Err(e) = app
+                    .global_shortcut()
+                    .register(commands::inbox::QUICK_CAPTURE_SHORTCUT)
+                {
+                    tracing::warn!(
+                        "Quick capture shortcut {} unavailable: {}",
+                        commands::inbox::QUICK_CAPTURE_SHORTCUT,
+                        e
+                    );
+                }
+            }
+
+            // Tray icon; with close-to-tray on, background services outlive the window
+            #[cfg(desktop)]
+            {
+                app.manage(tray::TrayState::default());
+                if let Err(e) = tray::init(app.handle()) {
+                    tracing::warn!("System tray unavailable: {}", e);
+                }
+            }
+
+            // nodespace:// links from other apps. On macOS the scheme comes from the
+            // bundle's Info.plist; Linux and Windows dev builds register at runtime.
+            app.manage(commands::deep_link::PendingDeepLinks::default());
+            app.manage(notifications::NotificationState::default());
+            #[cfg(desktop)]
+            {
+                use tauri_plugin_deep_link::DeepLinkExt;
+
+                #[cfg(any(target_os = "linux", all(debug_assertions, windows)))]
+                if let Err(e) = app.deep_link().register_all() {
+                    tracing::warn!("Failed to register nodespace:// scheme: {}", e);
+                }
+
+                let handle = app.handle().clone();
+                app.deep_link().on_open_url(move |event| {
+                    let urls = event.urls().iter().map(|url| url.to_string()).collect();
+                    commands::deep_link::handle_deep_links(&handle, urls);
+                });
+                // The app may have been launched by a link or a search hit
+                if let Ok(Some(urls)) = app.deep_link().get_current() {
+                    let urls = urls.iter().map(|url| url.to_string()).collect();
+                    commands::deep_link::handle_deep_links(app.handle(), urls);
+                }
+                for arg in std::env::args().skip(1) {
+                    services::search_index::open_index_file(
+                        app.handle(),
+                        std::path::Path::new(&arg),
+                    );
+                }
+            }
+
+            // Downloaded models live next to hand-installed ones in ~/.nodespace/models/
+            match nodespace_nlp_engine::ModelManager::in_default_dir() {
+                Ok(manager) => {
+                    app.manage(std::sync::Arc::new(manager));
+                }
+                Err(e) => tracing::warn!("Model downloads unavailable: {}", e),
+            }
+
+            // Register the shutdown coordinator as managed state so commands/db.rs can
+            // hand out tokens and add flush hooks when spawning background services
+            app.manage(shutdown_for_setup);
+
+            // Restarts crashed background services and reports their health to the UI
+            let status_handle = app.handle().clone();
+            app.manage(services::TaskSupervisor::new(move |status| {
+                let _ =
+                    status_handle.emit(services::supervisor::BACKGROUND_TASK_STATUS_EVENT, status);
+            }));
 
             // Note: MCP server initialization is deferred until database is initialized
             // See commands/db.rs::init_services() which calls initialize_mcp_server()
@@ -335,6 +996,8 @@ pub fn run() {
             } else if *event.id() == quit_id {
                 // Request exit through Tauri's event loop instead of std::process::exit(0)
                 // This triggers RunEvent::ExitRequested, allowing proper cleanup
+                #[cfg(desktop)]
+                app.state::<tray::TrayState>().request_quit();
                 if let Some(window) = app.get_webview_window("main") {
                     let _ = window.close();
                 }
@@ -344,6 +1007,7 @@ pub fn run() {
             greet,
             toggle_sidebar,
             commands::db::initialize_database,
+            commands::db::restore_database_from_backup,
             commands::embeddings::generate_root_embedding,
             commands::embeddings::search_roots,
             commands::embeddings::update_root_embedding,
@@ -352,12 +1016,18 @@ pub fn run() {
             commands::embeddings::on_root_idle,
             commands::embeddings::sync_embeddings,
             commands::embeddings::get_stale_root_count,
-            commands::models::ensure_models_installed,
+            commands::models::ensure_model_installed,
+            commands::models::list_installed_models,
+            commands::models::remove_model,
             commands::nodes::create_node,
             commands::nodes::create_root_node,
             commands::nodes::create_node_mention,
             commands::nodes::get_node,
+            commands::nodes::validate_node,
             commands::nodes::update_node,
+            commands::nodes::set_node_property,
+            commands::nodes::remove_node_property,
+            commands::nodes::set_node_appearance,
             commands::nodes::move_node,
             commands::nodes::reorder_node,
             commands::nodes::delete_node,
@@ -370,20 +1040,34 @@ pub fn run() {
             commands::nodes::get_outgoing_mentions,
             commands::nodes::get_incoming_mentions,
             commands::nodes::get_mentioning_roots,
+            commands::nodes::get_broken_links,
+            commands::nodes::relink_broken_link,
+            commands::nodes::merge_nodes,
+            commands::nodes::find_and_replace,
+            commands::nodes::undo_find_and_replace,
+            commands::nodes::get_pending_renames,
+            commands::nodes::skip_rename_propagation,
+            commands::nodes::get_highlights,
+            commands::nodes::get_or_create_period_node,
             commands::nodes::delete_node_mention,
             commands::nodes::update_task_node,
+            // Structured clipboard paste
+            commands::clipboard::paste_structured,
             // Collection commands (Issue #757 - Collection browsing and management UI)
             commands::collections::get_all_collections,
             commands::collections::get_collection_members,
             commands::collections::get_collection_members_recursive,
+            commands::collections::get_collection_stats,
             commands::collections::get_node_collections,
             commands::collections::add_node_to_collection,
             commands::collections::add_node_to_collection_path,
             commands::collections::remove_node_from_collection,
+            commands::collections::reorder_collection_member,
             commands::collections::find_collection_by_path,
             commands::collections::get_collection_by_name,
             commands::collections::create_collection,
             commands::collections::rename_collection,
+            commands::collections::rename_collection_path,
             commands::collections::delete_collection,
             // Schema read commands (Issue #690 - mutation commands removed, not used by UI)
             commands::schemas::get_all_schemas,
@@ -391,16 +1075,83 @@ pub fn run() {
             // Diagnostic commands for debugging persistence issues
             commands::diagnostics::get_database_diagnostics,
             commands::diagnostics::test_node_persistence,
+            commands::diagnostics::explain_query,
+            commands::diagnostics::get_background_task_status,
+            commands::diagnostics::get_database_storage,
+            commands::diagnostics::get_slow_queries,
+            commands::diagnostics::run_database_maintenance,
+            commands::diagnostics::get_maintenance_report,
             // File import commands for bulk markdown import
             commands::import::import_markdown_file,
             commands::import::import_markdown_files,
             commands::import::import_markdown_directory,
+            commands::import::import_files,
+            commands::import::import_csv_file,
+            // Subtree export (HTML / PDF / todo.txt)
+            commands::export::export_subtree_html,
+            commands::export::export_subtree_pdf,
+            commands::export::export_todo_txt,
+            commands::export::export_bibtex,
+            commands::export::create_share_bundle,
+            commands::export::list_shares,
+            commands::export::revoke_share,
             // Settings commands
             commands::settings::get_settings,
             commands::settings::update_display_settings,
             commands::settings::select_new_database,
             commands::settings::restart_app,
             commands::settings::reset_database_to_default,
+            commands::settings::set_vault_mirror_folder,
+            commands::settings::set_close_to_tray,
+            commands::settings::set_os_search_index,
+            commands::settings::set_task_rollover,
+            commands::settings::set_weekly_digest,
+            commands::settings::set_propagate_renames,
+            commands::settings::set_language,
+            commands::settings::set_read_only_mode,
+            // Quick capture and inbox triage
+            commands::inbox::quick_capture,
+            commands::inbox::get_inbox,
+            commands::inbox::process_inbox_item,
+            // Task due dates, time tracking and roll-over
+            commands::tasks::get_overdue_tasks,
+            commands::tasks::get_tasks_due_within,
+            commands::tasks::start_timer,
+            commands::tasks::stop_timer,
+            commands::tasks::get_tracked_time,
+            commands::tasks::roll_over_tasks,
+            commands::tasks::extract_action_items,
+            commands::goals::get_goal_progress,
+            commands::graph::get_graph_data,
+            commands::graph::get_central_notes,
+            commands::digest::generate_weekly_digest,
+            // Contact interaction log
+            commands::people::log_interaction,
+            commands::people::get_interaction_history,
+            commands::relationships::create_relationship,
+            commands::relationships::delete_relationship,
+            commands::relationships::get_relationships,
+            // Saved query result diffs
+            commands::queries::watch_query,
+            commands::queries::unwatch_query,
+            commands::queries::get_child_views,
+            commands::queries::save_child_view,
+            commands::queries::delete_child_view,
+            commands::queries::get_children_view,
+            // nodespace:// deep links
+            commands::deep_link::take_deep_links,
+            commands::deep_link::resolve_deep_link,
+            // Crash-safe autosave journal
+            commands::journal::journal_edit,
+            commands::journal::acknowledge_edit,
+            commands::journal::replay_edit_journal,
+            // Passphrase lock screen
+            commands::workspace_lock::get_lock_status,
+            commands::workspace_lock::lock_workspace,
+            commands::workspace_lock::unlock_workspace,
+            commands::workspace_lock::set_workspace_passphrase,
+            commands::workspace_lock::set_auto_lock_timeout,
+            commands::workspace_lock::record_workspace_activity,
         ])
         .build(tauri::generate_context!())
         .expect("error while building tauri application");
@@ -414,42 +1165,78 @@ pub fn run() {
     // 2. RunEvent::ExitRequested - when app is about to exit
     // 3. RunEvent::Exit - final cleanup before process termination
     //
-    // The shutdown_token is cancelled to signal background tasks (MCP server,
-    // domain event forwarder) to exit their loops before the Tokio runtime drops.
-    let shutdown_token_for_events = shutdown_token.clone();
+    // shutdown() cancels the token so background tasks (MCP server, domain event
+    // forwarder) exit their loops, then blocks until the flush hooks (embedding
+    // queue, vault mirror, edit journal) finish - all before the Tokio runtime drops.
+    let shutdown_for_events = shutdown.clone();
     app.run(move |app_handle, event| {
         match event {
             RunEvent::WindowEvent {
                 label,
-                event: tauri::WindowEvent::CloseRequested { .. },
+                event: tauri::WindowEvent::CloseRequested { api, .. },
                 ..
             } => {
+                // Background mode: hide to the tray and keep services running
+                #[cfg(desktop)]
+                if label == "main"
+                    && app_handle
+                        .try_state::<tray::TrayState>()
+                        .is_some_and(|state| state.hides_on_close())
+                {
+                    api.prevent_close();
+                    if let Some(window) = app_handle.get_webview_window("main") {
+                        let _ = window.hide();
+                    }
+                    return;
+                }
+                #[cfg(not(desktop))]
+                let _ = api;
+
                 // Window close requested - signal background tasks and release GPU resources
                 // This is the most reliable place to do cleanup on macOS
                 tracing::info!(
                     "Window '{}' close requested, signaling shutdown and releasing GPU context...",
                     label
                 );
-                shutdown_token_for_events.cancel();
+                shutdown_for_events.shutdown();
                 // Brief pause to let background tasks exit their loops before
                 // releasing GPU resources they may still reference
                 std::thread::sleep(std::time::Duration::from_millis(50));
                 release_gpu_resources(app_handle);
             }
+            // Search hits (index files) opened on macOS
+            #[cfg(any(target_os = "macos", target_os = "ios"))]
+            RunEvent::Opened { urls } => {
+                for path in urls.iter().filter_map(|url| url.to_file_path().ok()) {
+                    services::search_index::open_index_file(app_handle, &path);
+                }
+            }
+            // A focus shortly after a background notification is its click-through
+            RunEvent::WindowEvent {
+                label,
+                event: tauri::WindowEvent::Focused(true),
+                ..
+            } if label == "main" => notifications::on_main_window_focused(app_handle),
             RunEvent::ExitRequested { code, .. } => {
                 // App exit requested - this may not fire on macOS (Tauri issue #9198)
                 tracing::info!(
                     "App exit requested (code: {:?}), performing cleanup...",
                     code
                 );
-                shutdown_token_for_events.cancel();
+                shutdown_for_events.shutdown();
                 release_gpu_resources(app_handle);
                 tracing::info!("Cleanup complete, exiting...");
             }
+            // Dock icon clicked while the window is hidden in the tray
+            #[cfg(target_os = "macos")]
+            RunEvent::Reopen {
+                has_visible_windows: false,
+                ..
+            } => tray::show_main_window(app_handle),
             RunEvent::Exit => {
-                // Final exit - ensure shutdown signal is sent (idempotent)
+                // Final exit - ensure shutdown ran (idempotent)
                 tracing::info!("App exiting, ensuring shutdown signal sent...");
-                shutdown_token_for_events.cancel();
+                shutdown_for_events.shutdown();
             }
             _ => {}
         }
@@ -477,6 +1264,9 @@ fn release_gpu_resources(app_handle: &tauri::AppHandle) {
         tracing::info!("Releasing GPU context to prevent Metal crash...");
         // Step 1: Release the LlamaState (context + model) which holds Metal residency sets
         embedding_state.service.nlp_engine().release_gpu_context();
+        if let Some(summarizer) = embedding_state.service.summarizer() {
+            summarizer.release_gpu_context();
+        }
         tracing::info!("GPU context released successfully");
     }
 
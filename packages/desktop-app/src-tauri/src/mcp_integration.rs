@@ -10,6 +10,7 @@
 //! As of Issue #715, MCP server is a managed service (McpServerService).
 //! This module provides the Tauri event callback for UI reactivity.
 
+use nodespace_core::mcp::AgentProfile;
 use nodespace_core::services::{
     default_mcp_port, McpResponseCallback, McpServerService, NodeEmbeddingService,
 };
@@ -52,6 +53,7 @@ struct NodeDeletedEvent {
 /// # Arguments
 /// * `node_service` - Shared NodeService for node operations
 /// * `embedding_service` - Shared embedding service for semantic search
+/// * `agent_profiles` - Profiles scoping each MCP connection (empty = unrestricted)
 /// * `app` - Tauri AppHandle for event emission
 ///
 /// # Returns
@@ -60,6 +62,7 @@ struct NodeDeletedEvent {
 pub fn create_mcp_service_with_events(
     node_service: Arc<NodeService>,
     embedding_service: Arc<NodeEmbeddingService>,
+    agent_profiles: Vec<AgentProfile>,
     app: AppHandle,
 ) -> (McpServerService, McpResponseCallback) {
     let port = default_mcp_port();
@@ -69,7 +72,8 @@ pub fn create_mcp_service_with_events(
         emit_event_for_method(&app, method, result);
     });
 
-    let service = McpServerService::new(node_service, embedding_service, port);
+    let service = McpServerService::new(node_service, embedding_service, port)
+        .with_agent_profiles(agent_profiles);
 
     (service, callback)
 }
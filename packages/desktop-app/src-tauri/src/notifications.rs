@@ -0,0 +1,63 @@
+//! Native OS notifications
+//!
+//! Surfaces tasks coming due (core `ReminderService`), finished background
+//! imports, and nodes deleted through MCP.
+//!
+//! Desktop notifications do not report clicks back to the app, but clicking
+//! one activates it. So while the main window is unfocused, the deep link of
+//! the latest notification is remembered, and opened if the window gains focus
+//! within [`CLICK_THROUGH_WINDOW`].
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use tauri::{AppHandle, Manager};
+use tauri_plugin_notification::NotificationExt;
+
+use crate::commands::deep_link::handle_deep_links;
+
+/// How long after a notification a focus of the main window counts as a click
+pub const CLICK_THROUGH_WINDOW: Duration = Duration::from_secs(30);
+
+/// Deep link of the latest notification shown while the app was in the background
+#[derive(Default)]
+pub struct NotificationState(Mutex<Option<(String, Instant)>>);
+
+/// Whether the main window currently has focus
+pub fn main_window_focused(app: &AppHandle) -> bool {
+    app.get_webview_window("main")
+        .and_then(|window| window.is_focused().ok())
+        .unwrap_or(false)
+}
+
+/// Show an OS notification, optionally linked to a `nodespace://` URI
+pub fn notify(app: &AppHandle, title: &str, body: &str, link: Option<String>) {
+    if let Err(e) = app.notification().builder().title(title).body(body).show() {
+        tracing::warn!("Failed to show notification '{}': {}", title, e);
+        return;
+    }
+
+    if let (Some(link), Some(state)) = (link, app.try_state::<NotificationState>()) {
+        if !main_window_focused(app) {
+            *state.0.lock().unwrap_or_else(|e| e.into_inner()) = Some((link, Instant::now()));
+        }
+    }
+}
+
+/// Open the latest notification's link if it was clicked to focus the app
+pub fn on_main_window_focused(app: &AppHandle) {
+    let Some(state) = app.try_state::<NotificationState>() else {
+        return;
+    };
+    let pending = state.0.lock().unwrap_or_else(|e| e.into_inner()).take();
+    if let Some((link, shown_at)) = pending {
+        if shown_at.elapsed() <= CLICK_THROUGH_WINDOW {
+            handle_deep_links(app, vec![link]);
+        }
+    }
+}
+
+/// `nodespace://` link for a node
+pub fn node_link(node_id: &str) -> String {
+    format!("nodespace://node/{}", node_id)
+}
@@ -24,6 +24,54 @@ pub struct AppPreferences {
 
     #[serde(default)]
     pub import_sources: ImportSourcePreferences,
+
+    /// Folder that document roots are mirrored into as markdown (off when unset)
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vault_mirror_path: Option<PathBuf>,
+
+    /// Apply edits made to mirrored files back to the database
+    #[serde(default)]
+    pub vault_mirror_two_way: bool,
+
+    /// Hide the main window to the system tray on close, keeping background
+    /// services running
+    #[serde(default)]
+    pub close_to_tray: bool,
+
+    /// Export document titles and snippets to the OS search index
+    #[serde(default)]
+    pub os_search_index: bool,
+
+    /// Carry open tasks from past days to today each morning (off when unset)
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub task_rollover: Option<nodespace_core::services::RolloverMode>,
+
+    /// File a review of the past week under its week node every Monday
+    #[serde(default)]
+    pub weekly_digest: bool,
+
+    /// Update the text of mention links when the mentioned node is renamed
+    #[serde(default)]
+    pub propagate_renames: bool,
+
+    /// Token-selected profiles limiting what MCP agents may read and write
+    /// (unrestricted when empty; applied on restart)
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub mcp_agent_profiles: Vec<nodespace_core::mcp::AgentProfile>,
+
+    /// Lock the workspace after this many idle minutes (off when unset;
+    /// only applies once a passphrase is set)
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auto_lock_minutes: Option<u32>,
+
+    /// Open the workspace read-only: nodes can be browsed, searched and
+    /// exported but not changed
+    #[serde(default)]
+    pub read_only: bool,
 }
 
 /// Display-related user preferences
@@ -37,6 +85,11 @@ pub struct DisplayPreferences {
     /// Color theme: "system", "light", or "dark" (default: "system")
     #[serde(default = "default_theme")]
     pub theme: String,
+
+    /// BCP 47 tag of the language for error messages (English when unset)
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
 }
 
 impl Default for DisplayPreferences {
@@ -44,6 +97,7 @@ impl Default for DisplayPreferences {
         Self {
             render_markdown: false,
             theme: default_theme(),
+            language: None,
         }
     }
 }
@@ -1,3 +1,7 @@
 pub mod domain_event_forwarder;
+pub mod search_index;
+pub mod supervisor;
 
 pub use domain_event_forwarder::DomainEventForwarder;
+pub use search_index::SearchIndexService;
+pub use supervisor::{BackgroundTaskStatus, TaskSupervisor};
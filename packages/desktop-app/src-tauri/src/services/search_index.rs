@@ -0,0 +1,390 @@
+//! OS search index export (Spotlight, Windows Search)
+//!
+//! Each document root is written as a small `.nodespace` file into a folder
+//! the OS indexer crawls. The file name carries the title and the body holds
+//! the deep link, title and a snippet of the first children:
+//!
+//! ```text
+//! nodespace://node/<root-id>
+//! Quarterly planning
+//!
+//! Goals for Q3 · Hiring plan · ...
+//! ```
+//!
+//! The bundle registers NodeSpace as the opener for `.nodespace` files
+//! (declared as plain text, so Spotlight indexes the body). Opening a search
+//! hit hands the file to the app, which follows its link via
+//! [`open_index_file`]. Files are kept current from domain events.
+
+use anyhow::Result;
+use nodespace_core::db::DomainEvent;
+use nodespace_core::utils::truncate_chars;
+use nodespace_core::{Node, NodeService};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::AppHandle;
+use tokio::sync::broadcast;
+use tracing::{debug, info, warn};
+
+/// Extension of index files (registered as a file association in tauri.conf.json)
+pub const INDEX_FILE_EXTENSION: &str = "nodespace";
+
+/// Root types that are not documents and stay out of the OS index
+const EXCLUDED_ROOT_TYPES: &[&str] = &["schema", "collection", "query", "script"];
+
+/// Maximum snippet length in characters
+const SNIPPET_LEN: usize = 280;
+
+/// Maximum title length used in file names
+const MAX_FILE_TITLE_LEN: usize = 60;
+
+/// Quiet period before queued changes are written
+const DEBOUNCE: Duration = Duration::from_secs(2);
+
+/// Keeps a folder of `.nodespace` files in step with the document roots
+pub struct SearchIndexService {
+    node_service: Arc<NodeService>,
+    folder: PathBuf,
+    /// Root ID -> index file name
+    files: HashMap<String, String>,
+}
+
+impl SearchIndexService {
+    pub fn new(node_service: Arc<NodeService>, folder: PathBuf) -> Self {
+        Self {
+            node_service,
+            folder,
+            files: HashMap::new(),
+        }
+    }
+
+    /// Index every root, then follow domain events until the channel closes
+    ///
+    /// Callers typically spawn this and cancel it on shutdown.
+    pub async fn run(mut self) {
+        let mut rx = self.node_service.subscribe_to_events();
+        info!("Search index export into {:?}", self.folder);
+
+        let mut full_resync = true;
+        let mut dirty: HashSet<String> = HashSet::new();
+        let mut deleted: HashSet<String> = HashSet::new();
+        loop {
+            if full_resync {
+                dirty.clear();
+                deleted.clear();
+                full_resync = false;
+                if let Err(e) = self.sync_all().await {
+                    warn!("Search index sync failed: {}", e);
+                }
+            }
+
+            let pending = !dirty.is_empty() || !deleted.is_empty();
+            tokio::select! {
+                received = rx.recv() => match received {
+                    Ok(event) => collect(event, &mut dirty, &mut deleted),
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        debug!("Search index lagged {} events, resyncing", skipped);
+                        full_resync = true;
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                },
+                _ = tokio::time::sleep(DEBOUNCE), if pending => {
+                    self.flush(std::mem::take(&mut dirty), std::mem::take(&mut deleted))
+                        .await;
+                }
+            }
+        }
+    }
+
+    /// Rewrite the index from scratch, dropping files of vanished roots
+    async fn sync_all(&mut self) -> Result<()> {
+        tokio::fs::create_dir_all(&self.folder).await?;
+        self.files = read_existing_files(&self.folder).await;
+
+        let roots = self
+            .node_service
+            .store()
+            .get_children(None)
+            .await
+            .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+        let live: HashSet<&str> = roots
+            .iter()
+            .filter(|root| is_indexed_type(&root.node_type))
+            .map(|root| root.id.as_str())
+            .collect();
+
+        let stale: Vec<String> = self
+            .files
+            .keys()
+            .filter(|id| !live.contains(id.as_str()))
+            .cloned()
+            .collect();
+        for root_id in stale {
+            self.remove(&root_id).await;
+        }
+        for root in roots {
+            if is_indexed_type(&root.node_type) {
+                self.write(root).await?;
+            }
+        }
+        info!("Search index holds {} documents", self.files.len());
+        Ok(())
+    }
+
+    /// Re-index the roots containing `dirty` nodes and drop `deleted` roots
+    async fn flush(&mut self, dirty: HashSet<String>, deleted: HashSet<String>) {
+        for id in &deleted {
+            self.remove(id).await;
+        }
+
+        let mut roots = HashSet::new();
+        for id in dirty.difference(&deleted) {
+            match self.node_service.get_root_id(id).await {
+                Ok(root_id) => {
+                    roots.insert(root_id);
+                }
+                Err(e) => debug!("Skipping search index update for {}: {}", id, e),
+            }
+        }
+        for root_id in roots {
+            let result = match self.node_service.get_node(&root_id).await {
+                Ok(Some(root)) if is_indexed_type(&root.node_type) => self.write(root).await,
+                Ok(_) => {
+                    self.remove(&root_id).await;
+                    Ok(())
+                }
+                Err(e) => Err(e.into()),
+            };
+            if let Err(e) = result {
+                warn!("Failed to index {}: {}", root_id, e);
+            }
+        }
+    }
+
+    async fn write(&mut self, root: Node) -> Result<()> {
+        let children = self.node_service.get_children(&root.id).await?;
+        let file_name = index_file_name(&root);
+        let content = render_index_file(&root, &children);
+
+        if let Some(previous) = self.files.get(&root.id).filter(|f| **f != file_name) {
+            let _ = tokio::fs::remove_file(self.folder.join(previous)).await;
+        }
+        let path = self.folder.join(&file_name);
+        if tokio::fs::read_to_string(&path).await.ok().as_deref() != Some(content.as_str()) {
+            tokio::fs::write(&path, content).await?;
+        }
+        self.files.insert(root.id, file_name);
+        Ok(())
+    }
+
+    async fn remove(&mut self, root_id: &str) {
+        if let Some(file_name) = self.files.remove(root_id) {
+            let _ = tokio::fs::remove_file(self.folder.join(file_name)).await;
+        }
+    }
+}
+
+/// Follow the link in an opened index file
+///
+/// Returns false when `path` is not a readable index file.
+pub fn open_index_file(app: &AppHandle, path: &Path) -> bool {
+    if path.extension().and_then(|e| e.to_str()) != Some(INDEX_FILE_EXTENSION) {
+        return false;
+    }
+    let link = std::fs::read_to_string(path)
+        .ok()
+        .and_then(|content| parse_index_link(&content).map(str::to_string));
+    match link {
+        Some(link) => {
+            crate::commands::deep_link::handle_deep_links(app, vec![link]);
+            true
+        }
+        None => {
+            warn!("Not a NodeSpace index file: {:?}", path);
+            false
+        }
+    }
+}
+
+fn collect(event: DomainEvent, dirty: &mut HashSet<String>, deleted: &mut HashSet<String>) {
+    match event {
+        DomainEvent::NodeCreated { node_id, .. } | DomainEvent::NodeUpdated { node_id, .. } => {
+            dirty.insert(node_id);
+        }
+        DomainEvent::NodeDeleted { id, .. } => {
+            deleted.insert(id);
+        }
+        DomainEvent::RelationshipCreated { relationship, .. }
+        | DomainEvent::RelationshipUpdated { relationship, .. } => {
+            if relationship.relationship_type == "has_child" {
+                dirty.insert(relationship.from_id);
+                dirty.insert(relationship.to_id);
+            }
+        }
+        DomainEvent::RelationshipDeleted {
+            from_id,
+            to_id,
+            relationship_type,
+            ..
+        } => {
+            if relationship_type == "has_child" {
+                dirty.insert(from_id);
+                dirty.insert(to_id);
+            }
+        }
+    }
+}
+
+fn is_indexed_type(node_type: &str) -> bool {
+    !EXCLUDED_ROOT_TYPES.contains(&node_type)
+}
+
+fn root_title(root: &Node) -> &str {
+    root.content
+        .lines()
+        .next()
+        .unwrap_or("")
+        .trim_start_matches('#')
+        .trim()
+}
+
+/// `<Title> (<id prefix>).nodespace`, readable in search results
+fn index_file_name(root: &Node) -> String {
+    let title: String = root_title(root)
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || " -_.,'&".contains(c) {
+                c
+            } else {
+                ' '
+            }
+        })
+        .take(MAX_FILE_TITLE_LEN)
+        .collect();
+    let title = title.split_whitespace().collect::<Vec<_>>().join(" ");
+    let title = title.trim_start_matches('.');
+    let id_prefix: String = root
+        .id
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .take(8)
+        .collect();
+
+    if title.is_empty() {
+        format!("Untitled ({}).{}", id_prefix, INDEX_FILE_EXTENSION)
+    } else if title == root.id {
+        format!("{}.{}", title, INDEX_FILE_EXTENSION)
+    } else {
+        format!("{} ({}).{}", title, id_prefix, INDEX_FILE_EXTENSION)
+    }
+}
+
+fn render_index_file(root: &Node, children: &[Node]) -> String {
+    let mut snippet = String::new();
+    for line in children
+        .iter()
+        .flat_map(|child| child.content.lines())
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+    {
+        if !snippet.is_empty() {
+            snippet.push_str(" · ");
+        }
+        snippet.push_str(line);
+        if snippet.chars().count() >= SNIPPET_LEN {
+            snippet = truncate_chars(&snippet, SNIPPET_LEN);
+            break;
+        }
+    }
+    format!(
+        "nodespace://node/{}\n{}\n\n{}\n",
+        root.id,
+        root_title(root),
+        snippet
+    )
+}
+
+fn parse_index_link(content: &str) -> Option<&str> {
+    content
+        .lines()
+        .next()
+        .map(str::trim)
+        .filter(|line| line.starts_with("nodespace://"))
+}
+
+/// Root ID -> file name of index files already on disk
+async fn read_existing_files(folder: &Path) -> HashMap<String, String> {
+    let mut files = HashMap::new();
+    let Ok(mut entries) = tokio::fs::read_dir(folder).await else {
+        return files;
+    };
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        let path = entry.path();
+        if path.extension().and_then(|e| e.to_str()) != Some(INDEX_FILE_EXTENSION) {
+            continue;
+        }
+        let Ok(content) = tokio::fs::read_to_string(&path).await else {
+            continue;
+        };
+        let root_id = parse_index_link(&content)
+            .and_then(|link| link.strip_prefix("nodespace://node/"))
+            .map(str::to_string);
+        match (root_id, path.file_name().and_then(|n| n.to_str())) {
+            (Some(root_id), Some(name)) => {
+                files.insert(root_id, name.to_string());
+            }
+            // Unreadable leftovers are not ours to keep around
+            _ => {
+                let _ = tokio::fs::remove_file(&path).await;
+            }
+        }
+    }
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn node(id: &str, content: &str) -> Node {
+        Node::new_with_id(
+            id.to_string(),
+            "text".to_string(),
+            content.to_string(),
+            json!({}),
+        )
+    }
+
+    #[test]
+    fn test_index_file_names() {
+        assert_eq!(
+            index_file_name(&node("a1b2c3d4-e5f6", "# Q3: Planning / Review")),
+            "Q3 Planning Review (a1b2c3d4).nodespace"
+        );
+        assert_eq!(
+            index_file_name(&node("2025-01-15", "2025-01-15")),
+            "2025-01-15.nodespace"
+        );
+        assert_eq!(
+            index_file_name(&node("ffff0000", "")),
+            "Untitled (ffff0000).nodespace"
+        );
+    }
+
+    #[test]
+    fn test_index_file_round_trips_link() {
+        let root = node("root-1", "Reading list\nmore");
+        let children = vec![node("c1", "Dune"), node("c2", "\nFoundation\n")];
+        let content = render_index_file(&root, &children);
+
+        assert_eq!(
+            content,
+            "nodespace://node/root-1\nReading list\n\nDune · Foundation\n"
+        );
+        assert_eq!(parse_index_link(&content), Some("nodespace://node/root-1"));
+        assert_eq!(parse_index_link("hello"), None);
+    }
+}
@@ -0,0 +1,263 @@
+//! Background task supervisor with automatic restart
+//!
+//! Long-running services (MCP server, domain event forwarder) used to log a
+//! crash and stay down until the app restarted. [`TaskSupervisor::spawn`]
+//! runs such a task in a loop instead: when it returns an error or panics it
+//! is started again after an exponential backoff, until it exits cleanly or
+//! its cancellation token fires.
+//!
+//! Every state change is recorded in a [`BackgroundTaskStatus`] and passed
+//! to the supervisor's `on_change` callback, which the app uses to emit
+//! `background-task-status` events.
+
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use futures::FutureExt;
+use serde::Serialize;
+use tokio_util::sync::CancellationToken;
+
+/// Event carrying a [`BackgroundTaskStatus`] whenever a task changes state
+pub const BACKGROUND_TASK_STATUS_EVENT: &str = "background-task-status";
+
+/// Delay before the first restart
+pub const DEFAULT_INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+
+/// Upper bound for the restart delay; a task that stayed up this long starts
+/// over from the initial delay when it next fails
+pub const DEFAULT_MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskState {
+    Running,
+    /// Crashed, waiting for the backoff before starting again
+    Restarting,
+    /// Exited cleanly or was cancelled
+    Stopped,
+}
+
+/// Health of one supervised task, as shown to the UI
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackgroundTaskStatus {
+    pub name: String,
+    pub state: TaskState,
+    /// Restarts since the app started
+    pub restarts: u32,
+    /// Error or panic message of the most recent crash
+    pub last_error: Option<String>,
+    /// When the task entered its current state
+    pub since: DateTime<Utc>,
+}
+
+type StatusCallback = Arc<dyn Fn(&BackgroundTaskStatus) + Send + Sync>;
+
+/// Restarts crashed background tasks and tracks their health
+///
+/// Managed as Tauri state; clones share the status table.
+#[derive(Clone)]
+pub struct TaskSupervisor {
+    statuses: Arc<Mutex<BTreeMap<String, BackgroundTaskStatus>>>,
+    on_change: StatusCallback,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+}
+
+impl TaskSupervisor {
+    pub fn new(on_change: impl Fn(&BackgroundTaskStatus) + Send + Sync + 'static) -> Self {
+        Self {
+            statuses: Arc::new(Mutex::new(BTreeMap::new())),
+            on_change: Arc::new(on_change),
+            initial_backoff: DEFAULT_INITIAL_BACKOFF,
+            max_backoff: DEFAULT_MAX_BACKOFF,
+        }
+    }
+
+    pub fn with_backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.initial_backoff = initial;
+        self.max_backoff = max;
+        self
+    }
+
+    /// Current status of every task spawned so far, by name
+    pub fn statuses(&self) -> Vec<BackgroundTaskStatus> {
+        self.table().values().cloned().collect()
+    }
+
+    /// Run `make_task` under supervision until it succeeds or `cancel_token` fires
+    ///
+    /// `make_task` is called again for every restart, so it must build the
+    /// task from scratch.
+    pub fn spawn<F, Fut>(
+        &self,
+        name: &str,
+        cancel_token: CancellationToken,
+        make_task: F,
+    ) -> tauri::async_runtime::JoinHandle<()>
+    where
+        F: Fn() -> Fut + Send + 'static,
+        Fut: Future<Output = anyhow::Result<()>> + Send + 'static,
+    {
+        let supervisor = self.clone();
+        let name = name.to_string();
+        tauri::async_runtime::spawn(async move {
+            let mut backoff = supervisor.initial_backoff;
+            loop {
+                supervisor.update(&name, TaskState::Running, None);
+                let started = Instant::now();
+
+                // AssertUnwindSafe is needed because services contain non-UnwindSafe types
+                let outcome = tokio::select! {
+                    result = std::panic::AssertUnwindSafe(make_task()).catch_unwind() => result,
+                    _ = cancel_token.cancelled() => {
+                        tracing::info!("{} received shutdown signal", name);
+                        supervisor.update(&name, TaskState::Stopped, None);
+                        return;
+                    }
+                };
+                let error = match outcome {
+                    Ok(Ok(())) => {
+                        tracing::info!("✅ {} exited normally", name);
+                        supervisor.update(&name, TaskState::Stopped, None);
+                        return;
+                    }
+                    Ok(Err(e)) => format!("{:#}", e),
+                    Err(panic) => panic_message(panic.as_ref()),
+                };
+
+                if started.elapsed() >= supervisor.max_backoff {
+                    backoff = supervisor.initial_backoff;
+                }
+                tracing::error!(
+                    "💥 {} crashed, restarting in {:?}: {}",
+                    name,
+                    backoff,
+                    error
+                );
+                supervisor.update(&name, TaskState::Restarting, Some(error));
+
+                tokio::select! {
+                    _ = tokio::time::sleep(backoff) => {}
+                    _ = cancel_token.cancelled() => {
+                        supervisor.update(&name, TaskState::Stopped, None);
+                        return;
+                    }
+                }
+                backoff = (backoff * 2).min(supervisor.max_backoff);
+            }
+        })
+    }
+
+    fn update(&self, name: &str, state: TaskState, error: Option<String>) {
+        let status = {
+            let mut table = self.table();
+            let status = table
+                .entry(name.to_string())
+                .or_insert_with(|| BackgroundTaskStatus {
+                    name: name.to_string(),
+                    state,
+                    restarts: 0,
+                    last_error: None,
+                    since: Utc::now(),
+                });
+            if status.state == TaskState::Restarting && state == TaskState::Running {
+                status.restarts += 1;
+            }
+            if error.is_some() {
+                status.last_error = error;
+            }
+            status.state = state;
+            status.since = Utc::now();
+            status.clone()
+        };
+        (self.on_change)(&status);
+    }
+
+    fn table(&self) -> std::sync::MutexGuard<'_, BTreeMap<String, BackgroundTaskStatus>> {
+        self.statuses.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+fn panic_message(panic: &(dyn std::any::Any + Send)) -> String {
+    if let Some(message) = panic.downcast_ref::<&str>() {
+        format!("panicked: {}", message)
+    } else if let Some(message) = panic.downcast_ref::<String>() {
+        format!("panicked: {}", message)
+    } else {
+        "panicked".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    #[tokio::test]
+    async fn test_crashed_task_is_restarted_until_it_succeeds() {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let recorded = events.clone();
+        let supervisor = TaskSupervisor::new(move |status| {
+            recorded.lock().unwrap().push(status.state);
+        })
+        .with_backoff(Duration::from_millis(1), Duration::from_millis(10));
+
+        let attempts = Arc::new(AtomicU32::new(0));
+        let counter = attempts.clone();
+        supervisor
+            .spawn("flaky", CancellationToken::new(), move || {
+                let attempt = counter.fetch_add(1, Ordering::SeqCst);
+                async move {
+                    match attempt {
+                        0 => anyhow::bail!("connection lost"),
+                        1 => panic!("boom"),
+                        _ => Ok(()),
+                    }
+                }
+            })
+            .await
+            .unwrap();
+
+        let status = &supervisor.statuses()[0];
+        assert_eq!(status.state, TaskState::Stopped);
+        assert_eq!(status.restarts, 2);
+        assert_eq!(status.last_error.as_deref(), Some("panicked: boom"));
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+        assert_eq!(
+            *events.lock().unwrap(),
+            vec![
+                TaskState::Running,
+                TaskState::Restarting,
+                TaskState::Running,
+                TaskState::Restarting,
+                TaskState::Running,
+                TaskState::Stopped,
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_cancel_stops_restarts() {
+        let supervisor = TaskSupervisor::new(|_| {})
+            .with_backoff(Duration::from_secs(3600), Duration::from_secs(3600));
+        let cancel_token = CancellationToken::new();
+
+        let handle = supervisor.spawn("failing", cancel_token.clone(), || async {
+            anyhow::bail!("always fails")
+        });
+        while !matches!(
+            supervisor.statuses().first(),
+            Some(status) if status.state == TaskState::Restarting
+        ) {
+            tokio::task::yield_now().await;
+        }
+        cancel_token.cancel();
+        handle.await.unwrap();
+
+        assert_eq!(supervisor.statuses()[0].state, TaskState::Stopped);
+    }
+}
@@ -0,0 +1,114 @@
+//! Shutdown coordination for background services
+//!
+//! Background tasks get a child of one cancellation token and stop when it
+//! is cancelled. Work that must not be cut short (draining the embedding
+//! queue, writing pending mirror files, syncing the edit journal) registers
+//! a flush hook instead. [`ShutdownCoordinator::shutdown`] cancels the token
+//! and then runs the hooks to completion, so the exit path only returns once
+//! they are done or have timed out.
+
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use futures::future::BoxFuture;
+use futures::FutureExt;
+use tokio_util::sync::CancellationToken;
+
+/// Longest a single flush hook may hold up exit
+pub const FLUSH_TIMEOUT: Duration = Duration::from_secs(5);
+
+struct FlushHook {
+    name: &'static str,
+    flush: BoxFuture<'static, ()>,
+}
+
+/// Cancellation token plus flush hooks, managed as Tauri state
+///
+/// Clones share the token and hooks.
+#[derive(Clone, Default)]
+pub struct ShutdownCoordinator {
+    token: CancellationToken,
+    hooks: Arc<Mutex<Vec<FlushHook>>>,
+}
+
+impl ShutdownCoordinator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a child token for a background task.
+    /// Cancelling the parent automatically cancels all children.
+    pub fn child_token(&self) -> CancellationToken {
+        self.token.child_token()
+    }
+
+    /// Run `flush` on shutdown, after the token is cancelled
+    ///
+    /// Hooks run one at a time in the order they were added.
+    pub fn add_flush_hook(
+        &self,
+        name: &'static str,
+        flush: impl Future<Output = ()> + Send + 'static,
+    ) {
+        let hook = FlushHook {
+            name,
+            flush: flush.boxed(),
+        };
+        self.hooks
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(hook);
+    }
+
+    /// Signal background tasks to stop, then block until the flush hooks finish
+    ///
+    /// Must be called outside the async runtime (the Tauri event loop).
+    /// Idempotent - hooks only run on the first call.
+    pub fn shutdown(&self) {
+        self.token.cancel();
+
+        let hooks = std::mem::take(&mut *self.hooks.lock().unwrap_or_else(|e| e.into_inner()));
+        if hooks.is_empty() {
+            return;
+        }
+        tauri::async_runtime::block_on(async {
+            for hook in hooks {
+                tracing::info!("Flushing {} before exit...", hook.name);
+                if tokio::time::timeout(FLUSH_TIMEOUT, hook.flush)
+                    .await
+                    .is_err()
+                {
+                    tracing::warn!("Flushing {} timed out after {:?}", hook.name, FLUSH_TIMEOUT);
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shutdown_runs_hooks_once_in_order() {
+        let coordinator = ShutdownCoordinator::new();
+        let child = coordinator.child_token();
+        let ran = Arc::new(Mutex::new(Vec::new()));
+
+        for name in ["first", "second"] {
+            let ran = ran.clone();
+            let token = child.clone();
+            coordinator.add_flush_hook(name, async move {
+                // Hooks only start once background tasks were told to stop
+                assert!(token.is_cancelled());
+                ran.lock().unwrap().push(name);
+            });
+        }
+
+        coordinator.clone().shutdown();
+        coordinator.shutdown();
+        assert!(child.is_cancelled());
+        assert_eq!(*ran.lock().unwrap(), vec!["first", "second"]);
+    }
+}
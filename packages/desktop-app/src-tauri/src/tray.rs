@@ -0,0 +1,186 @@
+//! System tray icon and background mode
+//!
+//! The tray menu offers quick capture, today's date node, a status line, an
+//! embeddings pause toggle and Quit. With the `close_to_tray` preference on,
+//! closing the main window only hides it: the MCP server, embedding processor
+//! and other background tasks keep running until Quit is chosen.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use nodespace_core::services::{EmbeddingProcessor, McpServerService};
+use tauri::menu::{CheckMenuItem, Menu, MenuEvent, MenuItem, PredefinedMenuItem};
+use tauri::tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent};
+use tauri::{AppHandle, Manager, Wry};
+
+use crate::commands;
+
+const TRAY_ID: &str = "main";
+const QUICK_CAPTURE_ID: &str = "tray_quick_capture";
+const TODAY_ID: &str = "tray_today";
+const SHOW_ID: &str = "tray_show";
+const PAUSE_EMBEDDINGS_ID: &str = "tray_pause_embeddings";
+const QUIT_ID: &str = "tray_quit";
+
+/// Background-mode flags, consulted when the main window is closed
+#[derive(Default)]
+pub struct TrayState {
+    close_to_tray: AtomicBool,
+    quitting: AtomicBool,
+}
+
+impl TrayState {
+    pub fn set_close_to_tray(&self, enabled: bool) {
+        self.close_to_tray.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Mark that the app is quitting for real, so closing the window exits
+    pub fn request_quit(&self) {
+        self.quitting.store(true, Ordering::Relaxed);
+    }
+
+    /// Whether a close of the main window should hide it instead
+    pub fn hides_on_close(&self) -> bool {
+        self.close_to_tray.load(Ordering::Relaxed) && !self.quitting.load(Ordering::Relaxed)
+    }
+}
+
+/// Menu items whose text or state changes at runtime
+struct TrayItems {
+    status: MenuItem<Wry>,
+    pause_embeddings: CheckMenuItem<Wry>,
+}
+
+/// Create the tray icon
+///
+/// [`TrayState`] must already be managed; it is read by the quit handlers.
+pub fn init(app: &AppHandle) -> tauri::Result<()> {
+    let quick_capture =
+        MenuItem::with_id(app, QUICK_CAPTURE_ID, "Quick Capture", true, None::<&str>)?;
+    let today = MenuItem::with_id(app, TODAY_ID, "Today's Note", true, None::<&str>)?;
+    let show = MenuItem::with_id(app, SHOW_ID, "Show NodeSpace", true, None::<&str>)?;
+    let status = MenuItem::with_id(app, "tray_status", "Starting…", false, None::<&str>)?;
+    let pause_embeddings = CheckMenuItem::with_id(
+        app,
+        PAUSE_EMBEDDINGS_ID,
+        "Pause Embeddings",
+        false,
+        false,
+        None::<&str>,
+    )?;
+    let quit = MenuItem::with_id(app, QUIT_ID, "Quit NodeSpace", true, None::<&str>)?;
+
+    let menu = Menu::with_items(
+        app,
+        &[
+            &quick_capture,
+            &today,
+            &show,
+            &PredefinedMenuItem::separator(app)?,
+            &status,
+            &pause_embeddings,
+            &PredefinedMenuItem::separator(app)?,
+            &quit,
+        ],
+    )?;
+
+    let mut builder = TrayIconBuilder::with_id(TRAY_ID)
+        .tooltip("NodeSpace")
+        .menu(&menu)
+        .show_menu_on_left_click(false)
+        .on_menu_event(handle_menu_event)
+        .on_tray_icon_event(|tray, event| {
+            if let TrayIconEvent::Click {
+                button: MouseButton::Left,
+                button_state: MouseButtonState::Up,
+                ..
+            } = event
+            {
+                show_main_window(tray.app_handle());
+            }
+        });
+    if let Some(icon) = app.default_window_icon() {
+        builder = builder.icon(icon.clone());
+    }
+    builder.build(app)?;
+
+    app.manage(TrayItems {
+        status,
+        pause_embeddings,
+    });
+    refresh_status(app);
+
+    let handle = app.clone();
+    tauri::async_runtime::spawn(async move {
+        match crate::preferences::load_preferences(&handle).await {
+            Ok(prefs) => handle
+                .state::<TrayState>()
+                .set_close_to_tray(prefs.close_to_tray),
+            Err(e) => tracing::warn!("Close to tray not configured: {}", e),
+        }
+    });
+
+    Ok(())
+}
+
+/// Update the status line and pause toggle from the running services
+///
+/// Called once services are initialized and whenever the pause toggle changes.
+pub fn refresh_status(app: &AppHandle) {
+    let Some(items) = app.try_state::<TrayItems>() else {
+        return;
+    };
+
+    let processor = app.try_state::<Arc<EmbeddingProcessor>>();
+    let status = match (&processor, app.try_state::<McpServerService>()) {
+        (None, _) => "Database not open".to_string(),
+        (Some(processor), mcp) => {
+            let embeddings = if processor.is_paused() {
+                "Embeddings paused"
+            } else {
+                "Embeddings active"
+            };
+            match mcp {
+                Some(mcp) => format!("MCP on port {} · {}", mcp.port(), embeddings),
+                None => format!("MCP stopped · {}", embeddings),
+            }
+        }
+    };
+
+    let _ = items.status.set_text(status);
+    let _ = items.pause_embeddings.set_enabled(processor.is_some());
+    let _ = items
+        .pause_embeddings
+        .set_checked(processor.is_some_and(|p| p.is_paused()));
+}
+
+/// Show, unminimize and focus the main window
+pub fn show_main_window(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.unminimize();
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+}
+
+fn handle_menu_event(app: &AppHandle, event: MenuEvent) {
+    match event.id().as_ref() {
+        QUICK_CAPTURE_ID => commands::inbox::show_quick_capture_window(app),
+        // Reuses the deep link path: creates the date node and navigates to it
+        TODAY_ID => {
+            commands::deep_link::handle_deep_links(app, vec!["nodespace://date/today".into()])
+        }
+        SHOW_ID => show_main_window(app),
+        PAUSE_EMBEDDINGS_ID => {
+            if let Some(processor) = app.try_state::<Arc<EmbeddingProcessor>>() {
+                processor.set_paused(!processor.is_paused());
+            }
+            refresh_status(app);
+        }
+        QUIT_ID => {
+            app.state::<TrayState>().request_quit();
+            app.exit(0);
+        }
+        _ => {}
+    }
+}
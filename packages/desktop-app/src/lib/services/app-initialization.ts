@@ -138,7 +138,12 @@ export async function initializeApp(): Promise<void> {
       statusBar.success(`database: ${dbPath}`);
     } catch (error: unknown) {
       // Check if already initialized (this is expected on subsequent calls)
-      const errorMsg = error instanceof Error ? error.message : String(error);
+      // Rust returns { message, health, actions }; health is set when the
+      // database itself needs recovery (locked, corrupt, missing schemas)
+      const errorMsg =
+        error instanceof Error
+          ? error.message
+          : ((error as { message?: string } | null)?.message ?? String(error));
       if (errorMsg.includes('already initialized')) {
         log.debug('Database already initialized');
       } else {
@@ -62,12 +62,18 @@ export interface EdgeRecord {
   order: number;
 }
 
+/** Mirrors `nodespace_core::api::QueryRequest` */
 export interface NodeQuery {
   id?: string;
   mentionedBy?: string;
   contentContains?: string;
   nodeType?: string;
+  /** Collection ID to restrict results to */
+  collectionId?: string;
+  /** Collection path (e.g. "hr:policy"); takes precedence over collectionId */
+  collection?: string;
   limit?: number;
+  offset?: number;
 }
 
 export interface CreateContainerInput {
@@ -225,77 +225,103 @@ class MockCollectionService implements CollectionServiceInterface {
 const DEV_PROXY_URL = 'http://localhost:3001';
 
 class HttpCollectionService implements CollectionServiceInterface {
-  async getAllCollections(): Promise<CollectionInfo[]> {
-    log.debug('Fetching all collections via HTTP');
-    const response = await fetch(`${DEV_PROXY_URL}/api/collections`);
+  private async request<T>(path: string, init?: RequestInit): Promise<T> {
+    const response = await fetch(`${DEV_PROXY_URL}${path}`, {
+      ...init,
+      headers: init?.body ? { 'Content-Type': 'application/json' } : undefined
+    });
     if (!response.ok) {
-      throw new Error(`Failed to fetch collections: ${response.statusText}`);
+      const error = await response.json().catch(() => null);
+      throw new Error(error?.message || `HTTP ${response.status}: ${response.statusText}`);
+    }
+    if (response.status === 204) {
+      return undefined as T;
     }
     return response.json();
   }
 
+  async getAllCollections(): Promise<CollectionInfo[]> {
+    log.debug('Fetching all collections via HTTP');
+    return this.request<CollectionInfo[]>('/api/collections');
+  }
+
   async getCollectionMembers(collectionId: string): Promise<Node[]> {
     log.debug('Fetching collection members via HTTP', { collectionId });
-    const response = await fetch(`${DEV_PROXY_URL}/api/collections/${encodeURIComponent(collectionId)}/members`);
-    if (!response.ok) {
-      throw new Error(`Failed to fetch collection members: ${response.statusText}`);
-    }
-    return response.json();
+    return this.request<Node[]>(`/api/collections/${encodeURIComponent(collectionId)}/members`);
   }
 
   async getCollectionMembersRecursive(collectionId: string): Promise<Node[]> {
-    // For now, just return direct members (recursive not implemented in dev-proxy)
-    return this.getCollectionMembers(collectionId);
+    log.debug('Fetching recursive collection members via HTTP', { collectionId });
+    return this.request<Node[]>(
+      `/api/collections/${encodeURIComponent(collectionId)}/members/recursive`
+    );
   }
 
-  async getNodeCollections(_nodeId: string): Promise<string[]> {
-    // Not implemented in dev-proxy yet
-    return [];
+  async getNodeCollections(nodeId: string): Promise<string[]> {
+    log.debug('Fetching node collections via HTTP', { nodeId });
+    return this.request<string[]>(`/api/nodes/${encodeURIComponent(nodeId)}/collections`);
   }
 
-  async findCollectionByPath(_path: string): Promise<CollectionNode | null> {
-    // Not implemented in dev-proxy yet
-    return null;
+  async findCollectionByPath(path: string): Promise<CollectionNode | null> {
+    log.debug('Finding collection by path via HTTP', { path });
+    return this.request<CollectionNode | null>(
+      `/api/collections/by-path?path=${encodeURIComponent(path)}`
+    );
   }
 
-  async getCollectionByName(_name: string): Promise<CollectionNode | null> {
-    // Not implemented in dev-proxy yet
-    return null;
+  async getCollectionByName(name: string): Promise<CollectionNode | null> {
+    log.debug('Getting collection by name via HTTP', { name });
+    return this.request<CollectionNode | null>(
+      `/api/collections/by-name?name=${encodeURIComponent(name)}`
+    );
   }
 
-  async addNodeToCollection(_nodeId: string, _collectionId: string): Promise<void> {
-    // Not implemented in dev-proxy yet
+  async addNodeToCollection(nodeId: string, collectionId: string): Promise<void> {
+    log.debug('Adding node to collection via HTTP', { nodeId, collectionId });
+    return this.request<void>(`/api/collections/${encodeURIComponent(collectionId)}/members`, {
+      method: 'POST',
+      body: JSON.stringify({ nodeId })
+    });
   }
 
-  async addNodeToCollectionPath(_nodeId: string, _path: string): Promise<string> {
-    // Not implemented in dev-proxy yet
-    return '';
+  async addNodeToCollectionPath(nodeId: string, path: string): Promise<string> {
+    log.debug('Adding node to collection path via HTTP', { nodeId, path });
+    return this.request<string>(`/api/nodes/${encodeURIComponent(nodeId)}/collections`, {
+      method: 'POST',
+      body: JSON.stringify({ collectionPath: path })
+    });
   }
 
-  async removeNodeFromCollection(_nodeId: string, _collectionId: string): Promise<void> {
-    // Not implemented in dev-proxy yet
+  async removeNodeFromCollection(nodeId: string, collectionId: string): Promise<void> {
+    log.debug('Removing node from collection via HTTP', { nodeId, collectionId });
+    return this.request<void>(
+      `/api/collections/${encodeURIComponent(collectionId)}/members/${encodeURIComponent(nodeId)}`,
+      { method: 'DELETE' }
+    );
   }
 
-  async createCollection(_name: string, _description?: string): Promise<string> {
-    // Not implemented in dev-proxy yet
-    return '';
+  async createCollection(name: string, description?: string): Promise<string> {
+    log.debug('Creating collection via HTTP', { name, description });
+    return this.request<string>('/api/collections', {
+      method: 'POST',
+      body: JSON.stringify({ name, description })
+    });
   }
 
-  async renameCollection(collectionId: string, _version: number, newName: string): Promise<CollectionNode> {
-    // Not implemented in dev-proxy yet
-    return {
-      id: collectionId,
-      nodeType: 'collection',
-      content: newName,
-      createdAt: new Date().toISOString(),
-      modifiedAt: new Date().toISOString(),
-      version: 1,
-      properties: {}
-    };
+  async renameCollection(collectionId: string, version: number, newName: string): Promise<CollectionNode> {
+    log.debug('Renaming collection via HTTP', { collectionId, version, newName });
+    return this.request<CollectionNode>(`/api/collections/${encodeURIComponent(collectionId)}`, {
+      method: 'PATCH',
+      body: JSON.stringify({ version, newName })
+    });
   }
 
-  async deleteCollection(_collectionId: string, _version: number): Promise<void> {
-    // Not implemented in dev-proxy yet
+  async deleteCollection(collectionId: string, version: number): Promise<void> {
+    log.debug('Deleting collection via HTTP', { collectionId, version });
+    return this.request<void>(`/api/collections/${encodeURIComponent(collectionId)}`, {
+      method: 'DELETE',
+      body: JSON.stringify({ version })
+    });
   }
 }
 
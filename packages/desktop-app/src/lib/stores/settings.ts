@@ -11,6 +11,13 @@ export interface AppSettings {
         renderMarkdown: boolean;
         theme: string;
     };
+    vaultMirrorFolder: string | null;
+    vaultMirrorTwoWay: boolean;
+    closeToTray: boolean;
+    osSearchIndex: boolean;
+    taskRollover: 'move' | 'copy' | null;
+    weeklyDigest: boolean;
+    propagateRenames: boolean;
 }
 
 export const appSettings = writable<AppSettings | null>(null);
@@ -19,6 +19,12 @@ export interface CommandError {
 
   /** Optional detailed error information for debugging */
   details?: string;
+
+  /** Values interpolated into the message for `code`, for localizing it */
+  params?: Record<string, unknown>;
+
+  /** Correlation ID of the failed command, to find it in the backend logs */
+  correlationId?: string;
 }
 
 /**
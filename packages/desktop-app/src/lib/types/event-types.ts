@@ -113,6 +113,11 @@ export interface NodeWithChildren {
   /** Indexed title for @mention autocomplete search */
   title?: string | null;
 
+  /** Shown here through a reference edge; the node lives under another parent */
+  transcluded?: boolean;
+  /** Current content of the blocks embedded in `content` with ((node-id)) */
+  embedded?: Array<{ id: string; nodeType: string; content: string }>;
+
   // Nested children (recursive)
   children?: NodeWithChildren[];
 }
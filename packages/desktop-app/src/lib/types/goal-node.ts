@@ -0,0 +1,41 @@
+/**
+ * Goal Progress Types
+ *
+ * Goals are measured by the `key_result` nodes beneath them. The backend
+ * records each key result's value once a day; `get_goal_progress` returns
+ * the current values together with that history for trend charts.
+ */
+
+/**
+ * A key result's value on one day (mirrors Rust `ProgressSnapshot`)
+ */
+export interface ProgressSnapshot {
+  /** YYYY-MM-DD */
+  date: string;
+  value: number;
+}
+
+/**
+ * Current standing of one key result (mirrors Rust `KeyResultProgress`)
+ */
+export interface KeyResultProgress {
+  keyResultId: string;
+  /** Null when the metric can't be read */
+  value: number | null;
+  startValue: number;
+  targetValue: number;
+  /** 0-1, from start to target */
+  progress: number;
+  /** Oldest first */
+  snapshots: ProgressSnapshot[];
+}
+
+/**
+ * Current standing of a goal (mirrors Rust `GoalProgress`)
+ */
+export interface GoalProgress {
+  goalId: string;
+  /** Mean progress of the key results, 0-1 */
+  progress: number;
+  keyResults: KeyResultProgress[];
+}
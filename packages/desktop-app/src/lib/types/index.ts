@@ -56,6 +56,10 @@ export {
   TaskNodeHelpers
 } from './task-node';
 
+export type { ProjectNode, ProjectRollup } from './project-node';
+export type { GoalProgress, KeyResultProgress, ProgressSnapshot } from './goal-node';
+export { isProjectNode } from './project-node';
+
 export type { SchemaNode, SchemaField, ProtectionLevel, EnumValue } from './schema-node';
 // Only isSchemaNode remains - type guard for runtime checking
 // All other properties are typed top-level fields accessed directly (e.g., node.isCore, node.fields)
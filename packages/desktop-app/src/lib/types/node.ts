@@ -7,6 +7,30 @@
  * Philosophy: Single source of truth, zero schema drift.
  */
 
+/**
+ * Icon, accent color and cover shared by every node type
+ *
+ * Stored under `properties.appearance` and set via `set_node_appearance`.
+ */
+export interface NodeAppearance {
+  /** A single emoji */
+  icon?: string;
+  /** Accent color as `#rgb` or `#rrggbb` */
+  color?: string;
+  /** Cover image as an `attachment://<hash>` URI */
+  cover?: string;
+}
+
+/**
+ * Plain-text preview of a root's first children
+ *
+ * Stored under `properties.excerpt` of root nodes and kept current by the
+ * backend; clients only read it.
+ */
+export interface NodeExcerpt {
+  text: string;
+}
+
 /**
  * Lightweight reference to a node for backlinks display
  *
@@ -20,6 +44,8 @@ export interface NodeReference {
   title: string | null;
   /** Node type (e.g., "text", "task", "date") */
   nodeType: string;
+  /** Icon, color and cover, when the node has any */
+  appearance?: NodeAppearance;
 }
 
 /**
@@ -0,0 +1,47 @@
+/**
+ * Type-Safe ProjectNode Wrapper
+ *
+ * Projects group tasks beneath them. `get_node` returns a project together
+ * with a rollup of those tasks, computed and cached by the backend.
+ *
+ * @example
+ * ```typescript
+ * import { isProjectNode } from '$lib/types/project-node';
+ *
+ * if (isProjectNode(node) && node.rollup) {
+ *   console.log(`${node.content}: ${node.rollup.completionPercent}%`);
+ * }
+ * ```
+ */
+
+import type { Node } from './node';
+
+/**
+ * Task progress beneath a project (mirrors Rust `ProjectRollup`)
+ */
+export interface ProjectRollup {
+  /** Tasks not yet done or cancelled */
+  openTasks: number;
+  doneTasks: number;
+  /** Done share of open and done tasks, 0-100 */
+  completionPercent: number;
+  /** Earliest due date (YYYY-MM-DD) among open tasks */
+  nextDueDate?: string;
+}
+
+/**
+ * ProjectNode interface extending base Node
+ *
+ * `rollup` is present on nodes fetched through `get_node`.
+ */
+export interface ProjectNode extends Node {
+  nodeType: 'project';
+  rollup?: ProjectRollup;
+}
+
+/**
+ * Type guard to check if a node is a project node
+ */
+export function isProjectNode(node: Node): node is ProjectNode {
+  return node.nodeType === 'project';
+}
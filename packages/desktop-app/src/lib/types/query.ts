@@ -82,17 +82,49 @@ export interface QueryFilter {
 	caseSensitive?: boolean;
 
 	/** Relationship type for relationship filters */
-	relationshipType?: 'parent' | 'children' | 'mentions' | 'mentioned_by';
+	relationshipType?:
+		| 'parent'
+		| 'children'
+		| 'mentions'
+		| 'mentioned_by'
+		| 'member_of'
+		| 'descendants_of'
+		| 'ancestors_of';
 
 	/** Target node ID for relationship filters */
 	nodeId?: string;
+
+	/** Levels to traverse for `descendants_of` / `ancestors_of` (unbounded if omitted) */
+	maxDepth?: number;
+
+	/** Conditions on the relationship edge itself (relationship filters only) */
+	edgeFilters?: EdgeFilter[];
+}
+
+/**
+ * Condition on a relationship edge
+ *
+ * Timestamp values may be relative, e.g. `-7d` for "within the last week".
+ */
+export interface EdgeFilter {
+	/** Edge property (`added_at`, `created_at`, `modified_at`, or a stored edge property) */
+	property: string;
+
+	/** Comparison operator */
+	operator: QueryFilter['operator'];
+
+	/** Expected value */
+	value?: unknown;
+
+	/** Case sensitivity for text comparisons */
+	caseSensitive?: boolean;
 }
 
 /**
  * Sorting configuration
  */
 export interface SortConfig {
-	/** Property or field to sort by */
+	/** Property or field to sort by (`edge.<property>` sorts by the relationship edge) */
 	field: string;
 
 	/** Sort direction */
@@ -138,3 +170,23 @@ export interface ColumnConfig {
 	sortable?: boolean;
 	format?: 'text' | 'date' | 'number' | 'enum';
 }
+
+/**
+ * A node and its index in the new result list
+ */
+export interface ResultPosition {
+	nodeId: string;
+	index: number;
+}
+
+/**
+ * Payload of `query:results-changed` for a query opened with `watch_query`
+ *
+ * Indexes refer to the new result list; unlisted nodes keep their relative order.
+ */
+export interface QueryResultDiff {
+	queryId: string;
+	added: ResultPosition[];
+	removed: string[];
+	moved: ResultPosition[];
+}
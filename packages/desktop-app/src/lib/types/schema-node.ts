@@ -52,6 +52,9 @@ export interface EnumValue {
 
   /** Human-readable display label for UI/MCP clients */
   label: string;
+
+  /** Still accepted, but writes using it get a validation warning */
+  deprecated?: boolean;
 }
 
 /**
@@ -82,6 +85,9 @@ export interface SchemaField {
   /** Whether this field is required (cannot be null/undefined) */
   required?: boolean;
 
+  /** Whether a missing value produces a validation warning */
+  recommended?: boolean;
+
   /** Whether enum values can be extended by users */
   extensible?: boolean;
 
@@ -93,6 +93,17 @@ export interface TaskNode {
   assignee?: string | null;
   startedAt?: string | null;
   completedAt?: string | null;
+  /** Tracked work sessions, oldest first; a session without ended_at is running */
+  timeSessions?: TimeSession[];
+}
+
+/**
+ * One stretch of tracked work on a task (stored snake_case under
+ * `properties.task.time_sessions`)
+ */
+export interface TimeSession {
+  started_at: string;
+  ended_at?: string;
 }
 
 /**
@@ -101,7 +101,11 @@ async fn main() -> anyhow::Result<()> {
     println!("✅ NLP engine initialized");
 
     // Initialize embedding service
-    let embedding_service = Arc::new(NodeEmbeddingService::new(nlp_engine_arc, store.clone()));
+    let embedding_service = Arc::new(NodeEmbeddingService::new(
+        nlp_engine_arc,
+        store.clone(),
+        node_service.workspace_lock().clone(),
+    ));
 
     // Create MCP server service
     let port = default_mcp_port();
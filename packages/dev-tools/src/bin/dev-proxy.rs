@@ -23,25 +23,29 @@
 //! or can be persisted to `~/.nodespace/dev.db` using `bun run dev:db:persist`.
 
 use axum::{
-    extract::{Path, State},
-    http::StatusCode,
-    response::{sse::Event, Json, Sse},
+    extract::{Path, Query, Request, State},
+    http::{HeaderMap, HeaderValue, StatusCode},
+    middleware::{self, Next},
+    response::{sse::Event, Json, Response, Sse},
     routing::{delete, get, patch, post},
     Router,
 };
 use futures::stream::Stream;
 use nodespace_core::{
-    db::{events::DomainEvent, HttpStore},
+    api::{CreateNodeRequest, NodeDto, QueryRequest},
+    db::{events::DomainEvent, CollectionStats, HttpStore},
     models,
-    models::{Node, NodeFilter, NodeUpdate, SchemaNode, TaskNode, TaskNodeUpdate},
+    models::{Node, NodeUpdate, SchemaNode, TaskNode, TaskNodeUpdate},
     services::{
-        default_mcp_port, CreateNodeParams, EmbeddingProcessor, McpServerService,
-        NodeEmbeddingService, NodeService, NodeServiceError,
+        default_mcp_port, CollectionInfo, CollectionService, CreateNodeParams, EmbeddingProcessor,
+        IdempotencyService, IdempotentStart, McpServerService, NodeEmbeddingService, NodeService,
+        NodeServiceError, RenamedPath,
     },
+    utils::{current_correlation_id, new_correlation_id, with_correlation_id},
 };
 use nodespace_nlp_engine::EmbeddingService;
 use serde::{Deserialize, Serialize};
-use std::{convert::Infallible, sync::Arc, time::Duration};
+use std::{convert::Infallible, future::Future, sync::Arc, time::Duration};
 use tokio::sync::broadcast;
 use tokio_stream::{wrappers::BroadcastStream, StreamExt};
 use tower_http::cors::CorsLayer;
@@ -158,7 +162,8 @@ struct AppState {
 /// {
 ///   "message": "User-facing error message",
 ///   "code": "MACHINE_READABLE_CODE",
-///   "details": "Optional debugging information"
+///   "details": "Optional debugging information",
+///   "correlationId": "3f2a..."
 /// }
 /// ```
 #[derive(Debug, Serialize)]
@@ -171,6 +176,9 @@ struct ApiError {
     /// Optional detailed error information for debugging
     #[serde(skip_serializing_if = "Option::is_none")]
     pub details: Option<String>,
+    /// Correlation ID of the failed request, to find it in the logs
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub correlation_id: Option<String>,
 }
 
 impl ApiError {
@@ -179,6 +187,7 @@ impl ApiError {
             message: message.clone(),
             code: code.to_string(),
             details: Some(message),
+            correlation_id: current_correlation_id(),
         }
     }
 }
@@ -194,20 +203,12 @@ type ApiStatusResult = Result<StatusCode, (StatusCode, Json<ApiError>)>;
 ///
 /// Delegates to the canonical `models::node_to_typed_value` and maps errors to ApiError.
 fn node_to_typed_value(node: Node) -> Result<serde_json::Value, ApiError> {
-    models::node_to_typed_value(node).map_err(|e| ApiError {
-        message: e.clone(),
-        code: "CONVERSION_ERROR".to_string(),
-        details: Some(e),
-    })
+    models::node_to_typed_value(node).map_err(|e| ApiError::new("CONVERSION_ERROR", e))
 }
 
 /// Convert a list of Nodes to their strongly-typed JSON representations (Issue #673)
 fn nodes_to_typed_values(nodes: Vec<Node>) -> Result<Vec<serde_json::Value>, ApiError> {
-    models::nodes_to_typed_values(nodes).map_err(|e| ApiError {
-        message: e.clone(),
-        code: "CONVERSION_ERROR".to_string(),
-        details: Some(e),
-    })
+    models::nodes_to_typed_values(nodes).map_err(|e| ApiError::new("CONVERSION_ERROR", e))
 }
 
 /// Update node request with OCC version
@@ -269,12 +270,16 @@ struct MentionAutocompleteRequest {
 /// across all endpoints. Follows REST best practices:
 /// - 404 NOT FOUND: Resource doesn't exist
 /// - 400 BAD REQUEST: Validation failures
-/// - 409 CONFLICT: Version conflicts (OCC failures)
+/// - 409 CONFLICT: Version conflicts (OCC failures), reused idempotency keys
 /// - 500 INTERNAL SERVER ERROR: Unexpected errors
 fn map_node_service_error(err: NodeServiceError) -> (StatusCode, Json<ApiError>) {
     let error_str = err.to_string();
 
-    let (status, code) = if error_str.contains("not found") {
+    let (status, code) = if matches!(err, NodeServiceError::IdempotencyKeyConflict { .. }) {
+        (StatusCode::CONFLICT, "IDEMPOTENCY_KEY_CONFLICT")
+    } else if matches!(err, NodeServiceError::InvalidInput(_)) {
+        (StatusCode::BAD_REQUEST, "VALIDATION_ERROR")
+    } else if error_str.contains("not found") {
         (StatusCode::NOT_FOUND, "RESOURCE_NOT_FOUND")
     } else if error_str.contains("already exists") {
         (StatusCode::BAD_REQUEST, "ALREADY_EXISTS")
@@ -297,57 +302,58 @@ fn map_node_service_error(err: NodeServiceError) -> (StatusCode, Json<ApiError>)
 
 // NOTE: map_schema_error removed (Issue #690) - schema operations use map_node_service_error
 
-/// Create node request for POST /api/nodes endpoint
-///
-/// The frontend sends a partial node representation containing only the business data.
-/// The backend is responsible for adding infrastructure fields (timestamps, version).
-///
-/// # Field Semantics
-///
-/// - `id`: Optional UUID. If omitted, backend generates a new UUID.
-///   For deterministic IDs (e.g., date nodes use "YYYY-MM-DD"),
-///   the frontend MUST provide this field.
-/// - `node_type`: Required. Must be a registered node type (e.g., "text", "task", "date").
-/// - `content`: Primary text content. MAY be empty string (Issue #484 - blank nodes allowed).
-/// - `parent_id`: Optional parent node for hierarchical relationships.
-/// - `root_id`: Optional root for spatial/collection relationships.
-/// - `before_sibling_id`: Optional sibling for ordering within parent.
-/// - `properties`: Schema-driven JSON properties. Backend applies default values if schema exists.
-/// - `embedding_vector`: Optional vector for semantic search (currently unused).
-/// - `mentions`: Outgoing references to other nodes. `mentioned_by` is computed server-side
-///   and NOT accepted here (it's the inverse relationship).
-///
-/// # Validation
-///
-/// Backend performs two-stage validation:
-/// 1. Core behavior validation (via `NodeService.behaviors.validate_node()`)
-/// 2. Schema validation (via `NodeService.validate_node_against_schema()`)
-///
-/// # Example
+/// Run each request under a new correlation ID, also returned in the
+/// `X-Correlation-Id` response header
+async fn correlate(request: Request, next: Next) -> Response {
+    let correlation_id = new_correlation_id();
+    let mut response = with_correlation_id(correlation_id.clone(), next.run(request)).await;
+    if let Ok(value) = HeaderValue::from_str(&correlation_id) {
+        response.headers_mut().insert("x-correlation-id", value);
+    }
+    response
+}
+
+/// Run a mutating request at most once per `Idempotency-Key` header
 ///
-/// ```json
-/// {
-///   "nodeType": "text",
-///   "content": "",
-///   "parentId": "550e8400-e29b-41d4-a716-446655440000",
-///   "properties": {}
-/// }
-/// ```
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "camelCase")]
-struct CreateNodeRequest {
-    #[serde(default)]
-    pub id: Option<String>,
-    pub node_type: String,
-    pub content: String,
-    // CRITICAL FIX (Issue #528): Re-added parent_id to HTTP API for edge creation
-    // While Node type no longer has this field (stored as graph edges),
-    // the CREATE operation needs this info to establish parent-child relationships
-    // The operations layer (NodeOperations::create_node) handles edge creation
-    // Note (Issue #533): root_id removed - backend auto-derives root from parent chain
-    pub parent_id: Option<String>,
-    pub insert_after_node_id: Option<String>,
-    pub properties: serde_json::Value,
+/// A retry carrying the key of a request that already succeeded gets that
+/// request's response back; requests without the header simply run.
+async fn run_idempotent<T, F, Fut>(
+    state: &AppState,
+    headers: &HeaderMap,
+    operation: &str,
+    request: &impl Serialize,
+    run: F,
+) -> Result<T, (StatusCode, Json<ApiError>)>
+where
+    T: Serialize + serde::de::DeserializeOwned,
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = Result<T, (StatusCode, Json<ApiError>)>>,
+{
+    let key = match headers.get("idempotency-key") {
+        Some(value) => Some(value.to_str().map_err(|_| {
+            (
+                StatusCode::BAD_REQUEST,
+                Json(ApiError::new(
+                    "VALIDATION_ERROR",
+                    "Idempotency-Key must be visible ASCII".to_string(),
+                )),
+            )
+        })?),
+        None => None,
+    };
+    let idempotency = IdempotencyService::new(&state.node_service);
+    match idempotency
+        .start(key, operation, request)
+        .await
+        .map_err(map_node_service_error)?
+    {
+        IdempotentStart::Replay(response) => Ok(response),
+        IdempotentStart::Run(claim) => {
+            let result = run().await;
+            idempotency.finish(claim, &result).await;
+            result
+        }
+    }
 }
 
 #[tokio::main]
@@ -416,7 +422,11 @@ async fn main() -> anyhow::Result<()> {
     println!("✅ NLP engine initialized");
 
     // Initialize embedding service for MCP semantic search
-    let embedding_service = Arc::new(NodeEmbeddingService::new(nlp_engine_arc, store.clone()));
+    let embedding_service = Arc::new(NodeEmbeddingService::new(
+        nlp_engine_arc,
+        store.clone(),
+        node_service.workspace_lock().clone(),
+    ));
 
     // Initialize background embedding processor (event-driven, Issue #729)
     // Processes stale embeddings in the background - no polling, wakes on demand
@@ -491,6 +501,7 @@ async fn main() -> anyhow::Result<()> {
         .route("/api/tasks/:id", patch(update_task_node))
         // Hierarchy endpoints
         .route("/api/nodes/:id/parent", post(set_parent))
+        .route("/api/nodes/:id/reorder", post(reorder_node))
         // Query endpoints
         .route("/api/nodes/:id/children", get(get_children))
         .route("/api/nodes/:id/children-tree", get(get_children_tree))
@@ -515,9 +526,40 @@ async fn main() -> anyhow::Result<()> {
         .route("/api/schemas", get(get_all_schemas))
         .route("/api/schemas/:id", get(get_schema))
         // Collection endpoints (Issue #807)
-        .route("/api/collections", get(get_all_collections))
-        .route("/api/collections/:id/members", get(get_collection_members))
+        .route(
+            "/api/collections",
+            get(get_all_collections).post(create_collection),
+        )
+        .route("/api/collections/stats", get(get_collection_stats))
+        .route("/api/collections/by-path", get(find_collection_by_path))
+        .route("/api/collections/by-name", get(get_collection_by_name))
+        .route("/api/collections/rename-path", post(rename_collection_path))
+        .route(
+            "/api/collections/:id",
+            patch(rename_collection).delete(delete_collection),
+        )
+        .route(
+            "/api/collections/:id/members",
+            get(get_collection_members).post(add_node_to_collection),
+        )
+        .route(
+            "/api/collections/:id/members/recursive",
+            get(get_collection_members_recursive),
+        )
+        .route(
+            "/api/collections/:id/members/:node_id",
+            delete(remove_node_from_collection),
+        )
+        .route(
+            "/api/collections/:id/members/:node_id/reorder",
+            post(reorder_collection_member),
+        )
+        .route(
+            "/api/nodes/:id/collections",
+            get(get_node_collections).post(add_node_to_collection_path),
+        )
         .with_state(state)
+        .layer(middleware::from_fn(correlate))
         .layer(CorsLayer::permissive()); // Allow CORS from frontend (localhost:5173)
 
     // Start HTTP server on port 3001
@@ -572,6 +614,7 @@ async fn domain_event_to_sse_bridge(
                         node_id,
                         node_type,
                         source_client_id,
+                        ..
                     } => {
                         // Filter out events from dev-proxy (browser operations)
                         if source_client_id.as_deref() == Some("dev-proxy") {
@@ -593,6 +636,7 @@ async fn domain_event_to_sse_bridge(
                     DomainEvent::NodeUpdated {
                         node_id,
                         source_client_id,
+                        ..
                     } => {
                         // Filter out events from dev-proxy (browser operations)
                         // Don't send browser's own changes back to it
@@ -619,6 +663,7 @@ async fn domain_event_to_sse_bridge(
                     DomainEvent::NodeDeleted {
                         id,
                         source_client_id,
+                        ..
                     } => {
                         // Filter out events from dev-proxy (browser operations)
                         if source_client_id.as_deref() == Some("dev-proxy") {
@@ -639,6 +684,7 @@ async fn domain_event_to_sse_bridge(
                     DomainEvent::RelationshipCreated {
                         relationship,
                         source_client_id,
+                        ..
                     } => {
                         // Filter out events from dev-proxy (browser operations)
                         if source_client_id.as_deref() == Some("dev-proxy") {
@@ -659,6 +705,7 @@ async fn domain_event_to_sse_bridge(
                     DomainEvent::RelationshipUpdated {
                         relationship,
                         source_client_id,
+                        ..
                     } => {
                         // Filter out events from dev-proxy (browser operations)
                         if source_client_id.as_deref() == Some("dev-proxy") {
@@ -682,6 +729,7 @@ async fn domain_event_to_sse_bridge(
                         to_id,
                         relationship_type,
                         source_client_id,
+                        ..
                     } => {
                         // Filter out events from dev-proxy (browser operations)
                         if source_client_id.as_deref() == Some("dev-proxy") {
@@ -810,12 +858,17 @@ async fn init_database() -> Json<serde_json::Value> {
     }))
 }
 
+/// Create a node; the body is the [`CreateNodeRequest`] shared with the
+/// Tauri `create_node` command
+///
+/// ```json
+/// { "nodeType": "text", "content": "", "parentId": "550e8400-...", "properties": {} }
+/// ```
 async fn create_node(
     State(state): State<AppState>,
+    headers: HeaderMap,
     Json(req): Json<CreateNodeRequest>,
 ) -> ApiResult<String> {
-    // Use NodeService directly to handle node creation and edge creation
-    // (Issue #676: NodeOperations merged into NodeService)
     tracing::debug!(
         "create_node request: node_type={:?}, parent_id={:?}, insert_after_node_id={:?}",
         req.node_type,
@@ -823,25 +876,19 @@ async fn create_node(
         req.insert_after_node_id
     );
 
-    let params = CreateNodeParams {
-        id: req.id,
-        node_type: req.node_type,
-        content: req.content,
-        parent_id: req.parent_id,
-        insert_after_node_id: req.insert_after_node_id,
-        properties: req.properties,
-    };
-
-    let id = state
-        .node_service
-        .create_node_with_parent(params)
-        .await
-        .map_err(|e| {
-            (
-                StatusCode::BAD_REQUEST,
-                Json(ApiError::new("CREATE_FAILED", e.to_string())),
-            )
-        })?;
+    let id = run_idempotent(&state, &headers, "create_node", &req, || async {
+        state
+            .node_service
+            .create_node_with_parent(CreateNodeParams::from(req.clone()))
+            .await
+            .map_err(|e| {
+                (
+                    StatusCode::BAD_REQUEST,
+                    Json(ApiError::new("CREATE_FAILED", e.to_string())),
+                )
+            })
+    })
+    .await?;
 
     // NOTE: No SSE broadcast needed here - NodeService emits DomainEvent
     // which is converted to SSE by the domain_event_to_sse_bridge
@@ -878,21 +925,29 @@ async fn get_node(
 async fn update_node(
     State(state): State<AppState>,
     Path(id): Path<String>,
+    headers: HeaderMap,
     Json(request): Json<UpdateNodeRequest>,
 ) -> ApiResult<serde_json::Value> {
-    // Use optimistic concurrency control - returns updated node directly
-    let updated_node = state
-        .node_service
-        .update_node(&id, request.version, request.update)
-        .await
-        .map_err(map_node_service_error)?;
-
-    // NOTE: No SSE broadcast needed here - NodeService emits DomainEvent
-    // which is converted to SSE by the domain_event_to_sse_bridge
-    // This prevents duplicate events and enables proper client filtering
-
-    let typed = node_to_typed_value(updated_node)
-        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(e)))?;
+    let fingerprint = serde_json::json!({
+        "id": id,
+        "version": request.version,
+        "update": request.update,
+    });
+    let typed = run_idempotent(&state, &headers, "update_node", &fingerprint, || async {
+        // Use optimistic concurrency control - returns updated node directly
+        let updated_node = state
+            .node_service
+            .update_node(&id, request.version, request.update)
+            .await
+            .map_err(map_node_service_error)?;
+
+        // NOTE: No SSE broadcast needed here - NodeService emits DomainEvent
+        // which is converted to SSE by the domain_event_to_sse_bridge
+        // This prevents duplicate events and enables proper client filtering
+
+        node_to_typed_value(updated_node).map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(e)))
+    })
+    .await?;
     Ok(Json(typed))
 }
 
@@ -952,19 +1007,33 @@ async fn update_task_node(
 async fn delete_node(
     State(state): State<AppState>,
     Path(id): Path<String>,
+    headers: HeaderMap,
     Json(request): Json<DeleteNodeRequest>,
 ) -> ApiStatusResult {
+    let fingerprint = serde_json::json!({ "id": id, "version": request.version });
+    run_idempotent(&state, &headers, "delete_node", &fingerprint, || {
+        delete_node_with_version(&state, &id, request.version)
+    })
+    .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn delete_node_with_version(
+    state: &AppState,
+    id: &str,
+    version: i64,
+) -> Result<(), (StatusCode, Json<ApiError>)> {
     // Use optimistic concurrency control via version check
     let rows_affected = state
         .node_service
-        .delete_with_version_check(&id, request.version)
+        .delete_with_version_check(id, version)
         .await
         .map_err(map_node_service_error)?;
 
     // Check if delete succeeded (version matched and node existed)
     if rows_affected == 0 {
         // Check if node exists to distinguish between version conflict and not found
-        match state.node_service.get_node(&id).await {
+        match state.node_service.get_node(id).await {
             Ok(Some(_)) => {
                 // Node exists but version didn't match - conflict
                 return Err((
@@ -983,7 +1052,7 @@ async fn delete_node(
                 // Idempotent DELETE: return success (204) instead of 404
                 // This follows RESTful best practices - DELETE should succeed
                 // even if the resource is already gone
-                return Ok(StatusCode::NO_CONTENT);
+                return Ok(());
             }
             Err(e) => {
                 // Database error
@@ -996,7 +1065,7 @@ async fn delete_node(
     // which is converted to SSE by the domain_event_to_sse_bridge
     // This prevents duplicate events and enables proper client filtering
 
-    Ok(StatusCode::NO_CONTENT)
+    Ok(())
 }
 
 /// Set parent request (with OCC version)
@@ -1014,29 +1083,86 @@ struct SetParentRequest {
 async fn set_parent(
     State(state): State<AppState>,
     Path(node_id): Path<String>,
+    headers: HeaderMap,
     Json(request): Json<SetParentRequest>,
 ) -> ApiResult<serde_json::Value> {
-    let node = state
-        .node_service
-        .move_node(
-            &node_id,
-            request.version,
-            request.parent_id.as_deref(),
-            request.insert_after_node_id.as_deref(),
-        )
-        .await
-        .map_err(map_node_service_error)?;
+    let fingerprint = serde_json::json!({
+        "node_id": node_id,
+        "version": request.version,
+        "parent_id": request.parent_id,
+        "insert_after_node_id": request.insert_after_node_id,
+    });
+    let typed = run_idempotent(&state, &headers, "move_node", &fingerprint, || async {
+        let node = state
+            .node_service
+            .move_node(
+                &node_id,
+                request.version,
+                request.parent_id.as_deref(),
+                request.insert_after_node_id.as_deref(),
+            )
+            .await
+            .map_err(map_node_service_error)?;
 
-    // NOTE: No SSE broadcast needed here - NodeService emits DomainEvent
-    // (RelationshipCreated/Deleted) which is converted to SSE by the domain_event_to_sse_bridge
-    // This prevents duplicate events and enables proper client filtering
+        // NOTE: No SSE broadcast needed here - NodeService emits DomainEvent
+        // (RelationshipCreated/Deleted) which is converted to SSE by the domain_event_to_sse_bridge
+        // This prevents duplicate events and enables proper client filtering
 
-    // Return the updated node with new version (critical for frontend to sync local state)
-    let typed =
-        node_to_typed_value(node).map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(e)))?;
+        // Return the updated node with new version (critical for frontend to sync local state)
+        node_to_typed_value(node).map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(e)))
+    })
+    .await?;
     Ok(Json(typed))
 }
 
+/// Reorder node request (with OCC version)
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ReorderNodeRequest {
+    /// Expected version for optimistic concurrency control
+    pub version: i64,
+    /// Sibling to insert after (None = insert at beginning)
+    pub insert_after_node_id: Option<String>,
+}
+
+/// Move a node to another position under its current parent
+///
+/// # HTTP Endpoint
+/// ```text
+/// POST /api/nodes/:id/reorder
+/// ```
+///
+/// # Request Body
+/// ```json
+/// { "version": 2, "insertAfterNodeId": "sibling-123" }
+/// ```
+async fn reorder_node(
+    State(state): State<AppState>,
+    Path(node_id): Path<String>,
+    headers: HeaderMap,
+    Json(request): Json<ReorderNodeRequest>,
+) -> ApiStatusResult {
+    let fingerprint = serde_json::json!({
+        "node_id": node_id,
+        "version": request.version,
+        "insert_after_node_id": request.insert_after_node_id,
+    });
+    run_idempotent(&state, &headers, "reorder_node", &fingerprint, || async {
+        state
+            .node_service
+            .reorder_node(
+                &node_id,
+                request.version,
+                request.insert_after_node_id.as_deref(),
+            )
+            .await
+            .map_err(map_node_service_error)
+    })
+    .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
 async fn get_children(
     State(state): State<AppState>,
     Path(parent_id): Path<String>,
@@ -1067,16 +1193,14 @@ async fn get_children_tree(
 
 async fn query_nodes(
     State(state): State<AppState>,
-    Json(filter): Json<NodeFilter>,
-) -> ApiResult<Vec<serde_json::Value>> {
-    let nodes = state
-        .node_service
-        .query_nodes(filter)
+    Json(query): Json<QueryRequest>,
+) -> ApiResult<Vec<NodeDto>> {
+    let result = query
+        .execute(&state.node_service)
         .await
         .map_err(map_node_service_error)?;
 
-    let typed =
-        nodes_to_typed_values(nodes).map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(e)))?;
+    let typed = NodeDto::from_nodes(result.nodes).map_err(conversion_error)?;
     Ok(Json(typed))
 }
 
@@ -1263,21 +1387,73 @@ async fn get_schema(
 // ============================================================================
 // Collection Endpoints (Issue #807)
 // ============================================================================
+//
+// One endpoint per Tauri collection command, taking the command's arguments
+// as camelCase JSON (or query parameters for lookups) and returning the same
+// response shapes.
 
-/// Collection info with member count for UI display
-#[derive(Debug, serde::Serialize)]
+/// Create collection request
+#[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
-struct CollectionInfo {
-    id: String,
-    content: String,
-    node_type: String,
-    created_at: chrono::DateTime<chrono::Utc>,
-    modified_at: chrono::DateTime<chrono::Utc>,
-    version: i64,
-    properties: serde_json::Value,
-    member_count: usize,
-    /// IDs of parent collections (collections this collection is nested under)
-    parent_collection_ids: Vec<String>,
+struct CreateCollectionRequest {
+    pub name: String,
+    pub description: Option<String>,
+}
+
+/// Rename collection request (with OCC version)
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RenameCollectionRequest {
+    pub version: i64,
+    pub new_name: String,
+}
+
+/// Rename collection path request
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RenameCollectionPathRequest {
+    pub old_path: String,
+    pub new_path: String,
+}
+
+/// Collection membership request
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CollectionMemberRequest {
+    pub node_id: String,
+}
+
+/// Collection path membership request
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CollectionPathRequest {
+    pub collection_path: String,
+}
+
+/// Reorder collection member request
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ReorderCollectionMemberRequest {
+    pub insert_after_member_id: Option<String>,
+}
+
+/// `?path=` query parameter
+#[derive(Debug, Deserialize)]
+struct PathQuery {
+    pub path: String,
+}
+
+/// `?name=` query parameter
+#[derive(Debug, Deserialize)]
+struct NameQuery {
+    pub name: String,
+}
+
+fn conversion_error(e: String) -> (StatusCode, Json<ApiError>) {
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(ApiError::new("CONVERSION_ERROR", e)),
+    )
 }
 
 /// Get all collections with member counts
@@ -1300,33 +1476,12 @@ struct CollectionInfo {
 /// ]
 /// ```
 async fn get_all_collections(State(state): State<AppState>) -> ApiResult<Vec<CollectionInfo>> {
-    use nodespace_core::services::CollectionService;
-
     let store = state.node_service.store();
     let collection_service = CollectionService::new(store, &state.node_service);
 
     // Try the optimized single-query method first
-    match collection_service.get_all_collections_with_counts().await {
-        Ok(collections_with_counts) => {
-            let result: Vec<CollectionInfo> = collections_with_counts
-                .into_iter()
-                .map(|(node, member_count)| {
-                    let parent_collection_ids = node.member_of.clone();
-                    CollectionInfo {
-                        id: node.id,
-                        content: node.content,
-                        node_type: node.node_type,
-                        created_at: node.created_at,
-                        modified_at: node.modified_at,
-                        version: node.version,
-                        properties: node.properties,
-                        member_count,
-                        parent_collection_ids,
-                    }
-                })
-                .collect();
-            Ok(Json(result))
-        }
+    let collections_with_counts = match collection_service.get_all_collections_with_counts().await {
+        Ok(collections_with_counts) => collections_with_counts,
         Err(e) => {
             // Fallback: query collections via node type query and count members individually
             // This handles cases where the combined query has parsing issues in HTTP mode
@@ -1350,23 +1505,18 @@ async fn get_all_collections(State(state): State<AppState>) -> ApiResult<Vec<Col
                     .await
                     .map(|m| m.len())
                     .unwrap_or(0);
-
-                result.push(CollectionInfo {
-                    id: collection.id,
-                    content: collection.content,
-                    node_type: collection.node_type,
-                    created_at: collection.created_at,
-                    modified_at: collection.modified_at,
-                    version: collection.version,
-                    properties: collection.properties,
-                    member_count,
-                    parent_collection_ids: collection.member_of.clone(),
-                });
+                result.push((collection, member_count));
             }
-
-            Ok(Json(result))
+            result
         }
-    }
+    };
+
+    let result = collections_with_counts
+        .into_iter()
+        .map(|(collection, member_count)| CollectionInfo::new(collection, member_count))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(conversion_error)?;
+    Ok(Json(result))
 }
 
 /// Get members of a specific collection
@@ -1381,9 +1531,7 @@ async fn get_all_collections(State(state): State<AppState>) -> ApiResult<Vec<Col
 async fn get_collection_members(
     State(state): State<AppState>,
     Path(id): Path<String>,
-) -> ApiResult<Vec<nodespace_core::Node>> {
-    use nodespace_core::services::CollectionService;
-
+) -> ApiResult<Vec<serde_json::Value>> {
     let store = state.node_service.store();
     let collection_service = CollectionService::new(store, &state.node_service);
 
@@ -1393,5 +1541,394 @@ async fn get_collection_members(
         .await
         .map_err(map_node_service_error)?;
 
-    Ok(Json(members))
+    let typed =
+        nodes_to_typed_values(members).map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(e)))?;
+    Ok(Json(typed))
+}
+
+/// Get members of a collection and of all collections below it
+///
+/// # HTTP Endpoint
+/// ```text
+/// GET /api/collections/:id/members/recursive
+/// ```
+async fn get_collection_members_recursive(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> ApiResult<Vec<serde_json::Value>> {
+    let store = state.node_service.store();
+    let collection_service = CollectionService::new(store, &state.node_service);
+
+    let member_ids = collection_service
+        .get_collection_members_recursive(&id)
+        .await
+        .map_err(map_node_service_error)?;
+    let nodes_map = store.get_nodes_by_ids(&member_ids).await.map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiError::new("QUERY_ERROR", e.to_string())),
+        )
+    })?;
+
+    // Preserve order from member_ids
+    let members: Vec<Node> = member_ids
+        .into_iter()
+        .filter_map(|id| nodes_map.get(&id).cloned())
+        .collect();
+    let typed =
+        nodes_to_typed_values(members).map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(e)))?;
+    Ok(Json(typed))
+}
+
+/// Member counts and activity of the collection at a path
+///
+/// # HTTP Endpoint
+/// ```text
+/// GET /api/collections/stats?path=engineering
+/// ```
+async fn get_collection_stats(
+    State(state): State<AppState>,
+    Query(query): Query<PathQuery>,
+) -> ApiResult<CollectionStats> {
+    let store = state.node_service.store();
+    let collection_service = CollectionService::new(store, &state.node_service);
+
+    let stats = collection_service
+        .get_collection_stats(&query.path)
+        .await
+        .map_err(map_node_service_error)?;
+    Ok(Json(stats))
+}
+
+/// Find a collection by path without creating it
+///
+/// # HTTP Endpoint
+/// ```text
+/// GET /api/collections/by-path?path=hr:policy
+/// ```
+///
+/// # Response (200 OK)
+/// The collection node, or `null` if the path doesn't exist
+async fn find_collection_by_path(
+    State(state): State<AppState>,
+    Query(query): Query<PathQuery>,
+) -> ApiResult<Option<serde_json::Value>> {
+    let store = state.node_service.store();
+    let collection_service = CollectionService::new(store, &state.node_service);
+
+    let collection = collection_service
+        .find_collection_by_path(&query.path)
+        .await
+        .map_err(map_node_service_error)?;
+    let typed = collection
+        .map(node_to_typed_value)
+        .transpose()
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(e)))?;
+    Ok(Json(typed))
+}
+
+/// Find a collection by name (case-insensitive)
+///
+/// # HTTP Endpoint
+/// ```text
+/// GET /api/collections/by-name?name=Engineering
+/// ```
+async fn get_collection_by_name(
+    State(state): State<AppState>,
+    Query(query): Query<NameQuery>,
+) -> ApiResult<Option<serde_json::Value>> {
+    let store = state.node_service.store();
+    let collection_service = CollectionService::new(store, &state.node_service);
+
+    let collection = collection_service
+        .get_collection_by_name(&query.name)
+        .await
+        .map_err(map_node_service_error)?;
+    let typed = collection
+        .map(node_to_typed_value)
+        .transpose()
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(e)))?;
+    Ok(Json(typed))
+}
+
+/// Create a top-level collection
+///
+/// # HTTP Endpoint
+/// ```text
+/// POST /api/collections
+/// ```
+///
+/// # Request Body
+/// ```json
+/// { "name": "Engineering", "description": "Team docs" }
+/// ```
+///
+/// # Response (200 OK)
+/// ID of the new collection
+///
+/// # Errors
+/// - `400 BAD REQUEST`: A collection with this name already exists
+async fn create_collection(
+    State(state): State<AppState>,
+    Json(request): Json<CreateCollectionRequest>,
+) -> ApiResult<String> {
+    let store = state.node_service.store();
+    let collection_service = CollectionService::new(store, &state.node_service);
+
+    if collection_service
+        .get_collection_by_name(&request.name)
+        .await
+        .map_err(map_node_service_error)?
+        .is_some()
+    {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ApiError::new(
+                "COLLECTION_EXISTS",
+                format!("Collection '{}' already exists", request.name),
+            )),
+        ));
+    }
+
+    let properties = match request.description {
+        Some(description) => serde_json::json!({ "description": description }),
+        None => serde_json::json!({}),
+    };
+    let id = state
+        .node_service
+        .create_node_with_parent(CreateNodeParams {
+            id: None,
+            node_type: "collection".to_string(),
+            content: request.name,
+            parent_id: None,
+            insert_after_node_id: None,
+            properties,
+        })
+        .await
+        .map_err(map_node_service_error)?;
+    Ok(Json(id))
+}
+
+/// Rename a collection
+///
+/// # HTTP Endpoint
+/// ```text
+/// PATCH /api/collections/:id
+/// ```
+///
+/// # Request Body
+/// ```json
+/// { "version": 3, "newName": "Platform" }
+/// ```
+///
+/// # Errors
+/// - `400 BAD REQUEST`: Another collection already has the name
+/// - `409 CONFLICT`: Version mismatch (OCC failure)
+async fn rename_collection(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(request): Json<RenameCollectionRequest>,
+) -> ApiResult<serde_json::Value> {
+    let store = state.node_service.store();
+    let collection_service = CollectionService::new(store, &state.node_service);
+
+    if let Some(existing) = collection_service
+        .get_collection_by_name(&request.new_name)
+        .await
+        .map_err(map_node_service_error)?
+    {
+        if existing.id != id {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(ApiError::new(
+                    "COLLECTION_EXISTS",
+                    format!("Collection '{}' already exists", request.new_name),
+                )),
+            ));
+        }
+    }
+
+    let update = NodeUpdate {
+        content: Some(request.new_name),
+        ..Default::default()
+    };
+    let node = state
+        .node_service
+        .update_node(&id, request.version, update)
+        .await
+        .map_err(map_node_service_error)?;
+    let typed =
+        node_to_typed_value(node).map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(e)))?;
+    Ok(Json(typed))
+}
+
+/// Rename or move a collection by path, keeping its subtree
+///
+/// # HTTP Endpoint
+/// ```text
+/// POST /api/collections/rename-path
+/// ```
+///
+/// # Request Body
+/// ```json
+/// { "oldPath": "hr:policy", "newPath": "legal:policies" }
+/// ```
+async fn rename_collection_path(
+    State(state): State<AppState>,
+    Json(request): Json<RenameCollectionPathRequest>,
+) -> ApiResult<RenamedPath> {
+    let store = state.node_service.store();
+    let collection_service = CollectionService::new(store, &state.node_service);
+
+    let renamed = collection_service
+        .rename_path(&request.old_path, &request.new_path)
+        .await
+        .map_err(map_node_service_error)?;
+    Ok(Json(renamed))
+}
+
+/// Delete a collection (memberships are removed with it)
+///
+/// # HTTP Endpoint
+/// ```text
+/// DELETE /api/collections/:id
+/// ```
+///
+/// # Request Body
+/// ```json
+/// { "version": 3 }
+/// ```
+async fn delete_collection(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(request): Json<DeleteNodeRequest>,
+) -> ApiStatusResult {
+    state
+        .node_service
+        .delete_node(&id, request.version)
+        .await
+        .map_err(map_node_service_error)?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Add a node to a collection
+///
+/// # HTTP Endpoint
+/// ```text
+/// POST /api/collections/:id/members
+/// ```
+///
+/// # Request Body
+/// ```json
+/// { "nodeId": "node-123" }
+/// ```
+async fn add_node_to_collection(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(request): Json<CollectionMemberRequest>,
+) -> ApiStatusResult {
+    let store = state.node_service.store();
+    let collection_service = CollectionService::new(store, &state.node_service);
+
+    collection_service
+        .add_to_collection(&request.node_id, &id)
+        .await
+        .map_err(map_node_service_error)?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Remove a node from a collection
+///
+/// # HTTP Endpoint
+/// ```text
+/// DELETE /api/collections/:id/members/:node_id
+/// ```
+async fn remove_node_from_collection(
+    State(state): State<AppState>,
+    Path((id, node_id)): Path<(String, String)>,
+) -> ApiStatusResult {
+    let store = state.node_service.store();
+    let collection_service = CollectionService::new(store, &state.node_service);
+
+    collection_service
+        .remove_from_collection(&node_id, &id)
+        .await
+        .map_err(map_node_service_error)?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Move a member to another position within its collection
+///
+/// # HTTP Endpoint
+/// ```text
+/// POST /api/collections/:id/members/:node_id/reorder
+/// ```
+///
+/// # Request Body
+/// ```json
+/// { "insertAfterMemberId": "node-456" }
+/// ```
+/// `null` moves the member first.
+async fn reorder_collection_member(
+    State(state): State<AppState>,
+    Path((id, node_id)): Path<(String, String)>,
+    Json(request): Json<ReorderCollectionMemberRequest>,
+) -> ApiStatusResult {
+    let store = state.node_service.store();
+    let collection_service = CollectionService::new(store, &state.node_service);
+
+    collection_service
+        .reorder_collection_member(&id, &node_id, request.insert_after_member_id.as_deref())
+        .await
+        .map_err(map_node_service_error)?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// IDs of the collections a node belongs to
+///
+/// # HTTP Endpoint
+/// ```text
+/// GET /api/nodes/:id/collections
+/// ```
+async fn get_node_collections(
+    State(state): State<AppState>,
+    Path(node_id): Path<String>,
+) -> ApiResult<Vec<String>> {
+    let store = state.node_service.store();
+    let collection_service = CollectionService::new(store, &state.node_service);
+
+    let collection_ids = collection_service
+        .get_node_collections(&node_id)
+        .await
+        .map_err(map_node_service_error)?;
+    Ok(Json(collection_ids))
+}
+
+/// Add a node to the collection at a path, creating collections as needed
+///
+/// # HTTP Endpoint
+/// ```text
+/// POST /api/nodes/:id/collections
+/// ```
+///
+/// # Request Body
+/// ```json
+/// { "collectionPath": "hr:policy:vacation" }
+/// ```
+///
+/// # Response (200 OK)
+/// ID of the leaf collection
+async fn add_node_to_collection_path(
+    State(state): State<AppState>,
+    Path(node_id): Path<String>,
+    Json(request): Json<CollectionPathRequest>,
+) -> ApiResult<String> {
+    let store = state.node_service.store();
+    let collection_service = CollectionService::new(store, &state.node_service);
+
+    let resolved = collection_service
+        .add_to_collection_by_path(&node_id, &request.collection_path)
+        .await
+        .map_err(map_node_service_error)?;
+    Ok(Json(resolved.leaf_id().to_string()))
 }
@@ -0,0 +1,81 @@
+//! Seed a Synthetic Workspace for Manual Performance Testing
+//!
+//! Writes a generated workspace (see `nodespace_core::fixtures`) into the
+//! browser-mode SurrealDB, so the app can be tried against a realistically
+//! shaped workspace of any size. The same size and seed always give the same
+//! workspace, so a slowdown found this way can be reproduced.
+//!
+//! # Usage
+//!
+//! ```bash
+//! # Start SurrealDB first
+//! bun run dev:db
+//!
+//! # 10k nodes from seed 42 (the defaults)
+//! cargo run -p nodespace-dev-tools --bin seed-workspace
+//!
+//! # 100k nodes from seed 7
+//! cargo run -p nodespace-dev-tools --bin seed-workspace -- 100000 7
+//! ```
+//!
+//! Seeding again with the same seed updates the nodes in place rather than
+//! duplicating them.
+
+use nodespace_core::{
+    db::{HttpStore, DEFAULT_BULK_CHUNK_SIZE},
+    fixtures::{SyntheticWorkspace, WorkspaceShape},
+    services::NodeService,
+};
+use std::sync::Arc;
+use std::time::Instant;
+
+const DEFAULT_NODES: usize = 10_000;
+const DEFAULT_SEED: u64 = 42;
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let mut args = std::env::args().skip(1);
+    let nodes = match args.next() {
+        Some(nodes) => nodes.parse()?,
+        None => DEFAULT_NODES,
+    };
+    let seed = match args.next() {
+        Some(seed) => seed.parse()?,
+        None => DEFAULT_SEED,
+    };
+
+    println!("📡 Connecting to SurrealDB HTTP server on port 8000...");
+    let mut store =
+        match HttpStore::new_http("127.0.0.1:8000", "nodespace", "nodespace", "root", "root").await
+        {
+            Ok(s) => Arc::new(s),
+            Err(e) => {
+                eprintln!("❌ Failed to connect to SurrealDB: {}", e);
+                eprintln!("   Make sure SurrealDB server is running:");
+                eprintln!("   bun run dev:db");
+                return Err(e);
+            }
+        };
+
+    // Node types are only valid once their schemas are seeded
+    NodeService::new(&mut store)
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to initialize NodeService: {}", e))?;
+
+    let started = Instant::now();
+    let workspace = SyntheticWorkspace::generate(&WorkspaceShape::with_nodes(nodes), seed);
+    let documents = workspace.documents.len();
+    let collections = workspace.collections.len();
+    let edges = workspace.edges.len();
+    println!(
+        "🌱 Writing {} nodes ({} documents, {} collections) and {} edges from seed {}...",
+        nodes, documents, collections, edges, seed
+    );
+
+    let result = workspace.write_to(&store, DEFAULT_BULK_CHUNK_SIZE).await?;
+    if result.failed() > 0 {
+        eprintln!("⚠️  {} items failed to write", result.failed());
+    }
+    println!("✅ Seeded in {:.1}s", started.elapsed().as_secs_f64());
+    Ok(())
+}
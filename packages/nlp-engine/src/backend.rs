@@ -0,0 +1,247 @@
+/// Compute backend selection for llama.cpp models
+///
+/// Which GPU backends exist is decided at build time (the `metal`, `vulkan`
+/// and `cuda` features; Metal is always built on macOS) and whether one can
+/// be used at run time (llama.cpp reports whether it can offload layers).
+/// [`ComputeBackend::Auto`] takes the GPU when there is one; an explicit
+/// backend that isn't available falls back to the CPU with a warning rather
+/// than failing, so a bad setting never leaves the app without embeddings.
+///
+/// Some GPUs hang under llama.cpp; [`ComputeBackend::Cpu`] keeps every layer
+/// on the CPU for them.
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// Where model layers run
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ComputeBackend {
+    /// The GPU backend this build has, if the machine supports it, else CPU
+    #[default]
+    Auto,
+    Cpu,
+    Metal,
+    Vulkan,
+    Cuda,
+}
+
+impl ComputeBackend {
+    /// GPU backends in order of preference for [`ComputeBackend::Auto`]
+    const GPU_PREFERENCE: [ComputeBackend; 3] = [Self::Metal, Self::Cuda, Self::Vulkan];
+
+    /// Whether this build links the backend
+    pub fn is_compiled(self) -> bool {
+        match self {
+            Self::Auto | Self::Cpu => true,
+            Self::Metal => cfg!(all(
+                any(feature = "embedding-service", feature = "summarization"),
+                any(feature = "metal", target_os = "macos")
+            )),
+            Self::Vulkan => cfg!(feature = "vulkan"),
+            Self::Cuda => cfg!(feature = "cuda"),
+        }
+    }
+}
+
+impl fmt::Display for ComputeBackend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Auto => "auto",
+            Self::Cpu => "cpu",
+            Self::Metal => "metal",
+            Self::Vulkan => "vulkan",
+            Self::Cuda => "cuda",
+        };
+        f.write_str(name)
+    }
+}
+
+impl FromStr for ComputeBackend {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "auto" => Ok(Self::Auto),
+            "cpu" => Ok(Self::Cpu),
+            "metal" => Ok(Self::Metal),
+            "vulkan" => Ok(Self::Vulkan),
+            "cuda" => Ok(Self::Cuda),
+            other => Err(format!(
+                "Unknown backend '{}': expected auto, cpu, metal, vulkan or cuda",
+                other
+            )),
+        }
+    }
+}
+
+/// What the build and the machine can run
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackendCapabilities {
+    /// GPU backends this build links
+    pub compiled: Vec<ComputeBackend>,
+    /// Whether llama.cpp found a device to offload layers to
+    pub gpu_offload: bool,
+}
+
+impl BackendCapabilities {
+    /// Capabilities given whether llama.cpp can offload to a GPU
+    pub fn with_gpu_offload(gpu_offload: bool) -> Self {
+        Self {
+            compiled: ComputeBackend::GPU_PREFERENCE
+                .into_iter()
+                .filter(|backend| backend.is_compiled())
+                .collect(),
+            gpu_offload,
+        }
+    }
+
+    /// Capabilities of this build on this machine; initializes the llama.cpp
+    /// backend to ask it
+    pub fn detect() -> Self {
+        #[cfg(any(feature = "embedding-service", feature = "summarization"))]
+        {
+            match crate::embedding::get_or_init_backend() {
+                Ok(backend) => Self::with_gpu_offload(backend.supports_gpu_offload()),
+                Err(e) => {
+                    tracing::warn!("Could not detect GPU support: {}", e);
+                    Self::with_gpu_offload(false)
+                }
+            }
+        }
+        #[cfg(not(any(feature = "embedding-service", feature = "summarization")))]
+        {
+            Self::with_gpu_offload(false)
+        }
+    }
+
+    /// Backend and GPU layers to use for `requested`
+    pub fn resolve(&self, requested: ComputeBackend, n_gpu_layers: u32) -> BackendChoice {
+        let usable = |backend: &ComputeBackend| self.gpu_offload && self.compiled.contains(backend);
+        let (active, fallback_reason) = match requested {
+            ComputeBackend::Cpu => (ComputeBackend::Cpu, None),
+            ComputeBackend::Auto => match self.compiled.iter().find(|b| usable(b)) {
+                Some(gpu) => (*gpu, None),
+                None => (
+                    ComputeBackend::Cpu,
+                    Some("No usable GPU backend".to_string()),
+                ),
+            },
+            gpu if usable(&gpu) => (gpu, None),
+            gpu if !self.compiled.contains(&gpu) => (
+                ComputeBackend::Cpu,
+                Some(format!("{} is not included in this build", gpu)),
+            ),
+            gpu => (
+                ComputeBackend::Cpu,
+                Some(format!("{} is not supported on this machine", gpu)),
+            ),
+        };
+        // No layers on the GPU is CPU inference, whatever was asked for
+        let (active, gpu_layers) = match active {
+            ComputeBackend::Cpu => (ComputeBackend::Cpu, 0),
+            _ if n_gpu_layers == 0 => (ComputeBackend::Cpu, 0),
+            gpu => (gpu, n_gpu_layers),
+        };
+        BackendChoice {
+            requested,
+            active,
+            gpu_layers,
+            fallback_reason,
+        }
+    }
+}
+
+/// The backend a model runs on, and why if it isn't the one asked for
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackendChoice {
+    pub requested: ComputeBackend,
+    pub active: ComputeBackend,
+    /// Layers offloaded to the GPU, 0 on the CPU
+    pub gpu_layers: u32,
+    pub fallback_reason: Option<String>,
+}
+
+/// Diagnostics of a loaded model's backend
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackendReport {
+    #[serde(flatten)]
+    pub choice: BackendChoice,
+    pub capabilities: BackendCapabilities,
+    /// Threads for inference
+    pub threads: i32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gpu_build(gpu_offload: bool) -> BackendCapabilities {
+        BackendCapabilities {
+            compiled: vec![ComputeBackend::Metal],
+            gpu_offload,
+        }
+    }
+
+    #[test]
+    fn test_auto_prefers_an_available_gpu() {
+        let choice = gpu_build(true).resolve(ComputeBackend::Auto, 99);
+        assert_eq!(choice.active, ComputeBackend::Metal);
+        assert_eq!(choice.gpu_layers, 99);
+        assert_eq!(choice.fallback_reason, None);
+
+        let choice = gpu_build(false).resolve(ComputeBackend::Auto, 99);
+        assert_eq!(choice.active, ComputeBackend::Cpu);
+        assert_eq!(choice.gpu_layers, 0);
+        assert!(choice.fallback_reason.is_some());
+    }
+
+    #[test]
+    fn test_forced_cpu_offloads_nothing() {
+        let choice = gpu_build(true).resolve(ComputeBackend::Cpu, 99);
+        assert_eq!(choice.active, ComputeBackend::Cpu);
+        assert_eq!(choice.gpu_layers, 0);
+        assert_eq!(choice.fallback_reason, None);
+    }
+
+    #[test]
+    fn test_unavailable_backend_falls_back_to_cpu() {
+        let choice = gpu_build(true).resolve(ComputeBackend::Cuda, 99);
+        assert_eq!(choice.requested, ComputeBackend::Cuda);
+        assert_eq!(choice.active, ComputeBackend::Cpu);
+        assert_eq!(
+            choice.fallback_reason.as_deref(),
+            Some("cuda is not included in this build")
+        );
+
+        let choice = gpu_build(false).resolve(ComputeBackend::Metal, 99);
+        assert_eq!(choice.active, ComputeBackend::Cpu);
+        assert_eq!(
+            choice.fallback_reason.as_deref(),
+            Some("metal is not supported on this machine")
+        );
+    }
+
+    #[test]
+    fn test_zero_gpu_layers_is_cpu() {
+        let choice = gpu_build(true).resolve(ComputeBackend::Metal, 0);
+        assert_eq!(choice.active, ComputeBackend::Cpu);
+    }
+
+    #[test]
+    fn test_backend_parses_from_settings() {
+        assert_eq!("CPU".parse::<ComputeBackend>(), Ok(ComputeBackend::Cpu));
+        assert_eq!(
+            " vulkan ".parse::<ComputeBackend>(),
+            Ok(ComputeBackend::Vulkan)
+        );
+        assert!("opencl".parse::<ComputeBackend>().is_err());
+        assert_eq!(
+            serde_json::to_string(&ComputeBackend::Cuda).unwrap(),
+            "\"cuda\""
+        );
+    }
+}
@@ -0,0 +1,272 @@
+/// LRU cache of embeddings, bounded by entry count and by memory
+///
+/// Each entry is charged its key, its vector and a fixed per-entry overhead,
+/// so the reported byte count is an estimate of what the cache holds, close
+/// enough to size it for low-RAM machines. Inserting past either limit
+/// evicts the least recently used entries.
+///
+/// When more than [`EVICTION_PRESSURE_COUNT`] entries are evicted within
+/// [`EVICTION_PRESSURE_WINDOW`], the cache is too small for the working set
+/// and an [`EvictionPressure`] event goes to subscribers, at most once per
+/// window.
+use lru::LruCache;
+use serde::Serialize;
+use std::time::{Duration, Instant};
+use tokio::sync::broadcast;
+
+/// Memory the cache may use unless configured otherwise
+pub const DEFAULT_CACHE_MAX_BYTES: usize = 64 * 1024 * 1024;
+
+/// Evictions within [`EVICTION_PRESSURE_WINDOW`] that signal pressure
+pub const EVICTION_PRESSURE_COUNT: u64 = 500;
+
+pub const EVICTION_PRESSURE_WINDOW: Duration = Duration::from_secs(60);
+
+/// Bookkeeping per entry besides key and vector: the `String` and `Vec`
+/// headers plus the LRU list node and its hash table slot
+const ENTRY_OVERHEAD_BYTES: usize = 96;
+
+const PRESSURE_CHANNEL_CAPACITY: usize = 16;
+
+/// Size and effectiveness of the embedding cache
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmbeddingCacheStats {
+    pub entries: usize,
+    pub max_entries: usize,
+    /// Estimated memory held by the entries
+    pub bytes: usize,
+    pub max_bytes: usize,
+    pub hits: u64,
+    pub misses: u64,
+    pub evictions: u64,
+}
+
+/// The cache evicts faster than it is useful: it is too small
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EvictionPressure {
+    /// Evictions within the window
+    pub evictions: u64,
+    pub window_secs: u64,
+    pub bytes: usize,
+    pub max_bytes: usize,
+}
+
+pub(crate) struct EmbeddingCache {
+    entries: LruCache<String, Vec<f32>>,
+    max_entries: usize,
+    bytes: usize,
+    max_bytes: usize,
+    hits: u64,
+    misses: u64,
+    evictions: u64,
+    /// Start of the current pressure window and the evictions within it
+    window_start: Instant,
+    window_evictions: u64,
+    pressure_tx: broadcast::Sender<EvictionPressure>,
+}
+
+impl EmbeddingCache {
+    pub(crate) fn new(max_entries: usize, max_bytes: usize) -> Self {
+        let (pressure_tx, _) = broadcast::channel(PRESSURE_CHANNEL_CAPACITY);
+        Self {
+            entries: LruCache::unbounded(),
+            max_entries,
+            bytes: 0,
+            max_bytes,
+            hits: 0,
+            misses: 0,
+            evictions: 0,
+            window_start: Instant::now(),
+            window_evictions: 0,
+            pressure_tx,
+        }
+    }
+
+    pub(crate) fn get(&mut self, key: &str) -> Option<Vec<f32>> {
+        match self.entries.get(key) {
+            Some(embedding) => {
+                self.hits += 1;
+                Some(embedding.clone())
+            }
+            None => {
+                self.misses += 1;
+                None
+            }
+        }
+    }
+
+    /// Cache `embedding`, evicting least recently used entries to stay in
+    /// bounds; one larger than the whole budget is not cached
+    pub(crate) fn put(&mut self, key: String, embedding: Vec<f32>) {
+        let size = entry_bytes(&key, &embedding);
+        if size > self.max_bytes {
+            return;
+        }
+        if let Some(old) = self.entries.peek(&key) {
+            self.bytes -= entry_bytes(&key, old);
+        }
+        self.entries.put(key, embedding);
+        self.bytes += size;
+
+        let mut evicted = 0;
+        while self.entries.len() > self.max_entries || self.bytes > self.max_bytes {
+            let Some((key, embedding)) = self.entries.pop_lru() else {
+                break;
+            };
+            self.bytes -= entry_bytes(&key, &embedding);
+            evicted += 1;
+        }
+        if evicted > 0 {
+            self.record_evictions(evicted);
+        }
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.entries.clear();
+        self.bytes = 0;
+    }
+
+    pub(crate) fn stats(&self) -> EmbeddingCacheStats {
+        EmbeddingCacheStats {
+            entries: self.entries.len(),
+            max_entries: self.max_entries,
+            bytes: self.bytes,
+            max_bytes: self.max_bytes,
+            hits: self.hits,
+            misses: self.misses,
+            evictions: self.evictions,
+        }
+    }
+
+    pub(crate) fn subscribe(&self) -> broadcast::Receiver<EvictionPressure> {
+        self.pressure_tx.subscribe()
+    }
+
+    fn record_evictions(&mut self, evicted: u64) {
+        self.evictions += evicted;
+
+        let now = Instant::now();
+        if now.duration_since(self.window_start) > EVICTION_PRESSURE_WINDOW {
+            self.window_start = now;
+            self.window_evictions = 0;
+        }
+        let before = self.window_evictions;
+        self.window_evictions += evicted;
+
+        // Only the batch crossing the threshold reports, once per window
+        if before <= EVICTION_PRESSURE_COUNT && self.window_evictions > EVICTION_PRESSURE_COUNT {
+            let pressure = EvictionPressure {
+                evictions: self.window_evictions,
+                window_secs: EVICTION_PRESSURE_WINDOW.as_secs(),
+                bytes: self.bytes,
+                max_bytes: self.max_bytes,
+            };
+            tracing::warn!(
+                "Embedding cache evicted {} entries within {}s; {} of {} bytes used",
+                pressure.evictions,
+                pressure.window_secs,
+                pressure.bytes,
+                pressure.max_bytes
+            );
+            // No subscribers is fine
+            let _ = self.pressure_tx.send(pressure);
+        }
+    }
+}
+
+/// Estimated memory of one entry
+fn entry_bytes(key: &str, embedding: &[f32]) -> usize {
+    key.len() + std::mem::size_of_val(embedding) + ENTRY_OVERHEAD_BYTES
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn embedding(dimension: usize) -> Vec<f32> {
+        vec![0.5; dimension]
+    }
+
+    #[test]
+    fn test_bytes_track_inserts_and_replacements() {
+        let mut cache = EmbeddingCache::new(100, DEFAULT_CACHE_MAX_BYTES);
+        cache.put("a".to_string(), embedding(768));
+        cache.put("b".to_string(), embedding(768));
+        let two = cache.stats().bytes;
+        assert_eq!(two, 2 * (1 + 768 * 4 + ENTRY_OVERHEAD_BYTES));
+
+        // Replacing an entry charges it once
+        cache.put("a".to_string(), embedding(768));
+        assert_eq!(cache.stats().bytes, two);
+
+        cache.clear();
+        assert_eq!(cache.stats().bytes, 0);
+        assert_eq!(cache.stats().entries, 0);
+    }
+
+    #[test]
+    fn test_byte_budget_evicts_least_recently_used() {
+        let entry = entry_bytes("k0", &embedding(256));
+        let mut cache = EmbeddingCache::new(100, 3 * entry);
+        for i in 0..3 {
+            cache.put(format!("k{}", i), embedding(256));
+        }
+        // Touch k0 so k1 is the least recently used
+        assert!(cache.get("k0").is_some());
+        cache.put("k3".to_string(), embedding(256));
+
+        let stats = cache.stats();
+        assert_eq!(stats.entries, 3);
+        assert!(stats.bytes <= stats.max_bytes);
+        assert_eq!(stats.evictions, 1);
+        assert!(cache.get("k1").is_none());
+        assert!(cache.get("k0").is_some());
+
+        // An entry larger than the whole budget is not cached
+        cache.put("huge".to_string(), embedding(4096));
+        assert!(cache.get("huge").is_none());
+        assert_eq!(cache.stats().entries, 3);
+    }
+
+    #[test]
+    fn test_entry_count_still_bounds_the_cache() {
+        let mut cache = EmbeddingCache::new(2, DEFAULT_CACHE_MAX_BYTES);
+        for i in 0..5 {
+            cache.put(format!("k{}", i), embedding(8));
+        }
+        let stats = cache.stats();
+        assert_eq!(stats.entries, 2);
+        assert_eq!(stats.evictions, 3);
+    }
+
+    #[test]
+    fn test_hits_and_misses_are_counted() {
+        let mut cache = EmbeddingCache::new(10, DEFAULT_CACHE_MAX_BYTES);
+        cache.put("a".to_string(), embedding(4));
+        cache.get("a");
+        cache.get("b");
+        let stats = cache.stats();
+        assert_eq!((stats.hits, stats.misses), (1, 1));
+    }
+
+    #[test]
+    fn test_sustained_eviction_reports_pressure_once_per_window() {
+        let mut cache = EmbeddingCache::new(1, DEFAULT_CACHE_MAX_BYTES);
+        let mut pressure = cache.subscribe();
+
+        for i in 0..=EVICTION_PRESSURE_COUNT {
+            cache.put(format!("k{}", i), embedding(4));
+        }
+        assert!(pressure.try_recv().is_err());
+
+        cache.put("one more".to_string(), embedding(4));
+        let event = pressure.try_recv().unwrap();
+        assert_eq!(event.evictions, EVICTION_PRESSURE_COUNT + 1);
+        assert_eq!(event.window_secs, EVICTION_PRESSURE_WINDOW.as_secs());
+
+        cache.put("and another".to_string(), embedding(4));
+        assert!(pressure.try_recv().is_err());
+    }
+}
@@ -1,4 +1,6 @@
-/// Configuration for the embedding service using llama.cpp
+/// Configuration for the llama.cpp embedding and summarization services
+use crate::backend::ComputeBackend;
+use crate::cache::DEFAULT_CACHE_MAX_BYTES;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
@@ -6,6 +8,15 @@ use std::path::PathBuf;
 /// where any value >= total layers offloads everything.
 pub const GPU_OFFLOAD_ALL_LAYERS: u32 = 99;
 
+/// Environment variable overriding the embedding cache's memory, in MiB
+pub const CACHE_MAX_MB_ENV: &str = "NODESPACE_EMBEDDING_CACHE_MB";
+
+/// Environment variable selecting the compute backend (`auto`, `cpu`, `metal`, ...)
+pub const BACKEND_ENV: &str = "NODESPACE_EMBEDDING_BACKEND";
+
+/// Environment variable overriding the inference thread count
+pub const THREADS_ENV: &str = "NODESPACE_EMBEDDING_THREADS";
+
 /// Configuration for llama.cpp embedding model
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EmbeddingConfig {
@@ -15,6 +26,9 @@ pub struct EmbeddingConfig {
     /// Local model path (GGUF file)
     pub model_path: Option<PathBuf>,
 
+    /// Where to run the model; unavailable backends fall back to the CPU
+    pub backend: ComputeBackend,
+
     /// Number of GPU layers to offload. Use `GPU_OFFLOAD_ALL_LAYERS` (99) to offload all.
     pub n_gpu_layers: u32,
 
@@ -26,6 +40,9 @@ pub struct EmbeddingConfig {
 
     /// Maximum cache size (number of embeddings to cache)
     pub cache_capacity: usize,
+
+    /// Maximum memory the cache may hold, in bytes (estimated)
+    pub cache_max_bytes: usize,
 }
 
 impl Default for EmbeddingConfig {
@@ -33,12 +50,14 @@ impl Default for EmbeddingConfig {
         Self {
             model_name: "nomic-embed-text-v1.5".to_string(),
             model_path: None,
+            backend: ComputeBackend::Auto,
             n_gpu_layers: GPU_OFFLOAD_ALL_LAYERS,
             context_size: 8192,
             n_threads: std::thread::available_parallelism()
                 .map(|p| p.get() as i32)
                 .unwrap_or(4),
             cache_capacity: 10000,
+            cache_max_bytes: DEFAULT_CACHE_MAX_BYTES,
         }
     }
 }
@@ -50,42 +69,123 @@ impl EmbeddingConfig {
     /// - macOS/Linux: ~/.nodespace/models/nomic-embed-text-v1.5.Q8_0.gguf
     /// - Windows: %USERPROFILE%\.nodespace\models\nomic-embed-text-v1.5.Q8_0.gguf
     pub fn resolve_model_path(&self) -> Result<PathBuf, std::io::Error> {
-        if let Some(path) = &self.model_path {
-            if path.exists() {
-                return Ok(path.clone());
-            }
+        resolve_model_path(
+            self.model_path.as_ref(),
+            &self.model_name,
+            &["f16"],
+            "nomic-embed-text",
+        )
+    }
+
+    /// Validate configuration
+    pub fn validate(&self) -> Result<(), String> {
+        if self.model_name.is_empty() {
+            return Err("model_name cannot be empty".to_string());
         }
 
-        // Use centralized ~/.nodespace/models/ directory
-        let home_dir = dirs::home_dir().ok_or_else(|| {
-            std::io::Error::new(
-                std::io::ErrorKind::NotFound,
-                "Cannot determine home directory",
-            )
-        })?;
-
-        // Try multiple possible filenames
-        let base_path = home_dir.join(".nodespace").join("models");
-        let possible_names = [
-            format!("{}.gguf", sanitize_model_name(&self.model_name)),
-            format!("{}.Q8_0.gguf", sanitize_model_name(&self.model_name)),
-            format!("{}.f16.gguf", sanitize_model_name(&self.model_name)),
-        ];
-
-        for name in &possible_names {
-            let model_path = base_path.join(name);
-            if model_path.exists() {
-                return Ok(model_path);
+        if self.context_size == 0 {
+            return Err("context_size must be greater than 0".to_string());
+        }
+
+        if self.n_threads <= 0 {
+            return Err("n_threads must be greater than 0".to_string());
+        }
+
+        if self.cache_capacity == 0 {
+            return Err("cache_capacity must be greater than 0".to_string());
+        }
+
+        if self.cache_max_bytes == 0 {
+            return Err("cache_max_bytes must be greater than 0".to_string());
+        }
+
+        Ok(())
+    }
+
+    /// Apply settings from the environment: `NODESPACE_EMBEDDING_CACHE_MB`
+    /// caps the cache's memory, for low-RAM machines;
+    /// `NODESPACE_EMBEDDING_BACKEND` and `NODESPACE_EMBEDDING_THREADS` choose
+    /// where and on how many threads the model runs. Invalid values are
+    /// ignored with a warning.
+    pub fn with_env_overrides(mut self) -> Self {
+        if let Some(mb) = std::env::var(CACHE_MAX_MB_ENV)
+            .ok()
+            .and_then(|mb| mb.parse::<usize>().ok())
+            .filter(|mb| *mb > 0)
+        {
+            self.cache_max_bytes = mb.saturating_mul(1024 * 1024);
+        }
+        if let Ok(backend) = std::env::var(BACKEND_ENV) {
+            match backend.parse() {
+                Ok(backend) => self.backend = backend,
+                Err(e) => tracing::warn!("Ignoring {}: {}", BACKEND_ENV, e),
             }
         }
+        if let Ok(threads) = std::env::var(THREADS_ENV) {
+            match threads.parse::<i32>() {
+                Ok(threads) if threads > 0 => self.n_threads = threads,
+                _ => tracing::warn!(
+                    "Ignoring {}: '{}' is not a thread count",
+                    THREADS_ENV,
+                    threads
+                ),
+            }
+        }
+        self
+    }
+}
 
-        Err(std::io::Error::new(
-            std::io::ErrorKind::NotFound,
-            format!(
-                "Model not found. Please download nomic-embed-text GGUF to ~/.nodespace/models/. Tried: {:?}",
-                possible_names
-            ),
-        ))
+/// Configuration for the local generation model used for summaries
+///
+/// Any small instruction-tuned GGUF model works; without one the
+/// summarization service falls back to extractive summaries.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SummarizationConfig {
+    /// Model name or identifier
+    pub model_name: String,
+
+    /// Local model path (GGUF file)
+    pub model_path: Option<PathBuf>,
+
+    /// Where to run the model; unavailable backends fall back to the CPU
+    pub backend: ComputeBackend,
+
+    /// Number of GPU layers to offload. Use `GPU_OFFLOAD_ALL_LAYERS` (99) to offload all.
+    pub n_gpu_layers: u32,
+
+    /// Context size shared by the prompt and the generated summary
+    pub context_size: u32,
+
+    /// Number of threads for CPU inference
+    pub n_threads: i32,
+}
+
+impl Default for SummarizationConfig {
+    fn default() -> Self {
+        Self {
+            model_name: "qwen2.5-0.5b-instruct".to_string(),
+            model_path: None,
+            backend: ComputeBackend::Auto,
+            n_gpu_layers: GPU_OFFLOAD_ALL_LAYERS,
+            context_size: 4096,
+            n_threads: std::thread::available_parallelism()
+                .map(|p| p.get() as i32)
+                .unwrap_or(4),
+        }
+    }
+}
+
+impl SummarizationConfig {
+    /// Get the model path, resolving it from ~/.nodespace/models/
+    ///
+    /// Looks for `<model_name>.gguf`, then the `Q8_0` and `Q4_K_M` quantizations.
+    pub fn resolve_model_path(&self) -> Result<PathBuf, std::io::Error> {
+        resolve_model_path(
+            self.model_path.as_ref(),
+            &self.model_name,
+            &["Q4_K_M"],
+            "an instruction-tuned",
+        )
     }
 
     /// Validate configuration
@@ -94,18 +194,67 @@ impl EmbeddingConfig {
             return Err("model_name cannot be empty".to_string());
         }
 
-        if self.context_size == 0 {
-            return Err("context_size must be greater than 0".to_string());
+        if self.context_size < 512 {
+            return Err("context_size must be at least 512".to_string());
         }
 
-        if self.cache_capacity == 0 {
-            return Err("cache_capacity must be greater than 0".to_string());
+        if self.n_threads <= 0 {
+            return Err("n_threads must be greater than 0".to_string());
         }
 
         Ok(())
     }
 }
 
+/// Find a GGUF model: `explicit` if it exists, else the first of
+/// `<name>.gguf`, `<name>.Q8_0.gguf` and `<name>.<quantization>.gguf` for
+/// each of `extra_quantizations` under ~/.nodespace/models/
+fn resolve_model_path(
+    explicit: Option<&PathBuf>,
+    model_name: &str,
+    extra_quantizations: &[&str],
+    download_hint: &str,
+) -> Result<PathBuf, std::io::Error> {
+    if let Some(path) = explicit {
+        if path.exists() {
+            return Ok(path.clone());
+        }
+    }
+
+    // Use centralized ~/.nodespace/models/ directory
+    let home_dir = dirs::home_dir().ok_or_else(|| {
+        std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            "Cannot determine home directory",
+        )
+    })?;
+
+    // Try multiple possible filenames
+    let base_path = home_dir.join(".nodespace").join("models");
+    let name = sanitize_model_name(model_name);
+    let mut possible_names = vec![format!("{}.gguf", name), format!("{}.Q8_0.gguf", name)];
+    possible_names.extend(
+        extra_quantizations
+            .iter()
+            .map(|quantization| format!("{}.{}.gguf", name, quantization)),
+    );
+
+    for name in &possible_names {
+        let model_path = base_path.join(name);
+        if model_path.exists() {
+            return Ok(model_path);
+        }
+    }
+
+    Err(std::io::Error::new(
+        std::io::ErrorKind::NotFound,
+        format!(
+            "Model not found. Please download {} GGUF to ~/.nodespace/models/. Tried: {:?}",
+            download_hint, possible_names
+        ),
+    ))
+}
+
 /// Sanitize model name to be filesystem-safe
 fn sanitize_model_name(name: &str) -> String {
     name.chars()
@@ -128,6 +277,8 @@ mod tests {
         assert_eq!(config.n_gpu_layers, GPU_OFFLOAD_ALL_LAYERS);
         assert_eq!(config.context_size, 8192);
         assert_eq!(config.cache_capacity, 10000);
+        assert_eq!(config.cache_max_bytes, DEFAULT_CACHE_MAX_BYTES);
+        assert_eq!(config.backend, ComputeBackend::Auto);
     }
 
     #[test]
@@ -150,6 +301,25 @@ mod tests {
         config.context_size = 8192;
         config.cache_capacity = 0;
         assert!(config.validate().is_err());
+
+        // Invalid: zero cache memory
+        config.cache_capacity = 10000;
+        config.cache_max_bytes = 0;
+        assert!(config.validate().is_err());
+
+        // Invalid: no threads
+        config.cache_max_bytes = DEFAULT_CACHE_MAX_BYTES;
+        config.n_threads = 0;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_summarization_config_validation() {
+        let mut config = SummarizationConfig::default();
+        assert!(config.validate().is_ok());
+
+        config.context_size = 256;
+        assert!(config.validate().is_err());
     }
 
     #[test]
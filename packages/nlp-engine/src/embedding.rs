@@ -9,11 +9,12 @@
 /// The service reuses LlamaContext across embedding calls to avoid the overhead
 /// of Metal kernel compilation on each call. The context is created once during
 /// initialization and reused for all subsequent embeddings.
+use crate::backend::BackendReport;
+use crate::cache::{EmbeddingCache, EmbeddingCacheStats, EvictionPressure};
 use crate::config::EmbeddingConfig;
 use crate::error::{EmbeddingError, Result};
-use lru::LruCache;
-use std::num::NonZeroUsize;
 use std::sync::{Arc, Mutex};
+use tokio::sync::broadcast;
 
 /// Embedding vector dimension for nomic-embed-vision-v1.5
 pub const EMBEDDING_DIMENSION: usize = 768;
@@ -22,11 +23,13 @@ pub const EMBEDDING_DIMENSION: usize = 768;
 const SEARCH_DOCUMENT_PREFIX: &str = "search_document: ";
 const SEARCH_QUERY_PREFIX: &str = "search_query: ";
 
+#[cfg(feature = "embedding-service")]
+use crate::backend::{BackendCapabilities, ComputeBackend};
 #[cfg(feature = "embedding-service")]
 use llama_cpp_2::context::params::LlamaContextParams;
 #[cfg(feature = "embedding-service")]
 use llama_cpp_2::context::LlamaContext;
-#[cfg(feature = "embedding-service")]
+#[cfg(any(feature = "embedding-service", feature = "summarization"))]
 use llama_cpp_2::llama_backend::LlamaBackend;
 #[cfg(feature = "embedding-service")]
 use llama_cpp_2::llama_batch::LlamaBatch;
@@ -44,8 +47,9 @@ use llama_cpp_2::model::{AddBos, LlamaModel};
 ///
 /// The llama.cpp backend can only be initialized once per process.
 /// The Mutex ensures thread-safe initialization and allows multiple EmbeddingService
-/// instances to share the same backend (important for tests running in parallel).
-#[cfg(feature = "embedding-service")]
+/// and SummarizationService instances to share the same backend (important for
+/// tests running in parallel).
+#[cfg(any(feature = "embedding-service", feature = "summarization"))]
 static LLAMA_BACKEND: Mutex<Option<LlamaBackend>> = Mutex::new(None);
 
 /// Initialize or get the global llama backend.
@@ -57,8 +61,8 @@ static LLAMA_BACKEND: Mutex<Option<LlamaBackend>> = Mutex::new(None);
 ///
 /// The returned `BackendGuard` must be held for the duration of backend usage
 /// (e.g., model loading, context creation).
-#[cfg(feature = "embedding-service")]
-fn get_or_init_backend() -> Result<BackendGuard> {
+#[cfg(any(feature = "embedding-service", feature = "summarization"))]
+pub(crate) fn get_or_init_backend() -> Result<BackendGuard> {
     use llama_cpp_2::LlamaCppError;
 
     let mut guard = LLAMA_BACKEND.lock().unwrap_or_else(|p| p.into_inner());
@@ -100,10 +104,10 @@ fn get_or_init_backend() -> Result<BackendGuard> {
 ///
 /// Dereferences to `&LlamaBackend` for convenient use with llama.cpp APIs.
 /// The lock is released when the guard is dropped.
-#[cfg(feature = "embedding-service")]
-struct BackendGuard(std::sync::MutexGuard<'static, Option<LlamaBackend>>);
+#[cfg(any(feature = "embedding-service", feature = "summarization"))]
+pub(crate) struct BackendGuard(std::sync::MutexGuard<'static, Option<LlamaBackend>>);
 
-#[cfg(feature = "embedding-service")]
+#[cfg(any(feature = "embedding-service", feature = "summarization"))]
 impl std::ops::Deref for BackendGuard {
     type Target = LlamaBackend;
 
@@ -120,9 +124,9 @@ impl std::ops::Deref for BackendGuard {
 /// rather than letting `__cxa_finalize_ranges` destroy it during static destruction
 /// when Metal/GPU state may already be partially torn down.
 ///
-/// Must be called AFTER all `LlamaState` instances (models, contexts) are dropped.
+/// Must be called AFTER all models and contexts (embedding and summarization) are dropped.
 /// Safe to call multiple times (idempotent).
-#[cfg(feature = "embedding-service")]
+#[cfg(any(feature = "embedding-service", feature = "summarization"))]
 pub fn release_llama_backend() {
     let mut guard = LLAMA_BACKEND.lock().unwrap_or_else(|p| p.into_inner());
     if guard.take().is_some() {
@@ -130,10 +134,10 @@ pub fn release_llama_backend() {
     }
 }
 
-/// Explicitly release the global llama backend (no-op when embedding-service feature is disabled).
-#[cfg(not(feature = "embedding-service"))]
+/// Explicitly release the global llama backend (no-op when no llama.cpp feature is enabled).
+#[cfg(not(any(feature = "embedding-service", feature = "summarization")))]
 pub fn release_llama_backend() {
-    // No-op: no backend to release when embedding-service feature is disabled
+    // No-op: no backend to release without llama.cpp
 }
 
 /// Wrapper to hold model and context together with proper lifetimes.
@@ -208,6 +212,7 @@ impl LlamaState {
                 .with_n_ctx(std::num::NonZeroU32::new(self.context_size))
                 .with_n_batch(required_batch_size)
                 .with_n_ubatch(required_batch_size)
+                .with_n_threads(self.n_threads)
                 .with_n_threads_batch(self.n_threads)
                 .with_embeddings(true);
 
@@ -226,10 +231,7 @@ impl LlamaState {
             self.context = Some(ctx);
             self.current_batch_size = required_batch_size;
 
-            tracing::info!(
-                "Context created with batch_size={} - Metal kernels compiled",
-                required_batch_size
-            );
+            tracing::info!("Context created with batch_size={}", required_batch_size);
         }
 
         Ok(self.context.as_mut().unwrap())
@@ -252,7 +254,9 @@ pub struct EmbeddingService {
     /// for cleanup without requiring &mut self (needed for Arc<EmbeddingService>).
     #[cfg(feature = "embedding-service")]
     state: Mutex<Option<LlamaState>>,
-    cache: Arc<Mutex<LruCache<String, Vec<f32>>>>,
+    cache: Arc<Mutex<EmbeddingCache>>,
+    /// Backend the loaded model runs on
+    backend: Option<BackendReport>,
     initialized: bool,
     #[cfg(feature = "embedding-service")]
     embedding_dimension: usize,
@@ -263,14 +267,14 @@ impl EmbeddingService {
     pub fn new(config: EmbeddingConfig) -> Result<Self> {
         config.validate().map_err(EmbeddingError::ConfigError)?;
 
-        let cache_capacity = NonZeroUsize::new(config.cache_capacity)
-            .ok_or_else(|| EmbeddingError::ConfigError("cache_capacity must be > 0".to_string()))?;
+        let cache = EmbeddingCache::new(config.cache_capacity, config.cache_max_bytes);
 
         Ok(Self {
             config,
             #[cfg(feature = "embedding-service")]
             state: Mutex::new(None),
-            cache: Arc::new(Mutex::new(LruCache::new(cache_capacity))),
+            cache: Arc::new(Mutex::new(cache)),
+            backend: None,
             initialized: false,
             #[cfg(feature = "embedding-service")]
             embedding_dimension: EMBEDDING_DIMENSION,
@@ -313,9 +317,24 @@ impl EmbeddingService {
             // BackendGuard holds the Mutex lock for the duration of model loading
             let backend = get_or_init_backend()?;
 
-            // Load model with GPU offloading
-            let model_params =
-                LlamaModelParams::default().with_n_gpu_layers(self.config.n_gpu_layers);
+            // Offload to the configured GPU backend, or stay on the CPU
+            let capabilities =
+                BackendCapabilities::with_gpu_offload(backend.supports_gpu_offload());
+            let choice = capabilities.resolve(self.config.backend, self.config.n_gpu_layers);
+            match &choice.fallback_reason {
+                Some(reason) if choice.requested != ComputeBackend::Auto => tracing::warn!(
+                    "Embedding backend {} unavailable ({}), using the CPU",
+                    choice.requested,
+                    reason
+                ),
+                _ => tracing::info!(
+                    "Embedding backend: {} ({} GPU layers, {} threads)",
+                    choice.active,
+                    choice.gpu_layers,
+                    self.config.n_threads
+                ),
+            }
+            let model_params = LlamaModelParams::default().with_n_gpu_layers(choice.gpu_layers);
 
             let model = LlamaModel::load_from_file(&backend, &model_path, &model_params)
                 .map_err(|e| EmbeddingError::ModelLoadError(format!("Model load failed: {}", e)))?;
@@ -331,6 +350,11 @@ impl EmbeddingService {
             // (backend is a global singleton, accessed via get_or_init_backend())
             let state = LlamaState::new(model, self.config.context_size, self.config.n_threads);
             *self.state.lock().unwrap_or_else(|p| p.into_inner()) = Some(state);
+            self.backend = Some(BackendReport {
+                choice,
+                capabilities,
+                threads: self.config.n_threads,
+            });
 
             tracing::info!(
                 "Embedding service initialized with persistent context (Issue #776 optimization)"
@@ -419,7 +443,7 @@ impl EmbeddingService {
         {
             let mut cache = self.cache.lock().unwrap_or_else(|p| p.into_inner());
             if let Some(cached) = cache.get(cache_key) {
-                return Ok(cached.clone());
+                return Ok(cached);
             }
         }
 
@@ -571,10 +595,17 @@ impl EmbeddingService {
         cache.clear();
     }
 
-    /// Get cache statistics (size, capacity)
-    pub fn cache_stats(&self) -> (usize, usize) {
+    /// Get cache statistics: entries and estimated memory against their
+    /// limits, hits, misses and evictions
+    pub fn cache_stats(&self) -> EmbeddingCacheStats {
+        let cache = self.cache.lock().unwrap_or_else(|p| p.into_inner());
+        cache.stats()
+    }
+
+    /// Receive an event whenever the cache evicts faster than it is useful
+    pub fn subscribe_cache_pressure(&self) -> broadcast::Receiver<EvictionPressure> {
         let cache = self.cache.lock().unwrap_or_else(|p| p.into_inner());
-        (cache.len(), cache.cap().get())
+        cache.subscribe()
     }
 
     /// Check if service is initialized
@@ -607,6 +638,7 @@ impl EmbeddingService {
         // Clear cache
         self.clear_cache();
 
+        self.backend = None;
         self.initialized = false;
         tracing::info!("Embedding service shutdown complete");
     }
@@ -652,14 +684,22 @@ impl EmbeddingService {
         }
     }
 
+    /// Backend the model runs on, `None` until a model is loaded
+    pub fn backend_report(&self) -> Option<&BackendReport> {
+        self.backend.as_ref()
+    }
+
     /// Get device information
     pub fn device_info(&self) -> String {
         #[cfg(feature = "embedding-service")]
         {
-            if self.initialized {
-                format!("llama.cpp (GPU layers: {})", self.config.n_gpu_layers)
-            } else {
-                "llama.cpp (not initialized)".to_string()
+            match (&self.backend, self.initialized) {
+                (Some(report), _) => format!(
+                    "llama.cpp ({}, GPU layers: {})",
+                    report.choice.active, report.choice.gpu_layers
+                ),
+                (None, true) => "llama.cpp (no model)".to_string(),
+                (None, false) => "llama.cpp (not initialized)".to_string(),
             }
         }
         #[cfg(not(feature = "embedding-service"))]
@@ -699,9 +739,11 @@ mod tests {
     fn test_cache_stats() {
         let config = EmbeddingConfig::default();
         let service = EmbeddingService::new(config).unwrap();
-        let (len, capacity) = service.cache_stats();
-        assert_eq!(len, 0);
-        assert!(capacity > 0);
+        let stats = service.cache_stats();
+        assert_eq!(stats.entries, 0);
+        assert_eq!(stats.bytes, 0);
+        assert!(stats.max_entries > 0);
+        assert!(stats.max_bytes > 0);
     }
 
     #[test]
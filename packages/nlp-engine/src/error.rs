@@ -29,6 +29,12 @@ pub enum EmbeddingError {
 
     #[error("Device initialization failed: {0}")]
     DeviceError(String),
+
+    #[error("Model download failed: {0}")]
+    DownloadError(String),
+
+    #[error("Model checksum mismatch: expected {expected}, got {actual}")]
+    ChecksumMismatch { expected: String, actual: String },
 }
 
 pub type Result<T> = std::result::Result<T, EmbeddingError>;
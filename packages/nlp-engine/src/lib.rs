@@ -1,15 +1,19 @@
-/// NodeSpace NLP Engine - Unified Vector Embedding Service
+/// NodeSpace NLP Engine - Embeddings and Summaries
 ///
 /// This crate provides a high-performance embedding service using llama.cpp
-/// with nomic-embed-vision for semantic search across NodeSpace knowledge graphs.
+/// with nomic-embed-vision for semantic search across NodeSpace knowledge graphs,
+/// and an optional [`SummarizationService`] that writes short summaries with a
+/// small local generation model.
 ///
 /// # Features
 ///
 /// - **Local Model Bundling**: GGUF models bundled with application, no network required
-/// - **Metal GPU Acceleration**: Native Metal support on macOS via llama.cpp
-/// - **Efficient Caching**: LRU cache with automatic eviction for <5ms cache hits
+/// - **Model Downloads**: Resumable, SHA-256 verified installs of model versions side by side
+/// - **GPU Acceleration**: Metal, Vulkan or CUDA via llama.cpp, with CPU fallback
+/// - **Efficient Caching**: LRU cache bounded by entries and memory for <5ms cache hits
 /// - **Asymmetric Embeddings**: Separate prefixes for documents vs queries
 /// - **Vision Ready**: Foundation for future multimodal embedding support
+/// - **Offline Summaries**: GGUF instruction model, extractive fallback without one
 ///
 /// # Example
 ///
@@ -33,11 +37,19 @@
 ///     Ok(())
 /// }
 /// ```
+pub mod backend;
+pub mod cache;
 pub mod config;
 pub mod embedding;
 pub mod error;
+pub mod model_manager;
+pub mod summarization;
 
 // Re-export main types
-pub use config::EmbeddingConfig;
+pub use backend::{BackendCapabilities, BackendChoice, BackendReport, ComputeBackend};
+pub use cache::{EmbeddingCacheStats, EvictionPressure};
+pub use config::{EmbeddingConfig, SummarizationConfig};
 pub use embedding::{release_llama_backend, EmbeddingService, EMBEDDING_DIMENSION};
 pub use error::{EmbeddingError, Result};
+pub use model_manager::{DownloadProgress, DownloadStage, InstalledModel, ModelManager, ModelSpec};
+pub use summarization::SummarizationService;
@@ -0,0 +1,508 @@
+/// Downloads and installs GGUF models under ~/.nodespace/models/
+///
+/// Each model version gets its own directory, `<name>/<version>/<name>.gguf`,
+/// so a new version can be installed and tried while the previous one stays
+/// in place until it is removed.
+///
+/// Downloads go to a `.part` file next to the target and resume from it with
+/// an HTTP range request when interrupted (the app quit, the network dropped,
+/// the future was dropped). The file only takes its final name once its
+/// SHA-256 matches the [`ModelSpec`], so a model on disk is always a whole,
+/// verified one; a mismatch deletes the partial file so the next attempt
+/// starts clean.
+///
+/// Names and versions come from the UI, so each must be a single plain path
+/// component; anything that could step outside the models directory is
+/// rejected before a file is written or deleted.
+use crate::error::{EmbeddingError, Result};
+use reqwest::header::{CONTENT_LENGTH, RANGE};
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::{Component, Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+use tokio::fs::{self, File, OpenOptions};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::sync::Mutex;
+
+/// Least time between two `Downloading` progress reports
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(250);
+
+const PARTIAL_EXTENSION: &str = "part";
+
+/// A model version to install, with the checksum it must match
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelSpec {
+    /// Model name, e.g. `nomic-embed-text-v1.5`
+    pub name: String,
+    /// Version or quantization, e.g. `Q8_0`
+    pub version: String,
+    pub url: String,
+    /// Expected SHA-256 of the file, hex encoded
+    pub sha256: String,
+}
+
+/// A model version on disk
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstalledModel {
+    pub name: String,
+    pub version: String,
+    pub path: PathBuf,
+    pub size_bytes: u64,
+    #[serde(skip)]
+    pub installed_at: SystemTime,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DownloadStage {
+    Downloading,
+    Verifying,
+    Installed,
+}
+
+/// Progress of a model download, for the UI
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DownloadProgress {
+    pub name: String,
+    pub version: String,
+    pub stage: DownloadStage,
+    pub downloaded_bytes: u64,
+    /// Size of the file, when the server says
+    pub total_bytes: Option<u64>,
+    /// Bytes that were already on disk from an earlier attempt
+    pub resumed_from: u64,
+}
+
+/// Installs, lists and removes model versions in one directory
+pub struct ModelManager {
+    models_dir: PathBuf,
+    client: reqwest::Client,
+    /// One download at a time: two of the same file would share a `.part`
+    downloads: Mutex<()>,
+}
+
+impl ModelManager {
+    pub fn new(models_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            models_dir: models_dir.into(),
+            client: reqwest::Client::new(),
+            downloads: Mutex::new(()),
+        }
+    }
+
+    /// A manager for ~/.nodespace/models/
+    pub fn in_default_dir() -> Result<Self> {
+        let home_dir = dirs::home_dir().ok_or_else(|| {
+            EmbeddingError::IoError(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                "Cannot determine home directory",
+            ))
+        })?;
+        Ok(Self::new(home_dir.join(".nodespace").join("models")))
+    }
+
+    /// Where `name` at `version` is (or would be) installed
+    pub fn model_path(&self, name: &str, version: &str) -> Result<PathBuf> {
+        Ok(self
+            .version_dir(name, version)?
+            .join(format!("{}.gguf", name)))
+    }
+
+    /// The directory holding one version, checked to be inside the models
+    /// directory
+    fn version_dir(&self, name: &str, version: &str) -> Result<PathBuf> {
+        check_component("name", name)?;
+        check_component("version", version)?;
+        let dir = self.models_dir.join(name).join(version);
+        if !dir.starts_with(&self.models_dir) {
+            return Err(EmbeddingError::InvalidInput(format!(
+                "Model {} {} is outside the models directory",
+                name, version
+            )));
+        }
+        Ok(dir)
+    }
+
+    /// Installed versions of every model, by name then version
+    pub async fn installed(&self) -> Result<Vec<InstalledModel>> {
+        let mut models = Vec::new();
+        let Ok(mut names) = fs::read_dir(&self.models_dir).await else {
+            return Ok(models);
+        };
+        while let Some(name_entry) = names.next_entry().await? {
+            if !name_entry.file_type().await?.is_dir() {
+                // Models installed by hand sit directly in the directory
+                continue;
+            }
+            let name = name_entry.file_name().to_string_lossy().to_string();
+            let mut versions = fs::read_dir(name_entry.path()).await?;
+            while let Some(version_entry) = versions.next_entry().await? {
+                let version = version_entry.file_name().to_string_lossy().to_string();
+                let Ok(path) = self.model_path(&name, &version) else {
+                    continue;
+                };
+                if let Ok(metadata) = fs::metadata(&path).await {
+                    models.push(InstalledModel {
+                        name: name.clone(),
+                        version,
+                        path,
+                        size_bytes: metadata.len(),
+                        installed_at: metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH),
+                    });
+                }
+            }
+        }
+        models.sort_by(|a, b| (&a.name, &a.version).cmp(&(&b.name, &b.version)));
+        Ok(models)
+    }
+
+    /// The most recently installed version of `name`
+    pub async fn latest(&self, name: &str) -> Result<Option<InstalledModel>> {
+        Ok(self
+            .installed()
+            .await?
+            .into_iter()
+            .filter(|model| model.name == name)
+            .max_by_key(|model| model.installed_at))
+    }
+
+    /// Path of the model, downloading it first unless it is installed
+    ///
+    /// An installed file is trusted: it was verified when it was installed.
+    pub async fn ensure(
+        &self,
+        spec: &ModelSpec,
+        on_progress: impl Fn(&DownloadProgress),
+    ) -> Result<PathBuf> {
+        let path = self.model_path(&spec.name, &spec.version)?;
+        if fs::try_exists(&path).await? {
+            return Ok(path);
+        }
+        self.download(spec, on_progress).await
+    }
+
+    /// Download, verify and install `spec`, resuming an earlier attempt
+    pub async fn download(
+        &self,
+        spec: &ModelSpec,
+        on_progress: impl Fn(&DownloadProgress),
+    ) -> Result<PathBuf> {
+        let path = self.model_path(&spec.name, &spec.version)?;
+        let _download = self.downloads.lock().await;
+        let partial = path.with_extension(format!("gguf.{}", PARTIAL_EXTENSION));
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).await?;
+        }
+
+        // Bytes from an earlier attempt count towards the checksum
+        let mut hasher = Sha256::new();
+        let mut downloaded = hash_file(&partial, &mut hasher).await?;
+        let resumed_from = downloaded;
+
+        let mut request = self.client.get(&spec.url);
+        if downloaded > 0 {
+            request = request.header(RANGE, format!("bytes={}-", downloaded));
+        }
+        let mut response = request.send().await.map_err(download_error)?;
+
+        let mut file = match response.status() {
+            StatusCode::PARTIAL_CONTENT => OpenOptions::new().append(true).open(&partial).await?,
+            // The partial file is already whole; only verification is left
+            StatusCode::RANGE_NOT_SATISFIABLE if downloaded > 0 => {
+                OpenOptions::new().append(true).open(&partial).await?
+            }
+            status if status.is_success() => {
+                // The server sent the whole file, so start over
+                hasher = Sha256::new();
+                downloaded = 0;
+                File::create(&partial).await?
+            }
+            status => {
+                return Err(EmbeddingError::DownloadError(format!(
+                    "{} answered {}",
+                    spec.url, status
+                )))
+            }
+        };
+        let total_bytes = match response.status() {
+            StatusCode::RANGE_NOT_SATISFIABLE => Some(downloaded),
+            _ => response
+                .headers()
+                .get(CONTENT_LENGTH)
+                .and_then(|length| length.to_str().ok()?.parse::<u64>().ok())
+                .map(|length| length + downloaded),
+        };
+
+        let progress = |stage, downloaded_bytes| DownloadProgress {
+            name: spec.name.clone(),
+            version: spec.version.clone(),
+            stage,
+            downloaded_bytes,
+            total_bytes,
+            resumed_from,
+        };
+        on_progress(&progress(DownloadStage::Downloading, downloaded));
+
+        if response.status() != StatusCode::RANGE_NOT_SATISFIABLE {
+            let mut last_report = Instant::now();
+            while let Some(chunk) = response.chunk().await.map_err(download_error)? {
+                file.write_all(&chunk).await?;
+                hasher.update(&chunk);
+                downloaded += chunk.len() as u64;
+                if last_report.elapsed() >= PROGRESS_INTERVAL {
+                    last_report = Instant::now();
+                    on_progress(&progress(DownloadStage::Downloading, downloaded));
+                }
+            }
+        }
+        file.sync_all().await?;
+        drop(file);
+
+        on_progress(&progress(DownloadStage::Verifying, downloaded));
+        let actual = format!("{:x}", hasher.finalize());
+        if !actual.eq_ignore_ascii_case(spec.sha256.trim()) {
+            fs::remove_file(&partial).await?;
+            return Err(EmbeddingError::ChecksumMismatch {
+                expected: spec.sha256.clone(),
+                actual,
+            });
+        }
+
+        fs::rename(&partial, &path).await?;
+        on_progress(&progress(DownloadStage::Installed, downloaded));
+        tracing::info!(
+            "Installed model {} {} at {:?}",
+            spec.name,
+            spec.version,
+            path
+        );
+        Ok(path)
+    }
+
+    /// Remove one installed version, and an unfinished download of it
+    pub async fn remove(&self, name: &str, version: &str) -> Result<()> {
+        let dir = self.version_dir(name, version)?;
+        if fs::try_exists(&dir).await? {
+            fs::remove_dir_all(&dir).await?;
+        }
+        Ok(())
+    }
+}
+
+/// Reject a name or version that isn't exactly one plain path component:
+/// empty, `.`, `..`, absolute, or containing a separator
+fn check_component(what: &str, value: &str) -> Result<()> {
+    let mut components = Path::new(value).components();
+    match (components.next(), components.next()) {
+        (Some(Component::Normal(component)), None) if component == value => Ok(()),
+        _ => Err(EmbeddingError::InvalidInput(format!(
+            "Invalid model {} '{}'",
+            what, value
+        ))),
+    }
+}
+
+/// Feed the file at `path` to `hasher`; its length, 0 if it doesn't exist
+async fn hash_file(path: &Path, hasher: &mut Sha256) -> Result<u64> {
+    let mut file = match File::open(path).await {
+        Ok(file) => file,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+        Err(e) => return Err(e.into()),
+    };
+    let mut buffer = vec![0; 1 << 20];
+    let mut length = 0;
+    loop {
+        let read = file.read(&mut buffer).await?;
+        if read == 0 {
+            return Ok(length);
+        }
+        hasher.update(&buffer[..read]);
+        length += read as u64;
+    }
+}
+
+fn download_error(e: reqwest::Error) -> EmbeddingError {
+    EmbeddingError::DownloadError(e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex as StdMutex};
+    use tokio::io::AsyncBufReadExt;
+    use tokio::net::TcpListener;
+
+    const MODEL: &[u8] = b"GGUF fake model weights, long enough to split in two";
+
+    fn sha256(bytes: &[u8]) -> String {
+        format!("{:x}", Sha256::digest(bytes))
+    }
+
+    /// Serves MODEL over HTTP, honouring `Range: bytes=N-`; returns its URL
+    /// and the ranges requested
+    async fn serve_model() -> (String, Arc<StdMutex<Vec<Option<u64>>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/model.gguf", listener.local_addr().unwrap());
+        let ranges = Arc::new(StdMutex::new(Vec::new()));
+        let seen = ranges.clone();
+        tokio::spawn(async move {
+            loop {
+                let (stream, _) = listener.accept().await.unwrap();
+                let (read, mut write) = stream.into_split();
+                let mut lines = tokio::io::BufReader::new(read).lines();
+                let mut start = None;
+                while let Some(line) = lines.next_line().await.unwrap() {
+                    if line.is_empty() {
+                        break;
+                    }
+                    if let Some(range) = line.to_ascii_lowercase().strip_prefix("range: bytes=") {
+                        start = range.trim_end_matches('-').parse::<u64>().ok();
+                    }
+                }
+                seen.lock().unwrap().push(start);
+                let body = &MODEL[start.unwrap_or(0) as usize..];
+                let status = match start {
+                    Some(_) => "206 Partial Content",
+                    None => "200 OK",
+                };
+                let head = format!(
+                    "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                    status,
+                    body.len()
+                );
+                write.write_all(head.as_bytes()).await.unwrap();
+                write.write_all(body).await.unwrap();
+            }
+        });
+        (url, ranges)
+    }
+
+    fn spec(url: &str, sha256: String) -> ModelSpec {
+        ModelSpec {
+            name: "test-embed".to_string(),
+            version: "Q8_0".to_string(),
+            url: url.to_string(),
+            sha256,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_download_verifies_and_installs_side_by_side() {
+        let (url, _) = serve_model().await;
+        let dir = tempfile::TempDir::new().unwrap();
+        let manager = ModelManager::new(dir.path());
+        let stages = StdMutex::new(Vec::new());
+
+        let path = manager
+            .download(&spec(&url, sha256(MODEL)), |p| {
+                stages.lock().unwrap().push(p.stage)
+            })
+            .await
+            .unwrap();
+        assert_eq!(path, manager.model_path("test-embed", "Q8_0").unwrap());
+        assert_eq!(std::fs::read(&path).unwrap(), MODEL);
+        let stages = stages.into_inner().unwrap();
+        assert_eq!(stages.first(), Some(&DownloadStage::Downloading));
+        assert_eq!(stages.last(), Some(&DownloadStage::Installed));
+
+        // A second version installs next to the first
+        let mut q4 = spec(&url, sha256(MODEL));
+        q4.version = "Q4_K_M".to_string();
+        manager.download(&q4, |_| {}).await.unwrap();
+        let installed = manager.installed().await.unwrap();
+        let versions: Vec<&str> = installed.iter().map(|m| m.version.as_str()).collect();
+        assert_eq!(versions, vec!["Q4_K_M", "Q8_0"]);
+
+        manager.remove("test-embed", "Q4_K_M").await.unwrap();
+        assert_eq!(manager.installed().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_download_resumes_from_partial_file() {
+        let (url, ranges) = serve_model().await;
+        let dir = tempfile::TempDir::new().unwrap();
+        let manager = ModelManager::new(dir.path());
+        let path = manager.model_path("test-embed", "Q8_0").unwrap();
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path.with_extension("gguf.part"), &MODEL[..20]).unwrap();
+
+        let resumed = StdMutex::new(None);
+        manager
+            .download(&spec(&url, sha256(MODEL)), |p| {
+                *resumed.lock().unwrap() = Some(p.resumed_from)
+            })
+            .await
+            .unwrap();
+        assert_eq!(*ranges.lock().unwrap(), vec![Some(20)]);
+        assert_eq!(resumed.into_inner().unwrap(), Some(20));
+        assert_eq!(std::fs::read(&path).unwrap(), MODEL);
+    }
+
+    #[tokio::test]
+    async fn test_checksum_mismatch_keeps_nothing() {
+        let (url, _) = serve_model().await;
+        let dir = tempfile::TempDir::new().unwrap();
+        let manager = ModelManager::new(dir.path());
+
+        let result = manager
+            .download(&spec(&url, sha256(b"other weights")), |_| {})
+            .await;
+        assert!(matches!(
+            result,
+            Err(EmbeddingError::ChecksumMismatch { .. })
+        ));
+        let path = manager.model_path("test-embed", "Q8_0").unwrap();
+        assert!(!path.exists());
+        assert!(!path.with_extension("gguf.part").exists());
+        assert!(manager.installed().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_traversal_names_are_rejected() {
+        let root = tempfile::TempDir::new().unwrap();
+        let models_dir = root.path().join("models");
+        let manager = ModelManager::new(&models_dir);
+        let keep = models_dir.join("keep").join("v1");
+        std::fs::create_dir_all(&keep).unwrap();
+        let outside = root.path().join("outside");
+        std::fs::create_dir_all(&outside).unwrap();
+        let outside_path = outside.to_string_lossy().to_string();
+
+        let bad = [
+            ("", "v1"),
+            (".", "v1"),
+            ("..", "v1"),
+            ("keep", ".."),
+            ("keep", "../.."),
+            ("../outside", "v1"),
+            ("keep", "v1/"),
+            ("keep/v1", "x"),
+            (outside_path.as_str(), "v1"),
+        ];
+        for (name, version) in bad {
+            assert!(
+                matches!(
+                    manager.remove(name, version).await,
+                    Err(EmbeddingError::InvalidInput(_))
+                ),
+                "remove({:?}, {:?}) was not rejected",
+                name,
+                version
+            );
+            let mut spec = spec("http://127.0.0.1:9/never", sha256(MODEL));
+            spec.name = name.to_string();
+            spec.version = version.to_string();
+            assert!(matches!(
+                manager.ensure(&spec, |_| {}).await,
+                Err(EmbeddingError::InvalidInput(_))
+            ));
+        }
+        assert!(keep.exists());
+        assert!(outside.exists());
+        assert!(std::fs::read_dir(&outside).unwrap().next().is_none());
+    }
+}
@@ -0,0 +1,354 @@
+/// Local summarization service using a small llama.cpp generation model
+///
+/// Turns node content into short plain-text summaries without leaving the
+/// machine. The model is optional: when no GGUF file is found (or the
+/// `summarization` feature is disabled) the service runs in extractive mode
+/// and returns the leading sentences of the text instead, so callers always
+/// get a summary and can check [`SummarizationService::has_model`] when only
+/// a generated one will do.
+///
+/// Generation is greedy (deterministic) and creates a fresh context per call;
+/// summaries are requested far less often than embeddings, so the context is
+/// not worth keeping around.
+use crate::config::SummarizationConfig;
+use crate::error::{EmbeddingError, Result};
+use std::sync::Mutex;
+
+#[cfg(feature = "summarization")]
+use crate::backend::BackendCapabilities;
+#[cfg(feature = "summarization")]
+use crate::embedding::get_or_init_backend;
+#[cfg(feature = "summarization")]
+use llama_cpp_2::context::params::LlamaContextParams;
+#[cfg(feature = "summarization")]
+use llama_cpp_2::llama_batch::LlamaBatch;
+#[cfg(feature = "summarization")]
+use llama_cpp_2::model::params::LlamaModelParams;
+#[cfg(feature = "summarization")]
+use llama_cpp_2::model::{AddBos, LlamaChatMessage, LlamaModel, Special};
+#[cfg(feature = "summarization")]
+use llama_cpp_2::sampling::LlamaSampler;
+
+/// Instruction given to the model ahead of the text
+#[cfg(feature = "summarization")]
+const SYSTEM_PROMPT: &str = "You summarize personal notes. Reply with a concise plain-text \
+summary in the language of the notes. No preamble, no markdown, no bullet points.";
+
+/// Tokens reserved for the chat template and instructions around the text
+#[cfg(feature = "summarization")]
+const PROMPT_OVERHEAD_TOKENS: usize = 96;
+
+/// Rough tokens-per-word ratio used to turn a token budget into words
+const WORDS_PER_TOKEN: f32 = 0.75;
+
+/// Loaded generation model
+///
+/// ## Safety
+/// The model is only used while the `Mutex` in [`SummarizationService`] is
+/// held, so llama.cpp never sees concurrent access.
+#[cfg(feature = "summarization")]
+struct SummaryModel(LlamaModel);
+
+#[cfg(feature = "summarization")]
+unsafe impl Send for SummaryModel {}
+#[cfg(feature = "summarization")]
+unsafe impl Sync for SummaryModel {}
+
+/// Summarization service using llama.cpp, with an extractive fallback
+pub struct SummarizationService {
+    config: SummarizationConfig,
+    #[cfg(feature = "summarization")]
+    model: Mutex<Option<SummaryModel>>,
+    #[cfg(not(feature = "summarization"))]
+    model: Mutex<Option<()>>,
+    initialized: bool,
+}
+
+impl SummarizationService {
+    /// Create a new summarization service with the given configuration
+    pub fn new(config: SummarizationConfig) -> Result<Self> {
+        config.validate().map_err(EmbeddingError::ConfigError)?;
+        Ok(Self {
+            config,
+            model: Mutex::new(None),
+            initialized: false,
+        })
+    }
+
+    /// Load the generation model
+    ///
+    /// If the model file is not found, the service operates in extractive
+    /// mode instead of failing.
+    pub fn initialize(&mut self) -> Result<()> {
+        if self.initialized {
+            return Ok(());
+        }
+
+        #[cfg(feature = "summarization")]
+        {
+            let model_path = match self.config.resolve_model_path() {
+                Ok(path) => path,
+                Err(e) => {
+                    tracing::info!(
+                        "Summarization model not found, using extractive summaries: {}",
+                        e
+                    );
+                    self.initialized = true;
+                    return Ok(());
+                }
+            };
+            tracing::info!("Loading summarization model from {:?}", model_path);
+
+            let backend = get_or_init_backend()?;
+            let choice = BackendCapabilities::with_gpu_offload(backend.supports_gpu_offload())
+                .resolve(self.config.backend, self.config.n_gpu_layers);
+            if let Some(reason) = &choice.fallback_reason {
+                tracing::info!("Summarization runs on the CPU: {}", reason);
+            }
+            let model_params = LlamaModelParams::default().with_n_gpu_layers(choice.gpu_layers);
+            let model = LlamaModel::load_from_file(&backend, &model_path, &model_params)
+                .map_err(|e| EmbeddingError::ModelLoadError(format!("Model load failed: {}", e)))?;
+            *self.model.lock().unwrap_or_else(|p| p.into_inner()) = Some(SummaryModel(model));
+            tracing::info!("Summarization model loaded");
+        }
+
+        #[cfg(not(feature = "summarization"))]
+        {
+            tracing::info!("Summarization feature disabled, using extractive summaries");
+        }
+
+        self.initialized = true;
+        Ok(())
+    }
+
+    /// Summarize `text` in at most about `max_tokens` tokens
+    ///
+    /// Blocks for as long as generation takes (seconds on CPU); call it from
+    /// a blocking thread in async code.
+    pub fn summarize(&self, text: &str, max_tokens: usize) -> Result<String> {
+        let text = text.trim();
+        if text.is_empty() {
+            return Err(EmbeddingError::InvalidInput(
+                "Cannot summarize empty text".to_string(),
+            ));
+        }
+        if max_tokens == 0 {
+            return Err(EmbeddingError::InvalidInput(
+                "max_tokens must be greater than 0".to_string(),
+            ));
+        }
+        if !self.initialized {
+            return Err(EmbeddingError::ModelNotInitialized);
+        }
+
+        #[cfg(feature = "summarization")]
+        {
+            let guard = self.model.lock().unwrap_or_else(|p| p.into_inner());
+            if let Some(SummaryModel(model)) = guard.as_ref() {
+                return self.generate(model, text, max_tokens);
+            }
+        }
+
+        Ok(extractive_summary(text, max_tokens))
+    }
+
+    /// Whether summaries come from the generation model rather than extraction
+    pub fn has_model(&self) -> bool {
+        self.model
+            .lock()
+            .unwrap_or_else(|p| p.into_inner())
+            .is_some()
+    }
+
+    /// Get the service configuration
+    pub fn config(&self) -> &SummarizationConfig {
+        &self.config
+    }
+
+    /// Check if service is initialized
+    pub fn is_initialized(&self) -> bool {
+        self.initialized
+    }
+
+    /// Release the model and its GPU resources
+    ///
+    /// Like [`EmbeddingService::release_gpu_context`](crate::EmbeddingService::release_gpu_context),
+    /// this must happen before the global llama backend is released on exit.
+    /// Afterwards the service falls back to extractive summaries.
+    pub fn release_gpu_context(&self) {
+        if self
+            .model
+            .lock()
+            .unwrap_or_else(|p| p.into_inner())
+            .take()
+            .is_some()
+        {
+            tracing::info!("Summarization model dropped, GPU resources freed");
+        }
+    }
+
+    /// Generate a summary with the loaded model
+    #[cfg(feature = "summarization")]
+    fn generate(&self, model: &LlamaModel, text: &str, max_tokens: usize) -> Result<String> {
+        let start = std::time::Instant::now();
+        let context_size = self.config.context_size as usize;
+        let max_tokens = max_tokens.min(context_size / 2);
+
+        // Keep the prompt within the context, shrinking the text if needed
+        let mut text = text.to_string();
+        let mut tokens = model
+            .str_to_token(&self.prompt(model, &text, max_tokens)?, AddBos::Always)
+            .map_err(|e| EmbeddingError::TokenizationError(e.to_string()))?;
+        let budget = context_size.saturating_sub(max_tokens + PROMPT_OVERHEAD_TOKENS);
+        if tokens.len() + max_tokens > context_size {
+            let keep = text.chars().count() * budget / tokens.len();
+            text = text.chars().take(keep).collect();
+            tokens = model
+                .str_to_token(&self.prompt(model, &text, max_tokens)?, AddBos::Always)
+                .map_err(|e| EmbeddingError::TokenizationError(e.to_string()))?;
+            if tokens.len() + max_tokens > context_size {
+                return Err(EmbeddingError::InvalidInput(format!(
+                    "Prompt of {} tokens does not fit a context of {}",
+                    tokens.len(),
+                    context_size
+                )));
+            }
+        }
+
+        let ctx_params = LlamaContextParams::default()
+            .with_n_ctx(std::num::NonZeroU32::new(self.config.context_size))
+            .with_n_batch(self.config.context_size)
+            .with_n_threads(self.config.n_threads)
+            .with_n_threads_batch(self.config.n_threads);
+        let mut ctx = {
+            let backend = get_or_init_backend()?;
+            model.new_context(&backend, ctx_params).map_err(|e| {
+                EmbeddingError::InferenceError(format!("Context creation failed: {}", e))
+            })?
+        };
+
+        let mut batch = LlamaBatch::new(context_size, 1);
+        let last = tokens.len() - 1;
+        for (pos, token) in tokens.iter().enumerate() {
+            batch
+                .add(*token, pos as i32, &[0], pos == last)
+                .map_err(|e| EmbeddingError::InferenceError(format!("Batch add failed: {}", e)))?;
+        }
+        ctx.decode(&mut batch)
+            .map_err(|e| EmbeddingError::InferenceError(format!("Decoding failed: {}", e)))?;
+
+        let mut sampler = LlamaSampler::greedy();
+        let mut position = tokens.len() as i32;
+        let mut output = Vec::new();
+        for _ in 0..max_tokens {
+            let token = sampler.sample(&ctx, batch.n_tokens() - 1);
+            sampler.accept(token);
+            if model.is_eog_token(token) {
+                break;
+            }
+            let bytes = model
+                .token_to_bytes(token, Special::Tokenize)
+                .map_err(|e| EmbeddingError::TokenizationError(e.to_string()))?;
+            output.extend(bytes);
+
+            batch.clear();
+            batch
+                .add(token, position, &[0], true)
+                .map_err(|e| EmbeddingError::InferenceError(format!("Batch add failed: {}", e)))?;
+            position += 1;
+            ctx.decode(&mut batch)
+                .map_err(|e| EmbeddingError::InferenceError(format!("Decoding failed: {}", e)))?;
+        }
+
+        let summary = String::from_utf8_lossy(&output).trim().to_string();
+        tracing::debug!(
+            "Generated {}-byte summary of {} prompt tokens in {:?}",
+            summary.len(),
+            tokens.len(),
+            start.elapsed()
+        );
+        Ok(summary)
+    }
+
+    /// Prompt for `text` in the model's chat format
+    ///
+    /// Models without a chat template get a plain instruction prompt.
+    #[cfg(feature = "summarization")]
+    fn prompt(&self, model: &LlamaModel, text: &str, max_tokens: usize) -> Result<String> {
+        let words = ((max_tokens as f32 * WORDS_PER_TOKEN) as usize).max(1);
+        let request = format!("Summarize in at most {} words:\n\n{}", words, text);
+
+        let Ok(template) = model.chat_template(None) else {
+            return Ok(format!("{}\n\n{}\n\nSummary:", SYSTEM_PROMPT, request));
+        };
+        let messages = [
+            LlamaChatMessage::new("system".to_string(), SYSTEM_PROMPT.to_string()),
+            LlamaChatMessage::new("user".to_string(), request),
+        ]
+        .into_iter()
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .map_err(|e| EmbeddingError::InvalidInput(e.to_string()))?;
+        model
+            .apply_chat_template(&template, &messages, true)
+            .map_err(|e| EmbeddingError::InferenceError(format!("Chat template failed: {}", e)))
+    }
+}
+
+/// Leading sentences of `text` fitting about `max_tokens` tokens
+///
+/// Whitespace is collapsed. A first sentence longer than the budget is cut
+/// at a word boundary and marked with an ellipsis.
+fn extractive_summary(text: &str, max_tokens: usize) -> String {
+    let max_words = ((max_tokens as f32 * WORDS_PER_TOKEN) as usize).max(1);
+    let words: Vec<&str> = text.split_whitespace().collect();
+    if words.len() <= max_words {
+        return words.join(" ");
+    }
+
+    let ends_sentence = |word: &str| word.ends_with(['.', '!', '?']);
+    match words[..max_words]
+        .iter()
+        .rposition(|word| ends_sentence(word))
+    {
+        Some(last) => words[..=last].join(" "),
+        None => format!("{}…", words[..max_words].join(" ")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extractive_summary_keeps_whole_sentences() {
+        let text = "First point.  Second point\nspans lines! Third point is long and will not fit.";
+        assert_eq!(
+            extractive_summary(text, 8),
+            "First point. Second point spans lines!"
+        );
+        assert_eq!(extractive_summary("Short note", 50), "Short note");
+        assert_eq!(
+            extractive_summary("one two three four five six", 4),
+            "one two three…"
+        );
+    }
+
+    #[test]
+    fn test_summarize_requires_initialization_and_text() {
+        let mut service = SummarizationService::new(SummarizationConfig {
+            model_path: Some("/nonexistent/model.gguf".into()),
+            model_name: "nonexistent-model".to_string(),
+            ..Default::default()
+        })
+        .unwrap();
+        assert!(matches!(
+            service.summarize("Some text", 10),
+            Err(EmbeddingError::ModelNotInitialized)
+        ));
+
+        service.initialize().unwrap();
+        assert!(!service.has_model());
+        assert!(service.summarize("   ", 10).is_err());
+        assert_eq!(service.summarize("Just this.", 10).unwrap(), "Just this.");
+    }
+}
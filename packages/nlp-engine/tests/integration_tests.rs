@@ -133,8 +133,12 @@ mod integration_tests {
         let embedding1 = service.generate_embedding(text).unwrap();
 
         // Check cache stats
-        let (cache_size, _capacity) = service.cache_stats();
-        assert_eq!(cache_size, 1, "Cache should have 1 entry");
+        let stats = service.cache_stats();
+        assert_eq!(stats.entries, 1, "Cache should have 1 entry");
+        assert!(
+            stats.bytes > 0,
+            "Cache should account for the entry's memory"
+        );
 
         // Generate same embedding again (should hit cache)
         let embedding2 = service.generate_embedding(text).unwrap();
@@ -150,8 +154,9 @@ mod integration_tests {
 
         // Clear cache
         service.clear_cache();
-        let (cache_size, _) = service.cache_stats();
-        assert_eq!(cache_size, 0, "Cache should be empty after clear");
+        let stats = service.cache_stats();
+        assert_eq!(stats.entries, 0, "Cache should be empty after clear");
+        assert_eq!(stats.hits, 1, "Second embedding should have hit the cache");
     }
 
     #[tokio::test]